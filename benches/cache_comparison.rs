@@ -149,6 +149,9 @@ fn create_typed_cache_entry(size: usize, content_type: ContentType) -> CacheEntr
         created_at: std::time::SystemTime::now(),
         expires_at: std::time::SystemTime::now() + Duration::from_secs(3600),
         last_accessed_at: std::time::SystemTime::now(),
+        content_encoding: None,
+        headers: Vec::new(),
+        headers: Vec::new(),
     }
 }
 
@@ -164,6 +167,9 @@ fn create_cache_entry(size: usize) -> CacheEntry {
         created_at: std::time::SystemTime::now(),
         expires_at: std::time::SystemTime::now() + Duration::from_secs(3600),
         last_accessed_at: std::time::SystemTime::now(),
+        content_encoding: None,
+        headers: Vec::new(),
+        headers: Vec::new(),
     }
 }
 
@@ -17,6 +17,9 @@ fn create_cache_entry(size: usize) -> CacheEntry {
         created_at: std::time::SystemTime::now(),
         expires_at: std::time::SystemTime::now() + std::time::Duration::from_secs(3600),
         last_accessed_at: std::time::SystemTime::now(),
+        content_encoding: None,
+        headers: Vec::new(),
+        headers: Vec::new(),
     }
 }
 
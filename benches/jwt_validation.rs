@@ -93,6 +93,10 @@ fn bench_jwt_extraction_bearer_header(c: &mut Criterion) {
         algorithm: "HS256".to_string(),
         rsa_public_key_path: None,
         ecdsa_public_key_path: None,
+        eddsa_public_key_path: None,
+        allowed_algorithms: vec![],
+        issuer: None,
+        audience: vec![],
         token_sources: vec![TokenSource {
             source_type: "bearer".to_string(),
             name: None,
@@ -103,6 +107,10 @@ fn bench_jwt_extraction_bearer_header(c: &mut Criterion) {
         keys: vec![],
         jwks_url: None,
         jwks_refresh_interval_secs: None,
+        key_reload_interval_secs: None,
+        token_cache: Default::default(),
+        revocation: None,
+        secret_file: None,
     };
 
     let query_params = HashMap::new();
@@ -144,6 +152,10 @@ fn bench_jwt_extraction_query_param(c: &mut Criterion) {
         algorithm: "HS256".to_string(),
         rsa_public_key_path: None,
         ecdsa_public_key_path: None,
+        eddsa_public_key_path: None,
+        allowed_algorithms: vec![],
+        issuer: None,
+        audience: vec![],
         token_sources: vec![TokenSource {
             source_type: "query_parameter".to_string(),
             name: Some("token".to_string()),
@@ -154,6 +166,10 @@ fn bench_jwt_extraction_query_param(c: &mut Criterion) {
         keys: vec![],
         jwks_url: None,
         jwks_refresh_interval_secs: None,
+        key_reload_interval_secs: None,
+        token_cache: Default::default(),
+        revocation: None,
+        secret_file: None,
     };
 
     c.bench_function("jwt_extraction_query_param", |b| {
@@ -192,6 +208,10 @@ fn bench_jwt_extraction_custom_header(c: &mut Criterion) {
         algorithm: "HS256".to_string(),
         rsa_public_key_path: None,
         ecdsa_public_key_path: None,
+        eddsa_public_key_path: None,
+        allowed_algorithms: vec![],
+        issuer: None,
+        audience: vec![],
         token_sources: vec![TokenSource {
             source_type: "header".to_string(),
             name: Some("x-auth-token".to_string()),
@@ -202,6 +222,10 @@ fn bench_jwt_extraction_custom_header(c: &mut Criterion) {
         keys: vec![],
         jwks_url: None,
         jwks_refresh_interval_secs: None,
+        key_reload_interval_secs: None,
+        token_cache: Default::default(),
+        revocation: None,
+        secret_file: None,
     };
 
     let query_params = HashMap::new();
@@ -252,6 +276,10 @@ fn bench_jwt_algorithms(c: &mut Criterion) {
             algorithm: algorithm.to_string(),
             rsa_public_key_path: None,
             ecdsa_public_key_path: None,
+            eddsa_public_key_path: None,
+            allowed_algorithms: vec![],
+            issuer: None,
+            audience: vec![],
             token_sources: vec![TokenSource {
                 source_type: "bearer".to_string(),
                 name: None,
@@ -262,6 +290,10 @@ fn bench_jwt_algorithms(c: &mut Criterion) {
             keys: vec![],
             jwks_url: None,
             jwks_refresh_interval_secs: None,
+            key_reload_interval_secs: None,
+            token_cache: Default::default(),
+            revocation: None,
+            secret_file: None,
         };
 
         let query_params = HashMap::new();
@@ -309,6 +341,10 @@ fn bench_jwt_with_claims_validation(c: &mut Criterion) {
         algorithm: "HS256".to_string(),
         rsa_public_key_path: None,
         ecdsa_public_key_path: None,
+        eddsa_public_key_path: None,
+        allowed_algorithms: vec![],
+        issuer: None,
+        audience: vec![],
         token_sources: vec![TokenSource {
             source_type: "bearer".to_string(),
             name: None,
@@ -323,6 +359,10 @@ fn bench_jwt_with_claims_validation(c: &mut Criterion) {
         keys: vec![],
         jwks_url: None,
         jwks_refresh_interval_secs: None,
+        key_reload_interval_secs: None,
+        token_cache: Default::default(),
+        revocation: None,
+        secret_file: None,
     };
 
     let query_params = HashMap::new();
@@ -367,6 +407,10 @@ fn bench_jwt_5_claims_validation(c: &mut Criterion) {
         algorithm: "HS256".to_string(),
         rsa_public_key_path: None,
         ecdsa_public_key_path: None,
+        eddsa_public_key_path: None,
+        allowed_algorithms: vec![],
+        issuer: None,
+        audience: vec![],
         token_sources: vec![TokenSource {
             source_type: "bearer".to_string(),
             name: None,
@@ -403,6 +447,10 @@ fn bench_jwt_5_claims_validation(c: &mut Criterion) {
         keys: vec![],
         jwks_url: None,
         jwks_refresh_interval_secs: None,
+        key_reload_interval_secs: None,
+        token_cache: Default::default(),
+        revocation: None,
+        secret_file: None,
     };
 
     let query_params = HashMap::new();
@@ -451,6 +499,10 @@ fn bench_jwt_10_claims_validation(c: &mut Criterion) {
         algorithm: "HS256".to_string(),
         rsa_public_key_path: None,
         ecdsa_public_key_path: None,
+        eddsa_public_key_path: None,
+        allowed_algorithms: vec![],
+        issuer: None,
+        audience: vec![],
         token_sources: vec![TokenSource {
             source_type: "bearer".to_string(),
             name: None,
@@ -512,6 +564,10 @@ fn bench_jwt_10_claims_validation(c: &mut Criterion) {
         keys: vec![],
         jwks_url: None,
         jwks_refresh_interval_secs: None,
+        key_reload_interval_secs: None,
+        token_cache: Default::default(),
+        revocation: None,
+        secret_file: None,
     };
 
     let query_params = HashMap::new();
@@ -553,6 +609,10 @@ fn bench_jwt_multiple_sources(c: &mut Criterion) {
         algorithm: "HS256".to_string(),
         rsa_public_key_path: None,
         ecdsa_public_key_path: None,
+        eddsa_public_key_path: None,
+        allowed_algorithms: vec![],
+        issuer: None,
+        audience: vec![],
         token_sources: vec![
             TokenSource {
                 source_type: "bearer".to_string(),
@@ -575,6 +635,10 @@ fn bench_jwt_multiple_sources(c: &mut Criterion) {
         keys: vec![],
         jwks_url: None,
         jwks_refresh_interval_secs: None,
+        key_reload_interval_secs: None,
+        token_cache: Default::default(),
+        revocation: None,
+        secret_file: None,
     };
 
     let query_params = HashMap::new();
@@ -630,6 +694,10 @@ fn bench_jwt_nested_claims(c: &mut Criterion) {
         algorithm: "HS256".to_string(),
         rsa_public_key_path: None,
         ecdsa_public_key_path: None,
+        eddsa_public_key_path: None,
+        allowed_algorithms: vec![],
+        issuer: None,
+        audience: vec![],
         token_sources: vec![TokenSource {
             source_type: "bearer".to_string(),
             name: None,
@@ -640,6 +708,10 @@ fn bench_jwt_nested_claims(c: &mut Criterion) {
         keys: vec![],
         jwks_url: None,
         jwks_refresh_interval_secs: None,
+        key_reload_interval_secs: None,
+        token_cache: Default::default(),
+        revocation: None,
+        secret_file: None,
     };
 
     let query_params = HashMap::new();
@@ -681,6 +753,10 @@ fn bench_jwt_expired_token(c: &mut Criterion) {
         algorithm: "HS256".to_string(),
         rsa_public_key_path: None,
         ecdsa_public_key_path: None,
+        eddsa_public_key_path: None,
+        allowed_algorithms: vec![],
+        issuer: None,
+        audience: vec![],
         token_sources: vec![TokenSource {
             source_type: "bearer".to_string(),
             name: None,
@@ -691,6 +767,10 @@ fn bench_jwt_expired_token(c: &mut Criterion) {
         keys: vec![],
         jwks_url: None,
         jwks_refresh_interval_secs: None,
+        key_reload_interval_secs: None,
+        token_cache: Default::default(),
+        revocation: None,
+        secret_file: None,
     };
 
     let query_params = HashMap::new();
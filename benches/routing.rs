@@ -1,5 +1,11 @@
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
-use yatagarasu::config::{BucketConfig, CoalescingConfig, Config, S3Config, ServerConfig};
+use yatagarasu::config::{
+    AccessExportConfig, AccessGrantsConfig, AdminSecurityConfig, BucketConfig, CacheOverrideConfig,
+    ClientDisconnectConfig, CoalescingConfig, Config, EndpointAccessConfig, GrpcConfig,
+    IpBanConfig, KeyAnalyticsConfig, MaintenanceConfig, MetricsCardinalityConfig,
+    OriginProbeConfig, PriorityConfig, RangeAbuseConfig, ReadinessConfig, RequestQueueConfig,
+    S3Config, ServerConfig, TlsListenerConfig, UnavailableResponsesConfig, WorkerConfig,
+};
 use yatagarasu::router::Router;
 
 /// Benchmark routing with single bucket
@@ -15,6 +21,26 @@ fn bench_routing_single_bucket(c: &mut Criterion) {
             rate_limit: None,
             security_limits: Default::default(),
             coalescing: CoalescingConfig::default(),
+            workers: WorkerConfig::default(),
+            client_disconnect: ClientDisconnectConfig::default(),
+            ip_ban: IpBanConfig::default(),
+            range_abuse: RangeAbuseConfig::default(),
+            access_grants: AccessGrantsConfig::default(),
+            priority: PriorityConfig::default(),
+            maintenance: MaintenanceConfig::default(),
+            key_analytics: KeyAnalyticsConfig::default(),
+            access_export: AccessExportConfig::default(),
+            endpoint_access: EndpointAccessConfig::default(),
+            readiness: ReadinessConfig::default(),
+            origin_probe: OriginProbeConfig::default(),
+            unavailable_responses: UnavailableResponsesConfig::default(),
+            metrics_cardinality: MetricsCardinalityConfig::default(),
+            synthetic_endpoints: Vec::new(),
+            cache_override: CacheOverrideConfig::default(),
+            admin: AdminSecurityConfig::default(),
+            grpc: GrpcConfig::default(),
+            tls: TlsListenerConfig::default(),
+            request_queue: RequestQueueConfig::default(),
         },
         buckets: vec![BucketConfig {
             name: "test-bucket".to_string(),
@@ -30,6 +56,8 @@ fn bench_routing_single_bucket(c: &mut Criterion) {
                 rate_limit: None,
                 circuit_breaker: None,
                 retry: None,
+                bulkhead: None,
+                signing: None,
                 replicas: None,
             },
             auth: None,
@@ -37,6 +65,29 @@ fn bench_routing_single_bucket(c: &mut Criterion) {
             authorization: None,
             ip_filter: Default::default(),
             watermark: None,
+            logging: None,
+            resume: None,
+            content_type: None,
+            hotlink: None,
+            allowed_methods: None,
+            cors: None,
+            degraded: None,
+            server_timing: None,
+            early_hints: None,
+            etag_rewrite: None,
+            listing: None,
+            origin: None,
+            aliases: Vec::new(),
+            version: None,
+            hostnames: Vec::new(),
+            routes: Vec::new(),
+            upload_scan: None,
+            deprecation: None,
+            signed_url: None,
+            sigv4_auth: None,
+            post_policy: None,
+            presigned_redirect: None,
+            size_classes: Vec::new(),
         }],
         jwt: None,
         cache: None,
@@ -44,6 +95,8 @@ fn bench_routing_single_bucket(c: &mut Criterion) {
         observability: Default::default(),
         image_optimization: Default::default(),
         generation: 0,
+        batch: Default::default(),
+        vault: None,
     };
 
     let router = Router::new(config.buckets);
@@ -71,6 +124,8 @@ fn bench_routing_multiple_buckets(c: &mut Criterion) {
                 rate_limit: None,
                 circuit_breaker: None,
                 retry: None,
+                bulkhead: None,
+                signing: None,
                 replicas: None,
             },
             auth: None,
@@ -78,6 +133,29 @@ fn bench_routing_multiple_buckets(c: &mut Criterion) {
             authorization: None,
             ip_filter: Default::default(),
             watermark: None,
+            logging: None,
+            resume: None,
+            content_type: None,
+            hotlink: None,
+            allowed_methods: None,
+            cors: None,
+            degraded: None,
+            server_timing: None,
+            early_hints: None,
+            etag_rewrite: None,
+            listing: None,
+            origin: None,
+            aliases: Vec::new(),
+            version: None,
+            hostnames: Vec::new(),
+            routes: Vec::new(),
+            upload_scan: None,
+            deprecation: None,
+            signed_url: None,
+            sigv4_auth: None,
+            post_policy: None,
+            presigned_redirect: None,
+            size_classes: Vec::new(),
         })
         .collect();
 
@@ -91,6 +169,26 @@ fn bench_routing_multiple_buckets(c: &mut Criterion) {
             rate_limit: None,
             security_limits: Default::default(),
             coalescing: CoalescingConfig::default(),
+            workers: WorkerConfig::default(),
+            client_disconnect: ClientDisconnectConfig::default(),
+            ip_ban: IpBanConfig::default(),
+            range_abuse: RangeAbuseConfig::default(),
+            access_grants: AccessGrantsConfig::default(),
+            priority: PriorityConfig::default(),
+            maintenance: MaintenanceConfig::default(),
+            key_analytics: KeyAnalyticsConfig::default(),
+            access_export: AccessExportConfig::default(),
+            endpoint_access: EndpointAccessConfig::default(),
+            readiness: ReadinessConfig::default(),
+            origin_probe: OriginProbeConfig::default(),
+            unavailable_responses: UnavailableResponsesConfig::default(),
+            metrics_cardinality: MetricsCardinalityConfig::default(),
+            synthetic_endpoints: Vec::new(),
+            cache_override: CacheOverrideConfig::default(),
+            admin: AdminSecurityConfig::default(),
+            grpc: GrpcConfig::default(),
+            tls: TlsListenerConfig::default(),
+            request_queue: RequestQueueConfig::default(),
         },
         buckets,
         jwt: None,
@@ -99,6 +197,8 @@ fn bench_routing_multiple_buckets(c: &mut Criterion) {
         observability: Default::default(),
         image_optimization: Default::default(),
         generation: 0,
+        batch: Default::default(),
+        vault: None,
     };
 
     let router = Router::new(config.buckets);
@@ -140,6 +240,26 @@ fn bench_routing_path_lengths(c: &mut Criterion) {
             rate_limit: None,
             security_limits: Default::default(),
             coalescing: CoalescingConfig::default(),
+            workers: WorkerConfig::default(),
+            client_disconnect: ClientDisconnectConfig::default(),
+            ip_ban: IpBanConfig::default(),
+            range_abuse: RangeAbuseConfig::default(),
+            access_grants: AccessGrantsConfig::default(),
+            priority: PriorityConfig::default(),
+            maintenance: MaintenanceConfig::default(),
+            key_analytics: KeyAnalyticsConfig::default(),
+            access_export: AccessExportConfig::default(),
+            endpoint_access: EndpointAccessConfig::default(),
+            readiness: ReadinessConfig::default(),
+            origin_probe: OriginProbeConfig::default(),
+            unavailable_responses: UnavailableResponsesConfig::default(),
+            metrics_cardinality: MetricsCardinalityConfig::default(),
+            synthetic_endpoints: Vec::new(),
+            cache_override: CacheOverrideConfig::default(),
+            admin: AdminSecurityConfig::default(),
+            grpc: GrpcConfig::default(),
+            tls: TlsListenerConfig::default(),
+            request_queue: RequestQueueConfig::default(),
         },
         buckets: vec![BucketConfig {
             name: "test-bucket".to_string(),
@@ -155,6 +275,8 @@ fn bench_routing_path_lengths(c: &mut Criterion) {
                 rate_limit: None,
                 circuit_breaker: None,
                 retry: None,
+                bulkhead: None,
+                signing: None,
                 replicas: None,
             },
             auth: None,
@@ -162,6 +284,29 @@ fn bench_routing_path_lengths(c: &mut Criterion) {
             authorization: None,
             ip_filter: Default::default(),
             watermark: None,
+            logging: None,
+            resume: None,
+            content_type: None,
+            hotlink: None,
+            allowed_methods: None,
+            cors: None,
+            degraded: None,
+            server_timing: None,
+            early_hints: None,
+            etag_rewrite: None,
+            listing: None,
+            origin: None,
+            aliases: Vec::new(),
+            version: None,
+            hostnames: Vec::new(),
+            routes: Vec::new(),
+            upload_scan: None,
+            deprecation: None,
+            signed_url: None,
+            sigv4_auth: None,
+            post_policy: None,
+            presigned_redirect: None,
+            size_classes: Vec::new(),
         }],
         jwt: None,
         cache: None,
@@ -169,6 +314,8 @@ fn bench_routing_path_lengths(c: &mut Criterion) {
         observability: Default::default(),
         image_optimization: Default::default(),
         generation: 0,
+        batch: Default::default(),
+        vault: None,
     };
 
     let router = Router::new(config.buckets);
@@ -209,6 +356,26 @@ fn bench_s3_key_extraction(c: &mut Criterion) {
             rate_limit: None,
             security_limits: Default::default(),
             coalescing: CoalescingConfig::default(),
+            workers: WorkerConfig::default(),
+            client_disconnect: ClientDisconnectConfig::default(),
+            ip_ban: IpBanConfig::default(),
+            range_abuse: RangeAbuseConfig::default(),
+            access_grants: AccessGrantsConfig::default(),
+            priority: PriorityConfig::default(),
+            maintenance: MaintenanceConfig::default(),
+            key_analytics: KeyAnalyticsConfig::default(),
+            access_export: AccessExportConfig::default(),
+            endpoint_access: EndpointAccessConfig::default(),
+            readiness: ReadinessConfig::default(),
+            origin_probe: OriginProbeConfig::default(),
+            unavailable_responses: UnavailableResponsesConfig::default(),
+            metrics_cardinality: MetricsCardinalityConfig::default(),
+            synthetic_endpoints: Vec::new(),
+            cache_override: CacheOverrideConfig::default(),
+            admin: AdminSecurityConfig::default(),
+            grpc: GrpcConfig::default(),
+            tls: TlsListenerConfig::default(),
+            request_queue: RequestQueueConfig::default(),
         },
         buckets: vec![BucketConfig {
             name: "test-bucket".to_string(),
@@ -224,6 +391,8 @@ fn bench_s3_key_extraction(c: &mut Criterion) {
                 rate_limit: None,
                 circuit_breaker: None,
                 retry: None,
+                bulkhead: None,
+                signing: None,
                 replicas: None,
             },
             auth: None,
@@ -231,6 +400,29 @@ fn bench_s3_key_extraction(c: &mut Criterion) {
             authorization: None,
             ip_filter: Default::default(),
             watermark: None,
+            logging: None,
+            resume: None,
+            content_type: None,
+            hotlink: None,
+            allowed_methods: None,
+            cors: None,
+            degraded: None,
+            server_timing: None,
+            early_hints: None,
+            etag_rewrite: None,
+            listing: None,
+            origin: None,
+            aliases: Vec::new(),
+            version: None,
+            hostnames: Vec::new(),
+            routes: Vec::new(),
+            upload_scan: None,
+            deprecation: None,
+            signed_url: None,
+            sigv4_auth: None,
+            post_policy: None,
+            presigned_redirect: None,
+            size_classes: Vec::new(),
         }],
         jwt: None,
         cache: None,
@@ -238,6 +430,8 @@ fn bench_s3_key_extraction(c: &mut Criterion) {
         observability: Default::default(),
         image_optimization: Default::default(),
         generation: 0,
+        batch: Default::default(),
+        vault: None,
     };
 
     let router = Router::new(config.buckets);
@@ -279,6 +473,26 @@ fn bench_routing_longest_prefix(c: &mut Criterion) {
             rate_limit: None,
             security_limits: Default::default(),
             coalescing: CoalescingConfig::default(),
+            workers: WorkerConfig::default(),
+            client_disconnect: ClientDisconnectConfig::default(),
+            ip_ban: IpBanConfig::default(),
+            range_abuse: RangeAbuseConfig::default(),
+            access_grants: AccessGrantsConfig::default(),
+            priority: PriorityConfig::default(),
+            maintenance: MaintenanceConfig::default(),
+            key_analytics: KeyAnalyticsConfig::default(),
+            access_export: AccessExportConfig::default(),
+            endpoint_access: EndpointAccessConfig::default(),
+            readiness: ReadinessConfig::default(),
+            origin_probe: OriginProbeConfig::default(),
+            unavailable_responses: UnavailableResponsesConfig::default(),
+            metrics_cardinality: MetricsCardinalityConfig::default(),
+            synthetic_endpoints: Vec::new(),
+            cache_override: CacheOverrideConfig::default(),
+            admin: AdminSecurityConfig::default(),
+            grpc: GrpcConfig::default(),
+            tls: TlsListenerConfig::default(),
+            request_queue: RequestQueueConfig::default(),
         },
         buckets: vec![
             BucketConfig {
@@ -295,6 +509,8 @@ fn bench_routing_longest_prefix(c: &mut Criterion) {
                     rate_limit: None,
                     circuit_breaker: None,
                     retry: None,
+                    bulkhead: None,
+                    signing: None,
                     replicas: None,
                 },
                 auth: None,
@@ -302,6 +518,29 @@ fn bench_routing_longest_prefix(c: &mut Criterion) {
                 authorization: None,
                 ip_filter: Default::default(),
                 watermark: None,
+                logging: None,
+                resume: None,
+                content_type: None,
+                hotlink: None,
+                allowed_methods: None,
+                cors: None,
+                degraded: None,
+                server_timing: None,
+                early_hints: None,
+                etag_rewrite: None,
+                listing: None,
+                origin: None,
+                aliases: Vec::new(),
+                version: None,
+                hostnames: Vec::new(),
+                routes: Vec::new(),
+                upload_scan: None,
+                deprecation: None,
+                signed_url: None,
+                sigv4_auth: None,
+                post_policy: None,
+                presigned_redirect: None,
+                size_classes: Vec::new(),
             },
             BucketConfig {
                 name: "bucket-medium".to_string(),
@@ -317,6 +556,8 @@ fn bench_routing_longest_prefix(c: &mut Criterion) {
                     rate_limit: None,
                     circuit_breaker: None,
                     retry: None,
+                    bulkhead: None,
+                    signing: None,
                     replicas: None,
                 },
                 auth: None,
@@ -324,6 +565,29 @@ fn bench_routing_longest_prefix(c: &mut Criterion) {
                 authorization: None,
                 ip_filter: Default::default(),
                 watermark: None,
+                logging: None,
+                resume: None,
+                content_type: None,
+                hotlink: None,
+                allowed_methods: None,
+                cors: None,
+                degraded: None,
+                server_timing: None,
+                early_hints: None,
+                etag_rewrite: None,
+                listing: None,
+                origin: None,
+                aliases: Vec::new(),
+                version: None,
+                hostnames: Vec::new(),
+                routes: Vec::new(),
+                upload_scan: None,
+                deprecation: None,
+                signed_url: None,
+                sigv4_auth: None,
+                post_policy: None,
+                presigned_redirect: None,
+                size_classes: Vec::new(),
             },
             BucketConfig {
                 name: "bucket-long".to_string(),
@@ -339,6 +603,8 @@ fn bench_routing_longest_prefix(c: &mut Criterion) {
                     rate_limit: None,
                     circuit_breaker: None,
                     retry: None,
+                    bulkhead: None,
+                    signing: None,
                     replicas: None,
                 },
                 auth: None,
@@ -346,6 +612,29 @@ fn bench_routing_longest_prefix(c: &mut Criterion) {
                 authorization: None,
                 ip_filter: Default::default(),
                 watermark: None,
+                logging: None,
+                resume: None,
+                content_type: None,
+                hotlink: None,
+                allowed_methods: None,
+                cors: None,
+                degraded: None,
+                server_timing: None,
+                early_hints: None,
+                etag_rewrite: None,
+                listing: None,
+                origin: None,
+                aliases: Vec::new(),
+                version: None,
+                hostnames: Vec::new(),
+                routes: Vec::new(),
+                upload_scan: None,
+                deprecation: None,
+                signed_url: None,
+                sigv4_auth: None,
+                post_policy: None,
+                presigned_redirect: None,
+                size_classes: Vec::new(),
             },
         ],
         jwt: None,
@@ -354,6 +643,8 @@ fn bench_routing_longest_prefix(c: &mut Criterion) {
         observability: Default::default(),
         image_optimization: Default::default(),
         generation: 0,
+        batch: Default::default(),
+        vault: None,
     };
 
     let router = Router::new(config.buckets);
@@ -400,6 +691,8 @@ fn bench_routing_many_buckets(c: &mut Criterion) {
                     rate_limit: None,
                     circuit_breaker: None,
                     retry: None,
+                    bulkhead: None,
+                    signing: None,
                     replicas: None,
                 },
                 auth: None,
@@ -407,6 +700,29 @@ fn bench_routing_many_buckets(c: &mut Criterion) {
                 authorization: None,
                 ip_filter: Default::default(),
                 watermark: None,
+                logging: None,
+                resume: None,
+                content_type: None,
+                hotlink: None,
+                allowed_methods: None,
+                cors: None,
+                degraded: None,
+                server_timing: None,
+                early_hints: None,
+                etag_rewrite: None,
+                listing: None,
+                origin: None,
+                aliases: Vec::new(),
+                version: None,
+                hostnames: Vec::new(),
+                routes: Vec::new(),
+                upload_scan: None,
+                deprecation: None,
+                signed_url: None,
+                sigv4_auth: None,
+                post_policy: None,
+                presigned_redirect: None,
+                size_classes: Vec::new(),
             })
             .collect();
 
@@ -420,6 +736,26 @@ fn bench_routing_many_buckets(c: &mut Criterion) {
                 rate_limit: None,
                 security_limits: Default::default(),
                 coalescing: CoalescingConfig::default(),
+                workers: WorkerConfig::default(),
+                client_disconnect: ClientDisconnectConfig::default(),
+                ip_ban: IpBanConfig::default(),
+                range_abuse: RangeAbuseConfig::default(),
+                access_grants: AccessGrantsConfig::default(),
+                priority: PriorityConfig::default(),
+                maintenance: MaintenanceConfig::default(),
+                key_analytics: KeyAnalyticsConfig::default(),
+                access_export: AccessExportConfig::default(),
+                endpoint_access: EndpointAccessConfig::default(),
+                readiness: ReadinessConfig::default(),
+                origin_probe: OriginProbeConfig::default(),
+                unavailable_responses: UnavailableResponsesConfig::default(),
+                metrics_cardinality: MetricsCardinalityConfig::default(),
+                synthetic_endpoints: Vec::new(),
+                cache_override: CacheOverrideConfig::default(),
+                admin: AdminSecurityConfig::default(),
+                grpc: GrpcConfig::default(),
+                tls: TlsListenerConfig::default(),
+                request_queue: RequestQueueConfig::default(),
             },
             buckets,
             jwt: None,
@@ -428,6 +764,8 @@ fn bench_routing_many_buckets(c: &mut Criterion) {
             observability: Default::default(),
             image_optimization: Default::default(),
             generation: 0,
+            batch: Default::default(),
+            vault: None,
         };
 
         let router = Router::new(config.buckets);
@@ -466,6 +804,26 @@ fn bench_path_normalization(c: &mut Criterion) {
             rate_limit: None,
             security_limits: Default::default(),
             coalescing: CoalescingConfig::default(),
+            workers: WorkerConfig::default(),
+            client_disconnect: ClientDisconnectConfig::default(),
+            ip_ban: IpBanConfig::default(),
+            range_abuse: RangeAbuseConfig::default(),
+            access_grants: AccessGrantsConfig::default(),
+            priority: PriorityConfig::default(),
+            maintenance: MaintenanceConfig::default(),
+            key_analytics: KeyAnalyticsConfig::default(),
+            access_export: AccessExportConfig::default(),
+            endpoint_access: EndpointAccessConfig::default(),
+            readiness: ReadinessConfig::default(),
+            origin_probe: OriginProbeConfig::default(),
+            unavailable_responses: UnavailableResponsesConfig::default(),
+            metrics_cardinality: MetricsCardinalityConfig::default(),
+            synthetic_endpoints: Vec::new(),
+            cache_override: CacheOverrideConfig::default(),
+            admin: AdminSecurityConfig::default(),
+            grpc: GrpcConfig::default(),
+            tls: TlsListenerConfig::default(),
+            request_queue: RequestQueueConfig::default(),
         },
         buckets: vec![BucketConfig {
             name: "test-bucket".to_string(),
@@ -481,6 +839,8 @@ fn bench_path_normalization(c: &mut Criterion) {
                 rate_limit: None,
                 circuit_breaker: None,
                 retry: None,
+                bulkhead: None,
+                signing: None,
                 replicas: None,
             },
             auth: None,
@@ -488,6 +848,29 @@ fn bench_path_normalization(c: &mut Criterion) {
             authorization: None,
             ip_filter: Default::default(),
             watermark: None,
+            logging: None,
+            resume: None,
+            content_type: None,
+            hotlink: None,
+            allowed_methods: None,
+            cors: None,
+            degraded: None,
+            server_timing: None,
+            early_hints: None,
+            etag_rewrite: None,
+            listing: None,
+            origin: None,
+            aliases: Vec::new(),
+            version: None,
+            hostnames: Vec::new(),
+            routes: Vec::new(),
+            upload_scan: None,
+            deprecation: None,
+            signed_url: None,
+            sigv4_auth: None,
+            post_policy: None,
+            presigned_redirect: None,
+            size_classes: Vec::new(),
         }],
         jwt: None,
         cache: None,
@@ -495,6 +878,8 @@ fn bench_path_normalization(c: &mut Criterion) {
         observability: Default::default(),
         image_optimization: Default::default(),
         generation: 0,
+        batch: Default::default(),
+        vault: None,
     };
 
     let router = Router::new(config.buckets);
@@ -547,6 +932,8 @@ fn bench_bucket_lookup_by_name(c: &mut Criterion) {
                     rate_limit: None,
                     circuit_breaker: None,
                     retry: None,
+                    bulkhead: None,
+                    signing: None,
                     replicas: None,
                 },
                 auth: None,
@@ -554,6 +941,29 @@ fn bench_bucket_lookup_by_name(c: &mut Criterion) {
                 authorization: None,
                 ip_filter: Default::default(),
                 watermark: None,
+                logging: None,
+                resume: None,
+                content_type: None,
+                hotlink: None,
+                allowed_methods: None,
+                cors: None,
+                degraded: None,
+                server_timing: None,
+                early_hints: None,
+                etag_rewrite: None,
+                listing: None,
+                origin: None,
+                aliases: Vec::new(),
+                version: None,
+                hostnames: Vec::new(),
+                routes: Vec::new(),
+                upload_scan: None,
+                deprecation: None,
+                signed_url: None,
+                sigv4_auth: None,
+                post_policy: None,
+                presigned_redirect: None,
+                size_classes: Vec::new(),
             })
             .collect();
 
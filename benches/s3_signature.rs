@@ -3,7 +3,8 @@ use std::collections::HashMap;
 
 use yatagarasu::s3::{
     build_get_object_request, build_head_object_request, create_canonical_request,
-    create_string_to_sign, derive_signing_key, sha256_hex, sign_request, SigningParams,
+    create_string_to_sign, derive_signing_key, sha256_hex, sign_request, SigningKeyCache,
+    SigningParams,
 };
 
 /// Benchmark complete S3 signature generation (GET request)
@@ -508,6 +509,45 @@ fn bench_hmac_sha256(c: &mut Criterion) {
     group.finish();
 }
 
+/// Benchmark cached vs freshly derived signing keys
+///
+/// High-RPS workloads sign every upstream request with the same date-scoped
+/// key; the cache turns four HMAC-SHA256 operations into a map lookup.
+fn bench_signing_key_cache(c: &mut Criterion) {
+    let mut group = c.benchmark_group("signing_key_cache");
+
+    let secret_key = "wJalrXUtnFEMI/K7MDENG/bPxRfiCYEXAMPLEKEY";
+    let date = "20231115";
+    let region = "us-east-1";
+    let service = "s3";
+
+    group.bench_function("derived_every_request", |b| {
+        b.iter(|| {
+            derive_signing_key(
+                black_box(secret_key),
+                black_box(date),
+                black_box(region),
+                black_box(service),
+            )
+        })
+    });
+
+    let cache = SigningKeyCache::new();
+    cache.get_or_derive(secret_key, date, region, service);
+    group.bench_function("cached_hit", |b| {
+        b.iter(|| {
+            cache.get_or_derive(
+                black_box(secret_key),
+                black_box(date),
+                black_box(region),
+                black_box(service),
+            )
+        })
+    });
+
+    group.finish();
+}
+
 criterion_group!(
     benches,
     bench_s3_signature_get_request,
@@ -521,5 +561,6 @@ criterion_group!(
     bench_date_formatting,
     bench_header_canonicalization,
     bench_hmac_sha256,
+    bench_signing_key_cache,
 );
 criterion_main!(benches);
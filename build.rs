@@ -0,0 +1,13 @@
+// Generates the gRPC control plane service from proto/ when the `grpc`
+// feature is enabled. Requires `protoc` on PATH (or PROTOC set).
+
+fn main() {
+    #[cfg(feature = "grpc")]
+    {
+        println!("cargo:rerun-if-changed=proto/yatagarasu/control/v1/control.proto");
+        tonic_build::configure()
+            .build_client(false)
+            .compile_protos(&["proto/yatagarasu/control/v1/control.proto"], &["proto"])
+            .expect("failed to compile proto/yatagarasu/control/v1/control.proto");
+    }
+}
@@ -0,0 +1,95 @@
+use super::send_json_response;
+use crate::analytics::{parse_window, KeyPopularity, DEFAULT_WINDOW_SECS};
+use crate::config::Config;
+use pingora_proxy::Session;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Keys returned when no `limit` is given
+const DEFAULT_LIMIT: usize = 20;
+
+/// Handle requests to /admin/analytics/*
+pub async fn handle_request(
+    session: &mut Session,
+    path: &str,
+    method: &str,
+    query_params: &HashMap<String, String>,
+    config: &Config,
+    key_popularity: Option<&Arc<KeyPopularity>>,
+) -> bool {
+    let Some(key_popularity) = key_popularity else {
+        return send_json_response(
+            session,
+            404,
+            serde_json::json!({"error": "Key analytics is not enabled"}),
+        )
+        .await;
+    };
+
+    // GET /admin/analytics/top-keys?bucket=X&window=1h&limit=20
+    if path == "/admin/analytics/top-keys" && method == "GET" {
+        let Some(bucket) = query_params.get("bucket") else {
+            return send_json_response(
+                session,
+                400,
+                serde_json::json!({"error": "Missing required query parameter 'bucket'"}),
+            )
+            .await;
+        };
+        if !config.buckets.iter().any(|b| &b.name == bucket) {
+            return send_json_response(
+                session,
+                404,
+                serde_json::json!({"error": format!("Unknown bucket '{}'", bucket)}),
+            )
+            .await;
+        }
+
+        let window_secs = match query_params.get("window").map(|w| parse_window(w)) {
+            None => DEFAULT_WINDOW_SECS,
+            Some(Ok(secs)) if secs <= key_popularity.max_window_secs() => secs,
+            Some(Ok(_)) => {
+                return send_json_response(
+                    session,
+                    400,
+                    serde_json::json!({"error": format!(
+                        "Window exceeds the tracked maximum of {} seconds",
+                        key_popularity.max_window_secs()
+                    )}),
+                )
+                .await
+            }
+            Some(Err(e)) => {
+                return send_json_response(session, 400, serde_json::json!({"error": e})).await
+            }
+        };
+        let limit = match query_params.get("limit").map(|l| l.parse::<usize>()) {
+            None => DEFAULT_LIMIT,
+            Some(Ok(limit)) if limit > 0 => limit.min(key_popularity.max_limit()),
+            _ => {
+                return send_json_response(
+                    session,
+                    400,
+                    serde_json::json!({"error": "limit must be a positive integer"}),
+                )
+                .await
+            }
+        };
+
+        let report = key_popularity.top_keys(bucket, window_secs, limit);
+        return send_json_response(
+            session,
+            200,
+            serde_json::to_value(report).unwrap_or_default(),
+        )
+        .await;
+    }
+
+    // Unhandled path
+    send_json_response(
+        session,
+        404,
+        serde_json::json!({"error": "Endpoint not found"}),
+    )
+    .await
+}
@@ -0,0 +1,69 @@
+use super::send_json_response;
+use crate::analytics::parse_window;
+use crate::auth::failures::AuthFailureTracker;
+use pingora_proxy::Session;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Offenders returned when no `limit` is given
+const DEFAULT_LIMIT: usize = 20;
+
+/// Largest accepted `limit`
+const MAX_LIMIT: usize = 500;
+
+/// Handle requests to /admin/auth/failures
+pub async fn handle_request(
+    session: &mut Session,
+    method: &str,
+    query_params: &HashMap<String, String>,
+    tracker: &Arc<AuthFailureTracker>,
+) -> bool {
+    if method != "GET" {
+        return send_json_response(
+            session,
+            405,
+            serde_json::json!({"error": "Method not allowed"}),
+        )
+        .await;
+    }
+
+    // GET /admin/auth/failures?window=5m&limit=20
+    let window_secs = match query_params.get("window").map(|w| parse_window(w)) {
+        None => tracker.window_secs(),
+        Some(Ok(secs)) if secs <= tracker.window_secs() => secs,
+        Some(Ok(_)) => {
+            return send_json_response(
+                session,
+                400,
+                serde_json::json!({"error": format!(
+                    "Window exceeds the tracked maximum of {} seconds",
+                    tracker.window_secs()
+                )}),
+            )
+            .await
+        }
+        Some(Err(e)) => {
+            return send_json_response(session, 400, serde_json::json!({"error": e})).await
+        }
+    };
+    let limit = match query_params.get("limit").map(|l| l.parse::<usize>()) {
+        None => DEFAULT_LIMIT,
+        Some(Ok(limit)) if limit > 0 => limit.min(MAX_LIMIT),
+        _ => {
+            return send_json_response(
+                session,
+                400,
+                serde_json::json!({"error": "limit must be a positive integer"}),
+            )
+            .await
+        }
+    };
+
+    let report = tracker.top(window_secs, limit);
+    send_json_response(
+        session,
+        200,
+        serde_json::to_value(report).unwrap_or_default(),
+    )
+    .await
+}
@@ -0,0 +1,108 @@
+use super::send_json_response;
+use crate::security::IpBanManager;
+use pingora_proxy::Session;
+use std::net::IpAddr;
+use std::sync::Arc;
+
+/// Handle requests to /admin/bans/*
+pub async fn handle_request(
+    session: &mut Session,
+    path: &str,
+    method: &str,
+    manager: Option<&Arc<IpBanManager>>,
+) -> bool {
+    let Some(manager) = manager else {
+        return send_json_response(
+            session,
+            404,
+            serde_json::json!({"error": "IP banning is not enabled"}),
+        )
+        .await;
+    };
+
+    // GET /admin/bans - List active bans
+    if path == "/admin/bans" && method == "GET" {
+        let bans = manager.list();
+        return send_json_response(
+            session,
+            200,
+            serde_json::json!({"count": bans.len(), "bans": bans}),
+        )
+        .await;
+    }
+
+    // DELETE /admin/bans/{ip} - Lift a ban (locally and in shared state)
+    if path.starts_with("/admin/bans/") && method == "DELETE" {
+        let raw_ip = path.strip_prefix("/admin/bans/").unwrap();
+        let ip: IpAddr = match parse_ip(raw_ip) {
+            Some(ip) => ip,
+            None => {
+                return send_json_response(
+                    session,
+                    400,
+                    serde_json::json!({"error": format!("Invalid IP address '{}'", raw_ip)}),
+                )
+                .await
+            }
+        };
+
+        if manager.unban(ip).await {
+            tracing::info!(client_ip = %ip, "IP ban lifted via admin API");
+            return send_json_response(
+                session,
+                200,
+                serde_json::json!({"status": "unbanned", "ip": ip.to_string()}),
+            )
+            .await;
+        } else {
+            return send_json_response(
+                session,
+                404,
+                serde_json::json!({"error": format!("IP '{}' is not banned", ip)}),
+            )
+            .await;
+        }
+    }
+
+    // Unhandled path
+    send_json_response(
+        session,
+        404,
+        serde_json::json!({"error": "Endpoint not found"}),
+    )
+    .await
+}
+
+/// Parse an IP from the path segment (IPv6 may arrive percent-encoded or bracketed)
+pub(super) fn parse_ip(raw: &str) -> Option<IpAddr> {
+    let decoded = raw.replace("%3A", ":").replace("%3a", ":");
+    decoded
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .parse()
+        .ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ip() {
+        assert_eq!(
+            parse_ip("203.0.113.7"),
+            Some("203.0.113.7".parse().unwrap())
+        );
+        assert_eq!(
+            parse_ip("2001:db8::1"),
+            Some("2001:db8::1".parse().unwrap())
+        );
+        assert_eq!(
+            parse_ip("2001%3Adb8%3A%3A1"),
+            Some("2001:db8::1".parse().unwrap())
+        );
+        assert_eq!(parse_ip("[::1]"), Some("::1".parse().unwrap()));
+        assert_eq!(parse_ip("not-an-ip"), None);
+        assert_eq!(parse_ip(""), None);
+    }
+}
@@ -0,0 +1,139 @@
+use crate::constants::EVENT_STREAM_KEEPALIVE_SECS;
+use crate::error::ERROR_CODE_HEADER;
+use crate::events::{EventBus, RuntimeEvent};
+use bytes::Bytes;
+use pingora_http::ResponseHeader;
+use pingora_proxy::Session;
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::sync::broadcast::error::RecvError;
+
+/// Handle requests to /admin/events
+///
+/// Streams runtime events as Server-Sent Events until the client goes away.
+/// `?types=failover,config_reload` limits the stream to those event types.
+pub async fn handle_request(
+    session: &mut Session,
+    method: &str,
+    query_params: &HashMap<String, String>,
+) -> bool {
+    if method != "GET" {
+        let mut body = serde_json::json!({"error": "Method not allowed"});
+        let code = super::tag_error_body(405, &mut body);
+        let body_str = body.to_string();
+        if let Ok(mut header) = ResponseHeader::build(405, None) {
+            let _ = header.insert_header("Content-Type", "application/json");
+            let _ = header.insert_header("Allow", "GET");
+            if let Some(code) = code {
+                let _ = header.insert_header(ERROR_CODE_HEADER, code.as_str());
+            }
+            let _ = header.insert_header("Content-Length", body_str.len().to_string());
+            let _ = session.write_response_header(Box::new(header), false).await;
+            let _ = session
+                .write_response_body(Some(body_str.into()), true)
+                .await;
+        }
+        return true;
+    }
+
+    let types: Option<Vec<String>> = query_params.get("types").map(|types| {
+        types
+            .split(',')
+            .map(|t| t.trim().to_string())
+            .filter(|t| !t.is_empty())
+            .collect()
+    });
+
+    // Subscribe before sending headers so nothing published in between is lost
+    let mut rx = EventBus::global().subscribe();
+
+    let Ok(mut header) = ResponseHeader::build(200, None) else {
+        return true;
+    };
+    let _ = header.insert_header("Content-Type", "text/event-stream");
+    let _ = header.insert_header("Cache-Control", "no-cache");
+    let _ = header.insert_header("X-Accel-Buffering", "no");
+    if session
+        .write_response_header(Box::new(header), false)
+        .await
+        .is_err()
+    {
+        return true;
+    }
+    if write_frame(session, ": connected\n\n".to_string())
+        .await
+        .is_err()
+    {
+        return true;
+    }
+
+    tracing::info!("Admin event stream subscriber connected");
+    let mut keepalive = tokio::time::interval(Duration::from_secs(EVENT_STREAM_KEEPALIVE_SECS));
+    keepalive.tick().await;
+
+    loop {
+        let frame = tokio::select! {
+            received = rx.recv() => match received {
+                Ok(event) => {
+                    let wanted = types
+                        .as_ref()
+                        .map_or(true, |types| types.iter().any(|t| t == event.kind.name()));
+                    if !wanted {
+                        continue;
+                    }
+                    format_event(&event)
+                }
+                Err(RecvError::Lagged(missed)) => {
+                    format!("event: lagged\ndata: {{\"missed\":{}}}\n\n", missed)
+                }
+                Err(RecvError::Closed) => break,
+            },
+            _ = keepalive.tick() => ": keepalive\n\n".to_string(),
+        };
+        if write_frame(session, frame).await.is_err() {
+            break;
+        }
+    }
+
+    tracing::info!("Admin event stream subscriber disconnected");
+    true
+}
+
+/// Format an event as an SSE frame
+fn format_event(event: &RuntimeEvent) -> String {
+    let data = serde_json::to_string(event).unwrap_or_else(|_| "{}".to_string());
+    format!(
+        "id: {}\nevent: {}\ndata: {}\n\n",
+        event.id,
+        event.kind.name(),
+        data
+    )
+}
+
+async fn write_frame(session: &mut Session, frame: String) -> pingora_core::Result<()> {
+    session
+        .write_response_body(Some(Bytes::from(frame)), false)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventKind;
+
+    #[test]
+    fn test_format_event_as_sse_frame() {
+        let event = RuntimeEvent {
+            id: 7,
+            timestamp: "2025-01-01T00:00:00+00:00".to_string(),
+            kind: EventKind::CachePurge {
+                bucket: Some("images".to_string()),
+                key: None,
+            },
+        };
+        assert_eq!(
+            format_event(&event),
+            "id: 7\nevent: cache_purge\ndata: {\"id\":7,\"timestamp\":\"2025-01-01T00:00:00+00:00\",\"type\":\"cache_purge\",\"bucket\":\"images\"}\n\n"
+        );
+    }
+}
@@ -0,0 +1,419 @@
+//! `POST /admin/explain`: dry-run a hypothetical request.
+//!
+//! Operators describe a request (method, path, headers, query and optionally
+//! the JWT claims it would carry) and get back how the proxy would treat it:
+//! the matched bucket and key, whether authentication and authorization
+//! would pass, the cache key, and which replica would serve it. Nothing is
+//! fetched and no state is changed.
+
+use super::send_json_response;
+use crate::auth::{self, path_rules, Claims};
+use crate::cache::{policy as cache_policy, ttl as cache_ttl, CacheControl, CacheKey};
+use crate::config::{BucketConfig, Config};
+use crate::image_optimizer::ImageParams;
+use crate::replica_set::ReplicaSet;
+use crate::router::Router;
+use pingora_proxy::Session;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Largest accepted request body
+const MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// A hypothetical request to explain
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExplainRequest {
+    #[serde(default = "default_method")]
+    pub method: String,
+    pub path: String,
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    #[serde(default)]
+    pub query: HashMap<String, String>,
+    /// Claims to assume instead of verifying a token from `headers`
+    #[serde(default)]
+    pub claims: Option<serde_json::Map<String, serde_json::Value>>,
+}
+
+fn default_method() -> String {
+    "GET".to_string()
+}
+
+/// Handle requests to /admin/explain
+pub async fn handle_request(
+    session: &mut Session,
+    method: &str,
+    config: &Config,
+    router: &Router,
+    replica_sets: &HashMap<String, ReplicaSet>,
+) -> bool {
+    if method != "POST" {
+        return send_json_response(
+            session,
+            405,
+            serde_json::json!({"error": "Method not allowed"}),
+        )
+        .await;
+    }
+
+    let mut body = Vec::new();
+    loop {
+        match session.read_request_body().await {
+            Ok(Some(chunk)) => {
+                if body.len() + chunk.len() > MAX_BODY_BYTES {
+                    return send_json_response(
+                        session,
+                        413,
+                        serde_json::json!({"error": "Request body too large"}),
+                    )
+                    .await;
+                }
+                body.extend_from_slice(&chunk);
+            }
+            Ok(None) => break,
+            Err(e) => {
+                return send_json_response(
+                    session,
+                    500,
+                    serde_json::json!({"error": e.to_string()}),
+                )
+                .await
+            }
+        }
+    }
+
+    let request: ExplainRequest = match serde_json::from_slice(&body) {
+        Ok(request) => request,
+        Err(e) => {
+            return send_json_response(
+                session,
+                400,
+                serde_json::json!({"error": "Invalid JSON", "details": e.to_string()}),
+            )
+            .await
+        }
+    };
+
+    let explanation = explain(&request, config, router, replica_sets).await;
+    send_json_response(session, 200, explanation).await
+}
+
+/// Explain how the proxy would handle `request`
+pub async fn explain(
+    request: &ExplainRequest,
+    config: &Config,
+    router: &Router,
+    replica_sets: &HashMap<String, ReplicaSet>,
+) -> serde_json::Value {
+    let method = request.method.to_ascii_uppercase();
+    let host = request
+        .headers
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case("host"))
+        .map(|(_, value)| value.as_str());
+    let bucket_path = host
+        .and_then(|host| router.virtual_host_path(host, &request.path))
+        .or_else(|| router.pattern_path(&request.path))
+        .or_else(|| router.alias_path(&request.path))
+        .unwrap_or_else(|| request.path.clone());
+
+    let Some(bucket) = router.route(&bucket_path) else {
+        return serde_json::json!({
+            "request": {"method": method, "path": request.path},
+            "route": null,
+            "would_pass": false,
+            "reason": "No bucket matches the path",
+        });
+    };
+    let s3_key = router.extract_s3_key(&bucket_path).unwrap_or_default();
+    let method_allowed = bucket.allows_method(&method);
+
+    let (authentication, claims) = explain_authentication(request, config, bucket).await;
+    let authorization = explain_authorization(bucket, claims.as_ref(), &method, &s3_key);
+    let passes = |section: &serde_json::Value| {
+        !matches!(section["result"].as_str(), Some("fail") | Some("deny"))
+    };
+    let would_pass = method_allowed && passes(&authentication) && passes(&authorization);
+
+    serde_json::json!({
+        "request": {"method": method, "path": request.path},
+        "route": {
+            "bucket": bucket.name,
+            "path_prefix": bucket.path_prefix,
+            "rewritten_path": (bucket_path != request.path).then_some(&bucket_path),
+            "s3_key": s3_key,
+        },
+        "method_allowed": method_allowed,
+        "signed_url_required": bucket.signed_url.as_ref().is_some_and(|s| s.enabled),
+        "authentication": authentication,
+        "authorization": authorization,
+        "cache": explain_cache(request, config, bucket, &method, &s3_key),
+        "upstream": explain_upstream(bucket, replica_sets),
+        "would_pass": would_pass,
+    })
+}
+
+async fn explain_authentication(
+    request: &ExplainRequest,
+    config: &Config,
+    bucket: &BucketConfig,
+) -> (serde_json::Value, Option<Claims>) {
+    if bucket.sigv4_auth.as_ref().is_some_and(|s| s.enabled) {
+        return (
+            serde_json::json!({
+                "type": "sigv4",
+                "result": "not_evaluated",
+                "reason": "SigV4 signatures cover the exact request bytes",
+            }),
+            None,
+        );
+    }
+    let jwt_config = match (&bucket.auth, &config.jwt) {
+        (Some(auth_config), Some(jwt_config)) if auth_config.enabled => {
+            auth_config.jwt_config(jwt_config).into_owned()
+        }
+        _ => return (serde_json::json!({"type": "none", "result": "pass"}), None),
+    };
+
+    // Assumed claims skip the signature but still face issuer, audience and claim rules
+    let result = match &request.claims {
+        Some(custom) => {
+            let mut custom = custom.clone();
+            let claims = Claims {
+                sub: take_string(&mut custom, "sub"),
+                exp: custom.remove("exp").and_then(|v| v.as_u64()),
+                iat: custom.remove("iat").and_then(|v| v.as_u64()),
+                nbf: custom.remove("nbf").and_then(|v| v.as_u64()),
+                iss: take_string(&mut custom, "iss"),
+                custom,
+            };
+            if !auth::verify_issuer_and_audience(&claims, &jwt_config)
+                || !auth::verify_claims(&claims, &jwt_config.claims)
+            {
+                Err(auth::AuthError::ClaimsVerificationFailed)
+            } else {
+                Ok(claims)
+            }
+        }
+        None => auth::authenticate(&request.headers, &request.query, &jwt_config).await,
+    };
+
+    let source = if request.claims.is_some() {
+        "assumed_claims"
+    } else {
+        "token"
+    };
+    match result {
+        Ok(claims) => (
+            serde_json::json!({
+                "type": "jwt",
+                "source": source,
+                "result": "pass",
+                "subject": claims.sub,
+            }),
+            Some(claims),
+        ),
+        Err(e) => (
+            serde_json::json!({
+                "type": "jwt",
+                "source": source,
+                "result": "fail",
+                "reason": e.to_string(),
+            }),
+            None,
+        ),
+    }
+}
+
+fn take_string(map: &mut serde_json::Map<String, serde_json::Value>, key: &str) -> Option<String> {
+    map.remove(key)
+        .and_then(|v| v.as_str().map(|s| s.to_string()))
+}
+
+fn explain_authorization(
+    bucket: &BucketConfig,
+    claims: Option<&Claims>,
+    method: &str,
+    s3_key: &str,
+) -> serde_json::Value {
+    let Some(authz) = &bucket.authorization else {
+        return serde_json::json!({"type": "none", "result": "allow"});
+    };
+    if authz.auth_type != "claims" {
+        return serde_json::json!({
+            "type": authz.auth_type,
+            "result": "not_evaluated",
+            "reason": "External policy engines are not queried by a dry run",
+        });
+    }
+
+    let jwt_claims = claims
+        .map(|c| serde_json::to_value(c).unwrap_or_default())
+        .unwrap_or(serde_json::json!({}));
+    let matched = authz
+        .rules
+        .iter()
+        .find(|rule| path_rules::rule_allows(rule, &jwt_claims, method, s3_key));
+    match matched {
+        Some(rule) => serde_json::json!({
+            "type": "claims",
+            "result": "allow",
+            "matched_rule": rule.path,
+        }),
+        None => serde_json::json!({
+            "type": "claims",
+            "result": "deny",
+            "reason": "No claim path rule matches the key",
+        }),
+    }
+}
+
+fn explain_cache(
+    request: &ExplainRequest,
+    config: &Config,
+    bucket: &BucketConfig,
+    method: &str,
+    s3_key: &str,
+) -> serde_json::Value {
+    let effective = config.cache.as_ref().map(|global| match &bucket.cache {
+        Some(bucket_cache) => bucket_cache.merge_with_global(global),
+        None => global.clone(),
+    });
+    if !effective.is_some_and(|c| c.enabled) {
+        return serde_json::json!({"enabled": false});
+    }
+
+    let variant = (config.image_optimization.enabled && (method == "GET" || method == "HEAD"))
+        .then(|| ImageParams::from_query(&request.query))
+        .flatten()
+        .and_then(|params| params.ok())
+        .map(|params| params.to_cache_key());
+    let key = CacheKey {
+        bucket: bucket.name.clone(),
+        object_key: s3_key.to_string(),
+        etag: None,
+        variant,
+    };
+    serde_json::json!({
+        "enabled": true,
+        "cacheable_method": method == "GET",
+        // Bucket include/exclude path patterns; Content-Type is only known upstream
+        "cacheable_path": cache_policy::allows_key(bucket, s3_key),
+        "key": key.to_string(),
+        // TTL when the upstream response carries no max-age or Expires
+        "ttl_seconds": cache_ttl::entry_ttl(bucket, &CacheControl::default(), None, None).as_secs(),
+    })
+}
+
+fn explain_upstream(
+    bucket: &BucketConfig,
+    replica_sets: &HashMap<String, ReplicaSet>,
+) -> serde_json::Value {
+    let Some(replica_set) = replica_sets.get(&bucket.name) else {
+        return serde_json::json!({
+            "bucket": bucket.s3.bucket,
+            "endpoint": bucket.s3.endpoint,
+            "replica": null,
+        });
+    };
+
+    // Same order as upstream selection: the first replica whose breaker is not open
+    let replicas: Vec<serde_json::Value> = replica_set
+        .replicas
+        .iter()
+        .map(|replica| {
+            serde_json::json!({
+                "name": replica.name,
+                "priority": replica.priority,
+                "circuit": replica.circuit_breaker.state().as_str(),
+            })
+        })
+        .collect();
+    let selected = replica_set
+        .replicas
+        .iter()
+        .find(|replica| {
+            replica.circuit_breaker.state() != crate::circuit_breaker::CircuitState::Open
+        })
+        .map(|replica| replica.name.clone());
+    serde_json::json!({
+        "replica": selected,
+        "replicas": replicas,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_explain_claim_rules_and_cache_key() {
+        let config = Config::from_yaml_with_env(
+            r#"
+server:
+  address: "127.0.0.1"
+  port: 8080
+jwt:
+  enabled: true
+  secret: "secret"
+  algorithm: "HS256"
+  token_sources:
+    - type: bearer
+buckets:
+  - name: "tenants"
+    path_prefix: "/tenants"
+    s3:
+      bucket: "tenants"
+      region: "us-east-1"
+      access_key: "key"
+      secret_key: "secret"
+    auth:
+      enabled: true
+    authorization:
+      type: claims
+      rules:
+        - path: "/{tenant_id}/**"
+cache:
+  enabled: true
+"#,
+        )
+        .unwrap();
+        let router = Router::new(config.buckets.clone());
+        let request = |path: &str| ExplainRequest {
+            method: "get".to_string(),
+            path: path.to_string(),
+            headers: HashMap::new(),
+            query: HashMap::new(),
+            claims: serde_json::json!({"sub": "alice", "tenant_id": "acme"})
+                .as_object()
+                .cloned(),
+        };
+
+        let allowed = explain(
+            &request("/tenants/acme/report.csv"),
+            &config,
+            &router,
+            &HashMap::new(),
+        )
+        .await;
+        assert_eq!(allowed["route"]["bucket"], "tenants");
+        assert_eq!(allowed["route"]["s3_key"], "acme/report.csv");
+        assert_eq!(allowed["authentication"]["result"], "pass");
+        assert_eq!(allowed["authorization"]["matched_rule"], "/{tenant_id}/**");
+        assert_eq!(allowed["cache"]["key"], "tenants:acme/report.csv");
+        assert_eq!(allowed["would_pass"], true);
+
+        let denied = explain(
+            &request("/tenants/globex/report.csv"),
+            &config,
+            &router,
+            &HashMap::new(),
+        )
+        .await;
+        assert_eq!(denied["authorization"]["result"], "deny");
+        assert_eq!(denied["would_pass"], false);
+
+        let unrouted = explain(&request("/nowhere"), &config, &router, &HashMap::new()).await;
+        assert!(unrouted["route"].is_null());
+    }
+}
@@ -0,0 +1,198 @@
+//! `/admin/grants`: create, list and revoke temporary access grants.
+//!
+//! - `GET /admin/grants` lists active grants
+//! - `POST /admin/grants` creates a grant for a subject or client IP
+//! - `DELETE /admin/grants/{id}` revokes a grant before it expires
+
+use super::send_json_response;
+use crate::config::Config;
+use crate::security::{AccessGrantStore, GrantRequest};
+use pingora_proxy::Session;
+use std::sync::Arc;
+
+/// Largest accepted request body
+const MAX_BODY_BYTES: usize = 16 * 1024;
+
+/// Handle requests to /admin/grants and /admin/grants/{id}
+pub async fn handle_request(
+    session: &mut Session,
+    path: &str,
+    method: &str,
+    config: &Config,
+    store: Option<&Arc<AccessGrantStore>>,
+) -> bool {
+    let Some(store) = store else {
+        return send_json_response(
+            session,
+            404,
+            serde_json::json!({"error": "Access grants are not enabled"}),
+        )
+        .await;
+    };
+
+    // GET /admin/grants - List active grants
+    if path == "/admin/grants" && method == "GET" {
+        let grants = store.list();
+        return send_json_response(
+            session,
+            200,
+            serde_json::json!({"count": grants.len(), "grants": grants}),
+        )
+        .await;
+    }
+
+    // POST /admin/grants - Create a grant
+    if path == "/admin/grants" && method == "POST" {
+        let mut body = Vec::new();
+        loop {
+            match session.read_request_body().await {
+                Ok(Some(chunk)) => {
+                    if body.len() + chunk.len() > MAX_BODY_BYTES {
+                        return send_json_response(
+                            session,
+                            413,
+                            serde_json::json!({"error": "Request body too large"}),
+                        )
+                        .await;
+                    }
+                    body.extend_from_slice(&chunk);
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    return send_json_response(
+                        session,
+                        500,
+                        serde_json::json!({"error": e.to_string()}),
+                    )
+                    .await
+                }
+            }
+        }
+
+        let request: GrantRequest = match serde_json::from_slice(&body) {
+            Ok(request) => request,
+            Err(e) => {
+                return send_json_response(
+                    session,
+                    400,
+                    serde_json::json!({"error": "Invalid JSON", "details": e.to_string()}),
+                )
+                .await
+            }
+        };
+
+        let (status, body) = create(request, config, store).await;
+        return send_json_response(session, status, body).await;
+    }
+
+    // DELETE /admin/grants/{id} - Revoke a grant (locally and in shared state)
+    if let Some(id) = path.strip_prefix("/admin/grants/") {
+        if method == "DELETE" {
+            if store.revoke(id).await {
+                tracing::info!(grant_id = %id, "Access grant revoked via admin API");
+                return send_json_response(
+                    session,
+                    200,
+                    serde_json::json!({"status": "revoked", "id": id}),
+                )
+                .await;
+            }
+            return send_json_response(
+                session,
+                404,
+                serde_json::json!({"error": format!("Grant '{}' not found", id)}),
+            )
+            .await;
+        }
+    }
+
+    // Unhandled path
+    send_json_response(
+        session,
+        404,
+        serde_json::json!({"error": "Endpoint not found"}),
+    )
+    .await
+}
+
+/// Create a grant for `request` in a bucket that exists in `config`
+pub async fn create(
+    request: GrantRequest,
+    config: &Config,
+    store: &AccessGrantStore,
+) -> (u16, serde_json::Value) {
+    if !config.buckets.iter().any(|b| b.name == request.bucket) {
+        return (
+            404,
+            serde_json::json!({"error": format!("Bucket '{}' not found", request.bucket)}),
+        );
+    }
+
+    match store.create(request).await {
+        Ok(grant) => {
+            tracing::info!(
+                grant_id = %grant.id,
+                bucket = %grant.bucket,
+                prefix = %grant.prefix,
+                subject = ?grant.subject,
+                ip = ?grant.ip,
+                expires_at = grant.expires_at,
+                reason = ?grant.reason,
+                "Access grant created via admin API"
+            );
+            (201, serde_json::json!(grant))
+        }
+        Err(e) => (400, serde_json::json!({"error": e})),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::AccessGrantsConfig;
+
+    #[tokio::test]
+    async fn test_create_grant() {
+        let config = Config::from_yaml_with_env(
+            r#"
+server:
+  address: "127.0.0.1"
+  port: 8080
+buckets:
+  - name: media
+    path_prefix: /media
+    s3:
+      bucket: media-bucket
+      region: us-east-1
+      access_key: AKIA
+      secret_key: secret
+"#,
+        )
+        .unwrap();
+        let store = AccessGrantStore::new(AccessGrantsConfig {
+            enabled: true,
+            ..Default::default()
+        })
+        .unwrap();
+        let request = |bucket: &str, subject: Option<&str>| GrantRequest {
+            bucket: bucket.to_string(),
+            subject: subject.map(str::to_string),
+            prefix: "tickets/4711/".to_string(),
+            ..Default::default()
+        };
+
+        let (status, body) = create(request("media", Some("alice")), &config, &store).await;
+        assert_eq!(status, 201);
+        assert_eq!(body["subject"], "alice");
+        assert_eq!(body["prefix"], "tickets/4711/");
+        assert!(body["id"].is_string());
+        assert_eq!(store.list().len(), 1);
+
+        // Neither subject nor IP
+        let (status, _) = create(request("media", None), &config, &store).await;
+        assert_eq!(status, 400);
+        let (status, _) = create(request("missing", Some("alice")), &config, &store).await;
+        assert_eq!(status, 404);
+        assert_eq!(store.list().len(), 1);
+    }
+}
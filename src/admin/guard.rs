@@ -0,0 +1,376 @@
+//! Admin API hardening: strict methods, same-origin/CSRF checks and token IP binding
+//!
+//! Runs before any admin handler, including the legacy reload/purge/stats
+//! handlers in the proxy. A request for a known admin endpoint is rejected when:
+//! - the method is not one the endpoint implements (405 with `Allow`)
+//! - it changes state and comes from a foreign origin, or carries cookies
+//!   without echoing the CSRF cookie in the CSRF header (403)
+//! - `server.admin.bind_token_ip` is set and the token's IP claim does not
+//!   cover the peer address (403)
+
+use crate::auth::{authenticate, extract_cookie_token, Claims};
+use crate::config::{AdminCsrfConfig, Config};
+use crate::error::{ErrorCode, ERROR_CODE_HEADER};
+use crate::security::{constant_time_eq, IpRange};
+use pingora_http::ResponseHeader;
+use pingora_proxy::Session;
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+const GET: &[&str] = &["GET"];
+const POST: &[&str] = &["POST"];
+const DELETE: &[&str] = &["DELETE"];
+const GET_POST: &[&str] = &["GET", "POST"];
+
+/// Methods implemented by a known admin endpoint, `None` for other paths
+pub fn allowed_methods(path: &str) -> Option<&'static [&'static str]> {
+    let methods = match path {
+        "/admin/reload"
+        | "/admin/cache/purge"
+        | "/admin/cache/prewarm"
+        | "/admin/cache/prewarm/replay"
+        | "/admin/maintenance/on"
+        | "/admin/maintenance/off"
+        | "/admin/explain"
+        | "/admin/post-policy" => POST,
+        "/admin/cache/stats"
+        | "/admin/cache/info"
+        | "/admin/cache/prewarm/tasks"
+        | "/admin/bans"
+        | "/admin/maintenance"
+        | "/admin/analytics/top-keys"
+        | "/admin/rate-limits/state"
+        | "/admin/events"
+        | "/admin/auth/failures"
+        | "/admin/csrf-token" => GET,
+        "/admin/grants" => GET_POST,
+        _ if path.starts_with("/admin/cache/purge/") => POST,
+        _ if path.starts_with("/admin/cache/stats/") => GET,
+        _ if path.starts_with("/admin/cache/prewarm/status/") => GET,
+        _ if path.starts_with("/admin/cache/prewarm/") => DELETE,
+        _ if path.starts_with("/admin/bans/") => DELETE,
+        _ if path.starts_with("/admin/grants/") => DELETE,
+        _ => return None,
+    };
+    Some(methods)
+}
+
+/// Check if the method can change state
+fn is_state_changing(method: &str) -> bool {
+    !matches!(method, "GET" | "HEAD" | "OPTIONS")
+}
+
+/// Why an admin request was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdminRejection {
+    pub status: u16,
+    pub code: ErrorCode,
+    pub message: String,
+    /// `Allow` header value for 405 responses
+    pub allow: Option<String>,
+}
+
+impl AdminRejection {
+    fn forbidden(message: impl Into<String>) -> Self {
+        Self {
+            status: 403,
+            code: ErrorCode::AuthForbidden,
+            message: message.into(),
+            allow: None,
+        }
+    }
+}
+
+/// Run the method, CSRF and token binding checks for an admin request
+///
+/// Returns `None` when the request may proceed to its handler. Token
+/// validity itself is still checked by the handler.
+pub async fn check(
+    path: &str,
+    method: &str,
+    headers: &HashMap<String, String>,
+    query_params: &HashMap<String, String>,
+    config: &Config,
+    peer_ip: Option<IpAddr>,
+) -> Option<AdminRejection> {
+    let allowed = allowed_methods(path)?;
+    if !allowed.contains(&method) {
+        let allow = allowed.join(", ");
+        return Some(AdminRejection {
+            status: 405,
+            code: ErrorCode::MethodNotAllowed,
+            message: format!(
+                "Method {} is not allowed on {}. Allowed: {}",
+                method, path, allow
+            ),
+            allow: Some(allow),
+        });
+    }
+
+    let admin = &config.server.admin;
+    if admin.csrf.enabled && is_state_changing(method) {
+        if let Err(reason) = check_origin(&admin.csrf, headers) {
+            return Some(AdminRejection::forbidden(reason));
+        }
+        if let Err(reason) = check_csrf_token(&admin.csrf, headers) {
+            return Some(AdminRejection::forbidden(reason));
+        }
+    }
+
+    if admin.bind_token_ip {
+        if let Some(jwt_config) = config.jwt.as_ref().filter(|j| j.enabled) {
+            // Invalid tokens are left to the handler, which answers 401
+            if let Ok(claims) = authenticate(headers, query_params, jwt_config).await {
+                if let Err(reason) = check_ip_binding(&claims, &admin.ip_claim, peer_ip) {
+                    return Some(AdminRejection::forbidden(reason));
+                }
+            }
+        }
+    }
+
+    None
+}
+
+/// Origin of a URL (`scheme://host[:port]`), without path
+fn url_origin(url: &str) -> Option<&str> {
+    let scheme_end = url.find("://")? + 3;
+    let end = url[scheme_end..]
+        .find(['/', '?', '#'])
+        .map_or(url.len(), |i| scheme_end + i);
+    Some(&url[..end])
+}
+
+/// Require state-changing requests to come from the proxy's own origin or an
+/// allowlisted one
+///
+/// Requests without `Origin` or `Referer` (curl, scripts) pass; browsers
+/// always send one of them on cross-site writes.
+pub fn check_origin(
+    csrf: &AdminCsrfConfig,
+    headers: &HashMap<String, String>,
+) -> Result<(), String> {
+    let origin = headers
+        .get("origin")
+        .map(String::as_str)
+        .or_else(|| headers.get("referer").and_then(|r| url_origin(r)));
+    let host = headers.get("host").map(String::as_str);
+
+    if let Some(origin) = origin {
+        let allowlisted = csrf
+            .allowed_origins
+            .iter()
+            .any(|o| o.eq_ignore_ascii_case(origin));
+        let same_origin = host.is_some_and(|host| {
+            origin
+                .split_once("://")
+                .is_some_and(|(_, origin_host)| origin_host.eq_ignore_ascii_case(host))
+        });
+        if !(allowlisted || same_origin) {
+            return Err(format!(
+                "Cross-origin admin request from '{}' refused",
+                origin
+            ));
+        }
+        return Ok(());
+    }
+
+    if headers
+        .get("sec-fetch-site")
+        .is_some_and(|site| site.eq_ignore_ascii_case("cross-site"))
+    {
+        return Err("Cross-site admin request refused".to_string());
+    }
+    Ok(())
+}
+
+/// Require a cookie-bearing request to echo the CSRF cookie in the CSRF header
+///
+/// Requests without cookies, or with an `Authorization` header (which a
+/// browser never attaches on its own), cannot be forged by another site and
+/// are not checked.
+pub fn check_csrf_token(
+    csrf: &AdminCsrfConfig,
+    headers: &HashMap<String, String>,
+) -> Result<(), String> {
+    if !headers.contains_key("cookie") || headers.contains_key("authorization") {
+        return Ok(());
+    }
+    let cookie = extract_cookie_token(headers, &csrf.cookie_name);
+    let header = headers.get(&csrf.header_name.to_ascii_lowercase());
+    match (cookie, header) {
+        (Some(cookie), Some(header)) if constant_time_eq(&cookie, header.trim()) => Ok(()),
+        _ => Err(format!(
+            "Missing or invalid CSRF token: send the {} cookie value in the {} header",
+            csrf.cookie_name, csrf.header_name
+        )),
+    }
+}
+
+/// Require the token's IP claim (an address or CIDR range) to cover the peer
+/// address; `X-Forwarded-For` is ignored so it cannot be spoofed
+pub fn check_ip_binding(
+    claims: &Claims,
+    claim: &str,
+    peer_ip: Option<IpAddr>,
+) -> Result<(), String> {
+    let bound = claims
+        .custom
+        .get(claim)
+        .and_then(|v| v.as_str())
+        .ok_or_else(|| {
+            format!(
+                "Admin token is not bound to a client IP ('{}' claim)",
+                claim
+            )
+        })?;
+    let range = IpRange::parse(bound)
+        .map_err(|_| format!("Admin token has an invalid '{}' claim", claim))?;
+    let ip = peer_ip.ok_or_else(|| "Client IP could not be determined".to_string())?;
+    if range.contains(&ip) {
+        Ok(())
+    } else {
+        Err("Admin token is bound to a different client IP".to_string())
+    }
+}
+
+/// Send the rejection as a JSON error response
+pub async fn send_rejection(session: &mut Session, rejection: &AdminRejection) {
+    let body = serde_json::json!({
+        "status": "error",
+        "code": rejection.code.as_str(),
+        "message": rejection.message,
+    })
+    .to_string();
+    if let Ok(mut header) = ResponseHeader::build(rejection.status, None) {
+        let _ = header.insert_header("Content-Type", "application/json");
+        let _ = header.insert_header(ERROR_CODE_HEADER, rejection.code.as_str());
+        if let Some(allow) = &rejection.allow {
+            let _ = header.insert_header("Allow", allow.as_str());
+        }
+        let _ = header.insert_header("Content-Length", body.len().to_string());
+        let _ = session.write_response_header(Box::new(header), false).await;
+        let _ = session.write_response_body(Some(body.into()), true).await;
+    }
+}
+
+/// GET /admin/csrf-token - Issue a CSRF token as a cookie and in the body
+///
+/// The dashboard reads `token` from the body and sends it back in the CSRF
+/// header; the cookie lets the proxy compare the two without keeping state.
+pub async fn handle_csrf_token(session: &mut Session, config: &Config) -> bool {
+    let csrf = &config.server.admin.csrf;
+    let token = uuid::Uuid::new_v4().simple().to_string();
+    let body = serde_json::json!({
+        "token": token,
+        "header": csrf.header_name,
+    })
+    .to_string();
+    if let Ok(mut header) = ResponseHeader::build(200, None) {
+        let _ = header.insert_header("Content-Type", "application/json");
+        let _ = header.insert_header("Cache-Control", "no-store");
+        let _ = header.insert_header(
+            "Set-Cookie",
+            format!(
+                "{}={}; Path=/admin; SameSite=Strict",
+                csrf.cookie_name, token
+            ),
+        );
+        let _ = header.insert_header("Content-Length", body.len().to_string());
+        let _ = session.write_response_header(Box::new(header), false).await;
+        let _ = session.write_response_body(Some(body.into()), true).await;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(pairs: &[(&str, &str)]) -> HashMap<String, String> {
+        pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn test_allowed_methods_table() {
+        assert_eq!(allowed_methods("/admin/reload"), Some(POST));
+        assert_eq!(
+            allowed_methods("/admin/cache/purge/media/a.jpg"),
+            Some(POST)
+        );
+        assert_eq!(allowed_methods("/admin/cache/stats/media"), Some(GET));
+        assert_eq!(allowed_methods("/admin/cache/prewarm/status/t1"), Some(GET));
+        assert_eq!(allowed_methods("/admin/cache/prewarm/t1"), Some(DELETE));
+        assert_eq!(allowed_methods("/admin/bans/10.0.0.1"), Some(DELETE));
+        assert_eq!(allowed_methods("/admin/csrf-token"), Some(GET));
+        assert_eq!(allowed_methods("/admin/grants"), Some(GET_POST));
+        assert_eq!(allowed_methods("/admin/grants/abc"), Some(DELETE));
+        assert_eq!(allowed_methods("/admin/unknown"), None);
+        assert_eq!(allowed_methods("/media/admin/reload"), None);
+    }
+
+    #[test]
+    fn test_check_origin() {
+        let mut csrf = AdminCsrfConfig::default();
+        csrf.allowed_origins = vec!["https://dash.example.com".into()];
+
+        // Non-browser client
+        assert!(check_origin(&csrf, &headers(&[("host", "proxy:8080")])).is_ok());
+        // Same origin, allowlisted origin, same-origin Referer
+        let same = headers(&[("host", "proxy:8080"), ("origin", "https://proxy:8080")]);
+        assert!(check_origin(&csrf, &same).is_ok());
+        let dash = headers(&[
+            ("host", "proxy:8080"),
+            ("origin", "https://dash.example.com"),
+        ]);
+        assert!(check_origin(&csrf, &dash).is_ok());
+        let referer = headers(&[
+            ("host", "proxy:8080"),
+            ("referer", "http://proxy:8080/ui?x=1"),
+        ]);
+        assert!(check_origin(&csrf, &referer).is_ok());
+
+        let foreign = headers(&[("host", "proxy:8080"), ("origin", "https://evil.example")]);
+        assert!(check_origin(&csrf, &foreign).is_err());
+        let null = headers(&[("host", "proxy:8080"), ("origin", "null")]);
+        assert!(check_origin(&csrf, &null).is_err());
+        let cross = headers(&[("host", "proxy:8080"), ("sec-fetch-site", "cross-site")]);
+        assert!(check_origin(&csrf, &cross).is_err());
+    }
+
+    #[test]
+    fn test_check_csrf_token_double_submit() {
+        let csrf = AdminCsrfConfig::default();
+
+        // Bearer-only and cookie-less requests are not checked
+        assert!(check_csrf_token(&csrf, &headers(&[])).is_ok());
+        let bearer = headers(&[("authorization", "Bearer t"), ("cookie", "a=b")]);
+        assert!(check_csrf_token(&csrf, &bearer).is_ok());
+
+        let matching = headers(&[
+            ("cookie", "session=jwt; yatagarasu_csrf=abc123"),
+            ("x-csrf-token", "abc123"),
+        ]);
+        assert!(check_csrf_token(&csrf, &matching).is_ok());
+        let missing = headers(&[("cookie", "session=jwt; yatagarasu_csrf=abc123")]);
+        assert!(check_csrf_token(&csrf, &missing).is_err());
+        let wrong = headers(&[
+            ("cookie", "session=jwt; yatagarasu_csrf=abc123"),
+            ("x-csrf-token", "abc124"),
+        ]);
+        assert!(check_csrf_token(&csrf, &wrong).is_err());
+    }
+
+    #[test]
+    fn test_check_ip_binding() {
+        let claims: Claims =
+            serde_json::from_value(serde_json::json!({"sub": "ops", "ip": "10.1.0.0/16"})).unwrap();
+        let ip = |s: &str| Some(s.parse::<IpAddr>().unwrap());
+        assert!(check_ip_binding(&claims, "ip", ip("10.1.2.3")).is_ok());
+        assert!(check_ip_binding(&claims, "ip", ip("10.2.0.1")).is_err());
+        assert!(check_ip_binding(&claims, "ip", None).is_err());
+        assert!(check_ip_binding(&claims, "client_ip", ip("10.1.2.3")).is_err());
+    }
+}
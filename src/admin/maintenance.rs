@@ -0,0 +1,61 @@
+use super::send_json_response;
+use crate::maintenance::MaintenanceMode;
+use crate::metrics::Metrics;
+use pingora_proxy::Session;
+use std::sync::Arc;
+
+/// Handle requests to /admin/maintenance/*
+pub async fn handle_request(
+    session: &mut Session,
+    path: &str,
+    method: &str,
+    maintenance: &MaintenanceMode,
+    metrics: &Arc<Metrics>,
+) -> bool {
+    // GET /admin/maintenance - Current state
+    if path == "/admin/maintenance" && method == "GET" {
+        return send_json_response(
+            session,
+            200,
+            serde_json::json!({
+                "enabled": maintenance.is_enabled(),
+                "changed_at": maintenance.changed_at()
+            }),
+        )
+        .await;
+    }
+
+    // POST /admin/maintenance/on|off - Switch maintenance mode
+    if method == "POST" {
+        let enabled = match path {
+            "/admin/maintenance/on" => Some(true),
+            "/admin/maintenance/off" => Some(false),
+            _ => None,
+        };
+        if let Some(enabled) = enabled {
+            let previous = maintenance.set(enabled);
+            metrics.set_maintenance_mode(enabled);
+            if previous != enabled {
+                tracing::warn!(enabled = enabled, "Maintenance mode switched via admin API");
+            }
+            return send_json_response(
+                session,
+                200,
+                serde_json::json!({
+                    "status": "success",
+                    "enabled": enabled,
+                    "previous": previous
+                }),
+            )
+            .await;
+        }
+    }
+
+    // Unhandled path
+    send_json_response(
+        session,
+        404,
+        serde_json::json!({"error": "Endpoint not found"}),
+    )
+    .await
+}
@@ -1,17 +1,48 @@
-use crate::auth::{authenticate_request, verify_admin_claims};
+use crate::analytics::KeyPopularity;
+use crate::auth::failures::AuthFailureTracker;
+use crate::auth::{authenticate, verify_admin_claims};
 use crate::cache::warming::PrewarmManager;
 use crate::config::Config;
+use crate::error::{ErrorCode, ERROR_CODE_HEADER};
+use crate::maintenance::MaintenanceMode;
 use crate::metrics::Metrics;
+use crate::rate_limit::RateLimitManager;
+use crate::replica_set::ReplicaSet;
+use crate::router::Router;
+use crate::security::{AccessGrantStore, IpBanManager};
 use pingora_http::ResponseHeader;
 use pingora_proxy::Session;
 use std::collections::HashMap;
 use std::sync::Arc;
 
+pub mod analytics;
+pub mod auth_failures;
+pub mod bans;
+pub mod events;
+pub mod explain;
+pub mod grants;
+pub mod guard;
+pub mod maintenance;
+pub mod post_policy;
 pub mod prewarm;
+pub mod rate_limits;
 
 /// Check if the path is handled by the admin module
 pub fn is_handled_path(path: &str) -> bool {
     path.starts_with("/admin/cache/prewarm")
+        || path == "/admin/bans"
+        || path.starts_with("/admin/bans/")
+        || path == "/admin/maintenance"
+        || path.starts_with("/admin/maintenance/")
+        || path.starts_with("/admin/analytics/")
+        || path.starts_with("/admin/rate-limits/")
+        || path == "/admin/events"
+        || path == "/admin/auth/failures"
+        || path == "/admin/explain"
+        || path == "/admin/post-policy"
+        || path == "/admin/grants"
+        || path.starts_with("/admin/grants/")
+        || path == "/admin/csrf-token"
 }
 
 /// Handle requests to the /admin API tree
@@ -26,12 +57,20 @@ pub async fn handle_request(
     config: &Config,
     metrics: &Arc<Metrics>,
     prewarm_manager: &Arc<PrewarmManager>,
+    ip_ban_manager: Option<&Arc<IpBanManager>>,
+    maintenance: &MaintenanceMode,
+    key_popularity: Option<&Arc<KeyPopularity>>,
+    rate_limit_manager: Option<&Arc<RateLimitManager>>,
+    auth_failures: &Arc<AuthFailureTracker>,
+    router: &Router,
+    replica_sets: &HashMap<String, ReplicaSet>,
+    access_grants: Option<&Arc<AccessGrantStore>>,
 ) -> bool {
     // 1. Authentication & Authorization
     // All admin endpoints require authentication and admin claims
     if let Some(jwt_config) = &config.jwt {
         if jwt_config.enabled {
-            match authenticate_request(headers, query_params, jwt_config) {
+            match authenticate(headers, query_params, jwt_config).await {
                 Ok(claims) => {
                     // Check admin claims
                     if !verify_admin_claims(&claims, &jwt_config.admin_claims) {
@@ -39,9 +78,10 @@ pub async fn handle_request(
                             path = %path,
                             "Admin access denied: insufficient privileges"
                         );
-                        let _ = send_json_response(
+                        let _ = send_error_response(
                             session,
                             403,
+                            ErrorCode::AuthForbidden,
                             serde_json::json!({
                                 "status": "error",
                                 "message": "Admin access denied: insufficient privileges"
@@ -60,9 +100,10 @@ pub async fn handle_request(
                         error = %e,
                         "Admin authentication failed"
                     );
-                    let _ = send_json_response(
+                    let _ = send_error_response(
                         session,
                         401,
+                        e.error_code(),
                         serde_json::json!({
                             "status": "error",
                             "message": format!("Authentication required: {}", e)
@@ -80,6 +121,58 @@ pub async fn handle_request(
     if path.starts_with("/admin/cache/prewarm") {
         return prewarm::handle_request(session, path, method, prewarm_manager, config).await;
     }
+    if path == "/admin/bans" || path.starts_with("/admin/bans/") {
+        return bans::handle_request(session, path, method, ip_ban_manager).await;
+    }
+    if path == "/admin/maintenance" || path.starts_with("/admin/maintenance/") {
+        return maintenance::handle_request(session, path, method, maintenance, metrics).await;
+    }
+    if path.starts_with("/admin/analytics/") {
+        return analytics::handle_request(
+            session,
+            path,
+            method,
+            query_params,
+            config,
+            key_popularity,
+        )
+        .await;
+    }
+
+    if path.starts_with("/admin/rate-limits/") {
+        return rate_limits::handle_request(
+            session,
+            path,
+            method,
+            query_params,
+            rate_limit_manager,
+        )
+        .await;
+    }
+
+    if path == "/admin/events" {
+        return events::handle_request(session, method, query_params).await;
+    }
+
+    if path == "/admin/auth/failures" {
+        return auth_failures::handle_request(session, method, query_params, auth_failures).await;
+    }
+
+    if path == "/admin/explain" {
+        return explain::handle_request(session, method, config, router, replica_sets).await;
+    }
+
+    if path == "/admin/post-policy" {
+        return post_policy::handle_request(session, method, config).await;
+    }
+
+    if path == "/admin/grants" || path.starts_with("/admin/grants/") {
+        return grants::handle_request(session, path, method, config, access_grants).await;
+    }
+
+    if path == "/admin/csrf-token" {
+        return guard::handle_csrf_token(session, config).await;
+    }
 
     // Return false for unhandled admin paths (to allow legacy handlers in proxy/mod.rs to work)
     // Note: Legacy handlers (reload, cache/purge) perform their own auth checking.
@@ -87,15 +180,59 @@ pub async fn handle_request(
     false
 }
 
-/// Helper to send JSON response
+/// Add the machine-readable code to an admin error body
+///
+/// Returns the code for the `X-Yatagarasu-Error` header, or `None` for
+/// successful responses.
+fn tag_error_body(status: u16, body: &mut serde_json::Value) -> Option<ErrorCode> {
+    if status < 400 {
+        return None;
+    }
+    let code = ErrorCode::for_status(status);
+    if let Some(object) = body.as_object_mut() {
+        object.insert("code".to_string(), code.as_str().into());
+    }
+    Some(code)
+}
+
+/// Send a JSON response for the admin endpoints
+///
+/// Error bodies (status 400 and above) are tagged with the error code for
+/// the status. Always returns `true`, the request is handled.
 async fn send_json_response(
     session: &mut Session,
     status: u16,
-    body: serde_json::Value,
+    mut body: serde_json::Value,
+) -> bool {
+    let code = tag_error_body(status, &mut body);
+    let body_str = body.to_string();
+    if let Ok(mut header) = ResponseHeader::build(status, None) {
+        let _ = header.insert_header("Content-Type", "application/json");
+        if let Some(code) = code {
+            let _ = header.insert_header(ERROR_CODE_HEADER, code.as_str());
+        }
+        let _ = header.insert_header("Content-Length", body_str.len().to_string());
+
+        let _ = session.write_response_header(Box::new(header), false).await;
+        let _ = session
+            .write_response_body(Some(body_str.into()), true)
+            .await;
+    }
+    true
+}
+
+/// Send a JSON error response with an explicit error code
+async fn send_error_response(
+    session: &mut Session,
+    status: u16,
+    code: ErrorCode,
+    mut body: serde_json::Value,
 ) -> pingora_core::Result<()> {
+    body["code"] = code.as_str().into();
     let body_str = body.to_string();
     let mut header = ResponseHeader::build(status, None)?;
     header.insert_header("Content-Type", "application/json")?;
+    header.insert_header(ERROR_CODE_HEADER, code.as_str())?;
     header.insert_header("Content-Length", body_str.len().to_string())?;
 
     session
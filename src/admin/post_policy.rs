@@ -0,0 +1,165 @@
+//! `POST /admin/post-policy`: issue a signed browser upload policy.
+//!
+//! The caller names a bucket with `post_policy` enabled and what the upload
+//! may be (exact key or key prefix, content type, size, lifetime). The
+//! response holds the form action and the hidden fields the browser posts
+//! along with the file. Requests beyond the bucket's limits are refused.
+
+use super::send_json_response;
+use crate::config::Config;
+use crate::security::post_policy::{self, PolicyRequest};
+use crate::util::unix_now;
+use pingora_proxy::Session;
+use serde::Deserialize;
+
+/// Largest accepted request body
+const MAX_BODY_BYTES: usize = 16 * 1024;
+
+/// A policy request for one bucket
+#[derive(Debug, Clone, Deserialize)]
+pub struct IssueRequest {
+    pub bucket: String,
+    #[serde(flatten)]
+    pub policy: PolicyRequest,
+}
+
+/// Handle requests to /admin/post-policy
+pub async fn handle_request(session: &mut Session, method: &str, config: &Config) -> bool {
+    if method != "POST" {
+        return send_json_response(
+            session,
+            405,
+            serde_json::json!({"error": "Method not allowed"}),
+        )
+        .await;
+    }
+
+    let mut body = Vec::new();
+    loop {
+        match session.read_request_body().await {
+            Ok(Some(chunk)) => {
+                if body.len() + chunk.len() > MAX_BODY_BYTES {
+                    return send_json_response(
+                        session,
+                        413,
+                        serde_json::json!({"error": "Request body too large"}),
+                    )
+                    .await;
+                }
+                body.extend_from_slice(&chunk);
+            }
+            Ok(None) => break,
+            Err(e) => {
+                return send_json_response(
+                    session,
+                    500,
+                    serde_json::json!({"error": e.to_string()}),
+                )
+                .await
+            }
+        }
+    }
+
+    let request: IssueRequest = match serde_json::from_slice(&body) {
+        Ok(request) => request,
+        Err(e) => {
+            return send_json_response(
+                session,
+                400,
+                serde_json::json!({"error": "Invalid JSON", "details": e.to_string()}),
+            )
+            .await
+        }
+    };
+
+    let (status, body) = issue(&request, config, unix_now());
+    send_json_response(session, status, body).await
+}
+
+/// Issue a policy for `request` at time `now` (unix seconds)
+pub fn issue(request: &IssueRequest, config: &Config, now: u64) -> (u16, serde_json::Value) {
+    let Some(bucket) = config.buckets.iter().find(|b| b.name == request.bucket) else {
+        return (
+            404,
+            serde_json::json!({"error": format!("Bucket '{}' not found", request.bucket)}),
+        );
+    };
+    let Some(policy_config) = bucket.post_policy.as_ref().filter(|p| p.enabled) else {
+        return (
+            400,
+            serde_json::json!({
+                "error": format!("Bucket '{}' does not accept POST policy uploads", bucket.name)
+            }),
+        );
+    };
+
+    match post_policy::generate(policy_config, &request.policy, now) {
+        Ok(signed) => (
+            200,
+            serde_json::json!({
+                "bucket": bucket.name,
+                "url": bucket.path_prefix,
+                "method": "POST",
+                "enctype": "multipart/form-data",
+                "expiration": signed.expiration,
+                "fields": signed.fields,
+            }),
+        ),
+        Err(e) => (400, serde_json::json!({"error": e})),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_issue_post_policy() {
+        let yaml = r#"
+server:
+  address: "127.0.0.1"
+  port: 8080
+buckets:
+  - name: uploads
+    path_prefix: /uploads
+    allowed_methods: [GET, HEAD, POST]
+    s3:
+      bucket: uploads-bucket
+      region: us-east-1
+      access_key: AKIA
+      secret_key: secret
+    post_policy:
+      secret: uploads-key
+      key_prefix: incoming/
+  - name: media
+    path_prefix: /media
+    s3:
+      bucket: media-bucket
+      region: us-east-1
+      access_key: AKIA
+      secret_key: secret
+"#;
+        let config = Config::from_yaml_with_env(yaml).unwrap();
+        let request = |bucket: &str, key: Option<&str>| IssueRequest {
+            bucket: bucket.to_string(),
+            policy: PolicyRequest {
+                key: key.map(str::to_string),
+                ..Default::default()
+            },
+        };
+        let now = 1_700_000_000;
+
+        let (status, body) = issue(&request("uploads", Some("incoming/a.png")), &config, now);
+        assert_eq!(status, 200);
+        assert_eq!(body["url"], "/uploads");
+        assert_eq!(body["fields"]["key"], "incoming/a.png");
+        assert!(body["fields"]["signature"].is_string());
+
+        let (status, _) = issue(&request("uploads", Some("other/a.png")), &config, now);
+        assert_eq!(status, 400);
+        let (status, _) = issue(&request("media", None), &config, now);
+        assert_eq!(status, 400);
+        let (status, _) = issue(&request("missing", None), &config, now);
+        assert_eq!(status, 404);
+    }
+}
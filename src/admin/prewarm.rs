@@ -1,6 +1,7 @@
-use crate::cache::warming::{PrewarmManager, PrewarmOptions};
+use super::send_json_response;
+use crate::cache::replay::{hot_keys_from_audit_log, ReplayOptions};
+use crate::cache::warming::{PrewarmConfig, PrewarmManager, PrewarmOptions};
 use crate::config::Config;
-use pingora_http::ResponseHeader;
 use pingora_proxy::Session;
 use serde::Deserialize;
 use std::sync::Arc;
@@ -66,6 +67,16 @@ pub async fn handle_request(
             .await;
         }
 
+        // Prewarming lists the prefix with ListObjectsV2, which only S3 serves
+        if config
+            .buckets
+            .iter()
+            .any(|b| b.name == req.bucket && b.origin.is_some())
+        {
+            let message = format!("Bucket '{}' has a non-S3 origin", req.bucket);
+            return send_json_response(session, 400, serde_json::json!({"error": message})).await;
+        }
+
         let task_id =
             if let Some(bucket_config) = config.buckets.iter().find(|b| b.name == req.bucket) {
                 manager.create_task(req.bucket, req.path, req.options, bucket_config.s3.clone())
@@ -90,6 +101,79 @@ pub async fn handle_request(
         .await;
     }
 
+    // POST /admin/cache/prewarm/replay - Warm the most requested keys from the audit log
+    if path == "/admin/cache/prewarm/replay" && method == "POST" {
+        let options: ReplayOptions = match session.read_request_body().await {
+            Ok(Some(b)) if !b.is_empty() => match serde_json::from_slice(&b) {
+                Ok(o) => o,
+                Err(e) => {
+                    return send_json_response(
+                        session,
+                        400,
+                        serde_json::json!({"error": "Invalid JSON", "details": e.to_string()}),
+                    )
+                    .await
+                }
+            },
+            Ok(_) => ReplayOptions::default(),
+            Err(e) => {
+                return send_json_response(
+                    session,
+                    500,
+                    serde_json::json!({"error": e.to_string()}),
+                )
+                .await
+            }
+        };
+
+        let Some(log_path) = config
+            .audit_log
+            .as_ref()
+            .filter(|a| a.enabled)
+            .and_then(|a| a.file.as_ref())
+            .map(|f| f.path.clone())
+        else {
+            return send_json_response(
+                session,
+                409,
+                serde_json::json!({"error": "No audit log file configured"}),
+            )
+            .await;
+        };
+
+        let keys = match hot_keys_from_audit_log(std::path::Path::new(&log_path), &options) {
+            Ok(keys) => keys,
+            Err(e) => {
+                return send_json_response(session, 404, serde_json::json!({"error": e})).await
+            }
+        };
+        let key_count = keys.len();
+        let max_item_size_bytes = config
+            .cache
+            .as_ref()
+            .map(|c| c.memory.max_item_size_mb * 1024 * 1024)
+            .unwrap_or(u64::MAX);
+        let task_id = manager.create_key_task(
+            format!("replay:{}", log_path),
+            keys,
+            config.buckets.clone(),
+            max_item_size_bytes,
+            PrewarmConfig::default().concurrency,
+        );
+
+        return send_json_response(
+            session,
+            201,
+            serde_json::json!({
+                "status": "success",
+                "task_id": task_id,
+                "keys": key_count,
+                "message": "Replay prewarm task created"
+            }),
+        )
+        .await;
+    }
+
     // GET /admin/cache/prewarm/tasks - List tasks
     if path == "/admin/cache/prewarm/tasks" && method == "GET" {
         let tasks = manager.list_tasks();
@@ -140,20 +224,6 @@ pub async fn handle_request(
     true
 }
 
-async fn send_json_response(session: &mut Session, status: u16, body: serde_json::Value) -> bool {
-    let body_str = body.to_string();
-    if let Ok(mut header) = ResponseHeader::build(status, None) {
-        let _ = header.insert_header("Content-Type", "application/json");
-        let _ = header.insert_header("Content-Length", body_str.len().to_string());
-
-        let _ = session.write_response_header(Box::new(header), false).await;
-        let _ = session
-            .write_response_body(Some(body_str.into()), true)
-            .await;
-    }
-    true
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
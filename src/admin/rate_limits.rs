@@ -0,0 +1,113 @@
+use super::send_json_response;
+use crate::rate_limit::RateLimitManager;
+use pingora_proxy::Session;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+/// Handle requests to /admin/rate-limits/*
+pub async fn handle_request(
+    session: &mut Session,
+    path: &str,
+    method: &str,
+    query_params: &HashMap<String, String>,
+    manager: Option<&Arc<RateLimitManager>>,
+) -> bool {
+    let Some(manager) = manager else {
+        return send_json_response(
+            session,
+            404,
+            serde_json::json!({"error": "Rate limiting is not enabled"}),
+        )
+        .await;
+    };
+
+    // GET /admin/rate-limits/state?ip=1.2.3.4 or ?subject=user@example.com
+    if path == "/admin/rate-limits/state" && method == "GET" {
+        let (status, body) = state_response(manager, query_params);
+        return send_json_response(session, status, body).await;
+    }
+
+    send_json_response(
+        session,
+        404,
+        serde_json::json!({"error": "Endpoint not found"}),
+    )
+    .await
+}
+
+/// Token bucket state for the `ip` and/or `subject` query parameters
+fn state_response(
+    manager: &RateLimitManager,
+    query_params: &HashMap<String, String>,
+) -> (u16, serde_json::Value) {
+    let ip = query_params.get("ip");
+    let subject = query_params.get("subject");
+    if ip.is_none() && subject.is_none() {
+        return (
+            400,
+            serde_json::json!({"error": "Missing query parameter 'ip' or 'subject'"}),
+        );
+    }
+
+    let mut body = serde_json::Map::new();
+    if let Some(raw_ip) = ip {
+        let Some(ip) = super::bans::parse_ip(raw_ip) else {
+            return (
+                400,
+                serde_json::json!({"error": format!("Invalid IP address '{}'", raw_ip)}),
+            );
+        };
+        let state = manager.ip_state(ip);
+        body.insert(
+            "ip".to_string(),
+            serde_json::json!({
+                "ip": ip.to_string(),
+                "limit_configured": manager.has_ip_limit(),
+                "tracked": state.is_some(),
+                "state": state,
+            }),
+        );
+    }
+    if let Some(subject) = subject {
+        let state = manager.user_state(subject);
+        body.insert(
+            "subject".to_string(),
+            serde_json::json!({
+                "subject": subject,
+                "limit_configured": manager.has_user_limit(),
+                "tracked": state.is_some(),
+                "state": state,
+            }),
+        );
+    }
+    (200, serde_json::Value::Object(body))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rate_limit::RateQuota;
+
+    #[test]
+    fn test_state_response() {
+        let manager = RateLimitManager::with_quotas(None, Some(RateQuota::per_second(5)), None);
+        manager.check_ip("203.0.113.7".parse().unwrap());
+
+        let query = HashMap::from([
+            ("ip".to_string(), "203.0.113.7".to_string()),
+            ("subject".to_string(), "user@example.com".to_string()),
+        ]);
+        let (status, body) = state_response(&manager, &query);
+        assert_eq!(status, 200);
+        assert_eq!(body["ip"]["tracked"], true);
+        assert_eq!(body["ip"]["state"]["burst"], 5);
+        assert_eq!(body["ip"]["state"]["tokens_available"], 4);
+        assert_eq!(body["subject"]["limit_configured"], false);
+        assert!(body["subject"]["state"].is_null());
+
+        let (status, _) = state_response(&manager, &HashMap::new());
+        assert_eq!(status, 400);
+        let query = HashMap::from([("ip".to_string(), "not-an-ip".to_string())]);
+        assert_eq!(state_response(&manager, &query).0, 400);
+    }
+}
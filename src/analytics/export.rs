@@ -0,0 +1,406 @@
+//! Access statistics export.
+//!
+//! [`AccessStats`] aggregates requests, bytes sent and cache hits per object
+//! key and bucket. Every `interval_secs` the counters are drained into an
+//! [`AccessReport`], rendered as CSV and written to a local directory and/or
+//! uploaded to S3, so BI tools can consume traffic data without parsing raw
+//! audit logs.
+//!
+//! Each bucket keeps at most `max_keys` distinct keys per interval; requests
+//! for further keys are folded into a single `(other)` row so memory stays
+//! bounded.
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::config::{AccessExportConfig, AccessExportS3Config, BucketConfig};
+use crate::s3::S3Client;
+
+/// Key reported for requests beyond `max_keys` distinct keys
+pub const OTHER_KEYS: &str = "(other)";
+
+/// CSV header row
+const CSV_HEADER: &str = "period_start,period_end,bucket,key,requests,bytes,cache_hits,hit_ratio";
+
+/// Aggregated counters for one key (or a whole bucket)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AccessCounts {
+    pub requests: u64,
+    pub bytes: u64,
+    pub cache_hits: u64,
+}
+
+impl AccessCounts {
+    fn add(&mut self, bytes: u64, cache_hit: bool) {
+        self.requests += 1;
+        self.bytes += bytes;
+        if cache_hit {
+            self.cache_hits += 1;
+        }
+    }
+
+    fn merge(&mut self, other: &AccessCounts) {
+        self.requests += other.requests;
+        self.bytes += other.bytes;
+        self.cache_hits += other.cache_hits;
+    }
+
+    /// Fraction of requests served from cache (0.0 when there were none)
+    pub fn hit_ratio(&self) -> f64 {
+        if self.requests == 0 {
+            0.0
+        } else {
+            self.cache_hits as f64 / self.requests as f64
+        }
+    }
+}
+
+#[derive(Default)]
+struct BucketCounts {
+    keys: HashMap<String, AccessCounts>,
+    other: AccessCounts,
+}
+
+struct Period {
+    start: DateTime<Utc>,
+    buckets: HashMap<String, BucketCounts>,
+}
+
+/// One row of an access report; `key` is None for the bucket total
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessRow {
+    pub bucket: String,
+    pub key: Option<String>,
+    pub counts: AccessCounts,
+}
+
+/// Access statistics for one export interval
+#[derive(Debug, Clone)]
+pub struct AccessReport {
+    pub period_start: DateTime<Utc>,
+    pub period_end: DateTime<Utc>,
+    /// Per bucket: the total row first, then keys by descending requests
+    pub rows: Vec<AccessRow>,
+}
+
+impl AccessReport {
+    /// Render the report as CSV with a header row
+    pub fn to_csv(&self) -> String {
+        let start = self.period_start.to_rfc3339();
+        let end = self.period_end.to_rfc3339();
+        let mut out = String::with_capacity(64 * (self.rows.len() + 1));
+        out.push_str(CSV_HEADER);
+        out.push('\n');
+        for row in &self.rows {
+            out.push_str(&format!(
+                "{},{},{},{},{},{},{},{:.4}\n",
+                start,
+                end,
+                csv_field(&row.bucket),
+                csv_field(row.key.as_deref().unwrap_or("")),
+                row.counts.requests,
+                row.counts.bytes,
+                row.counts.cache_hits,
+                row.counts.hit_ratio()
+            ));
+        }
+        out
+    }
+
+    /// File name for this report, derived from the end of its period
+    pub fn file_name(&self) -> String {
+        format!(
+            "access-stats-{}.csv",
+            self.period_end.format("%Y%m%dT%H%M%SZ")
+        )
+    }
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Per-key access counters drained by the periodic export
+pub struct AccessStats {
+    config: AccessExportConfig,
+    period: Mutex<Period>,
+}
+
+impl AccessStats {
+    pub fn new(config: AccessExportConfig) -> Self {
+        Self {
+            config,
+            period: Mutex::new(Period {
+                start: Utc::now(),
+                buckets: HashMap::new(),
+            }),
+        }
+    }
+
+    /// Count a request for `key` in `bucket` that sent `bytes` to the client
+    pub fn record(&self, bucket: &str, key: &str, bytes: u64, cache_hit: bool) {
+        let mut period = self.period.lock();
+        if !period.buckets.contains_key(bucket) {
+            period
+                .buckets
+                .insert(bucket.to_string(), BucketCounts::default());
+        }
+        let Some(counts) = period.buckets.get_mut(bucket) else {
+            return;
+        };
+        if let Some(entry) = counts.keys.get_mut(key) {
+            entry.add(bytes, cache_hit);
+        } else if counts.keys.len() < self.config.max_keys {
+            counts
+                .keys
+                .entry(key.to_string())
+                .or_default()
+                .add(bytes, cache_hit);
+        } else {
+            counts.other.add(bytes, cache_hit);
+        }
+    }
+
+    /// Drain the counters into a report covering the period since the last drain
+    pub fn take_report(&self) -> AccessReport {
+        self.take_report_at(Utc::now())
+    }
+
+    fn take_report_at(&self, now: DateTime<Utc>) -> AccessReport {
+        let period = std::mem::replace(
+            &mut *self.period.lock(),
+            Period {
+                start: now,
+                buckets: HashMap::new(),
+            },
+        );
+
+        let mut buckets: Vec<(String, BucketCounts)> = period.buckets.into_iter().collect();
+        buckets.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut rows = Vec::new();
+        for (bucket, counts) in buckets {
+            let mut total = counts.other;
+            let mut keys: Vec<(String, AccessCounts)> = counts.keys.into_iter().collect();
+            keys.iter().for_each(|(_, c)| total.merge(c));
+            keys.sort_by(|a, b| b.1.requests.cmp(&a.1.requests).then_with(|| a.0.cmp(&b.0)));
+
+            rows.push(AccessRow {
+                bucket: bucket.clone(),
+                key: None,
+                counts: total,
+            });
+            rows.extend(keys.into_iter().map(|(key, counts)| AccessRow {
+                bucket: bucket.clone(),
+                key: Some(key),
+                counts,
+            }));
+            if counts.other.requests > 0 {
+                rows.push(AccessRow {
+                    bucket,
+                    key: Some(OTHER_KEYS.to_string()),
+                    counts: counts.other,
+                });
+            }
+        }
+
+        AccessReport {
+            period_start: period.start,
+            period_end: now,
+            rows,
+        }
+    }
+
+    /// Write a report to every configured destination
+    pub async fn export(
+        &self,
+        report: &AccessReport,
+        buckets: &[BucketConfig],
+    ) -> Result<(), String> {
+        let csv = report.to_csv();
+        let file_name = report.file_name();
+
+        if let Some(ref directory) = self.config.directory {
+            write_local(Path::new(directory), &file_name, &csv)?;
+        }
+        if let Some(ref s3) = self.config.s3 {
+            upload_s3(s3, buckets, report, &file_name, csv).await?;
+        }
+        Ok(())
+    }
+
+    /// Spawn the periodic export task
+    pub fn start_export_task(self: &Arc<Self>, buckets: Vec<BucketConfig>) {
+        let interval = Duration::from_secs(self.config.interval_secs);
+        let stats = Arc::downgrade(self);
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            // First tick completes immediately; the first period has just started
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(stats) = stats.upgrade() else {
+                    break;
+                };
+                let report = stats.take_report();
+                match stats.export(&report, &buckets).await {
+                    Ok(()) => tracing::info!(
+                        rows = report.rows.len(),
+                        file = %report.file_name(),
+                        "Access statistics exported"
+                    ),
+                    Err(e) => tracing::warn!(error = %e, "Failed to export access statistics"),
+                }
+            }
+        });
+    }
+}
+
+/// Write `contents` to `directory/file_name` atomically
+fn write_local(directory: &Path, file_name: &str, contents: &str) -> Result<(), String> {
+    std::fs::create_dir_all(directory)
+        .map_err(|e| format!("Failed to create {}: {}", directory.display(), e))?;
+    let path = directory.join(file_name);
+    let tmp = directory.join(format!("{}.tmp", file_name));
+    std::fs::write(&tmp, contents)
+        .map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+    std::fs::rename(&tmp, &path).map_err(|e| format!("Failed to rename {}: {}", tmp.display(), e))
+}
+
+/// Upload a report under `prefix/YYYY/MM/DD/` using a configured bucket's S3 settings
+async fn upload_s3(
+    destination: &AccessExportS3Config,
+    buckets: &[BucketConfig],
+    report: &AccessReport,
+    file_name: &str,
+    csv: String,
+) -> Result<(), String> {
+    let bucket = buckets
+        .iter()
+        .find(|b| b.name == destination.bucket)
+        .ok_or_else(|| format!("Unknown export bucket '{}'", destination.bucket))?;
+    let object_key = format!(
+        "{}{}/{}",
+        destination.prefix,
+        report.period_end.format("%Y/%m/%d"),
+        file_name
+    );
+    let client = S3Client {
+        config: bucket.s3.clone(),
+    }
+    .create_aws_client()
+    .await;
+
+    client
+        .put_object()
+        .bucket(&bucket.s3.bucket)
+        .key(&object_key)
+        .content_type("text/csv")
+        .body(csv.into_bytes().into())
+        .send()
+        .await
+        .map_err(|e| format!("Failed to upload {}: {}", object_key, e))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(max_keys: usize) -> AccessStats {
+        AccessStats::new(AccessExportConfig {
+            enabled: true,
+            max_keys,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_report_aggregates_per_key_and_bucket() {
+        let stats = stats(10);
+        stats.record("media", "a.png", 100, false);
+        stats.record("media", "a.png", 100, true);
+        stats.record("media", "b.png", 50, true);
+        stats.record("docs", "x.pdf", 10, false);
+
+        let report = stats.take_report();
+        let rows: Vec<(&str, Option<&str>, AccessCounts)> = report
+            .rows
+            .iter()
+            .map(|r| (r.bucket.as_str(), r.key.as_deref(), r.counts))
+            .collect();
+        let counts = |requests, bytes, cache_hits| AccessCounts {
+            requests,
+            bytes,
+            cache_hits,
+        };
+        assert_eq!(
+            rows,
+            vec![
+                ("docs", None, counts(1, 10, 0)),
+                ("docs", Some("x.pdf"), counts(1, 10, 0)),
+                ("media", None, counts(3, 250, 2)),
+                ("media", Some("a.png"), counts(2, 200, 1)),
+                ("media", Some("b.png"), counts(1, 50, 1)),
+            ]
+        );
+
+        // Counters are drained by the report
+        assert!(stats.take_report().rows.is_empty());
+    }
+
+    #[test]
+    fn test_keys_beyond_limit_fold_into_other() {
+        let stats = stats(1);
+        stats.record("media", "a.png", 1, false);
+        stats.record("media", "b.png", 2, false);
+        stats.record("media", "c.png", 3, true);
+
+        let report = stats.take_report();
+        assert_eq!(report.rows.len(), 3);
+        assert_eq!(report.rows[0].counts.requests, 3);
+        assert_eq!(report.rows[2].key.as_deref(), Some(OTHER_KEYS));
+        assert_eq!(report.rows[2].counts.bytes, 5);
+    }
+
+    #[test]
+    fn test_report_csv_output() {
+        let stats = stats(10);
+        stats.record("media", "photos/a,b.png", 100, true);
+        stats.record("media", "photos/a,b.png", 100, false);
+        let now = DateTime::parse_from_rfc3339("2024-05-01T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        let report = stats.take_report_at(now);
+        let csv = report.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines[0], CSV_HEADER);
+        assert!(lines[1].ends_with(",media,,2,200,1,0.5000"));
+        assert!(lines[2].ends_with(",media,\"photos/a,b.png\",2,200,1,0.5000"));
+        assert_eq!(report.file_name(), "access-stats-20240501T120000Z.csv");
+    }
+
+    #[test]
+    fn test_write_local_creates_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let target = dir.path().join("stats");
+        write_local(&target, "report.csv", "a,b\n").unwrap();
+        assert_eq!(
+            std::fs::read_to_string(target.join("report.csv")).unwrap(),
+            "a,b\n"
+        );
+        assert!(!target.join("report.csv.tmp").exists());
+    }
+}
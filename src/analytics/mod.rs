@@ -0,0 +1,324 @@
+//! Key popularity analytics.
+//!
+//! Counts requests per object key for each bucket in fixed time slots. Each
+//! slot holds a [`CountMinSketch`] for approximate counts and a bounded set
+//! of heavy-hitter candidates, so memory stays fixed no matter how many
+//! distinct keys are requested. Querying a window merges the slots it covers
+//! and ranks the candidates by their summed estimates.
+//!
+//! Configured by [`KeyAnalyticsConfig`] and served by
+//! `GET /admin/analytics/top-keys`. Periodic per-key access statistics for
+//! external tools are produced by [`AccessStats`].
+
+mod export;
+mod sketch;
+
+pub use export::{AccessCounts, AccessReport, AccessRow, AccessStats};
+pub use sketch::CountMinSketch;
+
+use parking_lot::{Mutex, RwLock};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
+
+use crate::config::KeyAnalyticsConfig;
+use crate::util::unix_now;
+
+/// Default window for top-key queries (1 hour)
+pub const DEFAULT_WINDOW_SECS: u64 = 3600;
+
+/// Requests counted in one time slot
+struct Slot {
+    /// Slot number (unix seconds / slot_secs)
+    index: u64,
+    total: u64,
+    sketch: CountMinSketch,
+    /// Candidate keys with their latest estimate
+    candidates: HashMap<String, u32>,
+    /// Lower bound of the smallest candidate estimate (skips most min scans)
+    min_candidate: u32,
+}
+
+impl Slot {
+    fn new(index: u64, config: &KeyAnalyticsConfig) -> Self {
+        Self {
+            index,
+            total: 0,
+            sketch: CountMinSketch::new(config.sketch_width, config.sketch_depth),
+            candidates: HashMap::new(),
+            min_candidate: 0,
+        }
+    }
+
+    fn record(&mut self, key: &str, max_candidates: usize) {
+        self.total += 1;
+        let estimate = self.sketch.increment(key);
+
+        if let Some(current) = self.candidates.get_mut(key) {
+            *current = estimate;
+            return;
+        }
+        if self.candidates.len() < max_candidates {
+            self.candidates.insert(key.to_string(), estimate);
+            return;
+        }
+        if estimate <= self.min_candidate {
+            return;
+        }
+
+        // Replace the weakest candidate if this key now beats it
+        if let Some((weakest, weakest_estimate)) = self
+            .candidates
+            .iter()
+            .min_by_key(|(_, estimate)| **estimate)
+            .map(|(k, v)| (k.clone(), *v))
+        {
+            if estimate > weakest_estimate {
+                self.candidates.remove(&weakest);
+                self.candidates.insert(key.to_string(), estimate);
+            }
+        }
+        self.min_candidate = self.candidates.values().copied().min().unwrap_or(0);
+    }
+}
+
+/// A popular key and its estimated request count
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TopKey {
+    pub key: String,
+    pub requests: u64,
+}
+
+/// Top keys of a bucket over a time window
+#[derive(Debug, Clone, Serialize)]
+pub struct TopKeysReport {
+    pub bucket: String,
+    pub window_secs: u64,
+    pub total_requests: u64,
+    pub keys: Vec<TopKey>,
+}
+
+/// Per-bucket approximate request counts by key
+pub struct KeyPopularity {
+    config: KeyAnalyticsConfig,
+    buckets: RwLock<HashMap<String, Arc<Mutex<VecDeque<Slot>>>>>,
+}
+
+impl KeyPopularity {
+    pub fn new(config: KeyAnalyticsConfig) -> Self {
+        Self {
+            config,
+            buckets: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Longest window that can be queried
+    pub fn max_window_secs(&self) -> u64 {
+        self.config.max_window_secs
+    }
+
+    /// Most keys a query can return
+    pub fn max_limit(&self) -> usize {
+        self.config.candidates
+    }
+
+    /// Count a request for `key` in `bucket`
+    pub fn record(&self, bucket: &str, key: &str) {
+        self.record_at(bucket, key, unix_now());
+    }
+
+    /// Top `limit` keys of `bucket` over the last `window_secs`
+    pub fn top_keys(&self, bucket: &str, window_secs: u64, limit: usize) -> TopKeysReport {
+        self.top_keys_at(bucket, window_secs, limit, unix_now())
+    }
+
+    fn slots(&self, bucket: &str) -> Arc<Mutex<VecDeque<Slot>>> {
+        if let Some(slots) = self.buckets.read().get(bucket) {
+            return slots.clone();
+        }
+        self.buckets
+            .write()
+            .entry(bucket.to_string())
+            .or_insert_with(|| Arc::new(Mutex::new(VecDeque::new())))
+            .clone()
+    }
+
+    fn record_at(&self, bucket: &str, key: &str, now: u64) {
+        let current = now / self.config.slot_secs;
+        let oldest = current.saturating_sub(self.config.slot_count() as u64 - 1);
+        let slots = self.slots(bucket);
+        let mut slots = slots.lock();
+
+        while slots.front().is_some_and(|slot| slot.index < oldest) {
+            slots.pop_front();
+        }
+        if slots.back().map(|slot| slot.index) != Some(current) {
+            slots.push_back(Slot::new(current, &self.config));
+        }
+        if let Some(slot) = slots.back_mut() {
+            slot.record(key, self.config.candidates);
+        }
+    }
+
+    fn top_keys_at(&self, bucket: &str, window_secs: u64, limit: usize, now: u64) -> TopKeysReport {
+        let window_secs = window_secs.clamp(self.config.slot_secs, self.config.max_window_secs);
+        let window_slots = window_secs.div_ceil(self.config.slot_secs);
+        let current = now / self.config.slot_secs;
+        let oldest = current.saturating_sub(window_slots - 1);
+
+        let mut report = TopKeysReport {
+            bucket: bucket.to_string(),
+            window_secs,
+            total_requests: 0,
+            keys: Vec::new(),
+        };
+        let Some(slots) = self.buckets.read().get(bucket).cloned() else {
+            return report;
+        };
+        let slots = slots.lock();
+        let in_window: Vec<&Slot> = slots
+            .iter()
+            .filter(|slot| slot.index >= oldest && slot.index <= current)
+            .collect();
+
+        report.total_requests = in_window.iter().map(|slot| slot.total).sum();
+        let candidates: HashSet<&String> = in_window
+            .iter()
+            .flat_map(|slot| slot.candidates.keys())
+            .collect();
+        let mut keys: Vec<TopKey> = candidates
+            .into_iter()
+            .map(|key| TopKey {
+                key: key.clone(),
+                requests: in_window
+                    .iter()
+                    .map(|slot| slot.sketch.estimate(key) as u64)
+                    .sum(),
+            })
+            .collect();
+        keys.sort_by(|a, b| b.requests.cmp(&a.requests).then_with(|| a.key.cmp(&b.key)));
+        keys.truncate(limit.min(self.config.candidates));
+        report.keys = keys;
+        report
+    }
+}
+
+/// Parse a window such as `90s`, `15m`, `1h`, `1d` or plain seconds
+pub fn parse_window(value: &str) -> Result<u64, String> {
+    let value = value.trim();
+    let (number, unit) = match value.find(|c: char| !c.is_ascii_digit()) {
+        Some(split) => value.split_at(split),
+        None => (value, "s"),
+    };
+    let multiplier = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 3600,
+        "d" => 86400,
+        _ => return Err(format!("Invalid window '{}': use s, m, h or d", value)),
+    };
+    match number.parse::<u64>() {
+        Ok(n) if n > 0 => Ok(n * multiplier),
+        _ => Err(format!("Invalid window '{}'", value)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn popularity(candidates: usize) -> KeyPopularity {
+        KeyPopularity::new(KeyAnalyticsConfig {
+            enabled: true,
+            slot_secs: 60,
+            max_window_secs: 3600,
+            candidates,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_top_keys_ranked_by_requests() {
+        let popularity = popularity(10);
+        let now = 1_000_000;
+        for _ in 0..5 {
+            popularity.record_at("media", "a.png", now);
+        }
+        for _ in 0..3 {
+            popularity.record_at("media", "b.png", now);
+        }
+        popularity.record_at("media", "c.png", now);
+        popularity.record_at("docs", "x.pdf", now);
+
+        let report = popularity.top_keys_at("media", 3600, 2, now);
+        assert_eq!(report.total_requests, 9);
+        assert_eq!(
+            report.keys,
+            vec![
+                TopKey {
+                    key: "a.png".to_string(),
+                    requests: 5
+                },
+                TopKey {
+                    key: "b.png".to_string(),
+                    requests: 3
+                },
+            ]
+        );
+        assert!(popularity
+            .top_keys_at("unknown", 3600, 10, now)
+            .keys
+            .is_empty());
+    }
+
+    #[test]
+    fn test_top_keys_window_excludes_old_slots() {
+        let popularity = popularity(10);
+        let now = 1_000_000;
+        popularity.record_at("media", "old.png", now - 600);
+        popularity.record_at("media", "old.png", now - 600);
+        popularity.record_at("media", "new.png", now);
+
+        let last_minute = popularity.top_keys_at("media", 60, 10, now);
+        assert_eq!(last_minute.total_requests, 1);
+        assert_eq!(last_minute.keys[0].key, "new.png");
+
+        let last_hour = popularity.top_keys_at("media", 3600, 10, now);
+        assert_eq!(last_hour.total_requests, 3);
+        assert_eq!(last_hour.keys[0].key, "old.png");
+        assert_eq!(last_hour.keys[0].requests, 2);
+
+        // Slots older than max_window_secs are dropped on the next record
+        popularity.record_at("media", "new.png", now + 3600);
+        let later = popularity.top_keys_at("media", 3600, 10, now + 3600);
+        assert!(later.keys.iter().all(|k| k.key != "old.png"));
+    }
+
+    #[test]
+    fn test_heavy_hitter_replaces_weak_candidate() {
+        let popularity = popularity(2);
+        let now = 1_000_000;
+        popularity.record_at("media", "a", now);
+        popularity.record_at("media", "b", now);
+        for _ in 0..4 {
+            popularity.record_at("media", "hot", now);
+        }
+
+        let report = popularity.top_keys_at("media", 60, 10, now);
+        assert_eq!(report.keys.len(), 2);
+        assert_eq!(report.keys[0].key, "hot");
+        assert_eq!(report.keys[0].requests, 4);
+    }
+
+    #[test]
+    fn test_parse_window() {
+        assert_eq!(parse_window("1h"), Ok(3600));
+        assert_eq!(parse_window("15m"), Ok(900));
+        assert_eq!(parse_window("1d"), Ok(86400));
+        assert_eq!(parse_window("90s"), Ok(90));
+        assert_eq!(parse_window("120"), Ok(120));
+        assert!(parse_window("0h").is_err());
+        assert!(parse_window("1w").is_err());
+        assert!(parse_window("h").is_err());
+    }
+}
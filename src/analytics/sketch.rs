@@ -0,0 +1,85 @@
+//! Count-min sketch for approximate per-key counts.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Fixed-size frequency sketch; estimates never undercount
+#[derive(Debug, Clone)]
+pub struct CountMinSketch {
+    width: usize,
+    depth: usize,
+    counters: Vec<u32>,
+}
+
+impl CountMinSketch {
+    pub fn new(width: usize, depth: usize) -> Self {
+        let width = width.max(1);
+        let depth = depth.max(1);
+        Self {
+            width,
+            depth,
+            counters: vec![0; width * depth],
+        }
+    }
+
+    /// Counter index for `key` in each row (double hashing)
+    fn indexes<'a>(&'a self, key: &str) -> impl Iterator<Item = usize> + 'a {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        let h1 = hasher.finish();
+        0xA5u8.hash(&mut hasher);
+        let h2 = hasher.finish() | 1;
+        (0..self.depth).map(move |row| {
+            let column = h1.wrapping_add((row as u64).wrapping_mul(h2)) % self.width as u64;
+            row * self.width + column as usize
+        })
+    }
+
+    /// Add one occurrence of `key` and return its new estimate
+    pub fn increment(&mut self, key: &str) -> u32 {
+        let indexes: Vec<usize> = self.indexes(key).collect();
+        let mut estimate = u32::MAX;
+        for idx in indexes {
+            let counter = &mut self.counters[idx];
+            *counter = counter.saturating_add(1);
+            estimate = estimate.min(*counter);
+        }
+        estimate
+    }
+
+    /// Estimated occurrences of `key`
+    pub fn estimate(&self, key: &str) -> u32 {
+        self.indexes(key)
+            .map(|idx| self.counters[idx])
+            .min()
+            .unwrap_or(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sketch_counts_and_never_undercounts() {
+        let mut sketch = CountMinSketch::new(64, 4);
+        for _ in 0..10 {
+            sketch.increment("hot");
+        }
+        for i in 0..200 {
+            sketch.increment(&format!("cold-{}", i));
+        }
+
+        assert!(sketch.estimate("hot") >= 10);
+        assert!(sketch.estimate("cold-7") >= 1);
+        assert_eq!(CountMinSketch::new(64, 4).estimate("hot"), 0);
+    }
+
+    #[test]
+    fn test_sketch_increment_returns_estimate() {
+        let mut sketch = CountMinSketch::new(1024, 4);
+        assert_eq!(sketch.increment("a"), 1);
+        assert_eq!(sketch.increment("a"), 2);
+        assert_eq!(sketch.estimate("a"), 2);
+    }
+}
@@ -68,6 +68,10 @@ pub struct AuditLogEntry {
     /// Referer header from request
     #[serde(skip_serializing_if = "Option::is_none")]
     pub referer: Option<String>,
+
+    /// Subject of the verified client certificate (mutual TLS listener)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_cert_subject: Option<String>,
 }
 
 impl AuditLogEntry {
@@ -94,6 +98,7 @@ impl AuditLogEntry {
             cache_status: CacheStatus::Miss,
             user_agent: None,
             referer: None,
+            client_cert_subject: None,
         }
     }
 
@@ -215,6 +220,9 @@ pub struct RequestContext {
 
     /// Referer header
     pub referer: Option<String>,
+
+    /// Subject of the verified client certificate
+    pub client_cert_subject: Option<String>,
 }
 
 impl RequestContext {
@@ -234,6 +242,7 @@ impl RequestContext {
             cache_status: None,
             user_agent: None,
             referer: None,
+            client_cert_subject: None,
         }
     }
 
@@ -256,6 +265,7 @@ impl RequestContext {
             cache_status: None,
             user_agent: None,
             referer: None,
+            client_cert_subject: None,
         }
     }
 
@@ -275,6 +285,7 @@ impl RequestContext {
             cache_status: None,
             user_agent: None,
             referer: None,
+            client_cert_subject: None,
         }
     }
 
@@ -351,6 +362,7 @@ impl RequestContext {
             cache_status: self.cache_status.clone().unwrap_or(CacheStatus::Miss),
             user_agent: self.user_agent.clone(),
             referer: self.referer.clone(),
+            client_cert_subject: self.client_cert_subject.clone(),
         }
     }
 }
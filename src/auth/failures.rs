@@ -0,0 +1,453 @@
+//! Auth failure breakdown.
+//!
+//! A bare "auth failed" counter cannot tell a key rollout that left some
+//! clients with bad signatures apart from clocks drifting or an IdP minting
+//! tokens for the wrong audience. [`classify`] turns an [`AuthError`] into an
+//! [`AuthFailureReason`], and [`issuer_label`] names the issuer of the rejected
+//! token without letting arbitrary `iss` values explode label cardinality.
+//!
+//! [`AuthFailureTracker`] keeps per-client counts over a rolling window for
+//! `GET /admin/auth/failures`, so the worst offenders are visible without
+//! digging through logs.
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use parking_lot::Mutex;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap, VecDeque};
+
+use super::{try_extract_token, verify_issuer_and_audience, AuthError, Claims};
+use crate::config::JwtConfig;
+use crate::constants::{
+    AUTH_FAILURE_MAX_CLIENTS_PER_SLOT, AUTH_FAILURE_SLOT_SECS, AUTH_FAILURE_WINDOW_SECS,
+};
+use crate::util::unix_now;
+
+/// Issuer label for tokens without an `iss` claim (or no token at all)
+pub const ISSUER_NONE: &str = "none";
+
+/// Issuer label for tokens from an issuer that is not configured
+pub const ISSUER_OTHER: &str = "other";
+
+/// Why a JWT was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AuthFailureReason {
+    /// No token in any configured source
+    Missing,
+    /// Not a decodable JWT
+    Malformed,
+    /// `exp` is in the past
+    Expired,
+    /// `nbf` is in the future
+    NotYetValid,
+    /// Signature does not verify against the configured keys
+    BadSignature,
+    /// `iss` does not match the configured issuer
+    WrongIssuer,
+    /// `aud` does not match any configured audience
+    WrongAudience,
+    /// A configured claim rule did not match
+    ClaimsMismatch,
+    /// `jti` or `sub` is on the revocation list
+    Revoked,
+    /// `alg` is not in the accepted list
+    AlgorithmRejected,
+    /// No usable key (missing kid, JWKS fetch failure, unreadable key file)
+    KeyUnavailable,
+}
+
+impl AuthFailureReason {
+    /// Label used in metrics and the admin view
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Missing => "missing",
+            Self::Malformed => "malformed",
+            Self::Expired => "expired",
+            Self::NotYetValid => "not_yet_valid",
+            Self::BadSignature => "bad_signature",
+            Self::WrongIssuer => "wrong_issuer",
+            Self::WrongAudience => "wrong_audience",
+            Self::ClaimsMismatch => "claims_mismatch",
+            Self::Revoked => "revoked",
+            Self::AlgorithmRejected => "algorithm_rejected",
+            Self::KeyUnavailable => "key_unavailable",
+        }
+    }
+}
+
+/// Decode a token's payload without checking the signature
+///
+/// Only used to label failures; never trust these claims for access decisions.
+pub fn unverified_claims(token: &str) -> Option<Claims> {
+    let payload = token.split('.').nth(1)?;
+    let bytes = URL_SAFE_NO_PAD.decode(payload.trim_end_matches('=')).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+/// Classify an authentication error
+///
+/// `claims` are the token's unverified claims, used to tell an issuer
+/// mismatch from an audience mismatch or a failed claim rule.
+pub fn classify(
+    error: &AuthError,
+    claims: Option<&Claims>,
+    jwt_config: &JwtConfig,
+) -> AuthFailureReason {
+    match error {
+        AuthError::MissingToken => AuthFailureReason::Missing,
+        AuthError::ClaimsVerificationFailed | AuthError::AdminAccessDenied => {
+            let Some(claims) = claims else {
+                return AuthFailureReason::ClaimsMismatch;
+            };
+            if verify_issuer_and_audience(claims, jwt_config) {
+                AuthFailureReason::ClaimsMismatch
+            } else if jwt_config
+                .issuer
+                .as_ref()
+                .is_some_and(|issuer| claims.iss.as_ref() != Some(issuer))
+            {
+                AuthFailureReason::WrongIssuer
+            } else {
+                AuthFailureReason::WrongAudience
+            }
+        }
+        AuthError::InvalidToken(message) => classify_message(message),
+    }
+}
+
+fn classify_message(message: &str) -> AuthFailureReason {
+    // jsonwebtoken errors surface as their kind name inside our messages
+    if message.contains("ExpiredSignature") {
+        AuthFailureReason::Expired
+    } else if message.contains("ImmatureSignature") {
+        AuthFailureReason::NotYetValid
+    } else if message.contains("InvalidSignature") {
+        AuthFailureReason::BadSignature
+    } else if message.contains("revoked") {
+        AuthFailureReason::Revoked
+    } else if message.contains("is not allowed") || message.contains("InvalidAlgorithm") {
+        AuthFailureReason::AlgorithmRejected
+    } else if [
+        "Failed to load",
+        "Failed to fetch",
+        "not found in JWKS",
+        "unusable",
+        "not configured",
+        "No validation keys",
+        "No key configured",
+    ]
+    .iter()
+    .any(|phrase| message.contains(phrase))
+    {
+        AuthFailureReason::KeyUnavailable
+    } else {
+        AuthFailureReason::Malformed
+    }
+}
+
+/// Issuer label for a rejected token
+///
+/// The configured issuer is reported as-is; anything else collapses to
+/// `other` (or `none` without an `iss`), so forged tokens cannot mint series.
+pub fn issuer_label(claims: Option<&Claims>, jwt_config: &JwtConfig) -> String {
+    match claims.and_then(|c| c.iss.as_ref()) {
+        None => ISSUER_NONE.to_string(),
+        Some(iss) if jwt_config.issuer.as_ref() == Some(iss) => iss.clone(),
+        Some(_) => ISSUER_OTHER.to_string(),
+    }
+}
+
+/// A classified authentication failure
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AuthFailure {
+    pub reason: AuthFailureReason,
+    pub issuer: String,
+}
+
+/// Classify the failure of a request, re-reading its token for the labels
+pub fn describe(
+    error: &AuthError,
+    headers: &HashMap<String, String>,
+    query_params: &HashMap<String, String>,
+    jwt_config: &JwtConfig,
+) -> AuthFailure {
+    let claims = try_extract_token(headers, query_params, &jwt_config.token_sources)
+        .and_then(|token| unverified_claims(&token));
+    AuthFailure {
+        reason: classify(error, claims.as_ref(), jwt_config),
+        issuer: issuer_label(claims.as_ref(), jwt_config),
+    }
+}
+
+/// Failures of one client within a slot
+#[derive(Default)]
+struct ClientFailures {
+    total: u64,
+    reasons: HashMap<AuthFailureReason, u64>,
+    buckets: HashMap<String, u64>,
+    last_seen: u64,
+}
+
+struct Slot {
+    /// Slot number (unix seconds / slot_secs)
+    index: u64,
+    total: u64,
+    clients: HashMap<String, ClientFailures>,
+}
+
+/// A client with its failures over the window
+#[derive(Debug, Clone, Serialize)]
+pub struct Offender {
+    pub client_ip: String,
+    pub failures: u64,
+    pub reasons: BTreeMap<&'static str, u64>,
+    pub buckets: BTreeMap<String, u64>,
+    /// Unix seconds of the latest failure
+    pub last_seen: u64,
+}
+
+/// Top offenders over a window
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthFailureReport {
+    pub window_secs: u64,
+    pub total_failures: u64,
+    pub offenders: Vec<Offender>,
+}
+
+/// Per-client auth failure counts over a rolling window
+pub struct AuthFailureTracker {
+    slot_secs: u64,
+    window_secs: u64,
+    max_clients_per_slot: usize,
+    slots: Mutex<VecDeque<Slot>>,
+}
+
+impl Default for AuthFailureTracker {
+    fn default() -> Self {
+        Self::new(
+            AUTH_FAILURE_SLOT_SECS,
+            AUTH_FAILURE_WINDOW_SECS,
+            AUTH_FAILURE_MAX_CLIENTS_PER_SLOT,
+        )
+    }
+}
+
+impl AuthFailureTracker {
+    pub fn new(slot_secs: u64, window_secs: u64, max_clients_per_slot: usize) -> Self {
+        Self {
+            slot_secs: slot_secs.max(1),
+            window_secs: window_secs.max(slot_secs),
+            max_clients_per_slot,
+            slots: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    /// Longest window the tracker can answer for
+    pub fn window_secs(&self) -> u64 {
+        self.window_secs
+    }
+
+    /// Record a failure from `client_ip` on `bucket`
+    pub fn record(&self, client_ip: &str, bucket: &str, reason: AuthFailureReason) {
+        self.record_at(unix_now(), client_ip, bucket, reason);
+    }
+
+    fn record_at(&self, now: u64, client_ip: &str, bucket: &str, reason: AuthFailureReason) {
+        let index = now / self.slot_secs;
+        let retained = self.window_secs / self.slot_secs;
+        let mut slots = self.slots.lock();
+
+        if slots.back().map(|s| s.index) != Some(index) {
+            slots.push_back(Slot {
+                index,
+                total: 0,
+                clients: HashMap::new(),
+            });
+        }
+        while slots.front().is_some_and(|s| s.index + retained <= index) {
+            slots.pop_front();
+        }
+
+        let Some(slot) = slots.back_mut() else {
+            return;
+        };
+        slot.total += 1;
+        if !slot.clients.contains_key(client_ip) && slot.clients.len() >= self.max_clients_per_slot
+        {
+            return;
+        }
+        let client = slot.clients.entry(client_ip.to_string()).or_default();
+        client.total += 1;
+        *client.reasons.entry(reason).or_insert(0) += 1;
+        *client.buckets.entry(bucket.to_string()).or_insert(0) += 1;
+        client.last_seen = now;
+    }
+
+    /// Clients with the most failures over the last `window_secs`
+    pub fn top(&self, window_secs: u64, limit: usize) -> AuthFailureReport {
+        self.top_at(unix_now(), window_secs, limit)
+    }
+
+    fn top_at(&self, now: u64, window_secs: u64, limit: usize) -> AuthFailureReport {
+        let window_secs = window_secs.min(self.window_secs);
+        let covered = window_secs.div_ceil(self.slot_secs).max(1);
+        let index = now / self.slot_secs;
+
+        let mut total_failures = 0;
+        let mut merged: HashMap<String, Offender> = HashMap::new();
+        let slots = self.slots.lock();
+        for slot in slots.iter().filter(|s| s.index + covered > index) {
+            total_failures += slot.total;
+            for (client_ip, failures) in &slot.clients {
+                let offender = merged.entry(client_ip.clone()).or_insert_with(|| Offender {
+                    client_ip: client_ip.clone(),
+                    failures: 0,
+                    reasons: BTreeMap::new(),
+                    buckets: BTreeMap::new(),
+                    last_seen: 0,
+                });
+                offender.failures += failures.total;
+                for (reason, count) in &failures.reasons {
+                    *offender.reasons.entry(reason.as_str()).or_insert(0) += count;
+                }
+                for (bucket, count) in &failures.buckets {
+                    *offender.buckets.entry(bucket.clone()).or_insert(0) += count;
+                }
+                offender.last_seen = offender.last_seen.max(failures.last_seen);
+            }
+        }
+        drop(slots);
+
+        let mut offenders: Vec<Offender> = merged.into_values().collect();
+        offenders.sort_by(|a, b| {
+            b.failures
+                .cmp(&a.failures)
+                .then_with(|| a.client_ip.cmp(&b.client_ip))
+        });
+        offenders.truncate(limit);
+
+        AuthFailureReport {
+            window_secs,
+            total_failures,
+            offenders,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jwt_config() -> JwtConfig {
+        serde_yaml::from_str(
+            r#"
+enabled: true
+secret: "secret"
+algorithm: "HS256"
+issuer: "https://idp.example.com"
+audience: ["storefront"]
+token_sources:
+  - type: bearer
+"#,
+        )
+        .unwrap()
+    }
+
+    fn token_with(payload: serde_json::Value) -> String {
+        format!(
+            "eyJhbGciOiJIUzI1NiJ9.{}.sig",
+            URL_SAFE_NO_PAD.encode(payload.to_string())
+        )
+    }
+
+    #[test]
+    fn test_classify_auth_failures() {
+        let config = jwt_config();
+        let invalid = |m: &str| AuthError::InvalidToken(m.to_string());
+
+        assert_eq!(
+            classify(&AuthError::MissingToken, None, &config),
+            AuthFailureReason::Missing
+        );
+        assert_eq!(
+            classify(&invalid("ExpiredSignature"), None, &config),
+            AuthFailureReason::Expired
+        );
+        assert_eq!(
+            classify(
+                &invalid("Token validation failed with key 'k1': InvalidSignature"),
+                None,
+                &config
+            ),
+            AuthFailureReason::BadSignature
+        );
+        assert_eq!(
+            classify(&invalid("Failed to fetch JWKS: timeout"), None, &config),
+            AuthFailureReason::KeyUnavailable
+        );
+        assert_eq!(
+            classify(&invalid("InvalidToken"), None, &config),
+            AuthFailureReason::Malformed
+        );
+
+        let wrong_aud = unverified_claims(&token_with(serde_json::json!({
+            "iss": "https://idp.example.com",
+            "aud": "billing"
+        })));
+        assert_eq!(
+            classify(
+                &AuthError::ClaimsVerificationFailed,
+                wrong_aud.as_ref(),
+                &config
+            ),
+            AuthFailureReason::WrongAudience
+        );
+        assert_eq!(
+            issuer_label(wrong_aud.as_ref(), &config),
+            "https://idp.example.com"
+        );
+
+        let wrong_iss = unverified_claims(&token_with(serde_json::json!({
+            "iss": "https://attacker.example",
+            "aud": "storefront"
+        })));
+        assert_eq!(
+            classify(
+                &AuthError::ClaimsVerificationFailed,
+                wrong_iss.as_ref(),
+                &config
+            ),
+            AuthFailureReason::WrongIssuer
+        );
+        assert_eq!(issuer_label(wrong_iss.as_ref(), &config), ISSUER_OTHER);
+        assert_eq!(issuer_label(None, &config), ISSUER_NONE);
+    }
+
+    #[test]
+    fn test_tracker_ranks_offenders_over_rolling_window() {
+        let tracker = AuthFailureTracker::new(60, 300, 100);
+        let start = 1_000_020;
+
+        tracker.record_at(start, "10.0.0.1", "products", AuthFailureReason::Expired);
+        for _ in 0..3 {
+            tracker.record_at(
+                start + 60,
+                "10.0.0.2",
+                "products",
+                AuthFailureReason::BadSignature,
+            );
+        }
+        tracker.record_at(start + 60, "10.0.0.1", "media", AuthFailureReason::Expired);
+
+        let report = tracker.top_at(start + 60, 300, 10);
+        assert_eq!(report.total_failures, 5);
+        assert_eq!(report.offenders[0].client_ip, "10.0.0.2");
+        assert_eq!(report.offenders[0].reasons.get("bad_signature"), Some(&3));
+        assert_eq!(report.offenders[1].failures, 2);
+        assert_eq!(report.offenders[1].buckets.len(), 2);
+
+        // Once the first slot leaves the window only the later failures remain
+        let later = tracker.top_at(start + 300, 300, 1);
+        assert_eq!(later.total_failures, 4);
+        assert_eq!(later.offenders.len(), 1);
+    }
+}
@@ -62,7 +62,7 @@ impl Jwks {
 /// JSON Web Key (JWK) - a single key in a JWKS
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JwkKey {
-    /// Key Type (e.g., "RSA", "EC")
+    /// Key Type (e.g., "RSA", "EC", "OKP")
     pub kty: String,
 
     /// Key ID - unique identifier for the key
@@ -87,11 +87,11 @@ pub struct JwkKey {
     pub e: Option<String>,
 
     // EC key parameters (RFC 7518)
-    /// EC curve name (e.g., "P-256", "P-384")
+    /// EC or OKP curve name (e.g., "P-256", "P-384", "Ed25519")
     #[serde(default)]
     pub crv: Option<String>,
 
-    /// EC x coordinate (base64url-encoded)
+    /// EC x coordinate, or OKP public key (base64url-encoded)
     #[serde(default)]
     pub x: Option<String>,
 
@@ -111,6 +111,11 @@ impl JwkKey {
         self.kty == "EC"
     }
 
+    /// Check if this is an OKP (Octet Key Pair, e.g. Ed25519) key
+    pub fn is_okp(&self) -> bool {
+        self.kty == "OKP"
+    }
+
     /// Get the algorithm, defaulting based on key type if not specified
     pub fn algorithm(&self) -> Option<&str> {
         self.alg.as_deref().or(self.default_algorithm())
@@ -126,6 +131,10 @@ impl JwkKey {
                 Some("P-521") => Some("ES512"),
                 _ => None,
             },
+            "OKP" => match self.crv.as_deref() {
+                Some("Ed25519") => Some("EdDSA"),
+                _ => None,
+            },
             _ => None,
         }
     }
@@ -159,6 +168,23 @@ impl JwkKey {
                 DecodingKey::from_ec_components(x, y)
                     .map_err(|e| JwkError::KeyCreationFailed(e.to_string()))
             }
+            "OKP" => {
+                match self.crv.as_deref() {
+                    Some("Ed25519") => {}
+                    other => {
+                        return Err(JwkError::UnsupportedCurve(
+                            other.unwrap_or("none").to_string(),
+                        ))
+                    }
+                }
+                let x = self
+                    .x
+                    .as_ref()
+                    .ok_or_else(|| JwkError::MissingParameter("x (public key)".to_string()))?;
+
+                DecodingKey::from_ed_components(x)
+                    .map_err(|e| JwkError::KeyCreationFailed(e.to_string()))
+            }
             other => Err(JwkError::UnsupportedKeyType(other.to_string())),
         }
     }
@@ -175,6 +201,7 @@ impl JwkKey {
             "RS512" => Ok(Algorithm::RS512),
             "ES256" => Ok(Algorithm::ES256),
             "ES384" => Ok(Algorithm::ES384),
+            "EdDSA" => Ok(Algorithm::EdDSA),
             _ => Err(JwkError::UnsupportedKeyType(format!(
                 "Unsupported algorithm: {}",
                 alg_str
@@ -425,4 +452,32 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), Algorithm::ES384);
     }
+
+    #[test]
+    fn test_okp_ed25519_key() {
+        // Public half of tests/fixtures/ed25519_private.pem
+        let key = JwkKey {
+            kty: "OKP".to_string(),
+            kid: Some("ed".to_string()),
+            key_use: None,
+            alg: None, // should default to EdDSA for Ed25519
+            n: None,
+            e: None,
+            crv: Some("Ed25519".to_string()),
+            x: Some("sc8QHWcuPoLZijK-HmWaKMwOI9iA4KQ7RkhS9Hh236U".to_string()),
+            y: None,
+        };
+        assert!(key.is_okp());
+        assert_eq!(key.jwt_algorithm().unwrap(), Algorithm::EdDSA);
+        assert!(key.to_decoding_key().is_ok());
+
+        let ed448 = JwkKey {
+            crv: Some("Ed448".to_string()),
+            ..key
+        };
+        assert!(matches!(
+            ed448.to_decoding_key(),
+            Err(JwkError::UnsupportedCurve(_))
+        ));
+    }
 }
@@ -2,13 +2,26 @@
 //!
 //! This module provides a client for fetching JWKS from remote endpoints
 //! and caching them with configurable refresh intervals.
+//!
+//! A token signed with a `kid` missing from the cached set triggers an early
+//! refetch, so keys rotated in at the identity provider are picked up without
+//! waiting for the next refresh. These refetches are rate limited
+//! (`JWKS_UNKNOWN_KID_REFRESH_COOLDOWN_SECS`) so tokens with made-up key IDs
+//! cannot hammer the provider.
+//!
+//! Token cache entries for JWKS buckets are keyed by the JWKS URL only, so a
+//! fetch that returns a different key set clears the token cache; tokens
+//! signed by a removed key are verified again on their next request.
 
 use super::jwks::{JwkError, JwkKey, Jwks};
+use super::TokenCache;
 use jsonwebtoken::DecodingKey;
-use parking_lot::RwLock;
+use parking_lot::{Mutex, RwLock};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use crate::constants::JWKS_UNKNOWN_KID_REFRESH_COOLDOWN_SECS;
+
 /// Error type for JWKS client operations
 #[derive(Debug)]
 pub enum JwksClientError {
@@ -85,6 +98,8 @@ impl Default for JwksClientConfig {
 pub struct JwksClient {
     config: JwksClientConfig,
     cached: RwLock<Option<CachedJwks>>,
+    /// Last refetch triggered by an unknown `kid`
+    unknown_kid_refresh_at: Mutex<Option<Instant>>,
 }
 
 impl JwksClient {
@@ -93,6 +108,7 @@ impl JwksClient {
         Self {
             config,
             cached: RwLock::new(None),
+            unknown_kid_refresh_at: Mutex::new(None),
         }
     }
 
@@ -150,12 +166,21 @@ impl JwksClient {
         let jwks = fetch_jwks(&self.config.url, self.config.timeout_secs).await?;
 
         // Update the cache
-        {
+        let changed = {
             let mut cached = self.cached.write();
+            let changed = cached
+                .as_ref()
+                .is_some_and(|previous| key_set_changed(&previous.jwks, &jwks));
             *cached = Some(CachedJwks {
                 jwks: jwks.clone(),
                 fetched_at: Instant::now(),
             });
+            changed
+        };
+        if changed {
+            tracing::info!("JWKS key set changed, clearing token cache");
+            // Tokens verified with a removed key must be checked again
+            TokenCache::global().clear();
         }
 
         tracing::info!(
@@ -181,6 +206,36 @@ impl JwksClient {
     pub async fn refresh(&self) -> Result<Jwks, JwksClientError> {
         self.fetch_and_cache().await
     }
+
+    /// Key for a `kid`, refetching the set once if the key is unknown
+    pub async fn key_for(&self, kid: &str) -> Result<JwkKey, JwksClientError> {
+        self.get_jwks().await?;
+        if let Some(key) = self.find_key(kid) {
+            return Ok(key);
+        }
+        if self.claim_unknown_kid_refresh() {
+            tracing::info!(kid = %kid, "Unknown JWKS key ID, refetching key set");
+            self.fetch_and_cache().await?;
+        }
+        self.find_key(kid)
+            .ok_or_else(|| JwksClientError::KeyNotFound(kid.to_string()))
+    }
+
+    /// Reserve an unknown-`kid` refetch unless one ran within the cooldown
+    fn claim_unknown_kid_refresh(&self) -> bool {
+        let mut last = self.unknown_kid_refresh_at.lock();
+        let cooldown = Duration::from_secs(JWKS_UNKNOWN_KID_REFRESH_COOLDOWN_SECS);
+        if last.is_some_and(|at| at.elapsed() < cooldown) {
+            return false;
+        }
+        *last = Some(Instant::now());
+        true
+    }
+}
+
+/// Whether two fetched key sets differ in any key
+fn key_set_changed(previous: &Jwks, next: &Jwks) -> bool {
+    serde_json::to_value(previous).ok() != serde_json::to_value(next).ok()
 }
 
 /// Thread-safe shared JWKS client
@@ -273,6 +328,25 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_unknown_kid_refresh_is_rate_limited() {
+        let client = JwksClient::from_url("http://example.com/.well-known/jwks.json");
+        assert!(client.claim_unknown_kid_refresh());
+        assert!(!client.claim_unknown_kid_refresh());
+    }
+
+    #[test]
+    fn test_key_set_change_detection() {
+        let jwks = |kid: &str| -> Jwks {
+            serde_json::from_value(serde_json::json!({
+                "keys": [{"kty": "RSA", "kid": kid, "n": "AQAB", "e": "AQAB"}]
+            }))
+            .unwrap()
+        };
+        assert!(!key_set_changed(&jwks("a"), &jwks("a")));
+        assert!(key_set_changed(&jwks("a"), &jwks("b")));
+    }
+
     #[tokio::test]
     async fn test_jwks_client_not_configured() {
         let client = JwksClient::new(JwksClientConfig::default());
@@ -0,0 +1,202 @@
+//! Key material that can rotate without a full config reload.
+//!
+//! HMAC secrets (`jwt.secret_file`) and PEM public keys are read from disk on
+//! first use and cached per path. A background task re-reads every cached path
+//! on `jwt.key_reload_interval_secs`, so a rotated key is picked up without
+//! rebuilding the router or touching the cache. JWKS clients are registered per
+//! URL and refreshed on their own `jwks_refresh_interval_secs` timer.
+//!
+//! A file that fails to re-read keeps its previous contents, so a half-written
+//! rotation never locks everyone out.
+
+use super::jwks_client::{JwksClient, JwksClientConfig, SharedJwksClient};
+use jsonwebtoken::DecodingKey;
+use parking_lot::RwLock;
+use std::collections::HashMap;
+use std::fs;
+use std::sync::{Arc, OnceLock};
+use std::time::Duration;
+
+use super::{KeyLoadError, TokenCache};
+use crate::config::JwtConfig;
+use crate::constants::{DEFAULT_JWKS_REFRESH_INTERVAL_SECS, DEFAULT_JWT_KEY_RELOAD_INTERVAL_SECS};
+
+/// Cached file contents and registered JWKS clients
+#[derive(Default)]
+pub struct KeyMaterial {
+    files: RwLock<HashMap<String, Arc<Vec<u8>>>>,
+    jwks: RwLock<HashMap<String, SharedJwksClient>>,
+}
+
+static KEY_MATERIAL: OnceLock<KeyMaterial> = OnceLock::new();
+
+impl KeyMaterial {
+    /// Process-wide key store
+    pub fn global() -> &'static KeyMaterial {
+        KEY_MATERIAL.get_or_init(KeyMaterial::default)
+    }
+
+    /// Contents of a key file, read on first use
+    pub fn file(&self, path: &str) -> Result<Arc<Vec<u8>>, KeyLoadError> {
+        if let Some(bytes) = self.files.read().get(path) {
+            return Ok(Arc::clone(bytes));
+        }
+        let bytes = Arc::new(read_key_file(path)?);
+        self.files
+            .write()
+            .insert(path.to_string(), Arc::clone(&bytes));
+        Ok(bytes)
+    }
+
+    /// HMAC secret for the config: `secret_file` if set, otherwise `secret`
+    pub fn hmac_secret(&self, jwt: &JwtConfig) -> Result<String, KeyLoadError> {
+        match &jwt.secret_file {
+            Some(path) => {
+                let bytes = self.file(path)?;
+                let secret = String::from_utf8_lossy(&bytes).trim().to_string();
+                if secret.is_empty() {
+                    return Err(KeyLoadError::InvalidKeyFormat(format!(
+                        "Secret file '{}' is empty",
+                        path
+                    )));
+                }
+                Ok(secret)
+            }
+            None => Ok(jwt.secret.clone()),
+        }
+    }
+
+    /// RSA public key from a cached PEM file
+    pub fn rsa_public_key(&self, path: &str) -> Result<DecodingKey, KeyLoadError> {
+        DecodingKey::from_rsa_pem(&self.file(path)?)
+            .map_err(|e| KeyLoadError::InvalidKeyFormat(format!("Invalid RSA PEM format: {}", e)))
+    }
+
+    /// ECDSA public key from a cached PEM file
+    pub fn ecdsa_public_key(&self, path: &str) -> Result<DecodingKey, KeyLoadError> {
+        DecodingKey::from_ec_pem(&self.file(path)?)
+            .map_err(|e| KeyLoadError::InvalidKeyFormat(format!("Invalid ECDSA PEM format: {}", e)))
+    }
+
+    /// Ed25519 (EdDSA) public key from a cached PEM file
+    pub fn eddsa_public_key(&self, path: &str) -> Result<DecodingKey, KeyLoadError> {
+        DecodingKey::from_ed_pem(&self.file(path)?)
+            .map_err(|e| KeyLoadError::InvalidKeyFormat(format!("Invalid EdDSA PEM format: {}", e)))
+    }
+
+    /// Re-read every cached file, returning the paths whose contents changed
+    pub fn reload_files(&self) -> Vec<String> {
+        let paths: Vec<String> = self.files.read().keys().cloned().collect();
+        let mut changed = Vec::new();
+        for path in paths {
+            match read_key_file(&path) {
+                Ok(bytes) => {
+                    let mut files = self.files.write();
+                    if files.get(&path).map(|old| **old != bytes).unwrap_or(true) {
+                        files.insert(path.clone(), Arc::new(bytes));
+                        changed.push(path);
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(path = %path, error = %e, "Failed to re-read key file, keeping previous key");
+                }
+            }
+        }
+        changed
+    }
+
+    /// JWKS client for a URL, created on first use
+    pub fn jwks_client(&self, url: &str, refresh_interval_secs: u64) -> SharedJwksClient {
+        if let Some(client) = self.jwks.read().get(url) {
+            return Arc::clone(client);
+        }
+        let mut jwks = self.jwks.write();
+        let client = jwks.entry(url.to_string()).or_insert_with(|| {
+            Arc::new(JwksClient::new(JwksClientConfig {
+                url: url.to_string(),
+                refresh_interval_secs,
+                ..Default::default()
+            }))
+        });
+        Arc::clone(client)
+    }
+
+    /// Start the file reload timer and, if configured, the JWKS refresh timer
+    ///
+    /// Must be called from within a Tokio runtime.
+    pub fn start_reload_tasks(&'static self, jwt: &JwtConfig) {
+        let interval = jwt
+            .key_reload_interval_secs
+            .unwrap_or(DEFAULT_JWT_KEY_RELOAD_INTERVAL_SECS);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(Duration::from_secs(interval));
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let changed = self.reload_files();
+                for path in &changed {
+                    tracing::info!(path = %path, "JWT key file changed, new key in effect");
+                }
+                if !changed.is_empty() {
+                    // Tokens verified with the old key must be checked again
+                    TokenCache::global().clear();
+                }
+            }
+        });
+
+        if let Some(url) = &jwt.jwks_url {
+            let refresh = jwt
+                .jwks_refresh_interval_secs
+                .unwrap_or(DEFAULT_JWKS_REFRESH_INTERVAL_SECS);
+            let client = self.jwks_client(url, refresh);
+            tokio::spawn(async move {
+                let mut ticker = tokio::time::interval(Duration::from_secs(refresh));
+                loop {
+                    ticker.tick().await;
+                    if let Err(e) = client.refresh().await {
+                        tracing::warn!(error = %e, "JWKS refresh failed, keeping cached keys");
+                    }
+                }
+            });
+        }
+    }
+}
+
+fn read_key_file(path: &str) -> Result<Vec<u8>, KeyLoadError> {
+    fs::read(path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => KeyLoadError::FileNotFound(path.to_string()),
+        _ => KeyLoadError::IoError(e),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_secret_file_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("secret");
+        fs::write(&path, "first\n").unwrap();
+        let path = path.to_str().unwrap().to_string();
+
+        let store = KeyMaterial::default();
+        let jwt = JwtConfig {
+            secret_file: Some(path.clone()),
+            ..serde_yaml::from_str("enabled: true\nalgorithm: HS256").unwrap()
+        };
+        assert_eq!(store.hmac_secret(&jwt).unwrap(), "first");
+
+        // Rotation is only visible after a reload
+        fs::write(&path, "second").unwrap();
+        assert_eq!(store.hmac_secret(&jwt).unwrap(), "first");
+        assert_eq!(store.reload_files(), vec![path.clone()]);
+        assert_eq!(store.hmac_secret(&jwt).unwrap(), "second");
+        assert!(store.reload_files().is_empty());
+
+        // A missing file keeps the previous secret
+        fs::remove_file(&path).unwrap();
+        assert!(store.reload_files().is_empty());
+        assert_eq!(store.hmac_secret(&jwt).unwrap(), "second");
+    }
+}
@@ -6,9 +6,14 @@
 //! # Features
 //!
 //! - **Multiple token sources**: Extract tokens from Authorization header, custom headers, or query parameters
-//! - **Algorithm support**: HS256, HS384, HS512 (HMAC), RS256/384/512 (RSA), ES256/384 (ECDSA)
+//! - **Algorithm support**: HS256, HS384, HS512 (HMAC), RS256/384/512 (RSA), ES256/384 (ECDSA), EdDSA (Ed25519)
+//! - **Algorithm allowlist**: Tokens whose `alg` header is not accepted are rejected up front
 //! - **Key management**: Static secrets, PEM files, or dynamic JWKS endpoints
 //! - **Custom claim rules**: Verify claims with the `equals` operator
+//! - **Revocation**: Tokens whose `jti` or `sub` is on the revocation list are rejected
+//! - **SigV4**: Buckets with `sigv4_auth` verify S3 SDK signatures instead (see [`sigv4`])
+//! - **Failure breakdown**: Rejections are classified by reason and issuer (see [`failures`])
+//! - **Claim-to-path rules**: Built-in prefix isolation from claims (see [`path_rules`])
 //! - **Admin claim support**: Separate claims for admin access verification
 //!
 //! # Token Sources
@@ -17,6 +22,7 @@
 //! - `bearer`: Standard `Authorization: Bearer <token>` header
 //! - `header`: Custom header with optional prefix (e.g., `X-Auth-Token`)
 //! - `query`: Query parameter (e.g., `?token=<jwt>`)
+//! - `cookie`: Named cookie (e.g., `Cookie: session=<jwt>`), for browser apps
 //!
 //! # Example Configuration
 //!
@@ -43,12 +49,23 @@ use std::fs;
 use std::path::Path;
 
 use crate::config::{ClaimRule, JwtConfig};
+use crate::constants::DEFAULT_JWKS_REFRESH_INTERVAL_SECS;
+use crate::error::ErrorCode;
 
+pub mod failures;
 pub mod jwks;
 pub mod jwks_client;
+pub mod key_material;
+pub mod path_rules;
+pub mod revocation;
+pub mod sigv4;
+pub mod token_cache;
 
 // Re-export JWKS client types for convenience
 pub use jwks_client::{JwksClient, JwksClientConfig, JwksClientError, SharedJwksClient};
+pub use key_material::KeyMaterial;
+pub use revocation::RevocationList;
+pub use token_cache::TokenCache;
 
 /// Error type for key loading operations
 #[derive(Debug)]
@@ -95,6 +112,19 @@ pub fn load_rsa_public_key(path: &str) -> Result<DecodingKey, KeyLoadError> {
         .map_err(|e| KeyLoadError::InvalidKeyFormat(format!("Invalid RSA PEM format: {}", e)))
 }
 
+/// Load Ed25519 (EdDSA) public key from PEM file
+pub fn load_eddsa_public_key(path: &str) -> Result<DecodingKey, KeyLoadError> {
+    let key_path = Path::new(path);
+    if !key_path.exists() {
+        return Err(KeyLoadError::FileNotFound(path.to_string()));
+    }
+
+    let pem_content = fs::read(key_path)?;
+
+    DecodingKey::from_ed_pem(&pem_content)
+        .map_err(|e| KeyLoadError::InvalidKeyFormat(format!("Invalid EdDSA PEM format: {}", e)))
+}
+
 /// Load ECDSA public key from PEM file
 pub fn load_ecdsa_public_key(path: &str) -> Result<DecodingKey, KeyLoadError> {
     let key_path = Path::new(path);
@@ -159,6 +189,23 @@ pub fn extract_query_token(
     })
 }
 
+/// Token from the named cookie in the `Cookie` header
+pub fn extract_cookie_token(
+    headers: &HashMap<String, String>,
+    cookie_name: &str,
+) -> Option<String> {
+    get_header_case_insensitive(headers, "Cookie").and_then(|cookies| {
+        cookies.split(';').find_map(|pair| {
+            let (name, value) = pair.split_once('=')?;
+            if name.trim() != cookie_name {
+                return None;
+            }
+            let value = value.trim().trim_matches('"');
+            (!value.is_empty()).then(|| value.to_string())
+        })
+    })
+}
+
 pub fn try_extract_token(
     headers: &HashMap<String, String>,
     query_params: &HashMap<String, String>,
@@ -195,6 +242,10 @@ pub fn try_extract_token(
                     None
                 }
             }
+            "cookie" => source
+                .name
+                .as_ref()
+                .and_then(|cookie_name| extract_cookie_token(headers, cookie_name)),
             _ => {
                 tracing::warn!(
                     "Unknown token source type '{}' - this should have been caught by config validation",
@@ -231,6 +282,7 @@ pub fn parse_algorithm(algorithm: &str) -> Algorithm {
         "RS512" => Algorithm::RS512,
         "ES256" => Algorithm::ES256,
         "ES384" => Algorithm::ES384,
+        "EdDSA" => Algorithm::EdDSA,
         _ => Algorithm::HS256, // Default to HS256 for backward compatibility
     }
 }
@@ -246,6 +298,7 @@ pub fn validate_jwt(
     let mut validation = Validation::new(algo);
     validation.validate_exp = true; // Validate expiration if present
     validation.validate_nbf = true; // Validate not-before if present
+    validation.validate_aud = false; // Checked against the configured audience in verify_issuer_and_audience
     validation.required_spec_claims.clear(); // Don't require exp, nbf, etc. (but validate if present)
 
     let token_data = decode::<Claims>(
@@ -268,6 +321,7 @@ pub fn validate_jwt_with_key(
     let mut validation = Validation::new(algo);
     validation.validate_exp = true;
     validation.validate_nbf = true;
+    validation.validate_aud = false;
     validation.required_spec_claims.clear();
 
     let token_data = decode::<Claims>(token, key, &validation)?;
@@ -275,6 +329,29 @@ pub fn validate_jwt_with_key(
     Ok(token_data.claims)
 }
 
+/// Reject a token whose header `alg` is not in the accepted list
+///
+/// Runs before any key is tried, so a token cannot pick a weaker or
+/// unexpected algorithm (e.g. HS256 signed with a public key).
+pub fn check_token_algorithm(token: &str, accepted: &[String]) -> Result<(), AuthError> {
+    let header = jsonwebtoken::decode_header(token)
+        .map_err(|e| AuthError::InvalidToken(format!("Malformed JWT header: {}", e)))?;
+    let alg = format!("{:?}", header.alg);
+    if accepted.iter().any(|a| *a == alg) {
+        Ok(())
+    } else {
+        tracing::warn!(
+            alg = %alg,
+            accepted = ?accepted,
+            "JWT algorithm not in allowlist"
+        );
+        Err(AuthError::InvalidToken(format!(
+            "Token algorithm '{}' is not allowed",
+            alg
+        )))
+    }
+}
+
 /// Extract kid (Key ID) from JWT header without validating
 pub fn extract_kid_from_token(token: &str) -> Option<String> {
     use jsonwebtoken::decode_header;
@@ -354,6 +431,25 @@ pub fn validate_jwt_with_keys(
                     continue;
                 }
             }
+            "EdDSA" => {
+                if let Some(ref path) = key_config.path {
+                    match load_eddsa_public_key(path) {
+                        Ok(decoding_key) => {
+                            validate_jwt_with_key(token, &decoding_key, &key_config.algorithm)
+                        }
+                        Err(e) => {
+                            tracing::debug!("Failed to load EdDSA key '{}': {}", key_config.id, e);
+                            continue;
+                        }
+                    }
+                } else {
+                    tracing::debug!(
+                        "Key '{}' has no path configured for EdDSA algorithm",
+                        key_config.id
+                    );
+                    continue;
+                }
+            }
             _ => {
                 tracing::debug!(
                     "Unsupported algorithm '{}' for key '{}'",
@@ -471,6 +567,20 @@ fn validate_single_key(
                 ))
             }
         }
+        "EdDSA" => {
+            if let Some(ref path) = key_config.path {
+                let decoding_key = load_eddsa_public_key(path).map_err(|_| {
+                    jsonwebtoken::errors::Error::from(
+                        jsonwebtoken::errors::ErrorKind::InvalidEddsaKey,
+                    )
+                })?;
+                validate_jwt_with_key(token, &decoding_key, &key_config.algorithm)
+            } else {
+                Err(jsonwebtoken::errors::Error::from(
+                    jsonwebtoken::errors::ErrorKind::InvalidSignature,
+                ))
+            }
+        }
         _ => Err(jsonwebtoken::errors::Error::from(
             jsonwebtoken::errors::ErrorKind::InvalidAlgorithm,
         )),
@@ -538,6 +648,34 @@ pub fn verify_claims(claims: &Claims, rules: &[ClaimRule]) -> bool {
     true
 }
 
+/// Check the `iss` and `aud` claims against the configured issuer and audience
+///
+/// `aud` may be a string or an array; the token passes when any of its
+/// audiences is configured. Nothing is checked when neither is set.
+pub fn verify_issuer_and_audience(claims: &Claims, jwt_config: &JwtConfig) -> bool {
+    if let Some(issuer) = &jwt_config.issuer {
+        if claims.iss.as_ref() != Some(issuer) {
+            tracing::warn!(expected = %issuer, actual = ?claims.iss, "JWT issuer mismatch");
+            return false;
+        }
+    }
+    if !jwt_config.audience.is_empty() {
+        let matches = match claims.custom.get("aud") {
+            Some(serde_json::Value::String(aud)) => jwt_config.audience.contains(aud),
+            Some(serde_json::Value::Array(auds)) => auds
+                .iter()
+                .filter_map(|aud| aud.as_str())
+                .any(|aud| jwt_config.audience.iter().any(|a| a == aud)),
+            _ => false,
+        };
+        if !matches {
+            tracing::warn!("JWT audience does not match any configured audience");
+            return false;
+        }
+    }
+    true
+}
+
 /// Helper function for numeric comparisons
 fn compare_numeric<F>(
     claim_value: Option<&serde_json::Value>,
@@ -615,6 +753,19 @@ impl std::fmt::Display for AuthError {
     }
 }
 
+impl AuthError {
+    /// Machine-readable code for the failure
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            AuthError::MissingToken => ErrorCode::AuthMissingToken,
+            AuthError::InvalidToken(_) | AuthError::ClaimsVerificationFailed => {
+                ErrorCode::AuthInvalidToken
+            }
+            AuthError::AdminAccessDenied => ErrorCode::AuthForbidden,
+        }
+    }
+}
+
 pub fn authenticate_request(
     headers: &HashMap<String, String>,
     query_params: &HashMap<String, String>,
@@ -623,7 +774,57 @@ pub fn authenticate_request(
     // Extract token from configured sources
     let token = try_extract_token(headers, query_params, &jwt_config.token_sources)
         .ok_or(AuthError::MissingToken)?;
+    check_token_algorithm(&token, &jwt_config.accepted_algorithms())?;
+
+    let cache_key = TokenCache::key(&token, jwt_config);
+    let claims = match cache_key
+        .as_ref()
+        .and_then(|key| TokenCache::global().get(key))
+    {
+        Some(claims) => claims,
+        None => {
+            let claims = verify_token_signature(&token, jwt_config)?;
+            if let Some(key) = cache_key {
+                TokenCache::global().insert(key, &claims, &jwt_config.token_cache);
+            }
+            claims
+        }
+    };
 
+    tracing::debug!("JWT signature valid, checking claims");
+
+    check_not_revoked(&claims, jwt_config)?;
+    if !verify_issuer_and_audience(&claims, jwt_config) {
+        return Err(AuthError::ClaimsVerificationFailed);
+    }
+
+    // Verify claims if rules are configured
+    if !jwt_config.claims.is_empty() {
+        tracing::debug!("Verifying {} custom claim rules", jwt_config.claims.len());
+        if !verify_claims(&claims, &jwt_config.claims) {
+            tracing::warn!("JWT claims verification failed");
+            return Err(AuthError::ClaimsVerificationFailed);
+        }
+        tracing::debug!("All JWT claims verified successfully");
+    }
+
+    tracing::debug!("JWT authentication successful");
+    Ok(claims)
+}
+
+/// Reject a token whose `jti` or `sub` is on the revocation list
+fn check_not_revoked(claims: &Claims, jwt_config: &JwtConfig) -> Result<(), AuthError> {
+    if jwt_config.revocation.is_some() && RevocationList::global().is_revoked(claims) {
+        tracing::warn!(sub = ?claims.sub, "Rejected revoked JWT");
+        return Err(AuthError::InvalidToken(
+            "Token has been revoked".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Verify a token's signature with the configured secret or PEM key
+fn verify_token_signature(token: &str, jwt_config: &JwtConfig) -> Result<Claims, AuthError> {
     // Validate JWT with configured algorithm
     tracing::debug!(
         "Validating JWT signature with algorithm: {}",
@@ -631,7 +832,7 @@ pub fn authenticate_request(
     );
 
     // Determine validation method based on algorithm
-    let claims = match jwt_config.algorithm.as_str() {
+    match jwt_config.algorithm.as_str() {
         "RS256" | "RS384" | "RS512" => {
             // Use RSA public key for RS* algorithms
             let key_path = jwt_config.rsa_public_key_path.as_ref().ok_or_else(|| {
@@ -641,12 +842,14 @@ pub fn authenticate_request(
                 ))
             })?;
 
-            let decoding_key = load_rsa_public_key(key_path).map_err(|e| {
-                tracing::error!("Failed to load RSA public key: {}", e);
-                AuthError::InvalidToken(format!("Failed to load RSA public key: {}", e))
-            })?;
+            let decoding_key = KeyMaterial::global()
+                .rsa_public_key(key_path)
+                .map_err(|e| {
+                    tracing::error!("Failed to load RSA public key: {}", e);
+                    AuthError::InvalidToken(format!("Failed to load RSA public key: {}", e))
+                })?;
 
-            validate_jwt_with_key(&token, &decoding_key, &jwt_config.algorithm)
+            validate_jwt_with_key(token, &decoding_key, &jwt_config.algorithm)
         }
         "ES256" | "ES384" => {
             // Use ECDSA public key for ES* algorithms
@@ -657,37 +860,46 @@ pub fn authenticate_request(
                 ))
             })?;
 
-            let decoding_key = load_ecdsa_public_key(key_path).map_err(|e| {
-                tracing::error!("Failed to load ECDSA public key: {}", e);
-                AuthError::InvalidToken(format!("Failed to load ECDSA public key: {}", e))
+            let decoding_key = KeyMaterial::global()
+                .ecdsa_public_key(key_path)
+                .map_err(|e| {
+                    tracing::error!("Failed to load ECDSA public key: {}", e);
+                    AuthError::InvalidToken(format!("Failed to load ECDSA public key: {}", e))
+                })?;
+
+            validate_jwt_with_key(token, &decoding_key, &jwt_config.algorithm)
+        }
+        "EdDSA" => {
+            // Use Ed25519 public key for EdDSA
+            let key_path = jwt_config.eddsa_public_key_path.as_ref().ok_or_else(|| {
+                AuthError::InvalidToken(
+                    "EdDSA public key path not configured for EdDSA algorithm".to_string(),
+                )
             })?;
 
-            validate_jwt_with_key(&token, &decoding_key, &jwt_config.algorithm)
+            let decoding_key = KeyMaterial::global()
+                .eddsa_public_key(key_path)
+                .map_err(|e| {
+                    tracing::error!("Failed to load EdDSA public key: {}", e);
+                    AuthError::InvalidToken(format!("Failed to load EdDSA public key: {}", e))
+                })?;
+
+            validate_jwt_with_key(token, &decoding_key, &jwt_config.algorithm)
         }
         _ => {
             // Use HMAC secret for HS* algorithms (default)
-            validate_jwt(&token, &jwt_config.secret, &jwt_config.algorithm)
+            let secret = KeyMaterial::global().hmac_secret(jwt_config).map_err(|e| {
+                tracing::error!("Failed to load JWT secret: {}", e);
+                AuthError::InvalidToken(format!("Failed to load JWT secret: {}", e))
+            })?;
+
+            validate_jwt(token, &secret, &jwt_config.algorithm)
         }
     }
     .map_err(|e| {
         tracing::warn!("JWT signature validation failed: {}", e);
         AuthError::InvalidToken(e.to_string())
-    })?;
-
-    tracing::debug!("JWT signature valid, checking claims");
-
-    // Verify claims if rules are configured
-    if !jwt_config.claims.is_empty() {
-        tracing::debug!("Verifying {} custom claim rules", jwt_config.claims.len());
-        if !verify_claims(&claims, &jwt_config.claims) {
-            tracing::warn!("JWT claims verification failed");
-            return Err(AuthError::ClaimsVerificationFailed);
-        }
-        tracing::debug!("All JWT claims verified successfully");
-    }
-
-    tracing::debug!("JWT authentication successful");
-    Ok(claims)
+    })
 }
 
 /// Validate JWT using JWKS (JSON Web Key Set) from a remote endpoint
@@ -715,23 +927,23 @@ pub async fn validate_jwt_with_jwks(
 
     tracing::debug!("Validating JWT with kid '{}' using JWKS", kid);
 
-    // Ensure JWKS is loaded/refreshed
-    jwks_client.get_jwks().await.map_err(|e| {
-        tracing::error!("Failed to fetch JWKS: {}", e);
-        AuthError::InvalidToken(format!("Failed to fetch JWKS: {}", e))
+    // Load the key set (refetching once if the kid is unknown) and pick the key
+    let jwk = jwks_client.key_for(&kid).await.map_err(|e| match e {
+        JwksClientError::KeyNotFound(_) => {
+            tracing::warn!("Key '{}' not found in JWKS", kid);
+            AuthError::InvalidToken(format!("Key '{}' not found in JWKS", kid))
+        }
+        e => {
+            tracing::error!("Failed to fetch JWKS: {}", e);
+            AuthError::InvalidToken(format!("Failed to fetch JWKS: {}", e))
+        }
     })?;
-
-    // Get the decoding key for this kid
-    let decoding_key = jwks_client.get_decoding_key(&kid).map_err(|e| {
-        tracing::warn!("Key '{}' not found in JWKS: {}", kid, e);
-        AuthError::InvalidToken(format!("Key '{}' not found in JWKS", kid))
+    let decoding_key = jwk.to_decoding_key().map_err(|e| {
+        tracing::warn!("Key '{}' in JWKS is unusable: {}", kid, e);
+        AuthError::InvalidToken(format!("Key '{}' in JWKS is unusable", kid))
     })?;
 
     // Determine algorithm from the JWK
-    let jwk = jwks_client
-        .find_key(&kid)
-        .ok_or_else(|| AuthError::InvalidToken(format!("Key '{}' not found in JWKS", kid)))?;
-
     let algorithm = jwk.algorithm().unwrap_or("RS256");
 
     // Validate the JWT
@@ -757,11 +969,29 @@ pub async fn authenticate_request_with_jwks(
     // Extract token from configured sources
     let token = try_extract_token(headers, query_params, &jwt_config.token_sources)
         .ok_or(AuthError::MissingToken)?;
+    check_token_algorithm(&token, &jwt_config.accepted_algorithms())?;
 
     // Validate using JWKS
-    let (claims, kid) = validate_jwt_with_jwks(&token, jwks_client).await?;
+    let cache_key = TokenCache::key(&token, jwt_config);
+    let claims = match cache_key
+        .as_ref()
+        .and_then(|key| TokenCache::global().get(key))
+    {
+        Some(claims) => claims,
+        None => {
+            let (claims, kid) = validate_jwt_with_jwks(&token, jwks_client).await?;
+            tracing::debug!("JWT validated with key '{}'", kid);
+            if let Some(key) = cache_key {
+                TokenCache::global().insert(key, &claims, &jwt_config.token_cache);
+            }
+            claims
+        }
+    };
 
-    tracing::debug!("JWT validated with key '{}'", kid);
+    check_not_revoked(&claims, jwt_config)?;
+    if !verify_issuer_and_audience(&claims, jwt_config) {
+        return Err(AuthError::ClaimsVerificationFailed);
+    }
 
     // Verify claims if rules are configured
     if !jwt_config.claims.is_empty() {
@@ -777,6 +1007,28 @@ pub async fn authenticate_request_with_jwks(
     Ok(claims)
 }
 
+/// Authenticate a request with the key source the config uses
+///
+/// With `jwks_url` set, keys come from the shared JWKS client in
+/// [`KeyMaterial`] and are selected by the token's `kid`; otherwise the
+/// static secret or PEM key is used via [`authenticate_request`].
+pub async fn authenticate(
+    headers: &HashMap<String, String>,
+    query_params: &HashMap<String, String>,
+    jwt_config: &JwtConfig,
+) -> Result<Claims, AuthError> {
+    match &jwt_config.jwks_url {
+        Some(url) => {
+            let refresh = jwt_config
+                .jwks_refresh_interval_secs
+                .unwrap_or(DEFAULT_JWKS_REFRESH_INTERVAL_SECS);
+            let client = KeyMaterial::global().jwks_client(url, refresh);
+            authenticate_request_with_jwks(headers, query_params, jwt_config, &client).await
+        }
+        None => authenticate_request(headers, query_params, jwt_config),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
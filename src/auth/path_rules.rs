@@ -0,0 +1,155 @@
+//! Built-in claim-to-path authorization.
+//!
+//! Buckets with `authorization.type: claims` restrict each caller to the
+//! object paths their JWT claims name, e.g. `/{tenant_id}/**` keeps every
+//! tenant inside its own prefix. This covers prefix isolation without
+//! running OPA or OpenFGA.
+//!
+//! A placeholder expands only to a single path segment: a claim that is
+//! missing, empty, not a string or number, `.`/`..`, or contains `/` or a
+//! glob character makes the rule fail to match instead of widening it.
+
+use serde_json::Value;
+
+use crate::config::ClaimPathRule;
+
+/// Value of a claim usable inside a path (dot notation for nested claims)
+fn claim_segment(claims: &Value, name: &str) -> Option<String> {
+    let mut current = claims;
+    for part in name.split('.') {
+        current = current.get(part)?;
+    }
+    let value = match current {
+        Value::String(s) => s.clone(),
+        Value::Number(n) => n.to_string(),
+        _ => return None,
+    };
+    let usable = !value.is_empty()
+        && value != "."
+        && value != ".."
+        && !value.contains(['/', '*', '?', '{', '}']);
+    usable.then_some(value)
+}
+
+/// Replace `{claim}` placeholders in `template` with the caller's claims
+///
+/// Returns `None` when any placeholder cannot be filled.
+pub fn expand(template: &str, claims: &Value) -> Option<String> {
+    let mut expanded = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let len = rest[start..].find('}')?;
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&claim_segment(claims, &rest[start + 1..start + len])?);
+        rest = &rest[start + len + 1..];
+    }
+    expanded.push_str(rest);
+    Some(expanded)
+}
+
+/// Glob match where `*` stays within a segment and `**` crosses segments
+fn glob_match(pattern: &[char], path: &[char]) -> bool {
+    match pattern.first() {
+        None => path.is_empty(),
+        Some('*') if pattern.get(1) == Some(&'*') => {
+            let rest = &pattern[2..];
+            // "**/" also matches zero directories
+            if rest.first() == Some(&'/') && glob_match(&rest[1..], path) {
+                return true;
+            }
+            (0..=path.len()).any(|i| glob_match(rest, &path[i..]))
+        }
+        Some('*') => {
+            let rest = &pattern[1..];
+            for i in 0..=path.len() {
+                if glob_match(rest, &path[i..]) {
+                    return true;
+                }
+                if path.get(i) == Some(&'/') {
+                    break;
+                }
+            }
+            false
+        }
+        Some('?') => {
+            path.first().is_some_and(|c| *c != '/') && glob_match(&pattern[1..], &path[1..])
+        }
+        Some(c) => path.first() == Some(c) && glob_match(&pattern[1..], &path[1..]),
+    }
+}
+
+/// Whether `rule` lets the caller `method` the object at `key`
+pub fn rule_allows(rule: &ClaimPathRule, claims: &Value, method: &str, key: &str) -> bool {
+    if !rule.methods.is_empty() && !rule.methods.iter().any(|m| m.eq_ignore_ascii_case(method)) {
+        return false;
+    }
+    let Some(pattern) = expand(&rule.path, claims) else {
+        return false;
+    };
+    path_matches(&pattern, key)
+}
+
+/// Whether `key` matches the glob `pattern`, ignoring leading slashes
+///
+/// `*` and `?` stay within one path segment; `**` crosses segments.
+pub fn path_matches(pattern: &str, key: &str) -> bool {
+    let pattern: Vec<char> = pattern.trim_start_matches('/').chars().collect();
+    let key: Vec<char> = key.trim_start_matches('/').chars().collect();
+    glob_match(&pattern, &key)
+}
+
+/// Whether any rule lets the caller `method` the object at `key`
+pub fn is_allowed(rules: &[ClaimPathRule], claims: &Value, method: &str, key: &str) -> bool {
+    rules
+        .iter()
+        .any(|rule| rule_allows(rule, claims, method, key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(path: &str, methods: &[&str]) -> ClaimPathRule {
+        ClaimPathRule {
+            path: path.to_string(),
+            methods: methods.iter().map(|m| m.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_claim_path_rules() {
+        let rules = vec![
+            rule("/{tenant_id}/**", &[]),
+            rule("/shared/{org.id}/*.pdf", &["GET", "HEAD"]),
+        ];
+        let claims = serde_json::json!({"tenant_id": "acme", "org": {"id": 42}});
+
+        assert!(is_allowed(&rules, &claims, "GET", "acme/reports/q1.csv"));
+        assert!(is_allowed(&rules, &claims, "PUT", "/acme/upload.bin"));
+        assert!(!is_allowed(&rules, &claims, "GET", "globex/reports/q1.csv"));
+        assert!(!is_allowed(&rules, &claims, "GET", "acme"));
+
+        // Single-segment `*` and method restriction
+        assert!(is_allowed(&rules, &claims, "get", "shared/42/terms.pdf"));
+        assert!(!is_allowed(
+            &rules,
+            &claims,
+            "GET",
+            "shared/42/old/terms.pdf"
+        ));
+        assert!(!is_allowed(
+            &rules,
+            &claims,
+            "DELETE",
+            "shared/42/terms.pdf"
+        ));
+
+        // Missing or unsafe claim values never match
+        let missing = serde_json::json!({"sub": "alice"});
+        assert!(!is_allowed(&rules, &missing, "GET", "acme/reports/q1.csv"));
+        let wildcard = serde_json::json!({"tenant_id": "*"});
+        assert!(!is_allowed(&rules, &wildcard, "GET", "acme/reports/q1.csv"));
+        let traversal = serde_json::json!({"tenant_id": "acme/../globex"});
+        assert_eq!(expand("/{tenant_id}/**", &traversal), None);
+    }
+}
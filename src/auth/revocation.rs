@@ -0,0 +1,254 @@
+//! Revoked token list.
+//!
+//! With `jwt.revocation` configured, a token whose `jti` or `sub` is on the
+//! list is rejected after its signature is validated, including tokens served
+//! from the [`TokenCache`](super::TokenCache). Entries come from a file, a
+//! Redis set, or both, and are re-read every `reload_interval_secs`, so a
+//! compromised token can be blocked before it expires without a config reload.
+//!
+//! A source that fails to re-read keeps its previous entries, so a Redis
+//! outage or half-written file never un-revokes a token.
+
+use parking_lot::RwLock;
+use redis::aio::ConnectionManager;
+use std::collections::HashSet;
+use std::fs;
+use std::sync::OnceLock;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+
+use super::Claims;
+use crate::config::RevocationConfig;
+
+/// Revoked `jti` and `sub` values
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RevokedEntries {
+    pub jti: HashSet<String>,
+    pub sub: HashSet<String>,
+}
+
+impl RevokedEntries {
+    /// Parse entries, one per line: `jti:<id>`, `sub:<subject>` or a bare `jti`
+    ///
+    /// Blank lines and lines starting with `#` are ignored.
+    pub fn parse<'a>(lines: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut entries = Self::default();
+        for line in lines {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            if let Some(sub) = line.strip_prefix("sub:") {
+                entries.sub.insert(sub.trim().to_string());
+            } else {
+                let jti = line.strip_prefix("jti:").unwrap_or(line);
+                entries.jti.insert(jti.trim().to_string());
+            }
+        }
+        entries
+    }
+
+    fn extend(&mut self, other: &RevokedEntries) {
+        self.jti.extend(other.jti.iter().cloned());
+        self.sub.extend(other.sub.iter().cloned());
+    }
+
+    /// Number of revoked entries
+    pub fn len(&self) -> usize {
+        self.jti.len() + self.sub.len()
+    }
+
+    /// Check if nothing is revoked
+    pub fn is_empty(&self) -> bool {
+        self.jti.is_empty() && self.sub.is_empty()
+    }
+}
+
+/// Process-wide revocation list, merged from every configured source
+#[derive(Default)]
+pub struct RevocationList {
+    entries: RwLock<RevokedEntries>,
+}
+
+static REVOCATION_LIST: OnceLock<RevocationList> = OnceLock::new();
+
+impl RevocationList {
+    /// Process-wide revocation list
+    pub fn global() -> &'static RevocationList {
+        REVOCATION_LIST.get_or_init(RevocationList::default)
+    }
+
+    /// Check whether the token's `jti` or `sub` has been revoked
+    pub fn is_revoked(&self, claims: &Claims) -> bool {
+        let entries = self.entries.read();
+        if entries.is_empty() {
+            return false;
+        }
+        let jti_revoked = claims
+            .custom
+            .get("jti")
+            .and_then(|jti| jti.as_str())
+            .is_some_and(|jti| entries.jti.contains(jti));
+        let sub_revoked = claims
+            .sub
+            .as_deref()
+            .is_some_and(|sub| entries.sub.contains(sub));
+        jti_revoked || sub_revoked
+    }
+
+    /// Replace the revoked entries
+    pub fn replace(&self, entries: RevokedEntries) {
+        *self.entries.write() = entries;
+    }
+
+    /// Number of revoked entries
+    pub fn len(&self) -> usize {
+        self.entries.read().len()
+    }
+
+    /// Check if nothing is revoked
+    pub fn is_empty(&self) -> bool {
+        self.entries.read().is_empty()
+    }
+
+    /// Load the file source now and re-read every source on the configured interval
+    ///
+    /// The file is read before this returns so revoked tokens are rejected
+    /// from the first request. Must be called from within a Tokio runtime.
+    pub fn start_reload_task(&'static self, config: &RevocationConfig) {
+        let mut sources = RevocationSources::new(config);
+        sources.reload_file();
+        self.replace(sources.merged());
+
+        let interval = Duration::from_secs(config.reload_interval_secs);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                sources.reload_file();
+                sources.reload_redis().await;
+                let merged = sources.merged();
+                if merged != *self.entries.read() {
+                    tracing::info!(entries = merged.len(), "JWT revocation list updated");
+                    self.replace(merged);
+                }
+            }
+        });
+    }
+}
+
+/// Last good entries from each source
+struct RevocationSources {
+    file: Option<String>,
+    redis: Option<(redis::Client, String)>,
+    connection: OnceCell<ConnectionManager>,
+    from_file: RevokedEntries,
+    from_redis: RevokedEntries,
+}
+
+impl RevocationSources {
+    fn new(config: &RevocationConfig) -> Self {
+        let redis = config.redis.as_ref().and_then(|redis_config| {
+            match redis::Client::open(redis_config.url.as_str()) {
+                Ok(client) => Some((client, redis_config.key.clone())),
+                Err(e) => {
+                    tracing::warn!(error = %e, "Invalid Redis URL for JWT revocation list");
+                    None
+                }
+            }
+        });
+        Self {
+            file: config.file.clone(),
+            redis,
+            connection: OnceCell::new(),
+            from_file: RevokedEntries::default(),
+            from_redis: RevokedEntries::default(),
+        }
+    }
+
+    fn reload_file(&mut self) {
+        let Some(path) = &self.file else {
+            return;
+        };
+        match fs::read_to_string(path) {
+            Ok(contents) => self.from_file = RevokedEntries::parse(contents.lines()),
+            Err(e) => {
+                tracing::warn!(path = %path, error = %e, "Failed to read JWT revocation file, keeping previous entries");
+            }
+        }
+    }
+
+    async fn reload_redis(&mut self) {
+        let Some((client, key)) = &self.redis else {
+            return;
+        };
+        let members = async {
+            let mut conn = self
+                .connection
+                .get_or_try_init(|| ConnectionManager::new(client.clone()))
+                .await
+                .cloned()
+                .map_err(|e| format!("Failed to connect to Redis: {}", e))?;
+            redis::cmd("SMEMBERS")
+                .arg(key)
+                .query_async::<Vec<String>>(&mut conn)
+                .await
+                .map_err(|e| format!("Redis SMEMBERS failed: {}", e))
+        }
+        .await;
+        match members {
+            Ok(members) => {
+                self.from_redis = RevokedEntries::parse(members.iter().map(|m| m.as_str()))
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to read JWT revocation set, keeping previous entries");
+            }
+        }
+    }
+
+    fn merged(&self) -> RevokedEntries {
+        let mut merged = self.from_file.clone();
+        merged.extend(&self.from_redis);
+        merged
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claims(sub: &str, jti: Option<&str>) -> Claims {
+        let mut custom = serde_json::Map::new();
+        if let Some(jti) = jti {
+            custom.insert("jti".to_string(), serde_json::json!(jti));
+        }
+        Claims {
+            sub: Some(sub.to_string()),
+            exp: None,
+            iat: None,
+            nbf: None,
+            iss: None,
+            custom,
+        }
+    }
+
+    #[test]
+    fn test_revocation_list_matches_jti_and_sub() {
+        let entries = RevokedEntries::parse(
+            "# leaked on 2025-01-01\njti:abc-123\nsub:mallory\n\nbare-id\n".lines(),
+        );
+        assert_eq!(entries.len(), 3);
+
+        let list = RevocationList::default();
+        assert!(!list.is_revoked(&claims("mallory", None)));
+        list.replace(entries);
+
+        assert!(list.is_revoked(&claims("alice", Some("abc-123"))));
+        assert!(list.is_revoked(&claims("alice", Some("bare-id"))));
+        assert!(list.is_revoked(&claims("mallory", None)));
+        assert!(!list.is_revoked(&claims("alice", Some("other"))));
+        // A sub value only revokes subjects, not token ids
+        assert!(!list.is_revoked(&claims("alice", Some("mallory"))));
+    }
+}
@@ -0,0 +1,675 @@
+//! Incoming AWS SigV4 verification.
+//!
+//! Buckets with `sigv4_auth` accept requests signed the way S3 SDKs sign
+//! them, either with an `Authorization: AWS4-HMAC-SHA256 ...` header or as a
+//! presigned URL (`X-Amz-Signature` and friends in the query). The signature
+//! is recomputed with the secret configured for the request's access key and
+//! compared in constant time.
+//!
+//! The signature covers the payload hash declared in `x-amz-content-sha256`
+//! (`UNSIGNED-PAYLOAD` for presigned URLs). When that is a SHA-256 digest,
+//! the proxy hashes the body through a [`PayloadDigest`] as it streams to S3
+//! and cuts the upload short if it does not match, so S3 never completes a
+//! body other than the one the client signed. Signed streaming payloads
+//! (`STREAMING-AWS4-HMAC-SHA256-PAYLOAD`), whose chunk signatures are not
+//! checked here, are rejected. Once verified, the client's signing headers
+//! are dropped and the request is re-signed upstream with the bucket's S3
+//! credentials.
+
+use bytes::{Bytes, BytesMut};
+use chrono::NaiveDateTime;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+
+use crate::config::SigV4AuthConfig;
+use crate::constants::MAX_PRESIGNED_URL_EXPIRES_SECS;
+use crate::s3::{
+    canonical_query_string, hmac_sha256, signing_key_cache, string_to_sign, uri_encode,
+    SigningParams, UNSIGNED_PAYLOAD,
+};
+use crate::security::constant_time_eq;
+
+/// Signing algorithm accepted from clients
+pub const ALGORITHM: &str = "AWS4-HMAC-SHA256";
+
+/// Unsigned aws-chunked body with trailing checksums (no chunk signatures)
+const STREAMING_UNSIGNED_PAYLOAD_TRAILER: &str = "STREAMING-UNSIGNED-PAYLOAD-TRAILER";
+
+/// SHA-256 of an empty body
+const EMPTY_PAYLOAD_SHA256: &str =
+    "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+/// Client headers that only authenticate the request to the proxy
+pub const CLIENT_SIGNING_HEADERS: &[&str] = &[
+    "authorization",
+    "x-amz-date",
+    "x-amz-content-sha256",
+    "x-amz-security-token",
+];
+
+/// Why a SigV4 request was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigV4Error {
+    /// No SigV4 `Authorization` header or presigned query
+    Missing,
+    /// Credential scope, date or signed headers are malformed
+    Malformed,
+    /// The access key is not configured for this bucket
+    UnknownAccessKey,
+    /// The request time is too far from the proxy clock
+    RequestTimeTooSkewed,
+    /// The presigned URL has expired
+    Expired,
+    /// The signature does not match
+    SignatureMismatch,
+    /// The body does not hash to `x-amz-content-sha256`
+    ContentSha256Mismatch,
+    /// The payload hash is neither a digest nor an unsigned payload marker,
+    /// e.g. a signed streaming payload
+    UnsupportedPayload,
+}
+
+impl SigV4Error {
+    /// Label used in logs and metrics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Missing => "missing",
+            Self::Malformed => "malformed",
+            Self::UnknownAccessKey => "unknown_access_key",
+            Self::RequestTimeTooSkewed => "time_skewed",
+            Self::Expired => "expired",
+            Self::SignatureMismatch => "signature_mismatch",
+            Self::ContentSha256Mismatch => "content_sha256_mismatch",
+            Self::UnsupportedPayload => "unsupported_payload",
+        }
+    }
+
+    /// S3 error code, so SDK clients report the familiar error
+    pub fn s3_code(&self) -> &'static str {
+        match self {
+            Self::Missing | Self::Expired => "AccessDenied",
+            Self::Malformed => "AuthorizationHeaderMalformed",
+            Self::UnknownAccessKey => "InvalidAccessKeyId",
+            Self::RequestTimeTooSkewed => "RequestTimeTooSkewed",
+            Self::SignatureMismatch => "SignatureDoesNotMatch",
+            Self::ContentSha256Mismatch => "XAmzContentSHA256Mismatch",
+            Self::UnsupportedPayload => "InvalidArgument",
+        }
+    }
+
+    /// HTTP status returned to the client
+    pub fn status(&self) -> u16 {
+        match self {
+            Self::ContentSha256Mismatch | Self::UnsupportedPayload => 400,
+            _ => 403,
+        }
+    }
+
+    /// Message returned to the client
+    pub fn message(&self) -> &'static str {
+        match self {
+            Self::Missing => "This bucket requires AWS Signature Version 4 authentication",
+            Self::Malformed => "The SigV4 credential scope or signed headers are malformed",
+            Self::UnknownAccessKey => "The access key ID does not exist in our records",
+            Self::RequestTimeTooSkewed => {
+                "The difference between the request time and the current time is too large"
+            }
+            Self::Expired => "Request has expired",
+            Self::SignatureMismatch => {
+                "The request signature we calculated does not match the signature you provided"
+            }
+            Self::ContentSha256Mismatch => {
+                "The provided 'x-amz-content-sha256' header does not match what was computed"
+            }
+            Self::UnsupportedPayload => {
+                "x-amz-content-sha256 must be a SHA-256 digest, UNSIGNED-PAYLOAD or STREAMING-UNSIGNED-PAYLOAD-TRAILER"
+            }
+        }
+    }
+
+    /// S3-style XML error body
+    pub fn to_xml(&self, request_id: &str) -> String {
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<Error><Code>{}</Code><Message>{}</Message><RequestId>{}</RequestId></Error>",
+            self.s3_code(),
+            self.message(),
+            request_id
+        )
+    }
+}
+
+/// The parts of a request covered by its signature
+#[derive(Debug, Clone, Copy)]
+pub struct IncomingRequest<'a> {
+    pub method: &'a str,
+    /// Path as sent by the client, without the query string
+    pub path: &'a str,
+    /// Raw query string, if any
+    pub query: Option<&'a str>,
+    /// Request headers with lowercase names
+    pub headers: &'a HashMap<String, String>,
+}
+
+/// Signature fields from the header or the presigned query
+struct SignatureFields {
+    access_key_id: String,
+    date: String,
+    region: String,
+    signed_headers: Vec<String>,
+    signature: String,
+    datetime: String,
+    payload_hash: String,
+    /// Presigned URL lifetime (None for header-signed requests)
+    expires_secs: Option<u64>,
+}
+
+/// Verify a SigV4-signed request at time `now` (unix seconds)
+///
+/// Returns the access key id the request was signed with.
+pub fn verify(
+    config: &SigV4AuthConfig,
+    request: &IncomingRequest,
+    now: u64,
+) -> Result<String, SigV4Error> {
+    let query = parse_query(request.query.unwrap_or(""));
+    let presigned = query.iter().any(|(name, _)| name == "X-Amz-Signature");
+    let fields = match request.headers.get("authorization") {
+        Some(value) if value.starts_with(ALGORITHM) => parse_authorization(value, request)?,
+        _ if presigned => parse_presigned(&query)?,
+        _ => return Err(SigV4Error::Missing),
+    };
+
+    if config
+        .region
+        .as_deref()
+        .is_some_and(|region| region != fields.region)
+    {
+        return Err(SigV4Error::Malformed);
+    }
+    let secret = config
+        .secret_for(&fields.access_key_id)
+        .ok_or(SigV4Error::UnknownAccessKey)?;
+
+    let signed_at = NaiveDateTime::parse_from_str(&fields.datetime, "%Y%m%dT%H%M%SZ")
+        .map_err(|_| SigV4Error::Malformed)?
+        .and_utc()
+        .timestamp();
+    if !fields.datetime.starts_with(&fields.date) {
+        return Err(SigV4Error::Malformed);
+    }
+    let signed_at = u64::try_from(signed_at).map_err(|_| SigV4Error::Malformed)?;
+    match fields.expires_secs {
+        // Presigned: valid from (about) signing time until it expires
+        Some(expires) => {
+            if signed_at > now + config.max_clock_skew_secs {
+                return Err(SigV4Error::RequestTimeTooSkewed);
+            }
+            if now > signed_at + expires {
+                return Err(SigV4Error::Expired);
+            }
+        }
+        None => {
+            if signed_at.abs_diff(now) > config.max_clock_skew_secs {
+                return Err(SigV4Error::RequestTimeTooSkewed);
+            }
+        }
+    }
+
+    // Only the headers the client signed, values trimmed with spaces collapsed
+    let mut headers = HashMap::with_capacity(fields.signed_headers.len());
+    for name in &fields.signed_headers {
+        let value = request.headers.get(name).ok_or(SigV4Error::Malformed)?;
+        headers.insert(
+            name.clone(),
+            value.split_whitespace().collect::<Vec<_>>().join(" "),
+        );
+    }
+
+    // S3 signs the path URI-encoded once, slashes kept
+    let decoded_path = urlencoding::decode(request.path)
+        .map(|p| p.into_owned())
+        .unwrap_or_else(|_| request.path.to_string());
+    let canonical_uri = uri_encode(&decoded_path, false);
+    let signed_query: Vec<(&str, &str)> = query
+        .iter()
+        .filter(|(name, _)| name != "X-Amz-Signature")
+        .map(|(name, value)| (name.as_str(), value.as_str()))
+        .collect();
+    let canonical_query = canonical_query_string(&signed_query);
+
+    let params = SigningParams {
+        method: request.method,
+        uri: &canonical_uri,
+        query_string: &canonical_query,
+        headers: &headers,
+        payload: &[],
+        access_key: &fields.access_key_id,
+        secret_key: secret,
+        region: &fields.region,
+        service: "s3",
+        date: &fields.date,
+        datetime: &fields.datetime,
+    };
+    let string_to_sign = string_to_sign(&params, &fields.payload_hash);
+    let signing_key = signing_key_cache().get_or_derive(secret, &fields.date, &fields.region, "s3");
+    let expected = hex::encode(hmac_sha256(&signing_key, string_to_sign.as_bytes()));
+
+    if !constant_time_eq(&fields.signature.to_ascii_lowercase(), &expected) {
+        return Err(SigV4Error::SignatureMismatch);
+    }
+    check_payload_hash(&fields.payload_hash, request.headers)?;
+    Ok(fields.access_key_id)
+}
+
+/// Reject payload hashes the body cannot be checked against, and empty
+/// bodies that do not match theirs (there is no body to hold back)
+fn check_payload_hash(
+    payload_hash: &str,
+    headers: &HashMap<String, String>,
+) -> Result<(), SigV4Error> {
+    if payload_hash == UNSIGNED_PAYLOAD || payload_hash == STREAMING_UNSIGNED_PAYLOAD_TRAILER {
+        return Ok(());
+    }
+    if !is_sha256_hex(payload_hash) {
+        return Err(SigV4Error::UnsupportedPayload);
+    }
+    let empty_body = headers
+        .get("content-length")
+        .is_some_and(|length| length.trim() == "0");
+    if empty_body && !payload_hash.eq_ignore_ascii_case(EMPTY_PAYLOAD_SHA256) {
+        return Err(SigV4Error::ContentSha256Mismatch);
+    }
+    Ok(())
+}
+
+fn is_sha256_hex(value: &str) -> bool {
+    value.len() == 64 && value.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// SHA-256 digest the client declared for the body, if it declared one
+pub fn declared_payload_sha256(headers: &HashMap<String, String>) -> Option<&str> {
+    headers
+        .get("x-amz-content-sha256")
+        .map(String::as_str)
+        .filter(|hash| is_sha256_hex(hash))
+}
+
+/// Checks a request body against its declared SHA-256 while it streams
+///
+/// Every chunk is hashed on its way through and the latest one is held
+/// back, so when the digest turns out not to match, the upstream request is
+/// aborted before its last bytes are sent and S3 never stores the body.
+pub struct PayloadDigest {
+    expected: String,
+    hasher: Sha256,
+    held: Option<Bytes>,
+}
+
+impl std::fmt::Debug for PayloadDigest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PayloadDigest")
+            .field("expected", &self.expected)
+            .finish_non_exhaustive()
+    }
+}
+
+impl PayloadDigest {
+    /// Check the body against `expected`, a hex SHA-256 digest
+    pub fn new(expected: &str) -> Self {
+        Self {
+            expected: expected.to_ascii_lowercase(),
+            hasher: Sha256::new(),
+            held: None,
+        }
+    }
+
+    /// Pass one body chunk through, returning what may be forwarded now
+    /// (an empty chunk forwards nothing)
+    pub fn filter(
+        &mut self,
+        chunk: Option<Bytes>,
+        end_of_stream: bool,
+    ) -> Result<Bytes, SigV4Error> {
+        let chunk = chunk.filter(|chunk| !chunk.is_empty());
+        if let Some(chunk) = &chunk {
+            self.hasher.update(chunk);
+        }
+        if !end_of_stream {
+            return Ok(match chunk {
+                Some(chunk) => self.held.replace(chunk).unwrap_or_default(),
+                None => Bytes::new(),
+            });
+        }
+
+        let actual = hex::encode(self.hasher.finalize_reset());
+        if !constant_time_eq(&actual, &self.expected) {
+            return Err(SigV4Error::ContentSha256Mismatch);
+        }
+        Ok(match (self.held.take(), chunk) {
+            (Some(held), Some(chunk)) => {
+                let mut body = BytesMut::with_capacity(held.len() + chunk.len());
+                body.extend_from_slice(&held);
+                body.extend_from_slice(&chunk);
+                body.freeze()
+            }
+            (held, chunk) => held.or(chunk).unwrap_or_default(),
+        })
+    }
+}
+
+/// Fields of `Authorization: AWS4-HMAC-SHA256 Credential=..., SignedHeaders=..., Signature=...`
+fn parse_authorization(
+    value: &str,
+    request: &IncomingRequest,
+) -> Result<SignatureFields, SigV4Error> {
+    let fields = value.strip_prefix(ALGORITHM).ok_or(SigV4Error::Malformed)?;
+    let mut credential = None;
+    let mut signed_headers = None;
+    let mut signature = None;
+    for part in fields.split(',') {
+        match part.trim().split_once('=') {
+            Some(("Credential", v)) => credential = Some(v),
+            Some(("SignedHeaders", v)) => signed_headers = Some(v),
+            Some(("Signature", v)) => signature = Some(v),
+            _ => {}
+        }
+    }
+    let (Some(credential), Some(signed_headers), Some(signature)) =
+        (credential, signed_headers, signature)
+    else {
+        return Err(SigV4Error::Malformed);
+    };
+    let (access_key_id, date, region) = parse_credential(credential)?;
+    let datetime = request
+        .headers
+        .get("x-amz-date")
+        .ok_or(SigV4Error::Malformed)?;
+    let payload_hash = request
+        .headers
+        .get("x-amz-content-sha256")
+        .ok_or(SigV4Error::Malformed)?;
+
+    Ok(SignatureFields {
+        access_key_id,
+        date,
+        region,
+        signed_headers: split_signed_headers(signed_headers)?,
+        signature: signature.to_string(),
+        datetime: datetime.clone(),
+        payload_hash: payload_hash.clone(),
+        expires_secs: None,
+    })
+}
+
+/// Fields of a presigned URL (`X-Amz-Algorithm`, `X-Amz-Credential`, ...)
+fn parse_presigned(query: &[(String, String)]) -> Result<SignatureFields, SigV4Error> {
+    let param = |name: &str| {
+        query
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+            .ok_or(SigV4Error::Malformed)
+    };
+    if param("X-Amz-Algorithm")? != ALGORITHM {
+        return Err(SigV4Error::Malformed);
+    }
+    let (access_key_id, date, region) = parse_credential(param("X-Amz-Credential")?)?;
+    let expires_secs: u64 = param("X-Amz-Expires")?
+        .parse()
+        .map_err(|_| SigV4Error::Malformed)?;
+    if expires_secs == 0 || expires_secs > MAX_PRESIGNED_URL_EXPIRES_SECS {
+        return Err(SigV4Error::Malformed);
+    }
+
+    Ok(SignatureFields {
+        access_key_id,
+        date,
+        region,
+        signed_headers: split_signed_headers(param("X-Amz-SignedHeaders")?)?,
+        signature: param("X-Amz-Signature")?.to_string(),
+        datetime: param("X-Amz-Date")?.to_string(),
+        payload_hash: UNSIGNED_PAYLOAD.to_string(),
+        expires_secs: Some(expires_secs),
+    })
+}
+
+/// `<access key>/<date>/<region>/s3/aws4_request`
+fn parse_credential(credential: &str) -> Result<(String, String, String), SigV4Error> {
+    let parts: Vec<&str> = credential.split('/').collect();
+    match parts.as_slice() {
+        [access_key_id, date, region, "s3", "aws4_request"]
+            if !access_key_id.is_empty() && date.len() == 8 && !region.is_empty() =>
+        {
+            Ok((
+                access_key_id.to_string(),
+                date.to_string(),
+                region.to_string(),
+            ))
+        }
+        _ => Err(SigV4Error::Malformed),
+    }
+}
+
+fn split_signed_headers(value: &str) -> Result<Vec<String>, SigV4Error> {
+    let names: Vec<String> = value
+        .split(';')
+        .map(|name| name.trim().to_ascii_lowercase())
+        .collect();
+    // The host header is always signed
+    if !names.iter().any(|name| name == "host") {
+        return Err(SigV4Error::Malformed);
+    }
+    Ok(names)
+}
+
+/// Decoded query pairs, keeping duplicates and valueless parameters
+fn parse_query(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let decode = |s: &str| {
+                urlencoding::decode(s)
+                    .map(|d| d.into_owned())
+                    .unwrap_or_else(|_| s.to_string())
+            };
+            (decode(name), decode(value))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::s3::{sha256_hex, sign_request};
+
+    fn config() -> SigV4AuthConfig {
+        serde_yaml::from_str(
+            "credentials:\n  - access_key_id: AKIDCLIENT\n    secret_access_key: client-secret",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_verify_header_and_presigned_requests() {
+        let config = config();
+        let now = 1_700_000_000; // 2023-11-14T22:13:20Z
+        let datetime = "20231114T221320Z";
+
+        // Header-signed, as produced by the proxy's own upstream signer
+        let mut headers = HashMap::from([
+            ("host".to_string(), "proxy.example.com".to_string()),
+            ("x-amz-date".to_string(), datetime.to_string()),
+            ("x-amz-content-sha256".to_string(), sha256_hex(b"")),
+        ]);
+        let authorization = sign_request(&SigningParams {
+            method: "GET",
+            uri: "/uploads/my%20file.txt",
+            query_string: "",
+            headers: &headers,
+            payload: b"",
+            access_key: "AKIDCLIENT",
+            secret_key: "client-secret",
+            region: "us-east-1",
+            service: "s3",
+            date: "20231114",
+            datetime,
+        });
+        headers.insert("authorization".to_string(), authorization);
+        headers.insert("user-agent".to_string(), "aws-sdk".to_string());
+        let request = IncomingRequest {
+            method: "GET",
+            path: "/uploads/my%20file.txt",
+            query: None,
+            headers: &headers,
+        };
+        assert_eq!(verify(&config, &request, now), Ok("AKIDCLIENT".to_string()));
+        assert_eq!(
+            verify(&config, &request, now + 901),
+            Err(SigV4Error::RequestTimeTooSkewed)
+        );
+        let tampered = IncomingRequest {
+            path: "/uploads/other.txt",
+            ..request
+        };
+        assert_eq!(
+            verify(&config, &tampered, now),
+            Err(SigV4Error::SignatureMismatch)
+        );
+        let mut pinned = config.clone();
+        pinned.region = Some("eu-west-1".to_string());
+        assert_eq!(verify(&pinned, &request, now), Err(SigV4Error::Malformed));
+        let unsigned = HashMap::from([("host".to_string(), "proxy.example.com".to_string())]);
+        assert_eq!(
+            verify(
+                &config,
+                &IncomingRequest {
+                    headers: &unsigned,
+                    ..request
+                },
+                now
+            ),
+            Err(SigV4Error::Missing)
+        );
+
+        // Presigned URL: the query minus X-Amz-Signature is signed
+        let query = "X-Amz-Algorithm=AWS4-HMAC-SHA256\
+            &X-Amz-Credential=AKIDCLIENT%2F20231114%2Fus-east-1%2Fs3%2Faws4_request\
+            &X-Amz-Date=20231114T221320Z&X-Amz-Expires=300&X-Amz-SignedHeaders=host";
+        let host_only = HashMap::from([("host".to_string(), "proxy.example.com".to_string())]);
+        let canonical_query = canonical_query_string(
+            &parse_query(query)
+                .iter()
+                .map(|(n, v)| (n.as_str(), v.as_str()))
+                .collect::<Vec<_>>(),
+        );
+        let string_to_sign = string_to_sign(
+            &SigningParams {
+                method: "GET",
+                uri: "/uploads/report.pdf",
+                query_string: &canonical_query,
+                headers: &host_only,
+                payload: b"",
+                access_key: "AKIDCLIENT",
+                secret_key: "client-secret",
+                region: "us-east-1",
+                service: "s3",
+                date: "20231114",
+                datetime,
+            },
+            UNSIGNED_PAYLOAD,
+        );
+        let key = signing_key_cache().get_or_derive("client-secret", "20231114", "us-east-1", "s3");
+        let signature = hex::encode(hmac_sha256(&key, string_to_sign.as_bytes()));
+        let presigned_query = format!("{}&X-Amz-Signature={}", query, signature);
+        let presigned = IncomingRequest {
+            method: "GET",
+            path: "/uploads/report.pdf",
+            query: Some(&presigned_query),
+            headers: &host_only,
+        };
+        assert_eq!(
+            verify(&config, &presigned, now + 60),
+            Ok("AKIDCLIENT".to_string())
+        );
+        assert_eq!(
+            verify(&config, &presigned, now + 301),
+            Err(SigV4Error::Expired)
+        );
+
+        let mut other_key = config.clone();
+        other_key.credentials[0].access_key_id = "AKIDOTHER".to_string();
+        assert_eq!(
+            verify(&other_key, &presigned, now),
+            Err(SigV4Error::UnknownAccessKey)
+        );
+    }
+
+    #[test]
+    fn test_payload_hash_must_be_checkable() {
+        let no_headers = HashMap::new();
+        assert_eq!(check_payload_hash(UNSIGNED_PAYLOAD, &no_headers), Ok(()));
+        assert_eq!(
+            check_payload_hash("STREAMING-UNSIGNED-PAYLOAD-TRAILER", &no_headers),
+            Ok(())
+        );
+        assert_eq!(
+            check_payload_hash("STREAMING-AWS4-HMAC-SHA256-PAYLOAD", &no_headers),
+            Err(SigV4Error::UnsupportedPayload)
+        );
+        assert_eq!(
+            check_payload_hash(&sha256_hex(b"body"), &no_headers),
+            Ok(())
+        );
+
+        // An empty body is checked up front
+        let empty = HashMap::from([("content-length".to_string(), "0".to_string())]);
+        assert_eq!(check_payload_hash(&sha256_hex(b""), &empty), Ok(()));
+        assert_eq!(
+            check_payload_hash(&sha256_hex(b"body"), &empty),
+            Err(SigV4Error::ContentSha256Mismatch)
+        );
+        assert_eq!(SigV4Error::ContentSha256Mismatch.status(), 400);
+    }
+
+    #[test]
+    fn test_payload_digest_holds_back_last_chunk_until_verified() {
+        let body = b"hello, signed world";
+        let mut digest = PayloadDigest::new(&sha256_hex(body));
+        // The first chunk is held; the second releases it
+        assert!(digest
+            .filter(Some(Bytes::from_static(b"hello, ")), false)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            digest
+                .filter(Some(Bytes::from_static(b"signed ")), false)
+                .unwrap(),
+            Bytes::from_static(b"hello, ")
+        );
+        assert_eq!(
+            digest
+                .filter(Some(Bytes::from_static(b"world")), true)
+                .unwrap(),
+            Bytes::from_static(b"signed world")
+        );
+
+        // A body that does not match never releases its last chunk
+        let mut digest = PayloadDigest::new(&sha256_hex(body));
+        assert!(digest
+            .filter(Some(Bytes::from_static(b"tampered")), false)
+            .unwrap()
+            .is_empty());
+        assert_eq!(
+            digest.filter(None, true),
+            Err(SigV4Error::ContentSha256Mismatch)
+        );
+
+        let headers = HashMap::from([(
+            "x-amz-content-sha256".to_string(),
+            UNSIGNED_PAYLOAD.to_string(),
+        )]);
+        assert_eq!(declared_payload_sha256(&headers), None);
+    }
+}
@@ -0,0 +1,193 @@
+//! Cache of tokens whose signature has already been verified.
+//!
+//! With `jwt.token_cache.enabled`, a token that passed signature and
+//! `exp`/`nbf` validation is remembered until its `exp` or
+//! `jwt.token_cache.ttl_secs`, whichever comes first. Repeat requests with the
+//! same token skip the signature check; issuer, audience and claim rules still
+//! run on every request.
+//!
+//! Entries are keyed by a SHA-256 of the token and the key source it was
+//! verified with (algorithm, secret, key paths, JWKS URL), so buckets with
+//! their own keys never share entries. The whole cache is dropped when a key
+//! file rotates or a JWKS fetch returns a different key set.
+
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::OnceLock;
+use std::time::{Duration, Instant};
+
+use super::Claims;
+use crate::config::{JwtConfig, TokenCacheConfig};
+use crate::metrics::Metrics;
+use crate::util::unix_now;
+
+/// Cache key: SHA-256 of the token and its key source
+pub type TokenCacheKey = [u8; 32];
+
+struct CachedToken {
+    claims: Claims,
+    expires_at: Instant,
+}
+
+/// Verified tokens with their claims
+#[derive(Default)]
+pub struct TokenCache {
+    entries: Mutex<HashMap<TokenCacheKey, CachedToken>>,
+}
+
+static TOKEN_CACHE: OnceLock<TokenCache> = OnceLock::new();
+
+impl TokenCache {
+    /// Process-wide token cache
+    pub fn global() -> &'static TokenCache {
+        TOKEN_CACHE.get_or_init(TokenCache::default)
+    }
+
+    /// Cache key for a token, or `None` when caching is disabled
+    pub fn key(token: &str, jwt: &JwtConfig) -> Option<TokenCacheKey> {
+        if !jwt.token_cache.enabled {
+            return None;
+        }
+        let mut hasher = Sha256::new();
+        hasher.update(token.as_bytes());
+        let sources = [
+            Some(jwt.algorithm.as_str()),
+            Some(jwt.secret.as_str()),
+            jwt.secret_file.as_deref(),
+            jwt.rsa_public_key_path.as_deref(),
+            jwt.ecdsa_public_key_path.as_deref(),
+            jwt.eddsa_public_key_path.as_deref(),
+            jwt.jwks_url.as_deref(),
+        ];
+        for source in sources {
+            hasher.update([0]);
+            hasher.update(source.unwrap_or_default().as_bytes());
+        }
+        for key in &jwt.keys {
+            for part in [
+                Some(key.id.as_str()),
+                Some(key.algorithm.as_str()),
+                key.secret.as_deref(),
+                key.path.as_deref(),
+            ] {
+                hasher.update([0]);
+                hasher.update(part.unwrap_or_default().as_bytes());
+            }
+        }
+        Some(hasher.finalize().into())
+    }
+
+    /// Claims of a cached, unexpired token
+    pub fn get(&self, key: &TokenCacheKey) -> Option<Claims> {
+        let mut entries = self.entries.lock();
+        let claims = match entries.get(key) {
+            Some(entry) if entry.expires_at > Instant::now() => Some(entry.claims.clone()),
+            Some(_) => {
+                entries.remove(key);
+                None
+            }
+            None => None,
+        };
+        Metrics::global().increment_jwt_token_cache(claims.is_some());
+        claims
+    }
+
+    /// Remember a verified token until its `exp` or the configured TTL
+    ///
+    /// Tokens at or past `exp` are not cached. When the cache is full,
+    /// expired entries are dropped first; if it is still full the token is
+    /// not cached.
+    pub fn insert(&self, key: TokenCacheKey, claims: &Claims, config: &TokenCacheConfig) {
+        let mut ttl = Duration::from_secs(config.ttl_secs);
+        if let Some(exp) = claims.exp {
+            let now = unix_now();
+            if exp <= now {
+                return;
+            }
+            ttl = ttl.min(Duration::from_secs(exp - now));
+        }
+
+        let now = Instant::now();
+        let mut entries = self.entries.lock();
+        if entries.len() >= config.max_entries {
+            entries.retain(|_, entry| entry.expires_at > now);
+            if entries.len() >= config.max_entries {
+                return;
+            }
+        }
+        entries.insert(
+            key,
+            CachedToken {
+                claims: claims.clone(),
+                expires_at: now + ttl,
+            },
+        );
+    }
+
+    /// Drop every cached token (after a key rotation)
+    pub fn clear(&self) {
+        self.entries.lock().clear();
+    }
+
+    /// Number of cached tokens
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    /// Check if no tokens are cached
+    pub fn is_empty(&self) -> bool {
+        self.entries.lock().is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claims(exp: Option<u64>) -> Claims {
+        Claims {
+            sub: Some("user".to_string()),
+            exp,
+            iat: None,
+            nbf: None,
+            iss: None,
+            custom: serde_json::Map::new(),
+        }
+    }
+
+    #[test]
+    fn test_token_cache_keys_by_key_source_and_respects_exp() {
+        let mut jwt: JwtConfig =
+            serde_yaml::from_str("enabled: true\nalgorithm: HS256\nsecret: one").unwrap();
+        assert!(TokenCache::key("t", &jwt).is_none());
+
+        jwt.token_cache.enabled = true;
+        let key = TokenCache::key("t", &jwt).unwrap();
+        let mut rotated = jwt.clone();
+        rotated.secret = "two".to_string();
+        assert_ne!(Some(key), TokenCache::key("t", &rotated));
+
+        let cache = TokenCache::default();
+        assert!(cache.get(&key).is_none());
+        cache.insert(key, &claims(None), &jwt.token_cache);
+        assert_eq!(cache.get(&key).unwrap().sub.as_deref(), Some("user"));
+
+        // Already expired tokens are never cached
+        let expired = TokenCache::key("expired", &jwt).unwrap();
+        cache.insert(expired, &claims(Some(1)), &jwt.token_cache);
+        assert!(cache.get(&expired).is_none());
+
+        // A full cache does not grow
+        let config = TokenCacheConfig {
+            max_entries: 1,
+            ..jwt.token_cache.clone()
+        };
+        let other = TokenCache::key("other", &jwt).unwrap();
+        cache.insert(other, &claims(None), &config);
+        assert!(cache.get(&other).is_none());
+
+        cache.clear();
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,144 @@
+//! Cache admission: whether an upstream response may populate the cache.
+//!
+//! Every skipped population has a [`CacheSkipReason`], counted in
+//! `yatagarasu_cache_population_skipped_total{reason}` so operators can see
+//! why objects are not being cached. A response is admitted only when:
+//!
+//! - its status is 200 (206 and 304 never replace a full cached body)
+//! - `Cache-Control` allows a shared cache to store it (no `no-store`,
+//!   `private`, or `max-age=0` without `stale-while-revalidate`)
+//! - it carries a validator (`ETag` or `Last-Modified`), so the entry can be
+//!   revalidated and served to conditional requests
+//!
+//! The size limit is enforced while the body is buffered and counted under
+//! [`CacheSkipReason::TooLarge`]. Objects in a bucket size class with
+//! `cache: none` are counted under [`CacheSkipReason::SizeClass`], and
+//! responses whose `Expires` has already passed under
+//! [`CacheSkipReason::Expired`]. A bucket's own `cache` block can exclude
+//! objects by path ([`CacheSkipReason::BucketPolicy`]) or Content-Type
+//! ([`CacheSkipReason::ContentType`]); see [`super::policy`].
+
+use super::CacheControl;
+
+/// Why a response was not stored in the cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheSkipReason {
+    /// 206 Partial Content: only a slice of the object
+    PartialContent,
+    /// 304 Not Modified: no body to store
+    NotModified,
+    /// Any other non-200 status
+    UncacheableStatus,
+    /// `Cache-Control: no-store`
+    NoStore,
+    /// `Cache-Control: private`
+    Private,
+    /// Immediately stale (`max-age=0` without `stale-while-revalidate`)
+    ZeroMaxAge,
+    /// Neither `ETag` nor `Last-Modified`
+    MissingValidator,
+    /// Body exceeded the cacheable size
+    TooLarge,
+    /// The object's size class is never cached
+    SizeClass,
+    /// `Expires` already in the past (see [`super::ttl`])
+    Expired,
+    /// Caching disabled for the bucket, or the key excluded by its path patterns
+    BucketPolicy,
+    /// Content-Type not cached for the bucket
+    ContentType,
+}
+
+impl CacheSkipReason {
+    /// Label used in metrics and logs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::PartialContent => "partial_content",
+            Self::NotModified => "not_modified",
+            Self::UncacheableStatus => "uncacheable_status",
+            Self::NoStore => "no_store",
+            Self::Private => "private",
+            Self::ZeroMaxAge => "zero_max_age",
+            Self::MissingValidator => "missing_validator",
+            Self::TooLarge => "too_large",
+            Self::SizeClass => "size_class",
+            Self::Expired => "expired",
+            Self::BucketPolicy => "bucket_policy",
+            Self::ContentType => "content_type",
+        }
+    }
+}
+
+/// Decide whether a response with these headers may be cached
+pub fn admit(
+    status: u16,
+    cache_control: &CacheControl,
+    etag: Option<&str>,
+    last_modified: Option<&str>,
+) -> Result<(), CacheSkipReason> {
+    match status {
+        200 => {}
+        206 => return Err(CacheSkipReason::PartialContent),
+        304 => return Err(CacheSkipReason::NotModified),
+        _ => return Err(CacheSkipReason::UncacheableStatus),
+    }
+    if cache_control.no_store {
+        return Err(CacheSkipReason::NoStore);
+    }
+    if cache_control.private {
+        return Err(CacheSkipReason::Private);
+    }
+    if !cache_control.should_store() {
+        return Err(CacheSkipReason::ZeroMaxAge);
+    }
+    let has_validator =
+        etag.is_some_and(|e| !e.is_empty()) || last_modified.is_some_and(|lm| !lm.is_empty());
+    if !has_validator {
+        return Err(CacheSkipReason::MissingValidator);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_admit_requires_200_storable_and_validator() {
+        let public = CacheControl::parse("public, max-age=60");
+        assert_eq!(admit(200, &public, Some("abc"), None), Ok(()));
+        assert_eq!(
+            admit(200, &public, None, Some("Wed, 01 Jan 2025 00:00:00 GMT")),
+            Ok(())
+        );
+
+        assert_eq!(
+            admit(206, &public, Some("abc"), None),
+            Err(CacheSkipReason::PartialContent)
+        );
+        assert_eq!(
+            admit(304, &public, Some("abc"), None),
+            Err(CacheSkipReason::NotModified)
+        );
+        assert_eq!(
+            admit(404, &public, Some("abc"), None),
+            Err(CacheSkipReason::UncacheableStatus)
+        );
+        assert_eq!(
+            admit(200, &CacheControl::parse("no-store"), Some("abc"), None),
+            Err(CacheSkipReason::NoStore)
+        );
+        assert_eq!(
+            admit(200, &CacheControl::parse("private"), Some("abc"), None),
+            Err(CacheSkipReason::Private)
+        );
+        assert_eq!(
+            admit(200, &CacheControl::parse("max-age=0"), Some("abc"), None),
+            Err(CacheSkipReason::ZeroMaxAge)
+        );
+        assert_eq!(
+            admit(200, &public, Some(""), None),
+            Err(CacheSkipReason::MissingValidator)
+        );
+    }
+}
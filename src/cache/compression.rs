@@ -0,0 +1,133 @@
+//! Compression of cache entry bodies.
+//!
+//! With `cache.compression.enabled`, [`TieredCache`](crate::cache::tiered::TieredCache)
+//! stores large, compressible entries zstd-compressed in every layer and marks
+//! them with [`CacheEntry::content_encoding`]. `Cache::get` returns decoded
+//! entries; the proxy reads hits with `Cache::get_encoded` and passes the
+//! compressed body straight through to clients that accept zstd.
+
+use bytes::Bytes;
+
+use crate::cache::{CacheCompressionConfig, CacheEntry, CacheError};
+use crate::compression::response::is_compressible_content_type;
+
+/// `content_encoding` value (and Content-Encoding token) for zstd entries
+pub const ZSTD_ENCODING: &str = "zstd";
+
+/// Compress an entry for storage if it qualifies
+///
+/// Entries below `min_size_bytes`, with an incompressible Content-Type, or
+/// that would not shrink are returned unchanged.
+pub fn compress_entry(mut entry: CacheEntry, config: &CacheCompressionConfig) -> CacheEntry {
+    if !config.enabled
+        || entry.content_encoding.is_some()
+        || entry.data.len() < config.min_size_bytes
+        || !is_compressible_content_type(Some(&entry.content_type))
+    {
+        return entry;
+    }
+
+    match zstd::bulk::compress(&entry.data, config.level) {
+        Ok(compressed) if compressed.len() < entry.data.len() => {
+            entry.data = Bytes::from(compressed);
+            entry.content_encoding = Some(ZSTD_ENCODING.to_string());
+            entry
+        }
+        Ok(_) => entry,
+        Err(e) => {
+            tracing::debug!(error = %e, "Cache entry compression failed, storing uncompressed");
+            entry
+        }
+    }
+}
+
+/// Decode a stored entry back to the original bytes
+pub fn decode_entry(mut entry: CacheEntry) -> Result<CacheEntry, CacheError> {
+    match entry.content_encoding.as_deref() {
+        None => Ok(entry),
+        Some(ZSTD_ENCODING) => {
+            let data = zstd::stream::decode_all(entry.data.as_ref()).map_err(|e| {
+                CacheError::SerializationError(format!("zstd decoding failed: {}", e))
+            })?;
+            entry.content_length = data.len();
+            entry.data = Bytes::from(data);
+            entry.content_encoding = None;
+            Ok(entry)
+        }
+        Some(other) => Err(CacheError::SerializationError(format!(
+            "Unsupported cache entry encoding: {}",
+            other
+        ))),
+    }
+}
+
+/// Check whether an Accept-Encoding header allows zstd (`q=0` refuses it)
+pub fn accepts_zstd(accept_encoding: Option<&str>) -> bool {
+    accept_encoding.is_some_and(|header| {
+        header.split(',').any(|part| {
+            let mut params = part.split(';');
+            let coding = params.next().unwrap_or_default().trim();
+            let quality = params
+                .filter_map(|p| p.trim().strip_prefix("q="))
+                .find_map(|q| q.trim().parse::<f32>().ok())
+                .unwrap_or(1.0);
+            coding.eq_ignore_ascii_case(ZSTD_ENCODING) && quality > 0.0
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(content_type: &str, data: Vec<u8>) -> CacheEntry {
+        CacheEntry::new(
+            Bytes::from(data),
+            content_type.to_string(),
+            "etag".to_string(),
+            None,
+            None,
+        )
+    }
+
+    #[test]
+    fn test_compress_entry_round_trip_and_thresholds() {
+        let config = CacheCompressionConfig {
+            enabled: true,
+            min_size_bytes: 1024,
+            level: 3,
+        };
+        let text = "hello cache ".repeat(1000).into_bytes();
+
+        let stored = compress_entry(entry("text/plain", text.clone()), &config);
+        assert_eq!(stored.content_encoding.as_deref(), Some(ZSTD_ENCODING));
+        assert!(stored.data.len() < text.len());
+        assert_eq!(stored.content_length, text.len());
+        let decoded = decode_entry(stored).unwrap();
+        assert_eq!(decoded.data.as_ref(), text.as_slice());
+        assert_eq!(decoded.content_encoding, None);
+
+        // Too small, incompressible type, or disabled: stored as-is
+        let small = compress_entry(entry("text/plain", b"short".to_vec()), &config);
+        assert_eq!(small.content_encoding, None);
+        let image = compress_entry(entry("image/jpeg", text.clone()), &config);
+        assert_eq!(image.content_encoding, None);
+        let disabled = CacheCompressionConfig {
+            enabled: false,
+            ..config
+        };
+        assert_eq!(
+            compress_entry(entry("text/plain", text), &disabled).content_encoding,
+            None
+        );
+    }
+
+    #[test]
+    fn test_accepts_zstd() {
+        assert!(accepts_zstd(Some("gzip, zstd")));
+        assert!(accepts_zstd(Some("br;q=1.0, ZSTD;q=0.5")));
+        assert!(!accepts_zstd(Some("zstd;q=0")));
+        assert!(!accepts_zstd(Some("gzip, br")));
+        assert!(!accepts_zstd(None));
+    }
+}
@@ -4,11 +4,25 @@
 //! - Memory cache configuration
 //! - Disk cache configuration
 //! - Redis cache configuration
-//! - Per-bucket cache overrides
+//! - Layer quarantine (automatic skipping of failing layers)
+//! - Hot key journal (startup warming of popular keys)
+//! - Entry compression (zstd above a size threshold)
+//! - Chunked caching of Range responses
+//! - Memory budget for responses buffered for cache population
+//! - Per-bucket cache overrides, including A/B cache policy experiments
 
 use serde::{Deserialize, Serialize};
 
-use crate::constants::{DEFAULT_MAX_CACHE_SIZE_MB, DEFAULT_MAX_ITEM_SIZE_MB, DEFAULT_TTL_SECONDS};
+use crate::constants::{
+    DEFAULT_CACHE_COMPRESSION_LEVEL, DEFAULT_CACHE_COMPRESSION_MIN_SIZE_BYTES,
+    DEFAULT_CACHE_EXPERIMENT_SPLIT_PERCENT, DEFAULT_CACHE_MAX_BUFFER_MEMORY_MB,
+    DEFAULT_CACHE_PRESERVE_HEADERS, DEFAULT_CACHE_QUARANTINE_COOLDOWN_SECS,
+    DEFAULT_CACHE_QUARANTINE_ERROR_THRESHOLD, DEFAULT_CACHE_QUARANTINE_WINDOW_SECS,
+    DEFAULT_CACHE_RANGE_CHUNK_SIZE_BYTES, DEFAULT_CACHE_RANGE_MAX_CHUNKS,
+    DEFAULT_HOT_KEY_JOURNAL_FLUSH_INTERVAL_SECS, DEFAULT_HOT_KEY_JOURNAL_TOP_K,
+    DEFAULT_HOT_KEY_JOURNAL_WARM_CONCURRENCY, DEFAULT_MAX_CACHE_SIZE_MB, DEFAULT_MAX_ITEM_SIZE_MB,
+    DEFAULT_TTL_SECONDS,
+};
 
 use super::sendfile::SendfileConfig;
 use super::warming::PrewarmConfig;
@@ -28,6 +42,27 @@ pub struct CacheConfig {
     pub warming: Option<PrewarmConfig>,
     #[serde(default = "default_cache_layers")]
     pub cache_layers: Vec<String>,
+    /// Skip disk/redis layers that keep failing (default: enabled)
+    #[serde(default)]
+    pub quarantine: LayerQuarantineConfig,
+    /// Journal of hot keys used to warm the memory layer on startup (default: off)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hot_keys: Option<HotKeyJournalConfig>,
+    /// Compress stored entries with zstd above a size threshold (default: off)
+    #[serde(default)]
+    pub compression: CacheCompressionConfig,
+    /// Serve Range requests from fixed-size cached chunks (default: off)
+    #[serde(default)]
+    pub range_chunks: RangeChunkConfig,
+    /// Memory shared by all responses buffered for cache population; beyond
+    /// it, responses are served without being cached (default: 256)
+    #[serde(default = "default_max_buffer_memory_mb")]
+    pub max_buffer_memory_mb: u64,
+    /// Upstream response headers stored with entries and replayed on hits;
+    /// a trailing `*` matches a prefix (default: Cache-Control,
+    /// Content-Disposition, Content-Language, Expires, x-amz-meta-*)
+    #[serde(default = "default_preserve_headers")]
+    pub preserve_headers: Vec<String>,
 }
 
 impl Default for CacheConfig {
@@ -39,6 +74,12 @@ impl Default for CacheConfig {
             redis: RedisCacheConfig::default(),
             warming: None,
             cache_layers: default_cache_layers(),
+            quarantine: LayerQuarantineConfig::default(),
+            hot_keys: None,
+            compression: CacheCompressionConfig::default(),
+            range_chunks: RangeChunkConfig::default(),
+            max_buffer_memory_mb: default_max_buffer_memory_mb(),
+            preserve_headers: default_preserve_headers(),
         }
     }
 }
@@ -47,6 +88,17 @@ fn default_cache_layers() -> Vec<String> {
     vec!["memory".to_string()]
 }
 
+fn default_max_buffer_memory_mb() -> u64 {
+    DEFAULT_CACHE_MAX_BUFFER_MEMORY_MB
+}
+
+fn default_preserve_headers() -> Vec<String> {
+    DEFAULT_CACHE_PRESERVE_HEADERS
+        .iter()
+        .map(|name| name.to_string())
+        .collect()
+}
+
 impl CacheConfig {
     /// Validate cache configuration
     pub fn validate(&self) -> Result<(), String> {
@@ -54,6 +106,17 @@ impl CacheConfig {
         self.memory.validate()?;
         self.disk.validate()?;
         self.redis.validate()?;
+        self.quarantine.validate()?;
+        self.compression.validate()?;
+        self.range_chunks
+            .validate(self.memory.max_item_size_bytes())?;
+        if self.enabled && self.max_buffer_memory_mb == 0 {
+            return Err("cache.max_buffer_memory_mb must be greater than 0".to_string());
+        }
+        if let Some(hot_keys) = &self.hot_keys {
+            hot_keys.validate()?;
+        }
+        super::preserve::validate(&self.preserve_headers)?;
 
         // Validate cache_layers
         if self.enabled && self.cache_layers.is_empty() {
@@ -164,6 +227,12 @@ pub struct DiskCacheConfig {
     /// sendfile configuration for zero-copy file serving (Linux)
     #[serde(default)]
     pub sendfile: SendfileConfig,
+    /// Store identical bodies once, shared by every key that caches them (default: false)
+    ///
+    /// Applies to the disk layer only; the memory and redis layers keep one
+    /// copy per key.
+    #[serde(default)]
+    pub dedup: bool,
 }
 
 impl Default for DiskCacheConfig {
@@ -173,6 +242,7 @@ impl Default for DiskCacheConfig {
             cache_dir: default_cache_dir(),
             max_disk_cache_size_mb: default_max_disk_cache_size_mb(),
             sendfile: SendfileConfig::default(),
+            dedup: false,
         }
     }
 }
@@ -254,6 +324,222 @@ impl RedisCacheConfig {
     }
 }
 
+/// Automatic quarantine of failing disk/redis layers
+///
+/// When a layer returns `error_threshold` errors within `window_secs`, it is
+/// skipped for `cooldown_secs` and requests go straight to the next layer
+/// (or S3) instead of paying the error latency every time.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LayerQuarantineConfig {
+    #[serde(default = "default_quarantine_enabled")]
+    pub enabled: bool,
+    #[serde(default = "default_quarantine_error_threshold")]
+    pub error_threshold: u32,
+    #[serde(default = "default_quarantine_window_secs")]
+    pub window_secs: u64,
+    #[serde(default = "default_quarantine_cooldown_secs")]
+    pub cooldown_secs: u64,
+}
+
+impl Default for LayerQuarantineConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_quarantine_enabled(),
+            error_threshold: default_quarantine_error_threshold(),
+            window_secs: default_quarantine_window_secs(),
+            cooldown_secs: default_quarantine_cooldown_secs(),
+        }
+    }
+}
+
+fn default_quarantine_enabled() -> bool {
+    true
+}
+
+fn default_quarantine_error_threshold() -> u32 {
+    DEFAULT_CACHE_QUARANTINE_ERROR_THRESHOLD
+}
+
+fn default_quarantine_window_secs() -> u64 {
+    DEFAULT_CACHE_QUARANTINE_WINDOW_SECS
+}
+
+fn default_quarantine_cooldown_secs() -> u64 {
+    DEFAULT_CACHE_QUARANTINE_COOLDOWN_SECS
+}
+
+impl LayerQuarantineConfig {
+    /// Validate quarantine configuration
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.error_threshold == 0 {
+            return Err("cache.quarantine.error_threshold must be greater than 0".to_string());
+        }
+        if self.window_secs == 0 {
+            return Err("cache.quarantine.window_secs must be greater than 0".to_string());
+        }
+        if self.cooldown_secs == 0 {
+            return Err("cache.quarantine.cooldown_secs must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Hot key journal for startup cache warming
+///
+/// Request counts per key are kept for roughly the `top_k` most requested
+/// objects and written to `path` every `flush_interval_secs`. On startup the
+/// journal is read back and those objects are fetched into the cache, so a
+/// fresh deploy does not start from an empty memory layer.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotKeyJournalConfig {
+    /// Journal file location (written atomically via a temporary file)
+    pub path: String,
+    #[serde(default = "default_hot_key_top_k")]
+    pub top_k: usize,
+    #[serde(default = "default_hot_key_flush_interval_secs")]
+    pub flush_interval_secs: u64,
+    #[serde(default = "default_hot_key_warm_concurrency")]
+    pub warm_concurrency: usize,
+}
+
+fn default_hot_key_top_k() -> usize {
+    DEFAULT_HOT_KEY_JOURNAL_TOP_K
+}
+
+fn default_hot_key_flush_interval_secs() -> u64 {
+    DEFAULT_HOT_KEY_JOURNAL_FLUSH_INTERVAL_SECS
+}
+
+fn default_hot_key_warm_concurrency() -> usize {
+    DEFAULT_HOT_KEY_JOURNAL_WARM_CONCURRENCY
+}
+
+impl HotKeyJournalConfig {
+    /// Validate hot key journal configuration
+    pub fn validate(&self) -> Result<(), String> {
+        if self.path.trim().is_empty() {
+            return Err("cache.hot_keys.path cannot be empty".to_string());
+        }
+        if self.top_k == 0 {
+            return Err("cache.hot_keys.top_k must be greater than 0".to_string());
+        }
+        if self.flush_interval_secs == 0 {
+            return Err("cache.hot_keys.flush_interval_secs must be greater than 0".to_string());
+        }
+        if self.warm_concurrency == 0 {
+            return Err("cache.hot_keys.warm_concurrency must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Compression of cache entry bodies
+///
+/// Entries of at least `min_size_bytes` with a compressible Content-Type are
+/// stored zstd-compressed in every layer, which fits more objects into the
+/// same memory and disk budget at the cost of CPU on writes and reads.
+/// Entries that do not shrink are stored as-is.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheCompressionConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default = "default_compression_min_size_bytes")]
+    pub min_size_bytes: usize,
+    /// zstd level, 1 (fastest) to 22 (smallest)
+    #[serde(default = "default_compression_level")]
+    pub level: i32,
+}
+
+impl Default for CacheCompressionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_size_bytes: default_compression_min_size_bytes(),
+            level: default_compression_level(),
+        }
+    }
+}
+
+fn default_compression_min_size_bytes() -> usize {
+    DEFAULT_CACHE_COMPRESSION_MIN_SIZE_BYTES
+}
+
+fn default_compression_level() -> i32 {
+    DEFAULT_CACHE_COMPRESSION_LEVEL
+}
+
+impl CacheCompressionConfig {
+    /// Validate compression configuration
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if !(1..=22).contains(&self.level) {
+            return Err("cache.compression.level must be between 1 and 22".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Chunked caching of Range responses
+///
+/// Objects are cached in fixed-size chunks keyed by offset. `206` responses
+/// store every chunk they cover completely; a later Range request whose
+/// chunks are all cached is assembled from them without contacting S3.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RangeChunkConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Chunk size; changing it starts a new set of chunks
+    #[serde(default = "default_range_chunk_size_bytes")]
+    pub chunk_size_bytes: u64,
+    /// Most chunks assembled into one response; larger ranges go to S3
+    #[serde(default = "default_range_max_chunks")]
+    pub max_chunks_per_response: u64,
+}
+
+impl Default for RangeChunkConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            chunk_size_bytes: default_range_chunk_size_bytes(),
+            max_chunks_per_response: default_range_max_chunks(),
+        }
+    }
+}
+
+fn default_range_chunk_size_bytes() -> u64 {
+    DEFAULT_CACHE_RANGE_CHUNK_SIZE_BYTES
+}
+
+fn default_range_max_chunks() -> u64 {
+    DEFAULT_CACHE_RANGE_MAX_CHUNKS
+}
+
+impl RangeChunkConfig {
+    /// Validate chunk sizing against the memory layer's item size limit
+    pub fn validate(&self, max_item_size_bytes: u64) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.chunk_size_bytes < 4096 || self.chunk_size_bytes > max_item_size_bytes {
+            return Err(format!(
+                "cache.range_chunks.chunk_size_bytes must be between 4096 and memory.max_item_size_mb ({} bytes)",
+                max_item_size_bytes
+            ));
+        }
+        if self.max_chunks_per_response == 0 {
+            return Err(
+                "cache.range_chunks.max_chunks_per_response must be greater than 0".to_string(),
+            );
+        }
+        Ok(())
+    }
+}
+
 /// Per-bucket cache override configuration
 /// This can be included in BucketConfig to override global cache settings
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -267,6 +553,130 @@ pub struct BucketCacheOverride {
     /// Override: custom max item size for this bucket (MB)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub max_item_size_mb: Option<u64>,
+    /// HEAD-before-GET: hits older than this (seconds) are confirmed with a
+    /// HEAD to S3 and refetched if the object changed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub soft_ttl_seconds: Option<u64>,
+    /// Shortest TTL for entries whose lifetime comes from upstream headers
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_ttl_seconds: Option<u64>,
+    /// Longest TTL for any entry, whatever upstream headers say
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_ttl_seconds: Option<u64>,
+    /// Ignore upstream `max-age`, `s-maxage` and `Expires`, always using
+    /// `ttl_seconds` (default: false)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ignore_upstream_ttl: Option<bool>,
+    /// A/B experiment between two cache policy variants
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub experiment: Option<CacheExperimentConfig>,
+    /// Only object keys matching one of these globs are cached (default: all)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include_paths: Vec<String>,
+    /// Object keys matching one of these globs are never cached
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_paths: Vec<String>,
+    /// Only these Content-Types are cached, e.g. `image/*` (default: all)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub content_types: Vec<String>,
+    /// These Content-Types are never cached, e.g. `text/html`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude_content_types: Vec<String>,
+}
+
+fn default_experiment_split_percent() -> u8 {
+    DEFAULT_CACHE_EXPERIMENT_SPLIT_PERCENT
+}
+
+/// A/B experiment between two cache policies for one bucket
+///
+/// Each request is assigned to a variant from the `header` value, else the
+/// `cookie` value, else the client IP, so the same caller always lands in
+/// the same variant.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheExperimentConfig {
+    /// Experiment name, used in metrics and to seed the assignment
+    pub name: String,
+    /// Request header whose value assigns the variant
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub header: Option<String>,
+    /// Cookie whose value assigns the variant (e.g. a session cookie)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cookie: Option<String>,
+    /// Percent of callers assigned to the second variant
+    #[serde(default = "default_experiment_split_percent")]
+    pub split_percent: u8,
+    /// Exactly two variants: control first, candidate second
+    pub variants: Vec<CacheVariantConfig>,
+}
+
+/// One cache policy under test
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CacheVariantConfig {
+    /// Variant name, used in metrics
+    pub name: String,
+    /// Hits older than this are treated as misses for this variant
+    pub ttl_seconds: u64,
+}
+
+impl CacheExperimentConfig {
+    /// Validate names, variant count and split
+    pub fn validate(&self) -> Result<(), String> {
+        let valid_name = |name: &str| {
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        };
+        if !valid_name(&self.name) {
+            return Err(format!(
+                "experiment name '{}' must be non-empty and use only letters, digits, '-' and '_'",
+                self.name
+            ));
+        }
+        if self.variants.len() != 2 {
+            return Err(format!(
+                "experiment '{}' must have exactly 2 variants, found {}",
+                self.name,
+                self.variants.len()
+            ));
+        }
+        for variant in &self.variants {
+            if !valid_name(&variant.name) {
+                return Err(format!(
+                    "experiment '{}': variant name '{}' must be non-empty and use only letters, digits, '-' and '_'",
+                    self.name, variant.name
+                ));
+            }
+            if variant.ttl_seconds == 0 {
+                return Err(format!(
+                    "experiment '{}': variant '{}' ttl_seconds must be greater than 0",
+                    self.name, variant.name
+                ));
+            }
+        }
+        if self.variants[0].name == self.variants[1].name {
+            return Err(format!(
+                "experiment '{}': variant names must differ",
+                self.name
+            ));
+        }
+        if self.split_percent > 100 {
+            return Err(format!(
+                "experiment '{}': split_percent must be between 0 and 100",
+                self.name
+            ));
+        }
+        if self.header.as_ref().is_some_and(|h| h.trim().is_empty())
+            || self.cookie.as_ref().is_some_and(|c| c.trim().is_empty())
+        {
+            return Err(format!(
+                "experiment '{}': header and cookie cannot be empty",
+                self.name
+            ));
+        }
+        Ok(())
+    }
 }
 
 impl BucketCacheOverride {
@@ -312,7 +722,31 @@ impl BucketCacheOverride {
             }
         }
 
-        Ok(())
+        // Validate soft TTL if specified (0 would HEAD on every hit)
+        if let Some(soft_ttl) = self.soft_ttl_seconds {
+            if soft_ttl == 0 {
+                return Err("soft_ttl_seconds must be greater than 0".to_string());
+            }
+        }
+
+        // Validate TTL clamps if specified
+        if self.min_ttl_seconds == Some(0) || self.max_ttl_seconds == Some(0) {
+            return Err("min_ttl_seconds and max_ttl_seconds must be greater than 0".to_string());
+        }
+        if let (Some(min), Some(max)) = (self.min_ttl_seconds, self.max_ttl_seconds) {
+            if min > max {
+                return Err(format!(
+                    "min_ttl_seconds ({}) must not exceed max_ttl_seconds ({})",
+                    min, max
+                ));
+            }
+        }
+
+        if let Some(experiment) = &self.experiment {
+            experiment.validate()?;
+        }
+
+        super::policy::validate(self)
     }
 }
 
@@ -343,6 +777,12 @@ enabled: false
             redis: RedisCacheConfig::default(),
             warming: None,
             cache_layers: vec!["memory".to_string()],
+            quarantine: LayerQuarantineConfig::default(),
+            hot_keys: None,
+            compression: CacheCompressionConfig::default(),
+            range_chunks: RangeChunkConfig::default(),
+            max_buffer_memory_mb: default_max_buffer_memory_mb(),
+            preserve_headers: default_preserve_headers(),
         };
         assert!(config.enabled);
 
@@ -353,6 +793,12 @@ enabled: false
             redis: RedisCacheConfig::default(),
             warming: None,
             cache_layers: vec!["memory".to_string()],
+            quarantine: LayerQuarantineConfig::default(),
+            hot_keys: None,
+            compression: CacheCompressionConfig::default(),
+            range_chunks: RangeChunkConfig::default(),
+            max_buffer_memory_mb: default_max_buffer_memory_mb(),
+            preserve_headers: default_preserve_headers(),
         };
         assert!(!config.enabled);
     }
@@ -571,6 +1017,7 @@ disk:
             cache_dir: String::new(),
             max_disk_cache_size_mb: 10240,
             sendfile: SendfileConfig::default(),
+            dedup: false,
         };
         let result = config.validate();
         assert!(result.is_err());
@@ -584,6 +1031,7 @@ disk:
             cache_dir: String::new(),
             max_disk_cache_size_mb: 10240,
             sendfile: SendfileConfig::default(),
+            dedup: false,
         };
         assert!(config.validate().is_ok());
     }
@@ -911,6 +1359,15 @@ max_item_size_mb: 5
             enabled: Some(false),
             ttl_seconds: None,
             max_item_size_mb: None,
+            soft_ttl_seconds: None,
+            min_ttl_seconds: None,
+            max_ttl_seconds: None,
+            ignore_upstream_ttl: None,
+            experiment: None,
+            include_paths: Vec::new(),
+            exclude_paths: Vec::new(),
+            content_types: Vec::new(),
+            exclude_content_types: Vec::new(),
         };
 
         let global = CacheConfig {
@@ -920,6 +1377,12 @@ max_item_size_mb: 5
             redis: RedisCacheConfig::default(),
             warming: None,
             cache_layers: vec!["memory".to_string()],
+            quarantine: LayerQuarantineConfig::default(),
+            hot_keys: None,
+            compression: CacheCompressionConfig::default(),
+            range_chunks: RangeChunkConfig::default(),
+            max_buffer_memory_mb: default_max_buffer_memory_mb(),
+            preserve_headers: default_preserve_headers(),
         };
 
         let merged = override_config.merge_with_global(&global);
@@ -932,6 +1395,15 @@ max_item_size_mb: 5
             enabled: None,
             ttl_seconds: Some(600),
             max_item_size_mb: None,
+            soft_ttl_seconds: None,
+            min_ttl_seconds: None,
+            max_ttl_seconds: None,
+            ignore_upstream_ttl: None,
+            experiment: None,
+            include_paths: Vec::new(),
+            exclude_paths: Vec::new(),
+            content_types: Vec::new(),
+            exclude_content_types: Vec::new(),
         };
 
         let global = CacheConfig {
@@ -941,6 +1413,12 @@ max_item_size_mb: 5
             redis: RedisCacheConfig::default(),
             warming: None,
             cache_layers: vec!["memory".to_string()],
+            quarantine: LayerQuarantineConfig::default(),
+            hot_keys: None,
+            compression: CacheCompressionConfig::default(),
+            range_chunks: RangeChunkConfig::default(),
+            max_buffer_memory_mb: default_max_buffer_memory_mb(),
+            preserve_headers: default_preserve_headers(),
         };
 
         let merged = override_config.merge_with_global(&global);
@@ -954,6 +1432,15 @@ max_item_size_mb: 5
             enabled: None,
             ttl_seconds: None,
             max_item_size_mb: Some(50),
+            soft_ttl_seconds: None,
+            min_ttl_seconds: None,
+            max_ttl_seconds: None,
+            ignore_upstream_ttl: None,
+            experiment: None,
+            include_paths: Vec::new(),
+            exclude_paths: Vec::new(),
+            content_types: Vec::new(),
+            exclude_content_types: Vec::new(),
         };
 
         let global = CacheConfig {
@@ -963,6 +1450,12 @@ max_item_size_mb: 5
             redis: RedisCacheConfig::default(),
             warming: None,
             cache_layers: vec!["memory".to_string()],
+            quarantine: LayerQuarantineConfig::default(),
+            hot_keys: None,
+            compression: CacheCompressionConfig::default(),
+            range_chunks: RangeChunkConfig::default(),
+            max_buffer_memory_mb: default_max_buffer_memory_mb(),
+            preserve_headers: default_preserve_headers(),
         };
 
         let merged = override_config.merge_with_global(&global);
@@ -975,6 +1468,15 @@ max_item_size_mb: 5
             enabled: None,
             ttl_seconds: None,
             max_item_size_mb: None,
+            soft_ttl_seconds: None,
+            min_ttl_seconds: None,
+            max_ttl_seconds: None,
+            ignore_upstream_ttl: None,
+            experiment: None,
+            include_paths: Vec::new(),
+            exclude_paths: Vec::new(),
+            content_types: Vec::new(),
+            exclude_content_types: Vec::new(),
         };
 
         let global = CacheConfig {
@@ -988,6 +1490,12 @@ max_item_size_mb: 5
             redis: RedisCacheConfig::default(),
             warming: None,
             cache_layers: vec!["memory".to_string()],
+            quarantine: LayerQuarantineConfig::default(),
+            hot_keys: None,
+            compression: CacheCompressionConfig::default(),
+            range_chunks: RangeChunkConfig::default(),
+            max_buffer_memory_mb: default_max_buffer_memory_mb(),
+            preserve_headers: default_preserve_headers(),
         };
 
         let merged = override_config.merge_with_global(&global);
@@ -1002,6 +1510,15 @@ max_item_size_mb: 5
             enabled: None,
             ttl_seconds: None,
             max_item_size_mb: Some(0),
+            soft_ttl_seconds: None,
+            min_ttl_seconds: None,
+            max_ttl_seconds: None,
+            ignore_upstream_ttl: None,
+            experiment: None,
+            include_paths: Vec::new(),
+            exclude_paths: Vec::new(),
+            content_types: Vec::new(),
+            exclude_content_types: Vec::new(),
         };
         let result = override_config.validate();
         assert!(result.is_err());
@@ -1013,6 +1530,15 @@ max_item_size_mb: 5
             enabled: None,
             ttl_seconds: Some(0),
             max_item_size_mb: None,
+            soft_ttl_seconds: None,
+            min_ttl_seconds: None,
+            max_ttl_seconds: None,
+            ignore_upstream_ttl: None,
+            experiment: None,
+            include_paths: Vec::new(),
+            exclude_paths: Vec::new(),
+            content_types: Vec::new(),
+            exclude_content_types: Vec::new(),
         };
         let result = override_config.validate();
         assert!(result.is_err());
@@ -1020,10 +1546,38 @@ max_item_size_mb: 5
             .unwrap_err()
             .contains("ttl_seconds must be greater than 0"));
 
+        let override_config = BucketCacheOverride {
+            soft_ttl_seconds: Some(0),
+            ..Default::default()
+        };
+        let result = override_config.validate();
+        assert!(result
+            .unwrap_err()
+            .contains("soft_ttl_seconds must be greater than 0"));
+
+        let override_config = BucketCacheOverride {
+            min_ttl_seconds: Some(600),
+            max_ttl_seconds: Some(60),
+            ..Default::default()
+        };
+        assert!(override_config
+            .validate()
+            .unwrap_err()
+            .contains("must not exceed max_ttl_seconds"));
+
         let override_config = BucketCacheOverride {
             enabled: Some(true),
             ttl_seconds: Some(300),
             max_item_size_mb: Some(5),
+            soft_ttl_seconds: None,
+            min_ttl_seconds: None,
+            max_ttl_seconds: None,
+            ignore_upstream_ttl: None,
+            experiment: None,
+            include_paths: Vec::new(),
+            exclude_paths: Vec::new(),
+            content_types: Vec::new(),
+            exclude_content_types: Vec::new(),
         };
         assert!(override_config.validate().is_ok());
     }
@@ -1038,10 +1592,17 @@ max_item_size_mb: 5
                 cache_dir: "".to_string(),
                 max_disk_cache_size_mb: 10240,
                 sendfile: SendfileConfig::default(),
+                dedup: false,
             },
             redis: RedisCacheConfig::default(),
             warming: None,
             cache_layers: vec!["memory".to_string()],
+            quarantine: LayerQuarantineConfig::default(),
+            hot_keys: None,
+            compression: CacheCompressionConfig::default(),
+            range_chunks: RangeChunkConfig::default(),
+            max_buffer_memory_mb: default_max_buffer_memory_mb(),
+            preserve_headers: default_preserve_headers(),
         };
 
         let result = config.validate();
@@ -1059,13 +1620,75 @@ max_item_size_mb: 5
                 cache_dir: "".to_string(),
                 max_disk_cache_size_mb: 10240,
                 sendfile: SendfileConfig::default(),
+                dedup: false,
             },
             redis: RedisCacheConfig::default(),
             warming: None,
             cache_layers: vec![],
+            quarantine: LayerQuarantineConfig::default(),
+            hot_keys: None,
+            compression: CacheCompressionConfig::default(),
+            range_chunks: RangeChunkConfig::default(),
+            max_buffer_memory_mb: default_max_buffer_memory_mb(),
+            preserve_headers: default_preserve_headers(),
         };
 
         let result = config.validate();
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_hot_key_journal_config() {
+        let yaml = r#"
+enabled: true
+hot_keys:
+  path: /var/lib/yatagarasu/hot_keys.json
+"#;
+        let config: CacheConfig = serde_yaml::from_str(yaml).unwrap();
+        let hot_keys = config.hot_keys.clone().unwrap();
+        assert_eq!(hot_keys.top_k, 1000);
+        assert_eq!(hot_keys.flush_interval_secs, 60);
+        assert_eq!(hot_keys.warm_concurrency, 8);
+        assert!(config.validate().is_ok());
+
+        let invalid = CacheConfig {
+            hot_keys: Some(HotKeyJournalConfig {
+                top_k: 0,
+                ..hot_keys.clone()
+            }),
+            ..config.clone()
+        };
+        assert!(invalid.validate().unwrap_err().contains("top_k"));
+
+        let invalid = CacheConfig {
+            hot_keys: Some(HotKeyJournalConfig {
+                path: " ".to_string(),
+                ..hot_keys
+            }),
+            ..config
+        };
+        assert!(invalid.validate().unwrap_err().contains("path"));
+    }
+
+    #[test]
+    fn test_range_chunk_config() {
+        let yaml = r#"
+enabled: true
+range_chunks:
+  enabled: true
+"#;
+        let config: CacheConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.range_chunks.chunk_size_bytes, 1024 * 1024);
+        assert_eq!(config.range_chunks.max_chunks_per_response, 16);
+        assert!(config.validate().is_ok());
+
+        // Chunks must fit in the memory layer
+        let mut invalid = config.clone();
+        invalid.range_chunks.chunk_size_bytes = invalid.memory.max_item_size_bytes() + 1;
+        assert!(invalid.validate().unwrap_err().contains("chunk_size_bytes"));
+
+        let mut invalid = config;
+        invalid.range_chunks.max_chunks_per_response = 0;
+        assert!(invalid.validate().is_err());
+    }
 }
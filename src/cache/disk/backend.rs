@@ -11,6 +11,14 @@ pub trait DiskBackend: Send + Sync {
     /// Read entire file contents
     async fn read_file(&self, path: &Path) -> Result<Bytes, DiskCacheError>;
 
+    /// Read entire file contents without copying into a heap buffer where possible
+    ///
+    /// Backends backed by the real filesystem memory-map the file; the default
+    /// falls back to `read_file`.
+    async fn read_file_mapped(&self, path: &Path) -> Result<Bytes, DiskCacheError> {
+        self.read_file(path).await
+    }
+
     /// Write file contents atomically (using temp file + rename)
     async fn write_file_atomic(&self, path: &Path, data: Bytes) -> Result<(), DiskCacheError>;
 
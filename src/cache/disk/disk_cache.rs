@@ -8,6 +8,10 @@ use async_trait::async_trait;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
+use tokio::sync::Mutex;
+
+/// Locks serializing writes and deletes of shared blobs, picked by content hash
+const BLOB_LOCK_STRIPES: usize = 64;
 
 /// Disk-based cache implementation
 pub struct DiskCache {
@@ -22,6 +26,11 @@ pub struct DiskCache {
     miss_count: Arc<AtomicU64>,
     /// sendfile configuration for zero-copy file serving
     sendfile_config: SendfileConfig,
+    /// Store bodies as content-addressed blobs shared by identical entries
+    dedup: bool,
+    /// A blob's reference count only moves between zero and one, and the blob
+    /// is only written or deleted, while its stripe is held
+    blob_locks: Arc<[Mutex<()>]>,
 }
 
 impl Default for DiskCache {
@@ -60,6 +69,33 @@ impl DiskCache {
             hit_count: Arc::new(AtomicU64::new(0)),
             miss_count: Arc::new(AtomicU64::new(0)),
             sendfile_config,
+            dedup: false,
+            blob_locks: (0..BLOB_LOCK_STRIPES).map(|_| Mutex::new(())).collect(),
+        }
+    }
+
+    /// Enable content-addressed storage: identical bodies cached under
+    /// different keys are written once and reference counted
+    pub fn with_dedup(mut self, dedup: bool) -> Self {
+        self.dedup = dedup;
+        self
+    }
+
+    fn blob_lock(&self, content_hash: &str) -> &Mutex<()> {
+        let stripe = content_hash
+            .get(..4)
+            .and_then(|prefix| usize::from_str_radix(prefix, 16).ok())
+            .unwrap_or(0);
+        &self.blob_locks[stripe % self.blob_locks.len()]
+    }
+
+    /// Drop an entry's blob reference, deleting the blob with the last one
+    async fn release_blob(&self, metadata: &super::types::EntryMetadata) {
+        if let Some(content_hash) = &metadata.content_hash {
+            let _guard = self.blob_lock(content_hash).lock().await;
+            if self.index.release_blob(content_hash, metadata.size_bytes) {
+                let _ = self.backend.delete_file(&metadata.file_path).await;
+            }
         }
     }
 }
@@ -92,11 +128,22 @@ impl Cache for DiskCache {
             return Ok(None);
         }
 
-        // Read data file
+        // Read data file (the shared blob in dedup mode)
         let hash = key_to_hash(key);
         let (data_path, _meta_path) = generate_paths(&self.cache_dir, &hash);
+        let data_path = match metadata.content_hash {
+            Some(_) => metadata.file_path.clone(),
+            None => data_path,
+        };
 
-        let data = match self.backend.read_file(&data_path).await {
+        // Large entries are memory-mapped so hits can be served without copying
+        let read_result = if metadata.size_bytes >= crate::cache::zero_copy::MMAP_THRESHOLD_BYTES {
+            self.backend.read_file_mapped(&data_path).await
+        } else {
+            self.backend.read_file(&data_path).await
+        };
+
+        let data = match read_result {
             Ok(d) => d,
             Err(_) => {
                 // File doesn't exist - remove from index
@@ -122,6 +169,8 @@ impl Cache for DiskCache {
                 + std::time::Duration::from_secs(metadata.expires_at),
             last_accessed_at: SystemTime::UNIX_EPOCH
                 + std::time::Duration::from_secs(metadata.last_accessed_at),
+            content_encoding: metadata.content_encoding.clone(),
+            headers: metadata.headers.clone(),
         };
 
         Ok(Some(entry))
@@ -129,11 +178,16 @@ impl Cache for DiskCache {
 
     async fn set(&self, key: CacheKey, entry: CacheEntry) -> Result<(), CacheError> {
         use super::types::EntryMetadata;
-        use super::utils::{generate_paths, key_to_hash};
+        use super::utils::{blob_path, content_hash, generate_paths, key_to_hash};
         use bytes::Bytes;
         use std::time::SystemTime;
 
-        let new_entry_size = entry.data.len() as u64;
+        // In dedup mode a body already on disk takes no extra space
+        let content_hash = self.dedup.then(|| content_hash(&entry.data));
+        let new_entry_size = match &content_hash {
+            Some(hash) if self.index.blob_ref_count(hash) > 0 => 0,
+            _ => entry.data.len() as u64,
+        };
 
         // Evict entries if necessary to make room for new entry
         while self.index.total_size() + new_entry_size > self.max_size_bytes {
@@ -152,11 +206,37 @@ impl Cache for DiskCache {
         // Generate file paths
         let hash = key_to_hash(&key);
         let (data_path, meta_path) = generate_paths(&self.cache_dir, &hash);
+        let data_path = match &content_hash {
+            Some(content_hash) => blob_path(&self.cache_dir, content_hash),
+            None => data_path,
+        };
 
-        // Write data file
-        self.backend
-            .write_file_atomic(&data_path, entry.data.clone())
-            .await?;
+        // Write data file. A shared blob is referenced before deciding whether
+        // to write it, so a concurrent delete cannot remove it under us
+        match &content_hash {
+            Some(content_hash) => {
+                let _guard = self.blob_lock(content_hash).lock().await;
+                if self
+                    .index
+                    .acquire_blob(content_hash, entry.data.len() as u64)
+                {
+                    if let Err(e) = self
+                        .backend
+                        .write_file_atomic(&data_path, entry.data.clone())
+                        .await
+                    {
+                        self.index
+                            .release_blob(content_hash, entry.data.len() as u64);
+                        return Err(e.into());
+                    }
+                }
+            }
+            None => {
+                self.backend
+                    .write_file_atomic(&data_path, entry.data.clone())
+                    .await?;
+            }
+        }
 
         // Create metadata
         let now = SystemTime::now()
@@ -169,7 +249,7 @@ impl Cache for DiskCache {
             .unwrap()
             .as_secs();
 
-        let metadata = EntryMetadata::new(
+        let mut metadata = EntryMetadata::new(
             key.clone(),
             data_path.clone(),
             entry.data.len() as u64,
@@ -179,16 +259,28 @@ impl Cache for DiskCache {
             entry.etag.clone(),
             entry.last_modified.clone(),
         );
+        metadata.content_encoding = entry.content_encoding.clone();
+        metadata.headers = entry.headers.clone();
+        metadata.content_hash = content_hash;
 
         // Write metadata file
-        let meta_json = serde_json::to_string(&metadata)
-            .map_err(|e| CacheError::SerializationError(e.to_string()))?;
-        self.backend
-            .write_file_atomic(&meta_path, Bytes::from(meta_json))
-            .await?;
+        let written = match serde_json::to_string(&metadata) {
+            Ok(meta_json) => self
+                .backend
+                .write_file_atomic(&meta_path, Bytes::from(meta_json))
+                .await
+                .map_err(CacheError::from),
+            Err(e) => Err(CacheError::SerializationError(e.to_string())),
+        };
+        if let Err(e) = written {
+            self.release_blob(&metadata).await;
+            return Err(e);
+        }
 
-        // Update index
-        self.index.insert(key, metadata);
+        // Update index; a replaced entry gives up its own blob reference
+        if let Some(replaced) = self.index.insert(key, metadata) {
+            self.release_blob(&replaced).await;
+        }
 
         Ok(())
     }
@@ -198,7 +290,7 @@ impl Cache for DiskCache {
         use super::utils::key_to_hash;
 
         // Try to remove from index first
-        let _metadata = match self.index.remove(key) {
+        let metadata = match self.index.remove(key) {
             Some(meta) => meta,
             None => return Ok(false), // Entry doesn't exist
         };
@@ -208,7 +300,13 @@ impl Cache for DiskCache {
         let (data_path, meta_path) = generate_paths(&self.cache_dir, &hash);
 
         // Delete both files (ignore errors - index is already updated)
-        let _ = self.backend.delete_file(&data_path).await;
+        // A shared blob is only deleted with its last reference
+        match &metadata.content_hash {
+            Some(_) => self.release_blob(&metadata).await,
+            None => {
+                let _ = self.backend.delete_file(&data_path).await;
+            }
+        }
         let _ = self.backend.delete_file(&meta_path).await;
 
         Ok(true)
@@ -238,6 +336,9 @@ impl Cache for DiskCache {
                 }
             }
         }
+        if self.dedup {
+            let _ = tokio::fs::remove_dir_all(self.cache_dir.join("blobs")).await;
+        }
 
         Ok(())
     }
@@ -255,6 +356,22 @@ impl Cache for DiskCache {
         Ok(count)
     }
 
+    async fn delete_object(&self, bucket: &str, object_key: &str) -> Result<usize, CacheError> {
+        let keys_to_delete: Vec<CacheKey> = self
+            .index
+            .keys_for_bucket(bucket)
+            .into_iter()
+            .filter(|key| key.object_key == object_key)
+            .collect();
+        let count = keys_to_delete.len();
+
+        for key in keys_to_delete {
+            let _ = self.delete(&key).await;
+        }
+
+        Ok(count)
+    }
+
     async fn stats(&self) -> Result<CacheStats, CacheError> {
         Ok(CacheStats {
             hits: self.hit_count.load(Ordering::Relaxed),
@@ -312,6 +429,11 @@ impl Cache for DiskCache {
             return Ok(None);
         }
 
+        // Compressed files must be decoded before they can be served
+        if metadata.content_encoding.is_some() {
+            return Ok(None);
+        }
+
         // Check if sendfile should be used for this file size
         if !self
             .sendfile_config
@@ -320,9 +442,13 @@ impl Cache for DiskCache {
             return Ok(None);
         }
 
-        // Generate file path
+        // Generate file path (the shared blob in dedup mode)
         let hash = key_to_hash(key);
         let (data_path, _meta_path) = generate_paths(&self.cache_dir, &hash);
+        let data_path = match metadata.content_hash {
+            Some(_) => metadata.file_path.clone(),
+            None => data_path,
+        };
 
         // Verify file exists before returning sendfile response
         if self.backend.file_size(&data_path).await.is_err() {
@@ -335,13 +461,14 @@ impl Cache for DiskCache {
         // Note: We don't increment hit count here because the proxy calls
         // cache.get() first, which already tracks the hit. get_sendfile()
         // is only used to check eligibility for zero-copy serving.
-        let response = SendfileResponse::new(
+        let mut response = SendfileResponse::new(
             data_path,
             metadata.size_bytes,
             metadata.content_type.clone(),
             Some(metadata.etag.clone()),
             metadata.last_modified.clone(),
         );
+        response.headers = metadata.headers.clone();
 
         Ok(Some(response))
     }
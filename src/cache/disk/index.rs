@@ -13,9 +13,13 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
 /// Thread-safe in-memory index of cached entries
+///
+/// Entries stored as shared blobs (dedup mode) are reference counted by
+/// content hash; a blob's size is counted once, however many keys use it.
 pub struct CacheIndex {
     entries: Arc<RwLock<HashMap<CacheKey, EntryMetadata>>>,
     total_size: Arc<AtomicU64>,
+    blob_refs: Arc<RwLock<HashMap<String, u64>>>,
 }
 
 impl CacheIndex {
@@ -23,6 +27,7 @@ impl CacheIndex {
         Self {
             entries: Arc::new(RwLock::new(HashMap::new())),
             total_size: Arc::new(AtomicU64::new(0)),
+            blob_refs: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
@@ -30,21 +35,70 @@ impl CacheIndex {
         self.entries.read().get(key).cloned()
     }
 
-    pub fn insert(&self, key: CacheKey, metadata: EntryMetadata) {
+    /// Insert an entry, returning the one it replaced
+    ///
+    /// Shared-blob entries (dedup mode) must hold a reference taken with
+    /// [`Self::acquire_blob`]; the replaced entry's blob reference is not
+    /// released, since the caller may have to delete the blob with it.
+    pub fn insert(&self, key: CacheKey, metadata: EntryMetadata) -> Option<EntryMetadata> {
         let size = metadata.size_bytes;
-        self.entries.write().insert(key, metadata);
-        self.total_size.fetch_add(size, Ordering::SeqCst);
+        let shared = metadata.content_hash.is_some();
+        let replaced = self.entries.write().insert(key, metadata);
+        if !shared {
+            self.total_size.fetch_add(size, Ordering::SeqCst);
+        }
+        if let Some(old) = replaced.as_ref().filter(|old| old.content_hash.is_none()) {
+            self.total_size.fetch_sub(old.size_bytes, Ordering::SeqCst);
+        }
+        replaced
     }
 
+    /// Remove an entry; a shared blob's reference stays held (see [`Self::insert`])
     pub fn remove(&self, key: &CacheKey) -> Option<EntryMetadata> {
         let removed = self.entries.write().remove(key);
-        if let Some(ref metadata) = removed {
+        if let Some(metadata) = removed.as_ref().filter(|m| m.content_hash.is_none()) {
             self.total_size
                 .fetch_sub(metadata.size_bytes, Ordering::SeqCst);
         }
         removed
     }
 
+    /// Number of references to the blob with this content hash
+    pub fn blob_ref_count(&self, hash: &str) -> u64 {
+        self.blob_refs.read().get(hash).copied().unwrap_or(0)
+    }
+
+    /// Take a reference to a blob of `size` bytes; true if it had none, in
+    /// which case the caller must write it
+    pub fn acquire_blob(&self, hash: &str, size: u64) -> bool {
+        let mut refs = self.blob_refs.write();
+        let count = refs.entry(hash.to_string()).or_insert(0);
+        *count += 1;
+        let first = *count == 1;
+        if first {
+            self.total_size.fetch_add(size, Ordering::SeqCst);
+        }
+        first
+    }
+
+    /// Drop a blob reference; true if it was the last, in which case the
+    /// caller must delete the blob
+    pub fn release_blob(&self, hash: &str, size: u64) -> bool {
+        let mut refs = self.blob_refs.write();
+        match refs.get_mut(hash) {
+            Some(count) if *count > 1 => {
+                *count -= 1;
+                false
+            }
+            Some(_) => {
+                refs.remove(hash);
+                self.total_size.fetch_sub(size, Ordering::SeqCst);
+                true
+            }
+            None => false,
+        }
+    }
+
     pub fn total_size(&self) -> u64 {
         self.total_size.load(Ordering::SeqCst)
     }
@@ -55,6 +109,7 @@ impl CacheIndex {
 
     pub fn clear(&self) {
         self.entries.write().clear();
+        self.blob_refs.write().clear();
         self.total_size.store(0, Ordering::SeqCst);
     }
 
@@ -161,19 +216,15 @@ impl CacheIndex {
             }
         };
 
-        // Convert Vec back to HashMap and calculate total size
-        let mut entries_map = HashMap::new();
-        let mut total_size: u64 = 0;
-
+        // Rebuild the index (total size and blob references) entry by entry
+        let index = Self::new();
         for entry in snapshot.entries {
-            total_size += entry.metadata.size_bytes;
-            entries_map.insert(entry.key, entry.metadata);
+            if let Some(hash) = &entry.metadata.content_hash {
+                index.acquire_blob(hash, entry.metadata.size_bytes);
+            }
+            index.insert(entry.key, entry.metadata);
         }
-
-        Ok(Self {
-            entries: Arc::new(RwLock::new(entries_map)),
-            total_size: Arc::new(AtomicU64::new(total_size)),
-        })
+        Ok(index)
     }
 
     /// Validate and repair the index by scanning the filesystem
@@ -281,15 +332,23 @@ impl CacheIndex {
         // Process each index entry
         let mut keys_to_remove = Vec::new();
         let mut new_total_size = 0u64;
+        let mut counted_blobs = HashSet::new();
 
         for (key, metadata) in &entries_snapshot {
             let hash = key_to_hash(key);
 
+            // Shared blobs (dedup mode) live outside entries_dir
+            let shared = metadata.content_hash.is_some();
+
             // Check if files exist
-            let data_path = cache_key_to_file_path(entries_dir, key, false);
+            let data_path = if shared {
+                metadata.file_path.clone()
+            } else {
+                cache_key_to_file_path(entries_dir, key, false)
+            };
             let meta_path = cache_key_to_file_path(entries_dir, key, true);
 
-            let data_exists = data_files.contains_key(&hash);
+            let data_exists = shared || data_files.contains_key(&hash);
             let meta_exists = meta_files.contains_key(&hash);
 
             // If files don't exist, remove from index
@@ -306,15 +365,19 @@ impl CacheIndex {
             if metadata.is_expired(now) {
                 // Expired - remove from index and delete files
                 keys_to_remove.push(key.clone());
-                let _ = backend.delete_file(&data_path).await;
+                if !shared {
+                    let _ = backend.delete_file(&data_path).await;
+                }
                 let _ = backend.delete_file(&meta_path).await;
                 continue;
             }
 
-            // Recalculate size from actual file
+            // Recalculate size from actual file (once per shared blob)
             match backend.file_size(&data_path).await {
                 Ok(actual_size) => {
-                    new_total_size += actual_size;
+                    if !shared || counted_blobs.insert(metadata.content_hash.clone()) {
+                        new_total_size += actual_size;
+                    }
 
                     // Update metadata if size changed
                     if actual_size != metadata.size_bytes {
@@ -338,6 +401,22 @@ impl CacheIndex {
             }
         }
 
+        // Recount blob references; delete blobs no entry uses any more
+        let mut blob_refs: HashMap<String, u64> = HashMap::new();
+        for metadata in self.entries.read().values() {
+            if let Some(hash) = &metadata.content_hash {
+                *blob_refs.entry(hash.clone()).or_insert(0) += 1;
+            }
+        }
+        for (_, metadata) in &entries_snapshot {
+            if let Some(hash) = &metadata.content_hash {
+                if !blob_refs.contains_key(hash) {
+                    let _ = backend.delete_file(&metadata.file_path).await;
+                }
+            }
+        }
+        *self.blob_refs.write() = blob_refs;
+
         // Update total size
         self.total_size.store(new_total_size, Ordering::SeqCst);
 
@@ -2360,6 +2360,8 @@ async fn test_size_updated_on_set() {
         created_at: now,
         expires_at: future,
         last_accessed_at: now,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key1.clone(), entry1).await.unwrap();
 
@@ -2385,6 +2387,8 @@ async fn test_size_updated_on_set() {
         created_at: now,
         expires_at: future,
         last_accessed_at: now,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key2.clone(), entry2).await.unwrap();
 
@@ -2426,6 +2430,8 @@ async fn test_size_updated_on_delete() {
         created_at: now,
         expires_at: future,
         last_accessed_at: now,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key1.clone(), entry1).await.unwrap();
 
@@ -2445,6 +2451,8 @@ async fn test_size_updated_on_delete() {
         created_at: now,
         expires_at: future,
         last_accessed_at: now,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key2.clone(), entry2).await.unwrap();
 
@@ -2516,6 +2524,8 @@ async fn test_detects_when_size_exceeds_max() {
         created_at: now,
         expires_at: future,
         last_accessed_at: now,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key1.clone(), entry1).await.unwrap();
 
@@ -2541,6 +2551,8 @@ async fn test_detects_when_size_exceeds_max() {
         created_at: now,
         expires_at: future,
         last_accessed_at: now,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key2.clone(), entry2).await.unwrap();
 
@@ -2566,6 +2578,8 @@ async fn test_detects_when_size_exceeds_max() {
         created_at: now,
         expires_at: future,
         last_accessed_at: now,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key3.clone(), entry3).await.unwrap();
 
@@ -2613,6 +2627,8 @@ async fn test_eviction_triggered_when_threshold_exceeded() {
         created_at: now,
         expires_at: future,
         last_accessed_at: now,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key1.clone(), entry1).await.unwrap();
 
@@ -2637,6 +2653,8 @@ async fn test_eviction_triggered_when_threshold_exceeded() {
         created_at: now2,
         expires_at: future,
         last_accessed_at: now2,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key2.clone(), entry2).await.unwrap();
 
@@ -2666,6 +2684,8 @@ async fn test_eviction_triggered_when_threshold_exceeded() {
         created_at: now3,
         expires_at: future,
         last_accessed_at: now3,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key3.clone(), entry3).await.unwrap();
 
@@ -2724,6 +2744,8 @@ async fn test_identifies_least_recently_accessed_entry() {
         created_at: now,
         expires_at: future,
         last_accessed_at: now,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key1.clone(), entry1).await.unwrap();
 
@@ -2748,6 +2770,8 @@ async fn test_identifies_least_recently_accessed_entry() {
         created_at: now2,
         expires_at: future,
         last_accessed_at: now2,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key2.clone(), entry2).await.unwrap();
 
@@ -2772,6 +2796,8 @@ async fn test_identifies_least_recently_accessed_entry() {
         created_at: now3,
         expires_at: future,
         last_accessed_at: now3,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key3.clone(), entry3).await.unwrap();
 
@@ -2801,6 +2827,8 @@ async fn test_identifies_least_recently_accessed_entry() {
         created_at: now4,
         expires_at: future,
         last_accessed_at: now4,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key4.clone(), entry4).await.unwrap();
 
@@ -2863,6 +2891,8 @@ async fn test_deletes_both_data_and_meta_files() {
         created_at: now,
         expires_at: future,
         last_accessed_at: now,
+        content_encoding: None,
+        headers: Vec::new(),
     };
 
     cache.set(key1.clone(), entry1).await.unwrap();
@@ -2900,6 +2930,8 @@ async fn test_deletes_both_data_and_meta_files() {
         created_at: now2,
         expires_at: future,
         last_accessed_at: now2,
+        content_encoding: None,
+        headers: Vec::new(),
     };
 
     cache.set(key2.clone(), entry2).await.unwrap();
@@ -2924,6 +2956,8 @@ async fn test_deletes_both_data_and_meta_files() {
         created_at: now3,
         expires_at: future,
         last_accessed_at: now3,
+        content_encoding: None,
+        headers: Vec::new(),
     };
 
     cache.set(key3.clone(), entry3).await.unwrap();
@@ -2984,6 +3018,8 @@ async fn test_removes_entry_from_index() {
         created_at: now,
         expires_at: future,
         last_accessed_at: now,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key1.clone(), entry1).await.unwrap();
 
@@ -3011,6 +3047,8 @@ async fn test_removes_entry_from_index() {
         created_at: now2,
         expires_at: future,
         last_accessed_at: now2,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key2.clone(), entry2).await.unwrap();
 
@@ -3034,6 +3072,8 @@ async fn test_removes_entry_from_index() {
         created_at: now3,
         expires_at: future,
         last_accessed_at: now3,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key3.clone(), entry3).await.unwrap();
 
@@ -3094,6 +3134,8 @@ async fn test_updates_stats_eviction_count() {
         created_at: now,
         expires_at: future,
         last_accessed_at: now,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key1.clone(), entry1).await.unwrap();
 
@@ -3117,6 +3159,8 @@ async fn test_updates_stats_eviction_count() {
         created_at: now2,
         expires_at: future,
         last_accessed_at: now2,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key2.clone(), entry2).await.unwrap();
 
@@ -3144,6 +3188,8 @@ async fn test_updates_stats_eviction_count() {
         created_at: now3,
         expires_at: future,
         last_accessed_at: now3,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key3.clone(), entry3).await.unwrap();
 
@@ -3174,6 +3220,8 @@ async fn test_updates_stats_eviction_count() {
         created_at: now4,
         expires_at: future,
         last_accessed_at: now4,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key4.clone(), entry4).await.unwrap();
 
@@ -3214,6 +3262,8 @@ async fn test_can_evict_multiple_entries_in_one_pass() {
         created_at: now,
         expires_at: future,
         last_accessed_at: now,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key1.clone(), entry1).await.unwrap();
 
@@ -3236,6 +3286,8 @@ async fn test_can_evict_multiple_entries_in_one_pass() {
         created_at: now2,
         expires_at: future,
         last_accessed_at: now2,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key2.clone(), entry2).await.unwrap();
 
@@ -3258,6 +3310,8 @@ async fn test_can_evict_multiple_entries_in_one_pass() {
         created_at: now3,
         expires_at: future,
         last_accessed_at: now3,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key3.clone(), entry3).await.unwrap();
 
@@ -3284,6 +3338,8 @@ async fn test_can_evict_multiple_entries_in_one_pass() {
         created_at: now4,
         expires_at: future,
         last_accessed_at: now4,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key4.clone(), entry4).await.unwrap();
 
@@ -3347,6 +3403,8 @@ async fn test_evicts_in_lru_order() {
         created_at: now,
         expires_at: future,
         last_accessed_at: now, // T0 - oldest
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key1.clone(), entry1).await.unwrap();
 
@@ -3369,6 +3427,8 @@ async fn test_evicts_in_lru_order() {
         created_at: now2,
         expires_at: future,
         last_accessed_at: now2, // T1 - second oldest
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key2.clone(), entry2).await.unwrap();
 
@@ -3391,6 +3451,8 @@ async fn test_evicts_in_lru_order() {
         created_at: now3,
         expires_at: future,
         last_accessed_at: now3, // T2 - second newest
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key3.clone(), entry3).await.unwrap();
 
@@ -3413,6 +3475,8 @@ async fn test_evicts_in_lru_order() {
         created_at: now4,
         expires_at: future,
         last_accessed_at: now4, // T3 - newest
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key4.clone(), entry4).await.unwrap();
 
@@ -3439,6 +3503,8 @@ async fn test_evicts_in_lru_order() {
         created_at: now5,
         expires_at: future,
         last_accessed_at: now5,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key5.clone(), entry5).await.unwrap();
 
@@ -3512,6 +3578,8 @@ async fn test_stops_when_enough_space_freed() {
             created_at: time,
             expires_at: future,
             last_accessed_at: time,
+            content_encoding: None,
+            headers: Vec::new(),
         };
         cache.set(key.clone(), entry).await.unwrap();
         keys.push(key);
@@ -3545,6 +3613,8 @@ async fn test_stops_when_enough_space_freed() {
         created_at: time_new,
         expires_at: future,
         last_accessed_at: time_new,
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(new_key.clone(), entry_new).await.unwrap();
 
@@ -4154,6 +4224,8 @@ async fn test_triggers_eviction_if_oversized_after_recovery() {
         created_at: SystemTime::now(),
         expires_at: SystemTime::now() + Duration::from_secs(3600),
         last_accessed_at: SystemTime::now(),
+        content_encoding: None,
+        headers: Vec::new(),
     };
 
     // Add the new entry - this should trigger eviction of key1 (LRU)
@@ -4887,6 +4959,8 @@ async fn test_cache_set_and_get_roundtrip() {
         created_at: SystemTime::now(),
         expires_at: SystemTime::now() + Duration::from_secs(3600),
         last_accessed_at: SystemTime::now(),
+        content_encoding: None,
+        headers: Vec::new(),
     };
 
     // Set entry
@@ -4926,6 +5000,8 @@ async fn test_cache_get_returns_none_if_expired() {
         created_at: SystemTime::now() - Duration::from_secs(7200),
         expires_at: SystemTime::now() - Duration::from_secs(3600), // Expired 1 hour ago
         last_accessed_at: SystemTime::now() - Duration::from_secs(7200),
+        content_encoding: None,
+        headers: Vec::new(),
     };
 
     // Set entry
@@ -4964,6 +5040,8 @@ async fn test_cache_delete_removes_entry() {
         created_at: SystemTime::now(),
         expires_at: SystemTime::now() + Duration::from_secs(3600),
         last_accessed_at: SystemTime::now(),
+        content_encoding: None,
+        headers: Vec::new(),
     };
 
     // Set entry
@@ -5023,6 +5101,8 @@ async fn test_cache_stats() {
         created_at: SystemTime::now(),
         expires_at: SystemTime::now() + Duration::from_secs(3600),
         last_accessed_at: SystemTime::now(),
+        content_encoding: None,
+        headers: Vec::new(),
     };
 
     cache.set(key.clone(), entry).await.unwrap();
@@ -5062,6 +5142,8 @@ async fn test_cache_clear_removes_all_entries() {
             created_at: SystemTime::now(),
             expires_at: SystemTime::now() + Duration::from_secs(3600),
             last_accessed_at: SystemTime::now(),
+            content_encoding: None,
+            headers: Vec::new(),
         };
 
         cache.set(key.clone(), entry).await.unwrap();
@@ -5109,6 +5191,8 @@ async fn test_cache_set_triggers_eviction_when_full() {
         created_at: SystemTime::now(),
         expires_at: SystemTime::now() + Duration::from_secs(3600),
         last_accessed_at: SystemTime::now(),
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key1.clone(), entry1).await.unwrap();
 
@@ -5130,6 +5214,8 @@ async fn test_cache_set_triggers_eviction_when_full() {
         created_at: SystemTime::now(),
         expires_at: SystemTime::now() + Duration::from_secs(3600),
         last_accessed_at: SystemTime::now(),
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key2.clone(), entry2).await.unwrap();
 
@@ -5151,6 +5237,8 @@ async fn test_cache_set_triggers_eviction_when_full() {
         created_at: SystemTime::now(),
         expires_at: SystemTime::now() + Duration::from_secs(3600),
         last_accessed_at: SystemTime::now(),
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key3.clone(), entry3).await.unwrap();
 
@@ -5196,6 +5284,8 @@ async fn test_cache_stores_multiple_entries() {
             created_at: SystemTime::now(),
             expires_at: SystemTime::now() + Duration::from_secs(3600),
             last_accessed_at: SystemTime::now(),
+            content_encoding: None,
+            headers: Vec::new(),
         };
 
         cache.set(key.clone(), entry).await.unwrap();
@@ -5250,6 +5340,8 @@ async fn test_integration_store_and_retrieve_100_entries() {
             created_at: SystemTime::now(),
             expires_at: SystemTime::now() + Duration::from_secs(3600),
             last_accessed_at: SystemTime::now(),
+            content_encoding: None,
+            headers: Vec::new(),
         };
 
         cache.set(key.clone(), entry).await.unwrap();
@@ -5415,6 +5507,8 @@ async fn test_integration_lru_eviction_end_to_end() {
             created_at: SystemTime::now(),
             expires_at: SystemTime::now() + Duration::from_secs(3600),
             last_accessed_at: SystemTime::now(),
+            content_encoding: None,
+            headers: Vec::new(),
         };
 
         cache.set(key, entry).await.unwrap();
@@ -5578,6 +5672,8 @@ async fn test_get_sendfile_returns_path_for_large_files() {
         created_at: SystemTime::now(),
         expires_at: SystemTime::now() + Duration::from_secs(3600),
         last_accessed_at: SystemTime::now(),
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key.clone(), entry).await.unwrap();
 
@@ -5632,6 +5728,8 @@ async fn test_get_sendfile_returns_none_for_small_files() {
         created_at: SystemTime::now(),
         expires_at: SystemTime::now() + Duration::from_secs(3600),
         last_accessed_at: SystemTime::now(),
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key.clone(), entry).await.unwrap();
 
@@ -5684,6 +5782,8 @@ async fn test_get_sendfile_returns_none_when_disabled() {
         created_at: SystemTime::now(),
         expires_at: SystemTime::now() + Duration::from_secs(3600),
         last_accessed_at: SystemTime::now(),
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key.clone(), entry).await.unwrap();
 
@@ -5731,6 +5831,8 @@ async fn test_get_sendfile_tracks_cache_hits() {
         created_at: SystemTime::now(),
         expires_at: SystemTime::now() + Duration::from_secs(3600),
         last_accessed_at: SystemTime::now(),
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key.clone(), entry).await.unwrap();
 
@@ -5815,6 +5917,8 @@ async fn test_get_sendfile_returns_none_for_expired_entry() {
         created_at: SystemTime::now() - Duration::from_secs(10),
         expires_at: SystemTime::now() - Duration::from_secs(1), // Already expired
         last_accessed_at: SystemTime::now() - Duration::from_secs(10),
+        content_encoding: None,
+        headers: Vec::new(),
     };
     cache.set(key.clone(), entry).await.unwrap();
 
@@ -5822,3 +5926,157 @@ async fn test_get_sendfile_returns_none_for_expired_entry() {
     let result = cache.get_sendfile(&key).await.unwrap();
     assert!(result.is_none(), "Should return None for expired entry");
 }
+
+#[tokio::test]
+async fn test_cache_get_large_entry_is_memory_mapped_roundtrip() {
+    use super::disk_cache::DiskCache;
+    use crate::cache::zero_copy::MMAP_THRESHOLD_BYTES;
+    use crate::cache::{Cache, CacheEntry, CacheKey};
+    use bytes::Bytes;
+    use std::time::{Duration, SystemTime};
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let cache = DiskCache::with_config(temp_dir.path().to_path_buf(), 64 * 1024 * 1024);
+    let key = CacheKey {
+        bucket: "test".to_string(),
+        object_key: "large.bin".to_string(),
+        etag: None,
+        variant: None,
+    };
+
+    // Just above the mmap threshold so the mapped read path is taken
+    let data = Bytes::from(vec![0xABu8; MMAP_THRESHOLD_BYTES as usize + 17]);
+    let entry = CacheEntry {
+        data: data.clone(),
+        content_type: "application/octet-stream".to_string(),
+        content_length: data.len(),
+        etag: "etag-large".to_string(),
+        last_modified: None,
+        created_at: SystemTime::now(),
+        expires_at: SystemTime::now() + Duration::from_secs(3600),
+        last_accessed_at: SystemTime::now(),
+        content_encoding: None,
+        headers: Vec::new(),
+    };
+
+    cache.set(key.clone(), entry).await.unwrap();
+
+    let retrieved = cache.get(&key).await.unwrap().unwrap();
+    assert_eq!(retrieved.content_length, data.len());
+    assert_eq!(retrieved.data, data);
+}
+
+#[tokio::test]
+async fn test_dedup_stores_identical_bodies_once() {
+    use super::disk_cache::DiskCache;
+    use crate::cache::{Cache, CacheEntry, CacheKey};
+    use bytes::Bytes;
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let cache =
+        DiskCache::with_config(temp_dir.path().to_path_buf(), 64 * 1024 * 1024).with_dedup(true);
+    let key = |bucket: &str| CacheKey {
+        bucket: bucket.to_string(),
+        object_key: "shared/logo.png".to_string(),
+        etag: None,
+        variant: None,
+    };
+    let data = Bytes::from(vec![0x5Au8; 4096]);
+    let entry = || {
+        CacheEntry::new(
+            data.clone(),
+            "image/png".to_string(),
+            "e".to_string(),
+            None,
+            None,
+        )
+    };
+
+    cache.set(key("tenant-a"), entry()).await.unwrap();
+    cache.set(key("tenant-b"), entry()).await.unwrap();
+    // Re-setting a key keeps a single reference for it
+    cache.set(key("tenant-b"), entry()).await.unwrap();
+
+    let blob = utils::blob_path(temp_dir.path(), &utils::content_hash(&data));
+    assert!(blob.exists());
+    assert_eq!(cache.index.total_size(), data.len() as u64);
+    assert_eq!(cache.index.entry_count(), 2);
+
+    // The blob outlives the first purge and goes with the last reference
+    assert!(cache.delete(&key("tenant-a")).await.unwrap());
+    assert!(blob.exists());
+    assert_eq!(
+        cache.get(&key("tenant-b")).await.unwrap().unwrap().data,
+        data
+    );
+    assert!(cache.delete(&key("tenant-b")).await.unwrap());
+    assert!(!blob.exists());
+    assert_eq!(cache.index.total_size(), 0);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_dedup_concurrent_set_and_delete_keep_blob() {
+    use super::disk_cache::DiskCache;
+    use crate::cache::{Cache, CacheEntry, CacheKey};
+    use bytes::Bytes;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+
+    let temp_dir = TempDir::new().unwrap();
+    let cache = Arc::new(
+        DiskCache::with_config(temp_dir.path().to_path_buf(), 64 * 1024 * 1024).with_dedup(true),
+    );
+    let key = |tenant: usize| CacheKey {
+        bucket: format!("tenant-{}", tenant),
+        object_key: "shared/logo.png".to_string(),
+        etag: None,
+        variant: None,
+    };
+    let data = Bytes::from(vec![0x5Au8; 4096]);
+    let hash = utils::content_hash(&data);
+    let blob = utils::blob_path(temp_dir.path(), &hash);
+
+    for _ in 0..50 {
+        // Tenants 0 and 1 are set while tenant 0 is also deleted
+        let tasks: Vec<_> = (0..4)
+            .map(|task| {
+                let cache = Arc::clone(&cache);
+                let entry = CacheEntry::new(
+                    data.clone(),
+                    "image/png".to_string(),
+                    "e".to_string(),
+                    None,
+                    None,
+                );
+                let (set_key, delete_key) = (key(task % 2), key(0));
+                tokio::spawn(async move {
+                    if task == 3 {
+                        let _ = cache.delete(&delete_key).await;
+                    } else {
+                        cache.set(set_key, entry).await.unwrap();
+                    }
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        let entries = cache.index.entry_count() as u64;
+        assert_eq!(cache.index.blob_ref_count(&hash), entries);
+        assert_eq!(blob.exists(), entries > 0);
+        for tenant in 0..2 {
+            if let Some(entry) = cache.get(&key(tenant)).await.unwrap() {
+                assert_eq!(entry.data, data);
+            }
+        }
+
+        // Start the next round from an empty cache
+        for tenant in 0..2 {
+            let _ = cache.delete(&key(tenant)).await;
+        }
+        assert!(!blob.exists());
+    }
+}
@@ -23,6 +23,15 @@ pub struct EntryMetadata {
     /// HTTP Last-Modified header value (RFC 2822 format)
     #[serde(default)]
     pub last_modified: Option<String>,
+    /// Encoding of the data file when stored compressed (e.g. "zstd")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_encoding: Option<String>,
+    /// SHA-256 of the body when stored as a shared blob (dedup mode)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_hash: Option<String>,
+    /// Upstream response headers replayed on hits
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub headers: Vec<(String, String)>,
 }
 
 fn default_content_type() -> String {
@@ -51,6 +60,9 @@ impl EntryMetadata {
             content_type,
             etag,
             last_modified,
+            content_encoding: None,
+            content_hash: None,
+            headers: Vec::new(),
         }
     }
 
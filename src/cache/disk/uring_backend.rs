@@ -174,6 +174,16 @@ impl DiskBackend for UringBackend {
             .map_err(|e| DiskCacheError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?
     }
 
+    async fn read_file_mapped(&self, path: &Path) -> Result<Bytes, DiskCacheError> {
+        // Large entries are mapped rather than read through io-uring into a buffer
+        let path_buf = path.to_path_buf();
+
+        tokio::task::spawn_blocking(move || crate::cache::zero_copy::map_file(&path_buf))
+            .await
+            .map_err(|e| DiskCacheError::Io(std::io::Error::new(std::io::ErrorKind::Other, e)))?
+            .map_err(DiskCacheError::from)
+    }
+
     async fn write_file_atomic(&self, path: &Path, data: Bytes) -> Result<(), DiskCacheError> {
         // Create parent directory if needed (use tokio::fs for directory operations)
         if let Some(parent) = path.parent() {
@@ -29,6 +29,18 @@ pub fn generate_paths(cache_dir: &Path, hash: &str) -> (PathBuf, PathBuf) {
     (data_path, meta_path)
 }
 
+/// SHA-256 of an entry body, naming its shared blob in dedup mode
+pub fn content_hash(data: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(data))
+}
+
+/// Path of the shared blob holding bodies with this content hash
+pub fn blob_path(cache_dir: &Path, content_hash: &str) -> PathBuf {
+    cache_dir
+        .join("blobs")
+        .join(format!("{}.data", content_hash))
+}
+
 /// Generate file path for a cache entry (data or metadata)
 pub fn cache_key_to_file_path(entries_dir: &Path, key: &CacheKey, is_metadata: bool) -> PathBuf {
     let hash = key_to_hash(key);
@@ -144,6 +144,10 @@ pub struct CacheEntry {
     pub expires_at: SystemTime,
     /// Last time this entry was accessed (for LRU eviction)
     pub last_accessed_at: SystemTime,
+    /// Encoding of `data` when stored compressed (e.g. "zstd"), None for raw bytes
+    pub content_encoding: Option<String>,
+    /// Upstream response headers replayed on hits (`cache.preserve_headers`)
+    pub headers: Vec<(String, String)>,
 }
 
 impl CacheEntry {
@@ -188,9 +192,17 @@ impl CacheEntry {
             created_at: now,
             expires_at,
             last_accessed_at: now,
+            content_encoding: None,
+            headers: Vec::new(),
         }
     }
 
+    /// Attach upstream response headers to replay on hits
+    pub fn with_headers(mut self, headers: Vec<(String, String)>) -> Self {
+        self.headers = headers;
+        self
+    }
+
     /// Check if this cache entry has expired
     pub fn is_expired(&self) -> bool {
         SystemTime::now() >= self.expires_at
@@ -203,9 +215,34 @@ impl CacheEntry {
     }
 
     /// Validate the cache entry's ETag against a provided ETag
-    /// Returns true if the ETags match
+    /// Returns true if the ETags match (quotes and `W/` are ignored)
     pub fn validate_etag(&self, etag: &str) -> bool {
-        self.etag == etag
+        super::etag::normalize(&self.etag) == super::etag::normalize(etag)
+    }
+
+    /// Check whether this entry still holds the object described by S3 metadata
+    ///
+    /// Matching ETags always count. When either ETag is not a plain MD5
+    /// (multipart or SSE-KMS), a mismatch falls back to comparing size and
+    /// Last-Modified, since such ETags change on re-upload with identical bytes.
+    pub fn matches_object(
+        &self,
+        etag: &str,
+        content_length: Option<u64>,
+        last_modified: Option<&str>,
+    ) -> bool {
+        if super::etag::matches(&self.etag, etag) {
+            return true;
+        }
+        if super::etag::is_md5(&self.etag) && super::etag::is_md5(etag) {
+            return false;
+        }
+        match (content_length, last_modified, self.last_modified.as_deref()) {
+            (Some(len), Some(theirs), Some(ours)) => {
+                len == self.content_length as u64 && theirs == ours
+            }
+            _ => false,
+        }
     }
 
     /// Check if the cache entry is valid (not expired and ETag matches)
@@ -223,13 +260,23 @@ impl CacheEntry {
         // String metadata size
         let content_type_size = self.content_type.len();
         let etag_size = self.etag.len();
+        let headers_size: usize = self
+            .headers
+            .iter()
+            .map(|(name, value)| name.len() + value.len())
+            .sum();
 
         // Fixed-size metadata
         let content_length_size = std::mem::size_of::<usize>();
         let timestamps_size = 3 * std::mem::size_of::<SystemTime>();
 
         // Total size
-        data_size + content_type_size + etag_size + content_length_size + timestamps_size
+        data_size
+            + content_type_size
+            + etag_size
+            + headers_size
+            + content_length_size
+            + timestamps_size
     }
 }
 
@@ -575,6 +622,8 @@ mod tests {
             created_at: now,
             expires_at: now,
             last_accessed_at: now,
+            content_encoding: None,
+            headers: Vec::new(),
         };
 
         assert_eq!(entry.data, data);
@@ -594,6 +643,8 @@ mod tests {
             created_at: now,
             expires_at: now,
             last_accessed_at: now,
+            content_encoding: None,
+            headers: Vec::new(),
         };
 
         assert_eq!(entry.data, Bytes::from("hello world"));
@@ -614,6 +665,8 @@ mod tests {
             created_at: now,
             expires_at: now,
             last_accessed_at: now,
+            content_encoding: None,
+            headers: Vec::new(),
         };
 
         let size = entry.size_bytes();
@@ -634,6 +687,8 @@ mod tests {
             created_at: now,
             expires_at: now,
             last_accessed_at: now,
+            content_encoding: None,
+            headers: Vec::new(),
         };
 
         let size = entry.size_bytes();
@@ -655,6 +710,8 @@ mod tests {
             created_at: past,
             expires_at: past,
             last_accessed_at: now,
+            content_encoding: None,
+            headers: Vec::new(),
         };
 
         assert!(expired_entry.is_expired());
@@ -668,6 +725,8 @@ mod tests {
             created_at: now,
             expires_at: future,
             last_accessed_at: now,
+            content_encoding: None,
+            headers: Vec::new(),
         };
 
         assert!(!valid_entry.is_expired());
@@ -815,9 +874,40 @@ mod tests {
             created_at: past,
             expires_at: past,
             last_accessed_at: now,
+            content_encoding: None,
+            headers: Vec::new(),
         };
 
         assert!(!entry.is_valid("valid-etag"));
         assert!(!entry.is_valid("different-etag"));
     }
+
+    #[test]
+    fn test_matches_object_with_multipart_etag() {
+        let md5 = "0123456789abcdef0123456789abcdef";
+        let entry = CacheEntry::new(
+            Bytes::from("data"),
+            "text/plain".to_string(),
+            format!("{}-3", md5),
+            Some("Wed, 21 Oct 2015 07:28:00 GMT".to_string()),
+            None,
+        );
+        let modified = Some("Wed, 21 Oct 2015 07:28:00 GMT");
+
+        assert!(entry.matches_object(&format!("\"{}-3\"", md5), None, None));
+        // Same bytes re-uploaded with a different part size
+        assert!(entry.matches_object(&format!("{}-5", md5), Some(4), modified));
+        assert!(!entry.matches_object(&format!("{}-5", md5), Some(5), modified));
+        assert!(!entry.matches_object(&format!("{}-5", md5), None, None));
+
+        // Two MD5 ETags that differ are a real change
+        let single = CacheEntry::new(
+            Bytes::from("data"),
+            "text/plain".to_string(),
+            md5.to_string(),
+            modified.map(str::to_string),
+            None,
+        );
+        assert!(!single.matches_object("fedcba9876543210fedcba9876543210", Some(4), modified));
+    }
 }
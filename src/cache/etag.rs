@@ -0,0 +1,76 @@
+//! ETag comparison that understands S3 multipart ETags.
+//!
+//! S3 ETags are only an MD5 of the body for single-part uploads without
+//! SSE-KMS. Multipart uploads get `<md5-of-part-md5s>-<parts>`, which changes
+//! whenever the object is re-uploaded with a different part size even if the
+//! bytes are identical. ETags also arrive quoted, unquoted or weak (`W/`)
+//! depending on who produced them.
+//!
+//! Comparisons here ignore quoting and weakness. When matching a cached entry
+//! against an object's current metadata, an ETag that is not a plain MD5 only
+//! confirms a match; on mismatch the size and Last-Modified decide instead.
+
+/// Strip `W/` and surrounding quotes
+pub fn normalize(etag: &str) -> &str {
+    let etag = etag.trim();
+    let etag = etag.strip_prefix("W/").unwrap_or(etag);
+    etag.trim_matches('"')
+}
+
+/// Whether the ETag is a plain MD5 of the body (32 hex digits)
+pub fn is_md5(etag: &str) -> bool {
+    let etag = normalize(etag);
+    etag.len() == 32 && etag.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Number of parts of a multipart ETag (`<32 hex>-<N>`), `None` otherwise
+pub fn multipart_parts(etag: &str) -> Option<u32> {
+    let (hash, parts) = normalize(etag).rsplit_once('-')?;
+    if !is_md5(hash) {
+        return None;
+    }
+    parts.parse().ok()
+}
+
+/// Compare two ETags ignoring quotes and weakness
+pub fn matches(a: &str, b: &str) -> bool {
+    let a = normalize(a);
+    !a.is_empty() && a == normalize(b)
+}
+
+/// Check an `If-None-Match` header (`*` or a comma-separated list) against an ETag
+pub fn if_none_match_matches(header: &str, etag: &str) -> bool {
+    if etag.is_empty() {
+        return false;
+    }
+    header
+        .split(',')
+        .any(|candidate| candidate.trim() == "*" || matches(candidate, etag))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MD5: &str = "d41d8cd98f00b204e9800998ecf8427e";
+
+    #[test]
+    fn test_multipart_etags_and_matching() {
+        assert!(is_md5(MD5));
+        assert!(is_md5(&format!("\"{}\"", MD5)));
+        assert!(!is_md5(&format!("{}-12", MD5)));
+        assert_eq!(multipart_parts(&format!("\"{}-12\"", MD5)), Some(12));
+        assert_eq!(multipart_parts(MD5), None);
+        assert_eq!(multipart_parts("abc-3"), None);
+
+        assert!(matches("\"abc-3\"", "abc-3"));
+        assert!(matches("W/\"abc-3\"", "\"abc-3\""));
+        assert!(!matches("abc-3", "abc-4"));
+        assert!(!matches("", ""));
+
+        assert!(if_none_match_matches("\"x\", \"abc-3\"", "abc-3"));
+        assert!(if_none_match_matches("*", "abc-3"));
+        assert!(!if_none_match_matches("\"x\"", "abc-3"));
+        assert!(!if_none_match_matches("*", ""));
+    }
+}
@@ -0,0 +1,159 @@
+//! A/B experiments between two cache policies.
+//!
+//! A bucket's `cache.experiment` splits callers between two variants with
+//! different TTLs, so a TTL change can be measured on real traffic before it
+//! is rolled out. The caller is identified by the configured header, else the
+//! configured cookie, else the client IP. The identity is hashed together with
+//! the experiment name, so a caller keeps its variant across requests and
+//! proxy instances. A header or cookie value equal to a variant name selects
+//! that variant directly, which lets testers pin themselves to one side.
+//!
+//! Both variants share cache entries. Entries are stored for the longest
+//! variant TTL, and each variant treats hits older than its own TTL as misses.
+//! Lookups are counted per variant in
+//! `yatagarasu_cache_experiment_requests_total{bucket,experiment,variant,result}`.
+
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::time::{Duration, SystemTime};
+
+use super::config::{CacheExperimentConfig, CacheVariantConfig};
+use crate::auth::{extract_cookie_token, extract_header_token};
+use crate::config::BucketConfig;
+use crate::constants::DEFAULT_TTL_SECONDS;
+
+/// Variant for a request to a bucket running `experiment`
+pub fn assign<'a>(
+    experiment: &'a CacheExperimentConfig,
+    headers: &HashMap<String, String>,
+    client_ip: &str,
+) -> &'a CacheVariantConfig {
+    let identity = experiment
+        .header
+        .as_deref()
+        .and_then(|name| extract_header_token(headers, name))
+        .or_else(|| {
+            experiment
+                .cookie
+                .as_deref()
+                .and_then(|name| extract_cookie_token(headers, name))
+        })
+        .unwrap_or_else(|| client_ip.to_string());
+
+    if let Some(variant) = experiment.variants.iter().find(|v| v.name == identity) {
+        return variant;
+    }
+
+    let digest = Sha256::digest(format!("{}:{}", experiment.name, identity).as_bytes());
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest[..8]);
+    let slot = u64::from_be_bytes(bytes) % 100;
+    if slot < u64::from(experiment.split_percent) {
+        &experiment.variants[1]
+    } else {
+        &experiment.variants[0]
+    }
+}
+
+/// Whether an entry created at `created_at` is still a hit for `variant`
+pub fn is_fresh(variant: &CacheVariantConfig, created_at: SystemTime) -> bool {
+    created_at
+        .elapsed()
+        .map(|age| age < Duration::from_secs(variant.ttl_seconds))
+        .unwrap_or(true)
+}
+
+/// TTL for entries filled without an upstream lifetime
+///
+/// A bucket running an experiment stores entries for its longest variant TTL
+/// so each variant can apply its own limit on lookup. Otherwise the bucket's
+/// `cache.ttl_seconds` applies, else the default of one hour.
+pub fn fill_ttl(bucket_config: &BucketConfig) -> Duration {
+    let cache = bucket_config.cache.as_ref();
+    let secs = cache
+        .and_then(|c| c.experiment.as_ref())
+        .and_then(|e| e.variants.iter().map(|v| v.ttl_seconds).max())
+        .or_else(|| cache.and_then(|c| c.ttl_seconds))
+        .unwrap_or(DEFAULT_TTL_SECONDS);
+    Duration::from_secs(secs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_experiment_assignment() {
+        let yaml = r#"
+name: ttl-test
+header: X-User-Id
+cookie: session
+split_percent: 50
+variants:
+  - name: control
+    ttl_seconds: 300
+  - name: long
+    ttl_seconds: 3600
+"#;
+        let experiment: CacheExperimentConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(experiment.validate().is_ok());
+
+        // Deterministic, and roughly an even split across callers
+        let headers =
+            |name: &str, value: &str| HashMap::from([(name.to_string(), value.to_string())]);
+        let first = assign(&experiment, &headers("x-user-id", "alice"), "10.0.0.1")
+            .name
+            .clone();
+        for _ in 0..3 {
+            assert_eq!(
+                assign(&experiment, &headers("x-user-id", "alice"), "10.0.0.2").name,
+                first
+            );
+        }
+        let long = (0..1000)
+            .filter(|i| {
+                assign(
+                    &experiment,
+                    &headers("cookie", &format!("session=s{}", i)),
+                    "10.0.0.1",
+                )
+                .name
+                    == "long"
+            })
+            .count();
+        assert!(
+            (400..600).contains(&long),
+            "{} of 1000 assigned to long",
+            long
+        );
+
+        // A variant name pins the caller
+        assert_eq!(
+            assign(&experiment, &headers("X-User-Id", "control"), "").name,
+            "control"
+        );
+        assert_eq!(
+            assign(&experiment, &headers("Cookie", "session=long"), "").name,
+            "long"
+        );
+
+        // Variant TTL on lookup
+        let control = &experiment.variants[0];
+        assert!(is_fresh(control, SystemTime::now()));
+        assert!(!is_fresh(
+            control,
+            SystemTime::now() - Duration::from_secs(600)
+        ));
+        assert!(is_fresh(
+            &experiment.variants[1],
+            SystemTime::now() - Duration::from_secs(600)
+        ));
+
+        let mut invalid = experiment.clone();
+        invalid.variants.pop();
+        assert!(invalid.validate().is_err());
+        let mut invalid = experiment;
+        invalid.variants[1].name = "control".to_string();
+        assert!(invalid.validate().is_err());
+    }
+}
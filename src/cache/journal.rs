@@ -0,0 +1,419 @@
+//! Hot key journal for startup cache warming.
+//!
+//! After a deploy the memory layer starts empty and the hit ratio dips until
+//! traffic refills it. [`HotKeyJournal`] counts requests per object for
+//! roughly the `top_k` most requested keys and periodically writes them to a
+//! JSON file. On startup [`warm_from_journal`] reads that file back and
+//! fetches the listed objects from S3 into the cache before traffic arrives.
+//!
+//! Counts are halved after every flush, so the journal follows what is hot
+//! now rather than what was hot last week.
+
+use aws_sdk_s3::primitives::DateTimeFormat;
+use futures::stream::{self, StreamExt};
+use parking_lot::Mutex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::cache::{Cache, CacheEntry, CacheKey, HotKeyJournalConfig};
+use crate::config::BucketConfig;
+use crate::s3::S3Client;
+use crate::util::unix_now;
+
+/// Journal file format version
+const JOURNAL_VERSION: u32 = 1;
+
+/// Tracked keys may grow to this multiple of `top_k` before pruning
+const PRUNE_FACTOR: usize = 4;
+
+/// A popular object and its (decayed) request count
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HotKey {
+    pub bucket: String,
+    pub key: String,
+    pub hits: u64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JournalFile {
+    version: u32,
+    saved_at: u64,
+    keys: Vec<HotKey>,
+}
+
+/// Result of warming the cache from a journal
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct WarmSummary {
+    pub warmed: usize,
+    pub skipped: usize,
+    pub failed: usize,
+}
+
+/// Approximate top-K request counter persisted for startup warming
+pub struct HotKeyJournal {
+    config: HotKeyJournalConfig,
+    counts: Mutex<HashMap<(String, String), u64>>,
+}
+
+impl HotKeyJournal {
+    pub fn new(config: HotKeyJournalConfig) -> Self {
+        Self {
+            config,
+            counts: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Count a request for `key` in `bucket`
+    pub fn record(&self, bucket: &str, key: &str) {
+        let mut counts = self.counts.lock();
+        if let Some(hits) = counts.get_mut(&(bucket.to_string(), key.to_string())) {
+            *hits += 1;
+            return;
+        }
+        counts.insert((bucket.to_string(), key.to_string()), 1);
+        if counts.len() > self.config.top_k * PRUNE_FACTOR {
+            prune(&mut counts, self.config.top_k);
+        }
+    }
+
+    /// Seed counts from a loaded journal so popularity carries across restarts
+    pub fn seed(&self, keys: &[HotKey]) {
+        let mut counts = self.counts.lock();
+        for hot in keys {
+            *counts
+                .entry((hot.bucket.clone(), hot.key.clone()))
+                .or_insert(0) += hot.hits;
+        }
+        if counts.len() > self.config.top_k {
+            prune(&mut counts, self.config.top_k);
+        }
+    }
+
+    /// The `top_k` most requested keys, most popular first
+    pub fn top_keys(&self) -> Vec<HotKey> {
+        let counts = self.counts.lock();
+        let mut keys: Vec<HotKey> = counts
+            .iter()
+            .map(|((bucket, key), hits)| HotKey {
+                bucket: bucket.clone(),
+                key: key.clone(),
+                hits: *hits,
+            })
+            .collect();
+        keys.sort_by(|a, b| b.hits.cmp(&a.hits).then_with(|| a.key.cmp(&b.key)));
+        keys.truncate(self.config.top_k);
+        keys
+    }
+
+    /// Write the current top keys to the journal file and decay counts
+    ///
+    /// Returns the number of keys written.
+    pub fn flush(&self) -> Result<usize, String> {
+        let keys = self.top_keys();
+        let written = keys.len();
+        save(Path::new(&self.config.path), keys)?;
+
+        let mut counts = self.counts.lock();
+        counts.retain(|_, hits| {
+            *hits /= 2;
+            *hits > 0
+        });
+        Ok(written)
+    }
+
+    /// Load the journal file, returning an empty list when it does not exist yet
+    pub fn load(&self) -> Result<Vec<HotKey>, String> {
+        load(Path::new(&self.config.path))
+    }
+
+    /// Flush the journal every `flush_interval_secs` until the journal is dropped
+    pub fn start_flush_task(self: &Arc<Self>) {
+        let interval = Duration::from_secs(self.config.flush_interval_secs);
+        let journal = Arc::downgrade(self);
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            // First tick completes immediately; nothing worth writing yet
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                let Some(journal) = journal.upgrade() else {
+                    break;
+                };
+                match journal.flush() {
+                    Ok(count) => tracing::debug!(keys = count, "Hot key journal written"),
+                    Err(e) => tracing::warn!(error = %e, "Failed to write hot key journal"),
+                }
+            }
+        });
+    }
+}
+
+/// Keep only the `top_k` highest counts
+fn prune(counts: &mut HashMap<(String, String), u64>, top_k: usize) {
+    let mut hits: Vec<u64> = counts.values().copied().collect();
+    hits.sort_unstable_by(|a, b| b.cmp(a));
+    let cutoff = hits.get(top_k.saturating_sub(1)).copied().unwrap_or(0);
+    counts.retain(|_, h| *h >= cutoff);
+    // Ties at the cutoff can leave more than top_k entries; drop the excess
+    if counts.len() > top_k {
+        let mut excess = counts.len() - top_k;
+        counts.retain(|_, h| {
+            if excess > 0 && *h == cutoff {
+                excess -= 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
+}
+
+fn save(path: &Path, keys: Vec<HotKey>) -> Result<(), String> {
+    let saved_at = unix_now();
+    let file = JournalFile {
+        version: JOURNAL_VERSION,
+        saved_at,
+        keys,
+    };
+    let json = serde_json::to_vec(&file).map_err(|e| e.to_string())?;
+
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+    }
+    // Write then rename so a crash never leaves a truncated journal
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, json).map_err(|e| format!("Failed to write {}: {}", tmp.display(), e))?;
+    std::fs::rename(&tmp, path).map_err(|e| format!("Failed to replace {}: {}", path.display(), e))
+}
+
+fn load(path: &Path) -> Result<Vec<HotKey>, String> {
+    let data = match std::fs::read(path) {
+        Ok(data) => data,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read {}: {}", path.display(), e)),
+    };
+    let file: JournalFile = serde_json::from_slice(&data)
+        .map_err(|e| format!("Invalid hot key journal {}: {}", path.display(), e))?;
+    if file.version != JOURNAL_VERSION {
+        return Err(format!(
+            "Unsupported hot key journal version {} in {}",
+            file.version,
+            path.display()
+        ));
+    }
+    Ok(file.keys)
+}
+
+/// Fetch journal keys from S3 into the cache
+///
+/// Keys for buckets that are no longer configured, have caching disabled or
+/// are served by a non-S3 origin, and objects larger than
+/// `max_item_size_bytes`, are skipped.
+pub async fn warm_from_journal(
+    cache: Arc<dyn Cache>,
+    keys: Vec<HotKey>,
+    buckets: &[BucketConfig],
+    max_item_size_bytes: u64,
+    concurrency: usize,
+) -> WarmSummary {
+    let mut summary = WarmSummary::default();
+    let mut clients = HashMap::new();
+    let mut jobs = Vec::with_capacity(keys.len());
+
+    for hot in keys {
+        let Some(bucket) = buckets.iter().find(|b| b.name == hot.bucket) else {
+            summary.skipped += 1;
+            continue;
+        };
+        // Keys are fetched with GetObject, so buckets with a non-S3 origin are skipped
+        if bucket.cache.as_ref().and_then(|c| c.enabled) == Some(false) || bucket.origin.is_some() {
+            summary.skipped += 1;
+            continue;
+        }
+        if !clients.contains_key(&bucket.name) {
+            let client = S3Client {
+                config: bucket.s3.clone(),
+            }
+            .create_aws_client()
+            .await;
+            clients.insert(bucket.name.clone(), client);
+        }
+        jobs.push((clients[&bucket.name].clone(), bucket.s3.bucket.clone(), hot));
+    }
+
+    let results: Vec<Result<bool, String>> = stream::iter(jobs)
+        .map(|(client, s3_bucket, hot)| {
+            let cache = cache.clone();
+            async move {
+                let resp = client
+                    .get_object()
+                    .bucket(&s3_bucket)
+                    .key(&hot.key)
+                    .send()
+                    .await
+                    .map_err(|e| format!("{}/{}: {}", hot.bucket, hot.key, e))?;
+                if resp
+                    .content_length
+                    .is_some_and(|len| len as u64 > max_item_size_bytes)
+                {
+                    return Ok(false);
+                }
+                let content_type = resp
+                    .content_type
+                    .clone()
+                    .unwrap_or_else(|| "application/octet-stream".to_string());
+                let etag = resp.e_tag.clone().unwrap_or_default();
+                let last_modified = resp
+                    .last_modified
+                    .and_then(|t| t.fmt(DateTimeFormat::HttpDate).ok());
+                let data = resp
+                    .body
+                    .collect()
+                    .await
+                    .map_err(|e| format!("{}/{}: {}", hot.bucket, hot.key, e))?
+                    .into_bytes();
+
+                let key = CacheKey {
+                    bucket: hot.bucket.clone(),
+                    object_key: hot.key.clone(),
+                    etag: None,
+                    variant: None,
+                };
+                let entry = CacheEntry::new(data, content_type, etag, last_modified, None);
+                cache
+                    .set(key, entry)
+                    .await
+                    .map_err(|e| format!("{}/{}: {}", hot.bucket, hot.key, e))?;
+                Ok(true)
+            }
+        })
+        .buffer_unordered(concurrency.max(1))
+        .collect()
+        .await;
+
+    for result in results {
+        match result {
+            Ok(true) => summary.warmed += 1,
+            Ok(false) => summary.skipped += 1,
+            Err(e) => {
+                tracing::debug!(error = %e, "Failed to warm hot key");
+                summary.failed += 1;
+            }
+        }
+    }
+    summary
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn new_journal(dir: &TempDir, top_k: usize) -> HotKeyJournal {
+        HotKeyJournal::new(HotKeyJournalConfig {
+            path: dir.path().join("hot_keys.json").display().to_string(),
+            top_k,
+            flush_interval_secs: 60,
+            warm_concurrency: 4,
+        })
+    }
+
+    #[test]
+    fn test_top_keys_sorted_by_hits() {
+        let dir = TempDir::new().unwrap();
+        let journal = new_journal(&dir, 2);
+
+        for _ in 0..3 {
+            journal.record("media", "a.png");
+        }
+        journal.record("media", "b.png");
+        journal.record("media", "b.png");
+        journal.record("docs", "c.pdf");
+
+        let top = journal.top_keys();
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].key, "a.png");
+        assert_eq!(top[0].hits, 3);
+        assert_eq!(top[1].key, "b.png");
+    }
+
+    #[test]
+    fn test_record_prunes_to_top_k() {
+        let dir = TempDir::new().unwrap();
+        let journal = new_journal(&dir, 2);
+
+        journal.record("media", "hot.png");
+        journal.record("media", "hot.png");
+        for i in 0..(2 * PRUNE_FACTOR) {
+            journal.record("media", &format!("cold-{}.png", i));
+        }
+
+        assert!(journal.counts.lock().len() <= 2 * PRUNE_FACTOR);
+        assert_eq!(journal.top_keys()[0].key, "hot.png");
+    }
+
+    #[test]
+    fn test_flush_and_load_round_trip() {
+        let dir = TempDir::new().unwrap();
+        let journal = new_journal(&dir, 10);
+        assert!(journal.load().unwrap().is_empty());
+
+        for _ in 0..4 {
+            journal.record("media", "a.png");
+        }
+        journal.record("media", "b.png");
+        assert_eq!(journal.flush().unwrap(), 2);
+
+        let loaded = journal.load().unwrap();
+        assert_eq!(loaded[0].key, "a.png");
+        assert_eq!(loaded[0].hits, 4);
+
+        // Counts decay after a flush; single hits drop out
+        let top = journal.top_keys();
+        assert_eq!(top.len(), 1);
+        assert_eq!(top[0].hits, 2);
+
+        let restarted = new_journal(&dir, 10);
+        restarted.seed(&loaded);
+        assert_eq!(restarted.top_keys()[0].hits, 4);
+    }
+
+    #[tokio::test]
+    async fn test_warm_skips_buckets_with_non_s3_origin() {
+        let cache: Arc<dyn Cache> = Arc::new(crate::cache::MemoryCache::new(
+            &crate::cache::MemoryCacheConfig::default(),
+        ));
+        let assets: BucketConfig = serde_yaml::from_str(
+            r#"
+name: "assets"
+path_prefix: "/assets"
+origin:
+  type: filesystem
+  root: "/srv/assets"
+"#,
+        )
+        .unwrap();
+        let keys = vec![HotKey {
+            bucket: "assets".to_string(),
+            key: "logo.png".to_string(),
+            hits: 5,
+        }];
+
+        let summary = warm_from_journal(cache, keys, &[assets], 1024 * 1024, 4).await;
+        assert_eq!((summary.warmed, summary.skipped, summary.failed), (0, 1, 0));
+    }
+
+    #[test]
+    fn test_load_rejects_invalid_journal() {
+        let dir = TempDir::new().unwrap();
+        let journal = new_journal(&dir, 10);
+        std::fs::write(dir.path().join("hot_keys.json"), b"not json").unwrap();
+        assert!(journal.load().is_err());
+    }
+}
@@ -225,6 +225,23 @@ impl Cache for MemoryCache {
         Ok(count)
     }
 
+    async fn delete_object(&self, bucket: &str, object_key: &str) -> Result<usize, CacheError> {
+        let keys_to_delete: Vec<CacheKey> = self
+            .cache
+            .iter()
+            .filter(|(k, _)| k.bucket == bucket && k.object_key == object_key)
+            .map(|(k, _)| (*k).clone())
+            .collect();
+
+        let count = keys_to_delete.len();
+        for key in keys_to_delete {
+            self.cache.invalidate(&key).await;
+        }
+        self.cache.run_pending_tasks().await;
+
+        Ok(count)
+    }
+
     async fn stats(&self) -> Result<CacheStats, CacheError> {
         Ok(self.get_stats())
     }
@@ -66,18 +66,53 @@ pub mod redis;
 // Tiered cache submodule (Phase 30)
 pub mod tiered;
 
+// Automatic quarantine of failing cache layers
+pub mod quarantine;
+
+// Hot key journal for startup cache warming
+pub mod journal;
+
+// zstd compression of stored entries
+pub mod compression;
+
+// ETag comparison aware of multipart ETags
+pub mod etag;
+
 // Cache warming submodule (Phase 1.3)
 pub mod warming;
 
+// Access log replay into the cache warmer
+pub mod replay;
+
 // sendfile support for zero-copy file serving (v1.4)
 pub mod sendfile;
 
+// Zero-copy slicing and memory-mapped reads for cache hits
+pub mod zero_copy;
+
 // Cache-Control header parsing for RFC 7234 compliance (Phase 36)
 pub mod control;
 
+// Whether an upstream response may populate the cache, with skip reasons
+pub mod admission;
+
+// A/B experiments between two cache policies
+pub mod experiment;
+
+// Cache entry TTL from upstream Cache-Control / Expires headers
+pub mod ttl;
+
+// Upstream response headers stored with entries and replayed on hits
+pub mod preserve;
+
+// Per-bucket cache policy: path patterns, Content-Types and size limits
+pub mod policy;
+
 // Re-export configuration types
 pub use config::{
-    BucketCacheOverride, CacheConfig, DiskCacheConfig, MemoryCacheConfig, RedisCacheConfig,
+    BucketCacheOverride, CacheCompressionConfig, CacheConfig, CacheExperimentConfig,
+    CacheVariantConfig, DiskCacheConfig, HotKeyJournalConfig, LayerQuarantineConfig,
+    MemoryCacheConfig, RangeChunkConfig, RedisCacheConfig,
 };
 
 // Re-export sendfile types
@@ -101,6 +136,9 @@ pub use memory::{MemoryCache, NullCache};
 // Re-export Cache-Control types
 pub use control::CacheControl;
 
+// Re-export admission types
+pub use admission::CacheSkipReason;
+
 // ============================================================
 // Cache Factory Function
 // ============================================================
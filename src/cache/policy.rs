@@ -0,0 +1,217 @@
+//! Per-bucket cache policy.
+//!
+//! A bucket's `cache` block decides which of its objects use the cache at
+//! all, on top of the global cache settings:
+//!
+//! - `enabled: false` turns caching off for the bucket
+//! - `include_paths` / `exclude_paths` select object keys by glob (`*` within
+//!   a segment, `**` across segments)
+//! - `content_types` / `exclude_content_types` select responses by
+//!   Content-Type (`image/*` matches a whole type)
+//! - `max_item_size_mb` caps the body buffered for the cache
+//!
+//! Paths are known before the request goes upstream, so they also decide
+//! whether the cache is looked up ([`allows_key`]). Content-Type and size are
+//! only known from the response ([`admit`]).
+
+use super::admission::CacheSkipReason;
+use super::config::{BucketCacheOverride, CacheConfig};
+use crate::auth::path_rules::path_matches;
+use crate::config::BucketConfig;
+use crate::constants::DEFAULT_MAX_ITEM_SIZE_MB;
+
+/// Whether `key` of this bucket may be served from or stored in the cache
+pub fn allows_key(bucket_config: &BucketConfig, key: &str) -> bool {
+    let Some(cache) = &bucket_config.cache else {
+        return true;
+    };
+    if cache.enabled == Some(false) {
+        return false;
+    }
+    if !cache.include_paths.is_empty() && !cache.include_paths.iter().any(|p| path_matches(p, key))
+    {
+        return false;
+    }
+    !cache.exclude_paths.iter().any(|p| path_matches(p, key))
+}
+
+/// Whether a response with this Content-Type may be stored
+///
+/// A response without a Content-Type is only stored when the bucket has no
+/// `content_types` allowlist.
+pub fn allows_content_type(bucket_config: &BucketConfig, content_type: Option<&str>) -> bool {
+    let Some(cache) = &bucket_config.cache else {
+        return true;
+    };
+    let Some(content_type) = content_type else {
+        return cache.content_types.is_empty();
+    };
+    let content_type = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    (cache.content_types.is_empty() || matches_any(&cache.content_types, &content_type))
+        && !matches_any(&cache.exclude_content_types, &content_type)
+}
+
+/// Largest body stored in the cache for this bucket, in bytes
+pub fn max_item_bytes(bucket_config: &BucketConfig, global: Option<&CacheConfig>) -> u64 {
+    let max_item_size_mb = bucket_config
+        .cache
+        .as_ref()
+        .and_then(|c| c.max_item_size_mb)
+        .or(global.map(|c| c.memory.max_item_size_mb))
+        .unwrap_or(DEFAULT_MAX_ITEM_SIZE_MB);
+    max_item_size_mb * 1024 * 1024
+}
+
+/// Decide whether the bucket's policy lets a response for `key` be cached
+///
+/// `content_length` is checked against the bucket's size limit when known,
+/// so oversized objects are never buffered.
+pub fn admit(
+    bucket_config: &BucketConfig,
+    key: &str,
+    content_type: Option<&str>,
+    content_length: Option<u64>,
+    global: Option<&CacheConfig>,
+) -> Result<(), CacheSkipReason> {
+    if !allows_key(bucket_config, key) {
+        return Err(CacheSkipReason::BucketPolicy);
+    }
+    if !allows_content_type(bucket_config, content_type) {
+        return Err(CacheSkipReason::ContentType);
+    }
+    if content_length.is_some_and(|len| len > max_item_bytes(bucket_config, global)) {
+        return Err(CacheSkipReason::TooLarge);
+    }
+    Ok(())
+}
+
+/// Validate the path and Content-Type patterns of a bucket's `cache` block
+pub fn validate(cache: &BucketCacheOverride) -> Result<(), String> {
+    for (field, patterns) in [
+        ("include_paths", &cache.include_paths),
+        ("exclude_paths", &cache.exclude_paths),
+    ] {
+        if patterns
+            .iter()
+            .any(|p| p.trim_start_matches('/').is_empty())
+        {
+            return Err(format!("{} entries cannot be empty", field));
+        }
+    }
+    for (field, types) in [
+        ("content_types", &cache.content_types),
+        ("exclude_content_types", &cache.exclude_content_types),
+    ] {
+        if let Some(content_type) = types
+            .iter()
+            .find(|c| !c.contains('/') || c.contains(';') || c.trim() != c.as_str())
+        {
+            return Err(format!(
+                "{} entry '{}' must be type/subtype or type/*",
+                field, content_type
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Whether `content_type` (lowercase, without parameters) matches a pattern
+fn matches_any(patterns: &[String], content_type: &str) -> bool {
+    patterns.iter().any(|pattern| {
+        let pattern = pattern.to_ascii_lowercase();
+        match pattern.strip_suffix("/*") {
+            Some(major) => content_type
+                .split_once('/')
+                .is_some_and(|(m, _)| m == major),
+            None => content_type == pattern,
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bucket(cache: &str) -> BucketConfig {
+        let yaml = format!(
+            r#"
+name: assets
+path_prefix: /assets
+s3:
+  bucket: assets
+  region: us-east-1
+  access_key: test
+  secret_key: test
+cache:
+{}
+"#,
+            cache
+        );
+        serde_yaml::from_str(&yaml).unwrap()
+    }
+
+    #[test]
+    fn test_bucket_cache_policy() {
+        let config = bucket(
+            r#"  include_paths: ["static/**", "/img/*.png"]
+  exclude_paths: ["static/private/**"]
+  content_types: ["image/*", "text/css"]
+  exclude_content_types: ["image/svg+xml"]
+  max_item_size_mb: 2"#,
+        );
+        assert!(validate(config.cache.as_ref().unwrap()).is_ok());
+
+        assert!(allows_key(&config, "static/app/main.css"));
+        assert!(allows_key(&config, "/img/logo.png"));
+        assert!(!allows_key(&config, "img/2024/logo.png"));
+        assert!(!allows_key(&config, "static/private/keys.txt"));
+        assert!(!allows_key(&config, "index.html"));
+
+        assert!(allows_content_type(&config, Some("Image/PNG")));
+        assert!(allows_content_type(
+            &config,
+            Some("text/css; charset=utf-8")
+        ));
+        assert!(!allows_content_type(&config, Some("image/svg+xml")));
+        assert!(!allows_content_type(&config, Some("text/html")));
+        assert!(!allows_content_type(&config, None));
+
+        let key = "static/app/logo.png";
+        assert_eq!(
+            admit(&config, key, Some("image/png"), Some(1024), None),
+            Ok(())
+        );
+        assert_eq!(
+            admit(&config, key, Some("image/png"), Some(3 * 1024 * 1024), None),
+            Err(CacheSkipReason::TooLarge)
+        );
+        assert_eq!(
+            admit(&config, key, Some("text/html"), None, None),
+            Err(CacheSkipReason::ContentType)
+        );
+        assert_eq!(
+            admit(&config, "index.html", Some("image/png"), None, None),
+            Err(CacheSkipReason::BucketPolicy)
+        );
+
+        // Disabled buckets never use the cache; buckets without a block always may
+        let disabled = bucket("  enabled: false");
+        assert!(!allows_key(&disabled, "static/app/main.css"));
+        let mut open = disabled.clone();
+        open.cache = None;
+        assert!(allows_key(&open, "anything"));
+        assert!(allows_content_type(&open, None));
+        assert_eq!(
+            max_item_bytes(&open, None),
+            DEFAULT_MAX_ITEM_SIZE_MB * 1024 * 1024
+        );
+
+        assert!(validate(bucket("  content_types: [image]").cache.as_ref().unwrap()).is_err());
+        assert!(validate(bucket("  exclude_paths: [\"/\"]").cache.as_ref().unwrap()).is_err());
+    }
+}
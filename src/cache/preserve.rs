@@ -0,0 +1,113 @@
+//! Upstream response headers kept with cache entries.
+//!
+//! A cache hit rebuilds the response from the entry, which only knows the
+//! Content-Type, ETag and Last-Modified. Headers such as `Content-Disposition`
+//! or `x-amz-meta-*` would be lost, so the ones named in
+//! `cache.preserve_headers` are stored with the entry when it is filled and
+//! replayed on every hit. A pattern ending in `*` matches a name prefix.
+//!
+//! Headers the hit path writes itself (Content-Type, Content-Length, ETag,
+//! Last-Modified, Content-Encoding, ...) are never stored, whatever the
+//! patterns say.
+
+use http::HeaderMap;
+
+/// Headers the cache hit path sets itself, plus hop-by-hop headers
+const MANAGED_HEADERS: &[&str] = &[
+    "accept-ranges",
+    "connection",
+    "content-encoding",
+    "content-length",
+    "content-range",
+    "content-type",
+    "etag",
+    "keep-alive",
+    "last-modified",
+    "transfer-encoding",
+    "vary",
+    "x-cache",
+];
+
+/// Whether `name` matches one of `patterns` (case-insensitive)
+pub fn matches(patterns: &[String], name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    if MANAGED_HEADERS.contains(&name.as_str()) {
+        return false;
+    }
+    patterns.iter().any(|pattern| {
+        let pattern = pattern.to_ascii_lowercase();
+        match pattern.strip_suffix('*') {
+            Some(prefix) => name.starts_with(prefix),
+            None => name == pattern,
+        }
+    })
+}
+
+/// Upstream headers to store with an entry, in response order
+pub fn capture(patterns: &[String], headers: &HeaderMap) -> Vec<(String, String)> {
+    if patterns.is_empty() {
+        return Vec::new();
+    }
+    headers
+        .iter()
+        .filter(|(name, _)| matches(patterns, name.as_str()))
+        .filter_map(|(name, value)| {
+            value
+                .to_str()
+                .ok()
+                .map(|value| (name.as_str().to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+/// Validate `cache.preserve_headers`
+pub fn validate(patterns: &[String]) -> Result<(), String> {
+    for pattern in patterns {
+        let name = pattern.strip_suffix('*').unwrap_or(pattern);
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(format!(
+                "cache.preserve_headers: '{}' must be a header name, optionally ending in '*'",
+                pattern
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_preserved_headers() {
+        let patterns: Vec<String> = ["Content-Disposition", "x-amz-meta-*", "content-*"]
+            .iter()
+            .map(|p| p.to_string())
+            .collect();
+        assert!(validate(&patterns).is_ok());
+        assert!(validate(&["*".to_string()]).is_err());
+        assert!(validate(&["x amz".to_string()]).is_err());
+
+        let mut headers = HeaderMap::new();
+        headers.insert("content-disposition", "attachment".parse().unwrap());
+        headers.insert("x-amz-meta-owner", "alice".parse().unwrap());
+        headers.insert("content-language", "de".parse().unwrap());
+        headers.insert("content-length", "42".parse().unwrap());
+        headers.insert("x-amz-request-id", "abc".parse().unwrap());
+        let mut captured = capture(&patterns, &headers);
+        captured.sort();
+        assert_eq!(
+            captured,
+            vec![
+                ("content-disposition".to_string(), "attachment".to_string()),
+                ("content-language".to_string(), "de".to_string()),
+                ("x-amz-meta-owner".to_string(), "alice".to_string()),
+            ]
+        );
+        assert!(capture(&[], &headers).is_empty());
+    }
+}
@@ -0,0 +1,188 @@
+//! Automatic quarantine of failing cache layers.
+//!
+//! A disk or Redis layer that starts failing (full disk, dead Redis, slow
+//! timeouts) still costs its error latency on every request, because
+//! [`TieredCache`](crate::cache::tiered::TieredCache) tries each layer in
+//! turn. [`LayerHealth`] counts errors in a fixed window; once a layer
+//! reaches `error_threshold` it is quarantined (skipped) for `cooldown_secs`,
+//! after which it is tried again with a clean slate.
+//!
+//! Quarantine transitions are logged and exported as
+//! `yatagarasu_cache_layer_quarantines_total` and
+//! `yatagarasu_cache_layer_quarantined`.
+
+use parking_lot::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::cache::LayerQuarantineConfig;
+use crate::metrics::Metrics;
+
+#[derive(Debug)]
+struct HealthState {
+    window_start: Instant,
+    errors: u32,
+    quarantined_until: Option<Instant>,
+}
+
+/// Error tracking and quarantine state for a single cache layer
+#[derive(Debug)]
+pub struct LayerHealth {
+    layer: String,
+    config: LayerQuarantineConfig,
+    state: Mutex<HealthState>,
+}
+
+impl LayerHealth {
+    /// Create health tracking for the named layer (`disk`, `redis`, ...)
+    pub fn new(layer: impl Into<String>, config: LayerQuarantineConfig) -> Self {
+        Self {
+            layer: layer.into(),
+            config,
+            state: Mutex::new(HealthState {
+                window_start: Instant::now(),
+                errors: 0,
+                quarantined_until: None,
+            }),
+        }
+    }
+
+    /// Name of the tracked layer
+    pub fn layer(&self) -> &str {
+        &self.layer
+    }
+
+    /// Check if the layer should be used, lifting an expired quarantine
+    pub fn is_available(&self) -> bool {
+        self.is_available_at(Instant::now())
+    }
+
+    /// Check if the layer is currently quarantined (does not lift expired quarantines)
+    pub fn is_quarantined(&self) -> bool {
+        self.state
+            .lock()
+            .quarantined_until
+            .is_some_and(|until| Instant::now() < until)
+    }
+
+    /// Record a failed operation; returns true if this error quarantined the layer
+    pub fn record_error(&self) -> bool {
+        Metrics::global().increment_cache_layer_error(&self.layer);
+        let quarantined = self.record_error_at(Instant::now());
+        if quarantined {
+            tracing::warn!(
+                layer = %self.layer,
+                errors = self.config.error_threshold,
+                window_secs = self.config.window_secs,
+                cooldown_secs = self.config.cooldown_secs,
+                "Cache layer quarantined after repeated errors"
+            );
+            Metrics::global().record_cache_layer_quarantine(&self.layer);
+        }
+        quarantined
+    }
+
+    fn is_available_at(&self, now: Instant) -> bool {
+        let mut state = self.state.lock();
+        match state.quarantined_until {
+            Some(until) if now < until => false,
+            Some(_) => {
+                state.quarantined_until = None;
+                state.errors = 0;
+                state.window_start = now;
+                drop(state);
+                tracing::info!(layer = %self.layer, "Cache layer quarantine lifted");
+                Metrics::global().set_cache_layer_quarantined(&self.layer, false);
+                true
+            }
+            None => true,
+        }
+    }
+
+    fn record_error_at(&self, now: Instant) -> bool {
+        if !self.config.enabled {
+            return false;
+        }
+        let mut state = self.state.lock();
+        if state.quarantined_until.is_some() {
+            return false;
+        }
+        if now.duration_since(state.window_start) >= Duration::from_secs(self.config.window_secs) {
+            state.window_start = now;
+            state.errors = 0;
+        }
+        state.errors += 1;
+        if state.errors >= self.config.error_threshold {
+            state.quarantined_until = Some(now + Duration::from_secs(self.config.cooldown_secs));
+            return true;
+        }
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn health(threshold: u32) -> LayerHealth {
+        LayerHealth::new(
+            "disk",
+            LayerQuarantineConfig {
+                error_threshold: threshold,
+                window_secs: 10,
+                cooldown_secs: 30,
+                ..Default::default()
+            },
+        )
+    }
+
+    #[test]
+    fn test_quarantine_after_threshold_errors() {
+        let health = health(3);
+        let now = Instant::now();
+
+        assert!(!health.record_error_at(now));
+        assert!(!health.record_error_at(now));
+        assert!(health.is_available_at(now));
+        assert!(health.record_error_at(now));
+        assert!(!health.is_available_at(now + Duration::from_secs(29)));
+        // Errors while quarantined do not re-trigger
+        assert!(!health.record_error_at(now));
+    }
+
+    #[test]
+    fn test_quarantine_lifts_after_cooldown() {
+        let health = health(1);
+        let now = Instant::now();
+
+        assert!(health.record_error_at(now));
+        assert!(!health.is_available_at(now));
+        assert!(health.is_available_at(now + Duration::from_secs(30)));
+        // Fresh window after the cooldown
+        assert!(health.record_error_at(now + Duration::from_secs(31)));
+    }
+
+    #[test]
+    fn test_errors_outside_window_do_not_accumulate() {
+        let health = health(2);
+        let now = Instant::now();
+
+        assert!(!health.record_error_at(now));
+        assert!(!health.record_error_at(now + Duration::from_secs(11)));
+        assert!(health.record_error_at(now + Duration::from_secs(12)));
+    }
+
+    #[test]
+    fn test_disabled_quarantine_never_triggers() {
+        let health = LayerHealth::new(
+            "redis",
+            LayerQuarantineConfig {
+                enabled: false,
+                error_threshold: 1,
+                ..Default::default()
+            },
+        );
+        assert!(!health.record_error());
+        assert!(health.is_available());
+        assert!(!health.is_quarantined());
+    }
+}
@@ -30,6 +30,12 @@ struct SerializableCacheEntry {
     expires_at_secs: u64,
     /// Last time this entry was accessed (seconds since UNIX_EPOCH)
     last_accessed_at_secs: u64,
+    /// Encoding of `data` when stored compressed (e.g. "zstd")
+    #[serde(default)]
+    content_encoding: Option<String>,
+    /// Upstream response headers replayed on hits
+    #[serde(default)]
+    headers: Vec<(String, String)>,
 }
 
 /// Serializes a CacheEntry to bytes using MessagePack
@@ -67,6 +73,8 @@ pub fn serialize_entry(entry: &CacheEntry) -> Result<Vec<u8>, CacheError> {
                 CacheError::SerializationError(format!("Invalid last_accessed_at: {}", e))
             })?
             .as_secs(),
+        content_encoding: entry.content_encoding.clone(),
+        headers: entry.headers.clone(),
     };
 
     rmp_serde::to_vec(&serializable)
@@ -130,6 +138,8 @@ pub fn deserialize_entry(bytes: &[u8]) -> Result<CacheEntry, CacheError> {
         created_at,
         expires_at,
         last_accessed_at,
+        content_encoding: serializable.content_encoding,
+        headers: serializable.headers,
     })
 }
 
@@ -278,6 +288,16 @@ mod tests {
         assert_eq!(deserialized.etag, entry.etag);
     }
 
+    #[test]
+    fn test_round_trips_preserved_headers() {
+        let entry = create_test_entry().with_headers(vec![(
+            "content-disposition".to_string(),
+            "attachment; filename=\"report.pdf\"".to_string(),
+        )]);
+        let deserialized = deserialize_entry(&serialize_entry(&entry).unwrap()).unwrap();
+        assert_eq!(deserialized.headers, entry.headers);
+    }
+
     #[test]
     fn test_validates_version_marker() {
         // Test: Validates version marker (schema version)
@@ -353,6 +373,8 @@ mod tests {
             created_at_secs: 1000,
             expires_at_secs: 2000,
             last_accessed_at_secs: 1000,
+            content_encoding: None,
+            headers: Vec::new(),
         };
 
         let bytes = rmp_serde::to_vec(&serializable).unwrap();
@@ -380,6 +402,8 @@ mod tests {
             created_at_secs: 1000,
             expires_at_secs: 2000,
             last_accessed_at_secs: 1000,
+            content_encoding: None,
+            headers: Vec::new(),
         };
 
         let bytes = rmp_serde::to_vec(&serializable).unwrap();
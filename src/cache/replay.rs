@@ -0,0 +1,221 @@
+//! Access log replay for cache warming.
+//!
+//! Reads the audit log (one JSON entry per line, see [`crate::audit`]) and its
+//! rotated backups, counts successful GETs per object inside a lookback window
+//! and returns the most requested keys. `POST /admin/cache/prewarm/replay`
+//! hands them to the [`PrewarmManager`](crate::cache::warming::PrewarmManager),
+//! so the cache is warmed with what clients actually requested.
+
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use crate::audit::AuditLogEntry;
+use crate::cache::journal::HotKey;
+
+/// Keys returned when `top` is not given
+const DEFAULT_TOP_KEYS: usize = 1000;
+
+/// Which keys to extract from the access log
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReplayOptions {
+    /// Number of most requested keys to return (default: 1000)
+    #[serde(default = "default_top")]
+    pub top: usize,
+    /// Only count requests from the last `since_secs` seconds (default: all)
+    #[serde(default)]
+    pub since_secs: Option<u64>,
+}
+
+impl Default for ReplayOptions {
+    fn default() -> Self {
+        Self {
+            top: default_top(),
+            since_secs: None,
+        }
+    }
+}
+
+fn default_top() -> usize {
+    DEFAULT_TOP_KEYS
+}
+
+/// Request counts per (bucket, object key)
+#[derive(Debug, Default)]
+pub struct KeyCounter {
+    counts: HashMap<(String, String), u64>,
+    /// Lines that were not valid audit entries
+    pub skipped_lines: usize,
+}
+
+impl KeyCounter {
+    /// Count the successful GETs in one log, ignoring entries before `cutoff`
+    pub fn count(&mut self, reader: impl BufRead, cutoff: Option<DateTime<Utc>>) {
+        for line in reader.lines() {
+            let Ok(line) = line else {
+                self.skipped_lines += 1;
+                continue;
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            let Ok(entry) = serde_json::from_str::<AuditLogEntry>(&line) else {
+                self.skipped_lines += 1;
+                continue;
+            };
+            let served =
+                (200..300).contains(&entry.response_status) || entry.response_status == 304;
+            if entry.http_method != "GET" || !served || entry.object_key.is_empty() {
+                continue;
+            }
+            if cutoff.is_some_and(|cutoff| entry.timestamp < cutoff) {
+                continue;
+            }
+            *self
+                .counts
+                .entry((entry.bucket, entry.object_key))
+                .or_insert(0) += 1;
+        }
+    }
+
+    /// The `top` most requested keys, most requested first
+    pub fn top_keys(self, top: usize) -> Vec<HotKey> {
+        let mut keys: Vec<HotKey> = self
+            .counts
+            .into_iter()
+            .map(|((bucket, key), hits)| HotKey { bucket, key, hits })
+            .collect();
+        keys.sort_by(|a, b| {
+            b.hits
+                .cmp(&a.hits)
+                .then_with(|| a.bucket.cmp(&b.bucket))
+                .then_with(|| a.key.cmp(&b.key))
+        });
+        keys.truncate(top);
+        keys
+    }
+}
+
+/// The audit log and its rotated backups (`{stem}.{timestamp}[.{ext}]`)
+fn log_files(path: &Path) -> Vec<PathBuf> {
+    let parent = path
+        .parent()
+        .filter(|p| !p.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+    let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+    let prefix = format!("{}.", stem);
+
+    let mut files: Vec<PathBuf> = std::fs::read_dir(parent)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|p| {
+            p != path
+                && p.file_name()
+                    .is_some_and(|name| name.to_string_lossy().starts_with(&prefix))
+        })
+        .collect();
+    files.sort();
+    files.push(path.to_path_buf());
+    files
+}
+
+/// Most requested keys in the audit log at `path` and its rotated backups
+pub fn hot_keys_from_audit_log(
+    path: &Path,
+    options: &ReplayOptions,
+) -> Result<Vec<HotKey>, String> {
+    if !path.exists() {
+        return Err(format!("Audit log {} not found", path.display()));
+    }
+    let cutoff = options
+        .since_secs
+        .map(|secs| Utc::now() - ChronoDuration::seconds(secs.min(i64::MAX as u64) as i64));
+
+    let mut counter = KeyCounter::default();
+    for file in log_files(path) {
+        let reader = File::open(&file)
+            .map(BufReader::new)
+            .map_err(|e| format!("Failed to read {}: {}", file.display(), e))?;
+        counter.count(reader, cutoff);
+    }
+    if counter.skipped_lines > 0 {
+        tracing::debug!(
+            path = %path.display(),
+            skipped = counter.skipped_lines,
+            "Skipped unparseable audit log lines during replay"
+        );
+    }
+    Ok(counter.top_keys(options.top))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn line(bucket: &str, key: &str, method: &str, status: u16, age_secs: i64) -> String {
+        serde_json::json!({
+            "timestamp": (Utc::now() - ChronoDuration::seconds(age_secs)).to_rfc3339(),
+            "correlation_id": "id",
+            "client_ip": "10.0.0.1",
+            "bucket": bucket,
+            "object_key": key,
+            "http_method": method,
+            "request_path": format!("/{}/{}", bucket, key),
+            "response_status": status,
+            "response_size_bytes": 10,
+            "duration_ms": 1,
+            "cache_status": "miss"
+        })
+        .to_string()
+    }
+
+    #[test]
+    fn test_replay_counts_recent_successful_gets() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("audit.log");
+        let current = [
+            line("media", "a.jpg", "GET", 200, 10),
+            line("media", "a.jpg", "GET", 304, 10),
+            line("media", "b.jpg", "GET", 200, 10),
+            line("media", "c.jpg", "HEAD", 200, 10),
+            line("media", "d.jpg", "GET", 404, 10),
+            line("media", "old.jpg", "GET", 200, 7200),
+            "not json".to_string(),
+        ];
+        std::fs::write(&path, current.join("\n")).unwrap();
+        // Rotated backup from the audit writer
+        std::fs::write(
+            dir.path().join("audit.20260101_000000.log"),
+            line("docs", "b.pdf", "GET", 200, 10),
+        )
+        .unwrap();
+
+        let options = ReplayOptions {
+            top: 10,
+            since_secs: Some(3600),
+        };
+        let keys = hot_keys_from_audit_log(&path, &options).unwrap();
+        let names: Vec<_> = keys
+            .iter()
+            .map(|k| (k.bucket.as_str(), k.key.as_str()))
+            .collect();
+        assert_eq!(
+            names,
+            vec![("media", "a.jpg"), ("docs", "b.pdf"), ("media", "b.jpg")]
+        );
+        assert_eq!(keys[0].hits, 2);
+
+        let top_one = ReplayOptions {
+            top: 1,
+            since_secs: None,
+        };
+        assert_eq!(hot_keys_from_audit_log(&path, &top_one).unwrap().len(), 1);
+        assert!(hot_keys_from_audit_log(&dir.path().join("missing.log"), &options).is_err());
+    }
+}
@@ -26,6 +26,7 @@
 //!         content_type: "application/octet-stream".to_string(),
 //!         etag: Some("abc123".to_string()),
 //!         last_modified: None,
+//!         headers: Vec::new(),
 //!     };
 //!
 //!     let bytes_sent = sendfile_to_fd(socket_fd, &response)?;
@@ -50,6 +51,8 @@ pub struct SendfileResponse {
     pub etag: Option<String>,
     /// Last-Modified header value
     pub last_modified: Option<String>,
+    /// Upstream response headers replayed on hits
+    pub headers: Vec<(String, String)>,
 }
 
 impl SendfileResponse {
@@ -68,6 +71,7 @@ impl SendfileResponse {
             content_type,
             etag,
             last_modified,
+            headers: Vec::new(),
         }
     }
 
@@ -87,6 +91,7 @@ impl SendfileResponse {
             content_type,
             etag,
             last_modified,
+            headers: Vec::new(),
         }
     }
 }
@@ -274,6 +279,69 @@ pub async fn sendfile_to_fd_async(
     ))
 }
 
+/// Default read size for file-backed streaming of disk cache hits
+pub const FILE_STREAM_CHUNK_SIZE: usize = 256 * 1024; // 256KB
+
+/// File-backed streaming of a disk cache entry
+///
+/// Used when the downstream socket isn't directly accessible (e.g. behind
+/// Pingora's session abstraction): reads the entry's range in bounded chunks
+/// instead of loading the whole file, so multi-GB assets are served with
+/// constant memory.
+#[derive(Debug)]
+pub struct FileChunkStream {
+    file: tokio::fs::File,
+    remaining: u64,
+    chunk_size: usize,
+}
+
+impl FileChunkStream {
+    /// Open the file described by `response` and seek to its offset
+    pub async fn open(response: &SendfileResponse, chunk_size: usize) -> std::io::Result<Self> {
+        use tokio::io::AsyncSeekExt;
+
+        let mut file = tokio::fs::File::open(&response.file_path).await?;
+        let file_len = file.metadata().await?.len();
+        let available = file_len.saturating_sub(response.offset);
+        let remaining = if response.length == 0 {
+            available
+        } else {
+            response.length.min(available)
+        };
+
+        if response.offset > 0 {
+            file.seek(std::io::SeekFrom::Start(response.offset)).await?;
+        }
+
+        Ok(Self {
+            file,
+            remaining,
+            chunk_size: chunk_size.max(1),
+        })
+    }
+
+    /// Bytes left to stream
+    pub fn remaining(&self) -> u64 {
+        self.remaining
+    }
+
+    /// Read the next chunk, or `None` once the range has been fully read
+    pub async fn next_chunk(&mut self) -> std::io::Result<Option<bytes::Bytes>> {
+        use tokio::io::AsyncReadExt;
+
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+
+        let to_read = std::cmp::min(self.remaining, self.chunk_size as u64) as usize;
+        let mut buf = bytes::BytesMut::zeroed(to_read);
+        self.file.read_exact(&mut buf).await?;
+        self.remaining -= to_read as u64;
+
+        Ok(Some(buf.freeze()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -680,4 +748,77 @@ threshold_bytes: 131072
             assert_eq!(result.unwrap_err().kind(), std::io::ErrorKind::Unsupported);
         }
     }
+
+    // =========================================================================
+    // FileChunkStream Tests
+    // =========================================================================
+
+    #[tokio::test]
+    async fn test_file_chunk_stream_reads_whole_file_in_chunks() {
+        use std::io::Write;
+
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        let test_data: Vec<u8> = (0..255u8).cycle().take(10_000).collect();
+        src_file.write_all(&test_data).unwrap();
+        src_file.flush().unwrap();
+
+        let response = SendfileResponse::new(
+            src_file.path().to_path_buf(),
+            test_data.len() as u64,
+            "application/octet-stream".to_string(),
+            None,
+            None,
+        );
+
+        let mut stream = FileChunkStream::open(&response, 4096).await.unwrap();
+        let mut chunks = Vec::new();
+        while let Some(chunk) = stream.next_chunk().await.unwrap() {
+            chunks.push(chunk);
+        }
+
+        assert_eq!(
+            chunks.iter().map(|c| c.len()).collect::<Vec<_>>(),
+            vec![4096, 4096, 1808]
+        );
+        assert_eq!(chunks.concat(), test_data);
+        assert_eq!(stream.remaining(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_file_chunk_stream_honors_range() {
+        use std::io::Write;
+
+        let mut src_file = tempfile::NamedTempFile::new().unwrap();
+        src_file.write_all(b"Hello, sendfile world!").unwrap();
+        src_file.flush().unwrap();
+
+        let response = SendfileResponse::with_range(
+            src_file.path().to_path_buf(),
+            7,
+            8,
+            "text/plain".to_string(),
+            None,
+            None,
+        );
+
+        let mut stream = FileChunkStream::open(&response, 1024).await.unwrap();
+        assert_eq!(
+            stream.next_chunk().await.unwrap().unwrap().as_ref(),
+            b"sendfile"
+        );
+        assert!(stream.next_chunk().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_file_chunk_stream_missing_file() {
+        let response = SendfileResponse::new(
+            PathBuf::from("/nonexistent/yatagarasu/cache.bin"),
+            10,
+            "text/plain".to_string(),
+            None,
+            None,
+        );
+
+        assert!(FileChunkStream::open(&response, 1024).await.is_err());
+    }
 }
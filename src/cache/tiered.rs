@@ -2,15 +2,31 @@
 //!
 //! Provides a cache hierarchy with multiple layers (memory → disk → redis)
 //! that automatically promotes frequently accessed items to faster layers.
+//! Disk and redis layers that keep failing are quarantined (skipped) for a
+//! cool-down period; see [`crate::cache::quarantine`]. Entries are compressed
+//! before they reach the layers when `cache.compression` is enabled; see
+//! [`crate::cache::compression`].
+//!
+//! With `cache.range_chunks` enabled, objects are also cached in fixed-size
+//! chunks keyed by offset, filled from `206` responses by [`RangeChunkFill`]
+//! and assembled into Range responses by [`TieredCache::get_range`].
 
+use crate::cache::compression::{compress_entry, decode_entry};
 use crate::cache::disk::DiskCache;
+use crate::cache::quarantine::LayerHealth;
 use crate::cache::redis::{RedisCache, RedisConfig};
 use crate::cache::sendfile::SendfileResponse;
-use crate::cache::{Cache, CacheConfig, CacheEntry, CacheError, CacheKey, CacheStats, MemoryCache};
+use crate::cache::{
+    Cache, CacheCompressionConfig, CacheConfig, CacheEntry, CacheError, CacheKey, CacheStats,
+    LayerQuarantineConfig, MemoryCache, RangeChunkConfig,
+};
 use crate::metrics::Metrics;
+use crate::s3::ByteRange;
 use async_trait::async_trait;
+use bytes::{Bytes, BytesMut};
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
 
 /// Tiered cache with multiple layers (memory, disk, redis)
 ///
@@ -25,6 +41,16 @@ pub struct TieredCache {
     // Ordered list of cache layers from fastest to slowest
     // Uses Arc for background promotion tasks
     layers: Vec<Arc<dyn Cache + Send + Sync>>,
+    // Layer names ("memory", "disk", "redis") for logs and metrics
+    layer_names: Vec<String>,
+    // Quarantine tracking per layer (None for the memory layer)
+    health: Vec<Option<LayerHealth>>,
+    // Compression applied to entries before they are written to the layers
+    compression: CacheCompressionConfig,
+    // Chunked caching of Range responses
+    range_chunks: RangeChunkConfig,
+    // Upstream response headers stored with entries and replayed on hits
+    preserve_headers: Vec<String>,
 }
 
 impl TieredCache {
@@ -46,7 +72,69 @@ impl TieredCache {
     /// ]);
     /// ```
     pub fn new(layers: Vec<Arc<dyn Cache + Send + Sync>>) -> Self {
-        Self { layers }
+        let names = (0..layers.len())
+            .map(|idx| {
+                match idx {
+                    0 => "memory",
+                    1 => "disk",
+                    2 => "redis",
+                    _ => "unknown",
+                }
+                .to_string()
+            })
+            .collect();
+        Self::with_layer_names(layers, names, &LayerQuarantineConfig::default())
+    }
+
+    fn with_layer_names(
+        layers: Vec<Arc<dyn Cache + Send + Sync>>,
+        layer_names: Vec<String>,
+        quarantine: &LayerQuarantineConfig,
+    ) -> Self {
+        let health = layer_names
+            .iter()
+            .map(|name| {
+                (name != "memory").then(|| LayerHealth::new(name.clone(), quarantine.clone()))
+            })
+            .collect();
+        Self {
+            layers,
+            layer_names,
+            health,
+            compression: CacheCompressionConfig::default(),
+            range_chunks: RangeChunkConfig::default(),
+            preserve_headers: Vec::new(),
+        }
+    }
+
+    /// Check if a layer is in use (not quarantined)
+    fn is_layer_available(&self, layer_index: usize) -> bool {
+        match &self.health[layer_index] {
+            Some(health) => health.is_available(),
+            None => true,
+        }
+    }
+
+    /// Count an error against a layer's quarantine threshold
+    fn record_layer_error(&self, layer_index: usize) {
+        if let Some(health) = &self.health[layer_index] {
+            health.record_error();
+        }
+    }
+
+    /// Names of layers currently quarantined
+    pub fn quarantined_layers(&self) -> Vec<String> {
+        self.health
+            .iter()
+            .flatten()
+            .filter(|health| health.is_quarantined())
+            .map(|health| health.layer().to_string())
+            .collect()
+    }
+
+    /// Check if at least one layer can serve (not quarantined)
+    pub fn is_healthy(&self) -> bool {
+        (0..self.layers.len()).any(|idx| self.is_layer_available(idx))
     }
 
     /// Get the number of cache layers
@@ -93,7 +181,8 @@ impl TieredCache {
                         cache_dir,
                         max_size_bytes,
                         config.disk.sendfile.clone(),
-                    );
+                    )
+                    .with_dedup(config.disk.dedup);
                     layers.push(Arc::new(disk_cache));
                 }
                 "redis" => {
@@ -125,16 +214,31 @@ impl TieredCache {
             }
         }
 
-        Ok(Self { layers })
+        let mut tiered =
+            Self::with_layer_names(layers, config.cache_layers.clone(), &config.quarantine);
+        tiered.compression = config.compression.clone();
+        tiered.range_chunks = config.range_chunks.clone();
+        tiered.preserve_headers = config.preserve_headers.clone();
+        Ok(tiered)
     }
 }
 
 #[async_trait]
 impl Cache for TieredCache {
     async fn get(&self, key: &CacheKey) -> Result<Option<CacheEntry>, CacheError> {
+        match self.get_encoded(key).await? {
+            Some(entry) => decode_entry(entry).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    async fn get_encoded(&self, key: &CacheKey) -> Result<Option<CacheEntry>, CacheError> {
         // Check each layer in order (fastest to slowest)
         // On layer error, log and continue to next layer (graceful degradation)
         for (layer_index, layer) in self.layers.iter().enumerate() {
+            if !self.is_layer_available(layer_index) {
+                continue;
+            }
             match layer.get(key).await {
                 Ok(Some(entry)) => {
                     // Found in this layer
@@ -146,8 +250,15 @@ impl Cache for TieredCache {
                         let entry_clone = entry.clone();
 
                         // Clone Arc references to layers that need promotion
-                        let layers_to_promote: Vec<Arc<dyn Cache + Send + Sync>> =
-                            self.layers.iter().take(layer_index).cloned().collect();
+                        // Quarantined layers are left out
+                        let layers_to_promote: Vec<Arc<dyn Cache + Send + Sync>> = self
+                            .layers
+                            .iter()
+                            .enumerate()
+                            .take(layer_index)
+                            .filter(|(idx, _)| self.is_layer_available(*idx))
+                            .map(|(_, layer)| layer.clone())
+                            .collect();
 
                         // Spawn background task for promotion - doesn't block the response
                         tokio::spawn(async move {
@@ -183,6 +294,7 @@ impl Cache for TieredCache {
                         key = %format!("{}/{}", key.bucket, key.object_key),
                         "Cache layer error during get, falling back to next layer"
                     );
+                    self.record_layer_error(layer_index);
                     continue;
                 }
             }
@@ -201,6 +313,7 @@ impl Cache for TieredCache {
         if self.layers.is_empty() {
             return Ok(());
         }
+        let entry = compress_entry(entry, &self.compression);
 
         // Step 1: Write to first layer (memory) synchronously
         let first_layer = &self.layers[0];
@@ -220,13 +333,12 @@ impl Cache for TieredCache {
             // So we'll use a simple approach: write to each layer in a spawned task
 
             for (layer_idx, layer) in self.layers.iter().enumerate().skip(1) {
+                if !self.is_layer_available(layer_idx) {
+                    continue;
+                }
                 let key_for_task = key_clone.clone();
                 let entry_for_task = entry_clone.clone();
-                let layer_name = match layer_idx {
-                    1 => "disk",
-                    2 => "redis",
-                    _ => "unknown",
-                };
+                let layer_name = self.layer_names[layer_idx].as_str();
 
                 // Write to this layer synchronously but without blocking the response
                 // For now, we write inline but could be moved to a background channel
@@ -248,6 +360,7 @@ impl Cache for TieredCache {
                             error = %e,
                             "Background cache write failed"
                         );
+                        self.record_layer_error(layer_idx);
                         // Don't return error - memory write succeeded
                     }
                 }
@@ -266,6 +379,20 @@ impl Cache for TieredCache {
     }
 
     async fn delete(&self, key: &CacheKey) -> Result<bool, CacheError> {
+        // An object's chunk index goes with it, so its chunks stop being served
+        if self.range_chunks.enabled && key.variant.is_none() {
+            let index_key = range_index_key(key, self.range_chunks.chunk_size_bytes);
+            for layer in &self.layers {
+                if let Err(e) = layer.delete(&index_key).await {
+                    tracing::debug!(
+                        error = %e,
+                        key = %format!("{}/{}", key.bucket, key.object_key),
+                        "Failed to delete range chunk index"
+                    );
+                }
+            }
+        }
+
         // Delete from all layers
         // Returns true if any layer had the key
 
@@ -392,6 +519,41 @@ impl Cache for TieredCache {
         Ok(max_deleted)
     }
 
+    async fn delete_object(&self, bucket: &str, object_key: &str) -> Result<usize, CacheError> {
+        // Same aggregation as clear_bucket: maximum count, first error wins
+        let mut max_deleted = 0;
+        let mut first_error: Option<CacheError> = None;
+
+        for layer in &self.layers {
+            match layer.delete_object(bucket, object_key).await {
+                Ok(count) => max_deleted = max_deleted.max(count),
+                Err(e) => {
+                    if first_error.is_none() {
+                        first_error = Some(e);
+                    }
+                }
+            }
+        }
+
+        if let Some(error) = first_error {
+            return Err(error);
+        }
+
+        for layer in &self.layers {
+            layer.run_pending_tasks().await;
+        }
+
+        if max_deleted > 0 {
+            let metrics = Metrics::global();
+            if let Ok(stats) = self.stats().await {
+                metrics.set_cache_size_bytes(stats.current_size_bytes);
+                metrics.set_cache_items(stats.current_item_count);
+            }
+        }
+
+        Ok(max_deleted)
+    }
+
     async fn stats(&self) -> Result<CacheStats, CacheError> {
         // Aggregate stats across all layers
 
@@ -488,7 +650,10 @@ impl Cache for TieredCache {
     async fn get_sendfile(&self, key: &CacheKey) -> Result<Option<SendfileResponse>, CacheError> {
         // Check each layer for sendfile support
         // Only disk layers will return a response; memory/redis return None
-        for layer in &self.layers {
+        for (layer_index, layer) in self.layers.iter().enumerate() {
+            if !self.is_layer_available(layer_index) {
+                continue;
+            }
             match layer.get_sendfile(key).await {
                 Ok(Some(response)) => {
                     return Ok(Some(response));
@@ -504,6 +669,7 @@ impl Cache for TieredCache {
                         key = %format!("{}/{}", key.bucket, key.object_key),
                         "Cache layer error during get_sendfile, falling back to next layer"
                     );
+                    self.record_layer_error(layer_index);
                     continue;
                 }
             }
@@ -559,6 +725,259 @@ impl TieredCache {
     }
 }
 
+/// Variant prefix of range chunk entries and their index
+const RANGE_VARIANT_PREFIX: &str = "range";
+
+/// Key of the entry describing an object cached in chunks (size and validators)
+fn range_index_key(key: &CacheKey, chunk_size: u64) -> CacheKey {
+    CacheKey {
+        bucket: key.bucket.clone(),
+        object_key: key.object_key.clone(),
+        etag: None,
+        variant: Some(format!("{}:{}", RANGE_VARIANT_PREFIX, chunk_size)),
+    }
+}
+
+/// Key of chunk `index` (bytes `index * chunk_size` onwards) of an object
+fn range_chunk_key(key: &CacheKey, chunk_size: u64, index: u64) -> CacheKey {
+    CacheKey {
+        bucket: key.bucket.clone(),
+        object_key: key.object_key.clone(),
+        etag: None,
+        variant: Some(format!("{}:{}:{}", RANGE_VARIANT_PREFIX, chunk_size, index)),
+    }
+}
+
+/// Inclusive byte offsets of `range` in an object of `total_size` bytes,
+/// or None when it is not satisfiable
+pub fn resolve_range(range: &ByteRange, total_size: u64) -> Option<(u64, u64)> {
+    if total_size == 0 {
+        return None;
+    }
+    let last = total_size - 1;
+    match (range.start, range.end) {
+        (Some(start), Some(end)) if start <= end && start <= last => Some((start, end.min(last))),
+        (Some(start), None) if start <= last => Some((start, last)),
+        (None, Some(suffix)) if suffix > 0 => Some((total_size.saturating_sub(suffix), last)),
+        _ => None,
+    }
+}
+
+/// Object a `206` response is filling chunks for
+#[derive(Debug, Clone)]
+pub struct ChunkedObject {
+    /// Key of the full object (no variant)
+    pub key: CacheKey,
+    /// Size of the whole object, from `Content-Range`
+    pub total_size: u64,
+    pub content_type: String,
+    pub etag: String,
+    pub last_modified: Option<String>,
+    /// Upstream response headers replayed on hits
+    pub headers: Vec<(String, String)>,
+    pub ttl: Duration,
+}
+
+/// Byte range assembled from cached chunks
+#[derive(Debug, Clone)]
+pub struct CachedRange {
+    pub data: Bytes,
+    /// First byte offset (inclusive)
+    pub start: u64,
+    /// Last byte offset (inclusive)
+    pub end: u64,
+    pub total_size: u64,
+    pub content_type: String,
+    pub etag: String,
+    pub last_modified: Option<String>,
+    pub headers: Vec<(String, String)>,
+}
+
+/// Cuts the body of a `206` response into the chunks it covers completely
+///
+/// Bytes before the first chunk boundary and after the last complete chunk
+/// are not kept. At most one chunk is held in memory at a time.
+#[derive(Debug)]
+pub struct RangeChunkFill {
+    object: ChunkedObject,
+    chunk_size: u64,
+    // Object offset of the next body byte
+    offset: u64,
+    // Chunk being collected: index and bytes so far
+    pending: Option<(u64, BytesMut)>,
+}
+
+impl RangeChunkFill {
+    /// Start a fill for a response carrying bytes from `start` onwards
+    pub fn new(object: ChunkedObject, chunk_size: u64, start: u64) -> Self {
+        Self {
+            object,
+            chunk_size,
+            offset: start,
+            pending: None,
+        }
+    }
+
+    /// Object the chunks belong to
+    pub fn object(&self) -> &ChunkedObject {
+        &self.object
+    }
+
+    /// Feed the next body bytes; returns the chunks they completed as (index, data)
+    pub fn push(&mut self, mut data: &[u8]) -> Vec<(u64, Bytes)> {
+        let mut complete = Vec::new();
+        while !data.is_empty() && self.offset < self.object.total_size {
+            let index = self.offset / self.chunk_size;
+            let chunk_start = index * self.chunk_size;
+            let chunk_end = (chunk_start + self.chunk_size).min(self.object.total_size);
+            let take = ((chunk_end - self.offset) as usize).min(data.len());
+
+            if self.pending.is_none() && self.offset == chunk_start {
+                self.pending = Some((
+                    index,
+                    BytesMut::with_capacity((chunk_end - chunk_start) as usize),
+                ));
+            }
+            if let Some((_, buffer)) = self.pending.as_mut() {
+                buffer.extend_from_slice(&data[..take]);
+            }
+
+            self.offset += take as u64;
+            data = &data[take..];
+            if self.offset == chunk_end {
+                if let Some((index, buffer)) = self.pending.take() {
+                    complete.push((index, buffer.freeze()));
+                }
+            }
+        }
+        complete
+    }
+}
+
+// Chunked Range caching (not part of Cache trait)
+impl TieredCache {
+    /// Store an entry in the memory layer only
+    ///
+    /// Used for size classes whose objects are not worth a disk or Redis
+    /// write. Without a memory layer the entry goes to every layer.
+    pub async fn set_memory_only(
+        &self,
+        key: CacheKey,
+        entry: CacheEntry,
+    ) -> Result<(), CacheError> {
+        let Some(idx) = self.layer_names.iter().position(|name| name == "memory") else {
+            return self.set(key, entry).await;
+        };
+        let entry = compress_entry(entry, &self.compression);
+        self.layers[idx].set(key, entry).await?;
+        self.layers[idx].run_pending_tasks().await;
+        Ok(())
+    }
+
+    /// Chunk settings, when Range responses are cached
+    pub fn range_chunks(&self) -> Option<&RangeChunkConfig> {
+        self.range_chunks.enabled.then_some(&self.range_chunks)
+    }
+
+    /// Upstream response headers to store with entries (`cache.preserve_headers`)
+    pub fn preserve_headers(&self) -> &[String] {
+        &self.preserve_headers
+    }
+
+    /// Store chunk `index` of an object, and the index entry describing it
+    pub async fn set_range_chunk(
+        &self,
+        object: &ChunkedObject,
+        index: u64,
+        data: Bytes,
+    ) -> Result<(), CacheError> {
+        let chunk_size = self.range_chunks.chunk_size_bytes;
+        let index_entry = CacheEntry::new(
+            Bytes::from(object.total_size.to_string()),
+            object.content_type.clone(),
+            object.etag.clone(),
+            object.last_modified.clone(),
+            Some(object.ttl),
+        )
+        .with_headers(object.headers.clone());
+        self.set(range_index_key(&object.key, chunk_size), index_entry)
+            .await?;
+        let chunk_entry = CacheEntry::new(
+            data,
+            object.content_type.clone(),
+            object.etag.clone(),
+            object.last_modified.clone(),
+            Some(object.ttl),
+        );
+        self.set(range_chunk_key(&object.key, chunk_size, index), chunk_entry)
+            .await
+    }
+
+    /// Assemble `range` of an object from cached chunks
+    ///
+    /// Returns None unless every chunk the range touches is cached for the
+    /// same object version (ETag) and the range spans at most
+    /// `max_chunks_per_response` chunks.
+    pub async fn get_range(
+        &self,
+        key: &CacheKey,
+        range: &ByteRange,
+    ) -> Result<Option<CachedRange>, CacheError> {
+        let Some(config) = self.range_chunks() else {
+            return Ok(None);
+        };
+        let chunk_size = config.chunk_size_bytes;
+        let Some(index) = self.get(&range_index_key(key, chunk_size)).await? else {
+            return Ok(None);
+        };
+        let Some(total_size) = std::str::from_utf8(&index.data)
+            .ok()
+            .and_then(|size| size.parse::<u64>().ok())
+        else {
+            return Ok(None);
+        };
+        let Some((start, end)) = resolve_range(range, total_size) else {
+            return Ok(None);
+        };
+
+        let first = start / chunk_size;
+        let last = end / chunk_size;
+        if last - first + 1 > config.max_chunks_per_response {
+            return Ok(None);
+        }
+
+        let mut data = BytesMut::with_capacity((end - start + 1) as usize);
+        for chunk_index in first..=last {
+            let chunk_start = chunk_index * chunk_size;
+            let chunk_len = (chunk_start + chunk_size).min(total_size) - chunk_start;
+            let Some(chunk) = self
+                .get(&range_chunk_key(key, chunk_size, chunk_index))
+                .await?
+            else {
+                return Ok(None);
+            };
+            // A chunk of another version of the object is a miss
+            if chunk.etag != index.etag || chunk.data.len() as u64 != chunk_len {
+                return Ok(None);
+            }
+            let from = start.saturating_sub(chunk_start) as usize;
+            let to = ((end + 1).min(chunk_start + chunk_len) - chunk_start) as usize;
+            data.extend_from_slice(&chunk.data[from..to]);
+        }
+
+        Ok(Some(CachedRange {
+            data: data.freeze(),
+            start,
+            end,
+            total_size,
+            content_type: index.content_type,
+            etag: index.etag,
+            last_modified: index.last_modified,
+            headers: index.headers,
+        }))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -756,6 +1175,7 @@ mod tests {
                 cache_dir: cache_dir.clone(),
                 max_disk_cache_size_mb: 100,
                 sendfile: crate::cache::SendfileConfig::default(),
+                dedup: false,
             },
             ..Default::default()
         };
@@ -1408,4 +1828,204 @@ mod tests {
         assert!(retrieved.is_some(), "Should find entry in fallback layer");
         assert_eq!(retrieved.unwrap().data, Bytes::from("data from disk"));
     }
+
+    #[tokio::test]
+    async fn test_failing_layer_is_quarantined() {
+        // Test: a layer that keeps failing is skipped after error_threshold errors
+        let tiered = TieredCache::new(vec![
+            Arc::new(MockCache::new("memory")),
+            Arc::new(FailingMockCache::new("disk")),
+        ]);
+        let key = CacheKey {
+            bucket: "test-bucket".to_string(),
+            object_key: "quarantine.txt".to_string(),
+            etag: None,
+            variant: None,
+        };
+
+        let threshold = LayerQuarantineConfig::default().error_threshold;
+        for _ in 0..threshold - 1 {
+            assert!(tiered.get(&key).await.unwrap().is_none());
+        }
+        assert!(tiered.quarantined_layers().is_empty());
+
+        assert!(tiered.get(&key).await.unwrap().is_none());
+        assert_eq!(tiered.quarantined_layers(), vec!["disk".to_string()]);
+
+        // Quarantined layer is skipped; misses still succeed
+        assert!(tiered.get(&key).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_is_healthy_with_all_layers_quarantined() {
+        let tiered = TieredCache::with_layer_names(
+            vec![Arc::new(FailingMockCache::new("disk"))],
+            vec!["disk".to_string()],
+            &LayerQuarantineConfig::default(),
+        );
+        let key = CacheKey {
+            bucket: "test-bucket".to_string(),
+            object_key: "health.txt".to_string(),
+            etag: None,
+            variant: None,
+        };
+        assert!(tiered.is_healthy());
+
+        for _ in 0..LayerQuarantineConfig::default().error_threshold {
+            let _ = tiered.get(&key).await;
+        }
+        assert!(!tiered.is_healthy());
+    }
+
+    fn chunked_object(total_size: u64) -> ChunkedObject {
+        ChunkedObject {
+            key: CacheKey {
+                bucket: "media".to_string(),
+                object_key: "movie.mp4".to_string(),
+                etag: None,
+                variant: None,
+            },
+            total_size,
+            content_type: "video/mp4".to_string(),
+            etag: "abc".to_string(),
+            last_modified: None,
+            headers: vec![("content-disposition".to_string(), "attachment".to_string())],
+            ttl: Duration::from_secs(60),
+        }
+    }
+
+    #[test]
+    fn test_resolve_range() {
+        let range = |start, end| ByteRange { start, end };
+        assert_eq!(resolve_range(&range(Some(0), Some(9)), 100), Some((0, 9)));
+        assert_eq!(
+            resolve_range(&range(Some(90), Some(200)), 100),
+            Some((90, 99))
+        );
+        assert_eq!(resolve_range(&range(Some(50), None), 100), Some((50, 99)));
+        assert_eq!(resolve_range(&range(None, Some(10)), 100), Some((90, 99)));
+        assert_eq!(resolve_range(&range(None, Some(500)), 100), Some((0, 99)));
+        assert_eq!(resolve_range(&range(Some(100), None), 100), None);
+        assert_eq!(resolve_range(&range(Some(9), Some(1)), 100), None);
+        assert_eq!(resolve_range(&range(None, Some(0)), 100), None);
+    }
+
+    #[test]
+    fn test_range_chunk_fill_keeps_only_whole_chunks() {
+        // Response for bytes 5-24 of a 25-byte object, 10-byte chunks
+        let mut fill = RangeChunkFill::new(chunked_object(25), 10, 5);
+        let body: Vec<u8> = (5u8..25).collect();
+
+        // Bytes 5-9 are the tail of chunk 0 and are dropped
+        assert!(fill.push(&body[..3]).is_empty());
+        assert!(fill.push(&body[3..8]).is_empty());
+        let chunks = fill.push(&body[8..]);
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].0, 1);
+        assert_eq!(chunks[0].1, Bytes::from((10u8..20).collect::<Vec<_>>()));
+        // The last chunk is short
+        assert_eq!(chunks[1].0, 2);
+        assert_eq!(chunks[1].1, Bytes::from((20u8..25).collect::<Vec<_>>()));
+        assert!(fill.push(b"extra").is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_set_memory_only_skips_slower_layers() {
+        let memory_cache = MockCache::new("memory");
+        let memory_entries = memory_cache.entries.clone();
+        let disk_cache = MockCache::new("disk");
+        let disk_entries = disk_cache.entries.clone();
+        let tiered = TieredCache::new(vec![Arc::new(memory_cache), Arc::new(disk_cache)]);
+
+        let key = CacheKey {
+            bucket: "icons".to_string(),
+            object_key: "favicon.ico".to_string(),
+            etag: None,
+            variant: None,
+        };
+        let entry = CacheEntry::new(
+            Bytes::from("tiny"),
+            "image/x-icon".to_string(),
+            "etag1".to_string(),
+            None,
+            Some(Duration::from_secs(60)),
+        );
+        tiered.set_memory_only(key.clone(), entry).await.unwrap();
+
+        assert!(memory_entries
+            .lock()
+            .await
+            .contains_key("icons/favicon.ico"));
+        assert!(disk_entries.lock().await.is_empty());
+        assert!(tiered.get(&key).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_get_range_assembles_cached_chunks() {
+        // MockCache ignores variants, so chunks need a real memory layer
+        let memory = MemoryCache::new(&crate::cache::MemoryCacheConfig::default());
+        let mut tiered = TieredCache::new(vec![Arc::new(memory)]);
+        tiered.range_chunks = RangeChunkConfig {
+            enabled: true,
+            chunk_size_bytes: 10,
+            max_chunks_per_response: 2,
+        };
+        let object = chunked_object(25);
+        let range = |start, end| ByteRange { start, end };
+
+        // Nothing cached yet
+        assert!(tiered
+            .get_range(&object.key, &range(Some(0), Some(9)))
+            .await
+            .unwrap()
+            .is_none());
+
+        let body: Vec<u8> = (0u8..25).collect();
+        let mut fill = RangeChunkFill::new(object.clone(), 10, 0);
+        for (index, data) in fill.push(&body[..20]) {
+            tiered.set_range_chunk(&object, index, data).await.unwrap();
+        }
+
+        let cached = tiered
+            .get_range(&object.key, &range(Some(5), Some(14)))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!((cached.start, cached.end, cached.total_size), (5, 14, 25));
+        assert_eq!(cached.data, Bytes::from(body[5..15].to_vec()));
+        assert_eq!(cached.etag, "abc");
+        assert_eq!(cached.headers, object.headers);
+
+        // Chunk 2 is missing
+        assert!(tiered
+            .get_range(&object.key, &range(Some(15), None))
+            .await
+            .unwrap()
+            .is_none());
+
+        for (index, data) in fill.push(&body[20..]) {
+            tiered.set_range_chunk(&object, index, data).await.unwrap();
+        }
+        let cached = tiered
+            .get_range(&object.key, &range(None, Some(3)))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(cached.data, Bytes::from(body[22..].to_vec()));
+
+        // Spans three chunks, over max_chunks_per_response
+        assert!(tiered
+            .get_range(&object.key, &range(Some(0), Some(24)))
+            .await
+            .unwrap()
+            .is_none());
+
+        // Deleting the object drops its chunk index
+        tiered.delete(&object.key).await.unwrap();
+        assert!(tiered
+            .get_range(&object.key, &range(Some(5), Some(14)))
+            .await
+            .unwrap()
+            .is_none());
+    }
 }
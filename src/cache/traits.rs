@@ -17,6 +17,12 @@ pub trait Cache: Send + Sync {
     /// Returns None if the key is not found or the entry has expired
     async fn get(&self, key: &CacheKey) -> Result<Option<CacheEntry>, CacheError>;
 
+    /// Get a cache entry as stored, possibly still compressed
+    /// (see [`CacheEntry::content_encoding`]). Default: same as `get`
+    async fn get_encoded(&self, key: &CacheKey) -> Result<Option<CacheEntry>, CacheError> {
+        self.get(key).await
+    }
+
     /// Set a cache entry
     /// Overwrites existing entry if key already exists
     async fn set(&self, key: CacheKey, entry: CacheEntry) -> Result<(), CacheError>;
@@ -25,6 +31,19 @@ pub trait Cache: Send + Sync {
     /// Returns true if the entry was deleted, false if it didn't exist
     async fn delete(&self, key: &CacheKey) -> Result<bool, CacheError>;
 
+    /// Delete every entry of one object: the base entry and its variants
+    /// (image renditions, range chunks). Returns the number of entries deleted.
+    /// Default: the base entry only, for caches that do not key by variant
+    async fn delete_object(&self, bucket: &str, object_key: &str) -> Result<usize, CacheError> {
+        let key = CacheKey {
+            bucket: bucket.to_string(),
+            object_key: object_key.to_string(),
+            etag: None,
+            variant: None,
+        };
+        Ok(usize::from(self.delete(&key).await?))
+    }
+
     /// Clear all cache entries
     async fn clear(&self) -> Result<(), CacheError>;
 
@@ -0,0 +1,168 @@
+//! Cache entry TTL from upstream freshness headers.
+//!
+//! An entry lives as long as the upstream response says it stays fresh, in
+//! RFC 7234 order: `s-maxage`, then `max-age`, then `Expires` measured from
+//! the response's `Date`. Responses without any of them use the bucket's
+//! fill TTL (see [`fill_ttl`]). Per bucket, `min_ttl_seconds` raises short
+//! upstream lifetimes, `max_ttl_seconds` caps every entry, and
+//! `ignore_upstream_ttl` always uses the bucket TTL instead.
+//!
+//! A response that is already stale (`Expires` in the past, or unparseable as
+//! RFC 7234 requires) gets a zero TTL and is not cached; `min_ttl_seconds`
+//! does not revive it.
+
+use chrono::DateTime;
+use std::time::{Duration, SystemTime};
+
+use super::experiment::fill_ttl;
+use super::CacheControl;
+use crate::config::BucketConfig;
+
+/// Freshness lifetime the upstream response gives itself, if any
+///
+/// `Expires` is measured from `date` (the response's `Date` header), else from
+/// `now`, so clock skew between S3 and the proxy does not change it.
+pub fn upstream_lifetime(
+    cache_control: &CacheControl,
+    expires: Option<&str>,
+    date: Option<&str>,
+    now: SystemTime,
+) -> Option<Duration> {
+    if let Some(max_age) = cache_control.s_maxage.or(cache_control.max_age) {
+        return Some(max_age);
+    }
+    let Some(expires) = parse_http_date(expires?) else {
+        // Invalid dates, especially "0", mean already expired
+        return Some(Duration::ZERO);
+    };
+    let date = date.and_then(parse_http_date).unwrap_or(now);
+    Some(expires.duration_since(date).unwrap_or(Duration::ZERO))
+}
+
+/// TTL for an entry of `bucket_config` filled from a response with these headers
+///
+/// Zero means the response is already stale and should not be cached.
+pub fn entry_ttl(
+    bucket_config: &BucketConfig,
+    cache_control: &CacheControl,
+    expires: Option<&str>,
+    date: Option<&str>,
+) -> Duration {
+    let cache = bucket_config.cache.as_ref();
+    let max_ttl = cache
+        .and_then(|c| c.max_ttl_seconds)
+        .map(Duration::from_secs);
+    let cap = |ttl: Duration| max_ttl.map_or(ttl, |max| ttl.min(max));
+
+    if cache.and_then(|c| c.ignore_upstream_ttl).unwrap_or(false) {
+        return cap(fill_ttl(bucket_config));
+    }
+    match upstream_lifetime(cache_control, expires, date, SystemTime::now()) {
+        Some(lifetime) if lifetime.is_zero() => Duration::ZERO,
+        Some(lifetime) => {
+            let min_ttl = cache
+                .and_then(|c| c.min_ttl_seconds)
+                .map(Duration::from_secs);
+            cap(min_ttl.map_or(lifetime, |min| lifetime.max(min)))
+        }
+        None => cap(fill_ttl(bucket_config)),
+    }
+}
+
+/// Parse an HTTP-date (`Wed, 21 Oct 2015 07:28:00 GMT`)
+fn parse_http_date(value: &str) -> Option<SystemTime> {
+    DateTime::parse_from_rfc2822(value.trim())
+        .ok()
+        .map(SystemTime::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DATE: &str = "Wed, 21 Oct 2025 07:00:00 GMT";
+
+    fn bucket(cache: &str) -> BucketConfig {
+        let yaml = format!(
+            r#"
+name: assets
+path_prefix: /assets
+s3:
+  bucket: assets
+  region: us-east-1
+  access_key: test
+  secret_key: test
+cache:
+{}
+"#,
+            cache
+        );
+        serde_yaml::from_str(&yaml).unwrap()
+    }
+
+    #[test]
+    fn test_upstream_lifetime_precedence() {
+        let now = SystemTime::now();
+        let expires = Some("Wed, 21 Oct 2025 07:10:00 GMT");
+        let cc = CacheControl::parse("max-age=60, s-maxage=120");
+        assert_eq!(
+            upstream_lifetime(&cc, expires, Some(DATE), now),
+            Some(Duration::from_secs(120))
+        );
+        let cc = CacheControl::parse("max-age=60");
+        assert_eq!(
+            upstream_lifetime(&cc, expires, Some(DATE), now),
+            Some(Duration::from_secs(60))
+        );
+        let cc = CacheControl::default();
+        assert_eq!(
+            upstream_lifetime(&cc, expires, Some(DATE), now),
+            Some(Duration::from_secs(600))
+        );
+        // Past and invalid Expires are already stale
+        assert_eq!(
+            upstream_lifetime(&cc, Some(DATE), Some(expires.unwrap()), now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(
+            upstream_lifetime(&cc, Some("0"), Some(DATE), now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(upstream_lifetime(&cc, None, Some(DATE), now), None);
+    }
+
+    #[test]
+    fn test_entry_ttl_clamps_and_override() {
+        let cc = CacheControl::parse("max-age=30");
+        let no_headers = CacheControl::default();
+
+        let config = bucket("  ttl_seconds: 900");
+        assert_eq!(entry_ttl(&config, &cc, None, None), Duration::from_secs(30));
+        assert_eq!(
+            entry_ttl(&config, &no_headers, None, None),
+            Duration::from_secs(900)
+        );
+
+        let config = bucket("  min_ttl_seconds: 300\n  max_ttl_seconds: 86400");
+        assert_eq!(
+            entry_ttl(&config, &cc, None, None),
+            Duration::from_secs(300)
+        );
+        let year = CacheControl::parse("max-age=31536000");
+        assert_eq!(
+            entry_ttl(&config, &year, None, None),
+            Duration::from_secs(86400)
+        );
+        // A stale response stays uncacheable despite the floor
+        assert_eq!(
+            entry_ttl(&config, &no_headers, Some("0"), Some(DATE)),
+            Duration::ZERO
+        );
+
+        let config = bucket("  ttl_seconds: 900\n  ignore_upstream_ttl: true");
+        assert_eq!(
+            entry_ttl(&config, &cc, Some("0"), None),
+            Duration::from_secs(900)
+        );
+    }
+}
@@ -1,5 +1,6 @@
+use crate::cache::journal::{warm_from_journal, HotKey};
 use crate::cache::{Cache, CacheEntry, CacheKey};
-use crate::config::S3Config;
+use crate::config::{BucketConfig, S3Config};
 use crate::metrics::Metrics;
 use crate::s3::S3Client;
 use serde::{Deserialize, Serialize};
@@ -194,6 +195,82 @@ impl PrewarmManager {
         task_id
     }
 
+    /// Warm an explicit list of keys, e.g. replayed from the access log
+    ///
+    /// The task's bucket is `*` (keys may span buckets) and its path names the
+    /// key source. Keys for unknown buckets are skipped.
+    pub fn create_key_task(
+        &self,
+        source: String,
+        keys: Vec<HotKey>,
+        buckets: Vec<BucketConfig>,
+        max_item_size_bytes: u64,
+        concurrency: usize,
+    ) -> String {
+        self.cleanup_old_tasks();
+
+        let task = PrewarmTask::new("*".to_string(), source, PrewarmOptions::default());
+        let task_id = task.id.clone();
+        {
+            let mut tasks = self.tasks.lock().unwrap();
+            tasks.insert(task_id.clone(), task);
+        }
+
+        let cache_opt = {
+            let r = self.cache.read().unwrap();
+            r.clone()
+        };
+        let tasks_map = self.tasks.clone();
+        let task_id_clone = task_id.clone();
+        Metrics::global().increment_prewarm_tasks();
+
+        tokio::spawn(async move {
+            let Some(cache) = cache_opt else {
+                let mut t = tasks_map.lock().unwrap();
+                if let Some(task) = t.get_mut(&task_id_clone) {
+                    task.status = TaskStatus::Failed;
+                    task.error_message = Some("Cache is not enabled/configured".to_string());
+                    task.end_time = Some(SystemTime::now());
+                }
+                Metrics::global().increment_prewarm_errors();
+                return;
+            };
+
+            {
+                let mut t = tasks_map.lock().unwrap();
+                match t.get_mut(&task_id_clone) {
+                    Some(task) if task.status != TaskStatus::Cancelled => {
+                        task.status = TaskStatus::Running;
+                        task.start_time = Some(SystemTime::now());
+                        task.files_scanned = keys.len() as u64;
+                    }
+                    _ => return,
+                }
+            }
+
+            let summary =
+                warm_from_journal(cache, keys, &buckets, max_item_size_bytes, concurrency).await;
+            Metrics::global().increment_prewarm_files(summary.warmed as u64);
+            for _ in 0..summary.failed {
+                Metrics::global().increment_prewarm_errors();
+            }
+
+            let mut t = tasks_map.lock().unwrap();
+            if let Some(task) = t.get_mut(&task_id_clone) {
+                task.files_cached = summary.warmed as u64;
+                if task.status == TaskStatus::Running {
+                    task.status = TaskStatus::Completed;
+                    task.end_time = Some(SystemTime::now());
+                    if let Some(duration) = task.duration_seconds() {
+                        Metrics::global().record_prewarm_duration(duration);
+                    }
+                }
+            }
+        });
+
+        task_id
+    }
+
     pub fn get_task(&self, task_id: &str) -> Option<PrewarmTask> {
         let tasks = self.tasks.lock().unwrap();
         tasks.get(task_id).cloned()
@@ -0,0 +1,208 @@
+//! Zero-copy helpers for serving cache hits
+//!
+//! Large cached entries are written to the client as a sequence of `Bytes`
+//! slices that share the entry's underlying buffer, so serving a hit never
+//! copies or re-allocates the body. Disk cache entries above
+//! [`MMAP_THRESHOLD_BYTES`] are memory-mapped instead of being read into a
+//! heap buffer, keeping RSS flat for large objects.
+//!
+//! # Safety of mapped entries
+//!
+//! Disk cache files are only ever replaced via temp file + rename and removed
+//! via unlink, never truncated in place. A mapping therefore stays valid for
+//! as long as the returned `Bytes` (or any slice of it) is alive, even if the
+//! entry is evicted or overwritten concurrently.
+
+use bytes::Bytes;
+use std::path::Path;
+
+/// Entries larger than this are streamed to the client in slices
+pub const ZERO_COPY_STREAM_THRESHOLD_BYTES: usize = 1024 * 1024; // 1MB
+
+/// Size of each slice written to the client when streaming a cached entry
+pub const ZERO_COPY_CHUNK_SIZE: usize = 256 * 1024; // 256KB
+
+/// Disk cache entries at least this large are memory-mapped rather than read
+pub const MMAP_THRESHOLD_BYTES: u64 = 1024 * 1024; // 1MB
+
+/// Iterator over zero-copy slices of a `Bytes` buffer
+///
+/// Each item is produced with [`Bytes::slice`], which only bumps a reference
+/// count on the shared buffer.
+#[derive(Debug, Clone)]
+pub struct ZeroCopyChunks {
+    data: Bytes,
+    chunk_size: usize,
+    offset: usize,
+}
+
+impl ZeroCopyChunks {
+    /// Create an iterator yielding slices of at most `chunk_size` bytes
+    pub fn new(data: Bytes, chunk_size: usize) -> Self {
+        Self {
+            data,
+            chunk_size: chunk_size.max(1),
+            offset: 0,
+        }
+    }
+
+    /// Number of slices remaining
+    pub fn remaining_chunks(&self) -> usize {
+        (self.data.len() - self.offset).div_ceil(self.chunk_size)
+    }
+}
+
+impl Iterator for ZeroCopyChunks {
+    type Item = Bytes;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.offset >= self.data.len() {
+            return None;
+        }
+        let end = (self.offset + self.chunk_size).min(self.data.len());
+        let chunk = self.data.slice(self.offset..end);
+        self.offset = end;
+        Some(chunk)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.remaining_chunks();
+        (remaining, Some(remaining))
+    }
+}
+
+impl ExactSizeIterator for ZeroCopyChunks {}
+
+/// Check if a cached entry of `len` bytes should be streamed in slices
+pub fn should_stream_in_chunks(len: usize) -> bool {
+    len > ZERO_COPY_STREAM_THRESHOLD_BYTES
+}
+
+/// Read-only memory mapping of a whole file
+#[cfg(unix)]
+struct MappedFile {
+    ptr: *mut libc::c_void,
+    len: usize,
+}
+
+// SAFETY: the mapping is read-only and never mutated after creation
+#[cfg(unix)]
+unsafe impl Send for MappedFile {}
+#[cfg(unix)]
+unsafe impl Sync for MappedFile {}
+
+#[cfg(unix)]
+impl AsRef<[u8]> for MappedFile {
+    fn as_ref(&self) -> &[u8] {
+        // SAFETY: ptr/len describe a live PROT_READ mapping owned by self
+        unsafe { std::slice::from_raw_parts(self.ptr as *const u8, self.len) }
+    }
+}
+
+#[cfg(unix)]
+impl Drop for MappedFile {
+    fn drop(&mut self) {
+        // SAFETY: ptr/len were returned by a successful mmap call
+        unsafe {
+            libc::munmap(self.ptr, self.len);
+        }
+    }
+}
+
+/// Memory-map a file and expose it as `Bytes` without copying
+///
+/// The mapping is released when the last `Bytes` referencing it is dropped.
+/// Falls back to a regular read on non-unix platforms.
+#[cfg(unix)]
+pub fn map_file(path: &Path) -> std::io::Result<Bytes> {
+    use std::os::unix::io::AsRawFd;
+
+    let file = std::fs::File::open(path)?;
+    let len = file.metadata()?.len() as usize;
+    if len == 0 {
+        // mmap rejects zero-length mappings
+        return Ok(Bytes::new());
+    }
+
+    // SAFETY: fd is valid for the duration of the call; the mapping outlives
+    // the file handle, which is allowed by POSIX
+    let ptr = unsafe {
+        libc::mmap(
+            std::ptr::null_mut(),
+            len,
+            libc::PROT_READ,
+            libc::MAP_PRIVATE,
+            file.as_raw_fd(),
+            0,
+        )
+    };
+    if ptr == libc::MAP_FAILED {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    Ok(Bytes::from_owner(MappedFile { ptr, len }))
+}
+
+/// Memory-map a file and expose it as `Bytes` without copying
+#[cfg(not(unix))]
+pub fn map_file(path: &Path) -> std::io::Result<Bytes> {
+    std::fs::read(path).map(Bytes::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_zero_copy_chunks_split_and_share_buffer() {
+        let data = Bytes::from(vec![7u8; 10]);
+        let base = data.as_ptr();
+        let chunks: Vec<Bytes> = ZeroCopyChunks::new(data, 4).collect();
+
+        assert_eq!(
+            chunks.iter().map(|c| c.len()).collect::<Vec<_>>(),
+            vec![4, 4, 2]
+        );
+        // Slices point into the original allocation (no copy)
+        assert_eq!(chunks[1].as_ptr(), unsafe { base.add(4) });
+    }
+
+    #[test]
+    fn test_zero_copy_chunks_empty_and_exact_size() {
+        assert_eq!(ZeroCopyChunks::new(Bytes::new(), 4).count(), 0);
+
+        let chunks = ZeroCopyChunks::new(Bytes::from_static(b"abcdefgh"), 4);
+        assert_eq!(chunks.len(), 2);
+    }
+
+    #[test]
+    fn test_should_stream_in_chunks_threshold() {
+        assert!(!should_stream_in_chunks(ZERO_COPY_STREAM_THRESHOLD_BYTES));
+        assert!(should_stream_in_chunks(
+            ZERO_COPY_STREAM_THRESHOLD_BYTES + 1
+        ));
+    }
+
+    #[test]
+    fn test_map_file_matches_contents() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("entry.data");
+        std::fs::write(&path, b"mapped cache entry").unwrap();
+
+        let mapped = map_file(&path).unwrap();
+        assert_eq!(&mapped[..], b"mapped cache entry");
+
+        // Mapping stays readable after the file is unlinked (eviction)
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(&mapped.slice(0..6)[..], b"mapped");
+    }
+
+    #[test]
+    fn test_map_file_empty() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("empty.data");
+        std::fs::write(&path, b"").unwrap();
+
+        assert!(map_file(&path).unwrap().is_empty());
+    }
+}
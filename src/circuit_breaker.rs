@@ -9,6 +9,11 @@
 //!   - Success → Closed
 //!   - Failure → Open
 //!
+//! Trip policies:
+//! - **Consecutive failures** (default): open after `failure_threshold` failures in a row
+//! - **Error rate**: open when the failure ratio over the last `window_size`
+//!   requests reaches `error_rate_threshold` (once `minimum_requests` were seen)
+//!
 //! Configuration:
 //! - `failure_threshold`: Number of consecutive failures to open circuit
 //! - `success_threshold`: Number of successes in half-open to close circuit
@@ -51,6 +56,60 @@ impl From<u8> for CircuitState {
     }
 }
 
+impl CircuitState {
+    /// Lowercase name used in logs and events
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CircuitState::Closed => "closed",
+            CircuitState::Open => "open",
+            CircuitState::HalfOpen => "half_open",
+        }
+    }
+}
+
+/// What a proxied request observed from its origin
+///
+/// Recorded once per request so the breaker can classify connect errors,
+/// timeouts and error statuses the same way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UpstreamOutcome {
+    /// Connection refused, reset or closed, DNS or TLS failure
+    ConnectError,
+    /// Connect, read or write timed out
+    Timeout,
+    /// The origin answered with this status
+    Status(u16),
+}
+
+impl UpstreamOutcome {
+    /// Failure kind label used in metrics and logs
+    pub fn kind(&self) -> &'static str {
+        match self {
+            UpstreamOutcome::ConnectError => "connect_error",
+            UpstreamOutcome::Timeout => "timeout",
+            UpstreamOutcome::Status(status) if *status >= 500 => "server_error",
+            UpstreamOutcome::Status(_) => "status",
+        }
+    }
+}
+
+/// When a closed circuit should open
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum TripPolicy {
+    /// Open after `failure_threshold` consecutive failures
+    #[default]
+    ConsecutiveFailures,
+    /// Open when the failure ratio over a sliding window reaches `threshold`
+    ErrorRate {
+        /// Number of most recent requests in the window
+        window_size: u32,
+        /// Failure ratio (0.0-1.0) that opens the circuit
+        threshold: f64,
+        /// Requests needed in the window before the ratio is evaluated
+        minimum_requests: u32,
+    },
+}
+
 /// Circuit breaker configuration
 #[derive(Debug, Clone)]
 pub struct CircuitBreakerConfig {
@@ -62,6 +121,8 @@ pub struct CircuitBreakerConfig {
     pub timeout_duration: Duration,
     /// Max concurrent test requests in half-open state
     pub half_open_max_requests: u32,
+    /// Policy deciding when the closed circuit opens
+    pub policy: TripPolicy,
 }
 
 impl Default for CircuitBreakerConfig {
@@ -71,7 +132,77 @@ impl Default for CircuitBreakerConfig {
             success_threshold: 2,
             timeout_duration: Duration::from_secs(60),
             half_open_max_requests: 3,
+            policy: TripPolicy::ConsecutiveFailures,
+        }
+    }
+}
+
+const SLOT_EMPTY: u8 = 0;
+const SLOT_SUCCESS: u8 = 1;
+const SLOT_FAILURE: u8 = 2;
+
+/// Ring buffer of the most recent outcomes for the error-rate policy
+///
+/// Lock-free like the rest of the breaker; under concurrent updates the
+/// failure count may briefly lag the slots, which only shifts the trip point
+/// by a request or two.
+#[derive(Debug)]
+struct OutcomeWindow {
+    /// Outcome per slot (empty, success or failure)
+    slots: Box<[AtomicU8]>,
+    /// Outcomes recorded since the last reset
+    recorded: AtomicU64,
+    /// Failures currently held in the slots
+    failures: AtomicU64,
+}
+
+impl OutcomeWindow {
+    fn new(size: usize) -> Self {
+        Self {
+            slots: (0..size).map(|_| AtomicU8::new(SLOT_EMPTY)).collect(),
+            recorded: AtomicU64::new(0),
+            failures: AtomicU64::new(0),
+        }
+    }
+
+    /// Record an outcome, returning (requests in window, failures in window)
+    fn record(&self, failed: bool) -> (u64, u64) {
+        if self.slots.is_empty() {
+            return (0, 0);
+        }
+        let len = self.slots.len() as u64;
+        let index = self.recorded.fetch_add(1, Ordering::Relaxed);
+        let outcome = if failed { SLOT_FAILURE } else { SLOT_SUCCESS };
+        if failed {
+            self.failures.fetch_add(1, Ordering::Relaxed);
+        }
+        let evicted = self.slots[(index % len) as usize].swap(outcome, Ordering::Relaxed);
+        if evicted == SLOT_FAILURE {
+            let _ = self
+                .failures
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1));
+        }
+        ((index + 1).min(len), self.failures.load(Ordering::Relaxed))
+    }
+
+    /// Failure ratio over the current window, if anything was recorded
+    fn error_rate(&self) -> Option<f64> {
+        let requests = self
+            .recorded
+            .load(Ordering::Relaxed)
+            .min(self.slots.len() as u64);
+        if requests == 0 {
+            return None;
+        }
+        Some(self.failures.load(Ordering::Relaxed) as f64 / requests as f64)
+    }
+
+    fn reset(&self) {
+        for slot in self.slots.iter() {
+            slot.store(SLOT_EMPTY, Ordering::Relaxed);
         }
+        self.recorded.store(0, Ordering::Relaxed);
+        self.failures.store(0, Ordering::Relaxed);
     }
 }
 
@@ -91,6 +222,8 @@ pub struct CircuitBreaker {
     half_open_requests: Arc<AtomicU64>,
     /// Last state transition time as milliseconds since UNIX epoch (lock-free)
     last_transition_ms: Arc<AtomicU64>,
+    /// Recent outcomes (empty unless the error-rate policy is used)
+    window: Arc<OutcomeWindow>,
     /// Configuration
     config: Arc<CircuitBreakerConfig>,
 }
@@ -98,12 +231,17 @@ pub struct CircuitBreaker {
 impl CircuitBreaker {
     /// Create a new circuit breaker with the given configuration
     pub fn new(config: CircuitBreakerConfig) -> Self {
+        let window_size = match config.policy {
+            TripPolicy::ErrorRate { window_size, .. } => window_size as usize,
+            TripPolicy::ConsecutiveFailures => 0,
+        };
         Self {
             state: Arc::new(AtomicU8::new(CircuitState::Closed as u8)),
             failure_count: Arc::new(AtomicU64::new(0)),
             success_count: Arc::new(AtomicU64::new(0)),
             half_open_requests: Arc::new(AtomicU64::new(0)),
             last_transition_ms: Arc::new(AtomicU64::new(now_ms())),
+            window: Arc::new(OutcomeWindow::new(window_size)),
             config: Arc::new(config),
         }
     }
@@ -149,6 +287,7 @@ impl CircuitBreaker {
             CircuitState::Closed => {
                 // Reset failure count on success
                 self.failure_count.store(0, Ordering::Relaxed);
+                self.window.record(false);
             }
             CircuitState::HalfOpen => {
                 // Decrement half-open request count
@@ -191,10 +330,32 @@ impl CircuitBreaker {
                     "Circuit breaker failure in closed state"
                 );
 
-                // If we've exceeded the failure threshold, open the circuit
-                if failures >= self.config.failure_threshold as u64 {
-                    tracing::error!("Circuit breaker opening due to consecutive failures");
-                    self.transition_to_open();
+                match self.config.policy {
+                    TripPolicy::ConsecutiveFailures => {
+                        // If we've exceeded the failure threshold, open the circuit
+                        if failures >= self.config.failure_threshold as u64 {
+                            tracing::error!("Circuit breaker opening due to consecutive failures");
+                            self.transition_to_open();
+                        }
+                    }
+                    TripPolicy::ErrorRate {
+                        threshold,
+                        minimum_requests,
+                        ..
+                    } => {
+                        let (requests, window_failures) = self.window.record(true);
+                        if requests >= minimum_requests as u64
+                            && window_failures as f64 >= threshold * requests as f64
+                        {
+                            tracing::error!(
+                                requests = requests,
+                                failures = window_failures,
+                                threshold = threshold,
+                                "Circuit breaker opening due to error rate"
+                            );
+                            self.transition_to_open();
+                        }
+                    }
                 }
             }
             CircuitState::HalfOpen => {
@@ -218,11 +379,58 @@ impl CircuitBreaker {
         }
     }
 
+    /// Release a half-open slot for a request that counted neither way
+    ///
+    /// Used when an allowed request never reached the origin (cache hit) or
+    /// ended with an outcome that is not classified as a failure or success.
+    pub fn release_half_open_request(&self) {
+        if self.state() == CircuitState::HalfOpen {
+            let _ = self.half_open_requests.fetch_update(
+                Ordering::Relaxed,
+                Ordering::Relaxed,
+                |current| current.checked_sub(1),
+            );
+        }
+    }
+
+    /// Claim a half-open test slot for a background probe
+    ///
+    /// Returns false while closed, before the open timeout has elapsed, or when
+    /// all half-open slots are taken. A claimed slot is released by
+    /// `record_success` or `record_failure`.
+    pub fn try_start_probe(&self) -> bool {
+        if self.state() == CircuitState::Closed || !self.should_allow_request() {
+            return false;
+        }
+        self.start_half_open_request();
+        true
+    }
+
     /// Get current failure count
     pub fn failure_count(&self) -> u64 {
         self.failure_count.load(Ordering::Relaxed)
     }
 
+    /// Time left until an open circuit allows a test request
+    ///
+    /// `None` unless the circuit is open.
+    pub fn cooldown_remaining(&self) -> Option<Duration> {
+        if self.state() != CircuitState::Open {
+            return None;
+        }
+        let elapsed_ms = now_ms().saturating_sub(self.last_transition_ms.load(Ordering::Acquire));
+        Some(
+            self.config
+                .timeout_duration
+                .saturating_sub(Duration::from_millis(elapsed_ms)),
+        )
+    }
+
+    /// Get the failure ratio over the sliding window (error-rate policy only)
+    pub fn error_rate(&self) -> Option<f64> {
+        self.window.error_rate()
+    }
+
     /// Get current success count (in half-open state)
     pub fn success_count(&self) -> u64 {
         self.success_count.load(Ordering::Relaxed)
@@ -233,6 +441,7 @@ impl CircuitBreaker {
     /// Uses Release ordering to ensure counter resets are visible before state change.
     fn transition_to_closed(&self) {
         self.failure_count.store(0, Ordering::Relaxed);
+        self.window.reset();
         self.success_count.store(0, Ordering::Relaxed);
         self.half_open_requests.store(0, Ordering::Relaxed);
         self.last_transition_ms.store(now_ms(), Ordering::Relaxed);
@@ -446,4 +655,129 @@ mod tests {
         assert_eq!(config.timeout_duration, Duration::from_secs(60));
         assert_eq!(config.half_open_max_requests, 3);
     }
+
+    #[test]
+    fn test_probe_closes_circuit_without_client_traffic() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 1,
+            success_threshold: 2,
+            timeout_duration: Duration::from_millis(0),
+            half_open_max_requests: 1,
+            ..Default::default()
+        };
+        let breaker = CircuitBreaker::new(config);
+        assert!(!breaker.try_start_probe()); // Closed: nothing to probe
+
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+
+        // Probe claims the only half-open slot
+        assert!(breaker.try_start_probe());
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(!breaker.try_start_probe());
+        breaker.record_success();
+
+        assert!(breaker.try_start_probe());
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+    }
+
+    #[test]
+    fn test_release_half_open_request_frees_slot() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 1,
+            timeout_duration: Duration::from_millis(0),
+            half_open_max_requests: 1,
+            ..Default::default()
+        };
+        let breaker = CircuitBreaker::new(config);
+        breaker.record_failure();
+
+        assert!(breaker.should_allow_request());
+        breaker.start_half_open_request();
+        assert!(!breaker.should_allow_request());
+
+        breaker.release_half_open_request();
+        assert_eq!(breaker.state(), CircuitState::HalfOpen);
+        assert!(breaker.should_allow_request());
+
+        // Never drops below zero
+        breaker.release_half_open_request();
+        breaker.start_half_open_request();
+        assert!(!breaker.should_allow_request());
+    }
+
+    #[test]
+    fn test_error_rate_policy_opens_on_ratio() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 2,
+            policy: TripPolicy::ErrorRate {
+                window_size: 10,
+                threshold: 0.5,
+                minimum_requests: 4,
+            },
+            ..Default::default()
+        };
+        let breaker = CircuitBreaker::new(config);
+
+        // Interleaved failures never hit the consecutive threshold,
+        // and nothing trips before minimum_requests
+        breaker.record_failure();
+        breaker.record_success();
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        assert_eq!(breaker.error_rate(), Some(2.0 / 3.0));
+
+        breaker.record_success();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+    }
+
+    #[test]
+    fn test_error_rate_window_slides() {
+        let config = CircuitBreakerConfig {
+            policy: TripPolicy::ErrorRate {
+                window_size: 4,
+                threshold: 0.75,
+                minimum_requests: 4,
+            },
+            ..Default::default()
+        };
+        let breaker = CircuitBreaker::new(config);
+
+        breaker.record_failure();
+        breaker.record_failure();
+        for _ in 0..4 {
+            breaker.record_success();
+        }
+        // Old failures slid out of the window
+        assert_eq!(breaker.error_rate(), Some(0.0));
+
+        breaker.record_failure();
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Closed);
+        breaker.record_failure();
+        assert_eq!(breaker.state(), CircuitState::Open);
+        assert_eq!(
+            CircuitBreaker::new(CircuitBreakerConfig::default()).error_rate(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_cooldown_remaining() {
+        let config = CircuitBreakerConfig {
+            failure_threshold: 1,
+            timeout_duration: Duration::from_secs(30),
+            ..Default::default()
+        };
+        let breaker = CircuitBreaker::new(config);
+        assert_eq!(breaker.cooldown_remaining(), None);
+
+        breaker.record_failure();
+        let remaining = breaker.cooldown_remaining().unwrap();
+        assert!(remaining <= Duration::from_secs(30));
+        assert!(remaining > Duration::from_secs(25));
+    }
 }
@@ -0,0 +1,163 @@
+//! Temporary access grant configuration.
+//!
+//! Admins create short-lived read grants through `POST /admin/grants`: a
+//! subject (JWT `sub` or SigV4 access key) or client IP may read a bucket or
+//! key prefix until the grant expires. Grants live in memory; with a `redis`
+//! section they are shared across proxy instances.
+//!
+//! Default values are sourced from `crate::constants`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{
+    DEFAULT_ACCESS_GRANT_MAX_GRANTS, DEFAULT_ACCESS_GRANT_MAX_TTL_SECS,
+    DEFAULT_ACCESS_GRANT_REDIS_KEY, DEFAULT_ACCESS_GRANT_SYNC_INTERVAL_SECS,
+    DEFAULT_ACCESS_GRANT_TTL_SECS,
+};
+
+fn default_ttl_secs() -> u64 {
+    DEFAULT_ACCESS_GRANT_TTL_SECS
+}
+
+fn default_max_ttl_secs() -> u64 {
+    DEFAULT_ACCESS_GRANT_MAX_TTL_SECS
+}
+
+fn default_max_grants() -> usize {
+    DEFAULT_ACCESS_GRANT_MAX_GRANTS
+}
+
+fn default_redis_key() -> String {
+    DEFAULT_ACCESS_GRANT_REDIS_KEY.to_string()
+}
+
+fn default_sync_interval_secs() -> u64 {
+    DEFAULT_ACCESS_GRANT_SYNC_INTERVAL_SECS
+}
+
+/// Temporary access grants configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessGrantsConfig {
+    /// Enable the grants API and grant checks (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Lifetime of a grant created without `ttl_secs` (default: 3600)
+    #[serde(default = "default_ttl_secs")]
+    pub default_ttl_secs: u64,
+    /// Longest lifetime a grant may be given (default: 86400)
+    #[serde(default = "default_max_ttl_secs")]
+    pub max_ttl_secs: u64,
+    /// Maximum number of active grants (default: 1000)
+    #[serde(default = "default_max_grants")]
+    pub max_grants: usize,
+    /// Share grants across instances through Redis (default: in-memory only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redis: Option<AccessGrantsRedisConfig>,
+}
+
+impl Default for AccessGrantsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            default_ttl_secs: default_ttl_secs(),
+            max_ttl_secs: default_max_ttl_secs(),
+            max_grants: default_max_grants(),
+            redis: None,
+        }
+    }
+}
+
+/// Redis shared state for access grants
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessGrantsRedisConfig {
+    /// Redis connection URL (e.g., "redis://localhost:6379")
+    pub url: String,
+    /// Hash holding grants by ID (default: "yatagarasu:access_grants")
+    #[serde(default = "default_redis_key")]
+    pub key: String,
+    /// How often shared grants are pulled from Redis in seconds (default: 5)
+    #[serde(default = "default_sync_interval_secs")]
+    pub sync_interval_secs: u64,
+}
+
+impl AccessGrantsConfig {
+    /// Validate lifetimes, limits and Redis settings
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.max_ttl_secs == 0 {
+            return Err("server.access_grants.max_ttl_secs must be greater than 0".to_string());
+        }
+        if self.default_ttl_secs == 0 || self.default_ttl_secs > self.max_ttl_secs {
+            return Err(
+                "server.access_grants.default_ttl_secs must be between 1 and max_ttl_secs"
+                    .to_string(),
+            );
+        }
+        if self.max_grants == 0 {
+            return Err("server.access_grants.max_grants must be greater than 0".to_string());
+        }
+        if let Some(redis) = &self.redis {
+            if redis.url.trim().is_empty() {
+                return Err("server.access_grants.redis.url cannot be empty".to_string());
+            }
+            if redis.key.trim().is_empty() {
+                return Err("server.access_grants.redis.key cannot be empty".to_string());
+            }
+            if redis.sync_interval_secs == 0 {
+                return Err(
+                    "server.access_grants.redis.sync_interval_secs must be greater than 0"
+                        .to_string(),
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_access_grants_config() {
+        let config: AccessGrantsConfig = serde_yaml::from_str("enabled: true").unwrap();
+        assert_eq!(config.default_ttl_secs, 3600);
+        assert_eq!(config.max_ttl_secs, 86400);
+        assert_eq!(config.max_grants, 1000);
+        assert!(config.redis.is_none());
+        assert!(config.validate().is_ok());
+
+        let config: AccessGrantsConfig =
+            serde_yaml::from_str("enabled: true\nredis:\n  url: redis://localhost:6379").unwrap();
+        let redis = config.redis.clone().unwrap();
+        assert_eq!(redis.key, "yatagarasu:access_grants");
+        assert_eq!(redis.sync_interval_secs, 5);
+
+        // Disabled config is never rejected
+        assert!(AccessGrantsConfig {
+            max_grants: 0,
+            ..Default::default()
+        }
+        .validate()
+        .is_ok());
+
+        assert!(AccessGrantsConfig {
+            default_ttl_secs: 7200,
+            max_ttl_secs: 3600,
+            ..config.clone()
+        }
+        .validate()
+        .is_err());
+        assert!(AccessGrantsConfig {
+            redis: Some(AccessGrantsRedisConfig {
+                url: " ".to_string(),
+                ..redis
+            }),
+            ..config
+        }
+        .validate()
+        .is_err());
+    }
+}
@@ -0,0 +1,153 @@
+//! Admin API hardening configuration types.
+//!
+//! Every admin endpoint accepts only the methods it implements. State-changing
+//! requests must come from the proxy's own origin (or an allowlisted one), and
+//! a request carrying cookies must echo the CSRF cookie in a header, so a
+//! browser session cannot be driven by another site. Admin tokens can
+//! optionally be bound to the client IP they were issued for.
+//!
+//! Default values are sourced from `crate::constants`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{
+    DEFAULT_ADMIN_CSRF_COOKIE, DEFAULT_ADMIN_CSRF_HEADER, DEFAULT_ADMIN_IP_CLAIM,
+};
+
+fn default_csrf_enabled() -> bool {
+    true
+}
+
+fn default_cookie_name() -> String {
+    DEFAULT_ADMIN_CSRF_COOKIE.to_string()
+}
+
+fn default_header_name() -> String {
+    DEFAULT_ADMIN_CSRF_HEADER.to_string()
+}
+
+fn default_ip_claim() -> String {
+    DEFAULT_ADMIN_IP_CLAIM.to_string()
+}
+
+/// Same-origin and double-submit CSRF checks for state-changing admin requests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminCsrfConfig {
+    /// Enforce the checks on POST/PUT/PATCH/DELETE (default: true)
+    #[serde(default = "default_csrf_enabled")]
+    pub enabled: bool,
+    /// Origins accepted besides the request's own host, e.g. `https://dash.example.com`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_origins: Vec<String>,
+    /// Cookie set by `GET /admin/csrf-token` (default: yatagarasu_csrf)
+    #[serde(default = "default_cookie_name")]
+    pub cookie_name: String,
+    /// Header that must echo the cookie (default: X-CSRF-Token)
+    #[serde(default = "default_header_name")]
+    pub header_name: String,
+}
+
+impl Default for AdminCsrfConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_csrf_enabled(),
+            allowed_origins: Vec::new(),
+            cookie_name: default_cookie_name(),
+            header_name: default_header_name(),
+        }
+    }
+}
+
+/// Admin API hardening configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AdminSecurityConfig {
+    /// Origin and CSRF token checks (default: enabled)
+    #[serde(default)]
+    pub csrf: AdminCsrfConfig,
+    /// Require admin tokens to carry the client IP in `ip_claim` (default: false)
+    #[serde(default)]
+    pub bind_token_ip: bool,
+    /// Claim holding the IP or CIDR range a token is bound to (default: ip)
+    #[serde(default = "default_ip_claim")]
+    pub ip_claim: String,
+}
+
+impl Default for AdminSecurityConfig {
+    fn default() -> Self {
+        Self {
+            csrf: AdminCsrfConfig::default(),
+            bind_token_ip: false,
+            ip_claim: default_ip_claim(),
+        }
+    }
+}
+
+impl AdminSecurityConfig {
+    /// Validate origins and names
+    pub fn validate(&self) -> Result<(), String> {
+        if self.csrf.cookie_name.is_empty()
+            || !self
+                .csrf
+                .cookie_name
+                .bytes()
+                .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+        {
+            return Err(format!(
+                "server.admin.csrf.cookie_name must be a non-empty token of letters, digits, '_' or '-', got '{}'",
+                self.csrf.cookie_name
+            ));
+        }
+        if self.csrf.header_name.is_empty()
+            || http::HeaderName::from_bytes(self.csrf.header_name.as_bytes()).is_err()
+        {
+            return Err(format!(
+                "server.admin.csrf.header_name is not a valid header name: '{}'",
+                self.csrf.header_name
+            ));
+        }
+        for origin in &self.csrf.allowed_origins {
+            if !(origin.starts_with("http://") || origin.starts_with("https://"))
+                || origin.ends_with('/')
+            {
+                return Err(format!(
+                    "server.admin.csrf.allowed_origins entries must be scheme://host[:port] without a trailing slash, got '{}'",
+                    origin
+                ));
+            }
+        }
+        if self.bind_token_ip && self.ip_claim.is_empty() {
+            return Err("server.admin.ip_claim must not be empty when bind_token_ip is set".into());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_admin_security_config_defaults_and_validation() {
+        let config: AdminSecurityConfig = serde_yaml::from_str("{}").unwrap();
+        assert!(config.csrf.enabled);
+        assert_eq!(config.csrf.cookie_name, "yatagarasu_csrf");
+        assert_eq!(config.csrf.header_name, "X-CSRF-Token");
+        assert!(!config.bind_token_ip);
+        assert!(config.validate().is_ok());
+
+        let mut bad = config.clone();
+        bad.csrf.allowed_origins = vec!["dash.example.com".into()];
+        assert!(bad.validate().is_err());
+        bad.csrf.allowed_origins = vec!["https://dash.example.com/".into()];
+        assert!(bad.validate().is_err());
+
+        let mut bad = config.clone();
+        bad.csrf.cookie_name = "csrf token".into();
+        assert!(bad.validate().is_err());
+
+        let mut bad = config;
+        bad.bind_token_ip = true;
+        bad.ip_claim = String::new();
+        assert!(bad.validate().is_err());
+    }
+}
@@ -0,0 +1,83 @@
+//! Bucket aliases and route versions.
+//!
+//! One bucket can be served under several path prefixes without repeating
+//! its whole block. An alias maps onto the bucket's `path_prefix` before
+//! routing, so `/v1/assets/logo.png` and `/assets/logo.png` share auth,
+//! cache entries, replicas and every other setting:
+//!
+//! ```yaml
+//! - name: assets
+//!   path_prefix: /assets
+//!   version: v2
+//!   aliases:
+//!     - path_prefix: /v1/assets
+//!       version: v1
+//! ```
+//!
+//! Requests are counted per prefix and version so traffic on old routes
+//! can be watched before they are removed.
+
+use serde::{Deserialize, Serialize};
+
+/// Additional path prefix for a bucket (YAML format)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BucketAliasConfig {
+    /// Prefix routed to the bucket as if it were its `path_prefix`
+    pub path_prefix: String,
+    /// Version tag reported in metrics for requests on this prefix (default: none)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+/// Validate a version tag (used as a metrics label)
+pub fn validate_version(bucket_name: &str, version: &str) -> Result<(), String> {
+    if version.is_empty()
+        || !version
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-'))
+    {
+        return Err(format!(
+            "Bucket '{}': version '{}' must be non-empty letters, digits, '.', '_' or '-'",
+            bucket_name, version
+        ));
+    }
+    Ok(())
+}
+
+impl BucketAliasConfig {
+    /// Validate the prefix and version tag
+    pub fn validate(&self, bucket_name: &str) -> Result<(), String> {
+        if !self.path_prefix.starts_with('/') || self.path_prefix.len() < 2 {
+            return Err(format!(
+                "Bucket '{}': alias path_prefix '{}' must start with / and not be the root",
+                bucket_name, self.path_prefix
+            ));
+        }
+        if let Some(version) = &self.version {
+            validate_version(bucket_name, version)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_alias_validation() {
+        let alias: BucketAliasConfig =
+            serde_yaml::from_str("path_prefix: /v1/assets\nversion: v1").unwrap();
+        assert!(alias.validate("assets").is_ok());
+
+        let mut bad = alias.clone();
+        bad.path_prefix = "v1/assets".into();
+        assert!(bad.validate("assets").is_err());
+        bad.path_prefix = "/".into();
+        assert!(bad.validate("assets").is_err());
+
+        let mut bad = alias;
+        bad.version = Some("v1:beta".into());
+        assert!(bad.validate("assets").is_err());
+    }
+}
@@ -0,0 +1,316 @@
+//! Key popularity analytics and access statistics export configuration.
+//!
+//! Requests per object are counted per bucket in fixed time slots using a
+//! count-min sketch plus a small set of heavy-hitter candidates. The top keys
+//! for any window up to `max_window_secs` are served by
+//! `GET /admin/analytics/top-keys`.
+//!
+//! Memory per active bucket is roughly
+//! `(max_window_secs / slot_secs) * sketch_width * sketch_depth * 4` bytes.
+//!
+//! Access statistics export (`server.access_export`) aggregates requests,
+//! bytes and cache hits per key and bucket, and periodically writes them as
+//! CSV to a local directory or to S3 through a configured bucket.
+//!
+//! Default values are sourced from `crate::constants`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{
+    DEFAULT_ACCESS_EXPORT_INTERVAL_SECS, DEFAULT_ACCESS_EXPORT_MAX_KEYS,
+    DEFAULT_ACCESS_EXPORT_S3_PREFIX, DEFAULT_KEY_ANALYTICS_CANDIDATES,
+    DEFAULT_KEY_ANALYTICS_MAX_WINDOW_SECS, DEFAULT_KEY_ANALYTICS_SKETCH_DEPTH,
+    DEFAULT_KEY_ANALYTICS_SKETCH_WIDTH, DEFAULT_KEY_ANALYTICS_SLOT_SECS,
+};
+
+fn default_slot_secs() -> u64 {
+    DEFAULT_KEY_ANALYTICS_SLOT_SECS
+}
+
+fn default_max_window_secs() -> u64 {
+    DEFAULT_KEY_ANALYTICS_MAX_WINDOW_SECS
+}
+
+fn default_sketch_width() -> usize {
+    DEFAULT_KEY_ANALYTICS_SKETCH_WIDTH
+}
+
+fn default_sketch_depth() -> usize {
+    DEFAULT_KEY_ANALYTICS_SKETCH_DEPTH
+}
+
+fn default_candidates() -> usize {
+    DEFAULT_KEY_ANALYTICS_CANDIDATES
+}
+
+fn default_export_interval_secs() -> u64 {
+    DEFAULT_ACCESS_EXPORT_INTERVAL_SECS
+}
+
+fn default_export_max_keys() -> usize {
+    DEFAULT_ACCESS_EXPORT_MAX_KEYS
+}
+
+fn default_export_s3_prefix() -> String {
+    DEFAULT_ACCESS_EXPORT_S3_PREFIX.to_string()
+}
+
+/// Per-bucket key popularity tracking configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyAnalyticsConfig {
+    /// Track key popularity (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Counting slot length in seconds; windows are rounded to whole slots (default: 300)
+    #[serde(default = "default_slot_secs")]
+    pub slot_secs: u64,
+    /// Longest queryable window in seconds (default: 86400)
+    #[serde(default = "default_max_window_secs")]
+    pub max_window_secs: u64,
+    /// Count-min sketch counters per row (default: 1024)
+    #[serde(default = "default_sketch_width")]
+    pub sketch_width: usize,
+    /// Count-min sketch rows (default: 4)
+    #[serde(default = "default_sketch_depth")]
+    pub sketch_depth: usize,
+    /// Top-key candidates kept per bucket and slot; caps the `limit` query parameter (default: 100)
+    #[serde(default = "default_candidates")]
+    pub candidates: usize,
+}
+
+impl Default for KeyAnalyticsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            slot_secs: default_slot_secs(),
+            max_window_secs: default_max_window_secs(),
+            sketch_width: default_sketch_width(),
+            sketch_depth: default_sketch_depth(),
+            candidates: default_candidates(),
+        }
+    }
+}
+
+impl KeyAnalyticsConfig {
+    /// Validate slot, window and sketch sizes
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.slot_secs == 0 {
+            return Err("server.key_analytics.slot_secs must be greater than 0".to_string());
+        }
+        if self.max_window_secs < self.slot_secs {
+            return Err(
+                "server.key_analytics.max_window_secs must be at least slot_secs".to_string(),
+            );
+        }
+        if self.sketch_width == 0 || self.sketch_depth == 0 {
+            return Err(
+                "server.key_analytics.sketch_width and sketch_depth must be greater than 0"
+                    .to_string(),
+            );
+        }
+        if self.candidates == 0 {
+            return Err("server.key_analytics.candidates must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+
+    /// Number of slots kept per bucket
+    pub fn slot_count(&self) -> usize {
+        self.max_window_secs.div_ceil(self.slot_secs.max(1)) as usize
+    }
+}
+
+/// Output format for exported access statistics
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AccessExportFormat {
+    /// Comma-separated values with a header row
+    #[default]
+    Csv,
+}
+
+/// Periodic export of per-key access statistics for external analytics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessExportConfig {
+    /// Aggregate and export access statistics (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Seconds between exports; each file covers one interval (default: 3600)
+    #[serde(default = "default_export_interval_secs")]
+    pub interval_secs: u64,
+    /// Output format (default: csv)
+    #[serde(default)]
+    pub format: AccessExportFormat,
+    /// Distinct keys tracked per bucket per interval; further keys are folded
+    /// into a single `(other)` row (default: 10000)
+    #[serde(default = "default_export_max_keys")]
+    pub max_keys: usize,
+    /// Local directory to write export files to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub directory: Option<String>,
+    /// Upload export files to S3
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub s3: Option<AccessExportS3Config>,
+}
+
+impl Default for AccessExportConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: default_export_interval_secs(),
+            format: AccessExportFormat::default(),
+            max_keys: default_export_max_keys(),
+            directory: None,
+            s3: None,
+        }
+    }
+}
+
+/// S3 destination for access statistics exports
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessExportS3Config {
+    /// Name of a configured bucket whose S3 settings and credentials are used
+    pub bucket: String,
+    /// Object key prefix for export files (default: "access-stats/")
+    #[serde(default = "default_export_s3_prefix")]
+    pub prefix: String,
+}
+
+impl AccessExportConfig {
+    /// Validate the interval, key limit and destination
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.interval_secs == 0 {
+            return Err("server.access_export.interval_secs must be greater than 0".to_string());
+        }
+        if self.max_keys == 0 {
+            return Err("server.access_export.max_keys must be greater than 0".to_string());
+        }
+        if self.directory.is_none() && self.s3.is_none() {
+            return Err(
+                "server.access_export: at least one of directory or s3 must be set".to_string(),
+            );
+        }
+        if self
+            .directory
+            .as_ref()
+            .is_some_and(|dir| dir.trim().is_empty())
+        {
+            return Err("server.access_export.directory cannot be empty".to_string());
+        }
+        if let Some(s3) = &self.s3 {
+            if s3.bucket.trim().is_empty() {
+                return Err("server.access_export.s3.bucket cannot be empty".to_string());
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_analytics_config_defaults() {
+        let config: KeyAnalyticsConfig = serde_yaml::from_str("enabled: true").unwrap();
+        assert_eq!(config.slot_secs, 300);
+        assert_eq!(config.max_window_secs, 86400);
+        assert_eq!(config.sketch_width, 1024);
+        assert_eq!(config.sketch_depth, 4);
+        assert_eq!(config.candidates, 100);
+        assert_eq!(config.slot_count(), 288);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_key_analytics_config_validate() {
+        // Disabled config is never rejected
+        let config = KeyAnalyticsConfig {
+            slot_secs: 0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        let enabled = KeyAnalyticsConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        assert!(KeyAnalyticsConfig {
+            slot_secs: 0,
+            ..enabled.clone()
+        }
+        .validate()
+        .is_err());
+        assert!(KeyAnalyticsConfig {
+            max_window_secs: 60,
+            ..enabled.clone()
+        }
+        .validate()
+        .unwrap_err()
+        .contains("max_window_secs"));
+        assert!(KeyAnalyticsConfig {
+            sketch_depth: 0,
+            ..enabled
+        }
+        .validate()
+        .is_err());
+    }
+
+    #[test]
+    fn test_access_export_config_defaults() {
+        let config: AccessExportConfig =
+            serde_yaml::from_str("enabled: true\ndirectory: /var/lib/yatagarasu/stats").unwrap();
+        assert_eq!(config.interval_secs, 3600);
+        assert_eq!(config.format, AccessExportFormat::Csv);
+        assert_eq!(config.max_keys, 10_000);
+        assert!(config.validate().is_ok());
+
+        let config: AccessExportConfig =
+            serde_yaml::from_str("enabled: true\ns3:\n  bucket: logs").unwrap();
+        assert_eq!(config.s3.unwrap().prefix, "access-stats/");
+
+        assert!(serde_yaml::from_str::<AccessExportConfig>("format: parquet").is_err());
+    }
+
+    #[test]
+    fn test_access_export_config_validate() {
+        // Disabled config is never rejected
+        assert!(AccessExportConfig::default().validate().is_ok());
+
+        let enabled = AccessExportConfig {
+            enabled: true,
+            directory: Some("/tmp/stats".to_string()),
+            ..Default::default()
+        };
+        assert!(enabled.validate().is_ok());
+        assert!(AccessExportConfig {
+            directory: None,
+            ..enabled.clone()
+        }
+        .validate()
+        .unwrap_err()
+        .contains("directory or s3"));
+        assert!(AccessExportConfig {
+            interval_secs: 0,
+            ..enabled.clone()
+        }
+        .validate()
+        .is_err());
+        assert!(AccessExportConfig {
+            directory: None,
+            s3: Some(AccessExportS3Config {
+                bucket: " ".to_string(),
+                prefix: default_export_s3_prefix(),
+            }),
+            ..enabled
+        }
+        .validate()
+        .is_err());
+    }
+}
@@ -3,8 +3,10 @@
 //! This module defines bucket-level authorization configuration supporting:
 //! - Open Policy Agent (OPA) integration for policy-as-code
 //! - OpenFGA integration for relationship-based access control (ReBAC)
+//! - Built-in claim-to-path rules (`type: claims`) for prefix isolation
+//!   without an external policy engine
 //!
-//! Both integrations support configurable timeouts, caching, and fail modes.
+//! Both external integrations support configurable timeouts, caching, and fail modes.
 //! Default values for timeouts and cache TTLs are sourced from `crate::constants`.
 
 use serde::{Deserialize, Serialize};
@@ -94,6 +96,59 @@ pub struct AuthorizationConfig {
     /// Supports dot notation for nested claims (e.g., "user.id")
     #[serde(skip_serializing_if = "Option::is_none")]
     pub openfga_user_claim: Option<String>,
+
+    /// Claim-to-path rules for `type: claims`; a request is allowed when any rule matches
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rules: Vec<ClaimPathRule>,
+}
+
+/// Allowed object paths for a caller, built from their JWT claims
+///
+/// `path` is a glob over the object key in which `{claim}` placeholders
+/// (dot notation for nested claims) are replaced by the caller's claim
+/// values, e.g. `/{tenant_id}/**`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClaimPathRule {
+    pub path: String,
+    /// Methods the rule applies to (default: all)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub methods: Vec<String>,
+}
+
+impl ClaimPathRule {
+    /// Validate the rule for the given bucket
+    pub fn validate(&self, bucket_name: &str) -> Result<(), String> {
+        if self.path.trim().is_empty() {
+            return Err(format!(
+                "Bucket '{}': authorization rule path cannot be empty",
+                bucket_name
+            ));
+        }
+        let mut rest = self.path.as_str();
+        while let Some(start) = rest.find('{') {
+            let Some(len) = rest[start..].find('}') else {
+                return Err(format!(
+                    "Bucket '{}': authorization rule path '{}' has an unclosed '{{'",
+                    bucket_name, self.path
+                ));
+            };
+            let claim = &rest[start + 1..start + len];
+            if claim.is_empty() || claim.contains('{') {
+                return Err(format!(
+                    "Bucket '{}': authorization rule path '{}' has an invalid placeholder",
+                    bucket_name, self.path
+                ));
+            }
+            rest = &rest[start + len + 1..];
+        }
+        if rest.contains('}') {
+            return Err(format!(
+                "Bucket '{}': authorization rule path '{}' has an unmatched '}}'",
+                bucket_name, self.path
+            ));
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -190,6 +245,31 @@ openfga_user_claim: "user.id"
         assert_eq!(config.openfga_user_claim, Some("user.id".to_string()));
     }
 
+    #[test]
+    fn test_authorization_config_claims_rules() {
+        let yaml = r#"
+type: claims
+rules:
+  - path: "/{tenant_id}/**"
+  - path: "/shared/**"
+    methods: [GET, HEAD]
+"#;
+        let config: AuthorizationConfig = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(config.auth_type, "claims");
+        assert_eq!(config.rules.len(), 2);
+        assert!(config.rules[0].methods.is_empty());
+        assert!(config.rules.iter().all(|r| r.validate("tenants").is_ok()));
+
+        for bad in ["", "/{tenant_id/**", "/{}/**", "/tenant}/**"] {
+            let rule = ClaimPathRule {
+                path: bad.to_string(),
+                methods: vec![],
+            };
+            assert!(rule.validate("tenants").is_err(), "{}", bad);
+        }
+    }
+
     #[test]
     fn test_authorization_config_defaults() {
         let yaml = r#"
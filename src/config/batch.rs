@@ -0,0 +1,136 @@
+//! Batch GET API configuration.
+//!
+//! The batch endpoint accepts a JSON list of object paths and fetches them
+//! concurrently, returning one NDJSON line per object with the body inlined as
+//! base64. It exists for clients that render grids of small objects (e.g.
+//! thumbnails) and would otherwise issue hundreds of sequential requests.
+//!
+//! Default values are sourced from `crate::constants`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{
+    DEFAULT_BATCH_GET_PATH, DEFAULT_BATCH_MAX_CONCURRENCY, DEFAULT_BATCH_MAX_INLINE_BYTES,
+    DEFAULT_BATCH_MAX_KEYS,
+};
+
+fn default_path() -> String {
+    DEFAULT_BATCH_GET_PATH.to_string()
+}
+
+fn default_max_keys() -> usize {
+    DEFAULT_BATCH_MAX_KEYS
+}
+
+fn default_max_concurrency() -> usize {
+    DEFAULT_BATCH_MAX_CONCURRENCY
+}
+
+fn default_max_inline_bytes() -> u64 {
+    DEFAULT_BATCH_MAX_INLINE_BYTES
+}
+
+/// Batch GET API configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchConfig {
+    /// Enable the batch GET endpoint (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Endpoint path (default: "/batch/get")
+    #[serde(default = "default_path")]
+    pub path: String,
+    /// Maximum objects per request (default: 100)
+    #[serde(default = "default_max_keys")]
+    pub max_keys: usize,
+    /// Objects fetched concurrently per request (default: 16)
+    #[serde(default = "default_max_concurrency")]
+    pub max_concurrency: usize,
+    /// Larger objects are reported with status 413 instead of inlined (default: 1 MB)
+    #[serde(default = "default_max_inline_bytes")]
+    pub max_inline_bytes: u64,
+}
+
+impl Default for BatchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: default_path(),
+            max_keys: default_max_keys(),
+            max_concurrency: default_max_concurrency(),
+            max_inline_bytes: default_max_inline_bytes(),
+        }
+    }
+}
+
+impl BatchConfig {
+    /// Validate the batch configuration
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if !self.path.starts_with('/') {
+            return Err(format!("batch.path '{}' must start with /", self.path));
+        }
+        if self.max_keys == 0 {
+            return Err("batch.max_keys must be greater than 0".to_string());
+        }
+        if self.max_concurrency == 0 {
+            return Err("batch.max_concurrency must be greater than 0".to_string());
+        }
+        if self.max_inline_bytes == 0 {
+            return Err("batch.max_inline_bytes must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+
+    /// Check if a request targets the batch endpoint
+    pub fn is_batch_request(&self, path: &str, method: &str) -> bool {
+        self.enabled && method == "POST" && path == self.path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_config_defaults_disabled() {
+        let config: BatchConfig = serde_yaml::from_str("{}").unwrap();
+
+        assert!(!config.enabled);
+        assert_eq!(config.path, DEFAULT_BATCH_GET_PATH);
+        assert_eq!(config.max_keys, DEFAULT_BATCH_MAX_KEYS);
+        assert_eq!(config.max_concurrency, DEFAULT_BATCH_MAX_CONCURRENCY);
+        assert!(!config.is_batch_request("/batch/get", "POST"));
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_batch_config_is_batch_request() {
+        let config = BatchConfig {
+            enabled: true,
+            ..Default::default()
+        };
+
+        assert!(config.is_batch_request("/batch/get", "POST"));
+        assert!(!config.is_batch_request("/batch/get", "GET"));
+        assert!(!config.is_batch_request("/batch/get/x", "POST"));
+    }
+
+    #[test]
+    fn test_batch_config_validate_rejects_invalid_limits() {
+        let config = BatchConfig {
+            enabled: true,
+            max_concurrency: 0,
+            ..Default::default()
+        };
+        assert!(config.validate().unwrap_err().contains("max_concurrency"));
+
+        let config = BatchConfig {
+            enabled: true,
+            path: "batch".to_string(),
+            ..Default::default()
+        };
+        assert!(config.validate().unwrap_err().contains("must start with /"));
+    }
+}
@@ -2,9 +2,16 @@
 //!
 //! This module defines per-bucket configuration including:
 //! - Bucket routing (name, path prefix)
-//! - S3 backend settings (credentials, endpoint, timeouts)
+//! - S3 backend settings (credentials, endpoint, timeouts, bulkheads)
 //! - HA replica configuration for multi-region failover
-//! - Per-bucket auth, cache, authorization, and IP filtering
+//! - Per-bucket auth (optionally with its own JWT public key, issuer, audience and claim rules), cache, authorization, and IP filtering
+//! - Per-bucket logging verbosity and redaction
+//! - Per-bucket download resumption tokens
+//! - Per-bucket Content-Type overrides
+//! - Per-bucket hotlink protection and signed URLs
+//! - Per-bucket allowed HTTP methods and CORS policy
+//! - Per-bucket serve-from-cache degraded mode
+//! - Per-bucket size classes (cache policy, timeouts, replica by object size)
 //!
 //! Default values for timeouts and pool sizes are sourced from `crate::constants`.
 //!
@@ -21,19 +28,47 @@
 //! before using the configuration.
 
 use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
 
 use crate::cache::BucketCacheOverride;
-use crate::constants::{DEFAULT_CONNECTION_POOL_SIZE, DEFAULT_S3_TIMEOUT_SECS};
+use crate::constants::{
+    BUCKET_WRITE_METHODS, DEFAULT_BUCKET_ALLOWED_METHODS, DEFAULT_CONNECTION_POOL_SIZE,
+    DEFAULT_S3_TIMEOUT_SECS, SUPPORTED_BUCKET_METHODS,
+};
 
 // Re-export IpFilterConfig from security module.
 // This allows tests and external code to access it via `config::IpFilterConfig`
 // while the canonical definition remains in the security module.
 pub use crate::security::IpFilterConfig;
 
+use super::alias::BucketAliasConfig;
 use super::authorization::AuthorizationConfig;
+use super::bulkhead::BulkheadConfig;
 use super::circuit_breaker::CircuitBreakerConfigYaml;
+use super::content_type::ContentTypeConfig;
+use super::cors::CorsConfig;
+use super::credentials::S3CredentialsConfig;
+use super::degraded::DegradedModeConfig;
+use super::deprecation::DeprecationConfig;
+use super::early_hints::EarlyHintsConfig;
+use super::etag::EtagRewriteConfig;
+use super::hotlink::HotlinkConfig;
+use super::jwt::{ClaimRule, JwtConfig, ASYMMETRIC_ALGORITHMS, CLAIM_OPERATORS};
+use super::listing::ListingConfig;
+use super::logging::BucketLoggingConfig;
+use super::origin::OriginConfig;
+use super::post_policy::PostPolicyConfig;
 use super::rate_limit::BucketRateLimitConfigYaml;
+use super::redirect::PresignedRedirectConfig;
+use super::resume::ResumeConfig;
 use super::retry::RetryConfigYaml;
+use super::routes::RoutePatternConfig;
+use super::server_timing::ServerTimingConfig;
+use super::signed_url::SignedUrlConfig;
+use super::signing::{S3SigningConfig, SignatureVersion};
+use super::sigv4_auth::SigV4AuthConfig;
+use super::size_class::SizeClassConfig;
+use super::upload_scan::UploadScanConfig;
 use crate::watermark::BucketWatermarkConfig;
 
 fn default_s3_timeout() -> u64 {
@@ -48,6 +83,20 @@ fn default_connection_pool_size() -> usize {
 pub struct BucketConfig {
     pub name: String,
     pub path_prefix: String,
+    /// Additional path prefixes served by this bucket (default: none)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<BucketAliasConfig>,
+    /// Version tag of `path_prefix`, reported in per-prefix metrics (default: none)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Host names routed to this bucket regardless of path (default: path routing only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hostnames: Vec<String>,
+    /// Glob/regex path patterns with S3 key templates (default: prefix routing only)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub routes: Vec<RoutePatternConfig>,
+    /// S3 backend (may be omitted when `origin` is set)
+    #[serde(default)]
     pub s3: S3Config,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub auth: Option<AuthConfig>,
@@ -61,6 +110,136 @@ pub struct BucketConfig {
     /// Watermark configuration for images served from this bucket
     #[serde(skip_serializing_if = "Option::is_none")]
     pub watermark: Option<BucketWatermarkConfig>,
+    /// Logging overrides (level, redacted query params and headers) for this bucket
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub logging: Option<BucketLoggingConfig>,
+    /// Download resumption tokens for large objects (default: disabled)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub resume: Option<ResumeConfig>,
+    /// Content-Type fixes by extension or magic bytes (default: upstream type as-is)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<ContentTypeConfig>,
+    /// Referer/Origin allowlist against hotlinking (default: disabled)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hotlink: Option<HotlinkConfig>,
+    /// HTTP methods accepted for this bucket (default: GET, HEAD, OPTIONS)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allowed_methods: Option<Vec<String>>,
+    /// CORS policy for preflight and object responses (default: no origin grants)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cors: Option<CorsConfig>,
+    /// Serve from cache (even stale) while all origins are down (default: disabled)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub degraded: Option<DegradedModeConfig>,
+    /// `Server-Timing` header with per-phase durations (default: disabled)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub server_timing: Option<ServerTimingConfig>,
+    /// Preload `Link` headers / `103 Early Hints` for HTML objects (default: disabled)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub early_hints: Option<EarlyHintsConfig>,
+    /// Replica-independent or replica-namespaced ETags (default: upstream ETag as-is)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub etag_rewrite: Option<EtagRewriteConfig>,
+    /// Key-less GET/HEAD as ListObjectsV2, optionally as JSON (default: disabled)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub listing: Option<ListingConfig>,
+    /// Non-S3 backend (HTTP origin) instead of `s3` (default: S3)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub origin: Option<OriginConfig>,
+    /// ClamAV scan of upload bodies before they reach S3 (default: disabled)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub upload_scan: Option<UploadScanConfig>,
+    /// `Deprecation`/`Sunset`/`Link` headers on every response (default: none)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub deprecation: Option<DeprecationConfig>,
+    /// Expiring HMAC-signed URLs required for every request (default: disabled)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signed_url: Option<SignedUrlConfig>,
+    /// Clients authenticate with AWS SigV4 access keys (default: disabled)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sigv4_auth: Option<SigV4AuthConfig>,
+    /// Browser form uploads authorized by a signed POST policy (default: disabled)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_policy: Option<PostPolicyConfig>,
+    /// 302 to a short-lived presigned S3 URL instead of proxying bytes (default: disabled)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub presigned_redirect: Option<PresignedRedirectConfig>,
+    /// Cache policy, timeouts and replica by object size, smallest class first (default: none)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub size_classes: Vec<SizeClassConfig>,
+}
+
+impl BucketConfig {
+    /// HTTP methods accepted for this bucket, uppercased
+    pub fn allowed_methods(&self) -> Vec<String> {
+        match &self.allowed_methods {
+            Some(methods) => methods.iter().map(|m| m.to_ascii_uppercase()).collect(),
+            None => DEFAULT_BUCKET_ALLOWED_METHODS
+                .iter()
+                .map(|m| m.to_string())
+                .collect(),
+        }
+    }
+
+    /// Check if `method` is accepted for this bucket (case-insensitive)
+    pub fn allows_method(&self, method: &str) -> bool {
+        match &self.allowed_methods {
+            Some(methods) => methods.iter().any(|m| m.eq_ignore_ascii_case(method)),
+            None => DEFAULT_BUCKET_ALLOWED_METHODS
+                .iter()
+                .any(|m| m.eq_ignore_ascii_case(method)),
+        }
+    }
+
+    /// Validate the allowed methods list
+    pub fn validate_allowed_methods(&self) -> Result<(), String> {
+        let Some(methods) = &self.allowed_methods else {
+            return Ok(());
+        };
+        if methods.is_empty() {
+            return Err(format!(
+                "Bucket '{}': allowed_methods cannot be empty",
+                self.name
+            ));
+        }
+        for method in methods {
+            if !SUPPORTED_BUCKET_METHODS
+                .iter()
+                .any(|m| m.eq_ignore_ascii_case(method))
+            {
+                return Err(format!(
+                    "Bucket '{}': allowed_methods entry '{}' is not supported (supported: {})",
+                    self.name,
+                    method,
+                    SUPPORTED_BUCKET_METHODS.join(", ")
+                ));
+            }
+        }
+        // Multipart requests are signed with SigV4 only
+        let writes = methods.iter().any(|m| {
+            BUCKET_WRITE_METHODS
+                .iter()
+                .any(|w| w.eq_ignore_ascii_case(m))
+        });
+        let v2 = self
+            .s3
+            .signing
+            .as_ref()
+            .is_some_and(|s| s.version == SignatureVersion::V2);
+        if writes && v2 {
+            return Err(format!(
+                "Bucket '{}': write methods (multipart uploads) require SigV4 signing",
+                self.name
+            ));
+        }
+        if writes && self.origin.is_some() {
+            return Err(format!(
+                "Bucket '{}': write methods (multipart uploads) require an S3 backend",
+                self.name
+            ));
+        }
+        Ok(())
+    }
 }
 
 /// S3 Replica configuration (for HA bucket replication)
@@ -69,13 +248,20 @@ pub struct S3Replica {
     pub name: String,
     pub bucket: String,
     pub region: String,
+    /// Static keys; left empty when `s3.credentials` supplies them
+    #[serde(default)]
     pub access_key: String,
+    #[serde(default)]
     pub secret_key: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub endpoint: Option<String>,
     pub priority: u8,
     #[serde(default = "default_s3_timeout")]
     pub timeout: u64,
+    /// Static `ip:port` addresses to connect to instead of resolving `endpoint`
+    /// (requests rotate across the healthy ones; `endpoint` still sets scheme and Host)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub endpoints: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -101,6 +287,14 @@ pub struct S3Config {
     pub rate_limit: Option<BucketRateLimitConfigYaml>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub retry: Option<RetryConfigYaml>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bulkhead: Option<BulkheadConfig>,
+    /// Signing quirks for S3-compatible backends (default: AWS SigV4)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signing: Option<S3SigningConfig>,
+    /// Credential provider for upstream requests (default: static keys)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub credentials: Option<S3CredentialsConfig>,
 
     // New replica set field (for HA - optional, mutually exclusive with legacy fields)
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -108,6 +302,20 @@ pub struct S3Config {
 }
 
 impl S3Config {
+    /// Static keys: the bucket's own, else the first replica's that has keys
+    ///
+    /// With `credentials.assume_role_arn`, these sign the STS call only.
+    pub fn static_keys(&self) -> Option<(&str, &str)> {
+        if !self.access_key.is_empty() && !self.secret_key.is_empty() {
+            return Some((&self.access_key, &self.secret_key));
+        }
+        self.replicas
+            .iter()
+            .flatten()
+            .find(|r| !r.access_key.is_empty() && !r.secret_key.is_empty())
+            .map(|r| (r.access_key.as_str(), r.secret_key.as_str()))
+    }
+
     /// Returns true if any legacy S3 fields are populated (non-empty).
     ///
     /// Legacy fields are: `bucket`, `region`, `access_key`, `secret_key`.
@@ -155,6 +363,60 @@ impl S3Config {
             ));
         }
 
+        if let Some(rate_limit) = &self.rate_limit {
+            rate_limit.validate(bucket_name)?;
+        }
+
+        if let Some(circuit_breaker) = &self.circuit_breaker {
+            circuit_breaker.validate(bucket_name)?;
+        }
+
+        if let Some(bulkhead) = &self.bulkhead {
+            bulkhead.validate(bucket_name)?;
+        }
+
+        if let Some(signing) = &self.signing {
+            signing.validate(bucket_name)?;
+        }
+
+        if let Some(credentials) = &self.credentials {
+            credentials.validate(bucket_name)?;
+            if credentials.is_dynamic() {
+                let static_keys = !self.access_key.is_empty()
+                    || !self.secret_key.is_empty()
+                    || self
+                        .replicas
+                        .iter()
+                        .flatten()
+                        .any(|r| !r.access_key.is_empty() || !r.secret_key.is_empty());
+                if static_keys {
+                    return Err(format!(
+                        "Bucket '{}': s3.credentials.source '{}' cannot be combined with static \
+                        access_key/secret_key",
+                        bucket_name,
+                        credentials.source.as_str()
+                    ));
+                }
+            } else if credentials.assume_role_arn.is_some() && self.static_keys().is_none() {
+                return Err(format!(
+                    "Bucket '{}': s3.credentials.assume_role_arn with source 'static' needs \
+                    access_key/secret_key to call STS",
+                    bucket_name
+                ));
+            }
+            if credentials.uses_provider()
+                && self
+                    .signing
+                    .as_ref()
+                    .is_some_and(|s| s.version == SignatureVersion::V2)
+            {
+                return Err(format!(
+                    "Bucket '{}': temporary credentials from s3.credentials require v4 signing",
+                    bucket_name
+                ));
+            }
+        }
+
         Ok(())
     }
 }
@@ -162,6 +424,107 @@ impl S3Config {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AuthConfig {
     pub enabled: bool,
+    /// Algorithm of this bucket's public key (RS*, ES* or EdDSA)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub algorithm: Option<String>,
+    /// PEM public key verifying this bucket's tokens instead of the `jwt` keys
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key_path: Option<String>,
+    /// Required `iss` claim, replacing `jwt.issuer` for this bucket
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub issuer: Option<String>,
+    /// Accepted `aud` values, replacing `jwt.audience` for this bucket
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub audience: Vec<String>,
+    /// Claim rules checked in addition to `jwt.claims`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub claims: Vec<ClaimRule>,
+}
+
+impl AuthConfig {
+    /// Validate the per-bucket public key and claim constraints
+    pub fn validate(&self, bucket_name: &str) -> Result<(), String> {
+        if self.issuer.as_ref().is_some_and(|i| i.trim().is_empty()) {
+            return Err(format!(
+                "Bucket '{}': auth.issuer cannot be empty",
+                bucket_name
+            ));
+        }
+        if self.audience.iter().any(|a| a.trim().is_empty()) {
+            return Err(format!(
+                "Bucket '{}': auth.audience entries cannot be empty",
+                bucket_name
+            ));
+        }
+        if let Some(rule) = self
+            .claims
+            .iter()
+            .find(|rule| !CLAIM_OPERATORS.contains(&rule.operator.as_str()))
+        {
+            return Err(format!(
+                "Bucket '{}': invalid auth claim operator '{}'. Supported operators: {}",
+                bucket_name,
+                rule.operator,
+                CLAIM_OPERATORS.join(", ")
+            ));
+        }
+        match (&self.algorithm, &self.public_key_path) {
+            (None, None) => Ok(()),
+            (Some(algorithm), Some(path)) => {
+                if !ASYMMETRIC_ALGORITHMS.contains(&algorithm.as_str()) {
+                    return Err(format!(
+                        "Bucket '{}': auth.algorithm '{}' must be one of: {}",
+                        bucket_name,
+                        algorithm,
+                        ASYMMETRIC_ALGORITHMS.join(", ")
+                    ));
+                }
+                if path.trim().is_empty() {
+                    return Err(format!(
+                        "Bucket '{}': auth.public_key_path cannot be empty",
+                        bucket_name
+                    ));
+                }
+                Ok(())
+            }
+            _ => Err(format!(
+                "Bucket '{}': auth.algorithm and auth.public_key_path must be set together",
+                bucket_name
+            )),
+        }
+    }
+
+    /// JWT settings for this bucket's tokens
+    ///
+    /// The global settings (token sources, claims) are kept. A per-bucket
+    /// public key replaces the key, algorithm and allowlist; `issuer` and
+    /// `audience` replace the global ones; `claims` are added to the global rules.
+    pub fn jwt_config<'a>(&self, global: &'a JwtConfig) -> Cow<'a, JwtConfig> {
+        let has_key = self.algorithm.is_some() && self.public_key_path.is_some();
+        if !has_key && self.issuer.is_none() && self.audience.is_empty() && self.claims.is_empty() {
+            return Cow::Borrowed(global);
+        }
+        let mut jwt = global.clone();
+        if let (Some(algorithm), Some(path)) = (&self.algorithm, &self.public_key_path) {
+            jwt.algorithm = algorithm.clone();
+            jwt.allowed_algorithms = vec![algorithm.clone()];
+            jwt.keys.clear();
+            jwt.jwks_url = None;
+            match algorithm.as_str() {
+                "ES256" | "ES384" => jwt.ecdsa_public_key_path = Some(path.clone()),
+                "EdDSA" => jwt.eddsa_public_key_path = Some(path.clone()),
+                _ => jwt.rsa_public_key_path = Some(path.clone()),
+            }
+        }
+        if self.issuer.is_some() {
+            jwt.issuer = self.issuer.clone();
+        }
+        if !self.audience.is_empty() {
+            jwt.audience = self.audience.clone();
+        }
+        jwt.claims.extend(self.claims.iter().cloned());
+        Cow::Owned(jwt)
+    }
 }
 
 #[cfg(test)]
@@ -555,12 +918,63 @@ ip_filter:
                 endpoint: None,
                 priority: 1,
                 timeout: 30,
+                endpoints: Vec::new(),
             }]),
             ..Default::default()
         };
         assert!(config.validate("test-bucket").is_ok());
     }
 
+    #[test]
+    fn test_s3_config_validate_credentials_source() {
+        let yaml = r#"
+bucket: my-bucket
+region: us-west-2
+credentials:
+  source: instance
+"#;
+        let config: S3Config = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.validate("test-bucket").is_ok());
+
+        // Static keys and a dynamic source are mutually exclusive
+        let config = S3Config {
+            access_key: "test".to_string(),
+            ..config
+        };
+        assert!(config.validate("test-bucket").is_err());
+
+        let yaml = r#"
+credentials:
+  source: auto
+signing:
+  version: v2
+replicas:
+  - name: primary
+    bucket: my-bucket
+    region: us-west-2
+    priority: 1
+"#;
+        let config: S3Config = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.validate("test-bucket").is_err());
+
+        // AssumeRole from static keys needs keys to sign the STS call
+        let yaml = r#"
+bucket: my-bucket
+region: us-west-2
+credentials:
+  assume_role_arn: arn:aws:iam::210987654321:role/media-reader
+"#;
+        let config: S3Config = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.validate("test-bucket").is_err());
+        let config = S3Config {
+            access_key: "test".to_string(),
+            secret_key: "secret".to_string(),
+            ..config
+        };
+        assert!(config.validate("test-bucket").is_ok());
+        assert_eq!(config.static_keys(), Some(("test", "secret")));
+    }
+
     #[test]
     fn test_s3_config_validate_rejects_both_legacy_and_replicas() {
         let config = S3Config {
@@ -577,6 +991,7 @@ ip_filter:
                 endpoint: None,
                 priority: 1,
                 timeout: 30,
+                endpoints: Vec::new(),
             }]),
             ..Default::default()
         };
@@ -661,4 +1076,137 @@ s3:
 
         assert!(config.watermark.is_none());
     }
+
+    #[test]
+    fn test_bucket_config_allowed_methods() {
+        let yaml = r#"
+name: "probes"
+path_prefix: "/probes"
+s3:
+  bucket: "probes"
+  region: "us-east-1"
+  access_key: "test-key"
+  secret_key: "test-secret"
+allowed_methods: ["head"]
+"#;
+        let config: BucketConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.allowed_methods(), vec!["HEAD"]);
+        assert!(config.allows_method("HEAD"));
+        assert!(!config.allows_method("GET"));
+        assert!(config.validate_allowed_methods().is_ok());
+
+        // Unset means the read-only default
+        let default = BucketConfig {
+            allowed_methods: None,
+            ..config.clone()
+        };
+        assert_eq!(default.allowed_methods(), vec!["GET", "HEAD", "OPTIONS"]);
+        assert!(default.allows_method("options"));
+        assert!(!default.allows_method("PUT"));
+
+        assert!(BucketConfig {
+            allowed_methods: Some(vec![]),
+            ..config.clone()
+        }
+        .validate_allowed_methods()
+        .is_err());
+        assert!(BucketConfig {
+            allowed_methods: Some(vec!["GET".to_string(), "PATCH".to_string()]),
+            ..config.clone()
+        }
+        .validate_allowed_methods()
+        .unwrap_err()
+        .contains("PATCH"));
+
+        // Write methods enable multipart uploads, which need SigV4
+        let writable = BucketConfig {
+            allowed_methods: Some(vec!["GET".to_string(), "PUT".to_string()]),
+            ..config.clone()
+        };
+        assert!(writable.validate_allowed_methods().is_ok());
+        let mut v2 = writable;
+        v2.s3.signing = Some(S3SigningConfig {
+            version: SignatureVersion::V2,
+            ..Default::default()
+        });
+        assert!(v2.validate_allowed_methods().unwrap_err().contains("SigV4"));
+    }
+
+    #[test]
+    fn test_auth_config_per_bucket_public_key() {
+        let global: JwtConfig = serde_yaml::from_str(
+            "enabled: true\nalgorithm: HS256\nsecret: s3cret\ntoken_sources:\n  - type: bearer",
+        )
+        .unwrap();
+
+        let plain: AuthConfig = serde_yaml::from_str("enabled: true").unwrap();
+        assert!(plain.validate("b").is_ok());
+        assert!(matches!(plain.jwt_config(&global), Cow::Borrowed(_)));
+
+        let eddsa: AuthConfig = serde_yaml::from_str(
+            "enabled: true\nalgorithm: EdDSA\npublic_key_path: /etc/keys/ed.pem",
+        )
+        .unwrap();
+        assert!(eddsa.validate("b").is_ok());
+        let jwt = eddsa.jwt_config(&global);
+        assert_eq!(jwt.algorithm, "EdDSA");
+        assert_eq!(jwt.accepted_algorithms(), vec!["EdDSA".to_string()]);
+        assert_eq!(jwt.public_key_path("EdDSA"), Some("/etc/keys/ed.pem"));
+        assert_eq!(jwt.token_sources.len(), 1);
+
+        let hmac = AuthConfig {
+            algorithm: Some("HS256".to_string()),
+            ..eddsa.clone()
+        };
+        assert!(hmac.validate("b").is_err());
+
+        let no_path = AuthConfig {
+            public_key_path: None,
+            ..eddsa
+        };
+        assert!(no_path.validate("b").is_err());
+    }
+
+    #[test]
+    fn test_auth_config_per_bucket_issuer_audience_and_claims() {
+        let global: JwtConfig = serde_yaml::from_str(
+            r#"
+enabled: true
+algorithm: HS256
+secret: s3cret
+issuer: https://auth.example.com
+claims:
+  - claim: role
+    operator: equals
+    value: user
+"#,
+        )
+        .unwrap();
+
+        let auth: AuthConfig = serde_yaml::from_str(
+            r#"
+enabled: true
+issuer: https://partners.example.com
+audience: [reports, reports-staging]
+claims:
+  - claim: tenant
+    operator: equals
+    value: acme
+"#,
+        )
+        .unwrap();
+        assert!(auth.validate("reports").is_ok());
+
+        let jwt = auth.jwt_config(&global);
+        assert_eq!(jwt.issuer.as_deref(), Some("https://partners.example.com"));
+        assert_eq!(jwt.audience, vec!["reports", "reports-staging"]);
+        assert_eq!(jwt.claims.len(), 2);
+        assert_eq!(jwt.algorithm, "HS256");
+
+        let blank = AuthConfig {
+            audience: vec![" ".to_string()],
+            ..auth
+        };
+        assert!(blank.validate("reports").is_err());
+    }
 }
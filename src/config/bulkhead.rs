@@ -0,0 +1,67 @@
+//! Per-bucket bulkhead configuration.
+//!
+//! A bulkhead caps how many requests a bucket may have in flight to its S3
+//! backend at once. When a slow origin holds its requests open, only that
+//! bucket's slots fill up; further requests for it get a fast 503 while other
+//! buckets keep their share of the proxy's capacity. Cache hits never take a
+//! slot.
+//!
+//! Multipart upload requests can get a pool of their own
+//! (`max_uploads_in_flight`), so long part uploads cannot starve downloads.
+
+use serde::{Deserialize, Serialize};
+
+/// Per-bucket upstream concurrency limit (YAML format)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BulkheadConfig {
+    /// Maximum concurrent upstream requests for the bucket
+    pub max_in_flight: usize,
+    /// Separate limit for write (multipart upload) requests; unset means
+    /// writes share `max_in_flight` with reads
+    #[serde(default)]
+    pub max_uploads_in_flight: Option<usize>,
+}
+
+impl BulkheadConfig {
+    /// Validate the bulkhead size
+    pub fn validate(&self, bucket_name: &str) -> Result<(), String> {
+        if self.max_in_flight == 0 {
+            return Err(format!(
+                "Bucket '{}': s3.bulkhead.max_in_flight must be greater than 0",
+                bucket_name
+            ));
+        }
+        if self.max_uploads_in_flight == Some(0) {
+            return Err(format!(
+                "Bucket '{}': s3.bulkhead.max_uploads_in_flight must be greater than 0",
+                bucket_name
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bulkhead_config() {
+        let config: BulkheadConfig = serde_yaml::from_str("max_in_flight: 32").unwrap();
+        assert_eq!(config.max_in_flight, 32);
+        assert_eq!(config.max_uploads_in_flight, None);
+        assert!(config.validate("media").is_ok());
+
+        let config = BulkheadConfig {
+            max_in_flight: 0,
+            max_uploads_in_flight: None,
+        };
+        assert!(config.validate("media").is_err());
+        let config = BulkheadConfig {
+            max_in_flight: 32,
+            max_uploads_in_flight: Some(0),
+        };
+        assert!(config.validate("media").is_err());
+        assert!(serde_yaml::from_str::<BulkheadConfig>("{}").is_err());
+    }
+}
@@ -0,0 +1,137 @@
+//! Per-request cache override header.
+//!
+//! Trusted internal clients can send `X-Yat-Cache: bypass|refresh|only-if-cached`
+//! to change how the cache treats a single request, e.g. while chasing a
+//! stale-content report. A client is trusted when it presents the configured
+//! token in `X-Yat-Cache-Token`, or (with `admin_claims: true`) when its JWT
+//! satisfies `jwt.admin_claims`. The header is ignored for everyone else.
+
+use serde::{Deserialize, Serialize};
+
+use crate::security::constant_time_eq;
+
+/// Cache override header configuration
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CacheOverrideConfig {
+    /// Honor the override header from trusted clients (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Shared secret expected in `X-Yat-Cache-Token` (supports `${ENV_VAR}`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token: Option<String>,
+    /// Trust requests whose JWT satisfies `jwt.admin_claims` (default: false)
+    #[serde(default)]
+    pub admin_claims: bool,
+}
+
+impl CacheOverrideConfig {
+    /// Validate that an enabled override has a way to establish trust
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.token.as_ref().is_some_and(|t| t.trim().is_empty()) {
+            return Err("server.cache_override.token cannot be empty".to_string());
+        }
+        if self.token.is_none() && !self.admin_claims {
+            return Err(
+                "server.cache_override requires a token or admin_claims when enabled".to_string(),
+            );
+        }
+        Ok(())
+    }
+
+    /// Check a presented `X-Yat-Cache-Token` against the configured token
+    pub fn token_matches(&self, presented: Option<&str>) -> bool {
+        match (&self.token, presented) {
+            (Some(expected), Some(token)) => constant_time_eq(token.trim(), expected),
+            _ => false,
+        }
+    }
+}
+
+/// Cache behavior requested with `X-Yat-Cache`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheOverrideMode {
+    /// Skip the cache lookup and do not store the response
+    Bypass,
+    /// Skip the cache lookup and store the fresh response
+    Refresh,
+    /// Answer from cache or fail with 504, never contacting the origin
+    OnlyIfCached,
+}
+
+impl CacheOverrideMode {
+    /// Parse a header value (case-insensitive)
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.eq_ignore_ascii_case("bypass") {
+            Some(Self::Bypass)
+        } else if value.eq_ignore_ascii_case("refresh") {
+            Some(Self::Refresh)
+        } else if value.eq_ignore_ascii_case("only-if-cached") {
+            Some(Self::OnlyIfCached)
+        } else {
+            None
+        }
+    }
+
+    /// Label used in logs and metrics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Bypass => "bypass",
+            Self::Refresh => "refresh",
+            Self::OnlyIfCached => "only-if-cached",
+        }
+    }
+
+    /// Whether the cache lookup is skipped
+    pub fn skips_lookup(&self) -> bool {
+        matches!(self, Self::Bypass | Self::Refresh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_override_config_validation() {
+        let config: CacheOverrideConfig = serde_yaml::from_str("{}").unwrap();
+        assert!(!config.enabled);
+        assert!(config.validate().is_ok());
+
+        let config: CacheOverrideConfig =
+            serde_yaml::from_str("enabled: true\ntoken: s3cret").unwrap();
+        assert!(config.validate().is_ok());
+
+        let no_trust: CacheOverrideConfig = serde_yaml::from_str("enabled: true").unwrap();
+        assert!(no_trust.validate().is_err());
+
+        let blank: CacheOverrideConfig =
+            serde_yaml::from_str("enabled: true\ntoken: \" \"").unwrap();
+        assert!(blank.validate().is_err());
+
+        assert!(config.token_matches(Some("s3cret")));
+        assert!(!config.token_matches(Some("guess")));
+        assert!(!config.token_matches(None));
+    }
+
+    #[test]
+    fn test_cache_override_mode_parse() {
+        assert_eq!(
+            CacheOverrideMode::parse("Bypass"),
+            Some(CacheOverrideMode::Bypass)
+        );
+        assert_eq!(
+            CacheOverrideMode::parse(" refresh "),
+            Some(CacheOverrideMode::Refresh)
+        );
+        assert_eq!(
+            CacheOverrideMode::parse("only-if-cached"),
+            Some(CacheOverrideMode::OnlyIfCached)
+        );
+        assert_eq!(CacheOverrideMode::parse("no-cache"), None);
+        assert!(!CacheOverrideMode::OnlyIfCached.skips_lookup());
+    }
+}
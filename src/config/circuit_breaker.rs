@@ -1,14 +1,19 @@
 //! Circuit breaker configuration for S3 backend resilience.
 //!
 //! This module defines the YAML configuration format for circuit breakers,
-//! which protect against cascading failures when S3 backends become unavailable.
+//! which protect against cascading failures when S3 backends become unavailable,
+//! which upstream outcomes count as breaker failures, and the background origin
+//! probe that tests half-open breakers without waiting for client traffic.
 //!
 //! Default values for thresholds and timeouts are sourced from `crate::constants`.
 
 use serde::{Deserialize, Serialize};
 
+use crate::circuit_breaker::{TripPolicy, UpstreamOutcome};
 use crate::constants::{
-    DEFAULT_CB_TIMEOUT_SECS, DEFAULT_FAILURE_THRESHOLD, DEFAULT_HALF_OPEN_MAX_REQUESTS,
+    DEFAULT_CB_ERROR_RATE_THRESHOLD, DEFAULT_CB_MINIMUM_REQUESTS, DEFAULT_CB_TIMEOUT_SECS,
+    DEFAULT_CB_WINDOW_SIZE, DEFAULT_FAILURE_THRESHOLD, DEFAULT_HALF_OPEN_MAX_REQUESTS,
+    DEFAULT_ORIGIN_PROBE_INTERVAL_SECS, DEFAULT_ORIGIN_PROBE_TIMEOUT_SECS,
     DEFAULT_SUCCESS_THRESHOLD,
 };
 
@@ -28,6 +33,42 @@ fn default_half_open_max_requests() -> u32 {
     DEFAULT_HALF_OPEN_MAX_REQUESTS
 }
 
+fn default_window_size() -> u32 {
+    DEFAULT_CB_WINDOW_SIZE
+}
+
+fn default_error_rate_threshold() -> f64 {
+    DEFAULT_CB_ERROR_RATE_THRESHOLD
+}
+
+fn default_minimum_requests() -> u32 {
+    DEFAULT_CB_MINIMUM_REQUESTS
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_probe_interval_secs() -> u64 {
+    DEFAULT_ORIGIN_PROBE_INTERVAL_SECS
+}
+
+fn default_probe_timeout_secs() -> u64 {
+    DEFAULT_ORIGIN_PROBE_TIMEOUT_SECS
+}
+
+/// How a closed circuit decides to open
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitBreakerMode {
+    /// Open after `failure_threshold` consecutive failures (default)
+    #[default]
+    Consecutive,
+    /// Open when the failure ratio over the last `window_size` requests
+    /// reaches `error_rate_threshold`
+    ErrorRate,
+}
+
 /// Circuit breaker configuration (YAML format)
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CircuitBreakerConfigYaml {
@@ -43,6 +84,21 @@ pub struct CircuitBreakerConfigYaml {
     /// Max concurrent test requests in half-open state
     #[serde(default = "default_half_open_max_requests")]
     pub half_open_max_requests: u32,
+    /// Trip policy: consecutive failures or error rate (default: consecutive)
+    #[serde(default)]
+    pub mode: CircuitBreakerMode,
+    /// Requests in the error-rate sliding window (default: 100)
+    #[serde(default = "default_window_size")]
+    pub window_size: u32,
+    /// Failure ratio that opens the circuit in error-rate mode (default: 0.5)
+    #[serde(default = "default_error_rate_threshold")]
+    pub error_rate_threshold: f64,
+    /// Requests needed in the window before the ratio is evaluated (default: 20)
+    #[serde(default = "default_minimum_requests")]
+    pub minimum_requests: u32,
+    /// Which upstream outcomes count as failures
+    #[serde(default)]
+    pub failure_classification: FailureClassificationConfig,
 }
 
 impl CircuitBreakerConfigYaml {
@@ -53,10 +109,133 @@ impl CircuitBreakerConfigYaml {
             success_threshold: self.success_threshold,
             timeout_duration: std::time::Duration::from_secs(self.timeout_seconds),
             half_open_max_requests: self.half_open_max_requests,
+            policy: match self.mode {
+                CircuitBreakerMode::Consecutive => TripPolicy::ConsecutiveFailures,
+                CircuitBreakerMode::ErrorRate => TripPolicy::ErrorRate {
+                    window_size: self.window_size,
+                    threshold: self.error_rate_threshold,
+                    minimum_requests: self.minimum_requests,
+                },
+            },
+        }
+    }
+
+    /// Validate the error-rate window settings
+    pub fn validate(&self, bucket_name: &str) -> Result<(), String> {
+        if self.mode != CircuitBreakerMode::ErrorRate {
+            return Ok(());
+        }
+        let field = format!("Bucket '{}': s3.circuit_breaker", bucket_name);
+        if self.window_size == 0 {
+            return Err(format!("{}.window_size must be greater than 0", field));
+        }
+        if !(self.error_rate_threshold > 0.0 && self.error_rate_threshold <= 1.0) {
+            return Err(format!(
+                "{}.error_rate_threshold must be between 0 (exclusive) and 1",
+                field
+            ));
+        }
+        if self.minimum_requests == 0 || self.minimum_requests > self.window_size {
+            return Err(format!(
+                "{}.minimum_requests must be between 1 and window_size",
+                field
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Upstream outcomes that count as circuit breaker failures (YAML format)
+///
+/// Connect errors, timeouts and 5xx responses count by default. Extra status
+/// codes (e.g. 429 throttling) can be added with `status_codes`. Responses
+/// below 400 count as successes; anything else leaves the breaker untouched.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FailureClassificationConfig {
+    /// Count connection refused/reset, DNS and TLS failures (default: true)
+    #[serde(default = "default_true")]
+    pub connect_errors: bool,
+    /// Count connect, read and write timeouts (default: true)
+    #[serde(default = "default_true")]
+    pub timeouts: bool,
+    /// Count 5xx responses from the origin (default: true)
+    #[serde(default = "default_true")]
+    pub server_errors: bool,
+    /// Additional status codes to count as failures (default: none)
+    #[serde(default)]
+    pub status_codes: Vec<u16>,
+}
+
+impl Default for FailureClassificationConfig {
+    fn default() -> Self {
+        Self {
+            connect_errors: true,
+            timeouts: true,
+            server_errors: true,
+            status_codes: Vec::new(),
+        }
+    }
+}
+
+impl FailureClassificationConfig {
+    /// Whether this outcome should be recorded as a breaker failure
+    pub fn is_failure(&self, outcome: UpstreamOutcome) -> bool {
+        match outcome {
+            UpstreamOutcome::ConnectError => self.connect_errors,
+            UpstreamOutcome::Timeout => self.timeouts,
+            UpstreamOutcome::Status(status) => {
+                (status >= 500 && self.server_errors) || self.status_codes.contains(&status)
+            }
+        }
+    }
+
+    /// Whether this outcome should be recorded as a breaker success
+    pub fn is_success(&self, outcome: UpstreamOutcome) -> bool {
+        matches!(outcome, UpstreamOutcome::Status(status) if status < 400)
+            && !self.is_failure(outcome)
+    }
+}
+
+/// Background origin probe configuration (YAML format)
+///
+/// Breakers that are due for a half-open test get a synthetic `HEAD` on their
+/// bucket instead of waiting for a client request to be sacrificed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OriginProbeConfig {
+    /// Probe open/half-open breakers in the background (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Seconds between probe rounds (default: 5)
+    #[serde(default = "default_probe_interval_secs")]
+    pub interval_secs: u64,
+    /// Timeout for a single probe in seconds (default: 2)
+    #[serde(default = "default_probe_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for OriginProbeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: default_probe_interval_secs(),
+            timeout_secs: default_probe_timeout_secs(),
         }
     }
 }
 
+impl OriginProbeConfig {
+    /// Validate probe interval and timeout
+    pub fn validate(&self) -> Result<(), String> {
+        if self.enabled && self.interval_secs == 0 {
+            return Err("server.origin_probe.interval_secs must be greater than 0".to_string());
+        }
+        if self.enabled && self.timeout_secs == 0 {
+            return Err("server.origin_probe.timeout_secs must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -115,6 +294,11 @@ timeout_seconds: 120
             success_threshold: 3,
             timeout_seconds: 120,
             half_open_max_requests: 2,
+            mode: CircuitBreakerMode::Consecutive,
+            window_size: DEFAULT_CB_WINDOW_SIZE,
+            error_rate_threshold: DEFAULT_CB_ERROR_RATE_THRESHOLD,
+            minimum_requests: DEFAULT_CB_MINIMUM_REQUESTS,
+            failure_classification: FailureClassificationConfig::default(),
         };
 
         let cb_config = yaml_config.to_circuit_breaker_config();
@@ -126,5 +310,78 @@ timeout_seconds: 120
             std::time::Duration::from_secs(120)
         );
         assert_eq!(cb_config.half_open_max_requests, 2);
+        assert_eq!(cb_config.policy, TripPolicy::ConsecutiveFailures);
+    }
+
+    #[test]
+    fn test_circuit_breaker_error_rate_mode() {
+        let yaml = r#"
+mode: error_rate
+window_size: 50
+error_rate_threshold: 0.25
+"#;
+        let config: CircuitBreakerConfigYaml = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.validate("media").is_ok());
+        assert_eq!(
+            config.to_circuit_breaker_config().policy,
+            TripPolicy::ErrorRate {
+                window_size: 50,
+                threshold: 0.25,
+                minimum_requests: DEFAULT_CB_MINIMUM_REQUESTS,
+            }
+        );
+
+        let config: CircuitBreakerConfigYaml =
+            serde_yaml::from_str("mode: error_rate\nwindow_size: 10").unwrap();
+        assert!(config.validate("media").is_err()); // minimum_requests > window_size
+
+        let config: CircuitBreakerConfigYaml =
+            serde_yaml::from_str("mode: error_rate\nerror_rate_threshold: 1.5").unwrap();
+        assert!(config.validate("media").is_err());
+    }
+
+    #[test]
+    fn test_failure_classification() {
+        let defaults = FailureClassificationConfig::default();
+        assert!(defaults.is_failure(UpstreamOutcome::ConnectError));
+        assert!(defaults.is_failure(UpstreamOutcome::Timeout));
+        assert!(defaults.is_failure(UpstreamOutcome::Status(503)));
+        assert!(!defaults.is_failure(UpstreamOutcome::Status(404)));
+        assert!(defaults.is_success(UpstreamOutcome::Status(304)));
+        assert!(!defaults.is_success(UpstreamOutcome::Status(404)));
+
+        let yaml = r#"
+failure_classification:
+  timeouts: false
+  status_codes: [429]
+"#;
+        let config: CircuitBreakerConfigYaml = serde_yaml::from_str(yaml).unwrap();
+        let classification = config.failure_classification;
+        assert!(classification.connect_errors);
+        assert!(!classification.is_failure(UpstreamOutcome::Timeout));
+        assert!(!classification.is_success(UpstreamOutcome::Timeout));
+        assert!(classification.is_failure(UpstreamOutcome::Status(429)));
+        assert!(classification.is_failure(UpstreamOutcome::Status(500)));
+    }
+
+    #[test]
+    fn test_origin_probe_config() {
+        let config: OriginProbeConfig = serde_yaml::from_str("enabled: true").unwrap();
+        assert_eq!(config.interval_secs, DEFAULT_ORIGIN_PROBE_INTERVAL_SECS);
+        assert_eq!(config.timeout_secs, DEFAULT_ORIGIN_PROBE_TIMEOUT_SECS);
+        assert!(config.validate().is_ok());
+
+        let config = OriginProbeConfig {
+            enabled: true,
+            interval_secs: 0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+        assert!(OriginProbeConfig {
+            enabled: false,
+            ..config
+        }
+        .validate()
+        .is_ok());
     }
 }
@@ -0,0 +1,193 @@
+//! Content-Type override configuration.
+//!
+//! Objects uploaded without explicit metadata come back from S3 as
+//! `binary/octet-stream` (or with no Content-Type at all), which breaks inline
+//! rendering of images, video and web assets. A bucket's `content_type`
+//! section lets the proxy fix the type from the object's file extension or,
+//! when the body is at hand, from its leading magic bytes.
+//!
+//! Default values are sourced from `crate::constants`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::constants::DEFAULT_GENERIC_CONTENT_TYPES;
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_generic_types() -> Vec<String> {
+    DEFAULT_GENERIC_CONTENT_TYPES
+        .iter()
+        .map(|s| s.to_string())
+        .collect()
+}
+
+/// Per-bucket Content-Type override rules (YAML format)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ContentTypeConfig {
+    /// Extension -> Content-Type rules, checked before the built-in table
+    /// (keys are case-insensitive, with or without a leading dot)
+    #[serde(default)]
+    pub extensions: HashMap<String, String>,
+    /// Fall back to the built-in extension table (default: true)
+    #[serde(default = "default_true")]
+    pub builtin_extensions: bool,
+    /// Detect the type from magic bytes when the body is available (default: true)
+    #[serde(default = "default_true")]
+    pub sniff: bool,
+    /// Upstream types treated as "unknown" and replaced
+    /// (default: binary/octet-stream, application/octet-stream)
+    #[serde(default = "default_generic_types")]
+    pub generic_types: Vec<String>,
+    /// Apply extension rules even when upstream sent a specific type (default: false)
+    #[serde(default)]
+    pub always_override: bool,
+}
+
+impl Default for ContentTypeConfig {
+    fn default() -> Self {
+        Self {
+            extensions: HashMap::new(),
+            builtin_extensions: default_true(),
+            sniff: default_true(),
+            generic_types: default_generic_types(),
+            always_override: false,
+        }
+    }
+}
+
+impl ContentTypeConfig {
+    /// Validate the Content-Type rules for the given bucket.
+    pub fn validate(&self, bucket_name: &str) -> Result<(), String> {
+        for (extension, content_type) in &self.extensions {
+            if normalize_extension(extension).is_empty() {
+                return Err(format!(
+                    "Bucket '{}': content_type.extensions has an empty extension",
+                    bucket_name
+                ));
+            }
+            if !is_valid_media_type(content_type) {
+                return Err(format!(
+                    "Bucket '{}': content_type.extensions['{}'] has invalid media type '{}'",
+                    bucket_name, extension, content_type
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Check if an upstream Content-Type should be replaced
+    ///
+    /// Missing and generic types are always replaceable; specific types only
+    /// when `always_override` is set.
+    pub fn is_replaceable(&self, upstream: Option<&str>) -> bool {
+        self.always_override || self.is_generic(upstream)
+    }
+
+    /// Check if an upstream Content-Type is missing or generic
+    pub fn is_generic(&self, upstream: Option<&str>) -> bool {
+        match upstream.map(media_type) {
+            None | Some("") => true,
+            Some(media) => self
+                .generic_types
+                .iter()
+                .any(|generic| generic.eq_ignore_ascii_case(media)),
+        }
+    }
+
+    /// Look up a user-defined rule for `extension`
+    pub fn extension_rule(&self, extension: &str) -> Option<&str> {
+        self.extensions
+            .iter()
+            .find(|(ext, _)| normalize_extension(ext).eq_ignore_ascii_case(extension))
+            .map(|(_, content_type)| content_type.as_str())
+    }
+}
+
+/// Strip parameters (`; charset=...`) and whitespace from a Content-Type
+fn media_type(content_type: &str) -> &str {
+    content_type.split(';').next().unwrap_or("").trim()
+}
+
+/// Extension key without leading dots, e.g. ".JPG" -> "JPG"
+fn normalize_extension(extension: &str) -> &str {
+    extension.trim().trim_start_matches('.')
+}
+
+/// Minimal `type/subtype` check so typos fail at load time, not per request
+fn is_valid_media_type(content_type: &str) -> bool {
+    let media = media_type(content_type);
+    match media.split_once('/') {
+        Some((kind, subtype)) => {
+            !kind.is_empty()
+                && !subtype.is_empty()
+                && !media.contains(char::is_whitespace)
+                && !content_type.contains(['\r', '\n'])
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_content_type_config_defaults() {
+        let config: ContentTypeConfig = serde_yaml::from_str("{}").unwrap();
+        assert!(config.builtin_extensions);
+        assert!(config.sniff);
+        assert!(!config.always_override);
+        assert!(config
+            .generic_types
+            .contains(&"binary/octet-stream".to_string()));
+    }
+
+    #[test]
+    fn test_content_type_config_validate() {
+        let mut config = ContentTypeConfig::default();
+        config
+            .extensions
+            .insert(".glb".to_string(), "model/gltf-binary".to_string());
+        assert!(config.validate("assets").is_ok());
+
+        config
+            .extensions
+            .insert("bad".to_string(), "not-a-type".to_string());
+        assert!(config.validate("assets").unwrap_err().contains("bad"));
+
+        let mut config = ContentTypeConfig::default();
+        config
+            .extensions
+            .insert(".".to_string(), "text/plain".to_string());
+        assert!(config.validate("assets").is_err());
+    }
+
+    #[test]
+    fn test_content_type_config_replaceable() {
+        let config = ContentTypeConfig::default();
+        assert!(config.is_replaceable(None));
+        assert!(config.is_replaceable(Some("binary/octet-stream")));
+        assert!(config.is_replaceable(Some("Application/Octet-Stream; charset=binary")));
+        assert!(!config.is_replaceable(Some("image/png")));
+
+        let config = ContentTypeConfig {
+            always_override: true,
+            ..Default::default()
+        };
+        assert!(config.is_replaceable(Some("image/png")));
+        assert!(!config.is_generic(Some("image/png")));
+    }
+
+    #[test]
+    fn test_content_type_config_extension_rule() {
+        let mut config = ContentTypeConfig::default();
+        config
+            .extensions
+            .insert(".GLB".to_string(), "model/gltf-binary".to_string());
+        assert_eq!(config.extension_rule("glb"), Some("model/gltf-binary"));
+        assert_eq!(config.extension_rule("gltf"), None);
+    }
+}
@@ -0,0 +1,165 @@
+//! Per-bucket CORS policy.
+//!
+//! Browsers send an `OPTIONS` preflight before cross-origin requests that
+//! carry credentials or non-simple headers. A bucket's `cors` section decides
+//! which origins get `Access-Control-Allow-*` headers, both on the preflight
+//! and on the object responses that follow. Buckets without a `cors` section
+//! keep the legacy preflight response (methods and headers, no origin grant).
+//!
+//! Default values are sourced from `crate::constants`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{DEFAULT_CORS_ALLOWED_HEADERS, DEFAULT_CORS_MAX_AGE_SECS};
+
+fn default_allowed_headers() -> Vec<String> {
+    DEFAULT_CORS_ALLOWED_HEADERS
+        .iter()
+        .map(|h| h.to_string())
+        .collect()
+}
+
+fn default_max_age_secs() -> u64 {
+    DEFAULT_CORS_MAX_AGE_SECS
+}
+
+/// Per-bucket CORS configuration (YAML format)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CorsConfig {
+    /// Origins granted access (`https://app.example.com`), or `*` for any origin
+    #[serde(default)]
+    pub allowed_origins: Vec<String>,
+    /// Request headers allowed in cross-origin requests
+    /// (default: Authorization, Content-Type, Range)
+    #[serde(default = "default_allowed_headers")]
+    pub allowed_headers: Vec<String>,
+    /// Response headers exposed to scripts (default: none)
+    #[serde(default)]
+    pub expose_headers: Vec<String>,
+    /// How long browsers may cache a preflight response in seconds (default: 86400)
+    #[serde(default = "default_max_age_secs")]
+    pub max_age_secs: u64,
+    /// Allow cookies and Authorization headers on cross-origin requests (default: false)
+    #[serde(default)]
+    pub allow_credentials: bool,
+}
+
+impl Default for CorsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_origins: Vec::new(),
+            allowed_headers: default_allowed_headers(),
+            expose_headers: Vec::new(),
+            max_age_secs: default_max_age_secs(),
+            allow_credentials: false,
+        }
+    }
+}
+
+impl CorsConfig {
+    /// Validate the CORS configuration for the given bucket
+    pub fn validate(&self, bucket_name: &str) -> Result<(), String> {
+        if self.allowed_origins.is_empty() {
+            return Err(format!(
+                "Bucket '{}': cors.allowed_origins must not be empty",
+                bucket_name
+            ));
+        }
+        for origin in &self.allowed_origins {
+            let valid = origin == "*"
+                || ((origin.starts_with("http://") || origin.starts_with("https://"))
+                    && !origin.ends_with('/'));
+            if !valid {
+                return Err(format!(
+                    "Bucket '{}': cors.allowed_origins entry '{}' must be '*' or an origin \
+                     like 'https://app.example.com'",
+                    bucket_name, origin
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Value for `Access-Control-Allow-Origin` if `origin` is granted access
+    ///
+    /// A wildcard policy answers `*`, except with credentials, where browsers
+    /// require the exact origin to be echoed back.
+    pub fn allow_origin(&self, origin: &str) -> Option<String> {
+        if origin.is_empty() || origin == "null" {
+            return None;
+        }
+        if self.allowed_origins.iter().any(|o| o == "*") {
+            return Some(if self.allow_credentials {
+                origin.to_string()
+            } else {
+                "*".to_string()
+            });
+        }
+        self.allowed_origins
+            .iter()
+            .find(|o| o.eq_ignore_ascii_case(origin))
+            .map(|_| origin.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cors_config_defaults() {
+        let config: CorsConfig =
+            serde_yaml::from_str("allowed_origins: [\"https://app.example.com\"]").unwrap();
+        assert_eq!(
+            config.allowed_headers,
+            vec!["Authorization", "Content-Type", "Range"]
+        );
+        assert!(config.expose_headers.is_empty());
+        assert_eq!(config.max_age_secs, 86400);
+        assert!(!config.allow_credentials);
+        assert!(config.validate("assets").is_ok());
+    }
+
+    #[test]
+    fn test_cors_config_validate() {
+        assert!(CorsConfig::default().validate("assets").is_err());
+        for origin in ["example.com", "https://example.com/", "*.example.com"] {
+            let config = CorsConfig {
+                allowed_origins: vec![origin.to_string()],
+                ..Default::default()
+            };
+            assert!(config.validate("assets").is_err(), "{}", origin);
+        }
+    }
+
+    #[test]
+    fn test_cors_allow_origin() {
+        let config = CorsConfig {
+            allowed_origins: vec!["https://app.example.com".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            config.allow_origin("https://app.example.com").as_deref(),
+            Some("https://app.example.com")
+        );
+        assert_eq!(config.allow_origin("https://evil.example"), None);
+        assert_eq!(config.allow_origin("null"), None);
+
+        let wildcard = CorsConfig {
+            allowed_origins: vec!["*".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            wildcard.allow_origin("https://any.example").as_deref(),
+            Some("*")
+        );
+        let credentialed = CorsConfig {
+            allow_credentials: true,
+            ..wildcard
+        };
+        assert_eq!(
+            credentialed.allow_origin("https://any.example").as_deref(),
+            Some("https://any.example")
+        );
+    }
+}
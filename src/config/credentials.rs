@@ -0,0 +1,273 @@
+//! Where a bucket's upstream S3 credentials come from.
+//!
+//! By default requests are signed with the static `access_key`/`secret_key`
+//! from the YAML. On AWS the proxy can instead use the temporary credentials
+//! of the role it runs as: the EC2 instance profile (IMDSv2), the ECS task
+//! role, or an EKS service account (IRSA web identity). Temporary credentials
+//! expire, so they are refreshed in the background ahead of expiry and no
+//! long-lived secret has to be written into the config.
+//!
+//! With `assume_role_arn`, the credentials from the source (static keys
+//! included) are only used to call STS `AssumeRole`; upstream requests are
+//! signed with the assumed role's session. This is how a bucket in another
+//! AWS account is reached.
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{
+    DEFAULT_ASSUME_ROLE_DURATION_SECS, DEFAULT_S3_CREDENTIALS_REFRESH_BEFORE_EXPIRY_SECS,
+    MAX_ASSUME_ROLE_DURATION_SECS, MIN_ASSUME_ROLE_DURATION_SECS,
+};
+
+/// Credential source for upstream requests
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CredentialSource {
+    /// `access_key`/`secret_key` from the config
+    #[default]
+    Static,
+    /// EC2 instance profile via IMDSv2
+    Instance,
+    /// ECS task role (`AWS_CONTAINER_CREDENTIALS_*`)
+    Ecs,
+    /// STS AssumeRoleWithWebIdentity (EKS IRSA, `AWS_WEB_IDENTITY_TOKEN_FILE`)
+    WebIdentity,
+    /// Web identity, then ECS, then instance, whichever the environment provides
+    Auto,
+}
+
+impl CredentialSource {
+    /// Label used in logs and errors
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CredentialSource::Static => "static",
+            CredentialSource::Instance => "instance",
+            CredentialSource::Ecs => "ecs",
+            CredentialSource::WebIdentity => "web_identity",
+            CredentialSource::Auto => "auto",
+        }
+    }
+}
+
+fn default_refresh_before_expiry_secs() -> u64 {
+    DEFAULT_S3_CREDENTIALS_REFRESH_BEFORE_EXPIRY_SECS
+}
+
+fn default_assume_role_duration_secs() -> u64 {
+    DEFAULT_ASSUME_ROLE_DURATION_SECS
+}
+
+/// Upstream credential provider settings (YAML format)
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct S3CredentialsConfig {
+    /// Where credentials come from (default: static)
+    #[serde(default)]
+    pub source: CredentialSource,
+    /// Refresh temporary credentials this many seconds before they expire
+    #[serde(default = "default_refresh_before_expiry_secs")]
+    pub refresh_before_expiry_secs: u64,
+    /// Instance metadata endpoint (default: `http://169.254.169.254`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub imds_endpoint: Option<String>,
+    /// Role assumed with the web identity token (default: `$AWS_ROLE_ARN`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role_arn: Option<String>,
+    /// Web identity token file (default: `$AWS_WEB_IDENTITY_TOKEN_FILE`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub web_identity_token_file: Option<String>,
+    /// STS endpoint for web identity and AssumeRole (default: `https://sts.amazonaws.com`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sts_endpoint: Option<String>,
+    /// Role assumed with the source credentials to sign upstream requests
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assume_role_arn: Option<String>,
+    /// External ID required by the role's trust policy
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    /// Session name for assumed roles (default: `$AWS_ROLE_SESSION_NAME` or `yatagarasu`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub role_session_name: Option<String>,
+    /// Lifetime requested for AssumeRole sessions (default: 3600)
+    #[serde(default = "default_assume_role_duration_secs")]
+    pub assume_role_duration_secs: u64,
+}
+
+impl Default for S3CredentialsConfig {
+    fn default() -> Self {
+        Self {
+            source: CredentialSource::default(),
+            refresh_before_expiry_secs: default_refresh_before_expiry_secs(),
+            imds_endpoint: None,
+            role_arn: None,
+            web_identity_token_file: None,
+            sts_endpoint: None,
+            assume_role_arn: None,
+            external_id: None,
+            role_session_name: None,
+            assume_role_duration_secs: default_assume_role_duration_secs(),
+        }
+    }
+}
+
+impl S3CredentialsConfig {
+    /// Whether credentials are fetched at runtime instead of read from the config
+    pub fn is_dynamic(&self) -> bool {
+        self.source != CredentialSource::Static
+    }
+
+    /// Whether upstream requests are signed with credentials fetched at runtime
+    ///
+    /// True for dynamic sources and for static keys exchanged via AssumeRole.
+    pub fn uses_provider(&self) -> bool {
+        self.is_dynamic() || self.assume_role_arn.is_some()
+    }
+
+    /// Validate the provider settings
+    pub fn validate(&self, bucket_name: &str) -> Result<(), String> {
+        if self.refresh_before_expiry_secs == 0 {
+            return Err(format!(
+                "Bucket '{}': s3.credentials.refresh_before_expiry_secs must be greater than 0",
+                bucket_name
+            ));
+        }
+        let fields = [
+            ("imds_endpoint", &self.imds_endpoint),
+            ("role_arn", &self.role_arn),
+            ("web_identity_token_file", &self.web_identity_token_file),
+            ("sts_endpoint", &self.sts_endpoint),
+            ("assume_role_arn", &self.assume_role_arn),
+            ("external_id", &self.external_id),
+            ("role_session_name", &self.role_session_name),
+        ];
+        for (name, value) in fields {
+            if value.as_ref().is_some_and(|v| v.trim().is_empty()) {
+                return Err(format!(
+                    "Bucket '{}': s3.credentials.{} cannot be empty",
+                    bucket_name, name
+                ));
+            }
+        }
+        let instance = matches!(
+            self.source,
+            CredentialSource::Instance | CredentialSource::Auto
+        );
+        if self.imds_endpoint.is_some() && !instance {
+            return Err(format!(
+                "Bucket '{}': s3.credentials.imds_endpoint requires source 'instance' or 'auto'",
+                bucket_name
+            ));
+        }
+        let web_identity = matches!(
+            self.source,
+            CredentialSource::WebIdentity | CredentialSource::Auto
+        );
+        if (self.role_arn.is_some() || self.web_identity_token_file.is_some()) && !web_identity {
+            return Err(format!(
+                "Bucket '{}': s3.credentials role_arn and web_identity_token_file \
+                 require source 'web_identity' or 'auto'",
+                bucket_name
+            ));
+        }
+        if self.sts_endpoint.is_some() && !web_identity && self.assume_role_arn.is_none() {
+            return Err(format!(
+                "Bucket '{}': s3.credentials.sts_endpoint requires source 'web_identity' or \
+                 'auto', or assume_role_arn",
+                bucket_name
+            ));
+        }
+        if let Some(arn) = &self.assume_role_arn {
+            if !arn.starts_with("arn:") || !arn.contains(":role/") {
+                return Err(format!(
+                    "Bucket '{}': s3.credentials.assume_role_arn '{}' is not a role ARN",
+                    bucket_name, arn
+                ));
+            }
+            let duration = self.assume_role_duration_secs;
+            if !(MIN_ASSUME_ROLE_DURATION_SECS..=MAX_ASSUME_ROLE_DURATION_SECS).contains(&duration)
+            {
+                return Err(format!(
+                    "Bucket '{}': s3.credentials.assume_role_duration_secs must be between {} and {}",
+                    bucket_name, MIN_ASSUME_ROLE_DURATION_SECS, MAX_ASSUME_ROLE_DURATION_SECS
+                ));
+            }
+            if self.refresh_before_expiry_secs >= duration {
+                return Err(format!(
+                    "Bucket '{}': s3.credentials.refresh_before_expiry_secs must be less than \
+                     assume_role_duration_secs",
+                    bucket_name
+                ));
+            }
+        } else if self.external_id.is_some() {
+            return Err(format!(
+                "Bucket '{}': s3.credentials.external_id requires assume_role_arn",
+                bucket_name
+            ));
+        }
+        if let Some(name) = &self.role_session_name {
+            let valid = (2..=64).contains(&name.len())
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || "_+=,.@-".contains(c));
+            if !valid {
+                return Err(format!(
+                    "Bucket '{}': s3.credentials.role_session_name '{}' must be 2-64 characters \
+                     of letters, digits and _+=,.@-",
+                    bucket_name, name
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_credentials_config() {
+        let config: S3CredentialsConfig = serde_yaml::from_str("{}").unwrap();
+        assert_eq!(config.source, CredentialSource::Static);
+        assert!(!config.is_dynamic());
+        assert_eq!(config.refresh_before_expiry_secs, 300);
+        assert!(config.validate("media").is_ok());
+
+        let yaml = "source: web_identity\nrole_arn: arn:aws:iam::123456789012:role/proxy";
+        let config: S3CredentialsConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.source, CredentialSource::WebIdentity);
+        assert!(config.is_dynamic());
+        assert!(config.validate("media").is_ok());
+
+        let config: S3CredentialsConfig =
+            serde_yaml::from_str("source: ecs\nimds_endpoint: http://[fd00:ec2::254]").unwrap();
+        assert!(config.validate("media").is_err());
+        let config: S3CredentialsConfig =
+            serde_yaml::from_str("source: instance\nrefresh_before_expiry_secs: 0").unwrap();
+        assert!(config.validate("media").is_err());
+        assert!(serde_yaml::from_str::<S3CredentialsConfig>("source: vault").is_err());
+    }
+
+    #[test]
+    fn test_assume_role_config() {
+        let yaml = "assume_role_arn: arn:aws:iam::210987654321:role/media-reader\n\
+                    external_id: tenant-42";
+        let config: S3CredentialsConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(!config.is_dynamic());
+        assert!(config.uses_provider());
+        assert_eq!(config.assume_role_duration_secs, 3600);
+        assert!(config.validate("media").is_ok());
+
+        for invalid in [
+            "assume_role_arn: media-reader",
+            "assume_role_arn: arn:aws:iam::210987654321:role/r\nassume_role_duration_secs: 60",
+            "assume_role_arn: arn:aws:iam::210987654321:role/r\nassume_role_duration_secs: 900\n\
+             refresh_before_expiry_secs: 900",
+            "external_id: tenant-42",
+            "sts_endpoint: https://sts.eu-west-1.amazonaws.com",
+            "assume_role_arn: arn:aws:iam::210987654321:role/r\nrole_session_name: \"a b\"",
+        ] {
+            let config: S3CredentialsConfig = serde_yaml::from_str(invalid).unwrap();
+            assert!(config.validate("media").is_err(), "{}", invalid);
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! Serve-from-cache-only degraded mode.
+//!
+//! When every origin of a bucket is down (its circuit breaker, or the
+//! breakers of all its replicas, are open) requests normally get a 503. With
+//! degraded mode enabled the proxy answers from cache instead: fresh entries
+//! as usual, expired entries with `Warning: 110 - "Response is Stale"` up to
+//! `max_stale_secs` past expiry. Cache misses still get the 503.
+//!
+//! Default values are sourced from `crate::constants`.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::constants::DEFAULT_DEGRADED_MAX_STALE_SECS;
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_serve_stale() -> bool {
+    true
+}
+
+fn default_max_stale_secs() -> u64 {
+    DEFAULT_DEGRADED_MAX_STALE_SECS
+}
+
+/// Per-bucket degraded mode configuration (YAML format)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DegradedModeConfig {
+    /// Serve from cache while origins are down (default: true when the section is present)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Also serve entries past their TTL (default: true)
+    #[serde(default = "default_serve_stale")]
+    pub serve_stale: bool,
+    /// Longest time past expiry an entry may be served, 0 = no limit (default: 86400)
+    #[serde(default = "default_max_stale_secs")]
+    pub max_stale_secs: u64,
+}
+
+impl Default for DegradedModeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            serve_stale: default_serve_stale(),
+            max_stale_secs: default_max_stale_secs(),
+        }
+    }
+}
+
+impl DegradedModeConfig {
+    /// Check if an entry `stale_for` past its expiry may be served
+    /// (`None` for entries that have not expired)
+    pub fn serves(&self, stale_for: Option<Duration>) -> bool {
+        match stale_for {
+            None => true,
+            Some(_) if !self.serve_stale => false,
+            Some(_) if self.max_stale_secs == 0 => true,
+            Some(age) => age.as_secs() <= self.max_stale_secs,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_degraded_mode_config_defaults() {
+        let config: DegradedModeConfig = serde_yaml::from_str("{}").unwrap();
+        assert!(config.enabled);
+        assert!(config.serve_stale);
+        assert_eq!(config.max_stale_secs, DEFAULT_DEGRADED_MAX_STALE_SECS);
+    }
+
+    #[test]
+    fn test_degraded_mode_serves() {
+        let config = DegradedModeConfig {
+            max_stale_secs: 600,
+            ..Default::default()
+        };
+        assert!(config.serves(None));
+        assert!(config.serves(Some(Duration::from_secs(600))));
+        assert!(!config.serves(Some(Duration::from_secs(601))));
+
+        let unlimited = DegradedModeConfig {
+            max_stale_secs: 0,
+            ..Default::default()
+        };
+        assert!(unlimited.serves(Some(Duration::from_secs(30 * 86400))));
+
+        let fresh_only = DegradedModeConfig {
+            serve_stale: false,
+            ..Default::default()
+        };
+        assert!(fresh_only.serves(None));
+        assert!(!fresh_only.serves(Some(Duration::from_secs(1))));
+    }
+}
@@ -0,0 +1,134 @@
+//! Deprecation and sunset headers for legacy routes.
+//!
+//! A bucket whose URL structure is being retired can announce it on every
+//! response so clients notice before it disappears:
+//!
+//! - `Deprecation: @<unix-seconds>` (RFC 9745)
+//! - `Sunset: <HTTP-date>` (RFC 8594)
+//! - `Link: <url>; rel="deprecation"` and `Link: <url>; rel="successor-version"`
+//!
+//! Dates are configured as RFC 3339 timestamps.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Per-bucket deprecation notice (YAML format)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeprecationConfig {
+    /// When the route was (or will be) deprecated, e.g. `2025-01-01T00:00:00Z`
+    pub deprecated_at: String,
+    /// When the route stops working (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sunset: Option<String>,
+    /// Migration notes, sent with `rel="deprecation"` (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub link: Option<String>,
+    /// Replacement URL, sent with `rel="successor-version"` (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub successor: Option<String>,
+}
+
+impl DeprecationConfig {
+    /// Validate dates and links
+    pub fn validate(&self, bucket_name: &str) -> Result<(), String> {
+        let deprecated_at = parse_date(&self.deprecated_at)
+            .map_err(|e| format!("Bucket '{}': deprecation.deprecated_at {}", bucket_name, e))?;
+        if let Some(sunset) = &self.sunset {
+            let sunset = parse_date(sunset)
+                .map_err(|e| format!("Bucket '{}': deprecation.sunset {}", bucket_name, e))?;
+            if sunset < deprecated_at {
+                return Err(format!(
+                    "Bucket '{}': deprecation.sunset must not be earlier than deprecated_at",
+                    bucket_name
+                ));
+            }
+        }
+        for (name, url) in [("link", &self.link), ("successor", &self.successor)] {
+            if url
+                .as_ref()
+                .is_some_and(|u| u.trim().is_empty() || u.contains(['<', '>', ' ']))
+            {
+                return Err(format!(
+                    "Bucket '{}': deprecation.{} must be a non-empty URL",
+                    bucket_name, name
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Headers to add to every response of the bucket
+    ///
+    /// Dates are checked by `validate` at load time; unparsable ones are skipped.
+    pub fn response_headers(&self) -> Vec<(&'static str, String)> {
+        let mut headers = Vec::new();
+        if let Ok(deprecated_at) = parse_date(&self.deprecated_at) {
+            headers.push(("Deprecation", format!("@{}", deprecated_at.timestamp())));
+        }
+        if let Some(Ok(sunset)) = self.sunset.as_deref().map(parse_date) {
+            headers.push((
+                "Sunset",
+                sunset.format("%a, %d %b %Y %H:%M:%S GMT").to_string(),
+            ));
+        }
+        if let Some(link) = &self.link {
+            headers.push(("Link", format!("<{}>; rel=\"deprecation\"", link)));
+        }
+        if let Some(successor) = &self.successor {
+            headers.push((
+                "Link",
+                format!("<{}>; rel=\"successor-version\"", successor),
+            ));
+        }
+        headers
+    }
+}
+
+fn parse_date(value: &str) -> Result<DateTime<Utc>, String> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|date| date.with_timezone(&Utc))
+        .map_err(|e| format!("'{}' is not an RFC 3339 timestamp: {}", value, e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deprecation_headers_and_validation() {
+        let config: DeprecationConfig = serde_yaml::from_str(
+            "deprecated_at: 2025-01-01T00:00:00Z\nsunset: 2025-06-30T23:59:59Z\nsuccessor: https://cdn.example.com/v2/",
+        )
+        .unwrap();
+        assert!(config.validate("legacy").is_ok());
+        assert_eq!(
+            config.response_headers(),
+            vec![
+                ("Deprecation", "@1735689600".to_string()),
+                ("Sunset", "Mon, 30 Jun 2025 23:59:59 GMT".to_string()),
+                (
+                    "Link",
+                    "<https://cdn.example.com/v2/>; rel=\"successor-version\"".to_string()
+                ),
+            ]
+        );
+
+        let early_sunset = DeprecationConfig {
+            sunset: Some("2024-12-31T00:00:00Z".to_string()),
+            ..config.clone()
+        };
+        assert!(early_sunset.validate("legacy").is_err());
+
+        let bad_date = DeprecationConfig {
+            deprecated_at: "January 2025".to_string(),
+            ..config.clone()
+        };
+        assert!(bad_date.validate("legacy").is_err());
+
+        let bad_link = DeprecationConfig {
+            link: Some(" ".to_string()),
+            ..config
+        };
+        assert!(bad_link.validate("legacy").is_err());
+    }
+}
@@ -0,0 +1,133 @@
+//! Client disconnect configuration types.
+//!
+//! When a client aborts mid-download the upstream fetch is cancelled so the
+//! proxy stops pulling bytes nobody will read. The one exception is a response
+//! that is being buffered for the cache and is already close to complete: the
+//! remainder is fetched in the background so the next request is a cache hit.
+//!
+//! Default values are sourced from `crate::constants`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{
+    DEFAULT_DISCONNECT_MAX_REMAINING_BYTES, DEFAULT_DISCONNECT_MIN_COMPLETION_PERCENT,
+};
+
+fn default_finish_cache_fill() -> bool {
+    true
+}
+
+fn default_min_completion_percent() -> u8 {
+    DEFAULT_DISCONNECT_MIN_COMPLETION_PERCENT
+}
+
+fn default_max_remaining_bytes() -> u64 {
+    DEFAULT_DISCONNECT_MAX_REMAINING_BYTES
+}
+
+/// Client disconnect handling configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClientDisconnectConfig {
+    /// Finish filling the cache for nearly complete responses (default: true)
+    #[serde(default = "default_finish_cache_fill")]
+    pub finish_cache_fill: bool,
+    /// Minimum percent of Content-Length already received to finish the fill (default: 90)
+    #[serde(default = "default_min_completion_percent")]
+    pub min_completion_percent: u8,
+    /// Maximum bytes still missing to finish the fill (default: 1 MB)
+    #[serde(default = "default_max_remaining_bytes")]
+    pub max_remaining_bytes: u64,
+}
+
+impl Default for ClientDisconnectConfig {
+    fn default() -> Self {
+        Self {
+            finish_cache_fill: default_finish_cache_fill(),
+            min_completion_percent: default_min_completion_percent(),
+            max_remaining_bytes: default_max_remaining_bytes(),
+        }
+    }
+}
+
+impl ClientDisconnectConfig {
+    /// Validate the completion threshold
+    pub fn validate(&self) -> Result<(), String> {
+        if self.min_completion_percent > 100 {
+            return Err(format!(
+                "server.client_disconnect.min_completion_percent must be between 0 and 100, got {}",
+                self.min_completion_percent
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check if a cache fill that has received `received` of `total` bytes
+    /// should be finished after the client went away
+    pub fn should_finish_cache_fill(&self, received: u64, total: u64) -> bool {
+        if !self.finish_cache_fill || total == 0 || received >= total {
+            return false;
+        }
+        let remaining = total - received;
+        remaining <= self.max_remaining_bytes
+            && received.saturating_mul(100)
+                >= total.saturating_mul(self.min_completion_percent as u64)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_client_disconnect_config_defaults() {
+        let config: ClientDisconnectConfig = serde_yaml::from_str("{}").unwrap();
+
+        assert!(config.finish_cache_fill);
+        assert_eq!(
+            config.min_completion_percent,
+            DEFAULT_DISCONNECT_MIN_COMPLETION_PERCENT
+        );
+        assert_eq!(
+            config.max_remaining_bytes,
+            DEFAULT_DISCONNECT_MAX_REMAINING_BYTES
+        );
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_client_disconnect_config_rejects_invalid_percent() {
+        let config = ClientDisconnectConfig {
+            min_completion_percent: 101,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_should_finish_cache_fill_thresholds() {
+        let config = ClientDisconnectConfig {
+            finish_cache_fill: true,
+            min_completion_percent: 90,
+            max_remaining_bytes: 100,
+        };
+
+        // 95% received, 50 bytes missing
+        assert!(config.should_finish_cache_fill(950, 1000));
+        // Below the completion threshold
+        assert!(!config.should_finish_cache_fill(500, 1000));
+        // Close to complete but too many bytes missing
+        assert!(!config.should_finish_cache_fill(9_500, 10_000));
+        // Nothing left to fetch or unknown length
+        assert!(!config.should_finish_cache_fill(1000, 1000));
+        assert!(!config.should_finish_cache_fill(0, 0));
+    }
+
+    #[test]
+    fn test_should_finish_cache_fill_disabled() {
+        let config = ClientDisconnectConfig {
+            finish_cache_fill: false,
+            ..Default::default()
+        };
+        assert!(!config.should_finish_cache_fill(999, 1000));
+    }
+}
@@ -0,0 +1,121 @@
+//! Preload hints for HTML entry points.
+//!
+//! The manifest maps an object key (e.g. `index.html`) to the assets a
+//! browser should start fetching while it waits for the page. Responses for
+//! that object carry one `Link: <asset>; rel=preload; as=...` per asset, and
+//! with `send_103` the same links go out first in a `103 Early Hints`
+//! response, before the cache lookup or the S3 round trip.
+//!
+//! The `as` destination is derived from the asset's extension; assets with
+//! an unknown extension are rejected at config load.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Per-bucket early hints configuration (YAML format)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EarlyHintsConfig {
+    /// Add preload links (default: true when the section is present)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Also send `103 Early Hints` ahead of the response (default: false)
+    #[serde(default)]
+    pub send_103: bool,
+    /// Object key -> asset URLs to preload
+    #[serde(default)]
+    pub manifest: HashMap<String, Vec<String>>,
+}
+
+/// `as` destination (and whether the fetch is CORS-mode) for an asset
+fn destination(asset: &str) -> Option<(&'static str, bool)> {
+    let path = asset.split(['?', '#']).next().unwrap_or(asset);
+    let ext = path.rsplit_once('.')?.1.to_ascii_lowercase();
+    match ext.as_str() {
+        "js" | "mjs" => Some(("script", false)),
+        "css" => Some(("style", false)),
+        "woff" | "woff2" | "ttf" | "otf" => Some(("font", true)),
+        "png" | "jpg" | "jpeg" | "gif" | "webp" | "avif" | "svg" | "ico" => Some(("image", false)),
+        "json" => Some(("fetch", true)),
+        _ => None,
+    }
+}
+
+impl EarlyHintsConfig {
+    /// `Link` header values for an object, `None` if it has no manifest entry
+    pub fn links(&self, object_key: &str) -> Option<Vec<String>> {
+        if !self.enabled {
+            return None;
+        }
+        let assets = self.manifest.get(object_key)?;
+        let links: Vec<String> = assets
+            .iter()
+            .filter_map(|asset| {
+                let (dest, crossorigin) = destination(asset)?;
+                let mut link = format!("<{}>; rel=preload; as={}", asset, dest);
+                if crossorigin {
+                    link.push_str("; crossorigin");
+                }
+                Some(link)
+            })
+            .collect();
+        (!links.is_empty()).then_some(links)
+    }
+
+    pub fn validate(&self, bucket_name: &str) -> Result<(), String> {
+        for (object_key, assets) in &self.manifest {
+            for asset in assets {
+                if asset.is_empty() || asset.contains(['<', '>', ',', '"']) {
+                    return Err(format!(
+                        "Bucket '{}': early_hints asset '{}' for '{}' is not a valid URL",
+                        bucket_name, asset, object_key
+                    ));
+                }
+                if destination(asset).is_none() {
+                    return Err(format!(
+                        "Bucket '{}': early_hints asset '{}' has no known preload type (js, css, font, image or json)",
+                        bucket_name, asset
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_links_for_manifest_entry() {
+        let config: EarlyHintsConfig = serde_yaml::from_str(
+            r#"
+manifest:
+  index.html:
+    - /assets/app.js?v=3
+    - /assets/app.css
+    - /assets/inter.woff2
+"#,
+        )
+        .unwrap();
+        assert!(config.validate("web").is_ok());
+        assert!(!config.send_103);
+        assert_eq!(
+            config.links("index.html").unwrap(),
+            vec![
+                "</assets/app.js?v=3>; rel=preload; as=script",
+                "</assets/app.css>; rel=preload; as=style",
+                "</assets/inter.woff2>; rel=preload; as=font; crossorigin",
+            ]
+        );
+        assert!(config.links("about.html").is_none());
+
+        let bad: EarlyHintsConfig =
+            serde_yaml::from_str("manifest:\n  index.html: [/assets/data.bin]").unwrap();
+        assert!(bad.validate("web").unwrap_err().contains("data.bin"));
+    }
+}
@@ -0,0 +1,200 @@
+//! Access control for the built-in health and metrics endpoints.
+//!
+//! `/health`, `/ready` and `/metrics` are served on the data port and bypass
+//! bucket authentication. On an internet-facing proxy they can be locked down
+//! with a bearer token, a list of allowed client IPs/CIDR ranges, or both
+//! (both must then pass). The client IP is the direct peer address;
+//! `X-Forwarded-For` is not trusted here.
+
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+use crate::security::{constant_time_eq, IpRange};
+
+/// Access rules for `/health` + `/ready` and for `/metrics`
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EndpointAccessConfig {
+    /// Rules for `/health` and `/ready` (default: open)
+    #[serde(default)]
+    pub health: EndpointAclConfig,
+    /// Rules for `/metrics` (default: open)
+    #[serde(default)]
+    pub metrics: EndpointAclConfig,
+}
+
+/// Token and IP restrictions for one group of endpoints
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EndpointAclConfig {
+    /// Require `Authorization: Bearer <token>` (supports `${ENV_VAR}`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bearer_token: Option<String>,
+    /// Only allow these client IPs or CIDR ranges (default: any)
+    #[serde(default)]
+    pub allowed_ips: Vec<String>,
+}
+
+/// Outcome of an endpoint access check
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EndpointAccess {
+    Allowed,
+    /// Missing or wrong bearer token (401)
+    Unauthorized,
+    /// Client IP not in `allowed_ips` (403)
+    Forbidden,
+}
+
+impl EndpointAccess {
+    /// Label used in logs and metrics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EndpointAccess::Allowed => "allowed",
+            EndpointAccess::Unauthorized => "unauthorized",
+            EndpointAccess::Forbidden => "forbidden",
+        }
+    }
+}
+
+impl EndpointAccessConfig {
+    /// Validate tokens and IP ranges
+    pub fn validate(&self) -> Result<(), String> {
+        self.health.validate("health")?;
+        self.metrics.validate("metrics")
+    }
+
+    /// Rules for a built-in endpoint path, if it is one
+    pub fn for_path(&self, path: &str) -> Option<(&'static str, &EndpointAclConfig)> {
+        match path {
+            "/health" | "/ready" => Some(("health", &self.health)),
+            "/metrics" => Some(("metrics", &self.metrics)),
+            _ => None,
+        }
+    }
+}
+
+impl EndpointAclConfig {
+    fn validate(&self, name: &str) -> Result<(), String> {
+        if self
+            .bearer_token
+            .as_ref()
+            .is_some_and(|t| t.trim().is_empty())
+        {
+            return Err(format!(
+                "server.endpoint_access.{}.bearer_token cannot be empty",
+                name
+            ));
+        }
+        for entry in &self.allowed_ips {
+            IpRange::parse(entry)
+                .map_err(|e| format!("server.endpoint_access.{}.allowed_ips: {}", name, e))?;
+        }
+        Ok(())
+    }
+
+    /// Check if any restriction is configured
+    pub fn is_restricted(&self) -> bool {
+        self.bearer_token.is_some() || !self.allowed_ips.is_empty()
+    }
+
+    /// Check a request's peer IP and `Authorization` header
+    ///
+    /// The IP check runs first so unlisted clients learn nothing about the
+    /// token requirement.
+    pub fn check(&self, client_ip: Option<IpAddr>, authorization: Option<&str>) -> EndpointAccess {
+        if !self.allowed_ips.is_empty() {
+            let allowed = client_ip.is_some_and(|ip| {
+                self.allowed_ips
+                    .iter()
+                    .filter_map(|entry| IpRange::parse(entry).ok())
+                    .any(|range| range.contains(&ip))
+            });
+            if !allowed {
+                return EndpointAccess::Forbidden;
+            }
+        }
+        if let Some(expected) = &self.bearer_token {
+            let presented = authorization
+                .and_then(|value| value.strip_prefix("Bearer "))
+                .map(str::trim);
+            if !presented.is_some_and(|token| constant_time_eq(token, expected)) {
+                return EndpointAccess::Unauthorized;
+            }
+        }
+        EndpointAccess::Allowed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ip(s: &str) -> Option<IpAddr> {
+        Some(s.parse().unwrap())
+    }
+
+    #[test]
+    fn test_endpoint_access_defaults_open() {
+        let config: EndpointAccessConfig = serde_yaml::from_str("{}").unwrap();
+        assert!(config.validate().is_ok());
+        let (name, acl) = config.for_path("/metrics").unwrap();
+        assert_eq!(name, "metrics");
+        assert!(!acl.is_restricted());
+        assert_eq!(acl.check(None, None), EndpointAccess::Allowed);
+        assert_eq!(config.for_path("/ready").unwrap().0, "health");
+        assert!(config.for_path("/bucket/key").is_none());
+    }
+
+    #[test]
+    fn test_endpoint_access_token_and_ips() {
+        let acl = EndpointAclConfig {
+            bearer_token: Some("s3cret".to_string()),
+            allowed_ips: vec!["10.0.0.0/8".to_string()],
+        };
+        assert_eq!(
+            acl.check(ip("10.1.2.3"), Some("Bearer s3cret")),
+            EndpointAccess::Allowed
+        );
+        assert_eq!(
+            acl.check(ip("10.1.2.3"), Some("Bearer wrong")),
+            EndpointAccess::Unauthorized
+        );
+        assert_eq!(
+            acl.check(ip("10.1.2.3"), None),
+            EndpointAccess::Unauthorized
+        );
+        assert_eq!(
+            acl.check(ip("203.0.113.9"), Some("Bearer s3cret")),
+            EndpointAccess::Forbidden
+        );
+        assert_eq!(
+            acl.check(None, Some("Bearer s3cret")),
+            EndpointAccess::Forbidden
+        );
+    }
+
+    #[test]
+    fn test_endpoint_access_validate() {
+        let config = EndpointAccessConfig {
+            metrics: EndpointAclConfig {
+                bearer_token: Some(" ".to_string()),
+                allowed_ips: Vec::new(),
+            },
+            ..Default::default()
+        };
+        assert!(config
+            .validate()
+            .unwrap_err()
+            .contains("metrics.bearer_token"));
+
+        let config = EndpointAccessConfig {
+            health: EndpointAclConfig {
+                bearer_token: None,
+                allowed_ips: vec!["10.0.0.0/99".to_string()],
+            },
+            ..Default::default()
+        };
+        assert!(config
+            .validate()
+            .unwrap_err()
+            .contains("health.allowed_ips"));
+    }
+}
@@ -0,0 +1,165 @@
+//! ETag rewriting for replicated buckets.
+//!
+//! Replicas of the same object often report different ETags: a multipart
+//! ETag depends on the part size the object was uploaded (or replicated)
+//! with. A client that revalidates against another replica after failover
+//! then never gets a 304 and re-downloads everything. Two modes help:
+//!
+//! - `checksum`: the ETag is derived from a content checksum stored in object
+//!   metadata (`checksum_header`), which is identical on every replica.
+//!   Objects without the header keep the upstream ETag.
+//! - `namespace`: the ETag is prefixed with the replica that produced it, so a
+//!   validator is only ever sent back to that replica. Revalidating against
+//!   another replica yields a 200 instead of a false match.
+//!
+//! Client validators (`If-None-Match`, `If-Match`) are translated back before
+//! the request goes upstream, since S3 only knows its own ETags.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::constants::DEFAULT_ETAG_CHECKSUM_HEADER;
+
+/// Prefix of ETags derived from a content checksum
+const CHECKSUM_ETAG_PREFIX: &str = "sha-";
+
+/// Namespace used when the request did not go to a named replica
+const PRIMARY_NAMESPACE: &str = "primary";
+
+fn default_checksum_header() -> String {
+    DEFAULT_ETAG_CHECKSUM_HEADER.to_string()
+}
+
+/// How ETags surfaced to clients are rewritten
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EtagRewriteMode {
+    /// Derive the ETag from a content checksum in object metadata
+    Checksum,
+    /// Prefix the ETag with the replica name
+    Namespace,
+}
+
+/// Per-bucket ETag rewrite configuration (YAML format)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EtagRewriteConfig {
+    pub mode: EtagRewriteMode,
+    /// Response header holding the content checksum (default: x-amz-meta-sha256)
+    #[serde(default = "default_checksum_header")]
+    pub checksum_header: String,
+}
+
+impl EtagRewriteConfig {
+    pub fn validate(&self, bucket_name: &str) -> Result<(), String> {
+        if self.mode == EtagRewriteMode::Checksum && self.checksum_header.trim().is_empty() {
+            return Err(format!(
+                "Bucket '{}': etag_rewrite.checksum_header cannot be empty",
+                bucket_name
+            ));
+        }
+        Ok(())
+    }
+
+    /// ETag to surface to clients (unquoted), `None` to keep the upstream one
+    ///
+    /// `checksum` is the value of `checksum_header` on the upstream response.
+    pub fn surfaced(
+        &self,
+        upstream_etag: &str,
+        replica: Option<&str>,
+        checksum: Option<&str>,
+    ) -> Option<String> {
+        let upstream_etag = crate::cache::etag::normalize(upstream_etag);
+        if upstream_etag.is_empty() {
+            return None;
+        }
+        match self.mode {
+            EtagRewriteMode::Checksum => {
+                let checksum = checksum.map(str::trim).filter(|c| !c.is_empty())?;
+                let digest = hex::encode(Sha256::digest(checksum.as_bytes()));
+                Some(format!("{}{}", CHECKSUM_ETAG_PREFIX, &digest[..32]))
+            }
+            EtagRewriteMode::Namespace => Some(format!(
+                "{}:{}",
+                replica.unwrap_or(PRIMARY_NAMESPACE),
+                upstream_etag
+            )),
+        }
+    }
+
+    /// Translate a client `If-None-Match`/`If-Match` value for the upstream
+    ///
+    /// Validators S3 cannot evaluate are dropped; `None` means drop the header.
+    pub fn upstream_validator(&self, header: &str, replica: Option<&str>) -> Option<String> {
+        let candidates: Vec<String> = header
+            .split(',')
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .filter_map(|candidate| {
+                if candidate == "*" {
+                    return Some(candidate.to_string());
+                }
+                let etag = crate::cache::etag::normalize(candidate);
+                match self.mode {
+                    EtagRewriteMode::Checksum if etag.starts_with(CHECKSUM_ETAG_PREFIX) => None,
+                    EtagRewriteMode::Checksum => Some(candidate.to_string()),
+                    EtagRewriteMode::Namespace => {
+                        let (namespace, inner) = etag.split_once(':')?;
+                        (namespace == replica.unwrap_or(PRIMARY_NAMESPACE))
+                            .then(|| format!("\"{}\"", inner))
+                    }
+                }
+            })
+            .collect();
+        (!candidates.is_empty()).then(|| candidates.join(", "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(mode: EtagRewriteMode) -> EtagRewriteConfig {
+        EtagRewriteConfig {
+            mode,
+            checksum_header: default_checksum_header(),
+        }
+    }
+
+    #[test]
+    fn test_rewrite_and_translate_back() {
+        let checksum = config(EtagRewriteMode::Checksum);
+        let a = checksum.surfaced("\"abc-3\"", Some("r1"), Some("deadbeef"));
+        let b = checksum.surfaced("\"abc-7\"", Some("r2"), Some("deadbeef"));
+        assert_eq!(a, b);
+        assert!(a.as_deref().unwrap().starts_with("sha-"));
+        assert_eq!(checksum.surfaced("\"abc-3\"", None, None), None);
+        let header = format!("\"{}\", \"abc-3\"", a.unwrap());
+        assert_eq!(
+            checksum.upstream_validator(&header, None),
+            Some("\"abc-3\"".to_string())
+        );
+
+        let namespace = config(EtagRewriteMode::Namespace);
+        assert_eq!(
+            namespace.surfaced("\"abc-3\"", Some("r1"), None),
+            Some("r1:abc-3".to_string())
+        );
+        assert_eq!(
+            namespace.surfaced("abc", None, None),
+            Some("primary:abc".to_string())
+        );
+        assert_eq!(
+            namespace.upstream_validator("\"r1:abc-3\"", Some("r1")),
+            Some("\"abc-3\"".to_string())
+        );
+        assert_eq!(
+            namespace.upstream_validator("\"r1:abc-3\"", Some("r2")),
+            None
+        );
+        assert_eq!(
+            namespace.upstream_validator("*", Some("r2")),
+            Some("*".to_string())
+        );
+    }
+}
@@ -0,0 +1,151 @@
+//! gRPC control plane configuration types.
+//!
+//! The control plane mirrors the cache purge, cache stats, reload and health
+//! admin endpoints as a typed gRPC service for fleet controllers. It listens
+//! on its own port and only accepts clients presenting a certificate signed by
+//! `tls.client_ca_file` (mutual TLS); there is no plaintext mode.
+//!
+//! The listener is only available in builds with the `grpc` Cargo feature.
+//! It is set up at startup, so changes to this section need a restart.
+//!
+//! Default values are sourced from `crate::constants`.
+
+use serde::{Deserialize, Serialize};
+use std::net::{IpAddr, SocketAddr};
+
+use crate::constants::{DEFAULT_GRPC_ADDRESS, DEFAULT_GRPC_PORT};
+
+fn default_address() -> String {
+    DEFAULT_GRPC_ADDRESS.to_string()
+}
+
+fn default_port() -> u16 {
+    DEFAULT_GRPC_PORT
+}
+
+/// Server certificate and the CA client certificates must be signed by
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrpcTlsConfig {
+    /// PEM certificate chain presented to clients
+    pub cert_file: String,
+    /// PEM private key for `cert_file`
+    pub key_file: String,
+    /// PEM CA bundle that client certificates are verified against
+    pub client_ca_file: String,
+}
+
+/// gRPC control plane listener
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrpcConfig {
+    /// Start the gRPC control plane (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Listen address (default: 127.0.0.1)
+    #[serde(default = "default_address")]
+    pub address: String,
+    /// Listen port (default: 9443)
+    #[serde(default = "default_port")]
+    pub port: u16,
+    /// Server certificate and client CA; required when enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tls: Option<GrpcTlsConfig>,
+}
+
+impl Default for GrpcConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            address: default_address(),
+            port: default_port(),
+            tls: None,
+        }
+    }
+}
+
+impl GrpcConfig {
+    /// Socket the control plane listens on
+    pub fn socket_addr(&self) -> Result<SocketAddr, String> {
+        let ip: IpAddr = self.address.parse().map_err(|_| {
+            format!(
+                "server.grpc.address must be an IP address, got '{}'",
+                self.address
+            )
+        })?;
+        Ok(SocketAddr::new(ip, self.port))
+    }
+
+    /// Validate the listener and TLS files; `http_port` is the proxy's own port
+    pub fn validate(&self, http_port: u16) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if !cfg!(feature = "grpc") {
+            return Err("server.grpc.enabled requires a build with the 'grpc' feature".to_string());
+        }
+        self.socket_addr()?;
+        if self.port == 0 || self.port == http_port {
+            return Err(format!(
+                "server.grpc.port must be non-zero and differ from server.port, got {}",
+                self.port
+            ));
+        }
+        let Some(tls) = &self.tls else {
+            return Err(
+                "server.grpc.tls is required: the control plane only accepts mutual TLS"
+                    .to_string(),
+            );
+        };
+        for (field, value) in [
+            ("cert_file", &tls.cert_file),
+            ("key_file", &tls.key_file),
+            ("client_ca_file", &tls.client_ca_file),
+        ] {
+            if value.trim().is_empty() {
+                return Err(format!("server.grpc.tls.{} cannot be empty", field));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grpc_config_defaults_and_validation() {
+        let config: GrpcConfig = serde_yaml::from_str("{}").unwrap();
+        assert!(!config.enabled);
+        assert_eq!(config.socket_addr().unwrap().to_string(), "127.0.0.1:9443");
+        assert!(config.validate(8080).is_ok());
+
+        let config: GrpcConfig = serde_yaml::from_str(
+            r#"
+enabled: true
+port: 9443
+tls:
+  cert_file: /etc/yatagarasu/grpc.crt
+  key_file: /etc/yatagarasu/grpc.key
+  client_ca_file: /etc/yatagarasu/fleet-ca.crt
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.validate(8080).is_ok(), cfg!(feature = "grpc"));
+
+        if cfg!(feature = "grpc") {
+            assert!(config.validate(9443).is_err());
+
+            let mut hostname = config.clone();
+            hostname.address = "localhost".to_string();
+            assert!(hostname.validate(8080).is_err());
+
+            let mut no_tls = config.clone();
+            no_tls.tls = None;
+            assert!(no_tls.validate(8080).is_err());
+
+            let mut no_ca = config;
+            no_ca.tls.as_mut().unwrap().client_ca_file = String::new();
+            assert!(no_ca.validate(8080).is_err());
+        }
+    }
+}
@@ -0,0 +1,248 @@
+//! Hotlink protection configuration.
+//!
+//! Public asset buckets are often embedded by third-party sites, which then
+//! serve our bandwidth to their visitors. A bucket's `hotlink` section checks
+//! the `Origin` (or, when absent, `Referer`) host against an allowlist and
+//! either denies foreign requests or serves them watermarked images using the
+//! bucket's watermark rules.
+
+use serde::{Deserialize, Serialize};
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_allow_empty_referer() -> bool {
+    true
+}
+
+/// What to do with a request whose Referer/Origin is not allowed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HotlinkAction {
+    /// Respond with 403 Forbidden
+    #[default]
+    Deny,
+    /// Serve images with the bucket's watermark rules applied
+    Watermark,
+}
+
+impl HotlinkAction {
+    /// Label used in logs and metrics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HotlinkAction::Deny => "deny",
+            HotlinkAction::Watermark => "watermark",
+        }
+    }
+}
+
+/// Per-bucket hotlink protection configuration (YAML format)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HotlinkConfig {
+    /// Enforce hotlink rules (default: true when the section is present)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Hosts allowed to embed this bucket's objects
+    /// (`example.com` for an exact host, `*.example.com` for its subdomains)
+    #[serde(default)]
+    pub allowed_domains: Vec<String>,
+    /// Allow requests without Referer/Origin, e.g. direct navigation or
+    /// privacy-stripping browsers (default: true)
+    #[serde(default = "default_allow_empty_referer")]
+    pub allow_empty_referer: bool,
+    /// Action for disallowed requests (default: deny)
+    #[serde(default)]
+    pub action: HotlinkAction,
+}
+
+impl Default for HotlinkConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            allowed_domains: Vec::new(),
+            allow_empty_referer: default_allow_empty_referer(),
+            action: HotlinkAction::default(),
+        }
+    }
+}
+
+impl HotlinkConfig {
+    /// Validate the hotlink configuration for the given bucket.
+    ///
+    /// `has_watermark_rules` reports whether the bucket defines watermark
+    /// rules, which the `watermark` action requires.
+    pub fn validate(&self, bucket_name: &str, has_watermark_rules: bool) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.allowed_domains.is_empty() {
+            return Err(format!(
+                "Bucket '{}': hotlink.allowed_domains must not be empty",
+                bucket_name
+            ));
+        }
+        for domain in &self.allowed_domains {
+            let host = domain.strip_prefix("*.").unwrap_or(domain);
+            if host.is_empty() || host.contains(['*', '/', ':', ' ']) {
+                return Err(format!(
+                    "Bucket '{}': hotlink.allowed_domains entry '{}' must be a host \
+                     like 'example.com' or '*.example.com'",
+                    bucket_name, domain
+                ));
+            }
+        }
+        if self.action == HotlinkAction::Watermark && !has_watermark_rules {
+            return Err(format!(
+                "Bucket '{}': hotlink.action 'watermark' requires watermark rules",
+                bucket_name
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check whether a request with the given `Referer` and `Origin` headers is allowed
+    ///
+    /// `Origin` is preferred when present; `Origin: null` carries no host and
+    /// counts as empty.
+    pub fn is_allowed(&self, referer: Option<&str>, origin: Option<&str>) -> bool {
+        if !self.enabled {
+            return true;
+        }
+        let source = origin
+            .map(str::trim)
+            .filter(|o| !o.is_empty() && *o != "null")
+            .or_else(|| referer.map(str::trim).filter(|r| !r.is_empty()));
+
+        match source {
+            None => self.allow_empty_referer,
+            Some(url) => match url_host(url) {
+                Some(host) => self.is_allowed_host(host),
+                None => false,
+            },
+        }
+    }
+
+    fn is_allowed_host(&self, host: &str) -> bool {
+        self.allowed_domains
+            .iter()
+            .any(|domain| match domain.strip_prefix("*.") {
+                Some(parent) => host
+                    .len()
+                    .checked_sub(parent.len() + 1)
+                    .map(|split| {
+                        host.as_bytes()[split] == b'.'
+                            && host[split + 1..].eq_ignore_ascii_case(parent)
+                    })
+                    .unwrap_or(false),
+                None => host.eq_ignore_ascii_case(domain),
+            })
+    }
+}
+
+/// Host part of an absolute URL (`https://user@cdn.example.com:8443/a` -> `cdn.example.com`)
+fn url_host(url: &str) -> Option<&str> {
+    let (_, rest) = url.split_once("://")?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let authority = authority.rsplit('@').next()?;
+    let host = if let Some(bracketed) = authority.strip_prefix('[') {
+        bracketed.split(']').next()?
+    } else {
+        authority.split(':').next()?
+    };
+    Some(host).filter(|h| !h.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(domains: &[&str]) -> HotlinkConfig {
+        HotlinkConfig {
+            allowed_domains: domains.iter().map(|d| d.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_hotlink_config_defaults() {
+        let config: HotlinkConfig =
+            serde_yaml::from_str("allowed_domains: [\"example.com\"]").unwrap();
+        assert!(config.enabled);
+        assert!(config.allow_empty_referer);
+        assert_eq!(config.action, HotlinkAction::Deny);
+
+        let config: HotlinkConfig =
+            serde_yaml::from_str("allowed_domains: [\"example.com\"]\naction: watermark").unwrap();
+        assert_eq!(config.action, HotlinkAction::Watermark);
+    }
+
+    #[test]
+    fn test_hotlink_config_validate() {
+        assert!(config(&["example.com", "*.example.com"])
+            .validate("assets", false)
+            .is_ok());
+        assert!(config(&[]).validate("assets", false).is_err());
+        assert!(config(&["https://example.com"])
+            .validate("assets", false)
+            .is_err());
+        assert!(config(&["foo.*.com"]).validate("assets", false).is_err());
+
+        let watermark = HotlinkConfig {
+            action: HotlinkAction::Watermark,
+            ..config(&["example.com"])
+        };
+        assert!(watermark
+            .validate("assets", false)
+            .unwrap_err()
+            .contains("watermark"));
+        assert!(watermark.validate("assets", true).is_ok());
+    }
+
+    #[test]
+    fn test_hotlink_exact_and_wildcard_domains() {
+        let config = config(&["example.com", "*.partner.org"]);
+
+        assert!(config.is_allowed(Some("https://example.com/page"), None));
+        assert!(config.is_allowed(Some("http://EXAMPLE.com:8080/"), None));
+        assert!(!config.is_allowed(Some("https://www.example.com/"), None));
+        assert!(config.is_allowed(Some("https://cdn.partner.org/x"), None));
+        assert!(!config.is_allowed(Some("https://partner.org/"), None));
+        assert!(!config.is_allowed(Some("https://evilpartner.org/"), None));
+        assert!(!config.is_allowed(Some("https://example.com.evil.net/"), None));
+        assert!(!config.is_allowed(Some("https://example.com@evil.net/"), None));
+        assert!(!config.is_allowed(Some("not a url"), None));
+    }
+
+    #[test]
+    fn test_hotlink_origin_preferred_over_referer() {
+        let config = config(&["example.com"]);
+
+        assert!(config.is_allowed(Some("https://evil.net/"), Some("https://example.com")));
+        assert!(!config.is_allowed(Some("https://example.com/"), Some("https://evil.net")));
+        // Opaque origin falls back to Referer
+        assert!(config.is_allowed(Some("https://example.com/"), Some("null")));
+    }
+
+    #[test]
+    fn test_hotlink_empty_referer() {
+        let mut config = config(&["example.com"]);
+        assert!(config.is_allowed(None, None));
+        assert!(config.is_allowed(Some(""), Some("null")));
+
+        config.allow_empty_referer = false;
+        assert!(!config.is_allowed(None, None));
+
+        config.enabled = false;
+        assert!(config.is_allowed(Some("https://evil.net/"), None));
+    }
+
+    #[test]
+    fn test_url_host() {
+        assert_eq!(url_host("https://a.example.com/x?y"), Some("a.example.com"));
+        assert_eq!(url_host("http://[::1]:8080/"), Some("::1"));
+        assert_eq!(url_host("https://user:pw@host.io#frag"), Some("host.io"));
+        assert_eq!(url_host("example.com/path"), None);
+        assert_eq!(url_host("https:///path"), None);
+    }
+}
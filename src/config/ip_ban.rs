@@ -0,0 +1,216 @@
+//! Automatic temporary IP banning configuration.
+//!
+//! Counts error responses per client IP in a fixed window. An IP that
+//! exceeds either threshold (client errors or rate-limited responses) is
+//! rejected with 403 for `ban_duration_secs`. With a `redis` section, bans are
+//! shared across proxy instances.
+//!
+//! Default values are sourced from `crate::constants`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{
+    DEFAULT_IP_BAN_DURATION_SECS, DEFAULT_IP_BAN_MAX_CLIENT_ERRORS,
+    DEFAULT_IP_BAN_MAX_RATE_LIMITED, DEFAULT_IP_BAN_MAX_TRACKED_IPS, DEFAULT_IP_BAN_REDIS_KEY,
+    DEFAULT_IP_BAN_SYNC_INTERVAL_SECS, DEFAULT_IP_BAN_WINDOW_SECS,
+};
+use crate::security::IpRange;
+
+fn default_window_secs() -> u64 {
+    DEFAULT_IP_BAN_WINDOW_SECS
+}
+
+fn default_max_client_errors() -> u32 {
+    DEFAULT_IP_BAN_MAX_CLIENT_ERRORS
+}
+
+fn default_max_rate_limited() -> u32 {
+    DEFAULT_IP_BAN_MAX_RATE_LIMITED
+}
+
+fn default_ban_duration_secs() -> u64 {
+    DEFAULT_IP_BAN_DURATION_SECS
+}
+
+fn default_max_tracked_ips() -> usize {
+    DEFAULT_IP_BAN_MAX_TRACKED_IPS
+}
+
+fn default_redis_key() -> String {
+    DEFAULT_IP_BAN_REDIS_KEY.to_string()
+}
+
+fn default_sync_interval_secs() -> u64 {
+    DEFAULT_IP_BAN_SYNC_INTERVAL_SECS
+}
+
+/// Automatic IP banning configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpBanConfig {
+    /// Enable automatic banning (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Window for counting error responses in seconds (default: 60)
+    #[serde(default = "default_window_secs")]
+    pub window_secs: u64,
+    /// Client errors (4xx other than 429) per window that trigger a ban; 0 disables (default: 100)
+    #[serde(default = "default_max_client_errors")]
+    pub max_client_errors: u32,
+    /// Rate-limited responses (429) per window that trigger a ban; 0 disables (default: 50)
+    #[serde(default = "default_max_rate_limited")]
+    pub max_rate_limited: u32,
+    /// How long a ban lasts in seconds (default: 600)
+    #[serde(default = "default_ban_duration_secs")]
+    pub ban_duration_secs: u64,
+    /// Maximum IPs tracked for error counting (default: 100000)
+    #[serde(default = "default_max_tracked_ips")]
+    pub max_tracked_ips: usize,
+    /// IPs or CIDR ranges that are never banned (e.g. internal health checkers)
+    #[serde(default)]
+    pub exempt: Vec<String>,
+    /// Share bans across instances through Redis (default: in-memory only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub redis: Option<IpBanRedisConfig>,
+}
+
+impl Default for IpBanConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window_secs: default_window_secs(),
+            max_client_errors: default_max_client_errors(),
+            max_rate_limited: default_max_rate_limited(),
+            ban_duration_secs: default_ban_duration_secs(),
+            max_tracked_ips: default_max_tracked_ips(),
+            exempt: Vec::new(),
+            redis: None,
+        }
+    }
+}
+
+/// Redis shared state for IP bans
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpBanRedisConfig {
+    /// Redis connection URL (e.g., "redis://localhost:6379")
+    pub url: String,
+    /// Sorted set holding banned IPs scored by expiry (default: "yatagarasu:ip_bans")
+    #[serde(default = "default_redis_key")]
+    pub key: String,
+    /// How often shared bans are pulled from Redis in seconds (default: 5)
+    #[serde(default = "default_sync_interval_secs")]
+    pub sync_interval_secs: u64,
+}
+
+impl IpBanConfig {
+    /// Validate thresholds, exemptions and Redis settings
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.window_secs == 0 {
+            return Err("server.ip_ban.window_secs must be greater than 0".to_string());
+        }
+        if self.ban_duration_secs == 0 {
+            return Err("server.ip_ban.ban_duration_secs must be greater than 0".to_string());
+        }
+        if self.max_client_errors == 0 && self.max_rate_limited == 0 {
+            return Err(
+                "server.ip_ban: at least one of max_client_errors or max_rate_limited must be set"
+                    .to_string(),
+            );
+        }
+        if self.max_tracked_ips == 0 {
+            return Err("server.ip_ban.max_tracked_ips must be greater than 0".to_string());
+        }
+        for entry in &self.exempt {
+            IpRange::parse(entry).map_err(|e| format!("server.ip_ban.exempt: {}", e))?;
+        }
+        if let Some(redis) = &self.redis {
+            if redis.url.trim().is_empty() {
+                return Err("server.ip_ban.redis.url cannot be empty".to_string());
+            }
+            if redis.key.trim().is_empty() {
+                return Err("server.ip_ban.redis.key cannot be empty".to_string());
+            }
+            if redis.sync_interval_secs == 0 {
+                return Err(
+                    "server.ip_ban.redis.sync_interval_secs must be greater than 0".to_string(),
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ip_ban_config_defaults() {
+        let config: IpBanConfig = serde_yaml::from_str("enabled: true").unwrap();
+        assert_eq!(config.window_secs, 60);
+        assert_eq!(config.max_client_errors, 100);
+        assert_eq!(config.max_rate_limited, 50);
+        assert_eq!(config.ban_duration_secs, 600);
+        assert!(config.redis.is_none());
+        assert!(config.validate().is_ok());
+
+        let config: IpBanConfig =
+            serde_yaml::from_str("enabled: true\nredis:\n  url: redis://localhost:6379").unwrap();
+        let redis = config.redis.unwrap();
+        assert_eq!(redis.key, "yatagarasu:ip_bans");
+        assert_eq!(redis.sync_interval_secs, 5);
+    }
+
+    #[test]
+    fn test_ip_ban_config_validate() {
+        // Disabled config is never rejected
+        let config = IpBanConfig {
+            window_secs: 0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_ok());
+
+        let enabled = IpBanConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        assert!(IpBanConfig {
+            max_client_errors: 0,
+            max_rate_limited: 0,
+            ..enabled.clone()
+        }
+        .validate()
+        .is_err());
+        assert!(IpBanConfig {
+            ban_duration_secs: 0,
+            ..enabled.clone()
+        }
+        .validate()
+        .is_err());
+        assert!(IpBanConfig {
+            exempt: vec!["10.0.0.0/8".to_string(), "::1".to_string()],
+            ..enabled.clone()
+        }
+        .validate()
+        .is_ok());
+        assert!(IpBanConfig {
+            exempt: vec!["10.0.0.0/99".to_string()],
+            ..enabled.clone()
+        }
+        .validate()
+        .unwrap_err()
+        .contains("exempt"));
+        assert!(IpBanConfig {
+            redis: Some(IpBanRedisConfig {
+                url: " ".to_string(),
+                key: default_redis_key(),
+                sync_interval_secs: 5,
+            }),
+            ..enabled
+        }
+        .validate()
+        .is_err());
+    }
+}
@@ -2,10 +2,14 @@
 //!
 //! This module defines JWT authentication configuration including:
 //! - Single-key and multi-key (rotation) support
-//! - HMAC (HS256/384/512) and asymmetric (RS256, ES256) algorithms
+//! - HMAC (HS256/384/512) and asymmetric (RS256/384/512, ES256/384, EdDSA) algorithms
+//! - An `alg` allowlist that rejects tokens signed with any other algorithm
 //! - Token extraction from headers, query params, or custom sources
+//! - Issuer (`iss`) and audience (`aud`) checks
 //! - Claim validation rules with various operators
 //! - JWKS (JSON Web Key Set) URL support for dynamic key fetching
+//! - Optional caching of verified tokens (`token_cache`)
+//! - A `jti`/`sub` revocation list loaded from a file or Redis set (`revocation`)
 //!
 //! # Key Resolution Order
 //!
@@ -19,22 +23,55 @@
 //!
 //! 3. **Legacy single-key fields** - For simple single-key configurations:
 //!    - `secret` - HMAC secret for HS256/HS384/HS512 algorithms
+//!    - `secret_file` - File holding the HMAC secret (takes precedence over `secret`)
 //!    - `rsa_public_key_path` - RSA public key PEM file for RS256/RS384/RS512
 //!    - `ecdsa_public_key_path` - ECDSA public key PEM file for ES256/ES384
+//!    - `eddsa_public_key_path` - Ed25519 public key PEM file for EdDSA
+//!
+//! A bucket's `auth` block can name its own `algorithm` and `public_key_path`,
+//! replacing these keys for that bucket's tokens, and its own `issuer`,
+//! `audience` and additional `claims`.
 //!
 //! Multiple key sources can be configured simultaneously; the first matching key wins.
 //!
 //! # Validation Requirements
 //!
 //! When `enabled: true`, the following are required:
-//! - `secret` must be non-empty for HMAC algorithms (validated in [`Config::validate()`])
+//! - `secret` (or `secret_file`) must be non-empty for HMAC algorithms (validated in [`Config::validate()`])
+//! - The public key path matching an RS*, ES* or EdDSA `algorithm` must be set
 //! - At least one `token_sources` entry must be configured
-//! - `algorithm` must be a supported algorithm: HS256, HS384, HS512
+//! - `algorithm` and every `allowed_algorithms` entry must be in [`SUPPORTED_ALGORITHMS`]
+//!
+//! With `jwks_url` set, neither a secret nor an HMAC `algorithm` is required:
+//! each JWKS key carries its own algorithm (RS*/ES*/EdDSA).
+//!
+//! # Algorithm Allowlist
+//!
+//! A token whose header `alg` is not accepted is rejected before any key is
+//! tried. `allowed_algorithms` sets the list explicitly; when it is empty the
+//! accepted algorithms are those of the configured keys (`algorithm` and
+//! `keys[].algorithm`), or every asymmetric algorithm with `jwks_url`.
 //!
 //! [`Config::validate()`]: super::Config::validate
 
 use serde::{Deserialize, Serialize};
 
+use crate::constants::{
+    DEFAULT_JWT_REVOCATION_REDIS_KEY, DEFAULT_JWT_REVOCATION_RELOAD_INTERVAL_SECS,
+    DEFAULT_JWT_TOKEN_CACHE_MAX_ENTRIES, DEFAULT_JWT_TOKEN_CACHE_TTL_SECS,
+};
+
+/// Algorithms accepted in `algorithm`, `keys[].algorithm` and `allowed_algorithms`
+pub const SUPPORTED_ALGORITHMS: &[&str] = &[
+    "HS256", "HS384", "HS512", "RS256", "RS384", "RS512", "ES256", "ES384", "EdDSA",
+];
+
+/// Public-key algorithms (usable with PEM keys and JWKS)
+pub const ASYMMETRIC_ALGORITHMS: &[&str] = &["RS256", "RS384", "RS512", "ES256", "ES384", "EdDSA"];
+
+/// Operators supported in claim rules
+pub const CLAIM_OPERATORS: &[&str] = &["equals", "in", "contains", "gt", "lt", "gte", "lte"];
+
 /// Individual JWT key configuration for multi-key support
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JwtKey {
@@ -45,7 +82,7 @@ pub struct JwtKey {
     /// Secret for HS256/HS384/HS512 algorithms
     #[serde(default)]
     pub secret: Option<String>,
-    /// Path to public key PEM file for RS256/RS384/RS512/ES256/ES384 algorithms
+    /// Path to public key PEM file for RS256/RS384/RS512/ES256/ES384/EdDSA algorithms
     #[serde(default)]
     pub path: Option<String>,
 }
@@ -64,11 +101,15 @@ pub struct JwtConfig {
     /// when `enabled: true`. Always provide a non-empty secret for HMAC algorithms.
     #[serde(default)]
     pub secret: String,
+    /// File holding the HMAC secret; takes precedence over `secret` and is
+    /// re-read every `key_reload_interval_secs` so it can rotate without a reload
+    #[serde(default)]
+    pub secret_file: Option<String>,
     /// Algorithm for single-key configuration.
     ///
-    /// Required field. Supported values: `HS256`, `HS384`, `HS512`.
-    /// For RSA/ECDSA, set this to the expected algorithm (RS256, ES256, etc.)
-    /// and provide the corresponding key path.
+    /// Required field. Supported values: see [`SUPPORTED_ALGORITHMS`].
+    /// For RSA/ECDSA/EdDSA, set this to the expected algorithm (RS256, ES256,
+    /// EdDSA, etc.) and provide the corresponding key path.
     pub algorithm: String,
     /// Path to RSA public key PEM file for RS256/RS384/RS512 algorithms
     #[serde(default)]
@@ -76,6 +117,18 @@ pub struct JwtConfig {
     /// Path to ECDSA public key PEM file for ES256/ES384 algorithms
     #[serde(default)]
     pub ecdsa_public_key_path: Option<String>,
+    /// Path to Ed25519 public key PEM file for the EdDSA algorithm
+    #[serde(default)]
+    pub eddsa_public_key_path: Option<String>,
+    /// Token `alg` values accepted (default: the algorithms of the configured keys)
+    #[serde(default)]
+    pub allowed_algorithms: Vec<String>,
+    /// Required `iss` claim (default: not checked)
+    #[serde(default)]
+    pub issuer: Option<String>,
+    /// Accepted `aud` values; the token must carry one of them (default: not checked)
+    #[serde(default)]
+    pub audience: Vec<String>,
     #[serde(default)]
     pub token_sources: Vec<TokenSource>,
     #[serde(default)]
@@ -93,6 +146,147 @@ pub struct JwtConfig {
     /// JWKS cache refresh interval in seconds (default: 3600 = 1 hour)
     #[serde(default)]
     pub jwks_refresh_interval_secs: Option<u64>,
+    /// How often secret and PEM key files are re-read (default: 60)
+    #[serde(default)]
+    pub key_reload_interval_secs: Option<u64>,
+    /// Cache of tokens whose signature was already verified
+    #[serde(default)]
+    pub token_cache: TokenCacheConfig,
+    /// Revoked `jti`/`sub` values rejected after signature validation
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revocation: Option<RevocationConfig>,
+}
+
+fn default_token_cache_ttl_secs() -> u64 {
+    DEFAULT_JWT_TOKEN_CACHE_TTL_SECS
+}
+
+fn default_token_cache_max_entries() -> usize {
+    DEFAULT_JWT_TOKEN_CACHE_MAX_ENTRIES
+}
+
+/// Verified-token cache settings
+///
+/// A cached token skips signature verification until its `exp` or `ttl_secs`,
+/// whichever comes first. Issuer, audience and claim rules are still checked
+/// on every request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenCacheConfig {
+    /// Cache verified tokens (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Longest time a token is served from the cache (default: 60)
+    #[serde(default = "default_token_cache_ttl_secs")]
+    pub ttl_secs: u64,
+    /// Most tokens kept at once (default: 10000)
+    #[serde(default = "default_token_cache_max_entries")]
+    pub max_entries: usize,
+}
+
+impl Default for TokenCacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            ttl_secs: DEFAULT_JWT_TOKEN_CACHE_TTL_SECS,
+            max_entries: DEFAULT_JWT_TOKEN_CACHE_MAX_ENTRIES,
+        }
+    }
+}
+
+fn default_revocation_reload_interval_secs() -> u64 {
+    DEFAULT_JWT_REVOCATION_RELOAD_INTERVAL_SECS
+}
+
+fn default_revocation_redis_key() -> String {
+    DEFAULT_JWT_REVOCATION_REDIS_KEY.to_string()
+}
+
+/// Token revocation list settings
+///
+/// Entries are `jti:<id>` or `sub:<subject>`, one per line in `file` or one
+/// per member of the Redis set; a bare value is treated as a `jti`. Both
+/// sources are re-read every `reload_interval_secs` and merged.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevocationConfig {
+    /// File listing revoked entries (`#` starts a comment)
+    #[serde(default)]
+    pub file: Option<String>,
+    /// Redis set listing revoked entries
+    #[serde(default)]
+    pub redis: Option<RevocationRedisConfig>,
+    /// How often the sources are re-read in seconds (default: 30)
+    #[serde(default = "default_revocation_reload_interval_secs")]
+    pub reload_interval_secs: u64,
+}
+
+/// Redis source for the revocation list
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RevocationRedisConfig {
+    /// Redis connection URL (e.g., "redis://localhost:6379")
+    pub url: String,
+    /// Set holding revoked entries (default: "yatagarasu:revoked_tokens")
+    #[serde(default = "default_revocation_redis_key")]
+    pub key: String,
+}
+
+impl RevocationConfig {
+    /// Validate sources and reload interval
+    pub fn validate(&self) -> Result<(), String> {
+        if self.file.is_none() && self.redis.is_none() {
+            return Err("jwt.revocation requires a file or redis source".to_string());
+        }
+        if self.file.as_ref().is_some_and(|f| f.trim().is_empty()) {
+            return Err("jwt.revocation.file cannot be empty".to_string());
+        }
+        if let Some(redis) = &self.redis {
+            if redis.url.trim().is_empty() {
+                return Err("jwt.revocation.redis.url cannot be empty".to_string());
+            }
+            if redis.key.trim().is_empty() {
+                return Err("jwt.revocation.redis.key cannot be empty".to_string());
+            }
+        }
+        if self.reload_interval_secs == 0 {
+            return Err("jwt.revocation.reload_interval_secs must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+impl JwtConfig {
+    /// Token `alg` values accepted by this configuration
+    ///
+    /// `allowed_algorithms` when set; otherwise the algorithms of the
+    /// configured keys, or every asymmetric algorithm with `jwks_url`.
+    pub fn accepted_algorithms(&self) -> Vec<String> {
+        if !self.allowed_algorithms.is_empty() {
+            return self.allowed_algorithms.clone();
+        }
+        let mut accepted: Vec<String> = if self.jwks_url.is_some() {
+            ASYMMETRIC_ALGORITHMS
+                .iter()
+                .map(|a| a.to_string())
+                .collect()
+        } else {
+            vec![self.algorithm.clone()]
+        };
+        for key in &self.keys {
+            if !accepted.contains(&key.algorithm) {
+                accepted.push(key.algorithm.clone());
+            }
+        }
+        accepted
+    }
+
+    /// Public key path configured for an RS*, ES* or EdDSA algorithm
+    pub fn public_key_path(&self, algorithm: &str) -> Option<&str> {
+        match algorithm {
+            "RS256" | "RS384" | "RS512" => self.rsa_public_key_path.as_deref(),
+            "ES256" | "ES384" => self.ecdsa_public_key_path.as_deref(),
+            "EdDSA" => self.eddsa_public_key_path.as_deref(),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -357,4 +551,50 @@ ecdsa_public_key_path: "/etc/keys/ec_public.pem"
             Some("/etc/keys/ec_public.pem".to_string())
         );
     }
+
+    #[test]
+    fn test_jwt_config_accepted_algorithms() {
+        let config: JwtConfig = serde_yaml::from_str(
+            "enabled: true\nalgorithm: EdDSA\neddsa_public_key_path: /etc/keys/ed.pem",
+        )
+        .unwrap();
+        assert_eq!(config.accepted_algorithms(), vec!["EdDSA".to_string()]);
+        assert_eq!(config.public_key_path("EdDSA"), Some("/etc/keys/ed.pem"));
+
+        let jwks: JwtConfig = serde_yaml::from_str(
+            "enabled: true\nalgorithm: RS256\njwks_url: https://auth.example.com/jwks",
+        )
+        .unwrap();
+        assert!(!jwks.accepted_algorithms().contains(&"HS256".to_string()));
+
+        let pinned = JwtConfig {
+            allowed_algorithms: vec!["ES256".to_string()],
+            ..jwks
+        };
+        assert_eq!(pinned.accepted_algorithms(), vec!["ES256".to_string()]);
+    }
+
+    #[test]
+    fn test_revocation_config_defaults_and_validation() {
+        let yaml = r#"
+redis:
+  url: "redis://localhost:6379"
+"#;
+        let config: RevocationConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.reload_interval_secs, 30);
+        assert_eq!(
+            config.redis.as_ref().unwrap().key,
+            "yatagarasu:revoked_tokens"
+        );
+        assert!(config.validate().is_ok());
+
+        let no_source: RevocationConfig = serde_yaml::from_str("reload_interval_secs: 5").unwrap();
+        assert!(no_source.validate().is_err());
+
+        let zero_interval = RevocationConfig {
+            reload_interval_secs: 0,
+            ..config
+        };
+        assert!(zero_interval.validate().is_err());
+    }
 }
@@ -0,0 +1,56 @@
+//! Bucket listing (ListObjectsV2) through the proxy.
+//!
+//! Listing exposes every key under the bucket, so it is off unless the
+//! bucket has a `listing` section. With `json: true`, clients that send
+//! `Accept: application/json` get the listing as JSON instead of S3's XML.
+
+use serde::{Deserialize, Serialize};
+
+use crate::s3::list::ListingFormat;
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Per-bucket listing configuration (YAML format)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ListingConfig {
+    /// Allow key-less GET/HEAD as ListObjectsV2 (default: true when the section is present)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Translate listings to JSON for clients accepting `application/json` (default: false)
+    #[serde(default)]
+    pub json: bool,
+}
+
+impl ListingConfig {
+    /// Response format for a client's `Accept` header
+    pub fn format(&self, accept: Option<&str>) -> ListingFormat {
+        let wants_json = accept.is_some_and(|a| a.contains("application/json"));
+        if self.json && wants_json {
+            ListingFormat::Json
+        } else {
+            ListingFormat::Xml
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_listing_format_negotiation() {
+        let config: ListingConfig = serde_yaml::from_str("json: true").unwrap();
+        assert!(config.enabled);
+        assert_eq!(
+            config.format(Some("application/json, text/plain, */*")),
+            ListingFormat::Json
+        );
+        assert_eq!(config.format(Some("application/xml")), ListingFormat::Xml);
+        assert_eq!(config.format(None), ListingFormat::Xml);
+
+        let config: ListingConfig = serde_yaml::from_str("{}").unwrap();
+        assert_eq!(config.format(Some("application/json")), ListingFormat::Xml);
+    }
+}
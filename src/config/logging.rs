@@ -0,0 +1,187 @@
+//! Per-bucket logging configuration.
+//!
+//! This module defines logging overrides that apply to every log line emitted
+//! for requests routed to a bucket:
+//! - Log level override (e.g. silence per-request `info` lines for a noisy bucket)
+//! - Query parameter redaction (e.g. tokenized URLs that must never be logged)
+//! - Header redaction (e.g. `Referer` values that embed signed URLs)
+//!
+//! Redaction reuses the helpers from [`crate::audit`] so that log lines and
+//! audit entries are redacted the same way.
+
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+use tracing::level_filters::LevelFilter;
+use tracing::Level;
+
+use crate::audit::{redact_headers, redact_query_params};
+
+/// Valid values for the `level` field.
+const VALID_LEVELS: &[&str] = &["off", "error", "warn", "info", "debug", "trace"];
+
+/// Per-bucket logging configuration (YAML format)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BucketLoggingConfig {
+    /// Maximum verbosity for log lines about this bucket (default: inherit global level)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<String>,
+    /// Query parameter names whose values are replaced with `[REDACTED]`
+    #[serde(default)]
+    pub redact_query_params: Vec<String>,
+    /// Header names whose values are replaced with `[REDACTED]`
+    #[serde(default)]
+    pub redact_headers: Vec<String>,
+}
+
+impl BucketLoggingConfig {
+    /// Validate the logging configuration for the given bucket.
+    pub fn validate(&self, bucket_name: &str) -> Result<(), String> {
+        if let Some(level) = &self.level {
+            if !VALID_LEVELS.contains(&level.to_lowercase().as_str()) {
+                return Err(format!(
+                    "Bucket '{}': Invalid logging level '{}'. Supported levels: {}",
+                    bucket_name,
+                    level,
+                    VALID_LEVELS.join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the configured level filter, or `None` to inherit the global level.
+    pub fn level_filter(&self) -> Option<LevelFilter> {
+        self.level
+            .as_deref()
+            .and_then(|l| LevelFilter::from_str(l).ok())
+    }
+
+    /// Check whether a log line at `level` should be emitted for this bucket.
+    pub fn is_enabled(&self, level: Level) -> bool {
+        match self.level_filter() {
+            Some(filter) => level <= filter,
+            None => true,
+        }
+    }
+
+    /// Redact configured query parameters from a path-and-query string.
+    pub fn redact_uri(&self, uri: &str) -> String {
+        if self.redact_query_params.is_empty() {
+            return uri.to_string();
+        }
+        let params: Vec<&str> = self
+            .redact_query_params
+            .iter()
+            .map(String::as_str)
+            .collect();
+        redact_query_params(uri, &params)
+    }
+
+    /// Redact a single header value if the header is configured for redaction.
+    ///
+    /// Other values still have configured query parameters redacted, since
+    /// headers such as `Referer` carry full URLs.
+    pub fn redact_header_value(&self, name: &str, value: &str) -> String {
+        if !self
+            .redact_headers
+            .iter()
+            .any(|h| h.eq_ignore_ascii_case(name))
+        {
+            return self.redact_uri(value);
+        }
+        let sensitive: Vec<&str> = self.redact_headers.iter().map(String::as_str).collect();
+        redact_headers(&[(name, value)], &sensitive)
+            .pop()
+            .map(|(_, v)| v)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_logging_config_defaults() {
+        let config: BucketLoggingConfig = serde_yaml::from_str("{}").unwrap();
+
+        assert!(config.level.is_none());
+        assert!(config.redact_query_params.is_empty());
+        assert!(config.redact_headers.is_empty());
+        assert!(config.is_enabled(Level::TRACE));
+    }
+
+    #[test]
+    fn test_bucket_logging_config_level_override() {
+        let yaml = r#"
+level: warn
+"#;
+        let config: BucketLoggingConfig = serde_yaml::from_str(yaml).unwrap();
+
+        assert!(config.is_enabled(Level::ERROR));
+        assert!(config.is_enabled(Level::WARN));
+        assert!(!config.is_enabled(Level::INFO));
+        assert!(!config.is_enabled(Level::DEBUG));
+    }
+
+    #[test]
+    fn test_bucket_logging_config_off_disables_everything() {
+        let config = BucketLoggingConfig {
+            level: Some("off".to_string()),
+            ..Default::default()
+        };
+
+        assert!(!config.is_enabled(Level::ERROR));
+    }
+
+    #[test]
+    fn test_bucket_logging_config_validate_rejects_unknown_level() {
+        let config = BucketLoggingConfig {
+            level: Some("verbose".to_string()),
+            ..Default::default()
+        };
+
+        let err = config.validate("tokens").unwrap_err();
+        assert!(err.contains("tokens"));
+        assert!(err.contains("verbose"));
+    }
+
+    #[test]
+    fn test_bucket_logging_config_redacts_query_params() {
+        let yaml = r#"
+redact_query_params:
+  - token
+  - X-Amz-Signature
+"#;
+        let config: BucketLoggingConfig = serde_yaml::from_str(yaml).unwrap();
+
+        let redacted = config.redact_uri("/files/a.pdf?token=secret&x-amz-signature=abc&v=2");
+        assert_eq!(
+            redacted,
+            "/files/a.pdf?token=[REDACTED]&x-amz-signature=[REDACTED]&v=2"
+        );
+    }
+
+    #[test]
+    fn test_bucket_logging_config_redacts_headers() {
+        let config = BucketLoggingConfig {
+            redact_headers: vec!["Referer".to_string()],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            config.redact_header_value("referer", "https://x/?token=1"),
+            "[REDACTED]"
+        );
+        assert_eq!(config.redact_header_value("user-agent", "curl"), "curl");
+
+        let config = BucketLoggingConfig {
+            redact_query_params: vec!["token".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            config.redact_header_value("referer", "https://x/a?token=1"),
+            "https://x/a?token=[REDACTED]"
+        );
+    }
+}
@@ -0,0 +1,177 @@
+//! Maintenance mode configuration.
+//!
+//! While maintenance mode is on, data-plane requests get a 503 with a
+//! `Retry-After` header instead of reaching S3. Health, readiness, metrics and
+//! admin endpoints keep working, as do any configured `allowed_paths`. The
+//! mode can be switched at runtime with `POST /admin/maintenance/on|off`.
+//!
+//! Default values are sourced from `crate::constants`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{
+    DEFAULT_MAINTENANCE_CONTENT_TYPE, DEFAULT_MAINTENANCE_MESSAGE,
+    DEFAULT_MAINTENANCE_RETRY_AFTER_SECS,
+};
+use crate::error::ErrorCode;
+
+fn default_message() -> String {
+    DEFAULT_MAINTENANCE_MESSAGE.to_string()
+}
+
+fn default_retry_after_secs() -> u64 {
+    DEFAULT_MAINTENANCE_RETRY_AFTER_SECS
+}
+
+fn default_content_type() -> String {
+    DEFAULT_MAINTENANCE_CONTENT_TYPE.to_string()
+}
+
+/// Paths that are never blocked by maintenance mode
+const ALWAYS_ALLOWED_PREFIXES: &[&str] = &["/health", "/ready", "/metrics", "/admin/"];
+
+/// Maintenance mode configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaintenanceConfig {
+    /// Start in maintenance mode (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Path prefixes still served during maintenance (e.g. "/status/")
+    #[serde(default)]
+    pub allowed_paths: Vec<String>,
+    /// Message shown to clients (default: "Service is undergoing maintenance")
+    #[serde(default = "default_message")]
+    pub message: String,
+    /// Retry-After header value in seconds (default: 300)
+    #[serde(default = "default_retry_after_secs")]
+    pub retry_after_secs: u64,
+    /// Custom response body; `{{message}}` and `{{retry_after}}` are substituted
+    /// (default: JSON error body)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Content-Type of the custom template (default: text/html; charset=utf-8)
+    #[serde(default = "default_content_type")]
+    pub content_type: String,
+}
+
+impl Default for MaintenanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            allowed_paths: Vec::new(),
+            message: default_message(),
+            retry_after_secs: default_retry_after_secs(),
+            template: None,
+            content_type: default_content_type(),
+        }
+    }
+}
+
+impl MaintenanceConfig {
+    /// Validate allowed paths and template settings
+    pub fn validate(&self) -> Result<(), String> {
+        for path in &self.allowed_paths {
+            if !path.starts_with('/') {
+                return Err(format!(
+                    "server.maintenance.allowed_paths entry '{}' must start with '/'",
+                    path
+                ));
+            }
+        }
+        if self.template.is_some() && self.content_type.trim().is_empty() {
+            return Err("server.maintenance.content_type cannot be empty".to_string());
+        }
+        if self.content_type.contains(['\r', '\n']) {
+            return Err("server.maintenance.content_type must be a single line".to_string());
+        }
+        Ok(())
+    }
+
+    /// Check if a request path is still served during maintenance
+    pub fn is_allowed_path(&self, path: &str) -> bool {
+        ALWAYS_ALLOWED_PREFIXES
+            .iter()
+            .any(|prefix| path.starts_with(prefix))
+            || self
+                .allowed_paths
+                .iter()
+                .any(|prefix| path.starts_with(prefix.as_str()))
+    }
+
+    /// Content-Type and body of the maintenance response
+    pub fn render(&self) -> (String, String) {
+        match &self.template {
+            Some(template) => (
+                self.content_type.clone(),
+                template
+                    .replace("{{message}}", &self.message)
+                    .replace("{{retry_after}}", &self.retry_after_secs.to_string()),
+            ),
+            None => (
+                "application/json".to_string(),
+                serde_json::json!({
+                    "error": "Service Unavailable",
+                    "code": ErrorCode::Maintenance.as_str(),
+                    "message": self.message,
+                    "status": 503
+                })
+                .to_string(),
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_maintenance_config_defaults() {
+        let config: MaintenanceConfig = serde_yaml::from_str("{}").unwrap();
+        assert!(!config.enabled);
+        assert_eq!(config.retry_after_secs, 300);
+        assert!(config.template.is_none());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_maintenance_config_validate() {
+        let config = MaintenanceConfig {
+            allowed_paths: vec!["status/".to_string()],
+            ..Default::default()
+        };
+        assert!(config.validate().unwrap_err().contains("status/"));
+    }
+
+    #[test]
+    fn test_maintenance_allowed_paths() {
+        let config = MaintenanceConfig {
+            allowed_paths: vec!["/public/status".to_string()],
+            ..Default::default()
+        };
+        assert!(config.is_allowed_path("/health"));
+        assert!(config.is_allowed_path("/ready"));
+        assert!(config.is_allowed_path("/metrics"));
+        assert!(config.is_allowed_path("/admin/maintenance"));
+        assert!(config.is_allowed_path("/public/status.json"));
+        assert!(!config.is_allowed_path("/public/photo.jpg"));
+        assert!(!config.is_allowed_path("/administrator/file"));
+    }
+
+    #[test]
+    fn test_maintenance_render() {
+        let (content_type, body) = MaintenanceConfig::default().render();
+        assert_eq!(content_type, "application/json");
+        assert!(body.contains("Service is undergoing maintenance"));
+
+        let config = MaintenanceConfig {
+            template: Some("<h1>{{message}}</h1><p>Back in {{retry_after}}s</p>".to_string()),
+            message: "Migrating storage".to_string(),
+            retry_after_secs: 60,
+            ..Default::default()
+        };
+        let (content_type, body) = config.render();
+        assert_eq!(content_type, "text/html; charset=utf-8");
+        assert_eq!(body, "<h1>Migrating storage</h1><p>Back in 60s</p>");
+    }
+}
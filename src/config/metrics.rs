@@ -0,0 +1,78 @@
+//! Metrics label cardinality limits.
+//!
+//! Per-bucket and per-replica metrics get one series per label value. With
+//! buckets registered dynamically that set is unbounded and can overwhelm
+//! Prometheus. Once a label has `max_*_labels` distinct values, further
+//! values are recorded under `other` and the overflow is reported in
+//! `yatagarasu_metrics_label_overflow_total`.
+//!
+//! Default values are sourced from `crate::constants`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{DEFAULT_METRICS_MAX_BUCKET_LABELS, DEFAULT_METRICS_MAX_REPLICA_LABELS};
+
+fn default_max_bucket_labels() -> usize {
+    DEFAULT_METRICS_MAX_BUCKET_LABELS
+}
+
+fn default_max_replica_labels() -> usize {
+    DEFAULT_METRICS_MAX_REPLICA_LABELS
+}
+
+/// Label cardinality limits for exported metrics
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricsCardinalityConfig {
+    /// Distinct `bucket` label values before folding into `other` (default: 1000)
+    #[serde(default = "default_max_bucket_labels")]
+    pub max_bucket_labels: usize,
+    /// Distinct bucket/replica pairs before folding into `other` (default: 2000)
+    #[serde(default = "default_max_replica_labels")]
+    pub max_replica_labels: usize,
+}
+
+impl Default for MetricsCardinalityConfig {
+    fn default() -> Self {
+        Self {
+            max_bucket_labels: default_max_bucket_labels(),
+            max_replica_labels: default_max_replica_labels(),
+        }
+    }
+}
+
+impl MetricsCardinalityConfig {
+    /// Validate label limits
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_bucket_labels == 0 {
+            return Err(
+                "server.metrics_cardinality.max_bucket_labels must be greater than 0".to_string(),
+            );
+        }
+        if self.max_replica_labels == 0 {
+            return Err(
+                "server.metrics_cardinality.max_replica_labels must be greater than 0".to_string(),
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metrics_cardinality_config() {
+        let config: MetricsCardinalityConfig = serde_yaml::from_str("{}").unwrap();
+        assert_eq!(config.max_bucket_labels, DEFAULT_METRICS_MAX_BUCKET_LABELS);
+        assert_eq!(
+            config.max_replica_labels,
+            DEFAULT_METRICS_MAX_REPLICA_LABELS
+        );
+        assert!(config.validate().is_ok());
+
+        let config: MetricsCardinalityConfig =
+            serde_yaml::from_str("max_bucket_labels: 0").unwrap();
+        assert!(config.validate().is_err());
+    }
+}
@@ -2,20 +2,62 @@
 //!
 //! This module provides YAML-based configuration loading with:
 //! - Environment variable substitution (`${VAR_NAME}`)
+//! - Secrets from HashiCorp Vault (`vault:<path>#<field>`)
 //! - Comprehensive validation
 //! - Hot reload support (via generation tracking)
 //!
 //! # Module Organization
 //!
 //! Configuration is split into focused submodules:
+//! - [`admin`] - Admin API method limits, CSRF checks and token IP binding
+//! - [`alias`] - Additional path prefixes and version tags for a bucket
+//! - [`analytics`] - Key popularity tracking and access statistics export
+//! - [`access_grants`] - Time-boxed read grants created through the admin API
 //! - [`audit`] - Audit logging (file, syslog, S3 export)
-//! - [`authorization`] - OPA/OpenFGA integration
+//! - [`authorization`] - OPA/OpenFGA integration and claim-to-path rules
+//! - [`batch`] - Batch GET API
 //! - [`bucket`] - Per-bucket S3 and routing config
-//! - [`circuit_breaker`] - Backend resilience
+//! - [`bulkhead`] - Per-bucket upstream concurrency limits
+//! - [`cache_override`] - Per-request cache override header for trusted clients
+//! - [`circuit_breaker`] - Backend resilience, failure classification and origin probes
+//! - [`content_type`] - Per-bucket Content-Type overrides
+//! - [`credentials`] - Upstream S3 credentials from instance metadata, ECS or web identity
+//! - [`cors`] - Per-bucket CORS policy
+//! - [`degraded`] - Serve-from-cache-only mode while origins are down
+//! - [`deprecation`] - Deprecation/Sunset headers for legacy routes
+//! - [`disconnect`] - Client abort handling
+//! - [`endpoint_access`] - Token/IP restrictions for health and metrics endpoints
+//! - [`grpc`] - Optional gRPC control plane listener and mTLS
+//! - [`hotlink`] - Referer/Origin hotlink protection
+//! - [`ip_ban`] - Automatic temporary IP banning
 //! - [`jwt`] - Token authentication
+//! - [`listing`] - Bucket listing (ListObjectsV2) and JSON translation
+//! - [`logging`] - Per-bucket log level and redaction overrides
+//! - [`maintenance`] - Maintenance mode responses and allowlist
+//! - [`metrics`] - Label cardinality limits for exported metrics
+//! - [`origin`] - Non-S3 backends (HTTP and filesystem origins) for a bucket
+//! - [`post_policy`] - Browser form uploads signed with an S3-style POST policy
+//! - [`priority`] - Priority header and reserved concurrency
+//! - [`queue`] - Bounded wait for a concurrency permit
+//! - [`range_abuse`] - Per-client throttling of pathological Range request patterns
 //! - [`rate_limit`] - Request throttling
+//! - [`readiness`] - `/ready` policy (origins, cache, always)
+//! - [`redirect`] - 302 to presigned S3 URLs instead of proxying object bytes
+//! - [`resume`] - Download resumption tokens for large objects
 //! - [`retry`] - Transient failure handling
+//! - [`routes`] - Glob/regex path patterns with S3 key templates
 //! - [`server`] - Server bindings and limits
+//! - [`signed_url`] - Expiring HMAC-signed download URLs
+//! - [`sigv4_auth`] - Incoming AWS SigV4 authentication for S3 SDK clients
+//! - [`size_class`] - Per-bucket cache policy, timeouts and replica by object size
+//! - [`subsystems`] - `--disable` switches that turn subsystems off at startup
+//! - [`signing`] - Per-bucket upstream signing quirks for S3-compatible stores
+//! - [`synthetic`] - Generated-data routes for load balancer and monitoring smoke tests
+//! - [`tls`] - HTTPS listener with ACME-managed certificates, OCSP stapling
+//!   and client certificate authentication
+//! - [`unavailable`] - 503 bodies and Retry-After for overload and origin failures
+//! - [`upload_scan`] - ClamAV scanning of upload bodies
+//! - [`vault`] - `vault:` secret references resolved from HashiCorp Vault
 //!
 //! # Default Values
 //!
@@ -23,32 +65,127 @@
 //! consistency and easy modification. Each submodule documents which
 //! constants it uses.
 
+pub mod access_grants;
+pub mod admin;
+pub mod alias;
+pub mod analytics;
 pub mod audit;
 pub mod authorization;
+pub mod batch;
 pub mod bucket;
+pub mod bulkhead;
+pub mod cache_override;
 pub mod circuit_breaker;
 pub mod coalescing;
+pub mod content_type;
+pub mod cors;
+pub mod credentials;
+pub mod degraded;
+pub mod deprecation;
+pub mod disconnect;
+pub mod early_hints;
+pub mod endpoint_access;
+pub mod etag;
+pub mod grpc;
+pub mod hotlink;
+pub mod ip_ban;
 pub mod jwt;
+pub mod listing;
+pub mod logging;
+pub mod maintenance;
+pub mod metrics;
+pub mod origin;
+pub mod post_policy;
+pub mod priority;
+pub mod queue;
+pub mod range_abuse;
 pub mod rate_limit;
+pub mod readiness;
+pub mod redirect;
+pub mod resume;
 pub mod retry;
+pub mod routes;
 pub mod server;
+pub mod server_timing;
+pub mod signed_url;
+pub mod signing;
+pub mod sigv4_auth;
+pub mod size_class;
+pub mod subsystems;
+pub mod synthetic;
+pub mod tls;
+pub mod unavailable;
+pub mod upload_scan;
+pub mod vault;
 
 // Re-export all types for backward compatibility
+pub use access_grants::{AccessGrantsConfig, AccessGrantsRedisConfig};
+pub use admin::{AdminCsrfConfig, AdminSecurityConfig};
+pub use alias::BucketAliasConfig;
+pub use analytics::{
+    AccessExportConfig, AccessExportFormat, AccessExportS3Config, KeyAnalyticsConfig,
+};
 pub use audit::{
     AuditFileConfig, AuditLogConfig, AuditLogLevel, AuditOutput, AuditS3ExportConfig,
     AuditSyslogConfig, RotationPolicy, SyslogFacility, SyslogProtocol,
 };
-pub use authorization::AuthorizationConfig;
+pub use authorization::{AuthorizationConfig, ClaimPathRule};
+pub use batch::BatchConfig;
 pub use bucket::{AuthConfig, BucketConfig, IpFilterConfig, S3Config, S3Replica};
-pub use circuit_breaker::CircuitBreakerConfigYaml;
+pub use bulkhead::BulkheadConfig;
+pub use cache_override::{CacheOverrideConfig, CacheOverrideMode};
+pub use circuit_breaker::{
+    CircuitBreakerConfigYaml, CircuitBreakerMode, FailureClassificationConfig, OriginProbeConfig,
+};
 pub use coalescing::{CoalescingConfig, CoalescingStrategy};
-pub use jwt::{ClaimRule, JwtConfig, JwtKey, TokenSource};
+pub use content_type::ContentTypeConfig;
+pub use cors::CorsConfig;
+pub use credentials::{CredentialSource, S3CredentialsConfig};
+pub use degraded::DegradedModeConfig;
+pub use deprecation::DeprecationConfig;
+pub use disconnect::ClientDisconnectConfig;
+pub use early_hints::EarlyHintsConfig;
+pub use endpoint_access::{EndpointAccess, EndpointAccessConfig, EndpointAclConfig};
+pub use etag::{EtagRewriteConfig, EtagRewriteMode};
+pub use grpc::{GrpcConfig, GrpcTlsConfig};
+pub use hotlink::{HotlinkAction, HotlinkConfig};
+pub use ip_ban::{IpBanConfig, IpBanRedisConfig};
+pub use jwt::{
+    ClaimRule, JwtConfig, JwtKey, RevocationConfig, RevocationRedisConfig, TokenCacheConfig,
+    TokenSource,
+};
+pub use listing::ListingConfig;
+pub use logging::BucketLoggingConfig;
+pub use maintenance::MaintenanceConfig;
+pub use metrics::MetricsCardinalityConfig;
+pub use origin::{FilesystemOriginConfig, HttpOriginConfig, OriginConfig};
+pub use post_policy::PostPolicyConfig;
+pub use priority::PriorityConfig;
+pub use queue::RequestQueueConfig;
+pub use range_abuse::RangeAbuseConfig;
 pub use rate_limit::{
     BucketRateLimitConfigYaml, GlobalRateLimitConfigYaml, PerIpRateLimitConfigYaml,
     RateLimitConfigYaml,
 };
+pub use readiness::{ReadinessConfig, ReadinessMode};
+pub use redirect::PresignedRedirectConfig;
+pub use resume::ResumeConfig;
 pub use retry::RetryConfigYaml;
-pub use server::{SecurityLimitsConfig, ServerConfig};
+pub use routes::RoutePatternConfig;
+pub use server::{SecurityLimitsConfig, ServerConfig, WorkerConfig};
+pub use server_timing::ServerTimingConfig;
+pub use signed_url::SignedUrlConfig;
+pub use signing::{S3SigningConfig, SignatureVersion};
+pub use sigv4_auth::{SigV4AuthConfig, SigV4Credential};
+pub use size_class::{SizeClassCache, SizeClassConfig};
+pub use subsystems::Subsystem;
+pub use tls::{ClientAuthMode, TlsListenerConfig, TlsVersion};
+pub use unavailable::{
+    RetryAfterStrategy, UnavailableReason, UnavailableResponse, UnavailableResponseConfig,
+    UnavailableResponsesConfig,
+};
+pub use upload_scan::UploadScanConfig;
+pub use vault::{VaultAuthConfig, VaultConfig};
 
 use regex::Regex;
 use serde::{Deserialize, Serialize};
@@ -56,8 +193,10 @@ use std::collections::HashSet;
 use std::path::Path;
 
 use crate::cache::CacheConfig;
+use crate::constants::VAULT_REFERENCE_PREFIX;
 use crate::image_optimizer::ImageConfig;
 use crate::observability::ObservabilityConfig;
+use jwt::{ASYMMETRIC_ALGORITHMS, CLAIM_OPERATORS, SUPPORTED_ALGORITHMS};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -74,6 +213,12 @@ pub struct Config {
     /// Observability configuration (tracing, request logging, slow queries)
     #[serde(default)]
     pub observability: ObservabilityConfig,
+    /// Batch GET API (default: disabled)
+    #[serde(default)]
+    pub batch: BatchConfig,
+    /// Vault server for `vault:` secret references (default: none)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vault: Option<VaultConfig>,
     #[serde(skip)]
     pub generation: u64, // Config version, increments on reload
 }
@@ -100,7 +245,15 @@ impl Config {
             std::env::var(var_name).unwrap() // Safe because we checked above
         });
 
-        let mut config: Config = serde_yaml::from_str(&substituted).map_err(|e| e.to_string())?;
+        // Resolve vault: references (the substituted text may contain them too)
+        let mut config: Config = if substituted.contains(VAULT_REFERENCE_PREFIX) {
+            let mut root: serde_yaml::Value =
+                serde_yaml::from_str(&substituted).map_err(|e| e.to_string())?;
+            vault::resolve_references(&mut root)?;
+            serde_yaml::from_value(root).map_err(|e| e.to_string())?
+        } else {
+            serde_yaml::from_str(&substituted).map_err(|e| e.to_string())?
+        };
         config.generation = 0; // Initialize generation to 0
 
         // Sort replicas by priority (1 = highest priority)
@@ -120,7 +273,14 @@ impl Config {
     }
 
     pub fn validate(&self) -> Result<(), String> {
+        self.server.validate()?;
+        self.observability.event_log.validate()?;
+        if let Some(vault) = &self.vault {
+            vault.validate()?;
+        }
+
         let mut seen_prefixes = HashSet::new();
+        let mut seen_hostnames = HashSet::new();
 
         // Validate each bucket configuration
         for bucket in &self.buckets {
@@ -149,8 +309,64 @@ impl Config {
                 ));
             }
 
-            // Validate S3 configuration (legacy vs replicas mutual exclusivity)
-            bucket.s3.validate(&bucket.name)?;
+            // Aliases share the prefix namespace with every bucket's path_prefix
+            if let Some(version) = &bucket.version {
+                alias::validate_version(&bucket.name, version)?;
+            }
+            for alias in &bucket.aliases {
+                alias.validate(&bucket.name)?;
+                if !seen_prefixes.insert(&alias.path_prefix) {
+                    return Err(format!(
+                        "Duplicate path_prefix '{}' found in bucket '{}' (alias)",
+                        alias.path_prefix, bucket.name
+                    ));
+                }
+            }
+
+            // Host names must be bare and claimed by a single bucket
+            for hostname in &bucket.hostnames {
+                if hostname.is_empty() || hostname.contains(['/', ':', ' ']) {
+                    return Err(format!(
+                        "Bucket '{}': hostname '{}' must be a bare host name (no scheme, port or path)",
+                        bucket.name, hostname
+                    ));
+                }
+                if !seen_hostnames.insert(hostname.to_ascii_lowercase()) {
+                    return Err(format!(
+                        "Duplicate hostname '{}' found in bucket '{}'",
+                        hostname, bucket.name
+                    ));
+                }
+            }
+
+            for route in &bucket.routes {
+                route.validate(&bucket.name)?;
+            }
+            if let Some(upload_scan) = &bucket.upload_scan {
+                upload_scan.validate(&bucket.name)?;
+            }
+            if let Some(deprecation) = &bucket.deprecation {
+                deprecation.validate(&bucket.name)?;
+            }
+
+            // Validate the backend: a non-S3 origin, or S3 (legacy vs replicas mutual exclusivity)
+            if let Some(origin) = &bucket.origin {
+                if bucket.s3.has_legacy_config() || bucket.s3.replicas.is_some() {
+                    return Err(format!(
+                        "Bucket '{}': Cannot use both 's3' and 'origin'. Choose one backend.",
+                        bucket.name
+                    ));
+                }
+                if bucket.listing.as_ref().is_some_and(|l| l.enabled) {
+                    return Err(format!(
+                        "Bucket '{}': listing requires an S3 backend",
+                        bucket.name
+                    ));
+                }
+                origin.validate(&bucket.name)?;
+            } else {
+                bucket.s3.validate(&bucket.name)?;
+            }
 
             // Validate replica set if present (Phase 23: HA Bucket Replication)
             if let Some(replicas) = &bucket.s3.replicas {
@@ -215,13 +431,38 @@ impl Config {
                             bucket.name, replica.name
                         ));
                     }
+
+                    // Static endpoint pools need a custom endpoint for scheme and Host
+                    if !replica.endpoints.is_empty() {
+                        if replica.endpoint.is_none() {
+                            return Err(format!(
+                                "Bucket '{}': Replica '{}' has static endpoints but no endpoint. Set endpoint to the URL the cluster expects (scheme and Host).",
+                                bucket.name, replica.name
+                            ));
+                        }
+                        let mut seen_addrs = HashSet::new();
+                        for addr in &replica.endpoints {
+                            let parsed = addr.parse::<std::net::SocketAddr>().map_err(|_| {
+                                format!(
+                                    "Bucket '{}': Replica '{}' static endpoint '{}' must be an ip:port address.",
+                                    bucket.name, replica.name, addr
+                                )
+                            })?;
+                            if !seen_addrs.insert(parsed) {
+                                return Err(format!(
+                                    "Bucket '{}': Replica '{}' lists static endpoint '{}' more than once.",
+                                    bucket.name, replica.name, addr
+                                ));
+                            }
+                        }
+                    }
                 }
             }
 
             // Validate authorization configuration if present (Phase 32: OPA Integration)
             if let Some(auth_config) = &bucket.authorization {
                 // Validate authorization type
-                const VALID_AUTH_TYPES: &[&str] = &["opa"];
+                const VALID_AUTH_TYPES: &[&str] = &["opa", "claims"];
                 if !VALID_AUTH_TYPES.contains(&auth_config.auth_type.as_str()) {
                     return Err(format!(
                         "Bucket '{}': Invalid authorization type '{}'. Supported types: {}",
@@ -259,30 +500,196 @@ impl Config {
                         }
                     }
                 }
+
+                // Claim-to-path rules need at least one rule and authenticated callers
+                if auth_config.auth_type == "claims" {
+                    if auth_config.rules.is_empty() {
+                        return Err(format!(
+                            "Bucket '{}': rules are required when authorization type is 'claims'",
+                            bucket.name
+                        ));
+                    }
+                    if !bucket.auth.as_ref().is_some_and(|a| a.enabled) {
+                        return Err(format!(
+                            "Bucket '{}': authorization type 'claims' requires auth.enabled",
+                            bucket.name
+                        ));
+                    }
+                    for rule in &auth_config.rules {
+                        rule.validate(&bucket.name)?;
+                    }
+                }
             }
 
             // Validate watermark configuration if present
             if let Some(watermark_config) = &bucket.watermark {
                 watermark_config.validate(&bucket.name)?;
             }
+
+            // Validate per-bucket logging overrides if present
+            if let Some(logging_config) = &bucket.logging {
+                logging_config.validate(&bucket.name)?;
+            }
+
+            // Validate download resumption settings if present
+            if let Some(resume_config) = &bucket.resume {
+                resume_config.validate(&bucket.name)?;
+            }
+
+            // Validate Content-Type override rules if present
+            if let Some(content_type_config) = &bucket.content_type {
+                content_type_config.validate(&bucket.name)?;
+            }
+
+            // Validate hotlink protection if present
+            if let Some(hotlink_config) = &bucket.hotlink {
+                let has_watermark_rules = bucket
+                    .watermark
+                    .as_ref()
+                    .is_some_and(|w| !w.rules.is_empty());
+                hotlink_config.validate(&bucket.name, has_watermark_rules)?;
+            }
+
+            // Validate signed URL settings if present
+            if let Some(signed_url_config) = &bucket.signed_url {
+                signed_url_config.validate(&bucket.name)?;
+            }
+
+            // Validate POST policy uploads; the form is POSTed to the bucket
+            if let Some(post_policy) = &bucket.post_policy {
+                post_policy.validate(&bucket.name)?;
+                if post_policy.enabled && !bucket.allowed_methods().iter().any(|m| m == "POST") {
+                    return Err(format!(
+                        "Bucket '{}': post_policy requires POST in allowed_methods",
+                        bucket.name
+                    ));
+                }
+            }
+
+            // Presigned redirects are SigV4 URLs for an S3 backend
+            if let Some(redirect) = bucket.presigned_redirect.as_ref().filter(|r| r.enabled) {
+                redirect.validate(&bucket.name)?;
+                if bucket.origin.is_some() {
+                    return Err(format!(
+                        "Bucket '{}': presigned_redirect requires an S3 backend",
+                        bucket.name
+                    ));
+                }
+                if bucket
+                    .s3
+                    .signing
+                    .as_ref()
+                    .is_some_and(|s| s.version == SignatureVersion::V2)
+                {
+                    return Err(format!(
+                        "Bucket '{}': presigned_redirect requires Signature Version 4",
+                        bucket.name
+                    ));
+                }
+            }
+
+            // Size classes are ordered and may only name the bucket's replicas
+            size_class::validate(bucket)?;
+
+            // Validate the bucket cache policy, including its experiment
+            if let Some(cache) = &bucket.cache {
+                cache
+                    .validate()
+                    .map_err(|e| format!("Bucket '{}': cache.{}", bucket.name, e))?;
+            }
+
+            // Validate incoming SigV4 settings; both schemes use Authorization
+            if let Some(sigv4_config) = &bucket.sigv4_auth {
+                sigv4_config.validate(&bucket.name)?;
+                let jwt_enabled = bucket.auth.as_ref().is_some_and(|a| a.enabled);
+                if sigv4_config.enabled && jwt_enabled {
+                    return Err(format!(
+                        "Bucket '{}': auth and sigv4_auth cannot both be enabled",
+                        bucket.name
+                    ));
+                }
+            }
+
+            // Validate allowed HTTP methods if overridden
+            bucket.validate_allowed_methods()?;
+
+            // Validate CORS policy if present
+            if let Some(cors_config) = &bucket.cors {
+                cors_config.validate(&bucket.name)?;
+            }
+
+            // Validate early hints manifest if present
+            if let Some(early_hints) = &bucket.early_hints {
+                early_hints.validate(&bucket.name)?;
+            }
+
+            // Validate ETag rewrite options if present
+            if let Some(etag_rewrite) = &bucket.etag_rewrite {
+                etag_rewrite.validate(&bucket.name)?;
+            }
+
+            // Validate a per-bucket JWT public key if present
+            if let Some(auth) = &bucket.auth {
+                auth.validate(&bucket.name)?;
+            }
         }
 
         // Validate JWT configuration if present
         if let Some(jwt) = &self.jwt {
-            // Validate that secret is not empty when JWT is enabled
-            if jwt.enabled && jwt.secret.is_empty() {
+            // Validate that secret is not empty when JWT is enabled (JWKS and
+            // public-key algorithms supply their own keys)
+            let asymmetric = ASYMMETRIC_ALGORITHMS.contains(&jwt.algorithm.as_str());
+            if jwt.enabled
+                && jwt.jwks_url.is_none()
+                && !asymmetric
+                && jwt.secret.is_empty()
+                && jwt.secret_file.is_none()
+            {
                 return Err("JWT secret cannot be empty when authentication is enabled".to_string());
             }
+            if let Some(url) = &jwt.jwks_url {
+                if !url.starts_with("http://") && !url.starts_with("https://") {
+                    return Err(format!(
+                        "JWT jwks_url '{}' must be an http:// or https:// URL",
+                        url
+                    ));
+                }
+            }
 
-            // Validate algorithm
-            const VALID_ALGORITHMS: &[&str] = &["HS256", "HS384", "HS512"];
-            if !VALID_ALGORITHMS.contains(&jwt.algorithm.as_str()) {
+            if jwt.key_reload_interval_secs == Some(0) {
+                return Err("JWT key_reload_interval_secs must be greater than 0".to_string());
+            }
+            if jwt.jwks_refresh_interval_secs == Some(0) {
+                return Err("JWT jwks_refresh_interval_secs must be greater than 0".to_string());
+            }
+
+            // Validate algorithm (with JWKS, each key's own algorithm is used)
+            if jwt.jwks_url.is_none() && !SUPPORTED_ALGORITHMS.contains(&jwt.algorithm.as_str()) {
                 return Err(format!(
                     "Invalid JWT algorithm '{}'. Supported algorithms: {}",
                     jwt.algorithm,
-                    VALID_ALGORITHMS.join(", ")
+                    SUPPORTED_ALGORITHMS.join(", ")
                 ));
             }
+            if jwt.enabled
+                && jwt.jwks_url.is_none()
+                && asymmetric
+                && jwt.public_key_path(&jwt.algorithm).is_none()
+            {
+                return Err(format!(
+                    "JWT algorithm '{}' requires its public key path (rsa_public_key_path, ecdsa_public_key_path or eddsa_public_key_path)",
+                    jwt.algorithm
+                ));
+            }
+            for algorithm in &jwt.allowed_algorithms {
+                if !SUPPORTED_ALGORITHMS.contains(&algorithm.as_str()) {
+                    return Err(format!(
+                        "Invalid JWT allowed_algorithms entry '{}'. Supported algorithms: {}",
+                        algorithm,
+                        SUPPORTED_ALGORITHMS.join(", ")
+                    ));
+                }
+            }
 
             // Validate that at least one token source exists when JWT is enabled
             if jwt.enabled && jwt.token_sources.is_empty() {
@@ -292,21 +699,36 @@ impl Config {
                 );
             }
 
+            if jwt.issuer.as_ref().is_some_and(|i| i.trim().is_empty()) {
+                return Err("JWT issuer cannot be empty".to_string());
+            }
+            if jwt.audience.iter().any(|a| a.trim().is_empty()) {
+                return Err("JWT audience entries cannot be empty".to_string());
+            }
+            if jwt.token_cache.enabled
+                && (jwt.token_cache.ttl_secs == 0 || jwt.token_cache.max_entries == 0)
+            {
+                return Err(
+                    "jwt.token_cache.ttl_secs and max_entries must be greater than 0".to_string(),
+                );
+            }
+            if let Some(revocation) = &jwt.revocation {
+                revocation.validate()?;
+            }
+
             // Validate claim operators
-            const VALID_OPERATORS: &[&str] =
-                &["equals", "in", "contains", "gt", "lt", "gte", "lte"];
             for claim_rule in &jwt.claims {
-                if !VALID_OPERATORS.contains(&claim_rule.operator.as_str()) {
+                if !CLAIM_OPERATORS.contains(&claim_rule.operator.as_str()) {
                     return Err(format!(
                         "Invalid claim operator '{}'. Supported operators: {}",
                         claim_rule.operator,
-                        VALID_OPERATORS.join(", ")
+                        CLAIM_OPERATORS.join(", ")
                     ));
                 }
             }
 
             // Validate token source types and required fields
-            const VALID_SOURCE_TYPES: &[&str] = &["bearer", "header", "query"];
+            const VALID_SOURCE_TYPES: &[&str] = &["bearer", "header", "query", "cookie"];
             for (idx, source) in jwt.token_sources.iter().enumerate() {
                 // Validate source type
                 if !VALID_SOURCE_TYPES.contains(&source.source_type.as_str()) {
@@ -318,8 +740,8 @@ impl Config {
                     ));
                 }
 
-                // Validate that 'header' and 'query' types have 'name' field
-                if matches!(source.source_type.as_str(), "header" | "query")
+                // Validate that 'header', 'query' and 'cookie' types have 'name' field
+                if matches!(source.source_type.as_str(), "header" | "query" | "cookie")
                     && source.name.is_none()
                 {
                     return Err(format!(
@@ -330,11 +752,41 @@ impl Config {
             }
         }
 
+        // Admin-claim trust for cache overrides needs claims to check against
+        let cache_override = &self.server.cache_override;
+        if cache_override.enabled
+            && cache_override.admin_claims
+            && !self
+                .jwt
+                .as_ref()
+                .is_some_and(|jwt| !jwt.admin_claims.is_empty())
+        {
+            return Err(
+                "server.cache_override.admin_claims requires jwt.admin_claims to be configured"
+                    .to_string(),
+            );
+        }
+
         // Validate cache configuration if present
         if let Some(cache) = &self.cache {
             cache.validate()?;
         }
 
+        // Validate batch GET API configuration
+        self.batch.validate()?;
+
+        // Access statistics uploads reuse the S3 settings of a configured bucket
+        if let Some(s3) = &self.server.access_export.s3 {
+            if self.server.access_export.enabled
+                && !self.buckets.iter().any(|b| b.name == s3.bucket)
+            {
+                return Err(format!(
+                    "server.access_export.s3.bucket '{}' does not match any configured bucket",
+                    s3.bucket
+                ));
+            }
+        }
+
         Ok(())
     }
 
@@ -357,6 +809,7 @@ impl Config {
                         endpoint: bucket.s3.endpoint.clone(),
                         priority: 1,
                         timeout: bucket.s3.timeout,
+                        endpoints: Vec::new(),
                     };
 
                     bucket.s3.replicas = Some(vec![replica]);
@@ -438,6 +891,38 @@ buckets:
         assert!(result.unwrap_err().contains("Duplicate path_prefix"));
     }
 
+    #[test]
+    fn test_config_validation_catches_alias_clashing_with_path_prefix() {
+        let yaml = r#"
+server:
+  address: "127.0.0.1"
+  port: 8080
+buckets:
+  - name: "assets"
+    path_prefix: "/assets"
+    aliases:
+      - path_prefix: "/api"
+        version: "v1"
+    s3:
+      bucket: "my-bucket-1"
+      region: "us-east-1"
+      access_key: "test-key-1"
+      secret_key: "test-secret-1"
+  - name: "api"
+    path_prefix: "/api"
+    s3:
+      bucket: "my-bucket-2"
+      region: "us-east-1"
+      access_key: "test-key-2"
+      secret_key: "test-secret-2"
+"#;
+        let config = Config::from_yaml_with_env(yaml).unwrap();
+        let result = config.validate();
+
+        assert!(result.is_err());
+        assert!(result.unwrap_err().contains("Duplicate path_prefix '/api'"));
+    }
+
     #[test]
     fn test_config_validation_catches_empty_bucket_name() {
         let yaml = r#"
@@ -641,6 +1126,38 @@ buckets:
         assert!(result.unwrap_err().contains("Replica name cannot be empty"));
     }
 
+    #[test]
+    fn test_config_validation_replica_static_endpoints() {
+        let yaml = r#"
+server:
+  address: "127.0.0.1"
+  port: 8080
+buckets:
+  - name: "products"
+    path_prefix: "/products"
+    s3:
+      replicas:
+        - name: "minio"
+          bucket: "bucket-1"
+          region: "us-east-1"
+          access_key: "key1"
+          secret_key: "secret1"
+          endpoint: "http://minio.internal:9000"
+          endpoints: ["10.0.0.11:9000", "10.0.0.12:9000"]
+          priority: 1
+"#;
+        let config = Config::from_yaml_with_env(yaml).unwrap();
+        assert!(config.validate().is_ok());
+
+        let hostname = yaml.replace("\"10.0.0.12:9000\"", "\"minio-2:9000\"");
+        let config = Config::from_yaml_with_env(&hostname).unwrap();
+        assert!(config.validate().unwrap_err().contains("ip:port"));
+
+        let no_endpoint = yaml.replace("          endpoint: \"http://minio.internal:9000\"\n", "");
+        let config = Config::from_yaml_with_env(&no_endpoint).unwrap();
+        assert!(config.validate().unwrap_err().contains("no endpoint"));
+    }
+
     #[test]
     fn test_config_validation_opa_requires_url() {
         let yaml = r#"
@@ -837,6 +1354,61 @@ jwt:
         assert!(result.unwrap_err().contains("JWT secret cannot be empty"));
     }
 
+    #[test]
+    fn test_config_validation_jwt_jwks_without_secret() {
+        let yaml = r#"
+server:
+  address: "127.0.0.1"
+  port: 8080
+buckets: []
+jwt:
+  enabled: true
+  algorithm: "RS256"
+  jwks_url: "https://auth.example.com/.well-known/jwks.json"
+  token_sources:
+    - type: bearer
+"#;
+        let config = Config::from_yaml_with_env(yaml).unwrap();
+        assert!(config.validate().is_ok());
+
+        let mut config = config;
+        config.jwt.as_mut().unwrap().jwks_url = Some("auth.example.com/jwks".to_string());
+        assert!(config.validate().unwrap_err().contains("jwks_url"));
+    }
+
+    #[test]
+    fn test_config_validation_jwt_asymmetric_algorithms() {
+        let yaml = r#"
+server:
+  address: "127.0.0.1"
+  port: 8080
+buckets: []
+jwt:
+  enabled: true
+  algorithm: "EdDSA"
+  eddsa_public_key_path: "/etc/keys/ed25519_public.pem"
+  allowed_algorithms: ["EdDSA"]
+  token_sources:
+    - type: bearer
+"#;
+        let config = Config::from_yaml_with_env(yaml).unwrap();
+        assert!(config.validate().is_ok());
+
+        let mut missing_key = config.clone();
+        missing_key.jwt.as_mut().unwrap().eddsa_public_key_path = None;
+        assert!(missing_key
+            .validate()
+            .unwrap_err()
+            .contains("requires its public key path"));
+
+        let mut bad_allowlist = config;
+        bad_allowlist.jwt.as_mut().unwrap().allowed_algorithms = vec!["none".to_string()];
+        assert!(bad_allowlist
+            .validate()
+            .unwrap_err()
+            .contains("allowed_algorithms"));
+    }
+
     #[test]
     fn test_config_validation_jwt_no_token_sources() {
         let yaml = r#"
@@ -0,0 +1,156 @@
+//! Non-S3 origins for a bucket.
+//!
+//! A bucket normally proxies to S3 (`s3` section). With an `origin` section
+//! instead, the same path prefix, auth, cache and audit pipeline front a
+//! different backend:
+//! - `http`: a plain HTTP(S) origin, objects at `<url>/<key>`
+//! - `filesystem`: a local or NFS-mounted directory, objects at `<root>/<key>`
+//!
+//! The backends themselves live in `crate::origin`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::constants::DEFAULT_S3_TIMEOUT_SECS;
+
+fn default_timeout() -> u64 {
+    DEFAULT_S3_TIMEOUT_SECS
+}
+
+/// Backend for a bucket that is not served from S3 (YAML format)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum OriginConfig {
+    /// Plain HTTP(S) origin
+    Http(HttpOriginConfig),
+    /// Directory on a local or mounted filesystem, served in-process
+    Filesystem(FilesystemOriginConfig),
+}
+
+/// Plain HTTP(S) origin configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HttpOriginConfig {
+    /// Base URL, e.g. `https://assets.internal:8443/static`
+    pub url: String,
+    /// Extra headers sent upstream, e.g. an origin credential (supports `${ENV_VAR}`)
+    #[serde(default)]
+    pub headers: HashMap<String, String>,
+    /// Connect/read/write timeout in seconds (default: 20)
+    #[serde(default = "default_timeout")]
+    pub timeout: u64,
+}
+
+/// Filesystem origin configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FilesystemOriginConfig {
+    /// Absolute directory objects are read from, e.g. `/mnt/nas/media`
+    pub root: String,
+}
+
+impl OriginConfig {
+    /// Backend name for logs and metrics
+    pub fn kind(&self) -> &'static str {
+        match self {
+            OriginConfig::Http(_) => "http",
+            OriginConfig::Filesystem(_) => "filesystem",
+        }
+    }
+
+    /// Validate the backend settings
+    pub fn validate(&self, bucket_name: &str) -> Result<(), String> {
+        match self {
+            OriginConfig::Http(http) => http.validate(bucket_name),
+            OriginConfig::Filesystem(fs) => fs.validate(bucket_name),
+        }
+    }
+}
+
+impl HttpOriginConfig {
+    fn validate(&self, bucket_name: &str) -> Result<(), String> {
+        let Some(rest) = self
+            .url
+            .strip_prefix("https://")
+            .or_else(|| self.url.strip_prefix("http://"))
+        else {
+            return Err(format!(
+                "Bucket '{}': origin.url '{}' must start with http:// or https://",
+                bucket_name, self.url
+            ));
+        };
+        let authority = rest.split('/').next().unwrap_or_default();
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (host, Some(port)),
+            None => (authority, None),
+        };
+        if host.is_empty() {
+            return Err(format!(
+                "Bucket '{}': origin.url '{}' has no host",
+                bucket_name, self.url
+            ));
+        }
+        if port.is_some_and(|p| p.parse::<u16>().is_err()) {
+            return Err(format!(
+                "Bucket '{}': origin.url '{}' has an invalid port",
+                bucket_name, self.url
+            ));
+        }
+        if self.timeout == 0 {
+            return Err(format!(
+                "Bucket '{}': origin.timeout must be greater than 0",
+                bucket_name
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl FilesystemOriginConfig {
+    fn validate(&self, bucket_name: &str) -> Result<(), String> {
+        if !std::path::Path::new(&self.root).is_absolute() {
+            return Err(format!(
+                "Bucket '{}': origin.root '{}' must be an absolute path",
+                bucket_name, self.root
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_http_origin_config_validation() {
+        let config: OriginConfig =
+            serde_yaml::from_str("type: http\nurl: https://assets.internal:8443/static").unwrap();
+        assert_eq!(config.kind(), "http");
+        assert!(config.validate("assets").is_ok());
+
+        for url in ["ftp://host", "https://", "http://host:port"] {
+            let config = OriginConfig::Http(HttpOriginConfig {
+                url: url.to_string(),
+                headers: HashMap::new(),
+                timeout: 20,
+            });
+            assert!(config.validate("assets").is_err(), "{}", url);
+        }
+
+        assert!(serde_yaml::from_str::<OriginConfig>("type: gopher\nurl: x").is_err());
+    }
+
+    #[test]
+    fn test_filesystem_origin_config_validation() {
+        let config: OriginConfig =
+            serde_yaml::from_str("type: filesystem\nroot: /mnt/nas/media").unwrap();
+        assert_eq!(config.kind(), "filesystem");
+        assert!(config.validate("legacy").is_ok());
+
+        for root in ["", "mnt/nas", "./media"] {
+            let config = OriginConfig::Filesystem(FilesystemOriginConfig {
+                root: root.to_string(),
+            });
+            assert!(config.validate("legacy").is_err(), "{}", root);
+        }
+    }
+}
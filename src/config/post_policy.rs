@@ -0,0 +1,153 @@
+//! Browser upload (POST policy) configuration.
+//!
+//! A bucket's `post_policy` section lets browsers upload a file straight
+//! through the proxy with an HTML form POST to the bucket's path prefix. The
+//! form carries an S3-style policy document signed with the bucket's secret;
+//! the proxy checks the signature, the policy conditions and the limits below
+//! before writing the object to S3. Policies are issued by
+//! `POST /admin/post-policy` or by any service holding the secret. Signing and
+//! verification live in [`crate::security::post_policy`].
+//!
+//! Uses constants from `crate::constants`:
+//! - `DEFAULT_POST_POLICY_MAX_SIZE_BYTES`
+//! - `DEFAULT_POST_POLICY_MAX_EXPIRES_SECS`
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{DEFAULT_POST_POLICY_MAX_EXPIRES_SECS, DEFAULT_POST_POLICY_MAX_SIZE_BYTES};
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_max_size_bytes() -> u64 {
+    DEFAULT_POST_POLICY_MAX_SIZE_BYTES
+}
+
+fn default_max_expires_secs() -> u64 {
+    DEFAULT_POST_POLICY_MAX_EXPIRES_SECS
+}
+
+/// Per-bucket POST policy uploads (YAML format)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PostPolicyConfig {
+    /// Accept POST policy uploads (default: true when the section is present)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// HMAC secret that signs policy documents
+    pub secret: String,
+    /// Largest file accepted, whatever the policy allows (default: 10 MiB)
+    #[serde(default = "default_max_size_bytes")]
+    pub max_size_bytes: u64,
+    /// Content types accepted, e.g. `image/png` or `image/*` (default: any)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_content_types: Vec<String>,
+    /// Object keys must start with this prefix (default: any key)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub key_prefix: Option<String>,
+    /// Reject policies that expire further than this in the future (default: 3600)
+    #[serde(default = "default_max_expires_secs")]
+    pub max_expires_secs: u64,
+}
+
+impl PostPolicyConfig {
+    /// Whether `content_type` is in `allowed_content_types`
+    pub fn allows_content_type(&self, content_type: &str) -> bool {
+        if self.allowed_content_types.is_empty() {
+            return true;
+        }
+        let content_type = content_type
+            .split(';')
+            .next()
+            .unwrap_or_default()
+            .trim()
+            .to_ascii_lowercase();
+        self.allowed_content_types.iter().any(|allowed| {
+            let allowed = allowed.to_ascii_lowercase();
+            match allowed.strip_suffix("/*") {
+                Some(major) => content_type
+                    .split_once('/')
+                    .is_some_and(|(m, _)| m == major),
+                None => content_type == allowed,
+            }
+        })
+    }
+
+    /// Validate the POST policy configuration for the given bucket
+    pub fn validate(&self, bucket_name: &str) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.secret.trim().is_empty() {
+            return Err(format!(
+                "Bucket '{}': post_policy.secret cannot be empty",
+                bucket_name
+            ));
+        }
+        if self.max_size_bytes == 0 {
+            return Err(format!(
+                "Bucket '{}': post_policy.max_size_bytes must be greater than 0",
+                bucket_name
+            ));
+        }
+        if self.max_expires_secs == 0 {
+            return Err(format!(
+                "Bucket '{}': post_policy.max_expires_secs must be greater than 0",
+                bucket_name
+            ));
+        }
+        if let Some(content_type) = self
+            .allowed_content_types
+            .iter()
+            .find(|c| !c.contains('/') || c.trim() != c.as_str())
+        {
+            return Err(format!(
+                "Bucket '{}': post_policy.allowed_content_types entry '{}' must be type/subtype",
+                bucket_name, content_type
+            ));
+        }
+        if self
+            .key_prefix
+            .as_ref()
+            .is_some_and(|p| p.is_empty() || p.starts_with('/'))
+        {
+            return Err(format!(
+                "Bucket '{}': post_policy.key_prefix must be non-empty and not start with '/'",
+                bucket_name
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_post_policy_config() {
+        let config: PostPolicyConfig = serde_yaml::from_str("secret: uploads-key").unwrap();
+        assert!(config.enabled);
+        assert_eq!(config.max_size_bytes, 10 * 1024 * 1024);
+        assert_eq!(config.max_expires_secs, 3600);
+        assert!(config.allows_content_type("application/zip"));
+        assert!(config.validate("uploads").is_ok());
+
+        let yaml = "secret: uploads-key\nallowed_content_types: [image/*, application/pdf]";
+        let config: PostPolicyConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.allows_content_type("image/png"));
+        assert!(config.allows_content_type("Application/PDF; charset=binary"));
+        assert!(!config.allows_content_type("text/html"));
+        assert!(!config.allows_content_type("imagex/png"));
+
+        for invalid in [
+            "secret: \"\"",
+            "secret: k\nmax_size_bytes: 0",
+            "secret: k\nallowed_content_types: [image]",
+            "secret: k\nkey_prefix: /uploads/",
+        ] {
+            let config: PostPolicyConfig = serde_yaml::from_str(invalid).unwrap();
+            assert!(config.validate("uploads").is_err(), "{}", invalid);
+        }
+    }
+}
@@ -0,0 +1,141 @@
+//! Request priority configuration.
+//!
+//! Trusted internal clients (health checkers, warmers, control-plane fetches)
+//! can mark requests with `X-Priority: high`. Such requests may use a slice of
+//! the concurrency limit reserved for them and skip resource-based load
+//! shedding, so they still succeed while the proxy is saturated.
+//!
+//! Default values are sourced from `crate::constants`.
+
+use http::header::HeaderName;
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{DEFAULT_PRIORITY_HEADER, DEFAULT_PRIORITY_RESERVED_PERMITS};
+use crate::security::IpRange;
+
+fn default_header() -> String {
+    DEFAULT_PRIORITY_HEADER.to_string()
+}
+
+fn default_reserved_permits() -> usize {
+    DEFAULT_PRIORITY_RESERVED_PERMITS
+}
+
+/// Request priority configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PriorityConfig {
+    /// Honor the priority header (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Header carrying the priority; only the value `high` is recognized (default: X-Priority)
+    #[serde(default = "default_header")]
+    pub header: String,
+    /// Permits of `max_concurrent_requests` kept for high-priority requests (default: 50)
+    #[serde(default = "default_reserved_permits")]
+    pub reserved_permits: usize,
+    /// IPs or CIDR ranges allowed to send high-priority requests
+    #[serde(default)]
+    pub trusted_clients: Vec<String>,
+}
+
+impl Default for PriorityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            header: default_header(),
+            reserved_permits: default_reserved_permits(),
+            trusted_clients: Vec::new(),
+        }
+    }
+}
+
+impl PriorityConfig {
+    /// Validate against the server's concurrency limit
+    pub fn validate(&self, max_concurrent_requests: usize) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if HeaderName::from_bytes(self.header.as_bytes()).is_err() {
+            return Err(format!(
+                "server.priority.header '{}' is not a valid header name",
+                self.header
+            ));
+        }
+        if self.reserved_permits >= max_concurrent_requests {
+            return Err(format!(
+                "server.priority.reserved_permits ({}) must be less than server.max_concurrent_requests ({})",
+                self.reserved_permits, max_concurrent_requests
+            ));
+        }
+        if self.trusted_clients.is_empty() {
+            return Err(
+                "server.priority.trusted_clients must not be empty when priority is enabled"
+                    .to_string(),
+            );
+        }
+        for entry in &self.trusted_clients {
+            IpRange::parse(entry).map_err(|e| format!("server.priority.trusted_clients: {}", e))?;
+        }
+        Ok(())
+    }
+
+    /// Permits left for normal requests
+    pub fn general_permits(&self, max_concurrent_requests: usize) -> usize {
+        if self.enabled {
+            max_concurrent_requests.saturating_sub(self.reserved_permits)
+        } else {
+            max_concurrent_requests
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn enabled() -> PriorityConfig {
+        PriorityConfig {
+            enabled: true,
+            trusted_clients: vec!["10.0.0.0/8".to_string()],
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_priority_config_defaults() {
+        let config: PriorityConfig = serde_yaml::from_str("{}").unwrap();
+        assert!(!config.enabled);
+        assert_eq!(config.header, "X-Priority");
+        assert_eq!(config.reserved_permits, 50);
+        assert!(config.validate(10).is_ok());
+        assert_eq!(config.general_permits(1000), 1000);
+        assert_eq!(enabled().general_permits(1000), 950);
+    }
+
+    #[test]
+    fn test_priority_config_validate() {
+        assert!(enabled().validate(1000).is_ok());
+        assert!(enabled()
+            .validate(50)
+            .unwrap_err()
+            .contains("reserved_permits"));
+        assert!(PriorityConfig {
+            trusted_clients: Vec::new(),
+            ..enabled()
+        }
+        .validate(1000)
+        .is_err());
+        assert!(PriorityConfig {
+            trusted_clients: vec!["not-an-ip".to_string()],
+            ..enabled()
+        }
+        .validate(1000)
+        .is_err());
+        assert!(PriorityConfig {
+            header: "X Priority".to_string(),
+            ..enabled()
+        }
+        .validate(1000)
+        .is_err());
+    }
+}
@@ -0,0 +1,87 @@
+//! Request queue configuration.
+//!
+//! When every concurrency permit is taken, requests normally get an immediate
+//! 503. With the queue enabled they wait up to `max_wait_ms` for a permit
+//! instead, so short spikes are absorbed rather than failed. At most
+//! `max_length` requests wait at once; beyond that, and after the wait
+//! expires, the usual 503 is returned.
+//!
+//! Default values are sourced from `crate::constants`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{DEFAULT_REQUEST_QUEUE_MAX_LENGTH, DEFAULT_REQUEST_QUEUE_MAX_WAIT_MS};
+
+fn default_max_wait_ms() -> u64 {
+    DEFAULT_REQUEST_QUEUE_MAX_WAIT_MS
+}
+
+fn default_max_length() -> usize {
+    DEFAULT_REQUEST_QUEUE_MAX_LENGTH
+}
+
+/// Bounded wait for a concurrency permit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RequestQueueConfig {
+    /// Queue requests when the concurrency limit is reached (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Longest wait for a permit in milliseconds (default: 500)
+    #[serde(default = "default_max_wait_ms")]
+    pub max_wait_ms: u64,
+    /// Maximum number of waiting requests (default: 1000)
+    #[serde(default = "default_max_length")]
+    pub max_length: usize,
+}
+
+impl Default for RequestQueueConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_wait_ms: default_max_wait_ms(),
+            max_length: default_max_length(),
+        }
+    }
+}
+
+impl RequestQueueConfig {
+    /// Validate wait and length limits
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.max_wait_ms == 0 {
+            return Err("server.request_queue.max_wait_ms must be greater than 0".to_string());
+        }
+        if self.max_length == 0 {
+            return Err("server.request_queue.max_length must be greater than 0".to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_queue_config() {
+        let config: RequestQueueConfig = serde_yaml::from_str("enabled: true").unwrap();
+        assert_eq!(config.max_wait_ms, DEFAULT_REQUEST_QUEUE_MAX_WAIT_MS);
+        assert_eq!(config.max_length, DEFAULT_REQUEST_QUEUE_MAX_LENGTH);
+        assert!(config.validate().is_ok());
+
+        let config = RequestQueueConfig {
+            enabled: true,
+            max_length: 0,
+            ..Default::default()
+        };
+        assert!(config.validate().is_err());
+        assert!(RequestQueueConfig {
+            enabled: false,
+            ..config
+        }
+        .validate()
+        .is_ok());
+    }
+}
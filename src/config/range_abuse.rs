@@ -0,0 +1,158 @@
+//! Range request abuse detection configuration.
+//!
+//! Range requests bypass the cache and go straight to S3. A client that
+//! fetches an object in thousands of tiny ranges turns one download into
+//! thousands of upstream requests. The proxy counts each client's small
+//! ranges per object in a fixed window; a client crossing the threshold is
+//! throttled to a few Range requests per second for `throttle_secs`.
+//! Requests without a `Range` header are never affected.
+//!
+//! Default values are sourced from `crate::constants`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{
+    DEFAULT_RANGE_ABUSE_MAX_SMALL_RANGES, DEFAULT_RANGE_ABUSE_MAX_TRACKED,
+    DEFAULT_RANGE_ABUSE_SMALL_RANGE_BYTES, DEFAULT_RANGE_ABUSE_THROTTLED_RPS,
+    DEFAULT_RANGE_ABUSE_THROTTLE_SECS, DEFAULT_RANGE_ABUSE_WINDOW_SECS,
+};
+use crate::security::IpRange;
+
+fn default_window_secs() -> u64 {
+    DEFAULT_RANGE_ABUSE_WINDOW_SECS
+}
+
+fn default_small_range_bytes() -> u64 {
+    DEFAULT_RANGE_ABUSE_SMALL_RANGE_BYTES
+}
+
+fn default_max_small_ranges() -> u32 {
+    DEFAULT_RANGE_ABUSE_MAX_SMALL_RANGES
+}
+
+fn default_throttle_secs() -> u64 {
+    DEFAULT_RANGE_ABUSE_THROTTLE_SECS
+}
+
+fn default_throttled_requests_per_second() -> u32 {
+    DEFAULT_RANGE_ABUSE_THROTTLED_RPS
+}
+
+fn default_max_tracked() -> usize {
+    DEFAULT_RANGE_ABUSE_MAX_TRACKED
+}
+
+/// Range abuse detection and throttling configuration
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RangeAbuseConfig {
+    /// Enable detection (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Window for counting small ranges in seconds (default: 60)
+    #[serde(default = "default_window_secs")]
+    pub window_secs: u64,
+    /// Largest range, in bytes, that counts as small (default: 65536)
+    #[serde(default = "default_small_range_bytes")]
+    pub small_range_bytes: u64,
+    /// Small ranges on one object per window that flag a client (default: 1000)
+    #[serde(default = "default_max_small_ranges")]
+    pub max_small_ranges: u32,
+    /// How long a flagged client is throttled in seconds (default: 300)
+    #[serde(default = "default_throttle_secs")]
+    pub throttle_secs: u64,
+    /// Range requests per second a throttled client may send; 0 rejects all (default: 5)
+    #[serde(default = "default_throttled_requests_per_second")]
+    pub throttled_requests_per_second: u32,
+    /// Maximum client/object pairs tracked for counting (default: 100000)
+    #[serde(default = "default_max_tracked")]
+    pub max_tracked: usize,
+    /// IPs or CIDR ranges that are never throttled (e.g. internal transcoders)
+    #[serde(default)]
+    pub exempt: Vec<String>,
+}
+
+impl Default for RangeAbuseConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            window_secs: default_window_secs(),
+            small_range_bytes: default_small_range_bytes(),
+            max_small_ranges: default_max_small_ranges(),
+            throttle_secs: default_throttle_secs(),
+            throttled_requests_per_second: default_throttled_requests_per_second(),
+            max_tracked: default_max_tracked(),
+            exempt: Vec::new(),
+        }
+    }
+}
+
+impl RangeAbuseConfig {
+    /// Validate thresholds and exemptions
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.window_secs == 0 {
+            return Err("server.range_abuse.window_secs must be greater than 0".to_string());
+        }
+        if self.small_range_bytes == 0 {
+            return Err("server.range_abuse.small_range_bytes must be greater than 0".to_string());
+        }
+        if self.max_small_ranges == 0 {
+            return Err("server.range_abuse.max_small_ranges must be greater than 0".to_string());
+        }
+        if self.throttle_secs == 0 {
+            return Err("server.range_abuse.throttle_secs must be greater than 0".to_string());
+        }
+        if self.max_tracked == 0 {
+            return Err("server.range_abuse.max_tracked must be greater than 0".to_string());
+        }
+        for entry in &self.exempt {
+            IpRange::parse(entry).map_err(|e| format!("server.range_abuse.exempt: {}", e))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_range_abuse_config() {
+        let config: RangeAbuseConfig = serde_yaml::from_str("enabled: true").unwrap();
+        assert_eq!(config.window_secs, 60);
+        assert_eq!(config.small_range_bytes, 65536);
+        assert_eq!(config.max_small_ranges, 1000);
+        assert_eq!(config.throttle_secs, 300);
+        assert_eq!(config.throttled_requests_per_second, 5);
+        assert!(config.validate().is_ok());
+
+        // Disabled config is never rejected
+        let disabled = RangeAbuseConfig {
+            max_small_ranges: 0,
+            ..Default::default()
+        };
+        assert!(disabled.validate().is_ok());
+
+        assert!(RangeAbuseConfig {
+            max_small_ranges: 0,
+            ..config.clone()
+        }
+        .validate()
+        .is_err());
+        assert!(RangeAbuseConfig {
+            exempt: vec!["10.0.0.0/99".to_string()],
+            ..config.clone()
+        }
+        .validate()
+        .is_err());
+        // Zero requests per second rejects every Range request while throttled
+        assert!(RangeAbuseConfig {
+            throttled_requests_per_second: 0,
+            ..config
+        }
+        .validate()
+        .is_ok());
+    }
+}
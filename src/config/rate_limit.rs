@@ -4,6 +4,11 @@
 //! - Global rate limits (server-wide)
 //! - Per-IP rate limits (client throttling)
 //! - Per-bucket rate limits (S3 backend protection)
+//!
+//! Every limit is a token bucket: `requests_per_second` is the sustained
+//! refill rate and `burst` the bucket capacity (default: one second's worth).
+//! A larger `burst` lets short legitimate spikes through; `burst: 1` spaces
+//! requests evenly at the sustained rate.
 
 use serde::{Deserialize, Serialize};
 
@@ -21,11 +26,28 @@ pub struct RateLimitConfigYaml {
     pub per_ip: Option<PerIpRateLimitConfigYaml>,
 }
 
+impl RateLimitConfigYaml {
+    /// Validate burst settings
+    pub fn validate(&self) -> Result<(), String> {
+        if let Some(global) = &self.global {
+            validate_burst(global.burst, "server.rate_limit.global.burst")?;
+        }
+        if let Some(per_ip) = &self.per_ip {
+            validate_burst(per_ip.burst, "server.rate_limit.per_ip.burst")?;
+        }
+        Ok(())
+    }
+}
+
 /// Global rate limit configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GlobalRateLimitConfigYaml {
     /// Requests per second (global limit)
     pub requests_per_second: u32,
+    /// Requests accepted at once before the sustained rate applies
+    /// (default: requests_per_second)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burst: Option<u32>,
 }
 
 /// Per-IP rate limit configuration
@@ -33,6 +55,10 @@ pub struct GlobalRateLimitConfigYaml {
 pub struct PerIpRateLimitConfigYaml {
     /// Requests per second per IP address
     pub requests_per_second: u32,
+    /// Requests accepted at once per IP before the sustained rate applies
+    /// (default: requests_per_second)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burst: Option<u32>,
 }
 
 /// Per-bucket rate limit configuration
@@ -40,6 +66,27 @@ pub struct PerIpRateLimitConfigYaml {
 pub struct BucketRateLimitConfigYaml {
     /// Requests per second for this bucket
     pub requests_per_second: u32,
+    /// Requests accepted at once before the sustained rate applies
+    /// (default: requests_per_second)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub burst: Option<u32>,
+}
+
+impl BucketRateLimitConfigYaml {
+    /// Validate burst settings for the given bucket
+    pub fn validate(&self, bucket_name: &str) -> Result<(), String> {
+        validate_burst(
+            self.burst,
+            &format!("Bucket '{}': s3.rate_limit.burst", bucket_name),
+        )
+    }
+}
+
+fn validate_burst(burst: Option<u32>, field: &str) -> Result<(), String> {
+    if burst == Some(0) {
+        return Err(format!("{} must be greater than 0", field));
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -120,5 +167,43 @@ requests_per_second: 200
         let config: BucketRateLimitConfigYaml = serde_yaml::from_str(yaml).unwrap();
 
         assert_eq!(config.requests_per_second, 200);
+        assert!(config.burst.is_none());
+    }
+
+    #[test]
+    fn test_rate_limit_config_burst() {
+        let yaml = r#"
+enabled: true
+global:
+  requests_per_second: 1000
+  burst: 5000
+per_ip:
+  requests_per_second: 10
+  burst: 1
+"#;
+        let config: RateLimitConfigYaml = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(config.global.as_ref().unwrap().burst, Some(5000));
+        assert_eq!(config.per_ip.as_ref().unwrap().burst, Some(1));
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_rate_limit_config_zero_burst_rejected() {
+        let yaml = r#"
+enabled: true
+per_ip:
+  requests_per_second: 10
+  burst: 0
+"#;
+        let config: RateLimitConfigYaml = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.validate().unwrap_err().contains("per_ip.burst"));
+
+        let bucket: BucketRateLimitConfigYaml =
+            serde_yaml::from_str("requests_per_second: 50\nburst: 0").unwrap();
+        assert!(bucket
+            .validate("products")
+            .unwrap_err()
+            .contains("products"));
     }
 }
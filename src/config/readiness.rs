@@ -0,0 +1,61 @@
+//! Readiness policy for the `/ready` endpoint.
+//!
+//! By default `/ready` reports ready only while every bucket has a replica
+//! with a closed circuit breaker. Breakers open during short S3 hiccups, and
+//! an orchestrator that acts on `/ready` then pulls pods that could still
+//! serve from cache. The readiness mode decides which signals count.
+
+use serde::{Deserialize, Serialize};
+
+/// How `/ready` decides between 200 and 503
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReadinessMode {
+    /// Every bucket needs a replica with a closed breaker (default)
+    #[default]
+    AllOrigins,
+    /// At least one bucket needs a replica with a closed breaker
+    AnyOrigin,
+    /// Ready while the cache is healthy, even if origins are down;
+    /// falls back to `all_origins` when no cache is configured
+    CacheOrOrigin,
+    /// Always ready while the process is up (liveness only)
+    Always,
+}
+
+impl ReadinessMode {
+    /// Name used in the `/ready` response body
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ReadinessMode::AllOrigins => "all_origins",
+            ReadinessMode::AnyOrigin => "any_origin",
+            ReadinessMode::CacheOrOrigin => "cache_or_origin",
+            ReadinessMode::Always => "always",
+        }
+    }
+}
+
+/// Readiness policy configuration (YAML format)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReadinessConfig {
+    /// Which signals make the proxy ready (default: all_origins)
+    #[serde(default)]
+    pub mode: ReadinessMode,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_readiness_config_modes() {
+        let config: ReadinessConfig = serde_yaml::from_str("{}").unwrap();
+        assert_eq!(config.mode, ReadinessMode::AllOrigins);
+
+        let config: ReadinessConfig = serde_yaml::from_str("mode: cache_or_origin").unwrap();
+        assert_eq!(config.mode, ReadinessMode::CacheOrOrigin);
+        assert_eq!(config.mode.as_str(), "cache_or_origin");
+
+        assert!(serde_yaml::from_str::<ReadinessConfig>("mode: sometimes").is_err());
+    }
+}
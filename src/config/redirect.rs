@@ -0,0 +1,93 @@
+//! Presigned-URL redirect configuration.
+//!
+//! A bucket with `presigned_redirect` does not proxy object bytes. After a
+//! GET or HEAD passes authentication and authorization, the proxy answers
+//! `302 Found` with a short-lived presigned S3 URL for the object, and the
+//! client downloads it from S3 directly. Large transfers leave the proxy
+//! while access control stays in one place. URLs are built by
+//! [`crate::s3::presign`].
+//!
+//! Uses constants from `crate::constants`:
+//! - `DEFAULT_PRESIGNED_REDIRECT_EXPIRES_SECS`
+//! - `MAX_PRESIGNED_URL_EXPIRES_SECS`
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{DEFAULT_PRESIGNED_REDIRECT_EXPIRES_SECS, MAX_PRESIGNED_URL_EXPIRES_SECS};
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_expires_secs() -> u64 {
+    DEFAULT_PRESIGNED_REDIRECT_EXPIRES_SECS
+}
+
+/// Per-bucket presigned redirect mode (YAML format)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresignedRedirectConfig {
+    /// Redirect instead of proxying (default: true when the section is present)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Lifetime of each presigned URL (default: 300)
+    #[serde(default = "default_expires_secs")]
+    pub expires_secs: u64,
+    /// Base URL clients reach S3 on, when it differs from the bucket's
+    /// `endpoint` (e.g. `https://minio.example.com`) (default: the endpoint)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_endpoint: Option<String>,
+}
+
+impl PresignedRedirectConfig {
+    /// Validate the lifetime and public endpoint for the given bucket
+    pub fn validate(&self, bucket_name: &str) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.expires_secs == 0 || self.expires_secs > MAX_PRESIGNED_URL_EXPIRES_SECS {
+            return Err(format!(
+                "Bucket '{}': presigned_redirect.expires_secs must be between 1 and {}, got {}",
+                bucket_name, MAX_PRESIGNED_URL_EXPIRES_SECS, self.expires_secs
+            ));
+        }
+        if let Some(endpoint) = &self.public_endpoint {
+            let host = endpoint
+                .strip_prefix("https://")
+                .or_else(|| endpoint.strip_prefix("http://"));
+            if !host.is_some_and(|h| !h.is_empty() && !h.contains('/')) {
+                return Err(format!(
+                    "Bucket '{}': presigned_redirect.public_endpoint must be scheme://host[:port], got '{}'",
+                    bucket_name, endpoint
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presigned_redirect_validation() {
+        let config: PresignedRedirectConfig = serde_yaml::from_str("{}").unwrap();
+        assert!(config.enabled);
+        assert_eq!(config.expires_secs, 300);
+        assert!(config.validate("media").is_ok());
+
+        let mut bad = config.clone();
+        bad.expires_secs = MAX_PRESIGNED_URL_EXPIRES_SECS + 1;
+        assert!(bad.validate("media").is_err());
+
+        let mut bad = config.clone();
+        bad.public_endpoint = Some("minio.example.com".into());
+        assert!(bad.validate("media").is_err());
+        bad.public_endpoint = Some("https://minio.example.com/path".into());
+        assert!(bad.validate("media").is_err());
+
+        let mut ok = config;
+        ok.public_endpoint = Some("https://minio.example.com:9000".into());
+        assert!(ok.validate("media").is_ok());
+    }
+}
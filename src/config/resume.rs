@@ -0,0 +1,101 @@
+//! Download resumption configuration.
+//!
+//! When enabled for a bucket, full (200) responses for objects of at least
+//! `min_size_bytes` carry `Accept-Ranges: bytes` and an opaque
+//! `X-Resume-Token` header. A client that loses its connection replays the
+//! token with a `Range` request; the proxy pins the upstream fetch to the
+//! same object version so the resumed bytes always match the first part.
+//!
+//! Default values are sourced from `crate::constants`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::DEFAULT_RESUME_MIN_SIZE_BYTES;
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_min_size_bytes() -> u64 {
+    DEFAULT_RESUME_MIN_SIZE_BYTES
+}
+
+/// Per-bucket download resumption configuration (YAML format)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResumeConfig {
+    /// Issue and honor resume tokens (default: true when the section is present)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Minimum object size that gets a resume token (default: 100 MB)
+    #[serde(default = "default_min_size_bytes")]
+    pub min_size_bytes: u64,
+}
+
+impl Default for ResumeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+            min_size_bytes: default_min_size_bytes(),
+        }
+    }
+}
+
+impl ResumeConfig {
+    /// Validate the resume configuration for the given bucket.
+    pub fn validate(&self, bucket_name: &str) -> Result<(), String> {
+        if self.enabled && self.min_size_bytes == 0 {
+            return Err(format!(
+                "Bucket '{}': resume.min_size_bytes must be greater than 0",
+                bucket_name
+            ));
+        }
+        Ok(())
+    }
+
+    /// Check if a response of `content_length` bytes should carry a resume token
+    pub fn applies_to(&self, content_length: u64) -> bool {
+        self.enabled && content_length >= self.min_size_bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_resume_config_defaults() {
+        let config: ResumeConfig = serde_yaml::from_str("{}").unwrap();
+
+        assert!(config.enabled);
+        assert_eq!(config.min_size_bytes, DEFAULT_RESUME_MIN_SIZE_BYTES);
+        assert!(config.validate("videos").is_ok());
+    }
+
+    #[test]
+    fn test_resume_config_applies_to_threshold() {
+        let config = ResumeConfig {
+            enabled: true,
+            min_size_bytes: 1000,
+        };
+        assert!(!config.applies_to(999));
+        assert!(config.applies_to(1000));
+
+        let disabled = ResumeConfig {
+            enabled: false,
+            ..config
+        };
+        assert!(!disabled.applies_to(5000));
+    }
+
+    #[test]
+    fn test_resume_config_rejects_zero_threshold() {
+        let config = ResumeConfig {
+            enabled: true,
+            min_size_bytes: 0,
+        };
+        assert!(config
+            .validate("videos")
+            .unwrap_err()
+            .contains("min_size_bytes"));
+    }
+}
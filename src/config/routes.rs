@@ -0,0 +1,164 @@
+//! Pattern routes for a bucket.
+//!
+//! Path prefixes cannot express URL schemes such as `/img/<id>/thumb/<file>`
+//! where the S3 key is laid out differently. A bucket's `routes` match whole
+//! request paths with a glob or a regex and build the S3 key from a template
+//! of the captured groups:
+//!
+//! ```yaml
+//! routes:
+//!   - glob: /img/*/thumb/**
+//!     key: thumbnails/{1}/{2}
+//!   - regex: ^/p/(?P<id>[0-9]+)\.jpg$
+//!     key: products/{id}/original.jpg
+//! ```
+//!
+//! In a glob, `*` matches within one path segment and `**` across segments;
+//! each wildcard is a numbered group. Regex groups are numbered or named.
+
+use regex::{Captures, Regex};
+use serde::{Deserialize, Serialize};
+
+/// One pattern route (YAML format)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RoutePatternConfig {
+    /// Glob over the request path (`*` one segment, `**` any depth)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub glob: Option<String>,
+    /// Regex over the request path
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub regex: Option<String>,
+    /// S3 key template with `{1}` or `{name}` placeholders
+    pub key: String,
+}
+
+impl RoutePatternConfig {
+    /// Compile the glob or regex into an anchored regex
+    pub fn compile(&self) -> Result<Regex, String> {
+        let pattern = match (&self.glob, &self.regex) {
+            (Some(glob), None) => glob_to_regex(glob),
+            (None, Some(regex)) => regex.clone(),
+            _ => return Err("exactly one of 'glob' or 'regex' must be set".to_string()),
+        };
+        Regex::new(&pattern).map_err(|e| format!("invalid pattern: {}", e))
+    }
+
+    /// Validate the pattern and that the key template only uses its groups
+    pub fn validate(&self, bucket_name: &str) -> Result<(), String> {
+        let regex = self
+            .compile()
+            .map_err(|e| format!("Bucket '{}': route {}", bucket_name, e))?;
+        if self.key.trim_matches('/').is_empty() {
+            return Err(format!(
+                "Bucket '{}': route key template cannot be empty",
+                bucket_name
+            ));
+        }
+        for name in placeholders(&self.key) {
+            let known = match name.parse::<usize>() {
+                Ok(index) => index > 0 && index < regex.captures_len(),
+                Err(_) => regex.capture_names().flatten().any(|n| n == name),
+            };
+            if !known {
+                return Err(format!(
+                    "Bucket '{}': route key '{}' uses unknown group '{{{}}}'",
+                    bucket_name, self.key, name
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Fill a key template from a match (unknown groups render empty)
+pub fn render_key(template: &str, captures: &Captures) -> String {
+    let mut key = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        key.push_str(&rest[..start]);
+        let Some(end) = rest[start..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 1..start + end];
+        let group = match name.parse::<usize>() {
+            Ok(index) => captures.get(index),
+            Err(_) => captures.name(name),
+        };
+        key.push_str(group.map(|m| m.as_str()).unwrap_or_default());
+        rest = &rest[start + end + 1..];
+    }
+    key.push_str(rest);
+    key.trim_start_matches('/').to_string()
+}
+
+/// Group names referenced by `{...}` in a template
+fn placeholders(template: &str) -> Vec<&str> {
+    template
+        .split('{')
+        .skip(1)
+        .filter_map(|part| part.split_once('}').map(|(name, _)| name))
+        .collect()
+}
+
+/// Anchored regex for a path glob
+fn glob_to_regex(glob: &str) -> String {
+    let mut pattern = String::from("^");
+    let mut chars = glob.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '*' {
+            if chars.peek() == Some(&'*') {
+                chars.next();
+                pattern.push_str("(.*)");
+            } else {
+                pattern.push_str("([^/]*)");
+            }
+        } else {
+            pattern.push_str(&regex::escape(&c.to_string()));
+        }
+    }
+    pattern.push('$');
+    pattern
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_and_regex_routes_render_keys() {
+        let glob: RoutePatternConfig =
+            serde_yaml::from_str("glob: /img/*/thumb/**\nkey: thumbnails/{1}/{2}").unwrap();
+        assert!(glob.validate("media").is_ok());
+        let regex = glob.compile().unwrap();
+        let captures = regex.captures("/img/42/thumb/a/b.jpg").unwrap();
+        assert_eq!(render_key(&glob.key, &captures), "thumbnails/42/a/b.jpg");
+        assert!(regex.captures("/img/4/2/thumb/a.jpg").is_none());
+        assert!(regex.captures("/img.x/thumb/a.jpg").is_none());
+
+        let named = RoutePatternConfig {
+            glob: None,
+            regex: Some(r"^/p/(?P<id>[0-9]+)\.jpg$".to_string()),
+            key: "products/{id}/original.jpg".to_string(),
+        };
+        assert!(named.validate("media").is_ok());
+        let captures = named.compile().unwrap().captures("/p/7.jpg").unwrap();
+        assert_eq!(render_key(&named.key, &captures), "products/7/original.jpg");
+
+        // Unknown groups, both or neither pattern, bad regex
+        let invalid = [
+            (Some("/img/*"), None, "{2}"),
+            (None, Some("^/p/(?P<id>.*)$"), "{name}"),
+            (Some("/a/*"), Some("^/a/(.*)$"), "{1}"),
+            (None, None, "key"),
+            (None, Some("^/a/(.*$"), "{1}"),
+        ];
+        for (glob, regex, key) in invalid {
+            let route = RoutePatternConfig {
+                glob: glob.map(str::to_string),
+                regex: regex.map(str::to_string),
+                key: key.to_string(),
+            };
+            assert!(route.validate("media").is_err(), "{:?} {:?}", glob, regex);
+        }
+    }
+}
@@ -3,16 +3,22 @@
 //! This module defines the server-level configuration including:
 //! - Address and port bindings
 //! - Request timeouts and concurrency limits
-//! - Security validation limits (body size, header size, URI length)
+//! - Security validation limits (body size, header size and value length, URI length)
 //! - Global rate limiting settings
+//! - Worker thread naming, work stealing and CPU affinity
+//! - Client disconnect handling
+//! - The HTTPS listener (`tls`)
 //!
 //! Default values are sourced from `crate::constants`.
 
+use http::header::HeaderName;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 use crate::constants::{
     DEFAULT_MAX_BODY_SIZE, DEFAULT_MAX_CONCURRENT_REQUESTS, DEFAULT_MAX_HEADER_SIZE,
-    DEFAULT_MAX_URI_LENGTH, DEFAULT_REQUEST_TIMEOUT_SECS, DEFAULT_THREADS,
+    DEFAULT_MAX_HEADER_VALUE_LENGTH, DEFAULT_MAX_URI_LENGTH, DEFAULT_REQUEST_TIMEOUT_SECS,
+    DEFAULT_THREADS, DEFAULT_WORKER_THREAD_NAME, MAX_CPU_AFFINITY_ID,
 };
 
 use super::rate_limit::RateLimitConfigYaml;
@@ -32,6 +38,14 @@ fn default_threads() -> usize {
     DEFAULT_THREADS
 }
 
+fn default_worker_thread_name() -> String {
+    DEFAULT_WORKER_THREAD_NAME.to_string()
+}
+
+fn default_work_stealing() -> bool {
+    true
+}
+
 // Default security limit values
 fn default_max_body_size() -> usize {
     DEFAULT_MAX_BODY_SIZE
@@ -45,6 +59,10 @@ fn default_max_uri_length() -> usize {
     DEFAULT_MAX_URI_LENGTH
 }
 
+fn default_max_header_value_length() -> usize {
+    DEFAULT_MAX_HEADER_VALUE_LENGTH
+}
+
 /// Security validation limits configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SecurityLimitsConfig {
@@ -57,6 +75,12 @@ pub struct SecurityLimitsConfig {
     /// Maximum URI length in bytes (default: 8 KB)
     #[serde(default = "default_max_uri_length")]
     pub max_uri_length: usize,
+    /// Maximum length of any single header value in bytes (default: 8 KB)
+    #[serde(default = "default_max_header_value_length")]
+    pub max_header_value_length: usize,
+    /// Per-header overrides of `max_header_value_length`, e.g. `cookie: 16384`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub header_value_limits: HashMap<String, usize>,
 }
 
 impl Default for SecurityLimitsConfig {
@@ -65,18 +89,110 @@ impl Default for SecurityLimitsConfig {
             max_body_size: default_max_body_size(),
             max_header_size: default_max_header_size(),
             max_uri_length: default_max_uri_length(),
+            max_header_value_length: default_max_header_value_length(),
+            header_value_limits: HashMap::new(),
         }
     }
 }
 
 impl SecurityLimitsConfig {
+    /// Validate header value limits
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_header_value_length == 0 {
+            return Err(
+                "server.security_limits.max_header_value_length must be greater than 0".to_string(),
+            );
+        }
+        for (name, limit) in &self.header_value_limits {
+            if HeaderName::from_bytes(name.as_bytes()).is_err() {
+                return Err(format!(
+                    "server.security_limits.header_value_limits: '{}' is not a valid header name",
+                    name
+                ));
+            }
+            if *limit == 0 {
+                return Err(format!(
+                    "server.security_limits.header_value_limits: limit for '{}' must be greater than 0",
+                    name
+                ));
+            }
+        }
+        Ok(())
+    }
+
     /// Convert to SecurityLimits from security module
     pub fn to_security_limits(&self) -> crate::security::SecurityLimits {
         crate::security::SecurityLimits {
             max_body_size: self.max_body_size,
             max_header_size: self.max_header_size,
             max_uri_length: self.max_uri_length,
+            max_header_value_length: self.max_header_value_length,
+            header_value_limits: self
+                .header_value_limits
+                .iter()
+                .map(|(name, limit)| (name.to_ascii_lowercase(), *limit))
+                .collect(),
+        }
+    }
+}
+
+/// Worker thread configuration
+///
+/// Controls how the proxy's worker runtime is laid out on the host. On large
+/// machines the proxy can be restricted to a subset of cores with
+/// `cpu_affinity` so it does not compete with co-located workloads.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkerConfig {
+    /// Name given to worker threads, visible in `top -H`, `ps -T` and profilers
+    /// (default: "yatagarasu")
+    #[serde(default = "default_worker_thread_name")]
+    pub thread_name: String,
+    /// Allow idle workers to steal tasks from busy ones (default: true)
+    #[serde(default = "default_work_stealing")]
+    pub work_stealing: bool,
+    /// CPU ids the proxy is pinned to (default: empty, no pinning).
+    /// Only supported on Linux; ignored with a warning elsewhere.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cpu_affinity: Vec<usize>,
+}
+
+impl Default for WorkerConfig {
+    fn default() -> Self {
+        Self {
+            thread_name: default_worker_thread_name(),
+            work_stealing: default_work_stealing(),
+            cpu_affinity: Vec::new(),
+        }
+    }
+}
+
+impl WorkerConfig {
+    /// Validate thread name and CPU affinity list
+    pub fn validate(&self) -> Result<(), String> {
+        if self.thread_name.trim().is_empty() {
+            return Err("server.workers.thread_name cannot be empty".to_string());
+        }
+        if self.thread_name.contains('\0') {
+            return Err("server.workers.thread_name cannot contain NUL bytes".to_string());
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        for &cpu in &self.cpu_affinity {
+            if cpu >= MAX_CPU_AFFINITY_ID {
+                return Err(format!(
+                    "server.workers.cpu_affinity: CPU id {} is out of range (must be < {})",
+                    cpu, MAX_CPU_AFFINITY_ID
+                ));
+            }
+            if !seen.insert(cpu) {
+                return Err(format!(
+                    "server.workers.cpu_affinity: CPU id {} is listed more than once",
+                    cpu
+                ));
+            }
         }
+
+        Ok(())
     }
 }
 
@@ -98,6 +214,104 @@ pub struct ServerConfig {
     /// Request coalescing configuration (default: enabled with wait_for_complete strategy)
     #[serde(default)]
     pub coalescing: super::coalescing::CoalescingConfig,
+    /// Worker thread naming and CPU pinning (default: no pinning)
+    #[serde(default)]
+    pub workers: WorkerConfig,
+    /// Client disconnect handling (default: cancel upstream, finish nearly complete cache fills)
+    #[serde(default)]
+    pub client_disconnect: super::disconnect::ClientDisconnectConfig,
+    /// Automatic temporary IP banning (default: disabled)
+    #[serde(default)]
+    pub ip_ban: super::ip_ban::IpBanConfig,
+    /// Per-client throttling of pathological Range request patterns (default: disabled)
+    #[serde(default)]
+    pub range_abuse: super::range_abuse::RangeAbuseConfig,
+    /// Time-boxed read grants created through the admin API (default: disabled)
+    #[serde(default)]
+    pub access_grants: super::access_grants::AccessGrantsConfig,
+    /// Priority header and reserved concurrency for trusted clients (default: disabled)
+    #[serde(default)]
+    pub priority: super::priority::PriorityConfig,
+    /// Bounded wait for a permit when the concurrency limit is reached (default: off)
+    #[serde(default)]
+    pub request_queue: super::queue::RequestQueueConfig,
+    /// Maintenance mode response and allowlisted paths (default: off)
+    #[serde(default)]
+    pub maintenance: super::maintenance::MaintenanceConfig,
+    /// Per-bucket top requested keys for the admin analytics API (default: disabled)
+    #[serde(default)]
+    pub key_analytics: super::analytics::KeyAnalyticsConfig,
+    /// Periodic per-key access statistics export (default: disabled)
+    #[serde(default)]
+    pub access_export: super::analytics::AccessExportConfig,
+    /// Bearer token / IP restrictions for /health, /ready and /metrics (default: open)
+    #[serde(default)]
+    pub endpoint_access: super::endpoint_access::EndpointAccessConfig,
+    /// Which signals make `/ready` report ready (default: all origins healthy)
+    #[serde(default)]
+    pub readiness: super::readiness::ReadinessConfig,
+    /// Synthetic HEAD probes for breakers due a half-open test (default: disabled)
+    #[serde(default)]
+    pub origin_probe: super::circuit_breaker::OriginProbeConfig,
+    /// Bodies and Retry-After of overload and origin-failure 503s (default: built-in JSON)
+    #[serde(default)]
+    pub unavailable_responses: super::unavailable::UnavailableResponsesConfig,
+    /// Distinct bucket/replica label values before metrics fold into `other`
+    #[serde(default)]
+    pub metrics_cardinality: super::metrics::MetricsCardinalityConfig,
+    /// Generated-data routes for smoke tests (default: none)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub synthetic_endpoints: Vec<super::synthetic::SyntheticEndpointConfig>,
+    /// `X-Yat-Cache` override header for trusted clients (default: disabled)
+    #[serde(default)]
+    pub cache_override: super::cache_override::CacheOverrideConfig,
+    /// Admin API method limits, CSRF checks and token IP binding (default: CSRF on)
+    #[serde(default)]
+    pub admin: super::admin::AdminSecurityConfig,
+    /// Typed gRPC control plane over mutual TLS (default: disabled)
+    #[serde(default)]
+    pub grpc: super::grpc::GrpcConfig,
+    /// HTTPS listener with certificates from an ACME directory (default: disabled)
+    #[serde(default)]
+    pub tls: super::tls::TlsListenerConfig,
+}
+
+impl ServerConfig {
+    /// Validate server-level settings
+    pub fn validate(&self) -> Result<(), String> {
+        if self.threads == 0 {
+            return Err("server.threads must be at least 1".to_string());
+        }
+        self.workers.validate()?;
+        self.security_limits.validate()?;
+        if let Some(rate_limit) = &self.rate_limit {
+            rate_limit.validate()?;
+        }
+        self.client_disconnect.validate()?;
+        self.ip_ban.validate()?;
+        self.range_abuse.validate()?;
+        self.access_grants.validate()?;
+        self.priority.validate(self.max_concurrent_requests)?;
+        self.request_queue.validate()?;
+        self.maintenance.validate()?;
+        self.key_analytics.validate()?;
+        self.access_export.validate()?;
+        self.endpoint_access.validate()?;
+        self.origin_probe.validate()?;
+        self.unavailable_responses.validate()?;
+        self.metrics_cardinality.validate()?;
+        self.cache_override.validate()?;
+        self.admin.validate()?;
+        self.grpc.validate(self.port)?;
+        self.tls.validate(self.port)?;
+        if self.tls.enabled && self.grpc.enabled && self.tls.port == self.grpc.port {
+            return Err(format!(
+                "server.tls.port and server.grpc.port must differ, both are {}",
+                self.tls.port
+            ));
+        }
+        super::synthetic::validate_endpoints(&self.synthetic_endpoints)
+    }
 }
 
 #[cfg(test)]
@@ -137,6 +351,29 @@ max_uri_length: 16384
         assert_eq!(config.max_uri_length, 16384);
     }
 
+    #[test]
+    fn test_security_limits_config_header_value_limits() {
+        let yaml = r#"
+max_header_value_length: 4096
+header_value_limits:
+  Cookie: 16384
+"#;
+        let config: SecurityLimitsConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.validate().is_ok());
+
+        let limits = config.to_security_limits();
+        assert_eq!(limits.header_value_limit("cookie"), 16384);
+        assert_eq!(limits.header_value_limit("user-agent"), 4096);
+
+        let zero: SecurityLimitsConfig =
+            serde_yaml::from_str("header_value_limits:\n  cookie: 0").unwrap();
+        assert!(zero.validate().is_err());
+
+        let bad_name: SecurityLimitsConfig =
+            serde_yaml::from_str("header_value_limits:\n  \"bad header\": 10").unwrap();
+        assert!(bad_name.validate().is_err());
+    }
+
     #[test]
     fn test_server_config_deserialize_defaults() {
         let yaml = r#"
@@ -213,4 +450,57 @@ security_limits:
             DEFAULT_MAX_URI_LENGTH
         );
     }
+
+    #[test]
+    fn test_worker_config_defaults() {
+        let yaml = r#"
+address: "127.0.0.1"
+port: 8080
+"#;
+        let config: ServerConfig = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(config.workers.thread_name, DEFAULT_WORKER_THREAD_NAME);
+        assert!(config.workers.work_stealing);
+        assert!(config.workers.cpu_affinity.is_empty());
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_worker_config_custom() {
+        let yaml = r#"
+address: "0.0.0.0"
+port: 8080
+threads: 8
+workers:
+  thread_name: "s3-proxy"
+  work_stealing: false
+  cpu_affinity: [2, 3, 4, 5]
+"#;
+        let config: ServerConfig = serde_yaml::from_str(yaml).unwrap();
+
+        assert_eq!(config.workers.thread_name, "s3-proxy");
+        assert!(!config.workers.work_stealing);
+        assert_eq!(config.workers.cpu_affinity, vec![2, 3, 4, 5]);
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_server_config_validate_rejects_invalid_workers() {
+        let mut config: ServerConfig =
+            serde_yaml::from_str("address: \"127.0.0.1\"\nport: 8080\n").unwrap();
+
+        config.threads = 0;
+        assert!(config.validate().unwrap_err().contains("threads"));
+        config.threads = 4;
+
+        config.workers.thread_name = "  ".to_string();
+        assert!(config.validate().unwrap_err().contains("thread_name"));
+        config.workers.thread_name = "yatagarasu".to_string();
+
+        config.workers.cpu_affinity = vec![0, 1, 1];
+        assert!(config.validate().unwrap_err().contains("more than once"));
+
+        config.workers.cpu_affinity = vec![MAX_CPU_AFFINITY_ID];
+        assert!(config.validate().unwrap_err().contains("out of range"));
+    }
 }
@@ -0,0 +1,28 @@
+//! `Server-Timing` response header.
+//!
+//! When enabled for a bucket, responses carry
+//! `Server-Timing: authz;dur=.., cache;dur=.., upstream;dur=.., total;dur=..`
+//! (milliseconds) so the time spent in each phase shows up in browser
+//! devtools. Phases a request never went through are omitted.
+
+use serde::{Deserialize, Serialize};
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Per-bucket `Server-Timing` configuration (YAML format)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerTimingConfig {
+    /// Emit the header (default: true when the section is present)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+impl Default for ServerTimingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_enabled(),
+        }
+    }
+}
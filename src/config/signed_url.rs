@@ -0,0 +1,91 @@
+//! Signed URL configuration.
+//!
+//! A bucket's `signed_url` section requires every request to carry
+//! `?expires=<unix seconds>&sig=<signature>`, where the signature is an
+//! HMAC-SHA256 of the path and expiry (and optionally the client IP) under the
+//! bucket's secret. This gives expiring, CDN-style download links without a
+//! JWT issuer. Verification lives in [`crate::security::signed_url`].
+
+use serde::{Deserialize, Serialize};
+
+use crate::security::IpRange;
+
+fn default_enabled() -> bool {
+    true
+}
+
+/// Per-bucket signed URL configuration (YAML format)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedUrlConfig {
+    /// Require signed URLs (default: true when the section is present)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// HMAC secret shared with the service that issues links
+    pub secret: String,
+    /// Include the client IP in the signature, so a link only works from the
+    /// address it was issued for (default: false)
+    #[serde(default)]
+    pub bind_ip: bool,
+    /// Load balancers (IPs or CIDRs) whose `X-Forwarded-For` is believed when
+    /// binding links to the client IP (default: none, the direct peer is used)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub trusted_proxies: Vec<String>,
+    /// Reject links that expire further than this in the future (default: no limit)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_ttl_secs: Option<u64>,
+}
+
+impl SignedUrlConfig {
+    /// Validate the signed URL configuration for the given bucket
+    pub fn validate(&self, bucket_name: &str) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.secret.trim().is_empty() {
+            return Err(format!(
+                "Bucket '{}': signed_url.secret cannot be empty",
+                bucket_name
+            ));
+        }
+        for entry in &self.trusted_proxies {
+            IpRange::parse(entry).map_err(|e| {
+                format!(
+                    "Bucket '{}': signed_url.trusted_proxies: {}",
+                    bucket_name, e
+                )
+            })?;
+        }
+        if self.max_ttl_secs == Some(0) {
+            return Err(format!(
+                "Bucket '{}': signed_url.max_ttl_secs must be greater than 0",
+                bucket_name
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signed_url_config() {
+        let config: SignedUrlConfig = serde_yaml::from_str("secret: downloads-key").unwrap();
+        assert!(config.enabled);
+        assert!(!config.bind_ip);
+        assert!(config.max_ttl_secs.is_none());
+        assert!(config.validate("downloads").is_ok());
+
+        let empty: SignedUrlConfig = serde_yaml::from_str("secret: \"\"").unwrap();
+        assert!(empty.validate("downloads").is_err());
+
+        let zero_ttl: SignedUrlConfig =
+            serde_yaml::from_str("secret: downloads-key\nmax_ttl_secs: 0").unwrap();
+        assert!(zero_ttl.validate("downloads").is_err());
+
+        let bad_proxy: SignedUrlConfig =
+            serde_yaml::from_str("secret: downloads-key\ntrusted_proxies: [lb]").unwrap();
+        assert!(bad_proxy.validate("downloads").is_err());
+    }
+}
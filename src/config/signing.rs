@@ -0,0 +1,90 @@
+//! Per-bucket upstream request signing options.
+//!
+//! S3-compatible stores differ from AWS in what they expect from a signed
+//! request. Ceph RGW and older MinIO releases only accept `us-east-1` in the
+//! SigV4 credential scope, some gateways want path-style URLs even on their
+//! virtual-host capable endpoints, some reject hashed payloads, and legacy
+//! appliances only understand Signature Version 2. These options adjust how
+//! one bucket's upstream requests are signed without affecting the others.
+
+use serde::{Deserialize, Serialize};
+
+/// AWS signature version used for upstream requests
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SignatureVersion {
+    /// Signature Version 4 (`AWS4-HMAC-SHA256`)
+    #[default]
+    V4,
+    /// Signature Version 2 (`AWS access:signature`, HMAC-SHA1)
+    V2,
+}
+
+/// Signing quirks for an S3-compatible backend (YAML format)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct S3SigningConfig {
+    /// Signature version (default: v4)
+    #[serde(default)]
+    pub version: SignatureVersion,
+    /// Always put the bucket in the path (`/bucket/key`), also on AWS
+    #[serde(default)]
+    pub force_path_style: bool,
+    /// Region placed in the SigV4 credential scope instead of `s3.region`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_region: Option<String>,
+    /// Send `UNSIGNED-PAYLOAD` instead of the payload's SHA-256
+    #[serde(default)]
+    pub unsigned_payload: bool,
+}
+
+impl S3SigningConfig {
+    /// Validate signing options
+    pub fn validate(&self, bucket_name: &str) -> Result<(), String> {
+        if self
+            .signing_region
+            .as_ref()
+            .is_some_and(|r| r.trim().is_empty())
+        {
+            return Err(format!(
+                "Bucket '{}': s3.signing.signing_region cannot be empty",
+                bucket_name
+            ));
+        }
+        if self.version == SignatureVersion::V2
+            && (self.signing_region.is_some() || self.unsigned_payload)
+        {
+            return Err(format!(
+                "Bucket '{}': s3.signing.signing_region and unsigned_payload only apply to v4 signatures",
+                bucket_name
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signing_config() {
+        let config: S3SigningConfig = serde_yaml::from_str("{}").unwrap();
+        assert_eq!(config.version, SignatureVersion::V4);
+        assert!(!config.force_path_style && !config.unsigned_payload);
+        assert!(config.validate("media").is_ok());
+
+        let yaml = "version: v4\nsigning_region: us-east-1\nunsigned_payload: true";
+        let config: S3SigningConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.signing_region.as_deref(), Some("us-east-1"));
+        assert!(config.validate("media").is_ok());
+
+        let config: S3SigningConfig =
+            serde_yaml::from_str("version: v2\nunsigned_payload: true").unwrap();
+        assert!(config.validate("media").is_err());
+        let config = S3SigningConfig {
+            signing_region: Some(" ".to_string()),
+            ..Default::default()
+        };
+        assert!(config.validate("media").is_err());
+    }
+}
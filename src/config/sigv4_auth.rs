@@ -0,0 +1,117 @@
+//! Incoming AWS SigV4 authentication.
+//!
+//! A bucket's `sigv4_auth` section lets S3 SDK clients talk to the proxy with
+//! their usual access key/secret signing: the proxy verifies the client's
+//! signature against the credentials listed here, then re-signs the upstream
+//! request with the bucket's real S3 credentials. Clients never see the S3
+//! keys. Verification lives in [`crate::auth::sigv4`].
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+
+use crate::constants::DEFAULT_SIGV4_MAX_CLOCK_SKEW_SECS;
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn default_max_clock_skew_secs() -> u64 {
+    DEFAULT_SIGV4_MAX_CLOCK_SKEW_SECS
+}
+
+/// Access key pair a client signs with
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SigV4Credential {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+}
+
+/// Per-bucket incoming SigV4 configuration (YAML format)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SigV4AuthConfig {
+    /// Require SigV4-signed requests (default: true when the section is present)
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Access keys accepted for this bucket
+    pub credentials: Vec<SigV4Credential>,
+    /// Region clients must sign for (default: any region)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    /// Allowed difference between the request time and the proxy clock (default: 900)
+    #[serde(default = "default_max_clock_skew_secs")]
+    pub max_clock_skew_secs: u64,
+}
+
+impl SigV4AuthConfig {
+    /// Secret for an access key id
+    pub fn secret_for(&self, access_key_id: &str) -> Option<&str> {
+        self.credentials
+            .iter()
+            .find(|c| c.access_key_id == access_key_id)
+            .map(|c| c.secret_access_key.as_str())
+    }
+
+    /// Validate the SigV4 configuration for the given bucket
+    pub fn validate(&self, bucket_name: &str) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if self.credentials.is_empty() {
+            return Err(format!(
+                "Bucket '{}': sigv4_auth.credentials cannot be empty",
+                bucket_name
+            ));
+        }
+        let mut seen = HashSet::new();
+        for credential in &self.credentials {
+            if credential.access_key_id.trim().is_empty()
+                || credential.secret_access_key.trim().is_empty()
+            {
+                return Err(format!(
+                    "Bucket '{}': sigv4_auth credentials need an access_key_id and secret_access_key",
+                    bucket_name
+                ));
+            }
+            if !seen.insert(credential.access_key_id.as_str()) {
+                return Err(format!(
+                    "Bucket '{}': duplicate sigv4_auth access_key_id '{}'",
+                    bucket_name, credential.access_key_id
+                ));
+            }
+        }
+        if self.max_clock_skew_secs == 0 {
+            return Err(format!(
+                "Bucket '{}': sigv4_auth.max_clock_skew_secs must be greater than 0",
+                bucket_name
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sigv4_auth_config() {
+        let config: SigV4AuthConfig = serde_yaml::from_str(
+            "credentials:\n  - access_key_id: AKIDCLIENT\n    secret_access_key: client-secret",
+        )
+        .unwrap();
+        assert!(config.enabled);
+        assert_eq!(config.max_clock_skew_secs, 900);
+        assert_eq!(config.secret_for("AKIDCLIENT"), Some("client-secret"));
+        assert_eq!(config.secret_for("AKIDOTHER"), None);
+        assert!(config.validate("uploads").is_ok());
+
+        let empty: SigV4AuthConfig = serde_yaml::from_str("credentials: []").unwrap();
+        assert!(empty.validate("uploads").is_err());
+
+        let duplicate: SigV4AuthConfig = serde_yaml::from_str(
+            "credentials:\n  - {access_key_id: A, secret_access_key: x}\n  - {access_key_id: A, secret_access_key: y}",
+        )
+        .unwrap();
+        assert!(duplicate.validate("uploads").is_err());
+    }
+}
@@ -0,0 +1,211 @@
+//! Per-bucket size class policies.
+//!
+//! Objects of very different sizes want different handling: a 2 KiB icon is
+//! worth keeping in memory, a 20 GiB video only evicts everything else and
+//! needs more patience from the upstream connection. A bucket lists size
+//! classes in ascending order of `max_bytes`; an object belongs to the first
+//! class it fits. The size comes from the response itself for cache
+//! decisions, and from sizes learned on earlier GET/HEAD responses for
+//! decisions made before the upstream request (timeouts, replica choice).
+
+use serde::{Deserialize, Serialize};
+
+use super::bucket::BucketConfig;
+
+/// How responses in a size class are cached
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SizeClassCache {
+    /// Cache as usual, in every configured layer
+    #[default]
+    Default,
+    /// Cache in the memory layer only
+    Memory,
+    /// Never cache; stream straight through
+    None,
+}
+
+impl SizeClassCache {
+    /// Label used in logs and docs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Memory => "memory",
+            Self::None => "none",
+        }
+    }
+}
+
+/// One size class of a bucket
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SizeClassConfig {
+    /// Name used in logs and metrics (e.g. "tiny", "huge")
+    pub name: String,
+    /// Largest object in this class, inclusive (omit for "everything larger")
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_bytes: Option<u64>,
+    /// Cache policy for the class (default: default)
+    #[serde(default)]
+    pub cache: SizeClassCache,
+    /// Upstream read/write timeout, replacing `s3.timeout` (default: unchanged)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_secs: Option<u64>,
+    /// Replica tried first while healthy (default: priority order)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub replica: Option<String>,
+}
+
+/// The class an object of `size` bytes belongs to, if any
+pub fn classify(classes: &[SizeClassConfig], size: u64) -> Option<&SizeClassConfig> {
+    classes
+        .iter()
+        .find(|class| class.max_bytes.map_or(true, |max| size <= max))
+}
+
+/// Validate a bucket's size classes
+pub fn validate(bucket: &BucketConfig) -> Result<(), String> {
+    let mut previous_max: Option<u64> = None;
+    for (idx, class) in bucket.size_classes.iter().enumerate() {
+        // Names become metric labels
+        if class.name.is_empty()
+            || !class
+                .name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(format!(
+                "Bucket '{}': size_classes[{}].name must be letters, digits, '-' or '_'",
+                bucket.name, idx
+            ));
+        }
+        if bucket.size_classes[..idx]
+            .iter()
+            .any(|other| other.name == class.name)
+        {
+            return Err(format!(
+                "Bucket '{}': duplicate size class '{}'",
+                bucket.name, class.name
+            ));
+        }
+        match class.max_bytes {
+            Some(max) if previous_max.is_some_and(|prev| max <= prev) => {
+                return Err(format!(
+                    "Bucket '{}': size class '{}' must have a larger max_bytes than the class before it",
+                    bucket.name, class.name
+                ));
+            }
+            Some(max) => previous_max = Some(max),
+            None if idx + 1 != bucket.size_classes.len() => {
+                return Err(format!(
+                    "Bucket '{}': only the last size class may omit max_bytes",
+                    bucket.name
+                ));
+            }
+            None => {}
+        }
+        if class.timeout_secs == Some(0) {
+            return Err(format!(
+                "Bucket '{}': size class '{}' timeout_secs must be greater than 0",
+                bucket.name, class.name
+            ));
+        }
+        if let Some(replica) = &class.replica {
+            let known = bucket
+                .s3
+                .replicas
+                .as_ref()
+                .is_some_and(|replicas| replicas.iter().any(|r| &r.name == replica));
+            if !known {
+                return Err(format!(
+                    "Bucket '{}': size class '{}' names unknown replica '{}'",
+                    bucket.name, class.name, replica
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bucket(size_classes: &str) -> BucketConfig {
+        let yaml = format!(
+            r#"
+name: media
+path_prefix: /media
+s3:
+  replicas:
+    - name: primary
+      bucket: media
+      region: us-east-1
+      access_key: AKIA
+      secret_key: secret
+      priority: 1
+    - name: bulk
+      bucket: media-bulk
+      region: us-east-1
+      access_key: AKIA
+      secret_key: secret
+      priority: 2
+size_classes:
+{}
+"#,
+            size_classes
+        );
+        serde_yaml::from_str(&yaml).unwrap()
+    }
+
+    #[test]
+    fn test_classify_and_validate_size_classes() {
+        let config = bucket(
+            r#"
+  - name: tiny
+    max_bytes: 65536
+    cache: memory
+  - name: medium
+    max_bytes: 104857600
+  - name: huge
+    cache: none
+    timeout_secs: 600
+    replica: bulk
+"#,
+        );
+        assert!(validate(&config).is_ok());
+        let classes = &config.size_classes;
+        assert_eq!(classes[0].cache, SizeClassCache::Memory);
+        assert_eq!(classes[1].cache, SizeClassCache::Default);
+        assert_eq!(classify(classes, 0).unwrap().name, "tiny");
+        assert_eq!(classify(classes, 65536).unwrap().name, "tiny");
+        assert_eq!(classify(classes, 65537).unwrap().name, "medium");
+        assert_eq!(classify(classes, u64::MAX).unwrap().name, "huge");
+        // Without an open-ended class, larger objects are unclassified
+        assert!(classify(&classes[..2], 104857601).is_none());
+
+        let unordered = bucket(
+            r#"
+  - name: big
+    max_bytes: 1000
+  - name: small
+    max_bytes: 10
+"#,
+        );
+        assert!(validate(&unordered).is_err());
+        let open_middle = bucket(
+            r#"
+  - name: rest
+  - name: small
+    max_bytes: 10
+"#,
+        );
+        assert!(validate(&open_middle).is_err());
+        let unknown_replica = bucket(
+            r#"
+  - name: huge
+    replica: elsewhere
+"#,
+        );
+        assert!(validate(&unknown_replica).is_err());
+    }
+}
@@ -0,0 +1,212 @@
+//! Startup switches that turn subsystems off regardless of the config file.
+//!
+//! `--disable cache,opa,audit` (or `YATAGARASU_DISABLE=cache,opa,audit`) is an
+//! emergency lever: when a subsystem such as the Redis cache layer is causing
+//! an outage, the proxy can be restarted without it and without editing the
+//! config. The switches are re-applied after every config reload, so a SIGHUP
+//! does not bring a disabled subsystem back.
+
+use std::fmt;
+use std::str::FromStr;
+
+use super::Config;
+
+/// Environment variable read when `--disable` is not given
+pub const DISABLE_SUBSYSTEMS_ENV: &str = "YATAGARASU_DISABLE";
+
+/// A subsystem that can be disabled at startup
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Subsystem {
+    /// The whole response cache
+    Cache,
+    /// The Redis cache layer (other layers keep working)
+    Redis,
+    /// The disk cache layer (other layers keep working)
+    Disk,
+    /// OPA authorization on every bucket
+    Opa,
+    /// OpenFGA authorization on every bucket
+    OpenFga,
+    /// Audit logging
+    Audit,
+    /// Global and per-IP rate limiting
+    RateLimit,
+    /// Request coalescing
+    Coalescing,
+    /// Image optimization
+    ImageOptimization,
+    /// OpenTelemetry tracing
+    Tracing,
+}
+
+impl Subsystem {
+    /// Every subsystem, in the order listed in `--help`
+    pub const ALL: &'static [Subsystem] = &[
+        Subsystem::Cache,
+        Subsystem::Redis,
+        Subsystem::Disk,
+        Subsystem::Opa,
+        Subsystem::OpenFga,
+        Subsystem::Audit,
+        Subsystem::RateLimit,
+        Subsystem::Coalescing,
+        Subsystem::ImageOptimization,
+        Subsystem::Tracing,
+    ];
+
+    /// Name used on the command line
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Cache => "cache",
+            Self::Redis => "redis",
+            Self::Disk => "disk",
+            Self::Opa => "opa",
+            Self::OpenFga => "openfga",
+            Self::Audit => "audit",
+            Self::RateLimit => "rate_limit",
+            Self::Coalescing => "coalescing",
+            Self::ImageOptimization => "image_optimization",
+            Self::Tracing => "tracing",
+        }
+    }
+
+    /// Turn this subsystem off in `config`
+    pub fn disable(&self, config: &mut Config) {
+        match self {
+            Self::Cache => {
+                if let Some(cache) = config.cache.as_mut() {
+                    cache.enabled = false;
+                }
+            }
+            Self::Redis => remove_cache_layer(config, "redis"),
+            Self::Disk => remove_cache_layer(config, "disk"),
+            Self::Opa => remove_authorization(config, "opa"),
+            Self::OpenFga => remove_authorization(config, "openfga"),
+            Self::Audit => {
+                if let Some(audit) = config.audit_log.as_mut() {
+                    audit.enabled = false;
+                }
+            }
+            Self::RateLimit => {
+                if let Some(rate_limit) = config.server.rate_limit.as_mut() {
+                    rate_limit.enabled = false;
+                }
+            }
+            Self::Coalescing => config.server.coalescing.enabled = false,
+            Self::ImageOptimization => config.image_optimization.enabled = false,
+            Self::Tracing => config.observability.tracing.enabled = false,
+        }
+    }
+}
+
+impl fmt::Display for Subsystem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Subsystem {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let name = s.trim().to_ascii_lowercase().replace('-', "_");
+        Subsystem::ALL
+            .iter()
+            .find(|subsystem| subsystem.as_str() == name)
+            .copied()
+            .ok_or_else(|| {
+                let known: Vec<&str> = Subsystem::ALL.iter().map(|s| s.as_str()).collect();
+                format!(
+                    "Unknown subsystem '{}' (expected one of: {})",
+                    s.trim(),
+                    known.join(", ")
+                )
+            })
+    }
+}
+
+/// Parse a comma-separated list such as `cache,opa,audit`
+pub fn parse_list(list: &str) -> Result<Vec<Subsystem>, String> {
+    let mut subsystems = Vec::new();
+    for name in list.split(',').filter(|name| !name.trim().is_empty()) {
+        let subsystem: Subsystem = name.parse()?;
+        if !subsystems.contains(&subsystem) {
+            subsystems.push(subsystem);
+        }
+    }
+    Ok(subsystems)
+}
+
+/// Turn off every subsystem in `subsystems`
+pub fn disable_all(config: &mut Config, subsystems: &[Subsystem]) {
+    for subsystem in subsystems {
+        subsystem.disable(config);
+    }
+}
+
+fn remove_cache_layer(config: &mut Config, layer: &str) {
+    if let Some(cache) = config.cache.as_mut() {
+        cache.cache_layers.retain(|l| l != layer);
+    }
+}
+
+fn remove_authorization(config: &mut Config, auth_type: &str) {
+    for bucket in &mut config.buckets {
+        if bucket
+            .authorization
+            .as_ref()
+            .is_some_and(|a| a.auth_type == auth_type)
+        {
+            bucket.authorization = None;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disable_subsystems() {
+        assert_eq!(
+            parse_list("cache, OPA,audit,cache"),
+            Ok(vec![Subsystem::Cache, Subsystem::Opa, Subsystem::Audit])
+        );
+        assert_eq!(parse_list("rate-limit"), Ok(vec![Subsystem::RateLimit]));
+        assert_eq!(parse_list(""), Ok(vec![]));
+        assert!(parse_list("cache,memcached").is_err());
+
+        let mut config = Config::from_yaml_with_env(
+            r#"
+server:
+  address: "127.0.0.1"
+  port: 8080
+buckets:
+  - name: "products"
+    path_prefix: "/products"
+    s3:
+      bucket: "products"
+      region: "us-east-1"
+      access_key: "key"
+      secret_key: "secret"
+    authorization:
+      type: opa
+      opa_url: "http://localhost:8181"
+      opa_policy_path: "yatagarasu/authz/allow"
+cache:
+  enabled: true
+  cache_layers: ["memory", "redis"]
+"#,
+        )
+        .unwrap();
+
+        disable_all(&mut config, &[Subsystem::Redis, Subsystem::Opa]);
+        let cache = config.cache.as_ref().unwrap();
+        assert!(cache.enabled);
+        assert_eq!(cache.cache_layers, vec!["memory".to_string()]);
+        assert!(config.buckets[0].authorization.is_none());
+
+        disable_all(&mut config, &[Subsystem::Cache]);
+        assert!(!config.cache.as_ref().unwrap().enabled);
+    }
+}
@@ -0,0 +1,108 @@
+//! Synthetic endpoints for smoke tests.
+//!
+//! A synthetic endpoint answers `GET`/`HEAD` on a fixed path with generated
+//! data of a configured size. Load balancers and monitoring can exercise the
+//! full proxy path (listener, filters, body streaming) without touching a real
+//! bucket or its credentials.
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{DEFAULT_SYNTHETIC_CONTENT_TYPE, MAX_SYNTHETIC_ENDPOINT_SIZE_BYTES};
+
+/// Paths served by built-in endpoints, which synthetic routes cannot shadow
+const RESERVED_PREFIXES: &[&str] = &["/health", "/ready", "/metrics", "/admin"];
+
+fn default_content_type() -> String {
+    DEFAULT_SYNTHETIC_CONTENT_TYPE.to_string()
+}
+
+/// One synthetic route (YAML format)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SyntheticEndpointConfig {
+    /// Exact request path, e.g. `/__selftest/1mb`
+    pub path: String,
+    /// Body size in bytes (at most 1 GiB)
+    pub size_bytes: u64,
+    /// Content-Type of the body (default: application/octet-stream)
+    #[serde(default = "default_content_type")]
+    pub content_type: String,
+}
+
+impl SyntheticEndpointConfig {
+    /// Validate path and size
+    pub fn validate(&self) -> Result<(), String> {
+        if !self.path.starts_with('/') {
+            return Err(format!(
+                "server.synthetic_endpoints: path '{}' must start with /",
+                self.path
+            ));
+        }
+        if RESERVED_PREFIXES
+            .iter()
+            .any(|prefix| self.path.starts_with(prefix))
+        {
+            return Err(format!(
+                "server.synthetic_endpoints: path '{}' is reserved for a built-in endpoint",
+                self.path
+            ));
+        }
+        if self.size_bytes > MAX_SYNTHETIC_ENDPOINT_SIZE_BYTES {
+            return Err(format!(
+                "server.synthetic_endpoints: size_bytes for '{}' must be at most {}",
+                self.path, MAX_SYNTHETIC_ENDPOINT_SIZE_BYTES
+            ));
+        }
+        if self.content_type.trim().is_empty() {
+            return Err(format!(
+                "server.synthetic_endpoints: content_type for '{}' cannot be empty",
+                self.path
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Validate every endpoint and reject duplicate paths
+pub fn validate_endpoints(endpoints: &[SyntheticEndpointConfig]) -> Result<(), String> {
+    let mut seen = std::collections::HashSet::new();
+    for endpoint in endpoints {
+        endpoint.validate()?;
+        if !seen.insert(endpoint.path.as_str()) {
+            return Err(format!(
+                "server.synthetic_endpoints: duplicate path '{}'",
+                endpoint.path
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_synthetic_endpoint_validation() {
+        let endpoints: Vec<SyntheticEndpointConfig> = serde_yaml::from_str(
+            "- path: /__selftest/1mb\n  size_bytes: 1048576\n- path: /__selftest/empty\n  size_bytes: 0\n  content_type: text/plain",
+        )
+        .unwrap();
+        assert_eq!(endpoints[0].content_type, "application/octet-stream");
+        assert!(validate_endpoints(&endpoints).is_ok());
+
+        let duplicate = vec![endpoints[0].clone(), endpoints[0].clone()];
+        assert!(validate_endpoints(&duplicate).is_err());
+
+        let reserved = SyntheticEndpointConfig {
+            path: "/health/synthetic".to_string(),
+            ..endpoints[0].clone()
+        };
+        assert!(reserved.validate().is_err());
+
+        let too_large = SyntheticEndpointConfig {
+            size_bytes: MAX_SYNTHETIC_ENDPOINT_SIZE_BYTES + 1,
+            ..endpoints[0].clone()
+        };
+        assert!(too_large.validate().is_err());
+    }
+}
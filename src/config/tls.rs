@@ -0,0 +1,382 @@
+//! HTTPS listener configuration types.
+//!
+//! The proxy can terminate TLS itself on `server.tls.port`, next to the plain
+//! HTTP listener on `server.port`. The certificate is read from a directory
+//! maintained by an external ACME client (certbot, lego, ...): the directory
+//! is polled every `reload_interval_secs`, and renewed files are picked up by
+//! new handshakes without a restart. OCSP responses for the certificate are
+//! fetched from its responder and stapled to handshakes.
+//!
+//! With `client_ca_file` set, the listener asks clients for a certificate
+//! issued by one of the CAs in that file (mutual TLS). `client_auth` decides
+//! whether a client without one is turned away or let through; a certificate
+//! that is presented but does not verify always fails the handshake.
+//!
+//! Session resumption (tickets and the server-side session cache), the
+//! minimum protocol version, cipher lists and the protocols offered over
+//! ALPN are configurable; the defaults accept TLS 1.2 and 1.3 with
+//! OpenSSL's default ciphers and offer HTTP/2 before HTTP/1.1.
+//!
+//! The listener is only available in builds with the `tls` Cargo feature.
+//! It is set up at startup, so changes to this section need a restart; the
+//! certificate files themselves are reloaded while running.
+//!
+//! Default values are sourced from `crate::constants`.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::constants::{
+    DEFAULT_TLS_ALPN, DEFAULT_TLS_CERT_FILE, DEFAULT_TLS_KEY_FILE, DEFAULT_TLS_PORT,
+    DEFAULT_TLS_RELOAD_INTERVAL_SECS, DEFAULT_TLS_SESSION_CACHE_SIZE,
+};
+
+/// Protocols the listener can serve over ALPN
+const SUPPORTED_ALPN: &[&str] = &["h2", "http/1.1"];
+
+fn default_port() -> u16 {
+    DEFAULT_TLS_PORT
+}
+
+fn default_cert_file() -> String {
+    DEFAULT_TLS_CERT_FILE.to_string()
+}
+
+fn default_key_file() -> String {
+    DEFAULT_TLS_KEY_FILE.to_string()
+}
+
+fn default_reload_interval_secs() -> u64 {
+    DEFAULT_TLS_RELOAD_INTERVAL_SECS
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_session_cache_size() -> usize {
+    DEFAULT_TLS_SESSION_CACHE_SIZE
+}
+
+fn default_alpn() -> Vec<String> {
+    DEFAULT_TLS_ALPN.iter().map(|p| p.to_string()).collect()
+}
+
+/// Whether clients of the HTTPS listener must present a certificate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ClientAuthMode {
+    /// Handshakes without a client certificate fail
+    #[default]
+    Required,
+    /// Clients without a certificate are served; presented ones must verify
+    Optional,
+}
+
+impl ClientAuthMode {
+    /// Label used in logs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ClientAuthMode::Required => "required",
+            ClientAuthMode::Optional => "optional",
+        }
+    }
+}
+
+/// Lowest TLS protocol version the HTTPS listener accepts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TlsVersion {
+    /// TLS 1.2 and 1.3
+    #[default]
+    #[serde(rename = "tls1.2")]
+    Tls12,
+    /// TLS 1.3 only
+    #[serde(rename = "tls1.3")]
+    Tls13,
+}
+
+impl TlsVersion {
+    /// Label used in logs
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TlsVersion::Tls12 => "tls1.2",
+            TlsVersion::Tls13 => "tls1.3",
+        }
+    }
+}
+
+/// HTTPS listener with certificates from an ACME client's directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TlsListenerConfig {
+    /// Start the HTTPS listener (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+    /// Listen port on `server.address` (default: 8443)
+    #[serde(default = "default_port")]
+    pub port: u16,
+    /// Directory the ACME client writes the certificate and key to,
+    /// e.g. `/etc/letsencrypt/live/example.com`; required when enabled
+    #[serde(default)]
+    pub cert_dir: String,
+    /// PEM certificate chain in `cert_dir`, leaf first (default: fullchain.pem)
+    #[serde(default = "default_cert_file")]
+    pub cert_file: String,
+    /// PEM private key in `cert_dir` (default: privkey.pem)
+    #[serde(default = "default_key_file")]
+    pub key_file: String,
+    /// How often `cert_dir` is checked for renewed files (default: 60)
+    #[serde(default = "default_reload_interval_secs")]
+    pub reload_interval_secs: u64,
+    /// Fetch OCSP responses for the certificate and staple them (default: true)
+    #[serde(default = "default_true")]
+    pub ocsp_stapling: bool,
+    /// PEM bundle of CAs that issue client certificates; enables mutual TLS
+    /// (default: none)
+    #[serde(default)]
+    pub client_ca_file: Option<String>,
+    /// Whether a client certificate is required or optional when
+    /// `client_ca_file` is set (default: required)
+    #[serde(default)]
+    pub client_auth: ClientAuthMode,
+    /// Resume sessions from stateless session tickets (default: true)
+    #[serde(default = "default_true")]
+    pub session_tickets: bool,
+    /// Resume sessions by ID from the server-side session cache (default: true)
+    #[serde(default = "default_true")]
+    pub session_ids: bool,
+    /// Sessions kept in the server-side session cache (default: 20480)
+    #[serde(default = "default_session_cache_size")]
+    pub session_cache_size: usize,
+    /// Lowest protocol version accepted, `tls1.2` or `tls1.3` (default: tls1.2)
+    #[serde(default)]
+    pub min_version: TlsVersion,
+    /// OpenSSL cipher list for TLS 1.2, e.g. `ECDHE+AESGCM:ECDHE+CHACHA20`
+    /// (default: OpenSSL's)
+    #[serde(default)]
+    pub cipher_list: Option<String>,
+    /// TLS 1.3 cipher suites, e.g. `TLS_AES_256_GCM_SHA384:TLS_AES_128_GCM_SHA256`
+    /// (default: OpenSSL's)
+    #[serde(default)]
+    pub ciphersuites: Option<String>,
+    /// Protocols offered over ALPN in preference order, from `h2` and
+    /// `http/1.1` (default: [h2, http/1.1])
+    #[serde(default = "default_alpn")]
+    pub alpn: Vec<String>,
+}
+
+impl Default for TlsListenerConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: default_port(),
+            cert_dir: String::new(),
+            cert_file: default_cert_file(),
+            key_file: default_key_file(),
+            reload_interval_secs: default_reload_interval_secs(),
+            ocsp_stapling: true,
+            client_ca_file: None,
+            client_auth: ClientAuthMode::default(),
+            session_tickets: true,
+            session_ids: true,
+            session_cache_size: default_session_cache_size(),
+            min_version: TlsVersion::default(),
+            cipher_list: None,
+            ciphersuites: None,
+            alpn: default_alpn(),
+        }
+    }
+}
+
+impl TlsListenerConfig {
+    /// Full path of the certificate chain
+    pub fn cert_path(&self) -> PathBuf {
+        Path::new(&self.cert_dir).join(&self.cert_file)
+    }
+
+    /// Full path of the private key
+    pub fn key_path(&self) -> PathBuf {
+        Path::new(&self.cert_dir).join(&self.key_file)
+    }
+
+    /// Validate the listener settings; `http_port` is the proxy's own port
+    pub fn validate(&self, http_port: u16) -> Result<(), String> {
+        if !self.enabled {
+            return Ok(());
+        }
+        if !cfg!(feature = "tls") {
+            return Err("server.tls.enabled requires a build with the 'tls' feature".to_string());
+        }
+        if self.port == 0 || self.port == http_port {
+            return Err(format!(
+                "server.tls.port must be non-zero and differ from server.port, got {}",
+                self.port
+            ));
+        }
+        for (field, value) in [
+            ("cert_dir", &self.cert_dir),
+            ("cert_file", &self.cert_file),
+            ("key_file", &self.key_file),
+        ] {
+            if value.trim().is_empty() {
+                return Err(format!("server.tls.{} cannot be empty", field));
+            }
+        }
+        if self.reload_interval_secs == 0 {
+            return Err("server.tls.reload_interval_secs must be greater than 0".to_string());
+        }
+        if let Some(ca_file) = &self.client_ca_file {
+            if ca_file.trim().is_empty() {
+                return Err("server.tls.client_ca_file cannot be empty".to_string());
+            }
+            if !Path::new(ca_file).is_file() {
+                return Err(format!(
+                    "server.tls.client_ca_file '{}' does not exist",
+                    ca_file
+                ));
+            }
+        }
+        if self.session_ids && self.session_cache_size == 0 {
+            return Err(
+                "server.tls.session_cache_size must be greater than 0 when session_ids is enabled"
+                    .to_string(),
+            );
+        }
+        for (field, value) in [
+            ("cipher_list", &self.cipher_list),
+            ("ciphersuites", &self.ciphersuites),
+        ] {
+            if value.as_ref().is_some_and(|v| v.trim().is_empty()) {
+                return Err(format!("server.tls.{} cannot be empty", field));
+            }
+        }
+        if self.alpn.is_empty() {
+            return Err("server.tls.alpn must list at least one protocol".to_string());
+        }
+        for protocol in &self.alpn {
+            if !SUPPORTED_ALPN.contains(&protocol.as_str()) {
+                return Err(format!(
+                    "server.tls.alpn protocol '{}' is not supported (expected one of: {})",
+                    protocol,
+                    SUPPORTED_ALPN.join(", ")
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tls_listener_config_defaults_and_validation() {
+        let config: TlsListenerConfig = serde_yaml::from_str("{}").unwrap();
+        assert!(!config.enabled);
+        assert_eq!(config.port, 8443);
+        assert!(config.ocsp_stapling);
+        assert!(config.validate(8080).is_ok());
+
+        let config: TlsListenerConfig = serde_yaml::from_str(
+            r#"
+enabled: true
+cert_dir: /etc/letsencrypt/live/example.com
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            config.cert_path(),
+            PathBuf::from("/etc/letsencrypt/live/example.com/fullchain.pem")
+        );
+        assert_eq!(
+            config.key_path(),
+            PathBuf::from("/etc/letsencrypt/live/example.com/privkey.pem")
+        );
+        assert_eq!(config.validate(8080).is_ok(), cfg!(feature = "tls"));
+
+        if cfg!(feature = "tls") {
+            assert!(config.validate(8443).is_err());
+
+            let mut no_dir = config.clone();
+            no_dir.cert_dir = String::new();
+            assert!(no_dir.validate(8080).is_err());
+
+            let mut no_interval = config.clone();
+            no_interval.reload_interval_secs = 0;
+            assert!(no_interval.validate(8080).is_err());
+
+            let mut missing_ca = config;
+            missing_ca.client_ca_file = Some("/nonexistent/clients.pem".to_string());
+            assert!(missing_ca.validate(8080).is_err());
+        }
+    }
+
+    #[test]
+    fn test_tls_listener_client_auth_modes() {
+        let config: TlsListenerConfig =
+            serde_yaml::from_str("client_ca_file: /etc/ca.pem").unwrap();
+        assert_eq!(config.client_ca_file.as_deref(), Some("/etc/ca.pem"));
+        assert_eq!(config.client_auth, ClientAuthMode::Required);
+
+        let config: TlsListenerConfig = serde_yaml::from_str(
+            r#"
+client_ca_file: /etc/ca.pem
+client_auth: optional
+"#,
+        )
+        .unwrap();
+        assert_eq!(config.client_auth, ClientAuthMode::Optional);
+        assert!(serde_yaml::from_str::<TlsListenerConfig>("client_auth: sometimes").is_err());
+    }
+
+    #[test]
+    fn test_tls_listener_session_protocol_and_alpn_settings() {
+        let config: TlsListenerConfig = serde_yaml::from_str("{}").unwrap();
+        assert!(config.session_tickets);
+        assert!(config.session_ids);
+        assert_eq!(config.session_cache_size, 20_480);
+        assert_eq!(config.min_version, TlsVersion::Tls12);
+        assert_eq!(config.alpn, vec!["h2".to_string(), "http/1.1".to_string()]);
+
+        let config: TlsListenerConfig = serde_yaml::from_str(
+            r#"
+enabled: true
+cert_dir: /etc/letsencrypt/live/example.com
+session_tickets: false
+min_version: tls1.3
+ciphersuites: TLS_AES_256_GCM_SHA384
+alpn: [http/1.1]
+"#,
+        )
+        .unwrap();
+        assert!(!config.session_tickets);
+        assert_eq!(config.min_version, TlsVersion::Tls13);
+        assert_eq!(config.min_version.as_str(), "tls1.3");
+        assert_eq!(
+            config.ciphersuites.as_deref(),
+            Some("TLS_AES_256_GCM_SHA384")
+        );
+        assert_eq!(config.alpn, vec!["http/1.1".to_string()]);
+        assert!(serde_yaml::from_str::<TlsListenerConfig>("min_version: tls1.1").is_err());
+
+        if cfg!(feature = "tls") {
+            assert!(config.validate(8080).is_ok());
+
+            let mut unknown_alpn = config.clone();
+            unknown_alpn.alpn = vec!["spdy/3".to_string()];
+            assert!(unknown_alpn.validate(8080).is_err());
+
+            let mut no_alpn = config.clone();
+            no_alpn.alpn.clear();
+            assert!(no_alpn.validate(8080).is_err());
+
+            let mut empty_ciphers = config.clone();
+            empty_ciphers.cipher_list = Some(" ".to_string());
+            assert!(empty_ciphers.validate(8080).is_err());
+
+            let mut no_cache = config;
+            no_cache.session_cache_size = 0;
+            assert!(no_cache.validate(8080).is_err());
+        }
+    }
+}
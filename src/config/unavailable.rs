@@ -0,0 +1,301 @@
+//! 503 response configuration for overload and origin failures.
+//!
+//! The proxy answers 503 when the concurrency limit is reached, when
+//! resources are exhausted, when a bucket's origins are unavailable (open
+//! circuit breaker) and when a bucket's bulkhead is full. Each response can be
+//! customised with its own message, `Retry-After` and body template. For
+//! origin failures `Retry-After` can follow the time left until the breaker
+//! lets a test request through.
+//!
+//! Default values are sourced from `crate::constants`.
+
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+use crate::constants::{
+    DEFAULT_BULKHEAD_FULL_RETRY_AFTER_SECS, DEFAULT_CONCURRENCY_LIMIT_RETRY_AFTER_SECS,
+    DEFAULT_ORIGIN_UNAVAILABLE_RETRY_AFTER_SECS, DEFAULT_RESOURCE_EXHAUSTED_RETRY_AFTER_SECS,
+    DEFAULT_UNAVAILABLE_CONTENT_TYPE,
+};
+use crate::error::ErrorCode;
+
+/// Why a request was answered with 503
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnavailableReason {
+    /// `server.max_concurrent_requests` reached
+    ConcurrencyLimit,
+    /// Resource monitor is shedding load
+    ResourceExhausted,
+    /// The bucket's origins are down (circuit breaker open)
+    OriginUnavailable,
+    /// The bucket's bulkhead is full
+    BulkheadFull,
+}
+
+impl UnavailableReason {
+    /// Config key and `{{reason}}` value
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UnavailableReason::ConcurrencyLimit => "concurrency_limit",
+            UnavailableReason::ResourceExhausted => "resource_exhausted",
+            UnavailableReason::OriginUnavailable => "origin_unavailable",
+            UnavailableReason::BulkheadFull => "bulkhead_full",
+        }
+    }
+
+    /// Machine-readable error code
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            UnavailableReason::ConcurrencyLimit => ErrorCode::ConcurrencyLimit,
+            UnavailableReason::ResourceExhausted => ErrorCode::ResourceExhausted,
+            UnavailableReason::OriginUnavailable => ErrorCode::OriginUnavailable,
+            UnavailableReason::BulkheadFull => ErrorCode::BulkheadFull,
+        }
+    }
+
+    fn default_message(&self) -> &'static str {
+        match self {
+            UnavailableReason::ConcurrencyLimit => {
+                "Server has reached maximum concurrent request limit. Please retry after {{retry_after}} seconds."
+            }
+            UnavailableReason::ResourceExhausted => {
+                "Server is under heavy load. Please retry after {{retry_after}} seconds."
+            }
+            UnavailableReason::OriginUnavailable => {
+                "S3 backend is experiencing issues. Circuit breaker is open."
+            }
+            UnavailableReason::BulkheadFull => {
+                "Too many in-flight requests to bucket '{{bucket}}'. Please retry shortly."
+            }
+        }
+    }
+
+    fn default_retry_after_secs(&self) -> u64 {
+        match self {
+            UnavailableReason::ConcurrencyLimit => DEFAULT_CONCURRENCY_LIMIT_RETRY_AFTER_SECS,
+            UnavailableReason::ResourceExhausted => DEFAULT_RESOURCE_EXHAUSTED_RETRY_AFTER_SECS,
+            UnavailableReason::OriginUnavailable => DEFAULT_ORIGIN_UNAVAILABLE_RETRY_AFTER_SECS,
+            UnavailableReason::BulkheadFull => DEFAULT_BULKHEAD_FULL_RETRY_AFTER_SECS,
+        }
+    }
+}
+
+/// How the `Retry-After` value is chosen
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RetryAfterStrategy {
+    /// Always send `retry_after_secs` (default)
+    #[default]
+    Fixed,
+    /// Seconds until the open breaker allows a test request, falling back to
+    /// `retry_after_secs` when unknown (origin_unavailable only)
+    BreakerCooldown,
+}
+
+/// One customised 503 response (YAML format)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UnavailableResponseConfig {
+    /// Message for the JSON body and `{{message}}` (default: per reason)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Retry-After header value in seconds (default: 5, 10, 60 or 1 per reason)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub retry_after_secs: Option<u64>,
+    /// Fixed Retry-After or breaker cooldown (default: fixed)
+    #[serde(default)]
+    pub retry_after: RetryAfterStrategy,
+    /// Custom response body; `{{message}}`, `{{retry_after}}`, `{{bucket}}` and
+    /// `{{reason}}` are substituted (default: JSON error body)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    /// Content-Type of the custom template (default: text/html; charset=utf-8)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+}
+
+/// 503 responses per reason (YAML format)
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UnavailableResponsesConfig {
+    #[serde(default)]
+    pub concurrency_limit: UnavailableResponseConfig,
+    #[serde(default)]
+    pub resource_exhausted: UnavailableResponseConfig,
+    #[serde(default)]
+    pub origin_unavailable: UnavailableResponseConfig,
+    #[serde(default)]
+    pub bulkhead_full: UnavailableResponseConfig,
+}
+
+/// A rendered 503 response
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnavailableResponse {
+    pub content_type: String,
+    pub body: String,
+    pub retry_after_secs: u64,
+}
+
+impl UnavailableResponsesConfig {
+    /// Config for one reason
+    pub fn get(&self, reason: UnavailableReason) -> &UnavailableResponseConfig {
+        match reason {
+            UnavailableReason::ConcurrencyLimit => &self.concurrency_limit,
+            UnavailableReason::ResourceExhausted => &self.resource_exhausted,
+            UnavailableReason::OriginUnavailable => &self.origin_unavailable,
+            UnavailableReason::BulkheadFull => &self.bulkhead_full,
+        }
+    }
+
+    /// Validate content types and Retry-After strategies
+    pub fn validate(&self) -> Result<(), String> {
+        for reason in [
+            UnavailableReason::ConcurrencyLimit,
+            UnavailableReason::ResourceExhausted,
+            UnavailableReason::OriginUnavailable,
+            UnavailableReason::BulkheadFull,
+        ] {
+            let response = self.get(reason);
+            let field = format!("server.unavailable_responses.{}", reason.as_str());
+            if let Some(content_type) = &response.content_type {
+                if content_type.trim().is_empty() || content_type.contains(['\r', '\n']) {
+                    return Err(format!(
+                        "{}.content_type must be a non-empty single line",
+                        field
+                    ));
+                }
+            }
+            if response.retry_after == RetryAfterStrategy::BreakerCooldown
+                && reason != UnavailableReason::OriginUnavailable
+            {
+                return Err(format!(
+                    "{}.retry_after: breaker_cooldown is only supported for origin_unavailable",
+                    field
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Render the response for a reason
+    ///
+    /// `cooldown` is the time left until the bucket's breaker allows a test
+    /// request, when known.
+    pub fn render(
+        &self,
+        reason: UnavailableReason,
+        bucket: Option<&str>,
+        cooldown: Option<Duration>,
+    ) -> UnavailableResponse {
+        let response = self.get(reason);
+        let fixed_secs = response
+            .retry_after_secs
+            .unwrap_or_else(|| reason.default_retry_after_secs());
+        let retry_after_secs = match (response.retry_after, cooldown) {
+            // Round up so clients never retry before the breaker is due
+            (RetryAfterStrategy::BreakerCooldown, Some(cooldown)) => {
+                cooldown.as_millis().div_ceil(1000).max(1) as u64
+            }
+            _ => fixed_secs,
+        };
+
+        let substitute = |text: &str| {
+            text.replace("{{retry_after}}", &retry_after_secs.to_string())
+                .replace("{{bucket}}", bucket.unwrap_or(""))
+                .replace("{{reason}}", reason.as_str())
+        };
+        let message = substitute(
+            response
+                .message
+                .as_deref()
+                .unwrap_or_else(|| reason.default_message()),
+        );
+
+        match &response.template {
+            Some(template) => UnavailableResponse {
+                content_type: response
+                    .content_type
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_UNAVAILABLE_CONTENT_TYPE.to_string()),
+                body: substitute(&template.replace("{{message}}", &message)),
+                retry_after_secs,
+            },
+            None => {
+                let mut body = serde_json::json!({
+                    "error": "Service Temporarily Unavailable",
+                    "code": reason.code().as_str(),
+                    "message": message,
+                    "status": 503
+                });
+                if let Some(bucket) = bucket {
+                    body["bucket"] = bucket.into();
+                }
+                UnavailableResponse {
+                    content_type: "application/json".to_string(),
+                    body: body.to_string(),
+                    retry_after_secs,
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_responses_match_builtin_bodies() {
+        let config = UnavailableResponsesConfig::default();
+        assert!(config.validate().is_ok());
+
+        let response = config.render(UnavailableReason::ConcurrencyLimit, None, None);
+        assert_eq!(response.content_type, "application/json");
+        assert_eq!(response.retry_after_secs, 5);
+        let body: serde_json::Value = serde_json::from_str(&response.body).unwrap();
+        assert_eq!(
+            body["message"],
+            "Server has reached maximum concurrent request limit. Please retry after 5 seconds."
+        );
+        assert!(body.get("bucket").is_none());
+        assert_eq!(body["code"], "YAT-AVAIL-001");
+
+        let response = config.render(
+            UnavailableReason::OriginUnavailable,
+            Some("media"),
+            Some(Duration::from_secs(12)),
+        );
+        assert_eq!(response.retry_after_secs, 60); // Fixed by default
+        let body: serde_json::Value = serde_json::from_str(&response.body).unwrap();
+        assert_eq!(body["bucket"], "media");
+        assert_eq!(body["status"], 503);
+    }
+
+    #[test]
+    fn test_template_and_breaker_cooldown() {
+        let yaml = r#"
+origin_unavailable:
+  retry_after: breaker_cooldown
+  retry_after_secs: 30
+  template: "<h1>{{bucket}} is down</h1><p>{{message}}</p>"
+  message: "Back in {{retry_after}}s"
+"#;
+        let config: UnavailableResponsesConfig = serde_yaml::from_str(yaml).unwrap();
+        assert!(config.validate().is_ok());
+
+        let response = config.render(
+            UnavailableReason::OriginUnavailable,
+            Some("media"),
+            Some(Duration::from_millis(4200)),
+        );
+        assert_eq!(response.retry_after_secs, 5);
+        assert_eq!(response.content_type, DEFAULT_UNAVAILABLE_CONTENT_TYPE);
+        assert_eq!(response.body, "<h1>media is down</h1><p>Back in 5s</p>");
+
+        // No open breaker to measure: fall back to the fixed value
+        let response = config.render(UnavailableReason::OriginUnavailable, Some("media"), None);
+        assert_eq!(response.retry_after_secs, 30);
+
+        let invalid: UnavailableResponsesConfig =
+            serde_yaml::from_str("bulkhead_full:\n  retry_after: breaker_cooldown").unwrap();
+        assert!(invalid.validate().is_err());
+    }
+}
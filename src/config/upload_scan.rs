@@ -0,0 +1,94 @@
+//! Upload virus scanning configuration.
+//!
+//! Buckets that accept multipart uploads can scan each upload body with
+//! ClamAV (`clamd`) before it reaches S3. The body is buffered, streamed to
+//! clamd with `INSTREAM`, and only forwarded when the scan comes back clean.
+//!
+//! Uses constants from `crate::constants`:
+//! - `DEFAULT_UPLOAD_SCAN_TIMEOUT_MS`
+//! - `DEFAULT_UPLOAD_SCAN_MAX_SIZE_MB`
+
+use serde::{Deserialize, Serialize};
+
+use crate::constants::{DEFAULT_UPLOAD_SCAN_MAX_SIZE_MB, DEFAULT_UPLOAD_SCAN_TIMEOUT_MS};
+
+fn default_timeout_ms() -> u64 {
+    DEFAULT_UPLOAD_SCAN_TIMEOUT_MS
+}
+
+fn default_max_size_mb() -> u64 {
+    DEFAULT_UPLOAD_SCAN_MAX_SIZE_MB
+}
+
+/// Per-bucket upload scanning (YAML format)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadScanConfig {
+    /// clamd TCP address, e.g. `127.0.0.1:3310`
+    pub clamd: String,
+    /// Forward uploads that could not be scanned (scanner down, timeout,
+    /// body over `max_size_mb`) instead of rejecting them (default: false)
+    #[serde(default)]
+    pub fail_open: bool,
+    /// Connect and scan timeout in milliseconds (default: 10000)
+    #[serde(default = "default_timeout_ms")]
+    pub timeout_ms: u64,
+    /// Largest body buffered for scanning in MiB (default: 100)
+    #[serde(default = "default_max_size_mb")]
+    pub max_size_mb: u64,
+}
+
+impl UploadScanConfig {
+    /// Largest body buffered for scanning in bytes
+    pub fn max_size_bytes(&self) -> u64 {
+        self.max_size_mb * 1024 * 1024
+    }
+
+    /// Validate the scanner address and limits
+    pub fn validate(&self, bucket_name: &str) -> Result<(), String> {
+        let address_ok = self
+            .clamd
+            .rsplit_once(':')
+            .is_some_and(|(host, port)| !host.is_empty() && port.parse::<u16>().is_ok());
+        if !address_ok {
+            return Err(format!(
+                "Bucket '{}': upload_scan.clamd '{}' must be host:port",
+                bucket_name, self.clamd
+            ));
+        }
+        if self.timeout_ms == 0 {
+            return Err(format!(
+                "Bucket '{}': upload_scan.timeout_ms must be greater than 0",
+                bucket_name
+            ));
+        }
+        if self.max_size_mb == 0 {
+            return Err(format!(
+                "Bucket '{}': upload_scan.max_size_mb must be greater than 0",
+                bucket_name
+            ));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upload_scan_config() {
+        let config: UploadScanConfig = serde_yaml::from_str("clamd: clamav:3310").unwrap();
+        assert!(!config.fail_open);
+        assert_eq!(config.timeout_ms, DEFAULT_UPLOAD_SCAN_TIMEOUT_MS);
+        assert_eq!(config.max_size_bytes(), 100 * 1024 * 1024);
+        assert!(config.validate("uploads").is_ok());
+
+        for clamd in ["clamav", ":3310", "clamav:port"] {
+            let config = UploadScanConfig {
+                clamd: clamd.to_string(),
+                ..config.clone()
+            };
+            assert!(config.validate("uploads").is_err(), "{}", clamd);
+        }
+    }
+}
@@ -0,0 +1,425 @@
+//! Secrets resolved from HashiCorp Vault while the config is loaded.
+//!
+//! Any string value of the form `vault:<path>#<field>` (e.g. an S3
+//! `secret_key` or a JWT `secret`) is replaced with the field read from Vault's
+//! HTTP API, so the secret is neither in the YAML file nor in the environment.
+//! References are resolved at startup and again on every reload, which also
+//! picks up rotated secrets.
+//!
+//! The top-level `vault` block says where Vault is and how to log in (a token
+//! or AppRole). KV v2 (`secret/data/...`) and KV v1 paths are both supported.
+//!
+//! Default values are sourced from `crate::constants`.
+
+use serde::{Deserialize, Serialize};
+use serde_yaml::Value;
+use std::collections::{BTreeSet, HashMap};
+use std::time::Duration;
+
+use crate::constants::{
+    DEFAULT_VAULT_APPROLE_MOUNT, DEFAULT_VAULT_TIMEOUT_SECS, VAULT_REFERENCE_PREFIX,
+};
+
+fn default_timeout_secs() -> u64 {
+    DEFAULT_VAULT_TIMEOUT_SECS
+}
+
+fn default_approle_mount() -> String {
+    DEFAULT_VAULT_APPROLE_MOUNT.to_string()
+}
+
+/// How the proxy logs in to Vault
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "method", rename_all = "snake_case")]
+pub enum VaultAuthConfig {
+    /// A Vault token (default: `$VAULT_TOKEN`)
+    Token {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        token: Option<String>,
+    },
+    /// AppRole login with a role ID and secret ID
+    #[serde(rename = "approle")]
+    AppRole {
+        role_id: String,
+        secret_id: String,
+        /// Auth method mount path (default: "approle")
+        #[serde(default = "default_approle_mount")]
+        mount: String,
+    },
+}
+
+impl Default for VaultAuthConfig {
+    fn default() -> Self {
+        VaultAuthConfig::Token { token: None }
+    }
+}
+
+/// Vault connection settings (YAML format)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VaultConfig {
+    /// Vault server URL (default: `$VAULT_ADDR`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    /// Enterprise namespace (default: `$VAULT_NAMESPACE`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    /// Login method (default: token)
+    #[serde(default)]
+    pub auth: VaultAuthConfig,
+    /// Timeout for one Vault API call (default: 10)
+    #[serde(default = "default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl VaultConfig {
+    /// Vault server URL from the config or `$VAULT_ADDR`
+    pub fn address(&self) -> Option<String> {
+        self.address
+            .clone()
+            .or_else(|| std::env::var("VAULT_ADDR").ok())
+            .map(|a| a.trim_end_matches('/').to_string())
+    }
+
+    /// Validate the connection settings
+    pub fn validate(&self) -> Result<(), String> {
+        let address = self
+            .address()
+            .ok_or("vault.address (or VAULT_ADDR) is required to resolve vault: references")?;
+        if !address.starts_with("http://") && !address.starts_with("https://") {
+            return Err(format!(
+                "vault.address '{}' must start with http:// or https://",
+                address
+            ));
+        }
+        if self.timeout_secs == 0 {
+            return Err("vault.timeout_secs must be greater than 0".to_string());
+        }
+        if let VaultAuthConfig::AppRole {
+            role_id,
+            secret_id,
+            mount,
+        } = &self.auth
+        {
+            if role_id.is_empty() || secret_id.is_empty() || mount.is_empty() {
+                return Err(
+                    "vault.auth role_id, secret_id and mount cannot be empty for approle"
+                        .to_string(),
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A `vault:<path>#<field>` reference
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct VaultReference {
+    pub path: String,
+    pub field: String,
+}
+
+impl VaultReference {
+    /// Parse a config value; `None` if it is not a Vault reference
+    pub fn parse(value: &str) -> Option<Result<Self, String>> {
+        let reference = value.strip_prefix(VAULT_REFERENCE_PREFIX)?;
+        Some(match reference.split_once('#') {
+            Some((path, field)) if !path.trim_matches('/').is_empty() && !field.is_empty() => {
+                Ok(Self {
+                    path: path.trim_matches('/').to_string(),
+                    field: field.to_string(),
+                })
+            }
+            _ => Err(format!(
+                "Invalid Vault reference '{}': expected vault:<path>#<field>",
+                value
+            )),
+        })
+    }
+}
+
+/// Replace every `vault:` reference in a parsed config with its secret
+///
+/// The `vault` block itself is not scanned. Each path is read once.
+pub fn resolve_references(root: &mut Value) -> Result<(), String> {
+    let mut references = BTreeSet::new();
+    for (key, value) in mapping_entries(root) {
+        if key != "vault" {
+            collect_references(value, &mut references)?;
+        }
+    }
+    if references.is_empty() {
+        return Ok(());
+    }
+
+    let config: VaultConfig = match root.get("vault") {
+        Some(vault) => serde_yaml::from_value(vault.clone())
+            .map_err(|e| format!("Invalid vault config: {}", e))?,
+        None => VaultConfig::default(),
+    };
+    config.validate()?;
+
+    // Blocking client on its own thread: reloads run inside the async runtime
+    let secrets = std::thread::scope(|scope| {
+        scope
+            .spawn(|| VaultClient::login(&config)?.read_all(&references))
+            .join()
+            .unwrap_or_else(|_| Err("Vault client thread panicked".to_string()))
+    })?;
+
+    for (key, value) in mapping_entries_mut(root) {
+        if key != "vault" {
+            replace_references(value, &secrets);
+        }
+    }
+    tracing::info!(
+        secrets = secrets.len(),
+        "Resolved vault: references in config"
+    );
+    Ok(())
+}
+
+fn mapping_entries(root: &Value) -> Vec<(String, &Value)> {
+    root.as_mapping()
+        .map(|m| {
+            m.iter()
+                .map(|(k, v)| (k.as_str().unwrap_or_default().to_string(), v))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn mapping_entries_mut(root: &mut Value) -> Vec<(String, &mut Value)> {
+    root.as_mapping_mut()
+        .map(|m| {
+            m.iter_mut()
+                .map(|(k, v)| (k.as_str().unwrap_or_default().to_string(), v))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn collect_references(
+    value: &Value,
+    references: &mut BTreeSet<VaultReference>,
+) -> Result<(), String> {
+    match value {
+        Value::String(s) => {
+            if let Some(reference) = VaultReference::parse(s) {
+                references.insert(reference?);
+            }
+        }
+        Value::Sequence(items) => {
+            for item in items {
+                collect_references(item, references)?;
+            }
+        }
+        Value::Mapping(map) => {
+            for item in map.values() {
+                collect_references(item, references)?;
+            }
+        }
+        Value::Tagged(tagged) => collect_references(&tagged.value, references)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+fn replace_references(value: &mut Value, secrets: &HashMap<VaultReference, String>) {
+    match value {
+        Value::String(s) => {
+            if let Some(Ok(reference)) = VaultReference::parse(s) {
+                if let Some(secret) = secrets.get(&reference) {
+                    *s = secret.clone();
+                }
+            }
+        }
+        Value::Sequence(items) => items
+            .iter_mut()
+            .for_each(|item| replace_references(item, secrets)),
+        Value::Mapping(map) => map
+            .values_mut()
+            .for_each(|item| replace_references(item, secrets)),
+        Value::Tagged(tagged) => replace_references(&mut tagged.value, secrets),
+        _ => {}
+    }
+}
+
+/// Field of a secret read response: KV v2 (`data.data`), else KV v1 (`data`)
+fn secret_field(response: &serde_json::Value, field: &str) -> Option<String> {
+    let data = response.get("data")?;
+    let value = data
+        .get("data")
+        .and_then(|inner| inner.get(field))
+        .or_else(|| data.get(field))?;
+    Some(match value {
+        serde_json::Value::String(s) => s.clone(),
+        other => other.to_string(),
+    })
+}
+
+/// Logged-in Vault HTTP API client
+struct VaultClient {
+    http: reqwest::blocking::Client,
+    address: String,
+    namespace: Option<String>,
+    token: String,
+}
+
+impl VaultClient {
+    fn login(config: &VaultConfig) -> Result<Self, String> {
+        let http = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(config.timeout_secs))
+            .build()
+            .map_err(|e| format!("Failed to create Vault client: {}", e))?;
+        let mut client = Self {
+            http,
+            address: config.address().unwrap_or_default(),
+            namespace: config
+                .namespace
+                .clone()
+                .or_else(|| std::env::var("VAULT_NAMESPACE").ok()),
+            token: String::new(),
+        };
+        client.token = match &config.auth {
+            VaultAuthConfig::Token { token } => token
+                .clone()
+                .or_else(|| std::env::var("VAULT_TOKEN").ok())
+                .ok_or("vault.auth.token (or VAULT_TOKEN) is required for token auth")?,
+            VaultAuthConfig::AppRole {
+                role_id,
+                secret_id,
+                mount,
+            } => client.approle_login(role_id, secret_id, mount)?,
+        };
+        Ok(client)
+    }
+
+    fn request(&self, method: reqwest::Method, path: &str) -> reqwest::blocking::RequestBuilder {
+        let mut request = self
+            .http
+            .request(method, format!("{}/v1/{}", self.address, path));
+        if let Some(namespace) = &self.namespace {
+            request = request.header("X-Vault-Namespace", namespace);
+        }
+        request
+    }
+
+    fn approle_login(&self, role_id: &str, secret_id: &str, mount: &str) -> Result<String, String> {
+        let path = format!("auth/{}/login", mount.trim_matches('/'));
+        let response = self
+            .request(reqwest::Method::POST, &path)
+            .json(&serde_json::json!({ "role_id": role_id, "secret_id": secret_id }))
+            .send()
+            .map_err(|e| format!("Vault AppRole login failed: {}", e))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!("Vault AppRole login failed: HTTP {}", status));
+        }
+        let body: serde_json::Value = response
+            .json()
+            .map_err(|e| format!("Vault AppRole login failed: {}", e))?;
+        body.pointer("/auth/client_token")
+            .and_then(|t| t.as_str())
+            .map(str::to_string)
+            .ok_or_else(|| "Vault AppRole login response has no client_token".to_string())
+    }
+
+    fn read(&self, path: &str) -> Result<serde_json::Value, String> {
+        let response = self
+            .request(reqwest::Method::GET, path)
+            .header("X-Vault-Token", &self.token)
+            .send()
+            .map_err(|e| format!("Failed to read Vault secret '{}': {}", path, e))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(format!(
+                "Failed to read Vault secret '{}': HTTP {}",
+                path, status
+            ));
+        }
+        response
+            .json()
+            .map_err(|e| format!("Failed to read Vault secret '{}': {}", path, e))
+    }
+
+    fn read_all(
+        &self,
+        references: &BTreeSet<VaultReference>,
+    ) -> Result<HashMap<VaultReference, String>, String> {
+        let mut responses: HashMap<&str, serde_json::Value> = HashMap::new();
+        let mut secrets = HashMap::new();
+        for reference in references {
+            if !responses.contains_key(reference.path.as_str()) {
+                responses.insert(reference.path.as_str(), self.read(&reference.path)?);
+            }
+            let secret = secret_field(&responses[reference.path.as_str()], &reference.field)
+                .ok_or_else(|| {
+                    format!(
+                        "Vault secret '{}' has no field '{}'",
+                        reference.path, reference.field
+                    )
+                })?;
+            secrets.insert(reference.clone(), secret);
+        }
+        Ok(secrets)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vault_reference_parse() {
+        let reference = VaultReference::parse("vault:secret/data/yatagarasu/s3#secret_key")
+            .unwrap()
+            .unwrap();
+        assert_eq!(reference.path, "secret/data/yatagarasu/s3");
+        assert_eq!(reference.field, "secret_key");
+
+        assert!(VaultReference::parse("plain-secret").is_none());
+        assert!(VaultReference::parse("vault:secret/data/s3")
+            .unwrap()
+            .is_err());
+        assert!(VaultReference::parse("vault:#field").unwrap().is_err());
+    }
+
+    #[test]
+    fn test_vault_config() {
+        let yaml = "address: https://vault.internal:8200\n\
+                    auth:\n  method: approle\n  role_id: r\n  secret_id: s";
+        let config: VaultConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.timeout_secs, 10);
+        assert!(
+            matches!(&config.auth, VaultAuthConfig::AppRole { mount, .. } if mount == "approle")
+        );
+        assert!(config.validate().is_ok());
+
+        let config: VaultConfig = serde_yaml::from_str("address: vault.internal").unwrap();
+        assert_eq!(config.auth, VaultAuthConfig::Token { token: None });
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn test_secret_field_kv_v1_and_v2() {
+        let v2 = serde_json::json!({"data": {"data": {"secret_key": "abc"}, "metadata": {}}});
+        assert_eq!(secret_field(&v2, "secret_key").as_deref(), Some("abc"));
+        let v1 = serde_json::json!({"data": {"secret_key": "abc", "port": 8200}});
+        assert_eq!(secret_field(&v1, "secret_key").as_deref(), Some("abc"));
+        assert_eq!(secret_field(&v1, "port").as_deref(), Some("8200"));
+        assert_eq!(secret_field(&v1, "missing"), None);
+    }
+
+    #[test]
+    fn test_resolve_references_without_references_is_noop() {
+        let yaml = "buckets:\n  - name: media\n    s3:\n      secret_key: plain\n";
+        let mut root: Value = serde_yaml::from_str(yaml).unwrap();
+        let before = root.clone();
+        assert!(resolve_references(&mut root).is_ok());
+        assert_eq!(root, before);
+
+        // Malformed references fail before Vault is contacted
+        let mut root: Value = serde_yaml::from_str("jwt:\n  secret: vault:secret/jwt").unwrap();
+        assert!(resolve_references(&mut root).is_err());
+    }
+}
@@ -17,6 +17,19 @@ pub const DEFAULT_MAX_CONCURRENT_REQUESTS: usize = 1000;
 /// Default number of worker threads
 pub const DEFAULT_THREADS: usize = 4;
 
+/// Default name for worker threads
+pub const DEFAULT_WORKER_THREAD_NAME: &str = "yatagarasu";
+
+/// Default minimum completion (percent of Content-Length) for finishing a cache fill
+/// after the client disconnects
+pub const DEFAULT_DISCONNECT_MIN_COMPLETION_PERCENT: u8 = 90;
+
+/// Default maximum remaining bytes fetched to finish a cache fill after a client disconnect (1 MB)
+pub const DEFAULT_DISCONNECT_MAX_REMAINING_BYTES: u64 = 1024 * 1024;
+
+/// Upper bound (exclusive) for CPU ids in `server.workers.cpu_affinity` (glibc CPU_SETSIZE)
+pub const MAX_CPU_AFFINITY_ID: usize = 1024;
+
 // =============================================================================
 // S3 defaults
 // =============================================================================
@@ -27,6 +40,31 @@ pub const DEFAULT_S3_TIMEOUT_SECS: u64 = 20;
 /// Default connection pool size per S3 bucket
 pub const DEFAULT_CONNECTION_POOL_SIZE: usize = 50;
 
+/// Default HTTP methods accepted for a bucket
+pub const DEFAULT_BUCKET_ALLOWED_METHODS: &[&str] = &["GET", "HEAD", "OPTIONS"];
+
+/// HTTP methods the proxy can serve for bucket paths; write methods are added
+/// here as upstream support for them lands
+pub const SUPPORTED_BUCKET_METHODS: &[&str] = &["GET", "HEAD", "OPTIONS", "PUT", "POST", "DELETE"];
+
+/// Write methods, accepted only for multipart upload requests
+pub const BUCKET_WRITE_METHODS: &[&str] = &["PUT", "POST", "DELETE"];
+
+/// Default request headers allowed in cross-origin requests
+pub const DEFAULT_CORS_ALLOWED_HEADERS: &[&str] = &["Authorization", "Content-Type", "Range"];
+
+/// Default lifetime of a cached CORS preflight response (24 hours)
+pub const DEFAULT_CORS_MAX_AGE_SECS: u64 = 86400;
+
+/// Default limit on how long past expiry a cached object may be served in degraded mode (24 hours)
+pub const DEFAULT_DEGRADED_MAX_STALE_SECS: u64 = 86400;
+
+/// Consecutive connect failures before a static replica endpoint is skipped
+pub const STATIC_ENDPOINT_FAILURE_THRESHOLD: u32 = 3;
+
+/// Seconds a failing static replica endpoint is skipped before it is retried
+pub const STATIC_ENDPOINT_COOLDOWN_SECS: u64 = 10;
+
 // =============================================================================
 // Security defaults
 // =============================================================================
@@ -37,6 +75,12 @@ pub const DEFAULT_MAX_BODY_SIZE: usize = 10 * 1024 * 1024;
 /// Default maximum header size (64 KB)
 pub const DEFAULT_MAX_HEADER_SIZE: usize = 64 * 1024;
 
+/// Default maximum length of a single header value (8 KB)
+pub const DEFAULT_MAX_HEADER_VALUE_LENGTH: usize = 8 * 1024;
+
+/// Distinct header names labeled in the oversized header metric
+pub const MAX_OVERSIZED_HEADER_LABELS: usize = 32;
+
 /// Default maximum URI length (8 KB)
 pub const DEFAULT_MAX_URI_LENGTH: usize = 8192;
 
@@ -53,6 +97,260 @@ pub const DEFAULT_MAX_CACHE_SIZE_MB: u64 = 1024;
 /// Default TTL in seconds
 pub const DEFAULT_TTL_SECONDS: u64 = 3600;
 
+/// Default errors within the quarantine window that take a disk/redis layer offline
+pub const DEFAULT_CACHE_QUARANTINE_ERROR_THRESHOLD: u32 = 5;
+
+/// Default window for counting cache layer errors
+pub const DEFAULT_CACHE_QUARANTINE_WINDOW_SECS: u64 = 10;
+
+/// Default time a quarantined cache layer is skipped
+pub const DEFAULT_CACHE_QUARANTINE_COOLDOWN_SECS: u64 = 30;
+
+/// Default number of hot keys kept in the startup warming journal
+pub const DEFAULT_HOT_KEY_JOURNAL_TOP_K: usize = 1000;
+
+/// Default interval between hot key journal writes
+pub const DEFAULT_HOT_KEY_JOURNAL_FLUSH_INTERVAL_SECS: u64 = 60;
+
+/// Default concurrent S3 fetches when warming from the hot key journal
+pub const DEFAULT_HOT_KEY_JOURNAL_WARM_CONCURRENCY: usize = 8;
+
+/// Default minimum entry size compressed before caching (4 KB)
+pub const DEFAULT_CACHE_COMPRESSION_MIN_SIZE_BYTES: usize = 4 * 1024;
+
+/// Default memory shared by response bodies buffered for cache population (256 MB)
+pub const DEFAULT_CACHE_MAX_BUFFER_MEMORY_MB: u64 = 256;
+
+/// Default zstd level for cache entry compression
+pub const DEFAULT_CACHE_COMPRESSION_LEVEL: i32 = 3;
+
+/// Default size of the fixed chunks Range responses are cached in (1 MB)
+pub const DEFAULT_CACHE_RANGE_CHUNK_SIZE_BYTES: u64 = 1024 * 1024;
+
+/// Default most chunks assembled into one cached Range response
+pub const DEFAULT_CACHE_RANGE_MAX_CHUNKS: u64 = 16;
+
+/// Default share of requests (percent) assigned to the second cache experiment variant
+pub const DEFAULT_CACHE_EXPERIMENT_SPLIT_PERCENT: u8 = 50;
+
+/// Default upstream response headers stored with cache entries and replayed on hits
+pub const DEFAULT_CACHE_PRESERVE_HEADERS: &[&str] = &[
+    "cache-control",
+    "content-disposition",
+    "content-language",
+    "expires",
+    "x-amz-meta-*",
+];
+
+/// Default minimum object size for issuing download resume tokens (100 MB)
+pub const DEFAULT_RESUME_MIN_SIZE_BYTES: u64 = 100 * 1024 * 1024;
+
+/// Upstream Content-Types treated as "unknown" by per-bucket Content-Type overrides
+pub const DEFAULT_GENERIC_CONTENT_TYPES: &[&str] =
+    &["binary/octet-stream", "application/octet-stream"];
+
+// =============================================================================
+// Batch GET defaults
+// =============================================================================
+
+/// Default path of the batch GET endpoint
+pub const DEFAULT_BATCH_GET_PATH: &str = "/batch/get";
+
+/// Default maximum number of objects per batch request
+pub const DEFAULT_BATCH_MAX_KEYS: usize = 100;
+
+/// Default number of objects fetched concurrently per batch request
+pub const DEFAULT_BATCH_MAX_CONCURRENCY: usize = 16;
+
+/// Default maximum object size returned inline in a batch response (1 MB)
+pub const DEFAULT_BATCH_MAX_INLINE_BYTES: u64 = 1024 * 1024;
+
+// =============================================================================
+// IP ban defaults
+// =============================================================================
+
+/// Default window for counting per-IP error responses (1 minute)
+pub const DEFAULT_IP_BAN_WINDOW_SECS: u64 = 60;
+
+/// Default client errors (4xx other than 429) per window before an IP is banned
+pub const DEFAULT_IP_BAN_MAX_CLIENT_ERRORS: u32 = 100;
+
+/// Default rate-limited responses (429) per window before an IP is banned
+pub const DEFAULT_IP_BAN_MAX_RATE_LIMITED: u32 = 50;
+
+/// Default ban duration (10 minutes)
+pub const DEFAULT_IP_BAN_DURATION_SECS: u64 = 600;
+
+/// Default maximum number of IPs tracked for error counting
+pub const DEFAULT_IP_BAN_MAX_TRACKED_IPS: usize = 100_000;
+
+/// Default Redis key holding shared bans
+pub const DEFAULT_IP_BAN_REDIS_KEY: &str = "yatagarasu:ip_bans";
+
+/// Default interval for syncing shared bans from Redis
+pub const DEFAULT_IP_BAN_SYNC_INTERVAL_SECS: u64 = 5;
+
+// =============================================================================
+// Range abuse defaults
+// =============================================================================
+
+/// Default window for counting a client's small ranges on one object (1 minute)
+pub const DEFAULT_RANGE_ABUSE_WINDOW_SECS: u64 = 60;
+
+/// Default largest range that counts as small (64 KiB)
+pub const DEFAULT_RANGE_ABUSE_SMALL_RANGE_BYTES: u64 = 64 * 1024;
+
+/// Default small ranges per client and object per window before throttling
+pub const DEFAULT_RANGE_ABUSE_MAX_SMALL_RANGES: u32 = 1000;
+
+/// Default time a flagged client stays throttled (5 minutes)
+pub const DEFAULT_RANGE_ABUSE_THROTTLE_SECS: u64 = 300;
+
+/// Default Range requests per second allowed to a throttled client
+pub const DEFAULT_RANGE_ABUSE_THROTTLED_RPS: u32 = 5;
+
+/// Default maximum number of client/object pairs tracked
+pub const DEFAULT_RANGE_ABUSE_MAX_TRACKED: usize = 100_000;
+
+// =============================================================================
+// Access grant defaults
+// =============================================================================
+
+/// Default lifetime of a temporary access grant (1 hour)
+pub const DEFAULT_ACCESS_GRANT_TTL_SECS: u64 = 3600;
+
+/// Default longest lifetime an admin may give a grant (24 hours)
+pub const DEFAULT_ACCESS_GRANT_MAX_TTL_SECS: u64 = 24 * 3600;
+
+/// Default maximum number of active grants
+pub const DEFAULT_ACCESS_GRANT_MAX_GRANTS: usize = 1000;
+
+/// Default Redis hash holding shared grants
+pub const DEFAULT_ACCESS_GRANT_REDIS_KEY: &str = "yatagarasu:access_grants";
+
+/// Default interval for pulling shared grants from Redis
+pub const DEFAULT_ACCESS_GRANT_SYNC_INTERVAL_SECS: u64 = 5;
+
+// =============================================================================
+// Size class defaults
+// =============================================================================
+
+/// Maximum number of object sizes remembered for size-class policies
+pub const MAX_TRACKED_OBJECT_SIZES: usize = 100_000;
+
+// =============================================================================
+// Request priority defaults
+// =============================================================================
+
+/// Default header carrying the request priority
+pub const DEFAULT_PRIORITY_HEADER: &str = "X-Priority";
+
+/// Default concurrency permits reserved for high-priority requests
+pub const DEFAULT_PRIORITY_RESERVED_PERMITS: usize = 50;
+
+// =============================================================================
+// Cache override header
+// =============================================================================
+
+/// Header selecting a per-request cache override
+pub const CACHE_OVERRIDE_HEADER: &str = "x-yat-cache";
+
+/// Header carrying the cache override token
+pub const CACHE_OVERRIDE_TOKEN_HEADER: &str = "x-yat-cache-token";
+
+// =============================================================================
+// Request queue defaults
+// =============================================================================
+
+/// Default longest time a request waits for a concurrency permit
+pub const DEFAULT_REQUEST_QUEUE_MAX_WAIT_MS: u64 = 500;
+
+/// Default maximum number of requests waiting for a permit
+pub const DEFAULT_REQUEST_QUEUE_MAX_LENGTH: usize = 1000;
+
+// =============================================================================
+// Maintenance mode defaults
+// =============================================================================
+
+/// Default message returned while in maintenance mode
+pub const DEFAULT_MAINTENANCE_MESSAGE: &str = "Service is undergoing maintenance";
+
+/// Default Retry-After for maintenance responses (5 minutes)
+pub const DEFAULT_MAINTENANCE_RETRY_AFTER_SECS: u64 = 300;
+
+/// Default Content-Type for custom maintenance templates
+pub const DEFAULT_MAINTENANCE_CONTENT_TYPE: &str = "text/html; charset=utf-8";
+
+// =============================================================================
+// 503 (service unavailable) response defaults
+// =============================================================================
+
+/// Default Retry-After when the concurrency limit is reached
+pub const DEFAULT_CONCURRENCY_LIMIT_RETRY_AFTER_SECS: u64 = 5;
+
+/// Default Retry-After when resources are exhausted (load shedding)
+pub const DEFAULT_RESOURCE_EXHAUSTED_RETRY_AFTER_SECS: u64 = 10;
+
+/// Default Retry-After when the bucket's circuit breaker is open
+pub const DEFAULT_ORIGIN_UNAVAILABLE_RETRY_AFTER_SECS: u64 = 60;
+
+/// Default Retry-After when the bucket's bulkhead is full
+pub const DEFAULT_BULKHEAD_FULL_RETRY_AFTER_SECS: u64 = 1;
+
+/// Default Content-Type for custom 503 templates
+pub const DEFAULT_UNAVAILABLE_CONTENT_TYPE: &str = "text/html; charset=utf-8";
+
+// =============================================================================
+// Metrics cardinality defaults
+// =============================================================================
+
+/// Default distinct `bucket` label values before folding into `other`
+pub const DEFAULT_METRICS_MAX_BUCKET_LABELS: usize = 1000;
+
+/// Default distinct bucket/replica label pairs before folding into `other`
+pub const DEFAULT_METRICS_MAX_REPLICA_LABELS: usize = 2000;
+
+// =============================================================================
+// Auth failure tracking
+// =============================================================================
+
+/// Time slot for per-client auth failure counts (1 minute)
+pub const AUTH_FAILURE_SLOT_SECS: u64 = 60;
+
+/// Rolling window kept for the top-offenders view (15 minutes)
+pub const AUTH_FAILURE_WINDOW_SECS: u64 = 900;
+
+/// Distinct clients tracked per slot (later clients in a full slot are not listed)
+pub const AUTH_FAILURE_MAX_CLIENTS_PER_SLOT: usize = 10_000;
+
+// =============================================================================
+// Key popularity analytics defaults
+// =============================================================================
+
+/// Default time slot for key popularity counting (5 minutes)
+pub const DEFAULT_KEY_ANALYTICS_SLOT_SECS: u64 = 300;
+
+/// Default longest queryable key popularity window (24 hours)
+pub const DEFAULT_KEY_ANALYTICS_MAX_WINDOW_SECS: u64 = 86400;
+
+/// Default count-min sketch width (counters per row)
+pub const DEFAULT_KEY_ANALYTICS_SKETCH_WIDTH: usize = 1024;
+
+/// Default count-min sketch depth (hash rows)
+pub const DEFAULT_KEY_ANALYTICS_SKETCH_DEPTH: usize = 4;
+
+/// Default top-key candidates tracked per bucket and slot
+pub const DEFAULT_KEY_ANALYTICS_CANDIDATES: usize = 100;
+
+/// Default interval between access statistics exports (1 hour)
+pub const DEFAULT_ACCESS_EXPORT_INTERVAL_SECS: u64 = 3600;
+
+/// Default distinct keys tracked per bucket between exports
+pub const DEFAULT_ACCESS_EXPORT_MAX_KEYS: usize = 10_000;
+
+/// Default object key prefix for access statistics exported to S3
+pub const DEFAULT_ACCESS_EXPORT_S3_PREFIX: &str = "access-stats/";
+
 // =============================================================================
 // Circuit breaker defaults
 // =============================================================================
@@ -69,6 +367,21 @@ pub const DEFAULT_CB_TIMEOUT_SECS: u64 = 60;
 /// Default maximum requests allowed in half-open state
 pub const DEFAULT_HALF_OPEN_MAX_REQUESTS: u32 = 3;
 
+/// Default sliding window size for the error-rate breaker policy
+pub const DEFAULT_CB_WINDOW_SIZE: u32 = 100;
+
+/// Default failure ratio that opens an error-rate breaker
+pub const DEFAULT_CB_ERROR_RATE_THRESHOLD: f64 = 0.5;
+
+/// Default requests needed in the window before the error rate is evaluated
+pub const DEFAULT_CB_MINIMUM_REQUESTS: u32 = 20;
+
+/// Default interval between background origin probe rounds in seconds
+pub const DEFAULT_ORIGIN_PROBE_INTERVAL_SECS: u64 = 5;
+
+/// Default timeout for a single background origin probe in seconds
+pub const DEFAULT_ORIGIN_PROBE_TIMEOUT_SECS: u64 = 2;
+
 // =============================================================================
 // Retry defaults
 // =============================================================================
@@ -117,3 +430,210 @@ pub const DEFAULT_OPENFGA_TIMEOUT_MS: u64 = 100;
 
 /// Default OpenFGA cache TTL in seconds
 pub const DEFAULT_OPENFGA_CACHE_TTL_SECS: u64 = 60;
+
+// =============================================================================
+// JWT key material defaults
+// =============================================================================
+
+/// Default interval for re-reading JWT secret and PEM key files
+pub const DEFAULT_JWT_KEY_RELOAD_INTERVAL_SECS: u64 = 60;
+
+/// Default JWKS refresh interval (1 hour)
+pub const DEFAULT_JWKS_REFRESH_INTERVAL_SECS: u64 = 3600;
+
+/// Minimum time between JWKS refetches triggered by an unknown `kid`
+pub const JWKS_UNKNOWN_KID_REFRESH_COOLDOWN_SECS: u64 = 30;
+
+/// Default longest time a validated token is served from the token cache
+pub const DEFAULT_JWT_TOKEN_CACHE_TTL_SECS: u64 = 60;
+
+/// Default number of validated tokens kept in the token cache
+pub const DEFAULT_JWT_TOKEN_CACHE_MAX_ENTRIES: usize = 10_000;
+
+/// Default interval for re-reading the JWT revocation list
+pub const DEFAULT_JWT_REVOCATION_RELOAD_INTERVAL_SECS: u64 = 30;
+
+/// Default Redis set holding revoked `jti`/`sub` entries
+pub const DEFAULT_JWT_REVOCATION_REDIS_KEY: &str = "yatagarasu:revoked_tokens";
+
+/// Default allowed clock skew for incoming SigV4 requests (15 minutes, as S3)
+pub const DEFAULT_SIGV4_MAX_CLOCK_SKEW_SECS: u64 = 900;
+
+// =============================================================================
+// S3 credential provider defaults
+// =============================================================================
+
+/// Default lead time for refreshing temporary S3 credentials before they expire (5 minutes)
+pub const DEFAULT_S3_CREDENTIALS_REFRESH_BEFORE_EXPIRY_SECS: u64 = 300;
+
+/// Delay before retrying a failed credential fetch
+pub const S3_CREDENTIALS_RETRY_SECS: u64 = 10;
+
+/// Timeout for one metadata service or STS call
+pub const S3_CREDENTIALS_FETCH_TIMEOUT_SECS: u64 = 5;
+
+/// Default EC2 instance metadata service endpoint
+pub const DEFAULT_IMDS_ENDPOINT: &str = "http://169.254.169.254";
+
+/// Lifetime requested for an IMDSv2 session token (6 hours)
+pub const IMDS_TOKEN_TTL_SECS: u64 = 21600;
+
+/// ECS task metadata endpoint used with `AWS_CONTAINER_CREDENTIALS_RELATIVE_URI`
+pub const ECS_CREDENTIALS_ENDPOINT: &str = "http://169.254.170.2";
+
+/// Default STS endpoint for web identity and AssumeRole
+pub const DEFAULT_STS_ENDPOINT: &str = "https://sts.amazonaws.com";
+
+/// Default lifetime requested for AssumeRole sessions (1 hour)
+pub const DEFAULT_ASSUME_ROLE_DURATION_SECS: u64 = 3600;
+
+/// Shortest AssumeRole session STS accepts (15 minutes)
+pub const MIN_ASSUME_ROLE_DURATION_SECS: u64 = 900;
+
+/// Longest AssumeRole session STS accepts (12 hours, if the role allows it)
+pub const MAX_ASSUME_ROLE_DURATION_SECS: u64 = 43200;
+
+// =============================================================================
+// ETag rewrite defaults
+// =============================================================================
+
+/// Default object metadata header holding a content checksum
+pub const DEFAULT_ETAG_CHECKSUM_HEADER: &str = "x-amz-meta-sha256";
+
+// =============================================================================
+// Synthetic endpoint defaults
+// =============================================================================
+
+/// Default Content-Type of synthetic endpoint bodies
+pub const DEFAULT_SYNTHETIC_CONTENT_TYPE: &str = "application/octet-stream";
+
+/// Largest body a synthetic endpoint may generate (1 GiB)
+pub const MAX_SYNTHETIC_ENDPOINT_SIZE_BYTES: u64 = 1024 * 1024 * 1024;
+
+/// Chunk size used to stream synthetic bodies (64 KiB)
+pub const SYNTHETIC_CHUNK_SIZE: usize = 64 * 1024;
+
+// =============================================================================
+// Filesystem origin defaults
+// =============================================================================
+
+/// Chunk size used to stream files from a filesystem origin (64 KiB)
+pub const FILESYSTEM_CHUNK_SIZE: usize = 64 * 1024;
+
+// =============================================================================
+// Upload scanning defaults
+// =============================================================================
+
+/// Default clamd connect/scan timeout in milliseconds
+pub const DEFAULT_UPLOAD_SCAN_TIMEOUT_MS: u64 = 10_000;
+
+/// Default largest upload body buffered for scanning (100 MiB)
+pub const DEFAULT_UPLOAD_SCAN_MAX_SIZE_MB: u64 = 100;
+
+// =============================================================================
+// POST policy upload defaults
+// =============================================================================
+
+/// Default largest file accepted by a browser POST upload (10 MiB)
+pub const DEFAULT_POST_POLICY_MAX_SIZE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Default longest lifetime of a POST policy (1 hour)
+pub const DEFAULT_POST_POLICY_MAX_EXPIRES_SECS: u64 = 3600;
+
+/// Form fields and multipart framing allowed on top of the file (64 KiB)
+pub const POST_POLICY_FORM_OVERHEAD_BYTES: u64 = 64 * 1024;
+
+// =============================================================================
+// Presigned redirect defaults
+// =============================================================================
+
+/// Default lifetime of the presigned URL a redirect bucket sends clients to
+pub const DEFAULT_PRESIGNED_REDIRECT_EXPIRES_SECS: u64 = 300;
+
+/// Longest presigned URL lifetime S3 accepts (7 days)
+pub const MAX_PRESIGNED_URL_EXPIRES_SECS: u64 = 7 * 24 * 60 * 60;
+
+// =============================================================================
+// Admin event stream defaults
+// =============================================================================
+
+/// Events buffered per `/admin/events` subscriber before it starts missing some
+pub const EVENT_STREAM_BUFFER_SIZE: usize = 256;
+
+/// Interval between SSE keepalive comments on an idle `/admin/events` stream
+pub const EVENT_STREAM_KEEPALIVE_SECS: u64 = 15;
+
+/// Version of the event log line schema; bumped only on incompatible changes
+pub const EVENT_LOG_SCHEMA_VERSION: u32 = 1;
+
+// =============================================================================
+// Admin API hardening defaults
+// =============================================================================
+
+/// Cookie set by `GET /admin/csrf-token` for double-submit CSRF protection
+pub const DEFAULT_ADMIN_CSRF_COOKIE: &str = "yatagarasu_csrf";
+
+/// Header a cookie-authenticated admin request must echo the CSRF cookie in
+pub const DEFAULT_ADMIN_CSRF_HEADER: &str = "X-CSRF-Token";
+
+/// JWT claim holding the IP or CIDR range an admin token is bound to
+pub const DEFAULT_ADMIN_IP_CLAIM: &str = "ip";
+
+// =============================================================================
+// Vault secret reference defaults
+// =============================================================================
+
+/// Prefix of config values resolved from Vault (`vault:<path>#<field>`)
+pub const VAULT_REFERENCE_PREFIX: &str = "vault:";
+
+/// Default timeout for one Vault API call
+pub const DEFAULT_VAULT_TIMEOUT_SECS: u64 = 10;
+
+/// Default mount path of the AppRole auth method
+pub const DEFAULT_VAULT_APPROLE_MOUNT: &str = "approle";
+
+// =============================================================================
+// gRPC control plane defaults
+// =============================================================================
+
+/// Default listen address of the gRPC control plane (loopback only)
+pub const DEFAULT_GRPC_ADDRESS: &str = "127.0.0.1";
+
+/// Default listen port of the gRPC control plane
+pub const DEFAULT_GRPC_PORT: u16 = 9443;
+
+// =============================================================================
+// HTTPS listener defaults
+// =============================================================================
+
+/// Default port of the HTTPS listener
+pub const DEFAULT_TLS_PORT: u16 = 8443;
+
+/// Default certificate chain file name in the ACME directory (certbot layout)
+pub const DEFAULT_TLS_CERT_FILE: &str = "fullchain.pem";
+
+/// Default private key file name in the ACME directory (certbot layout)
+pub const DEFAULT_TLS_KEY_FILE: &str = "privkey.pem";
+
+/// Default interval between checks of the certificate directory
+pub const DEFAULT_TLS_RELOAD_INTERVAL_SECS: u64 = 60;
+
+/// A stapled OCSP response is refreshed this long before its `nextUpdate`
+pub const OCSP_REFRESH_MARGIN_SECS: u64 = 3600;
+
+/// Timeout for one request to an OCSP responder
+pub const OCSP_REQUEST_TIMEOUT_SECS: u64 = 10;
+
+/// Client certificate subjects remembered for requests on mutual TLS
+/// connections; the map is emptied when it reaches this size
+pub const MAX_CLIENT_CERT_SUBJECTS: usize = 10_000;
+
+/// Default number of TLS sessions kept in the server-side session cache
+pub const DEFAULT_TLS_SESSION_CACHE_SIZE: usize = 20_480;
+
+/// Default protocols offered over ALPN by the HTTPS listener, in preference order
+pub const DEFAULT_TLS_ALPN: &[&str] = &["h2", "http/1.1"];
+
+/// Slots in the table that tells first requests on a downstream connection
+/// from keep-alive reuse (8 bytes each)
+pub const DOWNSTREAM_CONNECTION_SLOTS: usize = 65_536;
@@ -142,12 +142,23 @@ impl ProxyError {
         }
     }
 
+    /// Machine-readable code for the error
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            ProxyError::Config { .. } => ErrorCode::ConfigInvalid,
+            ProxyError::Auth { .. } => ErrorCode::AuthInvalidToken,
+            ProxyError::S3 { .. } => ErrorCode::UpstreamError,
+            ProxyError::Internal { .. } => ErrorCode::Internal,
+        }
+    }
+
     /// Convert error to JSON response string
     ///
     /// Produces consistent JSON error response with fields:
     /// - error: Error category ("config", "auth", "s3", "internal")
     /// - message: Human-readable error message
     /// - status: HTTP status code
+    /// - code: Machine-readable error code (e.g. "YAT-AUTH-002")
     /// - context: Optional context fields (bucket, key, user, operation, etc.)
     /// - request_id: Optional request ID for tracing
     ///
@@ -155,6 +166,7 @@ impl ProxyError {
     /// ```json
     /// {
     ///   "error": "auth",
+    ///   "code": "YAT-AUTH-002",
     ///   "message": "Authentication error: invalid token",
     ///   "status": 401,
     ///   "context": {
@@ -230,6 +242,7 @@ impl ProxyError {
             "error": error_type,
             "message": self.to_string(),
             "status": self.to_http_status(),
+            "code": self.code().as_str(),
         });
 
         // Add context if not empty
@@ -387,3 +400,186 @@ impl ProxyError {
         }
     }
 }
+
+/// Response header carrying the machine-readable error code
+pub const ERROR_CODE_HEADER: &str = "X-Yatagarasu-Error";
+
+/// Machine-readable error codes
+///
+/// Every error response carries one of these in the `X-Yatagarasu-Error`
+/// header and, when it has a JSON body, in its `code` field. Codes are stable:
+/// clients can branch on them instead of parsing messages. The format is
+/// `YAT-<CATEGORY>-<NNN>`:
+/// - AUTH: authentication and authorization rejections
+/// - RATE: rate limiting
+/// - AVAIL: load shedding, open circuits and disabled features
+/// - REQ: malformed or disallowed requests
+/// - S3: errors returned by or while talking to the S3 backend
+/// - CONFIG / INTERNAL: proxy-side failures
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    /// No bearer token in the request
+    AuthMissingToken,
+    /// Token is invalid, expired or fails claim checks
+    AuthInvalidToken,
+    /// Authenticated but not allowed (admin claims, OPA, OpenFGA)
+    AuthForbidden,
+    /// Client IP is temporarily banned
+    AuthIpBanned,
+    /// Built-in endpoint restricted by token or IP
+    AuthEndpointRestricted,
+    /// Referer/Origin not in the bucket's hotlink allowlist
+    AuthHotlinkDenied,
+    /// Signed URL missing, expired or with a bad signature
+    AuthSignedUrlInvalid,
+    /// Incoming SigV4 signature missing, expired or not matching
+    AuthSigV4Invalid,
+    /// Browser upload policy missing, expired, badly signed or not satisfied
+    AuthPostPolicyInvalid,
+    /// Global rate limit exceeded
+    RateLimitGlobal,
+    /// Per-IP rate limit exceeded
+    RateLimitIp,
+    /// Per-user rate limit exceeded
+    RateLimitUser,
+    /// Per-bucket rate limit exceeded
+    RateLimitBucket,
+    /// Client throttled for abusive Range request patterns
+    RateLimitRange,
+    /// `server.max_concurrent_requests` reached
+    ConcurrencyLimit,
+    /// Resource monitor is shedding load
+    ResourceExhausted,
+    /// The bucket's origins are down (circuit breaker open)
+    OriginUnavailable,
+    /// The bucket's bulkhead is full
+    BulkheadFull,
+    /// Maintenance mode is on
+    Maintenance,
+    /// A coalesced follower fell behind its leader's stream
+    StreamLagged,
+    /// The requested feature is not enabled (cache, hot reload, ...)
+    FeatureDisabled,
+    /// Any other temporary unavailability
+    Unavailable,
+    /// `X-Yat-Cache: only-if-cached` and the object is not cached
+    NotCached,
+    /// Malformed request or parameters
+    BadRequest,
+    /// HTTP method not allowed for the path
+    MethodNotAllowed,
+    /// No bucket, endpoint or entry matches the request
+    NotFound,
+    /// URI exceeds `security_limits.max_uri_length`
+    UriTooLong,
+    /// Headers exceed `security_limits.max_header_size`
+    HeadersTooLarge,
+    /// Body exceeds `security_limits.max_body_size`
+    PayloadTooLarge,
+    /// Path traversal attempt blocked
+    PathTraversal,
+    /// SQL injection attempt blocked
+    SqlInjection,
+    /// Invalid image optimization parameters
+    InvalidImageParams,
+    /// Resume token does not match the requested object
+    InvalidResumeToken,
+    /// S3 answered 404
+    S3NotFound,
+    /// S3 answered 403
+    S3AccessDenied,
+    /// S3 answered another 4xx
+    S3ClientError,
+    /// S3 answered 5xx
+    S3ServerError,
+    /// The proxy could not get a usable answer from S3
+    UpstreamError,
+    /// Configuration is invalid (e.g. a failed reload)
+    ConfigInvalid,
+    /// Unexpected proxy error
+    Internal,
+}
+
+impl ErrorCode {
+    /// Code as sent to clients, e.g. `YAT-AUTH-001`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::AuthMissingToken => "YAT-AUTH-001",
+            ErrorCode::AuthInvalidToken => "YAT-AUTH-002",
+            ErrorCode::AuthForbidden => "YAT-AUTH-003",
+            ErrorCode::AuthIpBanned => "YAT-AUTH-004",
+            ErrorCode::AuthEndpointRestricted => "YAT-AUTH-005",
+            ErrorCode::AuthHotlinkDenied => "YAT-AUTH-006",
+            ErrorCode::AuthSignedUrlInvalid => "YAT-AUTH-007",
+            ErrorCode::AuthSigV4Invalid => "YAT-AUTH-008",
+            ErrorCode::AuthPostPolicyInvalid => "YAT-AUTH-009",
+            ErrorCode::RateLimitGlobal => "YAT-RATE-001",
+            ErrorCode::RateLimitIp => "YAT-RATE-002",
+            ErrorCode::RateLimitUser => "YAT-RATE-003",
+            ErrorCode::RateLimitBucket => "YAT-RATE-004",
+            ErrorCode::RateLimitRange => "YAT-RATE-005",
+            ErrorCode::ConcurrencyLimit => "YAT-AVAIL-001",
+            ErrorCode::ResourceExhausted => "YAT-AVAIL-002",
+            ErrorCode::OriginUnavailable => "YAT-AVAIL-003",
+            ErrorCode::BulkheadFull => "YAT-AVAIL-004",
+            ErrorCode::Maintenance => "YAT-AVAIL-005",
+            ErrorCode::StreamLagged => "YAT-AVAIL-006",
+            ErrorCode::FeatureDisabled => "YAT-AVAIL-007",
+            ErrorCode::Unavailable => "YAT-AVAIL-008",
+            ErrorCode::NotCached => "YAT-AVAIL-009",
+            ErrorCode::BadRequest => "YAT-REQ-001",
+            ErrorCode::MethodNotAllowed => "YAT-REQ-002",
+            ErrorCode::NotFound => "YAT-REQ-003",
+            ErrorCode::UriTooLong => "YAT-REQ-004",
+            ErrorCode::HeadersTooLarge => "YAT-REQ-005",
+            ErrorCode::PayloadTooLarge => "YAT-REQ-006",
+            ErrorCode::PathTraversal => "YAT-REQ-007",
+            ErrorCode::SqlInjection => "YAT-REQ-008",
+            ErrorCode::InvalidImageParams => "YAT-REQ-009",
+            ErrorCode::InvalidResumeToken => "YAT-REQ-010",
+            ErrorCode::S3NotFound => "YAT-S3-001",
+            ErrorCode::S3AccessDenied => "YAT-S3-002",
+            ErrorCode::S3ClientError => "YAT-S3-003",
+            ErrorCode::S3ServerError => "YAT-S3-004",
+            ErrorCode::UpstreamError => "YAT-S3-005",
+            ErrorCode::ConfigInvalid => "YAT-CONFIG-001",
+            ErrorCode::Internal => "YAT-INTERNAL-001",
+        }
+    }
+
+    /// Generic code for an error status the proxy produced itself
+    ///
+    /// Used where no more specific code applies (e.g. admin API errors).
+    pub fn for_status(status: u16) -> Self {
+        match status {
+            401 => ErrorCode::AuthMissingToken,
+            403 => ErrorCode::AuthForbidden,
+            404 => ErrorCode::NotFound,
+            405 => ErrorCode::MethodNotAllowed,
+            413 => ErrorCode::PayloadTooLarge,
+            414 => ErrorCode::UriTooLong,
+            429 => ErrorCode::RateLimitGlobal,
+            431 => ErrorCode::HeadersTooLarge,
+            502 | 504 => ErrorCode::UpstreamError,
+            503 => ErrorCode::Unavailable,
+            500..=599 => ErrorCode::Internal,
+            _ => ErrorCode::BadRequest,
+        }
+    }
+
+    /// Code for an error status returned by S3
+    pub fn for_upstream_status(status: u16) -> Self {
+        match status {
+            403 => ErrorCode::S3AccessDenied,
+            404 => ErrorCode::S3NotFound,
+            500..=599 => ErrorCode::S3ServerError,
+            _ => ErrorCode::S3ClientError,
+        }
+    }
+}
+
+impl fmt::Display for ErrorCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
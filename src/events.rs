@@ -0,0 +1,311 @@
+//! Runtime event bus.
+//!
+//! State changes that operators want to watch live (replica failovers,
+//! circuit breaker transitions, config reloads, cache purges) are published
+//! here and streamed to `GET /admin/events` subscribers as Server-Sent Events.
+//!
+//! Lifecycle events (startup, cache initialization, reloads, breaker
+//! transitions, shutdown) can also be appended to a dedicated JSON-lines event
+//! log (`observability.event_log`), separate from request logs and tracing
+//! output. Publishing with no subscribers and no event log is a no-op, so emit
+//! points stay cheap.
+
+use parking_lot::{Mutex, RwLock};
+use serde::Serialize;
+use std::io::Write;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::OnceLock;
+use tokio::sync::broadcast;
+
+use crate::constants::{EVENT_LOG_SCHEMA_VERSION, EVENT_STREAM_BUFFER_SIZE};
+use crate::observability::{EventLogConfig, EventLogOutput};
+
+static EVENT_BUS: OnceLock<EventBus> = OnceLock::new();
+
+/// A structured runtime event
+#[derive(Debug, Clone, Serialize)]
+pub struct RuntimeEvent {
+    /// Monotonic id, sent as the SSE `id:` field
+    pub id: u64,
+    /// RFC 3339 time the event was published
+    pub timestamp: String,
+    #[serde(flatten)]
+    pub kind: EventKind,
+}
+
+/// Event payloads, tagged with `type` in JSON
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EventKind {
+    /// The proxy finished initializing and is about to accept connections
+    Started {
+        version: String,
+        address: String,
+        buckets: usize,
+        generation: u64,
+    },
+    /// The proxy is shutting down
+    Stopping { reason: String },
+    /// The response cache was set up at startup
+    CacheInitialized {
+        success: bool,
+        layers: Vec<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    /// A bucket started serving from a different replica
+    Failover {
+        bucket: String,
+        from: String,
+        to: String,
+    },
+    /// A bucket's circuit breaker changed state
+    BreakerTransition {
+        bucket: String,
+        from: String,
+        to: String,
+    },
+    /// A configuration reload was attempted
+    ConfigReload {
+        success: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        generation: Option<u64>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        error: Option<String>,
+    },
+    /// Cached entries were purged through the admin API
+    CachePurge {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        bucket: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        key: Option<String>,
+    },
+}
+
+impl EventKind {
+    /// Value of the `type` field, also used as the SSE `event:` name
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Started { .. } => "started",
+            Self::Stopping { .. } => "stopping",
+            Self::CacheInitialized { .. } => "cache_initialized",
+            Self::Failover { .. } => "failover",
+            Self::BreakerTransition { .. } => "breaker_transition",
+            Self::ConfigReload { .. } => "config_reload",
+            Self::CachePurge { .. } => "cache_purge",
+        }
+    }
+}
+
+/// One line of the event log: the event plus fields for consumers
+#[derive(Serialize)]
+struct EventLogLine<'a> {
+    schema_version: u32,
+    host: &'a str,
+    #[serde(flatten)]
+    event: &'a RuntimeEvent,
+}
+
+/// JSON-lines writer for the lifecycle event log
+pub struct EventLog {
+    writer: Mutex<Box<dyn Write + Send>>,
+    host: String,
+}
+
+impl EventLog {
+    /// Open the configured output; file output is appended to
+    pub fn open(config: &EventLogConfig) -> Result<Self, String> {
+        let writer: Box<dyn Write + Send> = match config.output {
+            EventLogOutput::Stdout => Box::new(std::io::stdout()),
+            EventLogOutput::Stderr => Box::new(std::io::stderr()),
+            EventLogOutput::File => {
+                let path = config.path.as_deref().unwrap_or_default();
+                let file = std::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .map_err(|e| format!("Failed to open event log '{}': {}", path, e))?;
+                Box::new(file)
+            }
+        };
+        Ok(Self::with_writer(writer))
+    }
+
+    /// Write events to any writer
+    pub fn with_writer(writer: Box<dyn Write + Send>) -> Self {
+        let host = hostname::get()
+            .map(|h| h.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        Self {
+            writer: Mutex::new(writer),
+            host,
+        }
+    }
+
+    fn write(&self, event: &RuntimeEvent) {
+        let line = EventLogLine {
+            schema_version: EVENT_LOG_SCHEMA_VERSION,
+            host: &self.host,
+            event,
+        };
+        let Ok(json) = serde_json::to_string(&line) else {
+            return;
+        };
+        // Flushed per event: lifecycle events are rare and must not sit in a buffer
+        let mut writer = self.writer.lock();
+        if let Err(e) = writeln!(writer, "{}", json).and_then(|_| writer.flush()) {
+            tracing::warn!(error = %e, event = event.kind.name(), "Failed to write event log");
+        }
+    }
+}
+
+/// Broadcast channel for runtime events
+pub struct EventBus {
+    sender: broadcast::Sender<RuntimeEvent>,
+    next_id: AtomicU64,
+    log: RwLock<Option<EventLog>>,
+}
+
+impl EventBus {
+    /// Create a bus buffering up to `capacity` events per subscriber
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity.max(1));
+        Self {
+            sender,
+            next_id: AtomicU64::new(1),
+            log: RwLock::new(None),
+        }
+    }
+
+    /// Process-wide bus
+    pub fn global() -> &'static EventBus {
+        EVENT_BUS.get_or_init(|| EventBus::new(EVENT_STREAM_BUFFER_SIZE))
+    }
+
+    /// Also append every event to this event log
+    pub fn set_log(&self, log: EventLog) {
+        *self.log.write() = Some(log);
+    }
+
+    /// Publish an event to current subscribers and the event log
+    pub fn publish(&self, kind: EventKind) {
+        let log = self.log.read();
+        if self.sender.receiver_count() == 0 && log.is_none() {
+            return;
+        }
+        let event = RuntimeEvent {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            kind,
+        };
+        if let Some(log) = log.as_ref() {
+            log.write(&event);
+        }
+        let _ = self.sender.send(event);
+    }
+
+    /// Receive events published from now on
+    pub fn subscribe(&self) -> broadcast::Receiver<RuntimeEvent> {
+        self.sender.subscribe()
+    }
+
+    /// Number of connected subscribers
+    pub fn subscriber_count(&self) -> usize {
+        self.sender.receiver_count()
+    }
+}
+
+/// Publish an event on the global bus
+pub fn publish(kind: EventKind) {
+    EventBus::global().publish(kind);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_event_bus_publish_and_serialize() {
+        let bus = EventBus::new(8);
+        // Nothing is buffered without subscribers
+        bus.publish(EventKind::CachePurge {
+            bucket: None,
+            key: None,
+        });
+
+        let mut rx = bus.subscribe();
+        bus.publish(EventKind::Failover {
+            bucket: "products".to_string(),
+            from: "primary".to_string(),
+            to: "replica-eu".to_string(),
+        });
+
+        let event = rx.recv().await.unwrap();
+        assert_eq!(event.id, 1);
+        assert_eq!(event.kind.name(), "failover");
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "failover");
+        assert_eq!(json["bucket"], "products");
+        assert_eq!(json["to"], "replica-eu");
+        assert!(json["timestamp"].is_string());
+
+        let reload = serde_json::to_value(EventKind::ConfigReload {
+            success: false,
+            generation: None,
+            error: Some("bad yaml".to_string()),
+        })
+        .unwrap();
+        assert_eq!(
+            reload,
+            serde_json::json!({"type": "config_reload", "success": false, "error": "bad yaml"})
+        );
+    }
+
+    /// Writer whose output the test can read back
+    #[derive(Clone, Default)]
+    struct SharedBuffer(std::sync::Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuffer {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_event_log_writes_json_lines_without_subscribers() {
+        let bus = EventBus::new(8);
+        let buffer = SharedBuffer::default();
+        bus.set_log(EventLog::with_writer(Box::new(buffer.clone())));
+
+        bus.publish(EventKind::Started {
+            version: "1.5.0".to_string(),
+            address: "0.0.0.0:8080".to_string(),
+            buckets: 3,
+            generation: 0,
+        });
+        bus.publish(EventKind::BreakerTransition {
+            bucket: "products".to_string(),
+            from: "closed".to_string(),
+            to: "open".to_string(),
+        });
+
+        let output = String::from_utf8(buffer.0.lock().clone()).unwrap();
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["schema_version"], 1);
+        assert_eq!(lines[0]["type"], "started");
+        assert_eq!(lines[0]["buckets"], 3);
+        assert!(lines[0]["host"].is_string());
+        assert_eq!(lines[1]["id"], 2);
+        assert_eq!(lines[1]["type"], "breaker_transition");
+        assert_eq!(lines[1]["to"], "open");
+    }
+}
@@ -0,0 +1,265 @@
+//! gRPC control plane (`grpc` feature).
+//!
+//! Serves `yatagarasu.control.v1.ControlPlane` (see
+//! `proto/yatagarasu/control/v1/control.proto`) on `server.grpc`, so fleet
+//! controllers can health-check, reload, purge and read cache stats with typed
+//! requests instead of parsing the admin JSON endpoints.
+//!
+//! Clients are authenticated by mutual TLS only: any certificate signed by
+//! `server.grpc.tls.client_ca_file` may call every method, so use a CA that
+//! issues certificates to controllers alone. Operations go through the
+//! proxy's [`ControlPlane`] handle and record the same metrics and events as
+//! their HTTP counterparts.
+
+use async_trait::async_trait;
+use pingora_core::server::ShutdownWatch;
+use pingora_core::services::background::BackgroundService;
+use std::net::SocketAddr;
+use tonic::transport::{Certificate, Identity, Server, ServerTlsConfig};
+use tonic::{Request, Response, Status};
+
+use crate::config::GrpcConfig;
+use crate::proxy::{ControlPlane, PurgeScope};
+
+/// Generated protobuf messages and service traits
+pub mod pb {
+    tonic::include_proto!("yatagarasu.control.v1");
+}
+
+use pb::control_plane_server::{ControlPlane as ControlPlaneRpc, ControlPlaneServer};
+
+/// `ControlPlane` service implementation
+pub struct ControlPlaneService {
+    control: ControlPlane,
+}
+
+impl ControlPlaneService {
+    pub fn new(control: ControlPlane) -> Self {
+        Self { control }
+    }
+}
+
+#[async_trait]
+impl ControlPlaneRpc for ControlPlaneService {
+    async fn health(
+        &self,
+        _request: Request<pb::HealthRequest>,
+    ) -> Result<Response<pb::HealthResponse>, Status> {
+        Ok(Response::new(pb::HealthResponse {
+            healthy: true,
+            ready: self.control.is_ready(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            uptime_seconds: self.control.uptime().as_secs(),
+            config_generation: self.control.config().generation,
+            maintenance: self.control.maintenance_enabled(),
+        }))
+    }
+
+    async fn reload(
+        &self,
+        request: Request<pb::ReloadRequest>,
+    ) -> Result<Response<pb::ReloadResponse>, Status> {
+        tracing::info!(peer = ?request.remote_addr(), "Configuration reload requested over gRPC");
+
+        // Reading the file (and any Vault references) blocks
+        let control = self.control.clone();
+        let generation = tokio::task::spawn_blocking(move || control.reload())
+            .await
+            .map_err(|e| Status::internal(format!("Reload task failed: {}", e)))?
+            .map_err(Status::failed_precondition)?;
+
+        Ok(Response::new(pb::ReloadResponse {
+            config_generation: generation,
+        }))
+    }
+
+    async fn purge(
+        &self,
+        request: Request<pb::PurgeRequest>,
+    ) -> Result<Response<pb::PurgeResponse>, Status> {
+        let peer = request.remote_addr();
+        let pb::PurgeRequest { bucket, key } = request.into_inner();
+        let key = format!("/{}", key.trim_start_matches('/'));
+
+        let scope = match (bucket.is_empty(), key == "/") {
+            (true, true) => PurgeScope::All,
+            (true, false) => return Err(Status::invalid_argument("key requires bucket")),
+            (false, true) => PurgeScope::Bucket(&bucket),
+            (false, false) => PurgeScope::Object {
+                bucket: &bucket,
+                key: &key,
+            },
+        };
+        self.check_cache(&bucket)?;
+
+        tracing::info!(peer = ?peer, scope = ?scope, "Cache purge requested over gRPC");
+        let entries_purged = self.control.purge(scope).await.map_err(Status::internal)?;
+
+        Ok(Response::new(pb::PurgeResponse { entries_purged }))
+    }
+
+    async fn stats(
+        &self,
+        request: Request<pb::StatsRequest>,
+    ) -> Result<Response<pb::StatsResponse>, Status> {
+        let bucket = request.into_inner().bucket;
+        self.check_cache(&bucket)?;
+
+        let stats = self
+            .control
+            .cache_stats(Some(bucket.as_str()).filter(|b| !b.is_empty()))
+            .await
+            .map_err(Status::internal)?;
+
+        Ok(Response::new(pb::StatsResponse {
+            hits: stats.hits,
+            misses: stats.misses,
+            hit_rate: stats.hit_rate(),
+            evictions: stats.evictions,
+            current_size_bytes: stats.current_size_bytes,
+            current_item_count: stats.current_item_count,
+            max_size_bytes: stats.max_size_bytes,
+        }))
+    }
+}
+
+impl ControlPlaneService {
+    /// Reject cache calls when caching is off or `bucket` is not configured
+    fn check_cache(&self, bucket: &str) -> Result<(), Status> {
+        if self.control.cache().is_none() {
+            return Err(Status::failed_precondition("Cache is not enabled"));
+        }
+        if !bucket.is_empty()
+            && !self
+                .control
+                .config()
+                .buckets
+                .iter()
+                .any(|b| b.name == bucket)
+        {
+            return Err(Status::not_found(format!("Unknown bucket '{}'", bucket)));
+        }
+        Ok(())
+    }
+}
+
+/// Pingora background service running the gRPC listener
+pub struct GrpcControlPlane {
+    addr: SocketAddr,
+    tls: ServerTlsConfig,
+    control: ControlPlane,
+}
+
+impl GrpcControlPlane {
+    /// Read the TLS files named in `config`; fails if any cannot be read
+    pub fn new(config: &GrpcConfig, control: ControlPlane) -> Result<Self, String> {
+        let tls = config.tls.as_ref().ok_or("server.grpc.tls is required")?;
+        let read =
+            |path: &str| std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path, e));
+
+        let tls = ServerTlsConfig::new()
+            .identity(Identity::from_pem(
+                read(&tls.cert_file)?,
+                read(&tls.key_file)?,
+            ))
+            .client_ca_root(Certificate::from_pem(read(&tls.client_ca_file)?));
+
+        Ok(Self {
+            addr: config.socket_addr()?,
+            tls,
+            control,
+        })
+    }
+}
+
+#[async_trait]
+impl BackgroundService for GrpcControlPlane {
+    async fn start(&self, mut shutdown: ShutdownWatch) {
+        let server = match Server::builder().tls_config(self.tls.clone()) {
+            Ok(server) => server,
+            Err(e) => {
+                tracing::error!(error = %e, "Invalid gRPC control plane TLS configuration");
+                return;
+            }
+        };
+
+        tracing::info!(address = %self.addr, "gRPC control plane listening (mutual TLS)");
+        let result = server
+            .add_service(ControlPlaneServer::new(ControlPlaneService::new(
+                self.control.clone(),
+            )))
+            .serve_with_shutdown(self.addr, async move {
+                let _ = shutdown.changed().await;
+            })
+            .await;
+        if let Err(e) = result {
+            tracing::error!(address = %self.addr, error = %e, "gRPC control plane stopped");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::proxy::YatagarasuProxy;
+
+    fn service() -> ControlPlaneService {
+        let config = Config::from_yaml_with_env(
+            r#"
+server:
+  address: "127.0.0.1"
+  port: 8080
+buckets:
+  - name: "assets"
+    path_prefix: "/assets"
+    s3:
+      bucket: "assets"
+      region: "us-east-1"
+      access_key: "test"
+      secret_key: "test"
+"#,
+        )
+        .unwrap();
+        ControlPlaneService::new(YatagarasuProxy::new(config).control_plane())
+    }
+
+    #[tokio::test]
+    async fn test_control_plane_rpcs_without_cache_or_reload() {
+        let service = service();
+
+        let health = service
+            .health(Request::new(pb::HealthRequest {}))
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(health.healthy);
+        assert_eq!(health.version, env!("CARGO_PKG_VERSION"));
+        assert!(!health.maintenance);
+
+        let reload = service.reload(Request::new(pb::ReloadRequest {})).await;
+        assert_eq!(reload.unwrap_err().code(), tonic::Code::FailedPrecondition);
+
+        let purge = |bucket: &str, key: &str| pb::PurgeRequest {
+            bucket: bucket.to_string(),
+            key: key.to_string(),
+        };
+        let status = service
+            .purge(Request::new(purge("", "logo.png")))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+        let status = service
+            .purge(Request::new(purge("assets", "")))
+            .await
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::FailedPrecondition);
+
+        let stats = service
+            .stats(Request::new(pb::StatsRequest {
+                bucket: String::new(),
+            }))
+            .await;
+        assert_eq!(stats.unwrap_err().code(), tonic::Code::FailedPrecondition);
+    }
+}
@@ -10,6 +10,7 @@ use hmac::{Hmac, Mac};
 use sha2::Sha256;
 
 use super::error::ImageError;
+use crate::security::constant_time_eq;
 
 type HmacSha256 = Hmac<Sha256>;
 
@@ -115,7 +116,7 @@ pub fn validate_signature(
         generate_signature(options, source_url, config).ok_or(ImageError::InvalidSignature)?;
 
     // Use constant-time comparison to prevent timing attacks
-    if constant_time_compare(signature, &expected) {
+    if constant_time_eq(signature, &expected) {
         Ok(())
     } else {
         Err(ImageError::InvalidSignature)
@@ -152,19 +153,6 @@ fn base64_url_encode(data: &[u8]) -> String {
     URL_SAFE_NO_PAD.encode(data)
 }
 
-/// Constant-time string comparison to prevent timing attacks
-fn constant_time_compare(a: &str, b: &str) -> bool {
-    if a.len() != b.len() {
-        return false;
-    }
-
-    let mut result = 0u8;
-    for (x, y) in a.bytes().zip(b.bytes()) {
-        result |= x ^ y;
-    }
-    result == 0
-}
-
 /// Validate image dimensions against security limits
 ///
 /// This should be called BEFORE fully decoding the image to protect
@@ -397,11 +385,4 @@ mod tests {
         assert!(glob_match("bucket/*", "bucket/image.jpg"));
         assert!(!glob_match("*.png", "image.jpg"));
     }
-
-    #[test]
-    fn test_constant_time_compare() {
-        assert!(constant_time_compare("hello", "hello"));
-        assert!(!constant_time_compare("hello", "world"));
-        assert!(!constant_time_compare("hello", "hell"));
-    }
 }
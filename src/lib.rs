@@ -2,6 +2,7 @@
 // Module declarations will be added as we implement them
 
 pub mod admin; // Phase 1 (v1.3): Admin API
+pub mod analytics; // Key popularity tracking
 pub mod audit; // Phase 33: Audit Logging
 pub mod auth;
 pub mod cache;
@@ -10,12 +11,17 @@ pub mod compression; // Phase 40: Request/Response Compression
 pub mod config;
 pub mod constants; // Centralized default values
 pub mod error;
+pub mod events; // Runtime event bus for /admin/events
+#[cfg(feature = "grpc")]
+pub mod grpc; // Typed control plane over mutual TLS
 pub mod image_optimizer; // Phase: Image Optimization
 pub mod logging;
+pub mod maintenance; // Maintenance mode switch
 pub mod metrics; // Phase 18: Prometheus Metrics
 pub mod observability; // Phase 34: Enhanced Observability
 pub mod opa; // Phase 32: OPA Integration
 pub mod openfga; // Phase 48: OpenFGA Integration
+pub mod origin; // Non-S3 bucket origins
 pub mod pipeline; // Phase 13: Request Pipeline Integration
 pub mod proxy;
 pub mod rate_limit; // Phase 21: Rate Limiting
@@ -28,4 +34,7 @@ pub mod router;
 pub mod s3;
 pub mod security; // Phase 21: Security Validations (request size, headers, path traversal)
 pub mod server; // Phase 12: Pingora Server Setup // Phase 15: Error Handling & Logging
+#[cfg(feature = "tls")]
+pub mod tls; // HTTPS listener with ACME certificates and OCSP stapling
+pub mod util; // Shared helpers (Unix time)
 pub mod watermark; // Watermarking: Text and image watermarks for images
@@ -1,11 +1,14 @@
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use pingora_core::server::configuration::{Opt, ServerConf};
 use pingora_core::server::Server;
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use yatagarasu::config::Config;
+use yatagarasu::config::{subsystems, Config};
+use yatagarasu::events::{self, EventBus, EventKind, EventLog};
+use yatagarasu::metrics::dashboard::{grafana_dashboard, DashboardOptions};
 use yatagarasu::proxy::YatagarasuProxy;
+use yatagarasu::reload::ReloadManager;
 
 /// Yatagarasu S3 Proxy - High-performance S3 proxy built with Cloudflare's Pingora
 #[derive(Parser, Debug)]
@@ -27,6 +30,33 @@ struct Args {
     /// Upgrade workers gracefully
     #[arg(long)]
     upgrade: bool,
+
+    /// Turn off subsystems regardless of the config, e.g. `cache,opa,audit`
+    /// (also read from YATAGARASU_DISABLE). Known: cache, redis, disk, opa,
+    /// openfga, audit, rate_limit, coalescing, image_optimization, tracing
+    #[arg(long, value_name = "LIST")]
+    disable: Option<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Print a Grafana dashboard for the proxy's metrics and exit
+    ExportDashboard {
+        /// Write the dashboard to a file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Dashboard title
+        #[arg(long, default_value = "Yatagarasu")]
+        title: String,
+
+        /// Dashboard UID (keep it stable so re-imports replace the dashboard)
+        #[arg(long, default_value = "yatagarasu")]
+        uid: String,
+    },
 }
 
 fn main() {
@@ -36,6 +66,26 @@ fn main() {
     // Parse command-line arguments
     let args = Args::parse();
 
+    // Subcommands that run without loading the configuration
+    if let Some(Command::ExportDashboard { output, title, uid }) = &args.command {
+        let options = DashboardOptions {
+            title: title.clone(),
+            uid: uid.clone(),
+        };
+        let dashboard = serde_json::to_string_pretty(&grafana_dashboard(&options))
+            .expect("dashboard JSON is serializable");
+        match output {
+            Some(path) => {
+                if let Err(e) = std::fs::write(path, dashboard + "\n") {
+                    eprintln!("Error: Failed to write {}: {}", path.display(), e);
+                    std::process::exit(1);
+                }
+            }
+            None => println!("{}", dashboard),
+        }
+        std::process::exit(0);
+    }
+
     // Log startup banner
     let version = env!("CARGO_PKG_VERSION");
     tracing::info!(version = version, "Starting Yatagarasu S3 Proxy");
@@ -58,7 +108,7 @@ fn main() {
         "Loading configuration"
     );
 
-    let config = Config::from_file(&args.config).unwrap_or_else(|e| {
+    let mut config = Config::from_file(&args.config).unwrap_or_else(|e| {
         eprintln!(
             "Error: Failed to load configuration from {}",
             config_path_display
@@ -68,9 +118,25 @@ fn main() {
         eprintln!("  - YAML syntax is correct");
         eprintln!("  - All required fields are present");
         eprintln!("  - Environment variables are set (if using ${{VAR}} syntax)");
+        eprintln!("  - Vault is reachable (if using vault: references)");
         std::process::exit(1);
     });
 
+    // Emergency switches: keep subsystems off whatever the config says
+    let disable_list = args
+        .disable
+        .clone()
+        .or_else(|| std::env::var(subsystems::DISABLE_SUBSYSTEMS_ENV).ok())
+        .unwrap_or_default();
+    let disabled_subsystems = subsystems::parse_list(&disable_list).unwrap_or_else(|e| {
+        eprintln!("Error: Invalid --disable list: {}", e);
+        std::process::exit(1);
+    });
+    subsystems::disable_all(&mut config, &disabled_subsystems);
+    for subsystem in &disabled_subsystems {
+        tracing::warn!(subsystem = %subsystem, "Subsystem disabled at startup");
+    }
+
     tracing::info!(
         config_file = %config_path_display,
         server_address = %config.server.address,
@@ -100,22 +166,61 @@ fn main() {
         );
         println!("  Buckets configured: {}", config.buckets.len());
         println!("  JWT enabled: {}", config.jwt.is_some());
+        if !disabled_subsystems.is_empty() {
+            let names: Vec<&str> = disabled_subsystems.iter().map(|s| s.as_str()).collect();
+            println!("  Disabled subsystems: {}", names.join(", "));
+        }
         std::process::exit(0);
     }
 
+    // Lifecycle events go to their own JSON-lines log, opened before anything emits them
+    let event_log = &config.observability.event_log;
+    if event_log.enabled {
+        let log = event_log.validate().and_then(|_| EventLog::open(event_log));
+        match log {
+            Ok(log) => EventBus::global().set_log(log),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+
     // Create Pingora server with configured thread count
+    let workers = &config.server.workers;
     let server_conf = ServerConf {
         threads: config.server.threads,
+        work_stealing: workers.work_stealing,
         ..Default::default()
     };
 
     let mut server = Server::new_with_opt_and_conf(opt, server_conf);
     server.bootstrap();
 
+    // HTTPS certificate, loaded before the reload manager so reloads re-read it
+    #[cfg(feature = "tls")]
+    let certificate_store = config.server.tls.enabled.then(|| {
+        yatagarasu::tls::CertificateStore::load(&config.server.tls).unwrap_or_else(|e| {
+            eprintln!("Error: Failed to load TLS certificate: {}", e);
+            std::process::exit(1);
+        })
+    });
+
     // Create YatagarasuProxy instance with reload support and cache initialization
     // Use a tokio runtime to initialize the cache (async operation) in the sync main function
     let proxy = {
-        let proxy = YatagarasuProxy::with_reload(config.clone(), args.config.clone());
+        let reload_manager = ReloadManager::new(args.config.clone())
+            .with_disabled_subsystems(disabled_subsystems.clone());
+        // SIGHUP and /admin/reload pick up a renewed certificate right away
+        #[cfg(feature = "tls")]
+        let reload_manager = match &certificate_store {
+            Some(store) => {
+                let store = std::sync::Arc::clone(store);
+                reload_manager.with_hook("tls certificate", move || store.reload().map(|_| ()))
+            }
+            None => reload_manager,
+        };
+        let proxy = YatagarasuProxy::with_reload_manager(config.clone(), reload_manager);
 
         // Initialize cache using a temporary tokio runtime
         let rt = tokio::runtime::Builder::new_current_thread()
@@ -126,8 +231,16 @@ fn main() {
         rt.block_on(proxy.init_cache())
     };
 
-    // Create HTTP proxy service
-    let mut proxy_service = pingora_proxy::http_proxy_service(&server.configuration, proxy);
+    // Shared with the gRPC control plane, which outlives the proxy's move into its service
+    #[cfg(feature = "grpc")]
+    let control_plane = proxy.control_plane();
+
+    // Create HTTP proxy service (service name is used as the worker thread name)
+    let mut proxy_service = pingora_proxy::http_proxy_service_with_name(
+        &server.configuration,
+        proxy,
+        &workers.thread_name,
+    );
 
     // Add TCP listener for HTTP
     let listen_addr = format!("{}:{}", config.server.address, config.server.port);
@@ -142,9 +255,48 @@ fn main() {
 
     proxy_service.add_tcp(&listen_addr);
 
+    // Add HTTPS listener, with a watcher reloading renewed ACME certificates
+    #[cfg(feature = "tls")]
+    let certificate_watcher = if let Some(store) = certificate_store {
+        use yatagarasu::tls::CertificateWatcher;
+
+        let tls_addr = format!("{}:{}", config.server.address, config.server.tls.port);
+        let settings = store.tls_settings().unwrap_or_else(|e| {
+            eprintln!("Error: Failed to set up HTTPS listener: {}", e);
+            std::process::exit(1);
+        });
+        tracing::info!(
+            address = %tls_addr,
+            subject = %store.current().subject(),
+            "HTTPS listener enabled"
+        );
+        proxy_service.add_tls_with_settings(&tls_addr, None, settings);
+        Some(CertificateWatcher::new(store))
+    } else {
+        None
+    };
+
     // Register service with server
     server.add_service(proxy_service);
 
+    #[cfg(feature = "tls")]
+    if let Some(watcher) = certificate_watcher {
+        use pingora_core::services::background::background_service;
+        server.add_service(background_service("tls certificate watcher", watcher));
+    }
+
+    #[cfg(feature = "grpc")]
+    if config.server.grpc.enabled {
+        use pingora_core::services::background::background_service;
+        use yatagarasu::grpc::GrpcControlPlane;
+
+        let grpc = GrpcControlPlane::new(&config.server.grpc, control_plane).unwrap_or_else(|e| {
+            eprintln!("Error: Failed to start gRPC control plane: {}", e);
+            std::process::exit(1);
+        });
+        server.add_service(background_service("grpc control plane", grpc));
+    }
+
     tracing::info!(
         address = %listen_addr,
         "Listening for connections"
@@ -174,6 +326,9 @@ fn main() {
             loop {
                 if shutdown_monitor.load(Ordering::Relaxed) {
                     tracing::info!("SIGTERM received, initiating graceful shutdown");
+                    events::publish(EventKind::Stopping {
+                        reason: "SIGTERM".to_string(),
+                    });
                     // Give a small grace period for in-flight requests
                     std::thread::sleep(std::time::Duration::from_millis(100));
                     tracing::info!("Graceful shutdown complete");
@@ -184,6 +339,29 @@ fn main() {
         });
     }
 
+    // Pin before Pingora spawns its worker runtimes so every worker inherits the mask
+    if !workers.cpu_affinity.is_empty() {
+        match yatagarasu::server::affinity::pin_current_thread(&workers.cpu_affinity) {
+            Ok(()) => tracing::info!(
+                cpus = ?workers.cpu_affinity,
+                threads = config.server.threads,
+                "Pinned worker threads to CPUs"
+            ),
+            Err(e) => tracing::warn!(
+                error = %e,
+                cpus = ?workers.cpu_affinity,
+                "Failed to apply CPU affinity, workers will run on all CPUs"
+            ),
+        }
+    }
+
+    events::publish(EventKind::Started {
+        version: version.to_string(),
+        address: listen_addr,
+        buckets: config.buckets.len(),
+        generation: config.generation,
+    });
+
     // Run server forever (blocks until shutdown)
     server.run_forever();
 }
@@ -0,0 +1,66 @@
+//! Maintenance mode state
+//!
+//! Holds whether the proxy is currently in maintenance mode. The initial
+//! state comes from `server.maintenance.enabled`; it can be switched at
+//! runtime through the admin API or by changing the config and reloading.
+//! What is blocked and how the 503 looks is defined by
+//! [`MaintenanceConfig`](crate::config::MaintenanceConfig).
+
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use crate::util::unix_now;
+
+/// Runtime maintenance mode switch
+pub struct MaintenanceMode {
+    enabled: AtomicBool,
+    /// Unix timestamp of the last state change
+    changed_at: AtomicU64,
+}
+
+impl MaintenanceMode {
+    /// Create the switch in the given state
+    pub fn new(enabled: bool) -> Self {
+        Self {
+            enabled: AtomicBool::new(enabled),
+            changed_at: AtomicU64::new(unix_now()),
+        }
+    }
+
+    /// Check if maintenance mode is on
+    pub fn is_enabled(&self) -> bool {
+        self.enabled.load(Ordering::Acquire)
+    }
+
+    /// Switch maintenance mode, returning the previous state
+    pub fn set(&self, enabled: bool) -> bool {
+        let previous = self.enabled.swap(enabled, Ordering::AcqRel);
+        if previous != enabled {
+            self.changed_at.store(unix_now(), Ordering::Release);
+        }
+        previous
+    }
+
+    /// Unix timestamp of the last state change (or creation)
+    pub fn changed_at(&self) -> u64 {
+        self.changed_at.load(Ordering::Acquire)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_maintenance_mode_toggle() {
+        let mode = MaintenanceMode::new(false);
+        assert!(!mode.is_enabled());
+
+        assert!(!mode.set(true));
+        assert!(mode.is_enabled());
+        assert!(mode.set(true));
+
+        assert!(mode.set(false));
+        assert!(!mode.is_enabled());
+        assert!(mode.changed_at() > 0);
+    }
+}
@@ -0,0 +1,117 @@
+//! Label cardinality limiting.
+//!
+//! A [`LabelLimiter`] admits the first `max_values` distinct values of one
+//! label. Later values resolve to [`OTHER_LABEL`], so the number of exported
+//! series stays bounded. Overflows are counted, and a warning is logged the
+//! first time the limit is hit.
+
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::RwLock;
+
+/// Label value used once a label's limit is reached
+pub const OTHER_LABEL: &str = "other";
+
+/// Bounded set of admitted values for one label
+#[derive(Debug)]
+pub struct LabelLimiter {
+    label: &'static str,
+    max_values: AtomicUsize,
+    values: RwLock<HashSet<String>>,
+    overflows: AtomicU64,
+    warned: AtomicBool,
+}
+
+impl LabelLimiter {
+    /// Create a limiter for `label` admitting at most `max_values` values
+    pub fn new(label: &'static str, max_values: usize) -> Self {
+        Self {
+            label,
+            max_values: AtomicUsize::new(max_values),
+            values: RwLock::new(HashSet::new()),
+            overflows: AtomicU64::new(0),
+            warned: AtomicBool::new(false),
+        }
+    }
+
+    /// Change the limit (values already admitted stay admitted)
+    pub fn set_max_values(&self, max_values: usize) {
+        self.max_values.store(max_values, Ordering::Relaxed);
+    }
+
+    /// Whether `value` has its own series (admitting it if there is room)
+    pub fn admit(&self, value: &str) -> bool {
+        // Fast path: already admitted, only a shared lock is needed
+        if let Ok(values) = self.values.read() {
+            if values.contains(value) {
+                return true;
+            }
+        }
+
+        let Ok(mut values) = self.values.write() else {
+            return true;
+        };
+        if values.contains(value) {
+            return true;
+        }
+        let max_values = self.max_values.load(Ordering::Relaxed);
+        if values.len() < max_values {
+            values.insert(value.to_string());
+            return true;
+        }
+
+        self.overflows.fetch_add(1, Ordering::Relaxed);
+        if !self.warned.swap(true, Ordering::Relaxed) {
+            tracing::warn!(
+                label = self.label,
+                max_values = max_values,
+                "Metrics label limit reached, further values are recorded as \"other\""
+            );
+        }
+        false
+    }
+
+    /// `value` if admitted, otherwise [`OTHER_LABEL`]
+    pub fn resolve<'a>(&self, value: &'a str) -> &'a str {
+        if self.admit(value) {
+            value
+        } else {
+            OTHER_LABEL
+        }
+    }
+
+    /// Label name
+    pub fn label(&self) -> &'static str {
+        self.label
+    }
+
+    /// Number of admitted values
+    pub fn tracked(&self) -> usize {
+        self.values.read().map(|values| values.len()).unwrap_or(0)
+    }
+
+    /// Number of recordings folded into [`OTHER_LABEL`]
+    pub fn overflows(&self) -> u64 {
+        self.overflows.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_label_limiter_folds_overflow_into_other() {
+        let limiter = LabelLimiter::new("bucket", 2);
+
+        assert_eq!(limiter.resolve("a"), "a");
+        assert_eq!(limiter.resolve("b"), "b");
+        assert_eq!(limiter.resolve("c"), OTHER_LABEL);
+        assert_eq!(limiter.resolve("a"), "a");
+        assert_eq!(limiter.tracked(), 2);
+        assert_eq!(limiter.overflows(), 1);
+
+        limiter.set_max_values(3);
+        assert_eq!(limiter.resolve("c"), "c");
+    }
+}
@@ -0,0 +1,170 @@
+//! Sharded labelled counters.
+//!
+//! Per-label counters (by bucket, status, method, ...) are bumped on every
+//! request. A single `Mutex<HashMap>` per metric serializes all workers on the
+//! hot path, so [`CounterMap`] splits labels across shards guarded by
+//! `RwLock`s holding atomic values:
+//! - Incrementing an existing label takes a shared read lock and a relaxed
+//!   `fetch_add`, so concurrent requests never block each other
+//! - The write lock is only taken the first time a label is seen
+//! - Scrapes aggregate all shards into a plain `HashMap`
+
+use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hash};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+/// Number of shards per counter map (power of two for cheap masking)
+const SHARD_COUNT: usize = 16;
+
+/// Concurrent map of label -> monotonically increasing counter
+#[derive(Debug)]
+pub struct CounterMap<K> {
+    shards: Box<[RwLock<HashMap<K, AtomicU64>>]>,
+    hasher: RandomState,
+}
+
+impl<K: Hash + Eq + Clone> CounterMap<K> {
+    /// Create an empty counter map
+    pub fn new() -> Self {
+        Self {
+            shards: (0..SHARD_COUNT)
+                .map(|_| RwLock::new(HashMap::new()))
+                .collect(),
+            hasher: RandomState::new(),
+        }
+    }
+
+    fn shard<Q>(&self, key: &Q) -> &RwLock<HashMap<K, AtomicU64>>
+    where
+        K: Borrow<Q>,
+        Q: Hash + ?Sized,
+    {
+        let index = self.hasher.hash_one(key) as usize & (SHARD_COUNT - 1);
+        &self.shards[index]
+    }
+
+    /// Add `value` to the counter for `key`
+    pub fn add<Q>(&self, key: &Q, value: u64)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        let shard = self.shard(key);
+
+        // Fast path: label already exists, only a shared lock is needed
+        if let Ok(counters) = shard.read() {
+            if let Some(counter) = counters.get(key) {
+                counter.fetch_add(value, Ordering::Relaxed);
+                return;
+            }
+        }
+
+        if let Ok(mut counters) = shard.write() {
+            counters
+                .entry(key.to_owned())
+                .or_insert_with(|| AtomicU64::new(0))
+                .fetch_add(value, Ordering::Relaxed);
+        }
+    }
+
+    /// Increment the counter for `key` by one
+    pub fn increment<Q>(&self, key: &Q)
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        self.add(key, 1);
+    }
+
+    /// Current value for `key` (0 if never incremented)
+    pub fn get<Q>(&self, key: &Q) -> u64
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.shard(key)
+            .read()
+            .ok()
+            .and_then(|counters| {
+                counters
+                    .get(key)
+                    .map(|counter| counter.load(Ordering::Relaxed))
+            })
+            .unwrap_or(0)
+    }
+
+    /// Aggregate all shards into a point-in-time snapshot
+    pub fn snapshot(&self) -> HashMap<K, u64> {
+        let mut snapshot = HashMap::new();
+        for shard in self.shards.iter() {
+            if let Ok(counters) = shard.read() {
+                for (key, counter) in counters.iter() {
+                    snapshot.insert(key.clone(), counter.load(Ordering::Relaxed));
+                }
+            }
+        }
+        snapshot
+    }
+}
+
+impl<K: Hash + Eq + Clone> Default for CounterMap<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_counter_map_increment_and_get() {
+        let counters: CounterMap<String> = CounterMap::new();
+
+        counters.increment("bucket-a");
+        counters.increment("bucket-a");
+        counters.add("bucket-b", 5);
+
+        assert_eq!(counters.get("bucket-a"), 2);
+        assert_eq!(counters.get("bucket-b"), 5);
+        assert_eq!(counters.get("missing"), 0);
+    }
+
+    #[test]
+    fn test_counter_map_snapshot_aggregates_shards() {
+        let counters: CounterMap<u16> = CounterMap::new();
+        for status in 200..240u16 {
+            counters.increment(&status);
+        }
+
+        let snapshot = counters.snapshot();
+        assert_eq!(snapshot.len(), 40);
+        assert!(snapshot.values().all(|&count| count == 1));
+    }
+
+    #[test]
+    fn test_counter_map_concurrent_increments() {
+        let counters: Arc<CounterMap<String>> = Arc::new(CounterMap::new());
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let counters = Arc::clone(&counters);
+                std::thread::spawn(move || {
+                    for i in 0..1000 {
+                        counters.increment(if i % 2 == 0 { "even" } else { "odd" });
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        assert_eq!(counters.get("even"), 4000);
+        assert_eq!(counters.get("odd"), 4000);
+    }
+}
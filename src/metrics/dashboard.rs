@@ -0,0 +1,396 @@
+//! Grafana dashboard export.
+//!
+//! `yatagarasu export-dashboard` prints a dashboard wired to the metric names
+//! and labels this crate exports. A `bucket` template variable filters every
+//! per-bucket panel. Panels are declared here, next to the metrics, and a
+//! test checks every metric they query against the `/metrics` output, so
+//! renaming a metric fails the tests instead of silently emptying a panel.
+
+use serde_json::{json, Value};
+
+/// Grafana schema version the dashboard is written for
+const SCHEMA_VERSION: u32 = 39;
+
+/// Panel width in grid units (the grid is 24 wide)
+const PANEL_WIDTH: u32 = 12;
+
+/// Panel height in grid units
+const PANEL_HEIGHT: u32 = 8;
+
+/// Options for the exported dashboard
+#[derive(Debug, Clone)]
+pub struct DashboardOptions {
+    /// Dashboard title
+    pub title: String,
+    /// Dashboard UID (stable across exports so re-imports overwrite)
+    pub uid: String,
+}
+
+impl Default for DashboardOptions {
+    fn default() -> Self {
+        Self {
+            title: "Yatagarasu".to_string(),
+            uid: "yatagarasu".to_string(),
+        }
+    }
+}
+
+/// One PromQL query and its legend
+struct Query {
+    expr: &'static str,
+    legend: &'static str,
+}
+
+/// One time series panel
+struct Panel {
+    title: &'static str,
+    unit: &'static str,
+    queries: &'static [Query],
+}
+
+/// A collapsible row of panels
+struct Row {
+    title: &'static str,
+    panels: &'static [Panel],
+}
+
+const ROWS: &[Row] = &[
+    Row {
+        title: "Overview",
+        panels: &[
+            Panel {
+                title: "Request rate",
+                unit: "reqps",
+                queries: &[Query {
+                    expr: "sum(rate(http_requests_total[$__rate_interval]))",
+                    legend: "requests",
+                }],
+            },
+            Panel {
+                title: "Responses by status",
+                unit: "reqps",
+                queries: &[Query {
+                    expr: "sum by (status) (rate(http_requests_by_status_total[$__rate_interval]))",
+                    legend: "{{status}}",
+                }],
+            },
+            Panel {
+                title: "Request duration",
+                unit: "s",
+                queries: &[Query {
+                    expr: "max by (quantile) (http_request_duration_seconds)",
+                    legend: "p{{quantile}}",
+                }],
+            },
+            Panel {
+                title: "Connections and queue",
+                unit: "short",
+                queries: &[
+                    Query {
+                        expr: "sum(active_connections)",
+                        legend: "active connections",
+                    },
+                    Query {
+                        expr: "sum(yatagarasu_request_queue_depth)",
+                        legend: "queued requests",
+                    },
+                ],
+            },
+            Panel {
+                title: "Load shedding",
+                unit: "reqps",
+                queries: &[
+                    Query {
+                        expr: "sum(rate(concurrency_limit_rejections_total[$__rate_interval]))",
+                        legend: "concurrency limit",
+                    },
+                    Query {
+                        expr: "sum by (outcome) (rate(yatagarasu_request_queue_total[$__rate_interval]))",
+                        legend: "queue {{outcome}}",
+                    },
+                ],
+            },
+            Panel {
+                title: "Authentication",
+                unit: "reqps",
+                queries: &[
+                    Query {
+                        expr: "sum(rate(auth_success_total[$__rate_interval]))",
+                        legend: "success",
+                    },
+                    Query {
+                        expr: "sum(rate(auth_failure_total[$__rate_interval]))",
+                        legend: "failure",
+                    },
+                ],
+            },
+        ],
+    },
+    Row {
+        title: "Buckets",
+        panels: &[
+            Panel {
+                title: "Requests by bucket",
+                unit: "reqps",
+                queries: &[Query {
+                    expr: "sum by (bucket) (rate(http_requests_by_bucket_total{bucket=~\"$bucket\"}[$__rate_interval]))",
+                    legend: "{{bucket}}",
+                }],
+            },
+            Panel {
+                title: "Cache hit ratio",
+                unit: "percentunit",
+                queries: &[Query {
+                    expr: "sum by (bucket) (rate(yatagarasu_cache_hits_by_bucket_layer{bucket=~\"$bucket\"}[$__rate_interval])) / (sum by (bucket) (rate(yatagarasu_cache_hits_by_bucket_layer{bucket=~\"$bucket\"}[$__rate_interval])) + sum by (bucket) (rate(yatagarasu_cache_misses_by_bucket_layer{bucket=~\"$bucket\"}[$__rate_interval])))",
+                    legend: "{{bucket}}",
+                }],
+            },
+            Panel {
+                title: "Rate limited",
+                unit: "reqps",
+                queries: &[Query {
+                    expr: "sum by (bucket) (rate(rate_limit_exceeded_total{bucket=~\"$bucket\"}[$__rate_interval]))",
+                    legend: "{{bucket}}",
+                }],
+            },
+            Panel {
+                title: "S3 retries",
+                unit: "reqps",
+                queries: &[
+                    Query {
+                        expr: "sum by (bucket) (rate(s3_retry_attempts_total{bucket=~\"$bucket\"}[$__rate_interval]))",
+                        legend: "{{bucket}} attempts",
+                    },
+                    Query {
+                        expr: "sum by (bucket) (rate(s3_retry_exhausted_total{bucket=~\"$bucket\"}[$__rate_interval]))",
+                        legend: "{{bucket}} exhausted",
+                    },
+                ],
+            },
+            Panel {
+                title: "Bulkhead in flight",
+                unit: "short",
+                queries: &[
+                    Query {
+                        expr: "max by (bucket) (yatagarasu_bulkhead_in_flight{bucket=~\"$bucket\"})",
+                        legend: "{{bucket}}",
+                    },
+                    Query {
+                        expr: "max by (bucket) (yatagarasu_bulkhead_max_in_flight{bucket=~\"$bucket\"})",
+                        legend: "{{bucket}} limit",
+                    },
+                ],
+            },
+            Panel {
+                title: "Bulkhead rejections",
+                unit: "reqps",
+                queries: &[Query {
+                    expr: "sum by (bucket) (rate(yatagarasu_bulkhead_rejections_total{bucket=~\"$bucket\"}[$__rate_interval]))",
+                    legend: "{{bucket}}",
+                }],
+            },
+        ],
+    },
+    Row {
+        title: "Backends",
+        panels: &[
+            Panel {
+                title: "Backend health",
+                unit: "short",
+                queries: &[Query {
+                    expr: "min by (bucket) (backend_health{bucket=~\"$bucket\"})",
+                    legend: "{{bucket}}",
+                }],
+            },
+            Panel {
+                title: "Replica health",
+                unit: "short",
+                queries: &[Query {
+                    expr: "min by (bucket, replica) (replica_health{bucket=~\"$bucket\"})",
+                    legend: "{{bucket}}/{{replica}}",
+                }],
+            },
+            Panel {
+                title: "Circuit breaker state (0=closed, 1=open, 2=half-open)",
+                unit: "short",
+                queries: &[Query {
+                    expr: "max by (bucket) (circuit_breaker_state{bucket=~\"$bucket\"})",
+                    legend: "{{bucket}}",
+                }],
+            },
+            Panel {
+                title: "Circuit breaker error rate",
+                unit: "percentunit",
+                queries: &[Query {
+                    expr: "max by (bucket) (circuit_breaker_error_rate{bucket=~\"$bucket\"})",
+                    legend: "{{bucket}}",
+                }],
+            },
+            Panel {
+                title: "Upstream failures by kind",
+                unit: "reqps",
+                queries: &[Query {
+                    expr: "sum by (bucket, kind) (rate(yatagarasu_circuit_breaker_failures_total{bucket=~\"$bucket\"}[$__rate_interval]))",
+                    legend: "{{bucket}} {{kind}}",
+                }],
+            },
+            Panel {
+                title: "Replica failovers",
+                unit: "reqps",
+                queries: &[Query {
+                    expr: "sum by (bucket, from, to) (rate(replica_failovers_total{bucket=~\"$bucket\"}[$__rate_interval]))",
+                    legend: "{{bucket}} {{from}} → {{to}}",
+                }],
+            },
+        ],
+    },
+];
+
+/// Build the Grafana dashboard JSON
+pub fn grafana_dashboard(options: &DashboardOptions) -> Value {
+    let datasource = json!({ "type": "prometheus", "uid": "${datasource}" });
+    let mut panels = Vec::new();
+    let mut next_id = 1;
+    let mut y = 0;
+
+    for row in ROWS {
+        panels.push(json!({
+            "id": next_id,
+            "type": "row",
+            "title": row.title,
+            "collapsed": false,
+            "gridPos": { "h": 1, "w": 24, "x": 0, "y": y },
+            "panels": []
+        }));
+        next_id += 1;
+        y += 1;
+
+        for (index, panel) in row.panels.iter().enumerate() {
+            let targets: Vec<Value> = panel
+                .queries
+                .iter()
+                .zip('A'..)
+                .map(|(query, ref_id)| {
+                    json!({
+                        "datasource": datasource,
+                        "expr": query.expr,
+                        "legendFormat": query.legend,
+                        "refId": ref_id.to_string()
+                    })
+                })
+                .collect();
+            let x = (index as u32 % 2) * PANEL_WIDTH;
+            panels.push(json!({
+                "id": next_id,
+                "type": "timeseries",
+                "title": panel.title,
+                "datasource": datasource,
+                "fieldConfig": { "defaults": { "unit": panel.unit }, "overrides": [] },
+                "gridPos": { "h": PANEL_HEIGHT, "w": PANEL_WIDTH, "x": x, "y": y },
+                "targets": targets
+            }));
+            next_id += 1;
+            if x > 0 || index + 1 == row.panels.len() {
+                y += PANEL_HEIGHT;
+            }
+        }
+    }
+
+    json!({
+        "uid": options.uid,
+        "title": options.title,
+        "tags": ["yatagarasu", "s3"],
+        "timezone": "browser",
+        "schemaVersion": SCHEMA_VERSION,
+        "version": 1,
+        "editable": true,
+        "refresh": "30s",
+        "time": { "from": "now-6h", "to": "now" },
+        "templating": {
+            "list": [
+                {
+                    "name": "datasource",
+                    "label": "Data source",
+                    "type": "datasource",
+                    "query": "prometheus"
+                },
+                {
+                    "name": "bucket",
+                    "label": "Bucket",
+                    "type": "query",
+                    "datasource": datasource,
+                    "query": {
+                        "query": "label_values(http_requests_by_bucket_total, bucket)",
+                        "refId": "bucket"
+                    },
+                    "definition": "label_values(http_requests_by_bucket_total, bucket)",
+                    "refresh": 2,
+                    "multi": true,
+                    "includeAll": true,
+                    "current": { "text": "All", "value": "$__all" },
+                    "sort": 1
+                }
+            ]
+        },
+        "panels": panels
+    })
+}
+
+/// Metric names referenced by the dashboard's queries
+pub fn referenced_metrics() -> Vec<&'static str> {
+    const PROMQL_WORDS: &[&str] = &["rate", "sum", "max", "min", "by"];
+
+    let mut metrics: Vec<&'static str> = ROWS
+        .iter()
+        .flat_map(|row| row.panels.iter())
+        .flat_map(|panel| panel.queries.iter())
+        .flat_map(|query| {
+            // Drop label matchers so label values are not mistaken for metrics
+            query
+                .expr
+                .split(['{', '}'])
+                .step_by(2)
+                .flat_map(|part| part.split(|c: char| !(c.is_ascii_alphanumeric() || c == '_')))
+        })
+        .filter(|word| word.contains('_') && !word.starts_with('_'))
+        .filter(|word| !PROMQL_WORDS.contains(word))
+        .collect();
+    metrics.sort_unstable();
+    metrics.dedup();
+    metrics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metrics::Metrics;
+
+    /// Gauges exported outside `Metrics::export_prometheus` (bucket circuit
+    /// breakers, bulkheads and the request queue are owned by the proxy)
+    const PROXY_GAUGES: &[&str] = &[
+        "circuit_breaker_state",
+        "circuit_breaker_error_rate",
+        "yatagarasu_bulkhead_in_flight",
+        "yatagarasu_bulkhead_max_in_flight",
+        "yatagarasu_request_queue_depth",
+    ];
+
+    #[test]
+    fn test_dashboard_queries_exported_metrics() {
+        let exported = Metrics::new().export_prometheus();
+        for metric in referenced_metrics() {
+            assert!(
+                exported.contains(&format!("# TYPE {} ", metric)) || PROXY_GAUGES.contains(&metric),
+                "dashboard queries unknown metric '{}'",
+                metric
+            );
+        }
+
+        let dashboard = grafana_dashboard(&DashboardOptions::default());
+        assert_eq!(dashboard["uid"], "yatagarasu");
+        assert_eq!(dashboard["templating"]["list"][1]["name"], "bucket");
+        let panels = dashboard["panels"].as_array().unwrap();
+        let ids: std::collections::HashSet<_> = panels.iter().map(|p| p["id"].clone()).collect();
+        assert_eq!(ids.len(), panels.len());
+    }
+}
@@ -1,9 +1,21 @@
 // Metrics module - Prometheus-compatible metrics tracking
 // Provides counters, histograms, and gauges for observability
 
+pub mod cardinality;
+pub mod counters;
+pub mod dashboard;
+pub mod runtime;
+pub mod size_histogram;
+
 use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Mutex, OnceLock};
+use std::time::Instant;
+
+use cardinality::{LabelLimiter, OTHER_LABEL};
+use counters::CounterMap;
+use runtime::{BusyRatioTracker, RuntimeRegistry};
+use size_histogram::{SizeHistogram, DURATION_MS_BOUNDS, OBJECT_SIZE_BOUNDS, PATH_LENGTH_BOUNDS};
 
 /// Histogram represents percentile statistics for latency measurements
 #[derive(Debug, Clone, Copy)]
@@ -21,13 +33,13 @@ pub struct Metrics {
     request_count: AtomicU64,
 
     // Status code counters (e.g., 200, 404, 500)
-    status_counts: Mutex<HashMap<u16, u64>>,
+    status_counts: CounterMap<u16>,
 
     // Bucket name counters
-    bucket_counts: Mutex<HashMap<String, u64>>,
+    bucket_counts: CounterMap<String>,
 
     // HTTP method counters (GET, HEAD, POST, etc.)
-    method_counts: Mutex<HashMap<String, u64>>,
+    method_counts: CounterMap<String>,
 
     // Duration tracking (stored in microseconds as u64)
     durations: Mutex<Vec<u64>>,
@@ -44,10 +56,10 @@ pub struct Metrics {
     auth_bypassed: AtomicU64,
 
     // Authentication error counters by type (missing, invalid, expired, etc.)
-    auth_errors: Mutex<HashMap<String, u64>>,
+    auth_errors: CounterMap<String>,
 
     // S3 operation counters (GET, HEAD, etc.)
-    s3_operations: Mutex<HashMap<String, u64>>,
+    s3_operations: CounterMap<String>,
 
     // System metrics
     active_connections: AtomicU64,
@@ -57,23 +69,24 @@ pub struct Metrics {
     uptime_seconds: AtomicU64,
 
     // S3 error counters by error code (NoSuchKey, AccessDenied, etc.)
-    s3_errors: Mutex<HashMap<String, u64>>,
+    s3_errors: CounterMap<String>,
 
     // Configuration reload metrics
     reload_success: AtomicU64,
     reload_failure: AtomicU64,
     config_generation: AtomicU64,
+    retired_generation_requests: AtomicU64,
 
     // Concurrency limiting metrics
     concurrency_limit_rejections: AtomicU64,
 
     // Rate limiting metrics (per-bucket)
-    rate_limit_exceeded: Mutex<HashMap<String, u64>>,
+    rate_limit_exceeded: CounterMap<String>,
 
     // Retry metrics (per-bucket)
-    s3_retry_attempts: Mutex<HashMap<String, u64>>,
-    s3_retry_success: Mutex<HashMap<String, u64>>,
-    s3_retry_exhausted: Mutex<HashMap<String, u64>>,
+    s3_retry_attempts: CounterMap<String>,
+    s3_retry_success: CounterMap<String>,
+    s3_retry_exhausted: CounterMap<String>,
 
     // Security validation metrics
     security_payload_too_large: AtomicU64,
@@ -87,11 +100,11 @@ pub struct Metrics {
 
     // Phase 23: Per-replica metrics
     // Key format: "bucket_name:replica_name"
-    replica_request_counts: Mutex<HashMap<String, u64>>,
-    replica_error_counts: Mutex<HashMap<String, u64>>,
+    replica_request_counts: CounterMap<String>,
+    replica_error_counts: CounterMap<String>,
     replica_latencies: Mutex<HashMap<String, Vec<u64>>>,
     // Key format for failovers: "bucket_name:from_replica:to_replica"
-    replica_failovers: Mutex<HashMap<String, u64>>,
+    replica_failovers: CounterMap<String>,
     // Replica health gauge: true=healthy, false=unhealthy
     replica_health: Mutex<HashMap<String, bool>>,
     // Active replica gauge: which replica is currently serving for each bucket
@@ -109,11 +122,16 @@ pub struct Metrics {
 
     // Phase 65.2: Per-bucket and per-layer cache metrics
     // Key format: "bucket:layer" where layer is "memory", "disk", or "redis"
-    cache_hits_by_bucket_layer: Mutex<HashMap<String, u64>>,
-    cache_misses_by_bucket_layer: Mutex<HashMap<String, u64>>,
-    cache_evictions_by_layer: Mutex<HashMap<String, u64>>, // Per-layer evictions
-    cache_size_by_layer: Mutex<HashMap<String, u64>>,      // Per-layer size in bytes
-    cache_items_by_layer: Mutex<HashMap<String, u64>>,     // Per-layer item count
+    cache_hits_by_bucket_layer: CounterMap<String>,
+    cache_misses_by_bucket_layer: CounterMap<String>,
+    cache_evictions_by_layer: CounterMap<String>, // Per-layer evictions
+    cache_size_by_layer: Mutex<HashMap<String, u64>>, // Per-layer size in bytes
+    cache_items_by_layer: Mutex<HashMap<String, u64>>, // Per-layer item count
+
+    // Cache layer quarantine (failing disk/redis layers are skipped)
+    cache_layer_errors: CounterMap<String>,
+    cache_layer_quarantines: CounterMap<String>,
+    cache_layer_quarantined: Mutex<HashMap<String, bool>>,
 
     // Phase v1.4: sendfile metrics
     cache_sendfile_count: AtomicU64, // Number of sendfile-eligible responses
@@ -140,9 +158,127 @@ pub struct Metrics {
     image_bytes_processed: AtomicU64,            // total processed bytes
     image_cache_hits: AtomicU64,                 // image variant cache hits
     image_cache_misses: AtomicU64,               // image variant cache misses
-    image_transformations: Mutex<HashMap<String, u64>>, // by transformation type
-    image_formats: Mutex<HashMap<String, u64>>,  // by output format
-    image_errors_by_type: Mutex<HashMap<String, u64>>, // by error type
+    image_transformations: CounterMap<String>,   // by transformation type
+    image_formats: CounterMap<String>,           // by output format
+    image_errors_by_type: CounterMap<String>,    // by error type
+
+    // Runtime internals: listener accepts, upstream connection pool, Tokio runtime
+    downstream_connections_accepted: AtomicU64,
+    downstream_requests_reused: AtomicU64,
+    upstream_connections_new: AtomicU64,
+    upstream_connections_reused: AtomicU64,
+    runtime_busy_tracker: BusyRatioTracker,
+
+    // Client disconnects: aborted transfers and upstream bytes not fetched
+    client_aborts_cancelled: AtomicU64,
+    client_aborts_cache_fill: AtomicU64,
+    client_abort_bytes_saved: AtomicU64,
+
+    // Hotlink protection: rejected/watermarked requests by "bucket:action"
+    hotlink_requests: CounterMap<String>,
+
+    // Automatic IP bans: bans issued by reason, requests rejected while banned
+    ip_bans: CounterMap<String>,
+    ip_ban_rejections: AtomicU64,
+
+    // Request priority: high-priority requests admitted by "reserved_permit" or "shed_bypass"
+    priority_admissions: CounterMap<String>,
+
+    // Cache override header: honored requests by mode, plus "untrusted" for ignored ones
+    cache_overrides: CounterMap<String>,
+
+    // Maintenance mode: current state (0/1) and requests answered with 503
+    maintenance_mode: AtomicU64,
+    maintenance_rejections: AtomicU64,
+
+    // Health/metrics endpoint access: denied requests by "endpoint:reason"
+    endpoint_access_denials: CounterMap<String>,
+
+    // Degraded mode: requests answered while origins were down by "bucket:outcome"
+    degraded_responses: CounterMap<String>,
+
+    // Background origin probes by "target:result"
+    origin_probes: CounterMap<String>,
+
+    // HEAD-before-GET freshness checks by "bucket:result"
+    freshness_checks: CounterMap<String>,
+
+    // Label cardinality limits (values past the limit are recorded as "other")
+    bucket_labels: LabelLimiter,
+    replica_labels: LabelLimiter,
+
+    // Circuit breaker failures by "bucket:kind"
+    circuit_breaker_failures: CounterMap<String>,
+
+    // Requests rejected because the bucket's bulkhead was full, by bucket
+    bulkhead_rejections: CounterMap<String>,
+
+    // Requests routed to buckets that carry a deprecation notice
+    deprecated_route_requests: CounterMap<String>,
+
+    // Requests by bucket, version and entry prefix, for buckets with aliases or a version
+    route_entry_requests: CounterMap<String>,
+
+    // Requests rejected for an oversized header value, by header name
+    oversized_headers: CounterMap<String>,
+    header_labels: LabelLimiter,
+
+    // Requests that waited for a concurrency permit, by outcome (admitted, timeout, full)
+    request_queue_outcomes: CounterMap<String>,
+
+    // Per-bucket size distributions: request path length, response body size
+    // and requested range span (206 responses)
+    request_path_lengths: SizeHistogram,
+    response_sizes: SizeHistogram,
+    range_spans: SizeHistogram,
+
+    // Upload virus scans by "bucket:result" and their duration per bucket
+    upload_scans: CounterMap<String>,
+    upload_scan_durations: SizeHistogram,
+
+    // TLS handshakes on the HTTPS listener by session (full/resumed) and
+    // their duration
+    tls_handshakes: CounterMap<String>,
+    tls_handshake_durations: SizeHistogram,
+
+    // Verified-token cache lookups by result (hit/miss)
+    jwt_token_cache: CounterMap<String>,
+
+    // Responses not stored in the cache, by skip reason
+    cache_population_skips: CounterMap<String>,
+
+    // Range requests answered from cached chunks, and chunks stored, by result
+    cache_range_chunks: CounterMap<String>,
+
+    // Responses served uncached because the response buffer budget was spent
+    response_buffer_budget_exceeded: AtomicU64,
+
+    // Signed URL checks by "bucket:result"
+    signed_url_requests: CounterMap<String>,
+
+    // Incoming SigV4 checks by "bucket:result"
+    sigv4_requests: CounterMap<String>,
+
+    // JWT failures by "bucket:reason:issuer" (issuer last, it may contain ':')
+    auth_failure_reasons: CounterMap<String>,
+
+    // Cache experiment lookups by "bucket:experiment:variant:result"
+    cache_experiment_requests: CounterMap<String>,
+
+    // Browser POST policy uploads by "bucket:result"
+    post_policy_uploads: CounterMap<String>,
+
+    // Presigned redirects by "bucket:result"
+    presigned_redirects: CounterMap<String>,
+
+    // Range abuse detections and throttled requests by "bucket:result"
+    range_abuse_events: CounterMap<String>,
+
+    // Requests allowed by temporary access grants by "bucket:match"
+    access_grant_requests: CounterMap<String>,
+
+    // Upstream responses by bucket size class: "bucket:class"
+    size_class_responses: CounterMap<String>,
 }
 
 /// Global singleton instance of metrics
@@ -153,41 +289,42 @@ impl Metrics {
     pub fn new() -> Self {
         Metrics {
             request_count: AtomicU64::new(0),
-            status_counts: Mutex::new(HashMap::new()),
-            bucket_counts: Mutex::new(HashMap::new()),
-            method_counts: Mutex::new(HashMap::new()),
+            status_counts: CounterMap::new(),
+            bucket_counts: CounterMap::new(),
+            method_counts: CounterMap::new(),
             durations: Mutex::new(Vec::new()),
             s3_latencies: Mutex::new(Vec::new()),
             bucket_latencies: Mutex::new(HashMap::new()),
             auth_success: AtomicU64::new(0),
             auth_failure: AtomicU64::new(0),
             auth_bypassed: AtomicU64::new(0),
-            auth_errors: Mutex::new(HashMap::new()),
-            s3_operations: Mutex::new(HashMap::new()),
+            auth_errors: CounterMap::new(),
+            s3_operations: CounterMap::new(),
             active_connections: AtomicU64::new(0),
             bytes_sent: AtomicU64::new(0),
             bytes_received: AtomicU64::new(0),
             memory_usage: AtomicU64::new(0),
             uptime_seconds: AtomicU64::new(0),
-            s3_errors: Mutex::new(HashMap::new()),
+            s3_errors: CounterMap::new(),
             reload_success: AtomicU64::new(0),
             reload_failure: AtomicU64::new(0),
             config_generation: AtomicU64::new(0),
+            retired_generation_requests: AtomicU64::new(0),
             concurrency_limit_rejections: AtomicU64::new(0),
-            rate_limit_exceeded: Mutex::new(HashMap::new()),
-            s3_retry_attempts: Mutex::new(HashMap::new()),
-            s3_retry_success: Mutex::new(HashMap::new()),
-            s3_retry_exhausted: Mutex::new(HashMap::new()),
+            rate_limit_exceeded: CounterMap::new(),
+            s3_retry_attempts: CounterMap::new(),
+            s3_retry_success: CounterMap::new(),
+            s3_retry_exhausted: CounterMap::new(),
             security_payload_too_large: AtomicU64::new(0),
             security_headers_too_large: AtomicU64::new(0),
             security_uri_too_long: AtomicU64::new(0),
             security_path_traversal_blocked: AtomicU64::new(0),
             security_sql_injection_blocked: AtomicU64::new(0),
             backend_health: Mutex::new(HashMap::new()),
-            replica_request_counts: Mutex::new(HashMap::new()),
-            replica_error_counts: Mutex::new(HashMap::new()),
+            replica_request_counts: CounterMap::new(),
+            replica_error_counts: CounterMap::new(),
             replica_latencies: Mutex::new(HashMap::new()),
-            replica_failovers: Mutex::new(HashMap::new()),
+            replica_failovers: CounterMap::new(),
             replica_health: Mutex::new(HashMap::new()),
             active_replica: Mutex::new(HashMap::new()),
             cache_hits: AtomicU64::new(0),
@@ -199,11 +336,14 @@ impl Metrics {
             cache_get_durations: Mutex::new(Vec::new()),
             cache_set_durations: Mutex::new(Vec::new()),
             // Phase 65.2: Per-bucket and per-layer cache metrics
-            cache_hits_by_bucket_layer: Mutex::new(HashMap::new()),
-            cache_misses_by_bucket_layer: Mutex::new(HashMap::new()),
-            cache_evictions_by_layer: Mutex::new(HashMap::new()),
+            cache_hits_by_bucket_layer: CounterMap::new(),
+            cache_misses_by_bucket_layer: CounterMap::new(),
+            cache_evictions_by_layer: CounterMap::new(),
             cache_size_by_layer: Mutex::new(HashMap::new()),
             cache_items_by_layer: Mutex::new(HashMap::new()),
+            cache_layer_errors: CounterMap::new(),
+            cache_layer_quarantines: CounterMap::new(),
+            cache_layer_quarantined: Mutex::new(HashMap::new()),
             // Phase v1.4: sendfile metrics
             cache_sendfile_count: AtomicU64::new(0),
             cache_sendfile_bytes: AtomicU64::new(0),
@@ -228,9 +368,70 @@ impl Metrics {
             image_bytes_processed: AtomicU64::new(0),
             image_cache_hits: AtomicU64::new(0),
             image_cache_misses: AtomicU64::new(0),
-            image_transformations: Mutex::new(HashMap::new()),
-            image_formats: Mutex::new(HashMap::new()),
-            image_errors_by_type: Mutex::new(HashMap::new()),
+            image_transformations: CounterMap::new(),
+            image_formats: CounterMap::new(),
+            image_errors_by_type: CounterMap::new(),
+
+            // Runtime internals
+            downstream_connections_accepted: AtomicU64::new(0),
+            downstream_requests_reused: AtomicU64::new(0),
+            upstream_connections_new: AtomicU64::new(0),
+            upstream_connections_reused: AtomicU64::new(0),
+            runtime_busy_tracker: BusyRatioTracker::new(),
+
+            // Client disconnects
+            client_aborts_cancelled: AtomicU64::new(0),
+            client_aborts_cache_fill: AtomicU64::new(0),
+            client_abort_bytes_saved: AtomicU64::new(0),
+            hotlink_requests: CounterMap::new(),
+            ip_bans: CounterMap::new(),
+            ip_ban_rejections: AtomicU64::new(0),
+            priority_admissions: CounterMap::new(),
+            cache_overrides: CounterMap::new(),
+            maintenance_mode: AtomicU64::new(0),
+            maintenance_rejections: AtomicU64::new(0),
+            endpoint_access_denials: CounterMap::new(),
+            degraded_responses: CounterMap::new(),
+            origin_probes: CounterMap::new(),
+            freshness_checks: CounterMap::new(),
+            bucket_labels: LabelLimiter::new(
+                "bucket",
+                crate::constants::DEFAULT_METRICS_MAX_BUCKET_LABELS,
+            ),
+            replica_labels: LabelLimiter::new(
+                "replica",
+                crate::constants::DEFAULT_METRICS_MAX_REPLICA_LABELS,
+            ),
+            circuit_breaker_failures: CounterMap::new(),
+            bulkhead_rejections: CounterMap::new(),
+            deprecated_route_requests: CounterMap::new(),
+            route_entry_requests: CounterMap::new(),
+            oversized_headers: CounterMap::new(),
+            header_labels: LabelLimiter::new(
+                "header",
+                crate::constants::MAX_OVERSIZED_HEADER_LABELS,
+            ),
+            request_queue_outcomes: CounterMap::new(),
+            request_path_lengths: SizeHistogram::new(PATH_LENGTH_BOUNDS),
+            response_sizes: SizeHistogram::new(OBJECT_SIZE_BOUNDS),
+            range_spans: SizeHistogram::new(OBJECT_SIZE_BOUNDS),
+            upload_scans: CounterMap::new(),
+            upload_scan_durations: SizeHistogram::new(DURATION_MS_BOUNDS),
+            tls_handshakes: CounterMap::new(),
+            tls_handshake_durations: SizeHistogram::new(DURATION_MS_BOUNDS),
+            jwt_token_cache: CounterMap::new(),
+            cache_population_skips: CounterMap::new(),
+            cache_range_chunks: CounterMap::new(),
+            response_buffer_budget_exceeded: AtomicU64::new(0),
+            signed_url_requests: CounterMap::new(),
+            sigv4_requests: CounterMap::new(),
+            auth_failure_reasons: CounterMap::new(),
+            cache_experiment_requests: CounterMap::new(),
+            post_policy_uploads: CounterMap::new(),
+            presigned_redirects: CounterMap::new(),
+            range_abuse_events: CounterMap::new(),
+            access_grant_requests: CounterMap::new(),
+            size_class_responses: CounterMap::new(),
         }
     }
 
@@ -239,6 +440,29 @@ impl Metrics {
         METRICS.get_or_init(Metrics::new)
     }
 
+    /// Apply label cardinality limits (`server.metrics_cardinality`)
+    pub fn set_label_limits(&self, max_bucket_labels: usize, max_replica_labels: usize) {
+        self.bucket_labels.set_max_values(max_bucket_labels);
+        self.replica_labels.set_max_values(max_replica_labels);
+    }
+
+    /// Bucket label value to record under (`other` past the limit)
+    fn bucket_label<'a>(&self, bucket: &'a str) -> &'a str {
+        self.bucket_labels.resolve(bucket)
+    }
+
+    /// Replica label value to record under (`other` past the limit)
+    fn replica_label<'a>(&self, bucket: &str, replica: &'a str) -> &'a str {
+        if self
+            .replica_labels
+            .admit(&format!("{}:{}", bucket, replica))
+        {
+            replica
+        } else {
+            OTHER_LABEL
+        }
+    }
+
     /// Check if metrics struct is valid (for testing)
     pub fn is_valid(&self) -> bool {
         true
@@ -251,23 +475,17 @@ impl Metrics {
 
     /// Increment counter for a specific HTTP status code
     pub fn increment_status_count(&self, status_code: u16) {
-        if let Ok(mut counts) = self.status_counts.lock() {
-            *counts.entry(status_code).or_insert(0) += 1;
-        }
+        self.status_counts.increment(&status_code);
     }
 
     /// Increment counter for a specific bucket name
     pub fn increment_bucket_count(&self, bucket_name: &str) {
-        if let Ok(mut counts) = self.bucket_counts.lock() {
-            *counts.entry(bucket_name.to_string()).or_insert(0) += 1;
-        }
+        self.bucket_counts.increment(self.bucket_label(bucket_name));
     }
 
     /// Increment counter for a specific HTTP method
     pub fn increment_method_count(&self, method: &str) {
-        if let Ok(mut counts) = self.method_counts.lock() {
-            *counts.entry(method.to_string()).or_insert(0) += 1;
-        }
+        self.method_counts.increment(method);
     }
 
     /// Record a request duration in milliseconds
@@ -330,24 +548,22 @@ impl Metrics {
 
     /// Increment cache hit counter with bucket and layer labels (Phase 65.2)
     pub fn increment_cache_hit_with_labels(&self, bucket: &str, layer: &str) {
+        let bucket = self.bucket_label(bucket);
         // Update global counter
         self.cache_hits.fetch_add(1, Ordering::Relaxed);
         // Update per-bucket-layer counter
         let key = format!("{}:{}", bucket, layer);
-        if let Ok(mut counts) = self.cache_hits_by_bucket_layer.lock() {
-            *counts.entry(key).or_insert(0) += 1;
-        }
+        self.cache_hits_by_bucket_layer.increment(&key);
     }
 
     /// Increment cache miss counter with bucket and layer labels (Phase 65.2)
     pub fn increment_cache_miss_with_labels(&self, bucket: &str, layer: &str) {
+        let bucket = self.bucket_label(bucket);
         // Update global counter
         self.cache_misses.fetch_add(1, Ordering::Relaxed);
         // Update per-bucket-layer counter
         let key = format!("{}:{}", bucket, layer);
-        if let Ok(mut counts) = self.cache_misses_by_bucket_layer.lock() {
-            *counts.entry(key).or_insert(0) += 1;
-        }
+        self.cache_misses_by_bucket_layer.increment(&key);
     }
 
     /// Increment cache eviction counter with layer label (Phase 65.2)
@@ -355,9 +571,7 @@ impl Metrics {
         // Update global counter
         self.cache_evictions.fetch_add(1, Ordering::Relaxed);
         // Update per-layer counter
-        if let Ok(mut counts) = self.cache_evictions_by_layer.lock() {
-            *counts.entry(layer.to_string()).or_insert(0) += 1;
-        }
+        self.cache_evictions_by_layer.increment(layer);
     }
 
     /// Update cache size gauge with layer label (Phase 65.2)
@@ -376,25 +590,52 @@ impl Metrics {
 
     /// Get cache hits by bucket and layer (Phase 65.2)
     pub fn get_cache_hits_by_bucket_layer(&self) -> HashMap<String, u64> {
-        self.cache_hits_by_bucket_layer
-            .lock()
-            .map(|g| g.clone())
-            .unwrap_or_default()
+        self.cache_hits_by_bucket_layer.snapshot()
     }
 
     /// Get cache misses by bucket and layer (Phase 65.2)
     pub fn get_cache_misses_by_bucket_layer(&self) -> HashMap<String, u64> {
-        self.cache_misses_by_bucket_layer
-            .lock()
-            .map(|g| g.clone())
-            .unwrap_or_default()
+        self.cache_misses_by_bucket_layer.snapshot()
     }
 
     /// Get cache evictions by layer (Phase 65.2)
     pub fn get_cache_evictions_by_layer(&self) -> HashMap<String, u64> {
-        self.cache_evictions_by_layer
+        self.cache_evictions_by_layer.snapshot()
+    }
+
+    /// Increment cache layer error counter (counted toward quarantine)
+    pub fn increment_cache_layer_error(&self, layer: &str) {
+        self.cache_layer_errors.increment(layer);
+    }
+
+    /// Record a cache layer entering quarantine
+    pub fn record_cache_layer_quarantine(&self, layer: &str) {
+        self.cache_layer_quarantines.increment(layer);
+        self.set_cache_layer_quarantined(layer, true);
+    }
+
+    /// Set whether a cache layer is currently quarantined
+    pub fn set_cache_layer_quarantined(&self, layer: &str, quarantined: bool) {
+        if let Ok(mut states) = self.cache_layer_quarantined.lock() {
+            states.insert(layer.to_string(), quarantined);
+        }
+    }
+
+    /// Get cache layer error counts by layer
+    pub fn get_cache_layer_errors(&self) -> HashMap<String, u64> {
+        self.cache_layer_errors.snapshot()
+    }
+
+    /// Get cache layer quarantine counts by layer
+    pub fn get_cache_layer_quarantines(&self) -> HashMap<String, u64> {
+        self.cache_layer_quarantines.snapshot()
+    }
+
+    /// Get current quarantine state by layer
+    pub fn get_cache_layer_quarantined(&self) -> HashMap<String, bool> {
+        self.cache_layer_quarantined
             .lock()
-            .map(|g| g.clone())
+            .map(|states| states.clone())
             .unwrap_or_default()
     }
 
@@ -470,31 +711,19 @@ impl Metrics {
     /// Get count for specific status code (for testing)
     #[cfg(test)]
     pub fn get_status_count(&self, status_code: u16) -> u64 {
-        self.status_counts
-            .lock()
-            .ok()
-            .and_then(|counts| counts.get(&status_code).copied())
-            .unwrap_or(0)
+        self.status_counts.get(&status_code)
     }
 
     /// Get count for specific bucket (for testing)
     #[cfg(test)]
     pub fn get_bucket_count(&self, bucket_name: &str) -> u64 {
-        self.bucket_counts
-            .lock()
-            .ok()
-            .and_then(|counts| counts.get(bucket_name).copied())
-            .unwrap_or(0)
+        self.bucket_counts.get(bucket_name)
     }
 
     /// Get count for specific HTTP method (for testing)
     #[cfg(test)]
     pub fn get_method_count(&self, method: &str) -> u64 {
-        self.method_counts
-            .lock()
-            .ok()
-            .and_then(|counts| counts.get(method).copied())
-            .unwrap_or(0)
+        self.method_counts.get(method)
     }
 
     /// Record S3 backend latency in milliseconds
@@ -508,6 +737,7 @@ impl Metrics {
     /// Record latency for a specific bucket in milliseconds
     pub fn record_bucket_latency(&self, bucket_name: &str, duration_ms: f64) {
         let duration_us = (duration_ms * 1000.0) as u64;
+        let bucket_name = self.bucket_label(bucket_name);
         if let Ok(mut latencies) = self.bucket_latencies.lock() {
             latencies
                 .entry(bucket_name.to_string())
@@ -586,9 +816,45 @@ impl Metrics {
 
     /// Increment counter for a specific authentication error type
     pub fn increment_auth_error(&self, error_type: &str) {
-        if let Ok(mut errors) = self.auth_errors.lock() {
-            *errors.entry(error_type.to_string()).or_insert(0) += 1;
-        }
+        self.auth_errors.increment(error_type);
+    }
+
+    /// Record a JWT failure by bucket, issuer label and reason
+    pub fn increment_auth_failure_reason(&self, bucket: &str, issuer: &str, reason: &str) {
+        let bucket = self.bucket_label(bucket);
+        self.auth_failure_reasons
+            .increment(&format!("{}:{}:{}", bucket, reason, issuer));
+    }
+
+    /// Get number of JWT failures for a bucket, issuer label and reason
+    pub fn get_auth_failure_reason_count(&self, bucket: &str, issuer: &str, reason: &str) -> u64 {
+        self.auth_failure_reasons
+            .get(&format!("{}:{}:{}", bucket, reason, issuer))
+    }
+
+    /// Record a cache lookup ("hit" or "miss") for a cache experiment variant
+    pub fn increment_cache_experiment(
+        &self,
+        bucket: &str,
+        experiment: &str,
+        variant: &str,
+        result: &str,
+    ) {
+        let bucket = self.bucket_label(bucket);
+        self.cache_experiment_requests
+            .increment(&format!("{}:{}:{}:{}", bucket, experiment, variant, result));
+    }
+
+    /// Get number of cache lookups for a cache experiment variant and result
+    pub fn get_cache_experiment_count(
+        &self,
+        bucket: &str,
+        experiment: &str,
+        variant: &str,
+        result: &str,
+    ) -> u64 {
+        self.cache_experiment_requests
+            .get(&format!("{}:{}:{}:{}", bucket, experiment, variant, result))
     }
 
     /// Get successful authentication count (for testing)
@@ -612,11 +878,7 @@ impl Metrics {
     /// Get count for specific auth error type (for testing)
     #[cfg(test)]
     pub fn get_auth_error_count(&self, error_type: &str) -> u64 {
-        self.auth_errors
-            .lock()
-            .ok()
-            .and_then(|errors| errors.get(error_type).copied())
-            .unwrap_or(0)
+        self.auth_errors.get(error_type)
     }
 
     /// Get cache hit count (Phase 30)
@@ -767,15 +1029,11 @@ impl Metrics {
         }
 
         // Record format
-        if let Ok(mut formats) = self.image_formats.lock() {
-            *formats.entry(format.to_string()).or_insert(0) += 1;
-        }
+        self.image_formats.increment(format);
 
         // Record transformations
-        if let Ok(mut transforms) = self.image_transformations.lock() {
-            for t in transformations {
-                *transforms.entry((*t).to_string()).or_insert(0) += 1;
-            }
+        for t in transformations {
+            self.image_transformations.increment(*t);
         }
 
         // Record cache hit/miss
@@ -789,9 +1047,7 @@ impl Metrics {
     /// Record an image processing error
     pub fn record_image_error(&self, error_type: &str) {
         self.image_processing_errors.fetch_add(1, Ordering::Relaxed);
-        if let Ok(mut errors) = self.image_errors_by_type.lock() {
-            *errors.entry(error_type.to_string()).or_insert(0) += 1;
-        }
+        self.image_errors_by_type.increment(error_type);
     }
 
     /// Get image processing total count
@@ -835,277 +1091,714 @@ impl Metrics {
 
     /// Get transformation count by type
     pub fn get_image_transformation_count(&self, transformation: &str) -> u64 {
-        self.image_transformations
-            .lock()
-            .ok()
-            .and_then(|t| t.get(transformation).copied())
-            .unwrap_or(0)
+        self.image_transformations.get(transformation)
     }
 
     /// Get format count
     pub fn get_image_format_count(&self, format: &str) -> u64 {
-        self.image_formats
-            .lock()
-            .ok()
-            .and_then(|f| f.get(format).copied())
-            .unwrap_or(0)
+        self.image_formats.get(format)
     }
 
     /// Get error count by type
     pub fn get_image_error_count(&self, error_type: &str) -> u64 {
-        self.image_errors_by_type
-            .lock()
-            .ok()
-            .and_then(|e| e.get(error_type).copied())
-            .unwrap_or(0)
+        self.image_errors_by_type.get(error_type)
     }
 
-    /// Increment counter for a specific S3 operation
-    pub fn increment_s3_operation(&self, operation: &str) {
-        if let Ok(mut operations) = self.s3_operations.lock() {
-            *operations.entry(operation.to_string()).or_insert(0) += 1;
+    // ========== Runtime Internals Metrics ==========
+
+    /// Record a downstream request, distinguishing freshly accepted connections
+    /// from requests arriving on a kept-alive connection
+    pub fn record_downstream_request(&self, new_connection: bool) {
+        if new_connection {
+            self.downstream_connections_accepted
+                .fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.downstream_requests_reused
+                .fetch_add(1, Ordering::Relaxed);
         }
     }
 
-    /// Increment counter for a specific S3 error code
-    pub fn increment_s3_error(&self, error_code: &str) {
-        if let Ok(mut errors) = self.s3_errors.lock() {
-            *errors.entry(error_code.to_string()).or_insert(0) += 1;
+    /// Record an upstream connection checkout (reused from the pool or newly dialed)
+    pub fn record_upstream_connection(&self, reused: bool) {
+        if reused {
+            self.upstream_connections_reused
+                .fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.upstream_connections_new
+                .fetch_add(1, Ordering::Relaxed);
         }
     }
 
-    /// Get count for specific S3 operation (for testing)
-    #[cfg(test)]
-    pub fn get_s3_operation_count(&self, operation: &str) -> u64 {
-        self.s3_operations
-            .lock()
-            .ok()
-            .and_then(|operations| operations.get(operation).copied())
-            .unwrap_or(0)
+    /// Get number of accepted downstream connections
+    pub fn get_downstream_connections_accepted(&self) -> u64 {
+        self.downstream_connections_accepted.load(Ordering::Relaxed)
     }
 
-    /// Get count for specific S3 error code (for testing)
-    #[cfg(test)]
-    pub fn get_s3_error_count(&self, error_code: &str) -> u64 {
-        self.s3_errors
-            .lock()
-            .ok()
-            .and_then(|errors| errors.get(error_code).copied())
-            .unwrap_or(0)
+    /// Get number of upstream connections reused from the pool
+    pub fn get_upstream_connections_reused(&self) -> u64 {
+        self.upstream_connections_reused.load(Ordering::Relaxed)
     }
 
-    // System metrics methods
+    /// Get number of newly established upstream connections
+    pub fn get_upstream_connections_new(&self) -> u64 {
+        self.upstream_connections_new.load(Ordering::Relaxed)
+    }
 
-    /// Increment active connections count (new client connected)
-    pub fn increment_active_connections(&self) {
-        self.active_connections.fetch_add(1, Ordering::Relaxed);
+    // ========== Client Disconnect Metrics ==========
+
+    /// Record a transfer aborted by the client.
+    ///
+    /// `finished_cache_fill` is true when the remainder is fetched anyway to
+    /// complete a cache entry; otherwise the upstream fetch was cancelled and
+    /// `bytes_saved` upstream bytes were never read.
+    pub fn record_client_abort(&self, finished_cache_fill: bool, bytes_saved: u64) {
+        if finished_cache_fill {
+            self.client_aborts_cache_fill
+                .fetch_add(1, Ordering::Relaxed);
+        } else {
+            self.client_aborts_cancelled.fetch_add(1, Ordering::Relaxed);
+            self.client_abort_bytes_saved
+                .fetch_add(bytes_saved, Ordering::Relaxed);
+        }
     }
 
-    /// Decrement active connections count (client disconnected)
-    pub fn decrement_active_connections(&self) {
-        self.active_connections.fetch_sub(1, Ordering::Relaxed);
+    /// Get number of aborted transfers whose upstream fetch was cancelled
+    pub fn get_client_aborts_cancelled(&self) -> u64 {
+        self.client_aborts_cancelled.load(Ordering::Relaxed)
     }
 
-    /// Add bytes sent to client
-    pub fn add_bytes_sent(&self, bytes: u64) {
-        self.bytes_sent.fetch_add(bytes, Ordering::Relaxed);
+    /// Get number of aborted transfers finished in the background for the cache
+    pub fn get_client_aborts_cache_fill(&self) -> u64 {
+        self.client_aborts_cache_fill.load(Ordering::Relaxed)
     }
 
-    /// Add bytes received from client
-    pub fn add_bytes_received(&self, bytes: u64) {
-        self.bytes_received.fetch_add(bytes, Ordering::Relaxed);
+    /// Get total upstream bytes not fetched because the client went away
+    pub fn get_client_abort_bytes_saved(&self) -> u64 {
+        self.client_abort_bytes_saved.load(Ordering::Relaxed)
     }
 
-    /// Update memory usage (RSS in bytes)
-    pub fn update_memory_usage(&self, bytes: u64) {
-        self.memory_usage.store(bytes, Ordering::Relaxed);
+    /// Record a request that failed a bucket's hotlink check
+    pub fn increment_hotlink_request(&self, bucket: &str, action: &str) {
+        let bucket = self.bucket_label(bucket);
+        let key = format!("{}:{}", bucket, action);
+        self.hotlink_requests.increment(&key);
     }
 
-    /// Update uptime (seconds since start)
-    pub fn update_uptime(&self, seconds: u64) {
-        self.uptime_seconds.store(seconds, Ordering::Relaxed);
+    /// Get hotlinked request count for a bucket and action
+    pub fn get_hotlink_requests(&self, bucket: &str, action: &str) -> u64 {
+        self.hotlink_requests.get(&format!("{}:{}", bucket, action))
     }
 
-    /// Get active connections count (for testing)
-    #[cfg(test)]
-    pub fn get_active_connections(&self) -> u64 {
-        self.active_connections.load(Ordering::Relaxed)
+    /// Record an automatic IP ban
+    pub fn record_ip_ban(&self, reason: &str) {
+        self.ip_bans.increment(reason);
     }
 
-    /// Get bytes sent (for testing)
-    #[cfg(test)]
-    pub fn get_bytes_sent(&self) -> u64 {
-        self.bytes_sent.load(Ordering::Relaxed)
+    /// Get number of IP bans issued for a reason
+    pub fn get_ip_bans(&self, reason: &str) -> u64 {
+        self.ip_bans.get(reason)
     }
 
-    /// Get bytes received (for testing)
-    #[cfg(test)]
-    pub fn get_bytes_received(&self) -> u64 {
-        self.bytes_received.load(Ordering::Relaxed)
+    /// Increment counter for requests rejected because the client IP is banned
+    pub fn increment_ip_ban_rejection(&self) {
+        self.ip_ban_rejections.fetch_add(1, Ordering::Relaxed);
     }
 
-    /// Get memory usage (for testing)
-    #[cfg(test)]
-    pub fn get_memory_usage(&self) -> u64 {
-        self.memory_usage.load(Ordering::Relaxed)
+    /// Get number of requests rejected because the client IP is banned
+    pub fn get_ip_ban_rejections(&self) -> u64 {
+        self.ip_ban_rejections.load(Ordering::Relaxed)
     }
 
-    /// Get uptime in seconds (for testing)
-    #[cfg(test)]
-    pub fn get_uptime_seconds(&self) -> u64 {
-        self.uptime_seconds.load(Ordering::Relaxed)
+    /// Record a high-priority request admitted only because of its priority
+    pub fn increment_priority_admission(&self, reason: &str) {
+        self.priority_admissions.increment(reason);
     }
 
-    // Configuration reload metrics methods
+    /// Get number of high-priority admissions for a reason
+    pub fn get_priority_admissions(&self, reason: &str) -> u64 {
+        self.priority_admissions.get(reason)
+    }
 
-    /// Increment successful config reload counter
-    pub fn increment_reload_success(&self) {
-        self.reload_success.fetch_add(1, Ordering::Relaxed);
+    /// Record a cache override request by mode, or "untrusted" when ignored
+    pub fn increment_cache_override(&self, mode: &str) {
+        self.cache_overrides.increment(mode);
     }
 
-    /// Increment failed config reload counter
-    pub fn increment_reload_failure(&self) {
-        self.reload_failure.fetch_add(1, Ordering::Relaxed);
+    /// Get number of cache override requests for a mode
+    pub fn get_cache_overrides(&self, mode: &str) -> u64 {
+        self.cache_overrides.get(mode)
     }
 
-    /// Set current config generation number
-    pub fn set_config_generation(&self, generation: u64) {
-        self.config_generation.store(generation, Ordering::Relaxed);
+    /// Set the maintenance mode gauge
+    pub fn set_maintenance_mode(&self, enabled: bool) {
+        self.maintenance_mode
+            .store(u64::from(enabled), Ordering::Relaxed);
     }
 
-    /// Increment concurrency limit rejection counter (503 responses)
-    pub fn increment_concurrency_limit_rejection(&self) {
-        self.concurrency_limit_rejections
-            .fetch_add(1, Ordering::Relaxed);
+    /// Get the maintenance mode gauge (1 = on)
+    pub fn get_maintenance_mode(&self) -> u64 {
+        self.maintenance_mode.load(Ordering::Relaxed)
     }
 
-    /// Increment rate limit exceeded counter for a specific bucket (429 responses)
-    pub fn increment_rate_limit_exceeded(&self, bucket: &str) {
-        let mut rate_limit_exceeded = self.rate_limit_exceeded.lock().unwrap();
-        *rate_limit_exceeded.entry(bucket.to_string()).or_insert(0) += 1;
+    /// Increment counter for requests rejected by maintenance mode
+    pub fn increment_maintenance_rejection(&self) {
+        self.maintenance_rejections.fetch_add(1, Ordering::Relaxed);
     }
 
-    /// Increment S3 retry attempt counter for a specific bucket
-    pub fn increment_s3_retry_attempt(&self, bucket: &str) {
-        let mut s3_retry_attempts = self.s3_retry_attempts.lock().unwrap();
-        *s3_retry_attempts.entry(bucket.to_string()).or_insert(0) += 1;
+    /// Get number of requests rejected by maintenance mode
+    pub fn get_maintenance_rejections(&self) -> u64 {
+        self.maintenance_rejections.load(Ordering::Relaxed)
     }
 
-    /// Increment S3 retry success counter for a specific bucket (eventually succeeded after retry)
-    pub fn increment_s3_retry_success(&self, bucket: &str) {
-        let mut s3_retry_success = self.s3_retry_success.lock().unwrap();
-        *s3_retry_success.entry(bucket.to_string()).or_insert(0) += 1;
+    /// Record a request denied by health/metrics endpoint access rules
+    pub fn increment_endpoint_access_denied(&self, endpoint: &str, reason: &str) {
+        self.endpoint_access_denials
+            .increment(&format!("{}:{}", endpoint, reason));
     }
 
-    /// Increment S3 retry exhausted counter for a specific bucket (all attempts failed)
-    pub fn increment_s3_retry_exhausted(&self, bucket: &str) {
-        let mut s3_retry_exhausted = self.s3_retry_exhausted.lock().unwrap();
-        *s3_retry_exhausted.entry(bucket.to_string()).or_insert(0) += 1;
+    /// Get number of denied endpoint requests for an endpoint and reason
+    pub fn get_endpoint_access_denied(&self, endpoint: &str, reason: &str) -> u64 {
+        self.endpoint_access_denials
+            .get(&format!("{}:{}", endpoint, reason))
     }
 
-    /// Increment security validation: payload too large (413 responses)
-    pub fn increment_security_payload_too_large(&self) {
-        self.security_payload_too_large
-            .fetch_add(1, Ordering::Relaxed);
+    /// Record a request answered in degraded mode (outcome: fresh, stale or miss)
+    pub fn increment_degraded_response(&self, bucket: &str, outcome: &str) {
+        let bucket = self.bucket_label(bucket);
+        self.degraded_responses
+            .increment(&format!("{}:{}", bucket, outcome));
     }
 
-    /// Increment security validation: headers too large (431 responses)
-    pub fn increment_security_headers_too_large(&self) {
-        self.security_headers_too_large
-            .fetch_add(1, Ordering::Relaxed);
+    /// Get number of degraded mode responses for a bucket and outcome
+    pub fn get_degraded_responses(&self, bucket: &str, outcome: &str) -> u64 {
+        self.degraded_responses
+            .get(&format!("{}:{}", bucket, outcome))
     }
 
-    /// Increment security validation: URI too long (414 responses)
-    pub fn increment_security_uri_too_long(&self) {
-        self.security_uri_too_long.fetch_add(1, Ordering::Relaxed);
+    /// Record a HEAD-before-GET freshness check (result: fresh, changed or error)
+    pub fn increment_freshness_check(&self, bucket: &str, result: &str) {
+        let bucket = self.bucket_label(bucket);
+        self.freshness_checks
+            .increment(&format!("{}:{}", bucket, result));
     }
 
-    /// Increment security validation: path traversal blocked (400 responses)
-    pub fn increment_security_path_traversal_blocked(&self) {
-        self.security_path_traversal_blocked
-            .fetch_add(1, Ordering::Relaxed);
+    /// Get number of freshness checks for a bucket and result
+    pub fn get_freshness_checks(&self, bucket: &str, result: &str) -> u64 {
+        self.freshness_checks.get(&format!("{}:{}", bucket, result))
     }
 
-    /// Increment security validation: SQL injection blocked (400 responses)
-    pub fn increment_security_sql_injection_blocked(&self) {
-        self.security_sql_injection_blocked
-            .fetch_add(1, Ordering::Relaxed);
+    /// Record a background origin probe (target: bucket or bucket/replica)
+    pub fn increment_origin_probe(&self, target: &str, result: &str) {
+        self.origin_probes
+            .increment(&format!("{}:{}", target, result));
     }
 
-    /// Set backend health status for a bucket (1=healthy, 0=unhealthy)
-    pub fn set_backend_health(&self, bucket_name: &str, is_healthy: bool) {
-        if let Ok(mut health) = self.backend_health.lock() {
-            health.insert(bucket_name.to_string(), is_healthy);
-        }
+    /// Get number of origin probes for a target and result
+    pub fn get_origin_probes(&self, target: &str, result: &str) -> u64 {
+        self.origin_probes.get(&format!("{}:{}", target, result))
     }
 
-    /// Get backend health status for all buckets
-    pub fn get_backend_health(&self) -> HashMap<String, bool> {
-        if let Ok(health) = self.backend_health.lock() {
-            health.clone()
-        } else {
-            HashMap::new()
-        }
+    /// Record a circuit breaker failure (kind: connect_error, timeout, server_error, status)
+    pub fn increment_circuit_breaker_failure(&self, bucket: &str, kind: &str) {
+        let bucket = self.bucket_label(bucket);
+        self.circuit_breaker_failures
+            .increment(&format!("{}:{}", bucket, kind));
     }
 
-    // Phase 23: Per-replica metrics methods
+    /// Get number of circuit breaker failures for a bucket and kind
+    pub fn get_circuit_breaker_failures(&self, bucket: &str, kind: &str) -> u64 {
+        self.circuit_breaker_failures
+            .get(&format!("{}:{}", bucket, kind))
+    }
 
-    /// Increment request count for a specific replica within a bucket
-    pub fn increment_replica_request_count(&self, bucket: &str, replica: &str) {
-        let key = format!("{}:{}", bucket, replica);
-        if let Ok(mut counts) = self.replica_request_counts.lock() {
-            *counts.entry(key).or_insert(0) += 1;
-        }
+    /// Record a request rejected by a full bucket bulkhead
+    pub fn increment_bulkhead_rejection(&self, bucket: &str) {
+        self.bulkhead_rejections
+            .increment(self.bucket_label(bucket));
     }
 
-    /// Get request count for a specific replica (for testing)
-    #[cfg(test)]
-    pub fn get_replica_request_count(&self, bucket: &str, replica: &str) -> u64 {
-        let key = format!("{}:{}", bucket, replica);
-        self.replica_request_counts
-            .lock()
-            .ok()
-            .and_then(|counts| counts.get(&key).copied())
-            .unwrap_or(0)
+    /// Get number of bulkhead rejections for a bucket
+    pub fn get_bulkhead_rejections(&self, bucket: &str) -> u64 {
+        self.bulkhead_rejections.get(bucket)
     }
 
-    /// Increment error count for a specific replica within a bucket
-    pub fn increment_replica_error_count(&self, bucket: &str, replica: &str) {
-        let key = format!("{}:{}", bucket, replica);
-        if let Ok(mut counts) = self.replica_error_counts.lock() {
-            *counts.entry(key).or_insert(0) += 1;
-        }
+    /// Record a request to a bucket with a deprecation notice
+    pub fn increment_deprecated_route_request(&self, bucket: &str) {
+        self.deprecated_route_requests
+            .increment(self.bucket_label(bucket));
     }
 
-    /// Get error count for a specific replica (for testing)
-    #[cfg(test)]
-    pub fn get_replica_error_count(&self, bucket: &str, replica: &str) -> u64 {
-        let key = format!("{}:{}", bucket, replica);
-        self.replica_error_counts
-            .lock()
-            .ok()
-            .and_then(|counts| counts.get(&key).copied())
-            .unwrap_or(0)
+    /// Get number of requests to a deprecated bucket
+    pub fn get_deprecated_route_requests(&self, bucket: &str) -> u64 {
+        self.deprecated_route_requests.get(bucket)
     }
 
-    /// Record latency for a specific replica within a bucket in milliseconds
-    pub fn record_replica_latency(&self, bucket: &str, replica: &str, duration_ms: f64) {
-        let key = format!("{}:{}", bucket, replica);
-        let duration_us = (duration_ms * 1000.0) as u64;
-        if let Ok(mut latencies) = self.replica_latencies.lock() {
-            latencies
-                .entry(key)
-                .or_insert_with(Vec::new)
-                .push(duration_us);
-        }
+    /// Record a request by the alias or prefix (and its version) it came in on
+    pub fn increment_route_entry_request(&self, bucket: &str, prefix: &str, version: Option<&str>) {
+        let bucket = self.bucket_label(bucket);
+        self.route_entry_requests.increment(&format!(
+            "{}:{}:{}",
+            bucket,
+            version.unwrap_or_default(),
+            prefix
+        ));
     }
 
-    /// Calculate histogram for specific replica (for testing)
-    #[cfg(test)]
-    pub fn get_replica_latency_histogram(&self, bucket: &str, replica: &str) -> Histogram {
-        let key = format!("{}:{}", bucket, replica);
-        if let Ok(latencies) = self.replica_latencies.lock() {
-            if let Some(replica_samples) = latencies.get(&key) {
+    /// Get number of requests on a bucket prefix and version
+    pub fn get_route_entry_requests(
+        &self,
+        bucket: &str,
+        prefix: &str,
+        version: Option<&str>,
+    ) -> u64 {
+        self.route_entry_requests.get(&format!(
+            "{}:{}:{}",
+            bucket,
+            version.unwrap_or_default(),
+            prefix
+        ))
+    }
+
+    /// Record a request rejected for an oversized header value
+    pub fn increment_oversized_header(&self, header: &str) {
+        self.oversized_headers
+            .increment(self.header_labels.resolve(header));
+    }
+
+    /// Get number of requests rejected for an oversized header value
+    pub fn get_oversized_headers(&self, header: &str) -> u64 {
+        self.oversized_headers.get(header)
+    }
+
+    /// Record the outcome of a queued request (admitted, timeout or full)
+    pub fn increment_request_queue_outcome(&self, outcome: &str) {
+        self.request_queue_outcomes.increment(outcome);
+    }
+
+    /// Get number of queued requests with an outcome
+    pub fn get_request_queue_outcomes(&self, outcome: &str) -> u64 {
+        self.request_queue_outcomes.get(outcome)
+    }
+
+    /// Record the request path length and response body size for a bucket
+    pub fn record_bucket_sizes(&self, bucket: &str, path_length: u64, response_size: u64) {
+        let bucket = self.bucket_label(bucket);
+        self.request_path_lengths.observe(bucket, path_length);
+        self.response_sizes.observe(bucket, response_size);
+    }
+
+    /// Record the span of a served byte range for a bucket
+    pub fn record_range_span(&self, bucket: &str, span: u64) {
+        self.range_spans.observe(self.bucket_label(bucket), span);
+    }
+
+    /// Get number of size observations for a bucket
+    pub fn get_bucket_size_observations(&self, bucket: &str) -> u64 {
+        self.response_sizes.count(bucket)
+    }
+
+    /// Get number of range span observations for a bucket
+    pub fn get_range_span_observations(&self, bucket: &str) -> u64 {
+        self.range_spans.count(bucket)
+    }
+
+    /// Record an upload scan (result: clean, infected, error or skipped)
+    ///
+    /// `duration_ms` is recorded for scans that reached the scanner.
+    pub fn record_upload_scan(&self, bucket: &str, result: &str, duration_ms: Option<u64>) {
+        let bucket = self.bucket_label(bucket);
+        self.upload_scans
+            .increment(&format!("{}:{}", bucket, result));
+        if let Some(duration_ms) = duration_ms {
+            self.upload_scan_durations.observe(bucket, duration_ms);
+        }
+    }
+
+    /// Get number of upload scans for a bucket and result
+    pub fn get_upload_scans(&self, bucket: &str, result: &str) -> u64 {
+        self.upload_scans.get(&format!("{}:{}", bucket, result))
+    }
+
+    /// Record a completed TLS handshake on the HTTPS listener
+    ///
+    /// `duration_ms` is recorded when the handshake's start was observed.
+    pub fn record_tls_handshake(&self, resumed: bool, duration_ms: Option<u64>) {
+        let session = if resumed { "resumed" } else { "full" };
+        self.tls_handshakes.increment(session);
+        if let Some(duration_ms) = duration_ms {
+            self.tls_handshake_durations.observe(session, duration_ms);
+        }
+    }
+
+    /// Get number of completed TLS handshakes by session (full or resumed)
+    pub fn get_tls_handshakes(&self, session: &str) -> u64 {
+        self.tls_handshakes.get(session)
+    }
+
+    /// Count a verified-token cache lookup
+    pub fn increment_jwt_token_cache(&self, hit: bool) {
+        self.jwt_token_cache
+            .increment(if hit { "hit" } else { "miss" });
+    }
+
+    /// Get number of verified-token cache lookups with a result (hit or miss)
+    pub fn get_jwt_token_cache(&self, result: &str) -> u64 {
+        self.jwt_token_cache.get(result)
+    }
+
+    /// Count a response that was not stored in the cache
+    pub fn increment_cache_population_skip(&self, reason: &str) {
+        self.cache_population_skips.increment(reason);
+    }
+
+    /// Get number of responses not cached for a reason
+    pub fn get_cache_population_skip(&self, reason: &str) -> u64 {
+        self.cache_population_skips.get(reason)
+    }
+
+    /// Count a chunked Range cache lookup (`hit`, `miss`) or stored chunk (`stored`)
+    pub fn increment_cache_range(&self, result: &str) {
+        self.cache_range_chunks.increment(result);
+    }
+
+    /// Get number of chunked Range cache events for a result
+    pub fn get_cache_range(&self, result: &str) -> u64 {
+        self.cache_range_chunks.get(result)
+    }
+
+    /// Count a response that stopped buffering because the memory budget was spent
+    pub fn increment_response_buffer_budget_exceeded(&self) {
+        self.response_buffer_budget_exceeded
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Get number of responses served uncached due to the buffer budget
+    pub fn get_response_buffer_budget_exceeded(&self) -> u64 {
+        self.response_buffer_budget_exceeded.load(Ordering::Relaxed)
+    }
+
+    /// Record a signed URL check (result: valid or the rejection reason)
+    pub fn increment_signed_url_request(&self, bucket: &str, result: &str) {
+        let bucket = self.bucket_label(bucket);
+        self.signed_url_requests
+            .increment(&format!("{}:{}", bucket, result));
+    }
+
+    /// Get number of signed URL checks for a bucket and result
+    pub fn get_signed_url_requests(&self, bucket: &str, result: &str) -> u64 {
+        self.signed_url_requests
+            .get(&format!("{}:{}", bucket, result))
+    }
+
+    /// Record a browser POST policy upload (result: uploaded or the rejection reason)
+    pub fn increment_post_policy_upload(&self, bucket: &str, result: &str) {
+        let bucket = self.bucket_label(bucket);
+        self.post_policy_uploads
+            .increment(&format!("{}:{}", bucket, result));
+    }
+
+    /// Get number of POST policy uploads for a bucket and result
+    pub fn get_post_policy_uploads(&self, bucket: &str, result: &str) -> u64 {
+        self.post_policy_uploads
+            .get(&format!("{}:{}", bucket, result))
+    }
+
+    /// Record a presigned redirect (result: redirected or unavailable)
+    pub fn increment_presigned_redirect(&self, bucket: &str, result: &str) {
+        let bucket = self.bucket_label(bucket);
+        self.presigned_redirects
+            .increment(&format!("{}:{}", bucket, result));
+    }
+
+    /// Get number of presigned redirects for a bucket and result
+    pub fn get_presigned_redirects(&self, bucket: &str, result: &str) -> u64 {
+        self.presigned_redirects
+            .get(&format!("{}:{}", bucket, result))
+    }
+
+    /// Record a Range abuse event (result: flagged or throttled)
+    pub fn increment_range_abuse(&self, bucket: &str, result: &str) {
+        let bucket = self.bucket_label(bucket);
+        self.range_abuse_events
+            .increment(&format!("{}:{}", bucket, result));
+    }
+
+    /// Get number of Range abuse events for a bucket and result
+    pub fn get_range_abuse_events(&self, bucket: &str, result: &str) -> u64 {
+        self.range_abuse_events
+            .get(&format!("{}:{}", bucket, result))
+    }
+
+    /// Record a request allowed by a temporary access grant (matched: ip or subject)
+    pub fn increment_access_grant_use(&self, bucket: &str, matched: &str) {
+        let bucket = self.bucket_label(bucket);
+        self.access_grant_requests
+            .increment(&format!("{}:{}", bucket, matched));
+    }
+
+    /// Get number of requests allowed by access grants for a bucket and match
+    pub fn get_access_grant_uses(&self, bucket: &str, matched: &str) -> u64 {
+        self.access_grant_requests
+            .get(&format!("{}:{}", bucket, matched))
+    }
+
+    /// Record an upstream response classified into a bucket size class
+    pub fn increment_size_class_response(&self, bucket: &str, class: &str) {
+        let bucket = self.bucket_label(bucket);
+        self.size_class_responses
+            .increment(&format!("{}:{}", bucket, class));
+    }
+
+    /// Get number of upstream responses in a bucket size class
+    pub fn get_size_class_responses(&self, bucket: &str, class: &str) -> u64 {
+        self.size_class_responses
+            .get(&format!("{}:{}", bucket, class))
+    }
+
+    /// Record an incoming SigV4 check (result: valid or the rejection reason)
+    pub fn increment_sigv4_request(&self, bucket: &str, result: &str) {
+        let bucket = self.bucket_label(bucket);
+        self.sigv4_requests
+            .increment(&format!("{}:{}", bucket, result));
+    }
+
+    /// Get number of incoming SigV4 checks for a bucket and result
+    pub fn get_sigv4_requests(&self, bucket: &str, result: &str) -> u64 {
+        self.sigv4_requests.get(&format!("{}:{}", bucket, result))
+    }
+
+    /// Increment counter for a specific S3 operation
+    pub fn increment_s3_operation(&self, operation: &str) {
+        self.s3_operations.increment(operation);
+    }
+
+    /// Increment counter for a specific S3 error code
+    pub fn increment_s3_error(&self, error_code: &str) {
+        self.s3_errors.increment(error_code);
+    }
+
+    /// Get count for specific S3 operation (for testing)
+    #[cfg(test)]
+    pub fn get_s3_operation_count(&self, operation: &str) -> u64 {
+        self.s3_operations.get(operation)
+    }
+
+    /// Get count for specific S3 error code (for testing)
+    #[cfg(test)]
+    pub fn get_s3_error_count(&self, error_code: &str) -> u64 {
+        self.s3_errors.get(error_code)
+    }
+
+    // System metrics methods
+
+    /// Increment active connections count (new client connected)
+    pub fn increment_active_connections(&self) {
+        self.active_connections.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Decrement active connections count (client disconnected)
+    pub fn decrement_active_connections(&self) {
+        self.active_connections.fetch_sub(1, Ordering::Relaxed);
+    }
+
+    /// Add bytes sent to client
+    pub fn add_bytes_sent(&self, bytes: u64) {
+        self.bytes_sent.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Add bytes received from client
+    pub fn add_bytes_received(&self, bytes: u64) {
+        self.bytes_received.fetch_add(bytes, Ordering::Relaxed);
+    }
+
+    /// Update memory usage (RSS in bytes)
+    pub fn update_memory_usage(&self, bytes: u64) {
+        self.memory_usage.store(bytes, Ordering::Relaxed);
+    }
+
+    /// Update uptime (seconds since start)
+    pub fn update_uptime(&self, seconds: u64) {
+        self.uptime_seconds.store(seconds, Ordering::Relaxed);
+    }
+
+    /// Get active connections count (for testing)
+    #[cfg(test)]
+    pub fn get_active_connections(&self) -> u64 {
+        self.active_connections.load(Ordering::Relaxed)
+    }
+
+    /// Get bytes sent (for testing)
+    #[cfg(test)]
+    pub fn get_bytes_sent(&self) -> u64 {
+        self.bytes_sent.load(Ordering::Relaxed)
+    }
+
+    /// Get bytes received (for testing)
+    #[cfg(test)]
+    pub fn get_bytes_received(&self) -> u64 {
+        self.bytes_received.load(Ordering::Relaxed)
+    }
+
+    /// Get memory usage (for testing)
+    #[cfg(test)]
+    pub fn get_memory_usage(&self) -> u64 {
+        self.memory_usage.load(Ordering::Relaxed)
+    }
+
+    /// Get uptime in seconds (for testing)
+    #[cfg(test)]
+    pub fn get_uptime_seconds(&self) -> u64 {
+        self.uptime_seconds.load(Ordering::Relaxed)
+    }
+
+    // Configuration reload metrics methods
+
+    /// Increment successful config reload counter
+    pub fn increment_reload_success(&self) {
+        self.reload_success.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Increment failed config reload counter
+    pub fn increment_reload_failure(&self) {
+        self.reload_failure.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Set current config generation number
+    pub fn set_config_generation(&self, generation: u64) {
+        self.config_generation.store(generation, Ordering::Relaxed);
+    }
+
+    /// Count a request that finished on a config generation replaced by a reload
+    pub fn increment_retired_generation_request(&self) {
+        self.retired_generation_requests
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Increment concurrency limit rejection counter (503 responses)
+    pub fn increment_concurrency_limit_rejection(&self) {
+        self.concurrency_limit_rejections
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Increment rate limit exceeded counter for a specific bucket (429 responses)
+    pub fn increment_rate_limit_exceeded(&self, bucket: &str) {
+        self.rate_limit_exceeded
+            .increment(self.bucket_label(bucket));
+    }
+
+    /// Increment S3 retry attempt counter for a specific bucket
+    pub fn increment_s3_retry_attempt(&self, bucket: &str) {
+        self.s3_retry_attempts.increment(self.bucket_label(bucket));
+    }
+
+    /// Increment S3 retry success counter for a specific bucket (eventually succeeded after retry)
+    pub fn increment_s3_retry_success(&self, bucket: &str) {
+        self.s3_retry_success.increment(self.bucket_label(bucket));
+    }
+
+    /// Increment S3 retry exhausted counter for a specific bucket (all attempts failed)
+    pub fn increment_s3_retry_exhausted(&self, bucket: &str) {
+        self.s3_retry_exhausted.increment(self.bucket_label(bucket));
+    }
+
+    /// Increment security validation: payload too large (413 responses)
+    pub fn increment_security_payload_too_large(&self) {
+        self.security_payload_too_large
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Increment security validation: headers too large (431 responses)
+    pub fn increment_security_headers_too_large(&self) {
+        self.security_headers_too_large
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Increment security validation: URI too long (414 responses)
+    pub fn increment_security_uri_too_long(&self) {
+        self.security_uri_too_long.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Increment security validation: path traversal blocked (400 responses)
+    pub fn increment_security_path_traversal_blocked(&self) {
+        self.security_path_traversal_blocked
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Increment security validation: SQL injection blocked (400 responses)
+    pub fn increment_security_sql_injection_blocked(&self) {
+        self.security_sql_injection_blocked
+            .fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Set backend health status for a bucket (1=healthy, 0=unhealthy)
+    pub fn set_backend_health(&self, bucket_name: &str, is_healthy: bool) {
+        let bucket_name = self.bucket_label(bucket_name);
+        if let Ok(mut health) = self.backend_health.lock() {
+            health.insert(bucket_name.to_string(), is_healthy);
+        }
+    }
+
+    /// Get backend health status for all buckets
+    pub fn get_backend_health(&self) -> HashMap<String, bool> {
+        if let Ok(health) = self.backend_health.lock() {
+            health.clone()
+        } else {
+            HashMap::new()
+        }
+    }
+
+    // Phase 23: Per-replica metrics methods
+
+    /// Increment request count for a specific replica within a bucket
+    pub fn increment_replica_request_count(&self, bucket: &str, replica: &str) {
+        let replica = self.replica_label(bucket, replica);
+        let bucket = self.bucket_label(bucket);
+        let key = format!("{}:{}", bucket, replica);
+        self.replica_request_counts.increment(&key);
+    }
+
+    /// Get request count for a specific replica (for testing)
+    #[cfg(test)]
+    pub fn get_replica_request_count(&self, bucket: &str, replica: &str) -> u64 {
+        let key = format!("{}:{}", bucket, replica);
+        self.replica_request_counts.get(&key)
+    }
+
+    /// Increment error count for a specific replica within a bucket
+    pub fn increment_replica_error_count(&self, bucket: &str, replica: &str) {
+        let replica = self.replica_label(bucket, replica);
+        let bucket = self.bucket_label(bucket);
+        let key = format!("{}:{}", bucket, replica);
+        self.replica_error_counts.increment(&key);
+    }
+
+    /// Get error count for a specific replica (for testing)
+    #[cfg(test)]
+    pub fn get_replica_error_count(&self, bucket: &str, replica: &str) -> u64 {
+        let key = format!("{}:{}", bucket, replica);
+        self.replica_error_counts.get(&key)
+    }
+
+    /// Record latency for a specific replica within a bucket in milliseconds
+    pub fn record_replica_latency(&self, bucket: &str, replica: &str, duration_ms: f64) {
+        let replica = self.replica_label(bucket, replica);
+        let bucket = self.bucket_label(bucket);
+        let key = format!("{}:{}", bucket, replica);
+        let duration_us = (duration_ms * 1000.0) as u64;
+        if let Ok(mut latencies) = self.replica_latencies.lock() {
+            latencies
+                .entry(key)
+                .or_insert_with(Vec::new)
+                .push(duration_us);
+        }
+    }
+
+    /// Calculate histogram for specific replica (for testing)
+    #[cfg(test)]
+    pub fn get_replica_latency_histogram(&self, bucket: &str, replica: &str) -> Histogram {
+        let key = format!("{}:{}", bucket, replica);
+        if let Ok(latencies) = self.replica_latencies.lock() {
+            if let Some(replica_samples) = latencies.get(&key) {
                 calculate_histogram(replica_samples)
             } else {
                 Histogram {
@@ -1127,25 +1820,24 @@ impl Metrics {
 
     /// Increment failover counter for a specific failover path (from → to)
     pub fn increment_replica_failover(&self, bucket: &str, from: &str, to: &str) {
+        let from = self.replica_label(bucket, from);
+        let to = self.replica_label(bucket, to);
+        let bucket = self.bucket_label(bucket);
         let key = format!("{}:{}:{}", bucket, from, to);
-        if let Ok(mut failovers) = self.replica_failovers.lock() {
-            *failovers.entry(key).or_insert(0) += 1;
-        }
+        self.replica_failovers.increment(&key);
     }
 
     /// Get failover count for a specific failover path (for testing)
     #[cfg(test)]
     pub fn get_replica_failover_count(&self, bucket: &str, from: &str, to: &str) -> u64 {
         let key = format!("{}:{}:{}", bucket, from, to);
-        self.replica_failovers
-            .lock()
-            .ok()
-            .and_then(|failovers| failovers.get(&key).copied())
-            .unwrap_or(0)
+        self.replica_failovers.get(&key)
     }
 
     /// Set health status for a specific replica (gauge: 1=healthy, 0=unhealthy)
     pub fn set_replica_health(&self, bucket: &str, replica: &str, is_healthy: bool) {
+        let replica = self.replica_label(bucket, replica);
+        let bucket = self.bucket_label(bucket);
         let key = format!("{}:{}", bucket, replica);
         if let Ok(mut health) = self.replica_health.lock() {
             health.insert(key, is_healthy);
@@ -1167,9 +1859,15 @@ impl Metrics {
     }
 
     /// Set active replica for a bucket (which replica is currently serving)
-    pub fn set_active_replica(&self, bucket: &str, replica: &str) {
-        if let Ok(mut active) = self.active_replica.lock() {
-            active.insert(bucket.to_string(), replica.to_string());
+    ///
+    /// Returns the previously active replica when this call changes it.
+    pub fn set_active_replica(&self, bucket: &str, replica: &str) -> Option<String> {
+        let replica = self.replica_label(bucket, replica);
+        let bucket = self.bucket_label(bucket);
+        let mut active = self.active_replica.lock().ok()?;
+        match active.insert(bucket.to_string(), replica.to_string()) {
+            Some(previous) if previous != replica => Some(previous),
+            _ => None,
         }
     }
 
@@ -1201,6 +1899,12 @@ impl Metrics {
         self.config_generation.load(Ordering::Relaxed)
     }
 
+    /// Get count of requests completed on retired config generations (for testing)
+    #[cfg(test)]
+    pub fn get_retired_generation_requests(&self) -> u64 {
+        self.retired_generation_requests.load(Ordering::Relaxed)
+    }
+
     /// Get concurrency limit rejection count (for testing)
     #[cfg(test)]
     pub fn get_concurrency_limit_rejections(&self) -> u64 {
@@ -1223,37 +1927,31 @@ impl Metrics {
         // Status code metrics
         output.push_str("\n# HELP http_requests_by_status_total HTTP requests by status code\n");
         output.push_str("# TYPE http_requests_by_status_total counter\n");
-        if let Ok(counts) = self.status_counts.lock() {
-            for (status, count) in counts.iter() {
-                output.push_str(&format!(
-                    "http_requests_by_status_total{{status=\"{}\"}} {}\n",
-                    status, count
-                ));
-            }
+        for (status, count) in self.status_counts.snapshot().iter() {
+            output.push_str(&format!(
+                "http_requests_by_status_total{{status=\"{}\"}} {}\n",
+                status, count
+            ));
         }
 
         // Bucket metrics
         output.push_str("\n# HELP http_requests_by_bucket_total HTTP requests by S3 bucket\n");
         output.push_str("# TYPE http_requests_by_bucket_total counter\n");
-        if let Ok(counts) = self.bucket_counts.lock() {
-            for (bucket, count) in counts.iter() {
-                output.push_str(&format!(
-                    "http_requests_by_bucket_total{{bucket=\"{}\"}} {}\n",
-                    bucket, count
-                ));
-            }
+        for (bucket, count) in self.bucket_counts.snapshot().iter() {
+            output.push_str(&format!(
+                "http_requests_by_bucket_total{{bucket=\"{}\"}} {}\n",
+                bucket, count
+            ));
         }
 
         // HTTP method metrics
         output.push_str("\n# HELP http_requests_by_method_total HTTP requests by method\n");
         output.push_str("# TYPE http_requests_by_method_total counter\n");
-        if let Ok(counts) = self.method_counts.lock() {
-            for (method, count) in counts.iter() {
-                output.push_str(&format!(
-                    "http_requests_by_method_total{{method=\"{}\"}} {}\n",
-                    method, count
-                ));
-            }
+        for (method, count) in self.method_counts.snapshot().iter() {
+            output.push_str(&format!(
+                "http_requests_by_method_total{{method=\"{}\"}} {}\n",
+                method, count
+            ));
         }
 
         // Authentication metrics
@@ -1281,25 +1979,21 @@ impl Metrics {
         // S3 operation metrics
         output.push_str("\n# HELP s3_operations_total S3 operations by type\n");
         output.push_str("# TYPE s3_operations_total counter\n");
-        if let Ok(ops) = self.s3_operations.lock() {
-            for (operation, count) in ops.iter() {
-                output.push_str(&format!(
-                    "s3_operations_total{{operation=\"{}\"}} {}\n",
-                    operation, count
-                ));
-            }
+        for (operation, count) in self.s3_operations.snapshot().iter() {
+            output.push_str(&format!(
+                "s3_operations_total{{operation=\"{}\"}} {}\n",
+                operation, count
+            ));
         }
 
         // S3 error metrics
         output.push_str("\n# HELP s3_errors_total S3 errors by error code\n");
         output.push_str("# TYPE s3_errors_total counter\n");
-        if let Ok(errors) = self.s3_errors.lock() {
-            for (error_code, count) in errors.iter() {
-                output.push_str(&format!(
-                    "s3_errors_total{{error_code=\"{}\"}} {}\n",
-                    error_code, count
-                ));
-            }
+        for (error_code, count) in self.s3_errors.snapshot().iter() {
+            output.push_str(&format!(
+                "s3_errors_total{{error_code=\"{}\"}} {}\n",
+                error_code, count
+            ));
         }
 
         // System metrics
@@ -1362,6 +2056,15 @@ impl Metrics {
             self.config_generation.load(Ordering::Relaxed)
         ));
 
+        output.push_str(
+            "\n# HELP config_retired_generation_requests_total Requests completed on a config generation replaced by a reload\n",
+        );
+        output.push_str("# TYPE config_retired_generation_requests_total counter\n");
+        output.push_str(&format!(
+            "config_retired_generation_requests_total {}\n",
+            self.retired_generation_requests.load(Ordering::Relaxed)
+        ));
+
         output.push_str(
             "\n# HELP concurrency_limit_rejections_total Requests rejected due to concurrency limit (503)\n",
         );
@@ -1374,8 +2077,7 @@ impl Metrics {
         // Rate limiting metrics
         output.push_str("\n# HELP rate_limit_exceeded_total Requests rejected due to rate limit (429) per bucket\n");
         output.push_str("# TYPE rate_limit_exceeded_total counter\n");
-        let rate_limit_exceeded = self.rate_limit_exceeded.lock().unwrap();
-        for (bucket, count) in rate_limit_exceeded.iter() {
+        for (bucket, count) in self.rate_limit_exceeded.snapshot().iter() {
             output.push_str(&format!(
                 "rate_limit_exceeded_total{{bucket=\"{}\"}} {}\n",
                 bucket, count
@@ -1385,8 +2087,7 @@ impl Metrics {
         // Retry metrics
         output.push_str("\n# HELP s3_retry_attempts_total Total retry attempts per bucket\n");
         output.push_str("# TYPE s3_retry_attempts_total counter\n");
-        let s3_retry_attempts = self.s3_retry_attempts.lock().unwrap();
-        for (bucket, count) in s3_retry_attempts.iter() {
+        for (bucket, count) in self.s3_retry_attempts.snapshot().iter() {
             output.push_str(&format!(
                 "s3_retry_attempts_total{{bucket=\"{}\"}} {}\n",
                 bucket, count
@@ -1395,8 +2096,7 @@ impl Metrics {
 
         output.push_str("\n# HELP s3_retry_success_total Successful retries per bucket (eventually succeeded)\n");
         output.push_str("# TYPE s3_retry_success_total counter\n");
-        let s3_retry_success = self.s3_retry_success.lock().unwrap();
-        for (bucket, count) in s3_retry_success.iter() {
+        for (bucket, count) in self.s3_retry_success.snapshot().iter() {
             output.push_str(&format!(
                 "s3_retry_success_total{{bucket=\"{}\"}} {}\n",
                 bucket, count
@@ -1405,8 +2105,7 @@ impl Metrics {
 
         output.push_str("\n# HELP s3_retry_exhausted_total Retries exhausted per bucket (all attempts failed)\n");
         output.push_str("# TYPE s3_retry_exhausted_total counter\n");
-        let s3_retry_exhausted = self.s3_retry_exhausted.lock().unwrap();
-        for (bucket, count) in s3_retry_exhausted.iter() {
+        for (bucket, count) in self.s3_retry_exhausted.snapshot().iter() {
             output.push_str(&format!(
                 "s3_retry_exhausted_total{{bucket=\"{}\"}} {}\n",
                 bucket, count
@@ -1489,18 +2188,16 @@ impl Metrics {
 
         // Replica request counts
         output.push_str(
-            "\n# HELP http_requests_by_replica_total HTTP requests per replica within bucket\n",
-        );
-        output.push_str("# TYPE http_requests_by_replica_total counter\n");
-        if let Ok(counts) = self.replica_request_counts.lock() {
-            for (key, count) in counts.iter() {
-                // key format: "bucket:replica"
-                if let Some((bucket, replica)) = key.split_once(':') {
-                    output.push_str(&format!(
-                        "http_requests_by_replica_total{{bucket=\"{}\",replica=\"{}\"}} {}\n",
-                        bucket, replica, count
-                    ));
-                }
+            "\n# HELP http_requests_by_replica_total HTTP requests per replica within bucket\n",
+        );
+        output.push_str("# TYPE http_requests_by_replica_total counter\n");
+        for (key, count) in self.replica_request_counts.snapshot().iter() {
+            // key format: "bucket:replica"
+            if let Some((bucket, replica)) = key.split_once(':') {
+                output.push_str(&format!(
+                    "http_requests_by_replica_total{{bucket=\"{}\",replica=\"{}\"}} {}\n",
+                    bucket, replica, count
+                ));
             }
         }
 
@@ -1509,15 +2206,13 @@ impl Metrics {
             "\n# HELP http_errors_by_replica_total HTTP errors per replica within bucket\n",
         );
         output.push_str("# TYPE http_errors_by_replica_total counter\n");
-        if let Ok(counts) = self.replica_error_counts.lock() {
-            for (key, count) in counts.iter() {
-                // key format: "bucket:replica"
-                if let Some((bucket, replica)) = key.split_once(':') {
-                    output.push_str(&format!(
-                        "http_errors_by_replica_total{{bucket=\"{}\",replica=\"{}\"}} {}\n",
-                        bucket, replica, count
-                    ));
-                }
+        for (key, count) in self.replica_error_counts.snapshot().iter() {
+            // key format: "bucket:replica"
+            if let Some((bucket, replica)) = key.split_once(':') {
+                output.push_str(&format!(
+                    "http_errors_by_replica_total{{bucket=\"{}\",replica=\"{}\"}} {}\n",
+                    bucket, replica, count
+                ));
             }
         }
 
@@ -1554,19 +2249,17 @@ impl Metrics {
         // Replica failover counters
         output.push_str("\n# HELP replica_failovers_total Replica failover events (from → to)\n");
         output.push_str("# TYPE replica_failovers_total counter\n");
-        if let Ok(failovers) = self.replica_failovers.lock() {
-            for (key, count) in failovers.iter() {
-                // key format: "bucket:from:to"
-                let parts: Vec<&str> = key.split(':').collect();
-                if parts.len() == 3 {
-                    let bucket = parts[0];
-                    let from = parts[1];
-                    let to = parts[2];
-                    output.push_str(&format!(
-                        "replica_failovers_total{{bucket=\"{}\",from=\"{}\",to=\"{}\"}} {}\n",
-                        bucket, from, to, count
-                    ));
-                }
+        for (key, count) in self.replica_failovers.snapshot().iter() {
+            // key format: "bucket:from:to"
+            let parts: Vec<&str> = key.split(':').collect();
+            if parts.len() == 3 {
+                let bucket = parts[0];
+                let from = parts[1];
+                let to = parts[2];
+                output.push_str(&format!(
+                    "replica_failovers_total{{bucket=\"{}\",from=\"{}\",to=\"{}\"}} {}\n",
+                    bucket, from, to, count
+                ));
             }
         }
 
@@ -1621,15 +2314,13 @@ impl Metrics {
             "\n# HELP yatagarasu_cache_hits_by_bucket_layer Cache hits by bucket and layer\n",
         );
         output.push_str("# TYPE yatagarasu_cache_hits_by_bucket_layer counter\n");
-        if let Ok(hits) = self.cache_hits_by_bucket_layer.lock() {
-            for (key, count) in hits.iter() {
-                // key format: "bucket:layer"
-                if let Some((bucket, layer)) = key.split_once(':') {
-                    output.push_str(&format!(
-                        "yatagarasu_cache_hits_by_bucket_layer{{bucket=\"{}\",layer=\"{}\"}} {}\n",
-                        bucket, layer, count
-                    ));
-                }
+        for (key, count) in self.cache_hits_by_bucket_layer.snapshot().iter() {
+            // key format: "bucket:layer"
+            if let Some((bucket, layer)) = key.split_once(':') {
+                output.push_str(&format!(
+                    "yatagarasu_cache_hits_by_bucket_layer{{bucket=\"{}\",layer=\"{}\"}} {}\n",
+                    bucket, layer, count
+                ));
             }
         }
 
@@ -1637,27 +2328,23 @@ impl Metrics {
             "\n# HELP yatagarasu_cache_misses_by_bucket_layer Cache misses by bucket and layer\n",
         );
         output.push_str("# TYPE yatagarasu_cache_misses_by_bucket_layer counter\n");
-        if let Ok(misses) = self.cache_misses_by_bucket_layer.lock() {
-            for (key, count) in misses.iter() {
-                // key format: "bucket:layer"
-                if let Some((bucket, layer)) = key.split_once(':') {
-                    output.push_str(&format!(
-                        "yatagarasu_cache_misses_by_bucket_layer{{bucket=\"{}\",layer=\"{}\"}} {}\n",
-                        bucket, layer, count
-                    ));
-                }
+        for (key, count) in self.cache_misses_by_bucket_layer.snapshot().iter() {
+            // key format: "bucket:layer"
+            if let Some((bucket, layer)) = key.split_once(':') {
+                output.push_str(&format!(
+                    "yatagarasu_cache_misses_by_bucket_layer{{bucket=\"{}\",layer=\"{}\"}} {}\n",
+                    bucket, layer, count
+                ));
             }
         }
 
         output.push_str("\n# HELP yatagarasu_cache_evictions_by_layer Cache evictions by layer\n");
         output.push_str("# TYPE yatagarasu_cache_evictions_by_layer counter\n");
-        if let Ok(evictions) = self.cache_evictions_by_layer.lock() {
-            for (layer, count) in evictions.iter() {
-                output.push_str(&format!(
-                    "yatagarasu_cache_evictions_by_layer{{layer=\"{}\"}} {}\n",
-                    layer, count
-                ));
-            }
+        for (layer, count) in self.cache_evictions_by_layer.snapshot().iter() {
+            output.push_str(&format!(
+                "yatagarasu_cache_evictions_by_layer{{layer=\"{}\"}} {}\n",
+                layer, count
+            ));
         }
 
         output.push_str("\n# HELP yatagarasu_cache_size_by_layer Cache size in bytes by layer\n");
@@ -1682,6 +2369,42 @@ impl Metrics {
             }
         }
 
+        output.push_str(
+            "\n# HELP yatagarasu_cache_layer_errors_total Cache layer errors counted toward quarantine\n",
+        );
+        output.push_str("# TYPE yatagarasu_cache_layer_errors_total counter\n");
+        for (layer, count) in self.cache_layer_errors.snapshot().iter() {
+            output.push_str(&format!(
+                "yatagarasu_cache_layer_errors_total{{layer=\"{}\"}} {}\n",
+                layer, count
+            ));
+        }
+
+        output.push_str(
+            "\n# HELP yatagarasu_cache_layer_quarantines_total Times a cache layer was quarantined\n",
+        );
+        output.push_str("# TYPE yatagarasu_cache_layer_quarantines_total counter\n");
+        for (layer, count) in self.cache_layer_quarantines.snapshot().iter() {
+            output.push_str(&format!(
+                "yatagarasu_cache_layer_quarantines_total{{layer=\"{}\"}} {}\n",
+                layer, count
+            ));
+        }
+
+        output.push_str(
+            "\n# HELP yatagarasu_cache_layer_quarantined Cache layer quarantine state (1=skipped, 0=active)\n",
+        );
+        output.push_str("# TYPE yatagarasu_cache_layer_quarantined gauge\n");
+        if let Ok(states) = self.cache_layer_quarantined.lock() {
+            for (layer, quarantined) in states.iter() {
+                output.push_str(&format!(
+                    "yatagarasu_cache_layer_quarantined{{layer=\"{}\"}} {}\n",
+                    layer,
+                    if *quarantined { 1 } else { 0 }
+                ));
+            }
+        }
+
         // Phase v1.4: sendfile metrics
         output.push_str(
             "\n# HELP yatagarasu_cache_sendfile_total Total sendfile-eligible cache hits\n",
@@ -1736,138 +2459,674 @@ impl Metrics {
             if !durations.is_empty() {
                 let histogram = calculate_histogram(&durations);
                 output.push_str(&format!(
-                    "yatagarasu_prewarm_duration_seconds{{quantile=\"0.5\"}} {:.3}\n",
-                    histogram.p50
+                    "yatagarasu_prewarm_duration_seconds{{quantile=\"0.5\"}} {:.3}\n",
+                    histogram.p50
+                ));
+                output.push_str(&format!(
+                    "yatagarasu_prewarm_duration_seconds{{quantile=\"0.95\"}} {:.3}\n",
+                    histogram.p95
+                ));
+                output.push_str(&format!(
+                    "yatagarasu_prewarm_duration_seconds{{quantile=\"0.99\"}} {:.3}\n",
+                    histogram.p99
+                ));
+            }
+        }
+
+        // Phase 50.7: Image optimization metrics
+        output.push_str(
+            "\n# HELP yatagarasu_image_processing_total Total image processing operations\n",
+        );
+        output.push_str("# TYPE yatagarasu_image_processing_total counter\n");
+        output.push_str(&format!(
+            "yatagarasu_image_processing_total {}\n",
+            self.image_processing_total.load(Ordering::Relaxed)
+        ));
+
+        output.push_str(
+            "\n# HELP yatagarasu_image_processing_errors_total Total image processing errors\n",
+        );
+        output.push_str("# TYPE yatagarasu_image_processing_errors_total counter\n");
+        output.push_str(&format!(
+            "yatagarasu_image_processing_errors_total {}\n",
+            self.image_processing_errors.load(Ordering::Relaxed)
+        ));
+
+        output.push_str(
+            "\n# HELP yatagarasu_image_bytes_saved_total Total bytes saved by image optimization\n",
+        );
+        output.push_str("# TYPE yatagarasu_image_bytes_saved_total counter\n");
+        output.push_str(&format!(
+            "yatagarasu_image_bytes_saved_total {}\n",
+            self.image_bytes_saved.load(Ordering::Relaxed)
+        ));
+
+        output.push_str(
+            "\n# HELP yatagarasu_image_bytes_original_total Total original image bytes processed\n",
+        );
+        output.push_str("# TYPE yatagarasu_image_bytes_original_total counter\n");
+        output.push_str(&format!(
+            "yatagarasu_image_bytes_original_total {}\n",
+            self.image_bytes_original.load(Ordering::Relaxed)
+        ));
+
+        output.push_str(
+            "\n# HELP yatagarasu_image_bytes_processed_total Total processed image bytes\n",
+        );
+        output.push_str("# TYPE yatagarasu_image_bytes_processed_total counter\n");
+        output.push_str(&format!(
+            "yatagarasu_image_bytes_processed_total {}\n",
+            self.image_bytes_processed.load(Ordering::Relaxed)
+        ));
+
+        output.push_str("\n# HELP yatagarasu_image_cache_hits_total Image variant cache hits\n");
+        output.push_str("# TYPE yatagarasu_image_cache_hits_total counter\n");
+        output.push_str(&format!(
+            "yatagarasu_image_cache_hits_total {}\n",
+            self.image_cache_hits.load(Ordering::Relaxed)
+        ));
+
+        output
+            .push_str("\n# HELP yatagarasu_image_cache_misses_total Image variant cache misses\n");
+        output.push_str("# TYPE yatagarasu_image_cache_misses_total counter\n");
+        output.push_str(&format!(
+            "yatagarasu_image_cache_misses_total {}\n",
+            self.image_cache_misses.load(Ordering::Relaxed)
+        ));
+
+        output.push_str(
+            "\n# HELP yatagarasu_image_processing_duration_us Image processing duration\n",
+        );
+        output.push_str("# TYPE yatagarasu_image_processing_duration_us summary\n");
+        if let Ok(durations) = self.image_processing_durations.lock() {
+            if !durations.is_empty() {
+                let histogram = calculate_histogram(&durations);
+                output.push_str(&format!(
+                    "yatagarasu_image_processing_duration_us{{quantile=\"0.5\"}} {:.3}\n",
+                    histogram.p50
+                ));
+                output.push_str(&format!(
+                    "yatagarasu_image_processing_duration_us{{quantile=\"0.95\"}} {:.3}\n",
+                    histogram.p95
+                ));
+                output.push_str(&format!(
+                    "yatagarasu_image_processing_duration_us{{quantile=\"0.99\"}} {:.3}\n",
+                    histogram.p99
+                ));
+            }
+        }
+
+        output.push_str(
+            "\n# HELP yatagarasu_image_transformations_total Image transformations by type\n",
+        );
+        output.push_str("# TYPE yatagarasu_image_transformations_total counter\n");
+        for (transform_type, count) in self.image_transformations.snapshot().iter() {
+            output.push_str(&format!(
+                "yatagarasu_image_transformations_total{{type=\"{}\"}} {}\n",
+                transform_type, count
+            ));
+        }
+
+        output.push_str("\n# HELP yatagarasu_image_formats_total Image output formats\n");
+        output.push_str("# TYPE yatagarasu_image_formats_total counter\n");
+        for (format, count) in self.image_formats.snapshot().iter() {
+            output.push_str(&format!(
+                "yatagarasu_image_formats_total{{format=\"{}\"}} {}\n",
+                format, count
+            ));
+        }
+
+        output.push_str("\n# HELP yatagarasu_image_errors_by_type_total Image errors by type\n");
+        output.push_str("# TYPE yatagarasu_image_errors_by_type_total counter\n");
+        for (error_type, count) in self.image_errors_by_type.snapshot().iter() {
+            output.push_str(&format!(
+                "yatagarasu_image_errors_by_type_total{{type=\"{}\"}} {}\n",
+                error_type, count
+            ));
+        }
+
+        // Runtime internals: listener accepts and upstream connection pool
+        output.push_str(
+            "\n# HELP yatagarasu_downstream_connections_accepted_total Downstream connections accepted by the listener, counted on their first request\n",
+        );
+        output.push_str("# TYPE yatagarasu_downstream_connections_accepted_total counter\n");
+        output.push_str(&format!(
+            "yatagarasu_downstream_connections_accepted_total {}\n",
+            self.downstream_connections_accepted.load(Ordering::Relaxed)
+        ));
+
+        output.push_str(
+            "\n# HELP yatagarasu_downstream_requests_reused_total Requests served on kept-alive downstream connections\n",
+        );
+        output.push_str("# TYPE yatagarasu_downstream_requests_reused_total counter\n");
+        output.push_str(&format!(
+            "yatagarasu_downstream_requests_reused_total {}\n",
+            self.downstream_requests_reused.load(Ordering::Relaxed)
+        ));
+
+        output.push_str(
+            "\n# HELP yatagarasu_upstream_connections_total Upstream connections by pool outcome\n",
+        );
+        output.push_str("# TYPE yatagarasu_upstream_connections_total counter\n");
+        output.push_str(&format!(
+            "yatagarasu_upstream_connections_total{{pool=\"new\"}} {}\n",
+            self.upstream_connections_new.load(Ordering::Relaxed)
+        ));
+        output.push_str(&format!(
+            "yatagarasu_upstream_connections_total{{pool=\"reused\"}} {}\n",
+            self.upstream_connections_reused.load(Ordering::Relaxed)
+        ));
+
+        // Client disconnects
+        output.push_str(
+            "\n# HELP yatagarasu_client_aborted_transfers_total Transfers aborted by the client mid-download\n",
+        );
+        output.push_str("# TYPE yatagarasu_client_aborted_transfers_total counter\n");
+        output.push_str(&format!(
+            "yatagarasu_client_aborted_transfers_total{{outcome=\"cancelled\"}} {}\n",
+            self.client_aborts_cancelled.load(Ordering::Relaxed)
+        ));
+        output.push_str(&format!(
+            "yatagarasu_client_aborted_transfers_total{{outcome=\"cache_fill\"}} {}\n",
+            self.client_aborts_cache_fill.load(Ordering::Relaxed)
+        ));
+
+        output.push_str(
+            "\n# HELP yatagarasu_client_abort_bytes_saved_total Upstream bytes not fetched after client aborts\n",
+        );
+        output.push_str("# TYPE yatagarasu_client_abort_bytes_saved_total counter\n");
+        output.push_str(&format!(
+            "yatagarasu_client_abort_bytes_saved_total {}\n",
+            self.client_abort_bytes_saved.load(Ordering::Relaxed)
+        ));
+
+        // Hotlink protection
+        output.push_str(
+            "\n# HELP yatagarasu_hotlink_requests_total Requests failing the hotlink check by bucket and action\n",
+        );
+        output.push_str("# TYPE yatagarasu_hotlink_requests_total counter\n");
+        for (key, count) in self.hotlink_requests.snapshot().iter() {
+            // key format: "bucket:action"
+            if let Some((bucket, action)) = key.split_once(':') {
+                output.push_str(&format!(
+                    "yatagarasu_hotlink_requests_total{{bucket=\"{}\",action=\"{}\"}} {}\n",
+                    bucket, action, count
+                ));
+            }
+        }
+
+        // Automatic IP bans
+        output.push_str("\n# HELP yatagarasu_ip_bans_total IP bans issued by reason\n");
+        output.push_str("# TYPE yatagarasu_ip_bans_total counter\n");
+        for (reason, count) in self.ip_bans.snapshot().iter() {
+            output.push_str(&format!(
+                "yatagarasu_ip_bans_total{{reason=\"{}\"}} {}\n",
+                reason, count
+            ));
+        }
+        output.push_str(
+            "\n# HELP yatagarasu_ip_ban_rejections_total Requests rejected because the client IP is banned\n",
+        );
+        output.push_str("# TYPE yatagarasu_ip_ban_rejections_total counter\n");
+        output.push_str(&format!(
+            "yatagarasu_ip_ban_rejections_total {}\n",
+            self.ip_ban_rejections.load(Ordering::Relaxed)
+        ));
+
+        // Request priority
+        output.push_str(
+            "\n# HELP yatagarasu_priority_admissions_total High-priority requests admitted via reserved permits or load shedding bypass\n",
+        );
+        output.push_str("# TYPE yatagarasu_priority_admissions_total counter\n");
+        for (reason, count) in self.priority_admissions.snapshot().iter() {
+            output.push_str(&format!(
+                "yatagarasu_priority_admissions_total{{reason=\"{}\"}} {}\n",
+                reason, count
+            ));
+        }
+
+        // Cache override header
+        output.push_str(
+            "\n# HELP yatagarasu_cache_overrides_total Requests carrying X-Yat-Cache by mode (untrusted = ignored)\n",
+        );
+        output.push_str("# TYPE yatagarasu_cache_overrides_total counter\n");
+        for (mode, count) in self.cache_overrides.snapshot().iter() {
+            output.push_str(&format!(
+                "yatagarasu_cache_overrides_total{{mode=\"{}\"}} {}\n",
+                mode, count
+            ));
+        }
+
+        // Maintenance mode
+        output.push_str(
+            "\n# HELP yatagarasu_maintenance_mode Whether maintenance mode is on (1) or off (0)\n",
+        );
+        output.push_str("# TYPE yatagarasu_maintenance_mode gauge\n");
+        output.push_str(&format!(
+            "yatagarasu_maintenance_mode {}\n",
+            self.maintenance_mode.load(Ordering::Relaxed)
+        ));
+        output.push_str(
+            "\n# HELP yatagarasu_maintenance_rejections_total Requests answered with 503 during maintenance\n",
+        );
+        output.push_str("# TYPE yatagarasu_maintenance_rejections_total counter\n");
+        output.push_str(&format!(
+            "yatagarasu_maintenance_rejections_total {}\n",
+            self.maintenance_rejections.load(Ordering::Relaxed)
+        ));
+
+        // Health/metrics endpoint access
+        output.push_str(
+            "\n# HELP yatagarasu_endpoint_access_denied_total Health and metrics requests denied by endpoint access rules\n",
+        );
+        output.push_str("# TYPE yatagarasu_endpoint_access_denied_total counter\n");
+        for (key, count) in self.endpoint_access_denials.snapshot().iter() {
+            // key format: "endpoint:reason"
+            if let Some((endpoint, reason)) = key.split_once(':') {
+                output.push_str(&format!(
+                    "yatagarasu_endpoint_access_denied_total{{endpoint=\"{}\",reason=\"{}\"}} {}\n",
+                    endpoint, reason, count
+                ));
+            }
+        }
+
+        // Degraded mode (origins down, cache only)
+        output.push_str(
+            "\n# HELP yatagarasu_degraded_responses_total Requests answered from cache only while all origins were down\n",
+        );
+        output.push_str("# TYPE yatagarasu_degraded_responses_total counter\n");
+        for (key, count) in self.degraded_responses.snapshot().iter() {
+            // key format: "bucket:outcome"
+            if let Some((bucket, outcome)) = key.split_once(':') {
+                output.push_str(&format!(
+                    "yatagarasu_degraded_responses_total{{bucket=\"{}\",outcome=\"{}\"}} {}\n",
+                    bucket, outcome, count
+                ));
+            }
+        }
+
+        // Background origin probes
+        output.push_str(
+            "\n# HELP yatagarasu_origin_probes_total Synthetic HEAD probes sent to open circuit breakers\n",
+        );
+        output.push_str("# TYPE yatagarasu_origin_probes_total counter\n");
+        for (key, count) in self.origin_probes.snapshot().iter() {
+            // key format: "target:result" (results never contain ':')
+            if let Some((target, result)) = key.rsplit_once(':') {
+                output.push_str(&format!(
+                    "yatagarasu_origin_probes_total{{target=\"{}\",result=\"{}\"}} {}\n",
+                    target, result, count
+                ));
+            }
+        }
+
+        // HEAD-before-GET freshness checks
+        output.push_str(
+            "\n# HELP yatagarasu_cache_freshness_checks_total HEAD requests confirming cache hits past their soft TTL\n",
+        );
+        output.push_str("# TYPE yatagarasu_cache_freshness_checks_total counter\n");
+        for (key, count) in self.freshness_checks.snapshot().iter() {
+            // key format: "bucket:result" (results never contain ':')
+            if let Some((bucket, result)) = key.rsplit_once(':') {
+                output.push_str(&format!(
+                    "yatagarasu_cache_freshness_checks_total{{bucket=\"{}\",result=\"{}\"}} {}\n",
+                    bucket, result, count
                 ));
+            }
+        }
+
+        // Circuit breaker failures by classification
+        output.push_str(
+            "\n# HELP yatagarasu_circuit_breaker_failures_total Origin failures recorded by bucket circuit breakers\n",
+        );
+        output.push_str("# TYPE yatagarasu_circuit_breaker_failures_total counter\n");
+        for (key, count) in self.circuit_breaker_failures.snapshot().iter() {
+            // key format: "bucket:kind"
+            if let Some((bucket, kind)) = key.split_once(':') {
                 output.push_str(&format!(
-                    "yatagarasu_prewarm_duration_seconds{{quantile=\"0.95\"}} {:.3}\n",
-                    histogram.p95
+                    "yatagarasu_circuit_breaker_failures_total{{bucket=\"{}\",kind=\"{}\"}} {}\n",
+                    bucket, kind, count
                 ));
+            }
+        }
+
+        // Bulkhead rejections
+        output.push_str(
+            "\n# HELP yatagarasu_bulkhead_rejections_total Requests rejected because the bucket's bulkhead was full\n",
+        );
+        output.push_str("# TYPE yatagarasu_bulkhead_rejections_total counter\n");
+        for (bucket, count) in self.bulkhead_rejections.snapshot().iter() {
+            output.push_str(&format!(
+                "yatagarasu_bulkhead_rejections_total{{bucket=\"{}\"}} {}\n",
+                bucket, count
+            ));
+        }
+
+        // Deprecated routes
+        output.push_str(
+            "\n# HELP yatagarasu_deprecated_route_requests_total Requests to buckets carrying a deprecation notice\n",
+        );
+        output.push_str("# TYPE yatagarasu_deprecated_route_requests_total counter\n");
+        for (bucket, count) in self.deprecated_route_requests.snapshot().iter() {
+            output.push_str(&format!(
+                "yatagarasu_deprecated_route_requests_total{{bucket=\"{}\"}} {}\n",
+                bucket, count
+            ));
+        }
+
+        // Requests per bucket alias / route version
+        output.push_str(
+            "\n# HELP yatagarasu_route_entry_requests_total Requests by bucket, path prefix (alias) and route version\n",
+        );
+        output.push_str("# TYPE yatagarasu_route_entry_requests_total counter\n");
+        for (key, count) in self.route_entry_requests.snapshot().iter() {
+            // key format: "bucket:version:prefix"
+            let mut parts = key.splitn(3, ':');
+            if let (Some(bucket), Some(version), Some(prefix)) =
+                (parts.next(), parts.next(), parts.next())
+            {
                 output.push_str(&format!(
-                    "yatagarasu_prewarm_duration_seconds{{quantile=\"0.99\"}} {:.3}\n",
-                    histogram.p99
+                    "yatagarasu_route_entry_requests_total{{bucket=\"{}\",prefix=\"{}\",version=\"{}\"}} {}\n",
+                    bucket, prefix, version, count
                 ));
             }
         }
 
-        // Phase 50.7: Image optimization metrics
+        // Oversized header values
         output.push_str(
-            "\n# HELP yatagarasu_image_processing_total Total image processing operations\n",
+            "\n# HELP yatagarasu_oversized_headers_total Requests rejected for an oversized header value (431) by header\n",
         );
-        output.push_str("# TYPE yatagarasu_image_processing_total counter\n");
-        output.push_str(&format!(
-            "yatagarasu_image_processing_total {}\n",
-            self.image_processing_total.load(Ordering::Relaxed)
-        ));
+        output.push_str("# TYPE yatagarasu_oversized_headers_total counter\n");
+        for (header, count) in self.oversized_headers.snapshot().iter() {
+            output.push_str(&format!(
+                "yatagarasu_oversized_headers_total{{header=\"{}\"}} {}\n",
+                header, count
+            ));
+        }
 
+        // Request queue outcomes
         output.push_str(
-            "\n# HELP yatagarasu_image_processing_errors_total Total image processing errors\n",
+            "\n# HELP yatagarasu_request_queue_total Requests that waited for a concurrency permit by outcome\n",
+        );
+        output.push_str("# TYPE yatagarasu_request_queue_total counter\n");
+        for (outcome, count) in self.request_queue_outcomes.snapshot().iter() {
+            output.push_str(&format!(
+                "yatagarasu_request_queue_total{{outcome=\"{}\"}} {}\n",
+                outcome, count
+            ));
+        }
+
+        // Per-bucket size distributions
+        self.request_path_lengths.export(
+            &mut output,
+            "yatagarasu_request_path_length_bytes",
+            "bucket",
+            "Request path length by bucket",
+        );
+        self.response_sizes.export(
+            &mut output,
+            "yatagarasu_response_size_bytes",
+            "bucket",
+            "Response body bytes sent by bucket",
+        );
+        self.range_spans.export(
+            &mut output,
+            "yatagarasu_range_span_bytes",
+            "bucket",
+            "Bytes covered by each served range (206) by bucket",
         );
-        output.push_str("# TYPE yatagarasu_image_processing_errors_total counter\n");
-        output.push_str(&format!(
-            "yatagarasu_image_processing_errors_total {}\n",
-            self.image_processing_errors.load(Ordering::Relaxed)
-        ));
 
+        // Upload virus scans
         output.push_str(
-            "\n# HELP yatagarasu_image_bytes_saved_total Total bytes saved by image optimization\n",
+            "\n# HELP yatagarasu_upload_scans_total Upload bodies scanned before reaching S3 by result\n",
+        );
+        output.push_str("# TYPE yatagarasu_upload_scans_total counter\n");
+        for (key, count) in self.upload_scans.snapshot().iter() {
+            // key format: "bucket:result" (results never contain ':')
+            if let Some((bucket, result)) = key.rsplit_once(':') {
+                output.push_str(&format!(
+                    "yatagarasu_upload_scans_total{{bucket=\"{}\",result=\"{}\"}} {}\n",
+                    bucket, result, count
+                ));
+            }
+        }
+        self.upload_scan_durations.export(
+            &mut output,
+            "yatagarasu_upload_scan_duration_ms",
+            "bucket",
+            "Upload scan latency in milliseconds by bucket",
         );
-        output.push_str("# TYPE yatagarasu_image_bytes_saved_total counter\n");
-        output.push_str(&format!(
-            "yatagarasu_image_bytes_saved_total {}\n",
-            self.image_bytes_saved.load(Ordering::Relaxed)
-        ));
 
+        // TLS handshakes
         output.push_str(
-            "\n# HELP yatagarasu_image_bytes_original_total Total original image bytes processed\n",
+            "\n# HELP yatagarasu_tls_handshakes_total Completed TLS handshakes on the HTTPS listener by session\n",
+        );
+        output.push_str("# TYPE yatagarasu_tls_handshakes_total counter\n");
+        for (session, count) in self.tls_handshakes.snapshot().iter() {
+            output.push_str(&format!(
+                "yatagarasu_tls_handshakes_total{{session=\"{}\"}} {}\n",
+                session, count
+            ));
+        }
+        self.tls_handshake_durations.export(
+            &mut output,
+            "yatagarasu_tls_handshake_duration_ms",
+            "session",
+            "TLS handshake latency in milliseconds from certificate selection",
         );
-        output.push_str("# TYPE yatagarasu_image_bytes_original_total counter\n");
-        output.push_str(&format!(
-            "yatagarasu_image_bytes_original_total {}\n",
-            self.image_bytes_original.load(Ordering::Relaxed)
-        ));
 
+        // Verified-token cache
         output.push_str(
-            "\n# HELP yatagarasu_image_bytes_processed_total Total processed image bytes\n",
+            "\n# HELP yatagarasu_jwt_token_cache_lookups_total Verified-token cache lookups by result\n",
         );
-        output.push_str("# TYPE yatagarasu_image_bytes_processed_total counter\n");
-        output.push_str(&format!(
-            "yatagarasu_image_bytes_processed_total {}\n",
-            self.image_bytes_processed.load(Ordering::Relaxed)
-        ));
+        output.push_str("# TYPE yatagarasu_jwt_token_cache_lookups_total counter\n");
+        for (result, count) in self.jwt_token_cache.snapshot().iter() {
+            output.push_str(&format!(
+                "yatagarasu_jwt_token_cache_lookups_total{{result=\"{}\"}} {}\n",
+                result, count
+            ));
+        }
 
-        output.push_str("\n# HELP yatagarasu_image_cache_hits_total Image variant cache hits\n");
-        output.push_str("# TYPE yatagarasu_image_cache_hits_total counter\n");
+        // Cache admission
+        output.push_str(
+            "\n# HELP yatagarasu_cache_population_skipped_total Responses not stored in the cache by reason\n",
+        );
+        output.push_str("# TYPE yatagarasu_cache_population_skipped_total counter\n");
+        for (reason, count) in self.cache_population_skips.snapshot().iter() {
+            output.push_str(&format!(
+                "yatagarasu_cache_population_skipped_total{{reason=\"{}\"}} {}\n",
+                reason, count
+            ));
+        }
+        output.push_str(
+            "\n# HELP yatagarasu_cache_range_total Range requests served from cached chunks (hit/miss) and chunks stored\n",
+        );
+        output.push_str("# TYPE yatagarasu_cache_range_total counter\n");
+        for (result, count) in self.cache_range_chunks.snapshot().iter() {
+            output.push_str(&format!(
+                "yatagarasu_cache_range_total{{result=\"{}\"}} {}\n",
+                result, count
+            ));
+        }
+        output.push_str(
+            "\n# HELP yatagarasu_response_buffer_budget_exceeded_total Responses served uncached because the response buffer budget was spent\n",
+        );
+        output.push_str("# TYPE yatagarasu_response_buffer_budget_exceeded_total counter\n");
         output.push_str(&format!(
-            "yatagarasu_image_cache_hits_total {}\n",
-            self.image_cache_hits.load(Ordering::Relaxed)
+            "yatagarasu_response_buffer_budget_exceeded_total {}\n",
+            self.response_buffer_budget_exceeded.load(Ordering::Relaxed)
         ));
 
-        output
-            .push_str("\n# HELP yatagarasu_image_cache_misses_total Image variant cache misses\n");
-        output.push_str("# TYPE yatagarasu_image_cache_misses_total counter\n");
-        output.push_str(&format!(
-            "yatagarasu_image_cache_misses_total {}\n",
-            self.image_cache_misses.load(Ordering::Relaxed)
-        ));
+        // Signed URLs
+        output.push_str(
+            "\n# HELP yatagarasu_signed_url_requests_total Signed URL checks by bucket and result\n",
+        );
+        output.push_str("# TYPE yatagarasu_signed_url_requests_total counter\n");
+        for (key, count) in self.signed_url_requests.snapshot().iter() {
+            // key format: "bucket:result" (results never contain ':')
+            if let Some((bucket, result)) = key.rsplit_once(':') {
+                output.push_str(&format!(
+                    "yatagarasu_signed_url_requests_total{{bucket=\"{}\",result=\"{}\"}} {}\n",
+                    bucket, result, count
+                ));
+            }
+        }
 
+        // Incoming SigV4
         output.push_str(
-            "\n# HELP yatagarasu_image_processing_duration_us Image processing duration\n",
+            "\n# HELP yatagarasu_sigv4_requests_total Incoming SigV4 checks by bucket and result\n",
         );
-        output.push_str("# TYPE yatagarasu_image_processing_duration_us summary\n");
-        if let Ok(durations) = self.image_processing_durations.lock() {
-            if !durations.is_empty() {
-                let histogram = calculate_histogram(&durations);
+        output.push_str("# TYPE yatagarasu_sigv4_requests_total counter\n");
+        for (key, count) in self.sigv4_requests.snapshot().iter() {
+            // key format: "bucket:result" (results never contain ':')
+            if let Some((bucket, result)) = key.rsplit_once(':') {
                 output.push_str(&format!(
-                    "yatagarasu_image_processing_duration_us{{quantile=\"0.5\"}} {:.3}\n",
-                    histogram.p50
+                    "yatagarasu_sigv4_requests_total{{bucket=\"{}\",result=\"{}\"}} {}\n",
+                    bucket, result, count
                 ));
+            }
+        }
+
+        // Browser POST policy uploads
+        output.push_str(
+            "\n# HELP yatagarasu_post_policy_uploads_total Browser POST policy uploads by bucket and result\n",
+        );
+        output.push_str("# TYPE yatagarasu_post_policy_uploads_total counter\n");
+        for (key, count) in self.post_policy_uploads.snapshot().iter() {
+            // key format: "bucket:result" (results never contain ':')
+            if let Some((bucket, result)) = key.rsplit_once(':') {
                 output.push_str(&format!(
-                    "yatagarasu_image_processing_duration_us{{quantile=\"0.95\"}} {:.3}\n",
-                    histogram.p95
+                    "yatagarasu_post_policy_uploads_total{{bucket=\"{}\",result=\"{}\"}} {}\n",
+                    bucket, result, count
                 ));
+            }
+        }
+
+        // Presigned redirects
+        output.push_str(
+            "\n# HELP yatagarasu_presigned_redirects_total Requests answered with a presigned S3 URL by bucket and result\n",
+        );
+        output.push_str("# TYPE yatagarasu_presigned_redirects_total counter\n");
+        for (key, count) in self.presigned_redirects.snapshot().iter() {
+            // key format: "bucket:result" (results never contain ':')
+            if let Some((bucket, result)) = key.rsplit_once(':') {
                 output.push_str(&format!(
-                    "yatagarasu_image_processing_duration_us{{quantile=\"0.99\"}} {:.3}\n",
-                    histogram.p99
+                    "yatagarasu_presigned_redirects_total{{bucket=\"{}\",result=\"{}\"}} {}\n",
+                    bucket, result, count
                 ));
             }
         }
 
+        // Range abuse
         output.push_str(
-            "\n# HELP yatagarasu_image_transformations_total Image transformations by type\n",
+            "\n# HELP yatagarasu_range_abuse_total Clients flagged for small-range patterns and Range requests throttled, by bucket\n",
         );
-        output.push_str("# TYPE yatagarasu_image_transformations_total counter\n");
-        if let Ok(transforms) = self.image_transformations.lock() {
-            for (transform_type, count) in transforms.iter() {
+        output.push_str("# TYPE yatagarasu_range_abuse_total counter\n");
+        for (key, count) in self.range_abuse_events.snapshot().iter() {
+            // key format: "bucket:result" (results never contain ':')
+            if let Some((bucket, result)) = key.rsplit_once(':') {
                 output.push_str(&format!(
-                    "yatagarasu_image_transformations_total{{type=\"{}\"}} {}\n",
-                    transform_type, count
+                    "yatagarasu_range_abuse_total{{bucket=\"{}\",result=\"{}\"}} {}\n",
+                    bucket, result, count
                 ));
             }
         }
 
-        output.push_str("\n# HELP yatagarasu_image_formats_total Image output formats\n");
-        output.push_str("# TYPE yatagarasu_image_formats_total counter\n");
-        if let Ok(formats) = self.image_formats.lock() {
-            for (format, count) in formats.iter() {
+        // Temporary access grants
+        output.push_str(
+            "\n# HELP yatagarasu_access_grant_requests_total Requests allowed by temporary access grants by bucket and match (ip/subject)\n",
+        );
+        output.push_str("# TYPE yatagarasu_access_grant_requests_total counter\n");
+        for (key, count) in self.access_grant_requests.snapshot().iter() {
+            // key format: "bucket:match" (matches never contain ':')
+            if let Some((bucket, matched)) = key.rsplit_once(':') {
                 output.push_str(&format!(
-                    "yatagarasu_image_formats_total{{format=\"{}\"}} {}\n",
-                    format, count
+                    "yatagarasu_access_grant_requests_total{{bucket=\"{}\",match=\"{}\"}} {}\n",
+                    bucket, matched, count
                 ));
             }
         }
 
-        output.push_str("\n# HELP yatagarasu_image_errors_by_type_total Image errors by type\n");
-        output.push_str("# TYPE yatagarasu_image_errors_by_type_total counter\n");
-        if let Ok(errors) = self.image_errors_by_type.lock() {
-            for (error_type, count) in errors.iter() {
+        // Size classes
+        output.push_str(
+            "\n# HELP yatagarasu_size_class_responses_total Upstream responses by bucket and object size class\n",
+        );
+        output.push_str("# TYPE yatagarasu_size_class_responses_total counter\n");
+        for (key, count) in self.size_class_responses.snapshot().iter() {
+            // key format: "bucket:class" (class names never contain ':')
+            if let Some((bucket, class)) = key.rsplit_once(':') {
+                output.push_str(&format!(
+                    "yatagarasu_size_class_responses_total{{bucket=\"{}\",class=\"{}\"}} {}\n",
+                    bucket, class, count
+                ));
+            }
+        }
+
+        // JWT failures by reason and issuer
+        output.push_str(
+            "\n# HELP yatagarasu_auth_failures_total JWT authentication failures by bucket, issuer and reason\n",
+        );
+        output.push_str("# TYPE yatagarasu_auth_failures_total counter\n");
+        for (key, count) in self.auth_failure_reasons.snapshot().iter() {
+            // key format: "bucket:reason:issuer" (the issuer may itself contain ':')
+            let mut parts = key.splitn(3, ':');
+            if let (Some(bucket), Some(reason), Some(issuer)) =
+                (parts.next(), parts.next(), parts.next())
+            {
                 output.push_str(&format!(
-                    "yatagarasu_image_errors_by_type_total{{type=\"{}\"}} {}\n",
-                    error_type, count
+                    "yatagarasu_auth_failures_total{{bucket=\"{}\",issuer=\"{}\",reason=\"{}\"}} {}\n",
+                    bucket, issuer, reason, count
                 ));
             }
         }
 
+        // Cache experiment lookups by variant
+        output.push_str(
+            "\n# HELP yatagarasu_cache_experiment_requests_total Cache lookups by experiment variant and result\n",
+        );
+        output.push_str("# TYPE yatagarasu_cache_experiment_requests_total counter\n");
+        for (key, count) in self.cache_experiment_requests.snapshot().iter() {
+            // key format: "bucket:experiment:variant:result"
+            let parts: Vec<&str> = key.split(':').collect();
+            if let [bucket, experiment, variant, result] = parts[..] {
+                output.push_str(&format!(
+                    "yatagarasu_cache_experiment_requests_total{{bucket=\"{}\",experiment=\"{}\",variant=\"{}\",result=\"{}\"}} {}\n",
+                    bucket, experiment, variant, result, count
+                ));
+            }
+        }
+
+        // Label cardinality limits
+        output.push_str(
+            "\n# HELP yatagarasu_metrics_label_values Distinct label values with their own series\n",
+        );
+        output.push_str("# TYPE yatagarasu_metrics_label_values gauge\n");
+        for limiter in [&self.bucket_labels, &self.replica_labels] {
+            output.push_str(&format!(
+                "yatagarasu_metrics_label_values{{label=\"{}\"}} {}\n",
+                limiter.label(),
+                limiter.tracked()
+            ));
+        }
+        output.push_str(
+            "\n# HELP yatagarasu_metrics_label_overflow_total Recordings folded into the \"other\" label value after the label limit was reached\n",
+        );
+        output.push_str("# TYPE yatagarasu_metrics_label_overflow_total counter\n");
+        for limiter in [&self.bucket_labels, &self.replica_labels] {
+            output.push_str(&format!(
+                "yatagarasu_metrics_label_overflow_total{{label=\"{}\"}} {}\n",
+                limiter.label(),
+                limiter.overflows()
+            ));
+        }
+
+        // Runtime internals: every runtime that served requests, plus the one
+        // serving this scrape (absent outside a runtime)
+        if let Some(snapshot) = RuntimeRegistry::global().capture_all() {
+            let busy_ratio = self.runtime_busy_tracker.observe(&snapshot, Instant::now());
+            output.push_str(&runtime::export_runtime_metrics(&snapshot, busy_ratio));
+        }
+
         output
     }
 }
@@ -2414,60 +3673,270 @@ mod tests {
     }
 
     #[test]
-    fn test_track_successful_config_reloads() {
-        // Test: Track successful config reload attempts
+    fn test_track_successful_config_reloads() {
+        // Test: Track successful config reload attempts
+        let metrics = Metrics::new();
+
+        // Start with zero successful reloads
+        assert_eq!(metrics.get_reload_success_count(), 0);
+
+        // Increment successful reload count
+        metrics.increment_reload_success();
+        assert_eq!(metrics.get_reload_success_count(), 1);
+
+        // Multiple successful reloads
+        metrics.increment_reload_success();
+        metrics.increment_reload_success();
+        assert_eq!(metrics.get_reload_success_count(), 3);
+    }
+
+    #[test]
+    fn test_track_failed_config_reloads() {
+        // Test: Track failed config reload attempts
+        let metrics = Metrics::new();
+
+        // Start with zero failed reloads
+        assert_eq!(metrics.get_reload_failure_count(), 0);
+
+        // Increment failed reload count
+        metrics.increment_reload_failure();
+        assert_eq!(metrics.get_reload_failure_count(), 1);
+
+        // Multiple failed reloads
+        metrics.increment_reload_failure();
+        metrics.increment_reload_failure();
+        assert_eq!(metrics.get_reload_failure_count(), 3);
+    }
+
+    #[test]
+    fn test_track_config_generation() {
+        // Test: Track current config generation number
+        let metrics = Metrics::new();
+
+        // Start with zero (initial generation)
+        assert_eq!(metrics.get_config_generation(), 0);
+
+        // Set config generation
+        metrics.set_config_generation(1);
+        assert_eq!(metrics.get_config_generation(), 1);
+
+        // Update to new generation
+        metrics.set_config_generation(5);
+        assert_eq!(metrics.get_config_generation(), 5);
+
+        // Generation can increase by any amount
+        metrics.set_config_generation(42);
+        assert_eq!(metrics.get_config_generation(), 42);
+    }
+
+    #[test]
+    fn test_jwt_token_cache_metrics() {
+        let metrics = Metrics::new();
+        metrics.increment_jwt_token_cache(false);
+        metrics.increment_jwt_token_cache(true);
+        metrics.increment_jwt_token_cache(true);
+
+        assert_eq!(metrics.get_jwt_token_cache("hit"), 2);
+        assert_eq!(metrics.get_jwt_token_cache("miss"), 1);
+        assert!(metrics
+            .export_prometheus()
+            .contains("yatagarasu_jwt_token_cache_lookups_total{result=\"hit\"} 2"));
+    }
+
+    #[test]
+    fn test_cache_population_skip_metrics() {
+        let metrics = Metrics::new();
+        metrics.increment_cache_population_skip("missing_validator");
+        metrics.increment_cache_population_skip("no_store");
+        metrics.increment_cache_population_skip("no_store");
+
+        assert_eq!(metrics.get_cache_population_skip("no_store"), 2);
+        assert_eq!(metrics.get_cache_population_skip("private"), 0);
+        assert!(metrics
+            .export_prometheus()
+            .contains("yatagarasu_cache_population_skipped_total{reason=\"missing_validator\"} 1"));
+    }
+
+    #[test]
+    fn test_cache_range_metrics() {
+        let metrics = Metrics::new();
+        metrics.increment_cache_range("miss");
+        metrics.increment_cache_range("stored");
+        metrics.increment_cache_range("stored");
+        metrics.increment_cache_range("hit");
+
+        assert_eq!(metrics.get_cache_range("stored"), 2);
+        assert_eq!(metrics.get_cache_range("hit"), 1);
+        assert!(metrics
+            .export_prometheus()
+            .contains("yatagarasu_cache_range_total{result=\"stored\"} 2"));
+    }
+
+    #[test]
+    fn test_response_buffer_budget_exceeded_metric() {
+        let metrics = Metrics::new();
+        metrics.increment_response_buffer_budget_exceeded();
+        metrics.increment_response_buffer_budget_exceeded();
+
+        assert_eq!(metrics.get_response_buffer_budget_exceeded(), 2);
+        assert!(metrics
+            .export_prometheus()
+            .contains("yatagarasu_response_buffer_budget_exceeded_total 2"));
+    }
+
+    #[test]
+    fn test_signed_url_request_metrics() {
+        let metrics = Metrics::new();
+        metrics.increment_signed_url_request("downloads", "valid");
+        metrics.increment_signed_url_request("downloads", "expired");
+        metrics.increment_signed_url_request("downloads", "valid");
+
+        assert_eq!(metrics.get_signed_url_requests("downloads", "valid"), 2);
+        assert_eq!(metrics.get_signed_url_requests("downloads", "missing"), 0);
+        assert!(metrics.export_prometheus().contains(
+            "yatagarasu_signed_url_requests_total{bucket=\"downloads\",result=\"expired\"} 1"
+        ));
+    }
+
+    #[test]
+    fn test_post_policy_upload_metrics() {
+        let metrics = Metrics::new();
+        metrics.increment_post_policy_upload("uploads", "uploaded");
+        metrics.increment_post_policy_upload("uploads", "expired");
+        metrics.increment_post_policy_upload("uploads", "uploaded");
+
+        assert_eq!(metrics.get_post_policy_uploads("uploads", "uploaded"), 2);
+        assert_eq!(metrics.get_post_policy_uploads("uploads", "too_large"), 0);
+        assert!(metrics.export_prometheus().contains(
+            "yatagarasu_post_policy_uploads_total{bucket=\"uploads\",result=\"expired\"} 1"
+        ));
+    }
+
+    #[test]
+    fn test_presigned_redirect_metrics() {
         let metrics = Metrics::new();
+        metrics.increment_presigned_redirect("media", "redirected");
+        metrics.increment_presigned_redirect("media", "redirected");
+        metrics.increment_presigned_redirect("media", "unavailable");
+
+        assert_eq!(metrics.get_presigned_redirects("media", "redirected"), 2);
+        assert_eq!(metrics.get_presigned_redirects("other", "redirected"), 0);
+        assert!(metrics.export_prometheus().contains(
+            "yatagarasu_presigned_redirects_total{bucket=\"media\",result=\"unavailable\"} 1"
+        ));
+    }
 
-        // Start with zero successful reloads
-        assert_eq!(metrics.get_reload_success_count(), 0);
+    #[test]
+    fn test_range_abuse_metrics() {
+        let metrics = Metrics::new();
+        metrics.increment_range_abuse("media", "flagged");
+        metrics.increment_range_abuse("media", "throttled");
+        metrics.increment_range_abuse("media", "throttled");
 
-        // Increment successful reload count
-        metrics.increment_reload_success();
-        assert_eq!(metrics.get_reload_success_count(), 1);
+        assert_eq!(metrics.get_range_abuse_events("media", "flagged"), 1);
+        assert_eq!(metrics.get_range_abuse_events("media", "throttled"), 2);
+        assert!(metrics
+            .export_prometheus()
+            .contains("yatagarasu_range_abuse_total{bucket=\"media\",result=\"throttled\"} 2"));
+    }
 
-        // Multiple successful reloads
-        metrics.increment_reload_success();
-        metrics.increment_reload_success();
-        assert_eq!(metrics.get_reload_success_count(), 3);
+    #[test]
+    fn test_access_grant_metrics() {
+        let metrics = Metrics::new();
+        metrics.increment_access_grant_use("media", "subject");
+        metrics.increment_access_grant_use("media", "ip");
+        metrics.increment_access_grant_use("media", "ip");
+
+        assert_eq!(metrics.get_access_grant_uses("media", "ip"), 2);
+        assert_eq!(metrics.get_access_grant_uses("other", "ip"), 0);
+        assert!(metrics.export_prometheus().contains(
+            "yatagarasu_access_grant_requests_total{bucket=\"media\",match=\"subject\"} 1"
+        ));
     }
 
     #[test]
-    fn test_track_failed_config_reloads() {
-        // Test: Track failed config reload attempts
+    fn test_size_class_metrics() {
         let metrics = Metrics::new();
+        metrics.increment_size_class_response("media", "tiny");
+        metrics.increment_size_class_response("media", "huge");
+        metrics.increment_size_class_response("media", "tiny");
 
-        // Start with zero failed reloads
-        assert_eq!(metrics.get_reload_failure_count(), 0);
+        assert_eq!(metrics.get_size_class_responses("media", "tiny"), 2);
+        assert_eq!(metrics.get_size_class_responses("media", "medium"), 0);
+        assert!(metrics
+            .export_prometheus()
+            .contains("yatagarasu_size_class_responses_total{bucket=\"media\",class=\"huge\"} 1"));
+    }
 
-        // Increment failed reload count
-        metrics.increment_reload_failure();
-        assert_eq!(metrics.get_reload_failure_count(), 1);
+    #[test]
+    fn test_cache_experiment_metrics() {
+        let metrics = Metrics::new();
+        metrics.increment_cache_experiment("media", "ttl-test", "control", "hit");
+        metrics.increment_cache_experiment("media", "ttl-test", "control", "hit");
+        metrics.increment_cache_experiment("media", "ttl-test", "long", "miss");
 
-        // Multiple failed reloads
-        metrics.increment_reload_failure();
-        metrics.increment_reload_failure();
-        assert_eq!(metrics.get_reload_failure_count(), 3);
+        assert_eq!(
+            metrics.get_cache_experiment_count("media", "ttl-test", "control", "hit"),
+            2
+        );
+        assert_eq!(
+            metrics.get_cache_experiment_count("media", "ttl-test", "long", "hit"),
+            0
+        );
+        let output = metrics.export_prometheus();
+        assert!(output.contains(
+            "yatagarasu_cache_experiment_requests_total{bucket=\"media\",experiment=\"ttl-test\",variant=\"long\",result=\"miss\"} 1"
+        ));
     }
 
     #[test]
-    fn test_track_config_generation() {
-        // Test: Track current config generation number
+    fn test_auth_failure_reason_metrics() {
         let metrics = Metrics::new();
+        metrics.increment_auth_failure_reason("products", "https://idp.example.com", "expired");
+        metrics.increment_auth_failure_reason("products", "https://idp.example.com", "expired");
+        metrics.increment_auth_failure_reason("products", "none", "missing");
 
-        // Start with zero (initial generation)
-        assert_eq!(metrics.get_config_generation(), 0);
+        assert_eq!(
+            metrics.get_auth_failure_reason_count("products", "https://idp.example.com", "expired"),
+            2
+        );
+        assert_eq!(
+            metrics.get_auth_failure_reason_count("products", "none", "bad_signature"),
+            0
+        );
+        let output = metrics.export_prometheus();
+        assert!(output.contains(
+            "yatagarasu_auth_failures_total{bucket=\"products\",issuer=\"https://idp.example.com\",reason=\"expired\"} 2"
+        ));
+        assert!(output.contains(
+            "yatagarasu_auth_failures_total{bucket=\"products\",issuer=\"none\",reason=\"missing\"} 1"
+        ));
+    }
 
-        // Set config generation
-        metrics.set_config_generation(1);
-        assert_eq!(metrics.get_config_generation(), 1);
+    #[test]
+    fn test_sigv4_request_metrics() {
+        let metrics = Metrics::new();
+        metrics.increment_sigv4_request("uploads", "valid");
+        metrics.increment_sigv4_request("uploads", "signature_mismatch");
 
-        // Update to new generation
-        metrics.set_config_generation(5);
-        assert_eq!(metrics.get_config_generation(), 5);
+        assert_eq!(metrics.get_sigv4_requests("uploads", "valid"), 1);
+        assert_eq!(metrics.get_sigv4_requests("uploads", "expired"), 0);
+        assert!(metrics.export_prometheus().contains(
+            "yatagarasu_sigv4_requests_total{bucket=\"uploads\",result=\"signature_mismatch\"} 1"
+        ));
+    }
 
-        // Generation can increase by any amount
-        metrics.set_config_generation(42);
-        assert_eq!(metrics.get_config_generation(), 42);
+    #[test]
+    fn test_track_requests_on_retired_generations() {
+        let metrics = Metrics::new();
+        assert_eq!(metrics.get_retired_generation_requests(), 0);
+
+        metrics.increment_retired_generation_request();
+        metrics.increment_retired_generation_request();
+        assert_eq!(metrics.get_retired_generation_requests(), 2);
+        assert!(metrics
+            .export_prometheus()
+            .contains("config_retired_generation_requests_total 2"));
     }
 
     #[test]
@@ -2738,8 +4207,15 @@ mod tests {
             "Should return active replica name"
         );
 
+        // Same replica again is not a change
+        assert_eq!(metrics.set_active_replica("products", "primary"), None);
+
         // Update active replica (simulating failover)
-        metrics.set_active_replica("products", "replica-eu");
+        assert_eq!(
+            metrics.set_active_replica("products", "replica-eu"),
+            Some("primary".to_string()),
+            "Should return the previous replica on change"
+        );
         assert_eq!(
             metrics.get_active_replica("products"),
             Some("replica-eu".to_string()),
@@ -3160,4 +4636,410 @@ mod tests {
         // bytes_saved should remain 0 when image grows
         assert_eq!(metrics.get_image_bytes_saved(), 0);
     }
+
+    // Runtime internals metrics tests
+
+    #[test]
+    fn test_connection_pool_and_accept_metrics() {
+        let metrics = Metrics::new();
+
+        metrics.record_downstream_request(true);
+        metrics.record_downstream_request(false);
+        metrics.record_downstream_request(false);
+        metrics.record_upstream_connection(false);
+        metrics.record_upstream_connection(true);
+        metrics.record_upstream_connection(true);
+
+        assert_eq!(metrics.get_downstream_connections_accepted(), 1);
+        assert_eq!(metrics.get_upstream_connections_new(), 1);
+        assert_eq!(metrics.get_upstream_connections_reused(), 2);
+
+        let output = metrics.export_prometheus();
+        assert!(output.contains("yatagarasu_downstream_connections_accepted_total 1"));
+        assert!(output.contains("yatagarasu_downstream_requests_reused_total 2"));
+        assert!(output.contains("yatagarasu_upstream_connections_total{pool=\"new\"} 1"));
+        assert!(output.contains("yatagarasu_upstream_connections_total{pool=\"reused\"} 2"));
+        // Not running inside a Tokio runtime, so runtime gauges are omitted
+        assert!(!output.contains("yatagarasu_runtime_workers"));
+    }
+
+    #[test]
+    fn test_client_abort_metrics() {
+        let metrics = Metrics::new();
+
+        metrics.record_client_abort(false, 4096);
+        metrics.record_client_abort(false, 1024);
+        metrics.record_client_abort(true, 512);
+
+        assert_eq!(metrics.get_client_aborts_cancelled(), 2);
+        assert_eq!(metrics.get_client_aborts_cache_fill(), 1);
+        // Bytes fetched to finish a cache fill are not saved
+        assert_eq!(metrics.get_client_abort_bytes_saved(), 5120);
+
+        let output = metrics.export_prometheus();
+        assert!(
+            output.contains("yatagarasu_client_aborted_transfers_total{outcome=\"cancelled\"} 2")
+        );
+        assert!(
+            output.contains("yatagarasu_client_aborted_transfers_total{outcome=\"cache_fill\"} 1")
+        );
+        assert!(output.contains("yatagarasu_client_abort_bytes_saved_total 5120"));
+    }
+
+    #[test]
+    fn test_hotlink_metrics() {
+        let metrics = Metrics::new();
+
+        metrics.increment_hotlink_request("assets", "deny");
+        metrics.increment_hotlink_request("assets", "deny");
+        metrics.increment_hotlink_request("photos", "watermark");
+
+        assert_eq!(metrics.get_hotlink_requests("assets", "deny"), 2);
+        assert_eq!(metrics.get_hotlink_requests("photos", "watermark"), 1);
+        assert_eq!(metrics.get_hotlink_requests("photos", "deny"), 0);
+
+        let output = metrics.export_prometheus();
+        assert!(output.contains("# TYPE yatagarasu_hotlink_requests_total counter"));
+        assert!(output
+            .contains("yatagarasu_hotlink_requests_total{bucket=\"assets\",action=\"deny\"} 2"));
+    }
+
+    #[test]
+    fn test_ip_ban_metrics() {
+        let metrics = Metrics::new();
+
+        metrics.record_ip_ban("client_errors");
+        metrics.record_ip_ban("rate_limited");
+        metrics.record_ip_ban("rate_limited");
+        metrics.increment_ip_ban_rejection();
+
+        assert_eq!(metrics.get_ip_bans("rate_limited"), 2);
+        assert_eq!(metrics.get_ip_bans("shared"), 0);
+        assert_eq!(metrics.get_ip_ban_rejections(), 1);
+
+        let output = metrics.export_prometheus();
+        assert!(output.contains("yatagarasu_ip_bans_total{reason=\"client_errors\"} 1"));
+        assert!(output.contains("yatagarasu_ip_ban_rejections_total 1"));
+    }
+
+    #[test]
+    fn test_priority_admission_metrics() {
+        let metrics = Metrics::new();
+
+        metrics.increment_priority_admission("reserved_permit");
+        metrics.increment_priority_admission("reserved_permit");
+        metrics.increment_priority_admission("shed_bypass");
+
+        assert_eq!(metrics.get_priority_admissions("reserved_permit"), 2);
+        assert_eq!(metrics.get_priority_admissions("shed_bypass"), 1);
+
+        let output = metrics.export_prometheus();
+        assert!(output.contains("# TYPE yatagarasu_priority_admissions_total counter"));
+        assert!(
+            output.contains("yatagarasu_priority_admissions_total{reason=\"reserved_permit\"} 2")
+        );
+    }
+
+    #[test]
+    fn test_cache_override_metrics() {
+        let metrics = Metrics::new();
+
+        metrics.increment_cache_override("bypass");
+        metrics.increment_cache_override("untrusted");
+        metrics.increment_cache_override("untrusted");
+
+        assert_eq!(metrics.get_cache_overrides("bypass"), 1);
+        assert_eq!(metrics.get_cache_overrides("untrusted"), 2);
+
+        let output = metrics.export_prometheus();
+        assert!(output.contains("# TYPE yatagarasu_cache_overrides_total counter"));
+        assert!(output.contains("yatagarasu_cache_overrides_total{mode=\"untrusted\"} 2"));
+    }
+
+    #[test]
+    fn test_maintenance_metrics() {
+        let metrics = Metrics::new();
+        assert_eq!(metrics.get_maintenance_mode(), 0);
+
+        metrics.set_maintenance_mode(true);
+        metrics.increment_maintenance_rejection();
+        metrics.increment_maintenance_rejection();
+
+        assert_eq!(metrics.get_maintenance_mode(), 1);
+        assert_eq!(metrics.get_maintenance_rejections(), 2);
+
+        let output = metrics.export_prometheus();
+        assert!(output.contains("yatagarasu_maintenance_mode 1"));
+        assert!(output.contains("yatagarasu_maintenance_rejections_total 2"));
+
+        metrics.set_maintenance_mode(false);
+        assert!(metrics
+            .export_prometheus()
+            .contains("yatagarasu_maintenance_mode 0"));
+    }
+
+    #[test]
+    fn test_endpoint_access_denied_metrics() {
+        let metrics = Metrics::new();
+        metrics.increment_endpoint_access_denied("metrics", "unauthorized");
+        metrics.increment_endpoint_access_denied("metrics", "unauthorized");
+        metrics.increment_endpoint_access_denied("health", "forbidden");
+
+        assert_eq!(
+            metrics.get_endpoint_access_denied("metrics", "unauthorized"),
+            2
+        );
+        assert_eq!(
+            metrics.get_endpoint_access_denied("health", "unauthorized"),
+            0
+        );
+
+        let output = metrics.export_prometheus();
+        assert!(output.contains("# TYPE yatagarasu_endpoint_access_denied_total counter"));
+        assert!(output.contains(
+            "yatagarasu_endpoint_access_denied_total{endpoint=\"health\",reason=\"forbidden\"} 1"
+        ));
+    }
+
+    #[test]
+    fn test_origin_probe_metrics() {
+        let metrics = Metrics::new();
+        metrics.increment_origin_probe("media", "failure");
+        metrics.increment_origin_probe("media", "success");
+        metrics.increment_origin_probe("media/backup", "success");
+
+        assert_eq!(metrics.get_origin_probes("media", "success"), 1);
+        assert_eq!(metrics.get_origin_probes("docs", "success"), 0);
+
+        let output = metrics.export_prometheus();
+        assert!(output.contains("# TYPE yatagarasu_origin_probes_total counter"));
+        assert!(output.contains(
+            "yatagarasu_origin_probes_total{target=\"media/backup\",result=\"success\"} 1"
+        ));
+    }
+
+    #[test]
+    fn test_degraded_response_metrics() {
+        let metrics = Metrics::new();
+        metrics.increment_degraded_response("media", "stale");
+        metrics.increment_degraded_response("media", "stale");
+        metrics.increment_degraded_response("media", "miss");
+
+        assert_eq!(metrics.get_degraded_responses("media", "stale"), 2);
+        assert_eq!(metrics.get_degraded_responses("media", "fresh"), 0);
+
+        let output = metrics.export_prometheus();
+        assert!(output.contains("# TYPE yatagarasu_degraded_responses_total counter"));
+        assert!(output
+            .contains("yatagarasu_degraded_responses_total{bucket=\"media\",outcome=\"miss\"} 1"));
+    }
+
+    #[test]
+    fn test_label_limits_fold_into_other() {
+        let metrics = Metrics::new();
+        metrics.set_label_limits(2, 1);
+        for bucket in ["a", "b", "c", "d"] {
+            metrics.increment_bucket_count(bucket);
+        }
+        metrics.increment_replica_request_count("a", "primary");
+        metrics.increment_replica_request_count("a", "backup");
+
+        assert_eq!(metrics.get_bucket_count("a"), 1);
+        assert_eq!(metrics.get_bucket_count(OTHER_LABEL), 2);
+        assert_eq!(metrics.get_replica_request_count("a", OTHER_LABEL), 1);
+
+        let output = metrics.export_prometheus();
+        assert!(output.contains("yatagarasu_metrics_label_values{label=\"bucket\"} 2"));
+        assert!(output.contains("yatagarasu_metrics_label_overflow_total{label=\"bucket\"} 2"));
+        assert!(output.contains("yatagarasu_metrics_label_overflow_total{label=\"replica\"} 1"));
+    }
+
+    #[test]
+    fn test_freshness_check_metrics() {
+        let metrics = Metrics::new();
+        metrics.increment_freshness_check("media", "fresh");
+        metrics.increment_freshness_check("media", "changed");
+
+        assert_eq!(metrics.get_freshness_checks("media", "fresh"), 1);
+        assert_eq!(metrics.get_freshness_checks("media", "error"), 0);
+
+        let output = metrics.export_prometheus();
+        assert!(output.contains("# TYPE yatagarasu_cache_freshness_checks_total counter"));
+        assert!(output.contains(
+            "yatagarasu_cache_freshness_checks_total{bucket=\"media\",result=\"changed\"} 1"
+        ));
+    }
+
+    #[test]
+    fn test_circuit_breaker_failure_metrics() {
+        let metrics = Metrics::new();
+        metrics.increment_circuit_breaker_failure("media", "timeout");
+        metrics.increment_circuit_breaker_failure("media", "timeout");
+        metrics.increment_circuit_breaker_failure("media", "connect_error");
+
+        assert_eq!(metrics.get_circuit_breaker_failures("media", "timeout"), 2);
+        assert_eq!(
+            metrics.get_circuit_breaker_failures("media", "server_error"),
+            0
+        );
+
+        let output = metrics.export_prometheus();
+        assert!(output.contains("# TYPE yatagarasu_circuit_breaker_failures_total counter"));
+        assert!(output.contains(
+            "yatagarasu_circuit_breaker_failures_total{bucket=\"media\",kind=\"connect_error\"} 1"
+        ));
+    }
+
+    #[test]
+    fn test_bulkhead_rejection_metrics() {
+        let metrics = Metrics::new();
+        metrics.increment_bulkhead_rejection("media");
+        metrics.increment_bulkhead_rejection("media");
+
+        assert_eq!(metrics.get_bulkhead_rejections("media"), 2);
+        assert_eq!(metrics.get_bulkhead_rejections("docs"), 0);
+        assert!(metrics
+            .export_prometheus()
+            .contains("yatagarasu_bulkhead_rejections_total{bucket=\"media\"} 2"));
+    }
+
+    #[test]
+    fn test_deprecated_route_metrics() {
+        let metrics = Metrics::new();
+        metrics.increment_deprecated_route_request("legacy");
+
+        assert_eq!(metrics.get_deprecated_route_requests("legacy"), 1);
+        assert!(metrics
+            .export_prometheus()
+            .contains("yatagarasu_deprecated_route_requests_total{bucket=\"legacy\"} 1"));
+    }
+
+    #[test]
+    fn test_route_entry_metrics() {
+        let metrics = Metrics::new();
+        metrics.increment_route_entry_request("assets", "/v1/assets", Some("v1"));
+        metrics.increment_route_entry_request("assets", "/v1/assets", Some("v1"));
+        metrics.increment_route_entry_request("assets", "/assets", None);
+
+        assert_eq!(
+            metrics.get_route_entry_requests("assets", "/v1/assets", Some("v1")),
+            2
+        );
+        let output = metrics.export_prometheus();
+        assert!(output.contains(
+            "yatagarasu_route_entry_requests_total{bucket=\"assets\",prefix=\"/v1/assets\",version=\"v1\"} 2"
+        ));
+        assert!(output.contains(
+            "yatagarasu_route_entry_requests_total{bucket=\"assets\",prefix=\"/assets\",version=\"\"} 1"
+        ));
+    }
+
+    #[test]
+    fn test_oversized_header_metrics() {
+        let metrics = Metrics::new();
+        metrics.increment_oversized_header("cookie");
+        metrics.increment_oversized_header("cookie");
+
+        assert_eq!(metrics.get_oversized_headers("cookie"), 2);
+        assert!(metrics
+            .export_prometheus()
+            .contains("yatagarasu_oversized_headers_total{header=\"cookie\"} 2"));
+    }
+
+    #[test]
+    fn test_bucket_size_histograms() {
+        let metrics = Metrics::new();
+        metrics.record_bucket_sizes("media", 24, 3000);
+        metrics.record_bucket_sizes("media", 24, 2 << 20);
+        metrics.record_range_span("media", 1 << 20);
+
+        assert_eq!(metrics.get_bucket_size_observations("media"), 2);
+        assert_eq!(metrics.get_range_span_observations("media"), 1);
+        let output = metrics.export_prometheus();
+        assert!(output
+            .contains("yatagarasu_response_size_bytes_bucket{bucket=\"media\",le=\"4096\"} 1"));
+        assert!(output
+            .contains("yatagarasu_request_path_length_bytes_bucket{bucket=\"media\",le=\"32\"} 2"));
+        assert!(output.contains("yatagarasu_range_span_bytes_count{bucket=\"media\"} 1"));
+    }
+
+    #[test]
+    fn test_upload_scan_metrics() {
+        let metrics = Metrics::new();
+        metrics.record_upload_scan("uploads", "clean", Some(40));
+        metrics.record_upload_scan("uploads", "skipped", None);
+
+        assert_eq!(metrics.get_upload_scans("uploads", "clean"), 1);
+        assert_eq!(metrics.get_upload_scans("uploads", "infected"), 0);
+        let output = metrics.export_prometheus();
+        assert!(output
+            .contains("yatagarasu_upload_scans_total{bucket=\"uploads\",result=\"skipped\"} 1"));
+        assert!(output
+            .contains("yatagarasu_upload_scan_duration_ms_bucket{bucket=\"uploads\",le=\"50\"} 1"));
+    }
+
+    #[test]
+    fn test_tls_handshake_metrics() {
+        let metrics = Metrics::new();
+        metrics.record_tls_handshake(false, Some(8));
+        metrics.record_tls_handshake(true, None);
+        metrics.record_tls_handshake(true, None);
+
+        assert_eq!(metrics.get_tls_handshakes("full"), 1);
+        assert_eq!(metrics.get_tls_handshakes("resumed"), 2);
+        let output = metrics.export_prometheus();
+        assert!(output.contains("yatagarasu_tls_handshakes_total{session=\"resumed\"} 2"));
+        assert!(output
+            .contains("yatagarasu_tls_handshake_duration_ms_bucket{session=\"full\",le=\"10\"} 1"));
+    }
+
+    #[test]
+    fn test_request_queue_metrics() {
+        let metrics = Metrics::new();
+        metrics.increment_request_queue_outcome("admitted");
+        metrics.increment_request_queue_outcome("admitted");
+        metrics.increment_request_queue_outcome("timeout");
+
+        assert_eq!(metrics.get_request_queue_outcomes("admitted"), 2);
+        assert_eq!(metrics.get_request_queue_outcomes("full"), 0);
+        assert!(metrics
+            .export_prometheus()
+            .contains("yatagarasu_request_queue_total{outcome=\"timeout\"} 1"));
+    }
+
+    #[test]
+    fn test_cache_layer_quarantine_metrics() {
+        let metrics = Metrics::new();
+
+        metrics.increment_cache_layer_error("disk");
+        metrics.increment_cache_layer_error("disk");
+        metrics.record_cache_layer_quarantine("disk");
+
+        assert_eq!(metrics.get_cache_layer_errors().get("disk"), Some(&2));
+        assert_eq!(metrics.get_cache_layer_quarantines().get("disk"), Some(&1));
+        assert_eq!(
+            metrics.get_cache_layer_quarantined().get("disk"),
+            Some(&true)
+        );
+
+        let output = metrics.export_prometheus();
+        assert!(output.contains("yatagarasu_cache_layer_errors_total{layer=\"disk\"} 2"));
+        assert!(output.contains("yatagarasu_cache_layer_quarantines_total{layer=\"disk\"} 1"));
+        assert!(output.contains("yatagarasu_cache_layer_quarantined{layer=\"disk\"} 1"));
+
+        metrics.set_cache_layer_quarantined("disk", false);
+        assert!(metrics
+            .export_prometheus()
+            .contains("yatagarasu_cache_layer_quarantined{layer=\"disk\"} 0"));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_export_includes_runtime_metrics_inside_runtime() {
+        let metrics = Metrics::new();
+        let output = metrics.export_prometheus();
+
+        assert!(output.contains("yatagarasu_runtime_workers 2"));
+        assert!(output.contains("# TYPE yatagarasu_runtime_worker_busy_ratio gauge"));
+        assert!(output.contains("# TYPE yatagarasu_runtime_global_queue_depth gauge"));
+    }
 }
@@ -0,0 +1,326 @@
+//! Runtime internals metrics.
+//!
+//! Samples the Tokio runtimes serving requests (worker count, alive tasks,
+//! global queue depth, worker busy ratio) so saturation shows up in
+//! `/metrics` before it shows up as latency.
+//!
+//! Pingora runs the proxy on one multi-thread runtime, or with
+//! `work_stealing: false` on one single-threaded runtime per worker, and a
+//! scrape is answered by just one of them. Each runtime registers itself in
+//! the [`RuntimeRegistry`] when it first handles a request, and a scrape sums
+//! over every registered runtime.
+//!
+//! The busy ratio is computed from the delta of total worker busy time between
+//! two scrapes, so the first scrape after startup reports the ratio since the
+//! metrics registry was created.
+
+use std::cell::Cell;
+use std::sync::{Mutex, OnceLock};
+use std::time::{Duration, Instant};
+use tokio::runtime::Handle;
+
+/// Point-in-time view of the Tokio runtimes.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RuntimeSnapshot {
+    /// Number of runtimes sampled
+    pub runtimes: usize,
+    /// Number of worker threads across the runtimes
+    pub workers: usize,
+    /// Number of tasks currently alive (spawned and not yet completed)
+    pub alive_tasks: usize,
+    /// Number of tasks waiting in the runtime's global injection queue
+    pub global_queue_depth: usize,
+    /// Sum of busy time across all workers since the runtime started
+    pub total_busy: Duration,
+}
+
+impl RuntimeSnapshot {
+    /// Sample the runtime the caller is running on.
+    ///
+    /// Returns `None` when called outside of a Tokio runtime.
+    pub fn capture() -> Option<Self> {
+        let handle = Handle::try_current().ok()?;
+        Some(Self::of(&handle))
+    }
+
+    fn of(handle: &Handle) -> Self {
+        let metrics = handle.metrics();
+        let workers = metrics.num_workers();
+        let total_busy = (0..workers)
+            .map(|worker| metrics.worker_total_busy_duration(worker))
+            .sum();
+
+        Self {
+            runtimes: 1,
+            workers,
+            alive_tasks: metrics.num_alive_tasks(),
+            global_queue_depth: metrics.global_queue_depth(),
+            total_busy,
+        }
+    }
+
+    fn add(mut self, other: Self) -> Self {
+        self.runtimes += other.runtimes;
+        self.workers += other.workers;
+        self.alive_tasks += other.alive_tasks;
+        self.global_queue_depth += other.global_queue_depth;
+        self.total_busy += other.total_busy;
+        self
+    }
+}
+
+/// Tokio runtimes that have handled requests
+#[derive(Debug, Default)]
+pub struct RuntimeRegistry {
+    handles: Mutex<Vec<Handle>>,
+}
+
+static RUNTIME_REGISTRY: OnceLock<RuntimeRegistry> = OnceLock::new();
+
+thread_local! {
+    /// Whether this thread's runtime has been registered
+    static REGISTERED: Cell<bool> = const { Cell::new(false) };
+}
+
+impl RuntimeRegistry {
+    /// Process-wide registry of the runtimes serving requests
+    pub fn global() -> &'static RuntimeRegistry {
+        RUNTIME_REGISTRY.get_or_init(RuntimeRegistry::default)
+    }
+
+    /// Register the runtime the calling thread runs on.
+    ///
+    /// Called on every request: after the first call on a thread this is a
+    /// thread-local check.
+    pub fn register_current() {
+        if REGISTERED.with(Cell::get) {
+            return;
+        }
+        if let Ok(handle) = Handle::try_current() {
+            Self::global().register(handle);
+            REGISTERED.with(|registered| registered.set(true));
+        }
+    }
+
+    /// Add `handle`'s runtime, unless it is already registered
+    pub fn register(&self, handle: Handle) {
+        let Ok(mut handles) = self.handles.lock() else {
+            return;
+        };
+        if !handles.iter().any(|known| known.id() == handle.id()) {
+            handles.push(handle);
+        }
+    }
+
+    /// Sample every registered runtime and the caller's own, summed.
+    ///
+    /// Returns `None` when nothing is registered and the caller is outside
+    /// a Tokio runtime.
+    pub fn capture_all(&self) -> Option<RuntimeSnapshot> {
+        let mut handles = match self.handles.lock() {
+            Ok(handles) => handles.clone(),
+            Err(_) => Vec::new(),
+        };
+        if let Ok(current) = Handle::try_current() {
+            if !handles.iter().any(|known| known.id() == current.id()) {
+                handles.push(current);
+            }
+        }
+        handles
+            .iter()
+            .map(RuntimeSnapshot::of)
+            .reduce(RuntimeSnapshot::add)
+    }
+}
+
+/// Tracks the previous busy-time sample to turn cumulative busy time into a ratio.
+#[derive(Debug)]
+pub struct BusyRatioTracker {
+    last_sample: Mutex<(Instant, Duration)>,
+}
+
+impl BusyRatioTracker {
+    /// Create a tracker whose first interval starts now
+    pub fn new() -> Self {
+        Self::starting_at(Instant::now())
+    }
+
+    /// Create a tracker whose first interval starts at `start`
+    pub fn starting_at(start: Instant) -> Self {
+        Self {
+            last_sample: Mutex::new((start, Duration::ZERO)),
+        }
+    }
+
+    /// Record a new sample and return the busy ratio (0.0-1.0) since the previous one.
+    pub fn observe(&self, snapshot: &RuntimeSnapshot, now: Instant) -> f64 {
+        let mut last = match self.last_sample.lock() {
+            Ok(guard) => guard,
+            Err(_) => return 0.0,
+        };
+
+        let (last_at, last_busy) = *last;
+        let elapsed = now.saturating_duration_since(last_at);
+        let busy = snapshot.total_busy.saturating_sub(last_busy);
+        *last = (now, snapshot.total_busy);
+
+        busy_ratio(busy, elapsed, snapshot.workers)
+    }
+}
+
+impl Default for BusyRatioTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Fraction of available worker time spent busy, clamped to 0.0-1.0.
+fn busy_ratio(busy: Duration, elapsed: Duration, workers: usize) -> f64 {
+    if workers == 0 || elapsed.is_zero() {
+        return 0.0;
+    }
+    let capacity = elapsed.as_secs_f64() * workers as f64;
+    (busy.as_secs_f64() / capacity).clamp(0.0, 1.0)
+}
+
+/// Render runtime metrics in Prometheus text format.
+pub fn export_runtime_metrics(snapshot: &RuntimeSnapshot, busy_ratio: f64) -> String {
+    let mut output = String::new();
+
+    output.push_str("\n# HELP yatagarasu_runtimes Number of Tokio runtimes sampled\n");
+    output.push_str("# TYPE yatagarasu_runtimes gauge\n");
+    output.push_str(&format!("yatagarasu_runtimes {}\n", snapshot.runtimes));
+
+    output.push_str(
+        "\n# HELP yatagarasu_runtime_workers Number of Tokio worker threads across runtimes\n",
+    );
+    output.push_str("# TYPE yatagarasu_runtime_workers gauge\n");
+    output.push_str(&format!(
+        "yatagarasu_runtime_workers {}\n",
+        snapshot.workers
+    ));
+
+    output.push_str("\n# HELP yatagarasu_runtime_alive_tasks Number of alive Tokio tasks\n");
+    output.push_str("# TYPE yatagarasu_runtime_alive_tasks gauge\n");
+    output.push_str(&format!(
+        "yatagarasu_runtime_alive_tasks {}\n",
+        snapshot.alive_tasks
+    ));
+
+    output.push_str(
+        "\n# HELP yatagarasu_runtime_global_queue_depth Tasks waiting in the Tokio global queue\n",
+    );
+    output.push_str("# TYPE yatagarasu_runtime_global_queue_depth gauge\n");
+    output.push_str(&format!(
+        "yatagarasu_runtime_global_queue_depth {}\n",
+        snapshot.global_queue_depth
+    ));
+
+    output.push_str(
+        "\n# HELP yatagarasu_runtime_worker_busy_ratio Fraction of worker time spent busy since last scrape\n",
+    );
+    output.push_str("# TYPE yatagarasu_runtime_worker_busy_ratio gauge\n");
+    output.push_str(&format!(
+        "yatagarasu_runtime_worker_busy_ratio {:.4}\n",
+        busy_ratio
+    ));
+
+    output
+        .push_str("\n# HELP yatagarasu_runtime_worker_busy_seconds_total Total worker busy time\n");
+    output.push_str("# TYPE yatagarasu_runtime_worker_busy_seconds_total counter\n");
+    output.push_str(&format!(
+        "yatagarasu_runtime_worker_busy_seconds_total {:.3}\n",
+        snapshot.total_busy.as_secs_f64()
+    ));
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_busy_ratio_clamps_and_handles_zero() {
+        assert_eq!(busy_ratio(Duration::from_secs(1), Duration::ZERO, 4), 0.0);
+        assert_eq!(
+            busy_ratio(Duration::from_secs(1), Duration::from_secs(1), 0),
+            0.0
+        );
+        assert_eq!(
+            busy_ratio(Duration::from_secs(10), Duration::from_secs(1), 2),
+            1.0
+        );
+        assert!(
+            (busy_ratio(Duration::from_secs(1), Duration::from_secs(1), 4) - 0.25).abs() < 1e-9
+        );
+    }
+
+    #[test]
+    fn test_busy_ratio_tracker_uses_delta_between_samples() {
+        let start = Instant::now();
+        let tracker = BusyRatioTracker::starting_at(start);
+
+        let first = RuntimeSnapshot {
+            workers: 2,
+            total_busy: Duration::from_secs(2),
+            ..Default::default()
+        };
+        // First sample: 2s busy over 2 workers * 2s since start
+        let ratio = tracker.observe(&first, start + Duration::from_secs(2));
+        assert!((ratio - 0.5).abs() < 1e-9);
+
+        let second = RuntimeSnapshot {
+            workers: 2,
+            total_busy: Duration::from_secs(6),
+            ..Default::default()
+        };
+        // Second sample: 4s busy over 2 workers * 2s elapsed
+        let ratio = tracker.observe(&second, start + Duration::from_secs(4));
+        assert!((ratio - 1.0).abs() < 1e-9);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_capture_inside_runtime() {
+        let snapshot = RuntimeSnapshot::capture().expect("should run inside tokio");
+        assert_eq!(snapshot.workers, 2);
+
+        let output = export_runtime_metrics(&snapshot, 0.5);
+        assert!(output.contains("# TYPE yatagarasu_runtime_workers gauge"));
+        assert!(output.contains("yatagarasu_runtime_workers 2"));
+        assert!(output.contains("yatagarasu_runtime_worker_busy_ratio 0.5000"));
+        assert!(output.contains("yatagarasu_runtime_global_queue_depth"));
+    }
+
+    #[test]
+    fn test_capture_outside_runtime_returns_none() {
+        assert!(RuntimeSnapshot::capture().is_none());
+        assert!(RuntimeRegistry::default().capture_all().is_none());
+    }
+
+    #[test]
+    fn test_registry_sums_single_threaded_runtimes() {
+        // work_stealing: false runs one current-thread runtime per worker
+        let runtimes: Vec<_> = (0..3)
+            .map(|_| {
+                tokio::runtime::Builder::new_current_thread()
+                    .build()
+                    .unwrap()
+            })
+            .collect();
+        let registry = RuntimeRegistry::default();
+        for runtime in &runtimes {
+            registry.register(runtime.handle().clone());
+            // Registering the same runtime twice counts it once
+            registry.register(runtime.handle().clone());
+        }
+
+        let snapshot = registry.capture_all().unwrap();
+        assert_eq!(snapshot.runtimes, 3);
+        assert_eq!(snapshot.workers, 3);
+
+        let output = export_runtime_metrics(&snapshot, 0.0);
+        assert!(output.contains("yatagarasu_runtimes 3"));
+        assert!(output.contains("yatagarasu_runtime_workers 3"));
+    }
+}
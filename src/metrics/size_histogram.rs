@@ -0,0 +1,169 @@
+//! Per-bucket size distributions.
+//!
+//! A [`SizeHistogram`] counts observations into fixed upper bounds per label,
+//! exported as a Prometheus histogram (`_bucket{le=...}`, `_sum`, `_count`).
+//! Bounds are fixed at construction so recording is a shared read lock, a
+//! bound lookup and two relaxed `fetch_add`s; the write lock is only taken the
+//! first time a label is seen.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::RwLock;
+
+/// Request path lengths in bytes
+pub const PATH_LENGTH_BOUNDS: &[u64] = &[16, 32, 64, 128, 256, 512, 1024, 2048];
+
+/// Object and range sizes in bytes (1 KiB to 1 GiB, powers of four)
+pub const OBJECT_SIZE_BOUNDS: &[u64] = &[
+    1 << 10,
+    1 << 12,
+    1 << 14,
+    1 << 16,
+    1 << 18,
+    1 << 20,
+    1 << 22,
+    1 << 24,
+    1 << 26,
+    1 << 28,
+    1 << 30,
+];
+
+/// Durations in milliseconds (5 ms to 30 s)
+pub const DURATION_MS_BOUNDS: &[u64] = &[
+    5, 10, 25, 50, 100, 250, 500, 1_000, 2_500, 5_000, 10_000, 30_000,
+];
+
+/// Counts for one label: one slot per bound plus `+Inf`
+#[derive(Debug)]
+struct Series {
+    counts: Box<[AtomicU64]>,
+    sum: AtomicU64,
+}
+
+impl Series {
+    fn new(bounds: usize) -> Self {
+        Self {
+            counts: (0..=bounds).map(|_| AtomicU64::new(0)).collect(),
+            sum: AtomicU64::new(0),
+        }
+    }
+}
+
+/// Histogram with fixed bounds, keyed by label value
+#[derive(Debug)]
+pub struct SizeHistogram {
+    bounds: &'static [u64],
+    series: RwLock<HashMap<String, Series>>,
+}
+
+impl SizeHistogram {
+    /// Create an empty histogram with ascending upper `bounds`
+    pub fn new(bounds: &'static [u64]) -> Self {
+        Self {
+            bounds,
+            series: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Record one observation for `label`
+    pub fn observe(&self, label: &str, value: u64) {
+        let slot = self.bounds.partition_point(|&bound| bound < value);
+        let record = |series: &Series| {
+            series.counts[slot].fetch_add(1, Ordering::Relaxed);
+            series.sum.fetch_add(value, Ordering::Relaxed);
+        };
+
+        if let Ok(series) = self.series.read() {
+            if let Some(series) = series.get(label) {
+                record(series);
+                return;
+            }
+        }
+        if let Ok(mut series) = self.series.write() {
+            record(
+                series
+                    .entry(label.to_string())
+                    .or_insert_with(|| Series::new(self.bounds.len())),
+            );
+        }
+    }
+
+    /// Number of observations recorded for `label`
+    pub fn count(&self, label: &str) -> u64 {
+        self.series
+            .read()
+            .ok()
+            .and_then(|series| {
+                series.get(label).map(|s| {
+                    s.counts
+                        .iter()
+                        .map(|c| c.load(Ordering::Relaxed))
+                        .sum::<u64>()
+                })
+            })
+            .unwrap_or(0)
+    }
+
+    /// Append the Prometheus exposition for every label to `output`
+    pub fn export(&self, output: &mut String, name: &str, label_name: &str, help: &str) {
+        output.push_str(&format!("\n# HELP {} {}\n", name, help));
+        output.push_str(&format!("# TYPE {} histogram\n", name));
+        let Ok(series) = self.series.read() else {
+            return;
+        };
+        let mut labels: Vec<&String> = series.keys().collect();
+        labels.sort();
+        for label in labels {
+            let s = &series[label];
+            let mut cumulative = 0;
+            for (slot, count) in s.counts.iter().enumerate() {
+                cumulative += count.load(Ordering::Relaxed);
+                let le = self
+                    .bounds
+                    .get(slot)
+                    .map(|bound| bound.to_string())
+                    .unwrap_or_else(|| "+Inf".to_string());
+                output.push_str(&format!(
+                    "{}_bucket{{{}=\"{}\",le=\"{}\"}} {}\n",
+                    name, label_name, label, le, cumulative
+                ));
+            }
+            output.push_str(&format!(
+                "{}_sum{{{}=\"{}\"}} {}\n",
+                name,
+                label_name,
+                label,
+                s.sum.load(Ordering::Relaxed)
+            ));
+            output.push_str(&format!(
+                "{}_count{{{}=\"{}\"}} {}\n",
+                name, label_name, label, cumulative
+            ));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_observe_and_export_cumulative_buckets() {
+        let histogram = SizeHistogram::new(&[10, 100]);
+        histogram.observe("media", 10);
+        histogram.observe("media", 50);
+        histogram.observe("media", 5000);
+
+        assert_eq!(histogram.count("media"), 3);
+        assert_eq!(histogram.count("docs"), 0);
+
+        let mut output = String::new();
+        histogram.export(&mut output, "sizes", "bucket", "Sizes");
+        assert!(output.contains("# TYPE sizes histogram"));
+        assert!(output.contains("sizes_bucket{bucket=\"media\",le=\"10\"} 1"));
+        assert!(output.contains("sizes_bucket{bucket=\"media\",le=\"100\"} 2"));
+        assert!(output.contains("sizes_bucket{bucket=\"media\",le=\"+Inf\"} 3"));
+        assert!(output.contains("sizes_sum{bucket=\"media\"} 5060"));
+        assert!(output.contains("sizes_count{bucket=\"media\"} 3"));
+    }
+}
@@ -17,6 +17,10 @@ pub struct ObservabilityConfig {
     /// Slow query logging configuration
     #[serde(default)]
     pub slow_query: SlowQueryConfig,
+
+    /// Lifecycle event log (started, reloads, breaker transitions)
+    #[serde(default)]
+    pub event_log: EventLogConfig,
 }
 
 /// OpenTelemetry tracing configuration
@@ -157,6 +161,48 @@ impl Default for SlowQueryConfig {
     }
 }
 
+/// Where lifecycle events are written
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EventLogOutput {
+    #[default]
+    Stdout,
+    Stderr,
+    File,
+}
+
+/// Lifecycle event log configuration
+///
+/// Events are written as one JSON object per line, separate from request
+/// logs and tracing output, so automation can consume them directly.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EventLogConfig {
+    /// Enable the event log
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Output: "stdout", "stderr" or "file" (default: stdout)
+    #[serde(default)]
+    pub output: EventLogOutput,
+
+    /// File appended to when output is "file"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+}
+
+impl EventLogConfig {
+    /// Validate configuration
+    pub fn validate(&self) -> Result<(), String> {
+        if self.enabled
+            && self.output == EventLogOutput::File
+            && self.path.as_deref().map_or(true, str::is_empty)
+        {
+            return Err("observability.event_log.path is required for file output".to_string());
+        }
+        Ok(())
+    }
+}
+
 // Default value functions
 fn default_exporter() -> String {
     "otlp".to_string()
@@ -263,6 +309,7 @@ mod tests {
         assert!(!config.tracing.enabled);
         assert!(!config.request_logging.log_requests);
         assert!(!config.slow_query.enabled);
+        assert!(!config.event_log.enabled);
     }
 
     #[test]
@@ -520,4 +567,21 @@ slow_query:
         assert!(headers.contains(&"cookie".to_string()));
         assert!(headers.contains(&"x-amz-security-token".to_string()));
     }
+
+    #[test]
+    fn test_event_log_config_validation() {
+        let yaml =
+            "event_log:\n  enabled: true\n  output: file\n  path: /var/log/yatagarasu/events.jsonl";
+        let config: ObservabilityConfig = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(config.event_log.output, EventLogOutput::File);
+        assert!(config.event_log.validate().is_ok());
+
+        let config = EventLogConfig {
+            enabled: true,
+            output: EventLogOutput::File,
+            path: None,
+        };
+        assert!(config.validate().is_err());
+        assert!(EventLogConfig::default().validate().is_ok());
+    }
 }
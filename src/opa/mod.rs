@@ -290,6 +290,9 @@ pub struct OpaInput {
     /// Client IP address (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
     client_ip: Option<String>,
+    /// Subject of the verified client certificate (optional)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client_cert_subject: Option<String>,
 }
 
 impl OpaInput {
@@ -307,9 +310,16 @@ impl OpaInput {
             path,
             method,
             client_ip,
+            client_cert_subject: None,
         }
     }
 
+    /// Add the subject of the client certificate the connection verified
+    pub fn with_client_cert_subject(mut self, subject: Option<String>) -> Self {
+        self.client_cert_subject = subject;
+        self
+    }
+
     /// Get the JWT claims
     pub fn jwt_claims(&self) -> &JsonValue {
         &self.jwt_claims
@@ -335,6 +345,11 @@ impl OpaInput {
         self.client_ip.as_deref()
     }
 
+    /// Get the client certificate subject
+    pub fn client_cert_subject(&self) -> Option<&str> {
+        self.client_cert_subject.as_deref()
+    }
+
     /// Generate a deterministic cache key based on input content
     ///
     /// The cache key is a SHA-256 hash of the serialized input, ensuring:
@@ -519,6 +534,11 @@ mod tests {
         assert!(json.contains("\"path\":\"/path\""));
         assert!(json.contains("\"method\":\"GET\""));
         assert!(json.contains("\"client_ip\":\"1.2.3.4\""));
+        assert!(!json.contains("client_cert_subject"));
+
+        let input = input.with_client_cert_subject(Some("CN=app,O=Example".to_string()));
+        let json = serde_json::to_string(&input).unwrap();
+        assert!(json.contains("\"client_cert_subject\":\"CN=app,O=Example\""));
     }
 
     #[test]
@@ -0,0 +1,73 @@
+//! Local or NFS-mounted filesystem origin.
+//!
+//! Objects are files under a root directory (`<root>/<key>`), read by the
+//! proxy itself rather than forwarded to a peer. Keys are resolved
+//! component by component so `..` or an absolute key cannot leave the root.
+
+use std::path::{Component, Path, PathBuf};
+
+use pingora_http::RequestHeader;
+
+use super::{Origin, UpstreamTarget};
+use crate::config::FilesystemOriginConfig;
+
+/// Origin serving files below a root directory
+pub struct FilesystemOrigin {
+    root: PathBuf,
+}
+
+impl FilesystemOrigin {
+    /// Create an origin for a validated config
+    pub fn new(config: &FilesystemOriginConfig) -> Self {
+        Self {
+            root: PathBuf::from(&config.root),
+        }
+    }
+}
+
+impl Origin for FilesystemOrigin {
+    fn kind(&self) -> &'static str {
+        "filesystem"
+    }
+
+    fn upstream(&self) -> Option<&UpstreamTarget> {
+        None
+    }
+
+    fn prepare_request(&self, _key: &str, _request: &mut RequestHeader) -> Result<(), String> {
+        Err("Filesystem origins are served in-process".to_string())
+    }
+
+    fn local_path(&self, key: &str) -> Result<Option<PathBuf>, String> {
+        let mut path = self.root.clone();
+        for component in Path::new(key).components() {
+            match component {
+                Component::Normal(part) => path.push(part),
+                Component::CurDir => {}
+                _ => return Err(format!("Object key '{}' escapes the origin root", key)),
+            }
+        }
+        Ok(Some(path))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_local_path_stays_under_root() {
+        let origin = FilesystemOrigin::new(&FilesystemOriginConfig {
+            root: "/mnt/nas/media".to_string(),
+        });
+        assert!(origin.upstream().is_none());
+        assert_eq!(
+            origin.local_path("photos/./cat.jpg").unwrap(),
+            Some(PathBuf::from("/mnt/nas/media/photos/cat.jpg"))
+        );
+
+        for key in ["../etc/passwd", "photos/../../secret", "/etc/passwd"] {
+            assert!(origin.local_path(key).is_err(), "{}", key);
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! Plain HTTP(S) origin.
+//!
+//! Objects are fetched from `<url>/<key>`. The client's query string and
+//! `Authorization` header are not forwarded; configured `headers` are added
+//! instead, so an origin credential never reaches clients.
+
+use std::time::Duration;
+
+use pingora_http::RequestHeader;
+
+use super::{Origin, UpstreamTarget};
+use crate::config::HttpOriginConfig;
+use crate::s3::uri_encode;
+
+/// Origin forwarding to a fixed HTTP(S) base URL
+pub struct HttpOrigin {
+    target: UpstreamTarget,
+    /// `Host` header value (port only when non-default)
+    authority: String,
+    /// Path prefix without a trailing slash
+    base_path: String,
+    headers: Vec<(String, String)>,
+}
+
+impl HttpOrigin {
+    /// Parse the base URL of a validated config
+    pub fn new(config: &HttpOriginConfig) -> Result<Self, String> {
+        let (tls, rest) = if let Some(rest) = config.url.strip_prefix("https://") {
+            (true, rest)
+        } else if let Some(rest) = config.url.strip_prefix("http://") {
+            (false, rest)
+        } else {
+            return Err(format!("Unsupported origin URL '{}'", config.url));
+        };
+        let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+        let default_port = if tls { 443 } else { 80 };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (
+                host.to_string(),
+                port.parse::<u16>()
+                    .map_err(|_| format!("Invalid port in origin URL '{}'", config.url))?,
+            ),
+            None => (authority.to_string(), default_port),
+        };
+
+        Ok(Self {
+            target: UpstreamTarget {
+                host,
+                port,
+                tls,
+                timeout: Duration::from_secs(config.timeout),
+            },
+            authority: authority.to_string(),
+            base_path: path.trim_end_matches('/').to_string(),
+            headers: config
+                .headers
+                .iter()
+                .map(|(name, value)| (name.clone(), value.clone()))
+                .collect(),
+        })
+    }
+
+    /// Upstream path for an object key
+    fn path_for(&self, key: &str) -> String {
+        if self.base_path.is_empty() {
+            format!("/{}", uri_encode(key, false))
+        } else {
+            format!("/{}/{}", self.base_path, uri_encode(key, false))
+        }
+    }
+}
+
+impl Origin for HttpOrigin {
+    fn kind(&self) -> &'static str {
+        "http"
+    }
+
+    fn upstream(&self) -> Option<&UpstreamTarget> {
+        Some(&self.target)
+    }
+
+    fn prepare_request(&self, key: &str, request: &mut RequestHeader) -> Result<(), String> {
+        let uri = self
+            .path_for(key)
+            .parse()
+            .map_err(|e| format!("Invalid origin URI: {}", e))?;
+        request.set_uri(uri);
+        request.remove_header("authorization");
+        request
+            .insert_header("host", self.authority.as_str())
+            .map_err(|e| e.to_string())?;
+        for (name, value) in &self.headers {
+            request
+                .insert_header(name.clone(), value.as_str())
+                .map_err(|e| e.to_string())?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_http_origin_prepares_request() {
+        let origin = HttpOrigin::new(&HttpOriginConfig {
+            url: "https://assets.internal:8443/static/".to_string(),
+            headers: HashMap::from([("x-origin-token".to_string(), "s3cret".to_string())]),
+            timeout: 5,
+        })
+        .unwrap();
+        let target = origin.upstream().unwrap();
+        assert_eq!(
+            (target.host.as_str(), target.port, target.tls),
+            ("assets.internal", 8443, true)
+        );
+
+        let mut request = RequestHeader::build("GET", b"/assets/css/a b.css?v=1", None).unwrap();
+        request
+            .insert_header("authorization", "Bearer client")
+            .unwrap();
+        origin.prepare_request("css/a b.css", &mut request).unwrap();
+
+        assert_eq!(request.uri.to_string(), "/static/css/a%20b.css");
+        assert_eq!(request.headers.get("host").unwrap(), "assets.internal:8443");
+        assert_eq!(request.headers.get("x-origin-token").unwrap(), "s3cret");
+        assert!(request.headers.get("authorization").is_none());
+    }
+}
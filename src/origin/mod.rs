@@ -0,0 +1,80 @@
+//! Pluggable origins for buckets that are not backed by S3.
+//!
+//! S3 buckets keep the built-in S3 path (replicas, SigV4, signing quirks).
+//! A bucket with an `origin` section is served through an [`Origin`]
+//! instead, behind the same routing, auth, cache and audit pipeline.
+//!
+//! An origin either names a network peer the proxy forwards to
+//! ([`Origin::upstream`]) and rewrites the forwarded request for an object
+//! key ([`Origin::prepare_request`]), or it is served in-process from a
+//! local path ([`Origin::local_path`]).
+
+pub mod filesystem;
+pub mod http;
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+
+use pingora_http::RequestHeader;
+
+use crate::config::{Config, OriginConfig};
+
+/// Network peer of a forwarding origin
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UpstreamTarget {
+    pub host: String,
+    pub port: u16,
+    pub tls: bool,
+    /// Connect, read and write timeout
+    pub timeout: Duration,
+}
+
+/// Backend serving a bucket's objects
+pub trait Origin: Send + Sync {
+    /// Backend name for logs and metrics (`http`, `filesystem`)
+    fn kind(&self) -> &'static str;
+
+    /// Peer to forward requests to, `None` for origins served in-process
+    fn upstream(&self) -> Option<&UpstreamTarget>;
+
+    /// Rewrite the forwarded request (path, `Host`, credentials) for `key`
+    fn prepare_request(&self, key: &str, request: &mut RequestHeader) -> Result<(), String>;
+
+    /// File backing `key` for origins served in-process, `None` otherwise
+    ///
+    /// Errors when the key would escape the origin's root.
+    fn local_path(&self, _key: &str) -> Result<Option<PathBuf>, String> {
+        Ok(None)
+    }
+}
+
+/// Shared origin handle
+pub type SharedOrigin = Arc<dyn Origin>;
+
+/// Build the origin for one bucket's `origin` section
+pub fn build(config: &OriginConfig) -> Result<SharedOrigin, String> {
+    match config {
+        OriginConfig::Http(http) => Ok(Arc::new(http::HttpOrigin::new(http)?)),
+        OriginConfig::Filesystem(fs) => Ok(Arc::new(filesystem::FilesystemOrigin::new(fs))),
+    }
+}
+
+/// Build origins for every bucket with an `origin` section
+pub fn from_config(config: &Config) -> HashMap<String, SharedOrigin> {
+    config
+        .buckets
+        .iter()
+        .filter_map(|bucket| {
+            let origin_config = bucket.origin.as_ref()?;
+            match build(origin_config) {
+                Ok(origin) => Some((bucket.name.clone(), origin)),
+                Err(e) => {
+                    tracing::error!(bucket = %bucket.name, error = %e, "Failed to initialize origin");
+                    None
+                }
+            }
+        })
+        .collect()
+}
@@ -2,13 +2,84 @@
 // Phase 13: Request Pipeline Integration
 
 use crate::audit::RequestContext as AuditRequestContext;
+use crate::auth::sigv4::PayloadDigest;
 use crate::auth::Claims;
-use crate::config::BucketConfig;
+use crate::cache::tiered::RangeChunkFill;
+use crate::circuit_breaker::UpstreamOutcome;
+use crate::config::{BucketConfig, CacheOverrideMode, Config};
 use crate::request_coalescing::StreamLeader;
+use crate::router::Router;
+use crate::s3::list::ListingFormat;
+use crate::security::virus_scan::UploadScanBuffer;
+use pingora_http::RequestHeader;
 use std::collections::HashMap;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio::sync::OwnedSemaphorePermit;
 use uuid::Uuid;
 
+/// Extract headers from Pingora RequestHeader into HashMap.
+///
+/// Keys are the lowercase header names as stored by the `http` crate, so
+/// lookups never need a case-insensitive fallback. Headers with non-UTF8
+/// values are skipped. The map is pre-sized to avoid rehashing.
+pub fn extract_headers(req: &RequestHeader) -> HashMap<String, String> {
+    let mut headers = HashMap::with_capacity(req.headers.len());
+    for (name, value) in req.headers.iter() {
+        if let Ok(value_str) = value.to_str() {
+            // as_str() avoids the Display round-trip of to_string()
+            headers.insert(name.as_str().to_owned(), value_str.to_owned());
+        }
+    }
+    headers
+}
+
+/// Extract query parameters from URI.
+///
+/// Parses the query string from the request URI and returns key-value pairs.
+/// Values are URL-decoded; values without escapes are copied without going
+/// through the decoder.
+pub fn extract_query_params(req: &RequestHeader) -> HashMap<String, String> {
+    let query = match req.uri.query() {
+        Some(query) if !query.is_empty() => query,
+        _ => return HashMap::new(),
+    };
+
+    let mut params = HashMap::with_capacity(query.matches('&').count() + 1);
+    for pair in query.split('&') {
+        if let Some((key, value)) = pair.split_once('=') {
+            let value = if value.contains('%') {
+                urlencoding::decode(value).unwrap_or_default().into_owned()
+            } else {
+                value.to_owned()
+            };
+            params.insert(key.to_owned(), value);
+        }
+    }
+    params
+}
+
+/// Request phases timed for the `Server-Timing` header
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Phase {
+    /// Authentication and authorization (JWT, OPA, OpenFGA)
+    Authz,
+    /// Cache lookup, including any freshness check
+    Cache,
+    /// First upstream attempt until the response headers arrive
+    Upstream,
+}
+
+impl Phase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Phase::Authz => "authz",
+            Phase::Cache => "cache",
+            Phase::Upstream => "upstream",
+        }
+    }
+}
+
 /// Request context that holds all information about an HTTP request
 /// as it flows through the middleware pipeline
 #[derive(Debug)]
@@ -34,10 +105,19 @@ pub struct RequestContext {
     response_last_modified: Option<String>,
     /// Cache-Control header from S3 response (for RFC 7234 compliance)
     response_cache_control: Option<String>,
+    /// Expires and Date headers from S3 response (entry TTL without max-age)
+    response_expires: Option<String>,
+    response_date: Option<String>,
+    /// Upstream headers stored with the cache entry (`cache.preserve_headers`)
+    response_preserved_headers: Vec<(String, String)>,
     /// Whether to cache this response (based on size, range requests, etc.)
     should_cache_response: bool,
     /// Total response size accumulated so far
     total_response_size: usize,
+    /// Content-Length announced by the upstream response (for client abort accounting)
+    response_content_length: Option<u64>,
+    /// Body bytes received from upstream so far
+    upstream_bytes_received: u64,
     /// Retry attempt counter (0-indexed: 0 = first attempt, 1 = first retry)
     retry_attempt: u32,
     /// Audit context
@@ -46,9 +126,49 @@ pub struct RequestContext {
     image_params: Option<crate::image_optimizer::ImageParams>,
     /// Whether the current response is being optimized (Phase: Image Optimization)
     optimizing_image: bool,
+    /// Request failed the bucket's hotlink check and is served watermarked
+    hotlinked: bool,
+    /// All origins for the bucket are down; only the cache may answer
+    origin_unavailable: bool,
+    /// Last outcome observed from the origin (None if it was never contacted)
+    upstream_outcome: Option<UpstreamOutcome>,
+    /// Bucket bulkhead slot, held until the request completes
+    bulkhead_permit: Option<OwnedSemaphorePermit>,
+    /// Bytes reserved from the response buffer budget, released with the buffer
+    buffer_permit: Option<OwnedSemaphorePermit>,
+    /// Range cache chunks being collected from a 206 response
+    range_fill: Option<RangeChunkFill>,
+    /// Cache the buffered response in the memory layer only (size class policy)
+    cache_memory_only: bool,
     /// Streaming coalescer leader handle
     /// If Some, this request is the leader and must broadcast data to followers
     streaming_leader: Option<StreamLeader>,
+    /// When the request was received (for phase and total durations)
+    started_at: Instant,
+    /// Durations of completed phases, in the order they finished
+    phase_timings: Vec<(Phase, Duration)>,
+    /// When the first upstream attempt started
+    upstream_started_at: Option<Instant>,
+    /// Preload `Link` header for the response (early hints manifest entry)
+    preload_links: Option<String>,
+    /// Bucket listing request and the format to answer it in
+    listing: Option<ListingFormat>,
+    /// Upload body held back for a virus scan
+    upload_scan: Option<UploadScanBuffer>,
+    /// Cache behavior requested by a trusted client via `X-Yat-Cache`
+    cache_override: Option<CacheOverrideMode>,
+    /// Generation of the config the request was routed with
+    config_generation: u64,
+    /// Object key resolved when the request was routed
+    s3_key: Option<String>,
+    /// Config loaded when the request arrived, used for its whole lifecycle
+    config: Option<Arc<Config>>,
+    /// Router loaded alongside `config`
+    router: Option<Arc<Router>>,
+    /// Subject of the verified client certificate (mutual TLS listener)
+    client_cert_subject: Option<String>,
+    /// Body check against the SigV4 `x-amz-content-sha256` digest
+    payload_digest: Option<PayloadDigest>,
 }
 
 impl RequestContext {
@@ -73,13 +193,38 @@ impl RequestContext {
             response_etag: None,
             response_last_modified: None,
             response_cache_control: None,
+            response_expires: None,
+            response_date: None,
+            response_preserved_headers: Vec::new(),
             should_cache_response: false,
             total_response_size: 0,
+            response_content_length: None,
+            upstream_bytes_received: 0,
             retry_attempt: 0,
             audit: AuditRequestContext::new(),
             image_params: None,
             optimizing_image: false,
+            hotlinked: false,
+            origin_unavailable: false,
+            upstream_outcome: None,
+            bulkhead_permit: None,
+            buffer_permit: None,
+            range_fill: None,
+            cache_memory_only: false,
             streaming_leader: None,
+            started_at: Instant::now(),
+            phase_timings: Vec::new(),
+            upstream_started_at: None,
+            preload_links: None,
+            listing: None,
+            upload_scan: None,
+            cache_override: None,
+            config_generation: 0,
+            s3_key: None,
+            config: None,
+            router: None,
+            client_cert_subject: None,
+            payload_digest: None,
         }
     }
 
@@ -104,13 +249,38 @@ impl RequestContext {
             response_etag: None,
             response_last_modified: None,
             response_cache_control: None,
+            response_expires: None,
+            response_date: None,
+            response_preserved_headers: Vec::new(),
             should_cache_response: false,
             total_response_size: 0,
+            response_content_length: None,
+            upstream_bytes_received: 0,
             retry_attempt: 0,
             audit: AuditRequestContext::new(),
             image_params: None,
             optimizing_image: false,
+            hotlinked: false,
+            origin_unavailable: false,
+            upstream_outcome: None,
+            bulkhead_permit: None,
+            buffer_permit: None,
+            range_fill: None,
+            cache_memory_only: false,
             streaming_leader: None,
+            started_at: Instant::now(),
+            phase_timings: Vec::new(),
+            upstream_started_at: None,
+            preload_links: None,
+            listing: None,
+            upload_scan: None,
+            cache_override: None,
+            config_generation: 0,
+            s3_key: None,
+            config: None,
+            router: None,
+            client_cert_subject: None,
+            payload_digest: None,
         }
     }
 
@@ -139,13 +309,38 @@ impl RequestContext {
             response_etag: None,
             response_last_modified: None,
             response_cache_control: None,
+            response_expires: None,
+            response_date: None,
+            response_preserved_headers: Vec::new(),
             should_cache_response: false,
             total_response_size: 0,
+            response_content_length: None,
+            upstream_bytes_received: 0,
             retry_attempt: 0,
             audit: AuditRequestContext::new(),
             image_params: None,
             optimizing_image: false,
+            hotlinked: false,
+            origin_unavailable: false,
+            upstream_outcome: None,
+            bulkhead_permit: None,
+            buffer_permit: None,
+            range_fill: None,
+            cache_memory_only: false,
             streaming_leader: None,
+            started_at: Instant::now(),
+            phase_timings: Vec::new(),
+            upstream_started_at: None,
+            preload_links: None,
+            listing: None,
+            upload_scan: None,
+            cache_override: None,
+            config_generation: 0,
+            s3_key: None,
+            config: None,
+            router: None,
+            client_cert_subject: None,
+            payload_digest: None,
         }
     }
 
@@ -209,6 +404,16 @@ impl RequestContext {
         self.bucket_config.as_ref()
     }
 
+    /// Whether a log line at `level` passes the bucket's `logging.level`
+    ///
+    /// Always true before routing or when the bucket sets no level.
+    pub fn log_enabled(&self, level: tracing::Level) -> bool {
+        self.bucket_config
+            .as_ref()
+            .and_then(|b| b.logging.as_ref())
+            .map_or(true, |l| l.is_enabled(level))
+    }
+
     /// Set the JWT claims for this request
     pub fn set_claims(&mut self, claims: Claims) {
         self.claims = Some(claims);
@@ -236,10 +441,17 @@ impl RequestContext {
         self.total_response_size = 0;
     }
 
+    /// Buffer the response without storing it in the cache (e.g., to optimize an image)
+    pub fn enable_response_buffering_only(&mut self) {
+        self.enable_response_buffering();
+        self.should_cache_response = false;
+    }
+
     /// Disable response buffering (e.g., for range requests or large files)
     pub fn disable_response_buffering(&mut self) {
         self.response_buffer = None;
         self.should_cache_response = false;
+        self.buffer_permit = None;
     }
 
     /// Check if response buffering is enabled
@@ -300,9 +512,37 @@ impl RequestContext {
         self.response_cache_control.as_deref()
     }
 
+    /// Set response Expires and Date from upstream headers (for the entry TTL)
+    pub fn set_response_expires(&mut self, expires: Option<String>, date: Option<String>) {
+        self.response_expires = expires;
+        self.response_date = date;
+    }
+
+    /// Get response Expires header value
+    pub fn response_expires(&self) -> Option<&str> {
+        self.response_expires.as_deref()
+    }
+
+    /// Get response Date header value
+    pub fn response_date(&self) -> Option<&str> {
+        self.response_date.as_deref()
+    }
+
+    /// Set upstream headers to store with the cache entry
+    pub fn set_response_preserved_headers(&mut self, headers: Vec<(String, String)>) {
+        self.response_preserved_headers = headers;
+    }
+
+    /// Upstream headers to store with the cache entry
+    pub fn response_preserved_headers(&self) -> &[(String, String)] {
+        &self.response_preserved_headers
+    }
+
     /// Check if this response should be cached
+    ///
+    /// Always false under a trusted `X-Yat-Cache: bypass`.
     pub fn should_cache_response(&self) -> bool {
-        self.should_cache_response
+        self.should_cache_response && self.cache_override != Some(CacheOverrideMode::Bypass)
     }
 
     /// Get total response size accumulated so far
@@ -310,6 +550,26 @@ impl RequestContext {
         self.total_response_size
     }
 
+    /// Set the Content-Length announced by the upstream response
+    pub fn set_response_content_length(&mut self, content_length: u64) {
+        self.response_content_length = Some(content_length);
+    }
+
+    /// Get the Content-Length announced by the upstream response
+    pub fn response_content_length(&self) -> Option<u64> {
+        self.response_content_length
+    }
+
+    /// Record body bytes received from upstream
+    pub fn add_upstream_bytes_received(&mut self, bytes: u64) {
+        self.upstream_bytes_received += bytes;
+    }
+
+    /// Get body bytes received from upstream so far
+    pub fn upstream_bytes_received(&self) -> u64 {
+        self.upstream_bytes_received
+    }
+
     /// Get current retry attempt number (0-indexed)
     pub fn retry_attempt(&self) -> u32 {
         self.retry_attempt
@@ -346,6 +606,82 @@ impl RequestContext {
         self.optimizing_image
     }
 
+    /// Mark the request as hotlinked (watermark action)
+    pub fn set_hotlinked(&mut self) {
+        self.hotlinked = true;
+    }
+
+    /// Check if the request is served watermarked because of hotlinking
+    pub fn is_hotlinked(&self) -> bool {
+        self.hotlinked
+    }
+
+    /// Mark the bucket's origins as down (degraded mode serves from cache only)
+    pub fn set_origin_unavailable(&mut self) {
+        self.origin_unavailable = true;
+    }
+
+    /// Check if the request may only be answered from cache
+    pub fn is_origin_unavailable(&self) -> bool {
+        self.origin_unavailable
+    }
+
+    /// Record what the origin returned; a retry overwrites earlier attempts
+    pub fn set_upstream_outcome(&mut self, outcome: UpstreamOutcome) {
+        self.upstream_outcome = Some(outcome);
+    }
+
+    /// Get the final origin outcome for circuit breaker accounting
+    pub fn upstream_outcome(&self) -> Option<UpstreamOutcome> {
+        self.upstream_outcome
+    }
+
+    /// Cache variant for the response: image parameters, kept apart for
+    /// hotlinked requests so watermarked images never reach allowed clients
+    pub fn cache_variant(&self) -> Option<String> {
+        self.image_params.as_ref().map(|params| {
+            let variant = params.to_cache_key();
+            if self.hotlinked {
+                format!("{}_hotlink", variant)
+            } else {
+                variant
+            }
+        })
+    }
+
+    /// Hold a bulkhead slot for the rest of the request
+    pub fn set_bulkhead_permit(&mut self, permit: OwnedSemaphorePermit) {
+        self.bulkhead_permit = Some(permit);
+    }
+
+    /// Add bytes reserved from the response buffer budget to this request's share
+    pub fn add_buffer_permit(&mut self, permit: OwnedSemaphorePermit) {
+        match self.buffer_permit.as_mut() {
+            Some(held) => held.merge(permit),
+            None => self.buffer_permit = Some(permit),
+        }
+    }
+
+    /// Collect range cache chunks from this response
+    pub fn set_range_fill(&mut self, fill: RangeChunkFill) {
+        self.range_fill = Some(fill);
+    }
+
+    /// Range cache fill in progress, if any
+    pub fn range_fill_mut(&mut self) -> Option<&mut RangeChunkFill> {
+        self.range_fill.as_mut()
+    }
+
+    /// Store the buffered response in the memory layer only
+    pub fn set_cache_memory_only(&mut self) {
+        self.cache_memory_only = true;
+    }
+
+    /// Whether the buffered response goes to the memory layer only
+    pub fn is_cache_memory_only(&self) -> bool {
+        self.cache_memory_only
+    }
+
     /// Set the streaming leader handle (Streaming Coalescing)
     pub fn set_streaming_leader(&mut self, leader: StreamLeader) {
         self.streaming_leader = Some(leader);
@@ -360,6 +696,128 @@ impl RequestContext {
     pub fn streaming_leader(&self) -> Option<&StreamLeader> {
         self.streaming_leader.as_ref()
     }
+    /// Time since the request was received
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    /// Record how long a phase took, replacing an earlier measurement
+    pub fn record_phase(&mut self, phase: Phase, duration: Duration) {
+        self.phase_timings.retain(|(p, _)| *p != phase);
+        self.phase_timings.push((phase, duration));
+    }
+
+    /// Durations of completed phases, in the order they finished
+    pub fn phase_timings(&self) -> &[(Phase, Duration)] {
+        &self.phase_timings
+    }
+
+    /// Start the upstream phase (retries keep the first start time)
+    pub fn mark_upstream_start(&mut self) {
+        self.upstream_started_at.get_or_insert_with(Instant::now);
+    }
+
+    /// Set the preload `Link` header value for the response
+    pub fn set_preload_links(&mut self, links: String) {
+        self.preload_links = Some(links);
+    }
+
+    /// Preload `Link` header value, if the object has early hints
+    pub fn preload_links(&self) -> Option<&str> {
+        self.preload_links.as_deref()
+    }
+
+    /// Mark the request as a bucket listing answered in `format`
+    pub fn set_listing(&mut self, format: ListingFormat) {
+        self.listing = Some(format);
+    }
+
+    /// Listing format, if this is a bucket listing request
+    pub fn listing(&self) -> Option<ListingFormat> {
+        self.listing
+    }
+
+    /// Upload scan buffer, created on first use
+    pub fn upload_scan_buffer(&mut self) -> &mut UploadScanBuffer {
+        self.upload_scan
+            .get_or_insert_with(UploadScanBuffer::default)
+    }
+
+    /// Apply a trusted client's cache override
+    pub fn set_cache_override(&mut self, mode: CacheOverrideMode) {
+        self.cache_override = Some(mode);
+    }
+
+    /// Cache override in effect for this request
+    pub fn cache_override(&self) -> Option<CacheOverrideMode> {
+        self.cache_override
+    }
+
+    /// Pin the config and router loaded when the request arrived
+    ///
+    /// Later hooks read these instead of the live `ArcSwap`s, so a reload in
+    /// the middle of a request cannot mix two config generations.
+    pub fn set_snapshot(&mut self, config: Arc<Config>, router: Arc<Router>) {
+        self.config_generation = config.generation;
+        self.config = Some(config);
+        self.router = Some(router);
+    }
+
+    /// Config snapshot pinned by `set_snapshot`
+    pub fn config(&self) -> Option<&Arc<Config>> {
+        self.config.as_ref()
+    }
+
+    /// Router snapshot pinned by `set_snapshot`
+    pub fn router(&self) -> Option<&Arc<Router>> {
+        self.router.as_ref()
+    }
+
+    /// Record the config generation and object key the request was routed with
+    ///
+    /// Later hooks use these instead of re-reading the live router, so a
+    /// reload in the middle of a request cannot change where it goes.
+    pub fn set_routing(&mut self, config_generation: u64, s3_key: String) {
+        self.config_generation = config_generation;
+        self.s3_key = Some(s3_key);
+    }
+
+    /// Generation of the config the request was routed with
+    pub fn config_generation(&self) -> u64 {
+        self.config_generation
+    }
+
+    /// Object key resolved at routing time (empty before routing)
+    pub fn s3_key(&self) -> &str {
+        self.s3_key.as_deref().unwrap_or_default()
+    }
+
+    /// Record the subject of the client certificate the connection verified
+    pub fn set_client_cert_subject(&mut self, subject: Option<String>) {
+        self.client_cert_subject = subject;
+    }
+
+    /// Subject of the verified client certificate, e.g. `CN=app,O=Example`
+    pub fn client_cert_subject(&self) -> Option<&str> {
+        self.client_cert_subject.as_deref()
+    }
+
+    /// Check the request body against the digest its SigV4 signature covers
+    pub fn set_payload_digest(&mut self, digest: PayloadDigest) {
+        self.payload_digest = Some(digest);
+    }
+
+    /// Body digest check, for SigV4 requests that declared a digest
+    pub fn payload_digest(&mut self) -> Option<&mut PayloadDigest> {
+        self.payload_digest.as_mut()
+    }
+
+    /// End the upstream phase, once response headers have arrived
+    pub fn finish_upstream(&mut self) {
+        if let Some(started) = self.upstream_started_at {
+            self.record_phase(Phase::Upstream, started.elapsed());
+        }
+    }
 }
 
 // Manual Clone implementation because StreamLeader cannot implement Clone
@@ -381,13 +839,38 @@ impl Clone for RequestContext {
             response_etag: self.response_etag.clone(),
             response_last_modified: self.response_last_modified.clone(),
             response_cache_control: self.response_cache_control.clone(),
+            response_expires: self.response_expires.clone(),
+            response_date: self.response_date.clone(),
+            response_preserved_headers: self.response_preserved_headers.clone(),
             should_cache_response: self.should_cache_response,
             total_response_size: self.total_response_size,
+            response_content_length: self.response_content_length,
+            upstream_bytes_received: self.upstream_bytes_received,
             retry_attempt: self.retry_attempt,
             audit: self.audit.clone(),
             image_params: self.image_params.clone(),
             optimizing_image: self.optimizing_image,
+            hotlinked: self.hotlinked,
+            origin_unavailable: self.origin_unavailable,
+            upstream_outcome: self.upstream_outcome,
+            bulkhead_permit: None,  // Not shared - the original keeps the slot
+            buffer_permit: None,    // Not shared - the original keeps the reservation
+            range_fill: None,       // Not shared - the original collects the chunks
             streaming_leader: None, // Cannot clone - RAII handle
+            cache_memory_only: self.cache_memory_only,
+            started_at: self.started_at,
+            phase_timings: self.phase_timings.clone(),
+            upstream_started_at: self.upstream_started_at,
+            preload_links: self.preload_links.clone(),
+            listing: self.listing,
+            upload_scan: None, // Not shared - the original holds the body
+            cache_override: self.cache_override,
+            config_generation: self.config_generation,
+            s3_key: self.s3_key.clone(),
+            config: self.config.clone(),
+            router: self.router.clone(),
+            client_cert_subject: self.client_cert_subject.clone(),
+            payload_digest: None, // Not shared - the original checks the body
         }
     }
 }
@@ -402,4 +885,71 @@ mod tests {
         assert_eq!(ctx.method(), "GET");
         assert_eq!(ctx.path(), "/test");
     }
+
+    #[test]
+    fn test_cache_variant_separates_hotlinked_requests() {
+        let mut ctx = RequestContext::new("GET".to_string(), "/img/a.jpg".to_string());
+        assert_eq!(ctx.cache_variant(), None);
+
+        ctx.set_image_params(crate::image_optimizer::ImageParams::default());
+        assert_eq!(ctx.cache_variant(), Some("default".to_string()));
+
+        ctx.set_hotlinked();
+        assert_eq!(ctx.cache_variant(), Some("default_hotlink".to_string()));
+    }
+
+    #[test]
+    fn test_routing_snapshot_survives_clone() {
+        let mut ctx = RequestContext::new("GET".to_string(), "/products/a.jpg".to_string());
+        assert_eq!(ctx.config_generation(), 0);
+        assert_eq!(ctx.s3_key(), "");
+
+        ctx.set_routing(3, "a.jpg".to_string());
+        let cloned = ctx.clone();
+        assert_eq!(cloned.config_generation(), 3);
+        assert_eq!(cloned.s3_key(), "a.jpg");
+    }
+
+    #[test]
+    fn test_config_snapshot_pins_generation() {
+        let mut ctx = RequestContext::new("GET".to_string(), "/products/a.jpg".to_string());
+        assert!(ctx.config().is_none());
+        assert!(ctx.router().is_none());
+
+        let yaml = r#"
+server:
+  address: "127.0.0.1"
+  port: 8080
+buckets: []
+"#;
+        let mut config = Config::from_yaml_with_env(yaml).unwrap();
+        config.generation = 5;
+        let config = Arc::new(config);
+        ctx.set_snapshot(Arc::clone(&config), Arc::new(Router::new(vec![])));
+
+        let cloned = ctx.clone();
+        assert_eq!(cloned.config_generation(), 5);
+        assert!(Arc::ptr_eq(cloned.config().unwrap(), &config));
+        assert!(cloned.router().is_some());
+    }
+
+    #[test]
+    fn test_extract_headers_uses_lowercase_keys() {
+        let mut req = RequestHeader::build("GET", b"/", None).unwrap();
+        req.insert_header("If-None-Match", "\"abc\"").unwrap();
+
+        let headers = extract_headers(&req);
+        assert_eq!(headers.get("if-none-match"), Some(&"\"abc\"".to_string()));
+        assert!(!headers.contains_key("If-None-Match"));
+    }
+
+    #[test]
+    fn test_extract_query_params_plain_and_encoded_values() {
+        let req = RequestHeader::build("GET", b"/path?plain=abc&enc=a%2Fb&flag", None).unwrap();
+
+        let params = extract_query_params(&req);
+        assert_eq!(params.get("plain"), Some(&"abc".to_string()));
+        assert_eq!(params.get("enc"), Some(&"a/b".to_string()));
+        assert!(!params.contains_key("flag"));
+    }
 }
@@ -0,0 +1,258 @@
+//! Batch GET API for the proxy.
+//!
+//! `POST /batch/get` takes `{"paths": ["/bucket-prefix/key", ...]}` and
+//! returns `application/x-ndjson`, one line per requested path in request
+//! order. Each path goes through the same routing (virtual hosts on the batch
+//! request's `Host`, pattern routes, aliases) and JWT authentication as a
+//! single GET; the body is inlined as base64 when it is small enough.
+//!
+//! Buckets protected by OPA or OpenFGA policies are not served through the
+//! batch endpoint (reported per item as 403) because those policies are
+//! evaluated against individual requests. Buckets with a non-S3 origin are
+//! fetched through it with [`fetch_from_origin`].
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bytes::Bytes;
+use pingora_http::RequestHeader;
+use serde::{Deserialize, Serialize};
+
+use super::filesystem;
+use crate::origin::Origin;
+use crate::s3::ObjectFetch;
+
+/// Maximum accepted size of the JSON request body (1 MB)
+pub const MAX_BATCH_REQUEST_BODY_BYTES: usize = 1024 * 1024;
+
+/// Batch GET request body
+#[derive(Debug, Clone, Deserialize)]
+pub struct BatchGetRequest {
+    /// Object paths as they would be requested through the proxy
+    pub paths: Vec<String>,
+}
+
+impl BatchGetRequest {
+    /// Parse and validate a request body against the configured key limit
+    pub fn parse(body: &[u8], max_keys: usize) -> Result<Self, String> {
+        let request: Self =
+            serde_json::from_slice(body).map_err(|e| format!("Invalid JSON: {}", e))?;
+        if request.paths.is_empty() {
+            return Err("paths must not be empty".to_string());
+        }
+        if request.paths.len() > max_keys {
+            return Err(format!(
+                "Too many paths: {} (maximum {})",
+                request.paths.len(),
+                max_keys
+            ));
+        }
+        Ok(request)
+    }
+}
+
+/// Outcome for a single path in a batch response
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct BatchItemResult {
+    pub path: String,
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    /// Base64-encoded object body
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl BatchItemResult {
+    /// Successful result with the body inlined
+    pub fn found(path: &str, data: &Bytes, content_type: String, etag: Option<String>) -> Self {
+        Self {
+            path: path.to_string(),
+            status: 200,
+            content_type: Some(content_type),
+            etag: etag.filter(|e| !e.is_empty()),
+            size: Some(data.len() as u64),
+            body: Some(STANDARD.encode(data)),
+            error: None,
+        }
+    }
+
+    /// Failed result with an HTTP-style status and message
+    pub fn error(path: &str, status: u16, message: impl Into<String>) -> Self {
+        Self {
+            path: path.to_string(),
+            status,
+            content_type: None,
+            etag: None,
+            size: None,
+            body: None,
+            error: Some(message.into()),
+        }
+    }
+
+    /// Object too large to inline; the client should fetch it directly
+    pub fn too_large(path: &str, size: u64) -> Self {
+        Self {
+            size: Some(size),
+            ..Self::error(path, 413, "Object too large to inline, fetch it directly")
+        }
+    }
+}
+
+/// Render results as newline-delimited JSON
+pub fn to_ndjson(results: &[BatchItemResult]) -> String {
+    let mut output = String::new();
+    for result in results {
+        if let Ok(line) = serde_json::to_string(result) {
+            output.push_str(&line);
+            output.push('\n');
+        }
+    }
+    output
+}
+
+/// Fetch a whole object through a non-S3 origin, like `S3Client::get_object`
+///
+/// Filesystem origins are read in-process; forwarding origins get a GET
+/// prepared by the origin, so the same path and headers are used as for a
+/// proxied request. Bodies over `max_size` are not read.
+pub async fn fetch_from_origin(
+    origin: &dyn Origin,
+    key: &str,
+    max_size: u64,
+) -> Result<ObjectFetch, String> {
+    let Some(target) = origin.upstream() else {
+        let Some(path) = origin.local_path(key)? else {
+            return Ok(ObjectFetch::NotFound);
+        };
+        let Some(object) = filesystem::stat(&path).await else {
+            return Ok(ObjectFetch::NotFound);
+        };
+        if object.size > max_size {
+            return Ok(ObjectFetch::TooLarge { size: object.size });
+        }
+        let body = tokio::fs::read(&path)
+            .await
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        return Ok(ObjectFetch::Found {
+            body: Bytes::from(body),
+            content_type: None,
+            etag: Some(object.etag.trim_matches('"').to_string()),
+            last_modified: object.last_modified,
+            cache_control: None,
+            expires: None,
+        });
+    };
+
+    let mut request = RequestHeader::build("GET", b"/", None).map_err(|e| e.to_string())?;
+    origin.prepare_request(key, &mut request)?;
+    let url = format!(
+        "{}://{}:{}{}",
+        if target.tls { "https" } else { "http" },
+        target.host,
+        target.port,
+        request.uri
+    );
+    let mut upstream = reqwest::Client::new().get(&url).timeout(target.timeout);
+    for (name, value) in request.headers.iter() {
+        upstream = upstream.header(name.as_str(), value.as_bytes());
+    }
+    let response = upstream
+        .send()
+        .await
+        .map_err(|e| format!("{}: {}", url, e))?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND {
+        return Ok(ObjectFetch::NotFound);
+    }
+    if !response.status().is_success() {
+        return Err(format!("{} answered {}", url, response.status()));
+    }
+    if let Some(size) = response.content_length().filter(|size| *size > max_size) {
+        return Ok(ObjectFetch::TooLarge { size });
+    }
+
+    let header = |name: &str| {
+        response
+            .headers()
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    };
+    let content_type = header("content-type");
+    let etag = header("etag").map(|etag| etag.trim_matches('"').to_string());
+    let last_modified = header("last-modified");
+    let cache_control = header("cache-control");
+    let expires = header("expires");
+    let body = response
+        .bytes()
+        .await
+        .map_err(|e| format!("{}: {}", url, e))?;
+    if body.len() as u64 > max_size {
+        return Ok(ObjectFetch::TooLarge {
+            size: body.len() as u64,
+        });
+    }
+    Ok(ObjectFetch::Found {
+        body,
+        content_type,
+        etag,
+        last_modified,
+        cache_control,
+        expires,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_request_parse_and_limits() {
+        let request =
+            BatchGetRequest::parse(br#"{"paths": ["/img/a.jpg", "/img/b.jpg"]}"#, 10).unwrap();
+        assert_eq!(request.paths, vec!["/img/a.jpg", "/img/b.jpg"]);
+
+        assert!(BatchGetRequest::parse(br#"{"paths": []}"#, 10)
+            .unwrap_err()
+            .contains("empty"));
+        assert!(BatchGetRequest::parse(br#"{"paths": ["/a", "/b"]}"#, 1)
+            .unwrap_err()
+            .contains("Too many paths"));
+        assert!(BatchGetRequest::parse(b"not json", 10)
+            .unwrap_err()
+            .contains("Invalid JSON"));
+    }
+
+    #[test]
+    fn test_batch_results_render_as_ndjson() {
+        let results = vec![
+            BatchItemResult::found(
+                "/img/a.txt",
+                &Bytes::from_static(b"hi"),
+                "text/plain".to_string(),
+                Some("abc".to_string()),
+            ),
+            BatchItemResult::error("/img/missing.txt", 404, "Not Found"),
+            BatchItemResult::too_large("/img/huge.bin", 5_000_000),
+        ];
+
+        let output = to_ndjson(&results);
+        let lines: Vec<serde_json::Value> = output
+            .lines()
+            .map(|l| serde_json::from_str(l).unwrap())
+            .collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["status"], 200);
+        assert_eq!(lines[0]["body"], "aGk=");
+        assert_eq!(lines[0]["etag"], "abc");
+        assert_eq!(lines[1]["status"], 404);
+        assert!(lines[1].get("body").is_none());
+        assert_eq!(lines[2]["status"], 413);
+        assert_eq!(lines[2]["size"], 5_000_000);
+    }
+}
@@ -0,0 +1,106 @@
+//! Global memory budget for response bodies buffered for cache population.
+//!
+//! Every cacheable response is held in memory (up to the 10MB item limit)
+//! until it is complete, so many concurrent misses can add up to gigabytes.
+//! A semaphore with one permit per byte caps the total: each buffered chunk
+//! takes permits for its size, held in the request context until the request
+//! finishes. When the budget is exhausted the response stops buffering and is
+//! streamed to the client without being cached.
+
+use std::sync::Arc;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::config::Config;
+use crate::constants::DEFAULT_CACHE_MAX_BUFFER_MEMORY_MB;
+
+/// Byte budget shared by all in-flight cache-population buffers
+pub struct BufferBudget {
+    max_bytes: usize,
+    permits: Arc<Semaphore>,
+}
+
+impl BufferBudget {
+    /// Create a budget of `max_bytes`
+    pub fn new(max_bytes: usize) -> Self {
+        let max_bytes = max_bytes.min(Semaphore::MAX_PERMITS);
+        Self {
+            max_bytes,
+            permits: Arc::new(Semaphore::new(max_bytes)),
+        }
+    }
+
+    /// Reserve `bytes`, or `None` when the budget cannot cover them
+    pub fn try_reserve(&self, bytes: usize) -> Option<OwnedSemaphorePermit> {
+        let bytes = u32::try_from(bytes).ok()?;
+        self.permits.clone().try_acquire_many_owned(bytes).ok()
+    }
+
+    /// Bytes currently reserved by buffering responses
+    pub fn in_use(&self) -> usize {
+        self.max_bytes
+            .saturating_sub(self.permits.available_permits())
+    }
+
+    /// Configured budget in bytes
+    pub fn max_bytes(&self) -> usize {
+        self.max_bytes
+    }
+
+    /// Export budget gauges for Prometheus
+    pub fn export_metrics(&self) -> String {
+        let mut output = String::new();
+        output.push_str(
+            "\n# HELP yatagarasu_response_buffer_bytes Bytes held by responses buffered for the cache\n",
+        );
+        output.push_str("# TYPE yatagarasu_response_buffer_bytes gauge\n");
+        output.push_str(&format!(
+            "yatagarasu_response_buffer_bytes {}\n",
+            self.in_use()
+        ));
+        output.push_str(
+            "\n# HELP yatagarasu_response_buffer_max_bytes Memory budget for responses buffered for the cache\n",
+        );
+        output.push_str("# TYPE yatagarasu_response_buffer_max_bytes gauge\n");
+        output.push_str(&format!(
+            "yatagarasu_response_buffer_max_bytes {}\n",
+            self.max_bytes
+        ));
+        output
+    }
+}
+
+/// Build the budget from `cache.max_buffer_memory_mb`
+pub fn from_config(config: &Config) -> BufferBudget {
+    let max_mb = config
+        .cache
+        .as_ref()
+        .map(|cache| cache.max_buffer_memory_mb)
+        .unwrap_or(DEFAULT_CACHE_MAX_BUFFER_MEMORY_MB);
+    BufferBudget::new((max_mb as usize).saturating_mul(1024 * 1024))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buffer_budget_reserves_and_releases() {
+        let budget = BufferBudget::new(100);
+        let mut held = budget.try_reserve(60).unwrap();
+        assert_eq!(budget.in_use(), 60);
+
+        // Over budget: refused without reserving anything
+        assert!(budget.try_reserve(50).is_none());
+        assert_eq!(budget.in_use(), 60);
+
+        held.merge(budget.try_reserve(40).unwrap());
+        assert_eq!(budget.in_use(), 100);
+
+        drop(held);
+        assert_eq!(budget.in_use(), 0);
+        assert!(budget
+            .export_metrics()
+            .contains("yatagarasu_response_buffer_max_bytes 100"));
+    }
+}
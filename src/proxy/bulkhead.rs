@@ -0,0 +1,174 @@
+//! Per-bucket bulkheads for upstream requests.
+//!
+//! Each bucket with `s3.bulkhead` configured gets its own semaphore. A permit
+//! is taken just before a request goes to S3 and kept in the request context
+//! until the request finishes, so a stalled origin can only tie up its own
+//! bucket's slots.
+//!
+//! With `max_uploads_in_flight`, write (multipart upload) requests draw from a
+//! second semaphore instead, so uploads and downloads cannot starve each other.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::config::Config;
+
+/// Upstream concurrency limit for one bucket
+pub struct Bulkhead {
+    max_in_flight: usize,
+    permits: Arc<Semaphore>,
+    /// Separate pool for write requests, with its size
+    uploads: Option<(usize, Arc<Semaphore>)>,
+}
+
+impl Bulkhead {
+    /// Create a bulkhead allowing `max_in_flight` concurrent upstream requests
+    pub fn new(max_in_flight: usize) -> Self {
+        Self {
+            max_in_flight,
+            permits: Arc::new(Semaphore::new(max_in_flight)),
+            uploads: None,
+        }
+    }
+
+    /// Give write requests their own pool of `max_uploads_in_flight` slots
+    pub fn with_uploads(mut self, max_uploads_in_flight: usize) -> Self {
+        self.uploads = Some((
+            max_uploads_in_flight,
+            Arc::new(Semaphore::new(max_uploads_in_flight)),
+        ));
+        self
+    }
+
+    /// Take a slot for an upstream request, or `None` when the bulkhead is full
+    pub fn try_acquire(&self) -> Option<OwnedSemaphorePermit> {
+        self.permits.clone().try_acquire_owned().ok()
+    }
+
+    /// Take a slot for a write request, from the upload pool when configured
+    pub fn try_acquire_upload(&self) -> Option<OwnedSemaphorePermit> {
+        match &self.uploads {
+            Some((_, permits)) => permits.clone().try_acquire_owned().ok(),
+            None => self.try_acquire(),
+        }
+    }
+
+    /// Write requests holding an upload pool slot (0 without an upload pool)
+    pub fn uploads_in_flight(&self) -> usize {
+        self.uploads
+            .as_ref()
+            .map(|(max, permits)| max.saturating_sub(permits.available_permits()))
+            .unwrap_or(0)
+    }
+
+    /// Upstream requests currently holding a slot
+    pub fn in_flight(&self) -> usize {
+        self.max_in_flight
+            .saturating_sub(self.permits.available_permits())
+    }
+
+    /// Configured slot count
+    pub fn max_in_flight(&self) -> usize {
+        self.max_in_flight
+    }
+}
+
+/// Build bulkheads for buckets with `s3.bulkhead` config
+pub fn from_config(config: &Config) -> HashMap<String, Arc<Bulkhead>> {
+    config
+        .buckets
+        .iter()
+        .filter_map(|bucket| {
+            let config = bucket.s3.bulkhead.as_ref()?;
+            let mut bulkhead = Bulkhead::new(config.max_in_flight);
+            if let Some(max_uploads) = config.max_uploads_in_flight {
+                bulkhead = bulkhead.with_uploads(max_uploads);
+            }
+            Some((bucket.name.clone(), Arc::new(bulkhead)))
+        })
+        .collect()
+}
+
+/// Export bulkhead gauges for Prometheus
+pub fn export_metrics(bulkheads: &HashMap<String, Arc<Bulkhead>>) -> String {
+    let mut output = String::new();
+
+    output.push_str(
+        "\n# HELP yatagarasu_bulkhead_in_flight Upstream requests holding a bulkhead slot per bucket\n",
+    );
+    output.push_str("# TYPE yatagarasu_bulkhead_in_flight gauge\n");
+    for (bucket_name, bulkhead) in bulkheads.iter() {
+        output.push_str(&format!(
+            "yatagarasu_bulkhead_in_flight{{bucket=\"{}\"}} {}\n",
+            bucket_name,
+            bulkhead.in_flight()
+        ));
+    }
+
+    output.push_str("\n# HELP yatagarasu_bulkhead_max_in_flight Bulkhead size per bucket\n");
+    output.push_str("# TYPE yatagarasu_bulkhead_max_in_flight gauge\n");
+    for (bucket_name, bulkhead) in bulkheads.iter() {
+        output.push_str(&format!(
+            "yatagarasu_bulkhead_max_in_flight{{bucket=\"{}\"}} {}\n",
+            bucket_name,
+            bulkhead.max_in_flight()
+        ));
+    }
+
+    output.push_str(
+        "\n# HELP yatagarasu_bulkhead_uploads_in_flight Write requests holding an upload slot per bucket\n",
+    );
+    output.push_str("# TYPE yatagarasu_bulkhead_uploads_in_flight gauge\n");
+    for (bucket_name, bulkhead) in bulkheads.iter() {
+        if bulkhead.uploads.is_some() {
+            output.push_str(&format!(
+                "yatagarasu_bulkhead_uploads_in_flight{{bucket=\"{}\"}} {}\n",
+                bucket_name,
+                bulkhead.uploads_in_flight()
+            ));
+        }
+    }
+
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bulkhead_limits_in_flight() {
+        let bulkhead = Bulkhead::new(2);
+        let first = bulkhead.try_acquire().unwrap();
+        let _second = bulkhead.try_acquire().unwrap();
+        assert_eq!(bulkhead.in_flight(), 2);
+        assert!(bulkhead.try_acquire().is_none());
+
+        drop(first);
+        assert_eq!(bulkhead.in_flight(), 1);
+        assert!(bulkhead.try_acquire().is_some());
+
+        let mut bulkheads = HashMap::new();
+        bulkheads.insert("media".to_string(), Arc::new(bulkhead));
+        let output = export_metrics(&bulkheads);
+        assert!(output.contains("yatagarasu_bulkhead_in_flight{bucket=\"media\"} 1"));
+        assert!(output.contains("yatagarasu_bulkhead_max_in_flight{bucket=\"media\"} 2"));
+    }
+
+    #[test]
+    fn test_upload_pool_is_separate() {
+        let bulkhead = Bulkhead::new(1).with_uploads(1);
+        let _read = bulkhead.try_acquire().unwrap();
+        let _upload = bulkhead.try_acquire_upload().unwrap();
+        assert_eq!(bulkhead.uploads_in_flight(), 1);
+        assert!(bulkhead.try_acquire_upload().is_none());
+        assert!(bulkhead.try_acquire().is_none());
+
+        // Without an upload pool, writes share the read slots
+        let shared = Bulkhead::new(1);
+        let _upload = shared.try_acquire_upload().unwrap();
+        assert!(shared.try_acquire().is_none());
+    }
+}
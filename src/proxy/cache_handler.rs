@@ -179,7 +179,7 @@ pub fn handle_conditional_request(
 ) -> ConditionalResult {
     // Check ETag first (stronger validator)
     if let Some(client_etag) = if_none_match {
-        if client_etag == entry.etag {
+        if crate::cache::etag::if_none_match_matches(client_etag, &entry.etag) {
             return ConditionalResult::NotModifiedByEtag {
                 etag: entry.etag.clone(),
             };
@@ -343,6 +343,29 @@ pub fn extract_conditional_headers(
     (if_none_match, if_modified_since)
 }
 
+/// Whether a Range request must skip the cached range chunks.
+///
+/// Chunks are served without evaluating validators, so conditional requests
+/// (`If-Range`, `If-Match`, `If-None-Match`, `If-Modified-Since`,
+/// `If-Unmodified-Since`) and resumed downloads (`X-Resume-Token`) go to S3,
+/// which answers them with 304, 412 or the matching bytes.
+///
+/// # Arguments
+///
+/// * `headers` - The request headers, with lowercase names.
+pub fn bypasses_range_chunks(headers: &std::collections::HashMap<String, String>) -> bool {
+    [
+        "if-range",
+        "if-match",
+        "if-none-match",
+        "if-modified-since",
+        "if-unmodified-since",
+        super::resume::RESUME_TOKEN_HEADER,
+    ]
+    .iter()
+    .any(|name| headers.contains_key(*name))
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -486,6 +509,26 @@ mod tests {
         assert_eq!(modified, Some("Wed, 21 Oct 2015 07:28:00 GMT".to_string()));
     }
 
+    #[test]
+    fn test_bypasses_range_chunks() {
+        let mut headers = std::collections::HashMap::new();
+        headers.insert("range".to_string(), "bytes=0-99".to_string());
+        assert!(!bypasses_range_chunks(&headers));
+
+        for name in [
+            "if-range",
+            "if-match",
+            "if-none-match",
+            "if-modified-since",
+            "if-unmodified-since",
+            "x-resume-token",
+        ] {
+            let mut conditional = headers.clone();
+            conditional.insert(name.to_string(), "\"abc123\"".to_string());
+            assert!(bypasses_range_chunks(&conditional), "{}", name);
+        }
+    }
+
     #[test]
     fn test_extract_conditional_headers_lowercase() {
         let mut headers = std::collections::HashMap::new();
@@ -0,0 +1,319 @@
+//! Content-Type correction for the proxy.
+//!
+//! Resolves the Content-Type a response should carry from a bucket's
+//! [`ContentTypeConfig`]: user extension rules first, then the built-in
+//! extension table, then magic-byte sniffing of the body.
+//!
+//! Streamed (uncached) responses send headers before any body bytes arrive,
+//! so they can only be corrected by extension. Cache fills, cache hits and
+//! batch items have the body at hand and are sniffed as well; once an object
+//! is cached, every response for it carries the same corrected type.
+
+use crate::config::{BucketConfig, ContentTypeConfig};
+
+/// Built-in extension -> Content-Type table for common web assets
+const BUILTIN_EXTENSIONS: &[(&str, &str)] = &[
+    ("html", "text/html"),
+    ("htm", "text/html"),
+    ("css", "text/css"),
+    ("js", "text/javascript"),
+    ("mjs", "text/javascript"),
+    ("json", "application/json"),
+    ("map", "application/json"),
+    ("xml", "application/xml"),
+    ("txt", "text/plain"),
+    ("csv", "text/csv"),
+    ("md", "text/markdown"),
+    ("svg", "image/svg+xml"),
+    ("png", "image/png"),
+    ("jpg", "image/jpeg"),
+    ("jpeg", "image/jpeg"),
+    ("gif", "image/gif"),
+    ("webp", "image/webp"),
+    ("avif", "image/avif"),
+    ("ico", "image/x-icon"),
+    ("bmp", "image/bmp"),
+    ("tif", "image/tiff"),
+    ("tiff", "image/tiff"),
+    ("pdf", "application/pdf"),
+    ("zip", "application/zip"),
+    ("gz", "application/gzip"),
+    ("tar", "application/x-tar"),
+    ("wasm", "application/wasm"),
+    ("mp4", "video/mp4"),
+    ("m4v", "video/mp4"),
+    ("webm", "video/webm"),
+    ("mov", "video/quicktime"),
+    ("ts", "video/mp2t"),
+    ("m3u8", "application/vnd.apple.mpegurl"),
+    ("mpd", "application/dash+xml"),
+    ("mp3", "audio/mpeg"),
+    ("m4a", "audio/mp4"),
+    ("ogg", "audio/ogg"),
+    ("wav", "audio/wav"),
+    ("flac", "audio/flac"),
+    ("woff", "font/woff"),
+    ("woff2", "font/woff2"),
+    ("ttf", "font/ttf"),
+    ("otf", "font/otf"),
+];
+
+/// Extension of the last path segment, if any (`a/b.tar.gz` -> `gz`)
+fn extension(key: &str) -> Option<&str> {
+    let file_name = key.rsplit('/').next()?;
+    match file_name.rsplit_once('.') {
+        Some((stem, ext)) if !stem.is_empty() && !ext.is_empty() => Some(ext),
+        _ => None,
+    }
+}
+
+/// Content-Type for `key` from the bucket's extension rules
+pub fn from_extension<'a>(config: &'a ContentTypeConfig, key: &str) -> Option<&'a str> {
+    let ext = extension(key)?;
+    if let Some(content_type) = config.extension_rule(ext) {
+        return Some(content_type);
+    }
+    if !config.builtin_extensions {
+        return None;
+    }
+    BUILTIN_EXTENSIONS
+        .iter()
+        .find(|(builtin, _)| builtin.eq_ignore_ascii_case(ext))
+        .map(|(_, content_type)| *content_type)
+}
+
+/// Detect a Content-Type from the leading bytes of a body
+pub fn sniff(body: &[u8]) -> Option<&'static str> {
+    const SIGNATURES: &[(&[u8], &str)] = &[
+        (b"\xFF\xD8\xFF", "image/jpeg"),
+        (b"\x89PNG\r\n\x1A\n", "image/png"),
+        (b"GIF87a", "image/gif"),
+        (b"GIF89a", "image/gif"),
+        (b"%PDF-", "application/pdf"),
+        (b"PK\x03\x04", "application/zip"),
+        (b"\x1F\x8B", "application/gzip"),
+        (b"\x1A\x45\xDF\xA3", "video/webm"),
+        (b"ID3", "audio/mpeg"),
+        (b"OggS", "audio/ogg"),
+        (b"fLaC", "audio/flac"),
+        (b"wOFF", "font/woff"),
+        (b"wOF2", "font/woff2"),
+        (b"\x00asm", "application/wasm"),
+        (b"\x00\x00\x01\x00", "image/x-icon"),
+    ];
+
+    if let Some((_, content_type)) = SIGNATURES
+        .iter()
+        .find(|(signature, _)| body.starts_with(signature))
+    {
+        return Some(content_type);
+    }
+
+    // RIFF containers carry the format at offset 8
+    if body.len() >= 12 && body.starts_with(b"RIFF") {
+        return match &body[8..12] {
+            b"WEBP" => Some("image/webp"),
+            b"WAVE" => Some("audio/wav"),
+            _ => None,
+        };
+    }
+
+    // ISO base media (MP4, AVIF, HEIC): size(4) + "ftyp" + major brand(4)
+    if body.len() >= 12 && &body[4..8] == b"ftyp" {
+        return match &body[8..12] {
+            b"avif" | b"avis" => Some("image/avif"),
+            b"heic" | b"heix" | b"mif1" => Some("image/heic"),
+            b"qt  " => Some("video/quicktime"),
+            b"M4A " => Some("audio/mp4"),
+            _ => Some("video/mp4"),
+        };
+    }
+
+    None
+}
+
+/// Corrected Content-Type for a response, or `None` to keep `upstream`
+///
+/// Extension rules apply whenever the upstream type is replaceable; magic
+/// bytes are only consulted for missing or generic types, when `body` is known.
+pub fn resolve(
+    config: &ContentTypeConfig,
+    key: &str,
+    upstream: Option<&str>,
+    body: Option<&[u8]>,
+) -> Option<String> {
+    if !config.is_replaceable(upstream) {
+        return None;
+    }
+
+    let corrected = from_extension(config, key).or_else(|| {
+        if config.sniff && config.is_generic(upstream) {
+            body.and_then(sniff)
+        } else {
+            None
+        }
+    })?;
+
+    match upstream {
+        Some(current) if current.eq_ignore_ascii_case(corrected) => None,
+        _ => Some(corrected.to_string()),
+    }
+}
+
+/// [`resolve`] using the bucket's rules (no-op for buckets without any)
+pub fn for_bucket(
+    bucket: &BucketConfig,
+    key: &str,
+    upstream: Option<&str>,
+    body: Option<&[u8]>,
+) -> Option<String> {
+    bucket
+        .content_type
+        .as_ref()
+        .and_then(|config| resolve(config, key, upstream, body))
+}
+
+/// Content-Type from the built-in extension table alone, for origins that
+/// store no type of their own (filesystem)
+pub fn builtin(key: &str) -> Option<&'static str> {
+    let ext = extension(key)?;
+    BUILTIN_EXTENSIONS
+        .iter()
+        .find(|(builtin, _)| builtin.eq_ignore_ascii_case(ext))
+        .map(|(_, content_type)| *content_type)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_extension_uses_last_segment() {
+        assert_eq!(extension("images/photo.JPG"), Some("JPG"));
+        assert_eq!(extension("archive.tar.gz"), Some("gz"));
+        assert_eq!(extension("v1.2/README"), None);
+        assert_eq!(extension("dir/.hidden"), None);
+        assert_eq!(extension("trailing."), None);
+    }
+
+    #[test]
+    fn test_builtin_without_bucket_rules() {
+        assert_eq!(builtin("docs/Report.PDF"), Some("application/pdf"));
+        assert_eq!(builtin("docs/README"), None);
+    }
+
+    #[test]
+    fn test_from_extension_prefers_user_rules() {
+        let mut config = ContentTypeConfig::default();
+        config
+            .extensions
+            .insert("js".to_string(), "application/javascript".to_string());
+
+        assert_eq!(
+            from_extension(&config, "app/main.js"),
+            Some("application/javascript")
+        );
+        assert_eq!(from_extension(&config, "img/a.PNG"), Some("image/png"));
+
+        config.builtin_extensions = false;
+        assert_eq!(from_extension(&config, "img/a.png"), None);
+    }
+
+    #[test]
+    fn test_sniff_magic_bytes() {
+        assert_eq!(sniff(b"\xFF\xD8\xFF\xE0\x00\x10JFIF"), Some("image/jpeg"));
+        assert_eq!(sniff(b"\x89PNG\r\n\x1A\n\x00\x00"), Some("image/png"));
+        assert_eq!(sniff(b"RIFF\x00\x00\x00\x00WEBPVP8 "), Some("image/webp"));
+        assert_eq!(
+            sniff(b"\x00\x00\x00\x1Cftypavif\x00\x00"),
+            Some("image/avif")
+        );
+        assert_eq!(
+            sniff(b"\x00\x00\x00\x18ftypisom\x00\x00"),
+            Some("video/mp4")
+        );
+        assert_eq!(sniff(b"%PDF-1.7"), Some("application/pdf"));
+        assert_eq!(sniff(b"plain text"), None);
+        assert_eq!(sniff(b""), None);
+    }
+
+    #[test]
+    fn test_resolve_replaces_generic_types_only() {
+        let config = ContentTypeConfig::default();
+
+        assert_eq!(
+            resolve(&config, "a/photo.jpg", Some("binary/octet-stream"), None),
+            Some("image/jpeg".to_string())
+        );
+        assert_eq!(
+            resolve(&config, "a/photo.jpg", None, None),
+            Some("image/jpeg".to_string())
+        );
+        // Specific upstream types are trusted by default
+        assert_eq!(
+            resolve(&config, "a/photo.jpg", Some("image/png"), None),
+            None
+        );
+        // Already correct: nothing to change
+        assert_eq!(
+            resolve(&config, "a/photo.jpg", Some("image/jpeg"), None),
+            None
+        );
+    }
+
+    #[test]
+    fn test_resolve_sniffs_when_extension_unknown() {
+        let config = ContentTypeConfig::default();
+        let png = b"\x89PNG\r\n\x1A\n\x00\x00\x00\x0DIHDR";
+
+        assert_eq!(
+            resolve(
+                &config,
+                "uploads/3f2a9c",
+                Some("binary/octet-stream"),
+                Some(png)
+            ),
+            Some("image/png".to_string())
+        );
+        // No body (streamed response): unknown extension stays as-is
+        assert_eq!(
+            resolve(&config, "uploads/3f2a9c", Some("binary/octet-stream"), None),
+            None
+        );
+
+        let config = ContentTypeConfig {
+            sniff: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            resolve(
+                &config,
+                "uploads/3f2a9c",
+                Some("binary/octet-stream"),
+                Some(png)
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_resolve_always_override() {
+        let mut config = ContentTypeConfig {
+            always_override: true,
+            ..Default::default()
+        };
+        config
+            .extensions
+            .insert("m3u8".to_string(), "application/x-mpegURL".to_string());
+
+        assert_eq!(
+            resolve(&config, "live/index.m3u8", Some("text/plain"), None),
+            Some("application/x-mpegURL".to_string())
+        );
+        // Sniffing never overrides a specific upstream type
+        assert_eq!(
+            resolve(&config, "blob", Some("text/plain"), Some(b"%PDF-1.4")),
+            None
+        );
+    }
+}
@@ -0,0 +1,231 @@
+//! Control operations shared by the proxy and out-of-band admin APIs.
+//!
+//! [`ControlPlane`] is a cheap, cloneable handle on the proxy's live state
+//! (config, router, cache, metrics) that outlives the proxy being moved into
+//! its Pingora service. The proxy runs SIGHUP and `/admin/reload` reloads
+//! through it, and the gRPC control plane (`grpc` feature) uses it for reload,
+//! purge, stats and health without going through HTTP.
+
+use arc_swap::ArcSwap;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::special_endpoints;
+use crate::cache::tiered::TieredCache;
+use crate::cache::{Cache, CacheKey, CacheStats};
+use crate::config::Config;
+use crate::events::{self, EventKind};
+use crate::maintenance::MaintenanceMode;
+use crate::metrics::Metrics;
+use crate::reload::ReloadManager;
+use crate::replica_set::ReplicaSet;
+use crate::router::Router;
+use crate::s3::credential_store::CredentialStore;
+
+/// What a purge removes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PurgeScope<'a> {
+    /// Every entry in every cache layer
+    All,
+    /// Every entry of one bucket
+    Bucket(&'a str),
+    /// One object, keyed by its path within the bucket (`/path/to/object`)
+    Object { bucket: &'a str, key: &'a str },
+}
+
+/// Handle on the proxy state used by reload, purge, stats and health
+#[derive(Clone)]
+pub struct ControlPlane {
+    pub(super) config: Arc<ArcSwap<Config>>,
+    pub(super) router: Arc<ArcSwap<Router>>,
+    pub(super) metrics: Arc<Metrics>,
+    pub(super) reload_manager: Option<Arc<ReloadManager>>,
+    pub(super) maintenance: Arc<MaintenanceMode>,
+    pub(super) credential_store: Arc<CredentialStore>,
+    pub(super) replica_sets: Arc<HashMap<String, ReplicaSet>>,
+    pub(super) cache: Option<Arc<TieredCache>>,
+    pub(super) start_time: Instant,
+}
+
+impl ControlPlane {
+    /// Current configuration
+    pub fn config(&self) -> Arc<Config> {
+        self.config.load_full()
+    }
+
+    /// Shared metrics registry
+    pub fn metrics(&self) -> &Arc<Metrics> {
+        &self.metrics
+    }
+
+    /// Tiered cache, if caching is enabled
+    pub fn cache(&self) -> Option<&Arc<TieredCache>> {
+        self.cache.as_ref()
+    }
+
+    /// Time since the proxy started
+    pub fn uptime(&self) -> Duration {
+        self.start_time.elapsed()
+    }
+
+    /// Whether maintenance mode is on
+    pub fn maintenance_enabled(&self) -> bool {
+        self.maintenance.is_enabled()
+    }
+
+    /// Whether `/ready` would currently answer 200
+    pub fn is_ready(&self) -> bool {
+        let config = self.config.load();
+        special_endpoints::handle_ready(
+            &config.buckets,
+            &self.replica_sets,
+            &self.metrics,
+            config.server.readiness.mode,
+            self.cache.as_ref().map(|cache| cache.is_healthy()),
+        )
+        .status
+            == 200
+    }
+
+    /// Reload configuration from disk, returning the new generation
+    pub fn reload(&self) -> Result<u64, String> {
+        let Some(reload_manager) = &self.reload_manager else {
+            return Err("Hot reload not enabled".to_string());
+        };
+
+        // Certificates are re-read even when the config file fails to load,
+        // so a renewal is never held back by an unrelated config error
+        reload_manager.run_hooks();
+
+        // Load current config to get generation
+        let current_config = self.config.load();
+        let current_generation = current_config.generation;
+
+        match reload_manager.reload_config_with_generation(current_generation) {
+            Ok(new_config) => {
+                let new_generation = new_config.generation;
+                tracing::info!(
+                    old_generation = current_generation,
+                    new_generation = new_generation,
+                    "Configuration loaded successfully, applying changes"
+                );
+
+                // Create new router
+                let new_router = Router::new(new_config.buckets.clone());
+
+                // Editing server.maintenance.enabled switches maintenance mode
+                let maintenance_enabled = new_config.server.maintenance.enabled;
+                if maintenance_enabled != current_config.server.maintenance.enabled {
+                    self.maintenance.set(maintenance_enabled);
+                    self.metrics.set_maintenance_mode(maintenance_enabled);
+                    tracing::warn!(
+                        enabled = maintenance_enabled,
+                        "Maintenance mode switched by configuration reload"
+                    );
+                }
+
+                // Label limits apply to values first seen after the reload
+                let cardinality = &new_config.server.metrics_cardinality;
+                for registry in [self.metrics.as_ref(), Metrics::global()] {
+                    registry.set_label_limits(
+                        cardinality.max_bucket_labels,
+                        cardinality.max_replica_labels,
+                    );
+                }
+
+                // New keys sign requests from now on; in-flight ones keep theirs
+                for (bucket, replica) in self.credential_store.update(&new_config) {
+                    tracing::info!(
+                        bucket = %bucket,
+                        replica = ?replica,
+                        "Upstream S3 credentials rotated by configuration reload"
+                    );
+                }
+
+                // Update shared state atomically (using ArcSwap)
+                self.config.store(Arc::new(new_config));
+                self.router.store(Arc::new(new_router));
+
+                // Record reload metrics
+                self.metrics.increment_reload_success();
+                self.metrics.set_config_generation(new_generation);
+                events::publish(EventKind::ConfigReload {
+                    success: true,
+                    generation: Some(new_generation),
+                    error: None,
+                });
+
+                Ok(new_generation)
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Configuration reload failed");
+                self.metrics.increment_reload_failure();
+                events::publish(EventKind::ConfigReload {
+                    success: false,
+                    generation: None,
+                    error: Some(e.clone()),
+                });
+                Err(e)
+            }
+        }
+    }
+
+    /// Purge cache entries, returning how many were removed
+    ///
+    /// The count is `None` for [`PurgeScope::All`], where the layers do not
+    /// report one.
+    pub async fn purge(&self, scope: PurgeScope<'_>) -> Result<Option<u64>, String> {
+        let cache = self.cache.as_ref().ok_or("Cache is not enabled")?;
+        let (bucket, key, purged) = match scope {
+            PurgeScope::All => {
+                cache
+                    .clear()
+                    .await
+                    .map_err(|e| format!("Failed to purge cache: {}", e))?;
+                (None, None, None)
+            }
+            PurgeScope::Bucket(bucket) => {
+                let deleted = cache
+                    .clear_bucket(bucket)
+                    .await
+                    .map_err(|e| format!("Failed to purge cache entries: {}", e))?;
+                (Some(bucket), None, Some(deleted as u64))
+            }
+            PurgeScope::Object { bucket, key } => {
+                let cache_key = CacheKey {
+                    bucket: bucket.to_string(),
+                    object_key: key.to_string(),
+                    etag: None,
+                    variant: None,
+                };
+                let deleted = cache
+                    .delete(&cache_key)
+                    .await
+                    .map_err(|e| format!("Failed to purge cache entry: {}", e))?;
+                if !deleted {
+                    return Ok(Some(0));
+                }
+                (Some(bucket), Some(key), Some(1))
+            }
+        };
+
+        tracing::info!(bucket = ?bucket, key = ?key, purged = ?purged, "Cache purged");
+        self.metrics.increment_cache_purge();
+        events::publish(EventKind::CachePurge {
+            bucket: bucket.map(str::to_string),
+            key: key.map(str::to_string),
+        });
+        Ok(purged)
+    }
+
+    /// Cache statistics for all buckets, or for one
+    pub async fn cache_stats(&self, bucket: Option<&str>) -> Result<CacheStats, String> {
+        let cache = self.cache.as_ref().ok_or("Cache is not enabled")?;
+        let stats = match bucket {
+            Some(bucket) => cache.stats_bucket(bucket).await,
+            None => cache.stats().await,
+        };
+        stats.map_err(|e| format!("Failed to retrieve cache stats: {}", e))
+    }
+}
@@ -0,0 +1,217 @@
+//! OPTIONS preflight and CORS response headers for the proxy.
+//!
+//! Preflight responses are built from the bucket the path routes to: `Allow`
+//! and `Access-Control-Allow-Methods` list the bucket's allowed methods, and
+//! origin grants come from its [`CorsConfig`]. Paths outside any bucket, and
+//! buckets without a `cors` section, get the legacy preflight (methods,
+//! default headers and max age, but no origin grant).
+//!
+//! Object responses (streamed and cache hits) carry
+//! `Access-Control-Allow-Origin` when the bucket's policy grants the
+//! request's `Origin`.
+
+use pingora_http::ResponseHeader;
+
+use crate::config::{BucketConfig, CorsConfig};
+use crate::constants::{
+    DEFAULT_BUCKET_ALLOWED_METHODS, DEFAULT_CORS_ALLOWED_HEADERS, DEFAULT_CORS_MAX_AGE_SECS,
+};
+
+/// Headers for an `OPTIONS` preflight response
+///
+/// `requested_method` is the `Access-Control-Request-Method` header; a
+/// method the bucket does not accept gets no origin grant, so the browser
+/// blocks the actual request.
+pub fn preflight_headers(
+    bucket: Option<&BucketConfig>,
+    origin: Option<&str>,
+    requested_method: Option<&str>,
+) -> Vec<(&'static str, String)> {
+    let methods = match bucket {
+        Some(bucket) => bucket.allowed_methods().join(", "),
+        None => DEFAULT_BUCKET_ALLOWED_METHODS.join(", "),
+    };
+    let mut headers = vec![("Allow", methods.clone())];
+
+    let Some(cors) = bucket.and_then(|b| b.cors.as_ref()) else {
+        headers.push(("Access-Control-Allow-Methods", methods));
+        headers.push((
+            "Access-Control-Allow-Headers",
+            DEFAULT_CORS_ALLOWED_HEADERS.join(", "),
+        ));
+        headers.push((
+            "Access-Control-Max-Age",
+            DEFAULT_CORS_MAX_AGE_SECS.to_string(),
+        ));
+        return headers;
+    };
+
+    headers.push(("Vary", "Origin".to_string()));
+    let method_allowed = match (bucket, requested_method) {
+        (Some(bucket), Some(method)) => bucket.allows_method(method),
+        _ => true,
+    };
+    if let Some(allow_origin) = origin
+        .filter(|_| method_allowed)
+        .and_then(|o| cors.allow_origin(o))
+    {
+        headers.push(("Access-Control-Allow-Origin", allow_origin));
+        headers.push(("Access-Control-Allow-Methods", methods));
+        if !cors.allowed_headers.is_empty() {
+            headers.push((
+                "Access-Control-Allow-Headers",
+                cors.allowed_headers.join(", "),
+            ));
+        }
+        headers.push(("Access-Control-Max-Age", cors.max_age_secs.to_string()));
+        push_credentials(&mut headers, cors);
+    }
+    headers
+}
+
+/// CORS headers for an object response, empty unless the bucket's policy grants `origin`
+pub fn response_headers(
+    bucket: &BucketConfig,
+    origin: Option<&str>,
+) -> Vec<(&'static str, String)> {
+    let Some(cors) = bucket.cors.as_ref() else {
+        return Vec::new();
+    };
+    let mut headers = vec![("Vary", "Origin".to_string())];
+    if let Some(allow_origin) = origin.and_then(|o| cors.allow_origin(o)) {
+        headers.push(("Access-Control-Allow-Origin", allow_origin));
+        if !cors.expose_headers.is_empty() {
+            headers.push((
+                "Access-Control-Expose-Headers",
+                cors.expose_headers.join(", "),
+            ));
+        }
+        push_credentials(&mut headers, cors);
+    }
+    headers
+}
+
+/// Add CORS headers to a response; `Vary` is appended so existing values are kept
+pub fn apply(
+    header: &mut ResponseHeader,
+    headers: Vec<(&'static str, String)>,
+) -> pingora_core::Result<()> {
+    for (name, value) in headers {
+        if name == "Vary" {
+            header.append_header(name, value)?;
+        } else {
+            header.insert_header(name, value)?;
+        }
+    }
+    Ok(())
+}
+
+fn push_credentials(headers: &mut Vec<(&'static str, String)>, cors: &CorsConfig) {
+    if cors.allow_credentials {
+        headers.push(("Access-Control-Allow-Credentials", "true".to_string()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bucket(cors: Option<CorsConfig>, allowed_methods: Option<Vec<&str>>) -> BucketConfig {
+        let mut bucket: BucketConfig = serde_yaml::from_str(
+            r#"
+name: "assets"
+path_prefix: "/assets"
+s3:
+  bucket: "assets"
+  region: "us-east-1"
+  access_key: "test-key"
+  secret_key: "test-secret"
+"#,
+        )
+        .unwrap();
+        bucket.cors = cors;
+        bucket.allowed_methods = allowed_methods.map(|m| m.into_iter().map(String::from).collect());
+        bucket
+    }
+
+    fn header<'a>(headers: &'a [(&'static str, String)], name: &str) -> Option<&'a str> {
+        headers
+            .iter()
+            .find(|(n, _)| *n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    fn app_cors() -> CorsConfig {
+        CorsConfig {
+            allowed_origins: vec!["https://app.example.com".to_string()],
+            expose_headers: vec!["ETag".to_string()],
+            max_age_secs: 600,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_preflight_without_cors_policy_keeps_legacy_headers() {
+        let headers = preflight_headers(None, Some("https://app.example.com"), Some("GET"));
+        assert_eq!(header(&headers, "Allow"), Some("GET, HEAD, OPTIONS"));
+        assert_eq!(header(&headers, "Access-Control-Max-Age"), Some("86400"));
+        assert_eq!(header(&headers, "Access-Control-Allow-Origin"), None);
+
+        let head_only = bucket(None, Some(vec!["HEAD", "OPTIONS"]));
+        let headers = preflight_headers(Some(&head_only), None, None);
+        assert_eq!(
+            header(&headers, "Access-Control-Allow-Methods"),
+            Some("HEAD, OPTIONS")
+        );
+    }
+
+    #[test]
+    fn test_preflight_grants_allowed_origin() {
+        let bucket = bucket(Some(app_cors()), None);
+        let headers =
+            preflight_headers(Some(&bucket), Some("https://app.example.com"), Some("GET"));
+        assert_eq!(
+            header(&headers, "Access-Control-Allow-Origin"),
+            Some("https://app.example.com")
+        );
+        assert_eq!(header(&headers, "Access-Control-Max-Age"), Some("600"));
+        assert_eq!(header(&headers, "Vary"), Some("Origin"));
+
+        // Foreign origin or a method the bucket does not accept: no grant
+        let headers = preflight_headers(Some(&bucket), Some("https://evil.example"), None);
+        assert_eq!(header(&headers, "Access-Control-Allow-Origin"), None);
+        let headers =
+            preflight_headers(Some(&bucket), Some("https://app.example.com"), Some("PUT"));
+        assert_eq!(header(&headers, "Access-Control-Allow-Origin"), None);
+        assert_eq!(header(&headers, "Allow"), Some("GET, HEAD, OPTIONS"));
+    }
+
+    #[test]
+    fn test_response_headers() {
+        assert!(response_headers(&bucket(None, None), Some("https://app.example.com")).is_empty());
+
+        let bucket = bucket(
+            Some(CorsConfig {
+                allow_credentials: true,
+                ..app_cors()
+            }),
+            None,
+        );
+        let headers = response_headers(&bucket, Some("https://app.example.com"));
+        assert_eq!(
+            header(&headers, "Access-Control-Allow-Origin"),
+            Some("https://app.example.com")
+        );
+        assert_eq!(
+            header(&headers, "Access-Control-Expose-Headers"),
+            Some("ETag")
+        );
+        assert_eq!(
+            header(&headers, "Access-Control-Allow-Credentials"),
+            Some("true")
+        );
+
+        let headers = response_headers(&bucket, None);
+        assert_eq!(headers, vec![("Vary", "Origin".to_string())]);
+    }
+}
@@ -0,0 +1,19 @@
+//! Deprecation and sunset headers for the proxy.
+//!
+//! Applied wherever response headers are built (cache hits, upstream
+//! responses, in-process origins) so every response of a deprecated bucket
+//! carries the notice. `Link` values are appended, keeping any preload links.
+
+use pingora_http::ResponseHeader;
+
+use crate::config::BucketConfig;
+
+/// Add the bucket's deprecation headers, if it has any
+pub fn apply(header: &mut ResponseHeader, bucket: &BucketConfig) -> pingora_core::Result<()> {
+    if let Some(deprecation) = &bucket.deprecation {
+        for (name, value) in deprecation.response_headers() {
+            header.append_header(name, value)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,104 @@
+//! Client disconnect handling for the proxy.
+//!
+//! Pingora drops the upstream connection as soon as a write to the client
+//! fails, so an aborted download stops pulling bytes from S3. This module
+//! classifies those failures and decides whether a response that was being
+//! buffered for the cache is close enough to complete that the remainder
+//! should be fetched in the background instead.
+
+use pingora_core::{Error, ErrorSource, ErrorType};
+
+use crate::config::ClientDisconnectConfig;
+
+/// What to do after the client went away mid-transfer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisconnectAction {
+    /// Upstream fetch is cancelled; `bytes_saved` bytes were never read
+    Cancel { bytes_saved: u64 },
+    /// Fetch the remaining bytes starting at `offset` to finish the cache fill
+    FinishCacheFill { offset: u64 },
+}
+
+/// Check if a request error was caused by the client disconnecting
+pub fn is_client_disconnect(error: &Error) -> bool {
+    matches!(error.esource(), ErrorSource::Downstream)
+        && matches!(
+            error.etype(),
+            ErrorType::ConnectionClosed
+                | ErrorType::WriteError
+                | ErrorType::WriteTimedout
+                | ErrorType::ReadError
+        )
+}
+
+/// Decide how to handle an aborted transfer
+///
+/// `received` is the number of body bytes read from upstream, `content_length`
+/// the size announced by upstream, and `cache_fill_in_progress` whether the
+/// whole body so far has been buffered for cache population.
+pub fn plan_disconnect(
+    config: &ClientDisconnectConfig,
+    received: u64,
+    content_length: Option<u64>,
+    cache_fill_in_progress: bool,
+) -> DisconnectAction {
+    let total = match content_length {
+        Some(total) => total,
+        None => return DisconnectAction::Cancel { bytes_saved: 0 },
+    };
+
+    if cache_fill_in_progress && config.should_finish_cache_fill(received, total) {
+        DisconnectAction::FinishCacheFill { offset: received }
+    } else {
+        DisconnectAction::Cancel {
+            bytes_saved: total.saturating_sub(received),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_client_disconnect_requires_downstream_source() {
+        let downstream = Error::explain(ErrorType::WriteError, "broken pipe").into_down();
+        assert!(is_client_disconnect(&downstream));
+
+        let upstream = Error::explain(ErrorType::WriteError, "broken pipe").into_up();
+        assert!(!is_client_disconnect(&upstream));
+
+        let internal = Error::explain(ErrorType::InternalError, "bug").into_down();
+        assert!(!is_client_disconnect(&internal));
+    }
+
+    #[test]
+    fn test_plan_disconnect_cancels_and_counts_saved_bytes() {
+        let config = ClientDisconnectConfig::default();
+
+        assert_eq!(
+            plan_disconnect(&config, 1000, Some(10_000), true),
+            DisconnectAction::Cancel { bytes_saved: 9000 }
+        );
+        // Not being cached: always cancel, even when nearly complete
+        assert_eq!(
+            plan_disconnect(&config, 9_990, Some(10_000), false),
+            DisconnectAction::Cancel { bytes_saved: 10 }
+        );
+        // Unknown length (chunked upstream response)
+        assert_eq!(
+            plan_disconnect(&config, 1000, None, true),
+            DisconnectAction::Cancel { bytes_saved: 0 }
+        );
+    }
+
+    #[test]
+    fn test_plan_disconnect_finishes_nearly_complete_cache_fill() {
+        let config = ClientDisconnectConfig::default();
+
+        assert_eq!(
+            plan_disconnect(&config, 9_500, Some(10_000), true),
+            DisconnectAction::FinishCacheFill { offset: 9_500 }
+        );
+    }
+}
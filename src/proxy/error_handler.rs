@@ -10,6 +10,9 @@
 //! modifying Pingora error objects directly. This keeps error handling
 //! testable and allows the caller to apply actions appropriately.
 
+use pingora_core::ErrorType;
+
+use crate::circuit_breaker::UpstreamOutcome;
 use crate::retry::RetryPolicy;
 
 // ============================================================================
@@ -288,6 +291,28 @@ pub fn classify_status(status_code: u16) -> ErrorClassification {
     }
 }
 
+/// Classify an upstream transport error for circuit breaker accounting.
+///
+/// Timeouts are reported separately so they can be classified on their own;
+/// every other connection-level error is a connect error.
+///
+/// # Arguments
+///
+/// * `error_type` - Pingora error type from `fail_to_connect` or `error_while_proxy`.
+///
+/// # Returns
+///
+/// `UpstreamOutcome::Timeout` or `UpstreamOutcome::ConnectError`.
+pub fn classify_upstream_error(error_type: &ErrorType) -> UpstreamOutcome {
+    match error_type {
+        ErrorType::ConnectTimedout
+        | ErrorType::TLSHandshakeTimedout
+        | ErrorType::ReadTimedout
+        | ErrorType::WriteTimedout => UpstreamOutcome::Timeout,
+        _ => UpstreamOutcome::ConnectError,
+    }
+}
+
 // ============================================================================
 // Backoff Calculation
 // ============================================================================
@@ -560,6 +585,26 @@ mod tests {
         assert_eq!(classify_status(400), ErrorClassification::Permanent);
     }
 
+    #[test]
+    fn test_classify_upstream_error() {
+        assert_eq!(
+            classify_upstream_error(&ErrorType::ConnectTimedout),
+            UpstreamOutcome::Timeout
+        );
+        assert_eq!(
+            classify_upstream_error(&ErrorType::ReadTimedout),
+            UpstreamOutcome::Timeout
+        );
+        assert_eq!(
+            classify_upstream_error(&ErrorType::ConnectRefused),
+            UpstreamOutcome::ConnectError
+        );
+        assert_eq!(
+            classify_upstream_error(&ErrorType::ConnectionClosed),
+            UpstreamOutcome::ConnectError
+        );
+    }
+
     // ========== Backoff Tests ==========
 
     #[test]
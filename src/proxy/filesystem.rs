@@ -0,0 +1,88 @@
+//! In-process serving for filesystem origins.
+//!
+//! [`stat`] describes the file behind an object key; the proxy then writes
+//! the response itself, streaming the body in [`FILESYSTEM_CHUNK_SIZE`]
+//! chunks. Validators are derived from size and modification time, so they
+//! change whenever the file is rewritten.
+
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use bytes::Bytes;
+use tokio::io::AsyncReadExt;
+
+use crate::constants::FILESYSTEM_CHUNK_SIZE;
+
+/// Metadata of a regular file served as an object
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalObject {
+    pub size: u64,
+    /// Quoted ETag (`"<size hex>-<mtime hex>"`)
+    pub etag: String,
+    /// HTTP-date of the modification time
+    pub last_modified: Option<String>,
+}
+
+impl LocalObject {
+    fn new(size: u64, modified: Option<SystemTime>) -> Self {
+        let mtime = modified
+            .and_then(|m| m.duration_since(UNIX_EPOCH).ok())
+            .unwrap_or_default();
+        let last_modified = modified.map(|m| {
+            chrono::DateTime::<chrono::Utc>::from(m)
+                .format("%a, %d %b %Y %H:%M:%S GMT")
+                .to_string()
+        });
+        Self {
+            size,
+            etag: format!("\"{:x}-{:x}\"", size, mtime.as_nanos()),
+            last_modified,
+        }
+    }
+}
+
+/// Metadata for `path`, `None` when missing or not a regular file
+pub async fn stat(path: &Path) -> Option<LocalObject> {
+    let metadata = tokio::fs::metadata(path).await.ok()?;
+    if !metadata.is_file() {
+        return None;
+    }
+    Some(LocalObject::new(metadata.len(), metadata.modified().ok()))
+}
+
+/// Open `path` for chunked reads
+pub async fn open(path: &Path) -> std::io::Result<tokio::fs::File> {
+    tokio::fs::File::open(path).await
+}
+
+/// Next body chunk, `None` at end of file
+pub async fn read_chunk(file: &mut tokio::fs::File) -> std::io::Result<Option<Bytes>> {
+    let mut buf = vec![0u8; FILESYSTEM_CHUNK_SIZE];
+    let n = file.read(&mut buf).await?;
+    if n == 0 {
+        return Ok(None);
+    }
+    buf.truncate(n);
+    Ok(Some(Bytes::from(buf)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_local_object_validators() {
+        let modified = UNIX_EPOCH + Duration::from_secs(1_700_000_000);
+        let object = LocalObject::new(42, Some(modified));
+        assert_eq!(object.etag, "\"2a-17979cfe362a0000\"");
+        assert_eq!(
+            object.last_modified.as_deref(),
+            Some("Tue, 14 Nov 2023 22:13:20 GMT")
+        );
+
+        // Rewriting the file changes the ETag
+        let rewritten = LocalObject::new(42, Some(modified + Duration::from_secs(1)));
+        assert_ne!(object.etag, rewritten.etag);
+    }
+}
@@ -3,49 +3,30 @@
 //! This module contains helper functions for request processing:
 //! - Header extraction from Pingora requests
 //! - Query parameter parsing
-//! - Client IP detection (X-Forwarded-For aware)
-//! - Circuit breaker metrics export
+//! - Client IP detection (X-Forwarded-For aware, or limited to trusted proxies)
+//! - Downstream connection tracking (first request per connection)
+//! - Circuit breaker metrics export and transition events
 
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::net::IpAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 
-use pingora_http::RequestHeader;
 use pingora_proxy::Session;
 
-use crate::circuit_breaker::CircuitBreaker;
+use crate::circuit_breaker::{CircuitBreaker, CircuitState};
+use crate::constants::DOWNSTREAM_CONNECTION_SLOTS;
+use crate::events::{self, EventKind};
+use crate::security::IpRange;
 
-/// Extract headers from Pingora RequestHeader into HashMap.
-///
-/// Converts all headers to string key-value pairs. Headers with non-UTF8
-/// values are skipped.
-pub fn extract_headers(req: &RequestHeader) -> HashMap<String, String> {
-    let mut headers = HashMap::new();
-    for (name, value) in req.headers.iter() {
-        if let Ok(value_str) = value.to_str() {
-            headers.insert(name.to_string(), value_str.to_string());
-        }
-    }
-    headers
-}
+/// `Warning` header value for cache entries served past expiry in degraded mode
+pub const STALE_WARNING: &str = "110 - \"Response is Stale\"";
 
-/// Extract query parameters from URI.
-///
-/// Parses the query string from the request URI and returns key-value pairs.
-/// Values are URL-decoded.
-pub fn extract_query_params(req: &RequestHeader) -> HashMap<String, String> {
-    let mut params = HashMap::new();
-    if let Some(query) = req.uri.query() {
-        for pair in query.split('&') {
-            if let Some((key, value)) = pair.split_once('=') {
-                params.insert(
-                    key.to_string(),
-                    urlencoding::decode(value).unwrap_or_default().to_string(),
-                );
-            }
-        }
-    }
-    params
-}
+// Header and query extraction live in the pipeline module so they can be
+// benchmarked; re-exported here for the proxy hooks.
+pub use crate::pipeline::{extract_headers, extract_query_params};
 
 /// Extract client IP address from session (X-Forwarded-For aware).
 ///
@@ -78,6 +59,110 @@ pub fn get_client_ip(session: &Session) -> String {
         .unwrap_or_else(|| "unknown".to_string())
 }
 
+/// Client IP that only believes `X-Forwarded-For` from trusted proxies.
+///
+/// Unlike [`get_client_ip`], a client connecting directly cannot claim another
+/// address. Returns `None` when the peer address is not an IP.
+pub fn trusted_client_ip(session: &Session, trusted_proxies: &[String]) -> Option<IpAddr> {
+    let peer = session.client_addr()?.as_inet()?.ip();
+    let forwarded_for = session
+        .req_header()
+        .headers
+        .get("x-forwarded-for")
+        .and_then(|v| v.to_str().ok());
+    let trusted: Vec<IpRange> = trusted_proxies
+        .iter()
+        .filter_map(|entry| IpRange::parse(entry).ok())
+        .collect();
+    Some(resolve_forwarded_ip(peer, forwarded_for, &trusted))
+}
+
+/// Walk `X-Forwarded-For` from the right while the hop is a trusted proxy
+///
+/// The first untrusted hop is the client. Entries left of it were written by
+/// the client itself and are ignored; an unparsable entry stops the walk at
+/// the last trusted hop.
+fn resolve_forwarded_ip(peer: IpAddr, forwarded_for: Option<&str>, trusted: &[IpRange]) -> IpAddr {
+    let mut client = peer;
+    let hops = forwarded_for.into_iter().flat_map(|v| v.rsplit(','));
+    for hop in hops {
+        if !trusted.iter().any(|range| range.contains(&client)) {
+            break;
+        }
+        match hop.trim().parse() {
+            Ok(ip) => client = ip,
+            Err(_) => break,
+        }
+    }
+    client
+}
+
+/// Downstream connections that have already sent a request.
+///
+/// Pingora does not expose listener accepts or per-connection state to
+/// `ProxyHttp`, but every request on a connection carries the connection's
+/// peer address and accept time. Their hash identifies the connection, and a
+/// direct-mapped table of atomic slots marks it as seen, so each connection
+/// is counted once, on its first request, without taking a lock.
+///
+/// Two open connections whose hashes share a slot evict each other, and a
+/// connection evicted between two of its requests is counted again. With
+/// `DOWNSTREAM_CONNECTION_SLOTS` slots this only skews the counts when a
+/// large share of the table is held by idle keep-alive connections.
+pub struct DownstreamConnections {
+    slots: Box<[AtomicU64]>,
+}
+
+impl Default for DownstreamConnections {
+    fn default() -> Self {
+        Self {
+            slots: (0..DOWNSTREAM_CONNECTION_SLOTS)
+                .map(|_| AtomicU64::new(0))
+                .collect(),
+        }
+    }
+}
+
+impl DownstreamConnections {
+    /// Whether this is the first request on its downstream connection.
+    ///
+    /// Returns `None` when the session carries no peer address or accept time.
+    pub fn is_first_request(&self, session: &Session) -> Option<bool> {
+        let digest = session.digest()?;
+        let accepted_at = digest.timing_digest.first()?.as_ref()?.established_ts;
+        let peer = session.client_addr()?.as_inet()?;
+        let mut hasher = DefaultHasher::new();
+        (peer, accepted_at).hash(&mut hasher);
+        Some(self.first_use(hasher.finish()))
+    }
+
+    /// Mark connection `id` as seen, returning whether it was new.
+    fn first_use(&self, id: u64) -> bool {
+        // 0 marks an empty slot
+        let id = id.max(1);
+        let slot = &self.slots[id as usize % self.slots.len()];
+        slot.swap(id, Ordering::Relaxed) != id
+    }
+}
+
+/// Publish a `breaker_transition` event when a bucket's breaker changed state.
+pub fn publish_breaker_transition(bucket: &str, before: CircuitState, after: CircuitState) {
+    if before == after {
+        return;
+    }
+    tracing::info!(
+        bucket = %bucket,
+        from = before.as_str(),
+        to = after.as_str(),
+        "Circuit breaker state changed"
+    );
+    events::publish(EventKind::BreakerTransition {
+        bucket: bucket.to_string(),
+        from: before.as_str().to_string(),
+        to: after.as_str().to_string(),
+    });
+}
+
 /// Export circuit breaker metrics for Prometheus.
 ///
 /// Generates Prometheus-compatible metrics text for all circuit breakers:
@@ -127,12 +212,54 @@ pub fn export_circuit_breaker_metrics(
         ));
     }
 
+    // Failure ratio over the sliding window (error-rate breakers only)
+    output.push_str(
+        "\n# HELP circuit_breaker_error_rate Failure ratio over the sliding window (error-rate mode)\n",
+    );
+    output.push_str("# TYPE circuit_breaker_error_rate gauge\n");
+
+    for (bucket_name, circuit_breaker) in circuit_breakers.iter() {
+        if let Some(error_rate) = circuit_breaker.error_rate() {
+            output.push_str(&format!(
+                "circuit_breaker_error_rate{{bucket=\"{}\"}} {}\n",
+                bucket_name, error_rate
+            ));
+        }
+    }
+
     output
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use pingora_http::RequestHeader;
+
+    #[test]
+    fn test_forwarded_ip_only_trusted_from_proxies() {
+        let ip = |s: &str| -> IpAddr { s.parse().unwrap() };
+        let trusted = [IpRange::parse("10.0.0.0/8").unwrap()];
+        let xff = Some("6.6.6.6, 203.0.113.7, 10.0.0.2");
+
+        // Direct clients cannot spoof their address
+        assert_eq!(
+            resolve_forwarded_ip(ip("198.51.100.1"), xff, &trusted),
+            ip("198.51.100.1")
+        );
+        // Behind trusted proxies, the first untrusted hop from the right wins
+        assert_eq!(
+            resolve_forwarded_ip(ip("10.0.0.1"), xff, &trusted),
+            ip("203.0.113.7")
+        );
+        assert_eq!(
+            resolve_forwarded_ip(ip("10.0.0.1"), None, &trusted),
+            ip("10.0.0.1")
+        );
+        assert_eq!(
+            resolve_forwarded_ip(ip("10.0.0.1"), xff, &[]),
+            ip("10.0.0.1")
+        );
+    }
 
     #[test]
     fn test_extract_headers_empty() {
@@ -179,5 +306,24 @@ mod tests {
         assert!(metrics.contains("circuit_breaker_state"));
         assert!(metrics.contains("circuit_breaker_failures"));
         assert!(metrics.contains("circuit_breaker_successes"));
+        assert!(metrics.contains("circuit_breaker_error_rate"));
+    }
+
+    #[test]
+    fn test_downstream_connections_counts_each_connection_once() {
+        let connections = DownstreamConnections::default();
+        let first = 1;
+        let second = 2;
+
+        assert!(connections.first_use(first));
+        assert!(!connections.first_use(first));
+        assert!(connections.first_use(second));
+        assert!(!connections.first_use(first));
+        assert!(!connections.first_use(second));
+
+        // A connection sharing a slot evicts the one before it
+        let colliding = first + DOWNSTREAM_CONNECTION_SLOTS as u64;
+        assert!(connections.first_use(colliding));
+        assert!(connections.first_use(first));
     }
 }
@@ -13,21 +13,33 @@ use std::collections::HashMap;
 use std::sync::Arc;
 use tokio::sync::Semaphore;
 
+use crate::analytics::{AccessStats, KeyPopularity};
 use crate::audit::AsyncAuditFileWriter;
+use crate::auth::failures::AuthFailureTracker;
 use crate::cache::tiered::TieredCache;
 use crate::cache::warming::PrewarmManager;
 use crate::cache::Cache;
 use crate::circuit_breaker::CircuitBreaker;
-use crate::config::Config;
+use crate::config::{Config, S3CredentialsConfig};
+use crate::maintenance::MaintenanceMode;
 use crate::metrics::Metrics;
 use crate::opa::{OpaCache, OpaClient, OpaClientConfig, SharedOpaClient};
 use crate::openfga::OpenFgaClient;
-use crate::rate_limit::RateLimitManager;
+use crate::origin::SharedOrigin;
+use crate::rate_limit::{RateLimitManager, RateQuota};
 use crate::request_coalescing::Coalescer;
 use crate::resources::ResourceMonitor;
 use crate::retry::RetryPolicy;
 use crate::router::Router;
-use crate::security::SecurityLimits;
+use crate::s3::credential_store::CredentialStore;
+use crate::s3::credentials::CredentialProvider;
+use crate::security::{AccessGrantStore, IpBanManager, RangeAbuseDetector, SecurityLimits};
+
+use super::buffer_budget::{self, BufferBudget};
+use super::bulkhead::{self, Bulkhead};
+use super::origin_probe::OriginProber;
+use super::priority::PriorityGate;
+use super::queue::RequestQueue;
 
 /// Components initialized from configuration.
 ///
@@ -40,12 +52,27 @@ pub(super) struct ProxyComponents {
     pub metrics: Arc<Metrics>,
     pub resource_monitor: Arc<ResourceMonitor>,
     pub request_semaphore: Arc<Semaphore>,
+    pub priority_gate: Option<PriorityGate>,
+    pub request_queue: Option<RequestQueue>,
+    pub maintenance: Arc<MaintenanceMode>,
+    pub key_popularity: Option<Arc<KeyPopularity>>,
+    pub auth_failures: Arc<AuthFailureTracker>,
+    pub access_stats: Option<Arc<AccessStats>>,
     pub coalescer: Option<Coalescer>,
     pub circuit_breakers: HashMap<String, Arc<CircuitBreaker>>,
+    pub bulkheads: HashMap<String, Arc<Bulkhead>>,
+    pub buffer_budget: Arc<BufferBudget>,
+    pub origins: HashMap<String, SharedOrigin>,
+    pub origin_prober: Option<Arc<OriginProber>>,
     pub rate_limit_manager: Option<Arc<RateLimitManager>>,
+    pub ip_ban_manager: Option<Arc<IpBanManager>>,
+    pub range_abuse: Option<Arc<RangeAbuseDetector>>,
+    pub access_grants: Option<Arc<AccessGrantStore>>,
     pub retry_policies: HashMap<String, RetryPolicy>,
     pub security_limits: SecurityLimits,
     pub replica_sets: HashMap<String, crate::replica_set::ReplicaSet>,
+    pub credential_providers: HashMap<String, Arc<CredentialProvider>>,
+    pub credential_store: Arc<CredentialStore>,
     pub cache: Option<Arc<TieredCache>>,
     pub opa_clients: HashMap<String, SharedOpaClient>,
     pub opa_cache: Option<Arc<OpaCache>>,
@@ -91,10 +118,16 @@ pub(super) fn initialize_audit_writer(config: &Config) -> Option<Arc<AsyncAuditF
 /// - Metrics collector
 /// - Resource monitor for system load tracking
 /// - Request semaphore for concurrency limiting
+/// - Request queue for bounded waits on the semaphore (if enabled)
 /// - Circuit breakers per bucket (if configured)
+/// - Upstream bulkheads per bucket (if configured)
+/// - Memory budget for cache-population buffers
+/// - Background origin prober for open breakers (if enabled)
 /// - Rate limit manager (if enabled)
 /// - Retry policies per bucket
 /// - Replica sets for HA failover
+/// - Versioned store of static upstream keys
+/// - Non-S3 origins per bucket
 /// - OPA clients and cache for authorization
 /// - OpenFGA clients for authorization
 /// - Audit writer for request logging
@@ -104,23 +137,97 @@ pub(super) fn initialize_from_config(config: Config) -> ProxyComponents {
     let config = config.normalize();
     let router = Router::new(config.buckets.clone());
     let metrics = Arc::new(Metrics::new());
+    // Bound per-bucket/per-replica series (both registries carry bucket labels)
+    let cardinality = &config.server.metrics_cardinality;
+    for registry in [metrics.as_ref(), Metrics::global()] {
+        registry.set_label_limits(
+            cardinality.max_bucket_labels,
+            cardinality.max_replica_labels,
+        );
+    }
     // Initialize resource monitor with auto-detected system limits
     let resource_monitor = Arc::new(ResourceMonitor::new_auto_detect());
-    // Initialize request semaphore with max concurrent requests limit
-    let request_semaphore = Arc::new(Semaphore::new(config.server.max_concurrent_requests));
+    // Initialize request semaphore with max concurrent requests limit,
+    // minus the permits reserved for high-priority requests
+    let request_semaphore = Arc::new(Semaphore::new(
+        config
+            .server
+            .priority
+            .general_permits(config.server.max_concurrent_requests),
+    ));
+    let priority_gate = PriorityGate::from_config(&config.server.priority);
+
+    // Bounded wait for a permit instead of an instant 503 (if enabled)
+    let request_queue = RequestQueue::from_config(&config.server.request_queue);
+
+    // Maintenance mode starts in the configured state
+    let maintenance = Arc::new(MaintenanceMode::new(config.server.maintenance.enabled));
+    metrics.set_maintenance_mode(config.server.maintenance.enabled);
+
+    // Key popularity tracking for the admin analytics API
+    let key_popularity = config
+        .server
+        .key_analytics
+        .enabled
+        .then(|| Arc::new(KeyPopularity::new(config.server.key_analytics.clone())));
+
+    // Rolling per-client auth failure counts for the admin top-offenders view
+    let auth_failures = Arc::new(AuthFailureTracker::default());
+
+    // Per-key access statistics for periodic export (task starts in init_cache)
+    let access_stats = config
+        .server
+        .access_export
+        .enabled
+        .then(|| Arc::new(AccessStats::new(config.server.access_export.clone())));
 
     // Initialize circuit breakers for buckets that have circuit_breaker config
     let circuit_breakers = initialize_circuit_breakers(&config);
 
+    // Per-bucket upstream concurrency limits for buckets with bulkhead config
+    let bulkheads = bulkhead::from_config(&config);
+
+    // Global byte budget for responses buffered for cache population
+    let buffer_budget = Arc::new(buffer_budget::from_config(&config));
+
     // Initialize rate limit manager if enabled
     let rate_limit_manager = initialize_rate_limit_manager(&config);
 
+    // Initialize automatic IP banning if enabled
+    let ip_ban_manager = initialize_ip_ban_manager(&config);
+
+    // Initialize Range abuse detection if enabled
+    let range_abuse = initialize_range_abuse_detector(&config);
+
+    // Initialize temporary access grants if enabled
+    let access_grants = initialize_access_grants(&config);
+
     // Initialize retry policies for buckets that have retry config
     let retry_policies = initialize_retry_policies(&config);
 
     // Initialize replica sets for each bucket (Phase 23: HA bucket replication)
     let replica_sets = initialize_replica_sets(&config);
 
+    // Providers for buckets whose upstream credentials come from the
+    // environment (first fetch and refresh task start in init_cache)
+    let credential_providers = initialize_credential_providers(&config);
+
+    // Static keys read at sign time, swapped on reload
+    let credential_store = Arc::new(CredentialStore::from_config(&config));
+
+    // Non-S3 backends for buckets with an origin section
+    let origins = crate::origin::from_config(&config);
+
+    // Background HEAD probes for breakers due a half-open test (task starts in init_cache)
+    let origin_prober = OriginProber::from_config(
+        &config.server.origin_probe,
+        &config.buckets,
+        &circuit_breakers,
+        &replica_sets,
+        metrics.clone(),
+    )
+    .map(Arc::new);
+
     let security_limits = config.server.security_limits.to_security_limits();
 
     // Cache is initialized to None here and then populated asynchronously
@@ -152,12 +259,27 @@ pub(super) fn initialize_from_config(config: Config) -> ProxyComponents {
         metrics,
         resource_monitor,
         request_semaphore,
+        priority_gate,
+        request_queue,
+        maintenance,
+        key_popularity,
+        auth_failures,
+        access_stats,
         coalescer,
         circuit_breakers,
+        bulkheads,
+        buffer_budget,
+        origins,
+        origin_prober,
         rate_limit_manager,
+        ip_ban_manager,
+        range_abuse,
+        access_grants,
         retry_policies,
         security_limits,
         replica_sets,
+        credential_providers,
+        credential_store,
         cache,
         opa_clients,
         opa_cache,
@@ -186,20 +308,24 @@ fn initialize_rate_limit_manager(config: &Config) -> Option<Arc<RateLimitManager
         return None;
     }
 
-    let global_rps = rate_limit_config
+    let global = rate_limit_config
         .global
         .as_ref()
-        .map(|g| g.requests_per_second);
-    let per_ip_rps = rate_limit_config
+        .map(|g| RateQuota::per_second(g.requests_per_second).with_burst(g.burst));
+    let per_ip = rate_limit_config
         .per_ip
         .as_ref()
-        .map(|p| p.requests_per_second);
-    let manager = RateLimitManager::new(global_rps, per_ip_rps);
+        .map(|p| RateQuota::per_second(p.requests_per_second).with_burst(p.burst));
+    let manager = RateLimitManager::with_quotas(global, per_ip, None);
 
     // Add per-bucket rate limiters
     for bucket in &config.buckets {
         if let Some(ref bucket_rate_limit) = bucket.s3.rate_limit {
-            manager.add_bucket_limiter(bucket.name.clone(), bucket_rate_limit.requests_per_second);
+            manager.add_bucket_quota(
+                bucket.name.clone(),
+                RateQuota::per_second(bucket_rate_limit.requests_per_second)
+                    .with_burst(bucket_rate_limit.burst),
+            );
         }
     }
 
@@ -210,6 +336,63 @@ fn initialize_rate_limit_manager(config: &Config) -> Option<Arc<RateLimitManager
     Some(Arc::new(manager))
 }
 
+/// Initialize automatic IP banning if enabled in config.
+///
+/// As with the rate limiter, the background cleanup/sync task is started
+/// later from `YatagarasuProxy::init_cache()` inside the Tokio runtime.
+fn initialize_ip_ban_manager(config: &Config) -> Option<Arc<IpBanManager>> {
+    let ip_ban_config = &config.server.ip_ban;
+    if !ip_ban_config.enabled {
+        return None;
+    }
+
+    match IpBanManager::new(ip_ban_config.clone()) {
+        Ok(manager) => Some(Arc::new(manager)),
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to initialize IP ban manager, banning disabled");
+            None
+        }
+    }
+}
+
+/// Initialize Range abuse detection if enabled in config.
+///
+/// The cleanup task is started from `YatagarasuProxy::init_cache()`, like
+/// the IP ban manager's.
+fn initialize_range_abuse_detector(config: &Config) -> Option<Arc<RangeAbuseDetector>> {
+    let range_config = &config.server.range_abuse;
+    if !range_config.enabled {
+        return None;
+    }
+
+    match RangeAbuseDetector::new(range_config.clone()) {
+        Ok(detector) => Some(Arc::new(detector)),
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to initialize Range abuse detection, disabled");
+            None
+        }
+    }
+}
+
+/// Initialize the temporary access grant store if enabled in config.
+///
+/// The cleanup/sync task is started from `YatagarasuProxy::init_cache()`,
+/// like the IP ban manager's.
+fn initialize_access_grants(config: &Config) -> Option<Arc<AccessGrantStore>> {
+    let grants_config = &config.server.access_grants;
+    if !grants_config.enabled {
+        return None;
+    }
+
+    match AccessGrantStore::new(grants_config.clone()) {
+        Ok(store) => Some(Arc::new(store)),
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to initialize access grants, disabled");
+            None
+        }
+    }
+}
+
 /// Initialize retry policies for all buckets.
 fn initialize_retry_policies(config: &Config) -> HashMap<String, RetryPolicy> {
     let mut retry_policies = HashMap::new();
@@ -225,6 +408,44 @@ fn initialize_retry_policies(config: &Config) -> HashMap<String, RetryPolicy> {
     retry_policies
 }
 
+/// Initialize credential providers for buckets with a dynamic `s3.credentials`
+/// source or an `assume_role_arn`.
+///
+/// Buckets with identical settings (and, for AssumeRole from static keys, the
+/// same keys) share one provider, so they fetch and refresh the same role's
+/// credentials once.
+fn initialize_credential_providers(config: &Config) -> HashMap<String, Arc<CredentialProvider>> {
+    type ProviderKey = (S3CredentialsConfig, Option<(String, String)>);
+    let mut shared: HashMap<ProviderKey, Arc<CredentialProvider>> = HashMap::new();
+    let mut providers = HashMap::new();
+    for bucket in &config.buckets {
+        let Some(credentials) = bucket.s3.credentials.as_ref().filter(|c| c.uses_provider()) else {
+            continue;
+        };
+        let static_keys = if credentials.is_dynamic() {
+            None
+        } else {
+            bucket
+                .s3
+                .static_keys()
+                .map(|(access_key, secret_key)| (access_key.to_string(), secret_key.to_string()))
+        };
+        let provider = shared
+            .entry((credentials.clone(), static_keys.clone()))
+            .or_insert_with(|| {
+                let provider = CredentialProvider::new(credentials.clone());
+                Arc::new(match &static_keys {
+                    Some((access_key, secret_key)) => {
+                        provider.with_static_keys(access_key, secret_key)
+                    }
+                    None => provider,
+                })
+            });
+        providers.insert(bucket.name.clone(), Arc::clone(provider));
+    }
+    providers
+}
+
 /// Initialize replica sets for HA bucket replication.
 fn initialize_replica_sets(config: &Config) -> HashMap<String, crate::replica_set::ReplicaSet> {
     let mut replica_sets = HashMap::new();
@@ -232,7 +453,10 @@ fn initialize_replica_sets(config: &Config) -> HashMap<String, crate::replica_se
         // After normalization, all buckets have replicas populated
         // (either from replicas array or converted from legacy fields)
         if let Some(ref replicas) = bucket.s3.replicas {
-            match crate::replica_set::ReplicaSet::new(replicas) {
+            match crate::replica_set::ReplicaSet::with_credentials(
+                replicas,
+                bucket.s3.credentials.as_ref(),
+            ) {
                 Ok(replica_set) => {
                     replica_sets.insert(bucket.name.clone(), replica_set);
                 }
@@ -245,7 +469,7 @@ fn initialize_replica_sets(config: &Config) -> HashMap<String, crate::replica_se
                     // Skip this bucket - it won't have failover support
                 }
             }
-        } else {
+        } else if bucket.origin.is_none() {
             tracing::warn!(
                 bucket = %bucket.name,
                 "Bucket has no replicas configured after normalization, skipping"
@@ -413,6 +637,33 @@ buckets: []
         assert!(manager.is_none());
     }
 
+    #[test]
+    fn test_initialize_ip_ban_manager() {
+        let mut config = minimal_config();
+        assert!(initialize_ip_ban_manager(&config).is_none());
+
+        config.server.ip_ban.enabled = true;
+        assert!(initialize_ip_ban_manager(&config).is_some());
+    }
+
+    #[test]
+    fn test_initialize_range_abuse_detector() {
+        let mut config = minimal_config();
+        assert!(initialize_range_abuse_detector(&config).is_none());
+
+        config.server.range_abuse.enabled = true;
+        assert!(initialize_range_abuse_detector(&config).is_some());
+    }
+
+    #[test]
+    fn test_initialize_access_grants() {
+        let mut config = minimal_config();
+        assert!(initialize_access_grants(&config).is_none());
+
+        config.server.access_grants.enabled = true;
+        assert!(initialize_access_grants(&config).is_some());
+    }
+
     #[test]
     fn test_initialize_retry_policies_defaults() {
         let config = minimal_config();
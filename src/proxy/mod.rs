@@ -8,26 +8,44 @@
 //! - Rate limiting and circuit breakers
 //! - Health and metrics endpoints
 
+mod batch;
+mod buffer_budget;
+mod bulkhead;
 #[allow(dead_code)] // Phase 37.4: Extracted module, integration pending
 mod cache_handler;
+mod content_type;
+mod control;
+mod cors;
+mod deprecation;
+mod disconnect;
 #[allow(dead_code)] // Phase 37.7: Extracted module, integration pending
 mod error_handler;
+mod filesystem;
 mod helpers;
 mod init;
 #[allow(dead_code)] // Phase 37.8: Extracted module, integration pending
 mod logging;
+mod origin_probe;
+mod preserved_headers;
+mod priority;
+mod queue;
 #[allow(dead_code)] // Phase 37.9: Extracted module, integration pending
 mod request_filter;
 #[allow(dead_code)] // Phase 37.6: Extracted module, integration pending
 mod response_handler;
+mod resume;
 #[allow(dead_code)] // Phase 37.3: Extracted module, integration pending
 mod routing_auth;
 mod security;
+mod server_timing;
+mod size_class;
 mod special_endpoints;
+mod synthetic;
 #[allow(dead_code)] // Phase 37.5: Extracted module, integration pending
 mod upstream;
 
 use async_trait::async_trait;
+use pingora_core::protocols::Digest;
 use pingora_core::upstreams::peer::HttpPeer;
 use pingora_core::Result;
 use pingora_http::{RequestHeader, ResponseHeader};
@@ -37,14 +55,34 @@ use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::{broadcast, Semaphore};
 
+use crate::analytics::{AccessStats, KeyPopularity};
 use crate::audit::AsyncAuditFileWriter;
-use crate::auth::{authenticate_request, AuthError};
-use crate::cache::tiered::TieredCache;
+use crate::auth::failures::{self as auth_failures, AuthFailureTracker};
+use crate::auth::{
+    authenticate, path_rules, sigv4, AuthError, Claims, KeyMaterial, RevocationList,
+};
+use crate::cache::compression::{accepts_zstd, decode_entry, ZSTD_ENCODING};
+use crate::cache::journal::{warm_from_journal, HotKeyJournal};
+use crate::cache::sendfile::{FileChunkStream, FILE_STREAM_CHUNK_SIZE};
+use crate::cache::tiered::{ChunkedObject, RangeChunkFill, TieredCache};
 use crate::cache::warming::PrewarmManager;
-use crate::cache::{Cache, CacheKey};
-use crate::circuit_breaker::CircuitBreaker;
-use crate::config::Config;
+use crate::cache::{
+    admission, experiment as cache_experiment, policy as cache_policy, preserve as cache_preserve,
+    ttl as cache_ttl, zero_copy, Cache, CacheControl, CacheKey, CacheSkipReason,
+};
+use crate::circuit_breaker::{CircuitBreaker, CircuitState, UpstreamOutcome};
+use crate::config::{
+    CacheOverrideMode, Config, EndpointAccess, FailureClassificationConfig, HotlinkAction,
+    S3SigningConfig, SizeClassCache, UnavailableReason,
+};
+use crate::constants::{
+    BUCKET_WRITE_METHODS, CACHE_OVERRIDE_HEADER, CACHE_OVERRIDE_TOKEN_HEADER,
+    MAX_TRACKED_OBJECT_SIZES, POST_POLICY_FORM_OVERHEAD_BYTES,
+};
+use crate::error::{ErrorCode, ERROR_CODE_HEADER};
+use crate::events::{self, EventKind};
 use crate::image_optimizer::ImageParams;
+use crate::maintenance::MaintenanceMode;
 use crate::metrics::Metrics;
 use crate::opa::{
     AuthorizationDecision as OpaAuthorizationDecision, FailMode as OpaFailMode, OpaCache, OpaInput,
@@ -55,15 +93,29 @@ use crate::openfga::{
     AuthorizationDecision as OpenFgaAuthorizationDecision, FailMode as OpenFgaFailMode,
     OpenFgaClient,
 };
-use crate::pipeline::RequestContext;
+use crate::origin::SharedOrigin;
+use crate::pipeline::{Phase, RequestContext};
 use crate::rate_limit::RateLimitManager;
 use crate::reload::ReloadManager;
 use crate::request_coalescing::{Coalescer, StreamMessage, StreamingSlot};
 use crate::resources::ResourceMonitor;
 use crate::retry::RetryPolicy;
 use crate::router::Router;
-use crate::s3::{build_get_object_request, build_head_object_request};
-use crate::security::SecurityLimits;
+use crate::s3::credential_store::CredentialStore;
+use crate::s3::credentials::CredentialProvider;
+use crate::s3::list::{self as s3_list, ListingFormat};
+use crate::s3::multipart::{self, MultipartOperation, UploadRequest};
+use crate::s3::presign::{self, PresignRequest};
+use crate::s3::{
+    build_get_object_request, build_head_object_request, parse_content_range, parse_range_header,
+    SECURITY_TOKEN_HEADER,
+};
+use crate::security::virus_scan::{self, ScanVerdict};
+use crate::security::{post_policy, signed_url};
+use crate::security::{
+    AccessGrantStore, GrantMatch, IpBanManager, RangeAbuseDetector, RangeVerdict, SecurityLimits,
+};
+use crate::util::unix_now;
 use crate::watermark::{ImageFetcher, ImageFetcherConfig, WatermarkContext, WatermarkProcessor};
 use arc_swap::ArcSwap;
 use std::path::PathBuf;
@@ -72,21 +124,65 @@ use std::str::FromStr;
 // ProxyComponents is defined in init.rs
 use init::ProxyComponents;
 
+pub use control::{ControlPlane, PurgeScope};
+
+/// Emit a tracing event at `$level` unless the request's bucket sets a
+/// quieter `logging.level` (see [`RequestContext::log_enabled`])
+macro_rules! bucket_log {
+    ($ctx:expr, $level:ident, $($arg:tt)+) => {
+        if $ctx.log_enabled(tracing::Level::$level) {
+            tracing::event!(tracing::Level::$level, $($arg)+);
+        }
+    };
+}
+
+/// Rejected POST policy upload: status, error code, metric reason and message
+type UploadRejection = (u16, ErrorCode, &'static str, String);
+
 /// YatagarasuProxy implements the Pingora ProxyHttp trait
 /// Handles routing, authentication, and S3 proxying
 pub struct YatagarasuProxy {
-    config: ArcSwap<Config>,
-    router: ArcSwap<Router>,
+    config: Arc<ArcSwap<Config>>,
+    router: Arc<ArcSwap<Router>>,
     metrics: Arc<Metrics>,
     reload_manager: Option<Arc<ReloadManager>>,
     resource_monitor: Arc<ResourceMonitor>,
     request_semaphore: Arc<Semaphore>,
+    /// Reserved concurrency for trusted high-priority requests (None when disabled)
+    priority_gate: Option<priority::PriorityGate>,
+    /// Bounded wait for a permit when the concurrency limit is reached (None when disabled)
+    request_queue: Option<queue::RequestQueue>,
+    /// Maintenance mode switch (toggled via admin API or config reload)
+    maintenance: Arc<MaintenanceMode>,
+    /// Per-bucket key popularity for the analytics API (None when disabled)
+    key_popularity: Option<Arc<KeyPopularity>>,
+    /// Rolling per-client auth failure counts for `/admin/auth/failures`
+    auth_failures: Arc<AuthFailureTracker>,
+    /// Per-key access statistics for periodic export (None when disabled)
+    access_stats: Option<Arc<AccessStats>>,
     /// Unified coalescer for deduplicating concurrent S3 requests (Phase 38/40)
     /// None if coalescing is disabled in config
     #[allow(dead_code)]
     coalescer: Option<Coalescer>,
     circuit_breakers: Arc<HashMap<String, Arc<CircuitBreaker>>>,
+    /// Per-bucket upstream concurrency limits (buckets with s3.bulkhead)
+    bulkheads: Arc<HashMap<String, Arc<bulkhead::Bulkhead>>>,
+    /// Memory shared by responses buffered for cache population
+    buffer_budget: Arc<buffer_budget::BufferBudget>,
+    /// Downstream connections seen so far, for accept vs keep-alive metrics
+    downstream_connections: helpers::DownstreamConnections,
+    /// Object sizes seen on earlier responses, for bucket size classes
+    object_sizes: size_class::ObjectSizeTracker,
+    /// Non-S3 backends per bucket (buckets with an origin section)
+    origins: Arc<HashMap<String, SharedOrigin>>,
+    /// Background HEAD probes for open circuit breakers (None when disabled)
+    origin_prober: Option<Arc<origin_probe::OriginProber>>,
     rate_limit_manager: Option<Arc<RateLimitManager>>,
+    /// Automatic temporary IP banning (None when disabled)
+    ip_ban_manager: Option<Arc<IpBanManager>>,
+    range_abuse: Option<Arc<RangeAbuseDetector>>,
+    /// Temporary read grants created through the admin API (None when disabled)
+    access_grants: Option<Arc<AccessGrantStore>>,
     /// Retry policies per bucket for automatic retry on transient S3 failures
     retry_policies: Arc<HashMap<String, RetryPolicy>>,
     /// Security validation limits (request size, headers, URI, path traversal)
@@ -95,9 +191,15 @@ pub struct YatagarasuProxy {
     start_time: Instant,
     /// Replica sets per bucket (Phase 23: High Availability bucket replication with automatic failover)
     replica_sets: Arc<HashMap<String, crate::replica_set::ReplicaSet>>,
+    /// Upstream credential providers for buckets with a dynamic `s3.credentials` source
+    credential_providers: Arc<HashMap<String, Arc<CredentialProvider>>>,
+    /// Static upstream keys, read at sign time and swapped on reload
+    credential_store: Arc<CredentialStore>,
     /// Tiered cache (memory → disk → redis) for caching S3 responses (Phase 30)
     /// Optional: cache is only enabled if configured
     cache: Option<Arc<TieredCache>>,
+    /// Hot key journal for startup cache warming (set up in init_cache when configured)
+    hot_key_journal: Option<Arc<HotKeyJournal>>,
     /// OPA clients per bucket (Phase 32: OPA Integration)
     /// Maps bucket name to OPA client for authorization decisions
     opa_clients: Arc<HashMap<String, SharedOpaClient>>,
@@ -123,20 +225,38 @@ impl YatagarasuProxy {
         reload_manager: Option<Arc<ReloadManager>>,
     ) -> Self {
         Self {
-            config: ArcSwap::from_pointee(components.config),
-            router: ArcSwap::from_pointee(components.router),
+            config: Arc::new(ArcSwap::from_pointee(components.config)),
+            router: Arc::new(ArcSwap::from_pointee(components.router)),
             metrics: components.metrics,
             reload_manager,
             resource_monitor: components.resource_monitor,
             request_semaphore: components.request_semaphore,
+            priority_gate: components.priority_gate,
+            request_queue: components.request_queue,
+            maintenance: components.maintenance,
+            key_popularity: components.key_popularity,
+            auth_failures: components.auth_failures,
+            access_stats: components.access_stats,
             coalescer: components.coalescer,
             circuit_breakers: Arc::new(components.circuit_breakers),
+            bulkheads: Arc::new(components.bulkheads),
+            buffer_budget: components.buffer_budget,
+            downstream_connections: helpers::DownstreamConnections::default(),
+            object_sizes: size_class::ObjectSizeTracker::new(MAX_TRACKED_OBJECT_SIZES),
+            origins: Arc::new(components.origins),
+            origin_prober: components.origin_prober,
             rate_limit_manager: components.rate_limit_manager,
+            ip_ban_manager: components.ip_ban_manager,
+            range_abuse: components.range_abuse,
+            access_grants: components.access_grants,
             retry_policies: Arc::new(components.retry_policies),
             security_limits: components.security_limits,
             start_time: Instant::now(),
             replica_sets: Arc::new(components.replica_sets),
+            credential_providers: Arc::new(components.credential_providers),
+            credential_store: components.credential_store,
             cache: components.cache,
+            hot_key_journal: None,
             opa_clients: Arc::new(components.opa_clients),
             opa_cache: components.opa_cache,
             openfga_clients: Arc::new(components.openfga_clients),
@@ -159,9 +279,15 @@ impl YatagarasuProxy {
 
     /// Create a new YatagarasuProxy with reload support.
     pub fn with_reload(config: Config, config_path: PathBuf) -> Self {
+        Self::with_reload_manager(config, ReloadManager::new(config_path))
+    }
+
+    /// Create proxy with a preconfigured reload manager (e.g. one keeping
+    /// subsystems disabled across reloads)
+    pub fn with_reload_manager(config: Config, reload_manager: ReloadManager) -> Self {
         let components = init::initialize_from_config(config);
 
-        let reload_manager = Arc::new(ReloadManager::new(config_path));
+        let reload_manager = Arc::new(reload_manager);
 
         #[cfg(unix)]
         {
@@ -194,41 +320,23 @@ impl YatagarasuProxy {
 
     /// Reload configuration from disk
     fn reload_configuration(&self) -> Result<u64, String> {
-        if let Some(reload_manager) = &self.reload_manager {
-            // Load current config to get generation
-            let current_config = self.config.load();
-            let current_generation = current_config.generation;
-
-            match reload_manager.reload_config_with_generation(current_generation) {
-                Ok(new_config) => {
-                    let new_generation = new_config.generation;
-                    tracing::info!(
-                        old_generation = current_generation,
-                        new_generation = new_generation,
-                        "Configuration loaded successfully, applying changes"
-                    );
-
-                    // Create new router
-                    let new_router = Router::new(new_config.buckets.clone());
-
-                    // Update shared state atomically (using ArcSwap)
-                    self.config.store(Arc::new(new_config));
-                    self.router.store(Arc::new(new_router));
-
-                    // Record reload metrics
-                    self.metrics.increment_reload_success();
-                    self.metrics.set_config_generation(new_generation);
+        self.control_plane().reload()
+    }
 
-                    Ok(new_generation)
-                }
-                Err(e) => {
-                    tracing::error!(error = %e, "Configuration reload failed");
-                    self.metrics.increment_reload_failure();
-                    Err(e)
-                }
-            }
-        } else {
-            Err("Hot reload not enabled".to_string())
+    /// Handle for reload, purge, stats and health outside the HTTP path
+    ///
+    /// Take it after [`init_cache`](Self::init_cache) so it sees the cache.
+    pub fn control_plane(&self) -> ControlPlane {
+        ControlPlane {
+            config: Arc::clone(&self.config),
+            router: Arc::clone(&self.router),
+            metrics: Arc::clone(&self.metrics),
+            reload_manager: self.reload_manager.clone(),
+            maintenance: Arc::clone(&self.maintenance),
+            credential_store: Arc::clone(&self.credential_store),
+            replica_sets: Arc::clone(&self.replica_sets),
+            cache: self.cache.clone(),
+            start_time: self.start_time,
         }
     }
 
@@ -251,104 +359,1585 @@ impl YatagarasuProxy {
                             layers = ?cache_config.cache_layers,
                             "Cache initialized successfully"
                         );
+                        events::publish(EventKind::CacheInitialized {
+                            success: true,
+                            layers: cache_config.cache_layers.clone(),
+                            error: None,
+                        });
                         self.cache = Some(Arc::new(tiered_cache));
 
                         // Update prewarm manager with new cache instance
                         if let Some(ref cache) = self.cache {
                             self.prewarm_manager.set_cache(cache.clone());
                         }
+
+                        if let Some(ref journal_config) = cache_config.hot_keys {
+                            self.hot_key_journal =
+                                Some(self.start_hot_key_journal(journal_config.clone()));
+                        }
+                    }
+                    Err(e) => {
+                        tracing::error!(
+                            error = %e,
+                            "Failed to initialize cache, continuing without cache"
+                        );
+                        events::publish(EventKind::CacheInitialized {
+                            success: false,
+                            layers: cache_config.cache_layers.clone(),
+                            error: Some(e.to_string()),
+                        });
+                        // Continue without cache - graceful degradation
                     }
+                }
+            } else {
+                tracing::info!("Cache is disabled or no cache layers configured");
+            }
+        } else {
+            tracing::info!("No cache configuration found");
+        }
+
+        // Start rate limiter cleanup task now that we have a Tokio runtime (Phase 36)
+        // This prevents unbounded memory growth from per-IP/per-user tracking
+        if let Some(ref rate_limit_manager) = self.rate_limit_manager {
+            rate_limit_manager.start_cleanup_task(None); // Uses default interval (60s)
+        }
+
+        // Start IP ban cleanup (and Redis sync, if shared) for the same reason
+        if let Some(ref ip_ban_manager) = self.ip_ban_manager {
+            ip_ban_manager.start_background_task();
+        }
+        if let Some(ref range_abuse) = self.range_abuse {
+            range_abuse.start_cleanup_task();
+        }
+        if let Some(ref access_grants) = self.access_grants {
+            access_grants.start_background_task();
+        }
+
+        // Start periodic access statistics export
+        if let Some(ref access_stats) = self.access_stats {
+            access_stats.start_export_task(config.buckets.clone());
+        }
+
+        // Re-read rotated JWT keys, refresh JWKS and the revocation list on
+        // their own timers, independently of full config reloads
+        if let Some(jwt) = config.jwt.as_ref().filter(|jwt| jwt.enabled) {
+            KeyMaterial::global().start_reload_tasks(jwt);
+            if let Some(revocation) = &jwt.revocation {
+                RevocationList::global().start_reload_task(revocation);
+            }
+        }
+
+        // Fetch upstream credentials from the environment and keep them fresh
+        let mut started = Vec::new();
+        for provider in self.credential_providers.values() {
+            if !started.iter().any(|p| Arc::ptr_eq(p, provider)) {
+                provider.start().await;
+                started.push(Arc::clone(provider));
+            }
+        }
+
+        // Start background probes for open circuit breakers
+        if let Some(ref origin_prober) = self.origin_prober {
+            origin_prober.start();
+        }
+
+        self
+    }
+
+    /// Load the hot key journal, warm the cache from it in the background and
+    /// start periodic journal writes
+    fn start_hot_key_journal(
+        &self,
+        journal_config: crate::cache::HotKeyJournalConfig,
+    ) -> Arc<HotKeyJournal> {
+        let config = self.config.load();
+        let concurrency = journal_config.warm_concurrency;
+        let max_item_size_bytes = config
+            .cache
+            .as_ref()
+            .map(|c| c.memory.max_item_size_mb * 1024 * 1024)
+            .unwrap_or(u64::MAX);
+        let journal = Arc::new(HotKeyJournal::new(journal_config));
+
+        match journal.load() {
+            Ok(keys) if !keys.is_empty() => {
+                journal.seed(&keys);
+                if let Some(ref cache) = self.cache {
+                    let cache = cache.clone() as Arc<dyn Cache>;
+                    let buckets = config.buckets.clone();
+                    tokio::spawn(async move {
+                        let started = Instant::now();
+                        let total = keys.len();
+                        let summary = warm_from_journal(
+                            cache,
+                            keys,
+                            &buckets,
+                            max_item_size_bytes,
+                            concurrency,
+                        )
+                        .await;
+                        tracing::info!(
+                            keys = total,
+                            warmed = summary.warmed,
+                            skipped = summary.skipped,
+                            failed = summary.failed,
+                            duration_ms = started.elapsed().as_millis() as u64,
+                            "Cache warmed from hot key journal"
+                        );
+                    });
+                }
+            }
+            Ok(_) => tracing::info!("Hot key journal is empty, skipping startup warming"),
+            Err(e) => tracing::warn!(error = %e, "Failed to load hot key journal"),
+        }
+
+        journal.start_flush_task();
+        journal
+    }
+
+    /// Get a reference to the metrics instance
+    pub fn metrics(&self) -> Arc<Metrics> {
+        Arc::clone(&self.metrics)
+    }
+
+    /// Extract headers from Pingora RequestHeader into HashMap.
+    fn extract_headers(req: &RequestHeader) -> HashMap<String, String> {
+        helpers::extract_headers(req)
+    }
+
+    /// Extract query parameters from URI.
+    fn extract_query_params(req: &RequestHeader) -> HashMap<String, String> {
+        helpers::extract_query_params(req)
+    }
+
+    /// Extract client IP address from session (X-Forwarded-For aware).
+    fn get_client_ip(&self, session: &Session) -> String {
+        helpers::get_client_ip(session)
+    }
+
+    /// Count a JWT failure by reason and issuer, and against the client for
+    /// the top-offenders view
+    fn record_auth_failure_reason(
+        &self,
+        error: &AuthError,
+        bucket: &str,
+        client_ip: &str,
+        headers: &HashMap<String, String>,
+        query_params: &HashMap<String, String>,
+        jwt_config: &crate::config::JwtConfig,
+    ) {
+        let failure = auth_failures::describe(error, headers, query_params, jwt_config);
+        self.metrics.increment_auth_failure_reason(
+            bucket,
+            &failure.issuer,
+            failure.reason.as_str(),
+        );
+        self.auth_failures.record(client_ip, bucket, failure.reason);
+    }
+
+    /// Export circuit breaker metrics for Prometheus.
+    fn export_circuit_breaker_metrics(&self) -> String {
+        helpers::export_circuit_breaker_metrics(&self.circuit_breakers)
+    }
+
+    /// Static endpoint pool of the replica selected for this request, if any.
+    fn endpoint_pool(
+        &self,
+        ctx: &RequestContext,
+    ) -> Option<&Arc<crate::replica_set::EndpointPool>> {
+        let bucket_config = ctx.bucket_config()?;
+        let replica_name = ctx.replica_name()?;
+        self.replica_sets
+            .get(&bucket_config.name)?
+            .replicas
+            .iter()
+            .find(|replica| replica.name == replica_name)?
+            .endpoint_pool
+            .as_ref()
+    }
+
+    /// Build WatermarkContext from request context for watermark template resolution.
+    /// Phase 50: Watermark integration
+    fn build_watermark_context(
+        ctx: &RequestContext,
+        bucket_name: &str,
+        client_ip: Option<&str>,
+    ) -> WatermarkContext {
+        let mut wm_ctx = WatermarkContext::default();
+
+        // Set client IP
+        if let Some(ip) = client_ip {
+            wm_ctx.client_ip = Some(ip.to_string());
+        }
+
+        // Set JWT claims if authenticated
+        if let Some(claims) = ctx.claims() {
+            // Add standard claims
+            if let Some(ref sub) = claims.sub {
+                wm_ctx.jwt_claims.insert("sub".to_string(), sub.clone());
+            }
+            if let Some(ref iss) = claims.iss {
+                wm_ctx.jwt_claims.insert("iss".to_string(), iss.clone());
+            }
+            // Add custom claims
+            for (key, value) in &claims.custom {
+                // Convert Value to String
+                if let Some(s) = value.as_str() {
+                    wm_ctx.jwt_claims.insert(key.clone(), s.to_string());
+                } else {
+                    wm_ctx.jwt_claims.insert(key.clone(), value.to_string());
+                }
+            }
+        }
+
+        // Set headers
+        wm_ctx.headers = ctx.headers().clone();
+
+        // Set path and bucket
+        wm_ctx.path = ctx.path().to_string();
+        wm_ctx.bucket = bucket_name.to_string();
+
+        wm_ctx
+    }
+
+    /// HEAD-before-GET: check a cache hit past the bucket's soft TTL against S3.
+    ///
+    /// Returns false when the object changed or was deleted; the stale entry is
+    /// removed so the request falls through to a normal fetch. If the HEAD
+    /// fails, the cached copy is served.
+    async fn confirm_cache_freshness(
+        &self,
+        bucket_config: &crate::config::BucketConfig,
+        cache_key: &CacheKey,
+        entry: &crate::cache::CacheEntry,
+    ) -> bool {
+        let Some(soft_ttl) = bucket_config
+            .cache
+            .as_ref()
+            .and_then(|c| c.soft_ttl_seconds)
+        else {
+            return true;
+        };
+        // Image variants carry no origin ETag to compare against
+        let age = std::time::SystemTime::now()
+            .duration_since(entry.created_at)
+            .unwrap_or_default();
+        if cache_key.variant.is_some() || age < Duration::from_secs(soft_ttl) {
+            return true;
+        }
+        // The check is a HeadObject; non-S3 origins are served until the entry expires
+        if self.origins.contains_key(&bucket_config.name) {
+            return true;
+        }
+
+        let s3_client = crate::s3::S3Client {
+            config: bucket_config.s3.clone(),
+        };
+        match s3_client.head_object(&cache_key.object_key).await {
+            Ok(Some(meta))
+                if entry.matches_object(
+                    &meta.etag,
+                    meta.content_length,
+                    meta.last_modified.as_deref(),
+                ) =>
+            {
+                self.metrics
+                    .increment_freshness_check(&bucket_config.name, "fresh");
+                true
+            }
+            Ok(_) => {
+                self.metrics
+                    .increment_freshness_check(&bucket_config.name, "changed");
+                tracing::debug!(
+                    bucket = %bucket_config.name,
+                    object_key = %cache_key.object_key,
+                    "Object changed since it was cached, refetching"
+                );
+                if let Some(ref cache) = self.cache {
+                    let _ = cache.delete(cache_key).await;
+                }
+                false
+            }
+            Err(e) => {
+                self.metrics
+                    .increment_freshness_check(&bucket_config.name, "error");
+                tracing::warn!(
+                    bucket = %bucket_config.name,
+                    object_key = %cache_key.object_key,
+                    error = %e,
+                    "Freshness HEAD failed, serving cached copy"
+                );
+                true
+            }
+        }
+    }
+
+    /// Handle a client that disconnected mid-transfer.
+    ///
+    /// Pingora has already dropped the upstream connection. If the response was
+    /// being buffered for the cache and is nearly complete, the remaining bytes
+    /// are fetched in the background so the cache entry is still populated.
+    fn handle_client_disconnect(&self, ctx: &mut RequestContext) {
+        use crate::cache::{CacheControl, CacheEntry};
+        use disconnect::DisconnectAction;
+
+        let config = ctx
+            .config()
+            .cloned()
+            .unwrap_or_else(|| self.config.load_full());
+        let received = ctx.upstream_bytes_received();
+        let cache_control = ctx
+            .response_cache_control()
+            .map(CacheControl::parse)
+            .unwrap_or_default();
+        let cache_fill_in_progress = self.cache.is_some()
+            && ctx.should_cache_response()
+            && ctx.is_response_buffering_enabled()
+            && !ctx.is_optimizing_image()
+            && ctx.listing().is_none()
+            && ctx.total_response_size() as u64 == received
+            && cache_control.should_store()
+            // A rewritten ETag can't make the tail fetch conditional (If-Match),
+            // and the tail is a ranged GetObject, which non-S3 origins don't serve
+            && !ctx
+                .bucket_config()
+                .is_some_and(|b| b.etag_rewrite.is_some() || self.origins.contains_key(&b.name));
+
+        let action = disconnect::plan_disconnect(
+            &config.server.client_disconnect,
+            received,
+            ctx.response_content_length(),
+            cache_fill_in_progress,
+        );
+
+        let offset = match action {
+            DisconnectAction::Cancel { bytes_saved } => {
+                self.metrics.record_client_abort(false, bytes_saved);
+                bucket_log!(ctx, INFO,
+                    request_id = %ctx.request_id(),
+                    bytes_received = received,
+                    bytes_saved = bytes_saved,
+                    "Client disconnected, upstream fetch cancelled"
+                );
+                return;
+            }
+            DisconnectAction::FinishCacheFill { offset } => offset,
+        };
+
+        // The request is over, so the buffer can be moved into the background task
+        let Some(buffered) = ctx.take_response_buffer() else {
+            return;
+        };
+        let (Some(bucket_config), Some(cache)) = (ctx.bucket_config(), self.cache.as_ref()) else {
+            return;
+        };
+
+        // Fetch from the replica that served the partial response, if any
+        let s3_client = ctx
+            .replica_name()
+            .and_then(|name| {
+                self.replica_sets
+                    .get(&bucket_config.name)
+                    .and_then(|set| set.replicas.iter().find(|r| r.name == name))
+            })
+            .map(|replica| replica.client.clone())
+            .unwrap_or_else(|| crate::s3::S3Client {
+                config: bucket_config.s3.clone(),
+            });
+
+        let cache_key = CacheKey {
+            bucket: bucket_config.name.clone(),
+            object_key: ctx.s3_key().to_string(),
+            etag: None,
+            variant: None,
+        };
+        let etag = ctx.response_etag().unwrap_or("").to_string();
+        let content_type = ctx
+            .response_content_type()
+            .unwrap_or("application/octet-stream")
+            .to_string();
+        let content_type_rules = bucket_config.content_type.clone();
+        let last_modified = ctx.response_last_modified().map(|s| s.to_string());
+        let preserved_headers = ctx.response_preserved_headers().to_vec();
+        let ttl = cache_ttl::entry_ttl(
+            bucket_config,
+            &cache_control,
+            ctx.response_expires(),
+            ctx.response_date(),
+        );
+        let expected_len = ctx.response_content_length().unwrap_or(0);
+        let cache = Arc::clone(cache);
+        let request_id = ctx.request_id().to_string();
+
+        self.metrics.record_client_abort(true, 0);
+        bucket_log!(ctx, INFO,
+            request_id = %request_id,
+            bytes_received = received,
+            bytes_remaining = expected_len.saturating_sub(received),
+            "Client disconnected, finishing nearly complete cache fill"
+        );
+
+        tokio::spawn(async move {
+            let tail = match s3_client
+                .get_object_from(&cache_key.object_key, offset, Some(&etag))
+                .await
+            {
+                Ok(tail) => tail,
+                Err(e) => {
+                    tracing::warn!(
+                        request_id = %request_id,
+                        error = %e,
+                        "Failed to fetch remainder after client disconnect"
+                    );
+                    return;
+                }
+            };
+
+            let mut data = buffered;
+            data.extend_from_slice(&tail);
+            if data.len() as u64 != expected_len {
+                tracing::warn!(
+                    request_id = %request_id,
+                    expected = expected_len,
+                    actual = data.len(),
+                    "Length mismatch after client disconnect, not caching"
+                );
+                return;
+            }
+
+            let content_type = content_type_rules
+                .as_ref()
+                .and_then(|rules| {
+                    content_type::resolve(
+                        rules,
+                        &cache_key.object_key,
+                        Some(&content_type),
+                        Some(&data),
+                    )
+                })
+                .unwrap_or(content_type);
+            let entry = CacheEntry::new(
+                bytes::Bytes::from(data),
+                content_type,
+                etag,
+                last_modified,
+                Some(ttl),
+            )
+            .with_headers(preserved_headers);
+            if let Err(e) = cache.set(cache_key, entry).await {
+                tracing::warn!(
+                    request_id = %request_id,
+                    error = %e,
+                    "Failed to populate cache after client disconnect"
+                );
+            } else {
+                tracing::debug!(
+                    request_id = %request_id,
+                    "Cached response completed after client disconnect"
+                );
+            }
+        });
+    }
+
+    /// Handle a browser form upload authorized by the bucket's POST policy:
+    /// verify the form, scan and write the file to S3, and drop any cached copy.
+    async fn handle_post_policy_upload(
+        &self,
+        session: &mut Session,
+        ctx: &mut RequestContext,
+        bucket_config: &crate::config::BucketConfig,
+        policy_config: &crate::config::PostPolicyConfig,
+    ) -> Result<bool> {
+        let request_origin = ctx.headers().get("origin").cloned();
+        let outcome = match self.read_post_policy_upload(session, policy_config).await {
+            Ok(upload) => self.store_post_policy_upload(bucket_config, upload).await,
+            Err(e) => {
+                let code = match e.status() {
+                    403 => ErrorCode::AuthPostPolicyInvalid,
+                    413 => ErrorCode::PayloadTooLarge,
+                    _ => ErrorCode::BadRequest,
+                };
+                Err((e.status(), code, e.as_str(), e.message().to_string()))
+            }
+        };
+
+        let (mut header, body) = match outcome {
+            Ok((key, etag)) => {
+                self.metrics
+                    .increment_post_policy_upload(&bucket_config.name, "uploaded");
+                bucket_log!(ctx, INFO,
+                    request_id = %ctx.request_id(),
+                    bucket = %bucket_config.name,
+                    object_key = %key,
+                    "Stored POST policy upload"
+                );
+                let mut header = ResponseHeader::build(204, None)?;
+                header.insert_header(
+                    "Location",
+                    format!(
+                        "{}/{}",
+                        bucket_config.path_prefix.trim_end_matches('/'),
+                        key
+                    ),
+                )?;
+                if let Some(etag) = etag {
+                    header.insert_header("ETag", format!("\"{}\"", etag))?;
+                }
+                (header, None)
+            }
+            Err((status, code, reason, message)) => {
+                self.metrics
+                    .increment_post_policy_upload(&bucket_config.name, reason);
+                bucket_log!(ctx, WARN,
+                    request_id = %ctx.request_id(),
+                    bucket = %bucket_config.name,
+                    reason = reason,
+                    "POST policy upload rejected"
+                );
+                let mut header = ResponseHeader::build(status, None)?;
+                header.insert_header(ERROR_CODE_HEADER, code.as_str())?;
+                header.insert_header("Content-Type", "application/json")?;
+                let error_body = serde_json::json!({
+                    "error": reason,
+                    "code": code.as_str(),
+                    "message": message,
+                    "status": status
+                })
+                .to_string();
+                (header, Some(error_body))
+            }
+        };
+
+        let status = header.status.as_u16();
+        header.insert_header(
+            "Content-Length",
+            body.as_ref().map_or(0, String::len).to_string(),
+        )?;
+        cors::apply(
+            &mut header,
+            cors::response_headers(bucket_config, request_origin.as_deref()),
+        )?;
+        session
+            .write_response_header(Box::new(header), body.is_none())
+            .await?;
+        if let Some(body) = body {
+            session.write_response_body(Some(body.into()), true).await?;
+        }
+        self.metrics.increment_status_count(status);
+        Ok(true)
+    }
+
+    /// Read and verify a POST policy upload form
+    async fn read_post_policy_upload(
+        &self,
+        session: &mut Session,
+        policy_config: &crate::config::PostPolicyConfig,
+    ) -> std::result::Result<post_policy::VerifiedUpload, post_policy::PostPolicyError> {
+        let boundary = session
+            .req_header()
+            .headers
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .and_then(post_policy::boundary)
+            .ok_or(post_policy::PostPolicyError::MalformedForm)?;
+
+        // The file plus its form fields, bounded before anything is parsed
+        let limit = policy_config.max_size_bytes + POST_POLICY_FORM_OVERHEAD_BYTES;
+        let mut body = bytes::BytesMut::new();
+        loop {
+            match session.read_request_body().await {
+                Ok(Some(chunk)) => {
+                    if (body.len() + chunk.len()) as u64 > limit {
+                        return Err(post_policy::PostPolicyError::TooLarge);
+                    }
+                    body.extend_from_slice(&chunk);
+                }
+                Ok(None) => break,
+                Err(_) => return Err(post_policy::PostPolicyError::MalformedForm),
+            }
+        }
+
+        let form = post_policy::parse_form(&body.freeze(), &boundary)?;
+        let now = unix_now();
+        post_policy::verify(policy_config, form, now)
+    }
+
+    /// Scan and write a verified upload, returning its key and ETag
+    async fn store_post_policy_upload(
+        &self,
+        bucket_config: &crate::config::BucketConfig,
+        upload: post_policy::VerifiedUpload,
+    ) -> std::result::Result<(String, Option<String>), UploadRejection> {
+        if let Some(scan_config) = &bucket_config.upload_scan {
+            let size = upload.data.len() as u64;
+            if size > scan_config.max_size_bytes() {
+                self.metrics
+                    .record_upload_scan(&bucket_config.name, "skipped", None);
+                if !scan_config.fail_open {
+                    return Err((
+                        413,
+                        ErrorCode::PayloadTooLarge,
+                        "scan_too_large",
+                        "Upload body exceeds the scan size limit".to_string(),
+                    ));
+                }
+            } else {
+                let started = Instant::now();
+                let verdict = virus_scan::scan_clamd(
+                    &scan_config.clamd,
+                    &upload.data,
+                    Duration::from_millis(scan_config.timeout_ms),
+                )
+                .await;
+                let duration_ms = started.elapsed().as_millis() as u64;
+                match verdict {
+                    Ok(ScanVerdict::Clean) => {
+                        self.metrics.record_upload_scan(
+                            &bucket_config.name,
+                            "clean",
+                            Some(duration_ms),
+                        );
+                    }
+                    Ok(ScanVerdict::Infected(signature)) => {
+                        self.metrics.record_upload_scan(
+                            &bucket_config.name,
+                            "infected",
+                            Some(duration_ms),
+                        );
+                        return Err((
+                            403,
+                            ErrorCode::AuthForbidden,
+                            "infected",
+                            format!("Upload rejected by virus scan: {}", signature),
+                        ));
+                    }
+                    Err(e) => {
+                        self.metrics.record_upload_scan(
+                            &bucket_config.name,
+                            "error",
+                            Some(duration_ms),
+                        );
+                        if !scan_config.fail_open {
+                            tracing::error!(
+                                bucket = %bucket_config.name,
+                                error = %e,
+                                "Upload scan failed, rejecting upload"
+                            );
+                            return Err((
+                                503,
+                                ErrorCode::Unavailable,
+                                "scan_error",
+                                "Upload could not be scanned".to_string(),
+                            ));
+                        }
+                    }
+                }
+            }
+        }
+
+        let s3_client = crate::s3::S3Client {
+            config: bucket_config.s3.clone(),
+        };
+        let etag = s3_client
+            .put_object(&upload.key, upload.data, &upload.content_type)
+            .await
+            .map_err(|e| {
+                tracing::error!(
+                    bucket = %bucket_config.name,
+                    object_key = %upload.key,
+                    error = %e,
+                    "Failed to store POST policy upload"
+                );
+                (
+                    502,
+                    ErrorCode::UpstreamError,
+                    "upstream_error",
+                    "Failed to store the upload".to_string(),
+                )
+            })?;
+
+        // The object was replaced: a read right after the upload must not
+        // get the old body, or an old variant or range chunk, from the cache
+        if let Some(cache) = &self.cache {
+            match cache.delete_object(&bucket_config.name, &upload.key).await {
+                Ok(0) => {}
+                Ok(_) => self.metrics.increment_cache_purge(),
+                Err(e) => {
+                    tracing::warn!(
+                        error = %e,
+                        "Failed to invalidate cache entry after POST policy upload"
+                    );
+                }
+            }
+        }
+
+        Ok((upload.key, etag))
+    }
+
+    /// Answer an authorized GET or HEAD with `302 Found` to a presigned S3 URL
+    /// for the object instead of proxying its bytes.
+    async fn handle_presigned_redirect(
+        &self,
+        session: &mut Session,
+        ctx: &mut RequestContext,
+        bucket_config: &crate::config::BucketConfig,
+        redirect: &crate::config::PresignedRedirectConfig,
+    ) -> Result<bool> {
+        // First replica whose breaker is not open, else the legacy S3 config.
+        // The breaker state is only read: no request goes through the proxy.
+        let replica = self.replica_sets.get(&bucket_config.name).and_then(|set| {
+            set.replicas
+                .iter()
+                .find(|r| r.circuit_breaker.state() != CircuitState::Open)
+        });
+        let s3 = replica.map_or(&bucket_config.s3, |r| &r.client.config);
+        let replica_name = replica.map(|r| r.name.as_str());
+
+        let (access_key, secret_key) =
+            match self.credential_store.get(&bucket_config.name, replica_name) {
+                Some(stored) => (stored.access_key.clone(), stored.secret_key.clone()),
+                None => (s3.access_key.clone(), s3.secret_key.clone()),
+            };
+        let credentials = match self.credential_providers.get(&bucket_config.name) {
+            Some(provider) => provider
+                .current()
+                .map(|c| (c.access_key_id, c.secret_access_key, c.session_token)),
+            None => Some((access_key, secret_key, None)),
+        };
+
+        let request_origin = ctx.headers().get("origin").cloned();
+        let Some((access_key, secret_key, session_token)) = credentials else {
+            self.metrics
+                .increment_presigned_redirect(&bucket_config.name, "unavailable");
+            bucket_log!(ctx, WARN,
+                request_id = %ctx.request_id(),
+                bucket = %bucket_config.name,
+                "Upstream S3 credentials are not available yet, cannot presign"
+            );
+            let mut header = ResponseHeader::build(503, None)?;
+            header.insert_header(ERROR_CODE_HEADER, ErrorCode::Unavailable.as_str())?;
+            header.insert_header("Content-Type", "application/json")?;
+            let error_body = serde_json::json!({
+                "error": "Service Unavailable",
+                "code": ErrorCode::Unavailable.as_str(),
+                "message": "Upstream S3 credentials are not available yet",
+                "status": 503
+            })
+            .to_string();
+            header.insert_header("Content-Length", error_body.len().to_string())?;
+            cors::apply(
+                &mut header,
+                cors::response_headers(bucket_config, request_origin.as_deref()),
+            )?;
+            session
+                .write_response_header(Box::new(header), false)
+                .await?;
+            session
+                .write_response_body(Some(error_body.into()), true)
+                .await?;
+            self.metrics.increment_status_count(503);
+            return Ok(true);
+        };
+
+        let signing = bucket_config.s3.signing.as_ref();
+        let path_style = s3.endpoint.is_some() || signing.is_some_and(|s| s.force_path_style);
+        let region = signing
+            .and_then(|s| s.signing_region.as_deref())
+            .unwrap_or(&s3.region);
+        let endpoint = match &redirect.public_endpoint {
+            Some(public) => public.trim_end_matches('/').to_string(),
+            None => presign::endpoint_url(&s3.bucket, region, s3.endpoint.as_deref(), path_style),
+        };
+        let location = presign::presigned_url(&PresignRequest {
+            method: ctx.method(),
+            endpoint: &endpoint,
+            path_style,
+            bucket: &s3.bucket,
+            key: ctx.s3_key(),
+            region,
+            access_key: &access_key,
+            secret_key: &secret_key,
+            session_token: session_token.as_deref(),
+            expires_secs: redirect.expires_secs,
+            now: chrono::Utc::now(),
+        });
+
+        self.metrics
+            .increment_presigned_redirect(&bucket_config.name, "redirected");
+        bucket_log!(ctx, DEBUG,
+            request_id = %ctx.request_id(),
+            bucket = %bucket_config.name,
+            object_key = %ctx.s3_key(),
+            replica = replica_name.unwrap_or_default(),
+            expires_secs = redirect.expires_secs,
+            "Redirecting to presigned S3 URL"
+        );
+
+        let mut header = ResponseHeader::build(302, None)?;
+        header.insert_header("Location", location)?;
+        // The URL carries a signature: never let a shared cache keep it
+        header.insert_header("Cache-Control", "no-store")?;
+        header.insert_header("Content-Length", "0")?;
+        cors::apply(
+            &mut header,
+            cors::response_headers(bucket_config, request_origin.as_deref()),
+        )?;
+        session
+            .write_response_header(Box::new(header), true)
+            .await?;
+        self.metrics.increment_status_count(302);
+        Ok(true)
+    }
+
+    /// Answer a single-range GET from cached range chunks
+    ///
+    /// Returns false (and counts a miss) when any chunk the range touches is
+    /// not cached; the request then goes to S3 and fills the chunks.
+    async fn serve_cached_range(
+        &self,
+        session: &mut Session,
+        ctx: &mut RequestContext,
+        cache: &TieredCache,
+        bucket_config: &crate::config::BucketConfig,
+        range: &crate::s3::ByteRange,
+    ) -> Result<bool> {
+        let cache_lookup_started = Instant::now();
+        let key = CacheKey {
+            bucket: bucket_config.name.clone(),
+            object_key: ctx.s3_key().to_string(),
+            etag: None,
+            variant: None,
+        };
+        let cached = match cache.get_range(&key, range).await {
+            Ok(cached) => cached,
+            Err(e) => {
+                bucket_log!(ctx, DEBUG,
+                    request_id = %ctx.request_id(),
+                    error = %e,
+                    "Range cache lookup failed"
+                );
+                None
+            }
+        };
+        ctx.record_phase(Phase::Cache, cache_lookup_started.elapsed());
+
+        let Some(cached) = cached else {
+            self.metrics.increment_cache_range("miss");
+            if self.audit_writer.is_some() {
+                ctx.audit()
+                    .set_cache_status(crate::audit::CacheStatus::Miss);
+            }
+            return Ok(false);
+        };
+
+        bucket_log!(ctx, DEBUG,
+            request_id = %ctx.request_id(),
+            bucket = %bucket_config.name,
+            start = cached.start,
+            end = cached.end,
+            total_size = cached.total_size,
+            "Cache hit - serving range from cached chunks"
+        );
+        if self.audit_writer.is_some() {
+            ctx.audit().set_cache_status(crate::audit::CacheStatus::Hit);
+        }
+
+        let request_origin = ctx.headers().get("origin").cloned();
+        let mut header = ResponseHeader::build(206, None)?;
+        header.insert_header("Content-Type", cached.content_type.as_str())?;
+        header.insert_header(
+            "Content-Range",
+            format!(
+                "bytes {}-{}/{}",
+                cached.start, cached.end, cached.total_size
+            ),
+        )?;
+        header.insert_header("Content-Length", cached.data.len().to_string())?;
+        header.insert_header("Accept-Ranges", "bytes")?;
+        header.insert_header("ETag", cached.etag.as_str())?;
+        if let Some(ref last_modified) = cached.last_modified {
+            header.insert_header("Last-Modified", last_modified.as_str())?;
+        }
+        preserved_headers::apply(&mut header, &cached.headers)?;
+        header.insert_header("X-Cache", "HIT")?;
+        server_timing::apply(&mut header, bucket_config, ctx)?;
+        deprecation::apply(&mut header, bucket_config)?;
+        cors::apply(
+            &mut header,
+            cors::response_headers(bucket_config, request_origin.as_deref()),
+        )?;
+        session
+            .write_response_header(Box::new(header), false)
+            .await?;
+        session.write_response_body(Some(cached.data), true).await?;
+
+        self.metrics.increment_cache_range("hit");
+        self.metrics.increment_status_count(206);
+        self.metrics.increment_cache_hit();
+        Ok(true)
+    }
+
+    /// Handle `POST /batch/get`: fetch the requested paths concurrently and
+    /// return one NDJSON line per path.
+    async fn handle_batch_get(
+        &self,
+        session: &mut Session,
+        ctx: &mut RequestContext,
+        config: &Config,
+        router: &Router,
+        headers: &HashMap<String, String>,
+        query_params: &HashMap<String, String>,
+    ) -> Result<bool> {
+        use futures::stream::{self, StreamExt};
+
+        // Read the JSON body, bounded to keep a single batch cheap to parse
+        let mut body = Vec::new();
+        let mut body_error = None;
+        loop {
+            match session.read_request_body().await {
+                Ok(Some(chunk)) => {
+                    if body.len() + chunk.len() > batch::MAX_BATCH_REQUEST_BODY_BYTES {
+                        body_error = Some((413, "Request body too large".to_string()));
+                        break;
+                    }
+                    body.extend_from_slice(&chunk);
+                }
+                Ok(None) => break,
+                Err(e) => {
+                    body_error = Some((400, format!("Failed to read request body: {}", e)));
+                    break;
+                }
+            }
+        }
+
+        let parsed = match body_error {
+            Some(err) => Err(err),
+            None => batch::BatchGetRequest::parse(&body, config.batch.max_keys)
+                .map_err(|message| (400, message)),
+        };
+
+        let client_ip = self.get_client_ip(session);
+        // Rate limits key on the direct connection IP, as for a single GET
+        let direct_ip = session
+            .client_addr()
+            .and_then(|addr| addr.as_inet().map(|inet| inet.ip()));
+        let req = session.req_header();
+        let host = req
+            .headers
+            .get("host")
+            .and_then(|v| v.to_str().ok())
+            .or_else(|| req.uri.host())
+            .map(str::to_string);
+        let (status, content_type, response_body) = match parsed {
+            Ok(request) => {
+                let results: Vec<batch::BatchItemResult> = stream::iter(request.paths.iter())
+                    .map(|path| {
+                        self.fetch_batch_item(
+                            path,
+                            config,
+                            router,
+                            host.as_deref(),
+                            headers,
+                            query_params,
+                            &client_ip,
+                            direct_ip,
+                        )
+                    })
+                    .buffered(config.batch.max_concurrency)
+                    .collect()
+                    .await;
+
+                bucket_log!(ctx, INFO,
+                    request_id = %ctx.request_id(),
+                    items = results.len(),
+                    found = results.iter().filter(|r| r.status == 200).count(),
+                    "Batch GET completed"
+                );
+                (200, "application/x-ndjson", batch::to_ndjson(&results))
+            }
+            Err((status, message)) => {
+                bucket_log!(ctx, WARN,
+                    request_id = %ctx.request_id(),
+                    error = %message,
+                    "Rejected batch GET request"
+                );
+                let error_body = serde_json::json!({
+                    "error": if status == 413 { "Payload Too Large" } else { "Bad Request" },
+                    "code": ErrorCode::for_status(status).as_str(),
+                    "message": message,
+                    "status": status
+                })
+                .to_string();
+                (status, "application/json", error_body)
+            }
+        };
+
+        let mut header = ResponseHeader::build(status, None)?;
+        if status >= 400 {
+            header.insert_header(ERROR_CODE_HEADER, ErrorCode::for_status(status).as_str())?;
+        }
+        header.insert_header("Content-Type", content_type)?;
+        header.insert_header("Content-Length", response_body.len().to_string())?;
+        session
+            .write_response_header(Box::new(header), false)
+            .await?;
+        session
+            .write_response_body(Some(response_body.into()), true)
+            .await?;
+
+        self.metrics.increment_status_count(status);
+        Ok(true)
+    }
+
+    /// Resolve one batch path: route, authenticate, then serve from cache or
+    /// the bucket's backend (S3 or its origin).
+    ///
+    /// Each item is checked against the bucket's allowed methods, hotlink
+    /// rules, rate limits and bulkhead like a single GET.
+    #[allow(clippy::too_many_arguments)]
+    async fn fetch_batch_item(
+        &self,
+        path: &str,
+        config: &Config,
+        router: &Router,
+        host: Option<&str>,
+        headers: &HashMap<String, String>,
+        query_params: &HashMap<String, String>,
+        client_ip: &str,
+        direct_ip: Option<std::net::IpAddr>,
+    ) -> batch::BatchItemResult {
+        use crate::cache::CacheEntry;
+        use crate::s3::{ObjectFetch, S3Client};
+        use batch::BatchItemResult;
+
+        // Items route like single requests on the batch request's host
+        let bucket_path = router.bucket_path(host, path);
+        let bucket_config = match router.route(&bucket_path) {
+            Some(bucket_config) => bucket_config,
+            None => return BatchItemResult::error(path, 404, "No bucket matches path"),
+        };
+        let object_key = router.extract_s3_key(&bucket_path).unwrap_or_default();
+        if object_key.is_empty() {
+            return BatchItemResult::error(path, 400, "Path does not name an object");
+        }
+
+        // Each item is a GET on its bucket
+        if let request_filter::MethodCheckResult::NotAllowed { .. } =
+            request_filter::check_method("GET", &bucket_config.allowed_methods())
+        {
+            return BatchItemResult::error(path, 405, "Method Not Allowed");
+        }
+
+        // Same JWT rules as a single GET
+        let mut claims = None;
+        if let (Some(auth_config), Some(jwt_config)) = (&bucket_config.auth, &config.jwt) {
+            if auth_config.enabled {
+                let jwt_config = auth_config.jwt_config(jwt_config);
+                match authenticate(headers, query_params, &jwt_config).await {
+                    Ok(verified) => {
+                        self.metrics.increment_auth_success();
+                        claims = Some(verified);
+                    }
+                    Err(e) => {
+                        self.metrics.increment_auth_failure();
+                        self.record_auth_failure_reason(
+                            &e,
+                            &bucket_config.name,
+                            client_ip,
+                            headers,
+                            query_params,
+                            &jwt_config,
+                        );
+                        if matches!(e, AuthError::MissingToken) {
+                            self.metrics.increment_auth_error("missing");
+                            return BatchItemResult::error(path, 401, "Unauthorized");
+                        }
+                        self.metrics.increment_auth_error("invalid");
+                        return BatchItemResult::error(path, 403, "Forbidden");
+                    }
+                }
+            }
+        }
+
+        // A signed URL covers one path, so it cannot sign a batch of them
+        if bucket_config.signed_url.as_ref().is_some_and(|s| s.enabled) {
+            return BatchItemResult::error(
+                path,
+                403,
+                "Bucket requires signed URLs and cannot be batched",
+            );
+        }
+
+        // Likewise a SigV4 signature covers the batch request, not each item
+        if bucket_config.sigv4_auth.as_ref().is_some_and(|s| s.enabled) {
+            return BatchItemResult::error(
+                path,
+                403,
+                "Bucket requires SigV4 signing and cannot be batched",
+            );
+        }
+
+        // Policy-based authorization is evaluated per request, not per batch item
+        if self.opa_clients.contains_key(&bucket_config.name)
+            || self.openfga_clients.contains_key(&bucket_config.name)
+        {
+            return BatchItemResult::error(
+                path,
+                403,
+                "Bucket requires policy authorization and cannot be batched",
+            );
+        }
+
+        // Claim-to-path rules are local, so each item is checked like a single GET
+        if let Some(authz) = bucket_config
+            .authorization
+            .as_ref()
+            .filter(|a| a.auth_type == "claims")
+        {
+            let jwt_claims = claims
+                .as_ref()
+                .map(|c| serde_json::to_value(c).unwrap_or_default())
+                .unwrap_or(serde_json::json!({}));
+            if !path_rules::is_allowed(&authz.rules, &jwt_claims, "GET", &object_key) {
+                return BatchItemResult::error(path, 403, "Forbidden");
+            }
+        }
+
+        self.metrics.increment_bucket_count(&bucket_config.name);
+
+        // Hotlinked items cannot be watermarked inline, so any action denies
+        if let Some(hotlink) = bucket_config.hotlink.as_ref().filter(|h| h.enabled) {
+            let referer = headers.get("referer").map(String::as_str);
+            let origin = headers.get("origin").map(String::as_str);
+            if !hotlink.is_allowed(referer, origin) {
+                self.metrics
+                    .increment_hotlink_request(&bucket_config.name, HotlinkAction::Deny.as_str());
+                return BatchItemResult::error(path, 403, "Hotlinking is not allowed");
+            }
+        }
+
+        // Every item spends a token, so a batch costs as much as its GETs
+        if let Some(rate_limit_manager) = &self.rate_limit_manager {
+            if let Err(e) = rate_limit_manager.check_all(&bucket_config.name, direct_ip) {
+                self.metrics
+                    .increment_rate_limit_exceeded(&bucket_config.name);
+                return BatchItemResult::error(path, 429, e.to_string());
+            }
+        }
+
+        let cache_key = CacheKey {
+            bucket: bucket_config.name.clone(),
+            object_key: object_key.clone(),
+            etag: None,
+            variant: None,
+        };
+        if let Some(cache) = &self.cache {
+            if let Ok(Some(entry)) = cache.get(&cache_key).await {
+                if entry.data.len() as u64 > config.batch.max_inline_bytes {
+                    return BatchItemResult::too_large(path, entry.data.len() as u64);
+                }
+                let content_type = content_type::for_bucket(
+                    bucket_config,
+                    &object_key,
+                    Some(&entry.content_type),
+                    Some(&entry.data),
+                )
+                .unwrap_or_else(|| entry.content_type.clone());
+                return BatchItemResult::found(
+                    path,
+                    &entry.data,
+                    content_type,
+                    Some(entry.etag.clone()),
+                );
+            }
+        }
+
+        // Held until the fetch completes, like a single GET's bulkhead slot
+        let _bulkhead_permit = match self.bulkheads.get(&bucket_config.name) {
+            Some(bulkhead) => match bulkhead.try_acquire() {
+                Some(permit) => Some(permit),
+                None => {
+                    self.metrics
+                        .increment_bulkhead_rejection(&bucket_config.name);
+                    return BatchItemResult::error(path, 503, "Service Unavailable");
+                }
+            },
+            None => None,
+        };
+
+        let fetched = match self.origins.get(&bucket_config.name) {
+            Some(origin) => {
+                batch::fetch_from_origin(
+                    origin.as_ref(),
+                    &object_key,
+                    config.batch.max_inline_bytes,
+                )
+                .await
+            }
+            None => {
+                // Prefer the first replica whose circuit breaker allows traffic
+                let s3_client = self
+                    .replica_sets
+                    .get(&bucket_config.name)
+                    .and_then(|set| {
+                        set.replicas
+                            .iter()
+                            .find(|r| r.circuit_breaker.should_allow_request())
+                    })
+                    .map(|replica| replica.client.clone())
+                    .unwrap_or_else(|| S3Client {
+                        config: bucket_config.s3.clone(),
+                    });
+                s3_client
+                    .get_object(&object_key, config.batch.max_inline_bytes)
+                    .await
+            }
+        };
+
+        match fetched {
+            Ok(ObjectFetch::Found {
+                body,
+                content_type,
+                etag,
+                last_modified,
+                cache_control,
+                expires,
+            }) => {
+                let content_type = content_type::for_bucket(
+                    bucket_config,
+                    &object_key,
+                    content_type.as_deref(),
+                    Some(&body),
+                )
+                .or(content_type)
+                .or_else(|| content_type::builtin(&object_key).map(str::to_string))
+                .unwrap_or_else(|| "application/octet-stream".to_string());
+                let result =
+                    BatchItemResult::found(path, &body, content_type.clone(), etag.clone());
+
+                if let Some(cache) = &self.cache {
+                    // Same admission, size class and TTL rules as a single GET
+                    let cache_control = cache_control
+                        .as_deref()
+                        .map(CacheControl::parse)
+                        .unwrap_or_default();
+                    let size = body.len() as u64;
+                    let size_class_cache =
+                        crate::config::size_class::classify(&bucket_config.size_classes, size)
+                            .map_or(SizeClassCache::Default, |class| class.cache);
+                    let ttl = cache_ttl::entry_ttl(
+                        bucket_config,
+                        &cache_control,
+                        expires.as_deref(),
+                        None,
+                    );
+                    let admitted = if size_class_cache == SizeClassCache::None {
+                        Err(CacheSkipReason::SizeClass)
+                    } else {
+                        cache_policy::admit(
+                            bucket_config,
+                            &object_key,
+                            Some(&content_type),
+                            Some(size),
+                            config.cache.as_ref(),
+                        )
+                    }
+                    .and_then(|()| {
+                        admission::admit(
+                            200,
+                            &cache_control,
+                            etag.as_deref(),
+                            last_modified.as_deref(),
+                        )
+                    })
+                    .and_then(|()| {
+                        if ttl.is_zero() {
+                            Err(CacheSkipReason::Expired)
+                        } else {
+                            Ok(())
+                        }
+                    });
+
+                    match admitted {
+                        Ok(()) => {
+                            let entry = CacheEntry::new(
+                                body,
+                                content_type,
+                                etag.unwrap_or_default(),
+                                last_modified,
+                                Some(ttl),
+                            );
+                            let stored = if size_class_cache == SizeClassCache::Memory {
+                                cache.set_memory_only(cache_key, entry).await
+                            } else {
+                                cache.set(cache_key, entry).await
+                            };
+                            if let Err(e) = stored {
+                                tracing::debug!(error = %e, path = %path, "Failed to cache batch item");
+                            }
+                        }
+                        Err(reason) => {
+                            self.metrics
+                                .increment_cache_population_skip(reason.as_str());
+                        }
+                    }
+                }
+                result
+            }
+            Ok(ObjectFetch::NotFound) => BatchItemResult::error(path, 404, "Not Found"),
+            Ok(ObjectFetch::TooLarge { size }) => BatchItemResult::too_large(path, size),
+            Err(e) => {
+                tracing::warn!(
+                    bucket = %bucket_config.name,
+                    path = %path,
+                    error = %e,
+                    "Batch GET upstream fetch failed"
+                );
+                BatchItemResult::error(path, 502, "Bad Gateway")
+            }
+        }
+    }
+
+    /// Serve an object from an in-process origin (filesystem) without an upstream
+    ///
+    /// Small files are also written to the cache, so later hits never touch
+    /// the mount.
+    async fn serve_local_object(
+        &self,
+        session: &mut Session,
+        ctx: &mut RequestContext,
+        origin: &SharedOrigin,
+    ) -> Result<bool> {
+        use crate::cache::CacheEntry;
+
+        let bucket_config = ctx.bucket_config().cloned().ok_or_else(|| {
+            pingora_core::Error::explain(
+                pingora_core::ErrorType::InternalError,
+                "Missing bucket config in context",
+            )
+        })?;
+        let object_key = ctx.s3_key().to_string();
+        let is_head = ctx.method() == "HEAD";
+
+        let located = match origin.local_path(&object_key) {
+            Ok(Some(path)) => filesystem::stat(&path).await.map(|object| (path, object)),
+            Ok(None) => None,
+            Err(e) => {
+                bucket_log!(ctx, WARN,
+                    request_id = %ctx.request_id(),
+                    bucket = %bucket_config.name,
+                    error = %e,
+                    "Rejecting object key for filesystem origin"
+                );
+                None
+            }
+        };
+        let Some((path, object)) = located else {
+            let error_body = serde_json::json!({
+                "error": "Not Found",
+                "code": ErrorCode::NotFound.as_str(),
+                "message": "Object not found",
+                "status": 404
+            })
+            .to_string();
+            let mut header = ResponseHeader::build(404, None)?;
+            header.insert_header(ERROR_CODE_HEADER, ErrorCode::NotFound.as_str())?;
+            header.insert_header("Content-Type", "application/json")?;
+            header.insert_header("Content-Length", error_body.len().to_string())?;
+            session
+                .write_response_header(Box::new(header), is_head)
+                .await?;
+            if !is_head {
+                session
+                    .write_response_body(Some(error_body.into()), true)
+                    .await?;
+            }
+            self.metrics.increment_status_count(404);
+            return Ok(true);
+        };
+
+        let request_origin = ctx.headers().get("origin").cloned();
+        let content_type = content_type::for_bucket(&bucket_config, &object_key, None, None)
+            .or_else(|| content_type::builtin(&object_key).map(str::to_string))
+            .unwrap_or_else(|| "application/octet-stream".to_string());
+
+        let not_modified = ctx
+            .headers()
+            .get("if-none-match")
+            .is_some_and(|client_etag| {
+                crate::cache::etag::if_none_match_matches(client_etag, &object.etag)
+            });
+        let status = if not_modified { 304 } else { 200 };
+        let mut header = ResponseHeader::build(status, None)?;
+        header.insert_header("ETag", object.etag.as_str())?;
+        if let Some(ref last_modified) = object.last_modified {
+            header.insert_header("Last-Modified", last_modified.as_str())?;
+        }
+        if !not_modified {
+            header.insert_header("Content-Type", content_type.as_str())?;
+            header.insert_header("Content-Length", object.size.to_string())?;
+        }
+        header.insert_header("X-Cache", "MISS")?;
+        server_timing::apply(&mut header, &bucket_config, ctx)?;
+        deprecation::apply(&mut header, &bucket_config)?;
+        cors::apply(
+            &mut header,
+            cors::response_headers(&bucket_config, request_origin.as_deref()),
+        )?;
+
+        let empty = not_modified || is_head || object.size == 0;
+        session
+            .write_response_header(Box::new(header), empty)
+            .await?;
+        self.metrics.increment_status_count(status);
+        if empty {
+            return Ok(true);
+        }
+
+        let cacheable = cache_policy::admit(
+            &bucket_config,
+            &object_key,
+            Some(&content_type),
+            Some(object.size),
+            ctx.config().and_then(|config| config.cache.as_ref()),
+        )
+        .is_ok();
+        match self.cache {
+            Some(ref cache) if cacheable => {
+                let data = match tokio::fs::read(&path).await {
+                    Ok(data) => bytes::Bytes::from(data),
                     Err(e) => {
-                        tracing::error!(
+                        bucket_log!(ctx, ERROR,
+                            request_id = %ctx.request_id(),
+                            path = %path.display(),
                             error = %e,
-                            "Failed to initialize cache, continuing without cache"
+                            "Failed to read file from filesystem origin"
                         );
-                        // Continue without cache - graceful degradation
+                        return Err(pingora_core::Error::explain(
+                            pingora_core::ErrorType::ReadError,
+                            "Failed to read file from filesystem origin",
+                        ));
                     }
+                };
+                session
+                    .write_response_body(Some(data.clone()), true)
+                    .await?;
+
+                let cache_key = CacheKey {
+                    bucket: bucket_config.name.clone(),
+                    object_key,
+                    etag: None,
+                    variant: None,
+                };
+                let entry = CacheEntry::new(
+                    data,
+                    content_type,
+                    object.etag,
+                    object.last_modified,
+                    Some(cache_ttl::entry_ttl(
+                        &bucket_config,
+                        &CacheControl::default(),
+                        None,
+                        None,
+                    )),
+                );
+                if let Err(e) = cache.set(cache_key, entry).await {
+                    bucket_log!(ctx, WARN,
+                        request_id = %ctx.request_id(),
+                        error = %e,
+                        "Failed to populate cache from filesystem origin"
+                    );
+                }
+            }
+            _ => {
+                let read_error = |e: std::io::Error| {
+                    tracing::error!(
+                        path = %path.display(),
+                        error = %e,
+                        "Failed to read file from filesystem origin"
+                    );
+                    pingora_core::Error::explain(
+                        pingora_core::ErrorType::ReadError,
+                        "Failed to read file from filesystem origin",
+                    )
+                };
+                let mut file = filesystem::open(&path).await.map_err(read_error)?;
+                let mut next = filesystem::read_chunk(&mut file)
+                    .await
+                    .map_err(read_error)?;
+                while let Some(chunk) = next {
+                    next = filesystem::read_chunk(&mut file)
+                        .await
+                        .map_err(read_error)?;
+                    session
+                        .write_response_body(Some(chunk), next.is_none())
+                        .await?;
                 }
-            } else {
-                tracing::info!("Cache is disabled or no cache layers configured");
             }
-        } else {
-            tracing::info!("No cache configuration found");
-        }
-
-        // Start rate limiter cleanup task now that we have a Tokio runtime (Phase 36)
-        // This prevents unbounded memory growth from per-IP/per-user tracking
-        if let Some(ref rate_limit_manager) = self.rate_limit_manager {
-            rate_limit_manager.start_cleanup_task(None); // Uses default interval (60s)
         }
 
-        self
-    }
-
-    /// Get a reference to the metrics instance
-    pub fn metrics(&self) -> Arc<Metrics> {
-        Arc::clone(&self.metrics)
-    }
-
-    /// Extract headers from Pingora RequestHeader into HashMap.
-    fn extract_headers(req: &RequestHeader) -> HashMap<String, String> {
-        helpers::extract_headers(req)
+        Ok(true)
     }
 
-    /// Extract query parameters from URI.
-    fn extract_query_params(req: &RequestHeader) -> HashMap<String, String> {
-        helpers::extract_query_params(req)
-    }
+    /// Reject a request because the bucket's origins are down (open circuit breakers)
+    async fn respond_origin_unavailable(
+        &self,
+        session: &mut Session,
+        bucket: &str,
+    ) -> Result<bool> {
+        // Time until the bucket breaker (or, without one, the first replica
+        // breaker) lets a test request through
+        let cooldown = match self.circuit_breakers.get(bucket) {
+            Some(breaker) => breaker.cooldown_remaining(),
+            None => self.replica_sets.get(bucket).and_then(|set| {
+                set.replicas
+                    .iter()
+                    .filter_map(|replica| replica.circuit_breaker.cooldown_remaining())
+                    .min()
+            }),
+        };
+        self.respond_unavailable(
+            session,
+            UnavailableReason::OriginUnavailable,
+            Some(bucket),
+            cooldown,
+        )
+        .await?;
 
-    /// Extract client IP address from session (X-Forwarded-For aware).
-    fn get_client_ip(&self, session: &Session) -> String {
-        helpers::get_client_ip(session)
-    }
+        self.metrics.increment_status_count(503);
 
-    /// Export circuit breaker metrics for Prometheus.
-    fn export_circuit_breaker_metrics(&self) -> String {
-        helpers::export_circuit_breaker_metrics(&self.circuit_breakers)
+        Ok(true)
     }
 
-    /// Build WatermarkContext from request context for watermark template resolution.
-    /// Phase 50: Watermark integration
-    fn build_watermark_context(
-        ctx: &RequestContext,
-        bucket_name: &str,
-        client_ip: Option<&str>,
-    ) -> WatermarkContext {
-        let mut wm_ctx = WatermarkContext::default();
-
-        // Set client IP
-        if let Some(ip) = client_ip {
-            wm_ctx.client_ip = Some(ip.to_string());
-        }
-
-        // Set JWT claims if authenticated
-        if let Some(claims) = ctx.claims() {
-            // Add standard claims
-            if let Some(ref sub) = claims.sub {
-                wm_ctx.jwt_claims.insert("sub".to_string(), sub.clone());
-            }
-            if let Some(ref iss) = claims.iss {
-                wm_ctx.jwt_claims.insert("iss".to_string(), iss.clone());
-            }
-            // Add custom claims
-            for (key, value) in &claims.custom {
-                // Convert Value to String
-                if let Some(s) = value.as_str() {
-                    wm_ctx.jwt_claims.insert(key.clone(), s.to_string());
-                } else {
-                    wm_ctx.jwt_claims.insert(key.clone(), value.to_string());
-                }
-            }
-        }
-
-        // Set headers
-        wm_ctx.headers = ctx.headers().clone();
-
-        // Set path and bucket
-        wm_ctx.path = ctx.path().to_string();
-        wm_ctx.bucket = bucket_name.to_string();
+    /// Write a 503 using the configured body and Retry-After for `reason`
+    async fn respond_unavailable(
+        &self,
+        session: &mut Session,
+        reason: UnavailableReason,
+        bucket: Option<&str>,
+        cooldown: Option<Duration>,
+    ) -> Result<()> {
+        let response = self
+            .config
+            .load()
+            .server
+            .unavailable_responses
+            .render(reason, bucket, cooldown);
+
+        let mut header = ResponseHeader::build(503, None)?;
+        header.insert_header(ERROR_CODE_HEADER, reason.code().as_str())?;
+        header.insert_header("Content-Type", response.content_type)?;
+        header.insert_header("Retry-After", response.retry_after_secs.to_string())?;
+        header.insert_header("Content-Length", response.body.len().to_string())?;
+
+        session
+            .write_response_header(Box::new(header), false)
+            .await?;
+        session
+            .write_response_body(Some(response.body.into()), true)
+            .await?;
 
-        wm_ctx
+        Ok(())
     }
 
     /// Handle a streaming coalescer follower response.
@@ -382,9 +1971,11 @@ impl YatagarasuProxy {
                     // Leader encountered an error - return 502
                     tracing::warn!(error = %e, "Streaming follower: leader reported error");
                     let mut header = ResponseHeader::build(502, None)?;
+                    header.insert_header(ERROR_CODE_HEADER, ErrorCode::UpstreamError.as_str())?;
                     header.insert_header("Content-Type", "application/json")?;
                     let error_body = serde_json::json!({
                         "error": "Bad Gateway",
+                        "code": ErrorCode::UpstreamError.as_str(),
                         "message": format!("Upstream error: {}", e),
                         "status": 502
                     })
@@ -406,10 +1997,12 @@ impl YatagarasuProxy {
                         "Streaming follower: lagged behind, rejecting with 503"
                     );
                     let mut header = ResponseHeader::build(503, None)?;
+                    header.insert_header(ERROR_CODE_HEADER, ErrorCode::StreamLagged.as_str())?;
                     header.insert_header("Content-Type", "application/json")?;
                     header.insert_header("Retry-After", "1")?;
                     let error_body = serde_json::json!({
                         "error": "Service Unavailable",
+                        "code": ErrorCode::StreamLagged.as_str(),
                         "message": "Request processing lagged behind. Please retry.",
                         "status": 503
                     })
@@ -428,9 +2021,11 @@ impl YatagarasuProxy {
                     // Leader died unexpectedly
                     tracing::error!("Streaming follower: leader channel closed unexpectedly");
                     let mut header = ResponseHeader::build(502, None)?;
+                    header.insert_header(ERROR_CODE_HEADER, ErrorCode::UpstreamError.as_str())?;
                     header.insert_header("Content-Type", "application/json")?;
                     let error_body = serde_json::json!({
                         "error": "Bad Gateway",
+                        "code": ErrorCode::UpstreamError.as_str(),
                         "message": "Stream leader closed unexpectedly",
                         "status": 502
                     })
@@ -448,6 +2043,44 @@ impl YatagarasuProxy {
             }
         }
     }
+
+    /// Drop every cached entry of the object a successful write replaced:
+    /// the base entry as well as its image variants and range chunks, which
+    /// would otherwise keep serving the old body
+    async fn invalidate_replaced_object(
+        &self,
+        ctx: &RequestContext,
+        query: Option<&str>,
+        success: bool,
+    ) {
+        if !success || !multipart::write_replaces_object(ctx.method(), query) {
+            return;
+        }
+        let (Some(cache), Some(bucket_config)) = (&self.cache, ctx.bucket_config()) else {
+            return;
+        };
+        match cache.delete_object(&bucket_config.name, ctx.s3_key()).await {
+            Ok(deleted) => {
+                bucket_log!(ctx, DEBUG,
+                    request_id = %ctx.request_id(),
+                    bucket = %bucket_config.name,
+                    object_key = %ctx.s3_key(),
+                    deleted = deleted,
+                    "Invalidated cache entries after write"
+                );
+                if deleted > 0 {
+                    self.metrics.increment_cache_purge();
+                }
+            }
+            Err(e) => {
+                bucket_log!(ctx, WARN,
+                    request_id = %ctx.request_id(),
+                    error = %e,
+                    "Failed to invalidate cache entries after write"
+                );
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -466,6 +2099,8 @@ impl ProxyHttp for YatagarasuProxy {
         _session: &mut Session,
         ctx: &mut Self::CTX,
     ) -> Result<Box<HttpPeer>> {
+        ctx.mark_upstream_start();
+
         // Get bucket config from context (set in request_filter)
         let bucket_config = ctx.bucket_config().ok_or_else(|| {
             pingora_core::Error::explain(
@@ -474,12 +2109,80 @@ impl ProxyHttp for YatagarasuProxy {
             )
         })?;
 
+        // Size class from the object's last known size: timeout and preferred replica
+        let object_class = (!bucket_config.size_classes.is_empty())
+            .then(|| self.object_sizes.get(&bucket_config.name, ctx.s3_key()))
+            .flatten()
+            .and_then(|size| crate::config::size_class::classify(&bucket_config.size_classes, size))
+            .cloned();
+
+        // Non-S3 origins name their own peer
+        if let Some(origin) = self.origins.get(&bucket_config.name) {
+            let target = origin.upstream().ok_or_else(|| {
+                pingora_core::Error::explain(
+                    pingora_core::ErrorType::InternalError,
+                    "Origin is not a network origin",
+                )
+            })?;
+            let mut peer = Box::new(HttpPeer::new(
+                (target.host.clone(), target.port),
+                target.tls,
+                target.host.clone(),
+            ));
+            peer.options.connection_timeout = Some(target.timeout);
+            peer.options.read_timeout = Some(target.timeout);
+            peer.options.write_timeout = Some(target.timeout);
+            size_class::apply_timeout(&mut peer, object_class.as_ref());
+            bucket_log!(ctx, DEBUG,
+                bucket = %bucket_config.name,
+                origin = origin.kind(),
+                host = %target.host,
+                "Selected origin for request"
+            );
+            return Ok(peer);
+        }
+
         // Phase 23: Check if ReplicaSet exists for this bucket
         let bucket_name = bucket_config.name.clone(); // Clone for logging to avoid borrow issues
+
+        // Read before the loop: selecting a replica borrows ctx mutably
+        let force_path_style = bucket_config
+            .s3
+            .signing
+            .as_ref()
+            .is_some_and(|s| s.force_path_style);
         if let Some(replica_set) = self.replica_sets.get(&bucket_name) {
-            // Select first healthy replica (circuit breaker not open)
-            for replica in &replica_set.replicas {
+            // Select first healthy replica (circuit breaker not open), trying
+            // the size class's replica ahead of the priority order
+            let preferred = object_class.as_ref().and_then(|c| c.replica.as_deref());
+            let replicas = replica_set
+                .replicas
+                .iter()
+                .filter(|r| Some(r.name.as_str()) == preferred)
+                .chain(
+                    replica_set
+                        .replicas
+                        .iter()
+                        .filter(|r| Some(r.name.as_str()) != preferred),
+                );
+            for replica in replicas {
                 if replica.circuit_breaker.should_allow_request() {
+                    // Static endpoint pools connect to a healthy ip:port in rotation
+                    let static_addr = match &replica.endpoint_pool {
+                        Some(pool) => match pool.select() {
+                            Some(addr) => Some(addr),
+                            None => {
+                                bucket_log!(ctx, WARN,
+                                    bucket = %bucket_name,
+                                    replica = %replica.name,
+                                    "All static endpoints cooling down, skipping replica"
+                                );
+                                continue;
+                            }
+                        },
+                        None => None,
+                    };
+
                     // Store selected replica name in context for logging
                     ctx.set_replica_name(replica.name.clone());
 
@@ -503,41 +2206,62 @@ impl ProxyHttp for YatagarasuProxy {
                             (host, port, use_tls)
                         } else {
                             // AWS S3 endpoint
-                            let endpoint = format!(
-                                "{}.s3.{}.amazonaws.com",
-                                replica.client.config.bucket, replica.client.config.region
+                            let endpoint = crate::s3::aws_host(
+                                &replica.client.config.bucket,
+                                &replica.client.config.region,
+                                force_path_style,
                             );
                             (endpoint, 443, true)
                         };
 
-                    let mut peer = Box::new(HttpPeer::new(
-                        (endpoint.clone(), port),
-                        use_tls,
-                        endpoint.clone(),
-                    ));
+                    let mut peer = Box::new(match static_addr {
+                        Some(addr) => HttpPeer::new(addr, use_tls, endpoint.clone()),
+                        None => HttpPeer::new((endpoint.clone(), port), use_tls, endpoint.clone()),
+                    });
 
                     // Configure timeouts from replica config
                     let timeout_duration = Duration::from_secs(replica.client.config.timeout);
                     peer.options.connection_timeout = Some(timeout_duration);
                     peer.options.read_timeout = Some(timeout_duration);
                     peer.options.write_timeout = Some(timeout_duration);
+                    size_class::apply_timeout(&mut peer, object_class.as_ref());
 
-                    tracing::info!(
+                    bucket_log!(ctx, INFO,
                         bucket = %bucket_name,
                         replica = %replica.name,
                         endpoint = %endpoint,
+                        static_addr = ?static_addr,
                         "Selected healthy replica for request"
                     );
 
                     // Phase 23: Track active replica in metrics
-                    self.metrics.set_active_replica(&bucket_name, &replica.name);
+                    if let Some(previous) =
+                        self.metrics.set_active_replica(&bucket_name, &replica.name)
+                    {
+                        bucket_log!(ctx, WARN,
+                            bucket = %bucket_name,
+                            from = %previous,
+                            to = %replica.name,
+                            "Replica failover"
+                        );
+                        self.metrics.increment_replica_failover(
+                            &bucket_name,
+                            &previous,
+                            &replica.name,
+                        );
+                        events::publish(EventKind::Failover {
+                            bucket: bucket_name.clone(),
+                            from: previous,
+                            to: replica.name.clone(),
+                        });
+                    }
 
                     return Ok(peer);
                 }
             }
 
             // All replicas unhealthy - return error
-            tracing::error!(
+            bucket_log!(ctx, ERROR,
                 bucket = %bucket_name,
                 "All replicas unhealthy (circuit breakers open)"
             );
@@ -569,9 +2293,10 @@ impl ProxyHttp for YatagarasuProxy {
             (host, port, use_tls)
         } else {
             // Default to AWS S3
-            let endpoint = format!(
-                "{}.s3.{}.amazonaws.com",
-                bucket_config.s3.bucket, bucket_config.s3.region
+            let endpoint = crate::s3::aws_host(
+                &bucket_config.s3.bucket,
+                &bucket_config.s3.region,
+                force_path_style,
             );
             (endpoint, 443, true)
         };
@@ -594,7 +2319,10 @@ impl ProxyHttp for YatagarasuProxy {
         // Set write timeout (how long to wait to send data to upstream)
         peer.options.write_timeout = Some(timeout_duration);
 
-        tracing::debug!(
+        // Large objects may get more time per read than the bucket default
+        size_class::apply_timeout(&mut peer, object_class.as_ref());
+
+        bucket_log!(ctx, DEBUG,
             bucket = %bucket_config.name,
             timeout_seconds = bucket_config.s3.timeout,
             endpoint = %endpoint_for_logging,
@@ -628,11 +2356,49 @@ impl ProxyHttp for YatagarasuProxy {
         }
         // -- End Audit Logging --
 
+        // Trusted clients may mark requests as high priority (reserved permits, no load shedding)
+        let high_priority = self.priority_gate.as_ref().is_some_and(|gate| {
+            let value = session
+                .req_header()
+                .headers
+                .get(gate.header())
+                .and_then(|v| v.to_str().ok());
+            let direct_ip = session
+                .client_addr()
+                .and_then(|addr| addr.as_inet().map(|inet| inet.ip()));
+            gate.is_high_priority(value, direct_ip)
+        });
+
         // Check concurrency limit FIRST - reject if at max concurrent requests
-        let _permit = match self.request_semaphore.try_acquire() {
-            Ok(permit) => permit,
-            Err(_) => {
-                tracing::warn!(
+        let mut permit = self.request_semaphore.try_acquire().ok();
+        if permit.is_none() && high_priority {
+            permit = self
+                .priority_gate
+                .as_ref()
+                .and_then(|gate| gate.try_acquire_reserved());
+            if permit.is_some() {
+                self.metrics.increment_priority_admission("reserved_permit");
+            }
+        }
+        // Optionally wait for a permit instead of rejecting right away
+        if permit.is_none() {
+            if let Some(ref request_queue) = self.request_queue {
+                match request_queue.acquire(&self.request_semaphore).await {
+                    Ok(queued) => {
+                        permit = Some(queued);
+                        self.metrics.increment_request_queue_outcome("admitted");
+                    }
+                    Err(rejection) => {
+                        self.metrics
+                            .increment_request_queue_outcome(rejection.as_str());
+                    }
+                }
+            }
+        }
+        let _permit = match permit {
+            Some(permit) => permit,
+            None => {
+                bucket_log!(ctx, WARN,
                     request_id = %ctx.request_id(),
                     "Rejecting request due to max concurrent requests reached"
                 );
@@ -640,14 +2406,67 @@ impl ProxyHttp for YatagarasuProxy {
                 // Increment metrics counter for concurrency limit rejections
                 self.metrics.increment_concurrency_limit_rejection();
 
-                let mut header = ResponseHeader::build(503, None)?;
+                self.respond_unavailable(session, UnavailableReason::ConcurrencyLimit, None, None)
+                    .await?;
+
+                return Ok(true); // Request handled
+            }
+        };
+        // Permit will be automatically released when _permit is dropped at end of function
+
+        // Track active connections (in-flight requests gauge)
+        self.metrics.increment_active_connections();
+
+        // Sample this worker's runtime in /metrics too (no-op after its first request)
+        crate::metrics::runtime::RuntimeRegistry::register_current();
+
+        // Track listener accepts vs keep-alive reuse
+        if let Some(first_request) = self.downstream_connections.is_first_request(session) {
+            self.metrics.record_downstream_request(first_request);
+        }
+
+        // Check resource exhaustion SECOND - reject requests if resources exhausted
+        // (high-priority requests are exempt from load shedding)
+        let resources_exhausted = !self.resource_monitor.should_accept_request();
+        if resources_exhausted && high_priority {
+            self.metrics.increment_priority_admission("shed_bypass");
+        }
+        if resources_exhausted && !high_priority {
+            bucket_log!(ctx, WARN,
+                request_id = %ctx.request_id(),
+                "Rejecting request due to resource exhaustion"
+            );
+
+            self.respond_unavailable(session, UnavailableReason::ResourceExhausted, None, None)
+                .await?;
+
+            return Ok(true); // Short-circuit (503 response sent)
+        }
+
+        // Automatic IP bans (direct connection IP, same as rate limiting)
+        if let Some(ref ip_ban_manager) = self.ip_ban_manager {
+            let direct_ip = session
+                .client_addr()
+                .and_then(|addr| addr.as_inet().map(|inet| inet.ip()));
+            if let Some(remaining) = direct_ip.and_then(|ip| ip_ban_manager.ban_remaining(ip)) {
+                bucket_log!(ctx, DEBUG,
+                    request_id = %ctx.request_id(),
+                    client_ip = ?direct_ip,
+                    remaining_secs = remaining.as_secs(),
+                    "Rejecting request from banned IP"
+                );
+                self.metrics.increment_ip_ban_rejection();
+
+                let mut header = ResponseHeader::build(403, None)?;
+                header.insert_header(ERROR_CODE_HEADER, ErrorCode::AuthIpBanned.as_str())?;
                 header.insert_header("Content-Type", "application/json")?;
-                header.insert_header("Retry-After", "5")?; // Suggest retry after 5 seconds
+                header.insert_header("Retry-After", remaining.as_secs().max(1).to_string())?;
 
                 let error_body = serde_json::json!({
-                    "error": "Service Temporarily Unavailable",
-                    "message": "Server has reached maximum concurrent request limit. Please retry after 5 seconds.",
-                    "status": 503
+                    "error": "Forbidden",
+                    "code": ErrorCode::AuthIpBanned.as_str(),
+                    "message": "Client IP is temporarily banned",
+                    "status": 403
                 })
                 .to_string();
 
@@ -660,48 +2479,51 @@ impl ProxyHttp for YatagarasuProxy {
                     .write_response_body(Some(error_body.into()), true)
                     .await?;
 
-                return Ok(true); // Request handled
+                return Ok(true);
             }
-        };
-        // Permit will be automatically released when _permit is dropped at end of function
-
-        // Track active connections (in-flight requests gauge)
-        self.metrics.increment_active_connections();
+        }
 
-        // Check resource exhaustion SECOND - reject requests if resources exhausted
-        if !self.resource_monitor.should_accept_request() {
-            tracing::warn!(
-                request_id = %ctx.request_id(),
-                "Rejecting request due to resource exhaustion"
-            );
+        // Maintenance mode: data-plane traffic gets a 503, allowlisted paths still work
+        if self.maintenance.is_enabled()
+            && !config
+                .server
+                .maintenance
+                .is_allowed_path(session.req_header().uri.path())
+        {
+            let maintenance_config = &config.server.maintenance;
+            self.metrics.increment_maintenance_rejection();
 
+            let (content_type, body) = maintenance_config.render();
             let mut header = ResponseHeader::build(503, None)?;
-            header.insert_header("Content-Type", "application/json")?;
-            header.insert_header("Retry-After", "10")?; // Suggest retry after 10 seconds
-
-            let error_body = serde_json::json!({
-                "error": "Service Temporarily Unavailable",
-                "message": "Server is under heavy load. Please retry after 10 seconds.",
-                "status": 503
-            })
-            .to_string();
-
-            header.insert_header("Content-Length", error_body.len().to_string())?;
+            header.insert_header(ERROR_CODE_HEADER, ErrorCode::Maintenance.as_str())?;
+            header.insert_header("Content-Type", content_type)?;
+            header.insert_header(
+                "Retry-After",
+                maintenance_config.retry_after_secs.to_string(),
+            )?;
+            header.insert_header("Cache-Control", "no-store")?;
+            header.insert_header("Content-Length", body.len().to_string())?;
 
             session
                 .write_response_header(Box::new(header), false)
                 .await?;
-            session
-                .write_response_body(Some(error_body.into()), true)
-                .await?;
+            session.write_response_body(Some(body.into()), true).await?;
 
-            return Ok(true); // Short-circuit (503 response sent)
+            return Ok(true);
         }
 
         // Extract request information
         let req = session.req_header();
         let path = req.uri.path().to_string();
         let method = req.method.to_string();
+        // Virtual hosts and pattern routes map onto a bucket's prefix;
+        // special endpoints keep the raw path
+        let host = req
+            .headers
+            .get("host")
+            .and_then(|v| v.to_str().ok())
+            .or_else(|| req.uri.host());
+        let bucket_path = router.bucket_path(host, &path);
 
         // Extract client IP for logging (X-Forwarded-For aware)
         let client_ip = self.get_client_ip(session);
@@ -709,74 +2531,132 @@ impl ProxyHttp for YatagarasuProxy {
             ctx.audit().client_ip = Some(client_ip.clone());
         }
 
+        // Identity from the mutual TLS handshake, for audit logs and OPA
+        #[cfg(feature = "tls")]
+        {
+            ctx.set_client_cert_subject(crate::tls::client_cert_subject(session));
+            ctx.audit().client_cert_subject = ctx.client_cert_subject().map(str::to_string);
+        }
+
         // SECURITY VALIDATIONS (check early before routing)
 
-        // 0. HTTP Method Validation (Read-Only Proxy - Phase 25)
-        // This proxy only supports GET and HEAD for S3 operations
-        // Special endpoints (/health, /ready, /metrics, /admin/reload, /admin/cache/*) are handled separately
+        // 0. HTTP Method Check
+        // Bucket paths accept the bucket's allowed_methods (default: GET, HEAD, OPTIONS);
+        // unrouted paths accept the default set.
+        // Special endpoints (/health, /ready, /metrics) are handled separately; known admin
+        // endpoints get a strict per-endpoint method check in the admin guard below
         if !(path.starts_with("/health")
             || path.starts_with("/ready")
             || path.starts_with("/metrics")
-            || (path == "/admin/reload" && method == "POST")
-            || (path.starts_with("/admin/cache/") && (method == "POST" || method == "GET")))
+            || crate::admin::guard::allowed_methods(&path).is_some()
+            || config.batch.is_batch_request(&path, &method))
         {
-            // Only GET, HEAD, and OPTIONS are allowed for S3 operations
-            match method.as_str() {
-                "GET" | "HEAD" | "OPTIONS" => {} // Allowed
-                _ => {
-                    tracing::warn!(
-                        request_id = %ctx.request_id(),
-                        client_ip = %client_ip,
-                        method = %method,
-                        path = %path,
-                        "Unsupported HTTP method for read-only proxy"
-                    );
+            let allowed_methods = match router.route(&bucket_path) {
+                Some(bucket) => bucket.allowed_methods(),
+                None => crate::constants::DEFAULT_BUCKET_ALLOWED_METHODS
+                    .iter()
+                    .map(|m| m.to_string())
+                    .collect(),
+            };
+            if let request_filter::MethodCheckResult::NotAllowed { allow } =
+                request_filter::check_method(&method, &allowed_methods)
+            {
+                bucket_log!(ctx, WARN,
+                    request_id = %ctx.request_id(),
+                    client_ip = %client_ip,
+                    method = %method,
+                    path = %path,
+                    allowed = %allow,
+                    "HTTP method not allowed for this path"
+                );
+
+                let mut header = ResponseHeader::build(405, None)?;
+                header.insert_header(ERROR_CODE_HEADER, ErrorCode::MethodNotAllowed.as_str())?;
+                header.insert_header("Content-Type", "application/json")?;
+                header.insert_header("Allow", allow.as_str())?;
+
+                let error_body = serde_json::json!({
+                    "error": "Method Not Allowed",
+                    "code": ErrorCode::MethodNotAllowed.as_str(),
+                    "message": format!(
+                        "Method {} is not allowed. This is a read-only S3 proxy. Allowed methods: {}",
+                        method, allow
+                    ),
+                    "status": 405
+                })
+                .to_string();
+
+                header.insert_header("Content-Length", error_body.len().to_string())?;
+                session
+                    .write_response_header(Box::new(header), false)
+                    .await?;
+                session
+                    .write_response_body(Some(error_body.into()), true)
+                    .await?;
 
-                    let mut header = ResponseHeader::build(405, None)?;
-                    header.insert_header("Content-Type", "application/json")?;
-                    header.insert_header("Allow", "GET, HEAD, OPTIONS")?;
+                self.metrics.increment_status_count(405);
+                return Ok(true); // Short-circuit
+            }
 
-                    let error_body = serde_json::json!({
-                        "error": "Method Not Allowed",
-                        "message": format!(
-                            "Method {} is not allowed. This is a read-only S3 proxy. Allowed methods: GET, HEAD, OPTIONS",
-                            method
-                        ),
-                        "status": 405
-                    })
-                    .to_string();
+            // Write methods only carry the multipart upload lifecycle, plus
+            // browser form uploads to buckets with a POST policy
+            let post_policy_upload = method == "POST"
+                && router
+                    .route(&bucket_path)
+                    .is_some_and(|bucket| bucket.post_policy.as_ref().is_some_and(|p| p.enabled));
+            if BUCKET_WRITE_METHODS.contains(&method.as_str())
+                && MultipartOperation::classify(&method, session.req_header().uri.query()).is_none()
+                && !post_policy_upload
+            {
+                let mut header = ResponseHeader::build(400, None)?;
+                header.insert_header(ERROR_CODE_HEADER, ErrorCode::BadRequest.as_str())?;
+                header.insert_header("Content-Type", "application/json")?;
 
-                    header.insert_header("Content-Length", error_body.len().to_string())?;
-                    session
-                        .write_response_header(Box::new(header), false)
-                        .await?;
-                    session
-                        .write_response_body(Some(error_body.into()), true)
-                        .await?;
+                let error_body = serde_json::json!({
+                    "error": "Bad Request",
+                    "code": ErrorCode::BadRequest.as_str(),
+                    "message": format!(
+                        "Only multipart upload requests are supported for {} \
+                         (CreateMultipartUpload, UploadPart, CompleteMultipartUpload, \
+                         AbortMultipartUpload)",
+                        method
+                    ),
+                    "status": 400
+                })
+                .to_string();
 
-                    self.metrics.increment_status_count(405);
-                    return Ok(true); // Short-circuit
-                }
+                header.insert_header("Content-Length", error_body.len().to_string())?;
+                session
+                    .write_response_header(Box::new(header), false)
+                    .await?;
+                session
+                    .write_response_body(Some(error_body.into()), true)
+                    .await?;
+
+                self.metrics.increment_status_count(400);
+                return Ok(true); // Short-circuit
             }
         }
 
         // Handle OPTIONS requests (CORS pre-flight)
-        // OPTIONS is allowed through method validation above, now handle it specially
+        // OPTIONS passed the method check above; the response reflects the
+        // bucket's allowed methods and CORS policy
         if method == "OPTIONS" {
-            tracing::debug!(
+            bucket_log!(ctx, DEBUG,
                 request_id = %ctx.request_id(),
                 path = %path,
                 "Handling OPTIONS request for CORS pre-flight"
             );
 
+            let header_str = |name: &str| req.headers.get(name).and_then(|v| v.to_str().ok());
+            let preflight = cors::preflight_headers(
+                router.route(&bucket_path),
+                header_str("origin"),
+                header_str("access-control-request-method"),
+            );
+
             let mut header = ResponseHeader::build(200, None)?;
-            header.insert_header("Allow", "GET, HEAD, OPTIONS")?;
-            header.insert_header("Access-Control-Allow-Methods", "GET, HEAD, OPTIONS")?;
-            header.insert_header(
-                "Access-Control-Allow-Headers",
-                "Authorization, Content-Type, Range",
-            )?;
-            header.insert_header("Access-Control-Max-Age", "86400")?; // 24 hours
+            cors::apply(&mut header, preflight)?;
             header.insert_header("Content-Length", "0")?;
 
             session
@@ -788,13 +2668,17 @@ impl ProxyHttp for YatagarasuProxy {
             return Ok(true); // Short-circuit
         }
 
+        // Query params extracted for image optimization are reused for the request
+        // context below instead of being parsed a second time
+        let mut extracted_query_params: Option<HashMap<String, String>> = None;
+
         // Image Optimization Check (Phase: Image Optimization)
         if config.image_optimization.enabled && (method == "GET" || method == "HEAD") {
-            let query_params = helpers::extract_query_params(req);
-            if let Some(parse_result) = ImageParams::from_query(&query_params) {
+            let query_params = extracted_query_params.insert(helpers::extract_query_params(req));
+            if let Some(parse_result) = ImageParams::from_query(query_params) {
                 match parse_result {
                     Ok(image_params) => {
-                        tracing::debug!(
+                        bucket_log!(ctx, DEBUG,
                             request_id = %ctx.request_id(),
                             params = ?image_params,
                             "Image optimization requested"
@@ -803,17 +2687,22 @@ impl ProxyHttp for YatagarasuProxy {
                     }
                     Err(image_error) => {
                         let status = image_error.to_http_status();
-                        tracing::warn!(
+                        bucket_log!(ctx, WARN,
                             request_id = %ctx.request_id(),
                             error = %image_error,
                             "Invalid image optimization parameters"
                         );
 
                         let mut header = ResponseHeader::build(status, None)?;
+                        header.insert_header(
+                            ERROR_CODE_HEADER,
+                            ErrorCode::InvalidImageParams.as_str(),
+                        )?;
                         header.insert_header("Content-Type", "application/json")?;
 
                         let error_body = serde_json::json!({
                             "error": "Bad Request",
+                            "code": ErrorCode::InvalidImageParams.as_str(),
                             "message": image_error.to_string(),
                             "status": status
                         })
@@ -857,9 +2746,20 @@ impl ProxyHttp for YatagarasuProxy {
             total_header_size,
             content_length,
             &self.security_limits,
-        ) {
+        )
+        .or_else(|| {
+            security::check_header_values(
+                ctx.request_id(),
+                &client_ip,
+                req.headers
+                    .iter()
+                    .map(|(name, value)| (name.as_str(), value.len())),
+                &self.security_limits,
+            )
+        }) {
             // Write the error response
             let mut header = ResponseHeader::build(violation.status, None)?;
+            header.insert_header(ERROR_CODE_HEADER, violation.code.as_str())?;
             header.insert_header("Content-Type", "application/json")?;
             header.insert_header("Content-Length", violation.error_body.len().to_string())?;
 
@@ -892,6 +2792,93 @@ impl ProxyHttp for YatagarasuProxy {
             }
         }
 
+        // Optional token/IP restrictions for /health, /ready and /metrics
+        if let Some((endpoint, acl)) = config.server.endpoint_access.for_path(&path) {
+            if acl.is_restricted() {
+                let direct_ip = session
+                    .client_addr()
+                    .and_then(|addr| addr.as_inet().map(|inet| inet.ip()));
+                let authorization = req
+                    .headers
+                    .get("authorization")
+                    .and_then(|v| v.to_str().ok());
+                let access = acl.check(direct_ip, authorization);
+                if access != EndpointAccess::Allowed {
+                    bucket_log!(ctx, WARN,
+                        request_id = %ctx.request_id(),
+                        client_ip = ?direct_ip,
+                        path = %path,
+                        reason = access.as_str(),
+                        "Denied access to built-in endpoint"
+                    );
+                    self.metrics
+                        .increment_endpoint_access_denied(endpoint, access.as_str());
+
+                    let (status, error) = match access {
+                        EndpointAccess::Unauthorized => (401, "Unauthorized"),
+                        _ => (403, "Forbidden"),
+                    };
+                    let mut header = ResponseHeader::build(status, None)?;
+                    header.insert_header(
+                        ERROR_CODE_HEADER,
+                        ErrorCode::AuthEndpointRestricted.as_str(),
+                    )?;
+                    header.insert_header("Content-Type", "application/json")?;
+                    if status == 401 {
+                        header.insert_header("WWW-Authenticate", "Bearer")?;
+                    }
+                    let error_body = serde_json::json!({
+                        "error": error,
+                        "code": ErrorCode::AuthEndpointRestricted.as_str(),
+                        "message": format!("Access to {} is restricted", path),
+                        "status": status
+                    })
+                    .to_string();
+                    header.insert_header("Content-Length", error_body.len().to_string())?;
+
+                    session
+                        .write_response_header(Box::new(header), false)
+                        .await?;
+                    session
+                        .write_response_body(Some(error_body.into()), true)
+                        .await?;
+
+                    self.metrics.increment_status_count(status);
+                    return Ok(true);
+                }
+            }
+        }
+
+        // Synthetic smoke-test endpoints (bypass auth and buckets, generated body)
+        if method == "GET" || method == "HEAD" {
+            if let Some(endpoint) = config
+                .server
+                .synthetic_endpoints
+                .iter()
+                .find(|e| e.path == path)
+            {
+                let mut header = ResponseHeader::build(200, None)?;
+                header.insert_header("Content-Type", endpoint.content_type.as_str())?;
+                header.insert_header("Content-Length", endpoint.size_bytes.to_string())?;
+                header.insert_header("Cache-Control", "no-store")?;
+
+                let empty = method == "HEAD" || endpoint.size_bytes == 0;
+                session
+                    .write_response_header(Box::new(header), empty)
+                    .await?;
+                if !empty {
+                    let mut chunks = synthetic::chunks(endpoint.size_bytes).peekable();
+                    while let Some(chunk) = chunks.next() {
+                        let last = chunks.peek().is_none();
+                        session.write_response_body(Some(chunk), last).await?;
+                    }
+                }
+
+                self.metrics.increment_status_count(200);
+                return Ok(true);
+            }
+        }
+
         // Special handling for /health endpoint (bypass auth, return health status)
         if path == "/health" {
             let response = special_endpoints::handle_health(self.start_time);
@@ -913,8 +2900,13 @@ impl ProxyHttp for YatagarasuProxy {
 
         // Special handling for /ready endpoint (bypass auth, check S3 backend health)
         if path == "/ready" {
-            let response =
-                special_endpoints::handle_ready(&config.buckets, &self.replica_sets, &self.metrics);
+            let response = special_endpoints::handle_ready(
+                &config.buckets,
+                &self.replica_sets,
+                &self.metrics,
+                config.server.readiness.mode,
+                self.cache.as_ref().map(|cache| cache.is_healthy()),
+            );
 
             let mut header = ResponseHeader::build(response.status, None)?;
             header.insert_header("Content-Type", response.content_type)?;
@@ -933,7 +2925,12 @@ impl ProxyHttp for YatagarasuProxy {
 
         // Special handling for /metrics endpoint (bypass auth, return Prometheus metrics)
         if path == "/metrics" {
-            let circuit_breaker_metrics = self.export_circuit_breaker_metrics();
+            let mut circuit_breaker_metrics = self.export_circuit_breaker_metrics();
+            circuit_breaker_metrics.push_str(&bulkhead::export_metrics(&self.bulkheads));
+            circuit_breaker_metrics.push_str(&self.buffer_budget.export_metrics());
+            if let Some(ref request_queue) = self.request_queue {
+                circuit_breaker_metrics.push_str(&request_queue.export_metrics());
+            }
             let response =
                 special_endpoints::handle_metrics(&self.metrics, circuit_breaker_metrics);
 
@@ -952,6 +2949,39 @@ impl ProxyHttp for YatagarasuProxy {
             return Ok(true);
         }
 
+        // Admin guard: strict methods, same-origin/CSRF checks and token IP binding
+        // for every known admin endpoint, including the legacy handlers below
+        if crate::admin::guard::allowed_methods(&path).is_some() {
+            let headers_map = Self::extract_headers(req);
+            let query_map = Self::extract_query_params(req);
+            let peer_ip = session
+                .client_addr()
+                .and_then(|addr| addr.as_inet().map(|inet| inet.ip()));
+            if let Some(rejection) = crate::admin::guard::check(
+                &path,
+                &method,
+                &headers_map,
+                &query_map,
+                &config,
+                peer_ip,
+            )
+            .await
+            {
+                bucket_log!(ctx, WARN,
+                    request_id = %ctx.request_id(),
+                    client_ip = %client_ip,
+                    method = %method,
+                    path = %path,
+                    status = rejection.status,
+                    reason = %rejection.message,
+                    "Admin request refused"
+                );
+                crate::admin::guard::send_rejection(session, &rejection).await;
+                self.metrics.increment_status_count(rejection.status);
+                return Ok(true);
+            }
+        }
+
         // Admin API Router (Phase 1)
         // Delegates to admin module for centralized handling and authentication
         // Note: Returns true if handled, false if not handled (allowing legacy fallbacks)
@@ -968,12 +2998,29 @@ impl ProxyHttp for YatagarasuProxy {
                 &config,
                 &self.metrics,
                 &self.prewarm_manager,
+                self.ip_ban_manager.as_ref(),
+                &self.maintenance,
+                self.key_popularity.as_ref(),
+                self.rate_limit_manager.as_ref(),
+                &self.auth_failures,
+                &router,
+                &self.replica_sets,
+                self.access_grants.as_ref(),
             )
             .await;
 
             return Ok(true);
         }
 
+        // Batch GET API: fetch many small objects in one round trip
+        if config.batch.is_batch_request(&path, &method) {
+            let headers = Self::extract_headers(req);
+            let query_params = Self::extract_query_params(req);
+            return self
+                .handle_batch_get(session, ctx, &config, &router, &headers, &query_params)
+                .await;
+        }
+
         // Special handling for /admin/reload endpoint (config hot reload)
         if path == "/admin/reload" && method == "POST" {
             if let Some(_reload_manager) = &self.reload_manager {
@@ -985,15 +3032,15 @@ impl ProxyHttp for YatagarasuProxy {
                         let query_params = Self::extract_query_params(req);
 
                         // Authenticate request
-                        match authenticate_request(&headers, &query_params, jwt_config) {
+                        match authenticate(&headers, &query_params, jwt_config).await {
                             Ok(_claims) => {
-                                tracing::debug!(
+                                bucket_log!(ctx, DEBUG,
                                     request_id = %ctx.request_id(),
                                     "Admin reload request authenticated successfully"
                                 );
                             }
                             Err(auth_error) => {
-                                tracing::warn!(
+                                bucket_log!(ctx, WARN,
                                     request_id = %ctx.request_id(),
                                     error = %auth_error,
                                     "Admin reload authentication failed"
@@ -1001,6 +3048,7 @@ impl ProxyHttp for YatagarasuProxy {
 
                                 // Build 401 Unauthorized response
                                 let response_json = serde_json::json!({
+                                    "code": auth_error.error_code().as_str(),
                                     "status": "error",
                                     "message": format!("Authentication required: {}", auth_error),
                                 });
@@ -1008,6 +3056,10 @@ impl ProxyHttp for YatagarasuProxy {
                                 let response_body = response_json.to_string();
 
                                 let mut header = ResponseHeader::build(401, None)?;
+                                header.insert_header(
+                                    ERROR_CODE_HEADER,
+                                    auth_error.error_code().as_str(),
+                                )?;
                                 header.insert_header("Content-Type", "application/json")?;
                                 header.insert_header(
                                     "Content-Length",
@@ -1033,7 +3085,7 @@ impl ProxyHttp for YatagarasuProxy {
                 // Attempt to reload configuration
                 match self.reload_configuration() {
                     Ok(new_generation) => {
-                        tracing::info!(
+                        bucket_log!(ctx, INFO,
                             request_id = %ctx.request_id(),
                             new_generation = new_generation,
                             "Configuration reloaded successfully via API"
@@ -1069,7 +3121,7 @@ impl ProxyHttp for YatagarasuProxy {
                         return Ok(true); // Short-circuit
                     }
                     Err(error_msg) => {
-                        tracing::error!(
+                        bucket_log!(ctx, ERROR,
                             request_id = %ctx.request_id(),
                             error = %error_msg,
                             "Configuration reload failed"
@@ -1080,6 +3132,7 @@ impl ProxyHttp for YatagarasuProxy {
 
                         // Build error response JSON
                         let response_json = serde_json::json!({
+                            "code": ErrorCode::ConfigInvalid.as_str(),
                             "status": "error",
                             "message": "Configuration reload failed",
                             "error": error_msg,
@@ -1088,6 +3141,8 @@ impl ProxyHttp for YatagarasuProxy {
                         let response_body = response_json.to_string();
 
                         let mut header = ResponseHeader::build(400, None)?;
+                        header
+                            .insert_header(ERROR_CODE_HEADER, ErrorCode::ConfigInvalid.as_str())?;
                         header.insert_header("Content-Type", "application/json")?;
                         header.insert_header("Content-Length", response_body.len().to_string())?;
 
@@ -1107,6 +3162,7 @@ impl ProxyHttp for YatagarasuProxy {
             } else {
                 // Reload manager not configured
                 let response_json = serde_json::json!({
+                    "code": ErrorCode::FeatureDisabled.as_str(),
                     "status": "error",
                     "message": "Hot reload not enabled",
                 });
@@ -1114,6 +3170,7 @@ impl ProxyHttp for YatagarasuProxy {
                 let response_body = response_json.to_string();
 
                 let mut header = ResponseHeader::build(503, None)?;
+                header.insert_header(ERROR_CODE_HEADER, ErrorCode::FeatureDisabled.as_str())?;
                 header.insert_header("Content-Type", "application/json")?;
                 header.insert_header("Content-Length", response_body.len().to_string())?;
 
@@ -1143,19 +3200,20 @@ impl ProxyHttp for YatagarasuProxy {
                         let query_params = Self::extract_query_params(req);
 
                         // Authenticate request
-                        match authenticate_request(&headers, &query_params, jwt_config) {
+                        match authenticate(&headers, &query_params, jwt_config).await {
                             Ok(claims) => {
                                 // Phase 65.1: Verify admin claims
                                 if !crate::auth::verify_admin_claims(
                                     &claims,
                                     &jwt_config.admin_claims,
                                 ) {
-                                    tracing::warn!(
+                                    bucket_log!(ctx, WARN,
                                         request_id = %ctx.request_id(),
                                         "Cache purge admin claims verification failed"
                                     );
 
                                     let response_json = serde_json::json!({
+                                        "code": ErrorCode::AuthForbidden.as_str(),
                                         "status": "error",
                                         "message": "Admin access denied: insufficient privileges",
                                     });
@@ -1163,6 +3221,10 @@ impl ProxyHttp for YatagarasuProxy {
                                     let response_body = response_json.to_string();
 
                                     let mut header = ResponseHeader::build(403, None)?;
+                                    header.insert_header(
+                                        ERROR_CODE_HEADER,
+                                        ErrorCode::AuthForbidden.as_str(),
+                                    )?;
                                     header.insert_header("Content-Type", "application/json")?;
                                     header.insert_header(
                                         "Content-Length",
@@ -1180,13 +3242,13 @@ impl ProxyHttp for YatagarasuProxy {
                                     return Ok(true);
                                 }
 
-                                tracing::debug!(
+                                bucket_log!(ctx, DEBUG,
                                     request_id = %ctx.request_id(),
                                     "Cache purge request authenticated successfully"
                                 );
                             }
                             Err(auth_error) => {
-                                tracing::warn!(
+                                bucket_log!(ctx, WARN,
                                     request_id = %ctx.request_id(),
                                     error = %auth_error,
                                     "Cache purge authentication failed"
@@ -1194,6 +3256,7 @@ impl ProxyHttp for YatagarasuProxy {
 
                                 // Build 401 Unauthorized response
                                 let response_json = serde_json::json!({
+                                    "code": auth_error.error_code().as_str(),
                                     "status": "error",
                                     "message": format!("Authentication required: {}", auth_error),
                                 });
@@ -1201,6 +3264,10 @@ impl ProxyHttp for YatagarasuProxy {
                                 let response_body = response_json.to_string();
 
                                 let mut header = ResponseHeader::build(401, None)?;
+                                header.insert_header(
+                                    ERROR_CODE_HEADER,
+                                    auth_error.error_code().as_str(),
+                                )?;
                                 header.insert_header("Content-Type", "application/json")?;
                                 header.insert_header(
                                     "Content-Length",
@@ -1226,7 +3293,7 @@ impl ProxyHttp for YatagarasuProxy {
                 // Purge cache (clear all layers)
                 match cache.clear().await {
                     Ok(()) => {
-                        tracing::info!(
+                        bucket_log!(ctx, INFO,
                             request_id = %ctx.request_id(),
                             "Cache purged successfully (all layers cleared)"
                         );
@@ -1257,11 +3324,15 @@ impl ProxyHttp for YatagarasuProxy {
                         // Record metrics
                         self.metrics.increment_status_count(200);
                         self.metrics.increment_cache_purge();
+                        events::publish(EventKind::CachePurge {
+                            bucket: None,
+                            key: None,
+                        });
 
                         return Ok(true); // Short-circuit
                     }
                     Err(e) => {
-                        tracing::error!(
+                        bucket_log!(ctx, ERROR,
                             request_id = %ctx.request_id(),
                             error = %e,
                             "Failed to purge cache"
@@ -1269,6 +3340,7 @@ impl ProxyHttp for YatagarasuProxy {
 
                         // Build error response JSON
                         let response_json = serde_json::json!({
+                            "code": ErrorCode::Internal.as_str(),
                             "status": "error",
                             "message": format!("Failed to purge cache: {}", e),
                         });
@@ -1276,6 +3348,7 @@ impl ProxyHttp for YatagarasuProxy {
                         let response_body = response_json.to_string();
 
                         let mut header = ResponseHeader::build(500, None)?;
+                        header.insert_header(ERROR_CODE_HEADER, ErrorCode::Internal.as_str())?;
                         header.insert_header("Content-Type", "application/json")?;
                         header.insert_header("Content-Length", response_body.len().to_string())?;
 
@@ -1294,12 +3367,13 @@ impl ProxyHttp for YatagarasuProxy {
                 }
             } else {
                 // Cache not enabled
-                tracing::warn!(
+                bucket_log!(ctx, WARN,
                     request_id = %ctx.request_id(),
                     "Cache purge requested but cache is not enabled"
                 );
 
                 let response_json = serde_json::json!({
+                    "code": ErrorCode::FeatureDisabled.as_str(),
                     "status": "error",
                     "message": "Cache is not enabled",
                 });
@@ -1307,6 +3381,7 @@ impl ProxyHttp for YatagarasuProxy {
                 let response_body = response_json.to_string();
 
                 let mut header = ResponseHeader::build(404, None)?;
+                header.insert_header(ERROR_CODE_HEADER, ErrorCode::FeatureDisabled.as_str())?;
                 header.insert_header("Content-Type", "application/json")?;
                 header.insert_header("Content-Length", response_body.len().to_string())?;
 
@@ -1346,26 +3421,31 @@ impl ProxyHttp for YatagarasuProxy {
                         let headers = Self::extract_headers(req);
                         let query_params = Self::extract_query_params(req);
 
-                        match authenticate_request(&headers, &query_params, jwt_config) {
+                        match authenticate(&headers, &query_params, jwt_config).await {
                             Ok(claims) => {
                                 // Phase 65.1: Verify admin claims
                                 if !crate::auth::verify_admin_claims(
                                     &claims,
                                     &jwt_config.admin_claims,
                                 ) {
-                                    tracing::warn!(
+                                    bucket_log!(ctx, WARN,
                                         request_id = %ctx.request_id(),
                                         bucket = %bucket_name,
                                         "Cache purge admin claims verification failed"
                                     );
 
                                     let response_json = serde_json::json!({
+                                        "code": ErrorCode::AuthForbidden.as_str(),
                                         "status": "error",
                                         "message": "Admin access denied: insufficient privileges",
                                     });
                                     let response_body = response_json.to_string();
 
                                     let mut header = ResponseHeader::build(403, None)?;
+                                    header.insert_header(
+                                        ERROR_CODE_HEADER,
+                                        ErrorCode::AuthForbidden.as_str(),
+                                    )?;
                                     header.insert_header("Content-Type", "application/json")?;
                                     header.insert_header(
                                         "Content-Length",
@@ -1382,26 +3462,31 @@ impl ProxyHttp for YatagarasuProxy {
                                     return Ok(true);
                                 }
 
-                                tracing::debug!(
+                                bucket_log!(ctx, DEBUG,
                                     request_id = %ctx.request_id(),
                                     bucket = %bucket_name,
                                     "Cache purge request authenticated successfully"
                                 );
                             }
                             Err(auth_error) => {
-                                tracing::warn!(
+                                bucket_log!(ctx, WARN,
                                     request_id = %ctx.request_id(),
                                     error = %auth_error,
                                     "Cache purge authentication failed"
                                 );
 
                                 let response_json = serde_json::json!({
+                                    "code": auth_error.error_code().as_str(),
                                     "status": "error",
                                     "message": format!("Authentication required: {}", auth_error),
                                 });
                                 let response_body = response_json.to_string();
 
                                 let mut header = ResponseHeader::build(401, None)?;
+                                header.insert_header(
+                                    ERROR_CODE_HEADER,
+                                    auth_error.error_code().as_str(),
+                                )?;
                                 header.insert_header("Content-Type", "application/json")?;
                                 header.insert_header(
                                     "Content-Length",
@@ -1433,7 +3518,7 @@ impl ProxyHttp for YatagarasuProxy {
 
                     match cache.delete(&cache_key).await {
                         Ok(deleted) => {
-                            tracing::info!(
+                            bucket_log!(ctx, INFO,
                                 request_id = %ctx.request_id(),
                                 bucket = %bucket_name,
                                 object_path = %obj_path,
@@ -1464,23 +3549,30 @@ impl ProxyHttp for YatagarasuProxy {
                             self.metrics.increment_status_count(200);
                             if deleted {
                                 self.metrics.increment_cache_purge();
+                                events::publish(EventKind::CachePurge {
+                                    bucket: Some(bucket_name.to_string()),
+                                    key: Some(obj_path.clone()),
+                                });
                             }
                             return Ok(true);
                         }
                         Err(e) => {
-                            tracing::error!(
+                            bucket_log!(ctx, ERROR,
                                 request_id = %ctx.request_id(),
                                 error = %e,
                                 "Failed to purge cache entry"
                             );
 
                             let response_json = serde_json::json!({
+                                "code": ErrorCode::Internal.as_str(),
                                 "status": "error",
                                 "message": format!("Failed to purge cache entry: {}", e),
                             });
                             let response_body = response_json.to_string();
 
                             let mut header = ResponseHeader::build(500, None)?;
+                            header
+                                .insert_header(ERROR_CODE_HEADER, ErrorCode::Internal.as_str())?;
                             header.insert_header("Content-Type", "application/json")?;
                             header
                                 .insert_header("Content-Length", response_body.len().to_string())?;
@@ -1497,7 +3589,7 @@ impl ProxyHttp for YatagarasuProxy {
                     }
                 } else {
                     // Purge entire bucket: /admin/cache/purge/:bucket
-                    tracing::info!(
+                    bucket_log!(ctx, INFO,
                         request_id = %ctx.request_id(),
                         bucket = %bucket_name,
                         "Purging all cache entries for bucket"
@@ -1505,6 +3597,10 @@ impl ProxyHttp for YatagarasuProxy {
 
                     match cache.clear_bucket(bucket_name).await {
                         Ok(deleted_count) => {
+                            events::publish(EventKind::CachePurge {
+                                bucket: Some(bucket_name.to_string()),
+                                key: None,
+                            });
                             let response_json = serde_json::json!({
                                 "status": "success",
                                 "message": format!("Purged {} cache entries for bucket", deleted_count),
@@ -1529,12 +3625,15 @@ impl ProxyHttp for YatagarasuProxy {
                         }
                         Err(e) => {
                             let response_json = serde_json::json!({
+                                "code": ErrorCode::Internal.as_str(),
                                 "status": "error",
                                 "message": format!("Failed to purge cache: {}", e),
                             });
                             let response_body = response_json.to_string();
 
                             let mut header = ResponseHeader::build(500, None)?;
+                            header
+                                .insert_header(ERROR_CODE_HEADER, ErrorCode::Internal.as_str())?;
                             header.insert_header("Content-Type", "application/json")?;
                             header
                                 .insert_header("Content-Length", response_body.len().to_string())?;
@@ -1553,12 +3652,14 @@ impl ProxyHttp for YatagarasuProxy {
             } else {
                 // Cache not enabled
                 let response_json = serde_json::json!({
+                    "code": ErrorCode::FeatureDisabled.as_str(),
                     "status": "error",
                     "message": "Cache is not enabled",
                 });
                 let response_body = response_json.to_string();
 
                 let mut header = ResponseHeader::build(404, None)?;
+                header.insert_header(ERROR_CODE_HEADER, ErrorCode::FeatureDisabled.as_str())?;
                 header.insert_header("Content-Type", "application/json")?;
                 header.insert_header("Content-Length", response_body.len().to_string())?;
 
@@ -1585,15 +3686,15 @@ impl ProxyHttp for YatagarasuProxy {
                         let query_params = Self::extract_query_params(req);
 
                         // Authenticate request
-                        match authenticate_request(&headers, &query_params, jwt_config) {
+                        match authenticate(&headers, &query_params, jwt_config).await {
                             Ok(_claims) => {
-                                tracing::debug!(
+                                bucket_log!(ctx, DEBUG,
                                     request_id = %ctx.request_id(),
                                     "Cache stats request authenticated successfully"
                                 );
                             }
                             Err(auth_error) => {
-                                tracing::warn!(
+                                bucket_log!(ctx, WARN,
                                     request_id = %ctx.request_id(),
                                     error = %auth_error,
                                     "Cache stats authentication failed"
@@ -1601,6 +3702,7 @@ impl ProxyHttp for YatagarasuProxy {
 
                                 // Build 401 Unauthorized response
                                 let response_json = serde_json::json!({
+                                    "code": auth_error.error_code().as_str(),
                                     "status": "error",
                                     "message": format!("Authentication required: {}", auth_error),
                                 });
@@ -1608,6 +3710,10 @@ impl ProxyHttp for YatagarasuProxy {
                                 let response_body = response_json.to_string();
 
                                 let mut header = ResponseHeader::build(401, None)?;
+                                header.insert_header(
+                                    ERROR_CODE_HEADER,
+                                    auth_error.error_code().as_str(),
+                                )?;
                                 header.insert_header("Content-Type", "application/json")?;
                                 header.insert_header(
                                     "Content-Length",
@@ -1633,7 +3739,7 @@ impl ProxyHttp for YatagarasuProxy {
                 // Get cache statistics
                 match cache.stats().await {
                     Ok(stats) => {
-                        tracing::debug!(
+                        bucket_log!(ctx, DEBUG,
                             request_id = %ctx.request_id(),
                             hits = stats.hits,
                             misses = stats.misses,
@@ -1689,7 +3795,7 @@ impl ProxyHttp for YatagarasuProxy {
                         return Ok(true); // Short-circuit
                     }
                     Err(e) => {
-                        tracing::error!(
+                        bucket_log!(ctx, ERROR,
                             request_id = %ctx.request_id(),
                             error = %e,
                             "Failed to retrieve cache stats"
@@ -1697,6 +3803,7 @@ impl ProxyHttp for YatagarasuProxy {
 
                         // Build error response JSON
                         let response_json = serde_json::json!({
+                            "code": ErrorCode::Internal.as_str(),
                             "status": "error",
                             "message": format!("Failed to retrieve cache stats: {}", e),
                         });
@@ -1704,6 +3811,7 @@ impl ProxyHttp for YatagarasuProxy {
                         let response_body = response_json.to_string();
 
                         let mut header = ResponseHeader::build(500, None)?;
+                        header.insert_header(ERROR_CODE_HEADER, ErrorCode::Internal.as_str())?;
                         header.insert_header("Content-Type", "application/json")?;
                         header.insert_header("Content-Length", response_body.len().to_string())?;
 
@@ -1722,12 +3830,13 @@ impl ProxyHttp for YatagarasuProxy {
                 }
             } else {
                 // Cache not enabled
-                tracing::warn!(
+                bucket_log!(ctx, WARN,
                     request_id = %ctx.request_id(),
                     "Cache stats requested but cache is not enabled"
                 );
 
                 let response_json = serde_json::json!({
+                    "code": ErrorCode::FeatureDisabled.as_str(),
                     "status": "error",
                     "message": "Cache is not enabled",
                 });
@@ -1735,6 +3844,7 @@ impl ProxyHttp for YatagarasuProxy {
                 let response_body = response_json.to_string();
 
                 let mut header = ResponseHeader::build(404, None)?;
+                header.insert_header(ERROR_CODE_HEADER, ErrorCode::FeatureDisabled.as_str())?;
                 header.insert_header("Content-Type", "application/json")?;
                 header.insert_header("Content-Length", response_body.len().to_string())?;
 
@@ -1762,12 +3872,14 @@ impl ProxyHttp for YatagarasuProxy {
 
             if bucket_name.is_empty() {
                 let response_json = serde_json::json!({
+                    "code": ErrorCode::BadRequest.as_str(),
                     "status": "error",
                     "message": "Bucket name is required",
                 });
                 let response_body = response_json.to_string();
 
                 let mut header = ResponseHeader::build(400, None)?;
+                header.insert_header(ERROR_CODE_HEADER, ErrorCode::BadRequest.as_str())?;
                 header.insert_header("Content-Type", "application/json")?;
                 header.insert_header("Content-Length", response_body.len().to_string())?;
 
@@ -1785,7 +3897,7 @@ impl ProxyHttp for YatagarasuProxy {
                 // Get bucket-specific statistics
                 match cache.stats_bucket(bucket_name).await {
                     Ok(stats) => {
-                        tracing::debug!(
+                        bucket_log!(ctx, DEBUG,
                             request_id = %ctx.request_id(),
                             bucket = %bucket_name,
                             current_size_bytes = stats.current_size_bytes,
@@ -1822,7 +3934,7 @@ impl ProxyHttp for YatagarasuProxy {
                         return Ok(true);
                     }
                     Err(e) => {
-                        tracing::error!(
+                        bucket_log!(ctx, ERROR,
                             request_id = %ctx.request_id(),
                             bucket = %bucket_name,
                             error = %e,
@@ -1830,6 +3942,7 @@ impl ProxyHttp for YatagarasuProxy {
                         );
 
                         let response_json = serde_json::json!({
+                            "code": ErrorCode::Internal.as_str(),
                             "status": "error",
                             "message": format!("Failed to retrieve bucket cache stats: {}", e),
                         });
@@ -1837,6 +3950,7 @@ impl ProxyHttp for YatagarasuProxy {
                         let response_body = response_json.to_string();
 
                         let mut header = ResponseHeader::build(500, None)?;
+                        header.insert_header(ERROR_CODE_HEADER, ErrorCode::Internal.as_str())?;
                         header.insert_header("Content-Type", "application/json")?;
                         header.insert_header("Content-Length", response_body.len().to_string())?;
 
@@ -1852,6 +3966,7 @@ impl ProxyHttp for YatagarasuProxy {
                 }
             } else {
                 let response_json = serde_json::json!({
+                    "code": ErrorCode::FeatureDisabled.as_str(),
                     "status": "error",
                     "message": "Cache is not enabled",
                 });
@@ -1859,6 +3974,7 @@ impl ProxyHttp for YatagarasuProxy {
                 let response_body = response_json.to_string();
 
                 let mut header = ResponseHeader::build(404, None)?;
+                header.insert_header(ERROR_CODE_HEADER, ErrorCode::FeatureDisabled.as_str())?;
                 header.insert_header("Content-Type", "application/json")?;
                 header.insert_header("Content-Length", response_body.len().to_string())?;
 
@@ -1886,15 +4002,15 @@ impl ProxyHttp for YatagarasuProxy {
                         let headers = Self::extract_headers(req);
 
                         // Authenticate request
-                        match authenticate_request(&headers, &query_params, jwt_config) {
+                        match authenticate(&headers, &query_params, jwt_config).await {
                             Ok(_claims) => {
-                                tracing::debug!(
+                                bucket_log!(ctx, DEBUG,
                                     request_id = %ctx.request_id(),
                                     "Cache info request authenticated successfully"
                                 );
                             }
                             Err(auth_error) => {
-                                tracing::warn!(
+                                bucket_log!(ctx, WARN,
                                     request_id = %ctx.request_id(),
                                     error = %auth_error,
                                     "Cache info authentication failed"
@@ -1902,6 +4018,7 @@ impl ProxyHttp for YatagarasuProxy {
 
                                 // Build 401 Unauthorized response
                                 let response_json = serde_json::json!({
+                                    "code": auth_error.error_code().as_str(),
                                     "status": "error",
                                     "message": format!("Authentication required: {}", auth_error),
                                 });
@@ -1909,6 +4026,10 @@ impl ProxyHttp for YatagarasuProxy {
                                 let response_body = response_json.to_string();
 
                                 let mut header = ResponseHeader::build(401, None)?;
+                                header.insert_header(
+                                    ERROR_CODE_HEADER,
+                                    auth_error.error_code().as_str(),
+                                )?;
                                 header.insert_header("Content-Type", "application/json")?;
                                 header.insert_header(
                                     "Content-Length",
@@ -1936,12 +4057,14 @@ impl ProxyHttp for YatagarasuProxy {
 
                 if key_param.is_none() || key_param.unwrap().is_empty() {
                     let response_json = serde_json::json!({
+                        "code": ErrorCode::BadRequest.as_str(),
                         "status": "error",
                         "message": "Missing required parameter 'key'. Format: bucket:object_key",
                     });
                     let response_body = response_json.to_string();
 
                     let mut header = ResponseHeader::build(400, None)?;
+                    header.insert_header(ERROR_CODE_HEADER, ErrorCode::BadRequest.as_str())?;
                     header.insert_header("Content-Type", "application/json")?;
                     header.insert_header("Content-Length", response_body.len().to_string())?;
 
@@ -1961,12 +4084,14 @@ impl ProxyHttp for YatagarasuProxy {
                 let parts: Vec<&str> = key_str.splitn(2, ':').collect();
                 if parts.len() != 2 || parts[0].is_empty() || parts[1].is_empty() {
                     let response_json = serde_json::json!({
+                        "code": ErrorCode::BadRequest.as_str(),
                         "status": "error",
                         "message": "Invalid key format. Expected: bucket:object_key",
                     });
                     let response_body = response_json.to_string();
 
                     let mut header = ResponseHeader::build(400, None)?;
+                    header.insert_header(ERROR_CODE_HEADER, ErrorCode::BadRequest.as_str())?;
                     header.insert_header("Content-Type", "application/json")?;
                     header.insert_header("Content-Length", response_body.len().to_string())?;
 
@@ -1994,7 +4119,7 @@ impl ProxyHttp for YatagarasuProxy {
                 // Try to get the entry from cache
                 match cache.get(&cache_key).await {
                     Ok(Some(entry)) => {
-                        tracing::debug!(
+                        bucket_log!(ctx, DEBUG,
                             request_id = %ctx.request_id(),
                             bucket = %bucket,
                             object_key = %object_key,
@@ -2054,7 +4179,7 @@ impl ProxyHttp for YatagarasuProxy {
                         return Ok(true);
                     }
                     Ok(None) => {
-                        tracing::debug!(
+                        bucket_log!(ctx, DEBUG,
                             request_id = %ctx.request_id(),
                             bucket = %bucket,
                             object_key = %object_key,
@@ -2062,6 +4187,7 @@ impl ProxyHttp for YatagarasuProxy {
                         );
 
                         let response_json = serde_json::json!({
+                            "code": ErrorCode::NotFound.as_str(),
                             "status": "error",
                             "message": "Cache entry not found",
                             "key": key_str,
@@ -2070,6 +4196,7 @@ impl ProxyHttp for YatagarasuProxy {
                         let response_body = response_json.to_string();
 
                         let mut header = ResponseHeader::build(404, None)?;
+                        header.insert_header(ERROR_CODE_HEADER, ErrorCode::NotFound.as_str())?;
                         header.insert_header("Content-Type", "application/json")?;
                         header.insert_header("Content-Length", response_body.len().to_string())?;
 
@@ -2083,7 +4210,7 @@ impl ProxyHttp for YatagarasuProxy {
                         return Ok(true);
                     }
                     Err(e) => {
-                        tracing::error!(
+                        bucket_log!(ctx, ERROR,
                             request_id = %ctx.request_id(),
                             bucket = %bucket,
                             object_key = %object_key,
@@ -2092,6 +4219,7 @@ impl ProxyHttp for YatagarasuProxy {
                         );
 
                         let response_json = serde_json::json!({
+                            "code": ErrorCode::Internal.as_str(),
                             "status": "error",
                             "message": format!("Failed to retrieve cache entry: {}", e),
                         });
@@ -2099,6 +4227,7 @@ impl ProxyHttp for YatagarasuProxy {
                         let response_body = response_json.to_string();
 
                         let mut header = ResponseHeader::build(500, None)?;
+                        header.insert_header(ERROR_CODE_HEADER, ErrorCode::Internal.as_str())?;
                         header.insert_header("Content-Type", "application/json")?;
                         header.insert_header("Content-Length", response_body.len().to_string())?;
 
@@ -2114,6 +4243,7 @@ impl ProxyHttp for YatagarasuProxy {
                 }
             } else {
                 let response_json = serde_json::json!({
+                    "code": ErrorCode::FeatureDisabled.as_str(),
                     "status": "error",
                     "message": "Cache is not enabled",
                 });
@@ -2121,6 +4251,7 @@ impl ProxyHttp for YatagarasuProxy {
                 let response_body = response_json.to_string();
 
                 let mut header = ResponseHeader::build(404, None)?;
+                header.insert_header(ERROR_CODE_HEADER, ErrorCode::FeatureDisabled.as_str())?;
                 header.insert_header("Content-Type", "application/json")?;
                 header.insert_header("Content-Length", response_body.len().to_string())?;
 
@@ -2135,11 +4266,17 @@ impl ProxyHttp for YatagarasuProxy {
             }
         }
 
+        // Prefix the request came in on, for buckets with aliases or a version
+        let route_entry = router.route_entry(&path);
+
         // Update context with request details
+        let path = bucket_path;
         ctx.set_method(method);
         ctx.set_path(path.clone());
         ctx.set_headers(Self::extract_headers(req));
-        ctx.set_query_params(Self::extract_query_params(req));
+        ctx.set_query_params(
+            extracted_query_params.unwrap_or_else(|| Self::extract_query_params(req)),
+        );
 
         // Route request to bucket
         let bucket_config = match router.route(&path) {
@@ -2147,6 +4284,7 @@ impl ProxyHttp for YatagarasuProxy {
             None => {
                 // No matching bucket found - return 404
                 let mut header = ResponseHeader::build(404, None)?;
+                header.insert_header(ERROR_CODE_HEADER, ErrorCode::NotFound.as_str())?;
                 header.insert_header("Content-Type", "text/plain")?;
                 header.insert_header("Content-Length", "0")?;
                 session
@@ -2160,8 +4298,18 @@ impl ProxyHttp for YatagarasuProxy {
             }
         };
 
-        // Store bucket config in context
+        // Store bucket config in context, pinning the request to this config generation
         ctx.set_bucket_config(bucket_config.clone());
+        ctx.set_snapshot(Arc::clone(&config), Arc::clone(&router));
+        ctx.set_routing(
+            config.generation,
+            router.extract_s3_key(&path).unwrap_or_default(),
+        );
+
+        if bucket_config.deprecation.is_some() {
+            self.metrics
+                .increment_deprecated_route_request(&bucket_config.name);
+        }
 
         // -- Audit Logging: Populate bucket and key --
         if self.audit_writer.is_some() {
@@ -2174,6 +4322,126 @@ impl ProxyHttp for YatagarasuProxy {
 
         // Record bucket metrics
         self.metrics.increment_bucket_count(&bucket_config.name);
+        if let Some(entry) = route_entry.filter(|e| e.bucket == bucket_config.name) {
+            self.metrics
+                .increment_route_entry_request(entry.bucket, entry.prefix, entry.version);
+        }
+
+        // Hotlink protection: Referer/Origin must match the bucket's allowlist
+        if let Some(hotlink) = bucket_config.hotlink.as_ref().filter(|h| h.enabled) {
+            let referer = ctx.headers().get("referer").map(String::as_str);
+            let origin = ctx.headers().get("origin").map(String::as_str);
+            if !hotlink.is_allowed(referer, origin) {
+                bucket_log!(ctx, WARN,
+                    request_id = %ctx.request_id(),
+                    bucket = %bucket_config.name,
+                    referer = ?referer.map(|v| match &bucket_config.logging {
+                        Some(logging) => logging.redact_header_value("referer", v),
+                        None => v.to_string(),
+                    }),
+                    origin = ?origin,
+                    action = hotlink.action.as_str(),
+                    "Hotlink request rejected by Referer/Origin allowlist"
+                );
+                self.metrics
+                    .increment_hotlink_request(&bucket_config.name, hotlink.action.as_str());
+
+                match hotlink.action {
+                    HotlinkAction::Deny => {
+                        let mut header = ResponseHeader::build(403, None)?;
+                        header.insert_header(
+                            ERROR_CODE_HEADER,
+                            ErrorCode::AuthHotlinkDenied.as_str(),
+                        )?;
+                        header.insert_header("Content-Type", "application/json")?;
+
+                        let error_body = serde_json::json!({
+                            "error": "Forbidden",
+                            "code": ErrorCode::AuthHotlinkDenied.as_str(),
+                            "message": "Hotlinking is not allowed for this resource",
+                            "status": 403
+                        })
+                        .to_string();
+
+                        header.insert_header("Content-Length", error_body.len().to_string())?;
+
+                        session
+                            .write_response_header(Box::new(header), false)
+                            .await?;
+                        session
+                            .write_response_body(Some(error_body.into()), true)
+                            .await?;
+
+                        self.metrics.increment_status_count(403);
+
+                        return Ok(true);
+                    }
+                    HotlinkAction::Watermark => {
+                        // Route images through the optimize + watermark pipeline
+                        if ctx.image_params().is_none() {
+                            ctx.set_image_params(ImageParams::default());
+                        }
+                        ctx.set_hotlinked();
+                    }
+                }
+            }
+        }
+
+        // Signed URLs: `expires` and `sig` must match the bucket's HMAC secret
+        if let Some(signed_url) = bucket_config.signed_url.as_ref().filter(|s| s.enabled) {
+            let signed_path = session.req_header().uri.path().to_string();
+            // Bound to the direct peer, or the client behind a trusted proxy,
+            // so a client cannot claim someone else's address
+            let bound_ip = helpers::trusted_client_ip(session, &signed_url.trusted_proxies)
+                .map(|ip| ip.to_string());
+            let now = unix_now();
+            let result = signed_url::verify(
+                signed_url,
+                &signed_path,
+                ctx.query_params(),
+                bound_ip.as_deref(),
+                now,
+            );
+            self.metrics.increment_signed_url_request(
+                &bucket_config.name,
+                result.err().map_or("valid", |e| e.as_str()),
+            );
+
+            if let Err(e) = result {
+                bucket_log!(ctx, WARN,
+                    request_id = %ctx.request_id(),
+                    bucket = %bucket_config.name,
+                    reason = e.as_str(),
+                    "Signed URL rejected"
+                );
+
+                let mut header = ResponseHeader::build(403, None)?;
+                header
+                    .insert_header(ERROR_CODE_HEADER, ErrorCode::AuthSignedUrlInvalid.as_str())?;
+                header.insert_header("Content-Type", "application/json")?;
+
+                let error_body = serde_json::json!({
+                    "error": "Forbidden",
+                    "code": ErrorCode::AuthSignedUrlInvalid.as_str(),
+                    "message": e.message(),
+                    "status": 403
+                })
+                .to_string();
+
+                header.insert_header("Content-Length", error_body.len().to_string())?;
+
+                session
+                    .write_response_header(Box::new(header), false)
+                    .await?;
+                session
+                    .write_response_body(Some(error_body.into()), true)
+                    .await?;
+
+                self.metrics.increment_status_count(403);
+
+                return Ok(true);
+            }
+        }
 
         // THIRD: Check rate limits (if enabled)
         if let Some(ref rate_limit_manager) = self.rate_limit_manager {
@@ -2190,7 +4458,7 @@ impl ProxyHttp for YatagarasuProxy {
             if let Err(rate_limit_error) =
                 rate_limit_manager.check_all(&bucket_config.name, client_ip)
             {
-                tracing::warn!(
+                bucket_log!(ctx, WARN,
                     request_id = %ctx.request_id(),
                     bucket = %bucket_config.name,
                     client_ip = %client_ip_str,
@@ -2204,11 +4472,13 @@ impl ProxyHttp for YatagarasuProxy {
                     .increment_rate_limit_exceeded(&bucket_config.name);
 
                 let mut header = ResponseHeader::build(429, None)?;
+                header.insert_header(ERROR_CODE_HEADER, rate_limit_error.error_code().as_str())?;
                 header.insert_header("Content-Type", "application/json")?;
                 header.insert_header("Retry-After", "1")?; // Suggest retry after 1 second
 
                 let error_body = serde_json::json!({
                     "error": "Too Many Requests",
+                    "code": rate_limit_error.error_code().as_str(),
                     "message": rate_limit_error.to_string(),
                     "status": 429
                 })
@@ -2230,56 +4500,215 @@ impl ProxyHttp for YatagarasuProxy {
             }
         }
 
+        // Clients fetching objects in thousands of tiny ranges bypass the
+        // cache and hammer S3: throttle their Range requests once flagged
+        if let (Some(range_abuse), Some(range)) = (&self.range_abuse, ctx.headers().get("range")) {
+            let direct_ip = session
+                .client_addr()
+                .and_then(|addr| addr.as_inet().map(|inet| inet.ip()));
+            let object = format!("{}/{}", bucket_config.name, ctx.s3_key());
+            let verdict = direct_ip.map_or(RangeVerdict::Allowed, |ip| {
+                range_abuse.check(ip, &object, range)
+            });
+            match verdict {
+                RangeVerdict::Allowed => {}
+                RangeVerdict::Flagged => {
+                    self.metrics
+                        .increment_range_abuse(&bucket_config.name, "flagged");
+                    bucket_log!(ctx, WARN,
+                        request_id = %ctx.request_id(),
+                        bucket = %bucket_config.name,
+                        object_key = %ctx.s3_key(),
+                        direct_ip = ?direct_ip,
+                        "Client flagged for small Range requests, throttling its Range requests"
+                    );
+                }
+                RangeVerdict::Throttled => {
+                    self.metrics
+                        .increment_range_abuse(&bucket_config.name, "throttled");
+                    bucket_log!(ctx, DEBUG,
+                        request_id = %ctx.request_id(),
+                        bucket = %bucket_config.name,
+                        direct_ip = ?direct_ip,
+                        "Range request throttled"
+                    );
+
+                    let code = ErrorCode::RateLimitRange;
+                    let mut header = ResponseHeader::build(429, None)?;
+                    header.insert_header(ERROR_CODE_HEADER, code.as_str())?;
+                    header.insert_header("Content-Type", "application/json")?;
+                    header.insert_header("Retry-After", "1")?;
+                    let error_body = serde_json::json!({
+                        "error": "Too Many Requests",
+                        "code": code.as_str(),
+                        "message": "Too many small Range requests; fetch larger ranges or the whole object",
+                        "status": 429
+                    })
+                    .to_string();
+                    header.insert_header("Content-Length", error_body.len().to_string())?;
+                    session
+                        .write_response_header(Box::new(header), false)
+                        .await?;
+                    session
+                        .write_response_body(Some(error_body.into()), true)
+                        .await?;
+                    self.metrics.increment_status_count(429);
+                    return Ok(true);
+                }
+            }
+        }
+
         // FOURTH: Check circuit breaker for this bucket (if configured)
+        let degraded_enabled = bucket_config.degraded.as_ref().is_some_and(|d| d.enabled);
         if let Some(circuit_breaker) = self.circuit_breakers.get(&bucket_config.name) {
             // Check if circuit breaker allows request
-            if !circuit_breaker.should_allow_request() {
-                tracing::warn!(
+            let before = circuit_breaker.state();
+            let allowed = circuit_breaker.should_allow_request();
+            helpers::publish_breaker_transition(
+                &bucket_config.name,
+                before,
+                circuit_breaker.state(),
+            );
+            if !allowed {
+                bucket_log!(ctx, WARN,
                     request_id = %ctx.request_id(),
                     bucket = %bucket_config.name,
                     state = ?circuit_breaker.state(),
                     "Circuit breaker rejecting request (circuit open)"
                 );
 
-                let mut header = ResponseHeader::build(503, None)?;
-                header.insert_header("Content-Type", "application/json")?;
-                header.insert_header("Retry-After", "60")?; // Suggest retry after circuit timeout
+                if degraded_enabled {
+                    // Degraded mode: only the cache may answer from here on
+                    ctx.set_origin_unavailable();
+                } else {
+                    return self
+                        .respond_origin_unavailable(session, &bucket_config.name)
+                        .await; // Request handled (circuit breaker rejected)
+                }
+            } else {
+                // If we're in half-open state, increment request counter
+                circuit_breaker.start_half_open_request();
+            }
+        }
+
+        // Degraded mode also covers buckets whose replicas all have open breakers
+        if degraded_enabled && !ctx.is_origin_unavailable() {
+            if let Some(replica_set) = self.replica_sets.get(&bucket_config.name) {
+                if !replica_set
+                    .replicas
+                    .iter()
+                    .any(|replica| replica.circuit_breaker.should_allow_request())
+                {
+                    bucket_log!(ctx, WARN,
+                        request_id = %ctx.request_id(),
+                        bucket = %bucket_config.name,
+                        "All replicas unhealthy, serving from cache only (degraded mode)"
+                    );
+                    ctx.set_origin_unavailable();
+                }
+            }
+        }
+
+        let authz_started = Instant::now();
+
+        // Temporary access grants (reads only): an IP grant stands in for
+        // authentication and authorization, a subject grant for authorization
+        let is_read = ctx.method() == "GET" || ctx.method() == "HEAD";
+        let grant_key = router.extract_s3_key(ctx.path()).unwrap_or_default();
+        let ip_grant = match (&self.access_grants, is_read) {
+            (Some(access_grants), true) => session
+                .client_addr()
+                .and_then(|addr| addr.as_inet().map(|inet| inet.ip()))
+                .and_then(|ip| access_grants.find_for_ip(&bucket_config.name, &grant_key, ip)),
+            _ => None,
+        };
+
+        // Incoming SigV4: S3 SDK clients sign with keys configured for the bucket
+        if let Some(sigv4_config) = bucket_config
+            .sigv4_auth
+            .as_ref()
+            .filter(|s| s.enabled && ip_grant.is_none())
+        {
+            let now = unix_now();
+            let req = session.req_header();
+            let result = sigv4::verify(
+                sigv4_config,
+                &sigv4::IncomingRequest {
+                    method: ctx.method(),
+                    path: req.uri.path(),
+                    query: req.uri.query(),
+                    headers: ctx.headers(),
+                },
+                now,
+            );
+            self.metrics.increment_sigv4_request(
+                &bucket_config.name,
+                result.as_ref().err().map_or("valid", |e| e.as_str()),
+            );
+
+            match result {
+                Ok(access_key_id) => {
+                    // The signature covers the declared digest, not the body:
+                    // the body is checked against it on its way to S3
+                    if let Some(expected) = sigv4::declared_payload_sha256(ctx.headers()) {
+                        let digest = sigv4::PayloadDigest::new(expected);
+                        ctx.set_payload_digest(digest);
+                    }
+                    if self.audit_writer.is_some() {
+                        ctx.audit().user = Some(access_key_id.clone());
+                    }
+                    // OPA and OpenFGA see the access key as the subject
+                    ctx.set_claims(Claims {
+                        sub: Some(access_key_id),
+                        exp: None,
+                        iat: None,
+                        nbf: None,
+                        iss: None,
+                        custom: serde_json::Map::new(),
+                    });
+                    self.metrics.increment_auth_success();
+                }
+                Err(e) => {
+                    bucket_log!(ctx, WARN,
+                        request_id = %ctx.request_id(),
+                        bucket = %bucket_config.name,
+                        reason = e.as_str(),
+                        "SigV4 request rejected"
+                    );
 
-                let error_body = serde_json::json!({
-                    "error": "Service Temporarily Unavailable",
-                    "message": "S3 backend is experiencing issues. Circuit breaker is open.",
-                    "bucket": bucket_config.name,
-                    "status": 503
-                })
-                .to_string();
+                    let mut header = ResponseHeader::build(e.status(), None)?;
+                    header
+                        .insert_header(ERROR_CODE_HEADER, ErrorCode::AuthSigV4Invalid.as_str())?;
+                    header.insert_header("Content-Type", "application/xml")?;
 
-                header.insert_header("Content-Length", error_body.len().to_string())?;
+                    let error_body = e.to_xml(ctx.request_id());
+                    header.insert_header("Content-Length", error_body.len().to_string())?;
 
-                session
-                    .write_response_header(Box::new(header), false)
-                    .await?;
-                session
-                    .write_response_body(Some(error_body.into()), true)
-                    .await?;
+                    session
+                        .write_response_header(Box::new(header), false)
+                        .await?;
+                    session
+                        .write_response_body(Some(error_body.into()), true)
+                        .await?;
 
-                self.metrics.increment_status_count(503);
+                    self.metrics.increment_auth_failure();
+                    self.metrics.increment_status_count(e.status());
 
-                return Ok(true); // Request handled (circuit breaker rejected)
+                    return Ok(true);
+                }
             }
-
-            // If we're in half-open state, increment request counter
-            circuit_breaker.start_half_open_request();
         }
 
         // Check if authentication is required
-        if let Some(auth_config) = &bucket_config.auth {
+        if let Some(auth_config) = bucket_config.auth.as_ref().filter(|_| ip_grant.is_none()) {
             if auth_config.enabled {
                 if let Some(jwt_config) = &config.jwt {
-                    // Authenticate request
+                    // Authenticate request (with the bucket's own key, if any)
+                    let jwt_config = auth_config.jwt_config(jwt_config);
                     let headers = ctx.headers();
                     let query_params = ctx.query_params();
 
-                    match authenticate_request(headers, query_params, jwt_config) {
+                    match authenticate(headers, query_params, &jwt_config).await {
                         Ok(claims) => {
                             if self.audit_writer.is_some() {
                                 ctx.audit().user = claims.sub.clone();
@@ -2291,6 +4720,10 @@ impl ProxyHttp for YatagarasuProxy {
                         Err(AuthError::MissingToken) => {
                             // Return 401 Unauthorized
                             let mut header = ResponseHeader::build(401, None)?;
+                            header.insert_header(
+                                ERROR_CODE_HEADER,
+                                ErrorCode::AuthMissingToken.as_str(),
+                            )?;
                             header.insert_header("Content-Type", "text/plain")?;
                             header.insert_header("WWW-Authenticate", "Bearer")?;
                             header.insert_header("Content-Length", "0")?;
@@ -2301,13 +4734,25 @@ impl ProxyHttp for YatagarasuProxy {
                             // Record authentication failure
                             self.metrics.increment_auth_failure();
                             self.metrics.increment_auth_error("missing");
+                            self.record_auth_failure_reason(
+                                &AuthError::MissingToken,
+                                &bucket_config.name,
+                                &client_ip,
+                                headers,
+                                query_params,
+                                &jwt_config,
+                            );
                             self.metrics.increment_status_count(401);
 
                             return Ok(true); // Short-circuit
                         }
-                        Err(_) => {
+                        Err(e) => {
                             // Return 403 Forbidden (invalid token or claims)
                             let mut header = ResponseHeader::build(403, None)?;
+                            header.insert_header(
+                                ERROR_CODE_HEADER,
+                                ErrorCode::AuthInvalidToken.as_str(),
+                            )?;
                             header.insert_header("Content-Type", "text/plain")?;
                             header.insert_header("Content-Length", "0")?;
                             session
@@ -2317,6 +4762,14 @@ impl ProxyHttp for YatagarasuProxy {
                             // Record authentication failure
                             self.metrics.increment_auth_failure();
                             self.metrics.increment_auth_error("invalid");
+                            self.record_auth_failure_reason(
+                                &e,
+                                &bucket_config.name,
+                                &client_ip,
+                                headers,
+                                query_params,
+                                &jwt_config,
+                            );
                             self.metrics.increment_status_count(403);
 
                             return Ok(true); // Short-circuit
@@ -2324,13 +4777,37 @@ impl ProxyHttp for YatagarasuProxy {
                     }
                 }
             }
-        } else {
+        } else if !bucket_config.sigv4_auth.as_ref().is_some_and(|s| s.enabled) {
             // Authentication bypassed (public bucket - no auth config)
             self.metrics.increment_auth_bypassed();
         }
 
+        let grant = ip_grant.map(|g| (g, GrantMatch::Ip)).or_else(|| {
+            let access_grants = self.access_grants.as_ref().filter(|_| is_read)?;
+            let subject = ctx.claims()?.sub.as_deref()?;
+            access_grants
+                .find_for_subject(&bucket_config.name, &grant_key, subject)
+                .map(|g| (g, GrantMatch::Subject))
+        });
+        if let Some((grant, matched)) = &grant {
+            bucket_log!(ctx, INFO,
+                request_id = %ctx.request_id(),
+                bucket = %bucket_config.name,
+                object = %grant_key,
+                grant_id = %grant.id,
+                matched = matched.as_str(),
+                "Request allowed by temporary access grant"
+            );
+            self.metrics
+                .increment_access_grant_use(&bucket_config.name, matched.as_str());
+        }
+
         // Phase 32: OPA Authorization check (after JWT authentication)
-        if let Some(opa_client) = self.opa_clients.get(&bucket_config.name) {
+        if let Some(opa_client) = self
+            .opa_clients
+            .get(&bucket_config.name)
+            .filter(|_| grant.is_none())
+        {
             // Get authorization config for fail mode
             let fail_mode = bucket_config
                 .authorization
@@ -2350,7 +4827,8 @@ impl ProxyHttp for YatagarasuProxy {
                 ctx.path().to_string(),
                 ctx.method().to_string(),
                 ctx.headers().get("x-forwarded-for").cloned(),
-            );
+            )
+            .with_client_cert_subject(ctx.client_cert_subject().map(str::to_string));
 
             // Check cache first
             let cache_key = opa_input.cache_key();
@@ -2362,7 +4840,7 @@ impl ProxyHttp for YatagarasuProxy {
 
             let decision = if let Some(allowed) = cached_decision {
                 // Cache hit
-                tracing::debug!(
+                bucket_log!(ctx, DEBUG,
                     request_id = %ctx.request_id(),
                     bucket = %bucket_config.name,
                     allowed = %allowed,
@@ -2380,7 +4858,7 @@ impl ProxyHttp for YatagarasuProxy {
                     opa_cache.put(cache_key, allowed).await;
                 }
 
-                tracing::debug!(
+                bucket_log!(ctx, DEBUG,
                     request_id = %ctx.request_id(),
                     bucket = %bucket_config.name,
                     allowed = %decision.is_allowed(),
@@ -2394,7 +4872,7 @@ impl ProxyHttp for YatagarasuProxy {
             // Log warning for fail-open decisions
             if decision.is_fail_open_allow() {
                 if let Some(error) = decision.error() {
-                    tracing::warn!(
+                    bucket_log!(ctx, WARN,
                         request_id = %ctx.request_id(),
                         bucket = %bucket_config.name,
                         error = %error,
@@ -2406,13 +4884,14 @@ impl ProxyHttp for YatagarasuProxy {
             // Deny if not allowed
             if !decision.is_allowed() {
                 let mut header = ResponseHeader::build(403, None)?;
+                header.insert_header(ERROR_CODE_HEADER, ErrorCode::AuthForbidden.as_str())?;
                 header.insert_header("Content-Type", "text/plain")?;
                 header.insert_header("Content-Length", "0")?;
                 session
                     .write_response_header(Box::new(header), true)
                     .await?;
 
-                tracing::warn!(
+                bucket_log!(ctx, WARN,
                     request_id = %ctx.request_id(),
                     bucket = %bucket_config.name,
                     "OPA authorization denied"
@@ -2424,7 +4903,11 @@ impl ProxyHttp for YatagarasuProxy {
         }
 
         // Phase 49: OpenFGA Authorization check (after JWT authentication and OPA)
-        if let Some(openfga_client) = self.openfga_clients.get(&bucket_config.name) {
+        if let Some(openfga_client) = self
+            .openfga_clients
+            .get(&bucket_config.name)
+            .filter(|_| grant.is_none())
+        {
             // Get authorization config for fail mode
             let fail_mode = bucket_config
                 .authorization
@@ -2463,7 +4946,7 @@ impl ProxyHttp for YatagarasuProxy {
                 let decision =
                     OpenFgaAuthorizationDecision::from_check_result(check_result, fail_mode);
 
-                tracing::debug!(
+                bucket_log!(ctx, DEBUG,
                     request_id = %ctx.request_id(),
                     bucket = %bucket_config.name,
                     user = %user,
@@ -2477,7 +4960,7 @@ impl ProxyHttp for YatagarasuProxy {
                 // Log warning for fail-open decisions
                 if decision.is_fail_open_allow() {
                     if let Some(error) = decision.error() {
-                        tracing::warn!(
+                        bucket_log!(ctx, WARN,
                             request_id = %ctx.request_id(),
                             bucket = %bucket_config.name,
                             user = %user,
@@ -2490,13 +4973,14 @@ impl ProxyHttp for YatagarasuProxy {
                 // Deny if not allowed
                 if !decision.is_allowed() {
                     let mut header = ResponseHeader::build(403, None)?;
+                    header.insert_header(ERROR_CODE_HEADER, ErrorCode::AuthForbidden.as_str())?;
                     header.insert_header("Content-Type", "text/plain")?;
                     header.insert_header("Content-Length", "0")?;
                     session
                         .write_response_header(Box::new(header), true)
                         .await?;
 
-                    tracing::warn!(
+                    bucket_log!(ctx, WARN,
                         request_id = %ctx.request_id(),
                         bucket = %bucket_config.name,
                         user = %user,
@@ -2510,7 +4994,7 @@ impl ProxyHttp for YatagarasuProxy {
                 }
             } else {
                 // No user ID found in claims - deny or fail-open based on config
-                tracing::warn!(
+                bucket_log!(ctx, WARN,
                     request_id = %ctx.request_id(),
                     bucket = %bucket_config.name,
                     "OpenFGA authorization failed: no user ID in JWT claims"
@@ -2519,6 +5003,7 @@ impl ProxyHttp for YatagarasuProxy {
                 // Default to deny if no user ID (security-first approach)
                 if fail_mode == OpenFgaFailMode::Closed {
                     let mut header = ResponseHeader::build(403, None)?;
+                    header.insert_header(ERROR_CODE_HEADER, ErrorCode::AuthForbidden.as_str())?;
                     header.insert_header("Content-Type", "text/plain")?;
                     header.insert_header("Content-Length", "0")?;
                     session
@@ -2532,23 +5017,271 @@ impl ProxyHttp for YatagarasuProxy {
             }
         }
 
+        // Built-in claim-to-path rules (authorization type "claims")
+        if let Some(authz) = bucket_config
+            .authorization
+            .as_ref()
+            .filter(|a| a.auth_type == "claims" && grant.is_none())
+        {
+            let jwt_claims = ctx
+                .claims()
+                .map(|c| serde_json::to_value(c).unwrap_or_default())
+                .unwrap_or(serde_json::json!({}));
+            let object_key = router.extract_s3_key(ctx.path()).unwrap_or_default();
+
+            if !path_rules::is_allowed(&authz.rules, &jwt_claims, ctx.method(), &object_key) {
+                let mut header = ResponseHeader::build(403, None)?;
+                header.insert_header(ERROR_CODE_HEADER, ErrorCode::AuthForbidden.as_str())?;
+                header.insert_header("Content-Type", "text/plain")?;
+                header.insert_header("Content-Length", "0")?;
+                session
+                    .write_response_header(Box::new(header), true)
+                    .await?;
+
+                bucket_log!(ctx, WARN,
+                    request_id = %ctx.request_id(),
+                    bucket = %bucket_config.name,
+                    user = ?ctx.claims().and_then(|c| c.sub.as_deref()),
+                    object = %object_key,
+                    method = %ctx.method(),
+                    "Claim path rules denied request"
+                );
+
+                self.metrics.increment_status_count(403);
+                return Ok(true); // Short-circuit
+            }
+        }
+        ctx.record_phase(Phase::Authz, authz_started.elapsed());
+
+        // Bucket listing: a key-less GET/HEAD is ListObjectsV2, if the bucket allows it
+        if (ctx.method() == "GET" || ctx.method() == "HEAD")
+            && router
+                .extract_s3_key(ctx.path())
+                .unwrap_or_default()
+                .is_empty()
+        {
+            match bucket_config.listing.as_ref().filter(|l| l.enabled) {
+                Some(listing) => {
+                    let accept = ctx.headers().get("accept").map(String::as_str);
+                    ctx.set_listing(listing.format(accept));
+                }
+                None => {
+                    let mut header = ResponseHeader::build(403, None)?;
+                    header.insert_header(ERROR_CODE_HEADER, ErrorCode::FeatureDisabled.as_str())?;
+                    header.insert_header("Content-Type", "application/json")?;
+
+                    let error_body = serde_json::json!({
+                        "error": "Forbidden",
+                        "code": ErrorCode::FeatureDisabled.as_str(),
+                        "message": "Listing is not enabled for this bucket",
+                        "status": 403
+                    })
+                    .to_string();
+
+                    header.insert_header("Content-Length", error_body.len().to_string())?;
+                    session
+                        .write_response_header(Box::new(header), false)
+                        .await?;
+                    session
+                        .write_response_body(Some(error_body.into()), true)
+                        .await?;
+
+                    self.metrics.increment_status_count(403);
+                    return Ok(true); // Short-circuit
+                }
+            }
+        }
+
+        // Early hints: preload links for HTML entry points, optionally sent
+        // ahead of the response as 103 (HTTP/1.0 clients can't take a 1xx)
+        if ctx.method() == "GET" {
+            let object_key = router.extract_s3_key(ctx.path()).unwrap_or_default();
+            if let Some(early_hints) = &bucket_config.early_hints {
+                if let Some(links) = early_hints.links(&object_key) {
+                    if early_hints.send_103
+                        && session.req_header().version >= http::Version::HTTP_11
+                    {
+                        let mut hints = ResponseHeader::build(103, None)?;
+                        for link in &links {
+                            hints.append_header("Link", link.as_str())?;
+                        }
+                        if let Err(e) = session.write_response_header(Box::new(hints), false).await
+                        {
+                            bucket_log!(ctx, DEBUG,
+                                request_id = %ctx.request_id(),
+                                error = %e,
+                                "Failed to send 103 Early Hints"
+                            );
+                        }
+                    }
+                    ctx.set_preload_links(links.join(", "));
+                }
+            }
+        }
+
+        // Download resumption: a replayed token must belong to this object
+        if let Some(token_value) = ctx.headers().get(resume::RESUME_TOKEN_HEADER) {
+            let resume_enabled = bucket_config
+                .resume
+                .as_ref()
+                .map(|r| r.enabled)
+                .unwrap_or(false);
+            let object_key = router.extract_s3_key(ctx.path()).unwrap_or_default();
+            let token_valid = resume::ResumeToken::decode(token_value)
+                .map(|token| token.matches(&bucket_config.name, &object_key))
+                .unwrap_or(false);
+
+            if resume_enabled && !token_valid {
+                bucket_log!(ctx, WARN,
+                    request_id = %ctx.request_id(),
+                    bucket = %bucket_config.name,
+                    "Rejected invalid resume token"
+                );
+
+                let mut header = ResponseHeader::build(400, None)?;
+                header.insert_header(ERROR_CODE_HEADER, ErrorCode::InvalidResumeToken.as_str())?;
+                header.insert_header("Content-Type", "application/json")?;
+
+                let error_body = serde_json::json!({
+                    "error": "Bad Request",
+                    "code": ErrorCode::InvalidResumeToken.as_str(),
+                    "message": "Resume token does not match the requested object",
+                    "status": 400
+                })
+                .to_string();
+
+                header.insert_header("Content-Length", error_body.len().to_string())?;
+                session
+                    .write_response_header(Box::new(header), false)
+                    .await?;
+                session
+                    .write_response_body(Some(error_body.into()), true)
+                    .await?;
+
+                self.metrics.increment_status_count(400);
+                return Ok(true); // Short-circuit
+            }
+        }
+
+        // Browser form uploads authorized by the bucket's POST policy
+        if ctx.method() == "POST"
+            && MultipartOperation::classify(ctx.method(), session.req_header().uri.query())
+                .is_none()
+        {
+            if let Some(policy_config) = bucket_config.post_policy.as_ref().filter(|p| p.enabled) {
+                return self
+                    .handle_post_policy_upload(session, ctx, &bucket_config, policy_config)
+                    .await;
+            }
+        }
+
+        // Buckets in redirect mode send authorized downloads to S3 directly
+        if matches!(ctx.method(), "GET" | "HEAD") && ctx.listing().is_none() {
+            if let Some(redirect) = bucket_config
+                .presigned_redirect
+                .as_ref()
+                .filter(|r| r.enabled)
+            {
+                return self
+                    .handle_presigned_redirect(session, ctx, &bucket_config, redirect)
+                    .await;
+            }
+        }
+
+        // Trusted clients may change cache behavior for this request (X-Yat-Cache)
+        let override_config = &config.server.cache_override;
+        if override_config.enabled {
+            if let Some(mode) = ctx
+                .headers()
+                .get(CACHE_OVERRIDE_HEADER)
+                .and_then(|value| CacheOverrideMode::parse(value))
+            {
+                let token = ctx
+                    .headers()
+                    .get(CACHE_OVERRIDE_TOKEN_HEADER)
+                    .map(String::as_str);
+                let trusted = override_config.token_matches(token)
+                    || (override_config.admin_claims
+                        && ctx.claims().is_some_and(|claims| {
+                            config.jwt.as_ref().is_some_and(|jwt| {
+                                crate::auth::verify_admin_claims(claims, &jwt.admin_claims)
+                            })
+                        }));
+                if trusted {
+                    bucket_log!(ctx, INFO,
+                        request_id = %ctx.request_id(),
+                        mode = mode.as_str(),
+                        "Applying cache override from trusted client"
+                    );
+                    self.metrics.increment_cache_override(mode.as_str());
+                    ctx.set_cache_override(mode);
+                } else {
+                    bucket_log!(ctx, DEBUG,
+                        request_id = %ctx.request_id(),
+                        "Ignoring cache override from untrusted client"
+                    );
+                    self.metrics.increment_cache_override("untrusted");
+                }
+            }
+        }
+
         // FOURTH: Check cache (Phase 30.7: Cache Integration)
         if let Some(ref cache) = self.cache {
+            let cache_lookup_started = Instant::now();
             // Check cache for GET and HEAD requests
             // GET: Return full response (headers + body)
             // HEAD: Return headers only (no body) - useful for metadata checks
             // Check method and extract HEAD flag before any mutable ctx borrows
-            let is_get_or_head = ctx.method() == "GET" || ctx.method() == "HEAD";
+            // Listings change with every write and are never cached
+            let is_get_or_head =
+                (ctx.method() == "GET" || ctx.method() == "HEAD") && ctx.listing().is_none();
             let is_head_request = ctx.method() == "HEAD";
             if is_get_or_head {
-                // Cache Bypass Logic: Range requests always bypass cache
-                // Range requests are for partial content (video seeking, parallel downloads)
-                // and we don't cache partial responses
-                let is_range_request =
-                    ctx.headers().contains_key("range") || ctx.headers().contains_key("Range");
-
-                if is_range_request {
-                    tracing::debug!(
+                // The bucket's cache block may exclude this key entirely
+                let policy_allows = ctx
+                    .bucket_config()
+                    .map_or(true, |b| cache_policy::allows_key(b, ctx.s3_key()));
+                // Cache Bypass Logic: Range requests bypass the cache, except
+                // single-range GETs when range chunks are cached
+                let is_range_request = ctx.headers().contains_key("range");
+                let chunk_range = if cache.range_chunks().is_some()
+                    && policy_allows
+                    && !is_head_request
+                    && ctx.cache_variant().is_none()
+                    && !cache_handler::bypasses_range_chunks(ctx.headers())
+                    && !ctx.cache_override().is_some_and(|mode| mode.skips_lookup())
+                {
+                    ctx.headers()
+                        .get("range")
+                        .and_then(|value| parse_range_header(value))
+                        .filter(|header| header.unit == "bytes" && header.ranges.len() == 1)
+                        .and_then(|mut header| header.ranges.pop())
+                } else {
+                    None
+                };
+
+                if let Some(range) = chunk_range {
+                    if let Some(bucket_config) = ctx.bucket_config().cloned() {
+                        if self
+                            .serve_cached_range(session, ctx, cache, &bucket_config, &range)
+                            .await?
+                        {
+                            return Ok(true);
+                        }
+                    }
+                    // Miss - fall through to S3, which fills the chunks
+                } else if !policy_allows {
+                    bucket_log!(ctx, DEBUG,
+                        request_id = %ctx.request_id(),
+                        key = %ctx.s3_key(),
+                        "Bucket cache policy excludes object - skipping cache lookup"
+                    );
+                    if self.audit_writer.is_some() {
+                        ctx.audit()
+                            .set_cache_status(crate::audit::CacheStatus::Bypass);
+                    }
+                } else if is_range_request {
+                    bucket_log!(ctx, DEBUG,
                         request_id = %ctx.request_id(),
                         "Range request detected - bypassing cache"
                     );
@@ -2557,6 +5290,15 @@ impl ProxyHttp for YatagarasuProxy {
                             .set_cache_status(crate::audit::CacheStatus::Bypass);
                     }
                     // Skip cache lookup - fall through to Ok(false) at the end
+                } else if ctx.cache_override().is_some_and(|mode| mode.skips_lookup()) {
+                    bucket_log!(ctx, DEBUG,
+                        request_id = %ctx.request_id(),
+                        "Cache override requested - skipping cache lookup"
+                    );
+                    if self.audit_writer.is_some() {
+                        ctx.audit()
+                            .set_cache_status(crate::audit::CacheStatus::Bypass);
+                    }
                 } else {
                     let bucket_config = ctx.bucket_config().cloned().ok_or_else(|| {
                         pingora_core::Error::explain(
@@ -2564,13 +5306,14 @@ impl ProxyHttp for YatagarasuProxy {
                             "Missing bucket config in context",
                         )
                     })?;
+                    let request_origin = ctx.headers().get("origin").cloned();
 
                     // Construct cache key from bucket and object path
                     // Use router.extract_s3_key for consistent key generation (same as cache set)
                     let object_key = router.extract_s3_key(ctx.path()).unwrap_or_default();
 
                     // Generate variant string if image optimization is requested
-                    let variant = ctx.image_params().map(|p| p.to_cache_key());
+                    let variant = ctx.cache_variant();
 
                     let cache_key = CacheKey {
                         bucket: bucket_config.name.clone(),
@@ -2579,27 +5322,255 @@ impl ProxyHttp for YatagarasuProxy {
                         variant,
                     };
 
+                    // Cache policy experiment: the caller's variant decides what counts as a hit
+                    let experiment = bucket_config
+                        .cache
+                        .as_ref()
+                        .and_then(|c| c.experiment.as_ref());
+                    let experiment_variant =
+                        experiment.map(|e| cache_experiment::assign(e, ctx.headers(), &client_ip));
+
+                    // Only plain objects can be re-fetched for startup warming
+                    if let Some(ref journal) = self.hot_key_journal {
+                        if cache_key.variant.is_none() && !is_head_request {
+                            journal.record(&bucket_config.name, &object_key);
+                        }
+                    }
+
                     // Extract conditional headers before mutable borrow of ctx for audit
-                    let if_none_match = ctx
-                        .headers()
-                        .get("If-None-Match")
-                        .or_else(|| ctx.headers().get("if-none-match"))
-                        .cloned();
-
-                    let if_modified_since = ctx
-                        .headers()
-                        .get("If-Modified-Since")
-                        .or_else(|| ctx.headers().get("if-modified-since"))
-                        .cloned();
+                    // (header names are stored lowercase, so a single lookup suffices)
+                    let if_none_match = ctx.headers().get("if-none-match").cloned();
+                    let if_modified_since = ctx.headers().get("if-modified-since").cloned();
+
+                    // Large disk cache hits are streamed straight from the cached file
+                    // instead of being read into memory. Conditional requests take the
+                    // regular path so 304 handling stays in one place, as do buckets
+                    // whose hits may need a freshness HEAD first or an experiment's age check.
+                    let soft_ttl = bucket_config
+                        .cache
+                        .as_ref()
+                        .and_then(|c| c.soft_ttl_seconds);
+                    if !is_head_request
+                        && if_none_match.is_none()
+                        && if_modified_since.is_none()
+                        && soft_ttl.is_none()
+                        && experiment_variant.is_none()
+                    {
+                        if let Ok(Some(sendfile_response)) = cache.get_sendfile(&cache_key).await {
+                            if zero_copy::should_stream_in_chunks(sendfile_response.length as usize)
+                            {
+                                match FileChunkStream::open(
+                                    &sendfile_response,
+                                    FILE_STREAM_CHUNK_SIZE,
+                                )
+                                .await
+                                {
+                                    Ok(mut stream) => {
+                                        if self.audit_writer.is_some() {
+                                            ctx.audit()
+                                                .set_cache_status(crate::audit::CacheStatus::Hit);
+                                        }
+                                        // Disk streaming only returns unexpired entries
+                                        if ctx.is_origin_unavailable() {
+                                            self.metrics.increment_degraded_response(
+                                                &bucket_config.name,
+                                                "fresh",
+                                            );
+                                        }
+                                        bucket_log!(ctx, DEBUG,
+                                            request_id = %ctx.request_id(),
+                                            bucket = %bucket_config.name,
+                                            object_key = %object_key,
+                                            file_path = %sendfile_response.file_path.display(),
+                                            length = stream.remaining(),
+                                            "Cache hit - streaming from disk cache file"
+                                        );
+
+                                        // Entries cached before the bucket's rules changed
+                                        let content_type = content_type::for_bucket(
+                                            &bucket_config,
+                                            &object_key,
+                                            Some(&sendfile_response.content_type),
+                                            None,
+                                        )
+                                        .unwrap_or_else(|| sendfile_response.content_type.clone());
+
+                                        let mut header = ResponseHeader::build(200, None)?;
+                                        header.insert_header("Content-Type", content_type)?;
+                                        if let Some(ref etag) = sendfile_response.etag {
+                                            header.insert_header("ETag", etag.as_str())?;
+                                        }
+                                        if let Some(ref last_modified) =
+                                            sendfile_response.last_modified
+                                        {
+                                            header.insert_header(
+                                                "Last-Modified",
+                                                last_modified.as_str(),
+                                            )?;
+                                        }
+                                        header.insert_header(
+                                            "Content-Length",
+                                            stream.remaining().to_string(),
+                                        )?;
+                                        preserved_headers::apply(
+                                            &mut header,
+                                            &sendfile_response.headers,
+                                        )?;
+                                        header.insert_header("X-Cache", "HIT")?;
+                                        if let Some(links) = ctx.preload_links() {
+                                            header.insert_header("Link", links)?;
+                                        }
+                                        ctx.record_phase(
+                                            Phase::Cache,
+                                            cache_lookup_started.elapsed(),
+                                        );
+                                        server_timing::apply(&mut header, &bucket_config, ctx)?;
+                                        deprecation::apply(&mut header, &bucket_config)?;
+                                        cors::apply(
+                                            &mut header,
+                                            cors::response_headers(
+                                                &bucket_config,
+                                                request_origin.as_deref(),
+                                            ),
+                                        )?;
+                                        session
+                                            .write_response_header(Box::new(header), false)
+                                            .await?;
+
+                                        let bytes_to_send = stream.remaining();
+                                        while let Some(chunk) =
+                                            stream.next_chunk().await.map_err(|e| {
+                                                pingora_core::Error::because(
+                                                    pingora_core::ErrorType::ReadError,
+                                                    "Failed to read disk cache file",
+                                                    e,
+                                                )
+                                            })?
+                                        {
+                                            let end_of_stream = stream.remaining() == 0;
+                                            session
+                                                .write_response_body(Some(chunk), end_of_stream)
+                                                .await?;
+                                        }
+
+                                        self.metrics.increment_cache_sendfile();
+                                        self.metrics.add_cache_sendfile_bytes(bytes_to_send);
+                                        self.metrics.increment_status_count(200);
+                                        self.metrics.increment_cache_hit();
+                                        return Ok(true);
+                                    }
+                                    Err(e) => {
+                                        // Entry vanished between index lookup and open
+                                        // (eviction) - fall back to the regular lookup
+                                        bucket_log!(ctx, DEBUG,
+                                            request_id = %ctx.request_id(),
+                                            error = %e,
+                                            "Failed to open disk cache file, using regular cache path"
+                                        );
+                                    }
+                                }
+                            }
+                        }
+                    }
 
                     // Try to get from cache (with duration tracking)
                     let cache_start = std::time::Instant::now();
-                    let cache_result = cache.get(&cache_key).await;
+                    // Compressed entries go out as-is to clients that accept zstd
+                    let zstd_accepted = accepts_zstd(
+                        session
+                            .req_header()
+                            .headers
+                            .get("accept-encoding")
+                            .and_then(|v| v.to_str().ok()),
+                    );
+                    let cache_result =
+                        cache
+                            .get_encoded(&cache_key)
+                            .await
+                            .and_then(|entry| match entry {
+                                Some(entry)
+                                    if entry.content_encoding.is_some()
+                                        && !(zstd_accepted
+                                            && entry.content_encoding.as_deref()
+                                                == Some(ZSTD_ENCODING)) =>
+                                {
+                                    decode_entry(entry).map(Some)
+                                }
+                                other => Ok(other),
+                            });
                     let cache_duration = cache_start.elapsed().as_secs_f64() * 1000.0; // Convert to ms
                     self.metrics.record_cache_get_duration(cache_duration);
 
+                    // HEAD-before-GET: hits past the soft TTL are confirmed with S3
+                    // (skipped while origins are down so degraded mode can serve)
+                    let cache_result = match cache_result {
+                        Ok(Some(entry)) if soft_ttl.is_some() && !ctx.is_origin_unavailable() => {
+                            if self
+                                .confirm_cache_freshness(&bucket_config, &cache_key, &entry)
+                                .await
+                            {
+                                Ok(Some(entry))
+                            } else {
+                                Ok(None)
+                            }
+                        }
+                        other => other,
+                    };
+
+                    // Entries older than the variant's TTL are misses for that variant
+                    let cache_result = match (cache_result, experiment, experiment_variant) {
+                        (Ok(Some(entry)), Some(experiment), Some(variant)) => {
+                            let fresh = cache_experiment::is_fresh(variant, entry.created_at);
+                            self.metrics.increment_cache_experiment(
+                                &bucket_config.name,
+                                &experiment.name,
+                                &variant.name,
+                                if fresh { "hit" } else { "miss" },
+                            );
+                            Ok(fresh.then_some(entry))
+                        }
+                        (other, Some(experiment), Some(variant)) => {
+                            self.metrics.increment_cache_experiment(
+                                &bucket_config.name,
+                                &experiment.name,
+                                &variant.name,
+                                "miss",
+                            );
+                            other
+                        }
+                        (other, _, _) => other,
+                    };
+                    ctx.record_phase(Phase::Cache, cache_lookup_started.elapsed());
+
                     match cache_result {
                         Ok(Some(cached_entry)) => {
+                            // Degraded mode: expired entries carry a Warning, or are refused
+                            let stale_warning = if ctx.is_origin_unavailable() {
+                                let stale_for = std::time::SystemTime::now()
+                                    .duration_since(cached_entry.expires_at)
+                                    .ok();
+                                if !bucket_config
+                                    .degraded
+                                    .as_ref()
+                                    .is_some_and(|d| d.serves(stale_for))
+                                {
+                                    self.metrics
+                                        .increment_degraded_response(&bucket_config.name, "miss");
+                                    return self
+                                        .respond_origin_unavailable(session, &bucket_config.name)
+                                        .await;
+                                }
+                                let outcome = if stale_for.is_some() {
+                                    "stale"
+                                } else {
+                                    "fresh"
+                                };
+                                self.metrics
+                                    .increment_degraded_response(&bucket_config.name, outcome);
+                                stale_for.is_some()
+                            } else {
+                                false
+                            };
                             if self.audit_writer.is_some() {
                                 ctx.audit().set_cache_status(crate::audit::CacheStatus::Hit);
                             }
@@ -2608,8 +5579,11 @@ impl ProxyHttp for YatagarasuProxy {
                             // Check if client sent If-None-Match header for conditional requests
                             if let Some(ref client_etag) = if_none_match {
                                 // If ETags match, return 304 Not Modified
-                                if client_etag == cached_entry.etag.as_str() {
-                                    tracing::debug!(
+                                if crate::cache::etag::if_none_match_matches(
+                                    client_etag,
+                                    &cached_entry.etag,
+                                ) {
+                                    bucket_log!(ctx, DEBUG,
                                         request_id = %ctx.request_id(),
                                         bucket = %bucket_config.name,
                                         object_key = %object_key,
@@ -2619,7 +5593,20 @@ impl ProxyHttp for YatagarasuProxy {
 
                                     let mut header = ResponseHeader::build(304, None)?;
                                     header.insert_header("ETag", cached_entry.etag.as_str())?;
+                                    preserved_headers::apply(&mut header, &cached_entry.headers)?;
                                     header.insert_header("X-Cache", "HIT")?;
+                                    if stale_warning {
+                                        header.insert_header("Warning", helpers::STALE_WARNING)?;
+                                    }
+                                    server_timing::apply(&mut header, &bucket_config, ctx)?;
+                                    deprecation::apply(&mut header, &bucket_config)?;
+                                    cors::apply(
+                                        &mut header,
+                                        cors::response_headers(
+                                            &bucket_config,
+                                            request_origin.as_deref(),
+                                        ),
+                                    )?;
 
                                     session
                                         .write_response_header(Box::new(header), true)
@@ -2635,7 +5622,7 @@ impl ProxyHttp for YatagarasuProxy {
                                 // If Last-Modified matches, return 304 Not Modified
                                 if let Some(ref last_modified) = cached_entry.last_modified {
                                     if client_modified_since == last_modified.as_str() {
-                                        tracing::debug!(
+                                        bucket_log!(ctx, DEBUG,
                                             request_id = %ctx.request_id(),
                                             bucket = %bucket_config.name,
                                             object_key = %object_key,
@@ -2654,7 +5641,24 @@ impl ProxyHttp for YatagarasuProxy {
                                                 cached_entry.etag.as_str(),
                                             )?;
                                         }
+                                        preserved_headers::apply(
+                                            &mut header,
+                                            &cached_entry.headers,
+                                        )?;
                                         header.insert_header("X-Cache", "HIT")?;
+                                        if stale_warning {
+                                            header
+                                                .insert_header("Warning", helpers::STALE_WARNING)?;
+                                        }
+                                        server_timing::apply(&mut header, &bucket_config, ctx)?;
+                                        deprecation::apply(&mut header, &bucket_config)?;
+                                        cors::apply(
+                                            &mut header,
+                                            cors::response_headers(
+                                                &bucket_config,
+                                                request_origin.as_deref(),
+                                            ),
+                                        )?;
 
                                         session
                                             .write_response_header(Box::new(header), true)
@@ -2666,19 +5670,25 @@ impl ProxyHttp for YatagarasuProxy {
                                 }
                             }
 
-                            tracing::debug!(
+                            bucket_log!(ctx, DEBUG,
                                 request_id = %ctx.request_id(),
                                 bucket = %bucket_config.name,
                                 object_key = %object_key,
                                 "Cache hit - returning cached response"
                             );
 
+                            // Entries cached before the bucket's rules changed
+                            let content_type = content_type::for_bucket(
+                                &bucket_config,
+                                &object_key,
+                                Some(&cached_entry.content_type),
+                                Some(&cached_entry.data),
+                            )
+                            .unwrap_or_else(|| cached_entry.content_type.clone());
+
                             // Build response from cached entry
                             let mut header = ResponseHeader::build(200, None)?;
-                            header.insert_header(
-                                "Content-Type",
-                                cached_entry.content_type.as_str(),
-                            )?;
+                            header.insert_header("Content-Type", content_type)?;
                             header.insert_header("ETag", cached_entry.etag.as_str())?;
                             // Add Last-Modified header if available
                             if let Some(ref last_modified) = cached_entry.last_modified {
@@ -2688,7 +5698,24 @@ impl ProxyHttp for YatagarasuProxy {
                                 "Content-Length",
                                 cached_entry.data.len().to_string(),
                             )?;
+                            if let Some(ref encoding) = cached_entry.content_encoding {
+                                header.insert_header("Content-Encoding", encoding.as_str())?;
+                                header.insert_header("Vary", "Accept-Encoding")?;
+                            }
+                            preserved_headers::apply(&mut header, &cached_entry.headers)?;
                             header.insert_header("X-Cache", "HIT")?; // Indicate cache hit
+                            if let Some(links) = ctx.preload_links() {
+                                header.insert_header("Link", links)?;
+                            }
+                            if stale_warning {
+                                header.insert_header("Warning", helpers::STALE_WARNING)?;
+                            }
+                            server_timing::apply(&mut header, &bucket_config, ctx)?;
+                            deprecation::apply(&mut header, &bucket_config)?;
+                            cors::apply(
+                                &mut header,
+                                cors::response_headers(&bucket_config, request_origin.as_deref()),
+                            )?;
 
                             // For HEAD requests: send only headers (no body)
                             // For GET requests: send headers + body
@@ -2702,7 +5729,8 @@ impl ProxyHttp for YatagarasuProxy {
                             if !is_head_request {
                                 // Phase v1.4: Check for sendfile capability
                                 // Track sendfile-eligible responses for observability
-                                // Actual sendfile usage requires Pingora socket fd integration
+                                // Large entries were already streamed from disk above; this covers
+                                // conditional requests and entries below the streaming threshold
                                 if let Ok(Some(sendfile_response)) =
                                     cache.get_sendfile(&cache_key).await
                                 {
@@ -2710,19 +5738,34 @@ impl ProxyHttp for YatagarasuProxy {
                                     self.metrics.increment_cache_sendfile();
                                     self.metrics
                                         .add_cache_sendfile_bytes(sendfile_response.length);
-                                    tracing::debug!(
+                                    bucket_log!(ctx, DEBUG,
                                         request_id = %ctx.request_id(),
                                         bucket = %bucket_config.name,
                                         object_key = %object_key,
                                         file_path = %sendfile_response.file_path.display(),
                                         length = sendfile_response.length,
-                                        "Sendfile-eligible cache hit (using regular path for now)"
+                                        "Sendfile-eligible cache hit served from cache entry buffer"
+                                    );
+                                }
+
+                                if zero_copy::should_stream_in_chunks(cached_entry.data.len()) {
+                                    // Stream large entries as zero-copy slices of the
+                                    // cached buffer (mapped file for disk hits)
+                                    let mut chunks = zero_copy::ZeroCopyChunks::new(
+                                        cached_entry.data,
+                                        zero_copy::ZERO_COPY_CHUNK_SIZE,
                                     );
+                                    while let Some(chunk) = chunks.next() {
+                                        let end_of_stream = chunks.remaining_chunks() == 0;
+                                        session
+                                            .write_response_body(Some(chunk), end_of_stream)
+                                            .await?;
+                                    }
+                                } else {
+                                    session
+                                        .write_response_body(Some(cached_entry.data), true)
+                                        .await?;
                                 }
-
-                                session
-                                    .write_response_body(Some(cached_entry.data), true)
-                                    .await?;
                             }
 
                             // Record metrics
@@ -2737,7 +5780,7 @@ impl ProxyHttp for YatagarasuProxy {
                                     .set_cache_status(crate::audit::CacheStatus::Miss);
                             }
                             // Cache miss - continue to upstream
-                            tracing::debug!(
+                            bucket_log!(ctx, DEBUG,
                                 request_id = %ctx.request_id(),
                                 bucket = %bucket_config.name,
                                 object_key = %object_key,
@@ -2752,7 +5795,7 @@ impl ProxyHttp for YatagarasuProxy {
                                     .set_cache_status(crate::audit::CacheStatus::Miss);
                             }
                             // Cache error - log but continue to upstream (don't fail request)
-                            tracing::warn!(
+                            bucket_log!(ctx, WARN,
                                 request_id = %ctx.request_id(),
                                 bucket = %bucket_config.name,
                                 object_key = %object_key,
@@ -2766,6 +5809,94 @@ impl ProxyHttp for YatagarasuProxy {
             }
         }
 
+        // only-if-cached: a cache hit would have answered above, so never ask the origin
+        if ctx.cache_override() == Some(CacheOverrideMode::OnlyIfCached)
+            && (ctx.method() == "GET" || ctx.method() == "HEAD")
+        {
+            let is_head = ctx.method() == "HEAD";
+            let error_body = serde_json::json!({
+                "error": "Gateway Timeout",
+                "code": ErrorCode::NotCached.as_str(),
+                "message": "Object is not cached and only-if-cached was requested",
+                "status": 504
+            })
+            .to_string();
+            let mut header = ResponseHeader::build(504, None)?;
+            header.insert_header(ERROR_CODE_HEADER, ErrorCode::NotCached.as_str())?;
+            header.insert_header("Content-Type", "application/json")?;
+            header.insert_header("Content-Length", error_body.len().to_string())?;
+            header.insert_header("X-Cache", "MISS")?;
+            session
+                .write_response_header(Box::new(header), is_head)
+                .await?;
+            if !is_head {
+                session
+                    .write_response_body(Some(error_body.into()), true)
+                    .await?;
+            }
+            self.metrics.increment_status_count(504);
+            return Ok(true);
+        }
+
+        // Degraded mode: nothing usable in cache and no origin to ask
+        if ctx.is_origin_unavailable() {
+            if let Some(bucket_config) = ctx.bucket_config() {
+                let bucket_name = bucket_config.name.clone();
+                bucket_log!(ctx, WARN,
+                    request_id = %ctx.request_id(),
+                    bucket = %bucket_name,
+                    "Origins down and no cached copy, rejecting request (degraded mode)"
+                );
+                self.metrics
+                    .increment_degraded_response(&bucket_name, "miss");
+                return self.respond_origin_unavailable(session, &bucket_name).await;
+            }
+        }
+
+        // In-process origins (filesystem) answer here instead of an upstream
+        let local_origin = ctx
+            .bucket_config()
+            .and_then(|bucket_config| self.origins.get(&bucket_config.name))
+            .filter(|origin| origin.upstream().is_none())
+            .cloned();
+        if let Some(origin) = local_origin {
+            return self.serve_local_object(session, ctx, &origin).await;
+        }
+
+        // Bulkhead: cap this bucket's in-flight upstream requests
+        if let Some(bucket_config) = ctx.bucket_config() {
+            if let Some(bulkhead) = self.bulkheads.get(&bucket_config.name) {
+                let bucket_name = bucket_config.name.clone();
+                let permit = if BUCKET_WRITE_METHODS.contains(&ctx.method()) {
+                    bulkhead.try_acquire_upload()
+                } else {
+                    bulkhead.try_acquire()
+                };
+                match permit {
+                    Some(permit) => ctx.set_bulkhead_permit(permit),
+                    None => {
+                        bucket_log!(ctx, WARN,
+                            request_id = %ctx.request_id(),
+                            bucket = %bucket_name,
+                            max_in_flight = bulkhead.max_in_flight(),
+                            "Rejecting request, bucket bulkhead is full"
+                        );
+                        self.metrics.increment_bulkhead_rejection(&bucket_name);
+
+                        self.respond_unavailable(
+                            session,
+                            UnavailableReason::BulkheadFull,
+                            Some(&bucket_name),
+                            None,
+                        )
+                        .await?;
+
+                        return Ok(true); // Request handled (bulkhead full)
+                    }
+                }
+            }
+        }
+
         // Streaming Coalescing
         // After cache miss, check if we should deduplicate this request
         if let Some(Coalescer::Streaming(ref coalescer)) = self.coalescer {
@@ -2778,14 +5909,14 @@ impl ProxyHttp for YatagarasuProxy {
                     bucket: bucket_name.clone(),
                     object_key,
                     etag: None,
-                    variant: ctx.image_params().map(|p| p.to_cache_key()),
+                    variant: ctx.cache_variant(),
                 };
 
                 match coalescer.acquire(&cache_key) {
                     StreamingSlot::Leader(leader) => {
                         // We are the leader - store the handle and proceed to upstream
                         ctx.set_streaming_leader(leader);
-                        tracing::debug!(
+                        bucket_log!(ctx, DEBUG,
                             request_id = %ctx.request_id(),
                             bucket = %bucket_name,
                             "Streaming coalescer: became leader, proceeding to S3"
@@ -2793,7 +5924,7 @@ impl ProxyHttp for YatagarasuProxy {
                     }
                     StreamingSlot::Follower(mut receiver) => {
                         // Another request is fetching - stream from them
-                        tracing::debug!(
+                        bucket_log!(ctx, DEBUG,
                             request_id = %ctx.request_id(),
                             bucket = %bucket_name,
                             "Streaming coalescer: became follower, streaming from leader"
@@ -2807,6 +5938,24 @@ impl ProxyHttp for YatagarasuProxy {
         Ok(false) // Continue to upstream
     }
 
+    /// Record upstream connection pool reuse for runtime metrics
+    async fn connected_to_upstream(
+        &self,
+        _session: &mut Session,
+        reused: bool,
+        peer: &HttpPeer,
+        #[cfg(unix)] _fd: std::os::unix::io::RawFd,
+        #[cfg(windows)] _sock: std::os::windows::io::RawSocket,
+        _digest: Option<&Digest>,
+        ctx: &mut Self::CTX,
+    ) -> Result<()> {
+        self.metrics.record_upstream_connection(reused);
+        if let (Some(pool), Some(addr)) = (self.endpoint_pool(ctx), peer._address.as_inet()) {
+            pool.record_success(*addr);
+        }
+        Ok(())
+    }
+
     /// Modify upstream request headers (add AWS Signature v4)
     async fn upstream_request_filter(
         &self,
@@ -2814,8 +5963,6 @@ impl ProxyHttp for YatagarasuProxy {
         upstream_request: &mut RequestHeader,
         ctx: &mut Self::CTX,
     ) -> Result<()> {
-        let router = self.router.load_full();
-
         let bucket_config = ctx.bucket_config().ok_or_else(|| {
             pingora_core::Error::explain(
                 pingora_core::ErrorType::InternalError,
@@ -2823,8 +5970,54 @@ impl ProxyHttp for YatagarasuProxy {
             )
         })?;
 
-        // Extract S3 key from path
-        let s3_key = router.extract_s3_key(ctx.path()).unwrap_or_default();
+        // S3 key resolved with the router the request was routed by
+        let s3_key = ctx.s3_key().to_string();
+
+        // Client validators may carry rewritten ETags that S3 doesn't know
+        if let Some(etag_rewrite) = &bucket_config.etag_rewrite {
+            for name in ["if-none-match", "if-match"] {
+                let Some(value) = upstream_request
+                    .headers
+                    .get(name)
+                    .and_then(|v| v.to_str().ok())
+                    .map(str::to_string)
+                else {
+                    continue;
+                };
+                match etag_rewrite.upstream_validator(&value, ctx.replica_name()) {
+                    Some(translated) => {
+                        upstream_request.insert_header(name, translated)?;
+                    }
+                    None => {
+                        upstream_request.remove_header(name);
+                    }
+                }
+            }
+        }
+
+        // Cache override headers are for the proxy only; never forward the token
+        upstream_request.remove_header(CACHE_OVERRIDE_HEADER);
+        upstream_request.remove_header(CACHE_OVERRIDE_TOKEN_HEADER);
+
+        // The client's SigV4 headers authenticated it to the proxy; S3 gets
+        // the request re-signed with the bucket's credentials
+        if bucket_config.sigv4_auth.as_ref().is_some_and(|s| s.enabled) {
+            for name in sigv4::CLIENT_SIGNING_HEADERS {
+                upstream_request.remove_header(*name);
+            }
+        }
+
+        // Non-S3 origins rewrite the request themselves (no S3 signing)
+        if let Some(origin) = self.origins.get(&bucket_config.name) {
+            return origin
+                .prepare_request(&s3_key, upstream_request)
+                .map_err(|e| {
+                    pingora_core::Error::explain(
+                        pingora_core::ErrorType::InternalError,
+                        format!("Failed to prepare origin request: {}", e),
+                    )
+                });
+        }
 
         // Phase 23: Use selected replica's config if available
         let (bucket, region, access_key, secret_key, endpoint): (
@@ -2876,6 +6069,48 @@ impl ProxyHttp for YatagarasuProxy {
             )
         };
 
+        // Static keys come from the versioned store rather than the replica
+        // clients built at startup, so a reload that rotates them applies from
+        // the next request on
+        let (access_key, secret_key) = match self
+            .credential_store
+            .get(&bucket_config.name, ctx.replica_name())
+        {
+            Some(stored) => {
+                bucket_log!(ctx, TRACE,
+                    bucket = %bucket_config.name,
+                    credential_version = stored.version,
+                    "Signing with stored upstream credentials"
+                );
+                (stored.access_key.clone(), stored.secret_key.clone())
+            }
+            None => (access_key, secret_key),
+        };
+
+        // Keys from the bucket's credential provider (metadata source or assumed
+        // role) replace the static ones
+        let (access_key, secret_key, session_token) =
+            match self.credential_providers.get(&bucket_config.name) {
+                Some(provider) => {
+                    let Some(credentials) = provider.current() else {
+                        return Err(pingora_core::Error::explain(
+                            pingora_core::ErrorType::InternalError,
+                            "Upstream S3 credentials are not available yet",
+                        ));
+                    };
+                    (
+                        credentials.access_key_id,
+                        credentials.secret_access_key,
+                        credentials.session_token,
+                    )
+                }
+                None => (access_key, secret_key, None),
+            };
+
+        // Signing quirks (path-style forcing, V2, unsigned payload) for this bucket
+        let signing = bucket_config.s3.signing.as_ref();
+        let path_style = endpoint.is_some() || signing.is_some_and(|s| s.force_path_style);
+
         // Determine the correct host for this endpoint (without port for signature)
         let host_for_signing = if let Some(custom_endpoint) = &endpoint {
             // For custom endpoints (MinIO), use the endpoint hostname WITHOUT port
@@ -2889,17 +6124,95 @@ impl ProxyHttp for YatagarasuProxy {
                 .to_string()
         } else {
             // For AWS S3, use the standard format
-            format!("{}.s3.{}.amazonaws.com", bucket, region)
+            crate::s3::aws_host(&bucket, &region, path_style)
         };
 
         // Build S3 request with correct HTTP method
         let s3_request = match ctx.method() {
             "HEAD" => build_head_object_request(&bucket, &s3_key, &region),
             _ => build_get_object_request(&bucket, &s3_key, &region),
+        }
+        .with_session_token(session_token.clone());
+
+        // Update URI to S3 path - for MinIO (or forced path-style) use /bucket/key, for AWS use /key
+        let uri = if path_style {
+            // Path-style: /bucket/key
+            format!("/{}/{}", bucket, s3_key)
+        } else {
+            // AWS virtual-hosted style: /key (bucket is in Host header)
+            format!("/{}", s3_key)
         };
 
+        // Multipart uploads forward their subresource query and sign it along
+        // with the client's x-amz-* headers (S3 rejects unsigned ones)
+        let multipart_op = MultipartOperation::classify(ctx.method(), upstream_request.uri.query());
+        let listing_query = ctx
+            .listing()
+            .map(|_| s3_list::upstream_query(upstream_request.uri.query()));
+        let upstream_query = multipart_op
+            .as_ref()
+            .map(|op| op.upstream_query())
+            .or_else(|| listing_query.clone());
+
         // Get signed headers with correct host for signature calculation
-        let signed_headers = if endpoint.is_some() {
+        let signed_headers = if let Some(query) = &upstream_query {
+            let mut amz_headers: Vec<(String, String)> = upstream_request
+                .headers
+                .iter()
+                .filter(|(name, _)| {
+                    name.as_str().starts_with("x-amz-") && name.as_str() != SECURITY_TOKEN_HEADER
+                })
+                .filter_map(|(name, value)| {
+                    Some((name.as_str().to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect();
+            for (name, _) in &amz_headers {
+                upstream_request.remove_header(name.as_str());
+            }
+            upstream_request.remove_header(SECURITY_TOKEN_HEADER);
+            if let Some(token) = &session_token {
+                amz_headers.push((SECURITY_TOKEN_HEADER.to_string(), token.clone()));
+            }
+            bucket_log!(ctx, DEBUG,
+                request_id = %ctx.request_id(),
+                operation = multipart_op.as_ref().map(|op| op.as_str()).unwrap_or_default(),
+                "Forwarding multipart upload request"
+            );
+            multipart::signed_headers(
+                &UploadRequest {
+                    method: ctx.method(),
+                    host: &host_for_signing,
+                    uri: &uri,
+                    query,
+                    amz_headers: &amz_headers,
+                },
+                &access_key,
+                &secret_key,
+                signing
+                    .and_then(|s| s.signing_region.as_deref())
+                    .unwrap_or(&region),
+            )
+        } else if let Some(query) = &listing_query {
+            // Listings sign the host, URI and query exactly as sent upstream
+            let default_signing = S3SigningConfig::default();
+            s3_request.get_signed_headers_with_query(
+                &access_key,
+                &secret_key,
+                &host_for_signing,
+                &uri,
+                query,
+                signing.unwrap_or(&default_signing),
+            )
+        } else if let Some(signing) = signing {
+            // Vendor quirks: sign exactly the host and URI sent upstream
+            s3_request.get_signed_headers_with_signing(
+                &access_key,
+                &secret_key,
+                &host_for_signing,
+                &uri,
+                signing,
+            )
+        } else if endpoint.is_some() {
             // For custom endpoints, use the custom host in the signature
             s3_request.get_signed_headers_with_host(&access_key, &secret_key, &host_for_signing)
         } else {
@@ -2907,6 +6220,11 @@ impl ProxyHttp for YatagarasuProxy {
             s3_request.get_signed_headers(&access_key, &secret_key)
         };
 
+        // Our session token replaces any the client sent
+        if session_token.is_some() {
+            upstream_request.remove_header(SECURITY_TOKEN_HEADER);
+        }
+
         // Add signed headers to upstream request
         // Use append_header instead of insert_header to avoid lifetime issues
         for (name, value) in signed_headers {
@@ -2945,7 +6263,7 @@ impl ProxyHttp for YatagarasuProxy {
                 .to_string()
         } else {
             // For AWS S3, use the standard format
-            format!("{}.s3.{}.amazonaws.com", bucket, region)
+            crate::s3::aws_host(&bucket, &region, path_style)
         };
 
         upstream_request.remove_header(&http::header::HOST);
@@ -2966,15 +6284,11 @@ impl ProxyHttp for YatagarasuProxy {
                 )
             })?;
 
-        // Update URI to S3 path - for MinIO use /bucket/key format, for AWS use /key
-        let uri = if endpoint.is_some() {
-            // MinIO path-style: /bucket/key
-            format!("/{}/{}", bucket, s3_key)
-        } else {
-            // AWS virtual-hosted style: /key (bucket is in Host header)
-            format!("/{}", s3_key)
+        let upstream_uri = match &upstream_query {
+            Some(query) => format!("{}?{}", uri, query),
+            None => uri,
         };
-        let parsed_uri = uri.parse().map_err(|e: http::uri::InvalidUri| {
+        let parsed_uri = upstream_uri.parse().map_err(|e: http::uri::InvalidUri| {
             pingora_core::Error::explain(
                 pingora_core::ErrorType::InternalError,
                 format!("Invalid URI: {}", e),
@@ -2982,6 +6296,30 @@ impl ProxyHttp for YatagarasuProxy {
         })?;
         upstream_request.set_uri(parsed_uri);
 
+        // Download resumption: pin resumed ranges to the token's object version
+        upstream_request.remove_header(resume::RESUME_TOKEN_HEADER);
+        let resume_enabled = bucket_config
+            .resume
+            .as_ref()
+            .map(|r| r.enabled)
+            .unwrap_or(false);
+        if resume_enabled {
+            if let Some(token) = ctx
+                .headers()
+                .get(resume::RESUME_TOKEN_HEADER)
+                .and_then(|t| resume::ResumeToken::decode(t))
+            {
+                upstream_request
+                    .insert_header(http::header::IF_MATCH, token.if_match())
+                    .map_err(|e| {
+                        pingora_core::Error::explain(
+                            pingora_core::ErrorType::InternalError,
+                            format!("Failed to set If-Match header: {}", e),
+                        )
+                    })?;
+            }
+        }
+
         // Record S3 operation metrics
         let method = ctx.method().to_uppercase();
         self.metrics.increment_s3_operation(&method);
@@ -2993,9 +6331,14 @@ impl ProxyHttp for YatagarasuProxy {
     async fn logging(
         &self,
         session: &mut Session,
-        _e: Option<&pingora_core::Error>,
+        e: Option<&pingora_core::Error>,
         ctx: &mut Self::CTX,
     ) {
+        // Client went away mid-transfer: account for it and maybe finish the cache fill
+        if e.is_some_and(disconnect::is_client_disconnect) {
+            self.handle_client_disconnect(ctx);
+        }
+
         // Get status code from response header
         let status_code = if let Some(resp) = session.response_written() {
             resp.status.as_u16()
@@ -3003,6 +6346,24 @@ impl ProxyHttp for YatagarasuProxy {
             500 // Default to 500 if no response written
         };
 
+        // Count 4xx/429 responses toward automatic IP bans
+        if let Some(ref ip_ban_manager) = self.ip_ban_manager {
+            if let Some(ip) = session
+                .client_addr()
+                .and_then(|addr| addr.as_inet().map(|inet| inet.ip()))
+            {
+                if let Some(reason) = ip_ban_manager.record_response(ip, status_code) {
+                    bucket_log!(ctx, WARN,
+                        request_id = %ctx.request_id(),
+                        client_ip = %ip,
+                        reason = reason.as_str(),
+                        "Client IP temporarily banned"
+                    );
+                    self.metrics.record_ip_ban(reason.as_str());
+                }
+            }
+        }
+
         // Calculate request duration
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
@@ -3019,32 +6380,122 @@ impl ProxyHttp for YatagarasuProxy {
         // Record bucket-specific metrics if bucket was identified
         if let Some(bucket_config) = ctx.bucket_config() {
             self.metrics.increment_bucket_count(&bucket_config.name);
+
+            // A reload happened while this request was in flight; it finished
+            // on the bucket config and object key it was routed with
+            let current_generation = self.config.load().generation;
+            if ctx.config_generation() < current_generation {
+                self.metrics.increment_retired_generation_request();
+                bucket_log!(ctx, DEBUG,
+                    request_id = %ctx.request_id(),
+                    bucket = %bucket_config.name,
+                    request_generation = ctx.config_generation(),
+                    current_generation = current_generation,
+                    "Request completed on retired config generation"
+                );
+            }
+
+            // Count successful object reads toward key popularity
+            if let Some(ref key_popularity) = self.key_popularity {
+                if ctx.method() == "GET"
+                    && (status_code < 300 || status_code == 304)
+                    && !ctx.s3_key().is_empty()
+                {
+                    key_popularity.record(&bucket_config.name, ctx.s3_key());
+                }
+            }
+
+            // Aggregate per-key access statistics for export
+            if let Some(ref access_stats) = self.access_stats {
+                if ctx.method() == "GET" && !ctx.s3_key().is_empty() {
+                    let object_key = ctx.s3_key().to_string();
+                    let (bytes, cache_hit) = session
+                        .response_written()
+                        .map(|resp| {
+                            let bytes = resp
+                                .headers
+                                .get("content-length")
+                                .and_then(|v| v.to_str().ok())
+                                .and_then(|s| s.parse::<u64>().ok())
+                                .unwrap_or(0);
+                            let cache_hit = resp
+                                .headers
+                                .get("x-cache")
+                                .is_some_and(|v| v.as_bytes() == b"HIT");
+                            (bytes, cache_hit)
+                        })
+                        .unwrap_or((0, false));
+                    access_stats.record(&bucket_config.name, &object_key, bytes, cache_hit);
+                }
+            }
             self.metrics
                 .record_bucket_latency(&bucket_config.name, duration_ms);
 
-            // Record circuit breaker success/failure if circuit breaker is configured
+            // Size distributions for cache and range segment sizing
+            self.metrics.record_bucket_sizes(
+                &bucket_config.name,
+                ctx.path().len() as u64,
+                session.body_bytes_sent() as u64,
+            );
+            if status_code == 206 {
+                let span = session
+                    .response_written()
+                    .and_then(|resp| resp.headers.get("content-range"))
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|v| v.strip_prefix("bytes "))
+                    .and_then(|v| v.split('/').next())
+                    .and_then(|v| v.split_once('-'))
+                    .and_then(|(start, end)| {
+                        Some(end.parse::<u64>().ok()?.checked_sub(start.parse().ok()?)? + 1)
+                    });
+                if let Some(span) = span {
+                    self.metrics.record_range_span(&bucket_config.name, span);
+                }
+            }
+
+            // Record circuit breaker success/failure if circuit breaker is configured.
+            // One outcome per request: the last origin attempt, classified by the
+            // bucket's failure_classification. Requests that never reached the
+            // origin (cache hits, local rejections) only release their half-open slot.
             if let Some(circuit_breaker) = self.circuit_breakers.get(&bucket_config.name) {
-                // 2xx: Success - record success
-                // 5xx: Server error (S3 backend failure) - record failure
-                // 4xx/3xx: Client error/redirect - don't affect circuit breaker
-                if (200..300).contains(&status_code) {
-                    circuit_breaker.record_success();
-                    tracing::debug!(
-                        request_id = %ctx.request_id(),
-                        bucket = %bucket_config.name,
-                        status_code = status_code,
-                        "Circuit breaker recorded success"
-                    );
-                } else if status_code >= 500 {
-                    circuit_breaker.record_failure();
-                    tracing::warn!(
-                        request_id = %ctx.request_id(),
-                        bucket = %bucket_config.name,
-                        status_code = status_code,
-                        failure_count = circuit_breaker.failure_count(),
-                        "Circuit breaker recorded failure"
-                    );
+                let default_classification = FailureClassificationConfig::default();
+                let classification = bucket_config
+                    .s3
+                    .circuit_breaker
+                    .as_ref()
+                    .map(|cb| &cb.failure_classification)
+                    .unwrap_or(&default_classification);
+                let before = circuit_breaker.state();
+                match ctx.upstream_outcome() {
+                    Some(outcome) if classification.is_failure(outcome) => {
+                        circuit_breaker.record_failure();
+                        self.metrics
+                            .increment_circuit_breaker_failure(&bucket_config.name, outcome.kind());
+                        bucket_log!(ctx, WARN,
+                            request_id = %ctx.request_id(),
+                            bucket = %bucket_config.name,
+                            outcome = ?outcome,
+                            kind = outcome.kind(),
+                            failure_count = circuit_breaker.failure_count(),
+                            "Circuit breaker recorded failure"
+                        );
+                    }
+                    Some(outcome) if classification.is_success(outcome) => {
+                        circuit_breaker.record_success();
+                        bucket_log!(ctx, DEBUG,
+                            request_id = %ctx.request_id(),
+                            bucket = %bucket_config.name,
+                            outcome = ?outcome,
+                            "Circuit breaker recorded success"
+                        );
+                    }
+                    _ => circuit_breaker.release_half_open_request(),
                 }
+                helpers::publish_breaker_transition(
+                    &bucket_config.name,
+                    before,
+                    circuit_breaker.state(),
+                );
             }
         }
 
@@ -3054,6 +6505,9 @@ impl ProxyHttp for YatagarasuProxy {
         // Extract client IP for logging
         let client_ip = self.get_client_ip(session);
 
+        // Header redaction for the audit entry (log levels go through bucket_log!)
+        let bucket_logging = ctx.bucket_config().and_then(|b| b.logging.clone());
+
         // Extract S3 error information from upstream response headers (if error status)
         let (s3_error_code, s3_error_message) = if status_code >= 400 {
             if let Some(resp) = session.response_written() {
@@ -3080,7 +6534,7 @@ impl ProxyHttp for YatagarasuProxy {
         // Log S3 errors with error code and message (if available)
         if status_code >= 400 {
             if let (Some(code), Some(message)) = (&s3_error_code, &s3_error_message) {
-                tracing::warn!(
+                bucket_log!(ctx, WARN,
                     request_id = %ctx.request_id(),
                     client_ip = %client_ip,
                     method = %ctx.method(),
@@ -3094,50 +6548,226 @@ impl ProxyHttp for YatagarasuProxy {
                 );
             } else {
                 // Error response but no S3 error headers (might be proxy error, not S3)
-                tracing::warn!(
+                bucket_log!(ctx, WARN,
                     request_id = %ctx.request_id(),
                     client_ip = %client_ip,
                     method = %ctx.method(),
                     path = %ctx.path(),
-                    status_code = status_code,
-                    bucket = ctx.bucket_config().map(|b| b.name.as_str()).unwrap_or("unknown"),
-                    duration_ms = duration_ms,
-                    "Error response without S3 error headers"
+                    status_code = status_code,
+                    bucket = ctx.bucket_config().map(|b| b.name.as_str()).unwrap_or("unknown"),
+                    duration_ms = duration_ms,
+                    "Error response without S3 error headers"
+                );
+            }
+        }
+
+        // Log request completion with request ID for tracing
+        bucket_log!(ctx, INFO,
+            request_id = %ctx.request_id(),
+            client_ip = %client_ip,
+            method = %ctx.method(),
+            path = %ctx.path(),
+            status_code = status_code,
+            duration_ms = duration_ms,
+            "Request completed"
+        );
+
+        // -- Audit Logging: Finalize and write log --
+        if let Some(writer) = &self.audit_writer {
+            let audit_ctx = ctx.audit();
+            audit_ctx.set_response_status(status_code);
+            if let Some(ref logging_config) = bucket_logging {
+                audit_ctx.user_agent = audit_ctx
+                    .user_agent
+                    .take()
+                    .map(|v| logging_config.redact_header_value("user-agent", &v));
+                audit_ctx.referer = audit_ctx
+                    .referer
+                    .take()
+                    .map(|v| logging_config.redact_header_value("referer", &v));
+            }
+            if let Some(resp) = session.response_written() {
+                let content_length = resp
+                    .headers
+                    .get("content-length")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|s| s.parse::<u64>().ok())
+                    .unwrap_or(0);
+                audit_ctx.set_response_size(content_length);
+            }
+
+            let entry = audit_ctx.to_audit_entry();
+            if let Err(e) = writer.write_entry(entry) {
+                tracing::error!("Failed to write audit entry: {}", e);
+            }
+        }
+        // -- End Audit Logging --
+    }
+
+    /// Check SigV4 bodies against their signed digest, and hold upload part
+    /// bodies back from S3 until the bucket's virus scan passes
+    async fn request_body_filter(
+        &self,
+        session: &mut Session,
+        body: &mut Option<bytes::Bytes>,
+        end_of_stream: bool,
+        ctx: &mut Self::CTX,
+    ) -> Result<()> {
+        if let Some(digest) = ctx.payload_digest() {
+            match digest.filter(body.take(), end_of_stream) {
+                Ok(chunk) => *body = Some(chunk),
+                Err(e) => {
+                    bucket_log!(ctx, WARN,
+                        request_id = %ctx.request_id(),
+                        path = %ctx.path(),
+                        reason = e.as_str(),
+                        "SigV4 request body rejected"
+                    );
+                    if let Some(bucket_config) = ctx.bucket_config() {
+                        self.metrics
+                            .increment_sigv4_request(&bucket_config.name, e.as_str());
+                    }
+                    return Err(pingora_core::Error::explain(
+                        pingora_core::ErrorType::HTTPStatus(e.status()),
+                        e.message(),
+                    ));
+                }
+            }
+        }
+
+        let Some(bucket_config) = ctx.bucket_config() else {
+            return Ok(());
+        };
+        let Some(scan_config) = bucket_config.upload_scan.clone() else {
+            return Ok(());
+        };
+        // Only part bodies carry object content (Create/Complete are small XML)
+        let is_part = matches!(
+            MultipartOperation::classify(ctx.method(), session.req_header().uri.query()),
+            Some(MultipartOperation::UploadPart { .. })
+        );
+        if !is_part {
+            return Ok(());
+        }
+        let bucket_name = bucket_config.name.clone();
+        let buffer = ctx.upload_scan_buffer();
+        if buffer.is_passthrough() {
+            return Ok(());
+        }
+
+        // Withhold chunks (an empty chunk sends nothing upstream)
+        let size = match body.take() {
+            Some(chunk) => buffer.push(chunk),
+            None => 0,
+        };
+        *body = Some(bytes::Bytes::new());
+
+        if size > scan_config.max_size_bytes() {
+            self.metrics
+                .record_upload_scan(&bucket_name, "skipped", None);
+            if !scan_config.fail_open {
+                bucket_log!(ctx, WARN,
+                    request_id = %ctx.request_id(),
+                    bucket = %bucket_name,
+                    max_size_mb = scan_config.max_size_mb,
+                    "Rejecting upload too large to scan"
+                );
+                return Err(pingora_core::Error::explain(
+                    pingora_core::ErrorType::HTTPStatus(413),
+                    "Upload body exceeds the scan size limit",
+                ));
+            }
+            bucket_log!(ctx, WARN,
+                request_id = %ctx.request_id(),
+                bucket = %bucket_name,
+                max_size_mb = scan_config.max_size_mb,
+                "Upload too large to scan, forwarding unscanned (fail-open)"
+            );
+            let buffer = ctx.upload_scan_buffer();
+            buffer.set_passthrough();
+            *body = Some(buffer.take());
+            return Ok(());
+        }
+        if !end_of_stream {
+            return Ok(());
+        }
+
+        let upload = ctx.upload_scan_buffer().take();
+        let started = Instant::now();
+        let verdict = virus_scan::scan_clamd(
+            &scan_config.clamd,
+            &upload,
+            Duration::from_millis(scan_config.timeout_ms),
+        )
+        .await;
+        let duration_ms = started.elapsed().as_millis() as u64;
+        match verdict {
+            Ok(ScanVerdict::Clean) => {
+                self.metrics
+                    .record_upload_scan(&bucket_name, "clean", Some(duration_ms));
+                *body = Some(upload);
+                Ok(())
+            }
+            Ok(ScanVerdict::Infected(signature)) => {
+                self.metrics
+                    .record_upload_scan(&bucket_name, "infected", Some(duration_ms));
+                bucket_log!(ctx, WARN,
+                    request_id = %ctx.request_id(),
+                    bucket = %bucket_name,
+                    path = %ctx.path(),
+                    signature = %signature,
+                    "Rejecting infected upload"
+                );
+                Err(pingora_core::Error::explain(
+                    pingora_core::ErrorType::HTTPStatus(403),
+                    format!("Upload rejected by virus scan: {}", signature),
+                ))
+            }
+            Err(e) => {
+                self.metrics
+                    .record_upload_scan(&bucket_name, "error", Some(duration_ms));
+                if scan_config.fail_open {
+                    bucket_log!(ctx, WARN,
+                        request_id = %ctx.request_id(),
+                        bucket = %bucket_name,
+                        error = %e,
+                        "Upload scan failed, forwarding unscanned (fail-open)"
+                    );
+                    *body = Some(upload);
+                    return Ok(());
+                }
+                bucket_log!(ctx, ERROR,
+                    request_id = %ctx.request_id(),
+                    bucket = %bucket_name,
+                    error = %e,
+                    "Upload scan failed, rejecting upload"
                 );
+                Err(pingora_core::Error::explain(
+                    pingora_core::ErrorType::HTTPStatus(503),
+                    "Upload could not be scanned",
+                ))
             }
         }
+    }
 
-        // Log request completion with request ID for tracing
-        tracing::info!(
-            request_id = %ctx.request_id(),
-            client_ip = %client_ip,
-            method = %ctx.method(),
-            path = %ctx.path(),
-            status_code = status_code,
-            duration_ms = duration_ms,
-            "Request completed"
-        );
-
-        // -- Audit Logging: Finalize and write log --
-        if let Some(writer) = &self.audit_writer {
-            let audit_ctx = ctx.audit();
-            audit_ctx.set_response_status(status_code);
-            if let Some(resp) = session.response_written() {
-                let content_length = resp
-                    .headers
-                    .get("content-length")
-                    .and_then(|v| v.to_str().ok())
-                    .and_then(|s| s.parse::<u64>().ok())
-                    .unwrap_or(0);
-                audit_ctx.set_response_size(content_length);
-            }
+    /// Runs before the response header reaches the client
+    async fn response_filter(
+        &self,
+        session: &mut Session,
+        upstream_response: &mut ResponseHeader,
+        ctx: &mut Self::CTX,
+    ) -> Result<()> {
+        // A successful write (completed multipart upload, PUT or DELETE)
+        // replaced the object: drop the cached copy before the client sees success, so a read through this proxy
+        // right after the write never gets the old body
+        self.invalidate_replaced_object(
+            ctx,
+            session.req_header().uri.query(),
+            upstream_response.status.is_success(),
+        )
+        .await;
 
-            let entry = audit_ctx.to_audit_entry();
-            if let Err(e) = writer.write_entry(entry) {
-                tracing::error!("Failed to write audit entry: {}", e);
-            }
-        }
-        // -- End Audit Logging --
+        Ok(())
     }
 
     /// Filter upstream responses to add custom headers (request correlation)
@@ -3148,11 +6778,13 @@ impl ProxyHttp for YatagarasuProxy {
         upstream_response: &mut ResponseHeader,
         ctx: &mut Self::CTX,
     ) -> Result<()> {
+        ctx.set_upstream_outcome(UpstreamOutcome::Status(upstream_response.status.as_u16()));
+
         // Add X-Request-ID header for request correlation
         upstream_response
             .insert_header("X-Request-ID", ctx.request_id())
             .map_err(|e| {
-                tracing::warn!(
+                bucket_log!(ctx, WARN,
                     request_id = %ctx.request_id(),
                     error = ?e,
                     "Failed to add X-Request-ID header"
@@ -3160,12 +6792,47 @@ impl ProxyHttp for YatagarasuProxy {
                 e
             })?;
 
+        // Tag S3 error responses so clients can tell them from proxy errors
+        let upstream_status = upstream_response.status.as_u16();
+        if upstream_status >= 400 {
+            upstream_response
+                .insert_header(
+                    ERROR_CODE_HEADER,
+                    ErrorCode::for_upstream_status(upstream_status).as_str(),
+                )
+                .ok();
+        }
+
         // Add X-Cache: MISS header for cache miss responses (response from S3)
         // This complements X-Cache: HIT added in request_filter for cache hits
         if self.cache.is_some() {
             upstream_response.insert_header("X-Cache", "MISS").ok();
         }
 
+        // Preload links from the bucket's early hints manifest
+        if (200..300).contains(&upstream_status) {
+            if let Some(links) = ctx.preload_links() {
+                upstream_response.insert_header("Link", links).ok();
+            }
+        }
+
+        // Time to upstream response headers, then the phase breakdown
+        ctx.finish_upstream();
+        if let Some(bucket_config) = ctx.bucket_config() {
+            server_timing::apply(upstream_response, bucket_config, ctx).ok();
+            deprecation::apply(upstream_response, bucket_config).ok();
+        }
+
+        // CORS grant for the request's Origin under the bucket's policy
+        if let Some(bucket_config) = ctx.bucket_config() {
+            let origin = ctx.headers().get("origin").map(String::as_str);
+            cors::apply(
+                upstream_response,
+                cors::response_headers(bucket_config, origin),
+            )
+            .ok();
+        }
+
         // Log successful requests with replica information (Phase 23: HA bucket replication)
         let status = upstream_response.status.as_u16();
         if (200..300).contains(&status) {
@@ -3181,7 +6848,7 @@ impl ProxyHttp for YatagarasuProxy {
                     .saturating_sub(ctx.timestamp()) as f64
                     * 1000.0; // Convert seconds to milliseconds
 
-                tracing::info!(
+                bucket_log!(ctx, INFO,
                     request_id = %ctx.request_id(),
                     bucket = bucket_config.name.as_str(),
                     replica = replica_name,
@@ -3192,8 +6859,271 @@ impl ProxyHttp for YatagarasuProxy {
             }
         }
 
+        // Capture Content-Length for client disconnect accounting
+        if let Some(content_length) = upstream_response
+            .headers
+            .get("content-length")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|s| s.parse::<u64>().ok())
+        {
+            ctx.set_response_content_length(content_length);
+        }
+
+        // Size classes: remember the object's size for later requests and
+        // apply its class's cache policy to this response
+        let mut size_class_cache = SizeClassCache::Default;
+        if (status == 200 || status == 206) && (ctx.method() == "GET" || ctx.method() == "HEAD") {
+            if let Some(bucket_config) = ctx.bucket_config().filter(|b| !b.size_classes.is_empty())
+            {
+                let size = if status == 206 {
+                    upstream_response
+                        .headers
+                        .get("content-range")
+                        .and_then(|v| v.to_str().ok())
+                        .and_then(parse_content_range)
+                        .map(|(_, _, total)| total)
+                } else {
+                    ctx.response_content_length()
+                };
+                if let Some(size) = size {
+                    self.object_sizes
+                        .record(&bucket_config.name, ctx.s3_key(), size);
+                    if let Some(class) =
+                        crate::config::size_class::classify(&bucket_config.size_classes, size)
+                    {
+                        bucket_log!(ctx, DEBUG,
+                            request_id = %ctx.request_id(),
+                            bucket = %bucket_config.name,
+                            size = size,
+                            class = %class.name,
+                            cache = class.cache.as_str(),
+                            "Classified object by size"
+                        );
+                        self.metrics
+                            .increment_size_class_response(&bucket_config.name, &class.name);
+                        size_class_cache = class.cache;
+                    }
+                }
+            }
+        }
+        if size_class_cache == SizeClassCache::Memory {
+            ctx.set_cache_memory_only();
+        }
+
+        // Content-Type overrides: only the extension is known before the body streams
+        if status == 200 || status == 206 {
+            if let Some(bucket_config) = ctx.bucket_config() {
+                if bucket_config.content_type.is_some() {
+                    let key = ctx.s3_key().to_string();
+                    let upstream_type = upstream_response
+                        .headers
+                        .get("content-type")
+                        .and_then(|v| v.to_str().ok());
+                    if let Some(corrected) =
+                        content_type::for_bucket(bucket_config, &key, upstream_type, None)
+                    {
+                        upstream_response
+                            .insert_header("Content-Type", corrected)
+                            .ok();
+                    }
+                }
+            }
+        }
+
+        // Replica-independent or namespaced ETags, rewritten before the ETag is
+        // captured so cached entries and 304s carry the same validator
+        if status == 200 || status == 206 || status == 304 {
+            if let Some(etag_rewrite) = ctx.bucket_config().and_then(|b| b.etag_rewrite.as_ref()) {
+                let header = |name: &str| {
+                    upstream_response
+                        .headers
+                        .get(name)
+                        .and_then(|v| v.to_str().ok())
+                };
+                if let Some(etag) = header("etag").and_then(|etag| {
+                    etag_rewrite.surfaced(
+                        etag,
+                        ctx.replica_name(),
+                        header(&etag_rewrite.checksum_header),
+                    )
+                }) {
+                    upstream_response
+                        .insert_header("ETag", format!("\"{}\"", etag))
+                        .ok();
+                }
+            }
+        }
+
+        // Multipart upload and listing responses are never cached, optimized or resumable
+        let uncacheable = BUCKET_WRITE_METHODS.contains(&ctx.method()) || ctx.listing().is_some();
+
+        // Listing JSON translation: the body is rewritten once the XML is complete
+        if ctx.listing() == Some(ListingFormat::Json) {
+            if status == 200 && ctx.method() == "GET" {
+                upstream_response.remove_header("Content-Length");
+                upstream_response.insert_header("Content-Type", "application/json")?;
+            } else {
+                ctx.set_listing(ListingFormat::Xml);
+            }
+        }
+
+        // Cache admission: only cacheable statuses with storable directives and a
+        // validator are buffered for the cache; every skip is counted by reason
+        let mut cache_admitted = false;
+        if self.cache.is_some()
+            && !uncacheable
+            && ctx.cache_override() != Some(CacheOverrideMode::Bypass)
+        {
+            let header = |name: &str| {
+                upstream_response
+                    .headers
+                    .get(name)
+                    .and_then(|v| v.to_str().ok())
+            };
+            let cache_control = header("cache-control")
+                .map(CacheControl::parse)
+                .unwrap_or_default();
+            let admitted = if size_class_cache == SizeClassCache::None {
+                Err(CacheSkipReason::SizeClass)
+            } else if let Some(bucket_config) = ctx.bucket_config() {
+                cache_policy::admit(
+                    bucket_config,
+                    ctx.s3_key(),
+                    header("content-type"),
+                    ctx.response_content_length(),
+                    ctx.config().and_then(|config| config.cache.as_ref()),
+                )
+            } else {
+                Ok(())
+            };
+            let admitted = admitted.and_then(|()| {
+                admission::admit(
+                    status,
+                    &cache_control,
+                    header("etag"),
+                    header("last-modified"),
+                )
+            });
+            // An entry that is stale on arrival would never be a hit
+            let admitted = admitted.and_then(|()| {
+                let expired = ctx.bucket_config().is_some_and(|bucket_config| {
+                    cache_ttl::entry_ttl(
+                        bucket_config,
+                        &cache_control,
+                        header("expires"),
+                        header("date"),
+                    )
+                    .is_zero()
+                });
+                if expired {
+                    Err(CacheSkipReason::Expired)
+                } else {
+                    Ok(())
+                }
+            });
+            match admitted {
+                Ok(()) => cache_admitted = true,
+                Err(reason) => {
+                    self.metrics
+                        .increment_cache_population_skip(reason.as_str());
+                    bucket_log!(ctx, DEBUG,
+                        request_id = %ctx.request_id(),
+                        status = status,
+                        reason = reason.as_str(),
+                        "Response not admitted to cache"
+                    );
+                }
+            }
+        }
+
+        // Chunked Range caching: a 206 stores the whole chunks it covers
+        if status == 206
+            && !uncacheable
+            && ctx.method() == "GET"
+            && ctx.cache_variant().is_none()
+            && size_class_cache != SizeClassCache::None
+            && ctx.cache_override() != Some(CacheOverrideMode::Bypass)
+        {
+            let fill = match (&self.cache, ctx.bucket_config()) {
+                (Some(cache), Some(bucket_config)) => cache.range_chunks().and_then(|chunks| {
+                    let header = |name: &str| {
+                        upstream_response
+                            .headers
+                            .get(name)
+                            .and_then(|v| v.to_str().ok())
+                    };
+                    let cache_control = header("cache-control")
+                        .map(CacheControl::parse)
+                        .unwrap_or_default();
+                    // Chunks of different object versions must never be mixed
+                    let etag = header("etag")
+                        .map(|etag| etag.trim_matches('"'))
+                        .filter(|etag| !etag.is_empty())?;
+                    let (start, _, total_size) =
+                        header("content-range").and_then(parse_content_range)?;
+                    if !cache_control.should_store()
+                        || cache_policy::admit(
+                            bucket_config,
+                            ctx.s3_key(),
+                            header("content-type"),
+                            None,
+                            ctx.config().and_then(|config| config.cache.as_ref()),
+                        )
+                        .is_err()
+                    {
+                        return None;
+                    }
+                    let ttl = cache_ttl::entry_ttl(
+                        bucket_config,
+                        &cache_control,
+                        header("expires"),
+                        header("date"),
+                    );
+                    if ttl.is_zero() {
+                        return None;
+                    }
+                    let object = ChunkedObject {
+                        key: CacheKey {
+                            bucket: bucket_config.name.clone(),
+                            object_key: ctx.s3_key().to_string(),
+                            etag: None,
+                            variant: None,
+                        },
+                        total_size,
+                        content_type: header("content-type")
+                            .unwrap_or("application/octet-stream")
+                            .to_string(),
+                        etag: etag.to_string(),
+                        last_modified: header("last-modified").map(str::to_string),
+                        headers: cache_preserve::capture(
+                            cache.preserve_headers(),
+                            &upstream_response.headers,
+                        ),
+                        ttl,
+                    };
+                    Some(RangeChunkFill::new(object, chunks.chunk_size_bytes, start))
+                }),
+                _ => None,
+            };
+            // At most one chunk is held at a time; reserve it from the buffer budget
+            if let Some(fill) = fill {
+                let chunk_size = self
+                    .cache
+                    .as_ref()
+                    .and_then(|cache| cache.range_chunks())
+                    .map_or(0, |chunks| chunks.chunk_size_bytes);
+                match self.buffer_budget.try_reserve(chunk_size as usize) {
+                    Some(permit) => {
+                        ctx.add_buffer_permit(permit);
+                        ctx.set_range_fill(fill);
+                    }
+                    None => self.metrics.increment_response_buffer_budget_exceeded(),
+                }
+            }
+        }
+
         // Phase 30 & Image Optimization: Enable response buffering and header capture
-        if status == 200 {
+        if status == 200 && !uncacheable {
             // Capture response headers (common for both caching and optimization)
             if let Some(content_type) = upstream_response
                 .headers
@@ -3239,10 +7169,26 @@ impl ProxyHttp for YatagarasuProxy {
                 }
             }
 
-            // check if cache is enabled to enable buffering for cache population
-            if self.cache.is_some() {
+            // Capture Expires and Date for the entry TTL when there is no max-age
+            let header_value = |name: &str| {
+                upstream_response
+                    .headers
+                    .get(name)
+                    .and_then(|v| v.to_str().ok())
+                    .map(str::to_string)
+            };
+            ctx.set_response_expires(header_value("expires"), header_value("date"));
+
+            // Buffer for cache population once the response is admitted
+            if cache_admitted {
+                if let Some(cache) = &self.cache {
+                    ctx.set_response_preserved_headers(cache_preserve::capture(
+                        cache.preserve_headers(),
+                        &upstream_response.headers,
+                    ));
+                }
                 ctx.enable_response_buffering();
-                tracing::debug!(
+                bucket_log!(ctx, DEBUG,
                     request_id = %ctx.request_id(),
                     "Enabled response buffering for cache population"
                 );
@@ -3257,9 +7203,9 @@ impl ProxyHttp for YatagarasuProxy {
             if ctx.image_params().is_some() && is_image {
                 ctx.set_optimizing_image(true);
 
-                // Ensure buffering is enabled even if cache is disabled
+                // Ensure buffering is enabled even if the response is not cached
                 if !ctx.is_response_buffering_enabled() {
-                    ctx.enable_response_buffering();
+                    ctx.enable_response_buffering_only();
                 }
 
                 // Strip headers that will be invalid after optimization
@@ -3269,7 +7215,7 @@ impl ProxyHttp for YatagarasuProxy {
                 upstream_response.remove_header("ETag");
                 upstream_response.remove_header("etag");
 
-                tracing::debug!(
+                bucket_log!(ctx, DEBUG,
                     request_id = %ctx.request_id(),
                     params = ?ctx.image_params(),
                     "Enabled image optimization (stripped headers)"
@@ -3277,16 +7223,45 @@ impl ProxyHttp for YatagarasuProxy {
             }
         }
 
+        // Download resumption: advertise ranges and hand out a token for large objects
+        if status == 200 && !uncacheable && !ctx.is_optimizing_image() {
+            if let (Some(bucket_config), Some(content_length), Some(etag)) = (
+                ctx.bucket_config(),
+                ctx.response_content_length(),
+                ctx.response_etag(),
+            ) {
+                let applies = bucket_config
+                    .resume
+                    .as_ref()
+                    .map(|r| r.applies_to(content_length))
+                    .unwrap_or(false);
+                if applies && !etag.is_empty() {
+                    let token = resume::ResumeToken {
+                        bucket: bucket_config.name.clone(),
+                        key: ctx.s3_key().to_string(),
+                        etag: etag.to_string(),
+                        size: content_length,
+                    };
+                    upstream_response
+                        .insert_header("Accept-Ranges", "bytes")
+                        .ok();
+                    upstream_response
+                        .insert_header(resume::RESUME_TOKEN_HEADER, token.encode())
+                        .ok();
+                }
+            }
+        }
+
         // Streaming Coalescing - broadcast headers to followers
         if let Some(leader) = ctx.streaming_leader() {
             if let Err(e) = leader.send_headers(upstream_response.clone()) {
-                tracing::warn!(
+                bucket_log!(ctx, WARN,
                     request_id = %ctx.request_id(),
                     error = ?e,
                     "Failed to broadcast headers to streaming followers"
                 );
             } else {
-                tracing::debug!(
+                bucket_log!(ctx, DEBUG,
                     request_id = %ctx.request_id(),
                     "Streaming leader: broadcast headers to followers"
                 );
@@ -3308,13 +7283,102 @@ impl ProxyHttp for YatagarasuProxy {
     where
         Self::CTX: Send + Sync,
     {
+        if let Some(chunk) = body.as_ref() {
+            ctx.add_upstream_bytes_received(chunk.len() as u64);
+        }
+
+        // Listing JSON translation: hold the XML back until it is complete
+        if ctx.listing() == Some(ListingFormat::Json) {
+            if !ctx.is_response_buffering_enabled() {
+                ctx.enable_response_buffering();
+            }
+            if let Some(chunk) = body.take() {
+                ctx.append_response_chunk(&chunk);
+            }
+            if end_of_stream {
+                let xml = ctx.take_response_buffer().unwrap_or_default();
+                let json = s3_list::to_json(&String::from_utf8_lossy(&xml)).unwrap_or_else(|e| {
+                    bucket_log!(ctx, WARN,
+                        request_id = %ctx.request_id(),
+                        error = %e,
+                        "Failed to translate bucket listing to JSON"
+                    );
+                    serde_json::json!({
+                        "error": "Bad Gateway",
+                        "message": "Listing response could not be translated",
+                        "status": 502
+                    })
+                    .to_string()
+                });
+                *body = Some(bytes::Bytes::from(json));
+            }
+            return Ok(None);
+        }
+
+        // Chunked Range caching: store each chunk as soon as it is complete
+        if let (Some(fill), Some(cache)) = (ctx.range_fill_mut(), &self.cache) {
+            let chunks = body
+                .as_ref()
+                .map(|data| fill.push(data))
+                .unwrap_or_default();
+            for (index, data) in chunks {
+                let cache = Arc::clone(cache);
+                let object = fill.object().clone();
+                let metrics = Arc::clone(&self.metrics);
+                tokio::spawn(async move {
+                    match cache.set_range_chunk(&object, index, data).await {
+                        Ok(()) => metrics.increment_cache_range("stored"),
+                        Err(e) => tracing::warn!(
+                            error = %e,
+                            bucket = %object.key.bucket,
+                            key = %object.key.object_key,
+                            chunk = index,
+                            "Failed to cache range chunk"
+                        ),
+                    }
+                });
+            }
+        }
+
         // If buffering is enabled, accumulate chunks
         if ctx.is_response_buffering_enabled() {
             // Buffer the current chunk (if any)
             if let Some(chunk) = body.as_ref() {
-                // Check if we'd exceed max cacheable size (10MB)
+                // Check if we'd exceed max buffered size: the bucket's cache item
+                // limit when caching, else 10MB for image optimization
                 const MAX_CACHE_SIZE: usize = 10 * 1024 * 1024; // 10MB
-                if ctx.total_response_size() + chunk.len() <= MAX_CACHE_SIZE {
+                let max_buffered = match ctx.bucket_config() {
+                    Some(bucket_config) if ctx.should_cache_response() => {
+                        cache_policy::max_item_bytes(
+                            bucket_config,
+                            ctx.config().and_then(|config| config.cache.as_ref()),
+                        ) as usize
+                    }
+                    _ => MAX_CACHE_SIZE,
+                };
+                // Cache-population buffers share a global memory budget; when
+                // it is spent the response is still served, just not cached
+                let within_budget = if ctx.should_cache_response() && !ctx.is_optimizing_image() {
+                    match self.buffer_budget.try_reserve(chunk.len()) {
+                        Some(permit) => {
+                            ctx.add_buffer_permit(permit);
+                            true
+                        }
+                        None => false,
+                    }
+                } else {
+                    true
+                };
+                if !within_budget {
+                    bucket_log!(ctx, DEBUG,
+                        request_id = %ctx.request_id(),
+                        in_use = self.buffer_budget.in_use(),
+                        max_bytes = self.buffer_budget.max_bytes(),
+                        "Response buffer budget exhausted, serving without caching"
+                    );
+                    self.metrics.increment_response_buffer_budget_exceeded();
+                    ctx.disable_response_buffering();
+                } else if ctx.total_response_size() + chunk.len() <= max_buffered {
                     ctx.append_response_chunk(chunk);
 
                     // IF optimizing, suppress output to client until we have full image
@@ -3323,11 +7387,15 @@ impl ProxyHttp for YatagarasuProxy {
                     }
                 } else {
                     // Response too large, disable buffering
-                    tracing::debug!(
+                    bucket_log!(ctx, DEBUG,
                         request_id = %ctx.request_id(),
                         total_size = ctx.total_response_size() + chunk.len(),
                         "Response too large for cache/optimization, disabling buffering"
                     );
+                    if ctx.should_cache_response() {
+                        self.metrics
+                            .increment_cache_population_skip(CacheSkipReason::TooLarge.as_str());
+                    }
                     ctx.disable_response_buffering();
                     // If optimizing, we stop optimizing (this may result in truncated info if we swallowed chunks)
                     if ctx.is_optimizing_image() {
@@ -3359,15 +7427,13 @@ impl ProxyHttp for YatagarasuProxy {
 
                             // RFC 7234 compliance: Skip caching for non-cacheable responses
                             if !cache_control.should_store() {
-                                tracing::debug!(
+                                bucket_log!(ctx, DEBUG,
                                     request_id = %ctx.request_id(),
                                     cache_control = ?ctx.response_cache_control(),
                                     "Skipping cache due to Cache-Control directives"
                                 );
                             } else {
-                                let router = self.router.load_full();
-                                let object_key =
-                                    router.extract_s3_key(ctx.path()).unwrap_or_default();
+                                let object_key = ctx.s3_key().to_string();
                                 let cache_key = CacheKey {
                                     bucket: bucket_config.name.clone(),
                                     object_key: object_key.to_string(),
@@ -3375,26 +7441,49 @@ impl ProxyHttp for YatagarasuProxy {
                                     variant: None, // Original always has None
                                 };
 
-                                // Use TTL from Cache-Control header or default to 1 hour
-                                let default_ttl = std::time::Duration::from_secs(3600);
-                                let ttl = cache_control.effective_ttl(default_ttl);
+                                // TTL from Cache-Control or Expires, else the bucket TTL
+                                // (see cache::ttl for clamps and overrides)
+                                let ttl = cache_ttl::entry_ttl(
+                                    bucket_config,
+                                    &cache_control,
+                                    ctx.response_expires(),
+                                    ctx.response_date(),
+                                );
 
-                                let cache_entry = CacheEntry::new(
-                                    bytes::Bytes::from(cache_data),
+                                // Body is complete here, so magic bytes can fix what the extension could not
+                                let content_type = content_type::for_bucket(
+                                    bucket_config,
+                                    &object_key,
+                                    ctx.response_content_type(),
+                                    Some(&cache_data),
+                                )
+                                .unwrap_or_else(|| {
                                     ctx.response_content_type()
                                         .unwrap_or("application/octet-stream")
-                                        .to_string(),
+                                        .to_string()
+                                });
+
+                                let cache_entry = CacheEntry::new(
+                                    bytes::Bytes::from(cache_data),
+                                    content_type,
                                     ctx.response_etag().unwrap_or("").to_string(),
                                     ctx.response_last_modified().map(|s| s.to_string()),
                                     Some(ttl),
-                                );
+                                )
+                                .with_headers(ctx.response_preserved_headers().to_vec());
 
                                 let cache_clone = Arc::clone(cache);
                                 let request_id = ctx.request_id().to_string();
                                 let ttl_secs = ttl.as_secs();
+                                let memory_only = ctx.is_cache_memory_only();
 
                                 tokio::spawn(async move {
-                                    if let Err(e) = cache_clone.set(cache_key, cache_entry).await {
+                                    let result = if memory_only {
+                                        cache_clone.set_memory_only(cache_key, cache_entry).await
+                                    } else {
+                                        cache_clone.set(cache_key, cache_entry).await
+                                    };
+                                    if let Err(e) = result {
                                         tracing::warn!(
                                             request_id = %request_id,
                                             error = %e,
@@ -3415,7 +7504,7 @@ impl ProxyHttp for YatagarasuProxy {
                     // 2. Perform Image Optimization
                     if ctx.is_optimizing_image() {
                         if let Some(params) = ctx.image_params() {
-                            tracing::debug!(
+                            bucket_log!(ctx, DEBUG,
                                 request_id = %ctx.request_id(),
                                 input_size = buffered_data.len(),
                                 "Starting image optimization"
@@ -3426,7 +7515,7 @@ impl ProxyHttp for YatagarasuProxy {
                                 params.clone(),
                             ) {
                                 Ok((optimized_data, content_type)) => {
-                                    tracing::debug!(
+                                    bucket_log!(ctx, DEBUG,
                                         request_id = %ctx.request_id(),
                                         output_size = optimized_data.len(),
                                         content_type = %content_type,
@@ -3439,7 +7528,7 @@ impl ProxyHttp for YatagarasuProxy {
                                     {
                                         if let Some(ref watermark_config) = bucket_config.watermark
                                         {
-                                            if watermark_config.enabled
+                                            if (watermark_config.enabled || ctx.is_hotlinked())
                                                 && !watermark_config.rules.is_empty()
                                             {
                                                 // Extract client IP from X-Forwarded-For header
@@ -3499,7 +7588,7 @@ impl ProxyHttp for YatagarasuProxy {
                                                                     crate::image_optimizer::encoder::EncoderQuality::with_quality(quality),
                                                                 ) {
                                                                     Ok(encoded) => {
-                                                                        tracing::debug!(
+                                                                        bucket_log!(ctx, DEBUG,
                                                                             request_id = %ctx.request_id(),
                                                                             watermark_size = encoded.data.len(),
                                                                             "Watermarks applied successfully"
@@ -3507,7 +7596,7 @@ impl ProxyHttp for YatagarasuProxy {
                                                                         encoded.data
                                                                     }
                                                                     Err(e) => {
-                                                                        tracing::warn!(
+                                                                        bucket_log!(ctx, WARN,
                                                                             request_id = %ctx.request_id(),
                                                                             error = %e,
                                                                             "Failed to encode watermarked image, using original"
@@ -3517,7 +7606,7 @@ impl ProxyHttp for YatagarasuProxy {
                                                                 }
                                                             }
                                                             Err(e) => {
-                                                                tracing::warn!(
+                                                                bucket_log!(ctx, WARN,
                                                                     request_id = %ctx.request_id(),
                                                                     error = %e,
                                                                     "Failed to apply watermarks, using optimized image"
@@ -3527,7 +7616,7 @@ impl ProxyHttp for YatagarasuProxy {
                                                         }
                                                     }
                                                     Err(e) => {
-                                                        tracing::warn!(
+                                                        bucket_log!(ctx, WARN,
                                                             request_id = %ctx.request_id(),
                                                             error = %e,
                                                             "Failed to decode optimized image for watermarking"
@@ -3546,7 +7635,7 @@ impl ProxyHttp for YatagarasuProxy {
                                     };
 
                                     // Store optimized version in cache (if cache enabled and cacheable)
-                                    if self.cache.is_some() {
+                                    if self.cache.is_some() && ctx.should_cache_response() {
                                         if let (Some(bucket_config), Some(cache)) =
                                             (ctx.bucket_config(), &self.cache)
                                         {
@@ -3562,23 +7651,21 @@ impl ProxyHttp for YatagarasuProxy {
 
                                             // RFC 7234: Skip caching for non-cacheable responses
                                             if cache_control.should_store() {
-                                                let router = self.router.load_full();
-                                                let object_key = router
-                                                    .extract_s3_key(ctx.path())
-                                                    .unwrap_or_default();
-                                                let variant = params.to_cache_key();
-
+                                                let object_key = ctx.s3_key().to_string();
                                                 let cache_key = CacheKey {
                                                     bucket: bucket_config.name.clone(),
                                                     object_key: object_key.to_string(),
                                                     etag: None,
-                                                    variant: Some(variant),
+                                                    variant: ctx.cache_variant(),
                                                 };
 
-                                                // Use TTL from Cache-Control or default
-                                                let default_ttl =
-                                                    std::time::Duration::from_secs(3600);
-                                                let ttl = cache_control.effective_ttl(default_ttl);
+                                                // TTL from Cache-Control/Expires or the bucket
+                                                let ttl = cache_ttl::entry_ttl(
+                                                    bucket_config,
+                                                    &cache_control,
+                                                    ctx.response_expires(),
+                                                    ctx.response_date(),
+                                                );
 
                                                 let cache_entry = CacheEntry::new(
                                                     bytes::Bytes::from(optimized_data.clone()),
@@ -3588,6 +7675,9 @@ impl ProxyHttp for YatagarasuProxy {
                                                     ctx.response_last_modified()
                                                         .map(|s| s.to_string()),
                                                     Some(ttl),
+                                                )
+                                                .with_headers(
+                                                    ctx.response_preserved_headers().to_vec(),
                                                 );
 
                                                 let cache_clone = Arc::clone(cache);
@@ -3608,7 +7698,7 @@ impl ProxyHttp for YatagarasuProxy {
                                     // Relying on chunked transfer encoding (which removal of CL header should trigger)
                                 }
                                 Err(e) => {
-                                    tracing::error!(
+                                    bucket_log!(ctx, ERROR,
                                         request_id = %ctx.request_id(),
                                         error = %e,
                                         "Image optimization failed, falling back to original"
@@ -3632,7 +7722,7 @@ impl ProxyHttp for YatagarasuProxy {
             if let Some(ref chunk) = body {
                 if let Some(leader) = ctx.streaming_leader() {
                     if let Err(e) = leader.send_chunk(chunk.clone()) {
-                        tracing::warn!(
+                        bucket_log!(ctx, WARN,
                             request_id = %ctx.request_id(),
                             error = ?e,
                             "Failed to broadcast chunk to streaming followers"
@@ -3645,13 +7735,13 @@ impl ProxyHttp for YatagarasuProxy {
             if end_of_stream {
                 if let Some(leader) = ctx.take_streaming_leader() {
                     if let Err(e) = leader.finish() {
-                        tracing::warn!(
+                        bucket_log!(ctx, WARN,
                             request_id = %ctx.request_id(),
                             error = ?e,
                             "Failed to broadcast finish to streaming followers"
                         );
                     } else {
-                        tracing::debug!(
+                        bucket_log!(ctx, DEBUG,
                             request_id = %ctx.request_id(),
                             "Streaming leader: broadcast finish to followers"
                         );
@@ -3672,10 +7762,26 @@ impl ProxyHttp for YatagarasuProxy {
     fn fail_to_connect(
         &self,
         _session: &mut Session,
-        _peer: &HttpPeer,
+        peer: &HttpPeer,
         ctx: &mut Self::CTX,
         mut e: Box<pingora_core::Error>,
     ) -> Box<pingora_core::Error> {
+        // Feed the circuit breaker (recorded once per request in logging)
+        ctx.set_upstream_outcome(error_handler::classify_upstream_error(e.etype()));
+
+        // Feed the static endpoint's health; a retry picks the next address
+        if let (Some(pool), Some(addr)) = (self.endpoint_pool(ctx), peer._address.as_inet()) {
+            if pool.record_failure(*addr) {
+                bucket_log!(ctx, WARN,
+                    request_id = %ctx.request_id(),
+                    replica = ctx.replica_name().unwrap_or_default(),
+                    endpoint = %addr,
+                    healthy = pool.healthy_count(),
+                    "Static endpoint failed repeatedly, skipping it for a cooldown"
+                );
+            }
+        }
+
         // Get bucket name from context to look up retry policy
         // Clone to owned String to avoid borrow conflicts
         let bucket_name = ctx
@@ -3699,7 +7805,7 @@ impl ProxyHttp for YatagarasuProxy {
                 // Track retry metric
                 self.metrics.increment_s3_retry_attempt(&bucket_name);
 
-                tracing::warn!(
+                bucket_log!(ctx, WARN,
                     request_id = %ctx.request_id(),
                     bucket = %bucket_name,
                     attempt = current_attempt,
@@ -3712,7 +7818,7 @@ impl ProxyHttp for YatagarasuProxy {
                 // Exhausted retries
                 self.metrics.increment_s3_retry_exhausted(&bucket_name);
 
-                tracing::error!(
+                bucket_log!(ctx, ERROR,
                     request_id = %ctx.request_id(),
                     bucket = %bucket_name,
                     attempt = current_attempt,
@@ -3742,6 +7848,11 @@ impl ProxyHttp for YatagarasuProxy {
         // Add peer context to error
         e = e.more_context(format!("Peer: {}", peer));
 
+        // Only origin-side errors count against the circuit breaker
+        if *e.esource() == pingora_core::ErrorSource::Upstream {
+            ctx.set_upstream_outcome(error_handler::classify_upstream_error(e.etype()));
+        }
+
         // Get bucket name from context to look up retry policy
         // Clone to owned String to avoid borrow conflicts
         let bucket_name = ctx
@@ -3769,7 +7880,7 @@ impl ProxyHttp for YatagarasuProxy {
                     // Track retry metric
                     self.metrics.increment_s3_retry_attempt(&bucket_name);
 
-                    tracing::warn!(
+                    bucket_log!(ctx, WARN,
                         request_id = %ctx.request_id(),
                         bucket = %bucket_name,
                         attempt = current_attempt,
@@ -3779,7 +7890,7 @@ impl ProxyHttp for YatagarasuProxy {
                         "Proxy error occurred, scheduling retry"
                     );
                 } else {
-                    tracing::warn!(
+                    bucket_log!(ctx, WARN,
                         request_id = %ctx.request_id(),
                         bucket = %bucket_name,
                         attempt = current_attempt,
@@ -3793,7 +7904,7 @@ impl ProxyHttp for YatagarasuProxy {
                 // Exhausted retries
                 self.metrics.increment_s3_retry_exhausted(&bucket_name);
 
-                tracing::error!(
+                bucket_log!(ctx, ERROR,
                     request_id = %ctx.request_id(),
                     bucket = %bucket_name,
                     attempt = current_attempt,
@@ -3957,4 +8068,265 @@ mod tests {
             log_line
         );
     }
+
+    #[tokio::test]
+    async fn test_batch_item_is_fetched_through_filesystem_origin() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(root.path().join("logo.txt"), b"hello origin").unwrap();
+        let config = Config::from_yaml_with_env(&format!(
+            r#"
+server:
+  address: "127.0.0.1"
+  port: 8080
+buckets:
+  - name: "assets"
+    path_prefix: "/assets"
+    origin:
+      type: filesystem
+      root: "{}"
+"#,
+            root.path().display()
+        ))
+        .unwrap();
+        let proxy = YatagarasuProxy::new(config.clone());
+        let router = Router::new(config.buckets.clone());
+        let fetch = |path: &'static str| {
+            proxy.fetch_batch_item(
+                path,
+                &config,
+                &router,
+                None,
+                &HashMap::new(),
+                &HashMap::new(),
+                "127.0.0.1",
+                None,
+            )
+        };
+
+        // Read from the origin root, not from an S3 bucket named after the section
+        let found = fetch("/assets/logo.txt").await;
+        assert_eq!(found.status, 200, "{:?}", found.error);
+        assert_eq!(found.size, Some(12));
+        assert_eq!(found.content_type.as_deref(), Some("text/plain"));
+        assert!(found.etag.is_some());
+
+        assert_eq!(fetch("/assets/missing.txt").await.status, 404);
+        assert_eq!(fetch("/assets/../secret.txt").await.status, 404);
+    }
+
+    #[tokio::test]
+    async fn test_batch_item_routes_like_a_single_request() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(root.path().join("logo.txt"), b"hello origin").unwrap();
+        let config = Config::from_yaml_with_env(&format!(
+            r#"
+server:
+  address: "127.0.0.1"
+  port: 8080
+buckets:
+  - name: "assets"
+    path_prefix: "/assets"
+    hostnames: ["cdn.example.com"]
+    aliases:
+      - path_prefix: "/static"
+    origin:
+      type: filesystem
+      root: "{}"
+"#,
+            root.path().display()
+        ))
+        .unwrap();
+        let proxy = YatagarasuProxy::new(config.clone());
+        let router = Router::new(config.buckets.clone());
+        let fetch = |host: Option<&'static str>, path: &'static str| {
+            proxy.fetch_batch_item(
+                path,
+                &config,
+                &router,
+                host,
+                &HashMap::new(),
+                &HashMap::new(),
+                "127.0.0.1",
+                None,
+            )
+        };
+
+        // Virtual host and alias paths resolve to the bucket's prefix
+        let by_host = fetch(Some("cdn.example.com"), "/logo.txt").await;
+        assert_eq!(by_host.status, 200, "{:?}", by_host.error);
+        assert_eq!(by_host.path, "/logo.txt");
+        let by_alias = fetch(None, "/static/logo.txt").await;
+        assert_eq!(by_alias.status, 200, "{:?}", by_alias.error);
+
+        // Other hosts route by path only
+        assert_eq!(
+            fetch(Some("other.example.com"), "/logo.txt").await.status,
+            404
+        );
+    }
+
+    #[tokio::test]
+    async fn test_batch_item_cache_population_follows_bucket_policy() {
+        let root = tempfile::tempdir().unwrap();
+        std::fs::write(root.path().join("logo.txt"), b"hello origin").unwrap();
+        std::fs::create_dir(root.path().join("private")).unwrap();
+        std::fs::write(root.path().join("private/key.txt"), b"secret").unwrap();
+        let config = Config::from_yaml_with_env(&format!(
+            r#"
+server:
+  address: "127.0.0.1"
+  port: 8080
+buckets:
+  - name: "assets"
+    path_prefix: "/assets"
+    origin:
+      type: filesystem
+      root: "{}"
+    cache:
+      ttl_seconds: 120
+      exclude_paths: ["private/**"]
+"#,
+            root.path().display()
+        ))
+        .unwrap();
+        let mut proxy = YatagarasuProxy::new(config.clone());
+        let memory: Arc<dyn Cache + Send + Sync> =
+            Arc::new(MemoryCache::new(&crate::cache::MemoryCacheConfig::default()));
+        let cache = Arc::new(TieredCache::new(vec![memory]));
+        proxy.cache = Some(cache.clone());
+        let router = Router::new(config.buckets.clone());
+        let fetch = |path: &'static str| {
+            proxy.fetch_batch_item(
+                path,
+                &config,
+                &router,
+                None,
+                &HashMap::new(),
+                &HashMap::new(),
+                "127.0.0.1",
+                None,
+            )
+        };
+        let cached = |object_key: &str| {
+            let key = CacheKey {
+                bucket: "assets".to_string(),
+                object_key: object_key.to_string(),
+                etag: None,
+                variant: None,
+            };
+            let cache = cache.clone();
+            async move { cache.get(&key).await.unwrap() }
+        };
+
+        // Validators and the bucket TTL are stored, as for a single GET
+        assert_eq!(fetch("/assets/logo.txt").await.status, 200);
+        let entry = cached("logo.txt").await.expect("batch item cached");
+        assert!(!entry.etag.is_empty());
+        assert!(entry.last_modified.is_some());
+        assert_eq!(
+            entry.expires_at.duration_since(entry.created_at).unwrap(),
+            Duration::from_secs(120)
+        );
+
+        // Keys the bucket's cache policy excludes are served but not cached
+        assert_eq!(fetch("/assets/private/key.txt").await.status, 200);
+        assert!(cached("private/key.txt").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_successful_writes_evict_cached_object_and_variants() {
+        let config = Config::from_yaml_with_env(
+            r#"
+server:
+  address: "127.0.0.1"
+  port: 8080
+buckets:
+  - name: "products"
+    path_prefix: "/products"
+    s3:
+      bucket: "products"
+      region: "us-east-1"
+      access_key: "key"
+      secret_key: "secret"
+"#,
+        )
+        .unwrap();
+        let mut proxy = YatagarasuProxy::new(config.clone());
+        let memory: Arc<dyn Cache + Send + Sync> =
+            Arc::new(MemoryCache::new(&crate::cache::MemoryCacheConfig::default()));
+        let cache = Arc::new(TieredCache::new(vec![memory]));
+        proxy.cache = Some(cache.clone());
+
+        let key = |variant: Option<&str>| CacheKey {
+            bucket: "products".to_string(),
+            object_key: "video.mp4".to_string(),
+            etag: None,
+            variant: variant.map(str::to_string),
+        };
+        let populate = || async {
+            for variant in [None, Some("w=200"), Some("range:1048576:0")] {
+                let entry = crate::cache::CacheEntry::new(
+                    bytes::Bytes::from_static(b"old body"),
+                    "video/mp4".to_string(),
+                    "\"v1\"".to_string(),
+                    None,
+                    None,
+                );
+                cache.set(key(variant), entry).await.unwrap();
+            }
+        };
+        let mut ctx = RequestContext::new("POST".to_string(), "/products/video.mp4".to_string());
+        ctx.set_bucket_config(config.buckets[0].clone());
+        ctx.set_routing(0, "video.mp4".to_string());
+
+        // An UploadPart response leaves the cached object alone
+        populate().await;
+        let mut part_ctx = ctx.clone();
+        part_ctx.set_method("PUT".to_string());
+        proxy
+            .invalidate_replaced_object(&part_ctx, Some("partNumber=1&uploadId=abc"), true)
+            .await;
+        assert!(cache.get(&key(None)).await.unwrap().is_some());
+
+        // A failed CompleteMultipartUpload leaves it too
+        proxy
+            .invalidate_replaced_object(&ctx, Some("uploadId=abc"), false)
+            .await;
+        assert!(cache.get(&key(None)).await.unwrap().is_some());
+
+        // A completed upload evicts the base entry and every variant
+        proxy
+            .invalidate_replaced_object(&ctx, Some("uploadId=abc"), true)
+            .await;
+        for variant in [None, Some("w=200"), Some("range:1048576:0")] {
+            assert!(
+                cache.get(&key(variant)).await.unwrap().is_none(),
+                "{:?} still cached",
+                variant
+            );
+        }
+
+        // So does a successful plain PUT or DELETE forwarded to S3
+        for method in ["PUT", "DELETE"] {
+            populate().await;
+            let mut write_ctx = ctx.clone();
+            write_ctx.set_method(method.to_string());
+            proxy
+                .invalidate_replaced_object(&write_ctx, None, false)
+                .await;
+            assert!(cache.get(&key(None)).await.unwrap().is_some());
+
+            proxy
+                .invalidate_replaced_object(&write_ctx, None, true)
+                .await;
+            for variant in [None, Some("w=200"), Some("range:1048576:0")] {
+                assert!(
+                    cache.get(&key(variant)).await.unwrap().is_none(),
+                    "{:?} still cached after {}",
+                    variant,
+                    method
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,235 @@
+//! Background probes for circuit breakers due a half-open test.
+//!
+//! Without probes an open breaker only recovers when a client request arrives
+//! after the timeout and is used as the test request. The prober claims those
+//! half-open slots itself every `interval_secs` and sends a `HEAD` on the
+//! bucket. Any S3 answer below 500 counts as a success; 5xx, timeouts and
+//! connection errors reopen the breaker.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::circuit_breaker::CircuitBreaker;
+use crate::config::{BucketConfig, OriginProbeConfig};
+use crate::metrics::Metrics;
+use crate::replica_set::ReplicaSet;
+use crate::s3::S3Client;
+
+/// A breaker and the client used to probe its origin
+struct ProbeTarget {
+    /// Bucket name, or `bucket/replica` for replica breakers
+    name: String,
+    client: S3Client,
+    breaker: Arc<CircuitBreaker>,
+}
+
+/// Periodic HEAD probes for open and half-open breakers
+pub struct OriginProber {
+    interval: Duration,
+    timeout: Duration,
+    targets: Vec<ProbeTarget>,
+    metrics: Arc<Metrics>,
+}
+
+impl OriginProber {
+    /// Build the prober from config, or `None` when probing is disabled or
+    /// there are no breakers to probe
+    pub fn from_config(
+        config: &OriginProbeConfig,
+        buckets: &[BucketConfig],
+        circuit_breakers: &HashMap<String, Arc<CircuitBreaker>>,
+        replica_sets: &HashMap<String, ReplicaSet>,
+        metrics: Arc<Metrics>,
+    ) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+
+        let mut targets = Vec::new();
+        for bucket in buckets {
+            // Probes are S3 HeadBucket calls; non-S3 origins recover on client requests
+            if bucket.origin.is_some() {
+                continue;
+            }
+            let replica_set = replica_sets.get(&bucket.name);
+            // Bucket breakers guard whichever replica is preferred
+            if let Some(breaker) = circuit_breakers.get(&bucket.name) {
+                let client = replica_set
+                    .and_then(|set| set.replicas.first())
+                    .map(|replica| replica.client.clone())
+                    .unwrap_or_else(|| S3Client {
+                        config: bucket.s3.clone(),
+                    });
+                targets.push(ProbeTarget {
+                    name: bucket.name.clone(),
+                    client,
+                    breaker: breaker.clone(),
+                });
+            }
+            for replica in replica_set
+                .map(|set| set.replicas.as_slice())
+                .unwrap_or(&[])
+            {
+                targets.push(ProbeTarget {
+                    name: format!("{}/{}", bucket.name, replica.name),
+                    client: replica.client.clone(),
+                    // Clones share the breaker's state
+                    breaker: Arc::new(replica.circuit_breaker.clone()),
+                });
+            }
+        }
+        if targets.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            interval: Duration::from_secs(config.interval_secs),
+            timeout: Duration::from_secs(config.timeout_secs),
+            targets,
+            metrics,
+        })
+    }
+
+    /// Start the probe loop (requires a Tokio runtime)
+    pub fn start(self: &Arc<Self>) {
+        let prober = Arc::downgrade(self);
+        let interval = self.interval;
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                // Stop once the proxy (and its prober) is dropped, e.g. after reload
+                let Some(prober) = prober.upgrade() else {
+                    break;
+                };
+                prober.probe_due().await;
+            }
+        });
+    }
+
+    /// Probe every breaker that is due a half-open test
+    async fn probe_due(&self) {
+        let probes = self
+            .targets
+            .iter()
+            .filter(|target| target.breaker.try_start_probe())
+            .map(|target| self.probe(target));
+        futures::future::join_all(probes).await;
+    }
+
+    async fn probe(&self, target: &ProbeTarget) {
+        let outcome = tokio::time::timeout(self.timeout, target.client.head_bucket_status()).await;
+        let healthy = match &outcome {
+            Ok(Ok(status)) => *status < 500,
+            _ => false,
+        };
+
+        if healthy {
+            target.breaker.record_success();
+            self.metrics.increment_origin_probe(&target.name, "success");
+        } else {
+            target.breaker.record_failure();
+            self.metrics.increment_origin_probe(&target.name, "failure");
+        }
+
+        tracing::info!(
+            target = %target.name,
+            healthy = healthy,
+            outcome = ?outcome.map_err(|_| "timeout"),
+            state = ?target.breaker.state(),
+            "Origin probe completed"
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::circuit_breaker::CircuitBreakerConfig;
+
+    fn bucket(name: &str) -> BucketConfig {
+        serde_yaml::from_str(&format!(
+            r#"
+name: "{name}"
+path_prefix: "/{name}"
+s3:
+  bucket: "{name}"
+  region: "us-east-1"
+  access_key: "test-key"
+  secret_key: "test-secret"
+"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_prober_targets() {
+        let buckets = vec![bucket("media"), bucket("docs")];
+        let mut breakers = HashMap::new();
+        breakers.insert(
+            "media".to_string(),
+            Arc::new(CircuitBreaker::new(CircuitBreakerConfig::default())),
+        );
+        let enabled = OriginProbeConfig {
+            enabled: true,
+            ..Default::default()
+        };
+        let metrics = Arc::new(Metrics::new());
+
+        let prober = OriginProber::from_config(
+            &enabled,
+            &buckets,
+            &breakers,
+            &HashMap::new(),
+            metrics.clone(),
+        )
+        .unwrap();
+        assert_eq!(prober.targets.len(), 1);
+        assert_eq!(prober.targets[0].name, "media");
+
+        assert!(OriginProber::from_config(
+            &OriginProbeConfig::default(),
+            &buckets,
+            &breakers,
+            &HashMap::new(),
+            metrics.clone(),
+        )
+        .is_none());
+        assert!(OriginProber::from_config(
+            &enabled,
+            &buckets,
+            &HashMap::new(),
+            &HashMap::new(),
+            metrics.clone(),
+        )
+        .is_none());
+
+        // A bucket behind a non-S3 origin has no bucket to HEAD
+        let assets: BucketConfig = serde_yaml::from_str(
+            r#"
+name: "assets"
+path_prefix: "/assets"
+origin:
+  type: http
+  url: "https://assets.internal/static"
+"#,
+        )
+        .unwrap();
+        let mut breakers = HashMap::new();
+        breakers.insert(
+            "assets".to_string(),
+            Arc::new(CircuitBreaker::new(CircuitBreakerConfig::default())),
+        );
+        assert!(OriginProber::from_config(
+            &enabled,
+            &[assets],
+            &breakers,
+            &HashMap::new(),
+            metrics,
+        )
+        .is_none());
+    }
+}
@@ -0,0 +1,19 @@
+//! Replay of upstream headers stored with cache entries.
+//!
+//! Applied to every response built from the cache (full hits, disk streaming,
+//! Range chunks and 304s) so hits carry the same `Content-Disposition`,
+//! `Cache-Control` or `x-amz-meta-*` headers as the response that filled the
+//! entry; see [`crate::cache::preserve`].
+
+use pingora_http::ResponseHeader;
+
+/// Add the headers stored with a cache entry
+pub fn apply(
+    header: &mut ResponseHeader,
+    headers: &[(String, String)],
+) -> pingora_core::Result<()> {
+    for (name, value) in headers {
+        header.append_header(name.clone(), value.as_str())?;
+    }
+    Ok(())
+}
@@ -0,0 +1,113 @@
+//! Request priority for the proxy.
+//!
+//! The concurrency semaphore is split in two: normal requests draw from the
+//! general pool, while high-priority requests from trusted clients fall back
+//! to a reserved pool once the general one is exhausted. High-priority
+//! requests also bypass resource-based load shedding.
+
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+use crate::config::PriorityConfig;
+use crate::security::IpRange;
+
+/// Priority classification and reserved concurrency slice
+pub struct PriorityGate {
+    header: String,
+    trusted_clients: Vec<IpRange>,
+    reserved: Arc<Semaphore>,
+}
+
+impl PriorityGate {
+    /// Build the gate from config, or `None` when priority is disabled
+    pub fn from_config(config: &PriorityConfig) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+        Some(Self {
+            header: config.header.clone(),
+            // Entries are checked by PriorityConfig::validate at load time
+            trusted_clients: config
+                .trusted_clients
+                .iter()
+                .filter_map(|entry| IpRange::parse(entry).ok())
+                .collect(),
+            reserved: Arc::new(Semaphore::new(config.reserved_permits)),
+        })
+    }
+
+    /// Header carrying the priority
+    pub fn header(&self) -> &str {
+        &self.header
+    }
+
+    /// Check if a request is high priority
+    ///
+    /// The header must be `high` (case-insensitive) and the direct client IP
+    /// must be trusted; untrusted clients claiming priority are treated as normal.
+    pub fn is_high_priority(&self, header_value: Option<&str>, client_ip: Option<IpAddr>) -> bool {
+        let requested = header_value.is_some_and(|value| value.trim().eq_ignore_ascii_case("high"));
+        requested
+            && client_ip
+                .is_some_and(|ip| self.trusted_clients.iter().any(|range| range.contains(&ip)))
+    }
+
+    /// Take a permit from the reserved pool, if one is free
+    pub fn try_acquire_reserved(&self) -> Option<SemaphorePermit<'_>> {
+        self.reserved.try_acquire().ok()
+    }
+
+    /// Free permits in the reserved pool
+    pub fn available_reserved(&self) -> usize {
+        self.reserved.available_permits()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn gate(reserved_permits: usize) -> PriorityGate {
+        PriorityGate::from_config(&PriorityConfig {
+            enabled: true,
+            reserved_permits,
+            trusted_clients: vec!["10.0.0.0/8".to_string(), "::1".to_string()],
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_disabled_config_has_no_gate() {
+        assert!(PriorityGate::from_config(&PriorityConfig::default()).is_none());
+    }
+
+    #[test]
+    fn test_high_priority_requires_trusted_client() {
+        let gate = gate(1);
+        let internal: IpAddr = "10.1.2.3".parse().unwrap();
+        let external: IpAddr = "203.0.113.7".parse().unwrap();
+
+        assert!(gate.is_high_priority(Some("high"), Some(internal)));
+        assert!(gate.is_high_priority(Some(" HIGH "), Some("::1".parse().unwrap())));
+        assert!(!gate.is_high_priority(Some("high"), Some(external)));
+        assert!(!gate.is_high_priority(Some("low"), Some(internal)));
+        assert!(!gate.is_high_priority(None, Some(internal)));
+        assert!(!gate.is_high_priority(Some("high"), None));
+    }
+
+    #[test]
+    fn test_reserved_permits_are_bounded() {
+        let gate = gate(2);
+        let first = gate.try_acquire_reserved();
+        let second = gate.try_acquire_reserved();
+        assert!(first.is_some() && second.is_some());
+        assert!(gate.try_acquire_reserved().is_none());
+        assert_eq!(gate.available_reserved(), 0);
+
+        drop(first);
+        assert!(gate.try_acquire_reserved().is_some());
+    }
+}
@@ -0,0 +1,144 @@
+//! Bounded request queue in front of the concurrency semaphore.
+//!
+//! A request that finds no free permit waits up to `max_wait_ms` for one
+//! instead of getting an immediate 503. The number of waiting requests is
+//! capped at `max_length`; a request arriving at a full queue is rejected
+//! right away.
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
+
+use tokio::sync::{Semaphore, SemaphorePermit};
+
+use crate::config::RequestQueueConfig;
+
+/// Why a queued request did not get a permit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueueRejection {
+    /// `max_length` requests were already waiting
+    Full,
+    /// No permit was freed within `max_wait_ms`
+    TimedOut,
+}
+
+impl QueueRejection {
+    /// Metric label for the outcome
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            QueueRejection::Full => "full",
+            QueueRejection::TimedOut => "timeout",
+        }
+    }
+}
+
+/// Wait queue for concurrency permits
+pub struct RequestQueue {
+    max_wait: Duration,
+    max_length: usize,
+    waiting: AtomicUsize,
+}
+
+/// Keeps the waiting count accurate even if the request is dropped mid-wait
+struct WaitingGuard<'a>(&'a AtomicUsize);
+
+impl Drop for WaitingGuard<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+impl RequestQueue {
+    /// Build the queue from config, or `None` when queuing is disabled
+    pub fn from_config(config: &RequestQueueConfig) -> Option<Self> {
+        if !config.enabled {
+            return None;
+        }
+        Some(Self {
+            max_wait: Duration::from_millis(config.max_wait_ms),
+            max_length: config.max_length,
+            waiting: AtomicUsize::new(0),
+        })
+    }
+
+    /// Wait for a permit from `semaphore`, bounded by queue length and wait time
+    pub async fn acquire<'a>(
+        &self,
+        semaphore: &'a Semaphore,
+    ) -> Result<SemaphorePermit<'a>, QueueRejection> {
+        let claimed = self
+            .waiting
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |waiting| {
+                (waiting < self.max_length).then_some(waiting + 1)
+            });
+        if claimed.is_err() {
+            return Err(QueueRejection::Full);
+        }
+        let _guard = WaitingGuard(&self.waiting);
+
+        match tokio::time::timeout(self.max_wait, semaphore.acquire()).await {
+            Ok(Ok(permit)) => Ok(permit),
+            // Timed out, or the semaphore was closed
+            _ => Err(QueueRejection::TimedOut),
+        }
+    }
+
+    /// Requests currently waiting for a permit
+    pub fn waiting(&self) -> usize {
+        self.waiting.load(Ordering::Relaxed)
+    }
+
+    /// Export the queue depth gauge for Prometheus
+    pub fn export_metrics(&self) -> String {
+        format!(
+            "\n# HELP yatagarasu_request_queue_depth Requests waiting for a concurrency permit\n\
+             # TYPE yatagarasu_request_queue_depth gauge\n\
+             yatagarasu_request_queue_depth {}\n",
+            self.waiting()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn queue(max_wait_ms: u64, max_length: usize) -> RequestQueue {
+        RequestQueue::from_config(&RequestQueueConfig {
+            enabled: true,
+            max_wait_ms,
+            max_length,
+        })
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_queued_request_gets_released_permit() {
+        let semaphore = Semaphore::new(1);
+        let queue = queue(1000, 10);
+        let held = semaphore.try_acquire().unwrap();
+
+        let (result, _) = tokio::join!(queue.acquire(&semaphore), async {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            drop(held);
+        });
+        assert!(result.is_ok());
+        assert_eq!(queue.waiting(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_queue_times_out_and_caps_length() {
+        let semaphore = Semaphore::new(1);
+        let _held = semaphore.try_acquire().unwrap();
+
+        let queue = queue(20, 1);
+        let (first, second) = tokio::join!(queue.acquire(&semaphore), queue.acquire(&semaphore));
+        assert_eq!(first.unwrap_err(), QueueRejection::TimedOut);
+        assert_eq!(second.unwrap_err(), QueueRejection::Full);
+        assert_eq!(queue.waiting(), 0);
+        assert!(queue
+            .export_metrics()
+            .contains("yatagarasu_request_queue_depth 0"));
+
+        assert!(RequestQueue::from_config(&RequestQueueConfig::default()).is_none());
+    }
+}
@@ -6,17 +6,18 @@
 //!
 //! # Design
 //!
-//! The request filter follows a 10-stage pipeline:
+//! The request filter follows an 11-stage pipeline:
 //! 1. Resource checks (concurrency, load)
 //! 2. Security validation
-//! 3. Special endpoint handling
-//! 4. Routing to bucket
-//! 5. Rate limiting
-//! 6. Circuit breaker
-//! 7. Authentication
-//! 8. Authorization
-//! 9. Cache lookup
-//! 10. Upstream forwarding
+//! 3. HTTP method check (per-bucket allowed methods)
+//! 4. Special endpoint handling
+//! 5. Routing to bucket
+//! 6. Rate limiting
+//! 7. Circuit breaker
+//! 8. Authentication
+//! 9. Authorization
+//! 10. Cache lookup
+//! 11. Upstream forwarding
 //!
 //! Each stage can either continue to the next stage or short-circuit
 //! with a response (success or error).
@@ -33,8 +34,10 @@ use std::time::Duration;
 pub enum RequestStage {
     /// Initial stage - check resource availability.
     ResourceCheck,
-    /// Security validation (method, path, headers).
+    /// Security validation (path, headers).
     SecurityValidation,
+    /// HTTP method check against the bucket's allowed methods.
+    MethodCheck,
     /// Special endpoint handling (health, metrics).
     SpecialEndpoint,
     /// Route request to bucket.
@@ -58,6 +61,7 @@ impl RequestStage {
     const STAGES: &'static [Self] = &[
         Self::ResourceCheck,
         Self::SecurityValidation,
+        Self::MethodCheck,
         Self::SpecialEndpoint,
         Self::Routing,
         Self::RateLimiting,
@@ -79,6 +83,7 @@ impl RequestStage {
         match self {
             RequestStage::ResourceCheck => "resource_check",
             RequestStage::SecurityValidation => "security_validation",
+            RequestStage::MethodCheck => "method_check",
             RequestStage::SpecialEndpoint => "special_endpoint",
             RequestStage::Routing => "routing",
             RequestStage::RateLimiting => "rate_limiting",
@@ -307,6 +312,53 @@ impl RateLimitResult {
     }
 }
 
+// ============================================================================
+// Method Check Results
+// ============================================================================
+
+/// Result of the HTTP method check.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MethodCheckResult {
+    /// Method accepted.
+    Allowed,
+    /// Method rejected.
+    NotAllowed {
+        /// Value for the `Allow` response header.
+        allow: String,
+    },
+}
+
+impl MethodCheckResult {
+    /// Check if the method is accepted.
+    pub fn is_allowed(&self) -> bool {
+        matches!(self, MethodCheckResult::Allowed)
+    }
+
+    /// Convert to stage outcome.
+    pub fn as_outcome(&self) -> StageOutcome {
+        match self {
+            MethodCheckResult::Allowed => StageOutcome::Continue,
+            MethodCheckResult::NotAllowed { allow } => {
+                StageOutcome::error(405, format!("Allowed methods: {}", allow))
+            }
+        }
+    }
+}
+
+/// Check `method` against a bucket's allowed methods (uppercased).
+pub fn check_method(method: &str, allowed_methods: &[String]) -> MethodCheckResult {
+    if allowed_methods
+        .iter()
+        .any(|allowed| allowed.eq_ignore_ascii_case(method))
+    {
+        MethodCheckResult::Allowed
+    } else {
+        MethodCheckResult::NotAllowed {
+            allow: allowed_methods.join(", "),
+        }
+    }
+}
+
 // ============================================================================
 // Circuit Breaker Results
 // ============================================================================
@@ -597,8 +649,8 @@ mod tests {
             count += 1;
         }
 
-        // Should have 10 stages total
-        assert_eq!(count, 10);
+        // Should have 11 stages total
+        assert_eq!(count, 11);
         assert_eq!(stage, RequestStage::UpstreamForward);
     }
 
@@ -693,6 +745,29 @@ mod tests {
         assert!(!result.as_outcome().should_continue());
     }
 
+    // -- Method check tests --
+
+    #[test]
+    fn test_method_check_allowed() {
+        let allowed = vec!["GET".to_string(), "HEAD".to_string()];
+        let result = check_method("head", &allowed);
+        assert!(result.is_allowed());
+        assert!(result.as_outcome().should_continue());
+    }
+
+    #[test]
+    fn test_method_check_not_allowed() {
+        let allowed = vec!["HEAD".to_string()];
+        let result = check_method("GET", &allowed);
+        assert_eq!(
+            result,
+            MethodCheckResult::NotAllowed {
+                allow: "HEAD".to_string()
+            }
+        );
+        assert!(!result.as_outcome().should_continue());
+    }
+
     // -- Circuit breaker tests --
 
     #[test]
@@ -27,6 +27,7 @@ use std::sync::Arc;
 use crate::auth::{authenticate_request, AuthError, Claims};
 use crate::circuit_breaker::{CircuitBreaker, CircuitState};
 use crate::config::{BucketConfig, JwtConfig};
+use crate::error::ErrorCode;
 use crate::metrics::Metrics;
 use crate::opa::{
     AuthorizationDecision as OpaAuthorizationDecision, FailMode as OpaFailMode, OpaCache, OpaInput,
@@ -195,8 +196,8 @@ pub fn authenticate_jwt(
         return AuthenticationResult::MissingToken;
     };
 
-    // Perform authentication
-    match authenticate_request(headers, query_params, jwt_config) {
+    // Perform authentication with the bucket's key and claim overrides
+    match authenticate_request(headers, query_params, &auth_config.jwt_config(jwt_config)) {
         Ok(claims) => AuthenticationResult::Authenticated(claims),
         Err(AuthError::MissingToken) => AuthenticationResult::MissingToken,
         Err(_) => AuthenticationResult::InvalidToken,
@@ -376,6 +377,7 @@ pub async fn authorize_with_openfga(
 pub fn build_rate_limit_error_body(error: &RateLimitError) -> String {
     serde_json::json!({
         "error": "Too Many Requests",
+        "code": error.error_code().as_str(),
         "message": error.to_string(),
         "status": 429
     })
@@ -386,6 +388,7 @@ pub fn build_rate_limit_error_body(error: &RateLimitError) -> String {
 pub fn build_circuit_breaker_error_body(bucket_name: &str) -> String {
     serde_json::json!({
         "error": "Service Temporarily Unavailable",
+        "code": ErrorCode::OriginUnavailable.as_str(),
         "message": "S3 backend is experiencing issues. Circuit breaker is open.",
         "bucket": bucket_name,
         "status": 503
@@ -500,6 +503,29 @@ mod tests {
             authorization: None,
             ip_filter: Default::default(),
             watermark: None,
+            logging: None,
+            resume: None,
+            content_type: None,
+            hotlink: None,
+            allowed_methods: None,
+            cors: None,
+            degraded: None,
+            server_timing: None,
+            early_hints: None,
+            etag_rewrite: None,
+            listing: None,
+            origin: None,
+            aliases: Vec::new(),
+            version: None,
+            hostnames: Vec::new(),
+            routes: Vec::new(),
+            upload_scan: None,
+            deprecation: None,
+            signed_url: None,
+            sigv4_auth: None,
+            post_policy: None,
+            presigned_redirect: None,
+            size_classes: Vec::new(),
         };
 
         let result = authenticate_jwt(&bucket_config, None, &HashMap::new(), &HashMap::new());
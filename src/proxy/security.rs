@@ -14,6 +14,7 @@
 //! The caller is responsible for writing the HTTP response and updating metrics.
 //! This separation avoids borrow checker issues with session references.
 
+use crate::error::ErrorCode;
 use crate::metrics::Metrics;
 use crate::security::{self, SecurityLimits};
 
@@ -24,15 +25,18 @@ pub struct SecurityViolation {
     pub status: u16,
     /// JSON error response body
     pub error_body: String,
+    /// Machine-readable error code
+    pub code: ErrorCode,
     /// Action to update metrics
     pub metric_action: SecurityMetricAction,
 }
 
 /// Metric action to take after a security violation.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub enum SecurityMetricAction {
     UriTooLong,
     HeadersTooLarge,
+    HeaderValueTooLong { header: String },
     PayloadTooLarge,
     PathTraversalBlocked,
     SqlInjectionBlocked,
@@ -45,6 +49,9 @@ impl SecurityMetricAction {
         match self {
             SecurityMetricAction::UriTooLong => metrics.increment_security_uri_too_long(),
             SecurityMetricAction::HeadersTooLarge => metrics.increment_security_headers_too_large(),
+            SecurityMetricAction::HeaderValueTooLong { header } => {
+                metrics.increment_oversized_header(header)
+            }
             SecurityMetricAction::PayloadTooLarge => metrics.increment_security_payload_too_large(),
             SecurityMetricAction::PathTraversalBlocked => {
                 metrics.increment_security_path_traversal_blocked()
@@ -57,9 +64,10 @@ impl SecurityMetricAction {
 }
 
 /// Build a JSON error response body.
-fn build_error_body(error_type: &str, message: &str, status: u16) -> String {
+fn build_error_body(error_type: &str, message: &str, status: u16, code: ErrorCode) -> String {
     serde_json::json!({
         "error": error_type,
+        "code": code.as_str(),
         "message": message,
         "status": status
     })
@@ -88,7 +96,13 @@ pub fn check_uri_length(
 
         return Some(SecurityViolation {
             status: 414,
-            error_body: build_error_body("URI Too Long", &security_error.to_string(), 414),
+            error_body: build_error_body(
+                "URI Too Long",
+                &security_error.to_string(),
+                414,
+                ErrorCode::UriTooLong,
+            ),
+            code: ErrorCode::UriTooLong,
             metric_action: SecurityMetricAction::UriTooLong,
         });
     }
@@ -121,13 +135,55 @@ pub fn check_header_size(
                 "Request Header Fields Too Large",
                 &security_error.to_string(),
                 431,
+                ErrorCode::HeadersTooLarge,
             ),
+            code: ErrorCode::HeadersTooLarge,
             metric_action: SecurityMetricAction::HeadersTooLarge,
         });
     }
     None
 }
 
+/// Validate each header value length against configured limits.
+///
+/// Takes `(lowercase name, value length)` pairs.
+///
+/// Returns `None` if validation passed.
+/// Returns `Some(SecurityViolation)` naming the first oversized header.
+pub fn check_header_values<'a>(
+    request_id: &str,
+    client_ip: &str,
+    headers: impl IntoIterator<Item = (&'a str, usize)>,
+    limits: &SecurityLimits,
+) -> Option<SecurityViolation> {
+    if let Err(security_error) = security::validate_header_values(headers, limits) {
+        let header = match &security_error {
+            security::SecurityError::HeaderValueTooLong { name, .. } => name.clone(),
+            _ => String::new(),
+        };
+        tracing::warn!(
+            request_id = %request_id,
+            client_ip = %client_ip,
+            header = %header,
+            error = %security_error,
+            "Header value too long"
+        );
+
+        return Some(SecurityViolation {
+            status: 431,
+            error_body: build_error_body(
+                "Request Header Fields Too Large",
+                &security_error.to_string(),
+                431,
+                ErrorCode::HeadersTooLarge,
+            ),
+            code: ErrorCode::HeadersTooLarge,
+            metric_action: SecurityMetricAction::HeaderValueTooLong { header },
+        });
+    }
+    None
+}
+
 /// Validate request body size from Content-Length header.
 ///
 /// Returns `None` if validation passed.
@@ -150,7 +206,13 @@ pub fn check_body_size(
 
         return Some(SecurityViolation {
             status: 413,
-            error_body: build_error_body("Payload Too Large", &security_error.to_string(), 413),
+            error_body: build_error_body(
+                "Payload Too Large",
+                &security_error.to_string(),
+                413,
+                ErrorCode::PayloadTooLarge,
+            ),
+            code: ErrorCode::PayloadTooLarge,
             metric_action: SecurityMetricAction::PayloadTooLarge,
         });
     }
@@ -177,7 +239,13 @@ pub fn check_path_traversal(
 
         return Some(SecurityViolation {
             status: 400,
-            error_body: build_error_body("Bad Request", &security_error.to_string(), 400),
+            error_body: build_error_body(
+                "Bad Request",
+                &security_error.to_string(),
+                400,
+                ErrorCode::PathTraversal,
+            ),
+            code: ErrorCode::PathTraversal,
             metric_action: SecurityMetricAction::PathTraversalBlocked,
         });
     }
@@ -204,7 +272,13 @@ pub fn check_sql_injection(
 
         return Some(SecurityViolation {
             status: 400,
-            error_body: build_error_body("Bad Request", &security_error.to_string(), 400),
+            error_body: build_error_body(
+                "Bad Request",
+                &security_error.to_string(),
+                400,
+                ErrorCode::SqlInjection,
+            ),
+            code: ErrorCode::SqlInjection,
             metric_action: SecurityMetricAction::SqlInjectionBlocked,
         });
     }
@@ -274,12 +348,18 @@ mod tests {
 
     #[test]
     fn test_build_error_body() {
-        let body = build_error_body("Test Error", "Something went wrong", 400);
+        let body = build_error_body(
+            "Test Error",
+            "Something went wrong",
+            400,
+            ErrorCode::BadRequest,
+        );
         let parsed: serde_json::Value = serde_json::from_str(&body).unwrap();
 
         assert_eq!(parsed["error"], "Test Error");
         assert_eq!(parsed["message"], "Something went wrong");
         assert_eq!(parsed["status"], 400);
+        assert_eq!(parsed["code"], "YAT-REQ-001");
     }
 
     #[test]
@@ -356,4 +436,22 @@ mod tests {
         assert!(result.is_some());
         assert_eq!(result.unwrap().status, 414);
     }
+
+    #[test]
+    fn test_check_header_values_names_oversized_header() {
+        let limits = SecurityLimits {
+            max_header_value_length: 16,
+            ..SecurityLimits::default()
+        };
+        assert!(check_header_values("test-req", "127.0.0.1", [("accept", 3)], &limits).is_none());
+
+        let violation =
+            check_header_values("test-req", "127.0.0.1", [("x-custom", 17)], &limits).unwrap();
+        assert_eq!(violation.status, 431);
+        assert!(violation.error_body.contains("x-custom"));
+        assert!(matches!(
+            violation.metric_action,
+            SecurityMetricAction::HeaderValueTooLong { ref header } if header == "x-custom"
+        ));
+    }
 }
@@ -0,0 +1,66 @@
+//! `Server-Timing` header for the proxy.
+//!
+//! Built from the phase durations recorded on the [`RequestContext`]: cache
+//! hits report `authz` and `cache`, streamed responses add `upstream` (time
+//! to the upstream response headers). `total` is the time from receiving the
+//! request to sending the response headers.
+
+use pingora_http::ResponseHeader;
+
+use crate::config::BucketConfig;
+use crate::pipeline::RequestContext;
+
+/// Header value, e.g. `authz;dur=0.4, cache;dur=1.2, total;dur=2.0`
+pub fn header_value(ctx: &RequestContext) -> String {
+    let mut metrics: Vec<String> = ctx
+        .phase_timings()
+        .iter()
+        .map(|(phase, duration)| {
+            format!(
+                "{};dur={:.1}",
+                phase.as_str(),
+                duration.as_secs_f64() * 1000.0
+            )
+        })
+        .collect();
+    metrics.push(format!(
+        "total;dur={:.1}",
+        ctx.elapsed().as_secs_f64() * 1000.0
+    ));
+    metrics.join(", ")
+}
+
+/// Add `Server-Timing` when the bucket has it enabled
+pub fn apply(
+    header: &mut ResponseHeader,
+    bucket: &BucketConfig,
+    ctx: &RequestContext,
+) -> pingora_core::Result<()> {
+    if bucket.server_timing.as_ref().is_some_and(|st| st.enabled) {
+        header.insert_header("Server-Timing", header_value(ctx))?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pipeline::Phase;
+    use std::time::Duration;
+
+    #[test]
+    fn test_header_value_lists_recorded_phases() {
+        let mut ctx = RequestContext::new("GET".to_string(), "/a".to_string());
+        assert!(header_value(&ctx).starts_with("total;dur="));
+
+        ctx.record_phase(Phase::Authz, Duration::from_micros(400));
+        ctx.record_phase(Phase::Cache, Duration::from_millis(3));
+        ctx.record_phase(Phase::Cache, Duration::from_micros(1240));
+        let value = header_value(&ctx);
+        assert!(
+            value.starts_with("authz;dur=0.4, cache;dur=1.2, total;dur="),
+            "{}",
+            value
+        );
+    }
+}
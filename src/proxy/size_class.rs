@@ -0,0 +1,98 @@
+//! Object sizes learned from upstream responses, for size-class policies.
+//!
+//! Timeouts and replica choice are made before the upstream request, when the
+//! object's size is not known yet. Each GET or HEAD response from a bucket
+//! with size classes records the object's size (`Content-Length`, or the
+//! total from `Content-Range` for a 206), and later requests for the same
+//! object use it. The table is bounded; when full, an arbitrary entry makes
+//! room for the new one.
+
+use parking_lot::Mutex;
+use pingora_core::upstreams::peer::HttpPeer;
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::config::SizeClassConfig;
+
+/// Last known size per `bucket/key`
+pub struct ObjectSizeTracker {
+    sizes: Mutex<HashMap<String, u64>>,
+    max_tracked: usize,
+}
+
+impl ObjectSizeTracker {
+    /// Create a tracker remembering at most `max_tracked` objects
+    pub fn new(max_tracked: usize) -> Self {
+        Self {
+            sizes: Mutex::new(HashMap::new()),
+            max_tracked,
+        }
+    }
+
+    /// Remember the size of an object
+    pub fn record(&self, bucket: &str, key: &str, size: u64) {
+        let object = format!("{}/{}", bucket, key);
+        let mut sizes = self.sizes.lock();
+        if !sizes.contains_key(&object) && sizes.len() >= self.max_tracked {
+            if let Some(evicted) = sizes.keys().next().cloned() {
+                sizes.remove(&evicted);
+            }
+        }
+        sizes.insert(object, size);
+    }
+
+    /// Last known size of an object
+    pub fn get(&self, bucket: &str, key: &str) -> Option<u64> {
+        self.sizes
+            .lock()
+            .get(&format!("{}/{}", bucket, key))
+            .copied()
+    }
+}
+
+/// Replace the peer's read and write timeouts with the class timeout, if any
+pub fn apply_timeout(peer: &mut HttpPeer, class: Option<&SizeClassConfig>) {
+    if let Some(timeout_secs) = class.and_then(|c| c.timeout_secs) {
+        let timeout = Duration::from_secs(timeout_secs);
+        peer.options.read_timeout = Some(timeout);
+        peer.options.write_timeout = Some(timeout);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tracker_records_and_bounds_sizes() {
+        let tracker = ObjectSizeTracker::new(2);
+        tracker.record("media", "a.mp4", 10);
+        tracker.record("media", "b.mp4", 20);
+        tracker.record("media", "a.mp4", 15);
+        assert_eq!(tracker.get("media", "a.mp4"), Some(15));
+        assert_eq!(tracker.get("other", "a.mp4"), None);
+
+        tracker.record("media", "c.mp4", 30);
+        assert_eq!(tracker.get("media", "c.mp4"), Some(30));
+        assert_eq!(tracker.sizes.lock().len(), 2);
+    }
+
+    #[test]
+    fn test_apply_timeout() {
+        let mut peer = HttpPeer::new("127.0.0.1:9000", false, String::new());
+        peer.options.read_timeout = Some(Duration::from_secs(20));
+        apply_timeout(&mut peer, None);
+        assert_eq!(peer.options.read_timeout, Some(Duration::from_secs(20)));
+
+        let class = SizeClassConfig {
+            name: "huge".to_string(),
+            max_bytes: None,
+            cache: Default::default(),
+            timeout_secs: Some(600),
+            replica: None,
+        };
+        apply_timeout(&mut peer, Some(&class));
+        assert_eq!(peer.options.read_timeout, Some(Duration::from_secs(600)));
+        assert_eq!(peer.options.write_timeout, Some(Duration::from_secs(600)));
+    }
+}
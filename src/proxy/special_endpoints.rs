@@ -15,7 +15,7 @@ use std::collections::HashMap;
 use std::time::Instant;
 
 use crate::circuit_breaker::CircuitState;
-use crate::config::BucketConfig;
+use crate::config::{BucketConfig, ReadinessMode};
 use crate::metrics::Metrics;
 use crate::replica_set::ReplicaSet;
 
@@ -70,14 +70,19 @@ pub fn handle_health(start_time: Instant) -> EndpointResponse {
 /// Generate response for /ready endpoint.
 ///
 /// Checks health of all S3 backends via circuit breaker state.
-/// Returns per-replica health status for each bucket.
+/// Returns per-replica health status for each bucket. `mode` decides whether
+/// the result is 200 or 503; `cache_healthy` is `None` when no cache is
+/// configured. Ready-by-policy with some origins down reports `degraded`.
 pub fn handle_ready(
     buckets: &[BucketConfig],
     replica_sets: &HashMap<String, ReplicaSet>,
     metrics: &Metrics,
+    mode: ReadinessMode,
+    cache_healthy: Option<bool>,
 ) -> EndpointResponse {
     let mut backends_health = serde_json::Map::new();
     let mut all_healthy = true;
+    let mut any_healthy = buckets.is_empty();
 
     for bucket_config in buckets {
         if let Some(replica_set) = replica_sets.get(&bucket_config.name) {
@@ -116,6 +121,7 @@ pub fn handle_ready(
                 all_healthy = false;
                 "unavailable" // All replicas unhealthy
             };
+            any_healthy |= bucket_has_healthy_replica;
 
             // Record backend health in metrics
             metrics.set_backend_health(&bucket_config.name, bucket_has_healthy_replica);
@@ -155,12 +161,29 @@ pub fn handle_ready(
         }
     }
 
-    let status_code = if all_healthy { 200 } else { 503 };
-    let body = serde_json::json!({
-        "status": if all_healthy { "ready" } else { "unavailable" },
+    let ready = match mode {
+        ReadinessMode::AllOrigins => all_healthy,
+        ReadinessMode::AnyOrigin => any_healthy,
+        ReadinessMode::CacheOrOrigin => cache_healthy.unwrap_or(false) || all_healthy,
+        ReadinessMode::Always => true,
+    };
+    let status = match (ready, all_healthy) {
+        (true, true) => "ready",
+        (true, false) => "degraded",
+        (false, _) => "unavailable",
+    };
+
+    let status_code = if ready { 200 } else { 503 };
+    let mut body = serde_json::json!({
+        "status": status,
+        "mode": mode.as_str(),
         "backends": backends_health
-    })
-    .to_string();
+    });
+    if let Some(healthy) = cache_healthy {
+        body["cache"] =
+            serde_json::Value::String(if healthy { "healthy" } else { "unhealthy" }.to_string());
+    }
+    let body = body.to_string();
 
     EndpointResponse::json(status_code, body)
 }
@@ -205,6 +228,29 @@ mod tests {
             authorization: None,
             ip_filter: Default::default(),
             watermark: None,
+            logging: None,
+            resume: None,
+            content_type: None,
+            hotlink: None,
+            allowed_methods: None,
+            cors: None,
+            degraded: None,
+            server_timing: None,
+            early_hints: None,
+            etag_rewrite: None,
+            listing: None,
+            origin: None,
+            aliases: Vec::new(),
+            version: None,
+            hostnames: Vec::new(),
+            routes: Vec::new(),
+            upload_scan: None,
+            deprecation: None,
+            signed_url: None,
+            sigv4_auth: None,
+            post_policy: None,
+            presigned_redirect: None,
+            size_classes: Vec::new(),
         }
     }
 
@@ -219,6 +265,7 @@ mod tests {
             circuit_breaker: crate::circuit_breaker::CircuitBreaker::new(
                 CircuitBreakerConfig::default(),
             ),
+            endpoint_pool: None,
         }
     }
 
@@ -239,6 +286,7 @@ mod tests {
                 config: test_s3_config(),
             },
             circuit_breaker: cb,
+            endpoint_pool: None,
         }
     }
 
@@ -309,7 +357,13 @@ mod tests {
         let metrics = Metrics::new();
 
         // Execute
-        let response = handle_ready(&buckets, &replica_sets, &metrics);
+        let response = handle_ready(
+            &buckets,
+            &replica_sets,
+            &metrics,
+            ReadinessMode::AllOrigins,
+            None,
+        );
 
         // Verify
         assert_eq!(response.status, 200);
@@ -342,7 +396,13 @@ mod tests {
         let metrics = Metrics::new();
 
         // Execute
-        let response = handle_ready(&buckets, &replica_sets, &metrics);
+        let response = handle_ready(
+            &buckets,
+            &replica_sets,
+            &metrics,
+            ReadinessMode::AllOrigins,
+            None,
+        );
 
         // Verify: Status 200 but bucket is "degraded"
         assert_eq!(response.status, 200);
@@ -374,7 +434,13 @@ mod tests {
         let metrics = Metrics::new();
 
         // Execute
-        let response = handle_ready(&buckets, &replica_sets, &metrics);
+        let response = handle_ready(
+            &buckets,
+            &replica_sets,
+            &metrics,
+            ReadinessMode::AllOrigins,
+            None,
+        );
 
         // Verify: Status 503 and bucket is "unavailable"
         assert_eq!(response.status, 503);
@@ -400,7 +466,13 @@ mod tests {
         let metrics = Metrics::new();
 
         // Execute
-        let response = handle_ready(&buckets, &replica_sets, &metrics);
+        let response = handle_ready(
+            &buckets,
+            &replica_sets,
+            &metrics,
+            ReadinessMode::AllOrigins,
+            None,
+        );
 
         // Verify: Status 503 and bucket is "unavailable"
         assert_eq!(response.status, 503);
@@ -430,7 +502,13 @@ mod tests {
         let metrics = Metrics::new();
 
         // Execute
-        let response = handle_ready(&buckets, &replica_sets, &metrics);
+        let response = handle_ready(
+            &buckets,
+            &replica_sets,
+            &metrics,
+            ReadinessMode::AllOrigins,
+            None,
+        );
 
         // Verify: Status 503 because one bucket is unavailable
         assert_eq!(response.status, 503);
@@ -449,7 +527,13 @@ mod tests {
         let metrics = Metrics::new();
 
         // Execute
-        let response = handle_ready(&buckets, &replica_sets, &metrics);
+        let response = handle_ready(
+            &buckets,
+            &replica_sets,
+            &metrics,
+            ReadinessMode::AllOrigins,
+            None,
+        );
 
         // Verify: Status 200, all_healthy is true when there's nothing to check
         assert_eq!(response.status, 200);
@@ -457,4 +541,42 @@ mod tests {
         let parsed: serde_json::Value = serde_json::from_str(&response.body).unwrap();
         assert_eq!(parsed["status"], "ready");
     }
+
+    #[test]
+    fn test_handle_ready_modes_with_origin_down() {
+        // Setup: one bucket reachable, one with its only breaker open
+        let buckets = vec![test_bucket_config("products"), test_bucket_config("images")];
+        let mut replica_sets = HashMap::new();
+        replica_sets.insert(
+            "products".to_string(),
+            ReplicaSet {
+                replicas: vec![healthy_replica("primary")],
+            },
+        );
+        replica_sets.insert(
+            "images".to_string(),
+            ReplicaSet {
+                replicas: vec![unhealthy_replica("primary")],
+            },
+        );
+        let metrics = Metrics::new();
+        let ready = |mode, cache| handle_ready(&buckets, &replica_sets, &metrics, mode, cache);
+
+        let response = ready(ReadinessMode::AnyOrigin, None);
+        assert_eq!(response.status, 200);
+        let parsed: serde_json::Value = serde_json::from_str(&response.body).unwrap();
+        assert_eq!(parsed["status"], "degraded");
+        assert_eq!(parsed["mode"], "any_origin");
+
+        // Cache keeps the proxy ready; without a healthy cache it falls back to origins
+        let response = ready(ReadinessMode::CacheOrOrigin, Some(true));
+        assert_eq!(response.status, 200);
+        let parsed: serde_json::Value = serde_json::from_str(&response.body).unwrap();
+        assert_eq!(parsed["cache"], "healthy");
+        assert_eq!(ready(ReadinessMode::CacheOrOrigin, Some(false)).status, 503);
+        assert_eq!(ready(ReadinessMode::CacheOrOrigin, None).status, 503);
+
+        assert_eq!(ready(ReadinessMode::Always, None).status, 200);
+        assert_eq!(ready(ReadinessMode::AllOrigins, Some(true)).status, 503);
+    }
 }
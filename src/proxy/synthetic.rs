@@ -0,0 +1,47 @@
+//! Generated bodies for synthetic smoke-test endpoints.
+//!
+//! Bodies are a fixed repeating byte pattern streamed in
+//! [`SYNTHETIC_CHUNK_SIZE`] chunks, so a 1 GiB endpoint never allocates more
+//! than one chunk.
+
+use bytes::Bytes;
+use std::sync::OnceLock;
+
+use crate::constants::SYNTHETIC_CHUNK_SIZE;
+
+/// Shared pattern chunk (`0x00..=0xff` repeated)
+fn pattern() -> &'static Bytes {
+    static PATTERN: OnceLock<Bytes> = OnceLock::new();
+    PATTERN.get_or_init(|| {
+        (0..SYNTHETIC_CHUNK_SIZE)
+            .map(|i| i as u8)
+            .collect::<Vec<u8>>()
+            .into()
+    })
+}
+
+/// Body chunks totalling `size` bytes
+pub fn chunks(size: u64) -> impl Iterator<Item = Bytes> {
+    let chunk_size = SYNTHETIC_CHUNK_SIZE as u64;
+    (0..size.div_ceil(chunk_size)).map(move |i| {
+        let len = (size - i * chunk_size).min(chunk_size) as usize;
+        pattern().slice(..len)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_chunks_total_size() {
+        assert_eq!(chunks(0).count(), 0);
+
+        let size = SYNTHETIC_CHUNK_SIZE as u64 * 2 + 10;
+        let lengths: Vec<usize> = chunks(size).map(|c| c.len()).collect();
+        assert_eq!(
+            lengths,
+            vec![SYNTHETIC_CHUNK_SIZE, SYNTHETIC_CHUNK_SIZE, 10]
+        );
+    }
+}
@@ -589,6 +589,29 @@ mod tests {
             authorization: None,
             ip_filter: Default::default(),
             watermark: None,
+            logging: None,
+            resume: None,
+            content_type: None,
+            hotlink: None,
+            allowed_methods: None,
+            cors: None,
+            degraded: None,
+            server_timing: None,
+            early_hints: None,
+            etag_rewrite: None,
+            listing: None,
+            origin: None,
+            aliases: Vec::new(),
+            version: None,
+            hostnames: Vec::new(),
+            routes: Vec::new(),
+            upload_scan: None,
+            deprecation: None,
+            signed_url: None,
+            sigv4_auth: None,
+            post_policy: None,
+            presigned_redirect: None,
+            size_classes: Vec::new(),
         };
         let replica_sets: HashMap<String, ReplicaSet> = HashMap::new();
 
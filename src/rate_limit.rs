@@ -10,6 +10,13 @@
 //! - Fast (lock-free atomic operations)
 //! - Memory efficient (in-memory state)
 //!
+//! ## Burst and Smoothing
+//!
+//! Each limiter refills at `requests_per_second` and holds up to `burst`
+//! tokens (default: `requests_per_second`). Raising `burst` absorbs short
+//! spikes such as a page load fetching many assets while the long-term rate
+//! stays enforced; `burst: 1` admits requests only at evenly spaced intervals.
+//!
 //! ## Rate Limiting Strategy
 //!
 //! Limits are checked in this order (fail fast):
@@ -19,6 +26,13 @@
 //!
 //! If any limit is exceeded, return 429 Too Many Requests immediately.
 //!
+//! ## Inspecting State
+//!
+//! Per-IP and per-user limiters remember the tokens left after their last
+//! check and their recent rejections. [`RateLimitManager::ip_state`] and
+//! [`RateLimitManager::user_state`] report them for the admin API, so
+//! operators can tell a throttled client how close to its limit it is.
+//!
 //! ## Configuration Example
 //!
 //! ```yaml
@@ -29,23 +43,29 @@
 //!       requests_per_second: 1000
 //!     per_ip:
 //!       requests_per_second: 10
+//!       burst: 50
 //!
 //! buckets:
 //!   - name: products
 //!     s3:
 //!       rate_limit:
 //!         requests_per_second: 100
+//!         burst: 1
 //! ```
 
+use governor::middleware::StateInformationMiddleware;
 use governor::{clock::DefaultClock, state::InMemoryState, Quota, RateLimiter};
 use parking_lot::RwLock;
-use std::collections::HashMap;
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
 use std::net::IpAddr;
 use std::num::NonZeroU32;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::oneshot;
 
+use crate::error::ErrorCode;
+
 /// Maximum number of per-IP rate limiters to track before cleanup
 const DEFAULT_MAX_IP_LIMITERS: usize = 100_000;
 /// Maximum number of per-user rate limiters to track before cleanup
@@ -54,11 +74,130 @@ const DEFAULT_MAX_USER_LIMITERS: usize = 50_000;
 const DEFAULT_IDLE_TTL: Duration = Duration::from_secs(5 * 60);
 /// Default cleanup interval (1 minute)
 const DEFAULT_CLEANUP_INTERVAL: Duration = Duration::from_secs(60);
+/// Window for "recent" rejections reported by the state API
+const REJECTION_WINDOW: Duration = Duration::from_secs(60);
+/// Most rejection timestamps kept per limiter
+const MAX_TRACKED_REJECTIONS: usize = 1024;
+
+/// Sustained rate and burst capacity for one limiter
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateQuota {
+    /// Tokens replenished per second (long-term rate)
+    pub requests_per_second: u32,
+    /// Maximum tokens held at once (None = requests_per_second)
+    pub burst: Option<u32>,
+}
+
+impl RateQuota {
+    /// Quota with the default burst of one second's worth of requests
+    pub fn per_second(requests_per_second: u32) -> Self {
+        Self {
+            requests_per_second,
+            burst: None,
+        }
+    }
+
+    /// Set the burst capacity
+    pub fn with_burst(mut self, burst: Option<u32>) -> Self {
+        self.burst = burst;
+        self
+    }
+
+    /// Governor quota, or None when the rate is zero (limiter disabled)
+    fn to_governor(self) -> Option<Quota> {
+        let rps = NonZeroU32::new(self.requests_per_second)?;
+        let quota = Quota::per_second(rps);
+        Some(match self.burst.and_then(NonZeroU32::new) {
+            Some(burst) => quota.allow_burst(burst),
+            None => quota,
+        })
+    }
+}
+
+/// Direct limiter whose checks report the remaining burst capacity
+type StatefulLimiter =
+    RateLimiter<governor::state::NotKeyed, InMemoryState, DefaultClock, StateInformationMiddleware>;
 
 /// A rate limiter entry with last access tracking for TTL-based eviction
 struct TrackedLimiter {
-    limiter: Arc<RateLimiter<governor::state::NotKeyed, InMemoryState, DefaultClock>>,
+    limiter: Arc<StatefulLimiter>,
     last_accessed: Instant,
+    /// Tokens left after the most recent check
+    remaining: u32,
+    /// Rejections within `REJECTION_WINDOW` (oldest first)
+    recent_rejections: VecDeque<Instant>,
+    rejections_total: u64,
+}
+
+impl TrackedLimiter {
+    fn new(quota: Quota) -> Self {
+        Self {
+            limiter: Arc::new(RateLimiter::direct(quota).with_middleware()),
+            last_accessed: Instant::now(),
+            remaining: quota.burst_size().get(),
+            recent_rejections: VecDeque::new(),
+            rejections_total: 0,
+        }
+    }
+
+    /// Take a token, recording the outcome for `state`
+    fn check(&mut self) -> bool {
+        let now = Instant::now();
+        self.last_accessed = now;
+        match self.limiter.check() {
+            Ok(snapshot) => {
+                self.remaining = snapshot.remaining_burst_capacity();
+                true
+            }
+            Err(_) => {
+                self.remaining = 0;
+                self.rejections_total += 1;
+                self.recent_rejections.push_back(now);
+                if self.recent_rejections.len() > MAX_TRACKED_REJECTIONS {
+                    self.recent_rejections.pop_front();
+                }
+                false
+            }
+        }
+    }
+
+    /// Current fill, estimated from the last check plus refill since then
+    fn state(&self, quota: Quota) -> LimiterState {
+        let now = Instant::now();
+        let idle = now.duration_since(self.last_accessed);
+        let interval = quota.replenish_interval();
+        let refilled = (idle.as_nanos() / interval.as_nanos().max(1)) as u64;
+        let burst = quota.burst_size().get();
+        LimiterState {
+            requests_per_second: 1.0 / interval.as_secs_f64(),
+            burst,
+            tokens_available: (self.remaining as u64 + refilled).min(burst as u64) as u32,
+            rejections_last_minute: self
+                .recent_rejections
+                .iter()
+                .filter(|at| now.duration_since(**at) < REJECTION_WINDOW)
+                .count(),
+            rejections_total: self.rejections_total,
+            idle_secs: idle.as_secs(),
+        }
+    }
+}
+
+/// Snapshot of one per-IP or per-user token bucket
+#[derive(Debug, Clone, Serialize)]
+pub struct LimiterState {
+    /// Sustained refill rate
+    pub requests_per_second: f64,
+    /// Bucket capacity
+    pub burst: u32,
+    /// Tokens the next requests can use right now (estimated)
+    pub tokens_available: u32,
+    /// Rejections in the last 60 seconds
+    pub rejections_last_minute: usize,
+    /// Rejections since the limiter was created (reset when evicted as idle)
+    pub rejections_total: u64,
+    /// Seconds since the last request checked against this limiter
+    pub idle_secs: u64,
 }
 
 /// Rate limiter manager handling global, per-bucket, per-IP, and per-user limits
@@ -79,10 +218,10 @@ pub struct RateLimitManager {
     ips: Arc<RwLock<HashMap<IpAddr, TrackedLimiter>>>,
     /// Per-user rate limiters with access tracking (keyed by user ID from JWT)
     users: Arc<RwLock<HashMap<String, TrackedLimiter>>>,
-    /// Per-IP rate limit config (requests per second)
-    per_ip_rps: Option<NonZeroU32>,
-    /// Per-user rate limit config (requests per second)
-    per_user_rps: Option<NonZeroU32>,
+    /// Per-IP rate limit quota
+    per_ip_quota: Option<Quota>,
+    /// Per-user rate limit quota
+    per_user_quota: Option<Quota>,
     /// Maximum number of tracked IPs before cleanup
     max_ip_limiters: usize,
     /// Maximum number of tracked users before cleanup
@@ -114,20 +253,35 @@ impl RateLimitManager {
         per_ip_rps: Option<u32>,
         per_user_rps: Option<u32>,
     ) -> Self {
-        let global = global_rps.and_then(|rps| {
-            NonZeroU32::new(rps).map(|nz| Arc::new(RateLimiter::direct(Quota::per_second(nz))))
-        });
+        Self::with_quotas(
+            global_rps.map(RateQuota::per_second),
+            per_ip_rps.map(RateQuota::per_second),
+            per_user_rps.map(RateQuota::per_second),
+        )
+    }
 
-        let per_ip_rps = per_ip_rps.and_then(NonZeroU32::new);
-        let per_user_rps = per_user_rps.and_then(NonZeroU32::new);
+    /// Create a new rate limit manager with explicit burst settings
+    ///
+    /// # Arguments
+    /// * `global` - Global quota (None = disabled)
+    /// * `per_ip` - Per-IP quota (None = disabled)
+    /// * `per_user` - Per-user quota (None = disabled)
+    pub fn with_quotas(
+        global: Option<RateQuota>,
+        per_ip: Option<RateQuota>,
+        per_user: Option<RateQuota>,
+    ) -> Self {
+        let global = global
+            .and_then(RateQuota::to_governor)
+            .map(|quota| Arc::new(RateLimiter::direct(quota)));
 
         Self {
             global,
             buckets: Arc::new(RwLock::new(HashMap::new())),
             ips: Arc::new(RwLock::new(HashMap::new())),
             users: Arc::new(RwLock::new(HashMap::new())),
-            per_ip_rps,
-            per_user_rps,
+            per_ip_quota: per_ip.and_then(RateQuota::to_governor),
+            per_user_quota: per_user.and_then(RateQuota::to_governor),
             max_ip_limiters: DEFAULT_MAX_IP_LIMITERS,
             max_user_limiters: DEFAULT_MAX_USER_LIMITERS,
             idle_ttl: DEFAULT_IDLE_TTL,
@@ -279,8 +433,17 @@ impl RateLimitManager {
     /// * `bucket_name` - Name of the bucket
     /// * `requests_per_second` - Rate limit for this bucket
     pub fn add_bucket_limiter(&self, bucket_name: String, requests_per_second: u32) {
-        if let Some(nz) = NonZeroU32::new(requests_per_second) {
-            let limiter = Arc::new(RateLimiter::direct(Quota::per_second(nz)));
+        self.add_bucket_quota(bucket_name, RateQuota::per_second(requests_per_second));
+    }
+
+    /// Add a per-bucket rate limiter with an explicit burst setting
+    ///
+    /// # Arguments
+    /// * `bucket_name` - Name of the bucket
+    /// * `quota` - Sustained rate and burst for this bucket
+    pub fn add_bucket_quota(&self, bucket_name: String, quota: RateQuota) {
+        if let Some(quota) = quota.to_governor() {
+            let limiter = Arc::new(RateLimiter::direct(quota));
             self.buckets.write().insert(bucket_name, limiter);
         }
     }
@@ -318,9 +481,9 @@ impl RateLimitManager {
     /// Each access updates the `last_accessed` timestamp to prevent TTL-based
     /// eviction of active limiters.
     pub fn check_ip(&self, ip: IpAddr) -> bool {
-        if self.per_ip_rps.is_none() {
+        let Some(quota) = self.per_ip_quota else {
             return true; // No per-IP limit configured
-        }
+        };
 
         let mut limiters = self.ips.write();
 
@@ -334,17 +497,11 @@ impl RateLimitManager {
             limiters.clear();
         }
 
-        let entry = limiters.entry(ip).or_insert_with(|| {
-            let rps = self.per_ip_rps.unwrap(); // Safe: checked above
-            TrackedLimiter {
-                limiter: Arc::new(RateLimiter::direct(Quota::per_second(rps))),
-                last_accessed: Instant::now(),
-            }
-        });
-
-        // Update last accessed time to prevent TTL eviction
-        entry.last_accessed = Instant::now();
-        entry.limiter.check().is_ok()
+        // Checking updates the last accessed time to prevent TTL eviction
+        limiters
+            .entry(ip)
+            .or_insert_with(|| TrackedLimiter::new(quota))
+            .check()
     }
 
     /// Check if a request should be allowed for a specific user (from JWT claims)
@@ -357,9 +514,9 @@ impl RateLimitManager {
     /// Each access updates the `last_accessed` timestamp to prevent TTL-based
     /// eviction of active limiters.
     pub fn check_user(&self, user_id: &str) -> bool {
-        if self.per_user_rps.is_none() {
+        let Some(quota) = self.per_user_quota else {
             return true; // No per-user limit configured
-        }
+        };
 
         let mut limiters = self.users.write();
 
@@ -373,17 +530,11 @@ impl RateLimitManager {
             limiters.clear();
         }
 
-        let entry = limiters.entry(user_id.to_string()).or_insert_with(|| {
-            let rps = self.per_user_rps.unwrap(); // Safe: checked above
-            TrackedLimiter {
-                limiter: Arc::new(RateLimiter::direct(Quota::per_second(rps))),
-                last_accessed: Instant::now(),
-            }
-        });
-
-        // Update last accessed time to prevent TTL eviction
-        entry.last_accessed = Instant::now();
-        entry.limiter.check().is_ok()
+        // Checking updates the last accessed time to prevent TTL eviction
+        limiters
+            .entry(user_id.to_string())
+            .or_insert_with(|| TrackedLimiter::new(quota))
+            .check()
     }
 
     /// Check all rate limits for a request
@@ -438,6 +589,31 @@ impl RateLimitManager {
         Ok(())
     }
 
+    /// Whether a per-IP limit is configured
+    pub fn has_ip_limit(&self) -> bool {
+        self.per_ip_quota.is_some()
+    }
+
+    /// Whether a per-user limit is configured
+    pub fn has_user_limit(&self) -> bool {
+        self.per_user_quota.is_some()
+    }
+
+    /// Token bucket state for an IP (None if no limit or not seen recently)
+    pub fn ip_state(&self, ip: IpAddr) -> Option<LimiterState> {
+        let quota = self.per_ip_quota?;
+        self.ips.read().get(&ip).map(|entry| entry.state(quota))
+    }
+
+    /// Token bucket state for a user (None if no limit or not seen recently)
+    pub fn user_state(&self, user_id: &str) -> Option<LimiterState> {
+        let quota = self.per_user_quota?;
+        self.users
+            .read()
+            .get(user_id)
+            .map(|entry| entry.state(quota))
+    }
+
     /// Get count of tracked IPs (for metrics/monitoring)
     pub fn tracked_ip_count(&self) -> usize {
         self.ips.read().len()
@@ -541,6 +717,18 @@ impl std::fmt::Display for RateLimitError {
 
 impl std::error::Error for RateLimitError {}
 
+impl RateLimitError {
+    /// Machine-readable code for the exceeded limit
+    pub fn error_code(&self) -> ErrorCode {
+        match self {
+            RateLimitError::Global => ErrorCode::RateLimitGlobal,
+            RateLimitError::PerIp(_) => ErrorCode::RateLimitIp,
+            RateLimitError::PerUser(_) => ErrorCode::RateLimitUser,
+            RateLimitError::PerBucket(_) => ErrorCode::RateLimitBucket,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -643,6 +831,70 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_burst_allows_spike_above_rate() {
+        let manager = RateLimitManager::with_quotas(
+            None,
+            Some(RateQuota::per_second(2).with_burst(Some(6))),
+            None,
+        );
+        manager.add_bucket_quota(
+            "products".to_string(),
+            RateQuota::per_second(1).with_burst(Some(4)),
+        );
+        let ip = IpAddr::V4(Ipv4Addr::new(192, 168, 1, 100));
+
+        // Burst capacity is available up front
+        for i in 0..6 {
+            assert!(manager.check_ip(ip), "Request {} should be allowed", i + 1);
+        }
+        assert!(!manager.check_ip(ip), "7th request exceeds the burst");
+
+        for _ in 0..4 {
+            assert!(manager.check_bucket("products"));
+        }
+        assert!(!manager.check_bucket("products"));
+    }
+
+    #[test]
+    fn test_burst_of_one_smooths_requests() {
+        let manager = RateLimitManager::with_quotas(
+            Some(RateQuota::per_second(20).with_burst(Some(1))),
+            None,
+            None,
+        );
+
+        // Only one request at a time, even though the rate is 20/s
+        assert!(manager.check_global());
+        assert!(!manager.check_global());
+
+        // One token is replenished every 50ms
+        thread::sleep(Duration::from_millis(60));
+        assert!(manager.check_global());
+        assert!(!manager.check_global());
+    }
+
+    #[test]
+    fn test_rate_quota_zero_rate_disables_limiter() {
+        let manager = RateLimitManager::with_quotas(
+            Some(RateQuota::per_second(0).with_burst(Some(10))),
+            None,
+            None,
+        );
+        for _ in 0..100 {
+            assert!(manager.check_global());
+        }
+        // Zero burst falls back to the default (one second's worth)
+        let manager = RateLimitManager::with_quotas(
+            Some(RateQuota::per_second(2).with_burst(Some(0))),
+            None,
+            None,
+        );
+        assert!(manager.check_global());
+        assert!(manager.check_global());
+        assert!(!manager.check_global());
+    }
+
     #[test]
     fn test_rate_limit_refills_over_time() {
         let manager = RateLimitManager::new(Some(10), None); // 10 requests per second
@@ -893,4 +1145,32 @@ mod tests {
             .check_all_with_user("api", Some(ip), Some("other-user"))
             .is_ok());
     }
+
+    #[test]
+    fn test_ip_state_reports_fill_and_rejections() {
+        let manager = RateLimitManager::with_quotas(
+            None,
+            Some(RateQuota::per_second(1).with_burst(Some(3))),
+            None,
+        );
+        let ip = IpAddr::V4(Ipv4Addr::new(192, 0, 2, 10));
+        assert!(manager.ip_state(ip).is_none());
+
+        assert!(manager.check_ip(ip));
+        let state = manager.ip_state(ip).unwrap();
+        assert_eq!(state.burst, 3);
+        assert_eq!(state.tokens_available, 2);
+        assert_eq!(state.rejections_total, 0);
+
+        assert!(manager.check_ip(ip));
+        assert!(manager.check_ip(ip));
+        assert!(!manager.check_ip(ip));
+        let state = manager.ip_state(ip).unwrap();
+        assert_eq!(state.tokens_available, 0);
+        assert_eq!(state.rejections_last_minute, 1);
+
+        // No per-user limit configured
+        assert!(!manager.has_user_limit());
+        assert!(manager.user_state("alice").is_none());
+    }
 }
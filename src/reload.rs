@@ -1,15 +1,23 @@
 // Configuration hot reload module
 // Handles SIGHUP signal to reload configuration without downtime
 
-use crate::config::Config;
+use crate::config::{subsystems, Config, Subsystem};
 use std::path::PathBuf;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
+/// Work done on every reload besides re-reading the config file, such as
+/// re-reading TLS certificates; returns a description of what failed
+pub type ReloadHook = Box<dyn Fn() -> Result<(), String> + Send + Sync>;
+
 /// ReloadManager handles configuration reload via SIGHUP signal
 pub struct ReloadManager {
     config_path: PathBuf,
     reload_requested: Arc<AtomicBool>,
+    /// Subsystems turned off at startup, kept off across reloads
+    disabled_subsystems: Vec<Subsystem>,
+    /// Run on every reload request, in registration order
+    hooks: Vec<(String, ReloadHook)>,
 }
 
 impl ReloadManager {
@@ -18,9 +26,40 @@ impl ReloadManager {
         Self {
             config_path,
             reload_requested: Arc::new(AtomicBool::new(false)),
+            disabled_subsystems: Vec::new(),
+            hooks: Vec::new(),
         }
     }
 
+    /// Run `hook` on every reload (SIGHUP or `POST /admin/reload`)
+    pub fn with_hook(
+        mut self,
+        name: &str,
+        hook: impl Fn() -> Result<(), String> + Send + Sync + 'static,
+    ) -> Self {
+        self.hooks.push((name.to_string(), Box::new(hook)));
+        self
+    }
+
+    /// Run the registered hooks; a failing hook is logged and does not stop
+    /// the others. Returns the number of hooks that failed
+    pub fn run_hooks(&self) -> usize {
+        let mut failed = 0;
+        for (name, hook) in &self.hooks {
+            if let Err(e) = hook() {
+                tracing::warn!(hook = %name, error = %e, "Reload hook failed");
+                failed += 1;
+            }
+        }
+        failed
+    }
+
+    /// Keep these subsystems disabled in every reloaded config
+    pub fn with_disabled_subsystems(mut self, subsystems: Vec<Subsystem>) -> Self {
+        self.disabled_subsystems = subsystems;
+        self
+    }
+
     /// Register SIGHUP signal handler
     /// Returns a handle that can be used to check if reload was requested
     #[cfg(unix)]
@@ -54,7 +93,8 @@ impl ReloadManager {
     /// Returns Ok(new_config) if reload successful, Err if validation fails
     pub fn reload_config(&self) -> Result<Config, String> {
         // Load new config from file
-        let new_config = Config::from_file(&self.config_path)?;
+        let mut new_config = Config::from_file(&self.config_path)?;
+        subsystems::disable_all(&mut new_config, &self.disabled_subsystems);
 
         // Validate before applying
         new_config.validate()?;
@@ -107,6 +147,27 @@ mod tests {
         assert!(result.is_ok(), "Should be able to register SIGHUP handler");
     }
 
+    #[test]
+    fn test_reload_hooks_run_in_order_and_failures_are_counted() {
+        use std::sync::Mutex;
+
+        let temp_file = NamedTempFile::new().unwrap();
+        let calls = Arc::new(Mutex::new(Vec::new()));
+        let (first, second) = (Arc::clone(&calls), Arc::clone(&calls));
+        let manager = ReloadManager::new(temp_file.path().to_path_buf())
+            .with_hook("first", move || {
+                first.lock().unwrap().push("first");
+                Err("unreadable".to_string())
+            })
+            .with_hook("second", move || {
+                second.lock().unwrap().push("second");
+                Ok(())
+            });
+
+        assert_eq!(manager.run_hooks(), 1);
+        assert_eq!(*calls.lock().unwrap(), vec!["first", "second"]);
+    }
+
     #[test]
     fn test_reload_requested_flag_starts_false() {
         let temp_file = NamedTempFile::new().unwrap();
@@ -1113,4 +1174,35 @@ jwt:
         // Old config still has only 1 token source (in-flight requests)
         assert_eq!(old_config.jwt.as_ref().unwrap().token_sources.len(), 1);
     }
+
+    #[test]
+    fn test_disabled_subsystems_stay_disabled_after_reload() {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        let config = r#"
+server:
+  address: "127.0.0.1"
+  port: 8080
+
+buckets:
+  - name: "test-bucket"
+    path_prefix: "/test"
+    s3:
+      bucket: "my-bucket"
+      region: "us-east-1"
+      access_key: "test-key"
+      secret_key: "test-secret"
+
+cache:
+  enabled: true
+  cache_layers: ["memory"]
+"#;
+        temp_file.write_all(config.as_bytes()).unwrap();
+        temp_file.flush().unwrap();
+
+        let manager = ReloadManager::new(temp_file.path().to_path_buf())
+            .with_disabled_subsystems(vec![Subsystem::Cache]);
+
+        let reloaded = manager.reload_config().unwrap();
+        assert!(!reloaded.cache.unwrap().enabled);
+    }
 }
@@ -0,0 +1,183 @@
+//! Static endpoint pools for replicas reached without DNS or a load balancer.
+//!
+//! A replica configured with `endpoints: ["10.0.0.11:9000", ...]` connects to
+//! those addresses directly. Requests rotate round-robin across them. An
+//! address that fails `STATIC_ENDPOINT_FAILURE_THRESHOLD` connections in a row
+//! is skipped for `STATIC_ENDPOINT_COOLDOWN_SECS`, then tried again; one
+//! successful connection makes it healthy.
+
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU32, AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::constants::{STATIC_ENDPOINT_COOLDOWN_SECS, STATIC_ENDPOINT_FAILURE_THRESHOLD};
+
+#[derive(Debug)]
+struct PoolEndpoint {
+    addr: SocketAddr,
+    consecutive_failures: AtomicU32,
+    skipped_until: Mutex<Option<Instant>>,
+}
+
+impl PoolEndpoint {
+    fn is_available(&self, now: Instant) -> bool {
+        match self.skipped_until.lock() {
+            Ok(until) => until.map_or(true, |until| now >= until),
+            Err(_) => true,
+        }
+    }
+}
+
+/// Round-robin pool of static addresses with per-address health
+#[derive(Debug)]
+pub struct EndpointPool {
+    endpoints: Vec<PoolEndpoint>,
+    next: AtomicUsize,
+    failure_threshold: u32,
+    cooldown: Duration,
+}
+
+impl EndpointPool {
+    /// Create a pool from configured `ip:port` strings with the default thresholds
+    pub fn from_config(addrs: &[String]) -> Result<Self, String> {
+        let addrs = addrs
+            .iter()
+            .map(|addr| {
+                addr.parse::<SocketAddr>()
+                    .map_err(|e| format!("Invalid static endpoint '{}': {}", addr, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::new(
+            addrs,
+            STATIC_ENDPOINT_FAILURE_THRESHOLD,
+            Duration::from_secs(STATIC_ENDPOINT_COOLDOWN_SECS),
+        ))
+    }
+
+    /// Create a pool with explicit failure threshold and cooldown
+    pub fn new(addrs: Vec<SocketAddr>, failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            endpoints: addrs
+                .into_iter()
+                .map(|addr| PoolEndpoint {
+                    addr,
+                    consecutive_failures: AtomicU32::new(0),
+                    skipped_until: Mutex::new(None),
+                })
+                .collect(),
+            next: AtomicUsize::new(0),
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+        }
+    }
+
+    /// Next usable address in rotation, or `None` when every address is cooling down
+    pub fn select(&self) -> Option<SocketAddr> {
+        let len = self.endpoints.len();
+        if len == 0 {
+            return None;
+        }
+        let start = self.next.fetch_add(1, Ordering::Relaxed);
+        let now = Instant::now();
+        (0..len)
+            .map(|offset| &self.endpoints[(start + offset) % len])
+            .find(|endpoint| endpoint.is_available(now))
+            .map(|endpoint| endpoint.addr)
+    }
+
+    /// Record a successful connection to `addr`
+    pub fn record_success(&self, addr: SocketAddr) {
+        if let Some(endpoint) = self.find(addr) {
+            endpoint.consecutive_failures.store(0, Ordering::Relaxed);
+            if let Ok(mut until) = endpoint.skipped_until.lock() {
+                *until = None;
+            }
+        }
+    }
+
+    /// Record a failed connection to `addr`
+    ///
+    /// Returns `true` when the failure starts a cooldown for the address.
+    pub fn record_failure(&self, addr: SocketAddr) -> bool {
+        let Some(endpoint) = self.find(addr) else {
+            return false;
+        };
+        let failures = endpoint
+            .consecutive_failures
+            .fetch_add(1, Ordering::Relaxed)
+            + 1;
+        if failures < self.failure_threshold {
+            return false;
+        }
+        if let Ok(mut until) = endpoint.skipped_until.lock() {
+            *until = Some(Instant::now() + self.cooldown);
+        }
+        true
+    }
+
+    /// Number of addresses not currently cooling down
+    pub fn healthy_count(&self) -> usize {
+        let now = Instant::now();
+        self.endpoints
+            .iter()
+            .filter(|endpoint| endpoint.is_available(now))
+            .count()
+    }
+
+    /// Number of addresses in the pool
+    pub fn len(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    /// Check if the pool has no addresses
+    pub fn is_empty(&self) -> bool {
+        self.endpoints.is_empty()
+    }
+
+    fn find(&self, addr: SocketAddr) -> Option<&PoolEndpoint> {
+        self.endpoints.iter().find(|endpoint| endpoint.addr == addr)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn addr(s: &str) -> SocketAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_endpoint_pool_rotates_and_skips_failing_endpoints() {
+        let pool = EndpointPool::new(
+            vec![addr("10.0.0.1:9000"), addr("10.0.0.2:9000")],
+            2,
+            Duration::from_secs(60),
+        );
+
+        assert_eq!(pool.select(), Some(addr("10.0.0.1:9000")));
+        assert_eq!(pool.select(), Some(addr("10.0.0.2:9000")));
+
+        assert!(!pool.record_failure(addr("10.0.0.1:9000")));
+        assert!(pool.record_failure(addr("10.0.0.1:9000")));
+        assert_eq!(pool.healthy_count(), 1);
+        for _ in 0..4 {
+            assert_eq!(pool.select(), Some(addr("10.0.0.2:9000")));
+        }
+
+        pool.record_failure(addr("10.0.0.2:9000"));
+        pool.record_failure(addr("10.0.0.2:9000"));
+        assert_eq!(pool.select(), None);
+
+        pool.record_success(addr("10.0.0.1:9000"));
+        assert_eq!(pool.select(), Some(addr("10.0.0.1:9000")));
+    }
+
+    #[test]
+    fn test_endpoint_pool_retries_after_cooldown() {
+        let pool = EndpointPool::new(vec![addr("10.0.0.1:9000")], 1, Duration::ZERO);
+        assert!(pool.record_failure(addr("10.0.0.1:9000")));
+        assert_eq!(pool.select(), Some(addr("10.0.0.1:9000")));
+    }
+}
@@ -5,6 +5,7 @@
 // - Independent S3 client with its own credentials
 // - Independent circuit breaker for health tracking
 // - Priority level (1 = highest priority)
+// - Optional static endpoint pool (ip:port addresses rotated without DNS)
 //
 // Failover strategy:
 // - Try replicas in priority order (1, 2, 3...)
@@ -12,8 +13,14 @@
 // - Return first successful response
 // - Error classification: Only failover on server/network errors, not client errors (4xx)
 
+pub mod endpoint_pool;
+
+pub use endpoint_pool::EndpointPool;
+
+use std::sync::Arc;
+
 use crate::circuit_breaker::CircuitBreaker;
-use crate::config::S3Replica;
+use crate::config::{S3CredentialsConfig, S3Replica};
 use crate::s3::S3Client;
 
 /// Decision on whether to failover to the next replica after an error
@@ -52,6 +59,8 @@ pub struct ReplicaEntry {
     pub priority: u8,
     pub client: S3Client,
     pub circuit_breaker: CircuitBreaker,
+    /// Static addresses connected to instead of resolving the endpoint host
+    pub endpoint_pool: Option<Arc<EndpointPool>>,
 }
 
 /// A set of replicas for a single bucket, stored in priority order
@@ -64,6 +73,15 @@ impl ReplicaSet {
     /// Create a new ReplicaSet from a list of replica configurations.
     /// Replicas are expected to already be sorted by priority.
     pub fn new(replica_configs: &[S3Replica]) -> Result<Self, String> {
+        Self::with_credentials(replica_configs, None)
+    }
+
+    /// Like [`Self::new`], for replicas whose keys come from the bucket's
+    /// `s3.credentials` provider instead of the replica config
+    pub fn with_credentials(
+        replica_configs: &[S3Replica],
+        credentials: Option<&S3CredentialsConfig>,
+    ) -> Result<Self, String> {
         if replica_configs.is_empty() {
             return Err("Cannot create ReplicaSet with empty replica list".to_string());
         }
@@ -72,17 +90,26 @@ impl ReplicaSet {
 
         for replica_config in replica_configs {
             // Create S3 client for this replica
-            let client = create_replica_client(replica_config)?;
+            let client = create_replica_client(replica_config, credentials)?;
 
             // Create circuit breaker for this replica (using default config)
             let circuit_breaker =
                 CircuitBreaker::new(crate::circuit_breaker::CircuitBreakerConfig::default());
 
+            let endpoint_pool = if replica_config.endpoints.is_empty() {
+                None
+            } else {
+                Some(Arc::new(EndpointPool::from_config(
+                    &replica_config.endpoints,
+                )?))
+            };
+
             replicas.push(ReplicaEntry {
                 name: replica_config.name.clone(),
                 priority: replica_config.priority,
                 client,
                 circuit_breaker,
+                endpoint_pool,
             });
         }
 
@@ -415,7 +442,10 @@ impl ReplicaSet {
 }
 
 /// Create an S3 client from a replica configuration
-fn create_replica_client(replica: &S3Replica) -> Result<S3Client, String> {
+fn create_replica_client(
+    replica: &S3Replica,
+    credentials: Option<&S3CredentialsConfig>,
+) -> Result<S3Client, String> {
     // Convert S3Replica to S3Config for client creation
     let s3_config = crate::config::S3Config {
         bucket: replica.bucket.clone(),
@@ -428,6 +458,9 @@ fn create_replica_client(replica: &S3Replica) -> Result<S3Client, String> {
         circuit_breaker: None,
         rate_limit: None,
         retry: None,
+        bulkhead: None,
+        signing: None,
+        credentials: credentials.cloned(),
         replicas: None, // Not used for individual replica clients
     };
 
@@ -452,6 +485,7 @@ mod tests {
                 endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                 priority: 1,
                 timeout: 30,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-eu".to_string(),
@@ -462,6 +496,7 @@ mod tests {
                 endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                 priority: 2,
                 timeout: 25,
+                endpoints: Vec::new(),
             },
         ];
 
@@ -505,6 +540,7 @@ mod tests {
                 endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                 priority: 1,
                 timeout: 30,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-eu".to_string(),
@@ -515,6 +551,7 @@ mod tests {
                 endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                 priority: 2,
                 timeout: 25,
+                endpoints: Vec::new(),
             },
         ];
 
@@ -561,6 +598,7 @@ mod tests {
                 endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                 priority: 1,
                 timeout: 30,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-eu".to_string(),
@@ -571,6 +609,7 @@ mod tests {
                 endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                 priority: 2,
                 timeout: 25,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-minio".to_string(),
@@ -581,6 +620,7 @@ mod tests {
                 endpoint: Some("https://minio.example.com".to_string()),
                 priority: 3,
                 timeout: 20,
+                endpoints: Vec::new(),
             },
         ];
 
@@ -625,6 +665,7 @@ mod tests {
                 endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                 priority: 1,
                 timeout: 30,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-eu".to_string(),
@@ -635,6 +676,7 @@ mod tests {
                 endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                 priority: 2,
                 timeout: 25,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-minio".to_string(),
@@ -645,6 +687,7 @@ mod tests {
                 endpoint: Some("https://minio.example.com".to_string()),
                 priority: 3,
                 timeout: 20,
+                endpoints: Vec::new(),
             },
         ];
 
@@ -718,6 +761,7 @@ mod tests {
                 endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                 priority: 1,
                 timeout: 30, // Fast primary: 30 seconds
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-eu".to_string(),
@@ -728,6 +772,7 @@ mod tests {
                 endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                 priority: 2,
                 timeout: 45, // Slower cross-region: 45 seconds
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-backup".to_string(),
@@ -738,6 +783,7 @@ mod tests {
                 endpoint: Some("https://minio.example.com".to_string()),
                 priority: 3,
                 timeout: 60, // Slow backup: 60 seconds
+                endpoints: Vec::new(),
             },
         ];
 
@@ -799,6 +845,7 @@ mod tests {
                 endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                 priority: 1,
                 timeout: 30,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-eu".to_string(),
@@ -809,6 +856,7 @@ mod tests {
                 endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                 priority: 2,
                 timeout: 25,
+                endpoints: Vec::new(),
             },
         ];
 
@@ -891,6 +939,7 @@ mod tests {
             endpoint: Some("https://s3.us-east-1.amazonaws.com".to_string()),
             priority: 1,
             timeout: 30,
+            endpoints: Vec::new(),
         };
 
         // Create ReplicaSet from single replica (simulating normalized config)
@@ -960,6 +1009,7 @@ mod tests {
                 endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                 priority: 1,
                 timeout: 30,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-eu".to_string(),
@@ -970,6 +1020,7 @@ mod tests {
                 endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                 priority: 2,
                 timeout: 25,
+                endpoints: Vec::new(),
             },
         ];
 
@@ -1018,6 +1069,7 @@ mod tests {
                 endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                 priority: 1,
                 timeout: 30,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-eu".to_string(),
@@ -1028,6 +1080,7 @@ mod tests {
                 endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                 priority: 2,
                 timeout: 25,
+                endpoints: Vec::new(),
             },
         ];
 
@@ -1084,6 +1137,7 @@ mod tests {
                 endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                 priority: 1,
                 timeout: 30,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-eu".to_string(),
@@ -1094,6 +1148,7 @@ mod tests {
                 endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                 priority: 2,
                 timeout: 25,
+                endpoints: Vec::new(),
             },
         ];
 
@@ -1150,6 +1205,7 @@ mod tests {
                 endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                 priority: 1,
                 timeout: 30,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-eu".to_string(),
@@ -1160,6 +1216,7 @@ mod tests {
                 endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                 priority: 2,
                 timeout: 25,
+                endpoints: Vec::new(),
             },
         ];
 
@@ -1216,6 +1273,7 @@ mod tests {
                 endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                 priority: 1,
                 timeout: 30,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-eu".to_string(),
@@ -1226,6 +1284,7 @@ mod tests {
                 endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                 priority: 2,
                 timeout: 25,
+                endpoints: Vec::new(),
             },
         ];
 
@@ -1282,6 +1341,7 @@ mod tests {
                 endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                 priority: 1,
                 timeout: 30,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-eu".to_string(),
@@ -1292,6 +1352,7 @@ mod tests {
                 endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                 priority: 2,
                 timeout: 25,
+                endpoints: Vec::new(),
             },
         ];
 
@@ -1348,6 +1409,7 @@ mod tests {
                 endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                 priority: 1,
                 timeout: 30,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-eu".to_string(),
@@ -1358,6 +1420,7 @@ mod tests {
                 endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                 priority: 2,
                 timeout: 25,
+                endpoints: Vec::new(),
             },
         ];
 
@@ -1415,6 +1478,7 @@ mod tests {
                 endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                 priority: 1,
                 timeout: 30,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-eu".to_string(),
@@ -1425,6 +1489,7 @@ mod tests {
                 endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                 priority: 2,
                 timeout: 25,
+                endpoints: Vec::new(),
             },
         ];
 
@@ -1486,6 +1551,7 @@ mod tests {
                 endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                 priority: 1,
                 timeout: 30,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-eu".to_string(),
@@ -1496,6 +1562,7 @@ mod tests {
                 endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                 priority: 2,
                 timeout: 25,
+                endpoints: Vec::new(),
             },
         ];
 
@@ -1629,6 +1696,7 @@ mod tests {
                 endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                 priority: 1,
                 timeout: 30,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-eu".to_string(),
@@ -1639,6 +1707,7 @@ mod tests {
                 endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                 priority: 2,
                 timeout: 25,
+                endpoints: Vec::new(),
             },
         ];
 
@@ -1699,6 +1768,7 @@ mod tests {
                 endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                 priority: 1,
                 timeout: 30,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-eu".to_string(),
@@ -1709,6 +1779,7 @@ mod tests {
                 endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                 priority: 2,
                 timeout: 25,
+                endpoints: Vec::new(),
             },
         ];
 
@@ -1776,6 +1847,7 @@ mod tests {
                 endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                 priority: 1,
                 timeout: 30,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-eu".to_string(),
@@ -1786,6 +1858,7 @@ mod tests {
                 endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                 priority: 2,
                 timeout: 25,
+                endpoints: Vec::new(),
             },
         ];
 
@@ -1844,6 +1917,7 @@ mod tests {
                 endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                 priority: 1,
                 timeout: 30,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-eu".to_string(),
@@ -1854,6 +1928,7 @@ mod tests {
                 endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                 priority: 2,
                 timeout: 25,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-ap".to_string(),
@@ -1864,6 +1939,7 @@ mod tests {
                 endpoint: Some("https://s3.ap-southeast-1.amazonaws.com".to_string()),
                 priority: 3,
                 timeout: 20,
+                endpoints: Vec::new(),
             },
         ];
 
@@ -1920,6 +1996,7 @@ mod tests {
                 endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                 priority: 1,
                 timeout: 30,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-eu".to_string(),
@@ -1930,6 +2007,7 @@ mod tests {
                 endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                 priority: 2,
                 timeout: 25,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-ap".to_string(),
@@ -1940,6 +2018,7 @@ mod tests {
                 endpoint: Some("https://s3.ap-southeast-1.amazonaws.com".to_string()),
                 priority: 3,
                 timeout: 20,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-sa".to_string(),
@@ -1950,6 +2029,7 @@ mod tests {
                 endpoint: Some("https://s3.sa-east-1.amazonaws.com".to_string()),
                 priority: 4,
                 timeout: 20,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-af".to_string(),
@@ -1960,6 +2040,7 @@ mod tests {
                 endpoint: Some("https://s3.af-south-1.amazonaws.com".to_string()),
                 priority: 5,
                 timeout: 20,
+                endpoints: Vec::new(),
             },
         ];
 
@@ -2025,6 +2106,7 @@ mod tests {
                 endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                 priority: 1,
                 timeout: 30,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-eu".to_string(),
@@ -2035,6 +2117,7 @@ mod tests {
                 endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                 priority: 2,
                 timeout: 25,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-ap".to_string(),
@@ -2045,6 +2128,7 @@ mod tests {
                 endpoint: Some("https://s3.ap-southeast-1.amazonaws.com".to_string()),
                 priority: 3,
                 timeout: 20,
+                endpoints: Vec::new(),
             },
         ];
 
@@ -2173,6 +2257,7 @@ mod tests {
                 endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                 priority: 1,
                 timeout: 30,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-eu".to_string(),
@@ -2183,6 +2268,7 @@ mod tests {
                 endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                 priority: 2,
                 timeout: 25,
+                endpoints: Vec::new(),
             },
             S3Replica {
                 name: "replica-ap".to_string(),
@@ -2193,6 +2279,7 @@ mod tests {
                 endpoint: Some("https://s3.ap-southeast-1.amazonaws.com".to_string()),
                 priority: 3,
                 timeout: 20,
+                endpoints: Vec::new(),
             },
         ];
 
@@ -2282,6 +2369,7 @@ mod tests {
                     endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                     priority: 1,
                     timeout: 30,
+                    endpoints: Vec::new(),
                 },
                 S3Replica {
                     name: "replica-eu".to_string(),
@@ -2292,6 +2380,7 @@ mod tests {
                     endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                     priority: 2,
                     timeout: 25,
+                    endpoints: Vec::new(),
                 },
                 S3Replica {
                     name: "replica-ap".to_string(),
@@ -2302,6 +2391,7 @@ mod tests {
                     endpoint: Some("https://s3.ap-southeast-1.amazonaws.com".to_string()),
                     priority: 3,
                     timeout: 20,
+                    endpoints: Vec::new(),
                 },
             ];
 
@@ -2381,6 +2471,7 @@ mod tests {
                     endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                     priority: 1,
                     timeout: 30,
+                    endpoints: Vec::new(),
                 },
                 S3Replica {
                     name: "replica-eu".to_string(),
@@ -2391,6 +2482,7 @@ mod tests {
                     endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                     priority: 2,
                     timeout: 25,
+                    endpoints: Vec::new(),
                 },
             ];
 
@@ -2476,6 +2568,7 @@ mod tests {
                     endpoint: Some("https://s3.us-west-2.amazonaws.com".to_string()),
                     priority: 1,
                     timeout: 30,
+                    endpoints: Vec::new(),
                 },
                 S3Replica {
                     name: "replica-eu".to_string(),
@@ -2486,6 +2579,7 @@ mod tests {
                     endpoint: Some("https://s3.eu-west-1.amazonaws.com".to_string()),
                     priority: 2,
                     timeout: 25,
+                    endpoints: Vec::new(),
                 },
             ];
 
@@ -10,6 +10,26 @@
 //! 3. Select the bucket with the longest matching prefix (most specific)
 //! 4. Strip the matched prefix from the path for the upstream S3 request
 //!
+//! # Virtual Hosts
+//!
+//! A bucket may also claim `hostnames`. A request whose `Host` matches one is
+//! mapped onto that bucket's prefix first ([`Router::virtual_host_path`]), so
+//! `assets.example.com/css/site.css` routes like `/assets/css/site.css`.
+//! Requests for other hosts fall back to path routing.
+//!
+//! # Aliases
+//!
+//! A bucket's `aliases` are extra prefixes mapped onto its `path_prefix`
+//! ([`Router::alias_path`]), so `/v1/assets/logo.png` routes like
+//! `/assets/logo.png`. An alias competes with bucket prefixes on length: it
+//! applies only when no bucket prefix matches at least as much of the path.
+//!
+//! # Pattern Routes
+//!
+//! A bucket's `routes` match whole paths with a glob or regex and build the
+//! S3 key from a template ([`Router::pattern_path`]). The first matching
+//! route, in configuration order, wins over prefix matching.
+//!
 //! # Example
 //!
 //! Given buckets configured with prefixes:
@@ -27,13 +47,43 @@
 //! - O(n) routing where n = number of configured buckets
 //! - O(1) bucket lookup by name via HashMap index
 
+use crate::config::routes::render_key;
 use crate::config::BucketConfig;
+use regex::Regex;
 use std::collections::HashMap;
 
+/// Alias prefix of a bucket
+struct AliasRoute {
+    bucket: usize,
+    prefix: String,
+    version: Option<String>,
+}
+
+/// Prefix a request entered a bucket through, for per-prefix metrics
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RouteEntry<'a> {
+    /// Bucket config name
+    pub bucket: &'a str,
+    /// Matched alias, or the bucket's own `path_prefix`
+    pub prefix: &'a str,
+    pub version: Option<&'a str>,
+}
+
+/// Compiled pattern route: bucket index, path pattern, key template
+struct PatternRoute {
+    bucket: usize,
+    pattern: Regex,
+    key: String,
+}
+
 pub struct Router {
     buckets: Vec<BucketConfig>,
     /// Index for O(1) bucket lookup by name
     bucket_by_name: HashMap<String, usize>,
+    /// Lowercased host name -> bucket index
+    bucket_by_host: HashMap<String, usize>,
+    aliases: Vec<AliasRoute>,
+    patterns: Vec<PatternRoute>,
 }
 
 impl Router {
@@ -43,11 +93,154 @@ impl Router {
             .enumerate()
             .map(|(idx, bucket)| (bucket.name.clone(), idx))
             .collect();
+        let bucket_by_host: HashMap<String, usize> = buckets
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, bucket)| {
+                bucket
+                    .hostnames
+                    .iter()
+                    .map(move |host| (host.to_ascii_lowercase(), idx))
+            })
+            .collect();
+        let aliases = buckets
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, bucket)| {
+                bucket.aliases.iter().map(move |alias| AliasRoute {
+                    bucket: idx,
+                    prefix: alias.path_prefix.clone(),
+                    version: alias.version.clone(),
+                })
+            })
+            .collect();
+        // Patterns are validated at config load; one that fails to compile is skipped
+        let patterns = buckets
+            .iter()
+            .enumerate()
+            .flat_map(|(idx, bucket)| {
+                bucket.routes.iter().filter_map(move |route| {
+                    Some(PatternRoute {
+                        bucket: idx,
+                        pattern: route.compile().ok()?,
+                        key: route.key.clone(),
+                    })
+                })
+            })
+            .collect();
 
         Router {
             buckets,
             bucket_by_name,
+            bucket_by_host,
+            aliases,
+            patterns,
+        }
+    }
+
+    /// Longest alias matching `path`, unless a bucket prefix matches as much
+    fn match_alias(&self, normalized_path: &str) -> Option<&AliasRoute> {
+        let alias = self
+            .aliases
+            .iter()
+            .filter(|alias| normalized_path.starts_with(&alias.prefix))
+            .max_by_key(|alias| alias.prefix.len())?;
+        match self.route(normalized_path) {
+            Some(bucket) if bucket.path_prefix.len() >= alias.prefix.len() => None,
+            _ => Some(alias),
+        }
+    }
+
+    /// Path under the bucket prefix for a request on one of its aliases
+    ///
+    /// Returns `None` when no alias applies, so the original path is routed
+    /// as-is.
+    pub fn alias_path(&self, path: &str) -> Option<String> {
+        if self.aliases.is_empty() {
+            return None;
+        }
+        let normalized_path = Self::normalize_path(path);
+        let alias = self.match_alias(&normalized_path)?;
+        Some(format!(
+            "{}{}",
+            self.buckets[alias.bucket].path_prefix,
+            &normalized_path[alias.prefix.len()..]
+        ))
+    }
+
+    /// Prefix and version `path` enters its bucket through
+    ///
+    /// Only reported for buckets that declare aliases or a version, so
+    /// buckets served under a single untagged prefix add no metrics.
+    pub fn route_entry(&self, path: &str) -> Option<RouteEntry<'_>> {
+        let normalized_path = Self::normalize_path(path);
+        if let Some(alias) = self.match_alias(&normalized_path) {
+            return Some(RouteEntry {
+                bucket: &self.buckets[alias.bucket].name,
+                prefix: &alias.prefix,
+                version: alias.version.as_deref(),
+            });
+        }
+        let bucket = self.route(&normalized_path)?;
+        if bucket.aliases.is_empty() && bucket.version.is_none() {
+            return None;
         }
+        Some(RouteEntry {
+            bucket: &bucket.name,
+            prefix: &bucket.path_prefix,
+            version: bucket.version.as_deref(),
+        })
+    }
+
+    /// Path under the bucket prefix for the first pattern route matching `path`
+    ///
+    /// The rendered key is appended to the bucket's prefix, so the result
+    /// routes (and extracts its key) like any prefix path.
+    pub fn pattern_path(&self, path: &str) -> Option<String> {
+        if self.patterns.is_empty() {
+            return None;
+        }
+        let normalized_path = Self::normalize_path(path);
+        self.patterns.iter().find_map(|route| {
+            let captures = route.pattern.captures(&normalized_path)?;
+            Some(format!(
+                "{}/{}",
+                self.buckets[route.bucket].path_prefix.trim_end_matches('/'),
+                render_key(&route.key, &captures)
+            ))
+        })
+    }
+
+    /// Path under the bucket prefix for a request to a virtual host
+    ///
+    /// `host` is the `Host` header (any port is ignored). Returns `None` when
+    /// no bucket claims the host, so the original path is routed as-is.
+    pub fn virtual_host_path(&self, host: &str, path: &str) -> Option<String> {
+        if self.bucket_by_host.is_empty() {
+            return None;
+        }
+        let name = match host.rsplit_once(':') {
+            Some((name, port)) if port.bytes().all(|b| b.is_ascii_digit()) => name,
+            _ => host,
+        };
+        let bucket = &self.buckets[*self.bucket_by_host.get(&name.to_ascii_lowercase())?];
+        Some(format!(
+            "{}/{}",
+            bucket.path_prefix.trim_end_matches('/'),
+            path.trim_start_matches('/')
+        ))
+    }
+
+    /// Path to route a request by: its virtual host, pattern route or alias
+    /// mapped onto the bucket's prefix, in that order, else `path` itself
+    ///
+    /// `host` is the request's `Host` header, if any. Both single requests
+    /// and batch items resolve their path here before [`Router::route`].
+    pub fn bucket_path(&self, host: Option<&str>, path: &str) -> String {
+        host.and_then(|host| self.virtual_host_path(host, path))
+            .or_else(|| self.pattern_path(path))
+            .or_else(|| self.alias_path(path))
+            .unwrap_or_else(|| path.to_string())
     }
 
     pub fn route(&self, path: &str) -> Option<&BucketConfig> {
@@ -0,0 +1,191 @@
+//! Versioned store of static upstream S3 keys.
+//!
+//! Upstream requests read their keys from this store when they are signed,
+//! rather than from clients built at startup. A reload that changes a bucket's
+//! or replica's `access_key`/`secret_key` swaps in a new version: requests
+//! signed before the swap complete with the old key, and every request signed
+//! after it uses the new one. Nothing is dropped or re-signed mid-flight.
+//!
+//! Keys from a credential provider (`s3.credentials`) are not stored here;
+//! the provider already rotates them.
+
+use arc_swap::ArcSwap;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use crate::config::Config;
+
+/// (bucket config name, replica name); `None` for the bucket's own keys
+type StoreKey = (String, Option<String>);
+
+/// One version of a bucket's or replica's keys
+#[derive(Clone, PartialEq, Eq)]
+pub struct StoredCredentials {
+    /// Store-wide version assigned when these keys were first seen
+    pub version: u64,
+    pub access_key: String,
+    pub secret_key: String,
+}
+
+impl std::fmt::Debug for StoredCredentials {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StoredCredentials")
+            .field("version", &self.version)
+            .field("access_key", &self.access_key)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Keys consulted at sign time, swapped atomically on reload
+#[derive(Debug, Default)]
+pub struct CredentialStore {
+    entries: ArcSwap<HashMap<StoreKey, Arc<StoredCredentials>>>,
+    next_version: AtomicU64,
+}
+
+impl CredentialStore {
+    /// Build a store holding the keys of `config`
+    pub fn from_config(config: &Config) -> Self {
+        let store = Self::default();
+        store.update(config);
+        store
+    }
+
+    /// Replace the stored keys with those of `config`
+    ///
+    /// Unchanged keys keep their version. Returns the entries whose keys
+    /// changed (not counting new or removed ones).
+    pub fn update(&self, config: &Config) -> Vec<(String, Option<String>)> {
+        let current = self.entries.load();
+        let mut entries = HashMap::new();
+        let mut rotated = Vec::new();
+
+        let mut insert = |key: StoreKey, access_key: &str, secret_key: &str| {
+            if access_key.is_empty() || secret_key.is_empty() {
+                return;
+            }
+            let previous = current.get(&key);
+            let credentials = match previous {
+                Some(c) if c.access_key == access_key && c.secret_key == secret_key => {
+                    Arc::clone(c)
+                }
+                _ => {
+                    if previous.is_some() {
+                        rotated.push(key.clone());
+                    }
+                    Arc::new(StoredCredentials {
+                        version: self.next_version.fetch_add(1, Ordering::Relaxed) + 1,
+                        access_key: access_key.to_string(),
+                        secret_key: secret_key.to_string(),
+                    })
+                }
+            };
+            entries.insert(key, credentials);
+        };
+
+        // Normalized so a legacy bucket also has its "default" replica entry
+        for bucket in &config.normalize().buckets {
+            let s3 = &bucket.s3;
+            insert((bucket.name.clone(), None), &s3.access_key, &s3.secret_key);
+            for replica in s3.replicas.iter().flatten() {
+                insert(
+                    (bucket.name.clone(), Some(replica.name.clone())),
+                    &replica.access_key,
+                    &replica.secret_key,
+                );
+            }
+        }
+
+        self.entries.store(Arc::new(entries));
+        rotated
+    }
+
+    /// Current keys of a replica, else of the bucket itself
+    pub fn get(&self, bucket: &str, replica: Option<&str>) -> Option<Arc<StoredCredentials>> {
+        let entries = self.entries.load();
+        replica
+            .and_then(|r| entries.get(&(bucket.to_string(), Some(r.to_string()))))
+            .or_else(|| entries.get(&(bucket.to_string(), None)))
+            .cloned()
+    }
+
+    /// Number of stored bucket and replica entries
+    pub fn len(&self) -> usize {
+        self.entries.load().len()
+    }
+
+    /// Check if the store is empty
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(media_secret: &str) -> Config {
+        let yaml = format!(
+            r#"
+server:
+  address: "127.0.0.1"
+  port: 8080
+buckets:
+  - name: media
+    path_prefix: /media
+    s3:
+      bucket: media-prod
+      region: us-east-1
+      access_key: AKIAMEDIA
+      secret_key: {}
+  - name: assets
+    path_prefix: /assets
+    s3:
+      replicas:
+        - name: primary
+          bucket: assets-us
+          region: us-east-1
+          access_key: AKIAPRIMARY
+          secret_key: primary-secret
+          priority: 1
+        - name: eu
+          bucket: assets-eu
+          region: eu-west-1
+          access_key: AKIAEU
+          secret_key: eu-secret
+          priority: 2
+"#,
+            media_secret
+        );
+        Config::from_yaml_with_env(&yaml).unwrap()
+    }
+
+    #[test]
+    fn test_credential_store_rotation_keeps_old_versions_alive() {
+        let store = CredentialStore::from_config(&config("old-secret"));
+        // media: bucket + "default" replica; assets: two replicas
+        assert_eq!(store.len(), 4);
+
+        let in_flight = store.get("media", Some("default")).unwrap();
+        assert_eq!(in_flight.secret_key, "old-secret");
+        let eu = store.get("assets", Some("eu")).unwrap();
+        assert_eq!(eu.access_key, "AKIAEU");
+        // Unknown replica falls back to the bucket's own keys
+        assert_eq!(
+            store.get("media", Some("other")).unwrap().access_key,
+            "AKIAMEDIA"
+        );
+        assert!(store.get("assets", None).is_none());
+
+        let rotated = store.update(&config("new-secret"));
+        assert_eq!(rotated.len(), 2);
+        let current = store.get("media", Some("default")).unwrap();
+        assert_eq!(current.secret_key, "new-secret");
+        assert!(current.version > in_flight.version);
+        // A request holding the old version still has the old key
+        assert_eq!(in_flight.secret_key, "old-secret");
+        // Unchanged keys keep their version
+        assert_eq!(store.get("assets", Some("eu")).unwrap().version, eu.version);
+    }
+}
@@ -0,0 +1,567 @@
+//! Temporary upstream credentials from the environment the proxy runs in.
+//!
+//! Buckets with `s3.credentials.source` other than `static` sign upstream
+//! requests with the credentials of the role the proxy runs as:
+//!
+//! - `instance`: EC2 instance profile via IMDSv2 (session token first, then
+//!   the role's credentials)
+//! - `ecs`: ECS task role from `AWS_CONTAINER_CREDENTIALS_RELATIVE_URI` or
+//!   `AWS_CONTAINER_CREDENTIALS_FULL_URI`
+//! - `web_identity`: STS `AssumeRoleWithWebIdentity` with the token in
+//!   `AWS_WEB_IDENTITY_TOKEN_FILE` (EKS IRSA)
+//! - `auto`: web identity, then ECS, then instance, depending on which the
+//!   environment configures
+//!
+//! With `assume_role_arn`, the source credentials (or the bucket's static
+//! keys) sign an STS `AssumeRole` call and the assumed role's session signs
+//! upstream requests, e.g. for a bucket in another account.
+//!
+//! Credentials are fetched once at startup and refreshed in the background
+//! `refresh_before_expiry_secs` before they expire. A failed refresh keeps the
+//! previous credentials and is retried, so a metadata service hiccup does not
+//! fail requests while the old credentials are still valid.
+
+use chrono::{DateTime, Utc};
+use parking_lot::RwLock;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+
+use super::{canonical_query_string, extract_xml_tag_content, sign_request, SigningParams};
+use crate::config::{CredentialSource, S3CredentialsConfig};
+use crate::constants::{
+    DEFAULT_IMDS_ENDPOINT, DEFAULT_STS_ENDPOINT, ECS_CREDENTIALS_ENDPOINT, IMDS_TOKEN_TTL_SECS,
+    S3_CREDENTIALS_FETCH_TIMEOUT_SECS, S3_CREDENTIALS_RETRY_SECS,
+};
+
+/// Role session name used when `AWS_ROLE_SESSION_NAME` is not set
+const DEFAULT_ROLE_SESSION_NAME: &str = "yatagarasu";
+
+/// Signing credentials for upstream requests
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionCredentials {
+    pub access_key_id: String,
+    pub secret_access_key: String,
+    /// Sent as `x-amz-security-token` with temporary credentials
+    pub session_token: Option<String>,
+    /// When the credentials stop working (`None`: never)
+    pub expiration: Option<DateTime<Utc>>,
+}
+
+/// Credentials document returned by IMDS and the ECS endpoint
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct MetadataCredentials {
+    #[serde(default)]
+    code: Option<String>,
+    access_key_id: String,
+    secret_access_key: String,
+    #[serde(default)]
+    token: Option<String>,
+    #[serde(default)]
+    expiration: Option<DateTime<Utc>>,
+}
+
+impl TryFrom<MetadataCredentials> for SessionCredentials {
+    type Error = String;
+
+    fn try_from(doc: MetadataCredentials) -> Result<Self, String> {
+        if let Some(code) = doc.code.filter(|c| c != "Success") {
+            return Err(format!("Metadata service returned code '{}'", code));
+        }
+        Ok(SessionCredentials {
+            access_key_id: doc.access_key_id,
+            secret_access_key: doc.secret_access_key,
+            session_token: doc.token,
+            expiration: doc.expiration,
+        })
+    }
+}
+
+/// Parse an `AssumeRole` or `AssumeRoleWithWebIdentity` XML response
+fn parse_sts_response(xml: &str) -> Result<SessionCredentials, String> {
+    if let Some(code) = extract_xml_tag_content(xml, "Code") {
+        let message = extract_xml_tag_content(xml, "Message").unwrap_or_default();
+        return Err(format!("STS error {}: {}", code, message));
+    }
+    let field = |tag: &str| {
+        extract_xml_tag_content(xml, tag)
+            .ok_or_else(|| format!("STS response is missing <{}>", tag))
+    };
+    let expiration = DateTime::parse_from_rfc3339(&field("Expiration")?)
+        .map_err(|e| format!("Invalid STS Expiration: {}", e))?;
+    Ok(SessionCredentials {
+        access_key_id: field("AccessKeyId")?,
+        secret_access_key: field("SecretAccessKey")?,
+        session_token: Some(field("SessionToken")?),
+        expiration: Some(expiration.with_timezone(&Utc)),
+    })
+}
+
+/// How long to wait before the next refresh
+///
+/// Refreshes `refresh_before` ahead of expiry, but never sooner than the retry
+/// delay so an already-short lifetime does not turn into a busy loop.
+fn refresh_delay(
+    credentials: &SessionCredentials,
+    refresh_before: Duration,
+    now: DateTime<Utc>,
+) -> Option<Duration> {
+    let expiration = credentials.expiration?;
+    let remaining = (expiration - now).to_std().unwrap_or_default();
+    Some(
+        remaining
+            .saturating_sub(refresh_before)
+            .max(Duration::from_secs(S3_CREDENTIALS_RETRY_SECS)),
+    )
+}
+
+/// Signing region of an STS endpoint (`sts.<region>.amazonaws.com`, else us-east-1)
+fn sts_region(endpoint: &str) -> &str {
+    endpoint
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .strip_prefix("sts.")
+        .and_then(|host| host.split_once('.'))
+        .map(|(region, _)| region)
+        .filter(|region| *region != "amazonaws")
+        .unwrap_or("us-east-1")
+}
+
+/// Fetches and caches the credentials for one `s3.credentials` config
+pub struct CredentialProvider {
+    config: S3CredentialsConfig,
+    /// Keys from the bucket config, exchanged via AssumeRole (source `static`)
+    static_credentials: Option<SessionCredentials>,
+    http: reqwest::Client,
+    current: RwLock<Option<SessionCredentials>>,
+}
+
+impl CredentialProvider {
+    /// Create a provider; nothing is fetched until [`Self::refresh`]
+    pub fn new(config: S3CredentialsConfig) -> Self {
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(S3_CREDENTIALS_FETCH_TIMEOUT_SECS))
+            .build()
+            .unwrap_or_default();
+        Self {
+            config,
+            static_credentials: None,
+            http,
+            current: RwLock::new(None),
+        }
+    }
+
+    /// Use static keys as the source credentials for AssumeRole
+    pub fn with_static_keys(mut self, access_key: &str, secret_key: &str) -> Self {
+        self.static_credentials = Some(SessionCredentials {
+            access_key_id: access_key.to_string(),
+            secret_access_key: secret_key.to_string(),
+            session_token: None,
+            expiration: None,
+        });
+        self
+    }
+
+    /// Settings this provider was built from
+    pub fn config(&self) -> &S3CredentialsConfig {
+        &self.config
+    }
+
+    /// Latest credentials, `None` until the first fetch succeeds
+    pub fn current(&self) -> Option<SessionCredentials> {
+        self.current.read().clone()
+    }
+
+    /// Fetch fresh credentials and make them current
+    pub async fn refresh(&self) -> Result<SessionCredentials, String> {
+        let credentials = self.fetch().await?;
+        *self.current.write() = Some(credentials.clone());
+        Ok(credentials)
+    }
+
+    /// Fetch now, then keep refreshing ahead of expiry in the background
+    ///
+    /// Must be called from within a Tokio runtime. Startup waits for the first
+    /// fetch so requests do not race it.
+    pub async fn start(self: &Arc<Self>) {
+        let mut next = self.refresh_logged().await;
+        let provider = Arc::clone(self);
+        tokio::spawn(async move {
+            // Non-expiring credentials never need a refresh
+            while let Some(delay) = next {
+                tokio::time::sleep(delay).await;
+                next = provider.refresh_logged().await;
+            }
+        });
+    }
+
+    /// Refresh and return the delay until the next attempt
+    async fn refresh_logged(&self) -> Option<Duration> {
+        match self.refresh().await {
+            Ok(credentials) => {
+                tracing::info!(
+                    source = self.config.source.as_str(),
+                    assume_role_arn = ?self.config.assume_role_arn,
+                    access_key_id = %credentials.access_key_id,
+                    expiration = ?credentials.expiration,
+                    "Fetched upstream S3 credentials"
+                );
+                refresh_delay(
+                    &credentials,
+                    Duration::from_secs(self.config.refresh_before_expiry_secs),
+                    Utc::now(),
+                )
+            }
+            Err(e) => {
+                tracing::warn!(
+                    source = self.config.source.as_str(),
+                    assume_role_arn = ?self.config.assume_role_arn,
+                    error = %e,
+                    retry_secs = S3_CREDENTIALS_RETRY_SECS,
+                    "Failed to fetch upstream S3 credentials, keeping previous ones"
+                );
+                Some(Duration::from_secs(S3_CREDENTIALS_RETRY_SECS))
+            }
+        }
+    }
+
+    async fn fetch(&self) -> Result<SessionCredentials, String> {
+        let source_credentials = match self.config.source {
+            CredentialSource::Static => self
+                .static_credentials
+                .clone()
+                .ok_or("Static credentials are read from the config")?,
+            _ => self.fetch_source().await?,
+        };
+        match &self.config.assume_role_arn {
+            Some(role_arn) => self.assume_role(role_arn, &source_credentials).await,
+            None => Ok(source_credentials),
+        }
+    }
+
+    async fn fetch_source(&self) -> Result<SessionCredentials, String> {
+        match self.config.source {
+            CredentialSource::Static => Err("Static credentials are read from the config".into()),
+            CredentialSource::Instance => self.fetch_instance().await,
+            CredentialSource::Ecs => self.fetch_ecs().await,
+            CredentialSource::WebIdentity => self.fetch_web_identity().await,
+            CredentialSource::Auto => {
+                if self.web_identity_params().is_some() {
+                    self.fetch_web_identity().await
+                } else if std::env::var_os("AWS_CONTAINER_CREDENTIALS_RELATIVE_URI").is_some()
+                    || std::env::var_os("AWS_CONTAINER_CREDENTIALS_FULL_URI").is_some()
+                {
+                    self.fetch_ecs().await
+                } else {
+                    self.fetch_instance().await
+                }
+            }
+        }
+    }
+
+    /// IMDSv2: session token, role name, then the role's credentials
+    async fn fetch_instance(&self) -> Result<SessionCredentials, String> {
+        let endpoint = self
+            .config
+            .imds_endpoint
+            .as_deref()
+            .unwrap_or(DEFAULT_IMDS_ENDPOINT)
+            .trim_end_matches('/');
+        let token = self
+            .http
+            .put(format!("{}/latest/api/token", endpoint))
+            .header(
+                "X-aws-ec2-metadata-token-ttl-seconds",
+                IMDS_TOKEN_TTL_SECS.to_string(),
+            )
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| format!("IMDS token request failed: {}", e))?
+            .text()
+            .await
+            .map_err(|e| format!("IMDS token request failed: {}", e))?;
+
+        let roles_url = format!("{}/latest/meta-data/iam/security-credentials/", endpoint);
+        let roles = self.imds_get(&roles_url, &token).await?;
+        let role = roles
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .ok_or("No IAM role is attached to this instance")?;
+
+        let document = self
+            .imds_get(&format!("{}{}", roles_url, role), &token)
+            .await?;
+        serde_json::from_str::<MetadataCredentials>(&document)
+            .map_err(|e| format!("Invalid IMDS credentials document: {}", e))?
+            .try_into()
+    }
+
+    async fn imds_get(&self, url: &str, token: &str) -> Result<String, String> {
+        self.http
+            .get(url)
+            .header("X-aws-ec2-metadata-token", token)
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| format!("IMDS request failed: {}", e))?
+            .text()
+            .await
+            .map_err(|e| format!("IMDS request failed: {}", e))
+    }
+
+    /// ECS container credentials endpoint
+    async fn fetch_ecs(&self) -> Result<SessionCredentials, String> {
+        let url = match (
+            std::env::var("AWS_CONTAINER_CREDENTIALS_RELATIVE_URI"),
+            std::env::var("AWS_CONTAINER_CREDENTIALS_FULL_URI"),
+        ) {
+            (Ok(relative), _) => format!("{}{}", ECS_CREDENTIALS_ENDPOINT, relative),
+            (_, Ok(full)) => full,
+            _ => {
+                return Err("AWS_CONTAINER_CREDENTIALS_RELATIVE_URI or \
+                     AWS_CONTAINER_CREDENTIALS_FULL_URI must be set"
+                    .to_string())
+            }
+        };
+        let authorization = match std::env::var("AWS_CONTAINER_AUTHORIZATION_TOKEN_FILE") {
+            Ok(path) => Some(
+                std::fs::read_to_string(&path)
+                    .map_err(|e| format!("Failed to read '{}': {}", path, e))?
+                    .trim()
+                    .to_string(),
+            ),
+            Err(_) => std::env::var("AWS_CONTAINER_AUTHORIZATION_TOKEN").ok(),
+        };
+
+        let mut request = self.http.get(&url);
+        if let Some(authorization) = authorization {
+            request = request.header("Authorization", authorization);
+        }
+        request
+            .send()
+            .await
+            .and_then(|r| r.error_for_status())
+            .map_err(|e| format!("ECS credentials request failed: {}", e))?
+            .json::<MetadataCredentials>()
+            .await
+            .map_err(|e| format!("Invalid ECS credentials document: {}", e))?
+            .try_into()
+    }
+
+    /// Role ARN and token file from the config, falling back to the environment
+    fn web_identity_params(&self) -> Option<(String, String)> {
+        let role_arn = self
+            .config
+            .role_arn
+            .clone()
+            .or_else(|| std::env::var("AWS_ROLE_ARN").ok())?;
+        let token_file = self
+            .config
+            .web_identity_token_file
+            .clone()
+            .or_else(|| std::env::var("AWS_WEB_IDENTITY_TOKEN_FILE").ok())?;
+        Some((role_arn, token_file))
+    }
+
+    /// STS AssumeRoleWithWebIdentity (the call itself is unsigned)
+    async fn fetch_web_identity(&self) -> Result<SessionCredentials, String> {
+        let (role_arn, token_file) = self.web_identity_params().ok_or(
+            "Web identity requires role_arn/AWS_ROLE_ARN and \
+             web_identity_token_file/AWS_WEB_IDENTITY_TOKEN_FILE",
+        )?;
+        // Re-read on every refresh: the token file is rotated by the kubelet
+        let token = std::fs::read_to_string(&token_file)
+            .map_err(|e| format!("Failed to read '{}': {}", token_file, e))?;
+        let session_name = self.session_name();
+        let endpoint = self
+            .config
+            .sts_endpoint
+            .as_deref()
+            .unwrap_or(DEFAULT_STS_ENDPOINT);
+
+        let body = self
+            .http
+            .get(endpoint)
+            .query(&[
+                ("Action", "AssumeRoleWithWebIdentity"),
+                ("Version", "2011-06-15"),
+                ("RoleArn", role_arn.as_str()),
+                ("RoleSessionName", session_name.as_str()),
+                ("WebIdentityToken", token.trim()),
+            ])
+            .send()
+            .await
+            .map_err(|e| format!("STS request failed: {}", e))?
+            .text()
+            .await
+            .map_err(|e| format!("STS request failed: {}", e))?;
+        parse_sts_response(&body)
+    }
+
+    /// Role session name from the config, the environment or the default
+    fn session_name(&self) -> String {
+        self.config
+            .role_session_name
+            .clone()
+            .or_else(|| std::env::var("AWS_ROLE_SESSION_NAME").ok())
+            .unwrap_or_else(|| DEFAULT_ROLE_SESSION_NAME.to_string())
+    }
+
+    /// STS AssumeRole, SigV4-signed with the source credentials
+    async fn assume_role(
+        &self,
+        role_arn: &str,
+        source: &SessionCredentials,
+    ) -> Result<SessionCredentials, String> {
+        let endpoint = self
+            .config
+            .sts_endpoint
+            .as_deref()
+            .unwrap_or(DEFAULT_STS_ENDPOINT)
+            .trim_end_matches('/');
+        let host = endpoint
+            .trim_start_matches("https://")
+            .trim_start_matches("http://")
+            .to_string();
+        let session_name = self.session_name();
+        let duration = self.config.assume_role_duration_secs.to_string();
+        let mut params = vec![
+            ("Action", "AssumeRole"),
+            ("Version", "2011-06-15"),
+            ("RoleArn", role_arn),
+            ("RoleSessionName", session_name.as_str()),
+            ("DurationSeconds", duration.as_str()),
+        ];
+        if let Some(external_id) = &self.config.external_id {
+            params.push(("ExternalId", external_id.as_str()));
+        }
+        let body = canonical_query_string(&params);
+
+        let now = Utc::now();
+        let date = now.format("%Y%m%d").to_string();
+        let datetime = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let mut headers = std::collections::HashMap::from([
+            ("host".to_string(), host),
+            (
+                "content-type".to_string(),
+                "application/x-www-form-urlencoded; charset=utf-8".to_string(),
+            ),
+            ("x-amz-date".to_string(), datetime.clone()),
+        ]);
+        if let Some(token) = &source.session_token {
+            headers.insert(super::SECURITY_TOKEN_HEADER.to_string(), token.clone());
+        }
+        let authorization = sign_request(&SigningParams {
+            method: "POST",
+            uri: "/",
+            query_string: "",
+            headers: &headers,
+            payload: body.as_bytes(),
+            access_key: &source.access_key_id,
+            secret_key: &source.secret_access_key,
+            region: sts_region(endpoint),
+            service: "sts",
+            date: &date,
+            datetime: &datetime,
+        });
+
+        let mut request = self.http.post(format!("{}/", endpoint)).body(body);
+        for (name, value) in &headers {
+            // reqwest sets Host from the URL
+            if name != "host" {
+                request = request.header(name, value);
+            }
+        }
+        let body = request
+            .header("Authorization", authorization)
+            .send()
+            .await
+            .map_err(|e| format!("STS AssumeRole request failed: {}", e))?
+            .text()
+            .await
+            .map_err(|e| format!("STS AssumeRole request failed: {}", e))?;
+        parse_sts_response(&body)
+    }
+}
+
+impl std::fmt::Debug for CredentialProvider {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CredentialProvider")
+            .field("source", &self.config.source)
+            .field("assume_role_arn", &self.config.assume_role_arn)
+            .field("ready", &self.current.read().is_some())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_credentials_and_refresh_delay() {
+        let imds = r#"{"Code":"Success","LastUpdated":"2026-10-16T10:00:00Z","Type":"AWS-HMAC",
+            "AccessKeyId":"ASIAEXAMPLE","SecretAccessKey":"secret","Token":"token",
+            "Expiration":"2026-10-16T16:00:00Z"}"#;
+        let credentials: SessionCredentials = serde_json::from_str::<MetadataCredentials>(imds)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        assert_eq!(credentials.access_key_id, "ASIAEXAMPLE");
+        assert_eq!(credentials.session_token.as_deref(), Some("token"));
+
+        let failed =
+            r#"{"Code":"AssumeRoleUnauthorizedAccess","AccessKeyId":"","SecretAccessKey":""}"#;
+        let failed = serde_json::from_str::<MetadataCredentials>(failed).unwrap();
+        assert!(SessionCredentials::try_from(failed).is_err());
+
+        let sts = "<AssumeRoleWithWebIdentityResponse><AssumeRoleWithWebIdentityResult>\
+            <Credentials><AccessKeyId>ASIASTS</AccessKeyId><SecretAccessKey>s</SecretAccessKey>\
+            <SessionToken>t</SessionToken><Expiration>2026-10-16T11:00:00Z</Expiration>\
+            </Credentials></AssumeRoleWithWebIdentityResult></AssumeRoleWithWebIdentityResponse>";
+        let sts = parse_sts_response(sts).unwrap();
+        assert_eq!(sts.access_key_id, "ASIASTS");
+        assert!(parse_sts_response(
+            "<ErrorResponse><Error><Code>AccessDenied</Code></Error></ErrorResponse>"
+        )
+        .is_err());
+
+        // Refresh 5 minutes ahead of expiry, never sooner than the retry delay
+        let now = "2026-10-16T10:00:00Z".parse::<DateTime<Utc>>().unwrap();
+        let before = Duration::from_secs(300);
+        assert_eq!(
+            refresh_delay(&sts, before, now),
+            Some(Duration::from_secs(3300))
+        );
+        let late = "2026-10-16T10:58:00Z".parse::<DateTime<Utc>>().unwrap();
+        assert_eq!(
+            refresh_delay(&sts, before, late),
+            Some(Duration::from_secs(S3_CREDENTIALS_RETRY_SECS))
+        );
+        let permanent = SessionCredentials {
+            expiration: None,
+            ..sts
+        };
+        assert_eq!(refresh_delay(&permanent, before, now), None);
+
+        // AssumeRole answers with the same credentials element
+        let assumed = "<AssumeRoleResponse><AssumeRoleResult><Credentials>\
+            <AccessKeyId>ASIAXACCT</AccessKeyId><SecretAccessKey>s</SecretAccessKey>\
+            <SessionToken>t</SessionToken><Expiration>2026-10-16T11:00:00Z</Expiration>\
+            </Credentials><AssumedRoleUser><Arn>arn:aws:sts::210987654321:assumed-role/r/y</Arn>\
+            </AssumedRoleUser></AssumeRoleResult></AssumeRoleResponse>";
+        assert_eq!(
+            parse_sts_response(assumed).unwrap().access_key_id,
+            "ASIAXACCT"
+        );
+        assert_eq!(sts_region("https://sts.amazonaws.com"), "us-east-1");
+        assert_eq!(
+            sts_region("https://sts.eu-west-1.amazonaws.com"),
+            "eu-west-1"
+        );
+        assert_eq!(sts_region("http://minio:9000"), "us-east-1");
+    }
+}
@@ -0,0 +1,187 @@
+//! ListObjectsV2 proxying.
+//!
+//! A `GET` (or `HEAD`) on a bucket's prefix with no object key is a bucket
+//! listing. The client's listing parameters are forwarded as a canonical
+//! `list-type=2` query, which is part of the SigV4 canonical request.
+//!
+//! Browser clients can ask for JSON instead of S3's XML; [`to_json`]
+//! translates a `ListBucketResult` document.
+
+use std::collections::HashMap;
+
+use super::canonical_query_string;
+
+/// Client query parameters forwarded to ListObjectsV2
+const FORWARDED_PARAMS: &[&str] = &[
+    "continuation-token",
+    "delimiter",
+    "encoding-type",
+    "fetch-owner",
+    "max-keys",
+    "prefix",
+    "start-after",
+];
+
+/// Response body format for a listing
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListingFormat {
+    /// S3's `ListBucketResult` XML, passed through
+    Xml,
+    /// Translated to JSON by [`to_json`]
+    Json,
+}
+
+/// Canonical ListObjectsV2 query for a client's raw query string
+///
+/// Unknown parameters are dropped; `list-type=2` is always set.
+pub fn upstream_query(query: Option<&str>) -> String {
+    let params: HashMap<&str, String> = query
+        .unwrap_or_default()
+        .split('&')
+        .filter_map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            FORWARDED_PARAMS.contains(&name).then(|| {
+                let value = urlencoding::decode(&value.replace('+', " "))
+                    .map(|v| v.into_owned())
+                    .unwrap_or_default();
+                (name, value)
+            })
+        })
+        .collect();
+
+    let mut pairs: Vec<(&str, &str)> = params
+        .iter()
+        .map(|(name, value)| (*name, value.as_str()))
+        .collect();
+    pairs.push(("list-type", "2"));
+    canonical_query_string(&pairs)
+}
+
+/// Translate a `ListBucketResult` XML document to JSON
+pub fn to_json(xml: &str) -> Result<String, String> {
+    if !xml.contains("<ListBucketResult") {
+        return Err("Response is not a ListBucketResult document".to_string());
+    }
+
+    let contents: Vec<serde_json::Value> = blocks(xml, "Contents")
+        .into_iter()
+        .map(|entry| {
+            serde_json::json!({
+                "key": tag(entry, "Key"),
+                "size": tag(entry, "Size").and_then(|s| s.parse::<u64>().ok()),
+                "last_modified": tag(entry, "LastModified"),
+                "etag": tag(entry, "ETag").map(|e| e.trim_matches('"').to_string()),
+                "storage_class": tag(entry, "StorageClass"),
+            })
+        })
+        .collect();
+    let common_prefixes: Vec<String> = blocks(xml, "CommonPrefixes")
+        .into_iter()
+        .filter_map(|entry| tag(entry, "Prefix"))
+        .collect();
+
+    // Top-level fields, without the nested Prefix of CommonPrefixes
+    let top = strip_blocks(&strip_blocks(xml, "Contents"), "CommonPrefixes");
+    let body = serde_json::json!({
+        "name": tag(&top, "Name"),
+        "prefix": tag(&top, "Prefix").unwrap_or_default(),
+        "delimiter": tag(&top, "Delimiter"),
+        "key_count": tag(&top, "KeyCount").and_then(|s| s.parse::<u64>().ok()),
+        "max_keys": tag(&top, "MaxKeys").and_then(|s| s.parse::<u64>().ok()),
+        "is_truncated": tag(&top, "IsTruncated").as_deref() == Some("true"),
+        "next_continuation_token": tag(&top, "NextContinuationToken"),
+        "contents": contents,
+        "common_prefixes": common_prefixes,
+    });
+    Ok(body.to_string())
+}
+
+/// Unescaped text of the first `<name>` element
+fn tag(xml: &str, name: &str) -> Option<String> {
+    let open = format!("<{}>", name);
+    let close = format!("</{}>", name);
+    let start = xml.find(&open)? + open.len();
+    let end = start + xml[start..].find(&close)?;
+    Some(unescape(&xml[start..end]))
+}
+
+/// Inner text of every `<name>` element
+fn blocks<'a>(xml: &'a str, name: &str) -> Vec<&'a str> {
+    let open = format!("<{}>", name);
+    let close = format!("</{}>", name);
+    let mut found = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open) {
+        let inner = &rest[start + open.len()..];
+        let Some(end) = inner.find(&close) else {
+            break;
+        };
+        found.push(&inner[..end]);
+        rest = &inner[end + close.len()..];
+    }
+    found
+}
+
+/// Document with every `<name>` element removed
+fn strip_blocks(xml: &str, name: &str) -> String {
+    let open = format!("<{}>", name);
+    let close = format!("</{}>", name);
+    let mut result = String::with_capacity(xml.len());
+    let mut rest = xml;
+    while let Some(start) = rest.find(&open) {
+        result.push_str(&rest[..start]);
+        match rest[start..].find(&close) {
+            Some(end) => rest = &rest[start + end + close.len()..],
+            None => {
+                rest = "";
+                break;
+            }
+        }
+    }
+    result.push_str(rest);
+    result
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#34;", "\"")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_upstream_query_and_json_translation() {
+        assert_eq!(upstream_query(None), "list-type=2");
+        assert_eq!(
+            upstream_query(Some("list-type=2&prefix=photos%2F2024&delimiter=/&x-id=1")),
+            "delimiter=%2F&list-type=2&prefix=photos%2F2024"
+        );
+
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+  <Name>media</Name><Prefix>photos/</Prefix><KeyCount>2</KeyCount><MaxKeys>1000</MaxKeys>
+  <Delimiter>/</Delimiter><IsTruncated>true</IsTruncated>
+  <NextContinuationToken>abc</NextContinuationToken>
+  <Contents><Key>photos/a&amp;b.jpg</Key><LastModified>2024-01-01T00:00:00.000Z</LastModified>
+    <ETag>&quot;d41d8&quot;</ETag><Size>42</Size><StorageClass>STANDARD</StorageClass></Contents>
+  <CommonPrefixes><Prefix>photos/2024/</Prefix></CommonPrefixes>
+</ListBucketResult>"#;
+        let json: serde_json::Value = serde_json::from_str(&to_json(xml).unwrap()).unwrap();
+        assert_eq!(json["name"], "media");
+        assert_eq!(json["prefix"], "photos/");
+        assert_eq!(json["is_truncated"], true);
+        assert_eq!(json["next_continuation_token"], "abc");
+        assert_eq!(json["contents"][0]["key"], "photos/a&b.jpg");
+        assert_eq!(json["contents"][0]["etag"], "d41d8");
+        assert_eq!(json["contents"][0]["size"], 42);
+        assert_eq!(json["common_prefixes"][0], "photos/2024/");
+
+        assert!(to_json("<Error><Code>AccessDenied</Code></Error>").is_err());
+    }
+}
@@ -0,0 +1,219 @@
+//! S3 multipart upload passthrough.
+//!
+//! Buckets that list `PUT`, `POST` or `DELETE` in `allowed_methods` accept the
+//! multipart upload lifecycle on those methods, and nothing else:
+//!
+//! | Operation | Request |
+//! |-----------|---------|
+//! | CreateMultipartUpload | `POST /key?uploads` |
+//! | UploadPart | `PUT /key?partNumber=N&uploadId=ID` |
+//! | CompleteMultipartUpload | `POST /key?uploadId=ID` |
+//! | AbortMultipartUpload | `DELETE /key?uploadId=ID` |
+//!
+//! Only these subresource parameters are forwarded; they are part of the
+//! SigV4 canonical request, together with the client's `x-amz-*` headers.
+//! Part bodies are streamed through unhashed (`UNSIGNED-PAYLOAD`).
+
+use std::collections::HashMap;
+
+use super::{
+    canonical_query_string, sign_request_unsigned_payload, SigningParams, UNSIGNED_PAYLOAD,
+};
+
+/// Highest part number S3 accepts
+const MAX_PART_NUMBER: u32 = 10_000;
+
+/// A multipart upload lifecycle request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MultipartOperation {
+    Create,
+    UploadPart { part_number: u32, upload_id: String },
+    Complete { upload_id: String },
+    Abort { upload_id: String },
+}
+
+impl MultipartOperation {
+    /// Identify the operation from the method and the raw query string
+    ///
+    /// Valueless parameters count (`?uploads` as sent by the AWS SDKs).
+    pub fn classify(method: &str, query: Option<&str>) -> Option<Self> {
+        let query: HashMap<&str, String> = query
+            .unwrap_or_default()
+            .split('&')
+            .filter(|pair| !pair.is_empty())
+            .map(|pair| {
+                let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+                let value = urlencoding::decode(value)
+                    .map(|v| v.into_owned())
+                    .unwrap_or_default();
+                (name, value)
+            })
+            .collect();
+        let upload_id = query.get("uploadId").filter(|id| !id.is_empty()).cloned();
+        match (method, upload_id) {
+            ("POST", None) if query.contains_key("uploads") => Some(Self::Create),
+            ("POST", Some(upload_id)) => Some(Self::Complete { upload_id }),
+            ("DELETE", Some(upload_id)) => Some(Self::Abort { upload_id }),
+            ("PUT", Some(upload_id)) => {
+                let part_number = query.get("partNumber")?.parse().ok()?;
+                (1..=MAX_PART_NUMBER)
+                    .contains(&part_number)
+                    .then_some(Self::UploadPart {
+                        part_number,
+                        upload_id,
+                    })
+            }
+            _ => None,
+        }
+    }
+
+    /// Operation name for logs and metrics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Create => "create",
+            Self::UploadPart { .. } => "upload_part",
+            Self::Complete { .. } => "complete",
+            Self::Abort { .. } => "abort",
+        }
+    }
+
+    /// Whether success replaces the object at the key, making cached copies stale
+    pub fn replaces_object(&self) -> bool {
+        matches!(self, Self::Complete { .. })
+    }
+
+    /// Canonical query string sent (and signed) upstream
+    pub fn upstream_query(&self) -> String {
+        match self {
+            Self::Create => canonical_query_string(&[("uploads", "")]),
+            Self::UploadPart {
+                part_number,
+                upload_id,
+            } => canonical_query_string(&[
+                ("partNumber", &part_number.to_string()),
+                ("uploadId", upload_id),
+            ]),
+            Self::Complete { upload_id } | Self::Abort { upload_id } => {
+                canonical_query_string(&[("uploadId", upload_id)])
+            }
+        }
+    }
+}
+
+/// Whether a successful write replaces or removes the object at its key
+///
+/// Of the multipart operations only CompleteMultipartUpload does. Any other
+/// `PUT` or `DELETE` (PutObject, CopyObject, DeleteObject) does too, though
+/// the proxy currently turns those away before they reach S3.
+pub fn write_replaces_object(method: &str, query: Option<&str>) -> bool {
+    match MultipartOperation::classify(method, query) {
+        Some(operation) => operation.replaces_object(),
+        None => matches!(method, "PUT" | "DELETE"),
+    }
+}
+
+/// Upstream request to sign for a multipart operation
+pub struct UploadRequest<'a> {
+    pub method: &'a str,
+    pub host: &'a str,
+    /// Path sent upstream (`/bucket/key` or `/key`)
+    pub uri: &'a str,
+    /// Canonical query string from [`MultipartOperation::upstream_query`]
+    pub query: &'a str,
+    /// Client `x-amz-*` headers forwarded upstream (S3 rejects unsigned ones)
+    pub amz_headers: &'a [(String, String)],
+}
+
+/// SigV4 headers for a multipart request (`host`, `x-amz-date`,
+/// `x-amz-content-sha256: UNSIGNED-PAYLOAD` and `authorization`)
+pub fn signed_headers(
+    request: &UploadRequest<'_>,
+    access_key: &str,
+    secret_key: &str,
+    region: &str,
+) -> HashMap<String, String> {
+    let now = chrono::Utc::now();
+    let datetime = now.format("%Y%m%dT%H%M%SZ").to_string();
+    let date = now.format("%Y%m%d").to_string();
+
+    let mut headers: HashMap<String, String> = request
+        .amz_headers
+        .iter()
+        .map(|(name, value)| (name.to_ascii_lowercase(), value.clone()))
+        .collect();
+    headers.insert("host".to_string(), request.host.to_string());
+    headers.insert("x-amz-date".to_string(), datetime.clone());
+    headers.insert(
+        "x-amz-content-sha256".to_string(),
+        UNSIGNED_PAYLOAD.to_string(),
+    );
+
+    let params = SigningParams {
+        method: request.method,
+        uri: request.uri,
+        query_string: request.query,
+        headers: &headers,
+        payload: b"",
+        access_key,
+        secret_key,
+        region,
+        service: "s3",
+        date: &date,
+        datetime: &datetime,
+    };
+    let authorization = sign_request_unsigned_payload(&params);
+    headers.insert("authorization".to_string(), authorization);
+    headers
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_and_upstream_query() {
+        let create = MultipartOperation::classify("POST", Some("uploads")).unwrap();
+        assert_eq!(create.upstream_query(), "uploads=");
+
+        let part =
+            MultipartOperation::classify("PUT", Some("uploadId=a%2Fb%2Bc%3D&partNumber=3&w=100"))
+                .unwrap();
+        assert_eq!(part.as_str(), "upload_part");
+        assert_eq!(part.upstream_query(), "partNumber=3&uploadId=a%2Fb%2Bc%3D");
+
+        let complete = MultipartOperation::classify("POST", Some("uploadId=x")).unwrap();
+        assert!(matches!(complete, MultipartOperation::Complete { .. }));
+        assert!(complete.replaces_object());
+        assert!(!part.replaces_object());
+        assert!(matches!(
+            MultipartOperation::classify("DELETE", Some("uploadId=x")),
+            Some(MultipartOperation::Abort { .. })
+        ));
+
+        // Plain writes and out-of-range parts are not multipart operations
+        assert_eq!(MultipartOperation::classify("PUT", None), None);
+        assert_eq!(
+            MultipartOperation::classify("DELETE", Some("versionId=1")),
+            None
+        );
+        assert_eq!(
+            MultipartOperation::classify("PUT", Some("uploadId=x&partNumber=10001")),
+            None
+        );
+    }
+
+    #[test]
+    fn test_write_replaces_object() {
+        assert!(write_replaces_object("POST", Some("uploadId=x")));
+        assert!(write_replaces_object("PUT", None));
+        assert!(write_replaces_object("DELETE", Some("versionId=1")));
+
+        assert!(!write_replaces_object("POST", Some("uploads")));
+        assert!(!write_replaces_object(
+            "PUT",
+            Some("partNumber=1&uploadId=x")
+        ));
+        assert!(!write_replaces_object("DELETE", Some("uploadId=x")));
+        assert!(!write_replaces_object("GET", None));
+    }
+}
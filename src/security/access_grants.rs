@@ -0,0 +1,504 @@
+//! Time-boxed temporary access grants.
+//!
+//! An [`AccessGrant`] lets one subject (JWT `sub` or SigV4 access key) or one
+//! client IP/CIDR read a bucket, or a key prefix in it, until it expires.
+//! Grants are created through `POST /admin/grants` for support workflows and
+//! consulted by the proxy for `GET` and `HEAD` requests:
+//!
+//! - an IP grant stands in for authentication and authorization
+//! - a subject grant stands in for authorization (OPA, OpenFGA, claim rules);
+//!   the token still has to be valid
+//!
+//! With Redis configured, grants are written to a hash keyed by grant ID and
+//! pulled back by every instance on a short interval, so a grant created or
+//! revoked on one proxy applies to all of them within `sync_interval_secs`.
+
+use parking_lot::RwLock;
+use redis::aio::ConnectionManager;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::OnceCell;
+use uuid::Uuid;
+
+use super::IpRange;
+use crate::config::AccessGrantsConfig;
+use crate::util::unix_now;
+
+/// A grant as requested through the admin API
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct GrantRequest {
+    /// JWT `sub` (or SigV4 access key ID) to grant access to
+    #[serde(default)]
+    pub subject: Option<String>,
+    /// Client IP or CIDR range to grant access to
+    #[serde(default)]
+    pub ip: Option<String>,
+    pub bucket: String,
+    /// Key prefix the grant covers (default: the whole bucket)
+    #[serde(default)]
+    pub prefix: String,
+    /// Lifetime in seconds (default: `default_ttl_secs`)
+    #[serde(default)]
+    pub ttl_secs: Option<u64>,
+    /// Free-form note, e.g. a support ticket
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+/// An active read grant
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccessGrant {
+    pub id: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subject: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip: Option<String>,
+    pub bucket: String,
+    pub prefix: String,
+    /// Expiry (unix seconds)
+    pub expires_at: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+impl AccessGrant {
+    fn covers(&self, bucket: &str, key: &str, now: u64) -> bool {
+        self.expires_at > now && self.bucket == bucket && self.covers_key(key)
+    }
+
+    /// Whether `key` is under the grant's prefix, on a `/` boundary: the
+    /// prefix `reports` covers `reports` and `reports/q3.pdf`, not
+    /// `reports-2023/q3.pdf`
+    fn covers_key(&self, key: &str) -> bool {
+        if self.prefix.is_empty() || self.prefix.ends_with('/') {
+            return key.starts_with(&self.prefix);
+        }
+        match key.strip_prefix(&self.prefix) {
+            Some(rest) => rest.is_empty() || rest.starts_with('/'),
+            None => false,
+        }
+    }
+}
+
+/// Who a grant matched a request by
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GrantMatch {
+    Ip,
+    Subject,
+}
+
+impl GrantMatch {
+    /// Label used in logs and metrics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            GrantMatch::Ip => "ip",
+            GrantMatch::Subject => "subject",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct StoredGrant {
+    grant: AccessGrant,
+    ip_range: Option<IpRange>,
+}
+
+impl StoredGrant {
+    fn new(grant: AccessGrant) -> Result<Self, String> {
+        let ip_range = grant
+            .ip
+            .as_deref()
+            .map(|ip| IpRange::parse(ip).map_err(|e| e.to_string()))
+            .transpose()?;
+        Ok(Self { grant, ip_range })
+    }
+}
+
+/// Redis hash of grants (JSON) keyed by grant ID
+struct SharedGrantStore {
+    client: redis::Client,
+    connection: OnceCell<ConnectionManager>,
+    key: String,
+}
+
+impl SharedGrantStore {
+    async fn connection(&self) -> Result<ConnectionManager, String> {
+        self.connection
+            .get_or_try_init(|| ConnectionManager::new(self.client.clone()))
+            .await
+            .cloned()
+            .map_err(|e| format!("Failed to connect to Redis: {}", e))
+    }
+
+    async fn put(&self, grant: &AccessGrant) -> Result<(), String> {
+        let json = serde_json::to_string(grant).map_err(|e| e.to_string())?;
+        let mut conn = self.connection().await?;
+        redis::cmd("HSET")
+            .arg(&self.key)
+            .arg(&grant.id)
+            .arg(json)
+            .query_async::<()>(&mut conn)
+            .await
+            .map_err(|e| format!("Redis HSET failed: {}", e))
+    }
+
+    async fn remove(&self, id: &str) -> Result<(), String> {
+        let mut conn = self.connection().await?;
+        redis::cmd("HDEL")
+            .arg(&self.key)
+            .arg(id)
+            .query_async::<()>(&mut conn)
+            .await
+            .map_err(|e| format!("Redis HDEL failed: {}", e))
+    }
+
+    async fn all(&self) -> Result<Vec<(String, String)>, String> {
+        let mut conn = self.connection().await?;
+        redis::cmd("HGETALL")
+            .arg(&self.key)
+            .query_async::<Vec<(String, String)>>(&mut conn)
+            .await
+            .map_err(|e| format!("Redis HGETALL failed: {}", e))
+    }
+}
+
+/// Active access grants, optionally shared through Redis
+pub struct AccessGrantStore {
+    config: AccessGrantsConfig,
+    grants: RwLock<HashMap<String, StoredGrant>>,
+    shared: Option<SharedGrantStore>,
+}
+
+impl AccessGrantStore {
+    /// Create a store from a validated config
+    ///
+    /// The Redis connection (if configured) is opened lazily on first use.
+    pub fn new(config: AccessGrantsConfig) -> Result<Self, String> {
+        let shared = match &config.redis {
+            Some(redis_config) => Some(SharedGrantStore {
+                client: redis::Client::open(redis_config.url.as_str())
+                    .map_err(|e| format!("Invalid Redis URL for access grants: {}", e))?,
+                connection: OnceCell::new(),
+                key: redis_config.key.clone(),
+            }),
+            None => None,
+        };
+        Ok(Self {
+            config,
+            grants: RwLock::new(HashMap::new()),
+            shared,
+        })
+    }
+
+    /// Validate a request and turn it into a grant expiring `ttl_secs` after `now`
+    fn build(&self, request: GrantRequest, now: u64) -> Result<StoredGrant, String> {
+        let subject = request.subject.filter(|s| !s.trim().is_empty());
+        let ip = request.ip.filter(|ip| !ip.trim().is_empty());
+        if subject.is_some() == ip.is_some() {
+            return Err("Exactly one of 'subject' or 'ip' is required".to_string());
+        }
+        if request.bucket.trim().is_empty() {
+            return Err("'bucket' is required".to_string());
+        }
+        let ttl_secs = request.ttl_secs.unwrap_or(self.config.default_ttl_secs);
+        if ttl_secs == 0 || ttl_secs > self.config.max_ttl_secs {
+            return Err(format!(
+                "'ttl_secs' must be between 1 and {}",
+                self.config.max_ttl_secs
+            ));
+        }
+        StoredGrant::new(AccessGrant {
+            id: Uuid::new_v4().to_string(),
+            subject,
+            ip,
+            bucket: request.bucket,
+            prefix: request.prefix.trim_start_matches('/').to_string(),
+            expires_at: now + ttl_secs,
+            reason: request.reason,
+        })
+    }
+
+    /// Create a grant locally and in shared state
+    pub async fn create(&self, request: GrantRequest) -> Result<AccessGrant, String> {
+        let stored = self.build(request, unix_now())?;
+        let grant = stored.grant.clone();
+        self.insert(stored, unix_now())?;
+        if let Some(shared) = &self.shared {
+            if let Err(e) = shared.put(&grant).await {
+                tracing::warn!(grant_id = %grant.id, error = %e, "Failed to share access grant");
+            }
+        }
+        Ok(grant)
+    }
+
+    fn insert(&self, stored: StoredGrant, now: u64) -> Result<(), String> {
+        let mut grants = self.grants.write();
+        grants.retain(|_, g| g.grant.expires_at > now);
+        if grants.len() >= self.config.max_grants {
+            return Err(format!(
+                "Too many active grants (max {})",
+                self.config.max_grants
+            ));
+        }
+        grants.insert(stored.grant.id.clone(), stored);
+        Ok(())
+    }
+
+    /// Revoke a grant locally and in shared state; returns false if it did not exist
+    pub async fn revoke(&self, id: &str) -> bool {
+        let removed = self.grants.write().remove(id).is_some();
+        if let Some(shared) = &self.shared {
+            if let Err(e) = shared.remove(id).await {
+                tracing::warn!(grant_id = %id, error = %e, "Failed to remove shared access grant");
+            }
+        }
+        removed
+    }
+
+    /// Active grants, soonest expiry first
+    pub fn list(&self) -> Vec<AccessGrant> {
+        let now = unix_now();
+        let mut grants: Vec<AccessGrant> = self
+            .grants
+            .read()
+            .values()
+            .filter(|g| g.grant.expires_at > now)
+            .map(|g| g.grant.clone())
+            .collect();
+        grants.sort_by(|a, b| a.expires_at.cmp(&b.expires_at).then(a.id.cmp(&b.id)));
+        grants
+    }
+
+    /// Grant letting `ip` read `key` in `bucket`, if any
+    pub fn find_for_ip(&self, bucket: &str, key: &str, ip: IpAddr) -> Option<AccessGrant> {
+        self.find_at(bucket, key, unix_now(), |g| {
+            g.ip_range.as_ref().is_some_and(|range| range.contains(&ip))
+        })
+    }
+
+    /// Grant letting `subject` read `key` in `bucket`, if any
+    pub fn find_for_subject(&self, bucket: &str, key: &str, subject: &str) -> Option<AccessGrant> {
+        self.find_at(bucket, key, unix_now(), |g| {
+            g.grant.subject.as_deref() == Some(subject)
+        })
+    }
+
+    fn find_at(
+        &self,
+        bucket: &str,
+        key: &str,
+        now: u64,
+        matches: impl Fn(&StoredGrant) -> bool,
+    ) -> Option<AccessGrant> {
+        self.grants
+            .read()
+            .values()
+            .find(|g| g.grant.covers(bucket, key, now) && matches(g))
+            .map(|g| g.grant.clone())
+    }
+
+    /// Drop expired grants
+    pub fn cleanup(&self) {
+        let now = unix_now();
+        self.grants.write().retain(|_, g| g.grant.expires_at > now);
+    }
+
+    /// Replace local grants with shared state, dropping expired ones there
+    pub async fn sync_shared(&self) -> Result<(), String> {
+        let Some(shared) = &self.shared else {
+            return Ok(());
+        };
+        let now = unix_now();
+        let mut remote = HashMap::new();
+        for (id, json) in shared.all().await? {
+            let stored = serde_json::from_str::<AccessGrant>(&json)
+                .map_err(|e| e.to_string())
+                .and_then(StoredGrant::new);
+            match stored {
+                Ok(stored) if stored.grant.expires_at > now => {
+                    remote.insert(id, stored);
+                }
+                Ok(_) => {
+                    if let Err(e) = shared.remove(&id).await {
+                        tracing::debug!(grant_id = %id, error = %e, "Failed to drop expired grant");
+                    }
+                }
+                Err(e) => {
+                    tracing::warn!(grant_id = %id, error = %e, "Ignoring invalid shared grant");
+                }
+            }
+        }
+        *self.grants.write() = remote;
+        Ok(())
+    }
+
+    /// Start the background cleanup/sync task (call once inside a Tokio runtime)
+    pub fn start_background_task(self: &Arc<Self>) {
+        let interval = self
+            .config
+            .redis
+            .as_ref()
+            .map(|r| Duration::from_secs(r.sync_interval_secs))
+            .unwrap_or(Duration::from_secs(60));
+        let store = Arc::downgrade(self);
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                // Stop once the proxy (and its store) is dropped, e.g. after reload
+                let Some(store) = store.upgrade() else {
+                    break;
+                };
+                store.cleanup();
+                if let Err(e) = store.sync_shared().await {
+                    tracing::warn!(error = %e, "Failed to sync shared access grants");
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn store(max_grants: usize) -> AccessGrantStore {
+        AccessGrantStore::new(AccessGrantsConfig {
+            enabled: true,
+            max_grants,
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn request(subject: Option<&str>, ip: Option<&str>, prefix: &str) -> GrantRequest {
+        GrantRequest {
+            subject: subject.map(str::to_string),
+            ip: ip.map(str::to_string),
+            bucket: "media".to_string(),
+            prefix: prefix.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_build_validates_request() {
+        let store = store(10);
+        let now = 1_700_000_000;
+
+        let grant = store
+            .build(request(Some("alice"), None, "/reports/"), now)
+            .unwrap()
+            .grant;
+        assert_eq!(grant.prefix, "reports/");
+        assert_eq!(grant.expires_at, now + 3600);
+
+        assert!(store.build(request(None, None, ""), now).is_err());
+        assert!(store
+            .build(request(Some("alice"), Some("10.0.0.1"), ""), now)
+            .is_err());
+        assert!(store
+            .build(request(None, Some("10.0.0.0/99"), ""), now)
+            .is_err());
+        assert!(store
+            .build(
+                GrantRequest {
+                    ttl_secs: Some(86_401),
+                    ..request(Some("alice"), None, "")
+                },
+                now
+            )
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_grants_match_subject_ip_and_prefix() {
+        let store = store(10);
+        let subject_grant = store
+            .create(request(Some("alice"), None, "reports/"))
+            .await
+            .unwrap();
+        store
+            .create(request(None, Some("203.0.113.0/24"), ""))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            store.find_for_subject("media", "reports/q3.pdf", "alice"),
+            Some(subject_grant.clone())
+        );
+        assert!(store
+            .find_for_subject("media", "private/q3.pdf", "alice")
+            .is_none());
+        assert!(store
+            .find_for_subject("other", "reports/q3.pdf", "alice")
+            .is_none());
+        assert!(store
+            .find_for_subject("media", "reports/q3.pdf", "bob")
+            .is_none());
+        // Sibling prefixes that merely share the leading characters do not match
+        assert!(store
+            .find_for_subject("media", "reports-2023/q3.pdf", "alice")
+            .is_none());
+
+        let ip: IpAddr = "203.0.113.9".parse().unwrap();
+        assert!(store.find_for_ip("media", "any/key", ip).is_some());
+        assert!(store
+            .find_for_ip("media", "any/key", "198.51.100.1".parse().unwrap())
+            .is_none());
+
+        assert_eq!(store.list().len(), 2);
+        assert!(store.revoke(&subject_grant.id).await);
+        assert!(!store.revoke(&subject_grant.id).await);
+        assert!(store
+            .find_for_subject("media", "reports/q3.pdf", "alice")
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_prefix_without_trailing_slash_stops_at_segment_boundary() {
+        let store = store(10);
+        store
+            .create(request(Some("alice"), None, "tickets/4711"))
+            .await
+            .unwrap();
+
+        assert!(store
+            .find_for_subject("media", "tickets/4711", "alice")
+            .is_some());
+        assert!(store
+            .find_for_subject("media", "tickets/4711/log.txt", "alice")
+            .is_some());
+        assert!(store
+            .find_for_subject("media", "tickets/47110/log.txt", "alice")
+            .is_none());
+        assert!(store
+            .find_for_subject("media", "tickets/4711.bak", "alice")
+            .is_none());
+    }
+
+    #[test]
+    fn test_expired_grants_do_not_match_or_count() {
+        let store = store(1);
+        let now = 1_700_000_000;
+
+        let expired = store
+            .build(request(Some("alice"), None, ""), now - 7200)
+            .unwrap();
+        store.insert(expired, now - 7200).unwrap();
+        assert!(store
+            .find_at("media", "a.txt", now, |g| g.grant.subject.is_some())
+            .is_none());
+
+        // The expired grant is dropped to make room
+        let fresh = store.build(request(Some("bob"), None, ""), now).unwrap();
+        assert!(store.insert(fresh, now).is_ok());
+        let full = store.build(request(Some("carol"), None, ""), now).unwrap();
+        assert!(store.insert(full, now).is_err());
+    }
+}
@@ -0,0 +1,479 @@
+//! Automatic temporary IP banning.
+//!
+//! [`IpBanManager`] counts client errors (4xx) and rate-limited responses
+//! (429) per IP in a fixed window. An IP crossing either threshold is banned
+//! for the configured duration and rejected before routing.
+//!
+//! With Redis configured, bans are written to a sorted set scored by expiry
+//! (unix seconds) and pulled back by every instance on a short interval, so an
+//! IP banned by one proxy is rejected by all of them within
+//! `sync_interval_secs`. Counting stays local: each instance only bans based
+//! on the traffic it sees.
+
+use parking_lot::{Mutex, RwLock};
+use redis::aio::ConnectionManager;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::OnceCell;
+
+use super::IpRange;
+use crate::config::IpBanConfig;
+use crate::util::unix_now;
+
+/// Why an IP was banned
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BanReason {
+    /// Too many 4xx responses (other than 429)
+    ClientErrors,
+    /// Too many 429 responses
+    RateLimited,
+    /// Ban pulled from shared Redis state
+    Shared,
+}
+
+impl BanReason {
+    /// Label used in logs and metrics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BanReason::ClientErrors => "client_errors",
+            BanReason::RateLimited => "rate_limited",
+            BanReason::Shared => "shared",
+        }
+    }
+}
+
+/// An active ban, as listed by the admin API
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BanEntry {
+    pub ip: IpAddr,
+    pub reason: BanReason,
+    /// Seconds until the ban expires
+    pub expires_in_secs: u64,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Ban {
+    until: Instant,
+    reason: BanReason,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct ErrorWindow {
+    started: Instant,
+    client_errors: u32,
+    rate_limited: u32,
+}
+
+/// Redis sorted set of banned IPs scored by expiry
+struct SharedBanStore {
+    client: redis::Client,
+    connection: OnceCell<ConnectionManager>,
+    key: String,
+}
+
+impl SharedBanStore {
+    async fn connection(&self) -> Result<ConnectionManager, String> {
+        self.connection
+            .get_or_try_init(|| ConnectionManager::new(self.client.clone()))
+            .await
+            .cloned()
+            .map_err(|e| format!("Failed to connect to Redis: {}", e))
+    }
+
+    async fn ban(&self, ip: IpAddr, expires_at: u64) -> Result<(), String> {
+        let mut conn = self.connection().await?;
+        redis::cmd("ZADD")
+            .arg(&self.key)
+            .arg(expires_at)
+            .arg(ip.to_string())
+            .query_async::<()>(&mut conn)
+            .await
+            .map_err(|e| format!("Redis ZADD failed: {}", e))
+    }
+
+    async fn unban(&self, ip: IpAddr) -> Result<(), String> {
+        let mut conn = self.connection().await?;
+        redis::cmd("ZREM")
+            .arg(&self.key)
+            .arg(ip.to_string())
+            .query_async::<()>(&mut conn)
+            .await
+            .map_err(|e| format!("Redis ZREM failed: {}", e))
+    }
+
+    /// Drop expired members and return the remaining (ip, expires_at) pairs
+    async fn active(&self, now: u64) -> Result<Vec<(String, u64)>, String> {
+        let mut conn = self.connection().await?;
+        redis::cmd("ZREMRANGEBYSCORE")
+            .arg(&self.key)
+            .arg("-inf")
+            .arg(now)
+            .query_async::<()>(&mut conn)
+            .await
+            .map_err(|e| format!("Redis ZREMRANGEBYSCORE failed: {}", e))?;
+        redis::cmd("ZRANGE")
+            .arg(&self.key)
+            .arg(0)
+            .arg(-1)
+            .arg("WITHSCORES")
+            .query_async::<Vec<(String, u64)>>(&mut conn)
+            .await
+            .map_err(|e| format!("Redis ZRANGE failed: {}", e))
+    }
+}
+
+/// Tracks per-IP error rates and active bans
+pub struct IpBanManager {
+    config: IpBanConfig,
+    exempt: Vec<IpRange>,
+    windows: Mutex<HashMap<IpAddr, ErrorWindow>>,
+    bans: RwLock<HashMap<IpAddr, Ban>>,
+    shared: Option<SharedBanStore>,
+}
+
+impl IpBanManager {
+    /// Create a manager from a validated config
+    ///
+    /// The Redis connection (if configured) is opened lazily on first use.
+    pub fn new(config: IpBanConfig) -> Result<Self, String> {
+        let exempt = config
+            .exempt
+            .iter()
+            .map(|entry| IpRange::parse(entry).map_err(|e| e.to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let shared = match &config.redis {
+            Some(redis_config) => Some(SharedBanStore {
+                client: redis::Client::open(redis_config.url.as_str())
+                    .map_err(|e| format!("Invalid Redis URL for IP bans: {}", e))?,
+                connection: OnceCell::new(),
+                key: redis_config.key.clone(),
+            }),
+            None => None,
+        };
+
+        Ok(Self {
+            config,
+            exempt,
+            windows: Mutex::new(HashMap::new()),
+            bans: RwLock::new(HashMap::new()),
+            shared,
+        })
+    }
+
+    fn is_exempt(&self, ip: &IpAddr) -> bool {
+        self.exempt.iter().any(|range| range.contains(ip))
+    }
+
+    /// Remaining ban time for `ip`, or `None` if it is not banned
+    pub fn ban_remaining(&self, ip: IpAddr) -> Option<Duration> {
+        self.ban_remaining_at(ip, Instant::now())
+    }
+
+    fn ban_remaining_at(&self, ip: IpAddr, now: Instant) -> Option<Duration> {
+        self.bans
+            .read()
+            .get(&ip)
+            .map(|ban| ban.until.saturating_duration_since(now))
+            .filter(|remaining| !remaining.is_zero())
+    }
+
+    /// Count a response sent to `ip`; returns the reason if this response triggered a ban
+    pub fn record_response(self: &Arc<Self>, ip: IpAddr, status: u16) -> Option<BanReason> {
+        let reason = self.record_response_at(ip, status, Instant::now())?;
+
+        if let (Some(_), Ok(handle)) = (&self.shared, tokio::runtime::Handle::try_current()) {
+            let manager = Arc::clone(self);
+            let expires_at = unix_now() + self.config.ban_duration_secs;
+            handle.spawn(async move {
+                if let Some(shared) = &manager.shared {
+                    if let Err(e) = shared.ban(ip, expires_at).await {
+                        tracing::warn!(ip = %ip, error = %e, "Failed to share IP ban");
+                    }
+                }
+            });
+        }
+        Some(reason)
+    }
+
+    fn record_response_at(&self, ip: IpAddr, status: u16, now: Instant) -> Option<BanReason> {
+        if !(400..500).contains(&status)
+            || self.is_exempt(&ip)
+            || self.ban_remaining_at(ip, now).is_some()
+        {
+            return None;
+        }
+
+        let window_len = Duration::from_secs(self.config.window_secs);
+        let reason = {
+            let mut windows = self.windows.lock();
+            if !windows.contains_key(&ip) && windows.len() >= self.config.max_tracked_ips {
+                windows.retain(|_, w| now.saturating_duration_since(w.started) < window_len);
+                if windows.len() >= self.config.max_tracked_ips {
+                    // Fail open rather than grow without bound
+                    return None;
+                }
+            }
+
+            let window = windows.entry(ip).or_insert(ErrorWindow {
+                started: now,
+                client_errors: 0,
+                rate_limited: 0,
+            });
+            if now.saturating_duration_since(window.started) >= window_len {
+                *window = ErrorWindow {
+                    started: now,
+                    client_errors: 0,
+                    rate_limited: 0,
+                };
+            }
+
+            let reason = if status == 429 {
+                window.rate_limited += 1;
+                let max = self.config.max_rate_limited;
+                (max > 0 && window.rate_limited >= max).then_some(BanReason::RateLimited)
+            } else {
+                window.client_errors += 1;
+                let max = self.config.max_client_errors;
+                (max > 0 && window.client_errors >= max).then_some(BanReason::ClientErrors)
+            }?;
+            windows.remove(&ip);
+            reason
+        };
+
+        self.bans.write().insert(
+            ip,
+            Ban {
+                until: now + Duration::from_secs(self.config.ban_duration_secs),
+                reason,
+            },
+        );
+        Some(reason)
+    }
+
+    /// Active bans, sorted by IP
+    pub fn list(&self) -> Vec<BanEntry> {
+        let now = Instant::now();
+        let mut entries: Vec<BanEntry> = self
+            .bans
+            .read()
+            .iter()
+            .filter(|(_, ban)| ban.until > now)
+            .map(|(ip, ban)| BanEntry {
+                ip: *ip,
+                reason: ban.reason,
+                expires_in_secs: ban.until.saturating_duration_since(now).as_secs(),
+            })
+            .collect();
+        entries.sort_by_key(|entry| entry.ip);
+        entries
+    }
+
+    /// Lift a ban locally and in shared state; returns false if `ip` was not banned
+    pub async fn unban(&self, ip: IpAddr) -> bool {
+        let removed = self.bans.write().remove(&ip).is_some();
+        self.windows.lock().remove(&ip);
+        if let Some(shared) = &self.shared {
+            if let Err(e) = shared.unban(ip).await {
+                tracing::warn!(ip = %ip, error = %e, "Failed to remove shared IP ban");
+            }
+        }
+        removed
+    }
+
+    /// Drop expired bans and error windows
+    pub fn cleanup(&self) {
+        let now = Instant::now();
+        let window_len = Duration::from_secs(self.config.window_secs);
+        self.bans.write().retain(|_, ban| ban.until > now);
+        self.windows
+            .lock()
+            .retain(|_, w| now.saturating_duration_since(w.started) < window_len);
+    }
+
+    /// Merge bans from shared state; shared bans lifted elsewhere are dropped
+    pub async fn sync_shared(&self) -> Result<(), String> {
+        let Some(shared) = &self.shared else {
+            return Ok(());
+        };
+        let now_unix = unix_now();
+        let active = shared.active(now_unix).await?;
+
+        let now = Instant::now();
+        let remote: HashMap<IpAddr, Instant> = active
+            .into_iter()
+            .filter_map(|(ip, expires_at)| {
+                let ip = ip.parse::<IpAddr>().ok()?;
+                Some((
+                    ip,
+                    now + Duration::from_secs(expires_at.saturating_sub(now_unix)),
+                ))
+            })
+            .collect();
+
+        let mut bans = self.bans.write();
+        bans.retain(|ip, ban| ban.reason != BanReason::Shared || remote.contains_key(ip));
+        for (ip, until) in remote {
+            if self.is_exempt(&ip) {
+                continue;
+            }
+            bans.entry(ip)
+                .and_modify(|ban| ban.until = ban.until.max(until))
+                .or_insert(Ban {
+                    until,
+                    reason: BanReason::Shared,
+                });
+        }
+        Ok(())
+    }
+
+    /// Start the background cleanup/sync task (call once inside a Tokio runtime)
+    pub fn start_background_task(self: &Arc<Self>) {
+        let interval = self
+            .config
+            .redis
+            .as_ref()
+            .map(|r| Duration::from_secs(r.sync_interval_secs))
+            .unwrap_or(Duration::from_secs(self.config.window_secs));
+        let manager = Arc::downgrade(self);
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                // Stop once the proxy (and its manager) is dropped, e.g. after reload
+                let Some(manager) = manager.upgrade() else {
+                    break;
+                };
+                manager.cleanup();
+                if let Err(e) = manager.sync_shared().await {
+                    tracing::warn!(error = %e, "Failed to sync shared IP bans");
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn manager(max_client_errors: u32, max_rate_limited: u32) -> IpBanManager {
+        IpBanManager::new(IpBanConfig {
+            enabled: true,
+            max_client_errors,
+            max_rate_limited,
+            exempt: vec!["10.0.0.0/8".to_string()],
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_bans_after_client_error_threshold() {
+        let manager = manager(3, 0);
+        let now = Instant::now();
+        let client = ip("203.0.113.7");
+
+        assert_eq!(manager.record_response_at(client, 404, now), None);
+        assert_eq!(manager.record_response_at(client, 200, now), None);
+        assert_eq!(manager.record_response_at(client, 403, now), None);
+        assert_eq!(
+            manager.record_response_at(client, 401, now),
+            Some(BanReason::ClientErrors)
+        );
+        assert!(manager.ban_remaining_at(client, now).is_some());
+        // 429s do not count towards client errors
+        assert_eq!(
+            manager.record_response_at(ip("203.0.113.8"), 429, now),
+            None
+        );
+    }
+
+    #[test]
+    fn test_bans_after_rate_limited_threshold() {
+        let manager = manager(0, 2);
+        let now = Instant::now();
+        let client = ip("2001:db8::1");
+
+        assert_eq!(manager.record_response_at(client, 429, now), None);
+        assert_eq!(
+            manager.record_response_at(client, 429, now),
+            Some(BanReason::RateLimited)
+        );
+        assert_eq!(manager.list()[0].reason, BanReason::RateLimited);
+    }
+
+    #[test]
+    fn test_window_resets_and_ban_expires() {
+        let manager = manager(2, 0);
+        let start = Instant::now();
+        let client = ip("198.51.100.1");
+
+        manager.record_response_at(client, 404, start);
+        // Next error falls into a new window
+        let later = start + Duration::from_secs(61);
+        assert_eq!(manager.record_response_at(client, 404, later), None);
+        assert_eq!(
+            manager.record_response_at(client, 404, later),
+            Some(BanReason::ClientErrors)
+        );
+
+        let expired = later + Duration::from_secs(601);
+        assert!(manager.ban_remaining_at(client, expired).is_none());
+    }
+
+    #[test]
+    fn test_exempt_ips_are_never_banned() {
+        let manager = manager(1, 1);
+        let now = Instant::now();
+        assert_eq!(manager.record_response_at(ip("10.1.2.3"), 404, now), None);
+        assert_eq!(manager.record_response_at(ip("10.1.2.3"), 429, now), None);
+        assert!(manager.list().is_empty());
+    }
+
+    #[test]
+    fn test_max_tracked_ips_fails_open() {
+        let manager = IpBanManager::new(IpBanConfig {
+            enabled: true,
+            max_client_errors: 2,
+            max_tracked_ips: 1,
+            ..Default::default()
+        })
+        .unwrap();
+        let now = Instant::now();
+
+        manager.record_response_at(ip("192.0.2.1"), 404, now);
+        // Table is full of live windows: the new IP is not tracked
+        manager.record_response_at(ip("192.0.2.2"), 404, now);
+        assert_eq!(manager.record_response_at(ip("192.0.2.2"), 404, now), None);
+        assert_eq!(
+            manager.record_response_at(ip("192.0.2.1"), 404, now),
+            Some(BanReason::ClientErrors)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_list_and_unban() {
+        let manager = manager(1, 0);
+        let client = ip("203.0.113.9");
+
+        manager.record_response_at(client, 404, Instant::now());
+        let bans = manager.list();
+        assert_eq!(bans.len(), 1);
+        assert_eq!(bans[0].ip, client);
+        assert!(bans[0].expires_in_secs > 590);
+
+        assert!(manager.unban(client).await);
+        assert!(manager.ban_remaining(client).is_none());
+        assert!(!manager.unban(client).await);
+    }
+}
@@ -7,14 +7,24 @@
 //!
 //! Returns appropriate HTTP status codes:
 //! - 413 Payload Too Large - Request body exceeds limit
-//! - 431 Request Header Fields Too Large - Headers exceed limit
+//! - 431 Request Header Fields Too Large - Headers (or a single header value) exceed limit
 //! - 400 Bad Request - Malformed input (path traversal, invalid format)
-//! - 403 Forbidden - Blocked IP or malformed JWT
+//! - 403 Forbidden - Blocked IP, malformed JWT, invalid signed URL or upload policy
 
+pub mod access_grants;
+pub mod ip_ban;
 pub mod ip_filter;
+pub mod post_policy;
+pub mod range_abuse;
+pub mod signed_url;
+pub mod virus_scan;
 
+pub use access_grants::{AccessGrant, AccessGrantStore, GrantMatch, GrantRequest};
+pub use ip_ban::{BanEntry, BanReason, IpBanManager};
 pub use ip_filter::{IpFilter, IpFilterConfig, IpFilterError, IpRange};
+pub use range_abuse::{RangeAbuseDetector, RangeVerdict};
 
+use std::collections::HashMap;
 use std::path::Path;
 
 /// Security validation error types
@@ -24,6 +34,12 @@ pub enum SecurityError {
     PayloadTooLarge { size: usize, limit: usize },
     /// Request headers too large (431)
     HeadersTooLarge { total_size: usize, limit: usize },
+    /// Single header value too long (431)
+    HeaderValueTooLong {
+        name: String,
+        length: usize,
+        limit: usize,
+    },
     /// Path traversal attempt detected (400)
     PathTraversal { path: String },
     /// URI too long (414)
@@ -45,6 +61,17 @@ impl std::fmt::Display for SecurityError {
                     total_size, limit
                 )
             }
+            SecurityError::HeaderValueTooLong {
+                name,
+                length,
+                limit,
+            } => {
+                write!(
+                    f,
+                    "Header '{}' value length {} exceeds limit {}",
+                    name, length, limit
+                )
+            }
             SecurityError::PathTraversal { path } => {
                 write!(f, "Path traversal attempt detected: {}", path)
             }
@@ -68,6 +95,10 @@ pub struct SecurityLimits {
     pub max_header_size: usize,
     /// Maximum URI length (default: 8192 bytes)
     pub max_uri_length: usize,
+    /// Maximum length of a single header value (default: 8 KB)
+    pub max_header_value_length: usize,
+    /// Per-header overrides of `max_header_value_length`, keyed by lowercase name
+    pub header_value_limits: HashMap<String, usize>,
 }
 
 impl Default for SecurityLimits {
@@ -76,10 +107,22 @@ impl Default for SecurityLimits {
             max_body_size: 10 * 1024 * 1024, // 10 MB
             max_header_size: 64 * 1024,      // 64 KB
             max_uri_length: 8192,            // 8 KB
+            max_header_value_length: 8192,   // 8 KB
+            header_value_limits: HashMap::new(),
         }
     }
 }
 
+impl SecurityLimits {
+    /// Value length limit for a header (lowercase name)
+    pub fn header_value_limit(&self, name: &str) -> usize {
+        self.header_value_limits
+            .get(name)
+            .copied()
+            .unwrap_or(self.max_header_value_length)
+    }
+}
+
 /// Validate request body size
 pub fn validate_body_size(
     content_length: Option<usize>,
@@ -101,6 +144,28 @@ pub fn validate_header_size(total_size: usize, limit: usize) -> Result<(), Secur
     Ok(())
 }
 
+/// Validate each header value length against its limit
+///
+/// Takes `(lowercase name, value length)` pairs and reports the first header
+/// over its limit. S3 rejects signatures for some oversized client headers
+/// with an opaque 403, so these are refused before forwarding.
+pub fn validate_header_values<'a>(
+    headers: impl IntoIterator<Item = (&'a str, usize)>,
+    limits: &SecurityLimits,
+) -> Result<(), SecurityError> {
+    for (name, length) in headers {
+        let limit = limits.header_value_limit(name);
+        if length > limit {
+            return Err(SecurityError::HeaderValueTooLong {
+                name: name.to_string(),
+                length,
+                limit,
+            });
+        }
+    }
+    Ok(())
+}
+
 /// Validate URI length
 pub fn validate_uri_length(uri: &str, limit: usize) -> Result<(), SecurityError> {
     let length = uri.len();
@@ -230,10 +295,29 @@ pub fn check_sql_injection(path: &str) -> Result<(), SecurityError> {
     Ok(())
 }
 
+/// Constant-time string comparison, so tokens and signatures do not leak
+/// through timing
+pub fn constant_time_eq(a: &str, b: &str) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.bytes()
+        .zip(b.bytes())
+        .fold(0u8, |acc, (x, y)| acc | (x ^ y))
+        == 0
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq("hello", "hello"));
+        assert!(!constant_time_eq("hello", "world"));
+        assert!(!constant_time_eq("hello", "hell"));
+    }
+
     #[test]
     fn test_validate_body_size_within_limit() {
         let result = validate_body_size(Some(1000), 10_000);
@@ -276,6 +360,26 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_validate_header_values_uses_per_header_limits() {
+        let mut limits = SecurityLimits {
+            max_header_value_length: 100,
+            ..SecurityLimits::default()
+        };
+        limits.header_value_limits.insert("cookie".to_string(), 500);
+
+        assert!(validate_header_values([("user-agent", 100), ("cookie", 400)], &limits).is_ok());
+        assert_eq!(
+            validate_header_values([("accept", 10), ("user-agent", 101)], &limits),
+            Err(SecurityError::HeaderValueTooLong {
+                name: "user-agent".to_string(),
+                length: 101,
+                limit: 100,
+            })
+        );
+        assert!(validate_header_values([("cookie", 501)], &limits).is_err());
+    }
+
     #[test]
     fn test_validate_uri_length_within_limit() {
         let uri = "/path/to/resource";
@@ -0,0 +1,642 @@
+//! POST policy signing and verification for browser uploads.
+//!
+//! A browser uploads with a `multipart/form-data` POST carrying the object
+//! `key`, an optional `Content-Type`, a `policy` and its `signature`, and the
+//! `file` as the last part. The policy is an S3-style JSON document:
+//!
+//! ```text
+//! {"expiration": "2026-01-01T12:00:00Z",
+//!  "conditions": [["starts-with", "$key", "avatars/"],
+//!                 {"Content-Type": "image/png"},
+//!                 ["content-length-range", 0, 1048576]]}
+//!
+//! policy    = base64(policy JSON)
+//! signature = base64url(HMAC-SHA256(secret, policy))
+//! ```
+//!
+//! Supported conditions are exact matches (`{"field": "value"}` or
+//! `["eq", "$field", "value"]`), prefixes (`["starts-with", "$field", "prefix"]`)
+//! and `["content-length-range", min, max]`. As with S3, every form field
+//! except `policy`, `signature`, `file` and `x-ignore-*` must be named by a
+//! condition, and `${filename}` in the key is replaced by the uploaded file's
+//! name. The bucket's own limits (size, content types, key prefix) apply on
+//! top of whatever the policy allows.
+
+use base64::engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD};
+use base64::Engine;
+use bytes::Bytes;
+use chrono::{DateTime, SecondsFormat, Utc};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::Sha256;
+use std::collections::{BTreeMap, HashSet};
+
+use crate::config::PostPolicyConfig;
+use crate::security::constant_time_eq;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Form field holding the base64 policy document
+pub const POLICY_FIELD: &str = "policy";
+
+/// Form field holding the policy signature
+pub const SIGNATURE_FIELD: &str = "signature";
+
+/// Form field holding the object key
+pub const KEY_FIELD: &str = "key";
+
+/// Form field holding the object's content type
+pub const CONTENT_TYPE_FIELD: &str = "Content-Type";
+
+/// Form field holding the uploaded file
+pub const FILE_FIELD: &str = "file";
+
+/// Placeholder in the key replaced by the uploaded file's name
+pub const FILENAME_PLACEHOLDER: &str = "${filename}";
+
+/// Why a POST upload was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PostPolicyError {
+    /// The body is not a form with a key and a file
+    MalformedForm,
+    /// `policy` or `signature` is missing
+    Missing,
+    /// The policy is not valid base64 JSON or has an unknown condition
+    MalformedPolicy,
+    /// The signature does not match
+    InvalidSignature,
+    /// The policy has expired
+    Expired,
+    /// The policy expires further out than `max_expires_secs`
+    ExpiryTooLong,
+    /// A form field fails a policy condition
+    ConditionFailed,
+    /// A form field is not named by any policy condition
+    FieldNotInPolicy,
+    /// The file is larger than the policy or the bucket allows
+    TooLarge,
+    /// The content type is not in `allowed_content_types`
+    ContentTypeNotAllowed,
+    /// The key is outside `key_prefix` or not a valid object key
+    KeyNotAllowed,
+}
+
+impl PostPolicyError {
+    /// Label used in logs and metrics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::MalformedForm => "malformed_form",
+            Self::Missing => "missing",
+            Self::MalformedPolicy => "malformed_policy",
+            Self::InvalidSignature => "invalid_signature",
+            Self::Expired => "expired",
+            Self::ExpiryTooLong => "expiry_too_long",
+            Self::ConditionFailed => "condition_failed",
+            Self::FieldNotInPolicy => "field_not_in_policy",
+            Self::TooLarge => "too_large",
+            Self::ContentTypeNotAllowed => "content_type_not_allowed",
+            Self::KeyNotAllowed => "key_not_allowed",
+        }
+    }
+
+    /// Message returned to the client
+    pub fn message(&self) -> &'static str {
+        match self {
+            Self::MalformedForm => "Upload must be a multipart form with a key and a file",
+            Self::Missing => "Upload requires a policy and signature",
+            Self::MalformedPolicy => "Upload policy is malformed",
+            Self::InvalidSignature => "Upload policy signature is invalid",
+            Self::Expired => "Upload policy has expired",
+            Self::ExpiryTooLong => "Upload policy expiry is too far in the future",
+            Self::ConditionFailed => "Upload does not satisfy the policy conditions",
+            Self::FieldNotInPolicy => "Upload has a form field not covered by the policy",
+            Self::TooLarge => "Uploaded file is too large",
+            Self::ContentTypeNotAllowed => "Content type is not allowed for uploads",
+            Self::KeyNotAllowed => "Object key is not allowed for uploads",
+        }
+    }
+
+    /// HTTP status for the rejection
+    pub fn status(&self) -> u16 {
+        match self {
+            Self::MalformedForm | Self::MalformedPolicy => 400,
+            Self::TooLarge => 413,
+            Self::ContentTypeNotAllowed => 415,
+            _ => 403,
+        }
+    }
+}
+
+/// Signature for a base64 policy document
+pub fn sign(secret: &str, policy: &str) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key");
+    mac.update(policy.as_bytes());
+    URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes())
+}
+
+/// What a caller asks to allow when issuing a policy
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct PolicyRequest {
+    /// Exact object key, may contain `${filename}`
+    #[serde(default)]
+    pub key: Option<String>,
+    /// Allow any key under this prefix instead of an exact key
+    #[serde(default)]
+    pub key_prefix: Option<String>,
+    /// Exact content type the upload must declare
+    #[serde(default)]
+    pub content_type: Option<String>,
+    /// Largest file in bytes (default and cap: the bucket's `max_size_bytes`)
+    #[serde(default)]
+    pub max_size_bytes: Option<u64>,
+    /// Policy lifetime in seconds (default and cap: `max_expires_secs`)
+    #[serde(default)]
+    pub expires_in_secs: Option<u64>,
+}
+
+/// A signed policy and the form fields a browser must send with it
+#[derive(Debug, Clone, Serialize)]
+pub struct SignedPolicy {
+    /// RFC 3339 expiry of the policy
+    pub expiration: String,
+    /// Hidden form fields, including `policy` and `signature`
+    pub fields: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PolicyDocument {
+    expiration: String,
+    conditions: Vec<Value>,
+}
+
+/// Issue a policy for `request` at time `now` (unix seconds)
+pub fn generate(
+    config: &PostPolicyConfig,
+    request: &PolicyRequest,
+    now: u64,
+) -> Result<SignedPolicy, String> {
+    let expires_in = request.expires_in_secs.unwrap_or(config.max_expires_secs);
+    if expires_in == 0 || expires_in > config.max_expires_secs {
+        return Err(format!(
+            "expires_in_secs must be between 1 and {}",
+            config.max_expires_secs
+        ));
+    }
+    let max_size = request.max_size_bytes.unwrap_or(config.max_size_bytes);
+    if max_size > config.max_size_bytes {
+        return Err(format!(
+            "max_size_bytes cannot exceed {}",
+            config.max_size_bytes
+        ));
+    }
+
+    let bucket_prefix = config.key_prefix.as_deref().unwrap_or_default();
+    let (key_field, key_condition) = match (&request.key, &request.key_prefix) {
+        (Some(_), Some(_)) => return Err("key and key_prefix are mutually exclusive".to_string()),
+        (Some(key), None) => {
+            if !key.starts_with(bucket_prefix) || !is_valid_key(key) {
+                return Err(format!("key '{}' is not allowed", key));
+            }
+            (key.clone(), serde_json::json!({ KEY_FIELD: key }))
+        }
+        (None, prefix) => {
+            let prefix = prefix.as_deref().unwrap_or(bucket_prefix);
+            if !prefix.starts_with(bucket_prefix) || prefix.starts_with('/') {
+                return Err(format!("key_prefix '{}' is not allowed", prefix));
+            }
+            (
+                format!("{}{}", prefix, FILENAME_PLACEHOLDER),
+                serde_json::json!(["starts-with", "$key", prefix]),
+            )
+        }
+    };
+
+    let mut conditions = vec![
+        key_condition,
+        serde_json::json!(["content-length-range", 0, max_size]),
+    ];
+    let mut fields = BTreeMap::from([(KEY_FIELD.to_string(), key_field)]);
+    if let Some(content_type) = &request.content_type {
+        if !config.allows_content_type(content_type) {
+            return Err(format!("content type '{}' is not allowed", content_type));
+        }
+        conditions.push(serde_json::json!({ CONTENT_TYPE_FIELD: content_type }));
+        fields.insert(CONTENT_TYPE_FIELD.to_string(), content_type.clone());
+    }
+
+    let expiration = DateTime::<Utc>::from_timestamp((now + expires_in) as i64, 0)
+        .ok_or("expiry out of range")?
+        .to_rfc3339_opts(SecondsFormat::Secs, true);
+    let document = PolicyDocument {
+        expiration: expiration.clone(),
+        conditions,
+    };
+    let policy = STANDARD.encode(serde_json::to_vec(&document).map_err(|e| e.to_string())?);
+    fields.insert(SIGNATURE_FIELD.to_string(), sign(&config.secret, &policy));
+    fields.insert(POLICY_FIELD.to_string(), policy);
+
+    Ok(SignedPolicy { expiration, fields })
+}
+
+/// The file part of an upload form
+#[derive(Debug, Clone)]
+pub struct FormFile {
+    /// File name sent by the browser
+    pub filename: String,
+    /// Content type of the part, if sent
+    pub content_type: Option<String>,
+    /// File contents
+    pub data: Bytes,
+}
+
+/// A parsed `multipart/form-data` upload
+#[derive(Debug, Clone, Default)]
+pub struct UploadForm {
+    /// Text fields in form order
+    pub fields: Vec<(String, String)>,
+    /// The `file` part
+    pub file: Option<FormFile>,
+}
+
+impl UploadForm {
+    /// Value of a text field (field names are case-insensitive)
+    pub fn field(&self, name: &str) -> Option<&str> {
+        self.fields
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Boundary of a `multipart/form-data` Content-Type header
+pub fn boundary(content_type: &str) -> Option<String> {
+    let mut params = content_type.split(';');
+    let media_type = params.next()?.trim();
+    if !media_type.eq_ignore_ascii_case("multipart/form-data") {
+        return None;
+    }
+    params.find_map(|param| {
+        let (name, value) = param.split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case("boundary")
+            .then(|| value.trim().trim_matches('"').to_string())
+            .filter(|b| !b.is_empty())
+    })
+}
+
+/// Parse a `multipart/form-data` body
+///
+/// Fields after the file part are ignored, as S3 does.
+pub fn parse_form(body: &Bytes, boundary: &str) -> Result<UploadForm, PostPolicyError> {
+    let delimiter = format!("--{}", boundary);
+    let part_delimiter = format!("\r\n--{}", boundary);
+    let mut pos = find(body, delimiter.as_bytes(), 0).ok_or(PostPolicyError::MalformedForm)?
+        + delimiter.len();
+    let mut form = UploadForm::default();
+
+    loop {
+        // "--" closes the form; anything else must start a new line
+        if body[pos..].starts_with(b"--") {
+            break;
+        }
+        if !body[pos..].starts_with(b"\r\n") {
+            return Err(PostPolicyError::MalformedForm);
+        }
+        pos += 2;
+
+        let headers_end = find(body, b"\r\n\r\n", pos).ok_or(PostPolicyError::MalformedForm)?;
+        let headers = std::str::from_utf8(&body[pos..headers_end])
+            .map_err(|_| PostPolicyError::MalformedForm)?;
+        let content_start = headers_end + 4;
+        let content_end = find(body, part_delimiter.as_bytes(), content_start)
+            .ok_or(PostPolicyError::MalformedForm)?;
+        pos = content_end + part_delimiter.len();
+
+        let mut name = None;
+        let mut filename = None;
+        let mut part_content_type = None;
+        for line in headers.split("\r\n") {
+            let Some((header, value)) = line.split_once(':') else {
+                continue;
+            };
+            if header.trim().eq_ignore_ascii_case("content-disposition") {
+                name = disposition_param(value, "name");
+                filename = disposition_param(value, "filename");
+            } else if header.trim().eq_ignore_ascii_case("content-type") {
+                part_content_type = Some(value.trim().to_string());
+            }
+        }
+        let name = name.ok_or(PostPolicyError::MalformedForm)?;
+
+        if name.eq_ignore_ascii_case(FILE_FIELD) {
+            form.file = Some(FormFile {
+                filename: filename.unwrap_or_default(),
+                content_type: part_content_type,
+                data: body.slice(content_start..content_end),
+            });
+            break;
+        }
+        let value = std::str::from_utf8(&body[content_start..content_end])
+            .map_err(|_| PostPolicyError::MalformedForm)?;
+        form.fields.push((name, value.to_string()));
+    }
+
+    Ok(form)
+}
+
+/// An upload that passed every check
+#[derive(Debug, Clone)]
+pub struct VerifiedUpload {
+    /// Object key, with `${filename}` substituted
+    pub key: String,
+    /// Content type to store the object with
+    pub content_type: String,
+    /// File contents
+    pub data: Bytes,
+}
+
+/// Verify an upload form against its policy and the bucket limits at time `now`
+pub fn verify(
+    config: &PostPolicyConfig,
+    form: UploadForm,
+    now: u64,
+) -> Result<VerifiedUpload, PostPolicyError> {
+    let (Some(policy), Some(signature)) = (form.field(POLICY_FIELD), form.field(SIGNATURE_FIELD))
+    else {
+        return Err(PostPolicyError::Missing);
+    };
+    let expected = sign(&config.secret, policy);
+    if !constant_time_eq(signature, &expected) {
+        return Err(PostPolicyError::InvalidSignature);
+    }
+
+    let document: PolicyDocument = STANDARD
+        .decode(policy)
+        .ok()
+        .and_then(|json| serde_json::from_slice(&json).ok())
+        .ok_or(PostPolicyError::MalformedPolicy)?;
+    let expiration = DateTime::parse_from_rfc3339(&document.expiration)
+        .map_err(|_| PostPolicyError::MalformedPolicy)?
+        .timestamp();
+    let expiration = u64::try_from(expiration).unwrap_or(0);
+    if expiration <= now {
+        return Err(PostPolicyError::Expired);
+    }
+    if expiration - now > config.max_expires_secs {
+        return Err(PostPolicyError::ExpiryTooLong);
+    }
+
+    let Some(file) = form.file.clone() else {
+        return Err(PostPolicyError::MalformedForm);
+    };
+    let filename = file
+        .filename
+        .rsplit(['/', '\\'])
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    let key = form
+        .field(KEY_FIELD)
+        .filter(|k| !k.is_empty())
+        .ok_or(PostPolicyError::MalformedForm)?
+        .replace(FILENAME_PLACEHOLDER, &filename);
+    let content_type = form
+        .field(CONTENT_TYPE_FIELD)
+        .map(str::to_string)
+        .or(file.content_type)
+        .unwrap_or_else(|| "application/octet-stream".to_string());
+    let size = file.data.len() as u64;
+
+    // Conditions see the key after substitution
+    let value_of = |field: &str| -> String {
+        if field.eq_ignore_ascii_case(KEY_FIELD) {
+            key.clone()
+        } else if field.eq_ignore_ascii_case(CONTENT_TYPE_FIELD) {
+            content_type.clone()
+        } else {
+            form.field(field).unwrap_or_default().to_string()
+        }
+    };
+    let mut covered = HashSet::new();
+    for condition in &document.conditions {
+        match condition {
+            Value::Object(map) if map.len() == 1 => {
+                let (field, expected) = map.iter().next().expect("one entry");
+                let expected = expected.as_str().ok_or(PostPolicyError::MalformedPolicy)?;
+                if value_of(field) != expected {
+                    return Err(PostPolicyError::ConditionFailed);
+                }
+                covered.insert(field.to_ascii_lowercase());
+            }
+            Value::Array(items) if items.len() == 3 => {
+                let op = items[0].as_str().ok_or(PostPolicyError::MalformedPolicy)?;
+                if op.eq_ignore_ascii_case("content-length-range") {
+                    let (Some(min), Some(max)) = (items[1].as_u64(), items[2].as_u64()) else {
+                        return Err(PostPolicyError::MalformedPolicy);
+                    };
+                    if size > max {
+                        return Err(PostPolicyError::TooLarge);
+                    }
+                    if size < min {
+                        return Err(PostPolicyError::ConditionFailed);
+                    }
+                    continue;
+                }
+                let field = items[1]
+                    .as_str()
+                    .and_then(|f| f.strip_prefix('$'))
+                    .ok_or(PostPolicyError::MalformedPolicy)?;
+                let expected = items[2].as_str().ok_or(PostPolicyError::MalformedPolicy)?;
+                let matched = if op.eq_ignore_ascii_case("eq") {
+                    value_of(field) == expected
+                } else if op.eq_ignore_ascii_case("starts-with") {
+                    value_of(field).starts_with(expected)
+                } else {
+                    return Err(PostPolicyError::MalformedPolicy);
+                };
+                if !matched {
+                    return Err(PostPolicyError::ConditionFailed);
+                }
+                covered.insert(field.to_ascii_lowercase());
+            }
+            _ => return Err(PostPolicyError::MalformedPolicy),
+        }
+    }
+
+    let exempt = |name: &str| {
+        name == POLICY_FIELD || name == SIGNATURE_FIELD || name.starts_with("x-ignore-")
+    };
+    if form
+        .fields
+        .iter()
+        .map(|(name, _)| name.to_ascii_lowercase())
+        .any(|name| !exempt(&name) && !covered.contains(&name))
+    {
+        return Err(PostPolicyError::FieldNotInPolicy);
+    }
+
+    if size > config.max_size_bytes {
+        return Err(PostPolicyError::TooLarge);
+    }
+    if !config.allows_content_type(&content_type) {
+        return Err(PostPolicyError::ContentTypeNotAllowed);
+    }
+    let outside_prefix = config
+        .key_prefix
+        .as_deref()
+        .is_some_and(|prefix| !key.starts_with(prefix));
+    if outside_prefix || !is_valid_key(&key) {
+        return Err(PostPolicyError::KeyNotAllowed);
+    }
+
+    Ok(VerifiedUpload {
+        key,
+        content_type,
+        data: file.data,
+    })
+}
+
+/// Object keys must be relative, without `.`/`..` segments or control characters
+fn is_valid_key(key: &str) -> bool {
+    !key.is_empty()
+        && !key.starts_with('/')
+        && !key.chars().any(char::is_control)
+        && key
+            .split('/')
+            .all(|segment| segment != "." && segment != "..")
+}
+
+/// Disposition parameter such as `name="key"`
+fn disposition_param(value: &str, param: &str) -> Option<String> {
+    value.split(';').skip(1).find_map(|part| {
+        let (name, value) = part.split_once('=')?;
+        name.trim()
+            .eq_ignore_ascii_case(param)
+            .then(|| value.trim().trim_matches('"').to_string())
+    })
+}
+
+fn find(haystack: &[u8], needle: &[u8], from: usize) -> Option<usize> {
+    haystack
+        .get(from..)?
+        .windows(needle.len())
+        .position(|window| window == needle)
+        .map(|i| i + from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn form_body(fields: &BTreeMap<String, String>, filename: &str, data: &[u8]) -> Bytes {
+        let mut body = Vec::new();
+        for (name, value) in fields {
+            body.extend_from_slice(
+                format!(
+                    "--XyZ\r\nContent-Disposition: form-data; name=\"{}\"\r\n\r\n{}\r\n",
+                    name, value
+                )
+                .as_bytes(),
+            );
+        }
+        body.extend_from_slice(
+            format!(
+                "--XyZ\r\nContent-Disposition: form-data; name=\"file\"; filename=\"{}\"\r\n\
+                 Content-Type: image/png\r\n\r\n",
+                filename
+            )
+            .as_bytes(),
+        );
+        body.extend_from_slice(data);
+        body.extend_from_slice(b"\r\n--XyZ--\r\n");
+        Bytes::from(body)
+    }
+
+    #[test]
+    fn test_post_policy_upload() {
+        let yaml = "secret: uploads-key\nmax_size_bytes: 1024\n\
+                    allowed_content_types: [image/*]\nkey_prefix: avatars/";
+        let config: PostPolicyConfig = serde_yaml::from_str(yaml).unwrap();
+        let now = 1_700_000_000;
+        let request = PolicyRequest {
+            key_prefix: Some("avatars/alice/".to_string()),
+            max_size_bytes: Some(16),
+            expires_in_secs: Some(600),
+            ..Default::default()
+        };
+        let signed = generate(&config, &request, now).unwrap();
+        assert_eq!(signed.fields[KEY_FIELD], "avatars/alice/${filename}");
+        assert_eq!(
+            boundary("multipart/form-data; boundary=\"XyZ\"").as_deref(),
+            Some("XyZ")
+        );
+
+        let parse = |fields: &BTreeMap<String, String>, filename: &str, data: &[u8]| {
+            parse_form(&form_body(fields, filename, data), "XyZ").unwrap()
+        };
+        let upload = verify(&config, parse(&signed.fields, "C:\\me.png", b"png!"), now).unwrap();
+        assert_eq!(upload.key, "avatars/alice/me.png");
+        assert_eq!(upload.content_type, "image/png");
+        assert_eq!(&upload.data[..], b"png!");
+
+        let check = |fields: &BTreeMap<String, String>, data: &[u8], now: u64| {
+            verify(&config, parse(fields, "me.png", data), now).unwrap_err()
+        };
+        assert_eq!(
+            check(&signed.fields, &[0u8; 17], now),
+            PostPolicyError::TooLarge
+        );
+        assert_eq!(
+            check(&signed.fields, b"png!", now + 600),
+            PostPolicyError::Expired
+        );
+
+        let mut fields = signed.fields.clone();
+        fields.insert(KEY_FIELD.to_string(), "avatars/bob/me.png".to_string());
+        assert_eq!(
+            check(&fields, b"png!", now),
+            PostPolicyError::ConditionFailed
+        );
+        let mut fields = signed.fields.clone();
+        fields.insert("acl".to_string(), "public-read".to_string());
+        assert_eq!(
+            check(&fields, b"png!", now),
+            PostPolicyError::FieldNotInPolicy
+        );
+        let mut fields = signed.fields.clone();
+        fields.insert(CONTENT_TYPE_FIELD.to_string(), "text/html".to_string());
+        assert_eq!(
+            check(&fields, b"png!", now),
+            PostPolicyError::FieldNotInPolicy
+        );
+        let mut fields = signed.fields.clone();
+        fields.insert(SIGNATURE_FIELD.to_string(), sign("other-key", "x"));
+        assert_eq!(
+            check(&fields, b"png!", now),
+            PostPolicyError::InvalidSignature
+        );
+
+        // Requests the bucket limits do not allow are refused at issue time
+        for request in [
+            PolicyRequest {
+                key: Some("documents/a.pdf".to_string()),
+                ..Default::default()
+            },
+            PolicyRequest {
+                max_size_bytes: Some(4096),
+                ..Default::default()
+            },
+            PolicyRequest {
+                content_type: Some("text/html".to_string()),
+                ..Default::default()
+            },
+            PolicyRequest {
+                expires_in_secs: Some(7200),
+                ..Default::default()
+            },
+        ] {
+            assert!(generate(&config, &request, now).is_err(), "{:?}", request);
+        }
+    }
+}
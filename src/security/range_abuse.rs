@@ -0,0 +1,311 @@
+//! Range request abuse detection.
+//!
+//! [`RangeAbuseDetector`] counts each client's small ranges per object in a
+//! fixed window. A client crossing `max_small_ranges` on any object is
+//! flagged and, for `throttle_secs`, may only send
+//! `throttled_requests_per_second` Range requests across all objects. The
+//! rest are rejected with 429 before they reach S3.
+//!
+//! Counting is local to each instance, like IP bans.
+
+use parking_lot::Mutex;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use super::IpRange;
+use crate::config::RangeAbuseConfig;
+
+/// Outcome of checking a Range request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RangeVerdict {
+    /// Forward the request
+    Allowed,
+    /// Forward the request; it pushed the client over the threshold
+    Flagged,
+    /// Reject the request; the client is throttled
+    Throttled,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RangeWindow {
+    started: Instant,
+    small_ranges: u32,
+}
+
+/// Token bucket of a flagged client
+#[derive(Debug, Clone, Copy)]
+struct Throttle {
+    until: Instant,
+    tokens: f64,
+    refilled: Instant,
+}
+
+/// Number of ranges in a `Range` header no larger than `small_range_bytes`
+///
+/// Open-ended ranges (`bytes=100-`) are never small. Malformed parts are
+/// ignored; S3 rejects them anyway.
+pub fn small_range_count(header: &str, small_range_bytes: u64) -> u32 {
+    let Some(ranges) = header.trim().strip_prefix("bytes=") else {
+        return 0;
+    };
+    let mut count: u32 = 0;
+    for range in ranges.split(',') {
+        let Some((start, end)) = range.trim().split_once('-') else {
+            continue;
+        };
+        let len = match (start.parse::<u64>(), end.parse::<u64>()) {
+            (Ok(start), Ok(end)) if end >= start => end - start + 1,
+            // Suffix range: the last `end` bytes
+            (Err(_), Ok(suffix)) if start.is_empty() => suffix,
+            _ => continue,
+        };
+        if len <= small_range_bytes {
+            count = count.saturating_add(1);
+        }
+    }
+    count
+}
+
+/// Tracks small ranges per client and object, and throttled clients
+pub struct RangeAbuseDetector {
+    config: RangeAbuseConfig,
+    exempt: Vec<IpRange>,
+    windows: Mutex<HashMap<(IpAddr, String), RangeWindow>>,
+    throttled: Mutex<HashMap<IpAddr, Throttle>>,
+}
+
+impl RangeAbuseDetector {
+    /// Create a detector from a validated config
+    pub fn new(config: RangeAbuseConfig) -> Result<Self, String> {
+        let exempt = config
+            .exempt
+            .iter()
+            .map(|entry| IpRange::parse(entry).map_err(|e| e.to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            config,
+            exempt,
+            windows: Mutex::new(HashMap::new()),
+            throttled: Mutex::new(HashMap::new()),
+        })
+    }
+
+    /// Check a Range request from `ip` for `object` (e.g. `bucket/key`)
+    pub fn check(&self, ip: IpAddr, object: &str, range_header: &str) -> RangeVerdict {
+        self.check_at(ip, object, range_header, Instant::now())
+    }
+
+    fn check_at(&self, ip: IpAddr, object: &str, range_header: &str, now: Instant) -> RangeVerdict {
+        if self.exempt.iter().any(|range| range.contains(&ip)) {
+            return RangeVerdict::Allowed;
+        }
+
+        if let Some(throttle) = self.throttled.lock().get_mut(&ip) {
+            if throttle.until > now {
+                let rate = f64::from(self.config.throttled_requests_per_second);
+                let elapsed = now.saturating_duration_since(throttle.refilled);
+                throttle.tokens = (throttle.tokens + elapsed.as_secs_f64() * rate).min(rate);
+                throttle.refilled = now;
+                if throttle.tokens < 1.0 {
+                    return RangeVerdict::Throttled;
+                }
+                throttle.tokens -= 1.0;
+                // Already flagged: no need to keep counting
+                return RangeVerdict::Allowed;
+            }
+        }
+
+        let small = small_range_count(range_header, self.config.small_range_bytes);
+        if small == 0 {
+            return RangeVerdict::Allowed;
+        }
+
+        let window_len = Duration::from_secs(self.config.window_secs);
+        {
+            let mut windows = self.windows.lock();
+            let key = (ip, object.to_string());
+            if !windows.contains_key(&key) && windows.len() >= self.config.max_tracked {
+                windows.retain(|_, w| now.saturating_duration_since(w.started) < window_len);
+                if windows.len() >= self.config.max_tracked {
+                    // Fail open rather than grow without bound
+                    return RangeVerdict::Allowed;
+                }
+            }
+
+            let window = windows.entry(key.clone()).or_insert(RangeWindow {
+                started: now,
+                small_ranges: 0,
+            });
+            if now.saturating_duration_since(window.started) >= window_len {
+                *window = RangeWindow {
+                    started: now,
+                    small_ranges: 0,
+                };
+            }
+            window.small_ranges = window.small_ranges.saturating_add(small);
+            if window.small_ranges < self.config.max_small_ranges {
+                return RangeVerdict::Allowed;
+            }
+            windows.remove(&key);
+        }
+
+        let rate = f64::from(self.config.throttled_requests_per_second);
+        self.throttled.lock().insert(
+            ip,
+            Throttle {
+                until: now + Duration::from_secs(self.config.throttle_secs),
+                tokens: rate,
+                refilled: now,
+            },
+        );
+        RangeVerdict::Flagged
+    }
+
+    /// Number of clients currently throttled
+    pub fn throttled_count(&self) -> usize {
+        let now = Instant::now();
+        self.throttled
+            .lock()
+            .values()
+            .filter(|throttle| throttle.until > now)
+            .count()
+    }
+
+    /// Drop expired windows and throttles
+    pub fn cleanup(&self) {
+        let now = Instant::now();
+        let window_len = Duration::from_secs(self.config.window_secs);
+        self.throttled
+            .lock()
+            .retain(|_, throttle| throttle.until > now);
+        self.windows
+            .lock()
+            .retain(|_, w| now.saturating_duration_since(w.started) < window_len);
+    }
+
+    /// Start the background cleanup task (call once inside a Tokio runtime)
+    pub fn start_cleanup_task(self: &Arc<Self>) {
+        let interval = Duration::from_secs(self.config.window_secs);
+        let detector = Arc::downgrade(self);
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+            loop {
+                ticker.tick().await;
+                // Stop once the proxy (and its detector) is dropped, e.g. after reload
+                let Some(detector) = detector.upgrade() else {
+                    break;
+                };
+                detector.cleanup();
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detector(max_small_ranges: u32, throttled_requests_per_second: u32) -> RangeAbuseDetector {
+        RangeAbuseDetector::new(RangeAbuseConfig {
+            enabled: true,
+            max_small_ranges,
+            throttled_requests_per_second,
+            small_range_bytes: 1024,
+            exempt: vec!["10.0.0.0/8".to_string()],
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn ip(s: &str) -> IpAddr {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn test_small_range_count() {
+        assert_eq!(small_range_count("bytes=0-1023", 1024), 1);
+        assert_eq!(small_range_count("bytes=0-1024", 1024), 0);
+        assert_eq!(small_range_count("bytes=-100", 1024), 1);
+        assert_eq!(small_range_count("bytes=100-", 1024), 0);
+        assert_eq!(small_range_count("bytes=0-0, 2-2,4-4", 1024), 3);
+        assert_eq!(small_range_count("bytes=9-1,x-y", 1024), 0);
+        assert_eq!(small_range_count("items=0-1", 1024), 0);
+    }
+
+    #[test]
+    fn test_flags_client_after_small_range_threshold() {
+        let detector = detector(3, 1);
+        let now = Instant::now();
+        let client = ip("203.0.113.7");
+
+        assert_eq!(
+            detector.check_at(client, "media/a.mp4", "bytes=0-99", now),
+            RangeVerdict::Allowed
+        );
+        // Large ranges and other objects do not count
+        assert_eq!(
+            detector.check_at(client, "media/a.mp4", "bytes=0-", now),
+            RangeVerdict::Allowed
+        );
+        assert_eq!(
+            detector.check_at(client, "media/b.mp4", "bytes=0-99", now),
+            RangeVerdict::Allowed
+        );
+        assert_eq!(
+            detector.check_at(client, "media/a.mp4", "bytes=100-199,200-299", now),
+            RangeVerdict::Flagged
+        );
+        assert_eq!(detector.throttled_count(), 1);
+
+        // One token per second, shared by all objects
+        assert_eq!(
+            detector.check_at(client, "media/b.mp4", "bytes=0-", now),
+            RangeVerdict::Allowed
+        );
+        assert_eq!(
+            detector.check_at(client, "media/c.mp4", "bytes=0-", now),
+            RangeVerdict::Throttled
+        );
+        let later = now + Duration::from_secs(1);
+        assert_eq!(
+            detector.check_at(client, "media/c.mp4", "bytes=0-", later),
+            RangeVerdict::Allowed
+        );
+
+        // Other clients are unaffected
+        assert_eq!(
+            detector.check_at(ip("203.0.113.8"), "media/a.mp4", "bytes=0-99", now),
+            RangeVerdict::Allowed
+        );
+    }
+
+    #[test]
+    fn test_throttle_expires_and_exempt_ips() {
+        let detector = detector(1, 0);
+        let now = Instant::now();
+        let client = ip("2001:db8::1");
+
+        assert_eq!(
+            detector.check_at(client, "media/a.mp4", "bytes=0-0", now),
+            RangeVerdict::Flagged
+        );
+        assert_eq!(
+            detector.check_at(client, "media/a.mp4", "bytes=0-", now),
+            RangeVerdict::Throttled
+        );
+        let expired = now + Duration::from_secs(301);
+        assert_eq!(
+            detector.check_at(client, "media/a.mp4", "bytes=0-", expired),
+            RangeVerdict::Allowed
+        );
+
+        assert_eq!(
+            detector.check_at(ip("10.1.2.3"), "media/a.mp4", "bytes=0-0", now),
+            RangeVerdict::Allowed
+        );
+    }
+}
@@ -0,0 +1,180 @@
+//! Signed URL verification.
+//!
+//! A signed URL carries `expires` (unix seconds) and `sig` query parameters:
+//!
+//! ```text
+//! sig = base64url(HMAC-SHA256(secret, path + "\n" + expires))
+//! sig = base64url(HMAC-SHA256(secret, path + "\n" + expires + "\n" + client_ip))  # bind_ip
+//! ```
+//!
+//! `path` is the request path as sent by the client, without the query
+//! string. Signatures are compared in constant time.
+
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::collections::HashMap;
+
+use crate::config::SignedUrlConfig;
+use crate::security::constant_time_eq;
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Query parameter holding the expiry time
+pub const EXPIRES_PARAM: &str = "expires";
+
+/// Query parameter holding the signature
+pub const SIGNATURE_PARAM: &str = "sig";
+
+/// Why a signed URL was rejected
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignedUrlError {
+    /// `expires` or `sig` is missing
+    Missing,
+    /// `expires` is not a unix timestamp
+    Malformed,
+    /// `expires` is in the past
+    Expired,
+    /// `expires` is further out than `max_ttl_secs`
+    TtlTooLong,
+    /// The signature does not match
+    InvalidSignature,
+}
+
+impl SignedUrlError {
+    /// Label used in logs and metrics
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Missing => "missing",
+            Self::Malformed => "malformed",
+            Self::Expired => "expired",
+            Self::TtlTooLong => "ttl_too_long",
+            Self::InvalidSignature => "invalid_signature",
+        }
+    }
+
+    /// Message returned to the client
+    pub fn message(&self) -> &'static str {
+        match self {
+            Self::Missing => "This resource requires a signed URL",
+            Self::Malformed => "Signed URL expiry is malformed",
+            Self::Expired => "Signed URL has expired",
+            Self::TtlTooLong => "Signed URL expiry is too far in the future",
+            Self::InvalidSignature => "Signed URL signature is invalid",
+        }
+    }
+}
+
+/// Signature for a path and expiry, bound to `client_ip` when given
+pub fn sign(secret: &str, path: &str, expires: u64, client_ip: Option<&str>) -> String {
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key");
+    mac.update(path.as_bytes());
+    mac.update(b"\n");
+    mac.update(expires.to_string().as_bytes());
+    if let Some(ip) = client_ip {
+        mac.update(b"\n");
+        mac.update(ip.as_bytes());
+    }
+    URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes())
+}
+
+/// Verify the signed URL parameters of a request at time `now` (unix seconds)
+pub fn verify(
+    config: &SignedUrlConfig,
+    path: &str,
+    query_params: &HashMap<String, String>,
+    client_ip: Option<&str>,
+    now: u64,
+) -> Result<(), SignedUrlError> {
+    let (Some(expires), Some(signature)) = (
+        query_params.get(EXPIRES_PARAM),
+        query_params.get(SIGNATURE_PARAM),
+    ) else {
+        return Err(SignedUrlError::Missing);
+    };
+    let expires: u64 = expires.parse().map_err(|_| SignedUrlError::Malformed)?;
+    if expires <= now {
+        return Err(SignedUrlError::Expired);
+    }
+    if config
+        .max_ttl_secs
+        .is_some_and(|max_ttl| expires - now > max_ttl)
+    {
+        return Err(SignedUrlError::TtlTooLong);
+    }
+
+    let client_ip = if config.bind_ip {
+        Some(client_ip.ok_or(SignedUrlError::InvalidSignature)?)
+    } else {
+        None
+    };
+    let expected = sign(&config.secret, path, expires, client_ip);
+    if constant_time_eq(signature, &expected) {
+        Ok(())
+    } else {
+        Err(SignedUrlError::InvalidSignature)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(expires: u64, sig: &str) -> HashMap<String, String> {
+        HashMap::from([
+            (EXPIRES_PARAM.to_string(), expires.to_string()),
+            (SIGNATURE_PARAM.to_string(), sig.to_string()),
+        ])
+    }
+
+    #[test]
+    fn test_verify_signed_url() {
+        let mut config: SignedUrlConfig =
+            serde_yaml::from_str("secret: downloads-key\nmax_ttl_secs: 3600").unwrap();
+        let path = "/downloads/report.pdf";
+        let now = 1_700_000_000;
+        let sig = sign("downloads-key", path, now + 60, None);
+
+        assert_eq!(
+            verify(&config, path, &params(now + 60, &sig), None, now),
+            Ok(())
+        );
+        assert_eq!(
+            verify(
+                &config,
+                "/downloads/other.pdf",
+                &params(now + 60, &sig),
+                None,
+                now
+            ),
+            Err(SignedUrlError::InvalidSignature)
+        );
+        assert_eq!(
+            verify(&config, path, &params(now + 60, &sig), None, now + 60),
+            Err(SignedUrlError::Expired)
+        );
+        assert_eq!(
+            verify(&config, path, &HashMap::new(), None, now),
+            Err(SignedUrlError::Missing)
+        );
+        let far = sign("downloads-key", path, now + 7200, None);
+        assert_eq!(
+            verify(&config, path, &params(now + 7200, &far), None, now),
+            Err(SignedUrlError::TtlTooLong)
+        );
+
+        // An IP-bound link only works from that address
+        config.bind_ip = true;
+        let bound = sign("downloads-key", path, now + 60, Some("203.0.113.7"));
+        let bound_params = params(now + 60, &bound);
+        assert_eq!(
+            verify(&config, path, &bound_params, Some("203.0.113.7"), now),
+            Ok(())
+        );
+        assert_eq!(
+            verify(&config, path, &bound_params, Some("198.51.100.1"), now),
+            Err(SignedUrlError::InvalidSignature)
+        );
+    }
+}
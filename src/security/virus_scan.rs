@@ -0,0 +1,132 @@
+//! Upload virus scanning with ClamAV.
+//!
+//! Upload bodies are collected in an [`UploadScanBuffer`] while the proxy
+//! holds them back from S3, then streamed to clamd with the `INSTREAM`
+//! command: `zINSTREAM\0`, length-prefixed chunks, and a zero-length chunk.
+//! clamd answers `stream: OK` or `stream: <signature> FOUND`.
+
+use std::time::Duration;
+
+use bytes::{Bytes, BytesMut};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpStream;
+
+/// Largest chunk sent in one `INSTREAM` frame
+const INSTREAM_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Result of a completed scan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanVerdict {
+    Clean,
+    /// Signature name reported by the scanner
+    Infected(String),
+}
+
+/// Upload body held back while it is collected for scanning
+#[derive(Debug, Default)]
+pub struct UploadScanBuffer {
+    chunks: Vec<Bytes>,
+    size: u64,
+    /// Body outgrew the scan limit and is forwarded unscanned (fail-open)
+    passthrough: bool,
+}
+
+impl UploadScanBuffer {
+    /// Hold back `chunk`, returning the buffered size
+    pub fn push(&mut self, chunk: Bytes) -> u64 {
+        self.size += chunk.len() as u64;
+        self.chunks.push(chunk);
+        self.size
+    }
+
+    /// Everything held back so far, as one body
+    pub fn take(&mut self) -> Bytes {
+        let mut body = BytesMut::with_capacity(self.size as usize);
+        for chunk in self.chunks.drain(..) {
+            body.extend_from_slice(&chunk);
+        }
+        self.size = 0;
+        body.freeze()
+    }
+
+    /// Forward the rest of the body without scanning
+    pub fn set_passthrough(&mut self) {
+        self.passthrough = true;
+    }
+
+    pub fn is_passthrough(&self) -> bool {
+        self.passthrough
+    }
+}
+
+/// Scan `body` with the clamd listening on `address`
+pub async fn scan_clamd(
+    address: &str,
+    body: &[u8],
+    timeout: Duration,
+) -> Result<ScanVerdict, String> {
+    let scan = async {
+        let mut stream = TcpStream::connect(address)
+            .await
+            .map_err(|e| format!("Failed to connect to clamd at {}: {}", address, e))?;
+        stream
+            .write_all(b"zINSTREAM\0")
+            .await
+            .map_err(|e| e.to_string())?;
+        for chunk in body.chunks(INSTREAM_CHUNK_SIZE) {
+            stream
+                .write_all(&(chunk.len() as u32).to_be_bytes())
+                .await
+                .map_err(|e| e.to_string())?;
+            stream.write_all(chunk).await.map_err(|e| e.to_string())?;
+        }
+        stream
+            .write_all(&0u32.to_be_bytes())
+            .await
+            .map_err(|e| e.to_string())?;
+
+        let mut reply = Vec::new();
+        stream
+            .read_to_end(&mut reply)
+            .await
+            .map_err(|e| e.to_string())?;
+        parse_reply(&String::from_utf8_lossy(&reply))
+    };
+    tokio::time::timeout(timeout, scan)
+        .await
+        .map_err(|_| format!("clamd scan timed out after {:?}", timeout))?
+}
+
+/// Interpret a clamd `INSTREAM` reply
+fn parse_reply(reply: &str) -> Result<ScanVerdict, String> {
+    let reply = reply.trim_end_matches(['\0', '\n']);
+    let status = reply.strip_prefix("stream: ").unwrap_or(reply);
+    if status == "OK" {
+        Ok(ScanVerdict::Clean)
+    } else if let Some(signature) = status.strip_suffix(" FOUND") {
+        Ok(ScanVerdict::Infected(signature.to_string()))
+    } else {
+        Err(format!("clamd error: {}", status))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_reply_and_buffer() {
+        assert_eq!(parse_reply("stream: OK\0"), Ok(ScanVerdict::Clean));
+        assert_eq!(
+            parse_reply("stream: Eicar-Test-Signature FOUND\0"),
+            Ok(ScanVerdict::Infected("Eicar-Test-Signature".to_string()))
+        );
+        assert!(parse_reply("INSTREAM size limit exceeded. ERROR\0").is_err());
+
+        let mut buffer = UploadScanBuffer::default();
+        buffer.push(Bytes::from_static(b"part-"));
+        assert_eq!(buffer.push(Bytes::from_static(b"body")), 9);
+        assert_eq!(buffer.take(), Bytes::from_static(b"part-body"));
+        assert!(!buffer.is_passthrough());
+    }
+}
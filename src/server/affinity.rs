@@ -0,0 +1,108 @@
+//! CPU affinity for worker threads.
+//!
+//! Pinning is applied to the calling thread before Pingora spawns its worker
+//! runtimes; Linux threads inherit the affinity mask of the thread that
+//! created them, so every worker ends up restricted to the configured CPUs.
+
+use std::io;
+
+/// Restrict the calling thread (and threads it spawns afterwards) to `cpus`
+#[cfg(target_os = "linux")]
+pub fn pin_current_thread(cpus: &[usize]) -> io::Result<()> {
+    if cpus.is_empty() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "CPU affinity list is empty",
+        ));
+    }
+
+    // SAFETY: cpu_set_t is a plain bitmask; zeroed is the empty set
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    for &cpu in cpus {
+        if cpu >= libc::CPU_SETSIZE as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("CPU id {} exceeds CPU_SETSIZE", cpu),
+            ));
+        }
+        // SAFETY: cpu is within the bounds of the set
+        unsafe { libc::CPU_SET(cpu, &mut set) };
+    }
+
+    // SAFETY: pid 0 targets the calling thread; set is a valid cpu_set_t
+    let rc = unsafe { libc::sched_setaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &set) };
+    if rc != 0 {
+        return Err(io::Error::last_os_error());
+    }
+    Ok(())
+}
+
+/// Restrict the calling thread (and threads it spawns afterwards) to `cpus`
+#[cfg(not(target_os = "linux"))]
+pub fn pin_current_thread(_cpus: &[usize]) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "CPU affinity is only supported on Linux",
+    ))
+}
+
+/// CPU ids the calling thread is currently allowed to run on
+#[cfg(target_os = "linux")]
+pub fn current_affinity() -> io::Result<Vec<usize>> {
+    // SAFETY: cpu_set_t is a plain bitmask; zeroed is the empty set
+    let mut set: libc::cpu_set_t = unsafe { std::mem::zeroed() };
+    // SAFETY: pid 0 targets the calling thread; set is a valid cpu_set_t
+    let rc =
+        unsafe { libc::sched_getaffinity(0, std::mem::size_of::<libc::cpu_set_t>(), &mut set) };
+    if rc != 0 {
+        return Err(io::Error::last_os_error());
+    }
+
+    Ok((0..libc::CPU_SETSIZE as usize)
+        // SAFETY: cpu is within the bounds of the set
+        .filter(|&cpu| unsafe { libc::CPU_ISSET(cpu, &set) })
+        .collect())
+}
+
+/// CPU ids the calling thread is currently allowed to run on
+#[cfg(not(target_os = "linux"))]
+pub fn current_affinity() -> io::Result<Vec<usize>> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "CPU affinity is only supported on Linux",
+    ))
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pin_current_thread_restricts_and_is_inherited() {
+        // Run on a dedicated thread so the test harness threads stay unpinned
+        std::thread::spawn(|| {
+            let allowed = current_affinity().unwrap();
+            let target = allowed[0];
+
+            pin_current_thread(&[target]).unwrap();
+            assert_eq!(current_affinity().unwrap(), vec![target]);
+
+            let child = std::thread::spawn(|| current_affinity().unwrap())
+                .join()
+                .unwrap();
+            assert_eq!(child, vec![target]);
+        })
+        .join()
+        .unwrap();
+    }
+
+    #[test]
+    fn test_pin_current_thread_rejects_invalid_sets() {
+        std::thread::spawn(|| {
+            assert!(pin_current_thread(&[]).is_err());
+            assert!(pin_current_thread(&[libc::CPU_SETSIZE as usize]).is_err());
+        })
+        .join()
+        .unwrap();
+    }
+}
@@ -1,5 +1,7 @@
 // Server module - Pingora HTTP server setup and configuration
 
+pub mod affinity;
+
 use crate::config::Config;
 use crate::constants::*;
 use pingora::server::configuration::Opt as ServerOpt;
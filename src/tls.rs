@@ -0,0 +1,803 @@
+//! HTTPS listener (`tls` feature).
+//!
+//! Terminates TLS on `server.tls` with a certificate taken from a directory
+//! maintained by an external ACME client. Every handshake is served from a
+//! [`CertificateStore`], whose contents a [`CertificateWatcher`] replaces
+//! when the files in the directory change, so renewals take effect without a
+//! restart and connections opened before a renewal keep their certificate.
+//!
+//! `reload::ReloadManager` also reloads the files on SIGHUP and
+//! `POST /admin/reload` (see [`CertificateStore::reload`]), so an ACME
+//! client's deploy hook can apply a renewal without waiting for the watcher.
+//!
+//! With `ocsp_stapling` on, the watcher fetches an OCSP response for the
+//! certificate from the responder named in it and staples it to handshakes
+//! from clients that ask for one. The response is refreshed ahead of its
+//! `nextUpdate`; a failed fetch keeps the previous response while it is
+//! still valid. Responses are not verified here: clients check them against
+//! the issuer as they would an unstapled response.
+//!
+//! With `client_ca_file` set, client certificates are verified against that
+//! CA bundle. Pingora only exposes a verified certificate's SHA-256 digest to
+//! requests, so the subject of each verified certificate is remembered in
+//! [`ClientSubjects`] during the handshake and looked up by that digest in
+//! [`client_cert_subject`].
+//!
+//! Session resumption, protocol version, ciphers and ALPN come from the
+//! listener config. Completed handshakes are counted as full or resumed in
+//! [`Metrics`], with the duration of full handshakes measured from
+//! certificate selection.
+
+use arc_swap::ArcSwap;
+use async_trait::async_trait;
+use openssl::asn1::Asn1GeneralizedTimeRef;
+use openssl::ex_data::Index;
+use openssl::hash::MessageDigest;
+use openssl::ocsp::{OcspCertId, OcspRequest, OcspResponse, OcspResponseStatus};
+use openssl::pkey::{PKey, Private};
+use openssl::ssl::{AlpnError, Ssl, SslOptions, SslSessionCacheMode, SslVerifyMode, SslVersion};
+use openssl::x509::{X509Name, X509Ref, X509};
+use parking_lot::Mutex;
+use pingora_core::listeners::tls::TlsSettings;
+use pingora_core::listeners::TlsAccept;
+use pingora_core::protocols::tls::TlsRef;
+use pingora_core::server::ShutdownWatch;
+use pingora_core::services::background::BackgroundService;
+use pingora_core::tls::ext;
+use pingora_proxy::Session;
+use std::any::Any;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::{Arc, OnceLock};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::config::{ClientAuthMode, TlsListenerConfig, TlsVersion};
+use crate::constants::{
+    MAX_CLIENT_CERT_SUBJECTS, OCSP_REFRESH_MARGIN_SECS, OCSP_REQUEST_TIMEOUT_SECS,
+};
+use crate::metrics::Metrics;
+
+/// OCSP response stapled to handshakes
+#[derive(Clone)]
+struct StapledOcsp {
+    der: Vec<u8>,
+    /// When the response stops being valid (`nextUpdate`), if it says
+    expires_at: Option<SystemTime>,
+    /// When a fresh response should be fetched
+    refresh_at: SystemTime,
+}
+
+/// Certificate chain, key and stapled OCSP response served to new handshakes
+pub struct CertifiedKey {
+    leaf: X509,
+    /// Intermediates, in the order of the chain file
+    chain: Vec<X509>,
+    key: PKey<Private>,
+    /// Newest modification time of the certificate and key files
+    modified: Option<SystemTime>,
+    ocsp: Option<StapledOcsp>,
+}
+
+impl CertifiedKey {
+    /// Read the certificate chain and key named in `config`
+    pub fn load(config: &TlsListenerConfig) -> Result<Self, String> {
+        let (cert_path, key_path) = (config.cert_path(), config.key_path());
+        let read = |path: &Path| {
+            std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+        };
+
+        let mut certs = X509::stack_from_pem(&read(&cert_path)?)
+            .map_err(|e| format!("Invalid certificate in {}: {}", cert_path.display(), e))?
+            .into_iter();
+        let leaf = certs
+            .next()
+            .ok_or_else(|| format!("No certificate in {}", cert_path.display()))?;
+        let key = PKey::private_key_from_pem(&read(&key_path)?)
+            .map_err(|e| format!("Invalid private key in {}: {}", key_path.display(), e))?;
+        let matches = leaf
+            .public_key()
+            .map(|public| public.public_eq(&key))
+            .unwrap_or(false);
+        if !matches {
+            return Err(format!(
+                "Private key in {} does not match the certificate in {}",
+                key_path.display(),
+                cert_path.display()
+            ));
+        }
+
+        Ok(Self {
+            leaf,
+            chain: certs.collect(),
+            key,
+            modified: files_modified(config),
+            ocsp: None,
+        })
+    }
+
+    /// Subject of the leaf certificate, for logs
+    pub fn subject(&self) -> String {
+        format_name(self.leaf.subject_name())
+    }
+
+    fn with_ocsp(&self, ocsp: Option<StapledOcsp>) -> Self {
+        Self {
+            leaf: self.leaf.clone(),
+            chain: self.chain.clone(),
+            key: self.key.clone(),
+            modified: self.modified,
+            ocsp,
+        }
+    }
+
+    /// Install the certificate, chain and OCSP response on a handshake
+    fn apply(&self, ssl: &mut TlsRef) -> Result<(), openssl::error::ErrorStack> {
+        ext::ssl_use_certificate(ssl, &self.leaf)?;
+        ext::ssl_use_private_key(ssl, &self.key)?;
+        for cert in &self.chain {
+            ext::ssl_add_chain_cert(ssl, cert)?;
+        }
+        if let Some(ocsp) = &self.ocsp {
+            ssl.set_ocsp_status(&ocsp.der)?;
+        }
+        Ok(())
+    }
+}
+
+/// Newest modification time of the certificate and key files
+fn files_modified(config: &TlsListenerConfig) -> Option<SystemTime> {
+    [config.cert_path(), config.key_path()]
+        .iter()
+        .filter_map(|path| std::fs::metadata(path).and_then(|m| m.modified()).ok())
+        .max()
+}
+
+/// `CN=example.com,O=Example` style rendering of an X.509 name
+pub(crate) fn format_name(name: &openssl::x509::X509NameRef) -> String {
+    name.entries()
+        .map(|entry| {
+            let field = entry.object().nid().short_name().unwrap_or("?");
+            let value = entry
+                .data()
+                .as_utf8()
+                .map(|value| value.to_string())
+                .unwrap_or_default();
+            format!("{}={}", field, value)
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Certificate currently served by the HTTPS listener
+pub struct CertificateStore {
+    config: TlsListenerConfig,
+    current: ArcSwap<CertifiedKey>,
+    http: reqwest::Client,
+}
+
+impl CertificateStore {
+    /// Load the certificate named in `config`; fails if it cannot be used
+    pub fn load(config: &TlsListenerConfig) -> Result<Arc<Self>, String> {
+        let current = CertifiedKey::load(config)?;
+        let http = reqwest::Client::builder()
+            .timeout(Duration::from_secs(OCSP_REQUEST_TIMEOUT_SECS))
+            .build()
+            .map_err(|e| format!("Failed to create OCSP client: {}", e))?;
+        Ok(Arc::new(Self {
+            config: config.clone(),
+            current: ArcSwap::from_pointee(current),
+            http,
+        }))
+    }
+
+    /// Certificate served to new handshakes
+    pub fn current(&self) -> Arc<CertifiedKey> {
+        self.current.load_full()
+    }
+
+    /// Pingora listener settings serving handshakes from this store
+    pub fn tls_settings(self: &Arc<Self>) -> Result<TlsSettings, String> {
+        let resolver = CertificateResolver {
+            store: Arc::clone(self),
+        };
+        let mut settings = TlsSettings::with_callbacks(Box::new(resolver))
+            .map_err(|e| format!("Failed to create TLS settings: {}", e))?;
+        apply_protocol_settings(&mut settings, &self.config)?;
+        // The resolver installs the response; this only reports whether it did
+        settings
+            .set_status_callback(|ssl| Ok(ssl.ocsp_status().is_some()))
+            .map_err(|e| format!("Failed to enable OCSP stapling: {}", e))?;
+        if let Some(ca_file) = &self.config.client_ca_file {
+            verify_client_certificates(&mut settings, ca_file, self.config.client_auth)?;
+        }
+        Ok(settings)
+    }
+
+    /// Reload the files if they changed since they were last read.
+    ///
+    /// Returns true when a new certificate was installed. A file that cannot
+    /// be read or does not match its key is logged and the old certificate is
+    /// kept, so an ACME client caught halfway through writing is retried on
+    /// the next check.
+    pub fn reload_if_changed(&self) -> bool {
+        match self.reload() {
+            Ok(reloaded) => reloaded,
+            Err(e) => {
+                tracing::warn!(error = %e, "Keeping the current TLS certificate");
+                false
+            }
+        }
+    }
+
+    /// Like [`reload_if_changed`](Self::reload_if_changed), but returns the
+    /// error instead of logging it; used by `reload::ReloadManager` so that
+    /// SIGHUP and `POST /admin/reload` pick up a renewal immediately.
+    ///
+    /// Only new handshakes get the new certificate: connections already
+    /// established keep the one they were handshaked with.
+    pub fn reload(&self) -> Result<bool, String> {
+        let modified = files_modified(&self.config);
+        if modified.is_none() || modified == self.current.load().modified {
+            return Ok(false);
+        }
+        let reloaded = CertifiedKey::load(&self.config)?;
+        tracing::info!(
+            cert_file = %self.config.cert_path().display(),
+            subject = %reloaded.subject(),
+            "Reloaded TLS certificate"
+        );
+        self.current.store(Arc::new(reloaded));
+        Ok(true)
+    }
+
+    /// Fetch a new OCSP response when the stapled one is due for refresh
+    pub async fn refresh_ocsp(&self) {
+        if !self.config.ocsp_stapling {
+            return;
+        }
+        let current = self.current();
+        let now = SystemTime::now();
+        if current
+            .ocsp
+            .as_ref()
+            .is_some_and(|ocsp| ocsp.refresh_at > now)
+        {
+            return;
+        }
+        let Some(issuer) = current.chain.first() else {
+            tracing::warn!(
+                cert_file = %self.config.cert_path().display(),
+                "No issuer certificate in the chain file; OCSP stapling skipped"
+            );
+            return;
+        };
+
+        let ocsp = match self.fetch_ocsp(&current.leaf, issuer).await {
+            Ok(ocsp) => Some(ocsp),
+            Err(e) => {
+                tracing::warn!(error = %e, "OCSP response fetch failed");
+                // Keep stapling the previous response until it expires
+                current
+                    .ocsp
+                    .clone()
+                    .filter(|ocsp| ocsp.expires_at.map_or(true, |expires| expires > now))
+            }
+        };
+
+        // Only install the response if the certificate was not replaced meanwhile
+        let updated = Arc::new(current.with_ocsp(ocsp));
+        self.current.compare_and_swap(&current, updated);
+    }
+
+    async fn fetch_ocsp(&self, leaf: &X509, issuer: &X509) -> Result<StapledOcsp, String> {
+        let responders = leaf.ocsp_responders().map_err(|e| e.to_string())?;
+        let url = responders
+            .iter()
+            .next()
+            .map(|url| url.to_string())
+            .ok_or("certificate names no OCSP responder")?;
+        let cert_id = || {
+            OcspCertId::from_cert(MessageDigest::sha1(), leaf, issuer).map_err(|e| e.to_string())
+        };
+
+        let mut request = OcspRequest::new().map_err(|e| e.to_string())?;
+        request.add_id(cert_id()?).map_err(|e| e.to_string())?;
+        let body = request.to_der().map_err(|e| e.to_string())?;
+
+        let response = self
+            .http
+            .post(&url)
+            .header("Content-Type", "application/ocsp-request")
+            .body(body)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| format!("{}: {}", url, e))?;
+        let der = response
+            .bytes()
+            .await
+            .map_err(|e| format!("{}: {}", url, e))?
+            .to_vec();
+
+        let parsed = OcspResponse::from_der(&der).map_err(|e| e.to_string())?;
+        if parsed.status() != OcspResponseStatus::SUCCESSFUL {
+            return Err(format!(
+                "{} answered with status {:?}",
+                url,
+                parsed.status()
+            ));
+        }
+        let basic = parsed.basic().map_err(|e| e.to_string())?;
+        let cert_id = cert_id()?;
+        let status = basic
+            .find_status(&cert_id)
+            .ok_or_else(|| format!("{} did not cover the certificate", url))?;
+        status
+            .check_validity(300, None)
+            .map_err(|e| format!("{} returned a response outside its validity: {}", url, e))?;
+
+        let now = SystemTime::now();
+        let expires_at = status.next_update.and_then(generalized_time);
+        Ok(StapledOcsp {
+            der,
+            expires_at,
+            refresh_at: ocsp_refresh_at(now, expires_at, self.interval()),
+        })
+    }
+
+    fn interval(&self) -> Duration {
+        Duration::from_secs(self.config.reload_interval_secs)
+    }
+}
+
+/// Apply the session resumption, protocol version, cipher and ALPN settings
+fn apply_protocol_settings(
+    settings: &mut TlsSettings,
+    config: &TlsListenerConfig,
+) -> Result<(), String> {
+    // Resumed sessions carry the verified client certificate; OpenSSL refuses
+    // to resume them without a context id
+    settings
+        .set_session_id_context(b"yatagarasu")
+        .map_err(|e| format!("Failed to set TLS session id context: {}", e))?;
+    // Ticket keys are generated by OpenSSL when the listener starts and live
+    // as long as the process, so a restart rotates them
+    if !config.session_tickets {
+        settings.set_options(SslOptions::NO_TICKET);
+    }
+    if config.session_ids {
+        settings.set_session_cache_mode(SslSessionCacheMode::SERVER);
+        settings.set_session_cache_size(config.session_cache_size.min(i32::MAX as usize) as i32);
+    } else {
+        settings.set_session_cache_mode(SslSessionCacheMode::OFF);
+    }
+
+    let min_version = match config.min_version {
+        TlsVersion::Tls12 => SslVersion::TLS1_2,
+        TlsVersion::Tls13 => SslVersion::TLS1_3,
+    };
+    settings
+        .set_min_proto_version(Some(min_version))
+        .map_err(|e| format!("Failed to set TLS minimum version: {}", e))?;
+    if let Some(cipher_list) = &config.cipher_list {
+        settings
+            .set_cipher_list(cipher_list)
+            .map_err(|e| format!("Invalid server.tls.cipher_list '{}': {}", cipher_list, e))?;
+    }
+    if let Some(ciphersuites) = &config.ciphersuites {
+        settings
+            .set_ciphersuites(ciphersuites)
+            .map_err(|e| format!("Invalid server.tls.ciphersuites '{}': {}", ciphersuites, e))?;
+    }
+
+    let offered = config.alpn.clone();
+    settings.set_alpn_select_callback(move |_, client| {
+        select_alpn(&offered, client).ok_or(AlpnError::NOACK)
+    });
+    tracing::info!(
+        min_version = config.min_version.as_str(),
+        session_tickets = config.session_tickets,
+        session_ids = config.session_ids,
+        alpn = %config.alpn.join(","),
+        "TLS protocol settings applied"
+    );
+    Ok(())
+}
+
+/// First of the `offered` protocols that is also in the client's ALPN list
+/// (wire format: each name prefixed by its length)
+fn select_alpn<'a>(offered: &[String], client: &'a [u8]) -> Option<&'a [u8]> {
+    let mut client_protocols = Vec::new();
+    let mut rest = client;
+    while let Some((&len, tail)) = rest.split_first() {
+        let len = len as usize;
+        if len == 0 || tail.len() < len {
+            return None;
+        }
+        client_protocols.push(&tail[..len]);
+        rest = &tail[len..];
+    }
+    offered.iter().find_map(|protocol| {
+        client_protocols
+            .iter()
+            .find(|candidate| **candidate == protocol.as_bytes())
+            .copied()
+    })
+}
+
+/// Ask clients for a certificate issued by a CA in `ca_file` and verify it
+fn verify_client_certificates(
+    settings: &mut TlsSettings,
+    ca_file: &str,
+    mode: ClientAuthMode,
+) -> Result<(), String> {
+    let ca_names = X509Name::load_client_ca_file(ca_file)
+        .map_err(|e| format!("Invalid client CA file {}: {}", ca_file, e))?;
+    settings
+        .set_ca_file(ca_file)
+        .map_err(|e| format!("Invalid client CA file {}: {}", ca_file, e))?;
+    settings.set_client_ca_list(ca_names);
+    let verify_mode = match mode {
+        ClientAuthMode::Required => SslVerifyMode::PEER | SslVerifyMode::FAIL_IF_NO_PEER_CERT,
+        ClientAuthMode::Optional => SslVerifyMode::PEER,
+    };
+    settings.set_verify_callback(verify_mode, |verified, chain| {
+        if verified && chain.error_depth() == 0 {
+            if let Some(cert) = chain.current_cert() {
+                ClientSubjects::global().record(cert);
+            }
+        }
+        verified
+    });
+    tracing::info!(
+        client_ca_file = %ca_file,
+        client_auth = mode.as_str(),
+        "TLS client certificate verification enabled"
+    );
+    Ok(())
+}
+
+/// Subjects of verified client certificates by SHA-256 digest
+#[derive(Default)]
+pub struct ClientSubjects {
+    subjects: Mutex<HashMap<Vec<u8>, String>>,
+}
+
+static CLIENT_SUBJECTS: OnceLock<ClientSubjects> = OnceLock::new();
+
+impl ClientSubjects {
+    /// Process-wide subject map filled by the HTTPS listener
+    pub fn global() -> &'static ClientSubjects {
+        CLIENT_SUBJECTS.get_or_init(ClientSubjects::default)
+    }
+
+    /// Remember the subject of a certificate that passed verification
+    pub fn record(&self, cert: &X509Ref) {
+        let Ok(digest) = cert.digest(MessageDigest::sha256()) else {
+            return;
+        };
+        let mut subjects = self.subjects.lock();
+        if subjects.contains_key(digest.as_ref()) {
+            return;
+        }
+        // Certificates seen again after the reset are recorded by their next
+        // full handshake
+        if subjects.len() >= MAX_CLIENT_CERT_SUBJECTS {
+            subjects.clear();
+        }
+        subjects.insert(digest.to_vec(), format_name(cert.subject_name()));
+    }
+
+    /// Subject of the verified certificate with this SHA-256 digest
+    pub fn get(&self, digest: &[u8]) -> Option<String> {
+        self.subjects.lock().get(digest).cloned()
+    }
+}
+
+/// Subject of the verified client certificate on the session's connection,
+/// `None` on plain HTTP or when the client presented none
+pub fn client_cert_subject(session: &Session) -> Option<String> {
+    let ssl = session.digest()?.ssl_digest.as_ref()?;
+    if ssl.cert_digest.is_empty() {
+        return None;
+    }
+    ClientSubjects::global().get(&ssl.cert_digest)
+}
+
+/// When to fetch the next response: a margin before `expires_at`, but not
+/// before the next check
+fn ocsp_refresh_at(
+    now: SystemTime,
+    expires_at: Option<SystemTime>,
+    interval: Duration,
+) -> SystemTime {
+    let margin = Duration::from_secs(OCSP_REFRESH_MARGIN_SECS);
+    let due = match expires_at {
+        Some(expires) => expires.checked_sub(margin).unwrap_or(now),
+        None => now + margin,
+    };
+    due.max(now + interval)
+}
+
+fn generalized_time(time: &Asn1GeneralizedTimeRef) -> Option<SystemTime> {
+    parse_asn1_time(&time.to_string())
+}
+
+/// Parse OpenSSL's printed time format, e.g. `Oct  5 12:00:00 2026 GMT`
+fn parse_asn1_time(printed: &str) -> Option<SystemTime> {
+    let printed = printed.split_whitespace().collect::<Vec<_>>().join(" ");
+    chrono::NaiveDateTime::parse_from_str(&printed, "%b %d %H:%M:%S %Y GMT")
+        .ok()
+        .map(|time| time.and_utc().into())
+}
+
+/// Serves each handshake from the store's current certificate
+struct CertificateResolver {
+    store: Arc<CertificateStore>,
+}
+
+/// Slot holding when a handshake reached certificate selection
+fn handshake_started_index() -> Option<Index<Ssl, Instant>> {
+    static INDEX: OnceLock<Option<Index<Ssl, Instant>>> = OnceLock::new();
+    *INDEX.get_or_init(|| Ssl::new_ex_index().ok())
+}
+
+#[async_trait]
+impl TlsAccept for CertificateResolver {
+    async fn certificate_callback(&self, ssl: &mut TlsRef) {
+        if let Some(index) = handshake_started_index() {
+            ssl.set_ex_data(index, Instant::now());
+        }
+        if let Err(e) = self.store.current().apply(ssl) {
+            tracing::error!(error = %e, "Failed to install TLS certificate on handshake");
+        }
+    }
+
+    async fn handshake_complete_callback(
+        &self,
+        ssl: &TlsRef,
+    ) -> Option<Arc<dyn Any + Send + Sync>> {
+        // Resumed handshakes skip certificate selection, so only full
+        // handshakes have a start time to measure from
+        let duration_ms = handshake_started_index()
+            .and_then(|index| ssl.ex_data(index))
+            .map(|started| started.elapsed().as_millis() as u64);
+        Metrics::global().record_tls_handshake(ssl.session_reused(), duration_ms);
+        None
+    }
+}
+
+/// Background service reloading the certificate and refreshing OCSP responses
+pub struct CertificateWatcher {
+    store: Arc<CertificateStore>,
+}
+
+impl CertificateWatcher {
+    pub fn new(store: Arc<CertificateStore>) -> Self {
+        Self { store }
+    }
+}
+
+#[async_trait]
+impl BackgroundService for CertificateWatcher {
+    async fn start(&self, mut shutdown: ShutdownWatch) {
+        let mut ticker = tokio::time::interval(self.store.interval());
+        loop {
+            tokio::select! {
+                _ = shutdown.changed() => return,
+                _ = ticker.tick() => {
+                    self.store.reload_if_changed();
+                    self.store.refresh_ocsp().await;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use openssl::asn1::Asn1Time;
+    use openssl::bn::BigNum;
+    use openssl::rsa::Rsa;
+    use openssl::x509::X509NameBuilder;
+
+    fn self_signed(common_name: &str) -> (Vec<u8>, Vec<u8>) {
+        let key = PKey::from_rsa(Rsa::generate(2048).unwrap()).unwrap();
+        let mut name = X509NameBuilder::new().unwrap();
+        name.append_entry_by_text("CN", common_name).unwrap();
+        let name = name.build();
+
+        let mut cert = X509::builder().unwrap();
+        cert.set_version(2).unwrap();
+        let serial = BigNum::from_u32(1).unwrap().to_asn1_integer().unwrap();
+        cert.set_serial_number(&serial).unwrap();
+        cert.set_subject_name(&name).unwrap();
+        cert.set_issuer_name(&name).unwrap();
+        cert.set_pubkey(&key).unwrap();
+        cert.set_not_before(&Asn1Time::days_from_now(0).unwrap())
+            .unwrap();
+        cert.set_not_after(&Asn1Time::days_from_now(90).unwrap())
+            .unwrap();
+        cert.sign(&key, MessageDigest::sha256()).unwrap();
+
+        (
+            cert.build().to_pem().unwrap(),
+            key.private_key_to_pem_pkcs8().unwrap(),
+        )
+    }
+
+    fn config(dir: &Path) -> TlsListenerConfig {
+        TlsListenerConfig {
+            enabled: true,
+            cert_dir: dir.to_string_lossy().into_owned(),
+            ..TlsListenerConfig::default()
+        }
+    }
+
+    fn write(dir: &Path, (cert, key): &(Vec<u8>, Vec<u8>)) {
+        std::fs::write(dir.join("fullchain.pem"), cert).unwrap();
+        std::fs::write(dir.join("privkey.pem"), key).unwrap();
+    }
+
+    /// Let the clock move past the files' modification time before rewriting them
+    fn wait_for_new_mtime() {
+        std::thread::sleep(Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_certificate_store_reloads_renewed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = config(dir.path());
+        write(dir.path(), &self_signed("old.example.com"));
+        let store = CertificateStore::load(&config).unwrap();
+        assert_eq!(store.current().subject(), "CN=old.example.com");
+
+        // Unchanged files are not reloaded
+        assert!(!store.reload_if_changed());
+
+        wait_for_new_mtime();
+        write(dir.path(), &self_signed("new.example.com"));
+        assert!(store.reload_if_changed());
+        assert_eq!(store.current().subject(), "CN=new.example.com");
+    }
+
+    #[test]
+    fn test_certificate_store_keeps_certificate_on_bad_renewal() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = config(dir.path());
+        write(dir.path(), &self_signed("old.example.com"));
+        let store = CertificateStore::load(&config).unwrap();
+
+        // Certificate and key from different pairs, as if caught mid-write
+        let (cert, _) = self_signed("new.example.com");
+        let (_, key) = self_signed("other.example.com");
+        wait_for_new_mtime();
+        write(dir.path(), &(cert, key));
+        assert!(!store.reload_if_changed());
+        assert_eq!(store.current().subject(), "CN=old.example.com");
+
+        // A mismatched pair is rejected at startup as well
+        assert!(CertificateStore::load(&config).is_err());
+    }
+
+    #[test]
+    fn test_reload_manager_hook_swaps_certificate() {
+        use crate::reload::ReloadManager;
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = config(dir.path());
+        write(dir.path(), &self_signed("old.example.com"));
+        let store = CertificateStore::load(&config).unwrap();
+        let hook_store = Arc::clone(&store);
+        let manager = ReloadManager::new(dir.path().join("config.yaml"))
+            .with_hook("tls certificate", move || hook_store.reload().map(|_| ()));
+
+        // A connection handshaked before the reload holds the old certificate
+        let in_flight = store.current();
+        wait_for_new_mtime();
+        write(dir.path(), &self_signed("new.example.com"));
+        assert_eq!(manager.run_hooks(), 0);
+        assert_eq!(store.current().subject(), "CN=new.example.com");
+        assert_eq!(in_flight.subject(), "CN=old.example.com");
+
+        // A broken renewal fails the hook and keeps serving the new certificate
+        let (cert, _) = self_signed("next.example.com");
+        let (_, key) = self_signed("other.example.com");
+        wait_for_new_mtime();
+        write(dir.path(), &(cert, key));
+        assert_eq!(manager.run_hooks(), 1);
+        assert_eq!(store.current().subject(), "CN=new.example.com");
+    }
+
+    #[test]
+    fn test_client_subjects_by_certificate_digest() {
+        let (pem, _) = self_signed("client.example.com");
+        let cert = X509::from_pem(&pem).unwrap();
+        let digest = cert.digest(MessageDigest::sha256()).unwrap();
+
+        let subjects = ClientSubjects::default();
+        assert!(subjects.get(&digest).is_none());
+        subjects.record(&cert);
+        assert_eq!(
+            subjects.get(&digest).as_deref(),
+            Some("CN=client.example.com")
+        );
+
+        let (other, _) = self_signed("other.example.com");
+        let other = X509::from_pem(&other).unwrap();
+        assert!(subjects
+            .get(&other.digest(MessageDigest::sha256()).unwrap())
+            .is_none());
+    }
+
+    #[test]
+    fn test_tls_settings_with_client_ca() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), &self_signed("server.example.com"));
+        let (ca, _) = self_signed("Clients CA");
+        let ca_file = dir.path().join("clients.pem");
+        std::fs::write(&ca_file, ca).unwrap();
+
+        let mut config = config(dir.path());
+        config.client_ca_file = Some(ca_file.to_string_lossy().into_owned());
+        for mode in [ClientAuthMode::Required, ClientAuthMode::Optional] {
+            config.client_auth = mode;
+            let store = CertificateStore::load(&config).unwrap();
+            assert!(store.tls_settings().is_ok());
+        }
+
+        config.client_ca_file = Some(
+            dir.path()
+                .join("missing.pem")
+                .to_string_lossy()
+                .into_owned(),
+        );
+        let store = CertificateStore::load(&config).unwrap();
+        assert!(store.tls_settings().is_err());
+    }
+
+    #[test]
+    fn test_ocsp_refresh_schedule() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(1_000_000);
+        let interval = Duration::from_secs(60);
+        let margin = Duration::from_secs(OCSP_REFRESH_MARGIN_SECS);
+
+        // A margin before nextUpdate
+        let expires = now + Duration::from_secs(7 * 24 * 3600);
+        assert_eq!(
+            ocsp_refresh_at(now, Some(expires), interval),
+            expires - margin
+        );
+        // Never sooner than the next check
+        assert_eq!(ocsp_refresh_at(now, Some(now), interval), now + interval);
+        // No nextUpdate: refetch after the margin
+        assert_eq!(ocsp_refresh_at(now, None, interval), now + margin);
+    }
+
+    #[test]
+    fn test_parse_asn1_time() {
+        let parsed = parse_asn1_time("Oct  5 12:00:00 2026 GMT").unwrap();
+        let expected: SystemTime = chrono::NaiveDate::from_ymd_opt(2026, 10, 5)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+            .and_utc()
+            .into();
+        assert_eq!(parsed, expected);
+        assert!(parse_asn1_time("not a time").is_none());
+    }
+
+    #[test]
+    fn test_alpn_selection_follows_configured_preference() {
+        let client = b"\x08http/1.1\x02h2";
+        let both = vec!["h2".to_string(), "http/1.1".to_string()];
+        assert_eq!(select_alpn(&both, client), Some(&b"h2"[..]));
+
+        let http1_only = vec!["http/1.1".to_string()];
+        assert_eq!(select_alpn(&http1_only, client), Some(&b"http/1.1"[..]));
+        assert_eq!(select_alpn(&http1_only, b"\x02h2"), None);
+        // Truncated client list
+        assert_eq!(select_alpn(&both, b"\x08http"), None);
+    }
+}
@@ -393,6 +393,7 @@ fn test_circuit_breaker_opens_on_failures() {
         success_threshold: 2,
         timeout_duration: Duration::from_secs(5),
         half_open_max_requests: 3,
+        ..Default::default()
     };
 
     let breaker = CircuitBreaker::new(config);
@@ -426,6 +427,7 @@ fn test_circuit_breaker_resets_on_success() {
         success_threshold: 2,
         timeout_duration: Duration::from_millis(100),
         half_open_max_requests: 3,
+        ..Default::default()
     };
 
     let breaker = CircuitBreaker::new(config);
@@ -464,6 +466,7 @@ fn test_circuit_breaker_failure_count_resets() {
         success_threshold: 1,
         timeout_duration: Duration::from_secs(60),
         half_open_max_requests: 3,
+        ..Default::default()
     };
 
     let breaker = CircuitBreaker::new(config);
@@ -28,6 +28,7 @@ async fn test_end_to_end_cache_hit_flow() {
             cache_dir: cache_dir.clone(),
             max_disk_cache_size_mb: 100,
             sendfile: SendfileConfig::default(),
+            dedup: false,
         },
         ..Default::default()
     };
@@ -177,6 +178,7 @@ async fn test_cache_stats_api_returns_accurate_data() {
             cache_dir: cache_dir.clone(),
             max_disk_cache_size_mb: 100,
             sendfile: SendfileConfig::default(),
+            dedup: false,
         },
         ..Default::default()
     };
@@ -254,6 +256,7 @@ async fn test_cache_clear_api() {
             cache_dir: cache_dir.clone(),
             max_disk_cache_size_mb: 100,
             sendfile: SendfileConfig::default(),
+            dedup: false,
         },
         ..Default::default()
     };
@@ -348,6 +351,7 @@ async fn test_cache_survives_disk_persistence() {
                 cache_dir: cache_dir.clone(),
                 max_disk_cache_size_mb: 100,
                 sendfile: SendfileConfig::default(),
+                dedup: false,
             },
             ..Default::default()
         };
@@ -375,6 +379,7 @@ async fn test_cache_survives_disk_persistence() {
                 cache_dir: cache_dir.clone(),
                 max_disk_cache_size_mb: 100,
                 sendfile: SendfileConfig::default(),
+                dedup: false,
             },
             ..Default::default()
         };
@@ -427,6 +432,7 @@ async fn test_s3_response_populates_cache() {
             cache_dir: cache_dir.clone(),
             max_disk_cache_size_mb: 100,
             sendfile: SendfileConfig::default(),
+            dedup: false,
         },
         ..Default::default()
     };
@@ -518,6 +524,7 @@ async fn test_cache_lookup_adds_less_than_1ms_latency() {
             cache_dir: cache_dir.clone(),
             max_disk_cache_size_mb: 100,
             sendfile: SendfileConfig::default(),
+            dedup: false,
         },
         ..Default::default()
     };
@@ -590,6 +597,7 @@ async fn test_cache_write_is_non_blocking() {
             cache_dir: cache_dir.clone(),
             max_disk_cache_size_mb: 100,
             sendfile: SendfileConfig::default(),
+            dedup: false,
         },
         ..Default::default()
     };
@@ -1304,6 +1304,8 @@ async fn test_get_double_checks_entry_not_expired_locally() {
         created_at: past - Duration::from_secs(60),
         expires_at: past, // Expired 10 seconds ago
         last_accessed_at: past,
+        content_encoding: None,
+        headers: Vec::new(),
     };
 
     let key = CacheKey {
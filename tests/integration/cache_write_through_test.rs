@@ -42,6 +42,8 @@ fn test_set_writes_to_memory_synchronously() {
             created_at: SystemTime::now(),
             expires_at: SystemTime::now() + Duration::from_secs(300),
             last_accessed_at: SystemTime::now(),
+            content_encoding: None,
+            headers: Vec::new(),
         };
 
         // set() should complete synchronously for memory layer
@@ -107,6 +109,8 @@ fn test_secondary_layer_failures_dont_fail_operation() {
             created_at: SystemTime::now(),
             expires_at: SystemTime::now() + Duration::from_secs(300),
             last_accessed_at: SystemTime::now(),
+            content_encoding: None,
+            headers: Vec::new(),
         };
 
         // This should succeed even if secondary layers fail
@@ -184,6 +188,8 @@ fn test_memory_write_returns_quickly() {
             created_at: SystemTime::now(),
             expires_at: SystemTime::now() + Duration::from_secs(300),
             last_accessed_at: SystemTime::now(),
+            content_encoding: None,
+            headers: Vec::new(),
         };
 
         let start = Instant::now();
@@ -226,6 +232,7 @@ fn test_multi_layer_write_primary_then_secondary() {
                 cache_dir: temp_dir.to_string_lossy().to_string(),
                 max_disk_cache_size_mb: 100,
                 sendfile: SendfileConfig::default(),
+                dedup: false,
             },
             ..Default::default()
         };
@@ -247,6 +254,8 @@ fn test_multi_layer_write_primary_then_secondary() {
             created_at: SystemTime::now(),
             expires_at: SystemTime::now() + Duration::from_secs(300),
             last_accessed_at: SystemTime::now(),
+            content_encoding: None,
+            headers: Vec::new(),
         };
 
         // Write should succeed
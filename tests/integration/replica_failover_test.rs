@@ -118,6 +118,7 @@ fn create_test_replica(name: &str, port: u16, priority: u8) -> S3Replica {
         endpoint: Some(format!("http://127.0.0.1:{}", port)),
         priority,
         timeout: 2, // Short timeout for tests
+        endpoints: Vec::new(),
     }
 }
 
@@ -248,6 +248,41 @@ fn test_case_insensitive_header_name_matching() {
     );
 }
 
+#[test]
+fn test_extracts_token_from_named_cookie() {
+    let mut headers = HashMap::new();
+    headers.insert(
+        "cookie".to_string(),
+        "theme=dark; session=cookietoken789; lang=en".to_string(),
+    );
+
+    assert_eq!(
+        extract_cookie_token(&headers, "session"),
+        Some("cookietoken789".to_string())
+    );
+    assert_eq!(extract_cookie_token(&headers, "missing"), None);
+    // Names must match exactly, not as a suffix
+    assert_eq!(extract_cookie_token(&headers, "ion"), None);
+
+    // Reached through try_extract_token after the other sources
+    let sources = vec![
+        TokenSource {
+            source_type: "bearer".to_string(),
+            name: None,
+            prefix: None,
+        },
+        TokenSource {
+            source_type: "cookie".to_string(),
+            name: Some("session".to_string()),
+            prefix: None,
+        },
+    ];
+    assert_eq!(
+        try_extract_token(&headers, &HashMap::new(), &sources),
+        Some("cookietoken789".to_string())
+    );
+}
+
 #[test]
 fn test_extracts_token_from_query_parameter_by_name() {
     // Create query parameters
@@ -2552,11 +2587,19 @@ fn test_passes_request_through_when_auth_disabled() {
         algorithm: "HS256".to_string(),
         rsa_public_key_path: None,
         ecdsa_public_key_path: None,
+        eddsa_public_key_path: None,
+        allowed_algorithms: vec![],
+        issuer: None,
+        audience: vec![],
         token_sources: vec![],
         claims: vec![],
         keys: vec![],
         jwks_url: None,
         jwks_refresh_interval_secs: None,
+        key_reload_interval_secs: None,
+        token_cache: Default::default(),
+        revocation: None,
+        secret_file: None,
         admin_claims: vec![],
     });
 
@@ -2582,6 +2625,10 @@ fn test_passes_request_through_when_auth_disabled() {
         algorithm: "HS256".to_string(),
         rsa_public_key_path: None,
         ecdsa_public_key_path: None,
+        eddsa_public_key_path: None,
+        allowed_algorithms: vec![],
+        issuer: None,
+        audience: vec![],
         token_sources: vec![TokenSource {
             source_type: "header".to_string(),
             name: Some("Authorization".to_string()),
@@ -2591,6 +2638,10 @@ fn test_passes_request_through_when_auth_disabled() {
         keys: vec![],
         jwks_url: None,
         jwks_refresh_interval_secs: None,
+        key_reload_interval_secs: None,
+        token_cache: Default::default(),
+        revocation: None,
+        secret_file: None,
         admin_claims: vec![],
     });
 
@@ -2644,6 +2695,10 @@ fn test_extracts_and_validates_jwt_when_auth_enabled() {
         algorithm: "HS256".to_string(),
         rsa_public_key_path: None,
         ecdsa_public_key_path: None,
+        eddsa_public_key_path: None,
+        allowed_algorithms: vec![],
+        issuer: None,
+        audience: vec![],
         token_sources: vec![TokenSource {
             source_type: "header".to_string(),
             name: Some("Authorization".to_string()),
@@ -2657,6 +2712,10 @@ fn test_extracts_and_validates_jwt_when_auth_enabled() {
         keys: vec![],
         jwks_url: None,
         jwks_refresh_interval_secs: None,
+        key_reload_interval_secs: None,
+        token_cache: Default::default(),
+        revocation: None,
+        secret_file: None,
         admin_claims: vec![],
     };
 
@@ -2706,6 +2765,10 @@ fn test_returns_missing_token_error_when_jwt_missing_and_auth_required() {
         algorithm: "HS256".to_string(),
         rsa_public_key_path: None,
         ecdsa_public_key_path: None,
+        eddsa_public_key_path: None,
+        allowed_algorithms: vec![],
+        issuer: None,
+        audience: vec![],
         token_sources: vec![TokenSource {
             source_type: "bearer".to_string(),
             name: None,
@@ -2715,6 +2778,10 @@ fn test_returns_missing_token_error_when_jwt_missing_and_auth_required() {
         keys: vec![],
         jwks_url: None,
         jwks_refresh_interval_secs: None,
+        key_reload_interval_secs: None,
+        token_cache: Default::default(),
+        revocation: None,
+        secret_file: None,
         admin_claims: vec![],
     };
 
@@ -2750,6 +2817,10 @@ fn test_returns_invalid_token_error_when_jwt_invalid_and_auth_required() {
         algorithm: "HS256".to_string(),
         rsa_public_key_path: None,
         ecdsa_public_key_path: None,
+        eddsa_public_key_path: None,
+        allowed_algorithms: vec![],
+        issuer: None,
+        audience: vec![],
         token_sources: vec![TokenSource {
             source_type: "bearer".to_string(),
             name: None,
@@ -2759,6 +2830,10 @@ fn test_returns_invalid_token_error_when_jwt_invalid_and_auth_required() {
         keys: vec![],
         jwks_url: None,
         jwks_refresh_interval_secs: None,
+        key_reload_interval_secs: None,
+        token_cache: Default::default(),
+        revocation: None,
+        secret_file: None,
         admin_claims: vec![],
     };
 
@@ -2831,6 +2906,10 @@ fn test_returns_claims_verification_failed_when_jwt_valid_but_claims_dont_match(
         algorithm: "HS256".to_string(),
         rsa_public_key_path: None,
         ecdsa_public_key_path: None,
+        eddsa_public_key_path: None,
+        allowed_algorithms: vec![],
+        issuer: None,
+        audience: vec![],
         token_sources: vec![TokenSource {
             source_type: "bearer".to_string(),
             name: None,
@@ -2844,6 +2923,10 @@ fn test_returns_claims_verification_failed_when_jwt_valid_but_claims_dont_match(
         keys: vec![],
         jwks_url: None,
         jwks_refresh_interval_secs: None,
+        key_reload_interval_secs: None,
+        token_cache: Default::default(),
+        revocation: None,
+        secret_file: None,
         admin_claims: vec![],
     };
 
@@ -2924,6 +3007,10 @@ fn test_attaches_validated_claims_to_request_context() {
         algorithm: "HS256".to_string(),
         rsa_public_key_path: None,
         ecdsa_public_key_path: None,
+        eddsa_public_key_path: None,
+        allowed_algorithms: vec![],
+        issuer: None,
+        audience: vec![],
         token_sources: vec![TokenSource {
             source_type: "bearer".to_string(),
             name: None,
@@ -2933,6 +3020,10 @@ fn test_attaches_validated_claims_to_request_context() {
         keys: vec![],
         jwks_url: None,
         jwks_refresh_interval_secs: None,
+        key_reload_interval_secs: None,
+        token_cache: Default::default(),
+        revocation: None,
+        secret_file: None,
         admin_claims: vec![],
     };
 
@@ -3024,6 +3115,10 @@ fn test_error_response_includes_clear_error_message() {
         algorithm: "HS256".to_string(),
         rsa_public_key_path: None,
         ecdsa_public_key_path: None,
+        eddsa_public_key_path: None,
+        allowed_algorithms: vec![],
+        issuer: None,
+        audience: vec![],
         token_sources: vec![TokenSource {
             source_type: "bearer".to_string(),
             name: None,
@@ -3033,6 +3128,10 @@ fn test_error_response_includes_clear_error_message() {
         keys: vec![],
         jwks_url: None,
         jwks_refresh_interval_secs: None,
+        key_reload_interval_secs: None,
+        token_cache: Default::default(),
+        revocation: None,
+        secret_file: None,
         admin_claims: vec![],
     };
 
@@ -3115,6 +3214,10 @@ fn test_error_response_includes_clear_error_message() {
         algorithm: "HS256".to_string(),
         rsa_public_key_path: None,
         ecdsa_public_key_path: None,
+        eddsa_public_key_path: None,
+        allowed_algorithms: vec![],
+        issuer: None,
+        audience: vec![],
         token_sources: vec![TokenSource {
             source_type: "bearer".to_string(),
             name: None,
@@ -3128,6 +3231,10 @@ fn test_error_response_includes_clear_error_message() {
         keys: vec![],
         jwks_url: None,
         jwks_refresh_interval_secs: None,
+        key_reload_interval_secs: None,
+        token_cache: Default::default(),
+        revocation: None,
+        secret_file: None,
         admin_claims: vec![],
     };
 
@@ -3585,6 +3692,10 @@ fn test_rs256_authenticate_request_with_config() {
         algorithm: "RS256".to_string(),
         rsa_public_key_path: Some("tests/fixtures/rsa_public.pem".to_string()),
         ecdsa_public_key_path: None,
+        eddsa_public_key_path: None,
+        allowed_algorithms: vec![],
+        issuer: None,
+        audience: vec![],
         token_sources: vec![TokenSource {
             source_type: "bearer".to_string(),
             name: None,
@@ -3594,6 +3705,10 @@ fn test_rs256_authenticate_request_with_config() {
         keys: vec![],
         jwks_url: None,
         jwks_refresh_interval_secs: None,
+        key_reload_interval_secs: None,
+        token_cache: Default::default(),
+        revocation: None,
+        secret_file: None,
         admin_claims: vec![],
     };
 
@@ -3647,6 +3762,10 @@ fn test_es256_authenticate_request_with_config() {
         algorithm: "ES256".to_string(),
         rsa_public_key_path: None,
         ecdsa_public_key_path: Some("tests/fixtures/ecdsa_public.pem".to_string()),
+        eddsa_public_key_path: None,
+        allowed_algorithms: vec![],
+        issuer: None,
+        audience: vec![],
         token_sources: vec![TokenSource {
             source_type: "bearer".to_string(),
             name: None,
@@ -3656,6 +3775,10 @@ fn test_es256_authenticate_request_with_config() {
         keys: vec![],
         jwks_url: None,
         jwks_refresh_interval_secs: None,
+        key_reload_interval_secs: None,
+        token_cache: Default::default(),
+        revocation: None,
+        secret_file: None,
         admin_claims: vec![],
     };
 
@@ -3713,6 +3836,10 @@ fn test_rs256_rejects_token_signed_with_wrong_key() {
         algorithm: "RS256".to_string(),
         rsa_public_key_path: Some("tests/fixtures/rsa_public.pem".to_string()),
         ecdsa_public_key_path: None,
+        eddsa_public_key_path: None,
+        allowed_algorithms: vec![],
+        issuer: None,
+        audience: vec![],
         token_sources: vec![TokenSource {
             source_type: "bearer".to_string(),
             name: None,
@@ -3722,6 +3849,10 @@ fn test_rs256_rejects_token_signed_with_wrong_key() {
         keys: vec![],
         jwks_url: None,
         jwks_refresh_interval_secs: None,
+        key_reload_interval_secs: None,
+        token_cache: Default::default(),
+        revocation: None,
+        secret_file: None,
         admin_claims: vec![],
     };
 
@@ -4855,9 +4986,17 @@ fn test_authenticate_request_enforces_algorithm() {
         claims: vec![],
         rsa_public_key_path: None,
         ecdsa_public_key_path: None,
+        eddsa_public_key_path: None,
+        allowed_algorithms: vec![],
+        issuer: None,
+        audience: vec![],
         keys: vec![],
         jwks_url: None,
         jwks_refresh_interval_secs: Some(300),
+        key_reload_interval_secs: None,
+        token_cache: Default::default(),
+        revocation: None,
+        secret_file: None,
         admin_claims: vec![],
     };
 
@@ -4876,3 +5015,158 @@ fn test_authenticate_request_enforces_algorithm() {
         "authenticate_request should reject HS256 token when HS384 is configured"
     );
 }
+
+/// Test: EdDSA tokens validate against an Ed25519 PEM key, and tokens
+/// signed with an algorithm outside the allowlist are rejected
+#[test]
+fn test_eddsa_authenticate_request_and_algorithm_allowlist() {
+    use jsonwebtoken::{encode, Algorithm, EncodingKey, Header};
+    use std::collections::HashMap;
+    use std::fs;
+    use yatagarasu::auth::{authenticate_request, AuthError};
+    use yatagarasu::config::{JwtConfig, TokenSource};
+
+    let private_key =
+        fs::read("tests/fixtures/ed25519_private.pem").expect("Failed to read private key");
+    let claims = serde_json::json!({
+        "sub": "eddsa-user",
+        "exp": chrono::Utc::now().timestamp() + 3600,
+    });
+    let eddsa_token = encode(
+        &Header::new(Algorithm::EdDSA),
+        &claims,
+        &EncodingKey::from_ed_pem(&private_key).expect("Failed to create encoding key"),
+    )
+    .expect("Failed to encode JWT");
+    let hs256_token = encode(
+        &Header::new(Algorithm::HS256),
+        &claims,
+        &EncodingKey::from_secret(b"guessable"),
+    )
+    .expect("Failed to encode JWT");
+
+    let config = JwtConfig {
+        enabled: true,
+        secret: "guessable".to_string(),
+        algorithm: "EdDSA".to_string(),
+        token_sources: vec![TokenSource {
+            source_type: "bearer".to_string(),
+            name: None,
+            prefix: None,
+        }],
+        claims: vec![],
+        rsa_public_key_path: None,
+        ecdsa_public_key_path: None,
+        eddsa_public_key_path: Some("tests/fixtures/ed25519_public.pem".to_string()),
+        allowed_algorithms: vec![],
+        issuer: None,
+        audience: vec![],
+        keys: vec![],
+        jwks_url: None,
+        jwks_refresh_interval_secs: None,
+        key_reload_interval_secs: None,
+        token_cache: Default::default(),
+        revocation: None,
+        secret_file: None,
+        admin_claims: vec![],
+    };
+    let bearer = |token: &str| {
+        let mut headers = HashMap::new();
+        headers.insert("Authorization".to_string(), format!("Bearer {}", token));
+        headers
+    };
+
+    let claims = authenticate_request(&bearer(&eddsa_token), &HashMap::new(), &config)
+        .expect("EdDSA token should validate");
+    assert_eq!(claims.sub, Some("eddsa-user".to_string()));
+
+    match authenticate_request(&bearer(&hs256_token), &HashMap::new(), &config) {
+        Err(AuthError::InvalidToken(reason)) => assert!(reason.contains("not allowed")),
+        other => panic!(
+            "HS256 token should be rejected by the allowlist: {:?}",
+            other
+        ),
+    }
+}
+
+#[test]
+fn test_authenticate_request_checks_issuer_and_audience() {
+    use yatagarasu::config::AuthConfig;
+
+    let global: JwtConfig = serde_yaml::from_str(
+        "enabled: true\nalgorithm: HS256\nsecret: s3cret\ntoken_sources:\n  - type: bearer",
+    )
+    .unwrap();
+    let bucket_auth: AuthConfig = serde_yaml::from_str(
+        "enabled: true\nissuer: https://partners.example.com\naudience: [reports]",
+    )
+    .unwrap();
+    let jwt_config = bucket_auth.jwt_config(&global);
+
+    let token = |claims: serde_json::Value| {
+        let token = encode(
+            &Header::new(Algorithm::HS256),
+            &claims,
+            &EncodingKey::from_secret(b"s3cret"),
+        )
+        .expect("Failed to encode JWT");
+        let mut headers = HashMap::new();
+        headers.insert("Authorization".to_string(), format!("Bearer {}", token));
+        headers
+    };
+
+    let valid = token(serde_json::json!({
+        "iss": "https://partners.example.com",
+        "aud": ["billing", "reports"],
+    }));
+    assert!(authenticate_request(&valid, &HashMap::new(), &jwt_config).is_ok());
+    // The global config checks neither, so an audience in the token is fine
+    assert!(authenticate_request(&valid, &HashMap::new(), &global).is_ok());
+
+    let wrong_issuer = token(serde_json::json!({
+        "iss": "https://auth.example.com",
+        "aud": "reports",
+    }));
+    assert!(matches!(
+        authenticate_request(&wrong_issuer, &HashMap::new(), &jwt_config),
+        Err(AuthError::ClaimsVerificationFailed)
+    ));
+
+    let wrong_audience = token(serde_json::json!({
+        "iss": "https://partners.example.com",
+        "aud": "billing",
+    }));
+    assert!(matches!(
+        authenticate_request(&wrong_audience, &HashMap::new(), &jwt_config),
+        Err(AuthError::ClaimsVerificationFailed)
+    ));
+}
+
+#[test]
+fn test_authenticate_request_rejects_revoked_token() {
+    use yatagarasu::auth::revocation::RevokedEntries;
+
+    let jwt_config: JwtConfig = serde_yaml::from_str(
+        "enabled: true\nalgorithm: HS256\nsecret: s3cret\ntoken_sources:\n  - type: bearer\nrevocation:\n  file: /etc/yatagarasu/revoked.txt",
+    )
+    .unwrap();
+    RevocationList::global().replace(RevokedEntries::parse(["jti:leaked-token-id"]));
+
+    let token = |jti: &str| {
+        let token = encode(
+            &Header::new(Algorithm::HS256),
+            &serde_json::json!({"sub": "alice", "jti": jti}),
+            &EncodingKey::from_secret(b"s3cret"),
+        )
+        .expect("Failed to encode JWT");
+        let mut headers = HashMap::new();
+        headers.insert("Authorization".to_string(), format!("Bearer {}", token));
+        headers
+    };
+
+    assert!(authenticate_request(&token("fresh-token-id"), &HashMap::new(), &jwt_config).is_ok());
+    assert!(matches!(
+        authenticate_request(&token("leaked-token-id"), &HashMap::new(), &jwt_config),
+        Err(AuthError::InvalidToken(_))
+    ));
+}
@@ -16,6 +16,26 @@ fn test_can_create_empty_config_struct() {
             rate_limit: None,
             security_limits: SecurityLimitsConfig::default(),
             coalescing: CoalescingConfig::default(),
+            workers: WorkerConfig::default(),
+            client_disconnect: ClientDisconnectConfig::default(),
+            ip_ban: IpBanConfig::default(),
+            range_abuse: RangeAbuseConfig::default(),
+            access_grants: AccessGrantsConfig::default(),
+            priority: PriorityConfig::default(),
+            maintenance: MaintenanceConfig::default(),
+            key_analytics: KeyAnalyticsConfig::default(),
+            access_export: AccessExportConfig::default(),
+            endpoint_access: EndpointAccessConfig::default(),
+            readiness: ReadinessConfig::default(),
+            origin_probe: OriginProbeConfig::default(),
+            unavailable_responses: UnavailableResponsesConfig::default(),
+            metrics_cardinality: MetricsCardinalityConfig::default(),
+            synthetic_endpoints: Vec::new(),
+            cache_override: CacheOverrideConfig::default(),
+            admin: AdminSecurityConfig::default(),
+            grpc: GrpcConfig::default(),
+            tls: TlsListenerConfig::default(),
+            request_queue: RequestQueueConfig::default(),
         },
         buckets: vec![],
         jwt: None,
@@ -29,6 +49,8 @@ fn test_can_create_empty_config_struct() {
         audit_log: None,
         observability: ObservabilityConfig::default(),
         generation: 0,
+        batch: Default::default(),
+        vault: None,
     };
 }
 
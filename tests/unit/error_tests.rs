@@ -1774,3 +1774,38 @@ fn test_stack_traces_only_in_logs_never_in_responses() {
     // - Response size is massive (slows down error handling)
     // - Violates security best practices
 }
+
+#[test]
+fn test_error_codes_are_stable_and_unique() {
+    use std::collections::HashSet;
+    use yatagarasu::error::ErrorCode;
+
+    // Codes are part of the client contract: never renumber them
+    assert_eq!(ErrorCode::AuthMissingToken.as_str(), "YAT-AUTH-001");
+    assert_eq!(ErrorCode::RateLimitIp.as_str(), "YAT-RATE-002");
+    assert_eq!(ErrorCode::OriginUnavailable.to_string(), "YAT-AVAIL-003");
+
+    let codes = [
+        ErrorCode::for_status(400),
+        ErrorCode::for_status(401),
+        ErrorCode::for_status(403),
+        ErrorCode::for_status(404),
+        ErrorCode::for_status(429),
+        ErrorCode::for_status(500),
+        ErrorCode::for_status(502),
+        ErrorCode::for_status(503),
+        ErrorCode::for_upstream_status(403),
+        ErrorCode::for_upstream_status(404),
+        ErrorCode::for_upstream_status(416),
+        ErrorCode::for_upstream_status(500),
+    ];
+    let unique: HashSet<_> = codes.iter().map(|code| code.as_str()).collect();
+    assert_eq!(unique.len(), codes.len());
+    assert!(codes.iter().all(|code| code.as_str().starts_with("YAT-")));
+
+    // ProxyError bodies carry the code next to the category
+    let parsed: serde_json::Value =
+        serde_json::from_str(&ProxyError::s3("timeout").to_json_response(None)).unwrap();
+    assert_eq!(parsed["error"], "s3");
+    assert_eq!(parsed["code"], "YAT-S3-005");
+}
@@ -237,6 +237,9 @@ fn test_router_middleware_extracts_bucket_from_request_path() {
                 rate_limit: None,
                 circuit_breaker: None,
                 retry: None,
+                bulkhead: None,
+                signing: None,
+                credentials: None,
                 replicas: None,
             },
             auth: None,
@@ -244,6 +247,29 @@ fn test_router_middleware_extracts_bucket_from_request_path() {
             authorization: None,
             ip_filter: IpFilterConfig::default(),
             watermark: None,
+            logging: None,
+            resume: None,
+            content_type: None,
+            hotlink: None,
+            allowed_methods: None,
+            cors: None,
+            degraded: None,
+            server_timing: None,
+            early_hints: None,
+            etag_rewrite: None,
+            listing: None,
+            origin: None,
+            aliases: Vec::new(),
+            version: None,
+            hostnames: Vec::new(),
+            routes: Vec::new(),
+            upload_scan: None,
+            deprecation: None,
+            signed_url: None,
+            sigv4_auth: None,
+            post_policy: None,
+            presigned_redirect: None,
+            size_classes: Vec::new(),
         },
         BucketConfig {
             name: "private".to_string(),
@@ -259,6 +285,9 @@ fn test_router_middleware_extracts_bucket_from_request_path() {
                 rate_limit: None,
                 circuit_breaker: None,
                 retry: None,
+                bulkhead: None,
+                signing: None,
+                credentials: None,
                 replicas: None,
             },
             auth: None,
@@ -266,6 +295,29 @@ fn test_router_middleware_extracts_bucket_from_request_path() {
             authorization: None,
             ip_filter: IpFilterConfig::default(),
             watermark: None,
+            logging: None,
+            resume: None,
+            content_type: None,
+            hotlink: None,
+            allowed_methods: None,
+            cors: None,
+            degraded: None,
+            server_timing: None,
+            early_hints: None,
+            etag_rewrite: None,
+            listing: None,
+            origin: None,
+            aliases: Vec::new(),
+            version: None,
+            hostnames: Vec::new(),
+            routes: Vec::new(),
+            upload_scan: None,
+            deprecation: None,
+            signed_url: None,
+            sigv4_auth: None,
+            post_policy: None,
+            presigned_redirect: None,
+            size_classes: Vec::new(),
         },
     ];
 
@@ -304,6 +356,9 @@ fn test_requests_to_products_route_to_products_bucket() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
         auth: None,
@@ -311,6 +366,29 @@ fn test_requests_to_products_route_to_products_bucket() {
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     }];
 
     let router = Router::new(buckets);
@@ -356,6 +434,9 @@ fn test_requests_to_private_route_to_private_bucket() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
         auth: None,
@@ -363,6 +444,29 @@ fn test_requests_to_private_route_to_private_bucket() {
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     }];
 
     let router = Router::new(buckets);
@@ -409,6 +513,9 @@ fn test_longest_prefix_matching_works() {
                 rate_limit: None,
                 circuit_breaker: None,
                 retry: None,
+                bulkhead: None,
+                signing: None,
+                credentials: None,
                 replicas: None,
             },
             auth: None,
@@ -416,6 +523,29 @@ fn test_longest_prefix_matching_works() {
             authorization: None,
             ip_filter: IpFilterConfig::default(),
             watermark: None,
+            logging: None,
+            resume: None,
+            content_type: None,
+            hotlink: None,
+            allowed_methods: None,
+            cors: None,
+            degraded: None,
+            server_timing: None,
+            early_hints: None,
+            etag_rewrite: None,
+            listing: None,
+            origin: None,
+            aliases: Vec::new(),
+            version: None,
+            hostnames: Vec::new(),
+            routes: Vec::new(),
+            upload_scan: None,
+            deprecation: None,
+            signed_url: None,
+            sigv4_auth: None,
+            post_policy: None,
+            presigned_redirect: None,
+            size_classes: Vec::new(),
         },
         BucketConfig {
             name: "products".to_string(),
@@ -431,6 +561,9 @@ fn test_longest_prefix_matching_works() {
                 rate_limit: None,
                 circuit_breaker: None,
                 retry: None,
+                bulkhead: None,
+                signing: None,
+                credentials: None,
                 replicas: None,
             },
             auth: None,
@@ -438,6 +571,29 @@ fn test_longest_prefix_matching_works() {
             authorization: None,
             ip_filter: IpFilterConfig::default(),
             watermark: None,
+            logging: None,
+            resume: None,
+            content_type: None,
+            hotlink: None,
+            allowed_methods: None,
+            cors: None,
+            degraded: None,
+            server_timing: None,
+            early_hints: None,
+            etag_rewrite: None,
+            listing: None,
+            origin: None,
+            aliases: Vec::new(),
+            version: None,
+            hostnames: Vec::new(),
+            routes: Vec::new(),
+            upload_scan: None,
+            deprecation: None,
+            signed_url: None,
+            sigv4_auth: None,
+            post_policy: None,
+            presigned_redirect: None,
+            size_classes: Vec::new(),
         },
     ];
 
@@ -488,6 +644,9 @@ fn test_unmapped_paths_return_none() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
         auth: None,
@@ -495,6 +654,29 @@ fn test_unmapped_paths_return_none() {
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     }];
 
     let router = Router::new(buckets);
@@ -540,6 +722,9 @@ fn test_s3_key_is_extracted_from_path() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
         auth: None,
@@ -547,6 +732,29 @@ fn test_s3_key_is_extracted_from_path() {
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     }];
 
     let router = Router::new(buckets);
@@ -600,6 +808,9 @@ fn test_router_middleware_adds_bucket_config_to_request_context() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
         auth: None,
@@ -607,6 +818,29 @@ fn test_router_middleware_adds_bucket_config_to_request_context() {
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     };
 
     // Add the bucket config to the context
@@ -626,6 +860,35 @@ fn test_router_middleware_adds_bucket_config_to_request_context() {
     assert_eq!(config.s3.region, "us-east-1", "S3 region should match");
 }
 
+// Test: Bucket logging level filters log lines once the request is routed
+#[test]
+fn test_request_context_log_enabled_follows_bucket_logging_level() {
+    use yatagarasu::config::BucketConfig;
+
+    let bucket_config: BucketConfig = serde_yaml::from_str(
+        r#"
+name: tokens
+path_prefix: /tokens
+s3:
+  bucket: tokens
+  region: us-east-1
+  access_key: test
+  secret_key: test
+logging:
+  level: warn
+"#,
+    )
+    .unwrap();
+
+    let mut context = RequestContext::new("GET".to_string(), "/tokens/a.pdf".to_string());
+    assert!(context.log_enabled(tracing::Level::DEBUG));
+
+    context.set_bucket_config(bucket_config);
+    assert!(context.log_enabled(tracing::Level::WARN));
+    assert!(!context.log_enabled(tracing::Level::INFO));
+    assert!(!context.log_enabled(tracing::Level::DEBUG));
+}
+
 // Test: Auth middleware skips validation for public buckets (auth.enabled=false)
 #[test]
 fn test_auth_middleware_skips_validation_for_public_buckets() {
@@ -648,13 +911,46 @@ fn test_auth_middleware_skips_validation_for_public_buckets() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
-        auth: Some(AuthConfig { enabled: false }),
+        auth: Some(AuthConfig {
+            enabled: false,
+            algorithm: None,
+            public_key_path: None,
+            issuer: None,
+            audience: vec![],
+            claims: vec![],
+        }),
         cache: None,
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     };
 
     // Create a request context without any JWT token
@@ -706,13 +1002,46 @@ fn test_auth_middleware_validates_jwt_for_private_buckets() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
-        auth: Some(AuthConfig { enabled: true }),
+        auth: Some(AuthConfig {
+            enabled: true,
+            algorithm: None,
+            public_key_path: None,
+            issuer: None,
+            audience: vec![],
+            claims: vec![],
+        }),
         cache: None,
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     };
 
     // Create a request context with a JWT token in Authorization header
@@ -1174,13 +1503,46 @@ fn test_missing_jwt_on_private_bucket_returns_401() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
-        auth: Some(AuthConfig { enabled: true }),
+        auth: Some(AuthConfig {
+            enabled: true,
+            algorithm: None,
+            public_key_path: None,
+            issuer: None,
+            audience: vec![],
+            claims: vec![],
+        }),
         cache: None,
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     };
 
     // Create a request context WITHOUT any JWT token
@@ -1581,13 +1943,46 @@ fn test_request_passes_through_middleware_in_correct_order() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
-        auth: Some(AuthConfig { enabled: true }),
+        auth: Some(AuthConfig {
+            enabled: true,
+            algorithm: None,
+            public_key_path: None,
+            issuer: None,
+            audience: vec![],
+            claims: vec![],
+        }),
         cache: None,
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     }];
 
     let secret = "test_secret_key_123";
@@ -1722,13 +2117,46 @@ fn test_middleware_can_short_circuit_request() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
-        auth: Some(AuthConfig { enabled: true }),
+        auth: Some(AuthConfig {
+            enabled: true,
+            algorithm: None,
+            public_key_path: None,
+            issuer: None,
+            audience: vec![],
+            claims: vec![],
+        }),
         cache: None,
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     }];
 
     // Create request WITHOUT JWT token (will fail auth)
@@ -1823,13 +2251,46 @@ fn test_short_circuit_prevents_handler_execution() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
-        auth: Some(AuthConfig { enabled: true }),
+        auth: Some(AuthConfig {
+            enabled: true,
+            algorithm: None,
+            public_key_path: None,
+            issuer: None,
+            audience: vec![],
+            claims: vec![],
+        }),
         cache: None,
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     }];
 
     let router = Router::new(buckets);
@@ -1897,13 +2358,46 @@ fn test_middleware_can_modify_request_context() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
-        auth: Some(AuthConfig { enabled: true }),
+        auth: Some(AuthConfig {
+            enabled: true,
+            algorithm: None,
+            public_key_path: None,
+            issuer: None,
+            audience: vec![],
+            claims: vec![],
+        }),
         cache: None,
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     }];
 
     // Create JWT token
@@ -2062,6 +2556,9 @@ fn test_errors_in_middleware_return_appropriate_http_status() {
                 rate_limit: None,
                 circuit_breaker: None,
                 retry: None,
+                bulkhead: None,
+                signing: None,
+                credentials: None,
                 replicas: None,
             },
             auth: None, // Public bucket
@@ -2069,6 +2566,29 @@ fn test_errors_in_middleware_return_appropriate_http_status() {
             authorization: None,
             ip_filter: IpFilterConfig::default(),
             watermark: None,
+            logging: None,
+            resume: None,
+            content_type: None,
+            hotlink: None,
+            allowed_methods: None,
+            cors: None,
+            degraded: None,
+            server_timing: None,
+            early_hints: None,
+            etag_rewrite: None,
+            listing: None,
+            origin: None,
+            aliases: Vec::new(),
+            version: None,
+            hostnames: Vec::new(),
+            routes: Vec::new(),
+            upload_scan: None,
+            deprecation: None,
+            signed_url: None,
+            sigv4_auth: None,
+            post_policy: None,
+            presigned_redirect: None,
+            size_classes: Vec::new(),
         },
         BucketConfig {
             name: "private".to_string(),
@@ -2084,13 +2604,46 @@ fn test_errors_in_middleware_return_appropriate_http_status() {
                 rate_limit: None,
                 circuit_breaker: None,
                 retry: None,
+                bulkhead: None,
+                signing: None,
+                credentials: None,
                 replicas: None,
             },
-            auth: Some(AuthConfig { enabled: true }),
+            auth: Some(AuthConfig {
+                enabled: true,
+                algorithm: None,
+                public_key_path: None,
+                issuer: None,
+                audience: vec![],
+                claims: vec![],
+            }),
             cache: None,
             authorization: None,
             ip_filter: IpFilterConfig::default(),
             watermark: None,
+            logging: None,
+            resume: None,
+            content_type: None,
+            hotlink: None,
+            allowed_methods: None,
+            cors: None,
+            degraded: None,
+            server_timing: None,
+            early_hints: None,
+            etag_rewrite: None,
+            listing: None,
+            origin: None,
+            aliases: Vec::new(),
+            version: None,
+            hostnames: Vec::new(),
+            routes: Vec::new(),
+            upload_scan: None,
+            deprecation: None,
+            signed_url: None,
+            sigv4_auth: None,
+            post_policy: None,
+            presigned_redirect: None,
+            size_classes: Vec::new(),
         },
     ];
 
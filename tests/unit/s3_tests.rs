@@ -26,6 +26,9 @@ fn test_can_create_s3_client_with_valid_credentials() {
         rate_limit: None,
         circuit_breaker: None,
         retry: None,
+        bulkhead: None,
+        signing: None,
+        credentials: None,
         replicas: None,
     };
 
@@ -60,6 +63,9 @@ fn test_can_create_s3_client_with_region_configuration() {
         rate_limit: None,
         circuit_breaker: None,
         retry: None,
+        bulkhead: None,
+        signing: None,
+        credentials: None,
         replicas: None,
     };
 
@@ -79,6 +85,9 @@ fn test_can_create_s3_client_with_region_configuration() {
         rate_limit: None,
         circuit_breaker: None,
         retry: None,
+        bulkhead: None,
+        signing: None,
+        credentials: None,
         replicas: None,
     };
 
@@ -98,6 +107,9 @@ fn test_can_create_s3_client_with_region_configuration() {
         rate_limit: None,
         circuit_breaker: None,
         retry: None,
+        bulkhead: None,
+        signing: None,
+        credentials: None,
         replicas: None,
     };
 
@@ -117,6 +129,9 @@ fn test_can_create_s3_client_with_region_configuration() {
         rate_limit: None,
         circuit_breaker: None,
         retry: None,
+        bulkhead: None,
+        signing: None,
+        credentials: None,
         replicas: None,
     };
 
@@ -139,6 +154,9 @@ fn test_can_create_s3_client_with_custom_endpoint() {
         rate_limit: None,
         circuit_breaker: None,
         retry: None,
+        bulkhead: None,
+        signing: None,
+        credentials: None,
         replicas: None,
     };
 
@@ -164,6 +182,9 @@ fn test_can_create_s3_client_with_custom_endpoint() {
         rate_limit: None,
         circuit_breaker: None,
         retry: None,
+        bulkhead: None,
+        signing: None,
+        credentials: None,
         replicas: None,
     };
 
@@ -192,6 +213,9 @@ fn test_can_create_s3_client_with_custom_endpoint() {
         rate_limit: None,
         circuit_breaker: None,
         retry: None,
+        bulkhead: None,
+        signing: None,
+        credentials: None,
         replicas: None,
     };
 
@@ -223,6 +247,9 @@ fn test_client_creation_fails_with_empty_credentials() {
         rate_limit: None,
         circuit_breaker: None,
         retry: None,
+        bulkhead: None,
+        signing: None,
+        credentials: None,
         replicas: None,
     };
 
@@ -245,6 +272,9 @@ fn test_client_creation_fails_with_empty_credentials() {
         rate_limit: None,
         circuit_breaker: None,
         retry: None,
+        bulkhead: None,
+        signing: None,
+        credentials: None,
         replicas: None,
     };
 
@@ -267,6 +297,9 @@ fn test_client_creation_fails_with_empty_credentials() {
         rate_limit: None,
         circuit_breaker: None,
         retry: None,
+        bulkhead: None,
+        signing: None,
+        credentials: None,
         replicas: None,
     };
 
@@ -289,6 +322,9 @@ fn test_client_creation_fails_with_empty_credentials() {
         rate_limit: None,
         circuit_breaker: None,
         retry: None,
+        bulkhead: None,
+        signing: None,
+        credentials: None,
         replicas: None,
     };
 
@@ -311,6 +347,9 @@ fn test_client_creation_fails_with_empty_credentials() {
         rate_limit: None,
         circuit_breaker: None,
         retry: None,
+        bulkhead: None,
+        signing: None,
+        credentials: None,
         replicas: None,
     };
 
@@ -332,6 +371,9 @@ fn test_can_create_multiple_independent_s3_clients() {
         rate_limit: None,
         circuit_breaker: None,
         retry: None,
+        bulkhead: None,
+        signing: None,
+        credentials: None,
         replicas: None,
     };
 
@@ -347,6 +389,9 @@ fn test_can_create_multiple_independent_s3_clients() {
         rate_limit: None,
         circuit_breaker: None,
         retry: None,
+        bulkhead: None,
+        signing: None,
+        credentials: None,
         replicas: None,
     };
 
@@ -362,6 +407,9 @@ fn test_can_create_multiple_independent_s3_clients() {
         rate_limit: None,
         circuit_breaker: None,
         retry: None,
+        bulkhead: None,
+        signing: None,
+        credentials: None,
         replicas: None,
     };
 
@@ -3822,6 +3870,24 @@ fn test_includes_accept_ranges_bytes_in_response_headers() {
     );
 }
 
+#[test]
+fn test_can_parse_content_range_header() {
+    assert_eq!(
+        parse_content_range("bytes 0-1023/4096"),
+        Some((0, 1023, 4096))
+    );
+    assert_eq!(
+        parse_content_range("bytes 4095-4095/4096"),
+        Some((4095, 4095, 4096))
+    );
+    // Unknown size, unsatisfied ranges and malformed values
+    assert_eq!(parse_content_range("bytes 0-1023/*"), None);
+    assert_eq!(parse_content_range("bytes */4096"), None);
+    assert_eq!(parse_content_range("bytes 10-5/4096"), None);
+    assert_eq!(parse_content_range("bytes 0-4096/4096"), None);
+    assert_eq!(parse_content_range("items 0-1/2"), None);
+}
+
 #[test]
 fn test_forwards_range_header_to_s3_with_aws_signature() {
     use std::collections::HashMap;
@@ -8161,6 +8227,9 @@ fn test_can_create_s3_http_client_from_bucket_config() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
         auth: None, // Public bucket
@@ -8168,6 +8237,29 @@ fn test_can_create_s3_http_client_from_bucket_config() {
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     };
 
     // Action: Create S3 client from BucketConfig
@@ -8227,6 +8319,9 @@ fn test_s3_client_uses_bucket_specific_credentials() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
         auth: None,
@@ -8234,6 +8329,29 @@ fn test_s3_client_uses_bucket_specific_credentials() {
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     };
 
     let private_bucket = BucketConfig {
@@ -8250,13 +8368,46 @@ fn test_s3_client_uses_bucket_specific_credentials() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
-        auth: Some(yatagarasu::config::AuthConfig { enabled: true }),
+        auth: Some(yatagarasu::config::AuthConfig {
+            enabled: true,
+            algorithm: None,
+            public_key_path: None,
+            issuer: None,
+            audience: vec![],
+            claims: vec![],
+        }),
         cache: None,
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     };
 
     let archive_bucket = BucketConfig {
@@ -8273,6 +8424,9 @@ fn test_s3_client_uses_bucket_specific_credentials() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
         auth: None,
@@ -8280,6 +8434,29 @@ fn test_s3_client_uses_bucket_specific_credentials() {
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     };
 
     // Action: Create S3 clients for each bucket
@@ -8389,6 +8566,9 @@ fn test_s3_client_connects_to_configured_endpoint_or_aws_default() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
         auth: None,
@@ -8396,6 +8576,29 @@ fn test_s3_client_connects_to_configured_endpoint_or_aws_default() {
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     };
 
     let minio_client = create_s3_client(&minio_bucket.s3).expect("Should create MinIO client");
@@ -8422,6 +8625,9 @@ fn test_s3_client_connects_to_configured_endpoint_or_aws_default() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
         auth: None,
@@ -8429,6 +8635,29 @@ fn test_s3_client_connects_to_configured_endpoint_or_aws_default() {
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     };
 
     let aws_client = create_s3_client(&aws_bucket.s3).expect("Should create AWS client");
@@ -8454,6 +8683,9 @@ fn test_s3_client_connects_to_configured_endpoint_or_aws_default() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
         auth: None,
@@ -8461,6 +8693,29 @@ fn test_s3_client_connects_to_configured_endpoint_or_aws_default() {
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     };
 
     let localstack_client =
@@ -8515,6 +8770,9 @@ fn test_s3_client_generates_valid_aws_signature_v4() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
         auth: None,
@@ -8522,6 +8780,29 @@ fn test_s3_client_generates_valid_aws_signature_v4() {
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     };
 
     let s3_client = create_s3_client(&bucket_config.s3).expect("Should create S3 client");
@@ -8592,6 +8873,9 @@ fn test_each_bucket_has_isolated_s3_client_no_credential_mixing() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
         auth: None, // Public bucket
@@ -8599,6 +8883,29 @@ fn test_each_bucket_has_isolated_s3_client_no_credential_mixing() {
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     };
 
     // Bucket 2: Private (authenticated, AWS S3)
@@ -8616,15 +8923,46 @@ fn test_each_bucket_has_isolated_s3_client_no_credential_mixing() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
         auth: Some(yatagarasu::config::AuthConfig {
             enabled: true, // Requires JWT
+            algorithm: None,
+            public_key_path: None,
+            issuer: None,
+            audience: vec![],
+            claims: vec![],
         }),
         cache: None,
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     };
 
     // Bucket 3: Archive (MinIO, custom endpoint)
@@ -8642,6 +8980,9 @@ fn test_each_bucket_has_isolated_s3_client_no_credential_mixing() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
         auth: None,
@@ -8649,6 +8990,29 @@ fn test_each_bucket_has_isolated_s3_client_no_credential_mixing() {
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     };
 
     // Simulate proxy initialization: Create isolated S3 client for each bucket
@@ -8775,6 +9139,9 @@ fn test_get_request_to_products_image_fetches_from_s3() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
         auth: None, // Public bucket
@@ -8782,6 +9149,29 @@ fn test_get_request_to_products_image_fetches_from_s3() {
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     }];
 
     let router = Router::new(buckets.clone());
@@ -9614,6 +10004,9 @@ fn test_requests_to_different_buckets_use_correct_credentials() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
         auth: None, // Public bucket, no JWT required
@@ -9621,6 +10014,29 @@ fn test_requests_to_different_buckets_use_correct_credentials() {
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     };
 
     // Bucket 2: Private (sensitive data with full access credentials)
@@ -9638,13 +10054,46 @@ fn test_requests_to_different_buckets_use_correct_credentials() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
-        auth: Some(AuthConfig { enabled: true }),
+        auth: Some(AuthConfig {
+            enabled: true,
+            algorithm: None,
+            public_key_path: None,
+            issuer: None,
+            audience: vec![],
+            claims: vec![],
+        }),
         cache: None,
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     };
 
     // Bucket 3: Archive (long-term storage with archive-specific credentials)
@@ -9662,6 +10111,9 @@ fn test_requests_to_different_buckets_use_correct_credentials() {
             rate_limit: None,
             circuit_breaker: None,
             retry: None,
+            bulkhead: None,
+            signing: None,
+            credentials: None,
             replicas: None,
         },
         auth: None,
@@ -9669,6 +10121,29 @@ fn test_requests_to_different_buckets_use_correct_credentials() {
         authorization: None,
         ip_filter: IpFilterConfig::default(),
         watermark: None,
+        logging: None,
+        resume: None,
+        content_type: None,
+        hotlink: None,
+        allowed_methods: None,
+        cors: None,
+        degraded: None,
+        server_timing: None,
+        early_hints: None,
+        etag_rewrite: None,
+        listing: None,
+        origin: None,
+        aliases: Vec::new(),
+        version: None,
+        hostnames: Vec::new(),
+        routes: Vec::new(),
+        upload_scan: None,
+        deprecation: None,
+        signed_url: None,
+        sigv4_auth: None,
+        post_policy: None,
+        presigned_redirect: None,
+        size_classes: Vec::new(),
     };
 
     // Create isolated S3 clients for each bucket